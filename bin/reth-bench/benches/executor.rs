@@ -0,0 +1,165 @@
+//! Benchmarks block execution, state root computation and persistence in isolation, on the blocks
+//! of a corpus downloaded with `reth-bench corpus`.
+#![allow(missing_docs)]
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use reth_bench::corpus::{read_corpus, CorpusBlock, CORPUS_DIR_ENV};
+use reth_chainspec::MAINNET;
+use reth_db::{test_utils::TempDatabase, DatabaseEnv};
+use reth_db_common::init::{insert_genesis_hashes, insert_genesis_state};
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_evm_ethereum::execute::EthExecutorProvider;
+use reth_primitives::{Account, Receipts, Requests, U256};
+use reth_provider::{
+    test_utils::create_test_provider_factory, BlockWriter, ExecutionOutcome, ProviderFactory,
+    StateRootProvider, TrieWriter,
+};
+use reth_revm::{database::StateProviderDatabase, test_utils::StateProviderTest};
+use reth_trie::{HashedPostState, StateRoot};
+use reth_trie_db::DatabaseStateRoot;
+use std::{path::PathBuf, sync::Arc};
+
+criterion_group!(benches, corpus);
+criterion_main!(benches);
+
+/// Returns the corpus directory, `corpus` in the crate directory unless configured with
+/// [`CORPUS_DIR_ENV`].
+fn corpus_dir() -> PathBuf {
+    std::env::var_os(CORPUS_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/corpus")))
+}
+
+fn corpus(c: &mut Criterion) {
+    let dir = corpus_dir();
+    let corpus = match read_corpus(&dir) {
+        Ok(corpus) if !corpus.is_empty() => corpus,
+        _ => {
+            eprintln!(
+                "No corpus found at {}, download one with `reth-bench corpus` or set {CORPUS_DIR_ENV}",
+                dir.display()
+            );
+            return
+        }
+    };
+
+    let executor_provider = EthExecutorProvider::ethereum(MAINNET.clone());
+    for (number, corpus_block) in corpus {
+        let block = corpus_block.block().unwrap();
+        let total_difficulty = corpus_block.total_difficulty;
+        let state = state_provider(&corpus_block);
+
+        let mut output = executor_provider
+            .executor(StateProviderDatabase::new(&state))
+            .execute((&block, total_difficulty).into())
+            .unwrap();
+        // the corpus is incomplete if the block doesn't execute as it did on mainnet
+        assert_eq!(output.gas_used, block.gas_used, "gas used of block {number} differs");
+        output.state.reverts.sort();
+
+        let mut group = c.benchmark_group("Executor");
+        group.throughput(Throughput::Elements(block.gas_used));
+        group.bench_function(BenchmarkId::from_parameter(number), |b| {
+            b.iter(|| {
+                executor_provider
+                    .executor(StateProviderDatabase::new(&state))
+                    .execute((&block, total_difficulty).into())
+                    .unwrap()
+            })
+        });
+        group.finish();
+
+        // the state root is computed on top of the pre-state of the block only, so it does not
+        // match the state root of the block
+        let factory = database(&corpus_block);
+        let latest = factory.latest().unwrap();
+        let hashed_state = HashedPostState::from_bundle_state(&output.state.state);
+
+        let mut group = c.benchmark_group("StateRoot");
+        group.throughput(Throughput::Elements(block.gas_used));
+        group.bench_function(BenchmarkId::from_parameter(number), |b| {
+            b.iter(|| latest.hashed_state_root_with_updates(hashed_state.clone()).unwrap())
+        });
+        group.finish();
+
+        let (_, trie_updates) =
+            latest.hashed_state_root_with_updates(hashed_state.clone()).unwrap();
+        let execution_outcome = ExecutionOutcome::new(
+            output.state,
+            Receipts::from(output.receipts),
+            number,
+            vec![Requests::from(output.requests)],
+        );
+        let block = block.seal_slow();
+
+        let mut group = c.benchmark_group("Persistence");
+        group.throughput(Throughput::Elements(block.gas_used));
+        group.bench_function(BenchmarkId::from_parameter(number), |b| {
+            b.iter_batched(
+                || {
+                    (
+                        block.clone(),
+                        execution_outcome.clone(),
+                        hashed_state.clone().into_sorted(),
+                        trie_updates.clone(),
+                    )
+                },
+                |(block, execution_outcome, hashed_state, trie_updates)| {
+                    // the transaction is not committed, so every iteration writes the block on top
+                    // of the pre-state
+                    let provider_rw = factory.provider_rw().unwrap();
+                    provider_rw
+                        .append_blocks_with_state(
+                            vec![block],
+                            execution_outcome,
+                            hashed_state,
+                            trie_updates,
+                        )
+                        .unwrap();
+                },
+                BatchSize::PerIteration,
+            )
+        });
+        group.finish();
+    }
+}
+
+/// Returns an in-memory state provider with the pre-state of the block.
+fn state_provider(corpus_block: &CorpusBlock) -> StateProviderTest {
+    let mut state = StateProviderTest::default();
+    for (address, account) in &corpus_block.pre_state {
+        state.insert_account(
+            *address,
+            Account {
+                nonce: account.nonce.unwrap_or_default(),
+                balance: account.balance,
+                bytecode_hash: None,
+            },
+            account.code.clone().filter(|code| !code.is_empty()),
+            account
+                .storage
+                .iter()
+                .flatten()
+                .map(|(slot, value)| (*slot, U256::from_be_bytes(value.0)))
+                .collect(),
+        );
+    }
+    for (number, hash) in &corpus_block.block_hashes {
+        state.insert_block_hash(*number, *hash);
+    }
+    state
+}
+
+/// Creates a temporary database that contains the pre-state of the block, including its hashed
+/// state and trie.
+fn database(corpus_block: &CorpusBlock) -> ProviderFactory<Arc<TempDatabase<DatabaseEnv>>> {
+    let factory = create_test_provider_factory();
+    let provider_rw = factory.provider_rw().unwrap();
+    let pre_state = &corpus_block.pre_state;
+    insert_genesis_state(&provider_rw, pre_state.len(), pre_state.iter()).unwrap();
+    insert_genesis_hashes(&provider_rw, pre_state.iter()).unwrap();
+    let (_, trie_updates) = StateRoot::from_tx(provider_rw.tx_ref()).root_with_updates().unwrap();
+    provider_rw.write_trie_updates(&trie_updates).unwrap();
+    provider_rw.commit().unwrap();
+    factory
+}
@@ -0,0 +1,168 @@
+//! Runs the `reth-bench corpus` command, which downloads a corpus of blocks for the executor
+//! benchmarks.
+
+use alloy_eips::BlockNumberOrTag;
+use alloy_provider::{Provider, ProviderBuilder};
+use clap::Parser;
+use reth_bench::corpus::{CorpusBlock, CORPUS_DIR_ENV};
+use reth_cli_runner::CliContext;
+use reth_primitives::{
+    revm_primitives::BLOCK_HASH_HISTORY, Address, Block, BlockNumber, GenesisAccount,
+};
+use reth_rpc_types::trace::{
+    common::TraceResult,
+    geth::{
+        AccountState, GethDebugBuiltInTracerType, GethDebugTracingOptions, PreStateFrame,
+        PreStateMode,
+    },
+};
+use std::{
+    collections::{btree_map::Entry, BTreeMap, HashSet},
+    path::PathBuf,
+};
+use tracing::info;
+
+/// `reth-bench corpus` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The RPC url to download the blocks from.
+    ///
+    /// The RPC must support `debug_traceBlockByNumber` with the `prestateTracer` for the
+    /// downloaded blocks, which usually requires an archive node.
+    #[arg(long, value_name = "RPC_URL", verbatim_doc_comment)]
+    rpc_url: String,
+
+    /// The first block to download.
+    #[arg(long, value_name = "FROM")]
+    from: BlockNumber,
+
+    /// The last block to download.
+    #[arg(long, value_name = "TO")]
+    to: BlockNumber,
+
+    /// The directory to write the corpus to.
+    #[arg(long, value_name = "DIR", env = CORPUS_DIR_ENV)]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `reth-bench corpus` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        if self.from > self.to {
+            return Err(eyre::eyre!("Block range is empty, --from must not be after --to"))
+        }
+        reth_fs_util::create_dir_all(&self.output)?;
+
+        info!("Downloading corpus from RPC URL: {}", self.rpc_url);
+        let provider = ProviderBuilder::new().on_http(self.rpc_url.parse()?);
+        let tracing_options = GethDebugTracingOptions::default()
+            .with_tracer(GethDebugBuiltInTracerType::PreStateTracer.into());
+
+        // hashes of the ancestors that are accessible with `BLOCKHASH`, shared by consecutive
+        // blocks
+        let mut block_hashes = BTreeMap::new();
+        for number in self.from..=self.to {
+            let oldest_ancestor = number.saturating_sub(BLOCK_HASH_HISTORY as u64);
+            block_hashes.retain(|ancestor, _| *ancestor >= oldest_ancestor);
+            for ancestor in oldest_ancestor..number {
+                if let Entry::Vacant(entry) = block_hashes.entry(ancestor) {
+                    let header = provider
+                        .get_block_by_number(ancestor.into(), false)
+                        .await?
+                        .ok_or_else(|| eyre::eyre!("Block {ancestor} not found"))?
+                        .header;
+                    entry.insert(
+                        header.hash.ok_or_else(|| eyre::eyre!("Block {ancestor} has no hash"))?,
+                    );
+                }
+            }
+
+            let block = provider
+                .get_block_by_number(number.into(), true)
+                .await?
+                .ok_or_else(|| eyre::eyre!("Block {number} not found"))?;
+            let total_difficulty = block.header.total_difficulty.unwrap_or_default();
+            let block = Block::try_from(block)?;
+
+            let traces: Vec<TraceResult<PreStateFrame, String>> = provider
+                .raw_request(
+                    "debug_traceBlockByNumber".into(),
+                    (BlockNumberOrTag::Number(number), &tracing_options),
+                )
+                .await?;
+            let pre_state = pre_state(traces)?;
+
+            info!(
+                number,
+                transactions = block.body.len(),
+                accounts = pre_state.len(),
+                "Downloaded block"
+            );
+            CorpusBlock::new(&block, total_difficulty, pre_state, block_hashes.clone())
+                .write(&self.output, number)?;
+        }
+
+        info!("Wrote corpus to {}", self.output.display());
+        Ok(())
+    }
+}
+
+/// Merges the pre-states of the transactions of a block into the pre-state of the block.
+///
+/// The pre-state of a transaction contains all accounts and storage slots the transaction accesses,
+/// so the first occurrence of an account or storage slot is its value before the block.
+fn pre_state(
+    traces: Vec<TraceResult<PreStateFrame, String>>,
+) -> eyre::Result<BTreeMap<Address, GenesisAccount>> {
+    let mut pre_state = BTreeMap::<Address, GenesisAccount>::new();
+    // accounts that did not exist before the block
+    let mut absent = HashSet::new();
+    for trace in traces {
+        let accounts = match trace {
+            TraceResult::Success {
+                result: PreStateFrame::Default(PreStateMode(accounts)), ..
+            } => accounts,
+            TraceResult::Success { tx_hash, .. } => {
+                return Err(eyre::eyre!("Unexpected diff mode pre-state of transaction {tx_hash:?}"))
+            }
+            TraceResult::Error { error, tx_hash } => {
+                return Err(eyre::eyre!("Failed to trace transaction {tx_hash:?}: {error}"))
+            }
+        };
+
+        for (address, AccountState { balance, code, nonce, storage }) in accounts {
+            if absent.contains(&address) {
+                continue
+            }
+            match pre_state.entry(address) {
+                Entry::Vacant(entry) => {
+                    let balance = balance.unwrap_or_default();
+                    if nonce.unwrap_or_default() == 0 &&
+                        balance.is_zero() &&
+                        code.as_ref().map_or(true, |code| code.is_empty()) &&
+                        storage.is_empty()
+                    {
+                        // the account did not exist before the block
+                        absent.insert(address);
+                        continue
+                    }
+                    entry.insert(GenesisAccount {
+                        nonce,
+                        balance,
+                        code,
+                        storage: (!storage.is_empty()).then_some(storage),
+                        private_key: None,
+                    });
+                }
+                Entry::Occupied(mut entry) => {
+                    let slots = entry.get_mut().storage.get_or_insert_with(BTreeMap::new);
+                    for (slot, value) in storage {
+                        slots.entry(slot).or_insert(value);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(pre_state)
+}
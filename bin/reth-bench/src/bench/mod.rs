@@ -6,6 +6,7 @@ use reth_node_core::args::LogArgs;
 use reth_tracing::FileWorkerGuard;
 
 mod context;
+mod corpus;
 mod new_payload_fcu;
 mod new_payload_only;
 mod output;
@@ -28,6 +29,9 @@ pub enum Subcommands {
 
     /// Benchmark which only calls subsequent `newPayload` calls.
     NewPayloadOnly(new_payload_only::Command),
+
+    /// Downloads a corpus of blocks and the state they access for the executor benchmarks.
+    Corpus(corpus::Command),
 }
 
 impl BenchmarkCommand {
@@ -39,6 +43,7 @@ impl BenchmarkCommand {
         match self.command {
             Subcommands::NewPayloadFcu(command) => command.execute(ctx).await,
             Subcommands::NewPayloadOnly(command) => command.execute(ctx).await,
+            Subcommands::Corpus(command) => command.execute(ctx).await,
         }
     }
 
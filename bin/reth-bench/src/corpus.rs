@@ -0,0 +1,139 @@
+//! A corpus of mainnet blocks together with the state they access.
+//!
+//! The corpus is used by the executor benchmarks to run block execution, state root computation
+//! and persistence in isolation, without a synced node. Every block of the corpus is stored as a
+//! JSON file named after its number, see [`CorpusBlock`].
+
+use alloy_rlp::{Decodable, Encodable};
+use reth_primitives::{
+    Address, Block, BlockNumber, BlockWithSenders, Bytes, GenesisAccount, B256, U256,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable that points the executor benchmarks to the corpus directory.
+pub const CORPUS_DIR_ENV: &str = "RETH_BENCH_CORPUS";
+
+/// A block of the corpus.
+///
+/// Besides the block, this contains all accounts and storage slots the block accesses, with their
+/// values before the block was executed, so the block can be executed without the rest of the
+/// state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorpusBlock {
+    /// The RLP encoded block.
+    pub block: Bytes,
+    /// The total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+    /// The accounts and storage slots the block accesses, before the block was executed.
+    pub pre_state: BTreeMap<Address, GenesisAccount>,
+    /// Hashes of the ancestors the block can access with the `BLOCKHASH` opcode.
+    pub block_hashes: BTreeMap<BlockNumber, B256>,
+}
+
+impl CorpusBlock {
+    /// Creates a new corpus block.
+    pub fn new(
+        block: &Block,
+        total_difficulty: U256,
+        pre_state: BTreeMap<Address, GenesisAccount>,
+        block_hashes: BTreeMap<BlockNumber, B256>,
+    ) -> Self {
+        let mut encoded = Vec::with_capacity(block.length());
+        block.encode(&mut encoded);
+        Self { block: encoded.into(), total_difficulty, pre_state, block_hashes }
+    }
+
+    /// Decodes the block and recovers the senders of its transactions.
+    pub fn block(&self) -> Result<BlockWithSenders, CorpusError> {
+        Block::decode(&mut self.block.as_ref())?
+            .with_recovered_senders()
+            .ok_or(CorpusError::SenderRecovery)
+    }
+
+    /// Returns the path of the file of the block with the given number in the corpus directory.
+    pub fn path(dir: &Path, number: BlockNumber) -> PathBuf {
+        dir.join(format!("{number}.json"))
+    }
+
+    /// Writes the block with the given number to the corpus directory.
+    pub fn write(&self, dir: &Path, number: BlockNumber) -> Result<(), CorpusError> {
+        let file = fs::File::create(Self::path(dir, number))?;
+        serde_json::to_writer(BufWriter::new(file), self)?;
+        Ok(())
+    }
+}
+
+/// Reads all blocks of the corpus in the directory, ordered by block number.
+///
+/// Files that are not named after a block number are ignored.
+pub fn read_corpus(dir: &Path) -> Result<Vec<(BlockNumber, CorpusBlock)>, CorpusError> {
+    let mut blocks = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map_or(true, |extension| extension != "json") {
+            continue
+        }
+        let Some(number) =
+            path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse().ok())
+        else {
+            continue
+        };
+        let file = fs::File::open(&path)?;
+        blocks.push((number, serde_json::from_reader(BufReader::new(file))?));
+    }
+    blocks.sort_unstable_by_key(|(number, _)| *number);
+    Ok(blocks)
+}
+
+/// Errors that can occur when reading or writing the corpus.
+#[derive(Debug, thiserror::Error)]
+pub enum CorpusError {
+    /// Failed to access the corpus directory.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Failed to (de)serialize a corpus block.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Failed to decode a block.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// Failed to recover the senders of the transactions of a block.
+    #[error("failed to recover the senders of the block's transactions")]
+    SenderRecovery,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Header;
+
+    #[test]
+    fn corpus_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let block = Block {
+            header: Header { number: 100, gas_limit: 30_000_000, ..Default::default() },
+            ..Default::default()
+        };
+        let pre_state = BTreeMap::from([(
+            Address::with_last_byte(1),
+            GenesisAccount::default()
+                .with_balance(U256::from(10))
+                .with_storage(Some(BTreeMap::from([(B256::ZERO, B256::with_last_byte(1))]))),
+        )]);
+        let corpus_block =
+            CorpusBlock::new(&block, U256::from(1), pre_state, BTreeMap::from([(99, B256::ZERO)]));
+        corpus_block.write(dir.path(), 100).unwrap();
+        fs::write(dir.path().join("README.md"), "not a block").unwrap();
+
+        let corpus = read_corpus(dir.path()).unwrap();
+        assert_eq!(corpus, vec![(100, corpus_block)]);
+        assert_eq!(corpus[0].1.block().unwrap().block, block);
+    }
+}
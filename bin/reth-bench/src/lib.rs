@@ -0,0 +1,3 @@
+//! Shared types of the `reth-bench` binary and the executor benchmarks.
+
+pub mod corpus;
@@ -9,12 +9,12 @@ use crate::{
     macros::block_executor,
     version::{LONG_VERSION, SHORT_VERSION},
 };
-use clap::{value_parser, Parser, Subcommand};
+use clap::{value_parser, CommandFactory, FromArgMatches, Parser, Subcommand};
 use reth_chainspec::ChainSpec;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, import, init_cmd, init_state,
+    config_cmd, db, dump_genesis, export, import, init_cmd, init_state, keys,
     node::{self, NoArgs},
-    p2p, prune, recover, stage,
+    p2p, prune, recover, rollback, stage,
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
@@ -76,7 +76,7 @@ pub struct Cli<Ext: clap::Args + fmt::Debug = NoArgs> {
 impl Cli {
     /// Parsers only the default CLI arguments
     pub fn parse_args() -> Self {
-        Self::parse()
+        Self::try_parse_args_from(std::env::args_os()).unwrap_or_else(|err| err.exit())
     }
 
     /// Parsers only the default CLI arguments from the given iterator
@@ -85,7 +85,17 @@ impl Cli {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        Self::try_parse_from(itr)
+        let matches = Self::command().try_get_matches_from(itr)?;
+        let mut cli =
+            Self::from_arg_matches(&matches).map_err(|err| err.format(&mut Self::command()))?;
+        // record the explicitly set arguments of the node command, which take precedence over
+        // the values the node derives itself
+        if let (Commands::Node(command), Some(matches)) =
+            (&mut cli.command, matches.subcommand_matches("node"))
+        {
+            command.set_explicit_args(matches);
+        }
+        Ok(cli)
     }
 }
 
@@ -162,16 +172,21 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             }
             Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Export(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner.run_command_until_exit(|ctx| {
                 command.execute(ctx, |chain_spec| block_executor!(chain_spec))
             }),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Keys(command) => runner.run_until_ctrl_c(command.execute()),
             #[cfg(feature = "dev")]
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Debug(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Recover(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Rollback(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            #[cfg(feature = "sql")]
+            Commands::Sql(command) => runner.run_until_ctrl_c(command.execute()),
         }
     }
 
@@ -213,12 +228,18 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Database debugging utilities
     #[command(name = "db")]
     Db(db::Command),
+    /// Export chain data for analytics.
+    #[command(name = "export")]
+    Export(export::Command),
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command),
     /// P2P Debugging utilities
     #[command(name = "p2p")]
     P2P(p2p::Command),
+    /// Manage the P2P secret key and the JWT secret
+    #[command(name = "keys")]
+    Keys(keys::Command),
     /// Generate Test Vectors
     #[cfg(feature = "dev")]
     #[command(name = "test-vectors")]
@@ -235,6 +256,13 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand),
+    /// Roll the node back to a block, removing all blocks after it
+    #[command(name = "rollback")]
+    Rollback(rollback::RollbackCommand),
+    /// Execute read-only SQL queries over chain data
+    #[cfg(feature = "sql")]
+    #[command(name = "sql")]
+    Sql(reth_cli_commands::sql::Command),
 }
 
 #[cfg(test)]
@@ -7,7 +7,7 @@ use futures::{stream::select as stream_select, StreamExt};
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
 use reth_cli_runner::CliContext;
-use reth_cli_util::get_secret_key;
+use reth_cli_util::get_secret_key_with_passphrase;
 use reth_config::Config;
 use reth_consensus::Consensus;
 use reth_db::DatabaseEnv;
@@ -34,7 +34,11 @@ use reth_tasks::TaskExecutor;
 use tokio::sync::watch;
 use tracing::*;
 
-use crate::{args::NetworkArgs, macros::block_executor, utils::get_single_header};
+use crate::{
+    args::{NetworkArgs, SecretsArgs},
+    macros::block_executor,
+    utils::get_single_header,
+};
 
 /// `reth debug execution` command
 #[derive(Debug, Parser)]
@@ -45,6 +49,9 @@ pub struct Command {
     #[command(flatten)]
     network: NetworkArgs,
 
+    #[command(flatten)]
+    secrets: SecretsArgs,
+
     /// The maximum block height.
     #[arg(long)]
     pub to: u64,
@@ -104,6 +111,7 @@ impl Command {
                         max_changes: None,
                         max_cumulative_gas: None,
                         max_duration: None,
+                        block_checkpoint_gas: None,
                     },
                     stage_conf.execution_external_clean_threshold(),
                     prune_modes,
@@ -123,7 +131,8 @@ impl Command {
         network_secret_path: PathBuf,
         default_peers_path: PathBuf,
     ) -> eyre::Result<NetworkHandle> {
-        let secret_key = get_secret_key(&network_secret_path)?;
+        let passphrase = self.secrets.passphrase()?;
+        let secret_key = get_secret_key_with_passphrase(&network_secret_path, passphrase.as_ref())?;
         let network = self
             .network
             .network_config(config, provider_factory.chain_spec(), secret_key, default_peers_path)
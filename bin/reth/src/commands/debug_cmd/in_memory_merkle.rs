@@ -6,7 +6,7 @@ use backon::{ConstantBuilder, Retryable};
 use clap::Parser;
 use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
 use reth_cli_runner::CliContext;
-use reth_cli_util::get_secret_key;
+use reth_cli_util::get_secret_key_with_passphrase;
 use reth_config::Config;
 use reth_db::DatabaseEnv;
 use reth_errors::BlockValidationError;
@@ -28,7 +28,7 @@ use reth_trie_db::DatabaseStateRoot;
 use tracing::*;
 
 use crate::{
-    args::NetworkArgs,
+    args::{NetworkArgs, SecretsArgs},
     macros::block_executor,
     utils::{get_single_body, get_single_header},
 };
@@ -45,6 +45,9 @@ pub struct Command {
     #[command(flatten)]
     network: NetworkArgs,
 
+    #[command(flatten)]
+    secrets: SecretsArgs,
+
     /// The number of retries per request
     #[arg(long, default_value = "5")]
     retries: usize,
@@ -63,7 +66,8 @@ impl Command {
         network_secret_path: PathBuf,
         default_peers_path: PathBuf,
     ) -> eyre::Result<NetworkHandle> {
-        let secret_key = get_secret_key(&network_secret_path)?;
+        let passphrase = self.secrets.passphrase()?;
+        let secret_key = get_secret_key_with_passphrase(&network_secret_path, passphrase.as_ref())?;
         let network = self
             .network
             .network_config(config, provider_factory.chain_spec(), secret_key, default_peers_path)
@@ -7,7 +7,7 @@ use clap::Parser;
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
 use reth_cli_runner::CliContext;
-use reth_cli_util::get_secret_key;
+use reth_cli_util::get_secret_key_with_passphrase;
 use reth_config::Config;
 use reth_consensus::Consensus;
 use reth_db::{tables, DatabaseEnv};
@@ -29,7 +29,11 @@ use reth_stages::{
 use reth_tasks::TaskExecutor;
 use tracing::*;
 
-use crate::{args::NetworkArgs, macros::block_executor, utils::get_single_header};
+use crate::{
+    args::{NetworkArgs, SecretsArgs},
+    macros::block_executor,
+    utils::get_single_header,
+};
 
 /// `reth debug merkle` command
 #[derive(Debug, Parser)]
@@ -40,6 +44,9 @@ pub struct Command {
     #[command(flatten)]
     network: NetworkArgs,
 
+    #[command(flatten)]
+    secrets: SecretsArgs,
+
     /// The number of retries per request
     #[arg(long, default_value = "5")]
     retries: usize,
@@ -62,7 +69,8 @@ impl Command {
         network_secret_path: PathBuf,
         default_peers_path: PathBuf,
     ) -> eyre::Result<NetworkHandle> {
-        let secret_key = get_secret_key(&network_secret_path)?;
+        let passphrase = self.secrets.passphrase()?;
+        let secret_key = get_secret_key_with_passphrase(&network_secret_path, passphrase.as_ref())?;
         let network = self
             .network
             .network_config(config, provider_factory.chain_spec(), secret_key, default_peers_path)
@@ -9,7 +9,7 @@ use reth_blockchain_tree::{
 };
 use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
 use reth_cli_runner::CliContext;
-use reth_cli_util::get_secret_key;
+use reth_cli_util::get_secret_key_with_passphrase;
 use reth_config::Config;
 use reth_consensus::Consensus;
 use reth_db::DatabaseEnv;
@@ -29,7 +29,10 @@ use reth_transaction_pool::noop::NoopTransactionPool;
 use tokio::sync::oneshot;
 use tracing::*;
 
-use crate::{args::NetworkArgs, macros::block_executor};
+use crate::{
+    args::{NetworkArgs, SecretsArgs},
+    macros::block_executor,
+};
 
 /// `reth debug replay-engine` command
 /// This script will read stored engine API messages and replay them by the timestamp.
@@ -42,6 +45,9 @@ pub struct Command {
     #[command(flatten)]
     network: NetworkArgs,
 
+    #[command(flatten)]
+    secrets: SecretsArgs,
+
     /// The path to read engine API messages from.
     #[arg(long = "engine-api-store", value_name = "PATH")]
     engine_api_store: PathBuf,
@@ -60,7 +66,8 @@ impl Command {
         network_secret_path: PathBuf,
         default_peers_path: PathBuf,
     ) -> eyre::Result<NetworkHandle> {
-        let secret_key = get_secret_key(&network_secret_path)?;
+        let passphrase = self.secrets.passphrase()?;
+        let secret_key = get_secret_key_with_passphrase(&network_secret_path, passphrase.as_ref())?;
         let network = self
             .network
             .network_config(config, provider_factory.chain_spec(), secret_key, default_peers_path)
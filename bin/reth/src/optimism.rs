@@ -4,7 +4,10 @@ use clap::Parser;
 use reth::cli::Cli;
 use reth_node_builder::EngineNodeLauncher;
 use reth_node_optimism::{
-    args::RollupArgs, node::OptimismAddOns, rpc::SequencerClient, OptimismNode,
+    args::RollupArgs,
+    node::OptimismAddOns,
+    rpc::{OpProvenance, OpProvenanceApiServer, SequencerClient},
+    OptimismNode,
 };
 use reth_provider::providers::BlockchainProvider2;
 use std::sync::Arc;
@@ -43,6 +46,11 @@ fn main() {
                             ));
                         }
 
+                        // register the L1 provenance endpoints of the `optimism` namespace
+                        ctx.modules.merge_configured(
+                            OpProvenance::new(ctx.provider().clone()).into_rpc(),
+                        )?;
+
                         Ok(())
                     })
                     .launch_with_fn(|builder| {
@@ -67,6 +75,11 @@ fn main() {
                             ));
                         }
 
+                        // register the L1 provenance endpoints of the `optimism` namespace
+                        ctx.modules.merge_configured(
+                            OpProvenance::new(ctx.provider().clone()).into_rpc(),
+                        )?;
+
                         Ok(())
                     })
                     .launch()
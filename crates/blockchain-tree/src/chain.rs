@@ -210,13 +210,14 @@ impl AppendableChain {
         let block = block.unseal();
 
         let state = executor.execute((&block, U256::MAX).into())?;
-        let BlockExecutionOutput { state, receipts, requests, .. } = state;
+        let BlockExecutionOutput { state, receipts, requests, internal_transfers, .. } = state;
         externals
             .consensus
             .validate_block_post_execution(&block, PostExecutionInput::new(&receipts, &requests))?;
 
         let initial_execution_outcome =
-            ExecutionOutcome::new(state, receipts.into(), block.number, vec![requests.into()]);
+            ExecutionOutcome::new(state, receipts.into(), block.number, vec![requests.into()])
+                .with_internal_transfers(vec![internal_transfers]);
 
         // check state root if the block extends the canonical chain __and__ if state root
         // validation was requested.
@@ -1,4 +1,5 @@
-use parking_lot::RwLock;
+use crate::slot_timing::{SlotPhase, SlotTimingTracker};
+use parking_lot::{Mutex, RwLock};
 use reth_chainspec::ChainInfo;
 use reth_primitives::{BlockNumHash, BlockNumber, SealedHeader};
 use std::{
@@ -6,7 +7,7 @@ use std::{
         atomic::{AtomicU64, Ordering},
         Arc,
     },
-    time::Instant,
+    time::{Instant, SystemTime},
 };
 use tokio::sync::watch;
 
@@ -31,6 +32,7 @@ impl ChainInfoTracker {
                 canonical_head: RwLock::new(head),
                 safe_block,
                 finalized_block,
+                slot_timing: Mutex::new(SlotTimingTracker::default()),
             }),
         }
     }
@@ -46,6 +48,23 @@ impl ChainInfoTracker {
         self.inner.last_forkchoice_update.write().replace(Instant::now());
     }
 
+    /// Records the time since the start of the slot until the payload with the given timestamp was
+    /// received.
+    pub fn on_new_payload_received(&self, timestamp: u64) {
+        self.inner.slot_timing.lock().record(SlotPhase::NewPayload, timestamp, SystemTime::now());
+    }
+
+    /// Records the time since the start of the slot of the canonical head until the forkchoice
+    /// update that made it the head was acknowledged.
+    pub fn on_forkchoice_update_acknowledged(&self) {
+        let timestamp = self.inner.canonical_head.read().timestamp;
+        self.inner.slot_timing.lock().record(
+            SlotPhase::ForkchoiceAck,
+            timestamp,
+            SystemTime::now(),
+        );
+    }
+
     /// Returns the instant when we received the latest forkchoice update.
     pub fn last_forkchoice_update_received_at(&self) -> Option<Instant> {
         *self.inner.last_forkchoice_update.read()
@@ -101,6 +120,12 @@ impl ChainInfoTracker {
 
     /// Sets the canonical head of the chain.
     pub fn set_canonical_head(&self, header: SealedHeader) {
+        self.inner.slot_timing.lock().record(
+            SlotPhase::Canonical,
+            header.timestamp,
+            SystemTime::now(),
+        );
+
         let number = header.number;
         *self.inner.canonical_head.write() = header;
 
@@ -152,4 +177,7 @@ struct ChainInfoInner {
     safe_block: watch::Sender<Option<SealedHeader>>,
     /// The block that the beacon node considers finalized.
     finalized_block: watch::Sender<Option<SealedHeader>>,
+    /// Tracks how long after the start of their slot blocks are received, made canonical and
+    /// acknowledged.
+    slot_timing: Mutex<SlotTimingTracker>,
 }
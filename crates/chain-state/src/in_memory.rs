@@ -2,20 +2,21 @@
 
 use crate::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
-    ChainInfoTracker, MemoryOverlayStateProvider,
+    ChainInfoTracker, ChainSegment, InMemoryRetention, MemoryOverlayStateProvider,
 };
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_metrics::{metrics::Gauge, Metrics};
 use reth_primitives::{
-    Address, BlockNumHash, Header, Receipt, Receipts, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, TransactionMeta, TransactionSigned, TxHash, B256,
+    Account, Address, BlockNumHash, Header, Log, Receipt, Receipts, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned, TxHash, B256, U256,
 };
 use reth_storage_api::StateProviderBox;
-use reth_trie::{updates::TrieUpdates, HashedPostState};
+use reth_trie::{updates::TrieUpdates, BranchNodeCompact, HashedPostState, HashedStorage, Nibbles};
 use std::{
     collections::{BTreeMap, HashMap},
+    mem,
     sync::Arc,
     time::Instant,
 };
@@ -48,6 +49,9 @@ pub(crate) struct InMemoryState {
     numbers: RwLock<BTreeMap<u64, B256>>,
     /// The pending block that has not yet been made canonical.
     pending: watch::Sender<Option<BlockState>>,
+    /// Executed blocks that are not part of the canonical chain and not on disk, like blocks of
+    /// forks and blocks that were reorged out, by hash.
+    sidechain: RwLock<HashMap<B256, Arc<BlockState>>>,
     /// Metrics for the in-memory state.
     metrics: InMemoryStateMetrics,
 }
@@ -63,6 +67,7 @@ impl InMemoryState {
             blocks: RwLock::new(blocks),
             numbers: RwLock::new(numbers),
             pending,
+            sidechain: Default::default(),
             metrics: Default::default(),
         };
         this.update_metrics();
@@ -104,15 +109,22 @@ impl InMemoryState {
     pub(crate) fn head_state(&self) -> Option<Arc<BlockState>> {
         self.numbers
             .read()
-            .iter()
-            .max_by_key(|(&number, _)| number)
+            .last_key_value()
             .and_then(|(_, hash)| self.blocks.read().get(hash).cloned())
     }
 
     /// Returns the pending state corresponding to the current head plus one,
     /// from the payload received in newPayload that does not have a FCU yet.
     pub(crate) fn pending_state(&self) -> Option<Arc<BlockState>> {
-        self.pending.borrow().as_ref().map(|state| Arc::new(BlockState::new(state.block.clone())))
+        self.pending.borrow().as_ref().map(|state| Arc::new(state.clone()))
+    }
+
+    /// Returns the state of a block that is not part of the canonical chain, including the
+    /// pending block.
+    pub(crate) fn non_canonical_state_by_hash(&self, hash: B256) -> Option<Arc<BlockState>> {
+        self.pending_state()
+            .filter(|state| state.hash() == hash)
+            .or_else(|| self.sidechain.read().get(&hash).cloned())
     }
 
     #[cfg(test)]
@@ -132,6 +144,11 @@ pub(crate) struct CanonicalInMemoryStateInner {
     pub(crate) in_memory_state: InMemoryState,
     /// A broadcast stream that emits events when the canonical chain is updated.
     pub(crate) canon_state_notification_sender: CanonStateNotificationSender,
+    /// Policy that decides how many blocks are kept in memory before they are persisted.
+    pub(crate) retention: InMemoryRetention,
+    /// The highest block that was removed from memory because it was persisted, the default
+    /// [`BlockNumHash`] until blocks are persisted for the first time.
+    pub(crate) persisted_block: watch::Sender<BlockNumHash>,
 }
 
 impl CanonicalInMemoryStateInner {
@@ -145,6 +162,7 @@ impl CanonicalInMemoryStateInner {
             self.in_memory_state.pending.send_modify(|p| {
                 p.take();
             });
+            self.in_memory_state.sidechain.write().clear();
         }
         self.in_memory_state.update_metrics();
     }
@@ -176,10 +194,13 @@ impl CanonicalInMemoryState {
         let (canon_state_notification_sender, _) =
             broadcast::channel(CANON_STATE_NOTIFICATION_CHANNEL_SIZE);
 
+        let (persisted_block, _) = watch::channel(BlockNumHash::default());
         let inner = CanonicalInMemoryStateInner {
             chain_info_tracker,
             in_memory_state,
             canon_state_notification_sender,
+            retention: InMemoryRetention::default(),
+            persisted_block,
         };
 
         Self { inner: Arc::new(inner) }
@@ -190,22 +211,35 @@ impl CanonicalInMemoryState {
         Self::new(HashMap::new(), BTreeMap::new(), None, None)
     }
 
-    /// Create a new in memory state with the given local head and finalized header
-    /// if it exists.
-    pub fn with_head(head: SealedHeader, finalized: Option<SealedHeader>) -> Self {
+    /// Create a new in memory state with the given local head, finalized header if it exists, and
+    /// the policy that decides how many blocks are kept in memory.
+    pub fn with_head(
+        head: SealedHeader,
+        finalized: Option<SealedHeader>,
+        retention: InMemoryRetention,
+    ) -> Self {
         let chain_info_tracker = ChainInfoTracker::new(head, finalized);
         let in_memory_state = InMemoryState::default();
         let (canon_state_notification_sender, _) =
             broadcast::channel(CANON_STATE_NOTIFICATION_CHANNEL_SIZE);
+        let (persisted_block, _) = watch::channel(BlockNumHash::default());
         let inner = CanonicalInMemoryStateInner {
             chain_info_tracker,
             in_memory_state,
             canon_state_notification_sender,
+            retention,
+            persisted_block,
         };
 
         Self { inner: Arc::new(inner) }
     }
 
+    /// Returns the policy that decides how many blocks are kept in memory before they are
+    /// persisted.
+    pub fn retention(&self) -> InMemoryRetention {
+        self.inner.retention
+    }
+
     /// Returns the block hash corresponding to the given number.
     pub fn hash_by_number(&self, number: u64) -> Option<B256> {
         self.inner.in_memory_state.hash_by_number(number)
@@ -223,17 +257,42 @@ impl CanonicalInMemoryState {
 
     /// Updates the pending block with the given block.
     ///
+    /// A replaced pending block is kept as a sidechain block.
+    ///
     /// Note: This assumes that the parent block of the pending block is canonical.
     pub fn set_pending_block(&self, pending: ExecutedBlock) {
         // fetch the state of the pending block's parent block
         let parent = self.state_by_hash(pending.block().parent_hash);
         let pending = BlockState::with_parent(pending, parent.map(|p| (*p).clone()));
+        let mut replaced = None;
         self.inner.in_memory_state.pending.send_modify(|p| {
-            p.replace(pending);
+            replaced = p.replace(pending);
         });
+        if let Some(replaced) = replaced {
+            self.insert_sidechain_state(replaced);
+        }
         self.inner.in_memory_state.update_metrics();
     }
 
+    /// Inserts an executed block that is not part of the canonical chain, like a block of a fork.
+    ///
+    /// The state of the block can be looked up with [`Self::non_canonical_state_by_hash`] until it
+    /// becomes canonical or is older than the persisted blocks.
+    pub fn insert_sidechain_block(&self, block: ExecutedBlock) {
+        let parent_hash = block.block().parent_hash;
+        let parent = self
+            .state_by_hash(parent_hash)
+            .or_else(|| self.non_canonical_state_by_hash(parent_hash));
+        self.insert_sidechain_state(BlockState::with_parent(block, parent.map(|p| (*p).clone())));
+    }
+
+    /// Inserts the state of a block that is not part of the canonical chain.
+    fn insert_sidechain_state(&self, state: BlockState) {
+        if self.state_by_hash(state.hash()).is_none() {
+            self.inner.in_memory_state.sidechain.write().insert(state.hash(), Arc::new(state));
+        }
+    }
+
     /// Append new blocks to the in memory state.
     fn update_blocks<I>(&self, new_blocks: I, reorged: I)
     where
@@ -244,19 +303,66 @@ impl CanonicalInMemoryState {
             let mut numbers = self.inner.in_memory_state.numbers.write();
             let mut blocks = self.inner.in_memory_state.blocks.write();
 
-            // we first remove the blocks from the reorged chain
+            let mut sidechain = self.inner.in_memory_state.sidechain.write();
+
+            // we first remove the blocks from the reorged chain, which are kept as sidechain
+            // blocks
             for block in reorged {
                 let hash = block.block().hash();
                 let number = block.block().number;
-                blocks.remove(&hash);
+                if let Some(state) = blocks.remove(&hash) {
+                    sidechain.insert(hash, state);
+                }
                 numbers.remove(&number);
             }
 
-            // insert the new blocks
-            for block in new_blocks {
-                let parent = blocks.get(&block.block().parent_hash).cloned();
-                let block_state =
-                    BlockState::with_parent(block.clone(), parent.map(|p| (*p).clone()));
+            // insert the new blocks, which are no longer sidechain blocks
+            let new_blocks = new_blocks.into_iter().collect::<Vec<_>>();
+            for block in &new_blocks {
+                sidechain.remove(&block.block().hash());
+            }
+            Self::insert_blocks(&mut blocks, &mut numbers, new_blocks);
+
+            // remove the pending state, and keep it as a sidechain block if it didn't become
+            // canonical
+            self.inner.in_memory_state.pending.send_modify(|p| {
+                if let Some(pending) = p.take() {
+                    if !blocks.contains_key(&pending.hash()) {
+                        sidechain.insert(pending.hash(), Arc::new(pending));
+                    }
+                }
+            });
+        }
+        self.inner.in_memory_state.update_metrics();
+    }
+
+    /// Inserts the states of the given blocks, which are ordered from the oldest to the newest
+    /// block.
+    ///
+    /// The chain of a block's state extends the chain of its parent's state, if the parent is in
+    /// memory. Consecutive blocks share the blocks of their chains.
+    fn insert_blocks(
+        blocks: &mut HashMap<B256, Arc<BlockState>>,
+        numbers: &mut BTreeMap<u64, B256>,
+        new_blocks: impl IntoIterator<Item = ExecutedBlock>,
+    ) {
+        let mut new_blocks = new_blocks.into_iter().peekable();
+        while let Some(first) = new_blocks.next() {
+            // collect the run of blocks that extend each other
+            let mut tip = first.block().hash();
+            let mut run = vec![first];
+            while let Some(block) = new_blocks.next_if(|block| block.block().parent_hash == tip) {
+                tip = block.block().hash();
+                run.push(block);
+            }
+
+            let parent_chain = blocks
+                .get(&run[0].block().parent_hash)
+                .map(|parent| parent.chain().clone())
+                .unwrap_or_default();
+            let chain = parent_chain.with_blocks(run);
+            for block in &chain.blocks()[parent_chain.len()..] {
+                let block_state = BlockState { chain: chain.slice(..=block.block().number) };
                 let hash = block_state.hash();
                 let number = block_state.number();
 
@@ -264,13 +370,7 @@ impl CanonicalInMemoryState {
                 blocks.insert(hash, Arc::new(block_state));
                 numbers.insert(number, hash);
             }
-
-            // remove the pending state
-            self.inner.in_memory_state.pending.send_modify(|p| {
-                p.take();
-            });
         }
-        self.inner.in_memory_state.update_metrics();
     }
 
     /// Update the in memory state with the given chain update.
@@ -288,8 +388,9 @@ impl CanonicalInMemoryState {
     /// Removes blocks from the in memory state that are persisted to the given height.
     ///
     /// This will update the links between blocks and remove all blocks that are [..
-    /// `persisted_height`].
-    pub fn remove_persisted_blocks(&self, persisted_height: u64) {
+    /// `persisted_block`].
+    pub fn remove_persisted_blocks(&self, persisted_block: BlockNumHash) {
+        let persisted_height = persisted_block.number;
         {
             let mut blocks = self.inner.in_memory_state.blocks.write();
             let mut numbers = self.inner.in_memory_state.numbers.write();
@@ -300,36 +401,46 @@ impl CanonicalInMemoryState {
             // drain all blocks and only keep the ones that are not persisted
             let mut old_blocks = blocks
                 .drain()
-                .map(|(_, b)| b.block.clone())
+                .map(|(_, b)| b.block())
                 .filter(|b| b.block().number > persisted_height)
                 .collect::<Vec<_>>();
 
             // sort the blocks by number so we can insert them back in natural order (low -> high)
             old_blocks.sort_unstable_by_key(|block| block.block().number);
 
-            for block in old_blocks {
-                let parent = blocks.get(&block.block().parent_hash).cloned();
-                let block_state =
-                    BlockState::with_parent(block.clone(), parent.map(|p| (*p).clone()));
-                let hash = block_state.hash();
-                let number = block_state.number();
+            Self::insert_blocks(&mut blocks, &mut numbers, old_blocks);
 
-                // append new blocks
-                blocks.insert(hash, Arc::new(block_state));
-                numbers.insert(number, hash);
-            }
+            // drop the sidechain blocks that are not newer than the persisted blocks
+            self.inner
+                .in_memory_state
+                .sidechain
+                .write()
+                .retain(|_, state| state.number() > persisted_height);
 
             // also shift the pending state if it exists
             self.inner.in_memory_state.pending.send_modify(|p| {
                 if let Some(p) = p.as_mut() {
-                    p.parent = blocks
-                        .get(&p.block().block.parent_hash)
-                        .cloned()
-                        .map(|p| Box::new((*p).clone()));
+                    let parent = blocks.get(&p.block().block.parent_hash).map(|p| (**p).clone());
+                    *p = BlockState::with_parent(p.block(), parent);
                 }
             });
         }
         self.inner.in_memory_state.update_metrics();
+        self.set_persisted_block(persisted_block);
+    }
+
+    /// Sets the highest persisted block, e.g. after the blocks of a backfill sync were written to
+    /// the database directly.
+    pub fn set_persisted_block(&self, persisted_block: BlockNumHash) {
+        self.inner.persisted_block.send_replace(persisted_block);
+    }
+
+    /// Subscribes to the highest block that was removed from memory because it was persisted,
+    /// see [`Self::remove_persisted_blocks`] and [`Self::set_persisted_block`].
+    ///
+    /// The value is the default [`BlockNumHash`] until blocks are persisted for the first time.
+    pub fn subscribe_persisted_block(&self) -> watch::Receiver<BlockNumHash> {
+        self.inner.persisted_block.subscribe()
     }
 
     /// Returns in memory state corresponding the given hash.
@@ -352,6 +463,12 @@ impl CanonicalInMemoryState {
         self.inner.in_memory_state.pending_state()
     }
 
+    /// Returns the in memory state of a block that is not part of the canonical chain, like the
+    /// pending block, a block of a fork or a block that was reorged out.
+    pub fn non_canonical_state_by_hash(&self, hash: B256) -> Option<Arc<BlockState>> {
+        self.inner.in_memory_state.non_canonical_state_by_hash(hash)
+    }
+
     /// Returns the in memory pending `BlockNumHash`.
     pub fn pending_block_num_hash(&self) -> Option<BlockNumHash> {
         self.inner
@@ -385,6 +502,16 @@ impl CanonicalInMemoryState {
         self.inner.chain_info_tracker.on_forkchoice_update_received();
     }
 
+    /// Hook for a new payload with the given timestamp.
+    pub fn on_new_payload_received(&self, timestamp: u64) {
+        self.inner.chain_info_tracker.on_new_payload_received(timestamp);
+    }
+
+    /// Hook for an acknowledged fork choice update.
+    pub fn on_forkchoice_update_acknowledged(&self) {
+        self.inner.chain_info_tracker.on_forkchoice_update_acknowledged();
+    }
+
     /// Returns the timestamp of the last received update.
     pub fn last_received_update_timestamp(&self) -> Option<Instant> {
         self.inner.chain_info_tracker.last_forkchoice_update_received_at()
@@ -478,13 +605,15 @@ impl CanonicalInMemoryState {
         hash: B256,
         historical: StateProviderBox,
     ) -> MemoryOverlayStateProvider {
-        let in_memory = if let Some(state) = self.state_by_hash(hash) {
-            state.chain().into_iter().map(|block_state| block_state.block()).collect()
-        } else {
-            Vec::new()
-        };
+        match self.state_by_hash(hash) {
+            Some(state) => state.state_provider(historical),
+            None => MemoryOverlayStateProvider::new(Vec::new(), historical),
+        }
+    }
 
-        MemoryOverlayStateProvider::new(in_memory, historical)
+    /// Returns the canonical blocks in the in-memory state, without the pending block.
+    pub fn canonical_segment(&self) -> ChainSegment {
+        self.head_state().map(|state| state.chain().clone()).unwrap_or_default()
     }
 
     /// Returns an iterator over all canonical blocks in the in-memory state, from newest to oldest.
@@ -515,13 +644,19 @@ impl CanonicalInMemoryState {
         None
     }
 
+    /// Returns the [`BlockState`] of the block that includes the given `TxHash` if found.
+    pub fn state_by_transaction_hash(&self, hash: TxHash) -> Option<Arc<BlockState>> {
+        self.canonical_chain()
+            .find(|block_state| block_state.block().block().body.iter().any(|tx| tx.hash() == hash))
+    }
+
     /// Returns a tuple with `TransactionSigned` and `TransactionMeta` for the
     /// given `TxHash` if found.
     pub fn transaction_by_hash_with_meta(
         &self,
         tx_hash: TxHash,
     ) -> Option<(TransactionSigned, TransactionMeta)> {
-        for (block_number, block_state) in self.canonical_chain().enumerate() {
+        for block_state in self.canonical_chain() {
             if let Some((index, tx)) = block_state
                 .block()
                 .block()
@@ -534,7 +669,7 @@ impl CanonicalInMemoryState {
                     tx_hash,
                     index: index as u64,
                     block_hash: block_state.hash(),
-                    block_number: block_number as u64,
+                    block_number: block_state.number(),
                     base_fee: block_state.block().block().header.base_fee_per_gas,
                     timestamp: block_state.block().block.timestamp,
                     excess_blob_gas: block_state.block().block.excess_blob_gas,
@@ -550,57 +685,68 @@ impl CanonicalInMemoryState {
 /// stored in memory and can be traced back to a canonical block on disk.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BlockState {
-    /// The executed block that determines the state after this block has been executed.
-    block: ExecutedBlock,
-    /// The block's parent block if it exists.
-    parent: Option<Box<BlockState>>,
+    /// The in-memory chain that leads to the state, from the oldest in-memory block up to and
+    /// including the executed block that determines the state.
+    ///
+    /// This is never empty.
+    chain: ChainSegment,
 }
 
 #[allow(dead_code)]
 impl BlockState {
     /// `BlockState` constructor.
-    pub const fn new(block: ExecutedBlock) -> Self {
-        Self { block, parent: None }
+    pub fn new(block: ExecutedBlock) -> Self {
+        Self { chain: ChainSegment::new(vec![block]) }
     }
 
     /// `BlockState` constructor with parent.
     pub fn with_parent(block: ExecutedBlock, parent: Option<Self>) -> Self {
-        Self { block, parent: parent.map(Box::new) }
+        match parent {
+            Some(parent) => Self { chain: parent.chain.with_blocks([block]) },
+            None => Self::new(block),
+        }
+    }
+
+    /// Returns the executed block that determines the state.
+    fn executed_block(&self) -> &ExecutedBlock {
+        self.chain.last().expect("chain of block state is never empty")
     }
 
     /// Returns the hash and block of the on disk block this state can be traced back to.
     pub fn anchor(&self) -> BlockNumHash {
-        if let Some(parent) = &self.parent {
-            parent.anchor()
-        } else {
-            self.block.block().parent_num_hash()
-        }
+        self.chain.anchor().expect("chain of block state is never empty")
+    }
+
+    /// Returns the cumulative number of transactions of the parent blocks, which are the
+    /// transactions between the anchor and the executed block that determines the state.
+    pub fn parent_tx_count(&self) -> u64 {
+        self.chain.tx_offset(self.number()).unwrap_or_default()
     }
 
     /// Returns the executed block that determines the state.
     pub fn block(&self) -> ExecutedBlock {
-        self.block.clone()
+        self.executed_block().clone()
     }
 
     /// Returns the hash of executed block that determines the state.
     pub fn hash(&self) -> B256 {
-        self.block.block().hash()
+        self.executed_block().block().hash()
     }
 
     /// Returns the block number of executed block that determines the state.
     pub fn number(&self) -> u64 {
-        self.block.block().number
+        self.executed_block().block().number
     }
 
     /// Returns the state root after applying the executed block that determines
     /// the state.
     pub fn state_root(&self) -> B256 {
-        self.block.block().header.state_root
+        self.executed_block().block().header.state_root
     }
 
     /// Returns the `Receipts` of executed block that determines the state.
     pub fn receipts(&self) -> &Receipts {
-        &self.block.execution_outcome().receipts
+        &self.executed_block().execution_outcome().receipts
     }
 
     /// Returns a vector of `Receipt` of executed block that determines the state.
@@ -625,32 +771,22 @@ impl BlockState {
             .unwrap_or_default()
     }
 
-    /// Returns a vector of parent `BlockStates`.
-    /// The block state order in the output vector is newest to oldest.
-    pub fn parent_state_chain(&self) -> Vec<&Self> {
-        let mut parents = Vec::new();
-        let mut current = self.parent.as_deref();
-
-        while let Some(parent) = current {
-            parents.push(parent);
-            current = parent.parent.as_deref();
-        }
-
-        parents
+    /// Returns the in-memory parent blocks of the executed block that determines the state, from
+    /// the oldest to the newest block.
+    pub fn parent_chain(&self) -> ChainSegment {
+        self.chain.slice(..self.number())
     }
 
-    /// Returns a vector of `BlockStates` representing the entire in memory chain.
-    /// The block state order in the output vector is newest to oldest, including
-    /// self as the first element.
-    pub fn chain(&self) -> Vec<&Self> {
-        let mut chain = vec![self];
-        self.append_parent_chain(&mut chain);
-        chain
+    /// Returns the in-memory chain that leads to the state, from the oldest block up to and
+    /// including the executed block that determines the state.
+    pub const fn chain(&self) -> &ChainSegment {
+        &self.chain
     }
 
-    /// Appends the parent chain of this `BlockState` to the given vector.
-    pub fn append_parent_chain<'a>(&'a self, chain: &mut Vec<&'a Self>) {
-        chain.extend(self.parent_state_chain());
+    /// Returns a state provider that overlays the in-memory chain that leads to the state on the
+    /// given historical state at the [anchor](Self::anchor).
+    pub fn state_provider(&self, historical: StateProviderBox) -> MemoryOverlayStateProvider {
+        MemoryOverlayStateProvider::new(self.chain.iter().rev().cloned().collect(), historical)
     }
 }
 
@@ -712,6 +848,56 @@ impl ExecutedBlock {
     pub fn trie_updates(&self) -> &TrieUpdates {
         &self.trie
     }
+
+    /// Calculates a heuristic for the in-memory size of the [`ExecutedBlock`].
+    pub fn size(&self) -> usize {
+        let receipts = self
+            .execution_output
+            .receipts
+            .receipt_vec
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|receipt| {
+                mem::size_of::<Receipt>() +
+                    receipt
+                        .logs
+                        .iter()
+                        .map(|log| mem::size_of::<Log>() + log.data.data.len())
+                        .sum::<usize>()
+            })
+            .sum::<usize>();
+        let hashed_storage = self
+            .hashed_state
+            .storages
+            .values()
+            .map(|storage| storage.storage.len() * mem::size_of::<(B256, U256)>())
+            .sum::<usize>();
+        let storage_tries = self
+            .trie
+            .storage_tries_ref()
+            .values()
+            .map(|trie| trie.len() * mem::size_of::<(Nibbles, BranchNodeCompact)>())
+            .sum::<usize>();
+
+        self.block.size() +
+            self.senders.len() * mem::size_of::<Address>() +
+            // the bundle state holds the changed accounts and slots with their original values
+            self.execution_output.bundle.size_hint() *
+                map_entry_size(&self.execution_output.bundle.state) +
+            receipts +
+            self.hashed_state.accounts.len() * mem::size_of::<(B256, Option<Account>)>() +
+            self.hashed_state.storages.len() * mem::size_of::<(B256, HashedStorage)>() +
+            hashed_storage +
+            self.trie.account_nodes_ref().len() * mem::size_of::<(Nibbles, BranchNodeCompact)>() +
+            self.trie.removed_nodes_ref().len() * mem::size_of::<Nibbles>() +
+            storage_tries
+    }
+}
+
+/// Returns the size of an entry of the map.
+fn map_entry_size<'a, K: 'a, V: 'a>(_map: impl IntoIterator<Item = (&'a K, &'a V)>) -> usize {
+    mem::size_of::<(K, V)>()
 }
 
 /// Non-empty chain of blocks.
@@ -805,8 +991,9 @@ mod tests {
     };
     use reth_storage_api::{
         AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateRootProvider,
+        StorageRangeProvider,
     };
-    use reth_trie::{prefix_set::TriePrefixSetsMut, AccountProof, HashedStorage};
+    use reth_trie::{prefix_set::TriePrefixSetsMut, AccountProof, HashedStorage, StorageRange};
 
     fn create_mock_state(
         test_block_builder: &mut TestBlockBuilder,
@@ -827,10 +1014,10 @@ mod tests {
         let mut parent_state: Option<BlockState> = None;
 
         for i in 1..=num_blocks {
-            let mut state = create_mock_state(test_block_builder, i, parent_hash);
-            if let Some(parent) = parent_state {
-                state.parent = Some(Box::new(parent));
-            }
+            let state = BlockState::with_parent(
+                test_block_builder.get_executed_block_with_number(i, parent_hash),
+                parent_state,
+            );
             parent_hash = state.hash();
             parent_state = Some(state.clone());
             chain.push(state);
@@ -933,6 +1120,18 @@ mod tests {
         }
     }
 
+    impl StorageRangeProvider for MockStateProvider {
+        fn hashed_storage_range(
+            &self,
+            _hashed_state: HashedPostState,
+            _address: Address,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<StorageRange> {
+            Ok(StorageRange::default())
+        }
+    }
+
     #[test]
     fn test_in_memory_state_impl_state_by_hash() {
         let mut state_by_hash = HashMap::new();
@@ -1001,8 +1200,8 @@ mod tests {
         let result = in_memory_state.pending_state();
         assert!(result.is_some());
         let actual_pending_state = result.unwrap();
-        assert_eq!(actual_pending_state.block.block().hash(), pending_hash);
-        assert_eq!(actual_pending_state.block.block().number, pending_number);
+        assert_eq!(actual_pending_state.hash(), pending_hash);
+        assert_eq!(actual_pending_state.number(), pending_number);
     }
 
     #[test]
@@ -1220,23 +1419,107 @@ mod tests {
         assert_eq!(chain[2].number(), 1);
     }
 
+    #[test]
+    fn test_canonical_in_memory_state_sidechain_blocks() {
+        let state = CanonicalInMemoryState::empty();
+        let mut block_builder = TestBlockBuilder::default();
+        let block1 = block_builder.get_executed_block_with_number(1, B256::random());
+        let block2 = block_builder.get_executed_block_with_number(2, block1.block().hash());
+        state.update_chain(NewCanonicalChain::Commit { new: vec![block1.clone(), block2.clone()] });
+
+        // a fork of two blocks on top of the first block
+        let fork2 = block_builder.get_executed_block_with_number(2, block1.block().hash());
+        let fork3 = block_builder.get_executed_block_with_number(3, fork2.block().hash());
+        state.insert_sidechain_block(fork2.clone());
+        state.insert_sidechain_block(fork3.clone());
+
+        assert!(state.state_by_hash(fork3.block().hash()).is_none());
+        let fork_state = state.non_canonical_state_by_hash(fork3.block().hash()).unwrap();
+        assert_eq!(
+            fork_state.chain().iter().map(|block| block.block().hash()).collect::<Vec<_>>(),
+            vec![block1.block().hash(), fork2.block().hash(), fork3.block().hash()]
+        );
+        assert!(state.non_canonical_state_by_hash(block2.block().hash()).is_none());
+
+        // a replaced pending block is kept
+        let pending1 = block_builder.get_executed_block_with_number(3, block2.block().hash());
+        let pending2 = block_builder.get_executed_block_with_number(3, block2.block().hash());
+        state.set_pending_block(pending1.clone());
+        state.set_pending_block(pending2.clone());
+        assert!(state.non_canonical_state_by_hash(pending1.block().hash()).is_some());
+        assert!(state.non_canonical_state_by_hash(pending2.block().hash()).is_some());
+
+        // the reorged blocks and the pending block become sidechain blocks, the new canonical
+        // blocks don't
+        state.update_chain(NewCanonicalChain::Reorg {
+            new: vec![fork2, fork3.clone()],
+            old: vec![block2.clone()],
+        });
+        assert!(state.non_canonical_state_by_hash(fork3.block().hash()).is_none());
+        assert!(state.non_canonical_state_by_hash(block2.block().hash()).is_some());
+        assert!(state.non_canonical_state_by_hash(pending2.block().hash()).is_some());
+
+        // sidechain blocks are dropped once blocks of their height are persisted
+        let persisted_block = state.subscribe_persisted_block();
+        assert_eq!(*persisted_block.borrow(), BlockNumHash::default());
+        state.remove_persisted_blocks(block2.block().num_hash());
+        assert_eq!(*persisted_block.borrow(), block2.block().num_hash());
+        assert!(state.non_canonical_state_by_hash(block2.block().hash()).is_none());
+        assert!(state.non_canonical_state_by_hash(pending2.block().hash()).is_some());
+    }
+
+    #[test]
+    fn test_canonical_in_memory_state_state_by_transaction_hash() {
+        let mut blocks = HashMap::new();
+        let mut numbers = BTreeMap::new();
+        let mut parent_hash = B256::random();
+        let mut block_builder = TestBlockBuilder::default();
+        // random blocks can be empty, but the blocks need to include transactions
+        let mut executed_block = |number, parent_hash| loop {
+            let block = block_builder.get_executed_block_with_number(number, parent_hash);
+            if !block.block().body.is_empty() {
+                break block
+            }
+        };
+
+        for i in 1..=2 {
+            let block = executed_block(i, parent_hash);
+            let hash = block.block().hash();
+            blocks.insert(hash, Arc::new(BlockState::new(block.clone())));
+            numbers.insert(i, hash);
+            parent_hash = hash;
+        }
+
+        let pending_block = executed_block(3, parent_hash);
+        let pending_tx_hash = pending_block.block().body[0].hash();
+        let pending_state = BlockState::new(pending_block);
+
+        let state = CanonicalInMemoryState::new(blocks, numbers, Some(pending_state), None);
+
+        let first_block = state.state_by_number(1).unwrap();
+        let tx_hash = first_block.block().block().body[0].hash();
+        assert_eq!(state.state_by_transaction_hash(tx_hash).unwrap().number(), 1);
+        assert_eq!(state.state_by_transaction_hash(pending_tx_hash).unwrap().number(), 3);
+        assert!(state.state_by_transaction_hash(B256::random()).is_none());
+    }
+
     #[test]
     fn test_block_state_parent_blocks() {
         let mut test_block_builder = TestBlockBuilder::default();
         let chain = create_mock_state_chain(&mut test_block_builder, 4);
 
-        let parents = chain[3].parent_state_chain();
+        let parents = chain[3].parent_chain();
         assert_eq!(parents.len(), 3);
-        assert_eq!(parents[0].block().block.number, 3);
-        assert_eq!(parents[1].block().block.number, 2);
-        assert_eq!(parents[2].block().block.number, 1);
+        assert_eq!(parents.blocks()[0].block().number, 1);
+        assert_eq!(parents.blocks()[1].block().number, 2);
+        assert_eq!(parents.blocks()[2].block().number, 3);
 
-        let parents = chain[2].parent_state_chain();
+        let parents = chain[2].parent_chain();
         assert_eq!(parents.len(), 2);
-        assert_eq!(parents[0].block().block.number, 2);
-        assert_eq!(parents[1].block().block.number, 1);
+        assert_eq!(parents.blocks()[0].block().number, 1);
+        assert_eq!(parents.blocks()[1].block().number, 2);
 
-        let parents = chain[0].parent_state_chain();
+        let parents = chain[0].parent_chain();
         assert_eq!(parents.len(), 0);
     }
 
@@ -1248,13 +1531,13 @@ mod tests {
             create_mock_state(&mut test_block_builder, single_block_number, B256::random());
         let single_block_hash = single_block.block().block.hash();
 
-        let parents = single_block.parent_state_chain();
+        let parents = single_block.parent_chain();
         assert_eq!(parents.len(), 0);
 
         let block_state_chain = single_block.chain();
         assert_eq!(block_state_chain.len(), 1);
-        assert_eq!(block_state_chain[0].block().block.number, single_block_number);
-        assert_eq!(block_state_chain[0].block().block.hash(), single_block_hash);
+        assert_eq!(block_state_chain.blocks()[0].block().number, single_block_number);
+        assert_eq!(block_state_chain.blocks()[0].block().hash(), single_block_hash);
     }
 
     #[test]
@@ -1264,18 +1547,32 @@ mod tests {
 
         let block_state_chain = chain[2].chain();
         assert_eq!(block_state_chain.len(), 3);
-        assert_eq!(block_state_chain[0].block().block.number, 3);
-        assert_eq!(block_state_chain[1].block().block.number, 2);
-        assert_eq!(block_state_chain[2].block().block.number, 1);
+        assert_eq!(block_state_chain.blocks()[0].block().number, 1);
+        assert_eq!(block_state_chain.blocks()[1].block().number, 2);
+        assert_eq!(block_state_chain.blocks()[2].block().number, 3);
 
         let block_state_chain = chain[1].chain();
         assert_eq!(block_state_chain.len(), 2);
-        assert_eq!(block_state_chain[0].block().block.number, 2);
-        assert_eq!(block_state_chain[1].block().block.number, 1);
+        assert_eq!(block_state_chain.blocks()[0].block().number, 1);
+        assert_eq!(block_state_chain.blocks()[1].block().number, 2);
 
         let block_state_chain = chain[0].chain();
         assert_eq!(block_state_chain.len(), 1);
-        assert_eq!(block_state_chain[0].block().block.number, 1);
+        assert_eq!(block_state_chain.blocks()[0].block().number, 1);
+    }
+
+    #[test]
+    fn test_block_state_anchor_and_parent_tx_count() {
+        let mut test_block_builder = TestBlockBuilder::default();
+        let chain = create_mock_state_chain(&mut test_block_builder, 3);
+        let anchor = chain[0].block().block().parent_num_hash();
+
+        let mut parent_tx_count = 0;
+        for state in &chain {
+            assert_eq!(state.anchor(), anchor);
+            assert_eq!(state.parent_tx_count(), parent_tx_count);
+            parent_tx_count += state.block().block().body.len() as u64;
+        }
     }
 
     #[test]
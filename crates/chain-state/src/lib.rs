@@ -11,9 +11,17 @@
 mod in_memory;
 pub use in_memory::*;
 
+mod retention;
+pub use retention::*;
+
+mod segment;
+pub use segment::ChainSegment;
+
 mod chain_info;
 pub use chain_info::ChainInfoTracker;
 
+mod slot_timing;
+
 mod notifications;
 pub use notifications::{
     BlockStateNotificationStream, CanonStateNotification, CanonStateNotificationSender,
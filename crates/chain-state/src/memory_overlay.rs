@@ -5,11 +5,11 @@ use reth_primitives::{
 };
 use reth_storage_api::{
     AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateProviderBox,
-    StateRootProvider,
+    StateRootProvider, StorageRangeProvider,
 };
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
-    HashedStorage,
+    HashedStorage, StorageRange,
 };
 use std::collections::HashMap;
 
@@ -174,6 +174,20 @@ impl StateProofProvider for MemoryOverlayStateProvider {
     }
 }
 
+impl StorageRangeProvider for MemoryOverlayStateProvider {
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let mut state = self.hashed_post_state.clone();
+        state.extend(hashed_state);
+        self.historical.hashed_storage_range(state, address, start, limit)
+    }
+}
+
 impl StateProvider for MemoryOverlayStateProvider {
     fn storage(
         &self,
@@ -189,6 +203,55 @@ impl StateProvider for MemoryOverlayStateProvider {
         self.historical.storage(address, storage_key)
     }
 
+    fn storage_multi(
+        &self,
+        slots: &[(Address, StorageKey)],
+    ) -> ProviderResult<Vec<Option<StorageValue>>> {
+        let values = slots
+            .iter()
+            .map(|(address, storage_key)| {
+                self.in_memory.iter().find_map(|block| {
+                    block.execution_output.storage(address, (*storage_key).into())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // read the slots that are not in the in-memory blocks in a single batch
+        let missing = slots
+            .iter()
+            .zip(&values)
+            .filter(|(_, value)| value.is_none())
+            .map(|(slot, _)| *slot)
+            .collect::<Vec<_>>();
+        let mut fetched = self.historical.storage_multi(&missing)?.into_iter();
+        Ok(values
+            .into_iter()
+            .map(|value| value.map(Some).unwrap_or_else(|| fetched.next().flatten()))
+            .collect())
+    }
+
+    fn basic_accounts(&self, addresses: &[Address]) -> ProviderResult<Vec<Option<Account>>> {
+        let accounts = addresses
+            .iter()
+            .map(|address| {
+                self.in_memory.iter().find_map(|block| block.execution_output.account(address))
+            })
+            .collect::<Vec<_>>();
+
+        // read the accounts that are not in the in-memory blocks in a single batch
+        let missing = addresses
+            .iter()
+            .zip(&accounts)
+            .filter(|(_, account)| account.is_none())
+            .map(|(address, _)| *address)
+            .collect::<Vec<_>>();
+        let mut fetched = self.historical.basic_accounts(&missing)?.into_iter();
+        Ok(accounts
+            .into_iter()
+            .map(|account| account.unwrap_or_else(|| fetched.next().flatten()))
+            .collect())
+    }
+
     fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
         for block in &self.in_memory {
             if let Some(contract) = block.execution_output.bytecode(&code_hash) {
@@ -3,8 +3,9 @@
 use auto_impl::auto_impl;
 use derive_more::{Deref, DerefMut};
 use reth_execution_types::{BlockReceipts, Chain};
-use reth_primitives::{SealedBlockWithSenders, SealedHeader};
+use reth_primitives::{ReorgRecord, SealedBlockWithSenders, SealedHeader, TxHash};
 use std::{
+    collections::HashSet,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
@@ -136,6 +137,59 @@ impl CanonStateNotification {
         );
         receipts
     }
+
+    /// Returns the hashes of the transactions of the reverted chain segment that are not included
+    /// in the newly imported chain segment.
+    ///
+    /// These transactions may still be included again by a later block.
+    pub fn dropped_transactions(&self) -> HashSet<TxHash> {
+        let Some(old) = self.reverted() else { return HashSet::new() };
+        let new_transactions = self
+            .committed()
+            .blocks_iter()
+            .flat_map(|block| block.body.iter().map(|tx| tx.hash()))
+            .collect::<HashSet<_>>();
+        old.blocks_iter()
+            .flat_map(|block| block.body.iter().map(|tx| tx.hash()))
+            .filter(|hash| !new_transactions.contains(hash))
+            .collect()
+    }
+
+    /// Returns the [`ReorgRecord`] of a reorg, if blocks of the canonical chain were replaced by
+    /// the blocks of another chain.
+    ///
+    /// Returns `None` if the canonical chain was only extended or reverted. `observed_at` is the
+    /// unix timestamp in milliseconds at which the reorg was observed.
+    ///
+    /// Only the transactions included again by the new chain segment are counted as reincluded,
+    /// the transactions that are included by later blocks have to be added by the caller.
+    pub fn reorg_record(&self, observed_at: u64) -> Option<ReorgRecord> {
+        let Self::Reorg { old, new } = self else { return None };
+        if old.is_empty() || new.is_empty() {
+            return None
+        }
+
+        let discarded_transactions =
+            old.blocks_iter().map(|block| block.body.len() as u64).sum::<u64>();
+        let reincluded_transactions =
+            discarded_transactions - self.dropped_transactions().len() as u64;
+
+        // the fork started with the earliest block of either chain above the fork block
+        let forked_at = old.first().timestamp.min(new.first().timestamp);
+        let fork_block = old.fork_block();
+        Some(ReorgRecord {
+            observed_at,
+            fork_block_number: fork_block.number,
+            fork_block_hash: fork_block.hash,
+            old_tip_hash: old.tip().hash(),
+            new_tip_hash: new.tip().hash(),
+            discarded_blocks: old.len() as u64,
+            added_blocks: new.len() as u64,
+            discarded_transactions,
+            reincluded_transactions,
+            resolution_duration: observed_at.saturating_sub(forked_at.saturating_mul(1000)),
+        })
+    }
 }
 
 /// Wrapper around a broadcast receiver that receives fork choice notifications.
@@ -213,3 +267,85 @@ impl<T: Clone + Sync + Send + 'static> Stream for BlockStateNotificationStream<T
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives::{
+        Header, SealedBlock, Signature, Transaction, TransactionSigned, TxLegacy, B256,
+    };
+
+    fn transaction(nonce: u64) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy { nonce, ..Default::default() }),
+            Signature::default(),
+        )
+    }
+
+    fn chain(
+        parent_hash: B256,
+        first_number: u64,
+        first_timestamp: u64,
+        nonces: &[Vec<u64>],
+    ) -> Arc<Chain> {
+        let mut parent_hash = parent_hash;
+        let blocks = nonces.iter().enumerate().map(|(index, nonces)| {
+            let number = first_number + index as u64;
+            let timestamp = first_timestamp + index as u64 * 12;
+            let header = Header { number, parent_hash, timestamp, ..Default::default() }
+                .seal(B256::random());
+            parent_hash = header.hash();
+            let body = nonces.iter().copied().map(transaction).collect::<Vec<_>>();
+            let senders = vec![Default::default(); body.len()];
+            SealedBlockWithSenders {
+                block: SealedBlock { header, body, ..Default::default() },
+                senders,
+            }
+        });
+        Arc::new(Chain::new(blocks.collect::<Vec<_>>(), ExecutionOutcome::default(), None))
+    }
+
+    #[test]
+    fn records_reorg() {
+        let fork_hash = B256::random();
+        let old = chain(fork_hash, 10, 120, &[vec![0, 1], vec![2]]);
+        // the first block of the new chain was built late
+        let new = chain(fork_hash, 10, 126, &[vec![1], vec![], vec![2, 3]]);
+
+        let observed_at = 130_500;
+        let notification = CanonStateNotification::Reorg { old: old.clone(), new: new.clone() };
+        assert_eq!(
+            notification.reorg_record(observed_at),
+            Some(ReorgRecord {
+                observed_at,
+                fork_block_number: 9,
+                fork_block_hash: fork_hash,
+                old_tip_hash: old.tip().hash(),
+                new_tip_hash: new.tip().hash(),
+                discarded_blocks: 2,
+                added_blocks: 3,
+                discarded_transactions: 3,
+                reincluded_transactions: 2,
+                // the first block of the old chain has the timestamp 120s
+                resolution_duration: 10_500,
+            })
+        );
+
+        // a commit or a revert is not a reorg
+        assert_eq!(CanonStateNotification::Commit { new }.reorg_record(observed_at), None);
+        let revert = CanonStateNotification::Reorg { old, new: Arc::new(Chain::default()) };
+        assert_eq!(revert.reorg_record(observed_at), None);
+    }
+
+    #[test]
+    fn dropped_transactions() {
+        let fork_hash = B256::random();
+        let old = chain(fork_hash, 10, 120, &[vec![0, 1], vec![2]]);
+        let new = chain(fork_hash, 10, 120, &[vec![1], vec![2, 3]]);
+
+        let notification = CanonStateNotification::Reorg { old, new: new.clone() };
+        assert_eq!(notification.dropped_transactions(), HashSet::from([transaction(0).hash()]));
+        assert!(CanonStateNotification::Commit { new }.dropped_transactions().is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+//! Retention policy of the canonical blocks kept in memory.
+
+use std::time::Duration;
+
+/// Default maximum number of canonical blocks kept in memory before they are persisted.
+pub const DEFAULT_MAX_IN_MEMORY_BLOCKS: u64 = 3;
+
+/// Default number of the most recent canonical blocks that are kept in memory after persisting.
+pub const DEFAULT_TARGET_IN_MEMORY_BLOCKS: u64 = 2;
+
+/// Policy that decides how many canonical blocks are kept in the [`CanonicalInMemoryState`]
+/// before they are persisted.
+///
+/// Blocks are persisted once any of the limits is exceeded, after which only the
+/// [`target_blocks`](Self::target_blocks) most recent blocks, and none that are older than the max
+/// age, are kept in memory. Memory-constrained nodes can keep fewer blocks, and nodes that serve a
+/// lot of RPC requests can keep more, to serve recent blocks without reading them from disk.
+///
+/// [`CanonicalInMemoryState`]: crate::CanonicalInMemoryState
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InMemoryRetention {
+    /// Maximum number of blocks kept in memory before they are persisted.
+    max_blocks: u64,
+    /// Number of the most recent blocks that are kept in memory after persisting.
+    target_blocks: u64,
+    /// Maximum estimated size in bytes of the blocks kept in memory.
+    max_bytes: Option<usize>,
+    /// Maximum age of the blocks kept in memory, relative to the timestamp of the canonical head.
+    max_age: Option<Duration>,
+}
+
+impl Default for InMemoryRetention {
+    fn default() -> Self {
+        Self {
+            max_blocks: DEFAULT_MAX_IN_MEMORY_BLOCKS,
+            target_blocks: DEFAULT_TARGET_IN_MEMORY_BLOCKS,
+            max_bytes: None,
+            max_age: None,
+        }
+    }
+}
+
+impl InMemoryRetention {
+    /// Returns the maximum number of blocks kept in memory before they are persisted.
+    pub const fn max_blocks(&self) -> u64 {
+        self.max_blocks
+    }
+
+    /// Returns the number of the most recent blocks that are kept in memory after persisting.
+    ///
+    /// This is at most [`Self::max_blocks`].
+    pub fn target_blocks(&self) -> u64 {
+        self.target_blocks.min(self.max_blocks)
+    }
+
+    /// Returns the maximum estimated size in bytes of the blocks kept in memory.
+    pub const fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Returns the maximum age of the blocks kept in memory.
+    pub const fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// Sets the maximum number of blocks kept in memory before they are persisted.
+    pub const fn with_max_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_blocks = max_blocks;
+        self
+    }
+
+    /// Sets the number of the most recent blocks that are kept in memory after persisting.
+    pub const fn with_target_blocks(mut self, target_blocks: u64) -> Self {
+        self.target_blocks = target_blocks;
+        self
+    }
+
+    /// Sets the maximum estimated size in bytes of the blocks kept in memory.
+    pub const fn with_max_bytes(mut self, max_bytes: Option<usize>) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Sets the maximum age of the blocks kept in memory.
+    ///
+    /// The age of a block is the difference between the timestamp of the canonical head and the
+    /// timestamp of the block.
+    pub const fn with_max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_blocks_at_most_max_blocks() {
+        let retention = InMemoryRetention::default();
+        assert_eq!(retention.target_blocks(), DEFAULT_TARGET_IN_MEMORY_BLOCKS);
+
+        let retention = retention.with_max_blocks(1).with_target_blocks(4);
+        assert_eq!(retention.target_blocks(), 1);
+        assert_eq!(retention.with_max_blocks(64).target_blocks(), 4);
+    }
+}
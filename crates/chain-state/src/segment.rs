@@ -0,0 +1,236 @@
+//! Contiguous segments of the in-memory chain.
+
+use crate::ExecutedBlock;
+use reth_primitives::{BlockNumHash, BlockNumber};
+use std::{
+    ops::{Bound, Range, RangeBounds},
+    slice,
+    sync::Arc,
+};
+
+/// A contiguous segment of in-memory blocks, ordered from the oldest to the newest block.
+///
+/// The blocks are shared by all segments that are sliced from the same segment, so cloning and
+/// slicing a segment is cheap. Since the numbers of the blocks are contiguous, blocks are looked up
+/// by number in constant time.
+#[derive(Debug, Clone, Default)]
+pub struct ChainSegment {
+    /// The blocks of the segment this segment was sliced from.
+    blocks: Arc<[ExecutedBlock]>,
+    /// The cumulative number of transactions of `blocks` before each block.
+    tx_offsets: Arc<[u64]>,
+    /// The part of `blocks` that this segment covers.
+    range: Range<usize>,
+}
+
+impl ChainSegment {
+    /// Creates a new segment of the given blocks.
+    ///
+    /// The blocks must be ordered from the oldest to the newest block, with every block being the
+    /// child of the previous block.
+    pub fn new(blocks: Vec<ExecutedBlock>) -> Self {
+        debug_assert!(
+            blocks.windows(2).all(|pair| pair[1].block().parent_hash == pair[0].block().hash()),
+            "blocks of a chain segment must be contiguous"
+        );
+        let tx_offsets = blocks
+            .iter()
+            .scan(0, |offset, block| {
+                let block_offset = *offset;
+                *offset += block.block().body.len() as u64;
+                Some(block_offset)
+            })
+            .collect();
+        Self { range: 0..blocks.len(), blocks: blocks.into(), tx_offsets }
+    }
+
+    /// Returns a new segment with the given blocks appended, the first of which must be the child
+    /// of the newest block of this segment.
+    ///
+    /// This copies the blocks of the segment, so slicing a segment should be preferred where
+    /// possible.
+    pub fn with_blocks(&self, blocks: impl IntoIterator<Item = ExecutedBlock>) -> Self {
+        Self::new(self.blocks().iter().cloned().chain(blocks).collect())
+    }
+
+    /// Returns the blocks of the segment, from the oldest to the newest block.
+    pub fn blocks(&self) -> &[ExecutedBlock] {
+        &self.blocks[self.range.clone()]
+    }
+
+    /// Returns an iterator over the blocks of the segment, from the oldest to the newest block.
+    pub fn iter(&self) -> slice::Iter<'_, ExecutedBlock> {
+        self.blocks().iter()
+    }
+
+    /// Returns the number of blocks in the segment.
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    /// Returns `true` if the segment contains no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Returns the oldest block of the segment.
+    pub fn first(&self) -> Option<&ExecutedBlock> {
+        self.blocks().first()
+    }
+
+    /// Returns the newest block of the segment.
+    pub fn last(&self) -> Option<&ExecutedBlock> {
+        self.blocks().last()
+    }
+
+    /// Returns the number and hash of the parent of the oldest block of the segment.
+    pub fn anchor(&self) -> Option<BlockNumHash> {
+        self.first().map(|block| block.block().parent_num_hash())
+    }
+
+    /// Returns the block with the given number.
+    pub fn block_by_number(&self, number: BlockNumber) -> Option<&ExecutedBlock> {
+        self.index_of(number).map(|index| &self.blocks()[index])
+    }
+
+    /// Returns the sub-segment of the blocks with numbers in the given range.
+    ///
+    /// Numbers of the range that are outside of the segment are ignored.
+    pub fn slice(&self, range: impl RangeBounds<BlockNumber>) -> Self {
+        let Some(first) = self.first().map(|block| block.block().number) else {
+            return self.clone()
+        };
+        let len = self.len() as u64;
+        let index = |number: BlockNumber| number.saturating_sub(first).min(len) as usize;
+        let start = match range.start_bound() {
+            Bound::Included(&number) => index(number),
+            Bound::Excluded(&number) => index(number.saturating_add(1)),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&number) => index(number.saturating_add(1)),
+            Bound::Excluded(&number) => index(number),
+            Bound::Unbounded => self.len(),
+        }
+        .max(start);
+
+        Self {
+            blocks: self.blocks.clone(),
+            tx_offsets: self.tx_offsets.clone(),
+            range: self.range.start + start..self.range.start + end,
+        }
+    }
+
+    /// Returns the number of transactions of the blocks of the segment that precede the block
+    /// with the given number.
+    pub fn tx_offset(&self, number: BlockNumber) -> Option<u64> {
+        let index = self.index_of(number)?;
+        Some(self.tx_offsets[self.range.start + index] - self.tx_offsets[self.range.start])
+    }
+
+    /// Returns the block that contains the transaction with the given index, counted from the
+    /// first transaction of the segment, and the index of the transaction within the block.
+    pub fn block_by_tx_index(&self, tx_index: u64) -> Option<(&ExecutedBlock, usize)> {
+        let offsets = &self.tx_offsets[self.range.clone()];
+        let tx_offset = offsets.first()? + tx_index;
+        // the block is the last one that starts at or before the transaction, empty blocks start
+        // at the same offset as the next block
+        let index = offsets.partition_point(|offset| *offset <= tx_offset).checked_sub(1)?;
+        let block = &self.blocks()[index];
+        let index_in_block = (tx_offset - offsets[index]) as usize;
+        (index_in_block < block.block().body.len()).then_some((block, index_in_block))
+    }
+
+    /// Returns the index of the block with the given number in the segment.
+    fn index_of(&self, number: BlockNumber) -> Option<usize> {
+        let first = self.first()?.block().number;
+        let index = usize::try_from(number.checked_sub(first)?).ok()?;
+        (index < self.len()).then_some(index)
+    }
+}
+
+impl PartialEq for ChainSegment {
+    fn eq(&self, other: &Self) -> bool {
+        self.blocks() == other.blocks()
+    }
+}
+
+impl Eq for ChainSegment {}
+
+impl<'a> IntoIterator for &'a ChainSegment {
+    type Item = &'a ExecutedBlock;
+    type IntoIter = slice::Iter<'a, ExecutedBlock>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestBlockBuilder;
+
+    fn segment(len: u64) -> ChainSegment {
+        let blocks = TestBlockBuilder::default().get_executed_blocks(1..len + 1).collect();
+        ChainSegment::new(blocks)
+    }
+
+    fn numbers(segment: &ChainSegment) -> Vec<BlockNumber> {
+        segment.iter().map(|block| block.block().number).collect()
+    }
+
+    #[test]
+    fn lookup_by_number() {
+        let segment = segment(5);
+        assert_eq!(segment.len(), 5);
+        assert_eq!(segment.anchor().unwrap().number, 0);
+        for number in 1..=5 {
+            assert_eq!(segment.block_by_number(number).unwrap().block().number, number);
+        }
+        assert!(segment.block_by_number(0).is_none());
+        assert!(segment.block_by_number(6).is_none());
+    }
+
+    #[test]
+    fn slicing() {
+        let segment = segment(5);
+        assert_eq!(numbers(&segment.slice(2..=4)), vec![2, 3, 4]);
+        assert_eq!(numbers(&segment.slice(2..4)), vec![2, 3]);
+        assert_eq!(numbers(&segment.slice(..=1)), vec![1]);
+        assert_eq!(numbers(&segment.slice(4..)), vec![4, 5]);
+        assert_eq!(numbers(&segment.slice(0..=100)), vec![1, 2, 3, 4, 5]);
+        assert!(segment.slice(..=0).is_empty());
+        assert!(segment.slice(6..).is_empty());
+        assert!(segment.slice((Bound::Included(4), Bound::Excluded(2))).is_empty());
+
+        // slices of slices are relative to the blocks of the slice
+        let slice = segment.slice(2..=4);
+        assert_eq!(numbers(&slice.slice(3..)), vec![3, 4]);
+        assert_eq!(slice.block_by_number(4).unwrap().block().number, 4);
+        assert!(slice.block_by_number(5).is_none());
+        assert_eq!(slice, ChainSegment::new(slice.blocks().to_vec()));
+    }
+
+    #[test]
+    fn transaction_offsets() {
+        let segment = segment(5);
+        let mut tx_offset = 0;
+        for block in &segment {
+            let number = block.block().number;
+            assert_eq!(segment.tx_offset(number), Some(tx_offset));
+            for index in 0..block.block().body.len() {
+                let (found, found_index) = segment.block_by_tx_index(tx_offset).unwrap();
+                assert_eq!((found.block().number, found_index), (number, index));
+                tx_offset += 1;
+            }
+        }
+        assert!(segment.block_by_tx_index(tx_offset).is_none());
+
+        // offsets of slices are relative to the first block of the slice
+        let slice = segment.slice(3..);
+        let skipped = segment.tx_offset(3).unwrap();
+        assert_eq!(slice.tx_offset(3), Some(0));
+        assert_eq!(slice.tx_offset(5), Some(segment.tx_offset(5).unwrap() - skipped));
+    }
+}
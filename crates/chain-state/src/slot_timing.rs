@@ -0,0 +1,190 @@
+//! Tracks how long after the start of their slot blocks are received, made canonical and
+//! acknowledged by the consensus layer.
+//!
+//! The start of the slot of a block is its timestamp. Delays are aggregated per epoch, where
+//! epochs are approximated as consecutive windows of [`EPOCH_DURATION`] since the unix epoch.
+
+use reth_metrics::{
+    metrics::{Gauge, Histogram},
+    Metrics,
+};
+use reth_primitives::constants::EPOCH_DURATION;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Blocks that reach a phase later than this after the start of their slot are not recorded.
+///
+/// This excludes blocks that are processed while the node is syncing.
+const MAX_RECORDED_DELAY: Duration = EPOCH_DURATION;
+
+/// The phases of a block that are timed relative to the start of its slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SlotPhase {
+    /// The block was received via `engine_newPayload`.
+    NewPayload,
+    /// The block became the canonical head.
+    Canonical,
+    /// The forkchoice update that made the block the head was acknowledged.
+    ForkchoiceAck,
+}
+
+impl SlotPhase {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::NewPayload => "new_payload",
+            Self::Canonical => "canonical",
+            Self::ForkchoiceAck => "forkchoice_ack",
+        }
+    }
+}
+
+/// Tracks the slot timing of all [`SlotPhase`]s.
+#[derive(Debug)]
+pub(crate) struct SlotTimingTracker {
+    new_payload: PhaseTiming,
+    canonical: PhaseTiming,
+    forkchoice_ack: PhaseTiming,
+}
+
+impl SlotTimingTracker {
+    /// Records that the block with the given timestamp reached the phase at `now`.
+    pub(crate) fn record(&mut self, phase: SlotPhase, timestamp: u64, now: SystemTime) {
+        let timing = match phase {
+            SlotPhase::NewPayload => &mut self.new_payload,
+            SlotPhase::Canonical => &mut self.canonical,
+            SlotPhase::ForkchoiceAck => &mut self.forkchoice_ack,
+        };
+        timing.record(timestamp, now);
+    }
+}
+
+impl Default for SlotTimingTracker {
+    fn default() -> Self {
+        Self {
+            new_payload: PhaseTiming::new(SlotPhase::NewPayload),
+            canonical: PhaseTiming::new(SlotPhase::Canonical),
+            forkchoice_ack: PhaseTiming::new(SlotPhase::ForkchoiceAck),
+        }
+    }
+}
+
+/// Slot timing of a single [`SlotPhase`].
+#[derive(Debug)]
+struct PhaseTiming {
+    /// Timestamp of the last recorded block, blocks are only recorded once per phase.
+    last_timestamp: u64,
+    /// Aggregated delays of the current epoch.
+    epoch: EpochTiming,
+    metrics: SlotTimingMetrics,
+}
+
+impl PhaseTiming {
+    fn new(phase: SlotPhase) -> Self {
+        Self {
+            last_timestamp: 0,
+            epoch: EpochTiming::default(),
+            metrics: SlotTimingMetrics::new_with_labels(&[("phase", phase.as_str())]),
+        }
+    }
+
+    fn record(&mut self, timestamp: u64, now: SystemTime) {
+        if timestamp <= self.last_timestamp {
+            return
+        }
+        self.last_timestamp = timestamp;
+
+        let slot_start = UNIX_EPOCH + Duration::from_secs(timestamp);
+        // blocks from the future are treated as received at the start of their slot
+        let delay = now.duration_since(slot_start).unwrap_or_default();
+        if delay > MAX_RECORDED_DELAY {
+            return
+        }
+        self.metrics.delay.record(delay.as_secs_f64());
+
+        let epoch = timestamp / EPOCH_DURATION.as_secs();
+        if epoch != self.epoch.epoch {
+            if self.epoch.blocks > 0 {
+                self.metrics.last_epoch_blocks.set(self.epoch.blocks as f64);
+                self.metrics.last_epoch_mean_delay.set(self.epoch.mean().as_secs_f64());
+                self.metrics.last_epoch_max_delay.set(self.epoch.max.as_secs_f64());
+            }
+            self.epoch = EpochTiming { epoch, ..Default::default() };
+        }
+        self.epoch.record(delay);
+    }
+}
+
+/// Delays of a phase aggregated over an epoch.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct EpochTiming {
+    epoch: u64,
+    blocks: u64,
+    total: Duration,
+    max: Duration,
+}
+
+impl EpochTiming {
+    fn record(&mut self, delay: Duration) {
+        self.blocks += 1;
+        self.total += delay;
+        self.max = self.max.max(delay);
+    }
+
+    fn mean(&self) -> Duration {
+        self.total / self.blocks.max(1) as u32
+    }
+}
+
+/// Slot timing metrics of a [`SlotPhase`].
+#[derive(Metrics)]
+#[metrics(scope = "chain_info.slot_timing")]
+struct SlotTimingMetrics {
+    /// Time from the start of the slot until the block reached the phase, in seconds.
+    delay: Histogram,
+    /// Number of blocks that reached the phase in the last completed epoch.
+    last_epoch_blocks: Gauge,
+    /// Mean delay of the blocks in the last completed epoch, in seconds.
+    last_epoch_mean_delay: Gauge,
+    /// Maximum delay of the blocks in the last completed epoch, in seconds.
+    last_epoch_max_delay: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregates_delays_per_epoch() {
+        let mut timing = PhaseTiming::new(SlotPhase::Canonical);
+        let epoch_start = EPOCH_DURATION.as_secs() * 100;
+        let at = |timestamp: u64, delay: u64| UNIX_EPOCH + Duration::from_secs(timestamp + delay);
+
+        timing.record(epoch_start, at(epoch_start, 1));
+        timing.record(epoch_start + 12, at(epoch_start + 12, 3));
+        // already recorded
+        timing.record(epoch_start + 12, at(epoch_start + 12, 5));
+        // syncing
+        timing.record(epoch_start + 24, at(epoch_start + 24, EPOCH_DURATION.as_secs() + 1));
+        assert_eq!(
+            timing.epoch,
+            EpochTiming {
+                epoch: 100,
+                blocks: 2,
+                total: Duration::from_secs(4),
+                max: Duration::from_secs(3)
+            }
+        );
+        assert_eq!(timing.epoch.mean(), Duration::from_secs(2));
+
+        let next_epoch = epoch_start + EPOCH_DURATION.as_secs();
+        timing.record(next_epoch, at(next_epoch, 2));
+        assert_eq!(
+            timing.epoch,
+            EpochTiming {
+                epoch: 101,
+                blocks: 1,
+                total: Duration::from_secs(2),
+                max: Duration::from_secs(2)
+            }
+        );
+    }
+}
@@ -1,6 +1,13 @@
 use crate::constants::MAINNET_DEPOSIT_CONTRACT;
 #[cfg(not(feature = "std"))]
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 use alloy_chains::{Chain, ChainKind, NamedChain};
 use alloy_genesis::Genesis;
 use alloy_primitives::{address, b256, Address, BlockNumber, B256, U256};
@@ -21,7 +28,7 @@ use reth_primitives_traits::{
 };
 use reth_trie_common::root::state_root_ref_unhashed;
 #[cfg(feature = "std")]
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 pub use alloy_eips::eip1559::BaseFeeParams;
 #[cfg(feature = "optimism")]
@@ -30,6 +37,7 @@ use reth_network_peers::{
     base_nodes, base_testnet_nodes, holesky_nodes, mainnet_nodes, op_nodes, op_testnet_nodes,
     sepolia_nodes,
 };
+pub use reth_primitives_traits::BlobParams;
 
 /// The Ethereum mainnet spec
 pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
@@ -51,6 +59,7 @@ pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             b256!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5"),
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
+        blob_params: ForkBlobParams::ethereum(),
         max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
         prune_delete_limit: 20000,
     };
@@ -75,6 +84,7 @@ pub static SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             b256!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5"),
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
+        blob_params: ForkBlobParams::ethereum(),
         max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
         prune_delete_limit: 10000,
     };
@@ -97,6 +107,7 @@ pub static HOLESKY: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             b256!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5"),
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
+        blob_params: ForkBlobParams::ethereum(),
         max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
         prune_delete_limit: 10000,
     };
@@ -263,6 +274,99 @@ impl From<ForkBaseFeeParams> for BaseFeeParamsKind {
 #[derive(Clone, Debug, PartialEq, Eq, From)]
 pub struct ForkBaseFeeParams(Vec<(Box<dyn Hardfork>, BaseFeeParams)>);
 
+/// A vector of tuples of [Hardfork] and the [`BlobParams`] that apply from its activation, sorted
+/// by [Hardfork] activation order. This is used to schedule changes of the EIP-4844 blob
+/// parameters.
+#[derive(Clone, Debug, PartialEq, Eq, From)]
+pub struct ForkBlobParams(Vec<(Box<dyn Hardfork>, BlobParams)>);
+
+impl ForkBlobParams {
+    /// The blob parameters of Ethereum, which were introduced with [`EthereumHardfork::Cancun`].
+    pub fn ethereum() -> Self {
+        Self(vec![(EthereumHardfork::Cancun.boxed(), BlobParams::cancun())])
+    }
+
+    /// Returns the scheduled blob parameters and the hardforks that activate them.
+    pub fn forks(&self) -> &[(Box<dyn Hardfork>, BlobParams)] {
+        &self.0
+    }
+
+    /// Extracts the blob parameters that the `blobSchedule` field of the genesis config schedules
+    /// for Ethereum hardforks, like:
+    ///
+    /// ```json
+    /// "blobSchedule": {
+    ///     "prague": { "target": 6, "max": 9, "baseFeeUpdateFraction": 5007716 }
+    /// }
+    /// ```
+    ///
+    /// [`EthereumHardfork::Cancun`] uses [`BlobParams::cancun`] unless it is scheduled otherwise.
+    ///
+    /// Returns an error if the schedule is malformed or names a fork that is not an Ethereum
+    /// hardfork.
+    pub fn from_genesis(genesis: &Genesis) -> Result<Self, BlobScheduleError> {
+        let mut schedule = BTreeMap::from([(EthereumHardfork::Cancun, BlobParams::cancun())]);
+        if let Some(scheduled) = genesis.config.extra_fields.get("blobSchedule") {
+            let scheduled =
+                serde_json::from_value::<BTreeMap<String, BlobParams>>(scheduled.clone())
+                    .map_err(|err| BlobScheduleError::Malformed(err.to_string()))?;
+            for (fork, params) in scheduled {
+                let fork = fork
+                    .parse::<EthereumHardfork>()
+                    .map_err(|_| BlobScheduleError::UnknownFork(fork))?;
+                schedule.insert(fork, params);
+            }
+        }
+
+        // hardforks are ordered by activation
+        Ok(Self::from_schedule(schedule))
+    }
+
+    /// Like [`Self::from_genesis`], but entries of the `blobSchedule` field that can't be parsed
+    /// or that name a fork that is not an Ethereum hardfork are ignored, and a malformed schedule
+    /// is ignored entirely.
+    pub fn from_genesis_lossy(genesis: &Genesis) -> Self {
+        let mut schedule = BTreeMap::from([(EthereumHardfork::Cancun, BlobParams::cancun())]);
+        if let Some(serde_json::Value::Object(scheduled)) =
+            genesis.config.extra_fields.get("blobSchedule")
+        {
+            for (fork, params) in scheduled {
+                if let (Ok(fork), Ok(params)) = (
+                    fork.parse::<EthereumHardfork>(),
+                    serde_json::from_value::<BlobParams>(params.clone()),
+                ) {
+                    schedule.insert(fork, params);
+                }
+            }
+        }
+        Self::from_schedule(schedule)
+    }
+
+    fn from_schedule(schedule: BTreeMap<EthereumHardfork, BlobParams>) -> Self {
+        Self(schedule.into_iter().map(|(fork, params)| (fork.boxed(), params)).collect())
+    }
+}
+
+/// Error of parsing the `blobSchedule` field of a genesis config.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum BlobScheduleError {
+    /// The schedule isn't a map of fork names to blob parameters.
+    #[display(fmt = "malformed blob schedule: {_0}")]
+    Malformed(String),
+    /// The schedule contains a fork that isn't an Ethereum hardfork.
+    #[display(fmt = "unknown fork in blob schedule: {_0}")]
+    UnknownFork(String),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlobScheduleError {}
+
+impl Default for ForkBlobParams {
+    fn default() -> Self {
+        Self::ethereum()
+    }
+}
+
 impl core::ops::Deref for ChainSpec {
     type Target = ChainHardforks;
 
@@ -305,6 +409,9 @@ pub struct ChainSpec {
     /// The parameters that configure how a block's base fee is computed
     pub base_fee_params: BaseFeeParamsKind,
 
+    /// The EIP-4844 blob parameters of the forks that change them
+    pub blob_params: ForkBlobParams,
+
     /// The maximum gas limit
     pub max_gas_limit: u64,
 
@@ -322,6 +429,7 @@ impl Default for ChainSpec {
             hardforks: Default::default(),
             deposit_contract: Default::default(),
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
+            blob_params: Default::default(),
             max_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             prune_delete_limit: MAINNET.prune_delete_limit,
         }
@@ -483,6 +591,22 @@ impl ChainSpec {
         }
     }
 
+    /// Get the [`BlobParams`] for the chain at the given timestamp.
+    ///
+    /// Before the first fork of the blob parameters, the parameters of that fork are returned.
+    pub fn blob_params_at_timestamp(&self, timestamp: u64) -> BlobParams {
+        let ForkBlobParams(ref blob_params) = self.blob_params;
+        // Walk through the blob params configuration in reverse order, and return the first one
+        // that corresponds to a hardfork that is active at the given timestamp.
+        for (fork, params) in blob_params.iter().rev() {
+            if self.hardforks.is_fork_active_at_timestamp(fork.clone(), timestamp) {
+                return *params
+            }
+        }
+
+        blob_params.first().map(|(_, params)| *params).unwrap_or_else(BlobParams::cancun)
+    }
+
     /// Get the hash of the genesis block.
     pub fn genesis_hash(&self) -> B256 {
         self.genesis_hash.unwrap_or_else(|| self.genesis_header().hash_slow())
@@ -699,7 +823,29 @@ impl ChainSpec {
 }
 
 impl From<Genesis> for ChainSpec {
+    /// Creates the chain spec of the genesis.
+    ///
+    /// Invalid entries of the `blobSchedule` of the genesis config are ignored, so the default
+    /// blob parameters apply to their forks, see [`ForkBlobParams::from_genesis_lossy`]. Use
+    /// [`ChainSpec::try_from_genesis`] to reject them instead.
     fn from(genesis: Genesis) -> Self {
+        let blob_params = ForkBlobParams::from_genesis_lossy(&genesis);
+        Self::from_genesis_with_blob_params(genesis, blob_params)
+    }
+}
+
+impl ChainSpec {
+    /// Creates the chain spec of the genesis.
+    ///
+    /// Returns an error if the `blobSchedule` of the genesis config is invalid, see
+    /// [`ForkBlobParams::from_genesis`].
+    pub fn try_from_genesis(genesis: Genesis) -> Result<Self, BlobScheduleError> {
+        let blob_params = ForkBlobParams::from_genesis(&genesis)?;
+        Ok(Self::from_genesis_with_blob_params(genesis, blob_params))
+    }
+
+    /// Creates the chain spec of the genesis with the given blob parameters.
+    fn from_genesis_with_blob_params(genesis: Genesis, blob_params: ForkBlobParams) -> Self {
         #[cfg(feature = "optimism")]
         let optimism_genesis_info = OptimismGenesisInfo::extract_from(&genesis);
         #[cfg(feature = "optimism")]
@@ -805,6 +951,7 @@ impl From<Genesis> for ChainSpec {
             deposit_contract,
             #[cfg(feature = "optimism")]
             base_fee_params: optimism_genesis_info.base_fee_params,
+            blob_params,
             ..Default::default()
         }
     }
@@ -2447,6 +2594,84 @@ Post-merge hard forks (timestamp based):
         assert_eq!(genesis.config.prague_time, Some(4662));
     }
 
+    #[test]
+    fn parse_genesis_blob_schedule() {
+        let genesis: Genesis = serde_json::from_str(
+            r#"{
+                "config": {
+                    "chainId": 1337,
+                    "shanghaiTime": 0,
+                    "cancunTime": 10,
+                    "pragueTime": 20,
+                    "blobSchedule": {
+                        "prague": { "target": 6, "max": 9, "baseFeeUpdateFraction": 5007716 }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let chainspec = ChainSpec::try_from_genesis(genesis).unwrap();
+
+        let prague = BlobParams::new(6, 9, 5007716);
+        assert_eq!(
+            chainspec.blob_params,
+            ForkBlobParams::from(vec![
+                (EthereumHardfork::Cancun.boxed(), BlobParams::cancun()),
+                (EthereumHardfork::Prague.boxed(), prague),
+            ])
+        );
+        assert_eq!(chainspec.blob_params_at_timestamp(0), BlobParams::cancun());
+        assert_eq!(chainspec.blob_params_at_timestamp(19), BlobParams::cancun());
+        assert_eq!(chainspec.blob_params_at_timestamp(20), prague);
+
+        // without a schedule, the cancun parameters apply to all forks
+        let chainspec = ChainSpecBuilder::mainnet().prague_activated().build();
+        assert_eq!(chainspec.blob_params_at_timestamp(0), BlobParams::cancun());
+    }
+
+    #[test]
+    fn reject_invalid_genesis_blob_schedule() {
+        let genesis = |schedule: &str| -> Genesis {
+            serde_json::from_str(&format!(
+                r#"{{ "config": {{ "chainId": 1337, "blobSchedule": {schedule} }} }}"#
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(
+            ChainSpec::try_from_genesis(genesis(
+                r#"{ "unknown": { "target": 1, "max": 1, "baseFeeUpdateFraction": 1 } }"#
+            )),
+            Err(BlobScheduleError::UnknownFork("unknown".to_string()))
+        );
+        assert!(matches!(
+            ChainSpec::try_from_genesis(genesis(r#"{ "prague": { "target": 6 } }"#)),
+            Err(BlobScheduleError::Malformed(_))
+        ));
+        assert!(matches!(
+            ChainSpec::try_from_genesis(genesis("[]")),
+            Err(BlobScheduleError::Malformed(_))
+        ));
+
+        // converting the genesis ignores the invalid entries
+        let prague = BlobParams::new(6, 9, 5007716);
+        let chainspec = ChainSpec::from(genesis(
+            r#"{
+                "unknown": { "target": 1, "max": 1, "baseFeeUpdateFraction": 1 },
+                "cancun": { "target": 6 },
+                "prague": { "target": 6, "max": 9, "baseFeeUpdateFraction": 5007716 }
+            }"#,
+        ));
+        assert_eq!(
+            chainspec.blob_params,
+            ForkBlobParams::from(vec![
+                (EthereumHardfork::Cancun.boxed(), BlobParams::cancun()),
+                (EthereumHardfork::Prague.boxed(), prague),
+            ])
+        );
+        assert_eq!(ChainSpec::from(genesis("[]")).blob_params, ForkBlobParams::ethereum());
+    }
+
     #[test]
     fn test_parse_cancun_genesis_all_formats() {
         let s = r#"{"config":{"ethash":{},"chainId":1337,"homesteadBlock":0,"eip150Block":0,"eip155Block":0,"eip158Block":0,"byzantiumBlock":0,"constantinopleBlock":0,"petersburgBlock":0,"istanbulBlock":0,"berlinBlock":0,"londonBlock":0,"terminalTotalDifficulty":0,"terminalTotalDifficultyPassed":true,"shanghaiTime":0,"cancunTime":4661},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{"658bdf435d810c91414ec09147daa6db62406379":{"balance":"0x487a9a304539440000"},"aa00000000000000000000000000000000000000":{"code":"0x6042","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x1","nonce":"0x1"},"bb00000000000000000000000000000000000000":{"code":"0x600154600354","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x2","nonce":"0x1"}},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x3b9aca00"}"#;
@@ -14,11 +14,13 @@ use reth_node_core::{
         DatabaseArgs, DatadirArgs,
     },
     dirs::{ChainPath, DataDirPath},
+    utils::cold_static_files,
 };
 use reth_primitives::B256;
 use reth_provider::{providers::StaticFileProvider, ProviderFactory, StaticFileProviderFactory};
 use reth_stages::{sets::DefaultStages, Pipeline, PipelineTarget};
 use reth_static_file::StaticFileProducer;
+use reth_tasks::TaskManager;
 use std::{path::PathBuf, sync::Arc};
 use tokio::sync::watch;
 use tracing::{debug, info, warn};
@@ -76,18 +78,32 @@ impl EnvironmentArgs {
             config.stages.etl.dir = Some(EtlConfig::from_datadir(data_dir.data_dir()));
         }
 
+        config.database.validate()?;
+        let db_args = self.db.database_args_with_config(&config.database);
+
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
         let (db, sfp) = match access {
-            AccessRights::RW => (
-                Arc::new(init_db(db_path, self.db.database_args())?),
-                StaticFileProvider::read_write(sf_path)?,
-            ),
+            AccessRights::RW => {
+                (Arc::new(init_db(db_path, db_args)?), StaticFileProvider::read_write(sf_path)?)
+            }
             AccessRights::RO => (
-                Arc::new(open_db_read_only(&db_path, self.db.database_args())?),
+                Arc::new(open_db_read_only(&db_path, db_args)?),
                 StaticFileProvider::read_only(sf_path)?,
             ),
         };
 
+        let sfp = match &config.cold_storage {
+            Some(cold_storage) => {
+                // The task manager is spawned onto the runtime, so the requests to the object
+                // store can be spawned for as long as the command runs.
+                let task_manager = TaskManager::current();
+                let task_executor = task_manager.executor();
+                tokio::spawn(task_manager);
+                sfp.with_cold_storage(cold_static_files(cold_storage, &data_dir, task_executor)?)?
+            }
+            None => sfp,
+        };
+
         let provider_factory = self.create_provider_factory(&config, db, sfp)?;
         if access.is_read_write() {
             debug!(target: "reth::cli", chain=%self.chain.chain, genesis=?self.chain.genesis_hash(), "Initializing genesis");
@@ -0,0 +1,126 @@
+use crate::common::AccessRights;
+use clap::Parser;
+use reth_db_api::database::Database;
+use reth_db_common::receipts_bloom::{
+    check_receipts_bloom, repair_header_bloom, verifiable_receipts_range, BloomMismatch,
+    BloomMismatchKind,
+};
+use reth_primitives::BlockNumber;
+use reth_provider::{
+    HeaderProvider, ProviderFactory, PruneCheckpointReader, ReceiptProvider, StageCheckpointReader,
+};
+use tracing::{info, warn};
+
+/// Number of blocks after which the progress of the check is logged.
+const PROGRESS_INTERVAL: u64 = 100_000;
+
+/// Number of header blooms that are repaired per database transaction.
+const REPAIR_BATCH_SIZE: usize = 1_000;
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db check-receipts-bloom` command
+pub struct Command {
+    /// The first block to verify.
+    ///
+    /// Defaults to the first block whose receipts were not pruned.
+    #[arg(long)]
+    from: Option<BlockNumber>,
+
+    /// The last block to verify.
+    ///
+    /// Defaults to the block the execution stage is synced to.
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// Repair corrupted header blooms by recomputing them from the logs of the receipts.
+    ///
+    /// A header is only repaired if the header with the recomputed bloom hashes to the canonical
+    /// block hash. Corrupted receipts can't be repaired and require re-executing the blocks.
+    #[arg(long)]
+    repair: bool,
+}
+
+impl Command {
+    /// Returns the database access rights that the command requires.
+    pub(crate) const fn access_rights(&self) -> AccessRights {
+        if self.repair {
+            AccessRights::RW
+        } else {
+            AccessRights::RO
+        }
+    }
+
+    /// Execute `db check-receipts-bloom` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        warn!("This command should be run without the node running!");
+
+        let mismatches = {
+            let provider = provider_factory.provider()?.disable_long_read_transaction_safety();
+            self.check(&provider)?
+        };
+
+        if !self.repair {
+            eyre::ensure!(
+                mismatches.is_empty(),
+                "Receipts bloom check failed for {} blocks, corrupted header blooms can be repaired with --repair",
+                mismatches.len()
+            );
+            return Ok(())
+        }
+
+        let mut repaired = 0;
+        for batch in mismatches.chunks(REPAIR_BATCH_SIZE) {
+            let provider = provider_factory.provider_rw()?;
+            for mismatch in batch {
+                if repair_header_bloom(&provider, mismatch)? {
+                    info!(block = mismatch.block, "Repaired header logs bloom");
+                    repaired += 1;
+                }
+            }
+            provider.commit()?;
+        }
+
+        eyre::ensure!(
+            repaired == mismatches.len(),
+            "Repaired {repaired} header blooms, {} blocks have corrupted receipts or corrupted headers and need to be re-synced",
+            mismatches.len() - repaired
+        );
+        Ok(())
+    }
+
+    /// Verifies the receipts blooms of the requested blocks and returns the mismatches.
+    fn check<P>(&self, provider: &P) -> eyre::Result<Vec<BloomMismatch>>
+    where
+        P: HeaderProvider + ReceiptProvider + StageCheckpointReader + PruneCheckpointReader,
+    {
+        let Some(range) = verifiable_receipts_range(provider)? else {
+            info!("No blocks with receipts to verify");
+            return Ok(Vec::new())
+        };
+        let from = self.from.unwrap_or(*range.start()).max(*range.start());
+        let to = self.to.unwrap_or(*range.end()).min(*range.end());
+        info!(from, to, "Verifying receipts blooms");
+
+        let mut mismatches = Vec::new();
+        for block in from..=to {
+            if let Some(Err(mismatch)) = check_receipts_bloom(provider, block)? {
+                warn!(block, kind = ?mismatch.kind, header_bloom = %mismatch.header_bloom, receipts_bloom = %mismatch.receipts_bloom, "Header logs bloom doesn't match the stored receipts");
+                mismatches.push(mismatch);
+            }
+            if (block - from + 1) % PROGRESS_INTERVAL == 0 {
+                info!(block, to, mismatches = mismatches.len(), "Verifying receipts blooms");
+            }
+        }
+
+        let count = |kind| mismatches.iter().filter(|mismatch| mismatch.kind == kind).count();
+        info!(
+            from,
+            to,
+            receipts = count(BloomMismatchKind::Receipts),
+            header_blooms = count(BloomMismatchKind::HeaderBloom),
+            unknown = count(BloomMismatchKind::Unknown),
+            "Finished verifying receipts blooms"
+        );
+        Ok(mismatches)
+    }
+}
@@ -0,0 +1,297 @@
+use clap::Parser;
+use reth_db::{tables, DatabaseEnv};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    table::Table,
+    transaction::DbTx,
+};
+use reth_db_common::DbTool;
+use reth_primitives::{keccak256, BlockNumber};
+use reth_provider::{
+    DatabaseProvider, HeaderProvider, ProviderError, PruneCheckpointReader, StageCheckpointReader,
+};
+use reth_prune::PruneSegment;
+use reth_stages::StageId;
+use reth_trie::{HashedPostState, StateRoot};
+use reth_trie_db::{DatabaseHashedPostState, DatabaseStateRoot};
+use std::{sync::Arc, time::Instant};
+use tracing::{info, warn};
+
+/// Number of entries after which the progress of a check is logged.
+const PROGRESS_INTERVAL: usize = 1_000_000;
+
+/// Maximum number of inconsistent entries that are logged per check.
+const MAX_REPORTED_INCONSISTENCIES: usize = 10;
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db check-trie` command
+pub struct Command {
+    /// The block to verify the state root for.
+    ///
+    /// Defaults to the block the trie tables are synced to. For earlier blocks, the state is
+    /// reverted using the changesets before the root is calculated.
+    #[arg(long)]
+    block: Option<BlockNumber>,
+}
+
+/// The result of comparing a hashed state table against its plain state counterpart.
+#[derive(Debug, Default)]
+struct HashedTableReport {
+    /// Number of plain state entries that were checked.
+    checked: usize,
+    /// Number of plain state entries without a hashed entry.
+    missing: usize,
+    /// Number of plain state entries whose hashed entry has a different value.
+    mismatched: usize,
+    /// Number of entries in the hashed table.
+    hashed_entries: usize,
+}
+
+impl HashedTableReport {
+    /// Returns the number of hashed entries that have no plain state counterpart.
+    const fn extra(&self) -> usize {
+        self.hashed_entries.saturating_sub(self.checked - self.missing)
+    }
+
+    /// Returns `true` if the hashed table matches the plain state table.
+    const fn is_consistent(&self) -> bool {
+        self.missing == 0 && self.mismatched == 0 && self.extra() == 0
+    }
+
+    /// Records an inconsistent entry and logs it if the reporting limit is not reached yet.
+    fn record(&mut self, table: &str, missing: bool, entry: impl std::fmt::Debug) {
+        if self.missing + self.mismatched < MAX_REPORTED_INCONSISTENCIES {
+            let kind = if missing { "Missing" } else { "Mismatched" };
+            warn!(table, ?entry, "{kind} hashed entry");
+        }
+        if missing {
+            self.missing += 1;
+        } else {
+            self.mismatched += 1;
+        }
+    }
+
+    fn log(&self, table: &str) {
+        info!(
+            table,
+            checked = self.checked,
+            missing = self.missing,
+            mismatched = self.mismatched,
+            extra = self.extra(),
+            "Finished checking hashed table"
+        );
+    }
+}
+
+impl Command {
+    /// Execute `db check-trie` command
+    pub fn execute(self, tool: &DbTool<Arc<DatabaseEnv>>) -> eyre::Result<()> {
+        warn!("This command should be run without the node running!");
+
+        let provider = tool.provider_factory.provider()?;
+        let checkpoint = |id: StageId| -> eyre::Result<BlockNumber> {
+            Ok(provider.get_stage_checkpoint(id)?.unwrap_or_default().block_number)
+        };
+        let execution = checkpoint(StageId::Execution)?;
+        let account_hashing = checkpoint(StageId::AccountHashing)?;
+        let storage_hashing = checkpoint(StageId::StorageHashing)?;
+        let merkle = checkpoint(StageId::MerkleExecute)?;
+        drop(provider);
+
+        if account_hashing != execution || storage_hashing != execution {
+            warn!(
+                execution,
+                account_hashing,
+                storage_hashing,
+                "Hashing stages are not synced to the execution stage, the hashed tables are expected to differ from the plain state"
+            );
+        }
+
+        let block = self.block.unwrap_or(merkle);
+        eyre::ensure!(
+            block <= merkle,
+            "Block {block} is ahead of the trie tables, which are synced to block {merkle}"
+        );
+
+        let provider = || -> eyre::Result<_> {
+            Ok(tool.provider_factory.provider()?.disable_long_read_transaction_safety())
+        };
+        let (accounts, storages, state_root) = std::thread::scope(|scope| {
+            let accounts = scope.spawn(|| check_hashed_accounts(&provider()?));
+            let storages = scope.spawn(|| check_hashed_storages(&provider()?));
+            let state_root = scope.spawn(|| check_state_root(&provider()?, block, merkle));
+            (
+                accounts.join().expect("account check panicked"),
+                storages.join().expect("storage check panicked"),
+                state_root.join().expect("state root check panicked"),
+            )
+        });
+        let (accounts, storages, state_root) = (accounts?, storages?, state_root?);
+
+        accounts.log(tables::HashedAccounts::NAME);
+        storages.log(tables::HashedStorages::NAME);
+
+        let mut failed = Vec::new();
+        if !accounts.is_consistent() {
+            failed.push("hashed accounts do not match the plain account state");
+        }
+        if !storages.is_consistent() {
+            failed.push("hashed storages do not match the plain storage state");
+        }
+        if !state_root {
+            failed.push("state root does not match the header");
+        }
+        if !failed.is_empty() {
+            eyre::bail!("Trie consistency check failed: {}", failed.join(", "))
+        }
+
+        info!(block, "Hashed state and trie tables are consistent");
+        Ok(())
+    }
+}
+
+/// Verifies that every entry of [`tables::PlainAccountState`] has a matching entry in
+/// [`tables::HashedAccounts`].
+fn check_hashed_accounts<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+) -> eyre::Result<HashedTableReport> {
+    let tx = provider.tx_ref();
+    let total = tx.entries::<tables::PlainAccountState>()?;
+    let mut report = HashedTableReport {
+        hashed_entries: tx.entries::<tables::HashedAccounts>()?,
+        ..Default::default()
+    };
+
+    info!(total, "Checking hashed accounts");
+    let start = Instant::now();
+    let mut hashed_cursor = tx.cursor_read::<tables::HashedAccounts>()?;
+    for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (address, account) = entry?;
+        match hashed_cursor.seek_exact(keccak256(address))? {
+            None => report.record(tables::HashedAccounts::NAME, true, address),
+            Some((_, hashed)) if hashed != account => {
+                report.record(tables::HashedAccounts::NAME, false, address)
+            }
+            Some(_) => {}
+        }
+
+        report.checked += 1;
+        if report.checked % PROGRESS_INTERVAL == 0 {
+            info!(
+                progress = %format!("{:.2}%", report.checked as f64 / total as f64 * 100.0),
+                checked = report.checked,
+                elapsed = ?start.elapsed(),
+                "Checking hashed accounts"
+            );
+        }
+    }
+
+    Ok(report)
+}
+
+/// Verifies that every entry of [`tables::PlainStorageState`] has a matching entry in
+/// [`tables::HashedStorages`].
+fn check_hashed_storages<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+) -> eyre::Result<HashedTableReport> {
+    let tx = provider.tx_ref();
+    let total = tx.entries::<tables::PlainStorageState>()?;
+    let mut report = HashedTableReport {
+        hashed_entries: tx.entries::<tables::HashedStorages>()?,
+        ..Default::default()
+    };
+
+    info!(total, "Checking hashed storages");
+    let start = Instant::now();
+    let mut hashed_cursor = tx.cursor_dup_read::<tables::HashedStorages>()?;
+    for entry in tx.cursor_dup_read::<tables::PlainStorageState>()?.walk(None)? {
+        let (address, storage) = entry?;
+        let hashed_slot = keccak256(storage.key);
+        match hashed_cursor
+            .seek_by_key_subkey(keccak256(address), hashed_slot)?
+            .filter(|hashed| hashed.key == hashed_slot)
+        {
+            None => report.record(tables::HashedStorages::NAME, true, (address, storage.key)),
+            Some(hashed) if hashed.value != storage.value => {
+                report.record(tables::HashedStorages::NAME, false, (address, storage.key))
+            }
+            Some(_) => {}
+        }
+
+        report.checked += 1;
+        if report.checked % PROGRESS_INTERVAL == 0 {
+            info!(
+                progress = %format!("{:.2}%", report.checked as f64 / total as f64 * 100.0),
+                checked = report.checked,
+                elapsed = ?start.elapsed(),
+                "Checking hashed storages"
+            );
+        }
+    }
+
+    Ok(report)
+}
+
+/// Calculates the state root at the given block from the stored intermediate trie nodes and
+/// compares it with the state root of the block header.
+///
+/// The trie tables are synced to the `trie_tip` block, so the changes of later blocks are
+/// reverted before calculating the root.
+fn check_state_root<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    block: BlockNumber,
+    trie_tip: BlockNumber,
+) -> eyre::Result<bool> {
+    let tx = provider.tx_ref();
+    let header =
+        provider.sealed_header(block)?.ok_or(ProviderError::HeaderNotFound(block.into()))?;
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        let pruned = provider.get_prune_checkpoint(segment)?.and_then(|c| c.block_number);
+        eyre::ensure!(
+            block == trie_tip || pruned.map_or(true, |pruned| block >= pruned),
+            "Changesets required to revert the state to block {block} have been pruned"
+        );
+    }
+
+    info!(block, trie_tip, "Calculating state root");
+    let start = Instant::now();
+    let state_root = if block == trie_tip {
+        StateRoot::from_tx(tx).root()?
+    } else {
+        StateRoot::overlay_root(tx, HashedPostState::from_reverts(tx, block + 1)?)?
+    };
+
+    if state_root == header.state_root {
+        info!(block, ?state_root, elapsed = ?start.elapsed(), "State root matches the header");
+        Ok(true)
+    } else {
+        warn!(
+            block,
+            expected = ?header.state_root,
+            got = ?state_root,
+            elapsed = ?start.elapsed(),
+            "State root mismatch"
+        );
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashed_table_report_consistency() {
+        let report = HashedTableReport { checked: 10, hashed_entries: 10, ..Default::default() };
+        assert!(report.is_consistent());
+
+        let report = HashedTableReport { checked: 10, hashed_entries: 11, ..Default::default() };
+        assert_eq!(report.extra(), 1);
+        assert!(!report.is_consistent());
+
+        let report =
+            HashedTableReport { checked: 10, missing: 1, hashed_entries: 9, ..Default::default() };
+        assert_eq!(report.extra(), 0);
+        assert!(!report.is_consistent());
+    }
+}
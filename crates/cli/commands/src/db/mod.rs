@@ -4,14 +4,18 @@ use reth_db::version::{get_db_version, DatabaseVersionError, DB_VERSION};
 use reth_db_common::DbTool;
 use std::io::{self, Write};
 
+mod check_receipts_bloom;
+mod check_trie;
 mod checksum;
 mod clear;
 mod diff;
 mod get;
 mod list;
+mod state_stats;
 mod stats;
 /// DB List TUI
 mod tui;
+mod utilization;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -28,10 +32,19 @@ pub struct Command {
 pub enum Subcommands {
     /// Lists all the tables, their entry count and their size
     Stats(stats::Command),
+    /// Reports the size of the state and its growth per day
+    StateStats(state_stats::Command),
+    /// Reports the page utilization and fragmentation of the tables
+    Utilization(utilization::Command),
     /// Lists the contents of a table
     List(list::Command),
     /// Calculates the content checksum of a table
     Checksum(checksum::Command),
+    /// Verifies the hashed state and trie tables against the plain state and the header state
+    /// root
+    CheckTrie(check_trie::Command),
+    /// Verifies the logs blooms of the headers against the logs of the stored receipts
+    CheckReceiptsBloom(check_receipts_bloom::Command),
     /// Create a diff between two database tables or two entire databases.
     Diff(diff::Command),
     /// Gets the content of a table for the given key
@@ -84,6 +97,16 @@ impl Command {
                     command.execute(data_dir, &tool)?;
                 });
             }
+            Subcommands::StateStats(command) => {
+                db_ro_exec!(self.env, tool, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::Utilization(command) => {
+                db_ro_exec!(self.env, tool, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::List(command) => {
                 db_ro_exec!(self.env, tool, {
                     command.execute(&tool)?;
@@ -94,6 +117,16 @@ impl Command {
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::CheckTrie(command) => {
+                db_ro_exec!(self.env, tool, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::CheckReceiptsBloom(command) => {
+                let Environment { provider_factory, .. } =
+                    self.env.init(command.access_rights())?;
+                command.execute(provider_factory)?;
+            }
             Subcommands::Diff(command) => {
                 db_ro_exec!(self.env, tool, {
                     command.execute(&tool)?;
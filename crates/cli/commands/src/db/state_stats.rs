@@ -0,0 +1,204 @@
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use eyre::WrapErr;
+use human_bytes::human_bytes;
+use reth_db::{tables, DatabaseEnv};
+use reth_db_api::{
+    cursor::DbCursorRO, database::Database, models::BlockNumberAddress, table::Table,
+    transaction::DbTx,
+};
+use reth_db_common::DbTool;
+use reth_primitives::BlockNumber;
+use reth_provider::{BlockNumReader, HeaderProvider};
+use std::{collections::BTreeMap, sync::Arc, time::Instant};
+use tracing::info;
+
+/// Number of seconds in a day.
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db state-stats` command
+pub struct Command {
+    /// The number of days to report state growth for, counting back from the latest block.
+    ///
+    /// Growth is derived from the account and storage changesets, so the range is limited to
+    /// blocks whose changesets have not been pruned.
+    #[arg(long, default_value_t = 7)]
+    days: u64,
+}
+
+/// State growth within a single day.
+#[derive(Debug, Default)]
+struct DailyGrowth {
+    /// First block of the day.
+    first_block: BlockNumber,
+    /// Last block of the day.
+    last_block: BlockNumber,
+    /// Number of accounts that did not exist before they were changed.
+    accounts_created: u64,
+    /// Number of storage slots that were zero before they were changed.
+    storage_slots_created: u64,
+}
+
+impl Command {
+    /// Execute `db state-stats` command
+    pub fn execute(self, tool: &DbTool<Arc<DatabaseEnv>>) -> eyre::Result<()> {
+        let size_table = self.state_size_table(tool)?;
+        println!("{size_table}");
+
+        println!("\n");
+
+        let growth_table = self.state_growth_table(tool)?;
+        println!("{growth_table}");
+
+        Ok(())
+    }
+
+    fn state_size_table(&self, tool: &DbTool<Arc<DatabaseEnv>>) -> eyre::Result<ComfyTable> {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["State", "Value"]);
+
+        tool.provider_factory.db_ref().view(|tx| {
+            let entries = |name: &str| -> eyre::Result<usize> {
+                let table_db = tx.inner.open_db(Some(name)).wrap_err("Could not open db.")?;
+                let stats = tx
+                    .inner
+                    .db_stat(&table_db)
+                    .wrap_err(format!("Could not find table: {name}"))?;
+                Ok(stats.entries())
+            };
+
+            let accounts = entries(tables::PlainAccountState::NAME)?;
+            let storage_slots = entries(tables::PlainStorageState::NAME)?;
+            let contracts = entries(tables::Bytecodes::NAME)?;
+
+            info!("Calculating the total size of contract bytecode, this might take a while");
+            let start = Instant::now();
+            let mut bytecode_bytes = 0;
+            for entry in tx.cursor_read::<tables::Bytecodes>()?.walk(None)? {
+                let (_, bytecode) = entry?;
+                bytecode_bytes += bytecode.len();
+            }
+            info!(elapsed = ?start.elapsed(), "Calculated the total size of contract bytecode");
+
+            let mut add_row = |name: &str, value: String| {
+                let mut row = Row::new();
+                row.add_cell(Cell::new(name)).add_cell(Cell::new(value));
+                table.add_row(row);
+            };
+            add_row("Accounts", accounts.to_string());
+            add_row("Storage slots", storage_slots.to_string());
+            add_row("Contracts", contracts.to_string());
+            add_row("Bytecode size", human_bytes(bytecode_bytes as f64));
+
+            Ok::<(), eyre::Report>(())
+        })??;
+
+        Ok(table)
+    }
+
+    fn state_growth_table(&self, tool: &DbTool<Arc<DatabaseEnv>>) -> eyre::Result<ComfyTable> {
+        let provider = tool.provider_factory.provider()?;
+        let tip = provider.last_block_number()?;
+        let tip_timestamp = provider
+            .header_by_number(tip)?
+            .ok_or_else(|| eyre::eyre!("Header for block {tip} not found"))?
+            .timestamp;
+        let min_timestamp = tip_timestamp.saturating_sub(self.days * SECONDS_PER_DAY);
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Day (UTC)", "Blocks", "New Accounts", "New Storage Slots"]);
+
+        // Collect the day of each block in the requested range by walking back from the tip.
+        let mut block_days = Vec::new();
+        let mut block = tip;
+        while let Some(header) = provider.header_by_number(block)? {
+            if header.timestamp < min_timestamp {
+                break
+            }
+            block_days.push((block, header.timestamp / SECONDS_PER_DAY));
+            if block == 0 {
+                break
+            }
+            block -= 1;
+        }
+        block_days.reverse();
+
+        let Some(&(start, _)) = block_days.first() else { return Ok(table) };
+        let mut days = BTreeMap::<u64, DailyGrowth>::new();
+        for &(block, day) in &block_days {
+            days.entry(day)
+                .or_insert_with(|| DailyGrowth { first_block: block, ..Default::default() })
+                .last_block = block;
+        }
+        // The collected blocks are contiguous, so the day of a block can be looked up by index.
+        let day_of_block =
+            |block: BlockNumber| block_days.get((block - start) as usize).map(|(_, day)| *day);
+
+        info!(start, end = tip, "Walking changesets");
+        let tx = provider.tx_ref();
+
+        for entry in tx.cursor_read::<tables::AccountChangeSets>()?.walk_range(start..=tip)? {
+            let (block, account_before) = entry?;
+            if account_before.info.is_none() {
+                if let Some(day) = day_of_block(block).and_then(|day| days.get_mut(&day)) {
+                    day.accounts_created += 1;
+                }
+            }
+        }
+
+        for entry in tx
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress::range(start..=tip))?
+        {
+            let (block_address, storage_before) = entry?;
+            if storage_before.value.is_zero() {
+                if let Some(day) =
+                    day_of_block(block_address.block_number()).and_then(|day| days.get_mut(&day))
+                {
+                    day.storage_slots_created += 1;
+                }
+            }
+        }
+
+        for (day, growth) in days.iter().rev() {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(format_day(*day)))
+                .add_cell(Cell::new(format!("{}..={}", growth.first_block, growth.last_block)))
+                .add_cell(Cell::new(growth.accounts_created))
+                .add_cell(Cell::new(growth.storage_slots_created));
+            table.add_row(row);
+        }
+
+        Ok(table)
+    }
+}
+
+/// Formats the number of days since the unix epoch as a `YYYY-MM-DD` date.
+fn format_day(days: u64) -> String {
+    // See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_days() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(19_723), "2024-01-01");
+        assert_eq!(format_day(19_782), "2024-02-29");
+    }
+}
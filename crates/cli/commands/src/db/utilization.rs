@@ -0,0 +1,103 @@
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_db::{mdbx::utilization::TableUtilization, DatabaseEnv, Tables};
+use reth_db_common::DbTool;
+use std::{sync::Arc, time::Instant};
+use tracing::info;
+
+/// Share of the database size held by free pages above which compaction is recommended.
+const FREELIST_COMPACTION_THRESHOLD: f64 = 0.1;
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db utilization` command
+pub struct Command {
+    /// The tables to report, defaults to all tables.
+    #[arg(long, value_delimiter = ',')]
+    tables: Vec<Tables>,
+}
+
+impl Command {
+    /// Execute `db utilization` command
+    pub fn execute(self, tool: &DbTool<Arc<DatabaseEnv>>) -> eyre::Result<()> {
+        let db = tool.provider_factory.db_ref();
+        let tables = if self.tables.is_empty() { Tables::ALL.to_vec() } else { self.tables };
+
+        let mut utilizations = Vec::with_capacity(tables.len());
+        for table in tables {
+            info!(table = table.name(), "Walking table");
+            let start = Instant::now();
+            let utilization = db.table_utilization(table)?;
+            info!(table = table.name(), elapsed = ?start.elapsed(), "Walked table");
+            utilizations.push((table, utilization));
+        }
+
+        println!("{}", utilization_table(&utilizations));
+
+        let page_size = db.stat()?.page_size() as usize;
+        let freelist_size = db.freelist()? * page_size;
+        let total_size = utilizations.iter().map(|(_, u)| u.size()).sum::<usize>() + freelist_size;
+        println!("\nFreelist: {}", human_bytes(freelist_size as f64));
+
+        let compact = utilizations
+            .iter()
+            .filter(|(_, utilization)| utilization.should_compact())
+            .map(|(table, _)| table.name())
+            .collect::<Vec<_>>();
+        let freelist_share = freelist_size as f64 / total_size.max(1) as f64;
+        if compact.is_empty() && freelist_share < FREELIST_COMPACTION_THRESHOLD {
+            println!("No compaction recommended.");
+        } else {
+            if !compact.is_empty() {
+                println!("Tables with sparse leaf pages: {}", compact.join(", "));
+            }
+            if freelist_share >= FREELIST_COMPACTION_THRESHOLD {
+                println!(
+                    "{:.0}% of the database consists of free pages, which are reused for new \
+                     data but never returned to the file system.",
+                    freelist_share * 100.0
+                );
+            }
+            let reclaimable =
+                utilizations.iter().map(|(_, u)| u.reclaimable_bytes()).sum::<usize>() +
+                    freelist_size;
+            println!(
+                "Compacting the database with `mdbx_copy -c` while the node is stopped would \
+                 reclaim up to {}.",
+                human_bytes(reclaimable as f64)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn utilization_table(utilizations: &[(Tables, TableUtilization)]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Table Name",
+        "Total Size",
+        "Leaf Pages",
+        "Overflow Pages",
+        "Leaf Fill",
+        "Overflow Fill",
+        "Reclaimable",
+        "Compact",
+    ]);
+
+    for (name, utilization) in utilizations {
+        let mut row = Row::new();
+        row.add_cell(Cell::new(name.name()))
+            .add_cell(Cell::new(human_bytes(utilization.size() as f64)))
+            .add_cell(Cell::new(utilization.leaf_pages))
+            .add_cell(Cell::new(utilization.overflow_pages))
+            .add_cell(Cell::new(format!("{:.1}%", utilization.leaf_fill() * 100.0)))
+            .add_cell(Cell::new(format!("{:.1}%", utilization.overflow_fill() * 100.0)))
+            .add_cell(Cell::new(human_bytes(utilization.reclaimable_bytes() as f64)))
+            .add_cell(Cell::new(if utilization.should_compact() { "yes" } else { "" }));
+        table.add_row(row);
+    }
+
+    table
+}
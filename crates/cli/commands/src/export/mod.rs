@@ -0,0 +1,28 @@
+//! `reth export` command.
+
+use clap::{Parser, Subcommand};
+
+pub mod parquet;
+
+/// `reth export` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth export` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Export chain data to partitioned Parquet datasets.
+    Parquet(parquet::Command),
+}
+
+impl Command {
+    /// Execute `export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Parquet(command) => command.execute().await,
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! `reth export parquet` command.
+//!
+//! Exports blocks, transactions, receipts and logs into one Parquet dataset per table. Each
+//! dataset is partitioned into files that cover `--partition-size` blocks, aligned to multiples
+//! of the partition size, so that repeated exports of new ranges extend existing datasets:
+//!
+//! ```text
+//! <output>/blocks/blocks_000000000_000099999.parquet
+//! <output>/transactions/transactions_000000000_000099999.parquet
+//! ```
+//!
+//! The schema of every table is fixed. Hashes and addresses are stored as fixed size binaries,
+//! amounts in wei, which may exceed 64 bits, are stored as decimal strings.
+
+use crate::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use eyre::{eyre, OptionExt};
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, ZstdLevel},
+    file::properties::WriterProperties,
+};
+use reth_chain_arrow::{ChainBatch, ChainTable};
+use reth_primitives::BlockNumber;
+use reth_provider::{BlockNumReader, BlockReader, InternalTransfersProvider};
+use std::{
+    fs::File,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Default number of blocks per partition file.
+const DEFAULT_PARTITION_SIZE: u64 = 100_000;
+
+/// Number of blocks that are read from the database and written as one record batch.
+const BATCH_BLOCKS: u64 = 1_000;
+
+/// `reth export parquet` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The tables to export.
+    ///
+    /// The `traces` table is not exported by default, as it requires internal transfers to be
+    /// indexed.
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = [
+            ChainTable::Blocks,
+            ChainTable::Transactions,
+            ChainTable::Receipts,
+            ChainTable::Logs,
+        ]
+    )]
+    tables: Vec<ChainTable>,
+
+    /// The range of blocks to export, either `from..to` (exclusive) or `from..=to` (inclusive).
+    ///
+    /// Defaults to all blocks up to the best block. The end of the range is capped at the best
+    /// block.
+    #[arg(long, value_parser = parse_block_range)]
+    range: Option<RangeInclusive<BlockNumber>>,
+
+    /// The directory to write the datasets to.
+    #[arg(long, value_name = "DIR")]
+    output: PathBuf,
+
+    /// The number of blocks per partition file.
+    #[arg(long, default_value_t = DEFAULT_PARTITION_SIZE, value_parser = clap::value_parser!(u64).range(1..))]
+    partition_size: u64,
+}
+
+impl Command {
+    /// Execute `export parquet` command
+    pub async fn execute(mut self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+
+        self.tables.sort_unstable();
+        self.tables.dedup();
+
+        let best_block = provider_factory.best_block_number()?;
+        let range = self.range.clone().unwrap_or(0..=best_block);
+        let range = *range.start()..=(*range.end()).min(best_block);
+        if range.is_empty() {
+            return Err(eyre!("no blocks to export in range {range:?}, best block is {best_block}"))
+        }
+
+        info!(target: "reth::cli", ?range, tables = ?self.tables, output = ?self.output, "Exporting chain data to Parquet");
+
+        let mut partition_start = *range.start();
+        while partition_start <= *range.end() {
+            let partition_end = (partition_start - partition_start % self.partition_size)
+                .saturating_add(self.partition_size - 1)
+                .min(*range.end());
+            self.export_partition(&provider_factory, partition_start..=partition_end)?;
+            partition_start = partition_end + 1;
+        }
+
+        info!(target: "reth::cli", ?range, "Exported chain data to Parquet");
+
+        Ok(())
+    }
+
+    /// Exports the blocks of a single partition into one file per table.
+    fn export_partition<P: BlockReader + InternalTransfersProvider>(
+        &self,
+        provider: &P,
+        range: RangeInclusive<BlockNumber>,
+    ) -> eyre::Result<()> {
+        let mut writers = self
+            .tables
+            .iter()
+            .map(|table| PartitionWriter::new(&self.output, *table, &range))
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        let mut batch_start = *range.start();
+        while batch_start <= *range.end() {
+            let batch_end = (batch_start + BATCH_BLOCKS - 1).min(*range.end());
+            let batch = ChainBatch::read(provider, batch_start..=batch_end, &self.tables)?;
+            for writer in &mut writers {
+                writer.write(&batch)?;
+            }
+            batch_start = batch_end + 1;
+        }
+
+        for writer in writers {
+            writer.finish()?;
+        }
+
+        info!(target: "reth::cli", ?range, "Exported partition");
+
+        Ok(())
+    }
+}
+
+/// Parses a block range, either `from..to` (exclusive) or `from..=to` (inclusive).
+fn parse_block_range(value: &str) -> eyre::Result<RangeInclusive<BlockNumber>> {
+    let (start, end, inclusive) = if let Some((start, end)) = value.split_once("..=") {
+        (start, end, true)
+    } else if let Some((start, end)) = value.split_once("..") {
+        (start, end, false)
+    } else {
+        return Err(eyre!("invalid block range `{value}`, expected `from..to` or `from..=to`"))
+    };
+
+    let start = start.parse::<BlockNumber>()?;
+    let end = end.parse::<BlockNumber>()?;
+    let end = if inclusive {
+        end
+    } else {
+        end.checked_sub(1).ok_or_eyre("block range must not be empty")?
+    };
+    if start > end {
+        return Err(eyre!("block range must not be empty"))
+    }
+
+    Ok(start..=end)
+}
+
+/// Writes a table of a partition to a temporary file, which is moved into place once the
+/// partition is complete.
+struct PartitionWriter {
+    table: ChainTable,
+    writer: ArrowWriter<File>,
+    tmp_path: PathBuf,
+    path: PathBuf,
+    rows: usize,
+}
+
+impl PartitionWriter {
+    fn new(
+        output: &Path,
+        table: ChainTable,
+        range: &RangeInclusive<BlockNumber>,
+    ) -> eyre::Result<Self> {
+        let dir = output.join(table.name());
+        reth_fs_util::create_dir_all(&dir)?;
+
+        let path =
+            dir.join(format!("{}_{:09}_{:09}.parquet", table.name(), range.start(), range.end()));
+        let tmp_path = path.with_extension("parquet.tmp");
+
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+        let writer = ArrowWriter::try_new(
+            reth_fs_util::create_file(&tmp_path)?,
+            table.schema(),
+            Some(properties),
+        )?;
+
+        Ok(Self { table, writer, tmp_path, path, rows: 0 })
+    }
+
+    fn write(&mut self, batch: &ChainBatch) -> eyre::Result<()> {
+        let batch = self.table.record_batch(batch)?;
+        self.rows += batch.num_rows();
+        self.writer.write(&batch)?;
+        Ok(())
+    }
+
+    fn finish(self) -> eyre::Result<()> {
+        self.writer.close()?;
+        reth_fs_util::rename(&self.tmp_path, &self.path)?;
+        info!(target: "reth::cli", path = ?self.path, rows = self.rows, "Wrote partition file");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_block_ranges() {
+        assert_eq!(parse_block_range("10..20").unwrap(), 10..=19);
+        assert_eq!(parse_block_range("10..=20").unwrap(), 10..=20);
+        assert_eq!(parse_block_range("0..=0").unwrap(), 0..=0);
+        assert!(parse_block_range("10..10").is_err());
+        assert!(parse_block_range("20..=10").is_err());
+        assert!(parse_block_range("10").is_err());
+        assert!(parse_block_range("a..b").is_err());
+    }
+}
@@ -0,0 +1,434 @@
+//! Command for managing the P2P secret key and the JWT secret of the node.
+//!
+//! Both secrets can be encrypted at rest with the passphrase of [`SecretsArgs`]. Note that the
+//! consensus client needs the plaintext JWT secret.
+//!
+//! The identity of the node is derived from the secp256k1 P2P secret key:
+//! - the peer id is the 64 byte uncompressed public key without the `0x04` prefix, it's the public
+//!   part of `enode://<peer id>@<ip>:<port>` URLs.
+//! - the discovery node id is the keccak256 hash of the peer id, it determines the distance to
+//!   other nodes in the discovery tables.
+
+use clap::{ArgGroup, Parser, Subcommand};
+use reth_chainspec::ChainSpec;
+use reth_cli_util::{
+    get_secret_key_with_passphrase,
+    load_secret_key::{rng_secret_key, write_secret_key},
+    secrets::{
+        is_encrypted_file, read_secret_file, remove_secret_file, write_secret_file,
+        SecretsPassphrase,
+    },
+};
+use reth_fs_util::FsPathError;
+use reth_network_peers::{pk2id, PeerId};
+use reth_node_core::{
+    args::{
+        utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
+        DatadirArgs, SecretsArgs,
+    },
+    utils::{create_jwt_secret, read_jwt_secret},
+};
+use reth_primitives::{keccak256, B256};
+use secp256k1::{SecretKey, SECP256K1};
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{info, warn};
+use zeroize::Zeroizing;
+
+/// `reth keys` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = chain_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// Secret key to use for this node.
+    ///
+    /// Defaults to `<DIR>/discovery-secret`.
+    #[arg(long, value_name = "PATH")]
+    p2p_secret_key: Option<PathBuf>,
+
+    /// Path to a JWT secret to use for the authenticated engine-API RPC server.
+    ///
+    /// Defaults to `<DIR>/jwt.hex`.
+    #[arg(long = "authrpc.jwtsecret", value_name = "PATH")]
+    auth_jwtsecret: Option<PathBuf>,
+
+    #[command(flatten)]
+    secrets: SecretsArgs,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth keys` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Prints the node identity derived from the P2P secret key and whether the secrets are
+    /// encrypted
+    Show,
+    /// Replaces the secrets with newly generated ones, the previous secrets are kept as backups
+    #[command(group(ArgGroup::new("rotated").required(true).multiple(true)))]
+    Rotate {
+        /// Rotate the P2P secret key, this changes the identity of the node.
+        #[arg(long, group = "rotated")]
+        p2p: bool,
+        /// Rotate the JWT secret, the consensus client has to be provisioned with the new secret.
+        #[arg(long, group = "rotated")]
+        jwt: bool,
+    },
+    /// Encrypts the plaintext secret files and their backups with the passphrase
+    Encrypt,
+}
+
+impl Command {
+    /// Execute `keys` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain);
+        let p2p_path = self.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret());
+        let jwt_path = self.auth_jwtsecret.clone().unwrap_or_else(|| data_dir.jwt());
+        let passphrase = self.secrets.passphrase()?;
+
+        match self.command {
+            Subcommands::Show => {
+                if p2p_path.exists() {
+                    let secret_key =
+                        get_secret_key_with_passphrase(&p2p_path, passphrase.as_ref())?;
+                    let identity = NodeIdentity::new(&secret_key);
+                    println!("P2P secret key: {p2p_path:?}");
+                    println!("  encrypted: {}", is_encrypted_file(&p2p_path)?);
+                    println!("  peer id:   {}", identity.peer_id);
+                    println!("  node id:   {}", identity.node_id);
+                } else {
+                    println!("P2P secret key: {p2p_path:?} (not created yet)");
+                }
+
+                if jwt_path.exists() {
+                    read_jwt_secret(&jwt_path, passphrase.as_ref())?;
+                    println!("JWT secret: {jwt_path:?}");
+                    println!("  encrypted: {}", is_encrypted_file(&jwt_path)?);
+                } else {
+                    println!("JWT secret: {jwt_path:?} (not created yet)");
+                }
+            }
+            Subcommands::Rotate { p2p, jwt } => {
+                if p2p {
+                    let (secret_key, backup) = rotate_secret_key(&p2p_path, passphrase.as_ref())?;
+                    let identity = NodeIdentity::new(&secret_key);
+                    info!(target: "reth::cli", path = ?p2p_path, ?backup, peer_id = %identity.peer_id, "Rotated P2P secret key");
+                }
+                if jwt {
+                    let backup = rotate_jwt_secret(&jwt_path, passphrase.as_ref())?;
+                    info!(target: "reth::cli", path = ?jwt_path, ?backup, "Rotated JWT secret");
+                    warn!(target: "reth::cli", "The consensus client must be provisioned with the new JWT secret");
+                }
+            }
+            Subcommands::Encrypt => {
+                let passphrase = passphrase.ok_or_else(|| {
+                    eyre::eyre!("A passphrase is required, set --secrets.passphrase-file")
+                })?;
+                for path in [&p2p_path, &jwt_path] {
+                    if !path.exists() {
+                        warn!(target: "reth::cli", ?path, "Secret file does not exist");
+                    } else if is_encrypted_file(path)? {
+                        info!(target: "reth::cli", ?path, "Secret file is already encrypted");
+                    } else {
+                        let secret = Zeroizing::new(read_secret_file(path, None)?);
+                        write_secret_file(path, &secret, Some(&passphrase))?;
+                        info!(target: "reth::cli", ?path, "Encrypted secret file");
+                    }
+                    let backups = encrypt_backups(path, &passphrase)?;
+                    if !backups.is_empty() {
+                        info!(target: "reth::cli", ?path, ?backups, "Encrypted plaintext backups");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The identity of a node, derived from its P2P secret key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeIdentity {
+    /// The uncompressed public key without the `0x04` prefix.
+    pub peer_id: PeerId,
+    /// The keccak256 hash of the peer id, used by discovery.
+    pub node_id: B256,
+}
+
+impl NodeIdentity {
+    /// Derives the identity of the node from the P2P secret key.
+    pub fn new(secret_key: &SecretKey) -> Self {
+        let peer_id = pk2id(&secret_key.public_key(SECP256K1));
+        Self { peer_id, node_id: keccak256(peer_id) }
+    }
+}
+
+/// Replaces the P2P secret key at the path with a new random key.
+///
+/// Returns the new key and the path of the backup of the previous key, if any.
+pub fn rotate_secret_key(
+    path: &Path,
+    passphrase: Option<&SecretsPassphrase>,
+) -> eyre::Result<(SecretKey, Option<PathBuf>)> {
+    if path.exists() {
+        // fail early if the current key can't be read
+        get_secret_key_with_passphrase(path, passphrase)?;
+    }
+    let backup = backup_secret_file(path, passphrase)?;
+    let secret_key = rng_secret_key();
+    write_secret_key(path, &secret_key, passphrase)?;
+    if let Some(passphrase) = passphrase {
+        encrypt_backups(path, passphrase)?;
+    }
+    Ok((secret_key, backup))
+}
+
+/// Replaces the JWT secret at the path with a new random secret.
+///
+/// Returns the path of the backup of the previous secret, if any.
+pub fn rotate_jwt_secret(
+    path: &Path,
+    passphrase: Option<&SecretsPassphrase>,
+) -> eyre::Result<Option<PathBuf>> {
+    if path.exists() {
+        // fail early if the current secret can't be read
+        read_jwt_secret(path, passphrase)?;
+    }
+    let backup = backup_secret_file(path, passphrase)?;
+    create_jwt_secret(path, passphrase)?;
+    if let Some(passphrase) = passphrase {
+        encrypt_backups(path, passphrase)?;
+    }
+    Ok(backup)
+}
+
+/// Links the secret file to `<path>.<unix timestamp>.bak`, if it exists, so that the secret file
+/// can be replaced atomically.
+///
+/// If a backup of the same second exists, a counter is appended to the timestamp, e.g.
+/// `<path>.<unix timestamp>-1.bak`, existing backups are never overwritten.
+///
+/// An encrypted secret is never replaced with a plaintext secret, so this fails if the file is
+/// encrypted and no passphrase is provided.
+fn backup_secret_file(
+    path: &Path,
+    passphrase: Option<&SecretsPassphrase>,
+) -> eyre::Result<Option<PathBuf>> {
+    if !path.exists() {
+        return Ok(None)
+    }
+    eyre::ensure!(
+        passphrase.is_some() || !is_encrypted_file(path)?,
+        "{path:?} is encrypted, the new secret can only be encrypted with the passphrase"
+    );
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let mut counter = 0u64;
+    loop {
+        let mut backup = path.as_os_str().to_owned();
+        if counter == 0 {
+            backup.push(format!(".{timestamp}.bak"));
+        } else {
+            backup.push(format!(".{timestamp}-{counter}.bak"));
+        }
+        let backup = PathBuf::from(backup);
+        // linking fails if the backup exists, so checking the name and creating the backup is a
+        // single step
+        match std::fs::hard_link(path, &backup) {
+            Ok(()) => return Ok(Some(backup)),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => counter += 1,
+            Err(err) => return Err(FsPathError::create_file(err, backup).into()),
+        }
+    }
+}
+
+/// Returns `true` if the file name is the name of a backup of the secret file `name`, i.e.
+/// `<name>.<unix timestamp>.bak` or `<name>.<unix timestamp>-<counter>.bak`.
+fn is_backup_name(file_name: &str, name: &str) -> bool {
+    let Some(suffix) = file_name
+        .strip_prefix(name)
+        .and_then(|n| n.strip_prefix('.'))
+        .and_then(|n| n.strip_suffix(".bak"))
+    else {
+        return false
+    };
+    let is_number = |n: &str| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit());
+    match suffix.split_once('-') {
+        Some((timestamp, counter)) => is_number(timestamp) && is_number(counter),
+        None => is_number(suffix),
+    }
+}
+
+/// Encrypts the plaintext backups `<path>.<unix timestamp>.bak` of the secret file with the
+/// passphrase.
+///
+/// Once the secret is encrypted, a plaintext backup would still leak it, so the plaintext backups
+/// are overwritten and replaced with encrypted ones. Returns the paths of the encrypted backups.
+fn encrypt_backups(path: &Path, passphrase: &SecretsPassphrase) -> eyre::Result<Vec<PathBuf>> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return Ok(Vec::new())
+    };
+    let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+
+    let mut encrypted = Vec::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        let backup = entry?.path();
+        let is_backup =
+            backup.file_name().and_then(|n| n.to_str()).is_some_and(|n| is_backup_name(n, name));
+        if !is_backup || is_encrypted_file(&backup)? {
+            continue
+        }
+
+        // write the encrypted backup next to the plaintext one, so the backup isn't lost if this
+        // is interrupted
+        let secret = Zeroizing::new(read_secret_file(&backup, None)?);
+        let mut tmp = backup.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        write_secret_file(&tmp, &secret, Some(passphrase))?;
+        remove_secret_file(&backup)?;
+        reth_fs_util::rename(&tmp, &backup)?;
+        encrypted.push(backup);
+    }
+    Ok(encrypted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_cli_util::{get_secret_key, secrets::SecretFileError};
+
+    #[test]
+    fn rotate_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let p2p_path = dir.path().join("discovery-secret");
+        let jwt_path = dir.path().join("jwt.hex");
+
+        let initial = get_secret_key(&p2p_path).unwrap();
+        let (rotated, backup) = rotate_secret_key(&p2p_path, None).unwrap();
+        assert_ne!(NodeIdentity::new(&initial), NodeIdentity::new(&rotated));
+        assert_eq!(get_secret_key(&backup.unwrap()).unwrap(), initial);
+        assert_eq!(get_secret_key(&p2p_path).unwrap(), rotated);
+
+        let passphrase = SecretsPassphrase::new("passphrase").with_work_factor(2);
+        let (encrypted, _) = rotate_secret_key(&p2p_path, Some(&passphrase)).unwrap();
+        assert!(is_encrypted_file(&p2p_path).unwrap());
+        assert_eq!(
+            get_secret_key_with_passphrase(&p2p_path, Some(&passphrase)).unwrap(),
+            encrypted
+        );
+
+        // the plaintext backup was encrypted when migrating to the encrypted key
+        for backup in backups(&p2p_path) {
+            assert!(is_encrypted_file(&backup).unwrap());
+        }
+
+        // the encrypted key can't be rotated without the passphrase
+        assert!(rotate_secret_key(&p2p_path, None).is_err());
+        assert!(matches!(
+            read_secret_file(&p2p_path, None),
+            Err(SecretFileError::MissingPassphrase(_))
+        ));
+
+        assert_eq!(rotate_jwt_secret(&jwt_path, None).unwrap(), None);
+        let initial = read_jwt_secret(&jwt_path, None).unwrap();
+        let backup = rotate_jwt_secret(&jwt_path, Some(&passphrase)).unwrap().unwrap();
+        assert!(is_encrypted_file(&jwt_path).unwrap());
+        assert!(read_jwt_secret(&jwt_path, Some(&passphrase)).is_ok());
+        assert!(is_encrypted_file(&backup).unwrap());
+        assert_eq!(read_jwt_secret(&backup, Some(&passphrase)).unwrap(), initial);
+
+        // the encrypted secret can't be rotated without the passphrase
+        assert!(rotate_jwt_secret(&jwt_path, None).is_err());
+        assert!(matches!(
+            read_secret_file(&jwt_path, None),
+            Err(SecretFileError::MissingPassphrase(_))
+        ));
+    }
+
+    #[test]
+    fn encrypt_plaintext_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let jwt_path = dir.path().join("jwt.hex");
+        let first = dir.path().join("jwt.hex.1.bak");
+        let second = dir.path().join("jwt.hex.2.bak");
+        let other = dir.path().join("jwt.hex.other.bak");
+        let secrets = ["first", "second"];
+        write_secret_file(&first, secrets[0], None).unwrap();
+        write_secret_file(&second, secrets[1], None).unwrap();
+        write_secret_file(&other, "other", None).unwrap();
+
+        let passphrase = SecretsPassphrase::new("passphrase").with_work_factor(2);
+        let mut encrypted = encrypt_backups(&jwt_path, &passphrase).unwrap();
+        encrypted.sort();
+        assert_eq!(encrypted, vec![first.clone(), second.clone()]);
+        for (backup, secret) in [&first, &second].into_iter().zip(secrets) {
+            assert!(is_encrypted_file(backup).unwrap());
+            assert_eq!(read_secret_file(backup, Some(&passphrase)).unwrap(), secret);
+        }
+
+        // only the backups of the secret file are encrypted, and no temporary files are left
+        assert!(!is_encrypted_file(&other).unwrap());
+        assert_eq!(reth_fs_util::read_dir(dir.path()).unwrap().count(), 3);
+        assert!(encrypt_backups(&jwt_path, &passphrase).unwrap().is_empty());
+    }
+
+    #[test]
+    fn backups_of_the_same_second() {
+        let dir = tempfile::tempdir().unwrap();
+        let jwt_path = dir.path().join("jwt.hex");
+        write_secret_file(&jwt_path, "secret", None).unwrap();
+
+        let first = rotate_jwt_secret(&jwt_path, None).unwrap().unwrap();
+        let rotated = read_jwt_secret(&jwt_path, None).unwrap();
+        let second = rotate_jwt_secret(&jwt_path, None).unwrap().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(read_secret_file(&first, None).unwrap(), "secret");
+        assert_eq!(read_jwt_secret(&second, None).unwrap(), rotated);
+
+        // both backups are encrypted
+        let passphrase = SecretsPassphrase::new("passphrase").with_work_factor(2);
+        assert_eq!(encrypt_backups(&jwt_path, &passphrase).unwrap().len(), 2);
+        assert_eq!(read_secret_file(&first, Some(&passphrase)).unwrap(), "secret");
+    }
+
+    #[test]
+    fn backup_names() {
+        assert!(is_backup_name("jwt.hex.1700000000.bak", "jwt.hex"));
+        assert!(is_backup_name("jwt.hex.1700000000-2.bak", "jwt.hex"));
+        assert!(!is_backup_name("jwt.hex.other.bak", "jwt.hex"));
+        assert!(!is_backup_name("jwt.hex.1700000000-.bak", "jwt.hex"));
+        assert!(!is_backup_name("jwt.hex.-2.bak", "jwt.hex"));
+        assert!(!is_backup_name("jwt.hex.1700000000.bak.tmp", "jwt.hex"));
+    }
+
+    fn backups(path: &Path) -> Vec<PathBuf> {
+        let name = path.file_name().unwrap().to_str().unwrap().to_string();
+        reth_fs_util::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|p| {
+                let file_name = p.file_name().unwrap().to_str().unwrap();
+                file_name.starts_with(&name) && file_name.ends_with(".bak")
+            })
+            .collect()
+    }
+}
@@ -12,13 +12,18 @@ pub mod common;
 pub mod config_cmd;
 pub mod db;
 pub mod dump_genesis;
+pub mod export;
 pub mod import;
 pub mod init_cmd;
 pub mod init_state;
+pub mod keys;
 pub mod node;
 pub mod p2p;
 pub mod prune;
 pub mod recover;
+pub mod rollback;
+#[cfg(feature = "sql")]
+pub mod sql;
 pub mod stage;
 #[cfg(feature = "dev")]
 pub mod test_vectors;
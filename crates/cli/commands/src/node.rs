@@ -1,22 +1,31 @@
 //! Main node command for launching a node
 
-use clap::{value_parser, Args, Parser};
+use clap::{
+    parser::ValueSource, value_parser, ArgMatches, Args, CommandFactory, FromArgMatches, Parser,
+};
+use eyre::WrapErr;
 use reth_chainspec::ChainSpec;
 use reth_cli_runner::CliContext;
 use reth_cli_util::parse_socket_address;
+use reth_config::Config;
 use reth_db::{init_db, DatabaseEnv};
+use reth_db_common::compat::check_datadir_compatibility;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_core::{
     args::{
         utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        CacheWarmingArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, FollowerArgs, IndexArgs,
+        NetworkArgs, NodeProfile, PayloadBuilderArgs, PruningArgs, RpcServerArgs, SecretsArgs,
+        ThreadPoolArgs, TxPoolArgs, WebhookArgs,
     },
     node_config::NodeConfig,
     version,
 };
 use reth_node_metrics::recorder::install_prometheus_recorder;
-use std::{ffi::OsString, fmt, future::Future, net::SocketAddr, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashSet, ffi::OsString, fmt, future::Future, net::SocketAddr, path::PathBuf,
+    sync::Arc,
+};
 
 /// Start the node
 #[derive(Debug, Parser)]
@@ -68,6 +77,20 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[arg(long, conflicts_with = "instance", global = true)]
     pub with_unused_ports: bool,
 
+    /// Apply the profile for conformance test suites like hive.
+    ///
+    /// Derives the timestamps of blocks mined in dev mode from their parent, disables discovery,
+    /// limits the node to a single peer and strictly validates RPC request parameters.
+    #[arg(long)]
+    pub test: bool,
+
+    /// Apply the defaults of a profile for a common deployment type.
+    ///
+    /// The profile sets the defaults of the pruning, index, transaction pool, RPC cache and RPC
+    /// limit arguments, which can still be overridden individually.
+    #[arg(long, value_enum, value_name = "PROFILE")]
+    pub profile: Option<NodeProfile>,
+
     /// All datadir related arguments
     #[command(flatten)]
     pub datadir: DatadirArgs,
@@ -104,15 +127,43 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[command(flatten)]
     pub pruning: PruningArgs,
 
+    /// All webhook related arguments with --webhook prefix
+    #[command(flatten)]
+    pub webhook: WebhookArgs,
+
+    /// All index related arguments with --index prefix
+    #[command(flatten)]
+    pub index: IndexArgs,
+
+    /// All follower related arguments with --follower prefix
+    #[command(flatten)]
+    pub follower: FollowerArgs,
+
+    /// All cache warming related arguments with --engine.cache-warming prefix
+    #[command(flatten)]
+    pub cache_warming: CacheWarmingArgs,
+
+    /// All secrets related arguments with --secrets prefix
+    #[command(flatten)]
+    pub secrets: SecretsArgs,
+
+    /// All thread pool related arguments
+    #[command(flatten)]
+    pub thread_pools: ThreadPoolArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
+
+    /// The ids of the arguments that were set explicitly, see [`Self::set_explicit_args`].
+    #[arg(skip)]
+    pub explicit_args: HashSet<String>,
 }
 
 impl NodeCommand {
     /// Parsers only the default CLI arguments
     pub fn parse_args() -> Self {
-        Self::parse()
+        Self::try_parse_args_from(std::env::args_os()).unwrap_or_else(|err| err.exit())
     }
 
     /// Parsers only the default [`NodeCommand`] arguments from the given iterator
@@ -121,7 +172,31 @@ impl NodeCommand {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        Self::try_parse_from(itr)
+        let matches = Self::command().try_get_matches_from(itr)?;
+        let mut command =
+            Self::from_arg_matches(&matches).map_err(|err| err.format(&mut Self::command()))?;
+        command.set_explicit_args(&matches);
+        Ok(command)
+    }
+}
+
+impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
+    /// Records the arguments of the parsed `matches` that were set explicitly, on the command
+    /// line or in the environment.
+    ///
+    /// The node doesn't override these arguments with the values it derives itself, e.g. from
+    /// the `--profile` or the memory budget.
+    pub fn set_explicit_args(&mut self, matches: &ArgMatches) {
+        self.explicit_args = matches
+            .ids()
+            .filter(|id| {
+                matches!(
+                    matches.value_source(id.as_str()),
+                    Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+                )
+            })
+            .map(|id| id.to_string())
+            .collect();
     }
 }
 
@@ -144,6 +219,8 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             metrics,
             instance,
             with_unused_ports,
+            test: _,
+            profile,
             network,
             rpc,
             txpool,
@@ -152,7 +229,14 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            webhook,
+            index,
+            follower,
+            cache_warming,
+            secrets,
+            thread_pools,
             ext,
+            explicit_args,
         } = self;
 
         // set up node config
@@ -170,8 +254,20 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            webhook,
+            index,
+            follower,
+            cache_warming,
+            secrets,
+            thread_pools,
+            explicit_args,
         };
 
+        if let Some(profile) = profile {
+            tracing::info!(target: "reth::cli", ?profile, "Applying node profile");
+            node_config = node_config.with_profile(profile);
+        }
+
         // Register the prometheus recorder before creating the database,
         // because database init needs it to register metrics.
         let _ = install_prometheus_recorder();
@@ -179,8 +275,30 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
         let data_dir = node_config.datadir();
         let db_path = data_dir.db();
 
+        // the database environment is configured in the config file, which is otherwise loaded
+        // by the launcher
+        let config_path = node_config.config.clone().unwrap_or_else(|| data_dir.config());
+        let db_config = confy::load_path::<Config>(&config_path)
+            .wrap_err_with(|| format!("Could not load config file {config_path:?}"))?
+            .database;
+        db_config.validate()?;
+
+        // refuse to open a datadir that was written by an incompatible version of reth or for a
+        // different chain, before anything is written to it
+        let report = check_datadir_compatibility(
+            &db_path,
+            &data_dir.static_files(),
+            &node_config.chain,
+            self.db.database_args_with_config(&db_config),
+        )?;
+        if !report.is_compatible() {
+            eyre::bail!("{report}")
+        }
+
         tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
+        let database = Arc::new(
+            init_db(db_path.clone(), self.db.database_args_with_config(&db_config))?.with_metrics(),
+        );
 
         if with_unused_ports {
             node_config = node_config.with_unused_ports();
@@ -202,6 +320,7 @@ pub struct NoArgs;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::ValueEnum;
     use reth_discv4::DEFAULT_DISCOVERY_PORT;
     use std::{
         net::{IpAddr, Ipv4Addr},
@@ -243,6 +362,23 @@ mod tests {
         assert_eq!(cmd.network.addr, IpAddr::V4(Ipv4Addr::LOCALHOST));
     }
 
+    #[test]
+    fn parse_explicit_args() {
+        let cmd = NodeCommand::try_parse_args_from([
+            "reth",
+            "--txpool.pending-max-size",
+            "10",
+            "--rpc.max-response-size",
+            "160",
+        ])
+        .unwrap();
+        assert!(cmd.explicit_args.contains("pending_max_size"));
+        assert!(cmd.explicit_args.contains("rpc_max_response_size"));
+        // arguments set to their default value aren't explicit
+        assert!(!cmd.explicit_args.contains("basefee_max_size"));
+        assert!(!cmd.explicit_args.contains("chain"));
+    }
+
     #[test]
     fn parse_discovery_port() {
         let cmd = NodeCommand::try_parse_args_from(["reth", "--discovery.port", "300"]).unwrap();
@@ -324,6 +460,59 @@ mod tests {
         assert!(cmd.dev.dev);
     }
 
+    #[test]
+    fn parse_test_profile() {
+        let cmd = NodeCommand::<NoArgs>::parse_from(["reth"]);
+        assert!(!cmd.test);
+        assert!(!cmd.network.single_peer);
+        assert!(!cmd.rpc.rpc_strict);
+
+        let cmd = NodeCommand::<NoArgs>::parse_from(["reth", "--test"]);
+        assert!(cmd.test);
+        assert!(cmd.network.discovery.disable_discovery);
+        assert!(cmd.network.single_peer);
+        assert!(cmd.rpc.rpc_strict);
+        assert!(cmd.dev.deterministic_timestamps);
+        assert!(!cmd.dev.dev);
+    }
+
+    #[test]
+    fn parse_profile() {
+        for profile in NodeProfile::value_variants() {
+            let name = profile.to_possible_value().unwrap().get_name().to_string();
+            let cmd = NodeCommand::<NoArgs>::parse_from(["reth", "--profile", &name]);
+            assert_eq!(cmd.profile, Some(*profile));
+        }
+
+        // individual arguments take precedence over the profile, even if they are set to their
+        // default value
+        let cmd = NodeCommand::<NoArgs>::try_parse_args_from([
+            "reth",
+            "--profile",
+            "rpc-heavy",
+            "--rpc.max-connections",
+            "500",
+        ])
+        .unwrap();
+        let config = NodeConfig { rpc: cmd.rpc, index: cmd.index, ..Default::default() }
+            .with_explicit_args(cmd.explicit_args)
+            .with_profile(cmd.profile.unwrap());
+        assert_eq!(config.rpc.rpc_max_connections, 500.into());
+        assert_eq!(config.rpc.rpc_state_cache.max_blocks, 20_000);
+        assert!(config.index.logs);
+        assert!(config.prune_config().is_none());
+
+        // the validator profile prunes receipts, so they aren't moved to static files
+        let cmd =
+            NodeCommand::<NoArgs>::try_parse_args_from(["reth", "--profile", "validator"]).unwrap();
+        let config = NodeConfig::default()
+            .with_explicit_args(cmd.explicit_args)
+            .with_profile(cmd.profile.unwrap());
+        assert!(config.pruning.full);
+        assert!(config.prune_config().unwrap().has_receipts_pruning());
+        assert_eq!(config.txpool.pending_max_count, 2 * TxPoolArgs::default().pending_max_count);
+    }
+
     #[test]
     fn parse_instance() {
         let mut cmd = NodeCommand::<NoArgs>::parse_from(["reth"]);
@@ -5,14 +5,14 @@ use std::{path::PathBuf, sync::Arc};
 use backon::{ConstantBuilder, Retryable};
 use clap::{Parser, Subcommand};
 use reth_chainspec::ChainSpec;
-use reth_cli_util::{get_secret_key, hash_or_num_value_parser};
+use reth_cli_util::{get_secret_key_with_passphrase, hash_or_num_value_parser};
 use reth_config::Config;
 use reth_network::{BlockDownloaderProvider, NetworkConfigBuilder};
 use reth_network_p2p::bodies::client::BodiesClient;
 use reth_node_core::{
     args::{
         utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
-        DatabaseArgs, DatadirArgs, NetworkArgs,
+        DatabaseArgs, DatadirArgs, NetworkArgs, SecretsArgs,
     },
     utils::get_single_header,
 };
@@ -46,6 +46,9 @@ pub struct Command {
     #[command(flatten)]
     network: NetworkArgs,
 
+    #[command(flatten)]
+    secrets: SecretsArgs,
+
     #[command(flatten)]
     datadir: DatadirArgs,
 
@@ -93,7 +96,8 @@ impl Command {
         let default_secret_key_path = data_dir.p2p_secret();
         let secret_key_path =
             self.network.p2p_secret_key.clone().unwrap_or(default_secret_key_path);
-        let p2p_secret_key = get_secret_key(&secret_key_path)?;
+        let passphrase = self.secrets.passphrase()?;
+        let p2p_secret_key = get_secret_key_with_passphrase(&secret_key_path, passphrase.as_ref())?;
         let rlpx_socket = (self.network.addr, self.network.port).into();
         let boot_nodes = self.chain.bootnodes().unwrap_or_default();
 
@@ -0,0 +1,201 @@
+//! Command that rolls the node back to a block.
+
+use crate::{
+    common::{AccessRights, Environment, EnvironmentArgs},
+    stage::unwind::{confirm_unwind, print_preview, unwind_pipeline},
+};
+use clap::Parser;
+use reth_db_api::database::Database;
+use reth_primitives::{BlockHashOrNumber, BlockNumber};
+use reth_provider::{
+    BlockNumReader, BlockReader, DatabaseProviderRO, DatabaseProviderRW, PruneCheckpointReader,
+    PruneCheckpointWriter, StageCheckpointReader, StaticFileProviderFactory, UnwindJournalReader,
+    UnwindPreviewProvider,
+};
+use reth_prune::PruneCheckpoint;
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use tracing::{info, warn};
+
+/// Rolls the node back to a block, removing all blocks after it.
+///
+/// All stages are unwound to the block, which also removes the data of the later blocks from the
+/// static files and reverts the history indices and the trie. The target is recorded in the unwind
+/// journal before any data is removed, so an interrupted rollback is finished by the next run of
+/// this command or the node.
+///
+/// The node has to be stopped, its in-memory state is rebuilt from the database on the next start.
+#[derive(Debug, Parser)]
+pub struct RollbackCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The block number or hash to roll back to. It becomes the new tip of the node.
+    #[arg(long = "to-block", value_name = "BLOCK")]
+    to_block: BlockHashOrNumber,
+
+    /// Skip the confirmation prompt and roll back right away.
+    #[arg(long, short)]
+    yes: bool,
+}
+
+impl RollbackCommand {
+    /// Execute the `rollback` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
+
+        let provider = provider_factory.provider()?;
+        let mut target = match self.to_block {
+            BlockHashOrNumber::Hash(hash) => provider
+                .block_number(hash)?
+                .ok_or_else(|| eyre::eyre!("Block hash not found in database: {hash:?}"))?,
+            BlockHashOrNumber::Number(number) => number,
+        };
+
+        // An interrupted unwind has to be finished before the database can be used again.
+        if let Some(journal_target) = provider.unwind_journal_target()? {
+            warn!(target: "reth::cli", target = %journal_target, "Found an interrupted unwind, it will be resumed");
+            target = target.min(journal_target);
+        }
+
+        // The tip is the highest block any stage or static file has progressed to, which can be
+        // ahead of the last block if the node stopped in a bad state.
+        let mut tip = provider.last_block_number()?;
+        for stage_id in StageId::ALL {
+            let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+            tip = tip.max(checkpoint.block_number);
+        }
+        if let Some(highest_static_file_block) =
+            provider_factory.static_file_provider().get_highest_static_files().max()
+        {
+            tip = tip.max(highest_static_file_block);
+        }
+
+        if target == 0 {
+            eyre::bail!("Cannot roll back to the genesis block")
+        }
+        if target > tip {
+            eyre::bail!("Target block {target} is higher than the tip {tip}")
+        }
+
+        if target < tip {
+            let preview = provider.unwind_preview(target + 1..=tip)?;
+            print_preview(&preview);
+            if !self.yes && !confirm_unwind()? {
+                println!("Rollback aborted!");
+                return Ok(())
+            }
+        }
+        drop(provider);
+
+        info!(target: "reth::cli", %target, %tip, "Rolling back");
+
+        let mut pipeline = unwind_pipeline(config, provider_factory.clone(), false);
+
+        // Move all applicable data from database to static files, so that the static files can be
+        // unwound together with the database.
+        pipeline.move_to_static_files()?;
+        pipeline.unwind(target, None)?;
+
+        let provider_rw = provider_factory.provider_rw()?;
+        reset_prune_checkpoints(&provider_rw, target)?;
+        provider_rw.commit()?;
+
+        verify_rollback(&provider_factory.provider()?, target)?;
+
+        info!(target: "reth::cli", %target, "Rolled back");
+
+        Ok(())
+    }
+}
+
+/// Resets the prune checkpoints that are ahead of the target, so that the data of blocks after the
+/// target is pruned again once they are synced.
+fn reset_prune_checkpoints<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    target: BlockNumber,
+) -> eyre::Result<()> {
+    let last_tx_number = provider.block_body_indices(target)?.map(|body| body.last_tx_num());
+    for (segment, checkpoint) in provider.get_prune_checkpoints()? {
+        if checkpoint.block_number.map_or(false, |block_number| block_number > target) {
+            info!(target: "reth::cli", %segment, ?checkpoint, "Resetting prune checkpoint");
+            provider.save_prune_checkpoint(
+                segment,
+                PruneCheckpoint {
+                    block_number: Some(target),
+                    tx_number: checkpoint.tx_number.and(last_tx_number),
+                    prune_mode: checkpoint.prune_mode,
+                },
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that no stage, static file or unwind journal refers to a block after the target.
+fn verify_rollback<DB: Database>(
+    provider: &DatabaseProviderRO<DB>,
+    target: BlockNumber,
+) -> eyre::Result<()> {
+    let mut inconsistencies = Vec::new();
+
+    for stage_id in StageId::ALL {
+        let checkpoint = provider.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+        if checkpoint.block_number > target {
+            inconsistencies
+                .push(format!("stage {stage_id} is at block {}", checkpoint.block_number));
+        }
+    }
+
+    let highest_static_files = provider.static_file_provider().get_highest_static_files();
+    for segment in
+        [StaticFileSegment::Headers, StaticFileSegment::Transactions, StaticFileSegment::Receipts]
+    {
+        if let Some(highest) = highest_static_files.highest(segment).filter(|h| *h > target) {
+            inconsistencies.push(format!("{segment} static files end at block {highest}"));
+        }
+    }
+
+    let last_block_number = provider.last_block_number()?;
+    if last_block_number != target {
+        inconsistencies.push(format!("the last block is {last_block_number}"));
+    }
+
+    if provider.unwind_journal_target()?.is_some() {
+        inconsistencies.push("the unwind journal was not cleared".to_string());
+    }
+
+    if !inconsistencies.is_empty() {
+        eyre::bail!(
+            "Rollback to block {target} left the node in an inconsistent state: {}",
+            inconsistencies.join(", ")
+        )
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::B256;
+
+    #[test]
+    fn parse_rollback() {
+        let cmd = RollbackCommand::parse_from(["reth", "--datadir", "dir", "--to-block", "100"]);
+        assert_eq!(cmd.to_block, BlockHashOrNumber::Number(100));
+        assert!(!cmd.yes);
+
+        let cmd = RollbackCommand::parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--to-block",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "-y",
+        ]);
+        assert_eq!(cmd.to_block, BlockHashOrNumber::Hash(B256::with_last_byte(1)));
+        assert!(cmd.yes);
+
+        assert!(RollbackCommand::try_parse_from(["reth", "--datadir", "dir"]).is_err());
+    }
+}
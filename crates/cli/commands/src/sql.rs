@@ -0,0 +1,68 @@
+//! Command that executes read-only SQL queries over chain data.
+
+use crate::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use reth_sql::{SqlConfig, SqlEngine};
+use serde_json::Value;
+
+/// `reth sql` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The query to execute.
+    ///
+    /// The tables `blocks`, `transactions`, `receipts`, `logs` and `traces` can be queried, e.g.
+    /// `SELECT number, gas_used FROM blocks WHERE number BETWEEN 100 AND 200`.
+    query: String,
+
+    /// Maximum number of blocks the query may scan per table.
+    ///
+    /// Filters on the `number` column of `blocks` and the `block_number` column of the other
+    /// tables restrict the blocks that are scanned.
+    #[arg(long, value_name = "COUNT", default_value_t = SqlConfig::default().max_blocks)]
+    max_blocks: u64,
+
+    /// Maximum number of rows the query may return.
+    #[arg(long, value_name = "COUNT", default_value_t = SqlConfig::default().max_rows)]
+    max_rows: usize,
+
+    /// Print the result as JSON instead of a table.
+    #[arg(long)]
+    json: bool,
+}
+
+impl Command {
+    /// Execute `sql` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+
+        let config =
+            SqlConfig::default().with_max_blocks(self.max_blocks).with_max_rows(self.max_rows);
+        let engine = SqlEngine::new(provider_factory, config)?;
+        let result = engine.query_json(&self.query).await?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&result)?);
+            return Ok(())
+        }
+
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(&result.columns);
+        for row in result.rows {
+            table.add_row(Row::from(row.into_iter().map(|value| {
+                Cell::new(match value {
+                    Value::Null => String::new(),
+                    Value::String(value) => value,
+                    value => value.to_string(),
+                })
+            })));
+        }
+        println!("{table}");
+
+        Ok(())
+    }
+}
@@ -97,6 +97,8 @@ impl Command {
                 tx.clear::<tables::StorageChangeSets>()?;
                 tx.clear::<tables::Bytecodes>()?;
                 tx.clear::<tables::Receipts>()?;
+                tx.clear::<tables::InternalTransfers>()?;
+                tx.clear::<tables::BlockExecutionCheckpoints>()?;
                 tx.put::<tables::StageCheckpoints>(
                     StageId::Execution.to_string(),
                     Default::default(),
@@ -170,6 +172,12 @@ impl Command {
                 )?;
                 insert_genesis_header(&provider_rw, &static_file_provider, self.env.chain)?;
             }
+            StageEnum::LogIndex => {
+                tx.clear::<tables::LogAddressHistory>()?;
+                tx.clear::<tables::LogTopicHistory>()?;
+                // The log index is only maintained once the stage has a checkpoint
+                tx.delete::<tables::StageCheckpoints>(StageId::IndexLogs.to_string(), None)?;
+            }
         }
 
         tx.put::<tables::StageCheckpoints>(StageId::Finish.to_string(), Default::default())?;
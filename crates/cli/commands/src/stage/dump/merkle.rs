@@ -94,6 +94,7 @@ fn unwind_and_copy<DB: Database>(
             max_changes: None,
             max_cumulative_gas: None,
             max_duration: None,
+            block_checkpoint_gas: None,
         },
         MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD,
         PruneModes::all(),
@@ -7,7 +7,7 @@ use clap::Parser;
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_chainspec::ChainSpec;
 use reth_cli_runner::CliContext;
-use reth_cli_util::get_secret_key;
+use reth_cli_util::get_secret_key_with_passphrase;
 use reth_config::config::{HashingConfig, SenderRecoveryConfig, TransactionLookupConfig};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -17,8 +17,9 @@ use reth_evm::execute::BlockExecutorProvider;
 use reth_exex::ExExManagerHandle;
 use reth_network::BlockDownloaderProvider;
 use reth_network_p2p::HeadersClient;
+use reth_node_builder::common::configure_metric_server;
 use reth_node_core::{
-    args::{NetworkArgs, StageEnum},
+    args::{NetworkArgs, SecretsArgs, StageEnum},
     primitives::BlockHashOrNumber,
     version::{
         BUILD_PROFILE_NAME, CARGO_PKG_VERSION, VERGEN_BUILD_TIMESTAMP, VERGEN_CARGO_FEATURES,
@@ -37,8 +38,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexLogsStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageError, StageExt, UnwindInput,
     UnwindOutput,
@@ -97,6 +98,9 @@ pub struct Command {
 
     #[command(flatten)]
     network: NetworkArgs,
+
+    #[command(flatten)]
+    secrets: SecretsArgs,
 }
 
 impl Command {
@@ -116,7 +120,7 @@ impl Command {
 
         if let Some(listen_addr) = self.metrics {
             info!(target: "reth::cli", "Starting metrics endpoint at {}", listen_addr);
-            let config = MetricServerConfig::new(
+            let server_config = MetricServerConfig::new(
                 listen_addr,
                 VersionInfo {
                     version: CARGO_PKG_VERSION,
@@ -132,8 +136,9 @@ impl Command {
                     provider_factory.static_file_provider(),
                 ),
             );
+            let server_config = configure_metric_server(server_config, &config.metrics)?;
 
-            MetricServer::new(config).serve().await?;
+            MetricServer::new(server_config).serve().await?;
         }
 
         let batch_size = self.batch_size.unwrap_or(self.to.saturating_sub(self.from) + 1);
@@ -147,12 +152,14 @@ impl Command {
                     let consensus =
                         Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
 
+                    let passphrase = self.secrets.passphrase()?;
                     let network_secret_path = self
                         .network
                         .p2p_secret_key
                         .clone()
                         .unwrap_or_else(|| data_dir.p2p_secret());
-                    let p2p_secret_key = get_secret_key(&network_secret_path)?;
+                    let p2p_secret_key =
+                        get_secret_key_with_passphrase(&network_secret_path, passphrase.as_ref())?;
 
                     let default_peers_path = data_dir.known_peers();
 
@@ -197,12 +204,14 @@ impl Command {
                     config.peers.trusted_nodes_only = self.network.trusted_only;
                     config.peers.trusted_nodes.extend(self.network.trusted_peers.clone());
 
+                    let passphrase = self.secrets.passphrase()?;
                     let network_secret_path = self
                         .network
                         .p2p_secret_key
                         .clone()
                         .unwrap_or_else(|| data_dir.p2p_secret());
-                    let p2p_secret_key = get_secret_key(&network_secret_path)?;
+                    let p2p_secret_key =
+                        get_secret_key_with_passphrase(&network_secret_path, passphrase.as_ref())?;
 
                     let default_peers_path = data_dir.known_peers();
 
@@ -224,8 +233,12 @@ impl Command {
                             .with_stream_batch_size(batch_size as usize)
                             .with_request_limit(config.stages.bodies.downloader_request_limit)
                             .with_max_buffered_blocks_size_bytes(
-                                config.stages.bodies.downloader_max_buffered_blocks_size_bytes,
+                                config.stages.bodies.max_buffered_blocks_size_bytes(),
                             )
+                            .with_max_spilled_blocks_size_bytes(
+                                config.stages.bodies.downloader_max_spilled_blocks_size_bytes,
+                            )
+                            .with_spill_dir(data_dir.spilled_bodies())
                             .with_concurrent_requests_range(
                                 config.stages.bodies.downloader_min_concurrent_requests..=
                                     config.stages.bodies.downloader_max_concurrent_requests,
@@ -248,6 +261,7 @@ impl Command {
                             max_changes: None,
                             max_cumulative_gas: None,
                             max_duration: None,
+                            block_checkpoint_gas: None,
                         },
                         config.stages.merkle.clean_threshold,
                         prune_modes,
@@ -259,7 +273,7 @@ impl Command {
                     Box::new(TransactionLookupStage::new(
                         TransactionLookupConfig { chunk_size: batch_size },
                         etl_config,
-                        prune_modes.transaction_lookup,
+                        prune_modes.transaction_lookup_skip_mode(),
                     )),
                     None,
                 ),
@@ -297,6 +311,14 @@ impl Command {
                     )),
                     None,
                 ),
+                StageEnum::LogIndex => (
+                    Box::new(IndexLogsStage::new(
+                        config.stages.index_logs,
+                        etl_config,
+                        prune_modes.log_index,
+                    )),
+                    None,
+                ),
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -2,6 +2,8 @@
 
 use crate::common::{AccessRights, Environment, EnvironmentArgs};
 use clap::{Parser, Subcommand};
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_config::Config;
 use reth_consensus::Consensus;
@@ -13,7 +15,8 @@ use reth_node_core::args::NetworkArgs;
 use reth_primitives::{BlockHashOrNumber, BlockNumber, B256};
 use reth_provider::{
     BlockExecutionWriter, BlockNumReader, ChainSpecProvider, FinalizedBlockReader,
-    FinalizedBlockWriter, ProviderFactory, StaticFileProviderFactory,
+    FinalizedBlockWriter, ProviderFactory, StageCheckpointWriter, StaticFileProviderFactory,
+    UnwindJournalReader, UnwindJournalWriter, UnwindPreview, UnwindPreviewProvider,
 };
 use reth_prune::PruneModes;
 use reth_stages::{
@@ -22,9 +25,13 @@ use reth_stages::{
     ExecutionStageThresholds, Pipeline, StageSet,
 };
 use reth_static_file::StaticFileProducer;
-use std::{ops::RangeInclusive, sync::Arc};
+use std::{
+    io::{self, Write},
+    ops::RangeInclusive,
+    sync::Arc,
+};
 use tokio::sync::watch;
-use tracing::info;
+use tracing::{info, warn};
 
 /// `reth stage unwind` command
 #[derive(Debug, Parser)]
@@ -42,6 +49,17 @@ pub struct Command {
     /// unwound.
     #[arg(long)]
     offline: bool,
+
+    /// Skip the confirmation prompt and unwind right away.
+    #[arg(long, short)]
+    yes: bool,
+
+    /// The number of blocks that are unwound in a single database transaction.
+    ///
+    /// The progress is committed after every chunk, and an interrupted unwind is resumed on the
+    /// next run of this command or the node.
+    #[arg(long, default_value_t = 10_000, value_parser = clap::value_parser!(u64).range(1..))]
+    chunk_size: u64,
 }
 
 impl Command {
@@ -49,11 +67,27 @@ impl Command {
     pub async fn execute(self) -> eyre::Result<()> {
         let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
 
-        let range = self.command.unwind_range(provider_factory.clone())?;
+        let mut range = self.command.unwind_range(provider_factory.clone())?;
+
+        // An interrupted unwind has to be finished before the database can be used again.
+        if let Some(target) = provider_factory.provider()?.unwind_journal_target()? {
+            warn!(target: "reth::cli", %target, "Found an interrupted unwind, it will be resumed");
+            if target < *range.start() {
+                range = target + 1..=*range.end();
+            }
+        }
+
         if *range.start() == 0 {
             eyre::bail!("Cannot unwind genesis block")
         }
 
+        let preview = provider_factory.provider()?.unwind_preview(range.clone())?;
+        print_preview(&preview);
+        if !self.yes && !confirm_unwind()? {
+            println!("Unwind aborted!");
+            return Ok(())
+        }
+
         let highest_static_file_block = provider_factory
             .static_file_provider()
             .get_highest_static_files()
@@ -78,92 +112,167 @@ impl Command {
             }
 
             // This will build an offline-only pipeline if the `offline` flag is enabled
-            let mut pipeline = self.build_pipeline(config, provider_factory)?;
+            let mut pipeline = unwind_pipeline(config, provider_factory, self.offline);
 
             // Move all applicable data from database to static files.
             pipeline.move_to_static_files()?;
 
             pipeline.unwind((*range.start()).saturating_sub(1), None)?;
         } else {
-            info!(target: "reth::cli", ?range, "Executing a database unwind.");
+            info!(target: "reth::cli", ?range, chunk_size = self.chunk_size, "Executing a database unwind.");
+
+            // Record the unwind target before removing any data, so that an interrupted unwind
+            // can be resumed.
             let provider = provider_factory.provider_rw()?;
+            provider.save_unwind_journal_target(*range.start() - 1)?;
+            provider.commit()?;
 
-            let _ = provider
-                .take_block_and_execution_range(range.clone())
-                .map_err(|err| eyre::eyre!("Transaction error on unwind: {err}"))?;
-
-            // update finalized block if needed
-            let last_saved_finalized_block_number = provider.last_finalized_block_number()?;
-            let range_min =
-                range.clone().min().ok_or(eyre::eyre!("Could not fetch lower range end"))?;
-            if last_saved_finalized_block_number.is_none() ||
-                Some(range_min) < last_saved_finalized_block_number
-            {
-                provider.save_finalized_block_number(BlockNumber::from(range_min))?;
-            }
+            // Unwind from the tip in chunks, each chunk leaves the database at a consistent tip.
+            let mut chunk_end = *range.end();
+            loop {
+                let chunk_start = chunk_end.saturating_sub(self.chunk_size - 1).max(*range.start());
+                let provider = provider_factory.provider_rw()?;
 
-            provider.commit()?;
+                let _ = provider
+                    .take_block_and_execution_range(chunk_start..=chunk_end)
+                    .map_err(|err| eyre::eyre!("Transaction error on unwind: {err}"))?;
+
+                // update finalized block if needed
+                let last_saved_finalized_block_number = provider.last_finalized_block_number()?;
+                if last_saved_finalized_block_number.is_none() ||
+                    Some(chunk_start) < last_saved_finalized_block_number
+                {
+                    provider.save_finalized_block_number(BlockNumber::from(chunk_start))?;
+                }
+
+                // Move the checkpoints of all stages to the new tip with the chunk, so that the
+                // stages never point at removed blocks.
+                provider.update_pipeline_stages(chunk_start - 1, true)?;
+
+                let done = chunk_start == *range.start();
+                if done {
+                    provider.clear_unwind_journal()?;
+                }
+                provider.commit()?;
+
+                info!(
+                    target: "reth::cli",
+                    unwound = range.end() - chunk_start + 1,
+                    total = preview.blocks(),
+                    "Unwound chunk"
+                );
+
+                if done {
+                    break
+                }
+                chunk_end = chunk_start - 1;
+            }
         }
 
         info!(target: "reth::cli", range=?range.clone(), count=range.count(), "Unwound blocks");
 
         Ok(())
     }
+}
 
-    fn build_pipeline<DB: Database + 'static>(
-        self,
-        config: Config,
-        provider_factory: ProviderFactory<Arc<DB>>,
-    ) -> Result<Pipeline<Arc<DB>>, eyre::Error> {
-        let consensus: Arc<dyn Consensus> =
-            Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
-        let stage_conf = &config.stages;
-        let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
-
-        let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-
-        // Unwinding does not require a valid executor
-        let executor = NoopBlockExecutorProvider::default();
-
-        let builder = if self.offline {
-            Pipeline::builder().add_stages(
-                OfflineStages::new(executor, config.stages, PruneModes::default())
-                    .builder()
-                    .disable(reth_stages::StageId::SenderRecovery),
-            )
-        } else {
-            Pipeline::builder().with_tip_sender(tip_tx).add_stages(
-                DefaultStages::new(
-                    provider_factory.clone(),
-                    tip_rx,
-                    Arc::clone(&consensus),
-                    NoopHeaderDownloader::default(),
-                    NoopBodiesDownloader::default(),
-                    executor.clone(),
-                    stage_conf.clone(),
-                    prune_modes.clone(),
-                )
-                .set(ExecutionStage::new(
-                    executor,
-                    ExecutionStageThresholds {
-                        max_blocks: None,
-                        max_changes: None,
-                        max_cumulative_gas: None,
-                        max_duration: None,
-                    },
-                    stage_conf.execution_external_clean_threshold(),
-                    prune_modes,
-                    ExExManagerHandle::empty(),
-                )),
+/// Builds a pipeline that can only unwind.
+///
+/// If `offline` is set, only the offline stages are part of the pipeline.
+pub(crate) fn unwind_pipeline<DB: Database + 'static>(
+    config: Config,
+    provider_factory: ProviderFactory<Arc<DB>>,
+    offline: bool,
+) -> Pipeline<Arc<DB>> {
+    let consensus: Arc<dyn Consensus> =
+        Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
+    let stage_conf = &config.stages;
+    let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+
+    let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
+
+    // Unwinding does not require a valid executor
+    let executor = NoopBlockExecutorProvider::default();
+
+    let builder = if offline {
+        Pipeline::builder().add_stages(
+            OfflineStages::new(executor, config.stages, PruneModes::default())
+                .builder()
+                .disable(reth_stages::StageId::SenderRecovery),
+        )
+    } else {
+        Pipeline::builder().with_tip_sender(tip_tx).add_stages(
+            DefaultStages::new(
+                provider_factory.clone(),
+                tip_rx,
+                Arc::clone(&consensus),
+                NoopHeaderDownloader::default(),
+                NoopBodiesDownloader::default(),
+                executor.clone(),
+                stage_conf.clone(),
+                prune_modes.clone(),
             )
-        };
+            .set(ExecutionStage::new(
+                executor,
+                ExecutionStageThresholds {
+                    max_blocks: None,
+                    max_changes: None,
+                    max_cumulative_gas: None,
+                    max_duration: None,
+                    block_checkpoint_gas: None,
+                },
+                stage_conf.execution_external_clean_threshold(),
+                prune_modes,
+                ExExManagerHandle::empty(),
+            )),
+        )
+    };
 
-        let pipeline = builder.build(
-            provider_factory.clone(),
-            StaticFileProducer::new(provider_factory, PruneModes::default()),
-        );
-        Ok(pipeline)
+    builder.build(
+        provider_factory.clone(),
+        StaticFileProducer::new(provider_factory, PruneModes::default()),
+    )
+}
+
+/// Prints the data that is removed by the unwind.
+pub(crate) fn print_preview(preview: &UnwindPreview) {
+    println!(
+        "Unwinding blocks {}..={} ({} blocks, {} transactions)",
+        preview.range.start(),
+        preview.range.end(),
+        preview.blocks(),
+        preview.transactions
+    );
+
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Table Name", "# Entries", "Size"]);
+    for table_preview in &preview.tables {
+        let mut row = Row::new();
+        row.add_cell(Cell::new(table_preview.table))
+            .add_cell(Cell::new(table_preview.entries))
+            .add_cell(Cell::new(human_bytes(table_preview.bytes as f64)));
+        table.add_row(row);
     }
+    let mut row = Row::new();
+    row.add_cell(Cell::new("Total"))
+        .add_cell(Cell::new(preview.entries()))
+        .add_cell(Cell::new(human_bytes(preview.bytes() as f64)));
+    table.add_row(row);
+    println!("{table}");
+    println!(
+        "The plain state, hashed state, history indices and trie tables are reverted using the changesets."
+    );
+}
+
+/// Asks the user to confirm the unwind.
+pub(crate) fn confirm_unwind() -> eyre::Result<bool> {
+    print!("Are you sure you want to unwind? This cannot be undone. (y/N): ");
+    // Flush the buffer to ensure the message is printed immediately
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().eq_ignore_ascii_case("y"))
 }
 
 /// `reth stage unwind` subcommand
@@ -216,5 +325,31 @@ mod tests {
 
         let cmd = Command::parse_from(["reth", "--datadir", "dir", "num-blocks", "100"]);
         assert_eq!(cmd.command, Subcommands::NumBlocks { amount: 100 });
+        assert!(!cmd.yes);
+        assert_eq!(cmd.chunk_size, 10_000);
+
+        let cmd = Command::parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--yes",
+            "--chunk-size",
+            "100",
+            "num-blocks",
+            "100",
+        ]);
+        assert!(cmd.yes);
+        assert_eq!(cmd.chunk_size, 100);
+
+        assert!(Command::try_parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--chunk-size",
+            "0",
+            "num-blocks",
+            "100"
+        ])
+        .is_err());
     }
 }
@@ -10,7 +10,7 @@
 
 //! Entrypoint for running commands.
 
-use reth_tasks::{TaskExecutor, TaskManager};
+use reth_tasks::{shutdown::ShutdownDeadlines, TaskExecutor, TaskManager};
 use std::{future::Future, pin::pin, sync::mpsc, time::Duration};
 use tracing::{debug, error, trace};
 
@@ -51,8 +51,9 @@ impl CliRunner {
             debug!(target: "reth::cli", "shutting down gracefully");
             // after the command has finished or exit signal was received we shutdown the task
             // manager which fires the shutdown signal to all tasks spawned via the task
-            // executor and awaiting on tasks spawned with graceful shutdown
-            task_manager.graceful_shutdown_with_timeout(Duration::from_secs(5));
+            // executor, one shutdown phase after the other, and awaiting on tasks spawned with
+            // graceful shutdown
+            task_manager.graceful_shutdown_with_deadlines(ShutdownDeadlines::default());
         }
 
         // `drop(tokio_runtime)` would block the current thread until its pools
@@ -10,7 +10,9 @@
 
 /// Helper function to load a secret key from a file.
 pub mod load_secret_key;
-pub use load_secret_key::get_secret_key;
+pub use load_secret_key::{get_secret_key, get_secret_key_with_passphrase};
+
+pub mod secrets;
 
 /// Cli parsers functions.
 pub mod parsers;
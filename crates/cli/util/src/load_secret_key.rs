@@ -1,4 +1,5 @@
-use reth_fs_util::{self as fs, FsPathError};
+use crate::secrets::{read_secret_file, write_secret_file, SecretFileError, SecretsPassphrase};
+use reth_fs_util::FsPathError;
 use secp256k1::{Error as SecretKeyBaseError, SecretKey};
 use std::{
     io,
@@ -22,6 +23,10 @@ pub enum SecretKeyError {
     #[error(transparent)]
     SecretKeyFsPathError(#[from] FsPathError),
 
+    /// Error reading or writing the, possibly encrypted, secret key file.
+    #[error(transparent)]
+    SecretFileError(#[from] SecretFileError),
+
     /// Represents an error when failed to access the key file.
     #[error("failed to access key file {secret_file:?}: {error}")]
     FailedToAccessKeyFile {
@@ -36,25 +41,28 @@ pub enum SecretKeyError {
 /// generates a secret key and stores it in the provided path. I/O errors might occur during write
 /// operations in the form of a [`SecretKeyError`]
 pub fn get_secret_key(secret_key_path: &Path) -> Result<SecretKey, SecretKeyError> {
+    get_secret_key_with_passphrase(secret_key_path, None)
+}
+
+/// Like [`get_secret_key`], but encrypted key files are decrypted with the passphrase, and a newly
+/// generated key is encrypted with it, see [`crate::secrets`].
+pub fn get_secret_key_with_passphrase(
+    secret_key_path: &Path,
+    passphrase: Option<&SecretsPassphrase>,
+) -> Result<SecretKey, SecretKeyError> {
     let exists = secret_key_path.try_exists();
 
     match exists {
         Ok(true) => {
-            let contents = fs::read_to_string(secret_key_path)?;
+            let contents = read_secret_file(secret_key_path, passphrase)?;
             Ok(contents
-                .as_str()
+                .trim()
                 .parse::<SecretKey>()
                 .map_err(SecretKeyError::SecretKeyDecodeError)?)
         }
         Ok(false) => {
-            if let Some(dir) = secret_key_path.parent() {
-                // Create parent directory
-                fs::create_dir_all(dir)?;
-            }
-
             let secret = rng_secret_key();
-            let hex = alloy_primitives::hex::encode(secret.as_ref());
-            fs::write(secret_key_path, hex)?;
+            write_secret_key(secret_key_path, &secret, passphrase)?;
             Ok(secret)
         }
         Err(error) => Err(SecretKeyError::FailedToAccessKeyFile {
@@ -63,3 +71,14 @@ pub fn get_secret_key(secret_key_path: &Path) -> Result<SecretKey, SecretKeyErro
         }),
     }
 }
+
+/// Writes the hex encoded [`SecretKey`] to the path, encrypted with the passphrase if provided.
+pub fn write_secret_key(
+    secret_key_path: &Path,
+    secret: &SecretKey,
+    passphrase: Option<&SecretsPassphrase>,
+) -> Result<(), SecretKeyError> {
+    let hex = alloy_primitives::hex::encode(secret.as_ref());
+    write_secret_file(secret_key_path, &hex, passphrase)?;
+    Ok(())
+}
@@ -0,0 +1,292 @@
+//! Secret files that are optionally encrypted at rest.
+//!
+//! Encrypted secret files use the [age](https://age-encryption.org) format with a passphrase
+//! recipient, so they are interoperable with the `age` CLI, e.g. an existing secret can be
+//! encrypted with `age --passphrase --output discovery-secret.age discovery-secret`.
+//!
+//! Whether a file is encrypted is detected from its header, so plaintext and encrypted secret
+//! files can be used interchangeably, only the passphrase is required to read encrypted files.
+
+use age::secrecy::SecretString;
+use reth_fs_util::{self as fs, FsPathError};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// The environment variable the passphrase of encrypted secret files is read from, if no
+/// passphrase file is configured.
+pub const SECRETS_PASSPHRASE_ENV: &str = "RETH_SECRETS_PASSPHRASE";
+
+/// The header that all age encrypted files start with.
+const AGE_HEADER: &[u8] = b"age-encryption.org/v1";
+
+/// The passphrase encrypted secret files are encrypted with.
+#[derive(Clone)]
+pub struct SecretsPassphrase {
+    passphrase: SecretString,
+    /// The scrypt work factor used for encryption, defaults to a work factor that takes about a
+    /// second on this machine.
+    work_factor: Option<u8>,
+}
+
+impl SecretsPassphrase {
+    /// Creates a new passphrase.
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Self { passphrase: SecretString::from(passphrase.into()), work_factor: None }
+    }
+
+    /// Reads the passphrase from the first line of the file.
+    pub fn from_file(path: &Path) -> Result<Self, SecretFileError> {
+        let contents = fs::read_to_string(path)?;
+        let passphrase = contents.lines().next().unwrap_or_default();
+        if passphrase.is_empty() {
+            return Err(SecretFileError::EmptyPassphrase(path.to_path_buf()))
+        }
+        Ok(Self::new(passphrase))
+    }
+
+    /// Reads the passphrase from the [`SECRETS_PASSPHRASE_ENV`] environment variable, if set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(SECRETS_PASSPHRASE_ENV)
+            .ok()
+            .filter(|passphrase| !passphrase.is_empty())
+            .map(Self::new)
+    }
+
+    /// Sets the scrypt work factor `log_n` used for encryption.
+    ///
+    /// Lower work factors make brute forcing the passphrase cheaper and should only be used in
+    /// tests.
+    pub const fn with_work_factor(mut self, log_n: u8) -> Self {
+        self.work_factor = Some(log_n);
+        self
+    }
+
+    fn recipient(&self) -> age::scrypt::Recipient {
+        let mut recipient = age::scrypt::Recipient::new(self.passphrase.clone());
+        if let Some(log_n) = self.work_factor {
+            recipient.set_work_factor(log_n);
+        }
+        recipient
+    }
+
+    fn identity(&self) -> age::scrypt::Identity {
+        age::scrypt::Identity::new(self.passphrase.clone())
+    }
+}
+
+impl fmt::Debug for SecretsPassphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretsPassphrase")
+            .field("passphrase", &"<redacted>")
+            .field("work_factor", &self.work_factor)
+            .finish()
+    }
+}
+
+/// Errors of reading or writing secret files.
+#[derive(Debug, thiserror::Error)]
+pub enum SecretFileError {
+    /// Error accessing the secret file.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+    /// The secret file is encrypted, but no passphrase was provided.
+    #[error("secret file {0:?} is encrypted, but no passphrase was provided")]
+    MissingPassphrase(PathBuf),
+    /// The passphrase file is empty.
+    #[error("passphrase file {0:?} is empty")]
+    EmptyPassphrase(PathBuf),
+    /// Failed to decrypt the secret file, e.g. because of a wrong passphrase.
+    #[error("failed to decrypt secret file {path:?}: {error}")]
+    Decrypt {
+        /// The decryption error.
+        error: Box<age::DecryptError>,
+        /// Path to the secret file.
+        path: PathBuf,
+    },
+    /// Failed to encrypt the secret.
+    #[error("failed to encrypt secret file {path:?}: {error}")]
+    Encrypt {
+        /// The encryption error.
+        error: Box<age::EncryptError>,
+        /// Path to the secret file.
+        path: PathBuf,
+    },
+    /// The decrypted secret is not valid UTF-8.
+    #[error("secret file {0:?} is not valid UTF-8")]
+    InvalidUtf8(PathBuf),
+}
+
+/// Returns `true` if the contents of a secret file are encrypted.
+pub fn is_encrypted(contents: &[u8]) -> bool {
+    contents.starts_with(AGE_HEADER)
+}
+
+/// Returns `true` if the secret file at the path is encrypted.
+pub fn is_encrypted_file(path: &Path) -> Result<bool, SecretFileError> {
+    Ok(is_encrypted(&fs::read(path)?))
+}
+
+/// Reads a secret file, decrypting it with the passphrase if it's encrypted.
+pub fn read_secret_file(
+    path: &Path,
+    passphrase: Option<&SecretsPassphrase>,
+) -> Result<String, SecretFileError> {
+    let mut contents = fs::read(path)?;
+    if is_encrypted(&contents) {
+        let passphrase =
+            passphrase.ok_or_else(|| SecretFileError::MissingPassphrase(path.to_path_buf()))?;
+        contents = age::decrypt(&passphrase.identity(), &contents).map_err(|error| {
+            SecretFileError::Decrypt { error: Box::new(error), path: path.to_path_buf() }
+        })?;
+    }
+    String::from_utf8(contents).map_err(|_| SecretFileError::InvalidUtf8(path.to_path_buf()))
+}
+
+/// Writes a secret file, encrypting it with the passphrase if one is provided.
+///
+/// The parent directories are created if they don't exist. On unix, the file is only readable by
+/// the owner.
+///
+/// The file is replaced atomically: the secret is written and synced to a temporary file in the
+/// same directory, which is then renamed to the path. An existing secret file is never left
+/// truncated or partially written.
+pub fn write_secret_file(
+    path: &Path,
+    secret: &str,
+    passphrase: Option<&SecretsPassphrase>,
+) -> Result<(), SecretFileError> {
+    let contents = match passphrase {
+        Some(passphrase) => {
+            age::encrypt(&passphrase.recipient(), secret.as_bytes()).map_err(|error| {
+                SecretFileError::Encrypt { error: Box::new(error), path: path.to_path_buf() }
+            })?
+        }
+        None => secret.as_bytes().to_vec(),
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    if let Err(err) = write_owner_only(&tmp, &contents).and_then(|()| fs::rename(&tmp, path)) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(err.into())
+    }
+    Ok(())
+}
+
+/// Overwrites the contents of a secret file with zeros and removes it.
+///
+/// This keeps a plaintext secret from staying readable in the freed blocks of the file, on file
+/// systems that overwrite data in place. On unix, the contents are only overwritten if the path
+/// is the last link to the file.
+pub fn remove_secret_file(path: &Path) -> Result<(), SecretFileError> {
+    use std::{fs::OpenOptions, io::Write};
+
+    let metadata = fs::metadata(path)?;
+    #[cfg(unix)]
+    if std::os::unix::fs::MetadataExt::nlink(&metadata) > 1 {
+        fs::remove_file(path)?;
+        return Ok(())
+    }
+
+    let len = metadata.len();
+    let mut file =
+        OpenOptions::new().write(true).open(path).map_err(|err| FsPathError::open(err, path))?;
+    file.write_all(&vec![0; len as usize]).map_err(|err| FsPathError::write(err, path))?;
+    file.sync_all().map_err(|err| FsPathError::write(err, path))?;
+    drop(file);
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn write_owner_only(path: &Path, contents: &[u8]) -> Result<(), FsPathError> {
+    use std::{
+        fs::{OpenOptions, Permissions},
+        io::Write,
+        os::unix::fs::{OpenOptionsExt, PermissionsExt},
+    };
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .map_err(|err| FsPathError::create_file(err, path))?;
+    // the mode only applies to newly created files
+    file.set_permissions(Permissions::from_mode(0o600))
+        .map_err(|err| FsPathError::write(err, path))?;
+    file.write_all(contents).map_err(|err| FsPathError::write(err, path))?;
+    file.sync_all().map_err(|err| FsPathError::write(err, path))
+}
+
+#[cfg(not(unix))]
+fn write_owner_only(path: &Path, contents: &[u8]) -> Result<(), FsPathError> {
+    use std::io::Write;
+
+    let mut file = fs::create_file(path)?;
+    file.write_all(contents).map_err(|err| FsPathError::write(err, path))?;
+    file.sync_all().map_err(|err| FsPathError::write(err, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypted_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret");
+        let passphrase = SecretsPassphrase::new("passphrase").with_work_factor(2);
+
+        write_secret_file(&path, "plain", None).unwrap();
+        assert!(!is_encrypted_file(&path).unwrap());
+        assert_eq!(read_secret_file(&path, Some(&passphrase)).unwrap(), "plain");
+
+        write_secret_file(&path, "secret", Some(&passphrase)).unwrap();
+        assert!(is_encrypted_file(&path).unwrap());
+        assert_eq!(read_secret_file(&path, Some(&passphrase)).unwrap(), "secret");
+        assert!(matches!(
+            read_secret_file(&path, None),
+            Err(SecretFileError::MissingPassphrase(_))
+        ));
+        assert!(matches!(
+            read_secret_file(&path, Some(&SecretsPassphrase::new("wrong"))),
+            Err(SecretFileError::Decrypt { .. })
+        ));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // the file is replaced through a temporary file, which doesn't outlive the write
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        remove_secret_file(&path).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn remove_linked_secret_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret");
+        let link = dir.path().join("secret.bak");
+        write_secret_file(&path, "secret", None).unwrap();
+        std::fs::hard_link(&path, &link).unwrap();
+
+        // the contents are still referenced by the other link, so they are kept
+        remove_secret_file(&link).unwrap();
+        assert!(!link.exists());
+        assert_eq!(read_secret_file(&path, None).unwrap(), "secret");
+    }
+}
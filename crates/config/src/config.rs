@@ -2,7 +2,7 @@
 
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
-use reth_stages_types::ExecutionStageThresholds;
+use reth_stages_types::{DiskSpacePolicy, ExecutionStageThresholds};
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     ffi::OsStr,
@@ -26,6 +26,22 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration of the database environment.
+    pub database: DatabaseConfig,
+    /// Configuration for serving old static files from an object store.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cold_storage: Option<ColdStorageConfig>,
+    /// Configuration for authenticating requests to the http and ws RPC servers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_auth: Option<RpcAuthConfig>,
+    /// Configuration of the memory limits of the node.
+    pub limits: LimitsConfig,
+    /// Configuration of the canonical blocks the engine keeps in memory.
+    pub in_memory: InMemoryConfig,
+    /// Configuration of the Prometheus metrics endpoint.
+    pub metrics: MetricsConfig,
+    /// Configuration of the header checks of the consensus and payload validators.
+    pub validation: ValidationConfig,
 }
 
 impl Config {
@@ -63,6 +79,9 @@ impl Config {
 #[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
 pub struct StageConfig {
+    /// What to do if the disk space that the pipeline is estimated to use exceeds the available
+    /// disk space.
+    pub disk_space_check: DiskSpacePolicy,
     /// Header stage configuration.
     pub headers: HeadersConfig,
     /// Body stage configuration.
@@ -85,6 +104,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -149,8 +170,15 @@ pub struct BodiesConfig {
     pub downloader_stream_batch_size: usize,
     /// The size of the internal block buffer in bytes.
     ///
-    /// Default: 2GB
-    pub downloader_max_buffered_blocks_size_bytes: usize,
+    /// Default: 2GB, see [`Self::max_buffered_blocks_size_bytes`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downloader_max_buffered_blocks_size_bytes: Option<usize>,
+    /// The maximum size of bodies in bytes that are spilled to the `bodies_spill` directory in the
+    /// datadir when the internal block buffer is full, instead of throttling the download.
+    /// Disabled if zero.
+    ///
+    /// Default: 0
+    pub downloader_max_spilled_blocks_size_bytes: usize,
     /// The minimum number of requests to send concurrently.
     ///
     /// Default: 5
@@ -167,13 +195,25 @@ impl Default for BodiesConfig {
         Self {
             downloader_request_limit: 200,
             downloader_stream_batch_size: 1_000,
-            downloader_max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
+            downloader_max_buffered_blocks_size_bytes: None,
+            downloader_max_spilled_blocks_size_bytes: 0,
             downloader_min_concurrent_requests: 5,
             downloader_max_concurrent_requests: 100,
         }
     }
 }
 
+impl BodiesConfig {
+    /// The default size of the internal block buffer in bytes.
+    pub const DEFAULT_MAX_BUFFERED_BLOCKS_SIZE_BYTES: usize = 2 * 1024 * 1024 * 1024; // ~2GB
+
+    /// Returns the size of the internal block buffer in bytes, or the default if it isn't set.
+    pub fn max_buffered_blocks_size_bytes(&self) -> usize {
+        self.downloader_max_buffered_blocks_size_bytes
+            .unwrap_or(Self::DEFAULT_MAX_BUFFERED_BLOCKS_SIZE_BYTES)
+    }
+}
+
 /// Sender recovery stage configuration.
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -204,6 +244,9 @@ pub struct ExecutionConfig {
         deserialize_with = "deserialize_duration"
     )]
     pub max_duration: Option<Duration>,
+    /// The amount of gas of a single block to execute before the execution stage commits the
+    /// progress within the block.
+    pub block_checkpoint_gas: Option<u64>,
 }
 
 impl Default for ExecutionConfig {
@@ -215,6 +258,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            block_checkpoint_gas: None,
         }
     }
 }
@@ -226,6 +270,7 @@ impl From<ExecutionConfig> for ExecutionStageThresholds {
             max_changes: config.max_changes,
             max_cumulative_gas: config.max_cumulative_gas,
             max_duration: config.max_duration,
+            block_checkpoint_gas: config.block_checkpoint_gas,
         }
     }
 }
@@ -338,6 +383,22 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexLogsConfig {
+    /// Whether the addresses and topics of logs are indexed.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -362,6 +423,450 @@ impl PruneConfig {
     }
 }
 
+/// Configuration of the cold storage tier of static files.
+///
+/// Static files of block ranges that end at or below `max_block` are moved to the object store and
+/// served from there, with a local cache of the accessed files.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ColdStorageConfig {
+    /// Base URL of the objects in the store, e.g. `https://storage.googleapis.com/<bucket>/<path>`.
+    pub url: String,
+    /// The highest block of the static files that are kept in the store.
+    pub max_block: u64,
+    /// Directory where static files downloaded from the store are cached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_dir: Option<PathBuf>,
+    /// Maximum size in bytes of the static files in the cache directory.
+    ///
+    /// The least recently accessed static files are deleted from the cache once it grows beyond
+    /// this size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cache_size: Option<u64>,
+    /// Name of the environment variable that holds the bearer token for the store.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_token_env: Option<String>,
+    /// Credentials to sign the requests to an S3 compatible store with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3: Option<S3AuthConfig>,
+}
+
+impl ColdStorageConfig {
+    /// Return default cache directory from datadir path.
+    pub fn cache_dir_from_datadir(path: &Path) -> PathBuf {
+        path.join("static_files_cache")
+    }
+
+    /// Returns the bearer token for the store, read from the configured environment variable.
+    pub fn auth_token(&self) -> Result<Option<String>, std::env::VarError> {
+        self.auth_token_env.as_ref().map(std::env::var).transpose()
+    }
+}
+
+/// Configuration of the access to an S3 compatible store.
+///
+/// The credentials are read from environment variables, which default to the ones of the AWS CLI.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct S3AuthConfig {
+    /// Region of the bucket, e.g. `us-east-1`.
+    pub region: String,
+    /// Name of the environment variable that holds the access key id.
+    pub access_key_id_env: String,
+    /// Name of the environment variable that holds the secret access key.
+    pub secret_access_key_env: String,
+    /// Name of the environment variable that holds the optional session token of temporary
+    /// credentials.
+    pub session_token_env: String,
+}
+
+impl Default for S3AuthConfig {
+    fn default() -> Self {
+        Self {
+            region: "us-east-1".to_string(),
+            access_key_id_env: "AWS_ACCESS_KEY_ID".to_string(),
+            secret_access_key_env: "AWS_SECRET_ACCESS_KEY".to_string(),
+            session_token_env: "AWS_SESSION_TOKEN".to_string(),
+        }
+    }
+}
+
+impl S3AuthConfig {
+    /// Returns the access key id and secret access key, read from the configured environment
+    /// variables.
+    pub fn credentials(&self) -> Result<(String, String), std::env::VarError> {
+        Ok((std::env::var(&self.access_key_id_env)?, std::env::var(&self.secret_access_key_env)?))
+    }
+
+    /// Returns the session token, if the configured environment variable is set.
+    pub fn session_token(&self) -> Option<String> {
+        std::env::var(&self.session_token_env).ok()
+    }
+}
+
+/// Configuration for authenticating requests to the http and ws RPC servers.
+///
+/// If configured, every request must carry one of the keys as bearer token in its `Authorization`
+/// header, and can only call the methods the key allows. Changes to the keys are picked up while
+/// the node is running.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct RpcAuthConfig {
+    /// The keys that are accepted by the RPC servers.
+    pub keys: Vec<RpcKeyConfig>,
+}
+
+/// A key that is accepted by the RPC servers, see [`RpcAuthConfig`].
+///
+/// Exactly one of `api_key` and `jwt_secret` must be set.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct RpcKeyConfig {
+    /// Name of the key, used to identify the key in logs.
+    pub name: String,
+    /// The API key that is passed as bearer token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// The hex encoded secret that signs the JWTs that are passed as bearer token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwt_secret: Option<String>,
+    /// The namespaces whose methods the key can call, e.g. `eth` or `debug`.
+    pub namespaces: Vec<String>,
+    /// Additional methods the key can call, e.g. `debug_traceTransaction`.
+    pub methods: Vec<String>,
+}
+
+/// Configuration of the Prometheus metrics endpoint.
+///
+/// At most one of `bearer_token` and `jwt_secret` can be set. If neither is set, the endpoint can
+/// be scraped without authentication.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// The token that scrapers must pass as bearer token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bearer_token: Option<String>,
+    /// The hex encoded secret that signs the JWTs that scrapers must pass as bearer token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwt_secret: Option<String>,
+    /// Rules that are applied in order to the rendered metrics, e.g. to drop metrics or labels
+    /// with a high cardinality.
+    pub relabel: Vec<MetricRelabelConfig>,
+}
+
+/// A rule that rewrites the metrics served by the metrics endpoint, see [`MetricsConfig`].
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct MetricRelabelConfig {
+    /// What the rule does with the matching metrics.
+    pub action: MetricRelabelAction,
+    /// Regular expression that must match the whole name of a metric, e.g. `reth_network_.*`.
+    pub name: String,
+    /// The label that is dropped by [`MetricRelabelAction::DropLabel`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// The action of a [`MetricRelabelConfig`].
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricRelabelAction {
+    /// The matching metrics are not served.
+    #[default]
+    Drop,
+    /// The label is removed from the matching metrics, and the values of the samples that only
+    /// differed in the label are aggregated.
+    DropLabel,
+}
+
+/// Byte size limits of the subsystems of the node that hold data in memory.
+///
+/// If a memory budget is configured, it is split among the consumers that don't have an explicit
+/// limit, in proportion to their weights. Consumers without an explicit limit keep the limits of
+/// their own configuration if no budget is configured.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct LimitsConfig {
+    /// Total number of bytes the consumers may use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_budget_bytes: Option<usize>,
+    /// Limit of the transactions in the pending, basefee and queued subpools.
+    pub transaction_pool: ResourceLimit,
+    /// Limit of the block bodies buffered by the bodies downloader.
+    pub bodies_downloader: ResourceLimit,
+    /// Limit of the size of a single RPC response.
+    pub rpc_responses: ResourceLimit,
+    /// Limit of the executed blocks the engine keeps in memory before persisting them.
+    pub memory_overlay: ResourceLimit,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            memory_budget_bytes: None,
+            transaction_pool: ResourceLimit::with_weight(1),
+            bodies_downloader: ResourceLimit::with_weight(4),
+            rpc_responses: ResourceLimit::with_weight(1),
+            memory_overlay: ResourceLimit::with_weight(2),
+        }
+    }
+}
+
+impl LimitsConfig {
+    /// Allocates the memory budget to the consumers.
+    ///
+    /// Consumers with an explicit limit are allocated that limit, the remaining budget is split
+    /// among the other consumers with a non-zero weight, in proportion to their weights.
+    pub fn allocate(&self) -> Result<MemoryLimits, LimitsConfigError> {
+        let consumers = [
+            self.transaction_pool,
+            self.bodies_downloader,
+            self.rpc_responses,
+            self.memory_overlay,
+        ];
+        let mut limits = consumers.map(|consumer| consumer.max_bytes);
+
+        if let Some(budget) = self.memory_budget_bytes {
+            let explicit = limits.iter().flatten().sum::<usize>();
+            if explicit > budget {
+                return Err(LimitsConfigError::ExplicitLimitsExceedBudget { explicit, budget })
+            }
+
+            let remaining = (budget - explicit) as u128;
+            let total_weight = consumers
+                .iter()
+                .filter(|consumer| consumer.max_bytes.is_none())
+                .map(|consumer| consumer.weight as u128)
+                .sum::<u128>();
+            for (limit, consumer) in limits.iter_mut().zip(consumers) {
+                if limit.is_none() && consumer.weight > 0 {
+                    *limit = Some((remaining * consumer.weight as u128 / total_weight) as usize);
+                }
+            }
+        }
+
+        if let Some(consumer) = limits.iter().position(|limit| *limit == Some(0)) {
+            return Err(LimitsConfigError::ZeroLimit(MemoryLimits::CONSUMERS[consumer]))
+        }
+
+        let [transaction_pool, bodies_downloader, rpc_responses, memory_overlay] = limits;
+        Ok(MemoryLimits { transaction_pool, bodies_downloader, rpc_responses, memory_overlay })
+    }
+}
+
+/// The byte size limit of a consumer of the memory budget, see [`LimitsConfig`].
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct ResourceLimit {
+    /// Explicit limit in bytes, which is taken from the memory budget before it is split.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<usize>,
+    /// Share of the consumer of the memory budget, relative to the weights of the other
+    /// consumers. The consumer is left out of the budget if zero.
+    pub weight: u32,
+}
+
+impl ResourceLimit {
+    /// Creates a limit with the given weight and no explicit limit.
+    pub const fn with_weight(weight: u32) -> Self {
+        Self { max_bytes: None, weight }
+    }
+}
+
+/// The byte size limits allocated to the consumers by [`LimitsConfig::allocate`].
+///
+/// Consumers without a limit use the limits of their own configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryLimits {
+    /// Limit of the transactions in the pending, basefee and queued subpools.
+    pub transaction_pool: Option<usize>,
+    /// Limit of the block bodies buffered by the bodies downloader.
+    pub bodies_downloader: Option<usize>,
+    /// Limit of the size of a single RPC response.
+    pub rpc_responses: Option<usize>,
+    /// Limit of the executed blocks the engine keeps in memory before persisting them.
+    pub memory_overlay: Option<usize>,
+}
+
+impl MemoryLimits {
+    /// Names of the consumers, in the order of [`Self::iter`].
+    pub const CONSUMERS: [&'static str; 4] =
+        ["transaction_pool", "bodies_downloader", "rpc_responses", "memory_overlay"];
+
+    /// Returns the names of the consumers with their limits.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, Option<usize>)> {
+        Self::CONSUMERS.into_iter().zip([
+            self.transaction_pool,
+            self.bodies_downloader,
+            self.rpc_responses,
+            self.memory_overlay,
+        ])
+    }
+}
+
+/// Errors of an invalid [`LimitsConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum LimitsConfigError {
+    /// The explicit limits of the consumers add up to more than the memory budget.
+    #[error(
+        "explicit memory limits of {explicit} bytes exceed the memory budget of {budget} bytes"
+    )]
+    ExplicitLimitsExceedBudget {
+        /// The sum of the explicit limits.
+        explicit: usize,
+        /// The memory budget.
+        budget: usize,
+    },
+    /// A consumer is limited to zero bytes.
+    #[error("memory limit of {0} is zero")]
+    ZeroLimit(&'static str),
+}
+
+/// Configuration of the canonical blocks the engine keeps in memory before persisting them.
+///
+/// The maximum size of the blocks is configured by [`LimitsConfig::memory_overlay`].
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct InMemoryConfig {
+    /// Maximum number of blocks kept in memory before they are persisted.
+    pub max_blocks: u64,
+    /// Number of the most recent blocks that are kept in memory after persisting.
+    pub target_blocks: u64,
+    /// Maximum age of the blocks kept in memory, relative to the timestamp of the canonical head.
+    #[serde(
+        serialize_with = "humantime_serde::serialize",
+        deserialize_with = "deserialize_duration",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_age: Option<Duration>,
+    /// Maximum number of blocks below the canonical head that validated fork blocks are kept on
+    /// disk for, after they were evicted from memory. Zero disables storing fork blocks on disk.
+    pub max_reorg_depth: u64,
+}
+
+impl Default for InMemoryConfig {
+    fn default() -> Self {
+        Self { max_blocks: 3, target_blocks: 2, max_age: None, max_reorg_depth: 64 }
+    }
+}
+
+/// Configuration of the header checks of the consensus and payload validators.
+///
+/// By default, all checks enforce the rules of Ethereum. If `permissive` is set, all checks that
+/// can be relaxed are skipped instead. The remaining fields override individual checks of either
+/// policy.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct ValidationConfig {
+    /// Whether to skip all checks that can be relaxed, unless they're set below.
+    pub permissive: bool,
+    /// The maximum size of the extra data of a header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_extra_data_size: Option<usize>,
+    /// Whether the gas limit of a header must be within the allowed change from the gas limit of
+    /// its parent, and above the minimum gas limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit_bounds: Option<bool>,
+    /// The number of seconds the timestamp of a pre-merge header may be ahead of the local clock.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_future_timestamp_drift: Option<u64>,
+}
+
+/// Configuration of the MDBX database environment.
+///
+/// Changes take effect the next time the database is opened.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// Maximum size of the database file in bytes.
+    ///
+    /// The address space of the memory map is reserved with this size, but no disk space is
+    /// allocated for it. The database can't be opened with a maximum size below its current size.
+    pub max_size: usize,
+    /// Size in bytes by which the database file grows when it's full.
+    ///
+    /// Larger steps reduce how often the file is extended and remapped during sync, at the cost of
+    /// preallocating more disk space.
+    pub growth_step: usize,
+    /// Durability of commits.
+    pub sync_mode: DatabaseSyncMode,
+    /// Whether the OS reads ahead when pages of the database file are read.
+    ///
+    /// Read-ahead speeds up linear scans, e.g. of the initial sync, but slows down random access
+    /// and fills the page cache with unused pages if the database doesn't fit in memory.
+    pub read_ahead: bool,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            // 4 TB
+            max_size: 4 * (1024 * 1024 * 1024 * 1024),
+            // 4 GB
+            growth_step: 4 * (1024 * 1024 * 1024),
+            sync_mode: DatabaseSyncMode::Durable,
+            read_ahead: false,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    /// Validates the geometry of the database.
+    pub const fn validate(&self) -> Result<(), DatabaseConfigError> {
+        if self.growth_step == 0 || self.growth_step > isize::MAX as usize {
+            return Err(DatabaseConfigError::InvalidGrowthStep(self.growth_step))
+        }
+        if self.max_size < self.growth_step {
+            return Err(DatabaseConfigError::MaxSizeBelowGrowthStep {
+                max_size: self.max_size,
+                growth_step: self.growth_step,
+            })
+        }
+        Ok(())
+    }
+}
+
+/// Durability of database commits.
+///
+/// All modes except [`DatabaseSyncMode::Durable`] trade durability for write performance, they
+/// only differ in what is lost on a system crash. A crash of only the node process never loses
+/// committed data.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseSyncMode {
+    /// Data and metadata are flushed to disk on every commit.
+    #[default]
+    Durable,
+    /// Data is flushed on every commit, but the metadata is only flushed with the next commit.
+    ///
+    /// A system crash may roll back the last commit.
+    NoMetaSync,
+    /// Nothing is flushed on commit, the OS writes the data to disk on its own schedule.
+    ///
+    /// A system crash rolls back to the last commit that was flushed, but can't corrupt the
+    /// database.
+    SafeNoSync,
+    /// Nothing is flushed on commit and the pages of previous commits are reused.
+    ///
+    /// A system crash may corrupt the database.
+    UtterlyNoSync,
+}
+
+/// Errors of an invalid [`DatabaseConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum DatabaseConfigError {
+    /// The growth step is zero or too large.
+    #[error("invalid database growth step {0}")]
+    InvalidGrowthStep(usize),
+    /// The maximum size is smaller than the growth step.
+    #[error("database max size {max_size} is smaller than the growth step {growth_step}")]
+    MaxSizeBelowGrowthStep {
+        /// The configured maximum size.
+        max_size: usize,
+        /// The configured growth step.
+        growth_step: usize,
+    },
+}
+
 /// Helper type to support older versions of Duration deserialization.
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
 where
@@ -382,9 +887,14 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{
+        BodiesConfig, Config, DatabaseConfig, DatabaseConfigError, DatabaseSyncMode,
+        DiskSpacePolicy, InMemoryConfig, LimitsConfig, LimitsConfigError, MemoryLimits,
+        MetricRelabelAction, MetricRelabelConfig, ResourceLimit, S3AuthConfig, EXTENSION,
+    };
+    use alloy_primitives::address;
     use reth_network_peers::TrustedPeer;
-    use std::{str::FromStr, time::Duration};
+    use std::{collections::BTreeSet, str::FromStr, time::Duration};
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -763,6 +1273,261 @@ receipts = 'full'
         assert!(err.contains("invalid value: string \"full\""), "{}", err);
     }
 
+    #[test]
+    fn test_transaction_lookup_allowlist() {
+        let s = r"#
+[prune.segments]
+transaction_lookup = 'full'
+transaction_lookup_allowlist = ['0xdac17f958d2ee523a2206206994597c13d831ec7']
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        let prune = conf.prune.clone().unwrap();
+        assert_eq!(
+            prune.segments.transaction_lookup_allowlist,
+            BTreeSet::from([address!("dac17f958d2ee523a2206206994597c13d831ec7")])
+        );
+
+        // the allowlist survives a roundtrip
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf.prune.unwrap(), prune);
+    }
+
+    #[test]
+    fn test_cold_storage_config() {
+        let s = r"#
+[cold_storage]
+url = 'https://storage.googleapis.com/bucket/static_files'
+max_block = 15000000
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        let cold_storage = conf.cold_storage.clone().unwrap();
+        assert_eq!(cold_storage.max_block, 15_000_000);
+        assert_eq!(cold_storage.cache_dir, None);
+        assert_eq!(cold_storage.auth_token().unwrap(), None);
+        assert_eq!(cold_storage.s3, None);
+
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf.cold_storage.unwrap(), cold_storage);
+
+        let s = r"#
+[cold_storage]
+url = 'https://my-bucket.s3.eu-west-1.amazonaws.com/static_files'
+max_block = 15000000
+max_cache_size = 1000000000
+
+[cold_storage.s3]
+region = 'eu-west-1'
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        let cold_storage = conf.cold_storage.clone().unwrap();
+        assert_eq!(cold_storage.max_cache_size, Some(1_000_000_000));
+        assert_eq!(
+            cold_storage.s3,
+            Some(S3AuthConfig { region: "eu-west-1".to_string(), ..Default::default() })
+        );
+
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf.cold_storage.unwrap(), cold_storage);
+    }
+
+    #[test]
+    fn test_rpc_auth_config() {
+        let s = r"#
+[[rpc_auth.keys]]
+name = 'indexer'
+api_key = 'secret'
+namespaces = ['eth', 'net']
+
+[[rpc_auth.keys]]
+name = 'tracer'
+jwt_secret = 'f79ae8046bc11c9927afe911db7143c51a806c4a537cc08e0d37140b0192f430'
+methods = ['debug_traceTransaction']
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        let rpc_auth = conf.rpc_auth.clone().unwrap();
+        assert_eq!(rpc_auth.keys.len(), 2);
+        assert_eq!(rpc_auth.keys[0].api_key.as_deref(), Some("secret"));
+        assert_eq!(rpc_auth.keys[0].namespaces, vec!["eth".to_string(), "net".to_string()]);
+        assert_eq!(rpc_auth.keys[1].api_key, None);
+        assert_eq!(rpc_auth.keys[1].methods, vec!["debug_traceTransaction".to_string()]);
+
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf.rpc_auth.unwrap(), rpc_auth);
+    }
+
+    #[test]
+    fn test_limits_config() {
+        let s = r"#
+[limits]
+memory_budget_bytes = 1000
+
+[limits.rpc_responses]
+max_bytes = 200
+
+[limits.memory_overlay]
+weight = 3
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        assert_eq!(conf.limits.transaction_pool, ResourceLimit::with_weight(1));
+        assert_eq!(conf.limits.rpc_responses, ResourceLimit { max_bytes: Some(200), weight: 0 });
+        assert_eq!(
+            conf.limits.allocate(),
+            Ok(MemoryLimits {
+                transaction_pool: Some(100),
+                bodies_downloader: Some(400),
+                rpc_responses: Some(200),
+                memory_overlay: Some(300),
+            })
+        );
+
+        // without a budget only explicit limits are set
+        let limits = LimitsConfig {
+            rpc_responses: ResourceLimit { max_bytes: Some(200), weight: 1 },
+            ..Default::default()
+        };
+        assert_eq!(
+            limits.allocate(),
+            Ok(MemoryLimits { rpc_responses: Some(200), ..Default::default() })
+        );
+
+        let limits = LimitsConfig { memory_budget_bytes: Some(100), ..limits };
+        assert_eq!(
+            limits.allocate(),
+            Err(LimitsConfigError::ExplicitLimitsExceedBudget { explicit: 200, budget: 100 })
+        );
+
+        let limits = LimitsConfig { memory_budget_bytes: Some(200), ..limits };
+        assert_eq!(limits.allocate(), Err(LimitsConfigError::ZeroLimit("transaction_pool")));
+    }
+
+    #[test]
+    fn test_in_memory_config() {
+        let s = r"#
+[in_memory]
+max_blocks = 64
+max_age = '12m'
+max_reorg_depth = 0
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        assert_eq!(
+            conf.in_memory,
+            InMemoryConfig {
+                max_blocks: 64,
+                target_blocks: 2,
+                max_age: Some(Duration::from_secs(12 * 60)),
+                max_reorg_depth: 0,
+            }
+        );
+
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf.in_memory.max_age, Some(Duration::from_secs(12 * 60)));
+    }
+
+    #[test]
+    fn test_disk_space_check_config() {
+        let s = r"#
+[stages]
+disk_space_check = 'refuse'
+
+[stages.headers]
+downloader_max_concurrent_requests = 100
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        assert_eq!(conf.stages.disk_space_check, DiskSpacePolicy::Refuse);
+        assert_eq!(Config::default().stages.disk_space_check, DiskSpacePolicy::Warn);
+
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf.stages.disk_space_check, DiskSpacePolicy::Refuse);
+    }
+
+    #[test]
+    fn test_metrics_config() {
+        let s = r"#
+[metrics]
+bearer_token = 'secret'
+
+[[metrics.relabel]]
+name = 'reth_network_peer_.*'
+
+[[metrics.relabel]]
+action = 'drop_label'
+name = 'reth_network_.*'
+label = 'peer_id'
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        assert_eq!(conf.metrics.bearer_token.as_deref(), Some("secret"));
+        assert_eq!(conf.metrics.jwt_secret, None);
+        assert_eq!(
+            conf.metrics.relabel,
+            vec![
+                MetricRelabelConfig {
+                    action: MetricRelabelAction::Drop,
+                    name: "reth_network_peer_.*".to_string(),
+                    label: None,
+                },
+                MetricRelabelConfig {
+                    action: MetricRelabelAction::DropLabel,
+                    name: "reth_network_.*".to_string(),
+                    label: Some("peer_id".to_string()),
+                },
+            ]
+        );
+
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf.metrics.relabel.len(), 2);
+    }
+
+    #[test]
+    fn test_database_config() {
+        let s = r"#
+[database]
+max_size = 8796093022208
+sync_mode = 'safe_no_sync'
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        assert_eq!(
+            conf.database,
+            DatabaseConfig {
+                max_size: 8 * 1024 * 1024 * 1024 * 1024,
+                sync_mode: DatabaseSyncMode::SafeNoSync,
+                ..Default::default()
+            }
+        );
+        assert_eq!(conf.database.validate(), Ok(()));
+
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf.database.sync_mode, DatabaseSyncMode::SafeNoSync);
+
+        let invalid = DatabaseConfig { max_size: 1024, ..Default::default() };
+        assert!(matches!(
+            invalid.validate(),
+            Err(DatabaseConfigError::MaxSizeBelowGrowthStep { .. })
+        ));
+        let invalid = DatabaseConfig { growth_step: 0, ..Default::default() };
+        assert_eq!(invalid.validate(), Err(DatabaseConfigError::InvalidGrowthStep(0)));
+    }
+
+    #[test]
+    fn test_bodies_config_max_buffered_blocks_size() {
+        let conf: Config = toml::from_str("[stages.bodies]").unwrap();
+        assert_eq!(conf.stages.bodies.downloader_max_buffered_blocks_size_bytes, None);
+        assert_eq!(
+            conf.stages.bodies.max_buffered_blocks_size_bytes(),
+            BodiesConfig::DEFAULT_MAX_BUFFERED_BLOCKS_SIZE_BYTES
+        );
+
+        // a size equal to the default is still recorded as set
+        let max_buffered_blocks_size = r"#
+[stages.bodies]
+downloader_max_buffered_blocks_size_bytes = 2147483648
+#";
+        let conf: Config = toml::from_str(max_buffered_blocks_size).unwrap();
+        assert_eq!(
+            conf.stages.bodies.downloader_max_buffered_blocks_size_bytes,
+            Some(BodiesConfig::DEFAULT_MAX_BUFFERED_BLOCKS_SIZE_BYTES)
+        );
+    }
+
     #[test]
     fn test_conf_trust_nodes_only() {
         let trusted_nodes_only = r"#
@@ -9,4 +9,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub mod config;
-pub use config::{BodiesConfig, Config, PruneConfig};
+pub use config::{
+    BodiesConfig, ColdStorageConfig, Config, DatabaseConfig, DatabaseConfigError, DatabaseSyncMode,
+    InMemoryConfig, LimitsConfig, LimitsConfigError, MemoryLimits, MetricRelabelAction,
+    MetricRelabelConfig, MetricsConfig, PruneConfig, ResourceLimit, RpcAuthConfig, RpcKeyConfig,
+    S3AuthConfig, ValidationConfig,
+};
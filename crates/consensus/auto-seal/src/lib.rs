@@ -24,9 +24,8 @@ use reth_execution_errors::{
 };
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    eip4844::calculate_excess_blob_gas, proofs, Block, BlockBody, BlockHash, BlockHashOrNumber,
-    BlockNumber, BlockWithSenders, Bloom, Header, Requests, SealedBlock, SealedHeader,
-    TransactionSigned, Withdrawals, B256, U256,
+    proofs, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Bloom,
+    Header, Requests, SealedBlock, SealedHeader, TransactionSigned, Withdrawals, B256, U256,
 };
 use reth_provider::{BlockReaderIdExt, StateProviderFactory, StateRootProvider};
 use reth_revm::database::StateProviderDatabase;
@@ -34,7 +33,7 @@ use reth_transaction_pool::TransactionPool;
 use std::{
     collections::HashMap,
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::{mpsc::UnboundedSender, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tracing::trace;
@@ -107,6 +106,7 @@ pub struct AutoSealBuilder<Client, Pool, Engine: EngineTypes, EvmConfig> {
     storage: Storage,
     to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
     evm_config: EvmConfig,
+    timestamp_interval: Option<u64>,
 }
 
 // === impl AutoSealBuilder ===
@@ -140,6 +140,7 @@ where
             mode,
             to_engine,
             evm_config,
+            timestamp_interval: None,
         }
     }
 
@@ -149,12 +150,30 @@ where
         self
     }
 
+    /// Derives the timestamp of each block from its parent, advanced by the given interval,
+    /// instead of using the current time.
+    ///
+    /// This makes the produced chain reproducible. The interval is at least one second.
+    pub fn deterministic_timestamps(mut self, interval: Duration) -> Self {
+        self.timestamp_interval = Some(interval.as_secs().max(1));
+        self
+    }
+
     /// Consumes the type and returns all components
     #[track_caller]
     pub fn build(
         self,
     ) -> (AutoSealConsensus, AutoSealClient, MiningTask<Client, Pool, EvmConfig, Engine>) {
-        let Self { client, consensus, pool, mode, storage, to_engine, evm_config } = self;
+        let Self {
+            client,
+            consensus,
+            pool,
+            mode,
+            storage,
+            to_engine,
+            evm_config,
+            timestamp_interval,
+        } = self;
         let auto_client = AutoSealClient::new(storage.clone());
         let task = MiningTask::new(
             Arc::clone(&consensus.chain_spec),
@@ -164,6 +183,7 @@ where
             client,
             pool,
             evm_config,
+            timestamp_interval,
         );
         (consensus, auto_client, task)
     }
@@ -256,6 +276,20 @@ impl StorageInner {
         self.hash_to_number.insert(self.best_hash, self.best_block);
     }
 
+    /// Returns the timestamp of the next block.
+    ///
+    /// If an interval is given, this is the timestamp of the current best block advanced by the
+    /// interval, otherwise the current time.
+    pub(crate) fn next_timestamp(&self, interval: Option<u64>) -> u64 {
+        match interval {
+            Some(interval) => {
+                let parent = self.headers.get(&self.best_block).map(|header| header.timestamp);
+                parent.unwrap_or_default() + interval
+            }
+            None => SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        }
+    }
+
     /// Fills in pre-execution header fields based on the current best block and given
     /// transactions.
     pub(crate) fn build_header_template(
@@ -325,8 +359,11 @@ impl StorageInner {
                 }
                 _ => (0, 0),
             };
-            header.excess_blob_gas =
-                Some(calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used))
+            header.excess_blob_gas = Some(
+                chain_spec
+                    .blob_params_at_timestamp(header.timestamp)
+                    .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used),
+            )
         }
 
         header
@@ -338,6 +375,7 @@ impl StorageInner {
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn build_and_execute<Provider, Executor>(
         &mut self,
+        timestamp: u64,
         transactions: Vec<TransactionSigned>,
         ommers: Vec<Header>,
         provider: &Provider,
@@ -348,8 +386,6 @@ impl StorageInner {
         Executor: BlockExecutorProvider,
         Provider: StateProviderFactory,
     {
-        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
-
         // if shanghai is active, include empty withdrawals
         let withdrawals =
             chain_spec.is_shanghai_active_at_timestamp(timestamp).then_some(Withdrawals::default());
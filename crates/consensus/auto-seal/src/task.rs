@@ -42,6 +42,8 @@ pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTyp
     pipe_line_events: Option<EventStream<PipelineEvent>>,
     /// The type used for block execution
     block_executor: Executor,
+    /// The interval between block timestamps, if they are derived from the parent block
+    timestamp_interval: Option<u64>,
 }
 
 // === impl MiningTask ===
@@ -59,6 +61,7 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
         client: Client,
         pool: Pool,
         block_executor: Executor,
+        timestamp_interval: Option<u64>,
     ) -> Self {
         Self {
             chain_spec,
@@ -71,6 +74,7 @@ impl<Executor, Client, Pool: TransactionPool, Engine: EngineTypes>
             queued: Default::default(),
             pipe_line_events: None,
             block_executor,
+            timestamp_interval,
         }
     }
 
@@ -115,6 +119,7 @@ where
                 let pool = this.pool.clone();
                 let events = this.pipe_line_events.take();
                 let executor = this.block_executor.clone();
+                let timestamp_interval = this.timestamp_interval;
 
                 // Create the mining future that creates a block, notifies the engine that drives
                 // the pipeline
@@ -129,8 +134,10 @@ where
                         })
                         .collect();
                     let ommers = vec![];
+                    let timestamp = storage.next_timestamp(timestamp_interval);
 
                     match storage.build_and_execute(
+                        timestamp,
                         transactions.clone(),
                         ommers.clone(),
                         &client,
@@ -4,6 +4,7 @@ use reth_blockchain_tree_api::{
     error::{BlockchainTreeError, CanonicalError, InsertBlockError, InsertBlockErrorKind},
     BlockStatus, BlockValidationKind, BlockchainTreeEngine, CanonicalOutcome, InsertPayloadOk,
 };
+use reth_consensus::ValidationPolicy;
 use reth_db_api::database::Database;
 use reth_engine_primitives::EngineTypes;
 use reth_errors::{BlockValidationError, ProviderResult, RethError, RethResult};
@@ -336,6 +337,12 @@ where
         Ok((this, handle))
     }
 
+    /// Sets the header checks of the payload validator.
+    pub fn with_validation_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.payload_validator = self.payload_validator.with_validation_policy(policy);
+        self
+    }
+
     /// Returns current [`EngineHookContext`] that's used for polling engine hooks.
     fn current_engine_hook_context(&self) -> RethResult<EngineHookContext> {
         Ok(EngineHookContext {
@@ -558,6 +565,8 @@ where
         match status {
             ForkchoiceStatus::Invalid => {}
             ForkchoiceStatus::Valid => {
+                // the FCU head is the canonical head
+                self.blockchain.on_forkchoice_update_acknowledged();
                 // FCU head is valid, we're no longer syncing
                 self.sync_state_updater.update_sync_state(SyncState::Idle);
                 // node's fully synced, clear active download requests
@@ -1082,6 +1091,7 @@ where
         cancun_fields: Option<CancunPayloadFields>,
     ) -> Result<Either<PayloadStatus, SealedBlock>, BeaconOnNewPayloadError> {
         self.metrics.new_payload_messages.increment(1);
+        self.blockchain.on_new_payload_received(payload.timestamp());
 
         // Ensures that the given payload does not violate any consensus rules that concern the
         // block's layout, like:
@@ -3,11 +3,7 @@
 use reth_chainspec::{ChainSpec, EthereumHardforks};
 use reth_consensus::ConsensusError;
 use reth_primitives::{
-    constants::{
-        eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK},
-        MAXIMUM_EXTRA_DATA_SIZE,
-    },
-    eip4844::calculate_excess_blob_gas,
+    constants::{eip4844::DATA_GAS_PER_BLOB, MAXIMUM_EXTRA_DATA_SIZE},
     EthereumHardfork, GotExpected, Header, SealedBlock, SealedHeader,
 };
 
@@ -109,10 +105,14 @@ pub fn validate_block_pre_execution(
 ///  * `blob_gas_used` exists as a header field
 ///  * `excess_blob_gas` exists as a header field
 ///  * `parent_beacon_block_root` exists as a header field
-///  * `blob_gas_used` is less than or equal to `MAX_DATA_GAS_PER_BLOCK`
+///  * `blob_gas_used` is less than or equal to the maximum blob gas per block of the
+///    [`BlobParams`](reth_chainspec::BlobParams) of the chain at the block
 ///  * `blob_gas_used` is a multiple of `DATA_GAS_PER_BLOB`
 ///  * `excess_blob_gas` is a multiple of `DATA_GAS_PER_BLOB`
-pub fn validate_4844_header_standalone(header: &Header) -> Result<(), ConsensusError> {
+pub fn validate_4844_header_standalone(
+    header: &Header,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
     let blob_gas_used = header.blob_gas_used.ok_or(ConsensusError::BlobGasUsedMissing)?;
     let excess_blob_gas = header.excess_blob_gas.ok_or(ConsensusError::ExcessBlobGasMissing)?;
 
@@ -120,10 +120,12 @@ pub fn validate_4844_header_standalone(header: &Header) -> Result<(), ConsensusE
         return Err(ConsensusError::ParentBeaconBlockRootMissing)
     }
 
-    if blob_gas_used > MAX_DATA_GAS_PER_BLOCK {
+    let max_blob_gas_per_block =
+        chain_spec.blob_params_at_timestamp(header.timestamp).max_blob_gas_per_block();
+    if blob_gas_used > max_blob_gas_per_block {
         return Err(ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock {
             blob_gas_used,
-            max_blob_gas_per_block: MAX_DATA_GAS_PER_BLOCK,
+            max_blob_gas_per_block,
         })
     }
 
@@ -135,7 +137,7 @@ pub fn validate_4844_header_standalone(header: &Header) -> Result<(), ConsensusE
     }
 
     // `excess_blob_gas` must also be a multiple of `DATA_GAS_PER_BLOB`. This will be checked later
-    // (via `calculate_excess_blob_gas`), but it doesn't hurt to catch the problem sooner.
+    // (via `validate_against_parent_4844`), but it doesn't hurt to catch the problem sooner.
     if excess_blob_gas % DATA_GAS_PER_BLOB != 0 {
         return Err(ConsensusError::ExcessBlobGasNotMultipleOfBlobGasPerBlob {
             excess_blob_gas,
@@ -239,13 +241,14 @@ pub const fn validate_against_parent_timestamp(
 pub fn validate_against_parent_4844(
     header: &Header,
     parent: &Header,
+    chain_spec: &ChainSpec,
 ) -> Result<(), ConsensusError> {
     // From [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844#header-extension):
     //
     // > For the first post-fork block, both parent.blob_gas_used and parent.excess_blob_gas
     // > are evaluated as 0.
     //
-    // This means in the first post-fork block, calculate_excess_blob_gas will return 0.
+    // This means in the first post-fork block, the expected excess blob gas is 0.
     let parent_blob_gas_used = parent.blob_gas_used.unwrap_or(0);
     let parent_excess_blob_gas = parent.excess_blob_gas.unwrap_or(0);
 
@@ -254,8 +257,10 @@ pub fn validate_against_parent_4844(
     }
     let excess_blob_gas = header.excess_blob_gas.ok_or(ConsensusError::ExcessBlobGasMissing)?;
 
-    let expected_excess_blob_gas =
-        calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used);
+    // the excess blob gas is computed with the target of the blob parameters of the header's fork
+    let expected_excess_blob_gas = chain_spec
+        .blob_params_at_timestamp(header.timestamp)
+        .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used);
     if expected_excess_blob_gas != excess_blob_gas {
         return Err(ConsensusError::ExcessBlobGasDiff {
             diff: GotExpected { got: excess_blob_gas, expected: expected_excess_blob_gas },
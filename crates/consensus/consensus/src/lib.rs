@@ -26,6 +26,9 @@ use alloc::{fmt::Debug, vec::Vec};
 /// A consensus implementation that does nothing.
 pub mod noop;
 
+pub mod policy;
+pub use policy::ValidationPolicy;
+
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking consensus
 pub mod test_utils;
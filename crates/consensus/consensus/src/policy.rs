@@ -0,0 +1,136 @@
+//! Configurable header validation rules.
+
+use crate::ConsensusError;
+use reth_primitives::{
+    constants::{ALLOWED_FUTURE_BLOCK_TIME_SECONDS, MAXIMUM_EXTRA_DATA_SIZE},
+    Header,
+};
+
+/// Toggles individual header checks of the consensus and payload validators.
+///
+/// The [`strict`](Self::strict) policy, which is the default, enforces the rules of Ethereum.
+/// Private networks and test environments can relax them with the
+/// [`permissive`](Self::permissive) policy, or by toggling individual checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    /// The maximum size of the extra data of a header, or `None` to accept any size.
+    pub max_extra_data_size: Option<usize>,
+    /// Whether the gas limit of a header must be within the allowed change from the gas limit of
+    /// its parent, and above the minimum gas limit.
+    pub gas_limit_bounds: bool,
+    /// The number of seconds the timestamp of a pre-merge header may be ahead of the local clock,
+    /// or `None` to accept any timestamp.
+    pub max_future_timestamp_drift: Option<u64>,
+}
+
+impl ValidationPolicy {
+    /// Returns the policy that enforces all checks with the limits of Ethereum.
+    pub const fn strict() -> Self {
+        Self {
+            max_extra_data_size: Some(MAXIMUM_EXTRA_DATA_SIZE),
+            gas_limit_bounds: true,
+            max_future_timestamp_drift: Some(ALLOWED_FUTURE_BLOCK_TIME_SECONDS),
+        }
+    }
+
+    /// Returns the policy that skips all checks that can be toggled.
+    pub const fn permissive() -> Self {
+        Self {
+            max_extra_data_size: None,
+            gas_limit_bounds: false,
+            max_future_timestamp_drift: None,
+        }
+    }
+
+    /// Sets the maximum size of the extra data of a header, or `None` to accept any size.
+    pub const fn with_max_extra_data_size(mut self, max_extra_data_size: Option<usize>) -> Self {
+        self.max_extra_data_size = max_extra_data_size;
+        self
+    }
+
+    /// Sets whether the gas limit of a header is checked against its parent and the minimum gas
+    /// limit.
+    pub const fn with_gas_limit_bounds(mut self, gas_limit_bounds: bool) -> Self {
+        self.gas_limit_bounds = gas_limit_bounds;
+        self
+    }
+
+    /// Sets the number of seconds the timestamp of a pre-merge header may be ahead of the local
+    /// clock, or `None` to accept any timestamp.
+    pub const fn with_max_future_timestamp_drift(
+        mut self,
+        max_future_timestamp_drift: Option<u64>,
+    ) -> Self {
+        self.max_future_timestamp_drift = max_future_timestamp_drift;
+        self
+    }
+
+    /// Validates the size of the extra data of the header.
+    pub fn validate_extra_data(&self, header: &Header) -> Result<(), ConsensusError> {
+        let len = header.extra_data.len();
+        if self.max_extra_data_size.is_some_and(|max| len > max) {
+            return Err(ConsensusError::ExtraDataExceedsMax { len })
+        }
+        Ok(())
+    }
+
+    /// Validates that the timestamp of the header is not too far ahead of the present timestamp.
+    pub fn validate_future_timestamp(
+        &self,
+        header: &Header,
+        present_timestamp: u64,
+    ) -> Result<(), ConsensusError> {
+        if self
+            .max_future_timestamp_drift
+            .is_some_and(|drift| header.timestamp > present_timestamp.saturating_add(drift))
+        {
+            return Err(ConsensusError::TimestampIsInFuture {
+                timestamp: header.timestamp,
+                present_timestamp,
+            })
+        }
+        Ok(())
+    }
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Bytes;
+
+    #[test]
+    fn extra_data_and_timestamp() {
+        let header = Header {
+            extra_data: Bytes::from(vec![0; MAXIMUM_EXTRA_DATA_SIZE + 1]),
+            timestamp: 100 + ALLOWED_FUTURE_BLOCK_TIME_SECONDS + 1,
+            ..Default::default()
+        };
+
+        let strict = ValidationPolicy::strict();
+        assert_eq!(
+            strict.validate_extra_data(&header),
+            Err(ConsensusError::ExtraDataExceedsMax { len: MAXIMUM_EXTRA_DATA_SIZE + 1 })
+        );
+        assert_eq!(
+            strict.validate_future_timestamp(&header, 100),
+            Err(ConsensusError::TimestampIsInFuture {
+                timestamp: header.timestamp,
+                present_timestamp: 100,
+            })
+        );
+        assert_eq!(strict.validate_future_timestamp(&header, 101), Ok(()));
+
+        let permissive = ValidationPolicy::permissive();
+        assert_eq!(permissive.validate_extra_data(&header), Ok(()));
+        assert_eq!(permissive.validate_future_timestamp(&header, 0), Ok(()));
+
+        let relaxed = strict.with_max_extra_data_size(Some(MAXIMUM_EXTRA_DATA_SIZE + 1));
+        assert_eq!(relaxed.validate_extra_data(&header), Ok(()));
+    }
+}
@@ -0,0 +1,184 @@
+use alloy_eips::BlockNumberOrTag;
+use alloy_provider::{Provider, ProviderBuilder};
+use futures::StreamExt;
+use reth_node_api::EngineTypes;
+use reth_node_core::primitives::{Block, SealedBlock, B256};
+use reth_rpc_api::EngineApiClient;
+use reth_rpc_builder::auth::AuthServerHandle;
+use reth_rpc_types::{
+    engine::{ExecutionPayload, ForkchoiceState, PayloadStatusEnum},
+    BlockTransactionsKind, ExecutionPayloadInputV2,
+};
+use reth_rpc_types_compat::engine::payload::block_to_payload;
+use reth_tracing::tracing::{debug, error, info, warn};
+use std::time::Duration;
+
+/// Follows the canonical chain of another execution client, like reth or geth, instead of a
+/// consensus client.
+///
+/// Subscribes to the new heads of the followed node over a `WebSocket` connection, fetches the
+/// full blocks and sends them as new payloads to the engine, which validates and executes them
+/// locally. Every valid new head is then made canonical with a forkchoice update, along with the
+/// safe and finalized blocks of the followed node. Ancestors that are missing locally are
+/// downloaded from the network by the engine.
+///
+/// If the connection fails, the follower reconnects after the reconnect interval.
+#[derive(Debug)]
+pub struct RpcFollower {
+    /// `WebSocket` URL of the followed node.
+    ws_rpc_url: String,
+    /// Handle to the engine API of this node.
+    auth_server: AuthServerHandle,
+    /// Time to wait before reconnecting to the followed node.
+    reconnect_interval: Duration,
+}
+
+impl RpcFollower {
+    /// Creates a new follower of the node with the given `WebSocket` URL.
+    pub const fn new(
+        ws_rpc_url: String,
+        auth_server: AuthServerHandle,
+        reconnect_interval: Duration,
+    ) -> Self {
+        Self { ws_rpc_url, auth_server, reconnect_interval }
+    }
+
+    /// Follows the node, reconnecting whenever the connection fails.
+    pub async fn run<T: EngineTypes>(self) {
+        loop {
+            if let Err(err) = self.follow::<T>().await {
+                warn!(target: "consensus::follower", url = %self.ws_rpc_url, %err, "Connection to followed node failed, reconnecting");
+            }
+            tokio::time::sleep(self.reconnect_interval).await;
+        }
+    }
+
+    /// Connects to the followed node and follows its new heads until the connection fails.
+    async fn follow<T: EngineTypes>(&self) -> eyre::Result<()> {
+        let execution_client = self.auth_server.http_client();
+        let provider = ProviderBuilder::new().on_builtin(&self.ws_rpc_url).await?;
+        let mut heads = provider.subscribe_blocks().await?.into_stream();
+        info!(target: "consensus::follower", url = %self.ws_rpc_url, "Following node");
+
+        // the hash of the block with the given tag, the zero hash if the followed node has none
+        let block_hash_by_tag = |tag: BlockNumberOrTag| {
+            let provider = &provider;
+            async move {
+                Ok::<_, eyre::Report>(
+                    provider
+                        .get_block_by_number(tag, false)
+                        .await?
+                        .and_then(|block| block.header.hash)
+                        .unwrap_or_default(),
+                )
+            }
+        };
+
+        while let Some(head) = heads.next().await {
+            let hash = head.header.hash.ok_or_else(|| eyre::eyre!("new head without hash"))?;
+            let Some(block) = provider.get_block_by_hash(hash, BlockTransactionsKind::Full).await?
+            else {
+                // the head was reorged out before it could be fetched
+                debug!(target: "consensus::follower", %hash, "New head of followed node not found");
+                continue
+            };
+            let block = Block::try_from(block)?.seal(hash);
+            let number = block.number;
+
+            let payload = NewPayload::new(block);
+            let status = payload.send::<T, _>(&execution_client).await?;
+            if let PayloadStatusEnum::Invalid { validation_error } = status {
+                error!(target: "consensus::follower", number, %hash, %validation_error, "Invalid new head of followed node");
+                continue
+            }
+
+            let state = ForkchoiceState {
+                head_block_hash: hash,
+                safe_block_hash: block_hash_by_tag(BlockNumberOrTag::Safe).await?,
+                finalized_block_hash: block_hash_by_tag(BlockNumberOrTag::Finalized).await?,
+            };
+            let status = payload.fork_choice_updated::<T, _>(&execution_client, state).await?;
+            debug!(target: "consensus::follower", number, %hash, ?status, "Followed new head");
+        }
+
+        Err(eyre::eyre!("subscription to new heads closed"))
+    }
+}
+
+/// A block of the followed node as "new payload" of the engine API version of its fork.
+#[derive(Debug)]
+struct NewPayload {
+    payload: ExecutionPayload,
+    versioned_hashes: Vec<B256>,
+    parent_beacon_block_root: Option<B256>,
+}
+
+impl NewPayload {
+    fn new(block: SealedBlock) -> Self {
+        Self {
+            versioned_hashes: block.blob_versioned_hashes().into_iter().copied().collect(),
+            parent_beacon_block_root: block.parent_beacon_block_root,
+            payload: block_to_payload(block),
+        }
+    }
+
+    /// Sends the payload with `engine_newPayload` and returns the status.
+    async fn send<T, C>(&self, client: &C) -> eyre::Result<PayloadStatusEnum>
+    where
+        T: EngineTypes,
+        C: EngineApiClient<T> + Sync,
+    {
+        let parent_beacon_block_root = self.parent_beacon_block_root.unwrap_or_default();
+        let status = match self.payload.clone() {
+            ExecutionPayload::V1(payload) => client.new_payload_v1(payload).await?,
+            ExecutionPayload::V2(payload) => {
+                client
+                    .new_payload_v2(ExecutionPayloadInputV2 {
+                        execution_payload: payload.payload_inner,
+                        withdrawals: Some(payload.withdrawals),
+                    })
+                    .await?
+            }
+            ExecutionPayload::V3(payload) => {
+                client
+                    .new_payload_v3(
+                        payload,
+                        self.versioned_hashes.clone(),
+                        parent_beacon_block_root,
+                    )
+                    .await?
+            }
+            ExecutionPayload::V4(payload) => {
+                client
+                    .new_payload_v4(
+                        payload,
+                        self.versioned_hashes.clone(),
+                        parent_beacon_block_root,
+                    )
+                    .await?
+            }
+        };
+        Ok(status.status)
+    }
+
+    /// Sends the forkchoice state with the `engine_forkchoiceUpdated` version of the fork of the
+    /// payload and returns the status.
+    async fn fork_choice_updated<T, C>(
+        &self,
+        client: &C,
+        state: ForkchoiceState,
+    ) -> eyre::Result<PayloadStatusEnum>
+    where
+        T: EngineTypes,
+        C: EngineApiClient<T> + Sync,
+    {
+        let updated = match self.payload {
+            ExecutionPayload::V1(_) => client.fork_choice_updated_v1(state, None).await?,
+            ExecutionPayload::V2(_) => client.fork_choice_updated_v2(state, None).await?,
+            ExecutionPayload::V3(_) | ExecutionPayload::V4(_) => {
+                client.fork_choice_updated_v3(state, None).await?
+            }
+        };
+        Ok(updated.payload_status.status)
+    }
+}
@@ -3,6 +3,9 @@
 //! This is a worker that sends FCUs and new payloads by fetching recent blocks from an external
 //! provider like Etherscan or an RPC endpoint. This allows to quickly test the execution client
 //! without running a consensus node.
+//!
+//! The [`RpcFollower`] follows the canonical chain of another execution client the same way, so
+//! that additional validating nodes can run without a consensus node of their own.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -13,7 +16,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod client;
+mod follower;
 mod providers;
 
 pub use client::{BlockProvider, DebugConsensusClient};
+pub use follower::RpcFollower;
 pub use providers::{EtherscanBlockProvider, RpcBlockProvider};
@@ -11,6 +11,7 @@ use reth_engine_tree::{
     engine::{EngineApiRequest, EngineApiRequestHandler, EngineHandler},
     persistence::PersistenceHandle,
     tree::{EngineApiTreeHandler, TreeConfig},
+    warming::SenderCache,
 };
 pub use reth_engine_tree::{
     chain::{ChainEvent, ChainOrchestrator},
@@ -22,7 +23,7 @@ use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_validator::ExecutionPayloadValidator;
 use reth_provider::{providers::BlockchainProvider2, ProviderFactory};
 use reth_prune::Pruner;
-use reth_stages_api::Pipeline;
+use reth_stages_api::{MetricEventsSender, Pipeline};
 use reth_tasks::TaskSpawner;
 use std::{
     marker::PhantomData,
@@ -55,6 +56,8 @@ where
     T: EngineTypes,
 {
     orchestrator: EngineServiceType<DB, Client, T>,
+    /// Handle to the persistence service of the engine.
+    persistence_handle: PersistenceHandle,
     _marker: PhantomData<E>,
 }
 
@@ -78,13 +81,17 @@ where
         provider: ProviderFactory<DB>,
         blockchain_db: BlockchainProvider2<DB>,
         pruner: Pruner<DB, ProviderFactory<DB>>,
+        sync_metrics_tx: MetricEventsSender,
         payload_builder: PayloadBuilderHandle<T>,
         tree_config: TreeConfig,
+        sender_cache: SenderCache,
     ) -> Self {
         let downloader = BasicBlockDownloader::new(client, consensus.clone());
 
-        let persistence_handle = PersistenceHandle::spawn_service(provider, pruner);
-        let payload_validator = ExecutionPayloadValidator::new(chain_spec);
+        let persistence_handle =
+            PersistenceHandle::spawn_service(provider, pruner, sync_metrics_tx);
+        let payload_validator = ExecutionPayloadValidator::new(chain_spec)
+            .with_validation_policy(tree_config.validation_policy());
 
         let canonical_in_memory_state = blockchain_db.canonical_in_memory_state();
 
@@ -93,10 +100,11 @@ where
             executor_factory,
             consensus,
             payload_validator,
-            persistence_handle,
+            persistence_handle.clone(),
             payload_builder,
             canonical_in_memory_state,
             tree_config,
+            sender_cache,
         );
 
         let engine_handler = EngineApiRequestHandler::new(to_tree_tx, from_tree);
@@ -106,6 +114,7 @@ where
 
         Self {
             orchestrator: ChainOrchestrator::new(handler, backfill_sync),
+            persistence_handle,
             _marker: Default::default(),
         }
     }
@@ -114,6 +123,11 @@ where
     pub fn orchestrator_mut(&mut self) -> &mut EngineServiceType<DB, Client, T> {
         &mut self.orchestrator
     }
+
+    /// Returns the handle to the persistence service of the engine.
+    pub const fn persistence_handle(&self) -> &PersistenceHandle {
+        &self.persistence_handle
+    }
 }
 
 impl<DB, Client, E, T> Stream for EngineService<DB, Client, E, T>
@@ -174,14 +188,18 @@ mod tests {
         let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
 
         let executor_factory = EthExecutorProvider::ethereum(chain_spec.clone());
-        let blockchain_db =
-            BlockchainProvider2::with_latest(provider_factory.clone(), SealedHeader::default())
-                .unwrap();
+        let blockchain_db = BlockchainProvider2::with_latest(
+            provider_factory.clone(),
+            SealedHeader::default(),
+            Default::default(),
+        )
+        .unwrap();
 
         let (_tx, rx) = watch::channel(FinishedExExHeight::NoExExs);
         let pruner =
             Pruner::<_, ProviderFactory<_>>::new(provider_factory.clone(), vec![], 0, 0, None, rx);
 
+        let (sync_metrics_tx, _sync_metrics_rx) = unbounded_channel();
         let (tx, _rx) = unbounded_channel();
         let _eth_service = EngineService::new(
             consensus,
@@ -194,8 +212,10 @@ mod tests {
             provider_factory,
             blockchain_db,
             pruner,
+            sync_metrics_tx,
             PayloadBuilderHandle::new(tx),
             TreeConfig::default(),
+            SenderCache::default(),
         );
     }
 }
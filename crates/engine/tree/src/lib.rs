@@ -102,6 +102,8 @@ pub mod metrics;
 pub mod persistence;
 /// Support for interacting with the blockchain tree.
 pub mod tree;
+/// Support for warming caches with pending transactions between blocks.
+pub mod warming;
 
 /// Test utilities.
 #[cfg(any(test, feature = "test-utils"))]
@@ -2,11 +2,20 @@
 
 use reth_chain_state::ExecutedBlock;
 use reth_db::Database;
+use reth_db_api::transaction::DbTx;
 use reth_errors::ProviderError;
-use reth_primitives::B256;
-use reth_provider::{writer::UnifiedStorageWriter, ProviderFactory, StaticFileProviderFactory};
+use reth_metrics::{metrics::Histogram, Metrics};
+use reth_primitives::{BlockNumber, B256};
+use reth_provider::{
+    writer::UnifiedStorageWriter, ExecutionOutcome, ForkBlockWriter, ProviderFactory,
+    ProviderResult, StaticFileProviderFactory,
+};
 use reth_prune::{Pruner, PrunerError, PrunerOutput};
-use std::sync::mpsc::{Receiver, SendError, Sender};
+use reth_stages_api::{MetricEvent, MetricEventsSender, StateSize, StateSizeDelta};
+use std::{
+    sync::mpsc::{Receiver, SendError, Sender},
+    time::Instant,
+};
 use thiserror::Error;
 use tokio::sync::oneshot;
 use tracing::{debug, error};
@@ -26,16 +35,77 @@ pub struct PersistenceService<DB> {
     incoming: Receiver<PersistenceAction>,
     /// The pruner
     pruner: Pruner<DB, ProviderFactory<DB>>,
+    /// Metrics for the persistence service.
+    metrics: PersistenceMetrics,
+    /// Sender of the state size metrics of the saved blocks.
+    sync_metrics_tx: MetricEventsSender,
+    /// Whether the size of the state was read since the service started or removed blocks.
+    state_size_reported: bool,
 }
 
 impl<DB: Database> PersistenceService<DB> {
     /// Create a new persistence service
-    pub const fn new(
+    pub fn new(
         provider: ProviderFactory<DB>,
         incoming: Receiver<PersistenceAction>,
         pruner: Pruner<DB, ProviderFactory<DB>>,
+        sync_metrics_tx: MetricEventsSender,
     ) -> Self {
-        Self { provider, incoming, pruner }
+        Self {
+            provider,
+            incoming,
+            pruner,
+            metrics: PersistenceMetrics::default(),
+            sync_metrics_tx,
+            state_size_reported: false,
+        }
+    }
+
+    /// Stores the given fork blocks and removes the stored fork blocks below the given block
+    /// number.
+    fn save_fork_blocks(
+        &self,
+        blocks: Vec<ExecutedBlock>,
+        remove_below: BlockNumber,
+    ) -> ProviderResult<()> {
+        let provider_rw = self.provider.provider_rw()?;
+        let saved = blocks.len();
+        provider_rw.insert_fork_blocks(blocks)?;
+        let removed = provider_rw.remove_fork_blocks_below(remove_below)?;
+        provider_rw.commit()?;
+
+        debug!(target: "tree::persistence", saved, removed, "Saved fork blocks");
+        Ok(())
+    }
+
+    /// Reports the size of the state and its change by the given blocks to the sync metrics, before
+    /// the blocks are written to the database.
+    fn report_state_size<TX: DbTx>(
+        &mut self,
+        tx: &TX,
+        blocks: &[ExecutedBlock],
+    ) -> ProviderResult<()> {
+        let Some((first, rest)) = blocks.split_first() else { return Ok(()) };
+
+        if !self.state_size_reported {
+            let _ = self
+                .sync_metrics_tx
+                .send(MetricEvent::ExecutionStageStateSizeTotal(StateSize::from_tx(tx)?));
+            self.state_size_reported = true;
+        }
+
+        // the changes of the blocks are merged, so that the storage wiped by selfdestructs is
+        // counted from the state in the database
+        let mut bundle = first.execution_outcome().state().clone();
+        for block in rest {
+            bundle.extend(block.execution_outcome().state().clone());
+        }
+        let outcome =
+            ExecutionOutcome::new(bundle, Default::default(), first.block().number, Vec::new());
+        let _ = self.sync_metrics_tx.send(MetricEvent::ExecutionStageStateSize(
+            StateSizeDelta::from_execution_outcome(tx, &outcome)?,
+        ));
+        Ok(())
     }
 
     /// Prunes block data before the given block hash according to the configured prune
@@ -58,12 +128,18 @@ where
         while let Ok(action) = self.incoming.recv() {
             match action {
                 PersistenceAction::RemoveBlocksAbove(new_tip_num, sender) => {
+                    let start = Instant::now();
                     let provider_rw = self.provider.provider_rw()?;
                     let sf_provider = self.provider.static_file_provider();
 
                     UnifiedStorageWriter::from(&provider_rw, &sf_provider)
                         .remove_blocks_above(new_tip_num)?;
                     UnifiedStorageWriter::commit_unwind(provider_rw, sf_provider)?;
+                    self.metrics.remove_blocks_above_duration.record(start.elapsed());
+
+                    // the state size metrics don't track removed blocks, so the size of the state
+                    // is read again
+                    self.state_size_reported = false;
 
                     // we ignore the error because the caller may or may not care about the result
                     let _ = sender.send(());
@@ -76,28 +152,57 @@ where
 
                     let last_block_hash = last_block.block().hash();
 
+                    let start = Instant::now();
                     let provider_rw = self.provider.provider_rw()?;
                     let static_file_provider = self.provider.static_file_provider();
 
+                    self.report_state_size(provider_rw.tx_ref(), &blocks)?;
                     UnifiedStorageWriter::from(&provider_rw, &static_file_provider)
                         .save_blocks(&blocks)?;
                     UnifiedStorageWriter::commit(provider_rw, static_file_provider)?;
+                    self.metrics.save_blocks_duration.record(start.elapsed());
 
                     // we ignore the error because the caller may or may not care about the result
                     let _ = sender.send(Some(last_block_hash));
                 }
+                PersistenceAction::SaveForkBlocks { blocks, remove_below } => {
+                    // fork blocks are only kept to avoid downloading them again, so failing to
+                    // store them is not fatal
+                    if let Err(err) = self.save_fork_blocks(blocks, remove_below) {
+                        error!(target: "engine::persistence", ?err, "Failed to save fork blocks");
+                    }
+                }
                 PersistenceAction::PruneBefore(block_num, sender) => {
                     let res = self.prune_before(block_num)?;
 
                     // we ignore the error because the caller may or may not care about the result
                     let _ = sender.send(res);
                 }
+                PersistenceAction::Shutdown(sender) => {
+                    // actions are performed in order, so all previous actions are done, and the
+                    // receiver is dropped first so that no more actions can be sent
+                    drop(self);
+                    let _ = sender.send(());
+                    return Ok(())
+                }
             }
         }
         Ok(())
     }
 }
 
+/// Metrics for the persistence service.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.persistence")]
+struct PersistenceMetrics {
+    /// Time spent writing the blocks handed over by the engine to the database and static files,
+    /// including the commit.
+    save_blocks_duration: Histogram,
+    /// Time spent removing the blocks above a block from the database and static files, including
+    /// the commit.
+    remove_blocks_above_duration: Histogram,
+}
+
 /// One of the errors that can happen when using the persistence service.
 #[derive(Debug, Error)]
 pub enum PersistenceError {
@@ -129,6 +234,19 @@ pub enum PersistenceAction {
     /// Prune associated block data before the given block number, according to already-configured
     /// prune modes.
     PruneBefore(u64, oneshot::Sender<PrunerOutput>),
+
+    /// Stores validated blocks that are not part of the canonical chain, and removes the stored
+    /// fork blocks below the given block number.
+    SaveForkBlocks {
+        /// The fork blocks to store.
+        blocks: Vec<ExecutedBlock>,
+        /// The block number below which stored fork blocks are removed.
+        remove_below: BlockNumber,
+    },
+
+    /// Notifies the sender once all previously sent actions have been performed, and stops the
+    /// service so that no more blocks are written while the node shuts down.
+    Shutdown(oneshot::Sender<()>),
 }
 
 /// A handle to the persistence service
@@ -148,6 +266,7 @@ impl PersistenceHandle {
     pub fn spawn_service<DB: Database + 'static>(
         provider_factory: ProviderFactory<DB>,
         pruner: Pruner<DB, ProviderFactory<DB>>,
+        sync_metrics_tx: MetricEventsSender,
     ) -> Self {
         // create the initial channels
         let (db_service_tx, db_service_rx) = std::sync::mpsc::channel();
@@ -156,7 +275,8 @@ impl PersistenceHandle {
         let persistence_handle = Self::new(db_service_tx);
 
         // spawn the persistence service
-        let db_service = PersistenceService::new(provider_factory, db_service_rx, pruner);
+        let db_service =
+            PersistenceService::new(provider_factory, db_service_rx, pruner, sync_metrics_tx);
         std::thread::Builder::new()
             .name("Persistence Service".to_string())
             .spawn(|| {
@@ -206,6 +326,16 @@ impl PersistenceHandle {
         self.send_action(PersistenceAction::RemoveBlocksAbove(block_num, tx))
     }
 
+    /// Tells the persistence service to store the given fork blocks, and to remove the stored fork
+    /// blocks below the given block number.
+    pub fn save_fork_blocks(
+        &self,
+        blocks: Vec<ExecutedBlock>,
+        remove_below: BlockNumber,
+    ) -> Result<(), SendError<PersistenceAction>> {
+        self.send_action(PersistenceAction::SaveForkBlocks { blocks, remove_below })
+    }
+
     /// Tells the persistence service to remove block data before the given hash, according to the
     /// configured prune config.
     ///
@@ -217,6 +347,14 @@ impl PersistenceHandle {
     ) -> Result<(), SendError<PersistenceAction>> {
         self.send_action(PersistenceAction::PruneBefore(block_num, tx))
     }
+
+    /// Tells the persistence service to perform all previously sent actions and stop.
+    ///
+    /// When the actions are performed, `()` is returned in the receiver end of the sender argument.
+    /// Actions that are sent afterwards fail to send.
+    pub fn shutdown(&self, tx: oneshot::Sender<()>) -> Result<(), SendError<PersistenceAction>> {
+        self.send_action(PersistenceAction::Shutdown(tx))
+    }
 }
 
 #[cfg(test)]
@@ -227,8 +365,14 @@ mod tests {
     use reth_primitives::B256;
     use reth_provider::{test_utils::create_test_provider_factory, ProviderFactory};
     use reth_prune::Pruner;
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
     fn default_persistence_handle() -> PersistenceHandle {
+        persistence_handle_with_sync_metrics().0
+    }
+
+    fn persistence_handle_with_sync_metrics() -> (PersistenceHandle, UnboundedReceiver<MetricEvent>)
+    {
         let provider = create_test_provider_factory();
 
         let (_finished_exex_height_tx, finished_exex_height_rx) =
@@ -243,7 +387,8 @@ mod tests {
             finished_exex_height_rx,
         );
 
-        PersistenceHandle::spawn_service(provider, pruner)
+        let (sync_metrics_tx, sync_metrics_rx) = unbounded_channel();
+        (PersistenceHandle::spawn_service(provider, pruner, sync_metrics_tx), sync_metrics_rx)
     }
 
     #[tokio::test]
@@ -260,6 +405,27 @@ mod tests {
         assert_eq!(hash, None);
     }
 
+    #[tokio::test]
+    async fn test_shutdown_after_save_blocks() {
+        reth_tracing::init_test_tracing();
+        let persistence_handle = default_persistence_handle();
+
+        let executed =
+            TestBlockBuilder::default().get_executed_block_with_number(0, B256::random());
+        let (save_tx, mut save_rx) = oneshot::channel();
+        persistence_handle.save_blocks(vec![executed], save_tx).unwrap();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        persistence_handle.shutdown(shutdown_tx).unwrap();
+
+        // the blocks are saved before the shutdown is acknowledged
+        shutdown_rx.await.unwrap();
+        assert!(save_rx.try_recv().unwrap().is_some());
+
+        // no more blocks are persisted after the shutdown
+        let (tx, _rx) = oneshot::channel();
+        assert!(persistence_handle.save_blocks(vec![], tx).is_err());
+    }
+
     #[tokio::test]
     async fn test_save_blocks_single_block() {
         reth_tracing::init_test_tracing();
@@ -318,4 +484,34 @@ mod tests {
             assert_eq!(last_hash, actual_hash);
         }
     }
+
+    #[tokio::test]
+    async fn test_save_blocks_reports_state_size() {
+        reth_tracing::init_test_tracing();
+        let (persistence_handle, mut sync_metrics_rx) = persistence_handle_with_sync_metrics();
+
+        let mut test_block_builder = TestBlockBuilder::default();
+        for range in [0..2, 2..4] {
+            let blocks = test_block_builder.get_executed_blocks(range).collect::<Vec<_>>();
+            let (tx, rx) = oneshot::channel();
+            persistence_handle.save_blocks(blocks, tx).unwrap();
+            rx.await.unwrap().unwrap();
+        }
+
+        // the size of the state is read once, and then maintained from the changes of every call
+        assert!(matches!(
+            sync_metrics_rx.try_recv(),
+            Ok(MetricEvent::ExecutionStageStateSizeTotal(StateSize {
+                accounts: 0,
+                storage_slots: 0
+            }))
+        ));
+        for _ in 0..2 {
+            assert!(matches!(
+                sync_metrics_rx.try_recv(),
+                Ok(MetricEvent::ExecutionStageStateSize(_))
+            ));
+        }
+        assert!(sync_metrics_rx.try_recv().is_err());
+    }
 }
@@ -1,11 +1,17 @@
 //! Engine tree configuration.
 
-const DEFAULT_PERSISTENCE_THRESHOLD: u64 = 3;
-const DEFAULT_MEMORY_BLOCK_BUFFER_TARGET: u64 = 2;
+use reth_chain_state::{
+    InMemoryRetention, DEFAULT_MAX_IN_MEMORY_BLOCKS as DEFAULT_PERSISTENCE_THRESHOLD,
+    DEFAULT_TARGET_IN_MEMORY_BLOCKS as DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+};
+use reth_consensus::ValidationPolicy;
+use std::time::Duration;
+
 const DEFAULT_BLOCK_BUFFER_LIMIT: u32 = 256;
 const DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH: u32 = 256;
 
 const DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE: usize = 4;
+const DEFAULT_MAX_REORG_DEPTH: u64 = 64;
 
 /// The configuration of the engine tree.
 #[derive(Debug)]
@@ -23,6 +29,17 @@ pub struct TreeConfig {
     max_invalid_header_cache_length: u32,
     /// Maximum number of blocks to execute sequentially in a batch.
     max_execute_block_batch_size: usize,
+    /// Maximum estimated size in bytes of the blocks kept in memory before persistence is
+    /// triggered, regardless of the persistence threshold.
+    max_in_memory_bytes: Option<usize>,
+    /// Maximum age of the blocks kept in memory, relative to the timestamp of the canonical head,
+    /// before persistence is triggered.
+    max_in_memory_age: Option<Duration>,
+    /// Maximum number of blocks below the canonical head that validated fork blocks are kept on
+    /// disk for, after they were evicted from memory.
+    max_reorg_depth: u64,
+    /// Header checks of the payload validator.
+    validation_policy: ValidationPolicy,
 }
 
 impl Default for TreeConfig {
@@ -33,6 +50,10 @@ impl Default for TreeConfig {
             block_buffer_limit: DEFAULT_BLOCK_BUFFER_LIMIT,
             max_invalid_header_cache_length: DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH,
             max_execute_block_batch_size: DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE,
+            max_in_memory_bytes: None,
+            max_in_memory_age: None,
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
+            validation_policy: ValidationPolicy::strict(),
         }
     }
 }
@@ -52,6 +73,10 @@ impl TreeConfig {
             block_buffer_limit,
             max_invalid_header_cache_length,
             max_execute_block_batch_size,
+            max_in_memory_bytes: None,
+            max_in_memory_age: None,
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
+            validation_policy: ValidationPolicy::strict(),
         }
     }
 
@@ -80,6 +105,26 @@ impl TreeConfig {
         self.max_execute_block_batch_size
     }
 
+    /// Return the maximum size of the blocks kept in memory.
+    pub const fn max_in_memory_bytes(&self) -> Option<usize> {
+        self.max_in_memory_bytes
+    }
+
+    /// Return the maximum age of the blocks kept in memory.
+    pub const fn max_in_memory_age(&self) -> Option<Duration> {
+        self.max_in_memory_age
+    }
+
+    /// Return the maximum depth below the canonical head of the fork blocks kept on disk.
+    pub const fn max_reorg_depth(&self) -> u64 {
+        self.max_reorg_depth
+    }
+
+    /// Return the header checks of the payload validator.
+    pub const fn validation_policy(&self) -> ValidationPolicy {
+        self.validation_policy
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -118,4 +163,42 @@ impl TreeConfig {
         self.max_execute_block_batch_size = max_execute_block_batch_size;
         self
     }
+
+    /// Setter for maximum size of the blocks kept in memory.
+    pub const fn with_max_in_memory_bytes(mut self, max_in_memory_bytes: Option<usize>) -> Self {
+        self.max_in_memory_bytes = max_in_memory_bytes;
+        self
+    }
+
+    /// Setter for maximum age of the blocks kept in memory.
+    pub const fn with_max_in_memory_age(mut self, max_in_memory_age: Option<Duration>) -> Self {
+        self.max_in_memory_age = max_in_memory_age;
+        self
+    }
+
+    /// Sets the persistence threshold, memory block buffer target, and maximum size and age of
+    /// the blocks kept in memory from the retention policy of the canonical in-memory state.
+    pub fn with_in_memory_retention(self, retention: InMemoryRetention) -> Self {
+        self.with_persistence_threshold(retention.max_blocks())
+            .with_memory_block_buffer_target(retention.target_blocks())
+            .with_max_in_memory_bytes(retention.max_bytes())
+            .with_max_in_memory_age(retention.max_age())
+    }
+
+    /// Setter for the maximum depth below the canonical head of the fork blocks kept on disk.
+    ///
+    /// Fork blocks are only kept in memory until the canonical blocks at their height are
+    /// persisted. After that, they're stored on disk, so that a reorg to a fork within this depth
+    /// doesn't require downloading its blocks again. Setting this to zero disables storing fork
+    /// blocks on disk.
+    pub const fn with_max_reorg_depth(mut self, max_reorg_depth: u64) -> Self {
+        self.max_reorg_depth = max_reorg_depth;
+        self
+    }
+
+    /// Setter for the header checks of the payload validator.
+    pub const fn with_validation_policy(mut self, validation_policy: ValidationPolicy) -> Self {
+        self.validation_policy = validation_policy;
+        self
+    }
 }
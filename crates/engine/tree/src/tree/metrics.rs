@@ -1,7 +1,10 @@
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    memory::MemoryConsumerMetrics,
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
+use reth_primitives::{constants::GWEI_TO_WEI, U256};
+use reth_provider::ProposerRevenue;
 
 /// Metrics for the `EngineApi`.
 #[derive(Metrics)]
@@ -15,5 +18,73 @@ pub(crate) struct EngineApiMetrics {
     pub(crate) forkchoice_updated_messages: Counter,
     /// The total count of new payload messages received.
     pub(crate) new_payload_messages: Counter,
-    // TODO add latency metrics
+    /// Metrics for the phases of forkchoice updated handling.
+    #[metric(skip)]
+    pub(crate) forkchoice_updated: ForkchoiceUpdatedMetrics,
+    /// Metrics for the value delivered to the proposers of executed blocks.
+    #[metric(skip)]
+    pub(crate) proposer_revenue: ProposerRevenueMetrics,
+    /// Memory usage metrics of the executed blocks.
+    #[metric(skip)]
+    pub(crate) memory: OverlayMemoryMetrics,
+}
+
+/// Memory usage metrics of the executed blocks, see [`MemoryConsumerMetrics::MEMORY_OVERLAY`].
+pub(crate) struct OverlayMemoryMetrics {
+    /// The estimated size of the canonical executed blocks in memory.
+    pub(crate) usage_bytes: Gauge,
+}
+
+impl Default for OverlayMemoryMetrics {
+    fn default() -> Self {
+        let metrics = MemoryConsumerMetrics::consumer(MemoryConsumerMetrics::MEMORY_OVERLAY);
+        Self { usage_bytes: metrics.usage_bytes }
+    }
+}
+
+impl std::fmt::Debug for OverlayMemoryMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OverlayMemoryMetrics").finish_non_exhaustive()
+    }
+}
+
+/// Metrics for the phases of forkchoice updated handling.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.beacon.forkchoice_updated")]
+pub(crate) struct ForkchoiceUpdatedMetrics {
+    /// Time from receiving a forkchoice update until the response is sent.
+    pub(crate) latency: Histogram,
+    /// Time spent making the head block canonical and updating the safe and finalized blocks.
+    pub(crate) canonicalization_duration: Histogram,
+    /// Time spent validating the payload attributes and starting the payload job.
+    pub(crate) payload_attributes_duration: Histogram,
+}
+
+/// Metrics for the value delivered to the proposers of executed blocks, in gwei.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.beacon.proposer_revenue")]
+pub(crate) struct ProposerRevenueMetrics {
+    /// The value delivered to the proposer, either the explicit payment of the fee recipient or
+    /// the revenue of the fee recipient.
+    pub(crate) proposer_value: Histogram,
+    /// The increase of the balance of the fee recipient.
+    pub(crate) fee_recipient_revenue: Histogram,
+    /// The value delivered to the proposer of the last executed block.
+    pub(crate) last_proposer_value: Gauge,
+    /// The number of executed blocks in which the fee recipient paid the proposer.
+    pub(crate) payments: Counter,
+}
+
+impl ProposerRevenueMetrics {
+    /// Records the proposer revenue of an executed block.
+    pub(crate) fn record(&self, revenue: &ProposerRevenue) {
+        let gwei = |value: U256| (value / U256::from(GWEI_TO_WEI)).saturating_to::<u64>() as f64;
+        let proposer_value = gwei(revenue.proposer_value());
+        self.proposer_value.record(proposer_value);
+        self.last_proposer_value.set(proposer_value);
+        self.fee_recipient_revenue.record(gwei(revenue.fee_recipient_revenue));
+        if revenue.payment.is_some() {
+            self.payments.increment(1);
+        }
+    }
 }
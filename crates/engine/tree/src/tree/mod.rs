@@ -3,10 +3,11 @@ use crate::{
     chain::FromOrchestrator,
     engine::{DownloadRequest, EngineApiEvent, FromEngine},
     persistence::PersistenceHandle,
+    warming::SenderCache,
 };
 use reth_beacon_consensus::{
-    BeaconConsensusEngineEvent, BeaconEngineMessage, ForkchoiceStateTracker, InvalidHeaderCache,
-    OnForkChoiceUpdated, MIN_BLOCKS_FOR_PIPELINE_RUN,
+    BeaconConsensusEngineEvent, BeaconEngineMessage, ForkchoiceStateTracker, ForkchoiceStatus,
+    InvalidHeaderCache, OnForkChoiceUpdated, MIN_BLOCKS_FOR_PIPELINE_RUN,
 };
 use reth_blockchain_tree::{
     error::{InsertBlockErrorKindTwo, InsertBlockErrorTwo, InsertBlockFatalError},
@@ -28,8 +29,8 @@ use reth_primitives::{
     SealedBlockWithSenders, SealedHeader, B256, U256,
 };
 use reth_provider::{
-    BlockReader, ExecutionOutcome, ProviderError, StateProviderBox, StateProviderFactory,
-    StateRootProvider,
+    BlockReader, ExecutionOutcome, ForkBlockReader, ProposerRevenue, ProviderError,
+    StateProviderBox, StateProviderFactory, StateRootProvider,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_types::{
@@ -102,6 +103,20 @@ impl TreeState {
         self.blocks_by_hash.len()
     }
 
+    /// Returns the estimated size in bytes of the canonical executed blocks, see
+    /// [`ExecutedBlock::size`].
+    ///
+    /// Fork blocks are not counted, since persisting the canonical blocks doesn't free them.
+    fn canonical_blocks_size(&self) -> usize {
+        let mut size = 0;
+        let mut current_hash = self.canonical_block_hash();
+        while let Some(block) = self.blocks_by_hash.get(&current_hash) {
+            size += block.size();
+            current_hash = block.block.parent_hash;
+        }
+        size
+    }
+
     /// Returns the block by hash.
     fn block_by_hash(&self, hash: B256) -> Option<Arc<SealedBlock>> {
         self.blocks_by_hash.get(&hash).map(|b| b.block.clone())
@@ -167,12 +182,23 @@ impl TreeState {
     }
 
     /// Remove all blocks up to the given block number.
-    pub(crate) fn remove_before(&mut self, upper_bound: Bound<BlockNumber>) {
+    ///
+    /// Returns the removed blocks that are not part of the canonical chain.
+    pub(crate) fn remove_before(&mut self, upper_bound: Bound<BlockNumber>) -> Vec<ExecutedBlock> {
         let mut numbers_to_remove = Vec::new();
         for (&number, _) in self.blocks_by_number.range((Bound::Unbounded, upper_bound)) {
             numbers_to_remove.push(number);
         }
 
+        // collect the canonical blocks in memory, to tell them apart from the fork blocks
+        let mut canonical = HashSet::new();
+        let mut current_hash = self.canonical_block_hash();
+        while let Some(block) = self.blocks_by_hash.get(&current_hash) {
+            canonical.insert(current_hash);
+            current_hash = block.block.parent_hash;
+        }
+
+        let mut fork_blocks = Vec::new();
         for number in numbers_to_remove {
             if let Some(blocks) = self.blocks_by_number.remove(&number) {
                 for block in blocks {
@@ -189,9 +215,15 @@ impl TreeState {
                     }
 
                     self.parent_to_child.remove(&block_hash);
+
+                    if !canonical.contains(&block_hash) {
+                        fork_blocks.push(block);
+                    }
                 }
             }
         }
+
+        fork_blocks
     }
 
     /// Updates the canonical head to the given block.
@@ -397,13 +429,16 @@ pub struct EngineApiTreeHandler<P, E, T: EngineTypes> {
     payload_builder: PayloadBuilderHandle<T>,
     /// Configuration settings.
     config: TreeConfig,
+    /// Senders of pending transactions, which don't need to be recovered again when they are
+    /// included in a block.
+    sender_cache: SenderCache,
     /// Metrics for the engine api.
     metrics: EngineApiMetrics,
 }
 
 impl<P, E, T> EngineApiTreeHandler<P, E, T>
 where
-    P: BlockReader + StateProviderFactory + Clone + 'static,
+    P: BlockReader + ForkBlockReader + StateProviderFactory + Clone + 'static,
     E: BlockExecutorProvider,
     T: EngineTypes,
 {
@@ -421,6 +456,7 @@ where
         persistence_state: PersistenceState,
         payload_builder: PayloadBuilderHandle<T>,
         config: TreeConfig,
+        sender_cache: SenderCache,
     ) -> Self {
         let (incoming_tx, incoming) = std::sync::mpsc::channel();
         Self {
@@ -437,6 +473,7 @@ where
             canonical_in_memory_state,
             payload_builder,
             config,
+            sender_cache,
             metrics: Default::default(),
             incoming_tx,
         }
@@ -457,6 +494,7 @@ where
         payload_builder: PayloadBuilderHandle<T>,
         canonical_in_memory_state: CanonicalInMemoryState,
         config: TreeConfig,
+        sender_cache: SenderCache,
     ) -> (Sender<FromEngine<EngineApiRequest<T>>>, UnboundedReceiver<EngineApiEvent>) {
         let best_block_number = provider.best_block_number().unwrap_or(0);
         let header = provider.sealed_header(best_block_number).ok().flatten().unwrap_or_default();
@@ -486,6 +524,7 @@ where
             persistence_state,
             payload_builder,
             config,
+            sender_cache,
         );
         let incoming = task.incoming_tx.clone();
         std::thread::Builder::new().name("Tree Task".to_string()).spawn(|| task.run()).unwrap();
@@ -574,6 +613,7 @@ where
     ) -> Result<TreeOutcome<PayloadStatus>, InsertBlockFatalError> {
         trace!(target: "engine", "invoked new payload");
         self.metrics.new_payload_messages.increment(1);
+        self.canonical_in_memory_state.on_new_payload_received(payload.timestamp());
 
         // Ensures that the given payload does not violate any consensus rules that concern the
         // block's layout, like:
@@ -730,13 +770,22 @@ where
             trace!(target: "engine", "fcu head hash is already canonical");
 
             // update the safe and finalized blocks and ensure their values are valid
-            if let Err(outcome) = self.ensure_consistent_forkchoice_state(state) {
+            let consistent = {
+                let _span = debug_span!(target: "engine", "fcu_canonicalization").entered();
+                let start = Instant::now();
+                let consistent = self.ensure_consistent_forkchoice_state(state);
+                self.metrics.forkchoice_updated.canonicalization_duration.record(start.elapsed());
+                consistent
+            };
+            if let Err(outcome) = consistent {
                 // safe or finalized hashes are invalid
                 return Ok(TreeOutcome::new(outcome))
             }
 
             // we still need to process payload attributes if the head is already canonical
             if let Some(attr) = attrs {
+                let _span = debug_span!(target: "engine", "fcu_payload_attributes").entered();
+                let start = Instant::now();
                 let tip = self
                     .block_by_hash(self.state.tree_state.canonical_block_hash())?
                     .ok_or_else(|| {
@@ -745,6 +794,7 @@ where
                         ProviderError::HeaderNotFound(state.head_block_hash.into())
                     })?;
                 let updated = self.process_payload_attributes(attr, &tip, state);
+                self.metrics.forkchoice_updated.payload_attributes_duration.record(start.elapsed());
                 return Ok(TreeOutcome::new(updated))
             }
 
@@ -753,18 +803,35 @@ where
         }
 
         // 2. ensure we can apply a new chain update for the head block
-        if let Some(chain_update) = self.state.tree_state.on_new_head(state.head_block_hash) {
+        let canonicalization_span = debug_span!(target: "engine", "fcu_canonicalization");
+        let canonicalization_start = Instant::now();
+        let chain_update = canonicalization_span
+            .in_scope(|| self.state.tree_state.on_new_head(state.head_block_hash));
+        if let Some(chain_update) = chain_update {
             let tip = chain_update.tip().header.clone();
-            self.on_canonical_chain_update(chain_update);
-
-            // update the safe and finalized blocks and ensure their values are valid
-            if let Err(outcome) = self.ensure_consistent_forkchoice_state(state) {
+            // the listeners, like the transaction pool maintenance task, process the
+            // notification asynchronously and record the duration of their work themselves
+            let consistent = canonicalization_span.in_scope(|| {
+                self.on_canonical_chain_update(chain_update);
+
+                // update the safe and finalized blocks and ensure their values are valid
+                self.ensure_consistent_forkchoice_state(state)
+            });
+            self.metrics
+                .forkchoice_updated
+                .canonicalization_duration
+                .record(canonicalization_start.elapsed());
+
+            if let Err(outcome) = consistent {
                 // safe or finalized hashes are invalid
                 return Ok(TreeOutcome::new(outcome))
             }
 
             if let Some(attr) = attrs {
+                let _span = debug_span!(target: "engine", "fcu_payload_attributes").entered();
+                let start = Instant::now();
                 let updated = self.process_payload_attributes(attr, &tip, state);
+                self.metrics.forkchoice_updated.payload_attributes_duration.record(start.elapsed());
                 return Ok(TreeOutcome::new(updated))
             }
 
@@ -847,11 +914,16 @@ where
     /// or send a new persistence action if necessary.
     fn advance_persistence(&mut self) -> Result<(), TryRecvError> {
         if self.should_persist() && !self.persistence_state.in_progress() {
+            let _span = debug_span!(target: "engine", "persistence_trigger").entered();
             let blocks_to_persist = self.get_canonical_blocks_to_persist();
             if !blocks_to_persist.is_empty() {
                 let (tx, rx) = oneshot::channel();
-                let _ = self.persistence.save_blocks(blocks_to_persist, tx);
-                self.persistence_state.start(rx);
+                if self.persistence.save_blocks(blocks_to_persist, tx).is_ok() {
+                    self.persistence_state.start(rx);
+                } else {
+                    // the persistence service was stopped by the shutdown of the node
+                    debug!(target: "engine", "Persistence service is shut down, not persisting blocks");
+                }
             } else {
                 debug!(target: "engine", "Returned empty set of blocks to persist");
             }
@@ -905,10 +977,12 @@ where
                 match request {
                     EngineApiRequest::InsertExecutedBlock(block) => {
                         self.state.tree_state.insert_executed(block);
+                        self.update_executed_blocks_metrics();
                     }
                     EngineApiRequest::Beacon(request) => {
                         match request {
                             BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx } => {
+                                let start = Instant::now();
                                 let mut output = self.on_forkchoice_updated(state, payload_attrs);
 
                                 if let Ok(res) = &mut output {
@@ -927,11 +1001,20 @@ where
                                     self.on_maybe_tree_event(res.event.take());
                                 }
 
+                                let acknowledged = output.as_ref().is_ok_and(|res| {
+                                    res.outcome.forkchoice_status() == ForkchoiceStatus::Valid
+                                });
                                 if let Err(err) =
                                     tx.send(output.map(|o| o.outcome).map_err(Into::into))
                                 {
                                     error!("Failed to send event: {err:?}");
                                 }
+                                if acknowledged {
+                                    // the FCU head is the canonical head
+                                    self.canonical_in_memory_state
+                                        .on_forkchoice_update_acknowledged();
+                                }
+                                self.metrics.forkchoice_updated.latency.record(start.elapsed());
                             }
                             BeaconEngineMessage::NewPayload { payload, cancun_fields, tx } => {
                                 let output = self.on_new_payload(payload, cancun_fields);
@@ -989,7 +1072,7 @@ where
         // state house keeping after backfill sync
         // remove all executed blocks below the backfill height
         self.state.tree_state.remove_before(Bound::Included(backfill_height));
-        self.metrics.executed_blocks.set(self.state.tree_state.block_count() as f64);
+        self.update_executed_blocks_metrics();
 
         // remove all buffered blocks below the backfill height
         self.state.buffer.remove_old_blocks(backfill_height);
@@ -1002,6 +1085,7 @@ where
             // persisted height are the same
             self.state.tree_state.set_canonical_head(new_head.num_hash());
             self.persistence_state.finish(new_head.hash(), new_head.number);
+            self.canonical_in_memory_state.set_persisted_block(new_head.num_hash());
 
             // update the tracked canonical head
             self.canonical_in_memory_state.set_canonical_head(new_head);
@@ -1075,9 +1159,114 @@ where
                 self.emit_event(EngineApiEvent::BackfillAction(action));
             }
             TreeEvent::Download(action) => {
-                self.emit_event(EngineApiEvent::Download(action));
+                if let Some(action) = self.download_from_fork_blocks(action) {
+                    self.emit_event(EngineApiEvent::Download(action));
+                }
+            }
+        }
+    }
+
+    /// Takes the requested blocks from the fork blocks stored on disk, and inserts them into the
+    /// tree with their stored execution outcome, without executing them again.
+    ///
+    /// Returns the request for the blocks that are not stored, if any.
+    fn download_from_fork_blocks(&mut self, request: DownloadRequest) -> Option<DownloadRequest> {
+        let mut found = Vec::new();
+        let remaining = match request {
+            DownloadRequest::BlockSet(hashes) => {
+                let mut missing = HashSet::with_capacity(hashes.len());
+                for hash in hashes {
+                    let Some(block) = self.fork_block(hash) else {
+                        missing.insert(hash);
+                        continue
+                    };
+                    // the stored ancestors of the block are taken as well, so that the block can
+                    // be connected to the tree without downloading them
+                    let mut parent = block.block.parent_hash;
+                    found.push(block);
+                    while self.state.tree_state.block_by_hash(parent).is_none() {
+                        let Some(block) = self.fork_block(parent) else { break };
+                        parent = block.block.parent_hash;
+                        found.push(block);
+                    }
+                }
+                (!missing.is_empty()).then_some(DownloadRequest::BlockSet(missing))
+            }
+            DownloadRequest::BlockRange(hash, count) => {
+                // the range is requested from the highest block, following the parent hashes
+                let mut next = hash;
+                let mut remaining = count;
+                while remaining > 0 {
+                    let Some(block) = self.fork_block(next) else { break };
+                    next = block.block.parent_hash;
+                    remaining -= 1;
+                    found.push(block);
+                }
+                (remaining > 0).then_some(DownloadRequest::BlockRange(next, remaining))
+            }
+        };
+
+        if !found.is_empty() {
+            debug!(target: "engine", count = found.len(), "Found requested blocks in stored fork blocks");
+            self.on_stored_fork_blocks(found);
+        }
+
+        remaining
+    }
+
+    /// Inserts the given stored fork blocks into the tree without executing them, and makes the
+    /// sync target canonical if it is one of them.
+    ///
+    /// Blocks whose parent is not known, because it still has to be downloaded, are sent to the
+    /// tree as downloaded blocks instead.
+    fn on_stored_fork_blocks(&mut self, mut blocks: Vec<ExecutedBlock>) {
+        // parents have to be inserted before their children
+        blocks.sort_unstable_by_key(|block| (block.block.number, block.block.hash()));
+        blocks.dedup_by_key(|block| block.block.hash());
+
+        let mut disconnected = Vec::new();
+        let mut make_canonical = None;
+        for block in blocks {
+            let num_hash = block.block.num_hash();
+            if self.state.tree_state.block_by_hash(num_hash.hash).is_some() {
+                continue
+            }
+
+            let parent = self.sealed_header_by_hash(block.block.parent_hash);
+            if let Err(err) = &parent {
+                debug!(target: "engine", ?err, ?num_hash, "Failed to read parent of fork block");
+            }
+            if !matches!(parent, Ok(Some(_))) {
+                disconnected.push(SealedBlockWithSenders {
+                    block: block.block.as_ref().clone(),
+                    senders: block.senders.as_ref().clone(),
+                });
+                continue
+            }
+
+            self.insert_executed_block(block, Instant::now());
+            if self.is_sync_target_head(num_hash.hash) {
+                make_canonical = Some(num_hash.hash);
+            } else {
+                self.try_connect_buffered_blocks(num_hash);
             }
         }
+
+        if let Some(target) = make_canonical {
+            self.make_canonical(target);
+        }
+        if !disconnected.is_empty() {
+            let _ = self.incoming_tx.send(FromEngine::DownloadedBlocks(disconnected));
+        }
+    }
+
+    /// Returns the fork block with the given hash that is stored on disk, if any.
+    fn fork_block(&self, hash: B256) -> Option<ExecutedBlock> {
+        self.provider
+            .fork_block(hash)
+            .inspect_err(|err| debug!(target: "engine", ?err, %hash, "Failed to read fork block"))
+            .ok()
+            .flatten()
     }
 
     /// Emits an outgoing event to the engine.
@@ -1110,9 +1299,9 @@ where
     }
 
     /// Returns true if the canonical chain length minus the last persisted
-    /// block is greater than or equal to the persistence threshold and
-    /// backfill is not running.
-    const fn should_persist(&self) -> bool {
+    /// block is greater than or equal to the persistence threshold, or the in-memory blocks exceed
+    /// their size or age limit, and backfill is not running.
+    fn should_persist(&self) -> bool {
         if !self.backfill_sync_state.is_idle() {
             // can't persist if backfill is running
             return false
@@ -1120,16 +1309,50 @@ where
 
         let min_block = self.persistence_state.last_persisted_block_number;
         self.state.tree_state.canonical_block_number().saturating_sub(min_block) >
-            self.config.persistence_threshold()
+            self.config.persistence_threshold() ||
+            self.config
+                .max_in_memory_bytes()
+                .is_some_and(|max| self.state.tree_state.canonical_blocks_size() > max) ||
+            self.has_expired_canonical_blocks()
+    }
+
+    /// Returns true if the oldest canonical block that is not persisted yet is older than the
+    /// maximum in-memory age, relative to the timestamp of the canonical head.
+    fn has_expired_canonical_blocks(&self) -> bool {
+        let Some(head) =
+            self.state.tree_state.blocks_by_hash.get(&self.state.tree_state.canonical_block_hash())
+        else {
+            return false
+        };
+        let last_persisted_number = self.persistence_state.last_persisted_block_number;
+
+        let mut oldest = head;
+        while let Some(parent) = self.state.tree_state.blocks_by_hash.get(&oldest.block.parent_hash)
+        {
+            if parent.block.number <= last_persisted_number {
+                break
+            }
+            oldest = parent;
+        }
+        self.is_expired(oldest, head)
+    }
+
+    /// Returns true if the block is older than the maximum in-memory age, relative to the
+    /// timestamp of the canonical head.
+    fn is_expired(&self, block: &ExecutedBlock, head: &ExecutedBlock) -> bool {
+        self.config.max_in_memory_age().is_some_and(|max_age| {
+            head.block.timestamp.saturating_sub(block.block.timestamp) > max_age.as_secs()
+        })
     }
 
     /// Returns a batch of consecutive canonical blocks to persist in the range
-    /// `(last_persisted_number .. canonical_head - threshold]` . The expected
-    /// order is oldest -> newest.
+    /// `(last_persisted_number .. canonical_head - threshold]`, extended by the blocks that are
+    /// older than the maximum in-memory age. The expected order is oldest -> newest.
     fn get_canonical_blocks_to_persist(&self) -> Vec<ExecutedBlock> {
         let mut blocks_to_persist = Vec::new();
         let mut current_hash = self.state.tree_state.canonical_block_hash();
         let last_persisted_number = self.persistence_state.last_persisted_block_number;
+        let head = self.state.tree_state.blocks_by_hash.get(&current_hash);
 
         let canonical_head_number = self.state.tree_state.canonical_block_number();
 
@@ -1141,7 +1364,9 @@ where
                 break;
             }
 
-            if block.block.number <= target_number {
+            if block.block.number <= target_number ||
+                head.is_some_and(|head| self.is_expired(block, head))
+            {
                 blocks_to_persist.push(block.clone());
             }
 
@@ -1162,11 +1387,48 @@ where
     ///
     /// Assumes that `finish` has been called on the `persistence_state` at least once
     fn on_new_persisted_block(&mut self) {
-        self.state
+        let fork_blocks = self
+            .state
             .tree_state
             .remove_before(Bound::Included(self.persistence_state.last_persisted_block_number));
-        self.canonical_in_memory_state
-            .remove_persisted_blocks(self.persistence_state.last_persisted_block_number);
+        self.update_executed_blocks_metrics();
+        self.save_fork_blocks(fork_blocks);
+        self.canonical_in_memory_state.remove_persisted_blocks(BlockNumHash::new(
+            self.persistence_state.last_persisted_block_number,
+            self.persistence_state.last_persisted_block_hash,
+        ));
+    }
+
+    /// Updates the metrics of the number and size of the executed blocks in memory.
+    fn update_executed_blocks_metrics(&self) {
+        self.metrics.executed_blocks.set(self.state.tree_state.block_count() as f64);
+        self.metrics.memory.usage_bytes.set(self.state.tree_state.canonical_blocks_size() as f64);
+    }
+
+    /// Sends the fork blocks that were removed from memory to the persistence service, to keep
+    /// the ones within the max reorg depth of the canonical head on disk.
+    fn save_fork_blocks(&self, fork_blocks: Vec<ExecutedBlock>) {
+        let max_reorg_depth = self.config.max_reorg_depth();
+        if max_reorg_depth == 0 {
+            return
+        }
+
+        let remove_below =
+            self.state.tree_state.canonical_block_number().saturating_sub(max_reorg_depth);
+        let blocks = fork_blocks
+            .into_iter()
+            .filter(|block| block.block.number >= remove_below)
+            .collect::<Vec<_>>();
+
+        // fork blocks that were stored before and are now below the max reorg depth are removed
+        // with the next fork blocks that are saved
+        if blocks.is_empty() {
+            return
+        }
+
+        if let Err(err) = self.persistence.save_fork_blocks(blocks, remove_below) {
+            error!(target: "engine", ?err, "Failed to send fork blocks to the persistence service");
+        }
     }
 
     /// Return sealed block from database or in-memory state by hash.
@@ -1418,7 +1680,7 @@ where
         &mut self,
         block: SealedBlock,
     ) -> Result<(), InsertBlockErrorTwo> {
-        match block.try_seal_with_senders() {
+        match self.sender_cache.seal_with_senders(block) {
             Ok(block) => self.buffer_block(block),
             Err(block) => Err(InsertBlockErrorTwo::sender_recovery_error(block)),
         }
@@ -1659,7 +1921,7 @@ where
         &mut self,
         block: SealedBlock,
     ) -> Result<InsertPayloadOk, InsertBlockErrorTwo> {
-        match block.try_seal_with_senders() {
+        match self.sender_cache.seal_with_senders(block) {
             Ok(block) => self.insert_block(block),
             Err(block) => Err(InsertBlockErrorTwo::sender_recovery_error(block)),
         }
@@ -1719,13 +1981,15 @@ where
         let executor = self.executor_provider.executor(StateProviderDatabase::new(&state_provider));
 
         let block_number = block.number;
-        let block_hash = block.hash();
         let sealed_block = Arc::new(block.block.clone());
         let block = block.unseal();
 
         let exec_time = Instant::now();
         let output = executor.execute((&block, U256::MAX).into())?;
         debug!(target: "engine", elapsed=?exec_time.elapsed(), ?block_number, "Executed block");
+        self.metrics
+            .proposer_revenue
+            .record(&ProposerRevenue::from_bundle_state(&block, &output.state));
 
         self.consensus.validate_block_post_execution(
             &block,
@@ -1747,26 +2011,45 @@ where
         debug!(target: "engine", elapsed=?root_time.elapsed(), ?block_number, "Calculated state root");
 
         let executed = ExecutedBlock {
-            block: sealed_block.clone(),
+            block: sealed_block,
             senders: Arc::new(block.senders),
-            execution_output: Arc::new(ExecutionOutcome::new(
-                output.state,
-                Receipts::from(output.receipts),
-                block_number,
-                vec![Requests::from(output.requests)],
-            )),
+            execution_output: Arc::new(
+                ExecutionOutcome::new(
+                    output.state,
+                    Receipts::from(output.receipts),
+                    block_number,
+                    vec![Requests::from(output.requests)],
+                )
+                .with_internal_transfers(vec![output.internal_transfers]),
+            ),
             hashed_state: Arc::new(hashed_state),
             trie: Arc::new(trie_output),
         };
+        self.insert_executed_block(executed, start);
+
+        let attachment = BlockAttachment::Canonical; // TODO: remove or revise attachment
+
+        Ok(InsertPayloadOk::Inserted(BlockStatus::Valid(attachment)))
+    }
+
+    /// Inserts an executed block into the tree state and the canonical in-memory state, and emits
+    /// the matching insert event.
+    fn insert_executed_block(&mut self, executed: ExecutedBlock, start: Instant) {
+        let block_hash = executed.block.hash();
+        let sealed_block = executed.block.clone();
 
         if self.state.tree_state.canonical_block_hash() == executed.block().parent_hash {
             debug!(target: "engine", pending = ?executed.block().num_hash() ,"updating pending block");
             // if the parent is the canonical head, we can insert the block as the pending block
             self.canonical_in_memory_state.set_pending_block(executed.clone());
+        } else {
+            // otherwise the block is tracked as a sidechain block, so that its state can be
+            // looked up by hash
+            self.canonical_in_memory_state.insert_sidechain_block(executed.clone());
         }
 
         self.state.tree_state.insert_executed(executed);
-        self.metrics.executed_blocks.set(self.state.tree_state.block_count() as f64);
+        self.update_executed_blocks_metrics();
 
         // emit insert event
         let engine_event = if self.state.tree_state.is_fork(block_hash) {
@@ -1775,10 +2058,6 @@ where
             BeaconConsensusEngineEvent::CanonicalBlockAdded(sealed_block, start.elapsed())
         };
         self.emit_event(EngineApiEvent::BeaconConsensus(engine_event));
-
-        let attachment = BlockAttachment::Canonical; // TODO: remove or revise attachment
-
-        Ok(InsertPayloadOk::Inserted(BlockStatus::Valid(attachment)))
     }
 
     /// Handles an error that occurred while inserting a block.
@@ -2026,8 +2305,8 @@ mod tests {
     use super::*;
     use crate::persistence::PersistenceAction;
     use alloy_rlp::Decodable;
-    use reth_beacon_consensus::{EthBeaconConsensus, ForkchoiceStatus};
-    use reth_chain_state::{test_utils::TestBlockBuilder, BlockState};
+    use reth_beacon_consensus::EthBeaconConsensus;
+    use reth_chain_state::{test_utils::TestBlockBuilder, BlockState, InMemoryRetention};
     use reth_chainspec::{ChainSpec, HOLESKY, MAINNET};
     use reth_ethereum_engine_primitives::EthEngineTypes;
     use reth_evm::test_utils::MockExecutorProvider;
@@ -2037,6 +2316,7 @@ mod tests {
     use std::{
         str::FromStr,
         sync::mpsc::{channel, Sender},
+        time::Duration,
     };
     use tokio::sync::mpsc::unbounded_channel;
 
@@ -2067,7 +2347,8 @@ mod tests {
 
             let header = chain_spec.genesis_header().seal_slow();
             let engine_api_tree_state = EngineApiTreeState::new(10, 10, header.num_hash());
-            let canonical_in_memory_state = CanonicalInMemoryState::with_head(header, None);
+            let canonical_in_memory_state =
+                CanonicalInMemoryState::with_head(header, None, Default::default());
 
             let (to_payload_service, _payload_command_rx) = unbounded_channel();
             let payload_builder = PayloadBuilderHandle::new(to_payload_service);
@@ -2084,6 +2365,7 @@ mod tests {
                 PersistenceState::default(),
                 payload_builder,
                 TreeConfig::default(),
+                SenderCache::default(),
             );
 
             let block_builder = TestBlockBuilder::default().with_chain_spec((*chain_spec).clone());
@@ -2376,6 +2658,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tree_should_persist_when_exceeding_memory_limit() {
+        let chain_spec = MAINNET.clone();
+        let blocks: Vec<_> = TestBlockBuilder::default()
+            .with_chain_spec((*chain_spec).clone())
+            .get_executed_blocks(1..3)
+            .collect();
+        let mut test_harness = TestHarness::new(chain_spec).with_blocks(blocks);
+
+        // the blocks don't reach the persistence threshold
+        assert!(!test_harness.tree.should_persist());
+
+        let blocks_size = test_harness.tree.state.tree_state.canonical_blocks_size();
+        test_harness.tree.config =
+            TreeConfig::default().with_max_in_memory_bytes(Some(blocks_size));
+        assert!(!test_harness.tree.should_persist());
+
+        // fork blocks don't count towards the limit
+        let fork_block = test_harness
+            .block_builder
+            .get_executed_block_with_number(2, test_harness.blocks[0].block().hash());
+        test_harness.tree.state.tree_state.insert_executed(fork_block);
+        assert_eq!(test_harness.tree.state.tree_state.canonical_blocks_size(), blocks_size);
+        assert!(!test_harness.tree.should_persist());
+
+        test_harness.tree.config =
+            TreeConfig::default().with_max_in_memory_bytes(Some(blocks_size - 1));
+        assert!(test_harness.tree.should_persist());
+    }
+
+    #[test]
+    fn test_tree_should_persist_expired_blocks() {
+        let chain_spec = MAINNET.clone();
+        let blocks: Vec<_> = TestBlockBuilder::default()
+            .with_chain_spec((*chain_spec).clone())
+            .get_executed_blocks(1..3)
+            .collect();
+        let mut test_harness = TestHarness::new(chain_spec).with_blocks(blocks);
+
+        // the test blocks are one second apart
+        test_harness.tree.config =
+            TreeConfig::default().with_max_in_memory_age(Some(Duration::from_secs(1)));
+        assert!(!test_harness.tree.should_persist());
+        assert!(test_harness.tree.get_canonical_blocks_to_persist().is_empty());
+
+        test_harness.tree.config = TreeConfig::default().with_in_memory_retention(
+            InMemoryRetention::default().with_max_age(Some(Duration::ZERO)),
+        );
+        assert!(test_harness.tree.should_persist());
+        let blocks_to_persist = test_harness.tree.get_canonical_blocks_to_persist();
+        assert_eq!(blocks_to_persist.len(), 1);
+        assert_eq!(blocks_to_persist[0].block.number, 1);
+    }
+
     #[tokio::test]
     async fn test_tree_persist_blocks() {
         let tree_config = TreeConfig::default();
@@ -2632,6 +2968,32 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_tree_state_remove_before_returns_fork_blocks() {
+        let mut tree_state = TreeState::new(BlockNumHash::default());
+        let mut test_block_builder = TestBlockBuilder::default();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..5).collect();
+        for block in &blocks {
+            tree_state.insert_executed(block.clone());
+        }
+        tree_state.set_canonical_head(blocks[3].block.num_hash());
+
+        // fork off block 1
+        let fork_block_2 =
+            test_block_builder.get_executed_block_with_number(2, blocks[0].block.hash());
+        let fork_block_3 =
+            test_block_builder.get_executed_block_with_number(3, fork_block_2.block.hash());
+        tree_state.insert_executed(fork_block_2.clone());
+        tree_state.insert_executed(fork_block_3.clone());
+
+        let removed = tree_state.remove_before(Bound::Included(2));
+        assert_eq!(
+            removed.iter().map(|block| block.block.hash()).collect::<Vec<_>>(),
+            vec![fork_block_2.block.hash()]
+        );
+        assert!(tree_state.blocks_by_hash.contains_key(&fork_block_3.block.hash()));
+    }
+
     #[tokio::test]
     async fn test_tree_state_on_new_head() {
         let mut tree_state = TreeState::new(BlockNumHash::default());
@@ -2810,6 +3172,39 @@ mod tests {
         test_harness.check_canon_head(fork_chain_last_hash);
     }
 
+    #[tokio::test]
+    async fn test_engine_tree_fcu_reorg_to_stored_fork_blocks() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec.clone());
+
+        let main_chain: Vec<_> = test_harness.block_builder.get_executed_blocks(0..5).collect();
+        test_harness = test_harness.with_blocks(main_chain.clone());
+
+        // store a fork that is longer than the canonical chain, the executor has no outcomes, so
+        // the blocks can't be executed again
+        let mut fork_chain = Vec::new();
+        let mut parent_hash = main_chain[2].block().hash();
+        for number in 3..6 {
+            let block =
+                test_harness.block_builder.get_executed_block_with_number(number, parent_hash);
+            parent_hash = block.block().hash();
+            test_harness.provider.add_fork_block(block.clone());
+            fork_chain.push(SealedBlockWithSenders {
+                block: block.block().clone(),
+                senders: block.senders.to_vec(),
+            });
+        }
+        let fork_chain_last_hash = fork_chain.last().unwrap().hash();
+
+        test_harness.fcu_to(fork_chain_last_hash, ForkchoiceStatus::Syncing).await;
+
+        // the stored blocks are inserted without downloading or executing them
+        test_harness.check_fork_chain_insertion(fork_chain).await;
+        test_harness.check_canon_commit(fork_chain_last_hash).await;
+        test_harness.check_canon_head(fork_chain_last_hash);
+        assert!(test_harness.from_tree_rx.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn test_engine_tree_live_sync_transition_required_blocks_requested() {
         reth_tracing::init_test_tracing();
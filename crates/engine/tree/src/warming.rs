@@ -0,0 +1,201 @@
+use parking_lot::Mutex;
+use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_primitives::{
+    revm_primitives::{EnvWithHandlerCfg, TxEnv},
+    Address, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionSigned,
+    TransactionSignedEcRecovered, TxHash, U256,
+};
+use reth_provider::{EvmEnvProvider, ProviderResult, StateProviderFactory};
+use reth_revm::database::StateProviderDatabase;
+use schnellru::{ByLength, LruMap};
+use std::{sync::Arc, time::Instant};
+use tracing::debug;
+
+/// The default number of senders kept in the [`SenderCache`].
+pub const DEFAULT_SENDER_CACHE_SIZE: u32 = 10_000;
+
+/// A cache of the recovered senders of transactions, by transaction hash.
+///
+/// The [`CacheWarmer`] fills the cache with the senders of the pending transactions of the pool,
+/// which were already recovered when they were validated, so that the senders of the transactions
+/// of new payloads don't need to be recovered again.
+#[derive(Debug, Clone)]
+pub struct SenderCache {
+    inner: Arc<Mutex<LruMap<TxHash, Address, ByLength>>>,
+    metrics: SenderCacheMetrics,
+}
+
+impl SenderCache {
+    /// Creates a new cache that keeps the senders of up to `max_len` transactions.
+    pub fn new(max_len: u32) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LruMap::new(ByLength::new(max_len)))),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Caches the senders of the given transactions.
+    pub fn insert(&self, senders: impl IntoIterator<Item = (TxHash, Address)>) {
+        let mut cache = self.inner.lock();
+        for (hash, sender) in senders {
+            cache.insert(hash, sender);
+        }
+    }
+
+    /// Recovers the senders of the transactions of the block, taking the senders that are cached
+    /// instead of recovering them.
+    ///
+    /// Returns the block if the sender of any transaction can't be recovered.
+    pub fn seal_with_senders(
+        &self,
+        block: SealedBlock,
+    ) -> Result<SealedBlockWithSenders, SealedBlock> {
+        let cached = {
+            let mut cache = self.inner.lock();
+            block.body.iter().map(|tx| cache.get(&tx.hash()).copied()).collect::<Vec<_>>()
+        };
+
+        let missing = block
+            .body
+            .iter()
+            .zip(&cached)
+            .filter(|(_, sender)| sender.is_none())
+            .map(|(tx, _)| tx)
+            .collect::<Vec<_>>();
+        self.metrics.hits.increment((cached.len() - missing.len()) as u64);
+        self.metrics.misses.increment(missing.len() as u64);
+
+        let missing_len = missing.len();
+        let Some(recovered) = TransactionSigned::recover_signers(missing, missing_len) else {
+            return Err(block)
+        };
+        let mut recovered = recovered.into_iter();
+        let senders = cached
+            .into_iter()
+            .map(|sender| sender.or_else(|| recovered.next()))
+            .collect::<Option<Vec<_>>>();
+
+        match senders {
+            Some(senders) => Ok(SealedBlockWithSenders { block, senders }),
+            None => Err(block),
+        }
+    }
+}
+
+impl Default for SenderCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_SENDER_CACHE_SIZE)
+    }
+}
+
+/// Speculatively executes pending transactions on top of the canonical head, to warm the caches
+/// of the state that the next block is likely to touch.
+///
+/// The results of the execution are discarded. The engine executes blocks on a new state provider
+/// without a cache of state reads that could be shared, so the cache that is warmed is the page
+/// cache of the database: executing the transactions loads the pages of the accounts, storage
+/// slots and bytecode they touch from disk, so that these are in memory by the time the next block,
+/// which likely includes the most valuable of the transactions, is executed. The senders of the
+/// transactions are kept in the [`SenderCache`].
+#[derive(Debug, Clone)]
+pub struct CacheWarmer<P, EvmConfig> {
+    provider: P,
+    evm_config: EvmConfig,
+    sender_cache: SenderCache,
+    metrics: CacheWarmerMetrics,
+}
+
+impl<P, EvmConfig> CacheWarmer<P, EvmConfig>
+where
+    P: StateProviderFactory + EvmEnvProvider,
+    EvmConfig: ConfigureEvm,
+{
+    /// Creates a new [`CacheWarmer`] that caches the senders of the transactions in the given
+    /// [`SenderCache`].
+    pub fn new(provider: P, evm_config: EvmConfig, sender_cache: SenderCache) -> Self {
+        Self { provider, evm_config, sender_cache, metrics: Default::default() }
+    }
+
+    /// Executes the transactions on top of the state of the given head, each on its own, and
+    /// discards the results.
+    ///
+    /// Returns the number of transactions that were executed successfully.
+    pub fn warm(
+        &self,
+        head: &SealedHeader,
+        transactions: Vec<TransactionSignedEcRecovered>,
+    ) -> ProviderResult<usize> {
+        let start = Instant::now();
+        self.sender_cache.insert(transactions.iter().map(|tx| (tx.hash(), tx.signer())));
+
+        let state = self.provider.state_by_block_hash(head.hash())?;
+        let (cfg, mut block_env) = self.provider.env_with_header(head, self.evm_config.clone())?;
+        // the transactions are executed as if they were included in the next block, whose base
+        // fee is not known yet
+        block_env.number += U256::from(1);
+        block_env.basefee = U256::ZERO;
+
+        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, TxEnv::default());
+        let mut evm = self.evm_config.evm_with_env(StateProviderDatabase::new(state), env);
+
+        let mut executed = 0;
+        for tx in &transactions {
+            self.evm_config.fill_tx_env(evm.tx_mut(), tx, tx.signer());
+            // the state is not committed, so every transaction is executed on top of the head
+            if evm.transact().is_ok() {
+                executed += 1;
+            }
+        }
+
+        self.metrics.transactions.increment(executed as u64);
+        self.metrics.duration.record(start.elapsed());
+        debug!(target: "engine::warming", number = head.number, hash = %head.hash(), total = transactions.len(), executed, elapsed = ?start.elapsed(), "Warmed caches");
+
+        Ok(executed)
+    }
+}
+
+/// Metrics for the [`SenderCache`].
+#[derive(Clone, Metrics)]
+#[metrics(scope = "engine.warming.senders")]
+struct SenderCacheMetrics {
+    /// The number of senders of block transactions that were cached.
+    hits: Counter,
+    /// The number of senders of block transactions that had to be recovered.
+    misses: Counter,
+}
+
+/// Metrics for the [`CacheWarmer`].
+#[derive(Clone, Metrics)]
+#[metrics(scope = "engine.warming")]
+struct CacheWarmerMetrics {
+    /// The number of transactions executed to warm the caches.
+    transactions: Counter,
+    /// Time spent warming the caches after a new head.
+    duration: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_testing_utils::generators::{self, random_block};
+
+    #[test]
+    fn seal_with_cached_senders() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(3), None);
+        let senders = block.senders().unwrap();
+
+        let cache = SenderCache::new(10);
+        // a cached sender is taken as is
+        let cached_sender = Address::random();
+        cache.insert([(block.body[1].hash(), cached_sender)]);
+
+        let sealed = cache.seal_with_senders(block).unwrap();
+        assert_eq!(sealed.senders, vec![senders[0], cached_sender, senders[2]]);
+    }
+}
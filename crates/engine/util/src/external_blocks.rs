@@ -0,0 +1,286 @@
+//! Stream wrapper that feeds blocks from external sources into the engine.
+//!
+//! This allows alternative consensus drivers, e.g. a relay feed, an L2 derivation pipeline or a
+//! file replay, to advance the chain through the same validation and canonicalization paths as
+//! blocks received via the engine API.
+
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_beacon_consensus::{BeaconEngineMessage, BeaconOnNewPayloadError};
+use reth_engine_primitives::EngineTypes;
+use reth_errors::RethError;
+use reth_primitives::SealedBlock;
+use reth_rpc_types::engine::{
+    CancunPayloadFields, ForkchoiceState, ForkchoiceUpdateError, ForkchoiceUpdated, PayloadStatus,
+};
+use reth_rpc_types_compat::engine::payload::block_to_payload;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::*;
+
+/// A request to insert a block from an external source.
+#[derive(Debug)]
+pub struct ExternalBlockRequest {
+    /// The block to insert.
+    pub block: SealedBlock,
+    /// The forkchoice state to apply once the block has been inserted.
+    ///
+    /// If `None`, the block becomes the new head and the safe and finalized hashes of the last
+    /// observed forkchoice state are retained.
+    pub forkchoice_state: Option<ForkchoiceState>,
+    /// The sender for returning the outcome of the request.
+    pub tx: oneshot::Sender<Result<ExternalBlockOutcome, ExternalBlockError>>,
+}
+
+/// The outcome of inserting a block from an external source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalBlockOutcome {
+    /// The status of the block after validation.
+    pub payload_status: PayloadStatus,
+    /// The response to the forkchoice update that followed the block.
+    pub forkchoice_updated: ForkchoiceUpdated,
+}
+
+/// Errors that can occur when inserting a block from an external source.
+#[derive(Debug, thiserror::Error)]
+pub enum ExternalBlockError {
+    /// The engine failed to process the block.
+    #[error(transparent)]
+    NewPayload(#[from] BeaconOnNewPayloadError),
+    /// The engine failed to process the forkchoice update.
+    #[error(transparent)]
+    ForkchoiceUpdated(#[from] RethError),
+    /// The forkchoice update was rejected.
+    #[error(transparent)]
+    ForkchoiceUpdate(#[from] ForkchoiceUpdateError),
+    /// The engine dropped the request before responding.
+    #[error("engine dropped the external block request")]
+    EngineDropped,
+}
+
+/// A cloneable handle for feeding blocks from external sources into the engine.
+///
+/// The receiving half of the handle needs to be attached to the engine message stream via
+/// [`EngineMessageStreamExt::external_blocks`](crate::EngineMessageStreamExt::external_blocks).
+/// The node launchers do this and expose the handle of the launched node as
+/// `FullNode::external_blocks`.
+#[derive(Debug, Clone)]
+pub struct ExternalBlockHandle {
+    to_engine: mpsc::UnboundedSender<ExternalBlockRequest>,
+}
+
+impl ExternalBlockHandle {
+    /// Creates a new handle and the receiver that should be attached to the engine message
+    /// stream.
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<ExternalBlockRequest>) {
+        let (to_engine, rx) = mpsc::unbounded_channel();
+        (Self { to_engine }, rx)
+    }
+
+    /// Inserts the block and makes it the new canonical head.
+    ///
+    /// The safe and finalized hashes of the last observed forkchoice state are retained.
+    pub async fn insert_block(
+        &self,
+        block: SealedBlock,
+    ) -> Result<ExternalBlockOutcome, ExternalBlockError> {
+        self.send(block, None).await
+    }
+
+    /// Inserts the block and applies the given forkchoice state afterwards.
+    pub async fn insert_block_with_forkchoice(
+        &self,
+        block: SealedBlock,
+        forkchoice_state: ForkchoiceState,
+    ) -> Result<ExternalBlockOutcome, ExternalBlockError> {
+        self.send(block, Some(forkchoice_state)).await
+    }
+
+    async fn send(
+        &self,
+        block: SealedBlock,
+        forkchoice_state: Option<ForkchoiceState>,
+    ) -> Result<ExternalBlockOutcome, ExternalBlockError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_engine
+            .send(ExternalBlockRequest { block, forkchoice_state, tx })
+            .map_err(|_| ExternalBlockError::EngineDropped)?;
+        rx.await.map_err(|_| ExternalBlockError::EngineDropped)?
+    }
+}
+
+type ExternalBlockResponseFut = Pin<Box<dyn Future<Output = ()> + Send + Sync>>;
+
+/// Engine API stream wrapper that merges blocks from an external source into the stream of
+/// engine messages.
+///
+/// Every external block is converted into a [`BeaconEngineMessage::NewPayload`] followed by a
+/// [`BeaconEngineMessage::ForkchoiceUpdated`], so it is processed exactly like a block received
+/// from the consensus layer.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct EngineExternalBlocks<S, Engine: EngineTypes> {
+    /// Underlying stream.
+    #[pin]
+    stream: S,
+    /// Incoming requests from [`ExternalBlockHandle`]s.
+    requests: UnboundedReceiverStream<ExternalBlockRequest>,
+    /// Messages that were created for external blocks but not yet forwarded.
+    queue: VecDeque<BeaconEngineMessage<Engine>>,
+    /// Last forkchoice state forwarded to the engine.
+    last_forkchoice_state: Option<ForkchoiceState>,
+    /// Pending engine responses to external block requests.
+    responses: FuturesUnordered<ExternalBlockResponseFut>,
+}
+
+impl<S, Engine: EngineTypes> EngineExternalBlocks<S, Engine> {
+    /// Creates new [`EngineExternalBlocks`] stream wrapper.
+    pub fn new(stream: S, requests: mpsc::UnboundedReceiver<ExternalBlockRequest>) -> Self {
+        Self {
+            stream,
+            requests: UnboundedReceiverStream::new(requests),
+            queue: VecDeque::new(),
+            last_forkchoice_state: None,
+            responses: FuturesUnordered::new(),
+        }
+    }
+}
+
+impl<S, Engine> Stream for EngineExternalBlocks<S, Engine>
+where
+    S: Stream<Item = BeaconEngineMessage<Engine>>,
+    Engine: EngineTypes,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            // drive pending responses, the outcome is delivered to the requester
+            if let Poll::Ready(Some(_)) = this.responses.poll_next_unpin(cx) {
+                continue
+            }
+
+            if let Some(msg) = this.queue.pop_front() {
+                return Poll::Ready(Some(msg))
+            }
+
+            // messages from the consensus layer take precedence over external blocks
+            if let Poll::Ready(next) = this.stream.as_mut().poll_next(cx) {
+                if let Some(BeaconEngineMessage::ForkchoiceUpdated { state, .. }) = &next {
+                    *this.last_forkchoice_state = Some(*state);
+                }
+                return Poll::Ready(next)
+            }
+
+            let Some(request) = ready!(this.requests.poll_next_unpin(cx)) else {
+                // the handles were dropped, continue with the underlying stream only
+                return Poll::Pending
+            };
+
+            let ExternalBlockRequest { block, forkchoice_state, tx } = request;
+            let block_hash = block.hash();
+            let state = forkchoice_state.unwrap_or_else(|| ForkchoiceState {
+                head_block_hash: block_hash,
+                safe_block_hash: this
+                    .last_forkchoice_state
+                    .map(|state| state.safe_block_hash)
+                    .unwrap_or_default(),
+                finalized_block_hash: this
+                    .last_forkchoice_state
+                    .map(|state| state.finalized_block_hash)
+                    .unwrap_or_default(),
+            });
+            *this.last_forkchoice_state = Some(state);
+
+            debug!(target: "engine::stream::external_blocks", number=block.number, hash=?block_hash, ?state, "Forwarding external block");
+
+            let cancun_fields = block.parent_beacon_block_root.map(|parent_beacon_block_root| {
+                CancunPayloadFields {
+                    parent_beacon_block_root,
+                    versioned_hashes: block.blob_versioned_hashes().into_iter().copied().collect(),
+                }
+            });
+
+            let (payload_tx, payload_rx) = oneshot::channel();
+            let (fcu_tx, fcu_rx) = oneshot::channel();
+            this.queue.extend([
+                BeaconEngineMessage::NewPayload {
+                    payload: block_to_payload(block),
+                    cancun_fields,
+                    tx: payload_tx,
+                },
+                BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs: None, tx: fcu_tx },
+            ]);
+
+            this.responses.push(Box::pin(async move {
+                let outcome = async move {
+                    let payload_status =
+                        payload_rx.await.map_err(|_| ExternalBlockError::EngineDropped)??;
+                    let forkchoice_updated =
+                        fcu_rx.await.map_err(|_| ExternalBlockError::EngineDropped)??.await?;
+                    Ok(ExternalBlockOutcome { payload_status, forkchoice_updated })
+                }
+                .await;
+                if let Err(error) = &outcome {
+                    warn!(target: "engine::stream::external_blocks", hash=?block_hash, %error, "Failed to insert external block");
+                }
+                let _ = tx.send(outcome);
+            }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_beacon_consensus::OnForkChoiceUpdated;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_rpc_types::engine::PayloadStatusEnum;
+    use reth_testing_utils::generators::{self, random_block};
+
+    #[tokio::test]
+    async fn forwards_external_block() {
+        let (handle, requests) = ExternalBlockHandle::new();
+        let mut stream = EngineExternalBlocks::<_, EthEngineTypes>::new(
+            futures::stream::pending::<BeaconEngineMessage<EthEngineTypes>>(),
+            requests,
+        );
+
+        let block = random_block(&mut generators::rng(), 1, None, Some(0), Some(0));
+        let hash = block.hash();
+        let outcome = tokio::spawn(async move { handle.insert_block(block).await });
+
+        let Some(BeaconEngineMessage::NewPayload { payload, tx, .. }) = stream.next().await else {
+            panic!("expected new payload")
+        };
+        assert_eq!(payload.block_hash(), hash);
+        tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Valid))).unwrap();
+
+        let Some(BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx }) =
+            stream.next().await
+        else {
+            panic!("expected forkchoice updated")
+        };
+        assert_eq!(state.head_block_hash, hash);
+        assert!(payload_attrs.is_none());
+        tx.send(Ok(OnForkChoiceUpdated::valid(PayloadStatus::from_status(
+            PayloadStatusEnum::Valid,
+        ))))
+        .unwrap();
+
+        // the stream needs to be polled to deliver the outcome
+        let outcome = tokio::select! {
+            outcome = outcome => outcome.unwrap().unwrap(),
+            _ = stream.next() => unreachable!("underlying stream is pending"),
+        };
+        assert!(outcome.payload_status.is_valid());
+        assert!(outcome.forkchoice_updated.is_valid());
+    }
+}
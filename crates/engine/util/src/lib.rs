@@ -5,6 +5,7 @@ use reth_beacon_consensus::BeaconEngineMessage;
 use reth_engine_primitives::EngineTypes;
 use reth_payload_validator::ExecutionPayloadValidator;
 use std::path::PathBuf;
+use tokio::sync::mpsc::UnboundedReceiver;
 use tokio_util::either::Either;
 
 pub mod engine_store;
@@ -19,6 +20,9 @@ use skip_new_payload::EngineSkipNewPayload;
 pub mod reorg;
 use reorg::EngineReorg;
 
+pub mod external_blocks;
+use external_blocks::{EngineExternalBlocks, ExternalBlockRequest};
+
 /// The collection of stream extensions for engine API message stream.
 pub trait EngineMessageStreamExt<Engine: EngineTypes>:
     Stream<Item = BeaconEngineMessage<Engine>>
@@ -132,6 +136,19 @@ pub trait EngineMessageStreamExt<Engine: EngineTypes>:
             Either::Right(self)
         }
     }
+
+    /// Merges blocks from external sources into the engine message stream.
+    ///
+    /// Requests are created via an [`ExternalBlockHandle`](external_blocks::ExternalBlockHandle).
+    fn external_blocks(
+        self,
+        requests: UnboundedReceiver<ExternalBlockRequest>,
+    ) -> EngineExternalBlocks<Self, Engine>
+    where
+        Self: Sized,
+    {
+        EngineExternalBlocks::new(self, requests)
+    }
 }
 
 impl<Engine, T> EngineMessageStreamExt<Engine> for T
@@ -8,9 +8,7 @@ use reth_errors::{BlockExecutionError, BlockValidationError, RethError, RethResu
 use reth_ethereum_forks::EthereumHardforks;
 use reth_evm::{system_calls::apply_beacon_root_contract_call, ConfigureEvm};
 use reth_payload_validator::ExecutionPayloadValidator;
-use reth_primitives::{
-    eip4844::calculate_excess_blob_gas, proofs, Block, Header, Receipt, Receipts, U256,
-};
+use reth_primitives::{proofs, Block, Header, Receipt, Receipts, U256};
 use reth_provider::{BlockReader, ExecutionOutcome, ProviderError, StateProviderFactory};
 use reth_revm::{
     database::StateProviderDatabase,
@@ -356,10 +354,14 @@ where
         if chain_spec.is_cancun_active_at_timestamp(reorg_target.timestamp) {
             (
                 Some(sum_blob_gas_used),
-                Some(calculate_excess_blob_gas(
-                    reorg_target_parent.excess_blob_gas.unwrap_or_default(),
-                    reorg_target_parent.blob_gas_used.unwrap_or_default(),
-                )),
+                Some(
+                    chain_spec
+                        .blob_params_at_timestamp(reorg_target.timestamp)
+                        .next_block_excess_blob_gas(
+                            reorg_target_parent.excess_blob_gas.unwrap_or_default(),
+                            reorg_target_parent.blob_gas_used.unwrap_or_default(),
+                        ),
+                ),
             )
         } else {
             (None, None)
@@ -9,12 +9,12 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use reth_chainspec::{ChainSpec, EthereumHardfork, EthereumHardforks};
-use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
+use reth_consensus::{Consensus, ConsensusError, PostExecutionInput, ValidationPolicy};
 use reth_consensus_common::validation::{
     validate_4844_header_standalone, validate_against_parent_4844,
     validate_against_parent_eip1559_base_fee, validate_against_parent_hash_number,
     validate_against_parent_timestamp, validate_block_pre_execution, validate_header_base_fee,
-    validate_header_extradata, validate_header_gas,
+    validate_header_gas,
 };
 use reth_primitives::{
     constants::MINIMUM_GAS_LIMIT, BlockWithSenders, Header, SealedBlock, SealedHeader,
@@ -32,12 +32,20 @@ pub use validation::validate_block_post_execution;
 pub struct EthBeaconConsensus {
     /// Configuration
     chain_spec: Arc<ChainSpec>,
+    /// The header checks to enforce.
+    policy: ValidationPolicy,
 }
 
 impl EthBeaconConsensus {
     /// Create a new instance of [`EthBeaconConsensus`]
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self { chain_spec, policy: ValidationPolicy::strict() }
+    }
+
+    /// Sets the [`ValidationPolicy`], which is [`ValidationPolicy::strict`] by default.
+    pub const fn with_validation_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.policy = policy;
+        self
     }
 
     /// Checks the gas limit for consistency between parent and self headers.
@@ -49,6 +57,10 @@ impl EthBeaconConsensus {
         header: &SealedHeader,
         parent: &SealedHeader,
     ) -> Result<(), ConsensusError> {
+        if !self.policy.gas_limit_bounds {
+            return Ok(())
+        }
+
         // Determine the parent gas limit, considering elasticity multiplier on the London fork.
         let parent_gas_limit =
             if self.chain_spec.fork(EthereumHardfork::London).transitions_at_block(header.number) {
@@ -103,7 +115,7 @@ impl Consensus for EthBeaconConsensus {
 
         // Ensures that EIP-4844 fields are valid once cancun is active.
         if self.chain_spec.is_cancun_active_at_timestamp(header.timestamp) {
-            validate_4844_header_standalone(header)?;
+            validate_4844_header_standalone(header, &self.chain_spec)?;
         } else if header.blob_gas_used.is_some() {
             return Err(ConsensusError::BlobGasUsedUnexpected)
         } else if header.excess_blob_gas.is_some() {
@@ -140,7 +152,7 @@ impl Consensus for EthBeaconConsensus {
 
         // ensure that the blob gas fields for this block
         if self.chain_spec.is_cancun_active_at_timestamp(header.timestamp) {
-            validate_against_parent_4844(header, parent)?;
+            validate_against_parent_4844(header, parent, &self.chain_spec)?;
         }
 
         Ok(())
@@ -178,7 +190,7 @@ impl Consensus for EthBeaconConsensus {
             // is greater than its parent timestamp.
 
             // validate header extradata for all networks post merge
-            validate_header_extradata(header)?;
+            self.policy.validate_extra_data(header)?;
 
             // mixHash is used instead of difficulty inside EVM
             // https://eips.ethereum.org/EIPS/eip-4399#using-mixhash-field-instead-of-difficulty
@@ -191,14 +203,9 @@ impl Consensus for EthBeaconConsensus {
             let present_timestamp =
                 SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
 
-            if header.exceeds_allowed_future_timestamp(present_timestamp) {
-                return Err(ConsensusError::TimestampIsInFuture {
-                    timestamp: header.timestamp,
-                    present_timestamp,
-                })
-            }
+            self.policy.validate_future_timestamp(header, present_timestamp)?;
 
-            validate_header_extradata(header)?;
+            self.policy.validate_extra_data(header)?;
         }
 
         Ok(())
@@ -267,6 +274,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gas_limit_without_bounds() {
+        let parent = header_with_gas_limit(1024 * 10);
+        let consensus = EthBeaconConsensus::new(Arc::new(ChainSpec::default()))
+            .with_validation_policy(ValidationPolicy::permissive());
+
+        for gas_limit in [parent.gas_limit * 2, MINIMUM_GAS_LIMIT - 1] {
+            let child = header_with_gas_limit(gas_limit);
+            assert_eq!(consensus.validate_against_parent_gas_limit(&child, &parent), Ok(()));
+        }
+    }
+
     #[test]
     fn test_valid_gas_limit_decrease_within_limit() {
         let parent = header_with_gas_limit(1024 * 10);
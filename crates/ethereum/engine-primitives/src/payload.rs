@@ -246,10 +246,11 @@ impl PayloadBuilderAttributes for EthPayloadBuilderAttributes {
         // ensure we're not missing any timestamp based hardforks
         let spec_id = revm_spec_by_timestamp_after_merge(chain_spec, self.timestamp());
 
+        let blob_params = chain_spec.blob_params_at_timestamp(self.timestamp());
         // if the parent block did not have excess blob gas (i.e. it was pre-cancun), but it is
         // cancun now, we need to set the excess blob gas to the default value
         let blob_excess_gas_and_price = parent
-            .next_block_excess_blob_gas()
+            .next_block_excess_blob_gas_with_params(blob_params)
             .or_else(|| {
                 if spec_id == SpecId::CANCUN {
                     // default excess blob gas is zero
@@ -258,7 +259,10 @@ impl PayloadBuilderAttributes for EthPayloadBuilderAttributes {
                     None
                 }
             })
-            .map(BlobExcessGasAndPrice::new);
+            .map(|excess_blob_gas| BlobExcessGasAndPrice {
+                excess_blob_gas,
+                blob_gasprice: blob_params.calc_blob_fee(excess_blob_gas),
+            });
 
         let mut basefee =
             parent.next_block_base_fee(chain_spec.base_fee_params_at_timestamp(self.timestamp()));
@@ -395,7 +399,7 @@ mod tests {
 
         // check that it deserializes properly
         let genesis: Genesis = serde_json::from_str(hive_london).unwrap();
-        let chainspec = ChainSpec::from(genesis);
+        let chainspec = ChainSpec::try_from_genesis(genesis).unwrap();
         let payload_builder_attributes =
             EthPayloadBuilderAttributes::new(chainspec.genesis_hash(), attributes);
 
@@ -9,8 +9,8 @@ use reth_chainspec::{ChainSpec, EthereumHardforks, MAINNET};
 use reth_ethereum_consensus::validate_block_post_execution;
 use reth_evm::{
     execute::{
-        BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
-        BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
+        BatchExecutor, BlockExecutionCheckpoint, BlockExecutionError, BlockExecutionInput,
+        BlockExecutionOutput, BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
     },
     system_calls::{
         apply_beacon_root_contract_call, apply_consolidation_requests_contract_call,
@@ -20,18 +20,26 @@ use reth_evm::{
 };
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    BlockNumber, BlockWithSenders, EthereumHardfork, Header, Receipt, Request, U256,
+    BlockNumber, BlockWithSenders, EthereumHardfork, Header, InternalTransfer, Receipt, Request,
+    U256,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::{
     batch::BlockBatchRecord,
-    db::states::bundle_state::BundleRetention,
+    db::{
+        states::{
+            bundle_state::BundleRetention,
+            reverts::{AccountInfoRevert, Reverts},
+        },
+        BundleState,
+    },
+    internal_transfers::InternalTransfersInspector,
     state_change::{apply_blockhashes_update, post_block_balance_increments},
     Evm, State,
 };
 use revm_primitives::{
     db::{Database, DatabaseCommit},
-    BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, ResultAndState,
+    BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, HashMap, ResultAndState,
 };
 
 #[cfg(not(feature = "std"))]
@@ -44,6 +52,8 @@ use std::sync::Arc;
 pub struct EthExecutorProvider<EvmConfig = EthEvmConfig> {
     chain_spec: Arc<ChainSpec>,
     evm_config: EvmConfig,
+    /// Whether the internal transfers of transactions are recorded.
+    record_internal_transfers: bool,
 }
 
 impl EthExecutorProvider {
@@ -61,7 +71,14 @@ impl EthExecutorProvider {
 impl<EvmConfig> EthExecutorProvider<EvmConfig> {
     /// Creates a new executor provider.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { chain_spec, evm_config }
+        Self { chain_spec, evm_config, record_internal_transfers: false }
+    }
+
+    /// Sets whether the [internal transfers](InternalTransfer) of transactions are recorded during
+    /// execution.
+    pub const fn with_internal_transfers(mut self, record_internal_transfers: bool) -> Self {
+        self.record_internal_transfers = record_internal_transfers;
+        self
     }
 }
 
@@ -78,6 +95,7 @@ where
             self.evm_config.clone(),
             State::builder().with_database(db).with_bundle_update().without_state_clear().build(),
         )
+        .with_internal_transfers(self.record_internal_transfers)
     }
 }
 
@@ -112,9 +130,20 @@ where
 struct EthExecuteOutput {
     receipts: Vec<Receipt>,
     requests: Vec<Request>,
+    internal_transfers: Vec<Vec<InternalTransfer>>,
     gas_used: u64,
 }
 
+/// Helper type for the progress of executing a block, that may be interrupted after a gas
+/// threshold.
+#[derive(Debug, Clone)]
+enum EthExecuteProgress {
+    /// The entire block was executed.
+    Done(EthExecuteOutput),
+    /// The execution was interrupted before all transactions were executed.
+    Interrupted { receipts: Vec<Receipt>, internal_transfers: Vec<Vec<InternalTransfer>> },
+}
+
 /// Helper container type for EVM with chain spec.
 #[derive(Debug, Clone)]
 struct EthEvmExecutor<EvmConfig> {
@@ -131,8 +160,15 @@ where
     /// Executes the transactions in the block and returns the receipts of the transactions in the
     /// block, the total gas used and the list of EIP-7685 [requests](Request).
     ///
-    /// This applies the pre-execution and post-execution changes that require an [EVM](Evm), and
-    /// executes the transactions.
+    /// This applies the pre-execution and post-execution changes that require an [EVM](Evm),
+    /// including [`ConfigureEvm::apply_post_execution_changes`], and executes the transactions.
+    /// `on_transaction` is called with the external context of the [EVM](Evm) after every
+    /// transaction.
+    ///
+    /// The execution continues after the transactions of the given `receipts`, whose changes must
+    /// already be applied to the state, and is interrupted once the transactions executed by this
+    /// call used at least `gas_threshold` gas. If it is interrupted, the receipts of all executed
+    /// transactions are returned instead.
     ///
     /// # Note
     ///
@@ -142,32 +178,45 @@ where
         &self,
         block: &BlockWithSenders,
         mut evm: Evm<'_, Ext, &mut State<DB>>,
-    ) -> Result<EthExecuteOutput, BlockExecutionError>
+        mut receipts: Vec<Receipt>,
+        gas_threshold: Option<u64>,
+        mut on_transaction: impl FnMut(&mut Ext),
+    ) -> Result<Result<EthExecuteOutput, Vec<Receipt>>, BlockExecutionError>
     where
         DB: Database,
         DB::Error: Into<ProviderError> + Display,
     {
-        // apply pre execution changes
-        apply_beacon_root_contract_call(
-            &self.evm_config,
-            &self.chain_spec,
-            block.timestamp,
-            block.number,
-            block.parent_beacon_block_root,
-            &mut evm,
-        )?;
-        apply_blockhashes_update(
-            evm.db_mut(),
-            &self.chain_spec,
-            block.timestamp,
-            block.number,
-            block.parent_hash,
-        )?;
+        // apply pre execution changes, unless they were applied before the execution was
+        // interrupted
+        let executed = receipts.len();
+        if executed == 0 {
+            apply_beacon_root_contract_call(
+                &self.evm_config,
+                &self.chain_spec,
+                block.timestamp,
+                block.number,
+                block.parent_beacon_block_root,
+                &mut evm,
+            )?;
+            apply_blockhashes_update(
+                evm.db_mut(),
+                &self.chain_spec,
+                block.timestamp,
+                block.number,
+                block.parent_hash,
+            )?;
+        }
 
         // execute transactions
-        let mut cumulative_gas_used = 0;
-        let mut receipts = Vec::with_capacity(block.body.len());
-        for (sender, transaction) in block.transactions_with_sender() {
+        let mut cumulative_gas_used =
+            receipts.last().map(|receipt| receipt.cumulative_gas_used).unwrap_or_default();
+        let gas_threshold = gas_threshold.map(|threshold| cumulative_gas_used + threshold);
+        receipts.reserve(block.body.len() - executed);
+        for (sender, transaction) in block.transactions_with_sender().skip(executed) {
+            if gas_threshold.is_some_and(|threshold| cumulative_gas_used >= threshold) {
+                return Ok(Err(receipts))
+            }
+
             // The sum of the transaction’s gas limit, Tg, and the gas utilized in this block prior,
             // must be no greater than the block’s gasLimit.
             let block_available_gas = block.header.gas_limit - cumulative_gas_used;
@@ -197,6 +246,7 @@ where
                 }
             })?;
             evm.db_mut().commit(state);
+            on_transaction(&mut evm.context.external);
 
             // append gas used
             cumulative_gas_used += result.gas_used();
@@ -235,7 +285,15 @@ where
             vec![]
         };
 
-        Ok(EthExecuteOutput { receipts, requests, gas_used: cumulative_gas_used })
+        // apply the chain specific post-execution changes
+        self.evm_config.apply_post_execution_changes(&self.chain_spec, &mut evm)?;
+
+        Ok(Ok(EthExecuteOutput {
+            receipts,
+            requests,
+            internal_transfers: Vec::new(),
+            gas_used: cumulative_gas_used,
+        }))
     }
 }
 
@@ -250,12 +308,25 @@ pub struct EthBlockExecutor<EvmConfig, DB> {
     executor: EthEvmExecutor<EvmConfig>,
     /// The state to use for execution
     state: State<DB>,
+    /// Whether the internal transfers of transactions are recorded.
+    record_internal_transfers: bool,
 }
 
 impl<EvmConfig, DB> EthBlockExecutor<EvmConfig, DB> {
     /// Creates a new Ethereum block executor.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig, state: State<DB>) -> Self {
-        Self { executor: EthEvmExecutor { chain_spec, evm_config }, state }
+        Self {
+            executor: EthEvmExecutor { chain_spec, evm_config },
+            state,
+            record_internal_transfers: false,
+        }
+    }
+
+    /// Sets whether the [internal transfers](InternalTransfer) of transactions are recorded during
+    /// execution.
+    pub const fn with_internal_transfers(mut self, record_internal_transfers: bool) -> Self {
+        self.record_internal_transfers = record_internal_transfers;
+        self
     }
 
     #[inline]
@@ -296,8 +367,8 @@ where
 
     /// Execute a single block and apply the state changes to the internal state.
     ///
-    /// Returns the receipts of the transactions in the block, the total gas used and the list of
-    /// EIP-7685 [requests](Request).
+    /// Returns the receipts of the transactions in the block, the total gas used, the list of
+    /// EIP-7685 [requests](Request) and the internal transfers if they are recorded.
     ///
     /// Returns an error if execution fails.
     fn execute_without_verification(
@@ -305,20 +376,70 @@ where
         block: &BlockWithSenders,
         total_difficulty: U256,
     ) -> Result<EthExecuteOutput, BlockExecutionError> {
+        match self.execute_until(block, total_difficulty, Vec::new(), Vec::new(), None)? {
+            EthExecuteProgress::Done(output) => Ok(output),
+            EthExecuteProgress::Interrupted { .. } => {
+                unreachable!("execution without gas threshold is not interrupted")
+            }
+        }
+    }
+
+    /// Execute a single block, or the rest of it after the transactions of the given `receipts`,
+    /// and apply the state changes to the internal state.
+    ///
+    /// The execution is interrupted once the transactions executed by this call used at least
+    /// `gas_threshold` gas, in which case the post execution changes are not applied.
+    ///
+    /// Returns an error if execution fails.
+    fn execute_until(
+        &mut self,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+        receipts: Vec<Receipt>,
+        mut internal_transfers: Vec<Vec<InternalTransfer>>,
+        gas_threshold: Option<u64>,
+    ) -> Result<EthExecuteProgress, BlockExecutionError> {
         // 1. prepare state on new block
         self.on_new_block(&block.header);
 
         // 2. configure the evm and execute
         let env = self.evm_env_for_block(&block.header, total_difficulty);
-        let output = {
+        let output = if self.record_internal_transfers {
+            internal_transfers.reserve(block.body.len() - internal_transfers.len());
+            let evm = self.executor.evm_config.evm_with_env_and_inspector(
+                &mut self.state,
+                env,
+                InternalTransfersInspector::default(),
+            );
+            self.executor
+                .execute_state_transitions(
+                    block,
+                    evm,
+                    receipts,
+                    gas_threshold,
+                    |inspector: &mut InternalTransfersInspector| {
+                        internal_transfers.push(inspector.take_transfers())
+                    },
+                )?
+                .map(|output| EthExecuteOutput {
+                    internal_transfers: core::mem::take(&mut internal_transfers),
+                    ..output
+                })
+        } else {
             let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
-            self.executor.execute_state_transitions(block, evm)
-        }?;
+            self.executor.execute_state_transitions(block, evm, receipts, gas_threshold, |_| {})?
+        };
+        let output = match output {
+            Ok(output) => output,
+            Err(receipts) => {
+                return Ok(EthExecuteProgress::Interrupted { receipts, internal_transfers })
+            }
+        };
 
         // 3. apply post execution changes
         self.post_execution(block, total_difficulty)?;
 
-        Ok(output)
+        Ok(EthExecuteProgress::Done(output))
     }
 
     /// Apply settings before a new block is executed.
@@ -376,13 +497,19 @@ where
     /// Returns an error if the block could not be executed or failed verification.
     fn execute(mut self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
         let BlockExecutionInput { block, total_difficulty } = input;
-        let EthExecuteOutput { receipts, requests, gas_used } =
+        let EthExecuteOutput { receipts, requests, internal_transfers, gas_used } =
             self.execute_without_verification(block, total_difficulty)?;
 
         // NOTE: we need to merge keep the reverts for the bundle retention
         self.state.merge_transitions(BundleRetention::Reverts);
 
-        Ok(BlockExecutionOutput { state: self.state.take_bundle(), receipts, requests, gas_used })
+        Ok(BlockExecutionOutput {
+            state: self.state.take_bundle(),
+            receipts,
+            requests,
+            internal_transfers,
+            gas_used,
+        })
     }
 }
 
@@ -407,30 +534,36 @@ impl<EvmConfig, DB> EthBatchExecutor<EvmConfig, DB> {
     }
 }
 
-impl<EvmConfig, DB> BatchExecutor<DB> for EthBatchExecutor<EvmConfig, DB>
+impl<EvmConfig, DB> EthBatchExecutor<EvmConfig, DB>
 where
     EvmConfig: ConfigureEvm,
     DB: Database<Error: Into<ProviderError> + Display>,
 {
-    type Input<'a> = BlockExecutionInput<'a, BlockWithSenders>;
-    type Output = ExecutionOutcome;
-    type Error = BlockExecutionError;
+    /// Verifies the output of the executed block and adds it to the batch.
+    ///
+    /// If the execution of the block was resumed from a checkpoint, the reverts of the executed
+    /// parts are merged into the reverts of the block.
+    fn verify_and_save(
+        &mut self,
+        block: &BlockWithSenders,
+        output: EthExecuteOutput,
+        resumed: bool,
+    ) -> Result<(), BlockExecutionError> {
+        let EthExecuteOutput { receipts, requests, internal_transfers, gas_used: _ } = output;
 
-    fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error> {
-        let BlockExecutionInput { block, total_difficulty } = input;
+        validate_block_post_execution(block, self.executor.chain_spec(), &receipts, &requests)?;
 
         if self.batch_record.first_block().is_none() {
             self.batch_record.set_first_block(block.number);
         }
 
-        let EthExecuteOutput { receipts, requests, gas_used: _ } =
-            self.executor.execute_without_verification(block, total_difficulty)?;
-
-        validate_block_post_execution(block, self.executor.chain_spec(), &receipts, &requests)?;
-
         // prepare the state according to the prune mode
         let retention = self.batch_record.bundle_retention(block.number);
+        let retain_reverts = matches!(retention, BundleRetention::Reverts);
         self.executor.state.merge_transitions(retention);
+        if resumed {
+            merge_block_reverts(&mut self.executor.state.bundle_state, retain_reverts);
+        }
 
         // store receipts in the set
         self.batch_record.save_receipts(receipts)?;
@@ -438,8 +571,93 @@ where
         // store requests in the set
         self.batch_record.save_requests(requests);
 
+        // store internal transfers in the set
+        if self.executor.record_internal_transfers {
+            self.batch_record.save_internal_transfers(internal_transfers);
+        }
+
         Ok(())
     }
+}
+
+impl<EvmConfig, DB> BatchExecutor<DB> for EthBatchExecutor<EvmConfig, DB>
+where
+    EvmConfig: ConfigureEvm,
+    DB: Database<Error: Into<ProviderError> + Display>,
+{
+    type Input<'a> = BlockExecutionInput<'a, BlockWithSenders>;
+    type Output = ExecutionOutcome;
+    type Error = BlockExecutionError;
+
+    fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error> {
+        let BlockExecutionInput { block, total_difficulty } = input;
+
+        let output = self.executor.execute_without_verification(block, total_difficulty)?;
+        self.verify_and_save(block, output, false)
+    }
+
+    fn execute_and_verify_one_with_checkpoint(
+        &mut self,
+        input: Self::Input<'_>,
+        checkpoint: Option<BlockExecutionCheckpoint>,
+        gas_threshold: u64,
+    ) -> Result<Option<BlockExecutionCheckpoint>, Self::Error> {
+        // The changes of an interrupted execution are only valid on top of the database, so only
+        // the first block of the batch can be interrupted or resumed.
+        if self.batch_record.first_block().is_some() {
+            self.execute_and_verify_one(input)?;
+            return Ok(None)
+        }
+
+        let BlockExecutionInput { block, total_difficulty } = input;
+
+        let checkpoint = checkpoint.filter(|checkpoint| {
+            checkpoint.block_number == block.number &&
+                checkpoint.block_hash == block.header.hash_slow()
+        });
+        let resumed = checkpoint.is_some();
+        let (receipts, internal_transfers) = match checkpoint {
+            Some(BlockExecutionCheckpoint { receipts, internal_transfers, state, .. }) => {
+                // continue on top of the changes of the executed transactions
+                self.executor.state.bundle_state = state;
+                self.executor.state.use_preloaded_bundle = true;
+                (receipts, internal_transfers)
+            }
+            None => Default::default(),
+        };
+
+        let executed = receipts.len();
+        match self.executor.execute_until(
+            block,
+            total_difficulty,
+            receipts,
+            internal_transfers,
+            Some(gas_threshold),
+        )? {
+            EthExecuteProgress::Done(output) => {
+                self.verify_and_save(block, output, resumed)?;
+                Ok(None)
+            }
+            EthExecuteProgress::Interrupted { mut receipts, mut internal_transfers } => {
+                // only the changes of this part, the previous parts are already checkpointed
+                let mut state = BundleState::default();
+                if let Some(transitions) = self.executor.state.transition_state.take() {
+                    state.apply_transitions_and_create_reverts(
+                        transitions,
+                        BundleRetention::Reverts,
+                    );
+                }
+                Ok(Some(BlockExecutionCheckpoint {
+                    block_number: block.number,
+                    block_hash: block.header.hash_slow(),
+                    receipts: receipts.split_off(executed),
+                    internal_transfers: internal_transfers
+                        .split_off(executed.min(internal_transfers.len())),
+                    state,
+                }))
+            }
+        }
+    }
 
     fn finalize(mut self) -> Self::Output {
         ExecutionOutcome::new(
@@ -448,6 +666,7 @@ where
             self.batch_record.first_block().unwrap_or_default(),
             self.batch_record.take_requests(),
         )
+        .with_internal_transfers(self.batch_record.take_internal_transfers())
     }
 
     fn set_tip(&mut self, tip: BlockNumber) {
@@ -463,6 +682,46 @@ where
     }
 }
 
+/// Merges the reverts of a block that was executed in multiple parts into the reverts of a single
+/// block, or removes them if they are not retained.
+///
+/// The reverts of earlier parts take precedence, since they revert to the state before the block.
+fn merge_block_reverts(bundle: &mut BundleState, retain: bool) {
+    let mut reverts = bundle.take_all_reverts();
+    if !retain {
+        return
+    }
+
+    let mut parts = reverts.drain(..);
+    let Some(mut merged) = parts.next() else { return };
+    let mut indices = merged
+        .iter()
+        .enumerate()
+        .map(|(index, (address, _))| (*address, index))
+        .collect::<HashMap<_, _>>();
+    for (address, revert) in parts.flatten() {
+        match indices.get(&address) {
+            Some(index) => {
+                let merged_revert = &mut merged[*index].1;
+                if matches!(merged_revert.account, AccountInfoRevert::DoNothing) {
+                    merged_revert.account = revert.account;
+                }
+                for (slot, value) in revert.storage {
+                    merged_revert.storage.entry(slot).or_insert(value);
+                }
+                merged_revert.wipe_storage |= revert.wipe_storage;
+            }
+            None => {
+                indices.insert(address, merged.len());
+                merged.push((address, revert));
+            }
+        }
+    }
+
+    bundle.reverts_size = merged.iter().map(|(_, revert)| revert.size_hint()).sum();
+    bundle.reverts = Reverts::new(vec![merged]);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -472,15 +731,19 @@ mod tests {
         eip7002::{WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_PREDEPLOY_CODE},
     };
     use reth_chainspec::{ChainSpecBuilder, ForkCondition};
+    use reth_evm::{system_calls::apply_balance_increments, ConfigureEvmEnv};
     use reth_primitives::{
         constants::{EMPTY_ROOT_HASH, ETH_TO_WEI},
-        keccak256, public_key_to_address, Account, Block, Transaction, TxKind, TxLegacy, B256,
+        keccak256,
+        proofs::calculate_receipt_root_no_memo,
+        public_key_to_address, Account, Address, Block, Transaction, TransactionSigned, TxKind,
+        TxLegacy, TxType, B256,
     };
     use reth_revm::{
         database::StateProviderDatabase, test_utils::StateProviderTest, TransitionState,
     };
     use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
-    use revm_primitives::{b256, fixed_bytes, Bytes, BLOCKHASH_SERVE_WINDOW};
+    use revm_primitives::{b256, fixed_bytes, Bytes, Env, TxEnv, BLOCKHASH_SERVE_WINDOW};
     use secp256k1::{Keypair, Secp256k1};
     use std::collections::HashMap;
 
@@ -523,7 +786,7 @@ mod tests {
     }
 
     fn executor_provider(chain_spec: Arc<ChainSpec>) -> EthExecutorProvider<EthEvmConfig> {
-        EthExecutorProvider { chain_spec, evm_config: Default::default() }
+        EthExecutorProvider::new(chain_spec, Default::default())
     }
 
     #[test]
@@ -1406,4 +1669,283 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn records_internal_transfers() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        let mut db = StateProviderTest::default();
+
+        let secp = Secp256k1::new();
+        let sender_key_pair = Keypair::new(&secp, &mut generators::rng());
+        let sender_address = public_key_to_address(sender_key_pair.public_key());
+        db.insert_account(
+            sender_address,
+            Account { nonce: 1, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+
+        // a contract that forwards 7 wei of its balance to the receiver
+        let forwarder = Address::repeat_byte(0x02);
+        let receiver = Address::repeat_byte(0x03);
+        let mut code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x07, 0x73];
+        code.extend_from_slice(receiver.as_slice());
+        code.extend([0x5a, 0xf1, 0x50, 0x00]);
+        let code = Bytes::from(code);
+        db.insert_account(
+            forwarder,
+            Account { nonce: 1, balance: U256::ZERO, bytecode_hash: Some(keccak256(&code)) },
+            Some(code),
+            HashMap::new(),
+        );
+
+        let header = Header {
+            timestamp: 1,
+            number: 1,
+            gas_limit: 1_000_000,
+            base_fee_per_gas: Some(7),
+            ..Header::default()
+        };
+        let tx = sign_tx_with_key_pair(
+            sender_key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 1,
+                gas_price: 7,
+                gas_limit: 100_000,
+                to: TxKind::Call(forwarder),
+                value: U256::from(10),
+                input: Bytes::new(),
+            }),
+        );
+        let block = Block {
+            header,
+            body: vec![tx],
+            ommers: vec![],
+            withdrawals: Some(vec![].into()),
+            requests: None,
+        }
+        .with_recovered_senders()
+        .unwrap();
+
+        let provider = executor_provider(chain_spec);
+
+        // transfers are not recorded by default
+        let BlockExecutionOutput { internal_transfers, .. } = provider
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+        assert!(internal_transfers.is_empty());
+
+        let BlockExecutionOutput { receipts, internal_transfers, .. } = provider
+            .with_internal_transfers(true)
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+        assert!(receipts[0].success);
+        assert_eq!(
+            internal_transfers,
+            vec![vec![InternalTransfer { from: forwarder, to: receiver, value: U256::from(7) }]]
+        );
+    }
+
+    #[test]
+    fn resumes_interrupted_block_execution() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        let mut db = StateProviderTest::default();
+
+        let secp = Secp256k1::new();
+        let sender_key_pair = Keypair::new(&secp, &mut generators::rng());
+        let sender_address = public_key_to_address(sender_key_pair.public_key());
+        db.insert_account(
+            sender_address,
+            Account { nonce: 1, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+
+        // a block with four plain transfers
+        let receiver = Address::repeat_byte(0x03);
+        let transactions = (1..=4)
+            .map(|nonce| {
+                sign_tx_with_key_pair(
+                    sender_key_pair,
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(chain_spec.chain.id()),
+                        nonce,
+                        gas_price: 7,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(receiver),
+                        value: U256::from(nonce),
+                        input: Bytes::new(),
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        let receipts = (1..=4)
+            .map(|index| Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: 21_000 * index,
+                logs: vec![],
+            })
+            .collect::<Vec<_>>();
+        let header = Header {
+            timestamp: 1,
+            number: 1,
+            gas_limit: 1_000_000,
+            gas_used: 84_000,
+            base_fee_per_gas: Some(7),
+            receipts_root: calculate_receipt_root_no_memo(&receipts.iter().collect::<Vec<_>>()),
+            ..Header::default()
+        };
+        let block = Block {
+            header,
+            body: transactions,
+            ommers: vec![],
+            withdrawals: Some(vec![].into()),
+            requests: None,
+        }
+        .with_recovered_senders()
+        .unwrap();
+
+        let provider = executor_provider(chain_spec);
+
+        let mut expected = provider
+            .batch_executor(StateProviderDatabase::new(&db))
+            .execute_and_verify_batch([(&block, U256::ZERO).into()])
+            .unwrap();
+
+        // interrupt the execution after every transaction, and resume it with a new executor from
+        // the encoded checkpoints of the executed parts
+        let mut parts = Vec::<Vec<u8>>::new();
+        let mut outcome = loop {
+            let checkpoint = parts
+                .iter()
+                .map(|part| bincode::deserialize::<BlockExecutionCheckpoint>(part).unwrap())
+                .reduce(|mut checkpoint, part| {
+                    checkpoint.extend(part);
+                    checkpoint
+                });
+            let mut executor = provider.batch_executor(StateProviderDatabase::new(&db));
+            match executor
+                .execute_and_verify_one_with_checkpoint(
+                    (&block, U256::ZERO).into(),
+                    checkpoint,
+                    21_000,
+                )
+                .unwrap()
+            {
+                Some(part) => {
+                    assert_eq!(part.block_hash, block.header.hash_slow());
+                    assert_eq!(part.receipts[..], receipts[parts.len()..=parts.len()]);
+                    parts.push(bincode::serialize(&part).unwrap());
+                }
+                None => break executor.finalize(),
+            }
+        };
+        assert_eq!(parts.len(), 3);
+
+        expected.bundle.reverts.sort();
+        outcome.bundle.reverts.sort();
+        assert_eq!(outcome, expected);
+    }
+
+    /// Pays a fixed reward to a treasury at the end of every block.
+    #[derive(Debug, Clone)]
+    struct TreasuryEvmConfig {
+        inner: EthEvmConfig,
+        treasury: Address,
+        reward: U256,
+    }
+
+    impl ConfigureEvmEnv for TreasuryEvmConfig {
+        fn fill_tx_env(
+            &self,
+            tx_env: &mut TxEnv,
+            transaction: &TransactionSigned,
+            sender: Address,
+        ) {
+            self.inner.fill_tx_env(tx_env, transaction, sender)
+        }
+
+        fn fill_tx_env_system_contract_call(
+            &self,
+            env: &mut Env,
+            caller: Address,
+            contract: Address,
+            data: Bytes,
+        ) {
+            self.inner.fill_tx_env_system_contract_call(env, caller, contract, data)
+        }
+
+        fn fill_cfg_env(
+            &self,
+            cfg_env: &mut CfgEnvWithHandlerCfg,
+            chain_spec: &ChainSpec,
+            header: &Header,
+            total_difficulty: U256,
+        ) {
+            self.inner.fill_cfg_env(cfg_env, chain_spec, header, total_difficulty)
+        }
+    }
+
+    impl ConfigureEvm for TreasuryEvmConfig {
+        type DefaultExternalContext<'a> = ();
+
+        fn evm<DB: Database>(&self, db: DB) -> Evm<'_, Self::DefaultExternalContext<'_>, DB> {
+            self.inner.evm(db)
+        }
+
+        fn default_external_context<'a>(&self) -> Self::DefaultExternalContext<'a> {}
+
+        fn apply_post_execution_changes<EXT, DB>(
+            &self,
+            _chain_spec: &ChainSpec,
+            evm: &mut Evm<'_, EXT, DB>,
+        ) -> Result<(), BlockExecutionError>
+        where
+            DB: Database + DatabaseCommit,
+            DB::Error: Display,
+        {
+            apply_balance_increments(evm, [(self.treasury, self.reward)])
+        }
+    }
+
+    #[test]
+    fn applies_post_execution_changes() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+        let treasury = Address::with_last_byte(0x42);
+        let reward = U256::from(ETH_TO_WEI);
+        let evm_config = TreasuryEvmConfig { inner: EthEvmConfig::default(), treasury, reward };
+        let provider = EthExecutorProvider::new(chain_spec, evm_config);
+
+        let mut db = StateProviderTest::default();
+        db.insert_account(
+            treasury,
+            Account { balance: reward, nonce: 0, bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+
+        let block = BlockWithSenders {
+            block: Block {
+                header: Header { timestamp: 1, number: 1, ..Header::default() },
+                body: vec![],
+                ommers: vec![],
+                withdrawals: Some(vec![].into()),
+                requests: None,
+            },
+            senders: vec![],
+        };
+        let output = provider
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+
+        let treasury_account = output.state.account(&treasury).unwrap();
+        assert_eq!(treasury_account.info.as_ref().unwrap().balance, reward * U256::from(2));
+    }
 }
@@ -20,12 +20,17 @@ use reth_node_builder::{
     BuilderContext, ConfigureEvm, Node, PayloadBuilderConfig, PayloadTypes,
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
+use reth_payload_relay::{
+    BeaconChainConfig, BuilderSigner, RegistrationServer, RelayConfig, RelaySubmitter,
+};
 use reth_provider::CanonStateSubscriptions;
 use reth_rpc::EthApi;
-use reth_tracing::tracing::{debug, info};
+use reth_tasks::shutdown::ShutdownPhase;
+use reth_tracing::tracing::{debug, info, warn};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore,
+    ingress::{read_ingress_records, replay_ingress, IngressRecorder},
+    EthTransactionPool, TransactionPool, TransactionValidationTaskExecutor,
 };
 
 use crate::{EthEngineTypes, EthEvmConfig};
@@ -114,7 +119,8 @@ where
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
         let chain_spec = ctx.chain_spec();
         let evm_config = EthEvmConfig::default();
-        let executor = EthExecutorProvider::new(chain_spec, evm_config);
+        let executor = EthExecutorProvider::new(chain_spec, evm_config)
+            .with_internal_transfers(ctx.config().index.internal_transfers);
 
         Ok((evm_config, executor))
     }
@@ -151,8 +157,11 @@ where
                 blob_store.clone(),
             );
 
-        let transaction_pool =
+        let mut transaction_pool =
             reth_transaction_pool::Pool::eth_pool(validator, blob_store, pool_config);
+        if let Some(path) = &ctx.config().txpool.record {
+            transaction_pool = transaction_pool.with_ingress_recorder(IngressRecorder::new(path)?);
+        }
         info!(target: "reth::cli", "Transaction pool initialized");
         let transactions_path = data_dir.txpool_transactions();
 
@@ -161,11 +170,16 @@ where
             let pool = transaction_pool.clone();
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
+            let mut transactions_backup_config =
                 reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+            if ctx.config().txpool.persist_all {
+                transactions_backup_config =
+                    transactions_backup_config.with_pool_snapshot(data_dir.txpool_snapshot());
+            }
 
-            ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+            ctx.task_executor().spawn_critical_with_shutdown_phase(
                 "local transactions backup task",
+                ShutdownPhase::TransactionPool,
                 |shutdown| {
                     reth_transaction_pool::maintain::backup_local_transactions_task(
                         shutdown,
@@ -189,6 +203,15 @@ where
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
         }
 
+        if let Some(path) = &ctx.config().txpool.replay {
+            let records = read_ingress_records(path)?;
+            let pool = transaction_pool.clone();
+            ctx.task_executor().spawn(Box::pin(async move {
+                let outcome = replay_ingress(pool, records).await;
+                info!(target: "reth::cli", ?outcome, "Replayed transaction pool ingress");
+            }));
+        }
+
         Ok(transaction_pool)
     }
 }
@@ -232,6 +255,7 @@ where
             .interval(conf.interval())
             .deadline(conf.deadline())
             .max_payload_tasks(conf.max_payload_tasks())
+            .max_builds_per_job(conf.max_builds_per_job())
             .extradata(conf.extradata_bytes());
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
@@ -247,6 +271,30 @@ where
 
         ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
 
+        let builder_args = &ctx.config().builder;
+        if let Some(secret_key_path) = &builder_args.relay_secret_key {
+            let chain = ctx.chain_spec().chain;
+            let beacon = BeaconChainConfig::from_chain(chain).ok_or_else(|| {
+                eyre::eyre!("Submitting payloads to relays is not supported on chain {chain}")
+            })?;
+            let signer =
+                BuilderSigner::from_hex(&reth_fs_util::read_to_string(secret_key_path)?, &beacon)?;
+            let submitter = RelaySubmitter::new(
+                payload_builder.clone(),
+                RelayConfig::new(builder_args.relays.clone(), beacon),
+                signer,
+            );
+            if let Some(addr) = builder_args.relay_registration_addr {
+                let server = RegistrationServer::new(submitter.relays().clone());
+                ctx.task_executor().spawn(Box::pin(async move {
+                    if let Err(error) = server.serve(addr).await {
+                        warn!(target: "reth::cli", %error, "Failed to serve validator registrations");
+                    }
+                }));
+            }
+            ctx.task_executor().spawn(Box::pin(submitter.run()));
+        }
+
         Ok(payload_builder)
     }
 }
@@ -290,7 +338,10 @@ where
         if ctx.is_dev() {
             Ok(Arc::new(AutoSealConsensus::new(ctx.chain_spec())))
         } else {
-            Ok(Arc::new(EthBeaconConsensus::new(ctx.chain_spec())))
+            Ok(Arc::new(
+                EthBeaconConsensus::new(ctx.chain_spec())
+                    .with_validation_policy(ctx.validation_policy()),
+            ))
         }
     }
 }
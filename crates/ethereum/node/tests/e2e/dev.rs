@@ -80,5 +80,5 @@ fn custom_chain() -> Arc<ChainSpec> {
 }
 "#;
     let genesis: Genesis = serde_json::from_str(custom_genesis).unwrap();
-    Arc::new(genesis.into())
+    Arc::new(ChainSpec::try_from_genesis(genesis).unwrap())
 }
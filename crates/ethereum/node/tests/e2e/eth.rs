@@ -129,3 +129,40 @@ async fn test_failed_run_eth_node_with_no_auth_engine_api_over_ipc_opts() -> eyr
 
     Ok(())
 }
+
+#[tokio::test]
+async fn can_insert_external_block() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, _tasks, _wallet) = setup::<EthereumNode>(
+        1,
+        Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+                .cancun_activated()
+                .build(),
+        ),
+        false,
+    )
+    .await?;
+
+    let mut node = nodes.pop().unwrap();
+    let wallet = Wallet::default();
+    let raw_tx = TransactionTestContext::transfer_tx_bytes(1, wallet.inner).await;
+    let tx_hash = node.rpc.inject_tx(raw_tx).await?;
+
+    // build a block without submitting it via the engine API
+    let (payload, _) = node.new_payload(eth_payload_attributes).await?;
+    let block = payload.block().clone();
+
+    // feed the block into the engine through the handle of an external consensus driver
+    let outcome = node.inner.external_blocks.insert_block(block.clone()).await?;
+    assert!(outcome.payload_status.is_valid());
+    assert!(outcome.forkchoice_updated.is_valid());
+
+    // assert the block has been committed to the blockchain
+    node.assert_new_block(tx_hash, block.hash(), block.number).await?;
+
+    Ok(())
+}
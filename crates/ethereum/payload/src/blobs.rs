@@ -0,0 +1,175 @@
+//! Selection of the blob transactions of a payload.
+
+use reth_primitives::{Address, TxHash};
+use std::collections::{HashMap, HashSet};
+
+/// A pending blob transaction that can be included in a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobCandidate {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The number of blobs of the transaction.
+    pub blob_count: u64,
+    /// The fees paid to the block producer if the transaction is included.
+    pub value: u128,
+}
+
+/// The blob transactions that are included in a payload.
+///
+/// There's only room for a few blobs per block, so picking blob transactions greedily in the
+/// order of their priority fee can leave blob space unused or waste it on less valuable
+/// transactions: a transaction with a single blob and a slightly higher tip can crowd out a
+/// transaction with several blobs that pays more in total. Instead, the blob transactions are
+/// selected to maximize the total value that fits into the max blob count of the block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlobTransactionSelection {
+    selected: HashSet<TxHash>,
+    blob_count: u64,
+    value: u128,
+}
+
+impl BlobTransactionSelection {
+    /// Selects the candidates with the highest total value that fit into `max_blob_count` blobs.
+    ///
+    /// Transactions of the same sender can only be included in nonce order, so the candidates of
+    /// every sender must be yielded in nonce order, and only a prefix of them is selected. On
+    /// equal value, the selection with more blobs is preferred.
+    pub fn select(
+        candidates: impl IntoIterator<Item = BlobCandidate>,
+        max_blob_count: u64,
+    ) -> Self {
+        let capacity = max_blob_count as usize;
+
+        // the candidates of every sender, in nonce order
+        let mut senders = HashMap::<Address, usize>::new();
+        let mut groups = Vec::<Vec<BlobCandidate>>::new();
+        for candidate in candidates {
+            if candidate.blob_count == 0 || candidate.blob_count > max_blob_count {
+                continue
+            }
+            let group = *senders.entry(candidate.sender).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(candidate);
+        }
+
+        // best[c] is the best (value, blob count) using at most c blobs, and choices[g][c] is
+        // the number of transactions of group g that are included for it
+        let mut best = vec![(0u128, 0u64); capacity + 1];
+        let mut choices = Vec::with_capacity(groups.len());
+        for group in &groups {
+            let previous = best.clone();
+            let mut choice = vec![0; capacity + 1];
+            for (c, best) in best.iter_mut().enumerate() {
+                let (mut blobs, mut value) = (0, 0u128);
+                for (len, candidate) in group.iter().enumerate() {
+                    blobs += candidate.blob_count as usize;
+                    value = value.saturating_add(candidate.value);
+                    if blobs > c {
+                        break
+                    }
+                    let (prev_value, prev_blobs) = previous[c - blobs];
+                    let score = (prev_value.saturating_add(value), prev_blobs + blobs as u64);
+                    if score > *best {
+                        *best = score;
+                        choice[c] = len + 1;
+                    }
+                }
+            }
+            choices.push(choice);
+        }
+
+        let (value, blob_count) = best[capacity];
+        let mut selected = HashSet::new();
+        let mut remaining = capacity;
+        for (group, choice) in groups.iter().zip(&choices).rev() {
+            let included = &group[..choice[remaining]];
+            remaining -= included.iter().map(|tx| tx.blob_count as usize).sum::<usize>();
+            selected.extend(included.iter().map(|tx| tx.hash));
+        }
+
+        Self { selected, blob_count, value }
+    }
+
+    /// Returns true if the transaction is selected.
+    pub fn contains(&self, hash: &TxHash) -> bool {
+        self.selected.contains(hash)
+    }
+
+    /// Returns the number of selected transactions.
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    /// Returns true if no transaction is selected.
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Returns the total number of blobs of the selected transactions.
+    pub const fn blob_count(&self) -> u64 {
+        self.blob_count
+    }
+
+    /// Returns the total value of the selected transactions.
+    pub const fn value(&self) -> u128 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(sender: Address, blob_count: u64, value: u128) -> BlobCandidate {
+        BlobCandidate { hash: TxHash::random(), sender, blob_count, value }
+    }
+
+    #[test]
+    fn select_most_valuable_blobs() {
+        // greedily picking the highest value per blob would include `a`, `b` and `d`, for a
+        // total value of 42
+        let a = candidate(Address::random(), 1, 10);
+        let b = candidate(Address::random(), 3, 27);
+        let c = candidate(Address::random(), 5, 45);
+        let d = candidate(Address::random(), 1, 5);
+
+        let selection = BlobTransactionSelection::select([a, b, c, d], 6);
+        assert_eq!(selection.blob_count(), 6);
+        assert_eq!(selection.value(), 55);
+        assert!(selection.contains(&a.hash));
+        assert!(selection.contains(&c.hash));
+        assert_eq!(selection.len(), 2);
+    }
+
+    #[test]
+    fn select_in_nonce_order() {
+        // the second transaction of the sender is the most valuable, but can only be included
+        // after the first one
+        let sender = Address::random();
+        let first = candidate(sender, 4, 1);
+        let second = candidate(sender, 2, 100);
+        let other = candidate(Address::random(), 3, 50);
+
+        let selection = BlobTransactionSelection::select([first, second, other], 6);
+        assert_eq!(selection.value(), 101);
+        assert!(selection.contains(&first.hash));
+        assert!(selection.contains(&second.hash));
+        assert!(!selection.contains(&other.hash));
+
+        let selection = BlobTransactionSelection::select([first, second, other], 5);
+        assert_eq!(selection.value(), 50);
+        assert!(selection.contains(&other.hash));
+        assert_eq!(selection.len(), 1);
+    }
+
+    #[test]
+    fn select_without_blob_space() {
+        let selection = BlobTransactionSelection::select([candidate(Address::random(), 7, 100)], 6);
+        assert!(selection.is_empty());
+        assert_eq!(selection.blob_count(), 0);
+    }
+}
@@ -9,6 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![allow(clippy::useless_let_if_seq)]
 
+use crate::metrics::EthereumPayloadBuilderMetrics;
 use reth_basic_payload_builder::{
     commit_withdrawals, is_better_payload, BuildArguments, BuildOutcome, PayloadBuilder,
     PayloadConfig, WithdrawalsOutcome,
@@ -16,7 +17,8 @@ use reth_basic_payload_builder::{
 use reth_errors::RethError;
 use reth_evm::{
     system_calls::{
-        post_block_withdrawal_requests_contract_call, pre_block_beacon_root_contract_call,
+        post_block_execution_changes, post_block_withdrawal_requests_contract_call,
+        pre_block_beacon_root_contract_call,
     },
     ConfigureEvm,
 };
@@ -26,10 +28,7 @@ use reth_payload_builder::{
     error::PayloadBuilderError, EthBuiltPayload, EthPayloadBuilderAttributes,
 };
 use reth_primitives::{
-    constants::{
-        eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS,
-    },
-    eip4844::calculate_excess_blob_gas,
+    constants::{BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS},
     proofs::{self, calculate_requests_root},
     Block, EthereumHardforks, Header, IntoRecoveredTransaction, Receipt, EMPTY_OMMER_ROOT_HASH,
     U256,
@@ -44,6 +43,11 @@ use revm::{
 };
 use tracing::{debug, trace, warn};
 
+mod blobs;
+pub use blobs::{BlobCandidate, BlobTransactionSelection};
+
+mod metrics;
+
 /// Ethereum payload builder
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
@@ -148,6 +152,44 @@ where
             PayloadBuilderError::Internal(err.into())
         })?;
 
+        // Calculate the requests and the requests root, before the chain specific post-execution
+        // changes like the block executor does.
+        let (requests, requests_root) =
+            if chain_spec.is_prague_active_at_timestamp(attributes.timestamp) {
+                // We do not calculate the EIP-6110 deposit requests because there are no
+                // transactions in an empty payload.
+                let withdrawal_requests = post_block_withdrawal_requests_contract_call(
+                    &self.evm_config,
+                    &mut db,
+                    &initialized_cfg,
+                    &initialized_block_env,
+                )
+                .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+
+                let requests = withdrawal_requests;
+                let requests_root = calculate_requests_root(&requests);
+                (Some(requests.into()), Some(requests_root))
+            } else {
+                (None, None)
+            };
+
+        // apply the chain specific post-execution changes
+        post_block_execution_changes(
+            &self.evm_config,
+            &chain_spec,
+            &mut db,
+            &initialized_cfg,
+            &initialized_block_env,
+        )
+        .map_err(|err| {
+            warn!(target: "payload_builder",
+                parent_hash=%parent_block.hash(),
+                %err,
+                "failed to apply post-execution changes for empty payload"
+            );
+            PayloadBuilderError::Internal(err.into())
+        })?;
+
         let WithdrawalsOutcome { withdrawals_root, withdrawals } = commit_withdrawals(
             &mut db,
             &chain_spec,
@@ -182,39 +224,23 @@ where
         let mut blob_gas_used = None;
 
         if chain_spec.is_cancun_active_at_timestamp(attributes.timestamp) {
+            let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
             excess_blob_gas = if chain_spec.is_cancun_active_at_timestamp(parent_block.timestamp) {
                 let parent_excess_blob_gas = parent_block.excess_blob_gas.unwrap_or_default();
                 let parent_blob_gas_used = parent_block.blob_gas_used.unwrap_or_default();
-                Some(calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used))
+                Some(
+                    blob_params
+                        .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used),
+                )
             } else {
                 // for the first post-fork block, both parent.blob_gas_used and
                 // parent.excess_blob_gas are evaluated as 0
-                Some(calculate_excess_blob_gas(0, 0))
+                Some(blob_params.next_block_excess_blob_gas(0, 0))
             };
 
             blob_gas_used = Some(0);
         }
 
-        // Calculate the requests and the requests root.
-        let (requests, requests_root) =
-            if chain_spec.is_prague_active_at_timestamp(attributes.timestamp) {
-                // We do not calculate the EIP-6110 deposit requests because there are no
-                // transactions in an empty payload.
-                let withdrawal_requests = post_block_withdrawal_requests_contract_call(
-                    &self.evm_config,
-                    &mut db,
-                    &initialized_cfg,
-                    &initialized_block_env,
-                )
-                .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
-
-                let requests = withdrawal_requests;
-                let requests_root = calculate_requests_root(&requests);
-                (Some(requests.into()), Some(requests_root))
-            } else {
-                (None, None)
-            };
-
         let header = Header {
             parent_hash: parent_block.hash(),
             ommers_hash: EMPTY_OMMER_ROOT_HASH,
@@ -280,16 +306,26 @@ where
     debug!(target: "payload_builder", id=%attributes.id, parent_hash = ?parent_block.hash(), parent_number = parent_block.number, "building new payload");
     let mut cumulative_gas_used = 0;
     let mut sum_blob_gas_used = 0;
+    let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
+    let max_blob_gas_per_block = blob_params.max_blob_gas_per_block();
     let block_gas_limit: u64 =
         initialized_block_env.gas_limit.try_into().unwrap_or(chain_spec.max_gas_limit);
     let base_fee = initialized_block_env.basefee.to::<u64>();
 
     let mut executed_txs = Vec::new();
 
-    let mut best_txs = pool.best_transactions_with_attributes(BestTransactionsAttributes::new(
+    let best_txs_attributes = BestTransactionsAttributes::new(
         base_fee,
         initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    );
+
+    let mut best_txs = pool.best_transactions_with_attributes(best_txs_attributes);
+
+    // blob transactions are collected while the other transactions are included, and the ones
+    // that make the best use of the limited blob space are included after them
+    let mut blob_txs = Vec::new();
+    let mut selected_blob_txs = None;
+    let mut skipped_blob_txs = 0;
 
     let mut total_fees = U256::ZERO;
 
@@ -326,7 +362,42 @@ where
     .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
 
     let mut receipts = Vec::new();
-    while let Some(pool_tx) = best_txs.next() {
+    loop {
+        let pool_tx = if let Some(selected) = &mut selected_blob_txs {
+            let Some(pool_tx) = Iterator::next(selected) else { break };
+            pool_tx
+        } else if let Some(pool_tx) = best_txs.next() {
+            if pool_tx.is_eip4844() {
+                // a sender can't have both blob and other transactions in the pool, so no other
+                // transaction depends on a blob transaction
+                blob_txs.push(pool_tx);
+                continue
+            }
+            pool_tx
+        } else {
+            let selection = BlobTransactionSelection::select(
+                blob_txs.iter().filter_map(|tx| {
+                    let blob_count =
+                        tx.to_recovered_transaction().as_eip4844()?.blob_versioned_hashes.len();
+                    Some(BlobCandidate {
+                        hash: *tx.hash(),
+                        sender: tx.sender(),
+                        blob_count: blob_count as u64,
+                        value: tx
+                            .effective_tip_per_gas(base_fee)
+                            .unwrap_or_default()
+                            .saturating_mul(tx.gas_limit() as u128),
+                    })
+                }),
+                blob_params.max_blob_count,
+            );
+            skipped_blob_txs = (blob_txs.len() - selection.len()) as u64;
+            trace!(target: "payload_builder", selected=selection.len(), skipped=skipped_blob_txs, blob_count=selection.blob_count(), "selected blob transactions");
+            blob_txs.retain(|tx| selection.contains(tx.hash()));
+            selected_blob_txs = Some(std::mem::take(&mut blob_txs).into_iter());
+            continue
+        };
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -345,16 +416,12 @@ where
         let tx = pool_tx.to_recovered_transaction();
 
         // There's only limited amount of blob space available per block, so we need to check if
-        // the EIP-4844 can still fit in the block
+        // the selected EIP-4844 transaction can still fit in the block. The transaction is still
+        // valid, so it's only skipped instead of being marked as invalid.
         if let Some(blob_tx) = tx.transaction.as_eip4844() {
             let tx_blob_gas = blob_tx.blob_gas();
-            if sum_blob_gas_used + tx_blob_gas > MAX_DATA_GAS_PER_BLOCK {
-                // we can't fit this _blob_ transaction into the block, so we mark it as
-                // invalid, which removes its dependent transactions from
-                // the iterator. This is similar to the gas limit condition
-                // for regular transactions above.
+            if sum_blob_gas_used + tx_blob_gas > max_blob_gas_per_block {
                 trace!(target: "payload_builder", tx=?tx.hash, ?sum_blob_gas_used, ?tx_blob_gas, "skipping blob transaction because it would exceed the max data gas per block");
-                best_txs.mark_invalid(&pool_tx);
                 continue
             }
         }
@@ -399,13 +466,7 @@ where
 
         // add to the total blob gas used if the transaction successfully executed
         if let Some(blob_tx) = tx.transaction.as_eip4844() {
-            let tx_blob_gas = blob_tx.blob_gas();
-            sum_blob_gas_used += tx_blob_gas;
-
-            // if we've reached the max data gas per block, we can skip blob txs entirely
-            if sum_blob_gas_used == MAX_DATA_GAS_PER_BLOCK {
-                best_txs.skip_blobs();
-            }
+            sum_blob_gas_used += blob_tx.blob_gas();
         }
 
         let gas_used = result.gas_used();
@@ -439,6 +500,14 @@ where
         return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
     }
 
+    if chain_spec.is_cancun_active_at_timestamp(attributes.timestamp) {
+        EthereumPayloadBuilderMetrics::default().record_blobs(
+            sum_blob_gas_used,
+            max_blob_gas_per_block,
+            skipped_blob_txs,
+        );
+    }
+
     // calculate the requests and the requests root
     let (requests, requests_root) = if chain_spec
         .is_prague_active_at_timestamp(attributes.timestamp)
@@ -460,6 +529,16 @@ where
         (None, None)
     };
 
+    // apply the chain specific post-execution changes
+    post_block_execution_changes(
+        &evm_config,
+        &chain_spec,
+        &mut db,
+        &initialized_cfg,
+        &initialized_block_env,
+    )
+    .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+
     let WithdrawalsOutcome { withdrawals_root, withdrawals } =
         commit_withdrawals(&mut db, &chain_spec, attributes.timestamp, attributes.withdrawals)?;
 
@@ -498,14 +577,18 @@ where
             executed_txs.iter().filter(|tx| tx.is_eip4844()).map(|tx| tx.hash).collect(),
         )?;
 
+        let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
         excess_blob_gas = if chain_spec.is_cancun_active_at_timestamp(parent_block.timestamp) {
             let parent_excess_blob_gas = parent_block.excess_blob_gas.unwrap_or_default();
             let parent_blob_gas_used = parent_block.blob_gas_used.unwrap_or_default();
-            Some(calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used))
+            Some(
+                blob_params
+                    .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used),
+            )
         } else {
             // for the first post-fork block, both parent.blob_gas_used and
             // parent.excess_blob_gas are evaluated as 0
-            Some(calculate_excess_blob_gas(0, 0))
+            Some(blob_params.next_block_excess_blob_gas(0, 0))
         };
 
         blob_gas_used = Some(sum_blob_gas_used);
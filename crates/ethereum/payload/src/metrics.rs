@@ -0,0 +1,35 @@
+//! Metrics for the ethereum payload builder
+
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_primitives::constants::eip4844::DATA_GAS_PER_BLOB;
+
+/// Ethereum payload builder metrics
+#[derive(Metrics)]
+#[metrics(scope = "payloads.ethereum")]
+pub(crate) struct EthereumPayloadBuilderMetrics {
+    /// The number of blobs included in built payloads
+    pub(crate) blobs: Histogram,
+    /// The share of the blob space of built payloads that is used, between 0 and 1
+    pub(crate) blob_utilization: Histogram,
+    /// Total number of blob transactions that were skipped because they weren't selected
+    pub(crate) skipped_blob_transactions: Counter,
+}
+
+impl EthereumPayloadBuilderMetrics {
+    /// Records the blob usage of a built payload.
+    pub(crate) fn record_blobs(
+        &self,
+        blob_gas_used: u64,
+        max_blob_gas_per_block: u64,
+        skipped_blob_transactions: u64,
+    ) {
+        self.blobs.record((blob_gas_used / DATA_GAS_PER_BLOB) as f64);
+        if max_blob_gas_per_block > 0 {
+            self.blob_utilization.record(blob_gas_used as f64 / max_blob_gas_per_block as f64);
+        }
+        self.skipped_blob_transactions.increment(skipped_blob_transactions);
+    }
+}
@@ -696,6 +696,7 @@ mod tests {
             receipts,
             requests: vec![],
             first_block: 10,
+            internal_transfers: vec![],
         };
 
         // Create a Chain object with a BTreeMap of blocks mapped to their block numbers,
@@ -715,6 +716,7 @@ mod tests {
             receipts: Receipts { receipt_vec: vec![vec![Some(receipt1)]] },
             requests: vec![],
             first_block: 10,
+            internal_transfers: vec![],
         };
 
         // Assert that the execution outcome at the first block contains only the first receipt
@@ -1,4 +1,4 @@
-use reth_primitives::{Request, U256};
+use reth_primitives::{BlockNumber, InternalTransfer, Receipt, Request, B256, U256};
 use revm::db::BundleState;
 
 /// A helper type for ethereum block inputs that consists of a block and the total difficulty.
@@ -36,6 +36,50 @@ pub struct BlockExecutionOutput<T> {
     pub receipts: Vec<T>,
     /// All the EIP-7685 requests of the transactions in the block.
     pub requests: Vec<Request>,
+    /// The internal ETH transfers of each transaction in the block, empty if they were not
+    /// recorded.
+    pub internal_transfers: Vec<Vec<InternalTransfer>>,
     /// The total gas used by the block.
     pub gas_used: u64,
 }
+
+/// The progress of a part of a block execution that was interrupted after some of the transactions
+/// of the block.
+///
+/// The checkpoint only contains the changes of the transactions executed in the part, the
+/// checkpoints of consecutive parts are combined with [`BlockExecutionCheckpoint::extend`].
+/// Resuming the execution from the combined checkpoint produces the same output as executing the
+/// entire block at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockExecutionCheckpoint {
+    /// The number of the block.
+    pub block_number: BlockNumber,
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// The receipts of the executed transactions.
+    pub receipts: Vec<Receipt>,
+    /// The internal ETH transfers of the executed transactions, empty if they are not recorded.
+    pub internal_transfers: Vec<Vec<InternalTransfer>>,
+    /// The state changes of the executed part, with the reverts to the state before the part.
+    pub state: BundleState,
+}
+
+impl BlockExecutionCheckpoint {
+    /// Returns the number of executed transactions.
+    pub fn transactions(&self) -> usize {
+        self.receipts.len()
+    }
+
+    /// Returns the gas used by the executed transactions.
+    pub fn gas_used(&self) -> u64 {
+        self.receipts.last().map(|receipt| receipt.cumulative_gas_used).unwrap_or_default()
+    }
+
+    /// Extends the checkpoint with the checkpoint of the next part of the block execution.
+    pub fn extend(&mut self, other: Self) {
+        self.receipts.extend(other.receipts);
+        self.internal_transfers.extend(other.internal_transfers);
+        self.state.extend(other.state);
+    }
+}
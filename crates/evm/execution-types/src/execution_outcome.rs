@@ -1,6 +1,6 @@
 use reth_primitives::{
-    logs_bloom, Account, Address, BlockNumber, Bloom, Bytecode, Log, Receipt, Receipts, Requests,
-    StorageEntry, B256, U256,
+    logs_bloom, Account, Address, BlockNumber, Bloom, Bytecode, InternalTransfer, Log, Receipt,
+    Receipts, Requests, StorageEntry, B256, U256,
 };
 use reth_trie::HashedPostState;
 use revm::{
@@ -33,6 +33,13 @@ pub struct ExecutionOutcome {
     /// A transaction may have zero or more requests, so the length of the inner vector is not
     /// guaranteed to be the same as the number of transactions.
     pub requests: Vec<Requests>,
+    /// The collection of internal ETH transfers.
+    /// Outer vector stores the transfers of each block sequentially.
+    /// The middle vector stores the transfers of each transaction ordered by transaction number.
+    ///
+    /// Empty if internal transfers were not recorded during execution. The transfers of a block
+    /// are empty if they are pruned.
+    pub internal_transfers: Vec<Vec<Vec<InternalTransfer>>>,
 }
 
 /// Type used to initialize revms bundle state.
@@ -56,7 +63,7 @@ impl ExecutionOutcome {
         first_block: BlockNumber,
         requests: Vec<Requests>,
     ) -> Self {
-        Self { bundle, receipts, first_block, requests }
+        Self { bundle, receipts, first_block, requests, internal_transfers: Vec::new() }
     }
 
     /// Creates a new `ExecutionOutcome` from initialization parameters.
@@ -98,7 +105,7 @@ impl ExecutionOutcome {
             contracts_init.into_iter().map(|(code_hash, bytecode)| (code_hash, bytecode.0)),
         );
 
-        Self { bundle, receipts, first_block, requests }
+        Self { bundle, receipts, first_block, requests, internal_transfers: Vec::new() }
     }
 
     /// Return revm bundle state.
@@ -215,6 +222,16 @@ impl ExecutionOutcome {
         &self.receipts[index]
     }
 
+    /// Returns the internal transfers of the transactions of the block, ordered by transaction
+    /// number.
+    ///
+    /// Returns an empty slice if no transfers were recorded for the block.
+    pub fn internal_transfers_by_block(&self, block_number: BlockNumber) -> &[Vec<InternalTransfer>] {
+        self.block_number_to_index(block_number)
+            .and_then(|index| self.internal_transfers.get(index))
+            .map_or(&[], Vec::as_slice)
+    }
+
     /// Is execution outcome empty.
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -248,6 +265,8 @@ impl ExecutionOutcome {
         self.receipts.truncate(new_len);
         // remove requests
         self.requests.truncate(new_len);
+        // remove internal transfers
+        self.internal_transfers.truncate(new_len);
         // Revert last n reverts.
         self.bundle.revert(rm_trx);
 
@@ -280,6 +299,11 @@ impl ExecutionOutcome {
         if at_idx < higher_state.requests.len() {
             higher_state.requests = higher_state.requests.split_off(at_idx);
         }
+        if at_idx < higher_state.internal_transfers.len() {
+            higher_state.internal_transfers = higher_state.internal_transfers.split_off(at_idx);
+        } else {
+            higher_state.internal_transfers.clear();
+        }
         higher_state.bundle.take_n_reverts(at_idx);
         higher_state.first_block = at;
 
@@ -292,6 +316,11 @@ impl ExecutionOutcome {
     /// we know that other state was build on top of this one.
     /// In most cases this would be true.
     pub fn extend(&mut self, other: Self) {
+        if !other.internal_transfers.is_empty() {
+            // keep the transfers aligned with the blocks if none were recorded for this state
+            self.internal_transfers.resize(self.len(), Vec::new());
+        }
+        self.internal_transfers.extend(other.internal_transfers);
         self.bundle.extend(other.bundle);
         self.receipts.extend(other.receipts.receipt_vec);
         self.requests.extend(other.requests);
@@ -324,6 +353,15 @@ impl ExecutionOutcome {
         self.requests = requests;
         self
     }
+
+    /// Create a new instance with updated internal transfers.
+    pub fn with_internal_transfers(
+        mut self,
+        internal_transfers: Vec<Vec<Vec<InternalTransfer>>>,
+    ) -> Self {
+        self.internal_transfers = internal_transfers;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -391,6 +429,7 @@ mod tests {
             receipts: receipts.clone(),
             requests: requests.clone(),
             first_block,
+            internal_transfers: vec![],
         };
 
         // Assert that creating a new ExecutionOutcome using the constructor matches exec_res
@@ -453,6 +492,7 @@ mod tests {
             receipts,
             requests: vec![],
             first_block,
+            internal_transfers: vec![],
         };
 
         // Test before the first block
@@ -491,6 +531,7 @@ mod tests {
             receipts,
             requests: vec![],
             first_block,
+            internal_transfers: vec![],
         };
 
         // Get logs for block number 123
@@ -526,6 +567,7 @@ mod tests {
             receipts,                   // Include the created receipts
             requests: vec![],           // Empty vector for requests
             first_block,                // Set the first block number
+            internal_transfers: vec![],
         };
 
         // Get receipts for block number 123 and convert the result into a vector
@@ -576,6 +618,7 @@ mod tests {
             receipts,                   // Include the created receipts
             requests: vec![],           // Empty vector for requests
             first_block,                // Set the first block number
+            internal_transfers: vec![],
         };
 
         // Assert that the length of receipts in exec_res is 1
@@ -590,6 +633,7 @@ mod tests {
             receipts: receipts_empty,   // Include the empty receipts
             requests: vec![],           // Empty vector for requests
             first_block,                // Set the first block number
+            internal_transfers: vec![],
         };
 
         // Assert that the length of receipts in exec_res_empty_receipts is 0
@@ -636,7 +680,7 @@ mod tests {
         // Create a ExecutionOutcome object with the created bundle, receipts, requests, and
         // first_block
         let mut exec_res =
-            ExecutionOutcome { bundle: Default::default(), receipts, requests, first_block };
+            ExecutionOutcome { bundle: Default::default(), receipts, requests, first_block, internal_transfers: vec![] };
 
         // Assert that the revert_to method returns true when reverting to the initial block number.
         assert!(exec_res.revert_to(123));
@@ -690,7 +734,7 @@ mod tests {
 
         // Create an ExecutionOutcome object.
         let mut exec_res =
-            ExecutionOutcome { bundle: Default::default(), receipts, requests, first_block };
+            ExecutionOutcome { bundle: Default::default(), receipts, requests, first_block, internal_transfers: vec![] };
 
         // Extend the ExecutionOutcome object by itself.
         exec_res.extend(exec_res.clone());
@@ -705,6 +749,7 @@ mod tests {
                 },
                 requests: vec![Requests(vec![request]), Requests(vec![request])],
                 first_block: 123,
+                internal_transfers: vec![],
             }
         );
     }
@@ -751,7 +796,7 @@ mod tests {
         // Create a ExecutionOutcome object with the created bundle, receipts, requests, and
         // first_block
         let exec_res =
-            ExecutionOutcome { bundle: Default::default(), receipts, requests, first_block };
+            ExecutionOutcome { bundle: Default::default(), receipts, requests, first_block, internal_transfers: vec![] };
 
         // Split the ExecutionOutcome at block number 124
         let result = exec_res.clone().split_at(124);
@@ -762,6 +807,7 @@ mod tests {
             receipts: Receipts { receipt_vec: vec![vec![Some(receipt.clone())]] },
             requests: vec![Requests(vec![request])],
             first_block,
+            internal_transfers: vec![],
         };
 
         // Define the expected higher ExecutionOutcome after splitting
@@ -772,6 +818,7 @@ mod tests {
             },
             requests: vec![Requests(vec![request]), Requests(vec![request])],
             first_block: 124,
+            internal_transfers: vec![],
         };
 
         // Assert that the split result matches the expected lower and higher outcomes
@@ -19,3 +19,9 @@ pub use execute::*;
 
 mod execution_outcome;
 pub use execution_outcome::*;
+
+mod proposer;
+pub use proposer::*;
+
+mod state_diff;
+pub use state_diff::*;
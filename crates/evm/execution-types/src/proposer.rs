@@ -0,0 +1,159 @@
+use reth_primitives::{Address, BlockWithSenders, TxHash, U256};
+use revm::{db::BundleState, primitives::AccountInfo};
+
+/// The value a block delivered to its proposer.
+///
+/// The proposer is paid either directly, if the fee recipient of the block is an address of the
+/// proposer, or by the builder of the block, which sets its own address as fee recipient and pays
+/// the proposer with the last transaction of the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProposerRevenue {
+    /// The fee recipient of the block.
+    pub fee_recipient: Address,
+    /// The increase of the balance of the fee recipient by the block, zero if the balance
+    /// decreased.
+    pub fee_recipient_revenue: U256,
+    /// The explicit payment of the fee recipient to the proposer, if any.
+    pub payment: Option<ProposerPayment>,
+}
+
+/// A payment of the fee recipient of a block to the proposer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProposerPayment {
+    /// The hash of the payment transaction.
+    pub transaction_hash: TxHash,
+    /// The index of the payment transaction in the block.
+    pub transaction_index: u64,
+    /// The recipient of the payment.
+    pub recipient: Address,
+    /// The value of the payment.
+    pub value: U256,
+}
+
+impl ProposerRevenue {
+    /// Creates the revenue of the block from the balances of the fee recipient before and after
+    /// the block.
+    ///
+    /// The last transaction of the block is considered a payment to the proposer if it's sent by
+    /// the fee recipient to another address.
+    pub fn new(block: &BlockWithSenders, balance_before: U256, balance_after: U256) -> Self {
+        let fee_recipient = block.beneficiary;
+        let payment = block
+            .body
+            .iter()
+            .zip(&block.senders)
+            .enumerate()
+            .last()
+            .filter(|(_, (_, sender))| **sender == fee_recipient)
+            .and_then(|(index, (tx, _))| {
+                let recipient = tx.to().filter(|to| *to != fee_recipient)?;
+                Some(ProposerPayment {
+                    transaction_hash: tx.hash(),
+                    transaction_index: index as u64,
+                    recipient,
+                    value: tx.value(),
+                })
+            });
+
+        Self {
+            fee_recipient,
+            fee_recipient_revenue: balance_after.saturating_sub(balance_before),
+            payment,
+        }
+    }
+
+    /// Creates the revenue of the block from the state changes of its execution.
+    pub fn from_bundle_state(block: &BlockWithSenders, state: &BundleState) -> Self {
+        let (before, after) = state
+            .account(&block.beneficiary)
+            .map(|account| {
+                let balance = |info: &Option<AccountInfo>| {
+                    info.as_ref().map_or(U256::ZERO, |info| info.balance)
+                };
+                (balance(&account.original_info), balance(&account.info))
+            })
+            .unwrap_or_default();
+        Self::new(block, before, after)
+    }
+
+    /// Returns the value delivered to the proposer, the value of the payment if the fee recipient
+    /// paid the proposer, otherwise the revenue of the fee recipient.
+    pub fn proposer_value(&self) -> U256 {
+        self.payment.map_or(self.fee_recipient_revenue, |payment| payment.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        Block, Header, Signature, Transaction, TransactionSigned, TxEip1559, TxKind,
+    };
+
+    fn transfer(to: Address, value: u64) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip1559(TxEip1559 {
+                to: TxKind::Call(to),
+                value: U256::from(value),
+                ..Default::default()
+            }),
+            Signature::default(),
+        )
+    }
+
+    fn block(
+        fee_recipient: Address,
+        transactions: Vec<(Address, TransactionSigned)>,
+    ) -> BlockWithSenders {
+        let (senders, body) = transactions.into_iter().unzip();
+        BlockWithSenders {
+            block: Block {
+                header: Header { beneficiary: fee_recipient, ..Default::default() },
+                body,
+                ..Default::default()
+            },
+            senders,
+        }
+    }
+
+    #[test]
+    fn proposer_revenue_without_payment() {
+        let fee_recipient = Address::random();
+        // the fee recipient only receives the fees, a transfer to itself is not a payment
+        let block = block(
+            fee_recipient,
+            vec![
+                (Address::random(), transfer(Address::random(), 1)),
+                (fee_recipient, transfer(fee_recipient, 2)),
+            ],
+        );
+
+        let revenue = ProposerRevenue::new(&block, U256::from(10), U256::from(15));
+        assert_eq!(revenue.payment, None);
+        assert_eq!(revenue.fee_recipient_revenue, U256::from(5));
+        assert_eq!(revenue.proposer_value(), U256::from(5));
+    }
+
+    #[test]
+    fn proposer_revenue_with_payment() {
+        let (builder, proposer) = (Address::random(), Address::random());
+        let payment = transfer(proposer, 100);
+        let block = block(
+            builder,
+            vec![(Address::random(), transfer(Address::random(), 1)), (builder, payment.clone())],
+        );
+
+        let revenue = ProposerRevenue::new(&block, U256::from(10), U256::from(5));
+        assert_eq!(revenue.fee_recipient_revenue, U256::ZERO);
+        assert_eq!(
+            revenue.payment,
+            Some(ProposerPayment {
+                transaction_hash: payment.hash(),
+                transaction_index: 1,
+                recipient: proposer,
+                value: U256::from(100),
+            })
+        );
+        assert_eq!(revenue.proposer_value(), U256::from(100));
+    }
+}
@@ -0,0 +1,257 @@
+use reth_primitives::{Account, Address, Bytecode, Bytes, B256, KECCAK_EMPTY, U256};
+use revm::db::states::BundleState;
+use std::collections::BTreeMap;
+
+/// The state changes of a block, with the values of the changed accounts and storage slots before
+/// and after the block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    /// The changed accounts.
+    pub accounts: BTreeMap<Address, AccountDiff>,
+}
+
+/// The changes of a single account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountDiff {
+    /// The account before the changes, `None` if it didn't exist.
+    pub before: Option<Account>,
+    /// The account after the changes, `None` if it doesn't exist anymore.
+    pub after: Option<Account>,
+    /// The code of the account before and after the changes, if it changed. The code is empty if
+    /// the account has no code.
+    pub code: Option<Delta<Bytes>>,
+    /// The changed storage slots.
+    pub storage: BTreeMap<B256, Delta<U256>>,
+}
+
+/// A value before and after a change.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Delta<T> {
+    /// The value before the change.
+    pub from: T,
+    /// The value after the change.
+    pub to: T,
+}
+
+impl<T: PartialEq> Delta<T> {
+    /// Returns the delta if the value changed.
+    pub fn changed(from: T, to: T) -> Option<Self> {
+        (from != to).then_some(Self { from, to })
+    }
+}
+
+impl AccountDiff {
+    /// Creates the diff of an account, looking up its code before and after the changes with the
+    /// given function if the code hash of the account changed.
+    pub fn new<E>(
+        before: Option<Account>,
+        after: Option<Account>,
+        storage: BTreeMap<B256, Delta<U256>>,
+        mut code_by_hash: impl FnMut(B256) -> Result<Option<Bytecode>, E>,
+    ) -> Result<Self, E> {
+        let code_hash = |account: Option<Account>| {
+            account.map_or(KECCAK_EMPTY, |account| account.get_bytecode_hash())
+        };
+        let (from, to) = (code_hash(before), code_hash(after));
+
+        let mut code = |hash| -> Result<Bytes, E> {
+            if hash == KECCAK_EMPTY {
+                return Ok(Bytes::new())
+            }
+            Ok(code_by_hash(hash)?.map(|code| code.original_bytes()).unwrap_or_default())
+        };
+        let code = if from == to { None } else { Some(Delta { from: code(from)?, to: code(to)? }) };
+
+        Ok(Self { before, after, code, storage })
+    }
+
+    /// Returns the balance before and after the changes, if it changed. The balance of an account
+    /// that doesn't exist is zero.
+    pub fn balance(&self) -> Option<Delta<U256>> {
+        Delta::changed(
+            self.before.map_or(U256::ZERO, |account| account.balance),
+            self.after.map_or(U256::ZERO, |account| account.balance),
+        )
+    }
+
+    /// Returns the nonce before and after the changes, if it changed. The nonce of an account that
+    /// doesn't exist is zero.
+    pub fn nonce(&self) -> Option<Delta<u64>> {
+        Delta::changed(
+            self.before.map_or(0, |account| account.nonce),
+            self.after.map_or(0, |account| account.nonce),
+        )
+    }
+}
+
+impl StateDiff {
+    /// Computes the diff of all changes of the bundle state.
+    ///
+    /// The code of the accounts that is not part of the bundle state, e.g. the code of an account
+    /// that self-destructed, is looked up with the given function.
+    ///
+    /// The storage of an account that was destroyed is wiped, and the bundle state only contains
+    /// the slots that were written afterwards. All slots of such an account before the changes
+    /// are looked up with `storage_before`, so that the wiped slots are part of the diff.
+    pub fn from_bundle<E>(
+        bundle: &BundleState,
+        mut code_by_hash: impl FnMut(B256) -> Result<Option<Bytecode>, E>,
+        mut storage_before: impl FnMut(Address) -> Result<BTreeMap<B256, U256>, E>,
+    ) -> Result<Self, E> {
+        let mut accounts = BTreeMap::new();
+        for (address, account) in &bundle.state {
+            let before = account.original_info.clone().map(Account::from);
+            let after = account.info.clone().map(Account::from);
+            let storage = if account.was_destroyed() {
+                // the original values of a destroyed account are not known to the bundle state
+                let mut storage = storage_before(*address)?
+                    .into_iter()
+                    .map(|(slot, value)| (slot, Delta { from: value, to: U256::ZERO }))
+                    .collect::<BTreeMap<_, _>>();
+                for (slot, value) in &account.storage {
+                    let slot = B256::from(*slot);
+                    let from = storage.get(&slot).map_or(U256::ZERO, |delta| delta.from);
+                    storage.insert(slot, Delta { from, to: value.present_value });
+                }
+                storage.retain(|_, delta| delta.from != delta.to);
+                storage
+            } else {
+                account
+                    .storage
+                    .iter()
+                    .filter_map(|(slot, value)| {
+                        let delta =
+                            Delta::changed(value.previous_or_original_value, value.present_value)?;
+                        Some((B256::from(*slot), delta))
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            };
+            if before == after && storage.is_empty() {
+                continue
+            }
+
+            let diff = AccountDiff::new(before, after, storage, |hash| {
+                match bundle.contracts.get(&hash) {
+                    Some(code) => Ok(Some(Bytecode(code.clone()))),
+                    None => code_by_hash(hash),
+                }
+            })?;
+            accounts.insert(*address, diff);
+        }
+        Ok(Self { accounts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        db::{states::StorageSlot, AccountStatus, BundleAccount},
+        primitives::{AccountInfo, Bytecode as RevmBytecode},
+    };
+    use std::{collections::HashMap, convert::Infallible};
+
+    #[test]
+    fn state_diff_from_bundle() {
+        let (created, updated, unchanged) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let code = RevmBytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let code_hash = code.hash_slow();
+        let info = |balance: u64, nonce| AccountInfo {
+            balance: U256::from(balance),
+            nonce,
+            ..Default::default()
+        };
+
+        let bundle = BundleState::new(
+            [
+                (
+                    created,
+                    None,
+                    Some(AccountInfo { code_hash, code: Some(code.clone()), ..info(0, 1) }),
+                    HashMap::from([(U256::from(1), (U256::ZERO, U256::from(7)))]),
+                ),
+                (updated, Some(info(10, 0)), Some(info(5, 1)), HashMap::new()),
+                (
+                    unchanged,
+                    Some(info(1, 0)),
+                    Some(info(1, 0)),
+                    HashMap::from([(U256::from(1), (U256::from(3), U256::from(3)))]),
+                ),
+            ],
+            [Vec::<(Address, Option<Option<AccountInfo>>, Vec<(U256, U256)>)>::new()],
+            [(code_hash, code)],
+        );
+        let diff =
+            StateDiff::from_bundle(&bundle, |_| Ok::<_, Infallible>(None), |_| Ok(BTreeMap::new()))
+                .unwrap();
+        assert_eq!(diff.accounts.keys().copied().collect::<Vec<_>>(), vec![created, updated]);
+
+        let created = &diff.accounts[&created];
+        assert_eq!(created.before, None);
+        assert_eq!(created.nonce(), Some(Delta { from: 0, to: 1 }));
+        assert_eq!(created.balance(), None);
+        assert_eq!(
+            created.code,
+            Some(Delta { from: Bytes::new(), to: Bytes::from_static(&[0x60, 0x00]) })
+        );
+        assert_eq!(
+            created.storage,
+            BTreeMap::from([(
+                B256::with_last_byte(1),
+                Delta { from: U256::ZERO, to: U256::from(7) }
+            )])
+        );
+
+        let updated = &diff.accounts[&updated];
+        assert_eq!(updated.balance(), Some(Delta { from: U256::from(10), to: U256::from(5) }));
+        assert_eq!(updated.nonce(), Some(Delta { from: 0, to: 1 }));
+        assert_eq!(updated.code, None);
+        assert!(updated.storage.is_empty());
+    }
+
+    #[test]
+    fn state_diff_from_bundle_with_destroyed_account() {
+        let destroyed = Address::with_last_byte(1);
+        let info = AccountInfo { nonce: 1, ..Default::default() };
+
+        // the account is destroyed and recreated, and only slot 2 is written afterwards
+        let mut bundle = BundleState::default();
+        bundle.state.insert(
+            destroyed,
+            BundleAccount::new(
+                Some(info.clone()),
+                Some(info),
+                HashMap::from([
+                    (U256::from(2), StorageSlot::new_changed(U256::ZERO, U256::from(5))),
+                    (U256::from(3), StorageSlot::new_changed(U256::ZERO, U256::from(6))),
+                ]),
+                AccountStatus::DestroyedChanged,
+            ),
+        );
+
+        let storage_before = BTreeMap::from([
+            (B256::with_last_byte(1), U256::from(1)),
+            (B256::with_last_byte(2), U256::from(2)),
+            (B256::with_last_byte(3), U256::from(6)),
+        ]);
+        let diff = StateDiff::from_bundle(
+            &bundle,
+            |_| Ok::<_, Infallible>(None),
+            |address| {
+                assert_eq!(address, destroyed);
+                Ok(storage_before.clone())
+            },
+        )
+        .unwrap();
+
+        // the wiped slot is cleared, and the slot that was written back to its value is unchanged
+        assert_eq!(
+            diff.accounts[&destroyed].storage,
+            BTreeMap::from([
+                (B256::with_last_byte(1), Delta { from: U256::from(1), to: U256::ZERO }),
+                (B256::with_last_byte(2), Delta { from: U256::from(2), to: U256::from(5) }),
+            ])
+        );
+    }
+}
@@ -4,7 +4,9 @@ use core::fmt::Display;
 
 use crate::execute::{BatchExecutor, BlockExecutorProvider, Executor};
 use reth_execution_errors::BlockExecutionError;
-use reth_execution_types::{BlockExecutionInput, BlockExecutionOutput, ExecutionOutcome};
+use reth_execution_types::{
+    BlockExecutionCheckpoint, BlockExecutionInput, BlockExecutionOutput, ExecutionOutcome,
+};
 use reth_primitives::{BlockNumber, BlockWithSenders, Receipt};
 use reth_prune_types::PruneModes;
 use reth_storage_errors::provider::ProviderError;
@@ -100,6 +102,22 @@ where
         }
     }
 
+    fn execute_and_verify_one_with_checkpoint(
+        &mut self,
+        input: Self::Input<'_>,
+        checkpoint: Option<BlockExecutionCheckpoint>,
+        gas_threshold: u64,
+    ) -> Result<Option<BlockExecutionCheckpoint>, Self::Error> {
+        match self {
+            Self::Left(a) => {
+                a.execute_and_verify_one_with_checkpoint(input, checkpoint, gas_threshold)
+            }
+            Self::Right(b) => {
+                b.execute_and_verify_one_with_checkpoint(input, checkpoint, gas_threshold)
+            }
+        }
+    }
+
     fn finalize(self) -> Self::Output {
         match self {
             Self::Left(a) => a.finalize(),
@@ -2,7 +2,9 @@
 
 // Re-export execution types
 pub use reth_execution_errors::{BlockExecutionError, BlockValidationError};
-pub use reth_execution_types::{BlockExecutionInput, BlockExecutionOutput, ExecutionOutcome};
+pub use reth_execution_types::{
+    BlockExecutionCheckpoint, BlockExecutionInput, BlockExecutionOutput, ExecutionOutcome,
+};
 pub use reth_storage_errors::provider::ProviderError;
 
 use core::fmt::Display;
@@ -47,6 +49,24 @@ pub trait BatchExecutor<DB> {
     /// Executes the next block in the batch, verifies the output and updates the state internally.
     fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error>;
 
+    /// Executes the next block in the batch like [`BatchExecutor::execute_and_verify_one`], but
+    /// interrupts the execution once the executed transactions used at least `gas_threshold` gas.
+    ///
+    /// The execution is resumed from the given checkpoint, if it belongs to the block. If the
+    /// execution is interrupted, the checkpoint to resume it is returned and the state of the batch
+    /// must be discarded.
+    ///
+    /// The default implementation executes the entire block at once.
+    fn execute_and_verify_one_with_checkpoint(
+        &mut self,
+        input: Self::Input<'_>,
+        _checkpoint: Option<BlockExecutionCheckpoint>,
+        _gas_threshold: u64,
+    ) -> Result<Option<BlockExecutionCheckpoint>, Self::Error> {
+        self.execute_and_verify_one(input)?;
+        Ok(None)
+    }
+
     /// Executes multiple inputs in the batch, verifies the output, and updates the state
     /// internally.
     ///
@@ -16,10 +16,12 @@ use core::ops::Deref;
 
 use crate::builder::RethEvmBuilder;
 use reth_chainspec::ChainSpec;
+use reth_execution_errors::BlockExecutionError;
 use reth_primitives::{Address, Header, TransactionSigned, TransactionSignedEcRecovered, U256};
-use revm::{Database, Evm, GetInspector};
+use revm::{Database, DatabaseCommit, Evm, GetInspector};
 use revm_primitives::{
-    BlockEnv, Bytes, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, SpecId, TxEnv,
+    BlobExcessGasAndPrice, BlockEnv, Bytes, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, SpecId,
+    TxEnv,
 };
 
 pub mod builder;
@@ -98,6 +100,30 @@ pub trait ConfigureEvm: ConfigureEvmEnv {
 
     /// Provides the default external context.
     fn default_external_context<'a>(&self) -> Self::DefaultExternalContext<'a>;
+
+    /// Applies chain specific state changes at the end of a block, e.g. the block rewards,
+    /// treasury payments or system calls of a sidechain.
+    ///
+    /// This is called with an EVM that is configured for the block, after the transactions and the
+    /// post-execution system calls of the block were executed, but before the withdrawals and the
+    /// block rewards of the protocol are applied. The block executor and the payload builder call
+    /// this the same way, so that built blocks pass validation.
+    ///
+    /// Balances can be increased with [`system_calls::apply_balance_increments`].
+    ///
+    /// Does nothing by default.
+    fn apply_post_execution_changes<EXT, DB>(
+        &self,
+        chain_spec: &ChainSpec,
+        evm: &mut Evm<'_, EXT, DB>,
+    ) -> Result<(), BlockExecutionError>
+    where
+        DB: Database + DatabaseCommit,
+        DB::Error: core::fmt::Display,
+    {
+        let _ = (chain_spec, evm);
+        Ok(())
+    }
 }
 
 /// This represents the set of methods used to configure the EVM's environment before block
@@ -135,7 +161,13 @@ pub trait ConfigureEvmEnv: Send + Sync + Unpin + Clone + 'static {
     );
 
     /// Fill [`BlockEnv`] field according to the chain spec and given header
-    fn fill_block_env(&self, block_env: &mut BlockEnv, header: &Header, after_merge: bool) {
+    fn fill_block_env(
+        &self,
+        block_env: &mut BlockEnv,
+        chain_spec: &ChainSpec,
+        header: &Header,
+        after_merge: bool,
+    ) {
         block_env.number = U256::from(header.number);
         block_env.coinbase = header.beneficiary;
         block_env.timestamp = U256::from(header.timestamp);
@@ -151,7 +183,11 @@ pub trait ConfigureEvmEnv: Send + Sync + Unpin + Clone + 'static {
 
         // EIP-4844 excess blob gas of this block, introduced in Cancun
         if let Some(excess_blob_gas) = header.excess_blob_gas {
-            block_env.set_blob_excess_gas_and_price(excess_blob_gas);
+            let blob_params = chain_spec.blob_params_at_timestamp(header.timestamp);
+            block_env.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice {
+                excess_blob_gas,
+                blob_gasprice: blob_params.calc_blob_fee(excess_blob_gas),
+            });
         }
     }
 
@@ -167,6 +203,6 @@ pub trait ConfigureEvmEnv: Send + Sync + Unpin + Clone + 'static {
     ) {
         self.fill_cfg_env(cfg, chain_spec, header, total_difficulty);
         let after_merge = cfg.handler_cfg.spec_id >= SpecId::MERGE;
-        self.fill_block_env(block_env, header, after_merge);
+        self.fill_block_env(block_env, chain_spec, header, after_merge);
     }
 }
@@ -19,8 +19,8 @@ use reth_execution_errors::{BlockExecutionError, BlockValidationError};
 use reth_primitives::{Buf, Request};
 use revm::{interpreter::Host, Database, DatabaseCommit, Evm};
 use revm_primitives::{
-    Address, BlockEnv, Bytes, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, FixedBytes,
-    ResultAndState, B256,
+    hash_map::Entry, Account, Address, BlockEnv, Bytes, CfgEnvWithHandlerCfg, EnvWithHandlerCfg,
+    ExecutionResult, FixedBytes, HashMap, ResultAndState, B256, U256,
 };
 
 /// Apply the [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788) pre block contract call.
@@ -405,3 +405,71 @@ where
 
     Ok(consolidation_requests)
 }
+
+/// Applies the chain specific post-execution changes of
+/// [`ConfigureEvm::apply_post_execution_changes`].
+///
+/// This constructs a new [Evm] with the given DB, and environment
+/// ([`CfgEnvWithHandlerCfg`] and [`BlockEnv`]) to apply the changes.
+pub fn post_block_execution_changes<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    chain_spec: &ChainSpec,
+    db: &mut DB,
+    initialized_cfg: &CfgEnvWithHandlerCfg,
+    initialized_block_env: &BlockEnv,
+) -> Result<(), BlockExecutionError>
+where
+    DB: Database + DatabaseCommit,
+    DB::Error: Display,
+    EvmConfig: ConfigureEvm,
+{
+    let mut evm_post_block = Evm::builder()
+        .with_db(db)
+        .with_env_with_handler_cfg(EnvWithHandlerCfg::new_with_cfg_env(
+            initialized_cfg.clone(),
+            initialized_block_env.clone(),
+            Default::default(),
+        ))
+        .build();
+
+    evm_config.apply_post_execution_changes(chain_spec, &mut evm_post_block)
+}
+
+/// Increases the balances of the given accounts and commits the changes to the database of the
+/// EVM, e.g. to pay block rewards in [`ConfigureEvm::apply_post_execution_changes`].
+///
+/// Accounts that don't exist are created.
+pub fn apply_balance_increments<EXT, DB>(
+    evm: &mut Evm<'_, EXT, DB>,
+    increments: impl IntoIterator<Item = (Address, U256)>,
+) -> Result<(), BlockExecutionError>
+where
+    DB: Database + DatabaseCommit,
+    DB::Error: Display,
+{
+    let mut state = HashMap::new();
+    for (address, increment) in increments {
+        if increment.is_zero() {
+            continue
+        }
+        let account = match state.entry(address) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let account = match evm
+                    .db_mut()
+                    .basic(address)
+                    .map_err(|_| BlockValidationError::IncrementBalanceFailed)?
+                {
+                    Some(info) => Account::from(info),
+                    None => Account::new_not_existing(),
+                };
+                entry.insert(account)
+            }
+        };
+        account.info.balance = account.info.balance.saturating_add(increment);
+        account.mark_touch();
+    }
+    evm.db_mut().commit(state);
+
+    Ok(())
+}
@@ -0,0 +1,243 @@
+use crate::{ExExBlock, ExExContext, ExExEvent, ExExNotification};
+use futures::{future::BoxFuture, stream, FutureExt, StreamExt};
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_node_api::FullNodeComponents;
+use reth_tracing::tracing::{debug, error};
+use std::{future::Future, sync::Arc, time::Duration};
+use tokio::sync::mpsc::{Receiver, UnboundedSender};
+
+/// The default number of blocks whose jobs run concurrently.
+pub const DEFAULT_MAX_CONCURRENT_BLOCK_JOBS: usize = 4;
+
+/// The default number of times a failed job is retried.
+pub const DEFAULT_MAX_BLOCK_JOB_RETRIES: usize = 3;
+
+/// The default delay before the first retry of a failed job, doubled on every further retry.
+pub const DEFAULT_BLOCK_JOB_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+type BlockJobFn = Arc<dyn Fn(ExExBlock) -> BoxFuture<'static, eyre::Result<()>> + Send + Sync>;
+
+/// Runs a job after every block that is committed to the canonical chain.
+///
+/// This is a building block for `ExEx`es that do the same work for every block, e.g. rebroadcast
+/// transactions or check settlements, without handling the notifications themselves:
+/// - The jobs of the blocks of a notification run concurrently, up to a limit, and the
+///   `FinishedHeight` of the `ExEx` is advanced once all of them are done.
+/// - A failed job is retried with an exponential backoff, and is logged and skipped once it runs
+///   out of retries.
+/// - If blocks are reverted by a reorg, the revert job runs for each of them, from the highest
+///   block to the lowest, before the jobs of the new blocks.
+pub struct BlockJobs {
+    on_commit: BlockJobFn,
+    on_revert: Option<BlockJobFn>,
+    max_concurrent_jobs: usize,
+    max_retries: usize,
+    retry_backoff: Duration,
+    metrics: BlockJobsMetrics,
+}
+
+impl BlockJobs {
+    /// Creates a new instance that runs the given job for every committed block.
+    pub fn new<F, Fut>(on_commit: F) -> Self
+    where
+        F: Fn(ExExBlock) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = eyre::Result<()>> + Send + 'static,
+    {
+        Self {
+            on_commit: Arc::new(move |block| on_commit(block).boxed()),
+            on_revert: None,
+            max_concurrent_jobs: DEFAULT_MAX_CONCURRENT_BLOCK_JOBS,
+            max_retries: DEFAULT_MAX_BLOCK_JOB_RETRIES,
+            retry_backoff: DEFAULT_BLOCK_JOB_RETRY_BACKOFF,
+            metrics: BlockJobsMetrics::default(),
+        }
+    }
+
+    /// Sets the job that compensates the work done for a block that was reverted.
+    pub fn with_revert<F, Fut>(mut self, on_revert: F) -> Self
+    where
+        F: Fn(ExExBlock) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = eyre::Result<()>> + Send + 'static,
+    {
+        self.on_revert = Some(Arc::new(move |block| on_revert(block).boxed()));
+        self
+    }
+
+    /// Sets the maximum number of blocks whose jobs run concurrently.
+    pub fn with_max_concurrent_jobs(mut self, max_concurrent_jobs: usize) -> Self {
+        self.max_concurrent_jobs = max_concurrent_jobs.max(1);
+        self
+    }
+
+    /// Sets the number of times a failed job is retried.
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry of a failed job.
+    pub const fn with_retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Runs the jobs for the notifications of the `ExEx` until the node shuts down.
+    pub async fn run<Node: FullNodeComponents>(
+        self,
+        mut ctx: ExExContext<Node>,
+    ) -> eyre::Result<()> {
+        self.run_with(&mut ctx.notifications, &ctx.events).await
+    }
+
+    /// Runs the jobs for the given notifications until the channel is closed, reporting the
+    /// finished height to `events`.
+    pub async fn run_with(
+        &self,
+        notifications: &mut Receiver<ExExNotification>,
+        events: &UnboundedSender<ExExEvent>,
+    ) -> eyre::Result<()> {
+        while let Some(notification) = notifications.recv().await {
+            if let (Some(on_revert), Some(reverted)) =
+                (&self.on_revert, notification.reverted_chain())
+            {
+                // revert the blocks in reverse order, each after the jobs of the later blocks
+                for block in reverted.block_views().collect::<Vec<_>>().into_iter().rev() {
+                    self.run_job(on_revert, "revert", block).await;
+                }
+            }
+
+            if let Some(committed) = notification.committed_chain() {
+                stream::iter(committed.block_views())
+                    .map(|block| self.run_job(&self.on_commit, "commit", block))
+                    .buffer_unordered(self.max_concurrent_jobs)
+                    .collect::<()>()
+                    .await;
+                events.send(ExExEvent::FinishedHeight(committed.tip().number))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the job for the block, retrying it until it succeeds or runs out of retries.
+    async fn run_job(&self, job: &BlockJobFn, kind: &'static str, block: ExExBlock) {
+        let number = block.number();
+        let mut backoff = self.retry_backoff;
+        for attempt in 0..=self.max_retries {
+            match job(block.clone()).await {
+                Ok(()) => {
+                    self.metrics.jobs.increment(1);
+                    return
+                }
+                Err(err) if attempt < self.max_retries => {
+                    debug!(target: "exex::block_jobs", kind, number, attempt, %err, "Block job failed, retrying");
+                    self.metrics.retries.increment(1);
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                }
+                Err(err) => {
+                    error!(target: "exex::block_jobs", kind, number, %err, "Block job failed, skipping");
+                    self.metrics.failed_jobs.increment(1);
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for BlockJobs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockJobs")
+            .field("reverts", &self.on_revert.is_some())
+            .field("max_concurrent_jobs", &self.max_concurrent_jobs)
+            .field("max_retries", &self.max_retries)
+            .field("retry_backoff", &self.retry_backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Metrics for [`BlockJobs`].
+#[derive(Metrics)]
+#[metrics(scope = "exex.block_jobs")]
+struct BlockJobsMetrics {
+    /// The number of jobs that succeeded.
+    jobs: Counter,
+    /// The number of times a failed job was retried.
+    retries: Counter,
+    /// The number of jobs that failed after all retries.
+    failed_jobs: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{BlockNumber, SealedBlockWithSenders};
+    use reth_provider::{Chain, ExecutionOutcome};
+    use reth_testing_utils::generators::{self, random_block};
+    use std::sync::Mutex;
+    use tokio::sync::mpsc;
+
+    fn chain(blocks: std::ops::RangeInclusive<BlockNumber>) -> crate::ExExChain {
+        let mut rng = generators::rng();
+        let blocks = blocks.map(|number| SealedBlockWithSenders {
+            block: random_block(&mut rng, number, None, Some(0), None),
+            senders: Vec::new(),
+        });
+        Arc::new(Chain::new(blocks, ExecutionOutcome::default(), None)).into()
+    }
+
+    #[tokio::test]
+    async fn runs_jobs_per_block() {
+        let committed = Arc::new(Mutex::new(Vec::new()));
+        let reverted = Arc::new(Mutex::new(Vec::new()));
+        let attempts = Arc::new(Mutex::new(0));
+
+        let jobs = BlockJobs::new({
+            let (committed, attempts) = (committed.clone(), attempts.clone());
+            move |block| {
+                let (committed, attempts) = (committed.clone(), attempts.clone());
+                async move {
+                    // the first attempt for block 2 fails
+                    if block.number() == 2 {
+                        let mut attempts = attempts.lock().unwrap();
+                        *attempts += 1;
+                        if *attempts == 1 {
+                            eyre::bail!("transient failure")
+                        }
+                    }
+                    committed.lock().unwrap().push(block.number());
+                    Ok(())
+                }
+            }
+        })
+        .with_revert({
+            let reverted = reverted.clone();
+            move |block| {
+                reverted.lock().unwrap().push(block.number());
+                async { Ok(()) }
+            }
+        })
+        .with_retry_backoff(Duration::ZERO);
+
+        let (notifications_tx, mut notifications) = mpsc::channel(2);
+        let (events, mut events_rx) = mpsc::unbounded_channel();
+        notifications_tx
+            .send(ExExNotification::ChainCommitted { new: chain(1..=3) })
+            .await
+            .unwrap();
+        notifications_tx
+            .send(ExExNotification::ChainReorged { old: chain(2..=3), new: chain(2..=2) })
+            .await
+            .unwrap();
+        drop(notifications_tx);
+
+        jobs.run_with(&mut notifications, &events).await.unwrap();
+
+        let mut committed = committed.lock().unwrap().clone();
+        committed.sort_unstable();
+        assert_eq!(committed, vec![1, 2, 2, 3]);
+        assert_eq!(*reverted.lock().unwrap(), vec![3, 2]);
+        assert_eq!(*attempts.lock().unwrap(), 3);
+        assert_eq!(events_rx.recv().await, Some(ExExEvent::FinishedHeight(3)));
+        assert_eq!(events_rx.recv().await, Some(ExExEvent::FinishedHeight(2)));
+    }
+}
@@ -23,6 +23,9 @@
 //! event. To clarify: if the `ExEx` emits `ExExEvent::FinishedHeight(0)` it will receive
 //! notifications for any `block_number > 0`.
 //!
+//! `ExEx`es that run the same job for every canonical block can use [`BlockJobs`], which handles
+//! the notifications, retries and reorgs, and emits the `FinishedHeight` events.
+//!
 //! [`Future`]: std::future::Future
 //! [`ExExContext`]: crate::ExExContext
 //! [`CanonStateNotification`]: reth_provider::CanonStateNotification
@@ -37,6 +40,9 @@
 mod backfill;
 pub use backfill::*;
 
+mod block_jobs;
+pub use block_jobs::*;
+
 mod context;
 pub use context::*;
 
@@ -169,9 +169,7 @@ pub struct TestExExHandle {
 impl TestExExHandle {
     /// Send a notification to the Execution Extension that the chain has been committed
     pub async fn send_notification_chain_committed(&self, chain: Chain) -> eyre::Result<()> {
-        self.notifications_tx
-            .send(ExExNotification::ChainCommitted { new: Arc::new(chain) })
-            .await?;
+        self.notifications_tx.send(ExExNotification::ChainCommitted { new: chain.into() }).await?;
         Ok(())
     }
 
@@ -182,16 +180,14 @@ impl TestExExHandle {
         new: Chain,
     ) -> eyre::Result<()> {
         self.notifications_tx
-            .send(ExExNotification::ChainReorged { old: Arc::new(old), new: Arc::new(new) })
+            .send(ExExNotification::ChainReorged { old: old.into(), new: new.into() })
             .await?;
         Ok(())
     }
 
     /// Send a notification to the Execution Extension that the chain has been reverted
     pub async fn send_notification_chain_reverted(&self, chain: Chain) -> eyre::Result<()> {
-        self.notifications_tx
-            .send(ExExNotification::ChainReverted { old: Arc::new(chain) })
-            .await?;
+        self.notifications_tx.send(ExExNotification::ChainReverted { old: chain.into() }).await?;
         Ok(())
     }
 
@@ -0,0 +1,268 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+    sync::{Arc, OnceLock},
+};
+
+use reth_primitives::{Account, Address, BlockNumber, Receipt, SealedBlockWithSenders, U256};
+use reth_provider::Chain;
+use revm::db::{states::reverts::AccountInfoRevert, BundleState};
+
+/// A chain of blocks that is shared between all `ExEx`es.
+///
+/// Cloning is cheap, and the per-block views returned by [`ExExChain::block_views`] are
+/// materialized lazily on first access and then shared by all clones, so that several `ExEx`es
+/// subscribing to the same notification don't need their own copies of the [`Chain`].
+#[derive(Debug, Clone)]
+pub struct ExExChain {
+    inner: Arc<ExExChainInner>,
+}
+
+#[derive(Debug)]
+struct ExExChainInner {
+    chain: Arc<Chain>,
+    /// The lazily computed state diffs of the blocks, indexed by their position in the chain.
+    state_diffs: Box<[OnceLock<BlockStateDiff>]>,
+}
+
+impl ExExChain {
+    /// Creates a new shared chain.
+    pub fn new(chain: Arc<Chain>) -> Self {
+        let state_diffs = (0..chain.len()).map(|_| OnceLock::new()).collect();
+        Self { inner: Arc::new(ExExChainInner { chain, state_diffs }) }
+    }
+
+    /// Returns the underlying [`Chain`].
+    pub fn chain(&self) -> &Arc<Chain> {
+        &self.inner.chain
+    }
+
+    /// Returns the views of all blocks in the chain, in ascending order.
+    pub fn block_views(&self) -> impl Iterator<Item = ExExBlock> + '_ {
+        self.inner
+            .chain
+            .blocks()
+            .keys()
+            .map(|number| ExExBlock { chain: self.clone(), number: *number })
+    }
+
+    /// Returns the view of the block with the given number, if it is part of the chain.
+    pub fn block_view(&self, number: BlockNumber) -> Option<ExExBlock> {
+        self.inner
+            .chain
+            .blocks()
+            .contains_key(&number)
+            .then(|| ExExBlock { chain: self.clone(), number })
+    }
+}
+
+impl Deref for ExExChain {
+    type Target = Chain;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner.chain
+    }
+}
+
+impl PartialEq for ExExChain {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.chain == other.inner.chain
+    }
+}
+
+impl Eq for ExExChain {}
+
+impl From<Arc<Chain>> for ExExChain {
+    fn from(chain: Arc<Chain>) -> Self {
+        Self::new(chain)
+    }
+}
+
+impl From<Chain> for ExExChain {
+    fn from(chain: Chain) -> Self {
+        Self::new(Arc::new(chain))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ExExChain {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.inner.chain.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ExExChain {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Chain::deserialize(deserializer).map(Into::into)
+    }
+}
+
+/// A view of a single block of an [`ExExChain`].
+#[derive(Debug, Clone)]
+pub struct ExExBlock {
+    chain: ExExChain,
+    number: BlockNumber,
+}
+
+impl ExExBlock {
+    /// Returns the number of the block.
+    pub const fn number(&self) -> BlockNumber {
+        self.number
+    }
+
+    /// Returns the block together with its transaction senders.
+    pub fn block(&self) -> &SealedBlockWithSenders {
+        &self.chain.blocks()[&self.number]
+    }
+
+    /// Returns the senders of the transactions of the block.
+    pub fn senders(&self) -> &[Address] {
+        &self.block().senders
+    }
+
+    /// Returns the receipts of the transactions of the block.
+    pub fn receipts(&self) -> &[Option<Receipt>] {
+        self.chain.execution_outcome().receipts_by_block(self.number)
+    }
+
+    /// Returns the state changes of the block.
+    ///
+    /// The diff is computed from the reverts of the chain on first access. Returns `None` if the
+    /// chain doesn't contain the reverts of its blocks, e.g. because they were pruned.
+    pub fn state_diff(&self) -> Option<&BlockStateDiff> {
+        let bundle = self.chain.execution_outcome().state();
+        if bundle.reverts.len() != self.chain.len() {
+            return None
+        }
+
+        let index = (self.number - self.chain.first().number) as usize;
+        Some(self.chain.inner.state_diffs[index].get_or_init(|| BlockStateDiff::new(bundle, index)))
+    }
+}
+
+/// The state changes of a single block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockStateDiff {
+    /// The accounts changed by the block with their state after the block, `None` if the account
+    /// doesn't exist anymore.
+    pub accounts: HashMap<Address, Option<Account>>,
+    /// The storage changes of the block, keyed by account.
+    pub storage: HashMap<Address, StorageDiff>,
+}
+
+/// The storage changes of a single account in a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageDiff {
+    /// Whether the storage of the account was wiped by the block.
+    pub wiped: bool,
+    /// The changed storage slots with their values after the block.
+    pub slots: HashMap<U256, U256>,
+}
+
+impl BlockStateDiff {
+    /// Computes the state changes of the block at the given index of the bundle reverts.
+    ///
+    /// The reverts only contain the previous values of the changed entries, so the value of an
+    /// entry after the block is the value that the next revert of the entry restores, or the value
+    /// of the bundle if no later block changed it.
+    fn new(bundle: &BundleState, index: usize) -> Self {
+        let mut diff = Self::default();
+        let mut pending_accounts = HashSet::new();
+        let mut pending_slots = HashSet::new();
+        for (address, revert) in &bundle.reverts[index] {
+            if !matches!(revert.account, AccountInfoRevert::DoNothing) {
+                pending_accounts.insert(*address);
+            }
+            if revert.wipe_storage || !revert.storage.is_empty() {
+                diff.storage.entry(*address).or_default().wiped = revert.wipe_storage;
+                pending_slots.extend(revert.storage.keys().map(|slot| (*address, *slot)));
+            }
+        }
+
+        for reverts in &bundle.reverts[index + 1..] {
+            for (address, revert) in reverts {
+                let account = match &revert.account {
+                    AccountInfoRevert::DoNothing => None,
+                    AccountInfoRevert::DeleteIt => Some(None),
+                    AccountInfoRevert::RevertTo(info) => Some(Some(info.clone().into())),
+                };
+                if let Some(account) = account {
+                    if pending_accounts.remove(address) {
+                        diff.accounts.insert(*address, account);
+                    }
+                }
+                for (slot, value) in &revert.storage {
+                    if pending_slots.remove(&(*address, *slot)) {
+                        diff.storage
+                            .entry(*address)
+                            .or_default()
+                            .slots
+                            .insert(*slot, value.to_previous_value());
+                    }
+                }
+            }
+        }
+
+        for address in pending_accounts {
+            let account = bundle.account(&address).and_then(|account| account.info.clone());
+            diff.accounts.insert(address, account.map(Into::into));
+        }
+        for (address, slot) in pending_slots {
+            let value = bundle
+                .account(&address)
+                .and_then(|account| account.storage_slot(slot))
+                .unwrap_or_default();
+            diff.storage.entry(address).or_default().slots.insert(slot, value);
+        }
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlock};
+    use reth_provider::ExecutionOutcome;
+    use revm::primitives::AccountInfo;
+
+    #[test]
+    fn block_state_diff() {
+        let address = Address::with_last_byte(1);
+        let info = |nonce| AccountInfo { nonce, ..Default::default() };
+        let slot = U256::from(1);
+
+        // block 1 creates the account and sets the slot, block 2 only bumps the nonce and block 3
+        // changes the slot
+        let bundle = BundleState::new(
+            [(address, None, Some(info(2)), HashMap::from([(slot, (U256::ZERO, U256::from(3)))]))],
+            [
+                vec![(address, Some(None), vec![(slot, U256::ZERO)])],
+                vec![(address, Some(Some(info(1))), vec![])],
+                vec![(address, None, vec![(slot, U256::from(1))])],
+            ],
+            [],
+        );
+        let blocks = (1..=3).map(|number| {
+            let header = Header { number, ..Default::default() }.seal_slow();
+            SealedBlock { header, ..Default::default() }.seal_with_senders().unwrap()
+        });
+        let chain = ExExChain::from(Chain::new(
+            blocks,
+            ExecutionOutcome::new(bundle, vec![vec![]; 3].into(), 1, vec![]),
+            None,
+        ));
+
+        let diffs = chain
+            .block_views()
+            .map(|block| block.state_diff().unwrap().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(diffs[0].accounts, HashMap::from([(address, Some(info(1).into()))]));
+        assert_eq!(diffs[0].storage[&address].slots, HashMap::from([(slot, U256::from(1))]));
+        assert_eq!(diffs[1].accounts, HashMap::from([(address, Some(info(2).into()))]));
+        assert!(diffs[1].storage.is_empty());
+        assert!(diffs[2].accounts.is_empty());
+        assert_eq!(diffs[2].storage[&address].slots, HashMap::from([(slot, U256::from(3))]));
+    }
+}
@@ -8,8 +8,10 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod chain;
 mod finished_height;
 mod notification;
 
+pub use chain::{BlockStateDiff, ExExBlock, ExExChain, StorageDiff};
 pub use finished_height::FinishedExExHeight;
 pub use notification::ExExNotification;
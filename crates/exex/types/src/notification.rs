@@ -1,34 +1,36 @@
-use std::sync::Arc;
+use reth_provider::CanonStateNotification;
 
-use reth_provider::{CanonStateNotification, Chain};
+use crate::ExExChain;
 
 /// Notifications sent to an `ExEx`.
+///
+/// The chains are shared between all `ExEx`es, see [`ExExChain`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExExNotification {
     /// Chain got committed without a reorg, and only the new chain is returned.
     ChainCommitted {
         /// The new chain after commit.
-        new: Arc<Chain>,
+        new: ExExChain,
     },
     /// Chain got reorged, and both the old and the new chains are returned.
     ChainReorged {
         /// The old chain before reorg.
-        old: Arc<Chain>,
+        old: ExExChain,
         /// The new chain after reorg.
-        new: Arc<Chain>,
+        new: ExExChain,
     },
     /// Chain got reverted, and only the old chain is returned.
     ChainReverted {
         /// The old chain before reversion.
-        old: Arc<Chain>,
+        old: ExExChain,
     },
 }
 
 impl ExExNotification {
     /// Returns the committed chain from the [`Self::ChainCommitted`] and [`Self::ChainReorged`]
     /// variants, if any.
-    pub fn committed_chain(&self) -> Option<Arc<Chain>> {
+    pub fn committed_chain(&self) -> Option<ExExChain> {
         match self {
             Self::ChainCommitted { new } | Self::ChainReorged { old: _, new } => Some(new.clone()),
             Self::ChainReverted { .. } => None,
@@ -37,7 +39,7 @@ impl ExExNotification {
 
     /// Returns the reverted chain from the [`Self::ChainReorged`] and [`Self::ChainReverted`]
     /// variants, if any.
-    pub fn reverted_chain(&self) -> Option<Arc<Chain>> {
+    pub fn reverted_chain(&self) -> Option<ExExChain> {
         match self {
             Self::ChainReorged { old, new: _ } | Self::ChainReverted { old } => Some(old.clone()),
             Self::ChainCommitted { .. } => None,
@@ -48,8 +50,10 @@ impl ExExNotification {
 impl From<CanonStateNotification> for ExExNotification {
     fn from(notification: CanonStateNotification) -> Self {
         match notification {
-            CanonStateNotification::Commit { new } => Self::ChainCommitted { new },
-            CanonStateNotification::Reorg { old, new } => Self::ChainReorged { old, new },
+            CanonStateNotification::Commit { new } => Self::ChainCommitted { new: new.into() },
+            CanonStateNotification::Reorg { old, new } => {
+                Self::ChainReorged { old: old.into(), new: new.into() }
+            }
         }
     }
 }
@@ -277,3 +277,29 @@ pub fn write_json_file<T: Serialize>(path: &Path, obj: &T) -> Result<()> {
         .map_err(|source| FsPathError::WriteJson { source, path: path.into() })?;
     writer.flush().map_err(|e| FsPathError::write(e, path))
 }
+
+/// The free space of the disk that a path is stored on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskSpace {
+    /// The mount point of the disk.
+    pub mount_point: PathBuf,
+    /// The available space of the disk in bytes.
+    pub available: u64,
+}
+
+/// Returns the free space of the disk that the path is, or would be, stored on.
+///
+/// The path doesn't need to exist, the disk of its closest existing ancestor is used. Returns
+/// `None` if the disk can't be determined.
+pub fn disk_space(path: impl AsRef<Path>) -> Option<DiskSpace> {
+    let path = path.as_ref().ancestors().find_map(|path| path.canonicalize().ok())?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| DiskSpace {
+            mount_point: disk.mount_point().to_path_buf(),
+            available: disk.available_space(),
+        })
+}
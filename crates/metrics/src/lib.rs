@@ -20,5 +20,7 @@ pub use reth_metrics_derive::Metrics;
 #[cfg(feature = "common")]
 pub mod common;
 
+pub mod memory;
+
 /// Re-export core metrics crate.
 pub use metrics;
@@ -0,0 +1,28 @@
+//! Metrics of the memory budget of the node.
+
+use metrics::Gauge;
+use reth_metrics_derive::Metrics;
+
+/// Metrics of a consumer of the memory budget, labeled by the consumer.
+#[derive(Clone, Metrics)]
+#[metrics(scope = "memory_budget")]
+pub struct MemoryConsumerMetrics {
+    /// The byte size limit of the consumer, zero if the consumer is not limited
+    pub limit_bytes: Gauge,
+    /// The estimated number of bytes the consumer currently uses
+    pub usage_bytes: Gauge,
+}
+
+impl MemoryConsumerMetrics {
+    /// Name of the consumer of the transactions in the pending, basefee and queued subpools.
+    pub const TRANSACTION_POOL: &'static str = "transaction_pool";
+    /// Name of the consumer of the block bodies buffered by the bodies downloader.
+    pub const BODIES_DOWNLOADER: &'static str = "bodies_downloader";
+    /// Name of the consumer of the executed blocks the engine keeps in memory.
+    pub const MEMORY_OVERLAY: &'static str = "memory_overlay";
+
+    /// Returns the metrics of the consumer with the given name.
+    pub fn consumer(consumer: &'static str) -> Self {
+        Self::new_with_labels(&[("consumer", consumer)])
+    }
+}
@@ -61,6 +61,11 @@ pub struct Discv4Config {
     pub enforce_expiration_timestamps: bool,
     /// Additional pairs to include in The [`Enr`](enr::Enr) if EIP-868 extension is enabled <https://eips.ethereum.org/EIPS/eip-868>
     pub additional_eip868_rlp_pairs: HashMap<Vec<u8>, Bytes>,
+    /// Keys of the pairs to retrieve from the [`Enr`](enr::Enr) of peers, if EIP-868 extension is
+    /// enabled.
+    ///
+    /// The pairs are reported with [`DiscoveryUpdate::EnrEntries`](crate::DiscoveryUpdate).
+    pub tracked_eip868_keys: HashSet<Vec<u8>>,
     /// If configured, try to resolve public ip
     pub external_ip_resolver: Option<NatResolver>,
     /// If configured and a `external_ip_resolver` is configured, try to resolve the external ip
@@ -135,6 +140,7 @@ impl Default for Discv4Config {
             enable_eip868: true,
             enforce_expiration_timestamps: true,
             additional_eip868_rlp_pairs: Default::default(),
+            tracked_eip868_keys: Default::default(),
             external_ip_resolver: Some(Default::default()),
             // By default retry public IP using a 5min interval
             resolve_external_ip_interval: Some(Duration::from_secs(60 * 5)),
@@ -259,6 +265,12 @@ impl Discv4ConfigBuilder {
         self
     }
 
+    /// Add a key of a pair to retrieve from the ENR of peers
+    pub fn track_eip868_key(&mut self, key: impl Into<Vec<u8>>) -> &mut Self {
+        self.config.tracked_eip868_keys.insert(key.into());
+        self
+    }
+
     /// A set of lists that can ban IP's or `PeerIds` from the server. See
     /// [`BanList`].
     pub fn ban_list(&mut self, ban_list: BanList) -> &mut Self {
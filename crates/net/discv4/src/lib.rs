@@ -1270,6 +1270,11 @@ impl Discv4Service {
                     (Some(new), None) => self.notify(DiscoveryUpdate::EnrForkId(record, new)),
                     _ => {}
                 }
+
+                let entries = msg.raw_rlp_entries(&self.config.tracked_eip868_keys);
+                if !entries.is_empty() {
+                    self.notify(DiscoveryUpdate::EnrEntries(record, entries))
+                }
             }
         }
     }
@@ -2274,6 +2279,10 @@ pub enum DiscoveryUpdate {
     DiscoveredAtCapacity(NodeRecord),
     /// Received a [`ForkId`] via EIP-868 for the given [`NodeRecord`].
     EnrForkId(NodeRecord, ForkId),
+    /// Received the pairs of the tracked keys via EIP-868 for the given [`NodeRecord`].
+    ///
+    /// See [`Discv4Config::tracked_eip868_keys`].
+    EnrEntries(NodeRecord, Vec<(Vec<u8>, Bytes)>),
     /// Node that was removed from the table
     Removed(PeerId),
     /// A series of updates
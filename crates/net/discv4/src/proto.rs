@@ -414,6 +414,19 @@ impl EnrResponse {
         let mut maybe_fork_id = self.enr.get_raw_rlp(b"eth")?;
         EnrForkIdEntry::decode(&mut maybe_fork_id).ok().map(Into::into)
     }
+
+    /// Returns the rlp encoded values of the given keys that are set
+    pub fn raw_rlp_entries<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a Vec<u8>>,
+    ) -> Vec<(Vec<u8>, Bytes)> {
+        keys.into_iter()
+            .filter_map(|key| {
+                let rlp = self.enr.get_raw_rlp(key)?;
+                Some((key.clone(), Bytes::copy_from_slice(rlp)))
+            })
+            .collect()
+    }
 }
 
 /// Represents a Ping packet.
@@ -827,6 +840,28 @@ mod tests {
         assert_eq!(fork_id, fork_id_decoded);
     }
 
+    #[test]
+    fn enr_response_raw_rlp_entries() {
+        use enr::secp256k1::SecretKey;
+
+        let mut rng = rand::rngs::OsRng;
+        let key = SecretKey::new(&mut rng);
+
+        let network_id = alloy_rlp::encode(10u64);
+        let enr = {
+            let mut builder = Enr::builder();
+            builder.add_value_rlp("l2", network_id.clone().into());
+            builder.build(&key).unwrap()
+        };
+        let enr_response = EnrResponse { request_hash: rng.gen(), enr };
+
+        let keys = [b"l2".to_vec(), b"shard".to_vec()];
+        assert_eq!(
+            enr_response.raw_rlp_entries(&keys),
+            vec![(b"l2".to_vec(), Bytes::from(network_id))]
+        );
+    }
+
     // test vector from the enr library rlp encoding tests
     // <https://github.com/sigp/enr/blob/e59dcb45ea07e423a7091d2a6ede4ad6d8ef2840/src/lib.rs#L1019>
 
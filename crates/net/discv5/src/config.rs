@@ -66,7 +66,9 @@ pub struct ConfigBuilder {
     tcp_socket: SocketAddr,
     /// List of `(key, rlp-encoded-value)` tuples that should be advertised in local node record
     /// (in addition to tcp port, udp port and fork).
-    other_enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
+    other_enr_kv_pairs: Vec<(Vec<u8>, Bytes)>,
+    /// Keys of kv-pairs to retrieve from the node records of discovered peers.
+    tracked_enr_keys: Vec<Vec<u8>>,
     /// Interval in seconds at which to run a lookup up query to populate kbuckets.
     lookup_interval: Option<u64>,
     /// Interval in seconds at which to run pulse lookup queries at bootstrap to boost kbucket
@@ -88,6 +90,7 @@ impl ConfigBuilder {
             fork,
             tcp_socket,
             other_enr_kv_pairs,
+            tracked_enr_keys,
             lookup_interval,
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
@@ -100,6 +103,7 @@ impl ConfigBuilder {
             fork: fork.map(|(key, fork_id)| (key, fork_id.fork_id)),
             tcp_socket,
             other_enr_kv_pairs,
+            tracked_enr_keys,
             lookup_interval: Some(lookup_interval),
             bootstrap_lookup_interval: Some(bootstrap_lookup_interval),
             bootstrap_lookup_countdown: Some(bootstrap_lookup_countdown),
@@ -171,8 +175,16 @@ impl ConfigBuilder {
 
     /// Adds an additional kv-pair to include in the local [`Enr`](discv5::enr::Enr). Takes the key
     /// to use for the kv-pair and the rlp encoded value.
-    pub fn add_enr_kv_pair(mut self, key: &'static [u8], value: Bytes) -> Self {
-        self.other_enr_kv_pairs.push((key, value));
+    pub fn add_enr_kv_pair(mut self, key: impl Into<Vec<u8>>, value: Bytes) -> Self {
+        self.other_enr_kv_pairs.push((key.into(), value));
+        self
+    }
+
+    /// Adds a key of a kv-pair to retrieve from the [`Enr`](discv5::enr::Enr) of discovered peers.
+    /// The rlp encoded values of the tracked keys are returned in the
+    /// [`DiscoveredPeer`](crate::DiscoveredPeer).
+    pub fn track_enr_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.tracked_enr_keys.push(key.into());
         self
     }
 
@@ -214,6 +226,7 @@ impl ConfigBuilder {
             fork,
             tcp_socket,
             other_enr_kv_pairs,
+            tracked_enr_keys,
             lookup_interval,
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
@@ -243,6 +256,7 @@ impl ConfigBuilder {
             fork,
             tcp_socket,
             other_enr_kv_pairs,
+            tracked_enr_keys,
             lookup_interval,
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
@@ -269,7 +283,9 @@ pub struct Config {
     pub(super) tcp_socket: SocketAddr,
     /// Additional kv-pairs (besides tcp port, udp port and fork) that should be advertised to
     /// peers by including in local node record.
-    pub(super) other_enr_kv_pairs: Vec<(&'static [u8], Bytes)>,
+    pub(super) other_enr_kv_pairs: Vec<(Vec<u8>, Bytes)>,
+    /// Keys of kv-pairs to retrieve from the node records of discovered peers.
+    pub(super) tracked_enr_keys: Vec<Vec<u8>>,
     /// Interval in seconds at which to run a lookup up query with to populate kbuckets.
     pub(super) lookup_interval: u64,
     /// Interval in seconds at which to run pulse lookup queries at bootstrap to boost kbucket
@@ -292,6 +308,7 @@ impl Config {
             fork: None,
             tcp_socket: rlpx_tcp_socket,
             other_enr_kv_pairs: Vec::new(),
+            tracked_enr_keys: Vec::new(),
             lookup_interval: None,
             bootstrap_lookup_interval: None,
             bootstrap_lookup_countdown: None,
@@ -73,6 +73,8 @@ pub struct Discv5 {
     fork_key: Option<&'static [u8]>,
     /// Filter applied to a discovered peers before passing it up to app.
     discovered_peer_filter: MustNotIncludeKeys,
+    /// Keys of kv-pairs to retrieve from the [`Enr`](discv5::Enr)s of discovered peers.
+    tracked_enr_keys: Arc<[Vec<u8>]>,
     /// Metrics for underlying [`discv5::Discv5`] node and filtered discovered peers.
     metrics: Discv5Metrics,
 }
@@ -180,6 +182,7 @@ impl Discv5 {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            tracked_enr_keys,
             ..
         } = discv5_config;
 
@@ -215,7 +218,14 @@ impl Discv5 {
         );
 
         Ok((
-            Self { discv5, rlpx_ip_mode, fork_key, discovered_peer_filter, metrics },
+            Self {
+                discv5,
+                rlpx_ip_mode,
+                fork_key,
+                discovered_peer_filter,
+                tracked_enr_keys: tracked_enr_keys.into(),
+                metrics,
+            },
             discv5_updates,
             bc_enr,
         ))
@@ -322,13 +332,15 @@ impl Discv5 {
             .then(|| self.get_fork_id(enr).ok())
             .flatten();
 
+        let enr_entries = self.get_enr_entries(enr);
+
         trace!(target: "net::discv5",
             ?fork_id,
             ?enr,
             "discovered peer"
         );
 
-        Some(DiscoveredPeer { node_record, fork_id })
+        Some(DiscoveredPeer { node_record, fork_id, enr_entries })
     }
 
     /// Tries to convert an [`Enr`](discv5::Enr) into the backwards compatible type [`NodeRecord`],
@@ -375,6 +387,21 @@ impl Discv5 {
         Ok(fork_id)
     }
 
+    /// Returns the rlp encoded values of the tracked keys that are set in the given
+    /// [`Enr`](discv5::Enr).
+    pub fn get_enr_entries<K: discv5::enr::EnrKey>(
+        &self,
+        enr: &discv5::enr::Enr<K>,
+    ) -> Vec<(Vec<u8>, Bytes)> {
+        self.tracked_enr_keys
+            .iter()
+            .filter_map(|key| {
+                let rlp = enr.get_raw_rlp(key)?;
+                Some((key.clone(), Bytes::copy_from_slice(rlp)))
+            })
+            .collect()
+    }
+
     ////////////////////////////////////////////////////////////////////////////////////////////////
     // Interface with sigp/discv5
     ////////////////////////////////////////////////////////////////////////////////////////////////
@@ -415,6 +442,8 @@ pub struct DiscoveredPeer {
     pub node_record: NodeRecord,
     /// [`ForkId`] extracted from ENR w.r.t. configured
     pub fork_id: Option<ForkId>,
+    /// Rlp encoded values of the tracked keys that are set in the ENR.
+    pub enr_entries: Vec<(Vec<u8>, Bytes)>,
 }
 
 /// Builds the local ENR with the supplied key.
@@ -671,6 +700,7 @@ mod test {
             rlpx_ip_mode: IpMode::Ip4,
             fork_key: None,
             discovered_peer_filter: MustNotIncludeKeys::default(),
+            tracked_enr_keys: Arc::new([]),
             metrics: Discv5Metrics::default(),
         }
     }
@@ -772,6 +802,26 @@ mod test {
         )
     }
 
+    #[test]
+    fn discovered_peer_tracked_enr_entries() {
+        const REMOTE_RLPX_PORT: u16 = 30303;
+        let remote_socket = "104.28.44.25:9000".parse().unwrap();
+        let remote_key = CombinedKey::generate_secp256k1();
+        let network_id = alloy_rlp::encode(10u64);
+        let remote_enr = Enr::builder()
+            .tcp4(REMOTE_RLPX_PORT)
+            .add_value_rlp("l2", network_id.clone().into())
+            .build(&remote_key)
+            .unwrap();
+
+        let mut discv5 = discv5_noop();
+        discv5.tracked_enr_keys = vec![b"l2".to_vec(), b"shard".to_vec()].into();
+
+        let peer = discv5.on_discovered_peer(&remote_enr, remote_socket).unwrap();
+
+        assert_eq!(peer.enr_entries, vec![(b"l2".to_vec(), Bytes::from(network_id))]);
+    }
+
     // Copied from sigp/discv5 with slight modification (U256 type)
     // <https://github.com/sigp/discv5/blob/master/src/kbucket/key.rs#L89-L101>
     #[allow(unreachable_pub)]
@@ -892,4 +942,16 @@ mod test {
         assert_eq!(fork_id, decoded_fork_id);
         assert_eq!(TCP_PORT, enr.tcp4().unwrap()); // listen config is defaulting to ip mode ipv4
     }
+
+    #[test]
+    fn build_enr_with_custom_kv_pairs() {
+        let config = Config::builder((Ipv4Addr::UNSPECIFIED, 30303).into())
+            .add_enr_kv_pair(b"l2".to_vec(), alloy_rlp::encode(10u64).into())
+            .build();
+
+        let sk = SecretKey::new(&mut thread_rng());
+        let (enr, _, _, _) = build_local_enr(&sk, &config);
+
+        assert_eq!(enr.get_decodable::<u64>("l2").unwrap().unwrap(), 10);
+    }
 }
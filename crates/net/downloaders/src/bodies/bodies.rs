@@ -1,9 +1,10 @@
-use super::queue::BodiesRequestQueue;
+use super::{queue::BodiesRequestQueue, spill::SpilledResponses};
 use crate::{bodies::task::TaskDownloader, metrics::BodyDownloaderMetrics};
 use futures::Stream;
 use futures_util::StreamExt;
 use reth_config::BodiesConfig;
 use reth_consensus::Consensus;
+use reth_metrics::memory::MemoryConsumerMetrics;
 use reth_network_p2p::{
     bodies::{
         client::BodiesClient,
@@ -13,18 +14,19 @@ use reth_network_p2p::{
     error::{DownloadError, DownloadResult},
 };
 use reth_primitives::{BlockNumber, SealedHeader};
-use reth_storage_api::HeaderProvider;
+use reth_storage_api::{errors::provider::ProviderError, HeaderProvider};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use std::{
     cmp::Ordering,
     collections::BinaryHeap,
     mem,
     ops::RangeInclusive,
+    path::PathBuf,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
 };
-use tracing::info;
+use tracing::{info, warn};
 
 /// Downloads bodies in batches.
 ///
@@ -56,10 +58,14 @@ pub struct BodiesDownloader<B: BodiesClient, Provider> {
     in_progress_queue: BodiesRequestQueue<B>,
     /// Buffered responses
     buffered_responses: BinaryHeap<OrderedBodiesResponse>,
+    /// Buffered responses that were spilled to disk because the in-memory buffer is full.
+    spilled_responses: SpilledResponses,
     /// Queued body responses that can be returned for insertion into the database.
     queued_bodies: Vec<BlockResponse>,
     /// The bodies downloader metrics.
     metrics: BodyDownloaderMetrics,
+    /// Memory usage metrics of the buffered blocks.
+    memory_metrics: MemoryConsumerMetrics,
 }
 
 impl<B, Provider> BodiesDownloader<B, Provider>
@@ -168,6 +174,7 @@ where
         nothing_to_request &&
             self.in_progress_queue.is_empty() &&
             self.buffered_responses.is_empty() &&
+            self.spilled_responses.is_empty() &&
             self.queued_bodies.is_empty()
     }
 
@@ -181,12 +188,16 @@ where
         self.queued_bodies = Vec::new();
         self.buffered_responses = BinaryHeap::new();
         self.buffered_blocks_size_bytes = 0;
+        self.spilled_responses.clear();
 
         // reset metrics
         self.metrics.in_flight_requests.set(0.);
         self.metrics.buffered_responses.set(0.);
         self.metrics.buffered_blocks.set(0.);
         self.metrics.buffered_blocks_size_bytes.set(0.);
+        self.memory_metrics.usage_bytes.set(0.);
+        self.metrics.spilled_responses.set(0.);
+        self.metrics.spilled_blocks_size_bytes.set(0.);
         self.metrics.queued_blocks.set(0.);
     }
 
@@ -204,11 +215,49 @@ where
         self.buffered_blocks_size_bytes -= resp.size();
         self.metrics.buffered_blocks.decrement(resp.len() as f64);
         self.metrics.buffered_blocks_size_bytes.set(self.buffered_blocks_size_bytes as f64);
+        self.memory_metrics.usage_bytes.set(self.buffered_blocks_size_bytes as f64);
         Some(resp)
     }
 
+    /// Removes the next response from the disk buffer and reads it.
+    ///
+    /// Returns `None` while the response is being read.
+    fn pop_spilled_response(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> DownloadResult<Option<Vec<BlockResponse>>> {
+        let Poll::Ready(resp) = self.spilled_responses.poll_pop_first(cx) else { return Ok(None) };
+        self.update_spilled_metrics();
+        resp.transpose().map_err(|err| {
+            DownloadError::Provider(ProviderError::FsPathError(format!(
+                "failed to read spilled bodies: {err}"
+            )))
+        })
+    }
+
+    /// Updates the metrics of the disk buffer.
+    fn update_spilled_metrics(&self) {
+        self.metrics.spilled_responses.set(self.spilled_responses.len() as f64);
+        self.metrics.spilled_blocks_size_bytes.set(self.spilled_responses.size_bytes() as f64);
+    }
+
     /// Adds a new response to the internal buffer
+    ///
+    /// If the in-memory buffer is full, the response is spilled to disk instead if the disk buffer
+    /// has capacity.
     fn buffer_bodies_response(&mut self, response: Vec<BlockResponse>) {
+        if !self.has_buffer_capacity() && self.spilled_responses.has_capacity() {
+            match self.spilled_responses.spill(&response) {
+                Ok(_) => {
+                    self.update_spilled_metrics();
+                    return
+                }
+                Err(error) => {
+                    warn!(target: "downloaders::bodies", %error, "Failed to spill response to disk");
+                }
+            }
+        }
+
         // take into account capacity
         let size = response.iter().map(BlockResponse::size).sum::<usize>() +
             response.capacity() * mem::size_of::<BlockResponse>();
@@ -221,32 +270,54 @@ where
 
         self.metrics.buffered_blocks.increment(response_len as f64);
         self.metrics.buffered_blocks_size_bytes.set(self.buffered_blocks_size_bytes as f64);
+        self.memory_metrics.usage_bytes.set(self.buffered_blocks_size_bytes as f64);
         self.metrics.buffered_responses.set(self.buffered_responses.len() as f64);
     }
 
     /// Returns a response if it's first block number matches the next expected.
-    fn try_next_buffered(&mut self) -> Option<Vec<BlockResponse>> {
-        if let Some(next) = self.buffered_responses.peek() {
-            let expected = self.next_expected_block_number();
-            let next_block_range = next.block_range();
-
-            if next_block_range.contains(&expected) {
-                return self.pop_buffered_response().map(|buffered| {
-                    buffered
-                        .resp
-                        .into_iter()
-                        .skip_while(|b| b.block_number() < expected)
-                        .take_while(|b| self.download_range.contains(&b.block_number()))
-                        .collect()
-                })
+    ///
+    /// The response is taken from the in-memory or the disk buffer, whichever has the response
+    /// with the lowest first block number. Returns `None` while a response is read from disk.
+    fn try_next_buffered(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> DownloadResult<Option<Vec<BlockResponse>>> {
+        let buffered = self.buffered_responses.peek().map(OrderedBodiesResponse::block_range);
+        let spilled = self.spilled_responses.first_block_range();
+        let (next_block_range, is_spilled) = match (buffered, spilled) {
+            (Some(buffered), Some(spilled)) if spilled.start() < buffered.start() => {
+                (spilled, true)
             }
+            (Some(buffered), _) => (buffered, false),
+            (None, Some(spilled)) => (spilled, true),
+            (None, None) => return Ok(None),
+        };
+        let expected = self.next_expected_block_number();
+
+        if next_block_range.contains(&expected) {
+            let resp = if is_spilled {
+                self.pop_spilled_response(cx)?
+            } else {
+                self.pop_buffered_response().map(|buffered| buffered.resp)
+            };
+            return Ok(resp.map(|resp| {
+                resp.into_iter()
+                    .skip_while(|b| b.block_number() < expected)
+                    .take_while(|b| self.download_range.contains(&b.block_number()))
+                    .collect()
+            }))
+        }
 
-            // Drop buffered response since we passed that range
-            if *next_block_range.end() < expected {
+        // Drop buffered response since we passed that range
+        if *next_block_range.end() < expected {
+            if is_spilled {
+                self.spilled_responses.discard_first();
+                self.update_spilled_metrics();
+            } else {
                 self.pop_buffered_response();
             }
         }
-        None
+        Ok(None)
     }
 
     /// Returns the next batch of block bodies that can be returned if we have enough buffered
@@ -271,7 +342,7 @@ where
         // can grow large if a certain request is slow, so we limit the followup requests if the
         // queued bodies grew too large
         self.queued_bodies.len() < 4 * self.stream_batch_size &&
-            self.has_buffer_capacity() &&
+            (self.has_buffer_capacity() || self.spilled_responses.has_capacity()) &&
             self.in_progress_queue.len() < self.concurrent_request_limit()
     }
 }
@@ -401,8 +472,16 @@ where
                 };
             }
 
-            while let Some(buf_response) = this.try_next_buffered() {
-                this.queue_bodies(buf_response);
+            loop {
+                match this.try_next_buffered(cx) {
+                    Ok(Some(buf_response)) => this.queue_bodies(buf_response),
+                    Ok(None) => break,
+                    Err(error) => {
+                        tracing::error!(target: "downloaders::bodies", %error, "Failed to read buffered response");
+                        this.clear();
+                        return Poll::Ready(Some(Err(error)))
+                    }
+                }
             }
 
             // shrink the buffer so that it doesn't grow indefinitely
@@ -414,7 +493,7 @@ where
         }
 
         // All requests are handled, stream is finished
-        if this.in_progress_queue.is_empty() {
+        if this.in_progress_queue.is_empty() && !this.spilled_responses.is_reading() {
             if this.queued_bodies.is_empty() {
                 return Poll::Ready(None)
             }
@@ -497,6 +576,11 @@ pub struct BodiesDownloaderBuilder {
     pub stream_batch_size: usize,
     /// Maximum number of bytes of received bodies to buffer internally.
     pub max_buffered_blocks_size_bytes: usize,
+    /// Maximum number of bytes of received bodies to spill to disk when the internal buffer is
+    /// full.
+    pub max_spilled_blocks_size_bytes: usize,
+    /// The directory received bodies are spilled to.
+    pub spill_dir: Option<PathBuf>,
     /// The maximum number of requests to send concurrently.
     pub concurrent_requests_range: RangeInclusive<usize>,
 }
@@ -508,7 +592,8 @@ impl BodiesDownloaderBuilder {
         Self::default()
             .with_stream_batch_size(config.downloader_stream_batch_size)
             .with_request_limit(config.downloader_request_limit)
-            .with_max_buffered_blocks_size_bytes(config.downloader_max_buffered_blocks_size_bytes)
+            .with_max_buffered_blocks_size_bytes(config.max_buffered_blocks_size_bytes())
+            .with_max_spilled_blocks_size_bytes(config.downloader_max_spilled_blocks_size_bytes)
             .with_concurrent_requests_range(
                 config.downloader_min_concurrent_requests..=
                     config.downloader_max_concurrent_requests,
@@ -522,6 +607,8 @@ impl Default for BodiesDownloaderBuilder {
             request_limit: 200,
             stream_batch_size: 1_000,
             max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
+            max_spilled_blocks_size_bytes: 0,
+            spill_dir: None,
             concurrent_requests_range: 5..=100,
         }
     }
@@ -558,6 +645,27 @@ impl BodiesDownloaderBuilder {
         self
     }
 
+    /// Set max spilled block bytes on the downloader.
+    ///
+    /// If non-zero, received bodies are spilled to a temporary directory in the
+    /// [spill directory](Self::with_spill_dir) when the internal buffer is full, instead of
+    /// throttling the downloads.
+    pub const fn with_max_spilled_blocks_size_bytes(
+        mut self,
+        max_spilled_blocks_size_bytes: usize,
+    ) -> Self {
+        self.max_spilled_blocks_size_bytes = max_spilled_blocks_size_bytes;
+        self
+    }
+
+    /// Set the directory received bodies are spilled to.
+    ///
+    /// Nothing is spilled if unset.
+    pub fn with_spill_dir(mut self, spill_dir: impl Into<PathBuf>) -> Self {
+        self.spill_dir = Some(spill_dir.into());
+        self
+    }
+
     /// Consume self and return the concurrent downloader.
     pub fn build<B, Provider>(
         self,
@@ -574,6 +682,8 @@ impl BodiesDownloaderBuilder {
             stream_batch_size,
             concurrent_requests_range,
             max_buffered_blocks_size_bytes,
+            max_spilled_blocks_size_bytes,
+            spill_dir,
         } = self;
         let metrics = BodyDownloaderMetrics::default();
         let in_progress_queue = BodiesRequestQueue::new(metrics.clone());
@@ -587,9 +697,13 @@ impl BodiesDownloaderBuilder {
             concurrent_requests_range,
             in_progress_queue,
             metrics,
+            memory_metrics: MemoryConsumerMetrics::consumer(
+                MemoryConsumerMetrics::BODIES_DOWNLOADER,
+            ),
             download_range: RangeInclusive::new(1, 0),
             latest_queued_block_number: None,
             buffered_responses: Default::default(),
+            spilled_responses: SpilledResponses::new(spill_dir, max_spilled_blocks_size_bytes),
             queued_bodies: Default::default(),
             buffered_blocks_size_bytes: 0,
         }
@@ -812,6 +926,48 @@ mod tests {
         }
     }
 
+    // Check that the downloader spills responses to disk once the size limit is reached.
+    #[tokio::test]
+    async fn spills_responses_after_exceeding_limit() {
+        // Generate some random blocks
+        let db = create_test_rw_db();
+        let (headers, mut bodies) = generate_bodies(0..=199);
+
+        insert_headers(db.db(), &headers);
+
+        let client = Arc::new(
+            TestBodiesClient::default().with_bodies(bodies.clone()).with_should_delay(true),
+        );
+
+        let (_static_dir, static_dir_path) = create_test_static_files_dir();
+        let spill_dir = tempfile::tempdir().unwrap();
+        // Set the max buffered block size to 1 byte, to make sure that every response is spilled
+        let mut downloader = BodiesDownloaderBuilder::default()
+            .with_stream_batch_size(200)
+            .with_request_limit(10)
+            .with_max_buffered_blocks_size_bytes(1)
+            .with_max_spilled_blocks_size_bytes(usize::MAX)
+            .with_spill_dir(spill_dir.path())
+            .build(
+                client.clone(),
+                Arc::new(TestConsensus::default()),
+                ProviderFactory::new(
+                    db,
+                    MAINNET.clone(),
+                    StaticFileProvider::read_write(static_dir_path).unwrap(),
+                ),
+            );
+
+        // Download the entire range
+        downloader.set_download_range(0..=199).expect("failed to set download range");
+        assert_matches!(
+            downloader.next().await,
+            Some(Ok(res)) => assert_eq!(res, zip_blocks(headers.iter(), &mut bodies))
+        );
+        assert!(downloader.spilled_responses.is_empty());
+        assert!(downloader.buffered_responses.is_empty());
+    }
+
     // Check that the downloader can tolerate a few completely empty responses
     #[tokio::test]
     async fn can_tolerate_empty_responses() {
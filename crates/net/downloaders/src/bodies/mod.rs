@@ -10,6 +10,7 @@ pub mod task;
 
 mod queue;
 mod request;
+mod spill;
 
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
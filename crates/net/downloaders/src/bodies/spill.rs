@@ -0,0 +1,367 @@
+use alloy_rlp::{Decodable, Encodable};
+use futures::FutureExt;
+use reth_network_p2p::bodies::response::BlockResponse;
+use reth_primitives::{BlockNumber, SealedBlock, SealedHeader};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    task::{Context, Poll},
+};
+use tempfile::TempDir;
+use tokio::task::JoinHandle;
+use tracing::{trace, warn};
+
+/// Marks an encoded [`BlockResponse::Empty`].
+const EMPTY_RESPONSE: u8 = 0;
+/// Marks an encoded [`BlockResponse::Full`].
+const FULL_RESPONSE: u8 = 1;
+/// Prefix of the directories the responses are spilled to.
+const DIR_PREFIX: &str = "reth-bodies-";
+
+/// Buffered responses of the bodies downloader that were spilled to disk, because the in-memory
+/// buffer is full.
+///
+/// Every response is written to its own file in a temporary directory, which is created in the
+/// spill directory when the first response is spilled and removed on drop. The responses are
+/// ordered by their first block number, like the in-memory buffer.
+///
+/// The files are written and read on the blocking thread pool of tokio, so they never block the
+/// task of the downloader.
+#[derive(Debug)]
+pub(crate) struct SpilledResponses {
+    /// The directory the temporary directory is created in. Nothing is spilled if unset.
+    spill_dir: Option<PathBuf>,
+    /// The directory of the response files.
+    dir: Option<TempDir>,
+    /// Maximum number of bytes of responses to keep on disk.
+    max_size_bytes: usize,
+    /// Current number of bytes of responses on disk.
+    size_bytes: usize,
+    /// The spilled responses by their first block number, and a sequence number to tell apart
+    /// responses for the same blocks.
+    responses: BTreeMap<(BlockNumber, u64), SpilledResponse>,
+    /// The response with the lowest first block number, if it's being read.
+    reading: Option<ReadingResponse>,
+    /// The sequence number of the next spilled response.
+    next_id: u64,
+}
+
+/// A response that was spilled to disk.
+#[derive(Debug)]
+struct SpilledResponse {
+    /// The file the response is written to.
+    path: PathBuf,
+    /// The block number of the last block of the response.
+    last_block_number: BlockNumber,
+    /// The number of blocks of the response.
+    len: usize,
+    /// The size of the file in bytes.
+    size: usize,
+    /// The task that writes the file.
+    write: JoinHandle<io::Result<()>>,
+}
+
+/// A spilled response that is being read from disk.
+#[derive(Debug)]
+struct ReadingResponse {
+    /// The range of the block numbers of the response.
+    block_range: RangeInclusive<BlockNumber>,
+    /// The task that reads and decodes the response.
+    read: JoinHandle<io::Result<Vec<BlockResponse>>>,
+}
+
+impl SpilledResponses {
+    /// Creates a new disk buffer that holds up to `max_size_bytes` of responses in `spill_dir`.
+    ///
+    /// Nothing is spilled if `spill_dir` is unset or `max_size_bytes` is zero. Directories of
+    /// responses that were spilled by a previous run are removed from the spill directory.
+    pub(crate) fn new(mut spill_dir: Option<PathBuf>, max_size_bytes: usize) -> Self {
+        if max_size_bytes == 0 {
+            spill_dir = None;
+        }
+        if let Some(dir) = &spill_dir {
+            if let Err(error) = prepare_spill_dir(dir) {
+                warn!(target: "downloaders::bodies", ?dir, %error, "Failed to prepare spill directory, not spilling bodies to disk");
+                spill_dir = None;
+            }
+        }
+
+        Self {
+            spill_dir,
+            dir: None,
+            max_size_bytes,
+            size_bytes: 0,
+            responses: BTreeMap::new(),
+            reading: None,
+            next_id: 0,
+        }
+    }
+
+    /// Returns true if the size of spilled responses is lower than the configured maximum.
+    pub(crate) const fn has_capacity(&self) -> bool {
+        self.spill_dir.is_some() && self.size_bytes < self.max_size_bytes
+    }
+
+    /// Returns true if no responses are spilled.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.responses.is_empty() && self.reading.is_none()
+    }
+
+    /// Returns true if a response is being read from disk.
+    pub(crate) const fn is_reading(&self) -> bool {
+        self.reading.is_some()
+    }
+
+    /// Returns the number of spilled responses.
+    pub(crate) fn len(&self) -> usize {
+        self.responses.len() + usize::from(self.reading.is_some())
+    }
+
+    /// Returns the number of bytes of spilled responses.
+    pub(crate) const fn size_bytes(&self) -> usize {
+        self.size_bytes
+    }
+
+    /// Returns the range of the block numbers of the spilled response with the lowest first block
+    /// number.
+    pub(crate) fn first_block_range(&self) -> Option<RangeInclusive<BlockNumber>> {
+        if let Some(reading) = &self.reading {
+            return Some(reading.block_range.clone())
+        }
+        self.responses
+            .first_key_value()
+            .map(|((first, _), response)| *first..=response.last_block_number)
+    }
+
+    /// Encodes the response and starts writing it to disk.
+    ///
+    /// Returns the number of bytes that are written. Errors of the write are returned when the
+    /// response is read again.
+    ///
+    /// # Panics
+    /// If the response is empty, or if no spill directory is set.
+    pub(crate) fn spill(&mut self, response: &[BlockResponse]) -> io::Result<usize> {
+        let first = response.first().expect("is not empty").block_number();
+        let last = response.last().expect("is not empty").block_number();
+
+        let mut buf = Vec::new();
+        for block in response {
+            match block {
+                BlockResponse::Empty(header) => {
+                    buf.push(EMPTY_RESPONSE);
+                    header.encode(&mut buf);
+                }
+                BlockResponse::Full(block) => {
+                    buf.push(FULL_RESPONSE);
+                    block.encode(&mut buf);
+                }
+            }
+        }
+        let size = buf.len();
+
+        let dir = match &self.dir {
+            Some(dir) => dir,
+            None => {
+                let spill_dir = self.spill_dir.as_ref().expect("spill directory is set");
+                self.dir.insert(tempfile::Builder::new().prefix(DIR_PREFIX).tempdir_in(spill_dir)?)
+            }
+        };
+        let id = self.next_id;
+        let path = dir.path().join(format!("{first}-{last}-{id}.rlp"));
+        trace!(target: "downloaders::bodies", ?path, size, "Spilling response to disk");
+        let write = tokio::task::spawn_blocking({
+            let path = path.clone();
+            move || fs::write(path, buf)
+        });
+
+        self.next_id += 1;
+        self.size_bytes += size;
+        self.responses.insert(
+            (first, id),
+            SpilledResponse { path, last_block_number: last, len: response.len(), size, write },
+        );
+        Ok(size)
+    }
+
+    /// Removes the spilled response with the lowest first block number and reads it from disk.
+    ///
+    /// Returns [`Poll::Pending`] until the response is read. Until then,
+    /// [`SpilledResponses::first_block_range`] still returns the range of the response.
+    pub(crate) fn poll_pop_first(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<io::Result<Vec<BlockResponse>>>> {
+        if self.reading.is_none() {
+            let Some(block_range) = self.first_block_range() else { return Poll::Ready(None) };
+            let response = self.remove_first().expect("is not empty");
+            let read = tokio::spawn(async move {
+                response.write.await.map_err(io::Error::other)??;
+                tokio::task::spawn_blocking(move || {
+                    let buf = fs::read(&response.path);
+                    let _ = fs::remove_file(&response.path);
+                    decode_response(&buf?, response.len)
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+                })
+                .await
+                .map_err(io::Error::other)?
+            });
+            self.reading = Some(ReadingResponse { block_range, read });
+        }
+
+        let reading = self.reading.as_mut().expect("is reading");
+        let response = futures::ready!(reading.read.poll_unpin(cx));
+        self.reading = None;
+        Poll::Ready(Some(response.map_err(io::Error::other).and_then(|response| response)))
+    }
+
+    /// Removes the spilled response with the lowest first block number without reading it.
+    pub(crate) fn discard_first(&mut self) {
+        if self.reading.take().is_some() {
+            return
+        }
+        if let Some(response) = self.remove_first() {
+            // The file is removed once it's written.
+            tokio::spawn(async move {
+                let _ = response.write.await;
+                let _ = tokio::fs::remove_file(&response.path).await;
+            });
+        }
+    }
+
+    /// Removes all spilled responses.
+    pub(crate) fn clear(&mut self) {
+        self.reading = None;
+        self.responses.clear();
+        self.size_bytes = 0;
+        // dropping the directory removes all files
+        if let Some(dir) = self.dir.take() {
+            tokio::task::spawn_blocking(move || drop(dir));
+        }
+    }
+
+    fn remove_first(&mut self) -> Option<SpilledResponse> {
+        let (_, response) = self.responses.pop_first()?;
+        self.size_bytes -= response.size;
+        Some(response)
+    }
+}
+
+/// Creates the spill directory and removes the directories of spilled responses that were left
+/// behind, e.g. by a crash.
+fn prepare_spill_dir(spill_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(spill_dir)?;
+    for entry in fs::read_dir(spill_dir)? {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with(DIR_PREFIX) {
+            trace!(target: "downloaders::bodies", path = ?entry.path(), "Removing stale spill directory");
+            fs::remove_dir_all(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a response of `len` blocks that was encoded by [`SpilledResponses::spill`].
+fn decode_response(mut buf: &[u8], len: usize) -> alloy_rlp::Result<Vec<BlockResponse>> {
+    let mut response = Vec::with_capacity(len);
+    while let Some((&kind, rest)) = buf.split_first() {
+        buf = rest;
+        let block = match kind {
+            EMPTY_RESPONSE => BlockResponse::Empty(SealedHeader::decode(&mut buf)?),
+            FULL_RESPONSE => BlockResponse::Full(SealedBlock::decode(&mut buf)?),
+            _ => return Err(alloy_rlp::Error::Custom("unknown block response kind")),
+        };
+        response.push(block);
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::poll_fn;
+    use reth_primitives::B256;
+    use reth_testing_utils::{generators, generators::random_block_range};
+
+    fn response(range: RangeInclusive<u64>) -> Vec<BlockResponse> {
+        let mut rng = generators::rng();
+        random_block_range(&mut rng, range, B256::ZERO, 0..3)
+            .into_iter()
+            .map(|block| {
+                if block.body.is_empty() {
+                    BlockResponse::Empty(block.header)
+                } else {
+                    BlockResponse::Full(block)
+                }
+            })
+            .collect()
+    }
+
+    async fn pop_first(spilled: &mut SpilledResponses) -> Option<io::Result<Vec<BlockResponse>>> {
+        poll_fn(|cx| spilled.poll_pop_first(cx)).await
+    }
+
+    #[tokio::test]
+    async fn spills_responses_in_order() {
+        let spill_dir = tempfile::tempdir().unwrap();
+        let mut spilled = SpilledResponses::new(Some(spill_dir.path().to_path_buf()), usize::MAX);
+        let first = response(0..=9);
+        let second = response(10..=19);
+
+        spilled.spill(&second).unwrap();
+        spilled.spill(&first).unwrap();
+        assert_eq!(spilled.len(), 2);
+        assert_eq!(spilled.first_block_range(), Some(0..=9));
+
+        assert_eq!(pop_first(&mut spilled).await.unwrap().unwrap(), first);
+        assert_eq!(spilled.first_block_range(), Some(10..=19));
+        assert_eq!(pop_first(&mut spilled).await.unwrap().unwrap(), second);
+        assert!(pop_first(&mut spilled).await.is_none());
+        assert_eq!(spilled.size_bytes(), 0);
+    }
+
+    #[tokio::test]
+    async fn tracks_capacity() {
+        let spill_dir = tempfile::tempdir().unwrap();
+        let mut spilled = SpilledResponses::new(Some(spill_dir.path().to_path_buf()), 1);
+        assert!(spilled.has_capacity());
+
+        let size = spilled.spill(&response(0..=9)).unwrap();
+        assert_eq!(spilled.size_bytes(), size);
+        assert!(!spilled.has_capacity());
+
+        spilled.discard_first();
+        assert!(spilled.is_empty());
+        assert!(spilled.has_capacity());
+
+        spilled.spill(&response(0..=9)).unwrap();
+        spilled.clear();
+        assert!(spilled.is_empty());
+        assert_eq!(spilled.size_bytes(), 0);
+    }
+
+    #[test]
+    fn disabled_without_limit_or_dir() {
+        let spill_dir = tempfile::tempdir().unwrap();
+        assert!(!SpilledResponses::new(Some(spill_dir.path().to_path_buf()), 0).has_capacity());
+        assert!(!SpilledResponses::new(None, usize::MAX).has_capacity());
+    }
+
+    #[tokio::test]
+    async fn spills_into_spill_dir_and_removes_stale_dirs() {
+        let spill_dir = tempfile::tempdir().unwrap();
+        let stale = spill_dir.path().join(format!("{DIR_PREFIX}stale"));
+        fs::create_dir(&stale).unwrap();
+
+        let mut spilled = SpilledResponses::new(Some(spill_dir.path().to_path_buf()), usize::MAX);
+        assert!(!stale.exists());
+
+        spilled.spill(&response(0..=9)).unwrap();
+        let dir = spilled.dir.as_ref().unwrap().path().to_path_buf();
+        assert_eq!(dir.parent(), Some(spill_dir.path()));
+
+        pop_first(&mut spilled).await.unwrap().unwrap();
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+    }
+}
@@ -35,6 +35,10 @@ pub struct BodyDownloaderMetrics {
     pub buffered_blocks: Gauge,
     /// Total amount of memory used by the buffered blocks in bytes
     pub buffered_blocks_size_bytes: Gauge,
+    /// The number of responses in the internal buffer that were spilled to disk.
+    pub spilled_responses: Gauge,
+    /// Total amount of disk space used by the spilled responses in bytes
+    pub spilled_blocks_size_bytes: Gauge,
     /// The number blocks that are contiguous and are queued for insertion into the db.
     pub queued_blocks: Gauge,
     /// The number of out-of-order requests sent by the downloader.
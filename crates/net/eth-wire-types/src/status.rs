@@ -2,7 +2,7 @@ use crate::EthVersion;
 use alloy_chains::{Chain, NamedChain};
 use alloy_genesis::Genesis;
 use alloy_rlp::{RlpDecodable, RlpEncodable};
-use reth_chainspec::{ChainSpec, MAINNET};
+use reth_chainspec::{BlobScheduleError, ChainSpec, MAINNET};
 use reth_codecs_derive::add_arbitrary_tests;
 use reth_primitives::{hex, EthereumHardfork, ForkId, Head, B256, U256};
 use std::fmt::{Debug, Display};
@@ -42,20 +42,22 @@ pub struct Status {
     pub forkid: ForkId,
 }
 
-impl From<Genesis> for Status {
-    fn from(genesis: Genesis) -> Self {
+impl TryFrom<Genesis> for Status {
+    type Error = BlobScheduleError;
+
+    fn try_from(genesis: Genesis) -> Result<Self, Self::Error> {
         let chain = genesis.config.chain_id;
         let total_difficulty = genesis.difficulty;
-        let chainspec = ChainSpec::from(genesis);
+        let chainspec = ChainSpec::try_from_genesis(genesis)?;
 
-        Self {
+        Ok(Self {
             version: EthVersion::Eth68 as u8,
             chain: Chain::from_id(chain),
             total_difficulty,
             blockhash: chainspec.genesis_hash(),
             genesis: chainspec.genesis_hash(),
             forkid: chainspec.fork_id(&Head::default()),
-        }
+        })
     }
 }
 
@@ -2,6 +2,7 @@
 
 use std::{fmt, net::SocketAddr, sync::Arc};
 
+use alloy_primitives::bytes::Bytes;
 use reth_eth_wire_types::{
     message::RequestPair, BlockBodies, BlockHeaders, Capabilities, DisconnectReason, EthMessage,
     EthVersion, GetBlockBodies, GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts,
@@ -69,6 +70,9 @@ pub enum DiscoveryEvent {
     NewNode(DiscoveredEvent),
     /// Retrieved a [`ForkId`] from the peer via ENR request, See <https://eips.ethereum.org/EIPS/eip-868>
     EnrForkId(PeerId, ForkId),
+    /// Retrieved the kv-pairs of the tracked keys from the ENR of the peer, with the rlp encoded
+    /// values.
+    EnrEntries(PeerId, Vec<(Vec<u8>, Bytes)>),
 }
 
 /// Represents events related to peer discovery in the network.
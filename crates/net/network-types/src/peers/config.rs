@@ -8,10 +8,13 @@ use std::{
 };
 
 use reth_net_banlist::BanList;
-use reth_network_peers::{NodeRecord, TrustedPeer};
-use tracing::info;
+use reth_network_peers::{
+    persisted::{live_peers, unix_timestamp, DEFAULT_PERSISTED_PEER_MAX_AGE},
+    NodeRecord, PersistedPeer, TrustedPeer,
+};
+use tracing::{debug, info};
 
-use crate::{BackoffKind, ReputationChangeWeights};
+use crate::{BackoffKind, PeerDiversityConfig, ReputationChangeWeights};
 
 /// Maximum number of available slots for outbound sessions.
 pub const DEFAULT_MAX_COUNT_PEERS_OUTBOUND: u32 = 100;
@@ -103,6 +106,9 @@ pub struct ConnectionsConfig {
     /// Maximum allowed concurrent outbound dials.
     #[cfg_attr(feature = "serde", serde(default))]
     pub max_concurrent_outbound_dials: usize,
+    /// Maximum allowed connections in total, regardless of their direction.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub max_peers: Option<usize>,
 }
 
 impl Default for ConnectionsConfig {
@@ -111,6 +117,7 @@ impl Default for ConnectionsConfig {
             max_outbound: DEFAULT_MAX_COUNT_PEERS_OUTBOUND as usize,
             max_inbound: DEFAULT_MAX_COUNT_PEERS_INBOUND as usize,
             max_concurrent_outbound_dials: DEFAULT_MAX_COUNT_CONCURRENT_OUTBOUND_DIALS,
+            max_peers: None,
         }
     }
 }
@@ -141,6 +148,15 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// Peers persisted by a previous run, most recently seen first.
+    ///
+    /// These are also included in the basic nodes, and are used to bootstrap discovery before
+    /// the configured boot nodes and DNS discovery.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_peers: Vec<PersistedPeer>,
+    /// How long a persisted peer is kept if it is not seen alive again.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub persisted_peer_max_age: Duration,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -149,6 +165,8 @@ pub struct PeersConfig {
     pub ban_list: BanList,
     /// Restrictions on connections.
     pub connection_info: ConnectionsConfig,
+    /// Limits on the number of peers that share a subnet, an autonomous system or a client.
+    pub diversity: PeerDiversityConfig,
     /// How to weigh reputation changes.
     pub reputation_weights: ReputationChangeWeights,
     /// How long to backoff peers that we are failed to connect to for non-fatal reasons.
@@ -162,6 +180,7 @@ impl Default for PeersConfig {
         Self {
             refill_slots_interval: Duration::from_millis(5_000),
             connection_info: Default::default(),
+            diversity: Default::default(),
             reputation_weights: Default::default(),
             ban_list: Default::default(),
             // Ban peers for 12h
@@ -170,6 +189,8 @@ impl Default for PeersConfig {
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
             basic_nodes: Default::default(),
+            persisted_peers: Default::default(),
+            persisted_peer_max_age: DEFAULT_PERSISTED_PEER_MAX_AGE,
             max_backoff_count: 5,
         }
     }
@@ -216,12 +237,27 @@ impl PeersConfig {
         self
     }
 
+    /// Maximum allowed connections in total with optional update.
+    pub const fn with_max_peers_opt(mut self, max_peers: Option<usize>) -> Self {
+        if let Some(max_peers) = max_peers {
+            self.connection_info.max_peers = Some(max_peers);
+        }
+        self
+    }
+
     /// Maximum allowed concurrent outbound dials.
     pub const fn with_max_concurrent_dials(mut self, max_concurrent_outbound_dials: usize) -> Self {
         self.connection_info.max_concurrent_outbound_dials = max_concurrent_outbound_dials;
         self
     }
 
+    /// Configures the limits on the number of peers that share a subnet, an autonomous system or
+    /// a client.
+    pub fn with_diversity(mut self, diversity: PeerDiversityConfig) -> Self {
+        self.diversity = diversity;
+        self
+    }
+
     /// Nodes to always connect to.
     pub fn with_trusted_nodes(mut self, nodes: Vec<TrustedPeer>) -> Self {
         self.trusted_nodes = nodes;
@@ -240,6 +276,12 @@ impl PeersConfig {
         self
     }
 
+    /// Configures how long a persisted peer is kept if it is not seen alive again.
+    pub const fn with_persisted_peer_max_age(mut self, persisted_peer_max_age: Duration) -> Self {
+        self.persisted_peer_max_age = persisted_peer_max_age;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub const fn with_max_backoff_count(mut self, max_backoff_count: u8) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -267,8 +309,11 @@ impl PeersConfig {
     }
 
     /// Read from file nodes available at launch. Ignored if None.
+    ///
+    /// Peers that were not seen alive for longer than the configured
+    /// [`persisted_peer_max_age`](Self::persisted_peer_max_age) are dropped.
     pub fn with_basic_nodes_from_file(
-        self,
+        mut self,
         optional_file: Option<impl AsRef<Path>>,
     ) -> Result<Self, io::Error> {
         let Some(file_path) = optional_file else { return Ok(self) };
@@ -278,8 +323,14 @@ impl PeersConfig {
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        let peers: Vec<PersistedPeer> = serde_json::from_reader(reader)?;
+        let num_saved = peers.len();
+        let peers = live_peers(peers, unix_timestamp(), self.persisted_peer_max_age);
+        debug!(target: "net::peers", live = peers.len(), dropped = num_saved - peers.len(), "Loaded saved peers");
+
+        self.basic_nodes = peers.iter().map(|peer| peer.record).collect();
+        self.persisted_peers = peers;
+        Ok(self)
     }
 
     /// Returns settings for testing
@@ -0,0 +1,169 @@
+//! Diversity constraints on the connected peers.
+
+use std::{
+    io::{self, BufRead},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    path::{Path, PathBuf},
+};
+
+/// Limits on the number of connected peers that share a subnet, an autonomous system or a client
+/// implementation.
+///
+/// Spreading the peers over many networks and clients makes it harder for an attacker who controls
+/// a few networks to eclipse the node. Trusted and static peers are exempt from the limits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct PeerDiversityConfig {
+    /// Maximum number of peers per IPv4 /24 or IPv6 /48 subnet.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_peers_per_subnet: Option<usize>,
+    /// Maximum number of peers per autonomous system, as looked up in the
+    /// [`asn_table`](Self::asn_table).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_peers_per_asn: Option<usize>,
+    /// Path to a table of IP ranges and the numbers of the autonomous systems that announce them,
+    /// in the tab separated format of <https://iptoasn.com>.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub asn_table: Option<PathBuf>,
+    /// Maximum number of peers per client implementation, as announced in their `Hello` message.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_peers_per_client: Option<usize>,
+}
+
+impl PeerDiversityConfig {
+    /// Returns `true` if any limit is configured.
+    pub const fn is_enabled(&self) -> bool {
+        self.max_peers_per_subnet.is_some() ||
+            self.max_peers_per_asn.is_some() ||
+            self.max_peers_per_client.is_some()
+    }
+}
+
+/// Returns the IPv4 /24 or IPv6 /48 subnet of the address, with the host bits set to zero.
+pub fn subnet(ip: IpAddr) -> IpAddr {
+    match ip.to_canonical() {
+        IpAddr::V4(ip) => IpAddr::V4(Ipv4Addr::from(u32::from(ip) & 0xffff_ff00)),
+        IpAddr::V6(ip) => IpAddr::V6(Ipv6Addr::from(u128::from(ip) & (u128::MAX << 80))),
+    }
+}
+
+/// Returns the name of the client implementation of a `Hello` client version, like `geth` for
+/// `Geth/v1.14.8-stable/linux-amd64/go1.22.6`.
+pub fn client_name(client_version: &str) -> String {
+    client_version.split('/').next().unwrap_or_default().trim().to_lowercase()
+}
+
+/// A lookup table of the autonomous system numbers of IP ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AsnTable {
+    /// The first and last address of each range and its ASN, sorted by the first address and
+    /// without overlaps.
+    ranges: Vec<(IpAddr, IpAddr, u32)>,
+}
+
+impl AsnTable {
+    /// Reads the table from a file in the tab separated format of <https://iptoasn.com>, where each
+    /// line starts with the first address, the last address and the ASN of a range.
+    ///
+    /// Ranges with ASN 0, which are not routed, and malformed lines are skipped.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let reader = io::BufReader::new(std::fs::File::open(path)?);
+        let mut ranges = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let mut columns = line.split('\t');
+            let (Some(first), Some(last), Some(asn)) =
+                (columns.next(), columns.next(), columns.next())
+            else {
+                continue
+            };
+            let (Ok(first), Ok(last), Ok(asn)) =
+                (first.parse::<IpAddr>(), last.parse::<IpAddr>(), asn.parse::<u32>())
+            else {
+                continue
+            };
+            if asn != 0 && first <= last {
+                ranges.push((first, last, asn));
+            }
+        }
+        Ok(Self::new(ranges))
+    }
+
+    /// Creates a table from the first and last address of each range and its ASN.
+    ///
+    /// Ranges that overlap with a range that starts before them are dropped.
+    pub fn new(mut ranges: Vec<(IpAddr, IpAddr, u32)>) -> Self {
+        ranges.sort_unstable();
+        let mut last_end: Option<IpAddr> = None;
+        ranges.retain(|(first, last, _)| {
+            let overlaps = last_end.is_some_and(|end| *first <= end);
+            if !overlaps {
+                last_end = Some(*last);
+            }
+            !overlaps
+        });
+        Self { ranges }
+    }
+
+    /// Returns the ASN of the range that contains the address.
+    pub fn lookup(&self, ip: IpAddr) -> Option<u32> {
+        let ip = ip.to_canonical();
+        let index = self.ranges.partition_point(|(first, _, _)| *first <= ip).checked_sub(1)?;
+        let (_, last, asn) = self.ranges[index];
+        (ip <= last).then_some(asn)
+    }
+
+    /// Returns the number of ranges of the table.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns `true` if the table has no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subnets_and_client_names() {
+        assert_eq!(subnet("1.2.3.4".parse().unwrap()), "1.2.3.0".parse::<IpAddr>().unwrap());
+        assert_eq!(subnet("::ffff:1.2.3.4".parse().unwrap()), "1.2.3.0".parse::<IpAddr>().unwrap());
+        assert_eq!(
+            subnet("2001:db8:1:2::1".parse().unwrap()),
+            "2001:db8:1::".parse::<IpAddr>().unwrap()
+        );
+
+        assert_eq!(client_name("Geth/v1.14.8-stable/linux-amd64/go1.22.6"), "geth");
+        assert_eq!(client_name("reth/v1.0.5"), "reth");
+        assert_eq!(client_name(""), "");
+    }
+
+    #[test]
+    fn asn_lookup() {
+        let dir = std::env::temp_dir().join("reth-test-asn-table");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ip2asn.tsv");
+        std::fs::write(
+            &path,
+            "1.0.0.0\t1.0.0.255\t13335\tUS\tCLOUDFLARENET\n\
+             1.0.1.0\t1.0.3.255\t0\tNone\tNot routed\n\
+             1.0.4.0\t1.0.7.255\t38803\tAU\tWPL-AS-AP\n\
+             malformed line\n\
+             2001:200::\t2001:200:ffff:ffff:ffff:ffff:ffff:ffff\t2500\tJP\tWIDE-BB\n",
+        )
+        .unwrap();
+
+        let table = AsnTable::from_file(&path).unwrap();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.lookup("1.0.0.1".parse().unwrap()), Some(13335));
+        assert_eq!(table.lookup("::ffff:1.0.5.1".parse().unwrap()), Some(38803));
+        assert_eq!(table.lookup("1.0.2.1".parse().unwrap()), None);
+        assert_eq!(table.lookup("0.255.255.255".parse().unwrap()), None);
+        assert_eq!(table.lookup("2001:200::1".parse().unwrap()), Some(2500));
+        assert_eq!(table.lookup("2001:201::1".parse().unwrap()), None);
+    }
+}
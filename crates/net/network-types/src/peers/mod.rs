@@ -1,11 +1,13 @@
 pub mod addr;
 pub mod config;
+pub mod diversity;
 pub mod kind;
 pub mod state;
 
 pub use reth_network_p2p::reputation;
 
 pub use config::{ConnectionsConfig, PeersConfig};
+pub use diversity::PeerDiversityConfig;
 pub use reputation::ReputationChangeWeights;
 
 use reth_ethereum_forks::ForkId;
@@ -36,6 +38,9 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// The name of the client implementation that the peer announced in its last session, see
+    /// [`diversity::client_name`].
+    pub client: Option<String>,
 }
 
 // === impl Peer ===
@@ -67,6 +72,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            client: None,
         }
     }
 
@@ -2,6 +2,8 @@
 
 use std::{collections::HashSet, net::SocketAddr, sync::Arc};
 
+use alloy_rlp::Encodable;
+
 use reth_chainspec::{ChainSpec, MAINNET};
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOVERY_ADDRESS};
 use reth_discv5::NetworkStackId;
@@ -9,7 +11,7 @@ use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
 use reth_network_types::{PeersConfig, SessionsConfig};
-use reth_primitives::{ForkFilter, Head};
+use reth_primitives::{bytes::Bytes, ForkFilter, Head};
 use reth_storage_api::{BlockNumReader, BlockReader, HeaderProvider};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use secp256k1::SECP256K1;
@@ -188,6 +190,10 @@ pub struct NetworkConfigBuilder {
     block_import: Option<Box<dyn BlockImport>>,
     /// How to instantiate transactions manager.
     transactions_manager_config: TransactionsManagerConfig,
+    /// Additional kv-pairs with rlp encoded values to advertise in the local node record.
+    enr_kv_pairs: Vec<(Vec<u8>, Bytes)>,
+    /// Keys of kv-pairs to retrieve from the node records of discovered peers.
+    tracked_enr_keys: Vec<Vec<u8>>,
 }
 
 // === impl NetworkConfigBuilder ===
@@ -220,6 +226,8 @@ impl NetworkConfigBuilder {
             tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
+            enr_kv_pairs: Vec::new(),
+            tracked_enr_keys: Vec::new(),
         }
     }
 
@@ -370,6 +378,28 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Adds a kv-pair to advertise in the local node record of the configured discovery services
+    /// (discv4 + discv5), e.g. to identify the network the node belongs to.
+    pub fn add_enr_kv_pair(self, key: impl Into<Vec<u8>>, value: impl Encodable) -> Self {
+        self.add_enr_rlp_kv_pair(key, Bytes::from(alloy_rlp::encode(&value)))
+    }
+
+    /// Adds a kv-pair with an rlp encoded value to advertise in the local node record of the
+    /// configured discovery services (discv4 + discv5).
+    pub fn add_enr_rlp_kv_pair(mut self, key: impl Into<Vec<u8>>, rlp: Bytes) -> Self {
+        self.enr_kv_pairs.push((key.into(), rlp));
+        self
+    }
+
+    /// Adds a key of a kv-pair to retrieve from the node records of discovered peers.
+    ///
+    /// The rlp encoded values are emitted as
+    /// [`DiscoveryEvent::EnrEntries`](reth_network_api::DiscoveryEvent::EnrEntries).
+    pub fn track_enr_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.tracked_enr_keys.push(key.into());
+        self
+    }
+
     /// Sets the dns discovery config to use.
     pub fn dns_discovery(mut self, config: DnsDiscoveryConfig) -> Self {
         self.dns_discovery_config = Some(config);
@@ -478,7 +508,7 @@ impl NetworkConfigBuilder {
         let Self {
             secret_key,
             mut dns_discovery_config,
-            discovery_v4_builder,
+            mut discovery_v4_builder,
             mut discovery_v5_builder,
             boot_nodes,
             discovery_addr,
@@ -494,13 +524,28 @@ impl NetworkConfigBuilder {
             tx_gossip_disabled,
             block_import,
             transactions_manager_config,
+            enr_kv_pairs,
+            tracked_enr_keys,
         } = self;
 
+        if let Some(builder) = discovery_v4_builder.as_mut() {
+            builder.extend_eip868_rlp_pairs(enr_kv_pairs.iter().cloned());
+            for key in &tracked_enr_keys {
+                builder.track_eip868_key(key.clone());
+            }
+        }
+
         discovery_v5_builder = discovery_v5_builder.map(|mut builder| {
             if let Some(network_stack_id) = NetworkStackId::id(&chain_spec) {
                 let fork_id = chain_spec.latest_fork_id();
                 builder = builder.fork(network_stack_id, fork_id)
             }
+            for (key, rlp) in enr_kv_pairs {
+                builder = builder.add_enr_kv_pair(key, rlp);
+            }
+            for key in tracked_enr_keys {
+                builder = builder.track_enr_key(key);
+            }
 
             builder
         });
@@ -637,4 +682,19 @@ mod tests {
         assert_eq!(status.forkid.hash, genesis_fork_hash);
         assert_eq!(fork_filter.current().hash, genesis_fork_hash);
     }
+
+    #[test]
+    fn test_network_enr_kv_pairs() {
+        let config = builder()
+            .add_enr_kv_pair(b"l2".to_vec(), 10u64)
+            .track_enr_key(b"l2".to_vec())
+            .build(NoopProvider::default());
+
+        let discv4 = config.discovery_v4_config.unwrap();
+        assert_eq!(
+            discv4.additional_eip868_rlp_pairs.get(b"l2".as_slice()),
+            Some(&Bytes::from(alloy_rlp::encode(10u64)))
+        );
+        assert!(discv4.tracked_eip868_keys.contains(b"l2".as_slice()));
+    }
 }
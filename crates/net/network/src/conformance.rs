@@ -0,0 +1,376 @@
+//! Scripted malicious peer for validating how a node handles protocol violations.
+//!
+//! The [`MaliciousPeer`] connects to a running node like a regular peer, completes the `RLPx`,
+//! `p2p` and `eth` handshakes and then deliberately violates the protocol, e.g. by sending
+//! malformed RLP, oversized messages or unexpected messages. It then observes whether the node
+//! drops the session or tolerates the message, and whether the node keeps accepting new sessions
+//! afterwards.
+//!
+//! This is intended for CI and for operators who want to validate the denial of service posture
+//! of a node after changing its configuration:
+//!
+//! ```no_run
+//! # async fn t(node: reth_network_peers::NodeRecord) {
+//! use reth_chainspec::MAINNET;
+//! use reth_network::conformance::{MaliciousPeer, Misbehavior};
+//!
+//! let peer = MaliciousPeer::with_chain_spec(&MAINNET);
+//! let report = peer.run(node, Misbehavior::default_suite()).await;
+//! assert!(report.passed(), "{report:?}");
+//! # }
+//! ```
+//!
+//! Every misbehavior is executed in a new session with a new random identity, so penalties the
+//! node applies to one identity don't affect the next scenario. Misbehavior during the handshake
+//! is not covered, because nodes ban the IP address of such peers.
+
+use crate::config::rng_secret_key;
+use alloy_rlp::Encodable;
+use futures::{SinkExt, StreamExt};
+use rand::Rng;
+use reth_chainspec::ChainSpec;
+use reth_ecies::{stream::ECIESStream, ECIESError};
+use reth_eth_wire::{
+    errors::{EthStreamError, P2PStreamError},
+    message::RequestPair,
+    BlockHeaders, DisconnectReason, EthMessage, EthMessageID, EthStream, GetBlockHeaders,
+    HeadersDirection, HelloMessage, P2PStream, ProtocolMessage, Status, UnauthedEthStream,
+    UnauthedP2PStream, MAX_MESSAGE_SIZE,
+};
+use reth_network_peers::{pk2id, NodeRecord};
+use reth_primitives::{bytes::Bytes, BlockHashOrNumber, ForkFilter, Head};
+use secp256k1::SECP256K1;
+use std::{io, time::Duration};
+use tokio::{net::TcpStream, time::Instant};
+
+/// The default time to wait for the node to react to a misbehavior.
+pub const DEFAULT_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A message id that is not part of any `eth` protocol version.
+const UNKNOWN_MESSAGE_ID: u8 = 0x1f;
+
+/// The connection of a [`MaliciousPeer`] after the handshakes.
+pub type MaliciousConnection = EthStream<P2PStream<ECIESStream<TcpStream>>>;
+
+/// Errors of the [`MaliciousPeer`].
+#[derive(Debug, thiserror::Error)]
+pub enum ConformanceError {
+    /// Failed to open a TCP connection to the node.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The `RLPx` handshake failed.
+    #[error(transparent)]
+    Ecies(#[from] ECIESError),
+    /// The `p2p` handshake failed, or sending a message failed.
+    #[error(transparent)]
+    P2P(#[from] P2PStreamError),
+    /// The `eth` handshake failed, or sending a message failed.
+    #[error(transparent)]
+    Eth(#[from] EthStreamError),
+}
+
+/// A protocol violation the [`MaliciousPeer`] commits after the handshake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Misbehavior {
+    /// Sends a message with the given id and a payload that is not valid RLP.
+    MalformedRlp(EthMessageID),
+    /// Sends a message that exceeds [`MAX_MESSAGE_SIZE`].
+    OversizedMessage,
+    /// Sends a message with an id that is not part of the `eth` protocol.
+    UnknownMessageId,
+    /// Sends another `Status` message after the handshake.
+    RepeatedStatus,
+    /// Sends a `BlockHeaders` response to a request the node never sent.
+    UnsolicitedResponse,
+    /// Sends a message with the given id and random bytes as payload.
+    RandomPayload {
+        /// The id of the message.
+        id: EthMessageID,
+        /// The length of the random payload.
+        len: usize,
+    },
+    /// Sends the given number of `GetBlockHeaders` requests without waiting for responses.
+    RequestFlood(usize),
+}
+
+impl Misbehavior {
+    /// Returns the misbehaviors that are checked by default.
+    pub fn default_suite() -> Vec<Self> {
+        vec![
+            Self::MalformedRlp(EthMessageID::Transactions),
+            Self::MalformedRlp(EthMessageID::GetBlockHeaders),
+            Self::OversizedMessage,
+            Self::UnknownMessageId,
+            Self::RepeatedStatus,
+            Self::UnsolicitedResponse,
+            Self::RandomPayload { id: EthMessageID::NewPooledTransactionHashes, len: 1024 },
+            Self::RandomPayload { id: EthMessageID::BlockBodies, len: 1024 },
+            Self::RequestFlood(1000),
+        ]
+    }
+
+    /// Returns how a node is expected to react to the misbehavior.
+    pub const fn expected_reaction(&self) -> ExpectedReaction {
+        match self {
+            Self::MalformedRlp(_) |
+            Self::OversizedMessage |
+            Self::UnknownMessageId |
+            Self::RepeatedStatus => ExpectedReaction::Disconnect,
+            // unsolicited responses are penalized, but don't close the session right away
+            Self::UnsolicitedResponse => ExpectedReaction::Tolerate,
+            Self::RandomPayload { .. } | Self::RequestFlood(_) => ExpectedReaction::Any,
+        }
+    }
+}
+
+/// How a node is expected to react to a [`Misbehavior`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectedReaction {
+    /// The node closes the session.
+    Disconnect,
+    /// The node keeps the session open.
+    Tolerate,
+    /// Either reaction is acceptable, as long as the node keeps accepting new sessions.
+    Any,
+}
+
+/// How a node reacted to a [`Misbehavior`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reaction {
+    /// The node closed the session, with the disconnect reason if it sent one.
+    Disconnected(Option<DisconnectReason>),
+    /// The node kept the session open until the response timeout elapsed.
+    Tolerated,
+}
+
+impl Reaction {
+    /// Returns `true` if the reaction is the expected one.
+    pub const fn matches(&self, expected: ExpectedReaction) -> bool {
+        matches!(
+            (self, expected),
+            (_, ExpectedReaction::Any) |
+                (Self::Disconnected(_), ExpectedReaction::Disconnect) |
+                (Self::Tolerated, ExpectedReaction::Tolerate)
+        )
+    }
+}
+
+/// The result of a single [`Misbehavior`].
+#[derive(Debug)]
+pub struct ScenarioResult {
+    /// The misbehavior that was committed.
+    pub misbehavior: Misbehavior,
+    /// The reaction of the node, or the error if the scenario could not be executed.
+    pub reaction: Result<Reaction, ConformanceError>,
+}
+
+impl ScenarioResult {
+    /// Returns `true` if the node reacted as expected.
+    pub fn passed(&self) -> bool {
+        self.reaction
+            .as_ref()
+            .is_ok_and(|reaction| reaction.matches(self.misbehavior.expected_reaction()))
+    }
+}
+
+/// The results of a [`MaliciousPeer::run`].
+#[derive(Debug)]
+pub struct ConformanceReport {
+    /// The result of every misbehavior, in order.
+    pub results: Vec<ScenarioResult>,
+    /// Whether the node still accepted a new session after all misbehaviors.
+    pub responsive: bool,
+}
+
+impl ConformanceReport {
+    /// Returns `true` if the node reacted to all misbehaviors as expected and is still responsive.
+    pub fn passed(&self) -> bool {
+        self.responsive && self.results.iter().all(ScenarioResult::passed)
+    }
+
+    /// Returns the results of the misbehaviors the node didn't react to as expected.
+    pub fn failures(&self) -> impl Iterator<Item = &ScenarioResult> {
+        self.results.iter().filter(|result| !result.passed())
+    }
+}
+
+/// A peer that connects to a node and violates the protocol in scripted ways.
+///
+/// See the [module docs](self) for details.
+#[derive(Debug, Clone)]
+pub struct MaliciousPeer {
+    /// The status sent in the `eth` handshake.
+    status: Status,
+    /// The fork filter the status of the node is validated against.
+    fork_filter: ForkFilter,
+    /// How long to wait for the node to react to a misbehavior.
+    response_timeout: Duration,
+}
+
+impl MaliciousPeer {
+    /// Creates a new peer that uses the given status and fork filter in the `eth` handshake.
+    pub const fn new(status: Status, fork_filter: ForkFilter) -> Self {
+        Self { status, fork_filter, response_timeout: DEFAULT_RESPONSE_TIMEOUT }
+    }
+
+    /// Creates a new peer that claims to be at the genesis block of the chain.
+    pub fn with_chain_spec(chain_spec: &ChainSpec) -> Self {
+        let head = Head {
+            hash: chain_spec.genesis_hash(),
+            number: 0,
+            timestamp: chain_spec.genesis.timestamp,
+            difficulty: chain_spec.genesis.difficulty,
+            total_difficulty: chain_spec.genesis.difficulty,
+        };
+        Self::new(Status::spec_builder(chain_spec, &head).build(), chain_spec.fork_filter(head))
+    }
+
+    /// Sets how long to wait for the node to react to a misbehavior.
+    ///
+    /// If the node neither closes the session nor the connection within this timeout, the
+    /// misbehavior is considered tolerated.
+    pub const fn with_response_timeout(mut self, response_timeout: Duration) -> Self {
+        self.response_timeout = response_timeout;
+        self
+    }
+
+    /// Connects to the node with a new random identity and completes all handshakes.
+    pub async fn connect(&self, node: NodeRecord) -> Result<MaliciousConnection, ConformanceError> {
+        let secret_key = rng_secret_key();
+        let outgoing = TcpStream::connect((node.address, node.tcp_port)).await?;
+        let ecies_stream = ECIESStream::connect(outgoing, secret_key, node.id).await?;
+
+        let hello = HelloMessage::builder(pk2id(&secret_key.public_key(SECP256K1))).build();
+        let (p2p_stream, _) = UnauthedP2PStream::new(ecies_stream).handshake(hello).await?;
+
+        let mut status = self.status;
+        status.set_eth_version(p2p_stream.shared_capabilities().eth_version()?);
+        let (eth_stream, _) =
+            UnauthedEthStream::new(p2p_stream).handshake(status, self.fork_filter.clone()).await?;
+
+        Ok(eth_stream)
+    }
+
+    /// Commits the misbehavior in a new session and returns how the node reacted.
+    pub async fn run_scenario(
+        &self,
+        node: NodeRecord,
+        misbehavior: &Misbehavior,
+    ) -> Result<Reaction, ConformanceError> {
+        let mut conn = self.connect(node).await?;
+        self.misbehave(&mut conn, misbehavior).await?;
+        Ok(wait_for_reaction(&mut conn, self.response_timeout).await)
+    }
+
+    /// Commits all misbehaviors, each in a new session, and checks that the node still accepts
+    /// new sessions afterwards.
+    pub async fn run(
+        &self,
+        node: NodeRecord,
+        misbehaviors: impl IntoIterator<Item = Misbehavior>,
+    ) -> ConformanceReport {
+        let mut results = Vec::new();
+        for misbehavior in misbehaviors {
+            let reaction = self.run_scenario(node, &misbehavior).await;
+            results.push(ScenarioResult { misbehavior, reaction });
+        }
+        let responsive = self.connect(node).await.is_ok();
+
+        ConformanceReport { results, responsive }
+    }
+
+    /// Sends the messages of the misbehavior on the connection.
+    pub async fn misbehave(
+        &self,
+        conn: &mut MaliciousConnection,
+        misbehavior: &Misbehavior,
+    ) -> Result<(), ConformanceError> {
+        match misbehavior {
+            Misbehavior::MalformedRlp(id) => {
+                // a list header announcing more bytes than the payload contains
+                send_raw(conn, *id as u8, &[0xf8, 0xff, 0x01]).await
+            }
+            Misbehavior::OversizedMessage => {
+                let payload = vec![0u8; MAX_MESSAGE_SIZE + 1];
+                send_raw(conn, EthMessageID::Transactions as u8, &payload).await
+            }
+            Misbehavior::UnknownMessageId => send_raw(conn, UNKNOWN_MESSAGE_ID, &[0xc0]).await,
+            Misbehavior::RepeatedStatus => {
+                // the eth stream refuses to send a status, so it's encoded manually
+                let mut status = self.status;
+                status.set_eth_version(conn.version());
+                let mut buf = Vec::new();
+                ProtocolMessage::from(EthMessage::Status(status)).encode(&mut buf);
+                conn.inner_mut().send(Bytes::from(buf)).await?;
+                Ok(())
+            }
+            Misbehavior::UnsolicitedResponse => {
+                let request_id = rand::thread_rng().gen();
+                conn.send(EthMessage::BlockHeaders(RequestPair {
+                    request_id,
+                    message: BlockHeaders::default(),
+                }))
+                .await?;
+                Ok(())
+            }
+            Misbehavior::RandomPayload { id, len } => {
+                let mut payload = vec![0u8; *len];
+                rand::thread_rng().fill(payload.as_mut_slice());
+                send_raw(conn, *id as u8, &payload).await
+            }
+            Misbehavior::RequestFlood(count) => {
+                for request_id in 0..*count as u64 {
+                    let request = GetBlockHeaders {
+                        start_block: BlockHashOrNumber::Number(0),
+                        limit: 1,
+                        skip: 0,
+                        direction: HeadersDirection::Rising,
+                    };
+                    conn.feed(EthMessage::GetBlockHeaders(RequestPair {
+                        request_id,
+                        message: request,
+                    }))
+                    .await?;
+                }
+                conn.flush().await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Sends a message with the given `eth` message id and payload, bypassing the encoding of the
+/// [`EthStream`].
+async fn send_raw(
+    conn: &mut MaliciousConnection,
+    id: u8,
+    payload: &[u8],
+) -> Result<(), ConformanceError> {
+    let mut msg = Vec::with_capacity(payload.len() + 1);
+    msg.push(id);
+    msg.extend_from_slice(payload);
+    conn.inner_mut().send(Bytes::from(msg)).await?;
+    Ok(())
+}
+
+/// Waits until the node closes the session or the timeout elapses.
+///
+/// Messages the node sends in the meantime are ignored.
+async fn wait_for_reaction(conn: &mut MaliciousConnection, timeout: Duration) -> Reaction {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match tokio::time::timeout_at(deadline, conn.next()).await {
+            Err(_) => return Reaction::Tolerated,
+            Ok(None) => return Reaction::Disconnected(None),
+            Ok(Some(Ok(_))) => {}
+            Ok(Some(Err(err))) => {
+                if let Some(reason) = err.as_disconnected() {
+                    return Reaction::Disconnected(Some(reason))
+                }
+                if err.as_io().is_some() {
+                    return Reaction::Disconnected(None)
+                }
+            }
+        }
+    }
+}
@@ -6,17 +6,18 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
+    time::Duration,
 };
 
 use enr::Enr;
 use futures::StreamExt;
 use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config};
-use reth_discv5::{DiscoveredPeer, Discv5};
+use reth_discv5::{enr::EnrCombinedKeyWrapper, DiscoveredPeer, Discv5};
 use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent};
-use reth_network_peers::{NodeRecord, PeerId};
+use reth_network_peers::{NodeRecord, PeerId, PersistedPeer};
 use reth_network_types::PeerAddr;
 use reth_primitives::{EnrForkIdEntry, ForkId};
 use secp256k1::SecretKey;
@@ -27,6 +28,7 @@ use tracing::trace;
 use crate::{
     cache::LruMap,
     error::{NetworkError, ServiceKind},
+    persisted::PeerLiveness,
 };
 
 /// Default max capacity for cache of discovered peers.
@@ -34,6 +36,12 @@ use crate::{
 /// Default is 10 000 peers.
 pub const DEFAULT_MAX_CAPACITY_DISCOVERED_PEERS_CACHE: u32 = 10_000;
 
+/// Max number of persisted peers that are used as boot nodes of the discovery services, most
+/// recently seen first.
+///
+/// Default is 64 peers.
+pub const MAX_PERSISTED_BOOT_NODES: usize = 64;
+
 /// An abstraction over the configured discovery protocol.
 ///
 /// Listens for new discovered nodes and emits events for discovered nodes and their
@@ -66,6 +74,8 @@ pub struct Discovery {
     queued_events: VecDeque<DiscoveryEvent>,
     /// List of listeners subscribed to discovery events.
     discovery_listeners: Vec<mpsc::UnboundedSender<DiscoveryEvent>>,
+    /// Liveness of discovered peers, used to persist peers across restarts.
+    liveness: PeerLiveness,
 }
 
 impl Discovery {
@@ -136,9 +146,36 @@ impl Discovery {
             _dns_disc_service,
             _dns_discovery,
             dns_discovery_updates,
+            liveness: Default::default(),
         })
     }
 
+    /// Continues tracking the liveness of the peers persisted by a previous run.
+    ///
+    /// Persisted peers that are not seen alive again within `max_age` are dropped by
+    /// [`Discovery::persisted_peers`].
+    pub(crate) fn set_persisted_peers(
+        &mut self,
+        persisted_peers: Vec<PersistedPeer>,
+        max_age: Duration,
+    ) {
+        self.liveness = PeerLiveness::new(persisted_peers, max_age);
+    }
+
+    /// Marks the peer as seen alive, e.g. because a session was established with it.
+    pub(crate) fn on_peer_seen(&mut self, peer_id: PeerId) {
+        self.liveness.on_peer_seen(peer_id)
+    }
+
+    /// Returns the given peers with their liveness and known ENRs, most recently seen first.
+    pub(crate) fn persisted_peers(
+        &self,
+        records: impl IntoIterator<Item = NodeRecord>,
+        is_connected: impl Fn(&PeerId) -> bool,
+    ) -> Vec<PersistedPeer> {
+        self.liveness.persisted_peers(records, is_connected)
+    }
+
     /// Registers a listener for receiving [`DiscoveryEvent`] updates.
     pub(crate) fn add_listener(&mut self, tx: mpsc::UnboundedSender<DiscoveryEvent>) {
         self.discovery_listeners.push(tx);
@@ -224,11 +261,16 @@ impl Discovery {
     fn on_discv4_update(&mut self, update: DiscoveryUpdate) {
         match update {
             DiscoveryUpdate::Added(record) | DiscoveryUpdate::DiscoveredAtCapacity(record) => {
+                // the node has been bonded with
+                self.liveness.on_peer_seen(record.id);
                 self.on_node_record_update(record, None);
             }
             DiscoveryUpdate::EnrForkId(node, fork_id) => {
                 self.queued_events.push_back(DiscoveryEvent::EnrForkId(node.id, fork_id))
             }
+            DiscoveryUpdate::EnrEntries(node, entries) => {
+                self.queued_events.push_back(DiscoveryEvent::EnrEntries(node.id, entries))
+            }
             DiscoveryUpdate::Removed(peer_id) => {
                 self.discovered_nodes.remove(&peer_id);
             }
@@ -260,10 +302,22 @@ impl Discovery {
                 self.discv5_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
             {
                 if let Some(discv5) = self.discv5.as_mut() {
-                    if let Some(DiscoveredPeer { node_record, fork_id }) =
+                    let enr = match &update {
+                        discv5::Event::SessionEstablished(enr, _) => Some(enr.clone()),
+                        _ => None,
+                    };
+                    if let Some(DiscoveredPeer { node_record, fork_id, enr_entries }) =
                         discv5.on_discv5_update(update)
                     {
+                        self.liveness.on_peer_seen(node_record.id);
+                        if let Some(enr) = enr {
+                            self.liveness.on_enr(node_record.id, EnrCombinedKeyWrapper(enr).into());
+                        }
                         self.on_node_record_update(node_record, fork_id);
+                        if !enr_entries.is_empty() {
+                            self.queued_events
+                                .push_back(DiscoveryEvent::EnrEntries(node_record.id, enr_entries));
+                        }
                     }
                 }
             }
@@ -273,6 +327,7 @@ impl Discovery {
                 self.dns_discovery_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
             {
                 self.add_discv4_node(update.node_record);
+                self.liveness.on_enr(update.node_record.id, update.enr.clone());
                 if let Err(err) = self.add_discv5_node(update.enr) {
                     trace!(target: "net::discovery",
                         %err,
@@ -324,6 +379,7 @@ impl Discovery {
             dns_discovery_updates: None,
             _dns_disc_service: None,
             discovery_listeners: Default::default(),
+            liveness: Default::default(),
         }
     }
 }
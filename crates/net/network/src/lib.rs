@@ -101,6 +101,8 @@
 //! - `serde` (default): Enable serde support for configuration types.
 //! - `test-utils`: Various utilities helpful for writing tests
 //! - `geth-tests`: Runs tests that require Geth to be installed locally.
+//! - `conformance`: A scripted malicious peer for validating how a node handles protocol
+//!   violations, see [`conformance`].
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -116,6 +118,8 @@ pub mod test_utils;
 
 pub mod cache;
 pub mod config;
+#[cfg(feature = "conformance")]
+pub mod conformance;
 pub mod error;
 pub mod eth_requests;
 pub mod import;
@@ -133,6 +137,7 @@ mod listener;
 mod manager;
 mod metrics;
 mod network;
+mod persisted;
 mod session;
 mod state;
 mod swarm;
@@ -29,13 +29,14 @@ use std::{
 
 use futures::{Future, StreamExt};
 use parking_lot::Mutex;
+use reth_discv5::{enr::EnrCombinedKeyWrapper, BootNode};
 use reth_eth_wire::{capability::CapabilityMessage, Capabilities, DisconnectReason};
 use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
 use reth_network_api::{
     test_utils::PeersHandle, EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest,
 };
-use reth_network_peers::{NodeRecord, PeerId};
+use reth_network_peers::{NodeRecord, PeerId, PersistedPeer};
 use reth_network_types::ReputationChangeKind;
 use reth_storage_api::BlockNumReader;
 use reth_tasks::shutdown::GracefulShutdown;
@@ -48,7 +49,7 @@ use tracing::{debug, error, trace, warn};
 use crate::{
     budget::{DEFAULT_BUDGET_TRY_DRAIN_NETWORK_HANDLE_CHANNEL, DEFAULT_BUDGET_TRY_DRAIN_SWARM},
     config::NetworkConfig,
-    discovery::Discovery,
+    discovery::{Discovery, MAX_PERSISTED_BOOT_NODES},
     error::{NetworkError, ServiceKind},
     eth_requests::IncomingEthRequest,
     import::{BlockImport, BlockImportOutcome, BlockValidation},
@@ -176,7 +177,7 @@ impl NetworkManager {
             mut discovery_v4_config,
             mut discovery_v5_config,
             listener_addr,
-            peers_config,
+            mut peers_config,
             sessions_config,
             chain_spec,
             block_import,
@@ -192,6 +193,8 @@ impl NetworkManager {
             transactions_manager_config: _,
         } = config;
 
+        let persisted_peers = std::mem::take(&mut peers_config.persisted_peers);
+        let persisted_peer_max_age = peers_config.persisted_peer_max_age;
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
@@ -206,18 +209,33 @@ impl NetworkManager {
         let resolved_boot_nodes =
             futures::future::try_join_all(boot_nodes.iter().map(|record| record.resolve())).await?;
 
+        // bootstrap from the most recently seen persisted peers, which are the most likely to still
+        // be alive, in addition to the configured boot nodes. DNS discovery is only used once its
+        // trees are resolved.
+        let bootstrap_peers =
+            &persisted_peers[..persisted_peers.len().min(MAX_PERSISTED_BOOT_NODES)];
+
         if let Some(disc_config) = discovery_v4_config.as_mut() {
+            disc_config.bootstrap_nodes.extend(bootstrap_peers.iter().map(|peer| peer.record));
             // merge configured boot nodes
             disc_config.bootstrap_nodes.extend(resolved_boot_nodes.clone());
             disc_config.add_eip868_pair("eth", status.forkid);
         }
 
         if let Some(discv5) = discovery_v5_config.as_mut() {
+            for peer in bootstrap_peers {
+                match &peer.enr {
+                    Some(enr) => discv5.insert_boot_node(BootNode::Enr(
+                        EnrCombinedKeyWrapper::from(enr.clone()).0,
+                    )),
+                    None => discv5.insert_unsigned_boot_node(peer.record),
+                }
+            }
             // merge configured boot nodes
             discv5.extend_unsigned_boot_nodes(resolved_boot_nodes)
         }
 
-        let discovery = Discovery::new(
+        let mut discovery = Discovery::new(
             listener_addr,
             discovery_v4_addr,
             secret_key,
@@ -226,6 +244,7 @@ impl NetworkManager {
             dns_discovery_config,
         )
         .await?;
+        discovery.set_persisted_peers(persisted_peers, persisted_peer_max_age);
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
         let discv4 = discovery.discv4();
@@ -343,6 +362,14 @@ impl NetworkManager {
         self.swarm.state().peers().iter_peers()
     }
 
+    /// Returns all peers in the peer set with their liveness, most recently seen first.
+    ///
+    /// Peers that were not seen alive within the configured
+    /// [`persisted_peer_max_age`](crate::PeersConfig::persisted_peer_max_age) are excluded.
+    pub fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        self.swarm.state().persisted_peers()
+    }
+
     /// Returns the number of peers in the peer set.
     pub fn num_known_peers(&self) -> usize {
         self.swarm.state().peers().num_known_peers()
@@ -357,8 +384,10 @@ impl NetworkManager {
 
     /// Collect the peers from the [`NetworkManager`] and write them to the given
     /// `persistent_peers_file`.
+    ///
+    /// The peers are written with their liveness and known ENRs, see [`PersistedPeer`].
     pub fn write_peers_to_file(&self, persistent_peers_file: &Path) -> Result<(), FsPathError> {
-        let known_peers = self.all_peers().collect::<Vec<_>>();
+        let known_peers = self.persisted_peers();
         persistent_peers_file.parent().map(fs::create_dir_all).transpose()?;
         reth_fs_util::write_json_file(persistent_peers_file, &known_peers)?;
         Ok(())
@@ -708,6 +737,11 @@ impl NetworkManager {
                     self.swarm.state_mut().peers_mut().on_active_outgoing_established(peer_id);
                 }
 
+                self.swarm
+                    .state_mut()
+                    .peers_mut()
+                    .on_session_client_version(peer_id, &client_version);
+
                 self.update_active_connection_metrics();
 
                 self.event_sender.notify(NetworkEvent::SessionEstablished {
@@ -17,10 +17,11 @@ use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
     peers::{
         config::PeerBackoffDurations,
+        diversity::{client_name, subnet, AsnTable},
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
-    ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerDiversityConfig, PeerKind,
+    PeersConfig, ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use reth_primitives::ForkId;
 use thiserror::Error;
@@ -64,6 +65,8 @@ pub struct PeersManager {
     reputation_weights: ReputationChangeWeights,
     /// Tracks current slot stats.
     connection_info: ConnectionInfo,
+    /// Limits on the number of peers that share a subnet, an autonomous system or a client.
+    diversity: PeerDiversity,
     /// Tracks unwanted ips/peer ids.
     ban_list: BanList,
     /// Tracks currently backed off peers.
@@ -92,6 +95,7 @@ impl PeersManager {
         let PeersConfig {
             refill_slots_interval,
             connection_info,
+            diversity,
             reputation_weights,
             ban_list,
             ban_duration,
@@ -100,6 +104,8 @@ impl PeersManager {
             trusted_nodes_only,
             basic_nodes,
             max_backoff_count,
+            persisted_peers: _,
+            persisted_peer_max_age: _,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -140,6 +146,7 @@ impl PeersManager {
             refill_slots_interval: tokio::time::interval(refill_slots_interval),
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info: ConnectionInfo::new(connection_info),
+            diversity: PeerDiversity::new(diversity),
             ban_list,
             backed_off_peers: Default::default(),
             ban_duration,
@@ -502,6 +509,31 @@ impl PeersManager {
         }
     }
 
+    /// Called when a session to a peer was established, with the client version that the peer
+    /// announced in its `Hello` message.
+    ///
+    /// This records the client of the peer and schedules a disconnect if the peer exceeds a limit
+    /// of the [`PeerDiversityConfig`]. Trusted and static peers are exempt from the limits.
+    pub(crate) fn on_session_client_version(&mut self, peer_id: PeerId, client_version: &str) {
+        let Some(peer) = self.peers.get_mut(&peer_id) else { return };
+        peer.client = Some(client_name(client_version));
+        if peer.is_trusted() || peer.is_static() || !self.diversity.config.is_enabled() {
+            return
+        }
+
+        let counts = self
+            .diversity
+            .count(self.peers.iter().filter(|(id, _)| **id != peer_id).map(|(_, peer)| peer));
+        if self.peers.get(&peer_id).is_some_and(|peer| self.diversity.exceeds_limits(&counts, peer))
+        {
+            trace!(target: "net::peers", ?peer_id, "disconnecting peer that exceeds diversity limits");
+            self.queued_actions.push_back(PeerAction::Disconnect {
+                peer_id,
+                reason: Some(DisconnectReason::TooManyPeers),
+            });
+        }
+    }
+
     /// Called when an _active_ session to a peer was forcefully dropped due to an error.
     ///
     /// Depending on whether the error is fatal, the peer will be removed from the peer set
@@ -750,13 +782,17 @@ impl PeersManager {
     /// If `trusted_nodes_only` is enabled, see [`PeersConfig`], then this will only consider
     /// `trusted` peers.
     ///
+    /// Peers that would exceed a limit of the [`PeerDiversityConfig`], given the `counts` of the
+    /// connected peers, are skipped.
+    ///
     /// Returns `None` if no peer is available.
-    fn best_unconnected(&mut self) -> Option<(PeerId, &mut Peer)> {
+    fn best_unconnected(&mut self, counts: &DiversityCounts) -> Option<(PeerId, &mut Peer)> {
         let mut unconnected = self.peers.iter_mut().filter(|(_, peer)| {
             !peer.is_backed_off() &&
                 !peer.is_banned() &&
                 peer.state.is_unconnected() &&
-                (!self.trusted_nodes_only || peer.is_trusted())
+                (!self.trusted_nodes_only || peer.is_trusted()) &&
+                !self.diversity.exceeds_limits(counts, peer)
         });
 
         // keep track of the best peer, if there's one
@@ -793,10 +829,12 @@ impl PeersManager {
             return
         }
 
+        let mut counts = self.diversity.count(self.peers.values());
+
         // as long as there are slots available fill them with the best peers
         while self.connection_info.has_out_capacity() {
-            let action = {
-                let (peer_id, peer) = match self.best_unconnected() {
+            let (peer_id, action) = {
+                let (peer_id, peer) = match self.best_unconnected(&counts) {
                     Some(peer) => peer,
                     _ => break,
                 };
@@ -804,10 +842,13 @@ impl PeersManager {
                 trace!(target: "net::peers", ?peer_id, addr=?peer.addr, "schedule outbound connection");
 
                 peer.state = PeerConnectionState::PendingOut;
-                PeerAction::Connect { peer_id, remote_addr: peer.addr.tcp() }
+                (peer_id, PeerAction::Connect { peer_id, remote_addr: peer.addr.tcp() })
             };
 
             self.connection_info.inc_pending_out();
+            if let Some(peer) = self.peers.get(&peer_id) {
+                self.diversity.insert(&mut counts, peer);
+            }
 
             self.queued_actions.push_back(action);
         }
@@ -924,12 +965,22 @@ impl ConnectionInfo {
     ///  Returns `true` if there's still capacity for a new outgoing connection.
     const fn has_out_capacity(&self) -> bool {
         self.num_pending_out < self.config.max_concurrent_outbound_dials &&
-            self.num_outbound < self.config.max_outbound
+            self.num_outbound < self.config.max_outbound &&
+            self.has_total_capacity(self.num_pending_out)
     }
 
     ///  Returns `true` if there's still capacity for a new incoming connection.
     const fn has_in_capacity(&self) -> bool {
-        self.num_inbound < self.config.max_inbound
+        self.num_inbound < self.config.max_inbound && self.has_total_capacity(0)
+    }
+
+    /// Returns `true` if the total number of connections, including the given number of pending
+    /// connections, is below the configured maximum.
+    const fn has_total_capacity(&self, pending: usize) -> bool {
+        match self.config.max_peers {
+            Some(max_peers) => self.num_inbound + self.num_outbound + pending < max_peers,
+            None => true,
+        }
     }
 
     fn decr_state(&mut self, state: PeerConnectionState) {
@@ -974,6 +1025,88 @@ impl ConnectionInfo {
     }
 }
 
+/// Enforces the limits of the [`PeerDiversityConfig`].
+#[derive(Debug, Default)]
+struct PeerDiversity {
+    config: PeerDiversityConfig,
+    /// The ASNs of IP ranges, read from the configured table.
+    asn_table: AsnTable,
+}
+
+impl PeerDiversity {
+    fn new(config: PeerDiversityConfig) -> Self {
+        let asn_table = match &config.asn_table {
+            Some(path) => AsnTable::from_file(path).unwrap_or_else(|err| {
+                warn!(target: "net::peers", %err, path=%path.display(), "Failed to read ASN table");
+                AsnTable::default()
+            }),
+            None => AsnTable::default(),
+        };
+        if config.max_peers_per_asn.is_some() && asn_table.is_empty() {
+            warn!(target: "net::peers", "Peers per ASN are not limited without an ASN table");
+        }
+        Self { config, asn_table }
+    }
+
+    /// Returns the number of connected and pending peers of the given peers by subnet, ASN and
+    /// client.
+    ///
+    /// Trusted and static peers are not counted.
+    fn count<'a>(&self, peers: impl Iterator<Item = &'a Peer>) -> DiversityCounts {
+        let mut counts = DiversityCounts::default();
+        if self.config.is_enabled() {
+            for peer in peers.filter(|peer| peer.state.is_connected()) {
+                self.insert(&mut counts, peer);
+            }
+        }
+        counts
+    }
+
+    /// Adds the peer to the counts, unless it's trusted or static.
+    fn insert(&self, counts: &mut DiversityCounts, peer: &Peer) {
+        if !self.config.is_enabled() || peer.is_trusted() || peer.is_static() {
+            return
+        }
+
+        let ip = peer.addr.tcp().ip();
+        *counts.subnets.entry(subnet(ip)).or_default() += 1;
+        if let Some(asn) = self.asn_table.lookup(ip) {
+            *counts.asns.entry(asn).or_default() += 1;
+        }
+        if let Some(client) = &peer.client {
+            *counts.clients.entry(client.clone()).or_default() += 1;
+        }
+    }
+
+    /// Returns `true` if connecting to the peer would exceed a limit, given the counts of the
+    /// other peers. Trusted and static peers never exceed the limits.
+    fn exceeds_limits(&self, counts: &DiversityCounts, peer: &Peer) -> bool {
+        if !self.config.is_enabled() || peer.is_trusted() || peer.is_static() {
+            return false
+        }
+
+        let at_limit = |count: Option<&usize>, max: Option<usize>| {
+            max.is_some_and(|max| count.copied().unwrap_or_default() >= max)
+        };
+        let ip = peer.addr.tcp().ip();
+        at_limit(counts.subnets.get(&subnet(ip)), self.config.max_peers_per_subnet) ||
+            self.asn_table.lookup(ip).is_some_and(|asn| {
+                at_limit(counts.asns.get(&asn), self.config.max_peers_per_asn)
+            }) ||
+            peer.client.as_ref().is_some_and(|client| {
+                at_limit(counts.clients.get(client), self.config.max_peers_per_client)
+            })
+    }
+}
+
+/// The number of connected and pending peers by subnet, ASN and client.
+#[derive(Debug, Default)]
+struct DiversityCounts {
+    subnets: HashMap<IpAddr, usize>,
+    asns: HashMap<u32, usize>,
+    clients: HashMap<String, usize>,
+}
+
 /// Actions the peer manager can trigger.
 #[derive(Debug)]
 pub enum PeerAction {
@@ -1065,7 +1198,8 @@ mod tests {
     use reth_network_api::Direction;
     use reth_network_peers::{PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, ReputationChangeKind,
+        peers::reputation::DEFAULT_REPUTATION, BackoffKind, PeerDiversityConfig,
+        ReputationChangeKind,
     };
     use reth_primitives::B512;
     use url::Host;
@@ -2173,6 +2307,26 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_single_peer_capacity() {
+        let mut config = PeersConfig::test().with_max_peers_opt(Some(1));
+        config.connection_info.max_inbound = 10;
+        let mut peers = PeersManager::new(config);
+        assert!(peers.connection_info.has_out_capacity());
+
+        let peer = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8009);
+        assert!(peers.on_incoming_pending_session(addr.ip()).is_ok());
+        peers.on_incoming_session_established(peer, addr);
+
+        // the single slot is occupied by the inbound peer
+        assert!(!peers.connection_info.has_out_capacity());
+        assert_eq!(
+            peers.on_incoming_pending_session(addr.ip()).unwrap_err(),
+            InboundConnectionError::ExceedsCapacity
+        );
+    }
+
     #[tokio::test]
     async fn test_tick() {
         let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
@@ -2381,4 +2535,61 @@ mod tests {
         // no more pending outbound connections
         assert_eq!(peer_manager.connection_info.num_pending_out, 0);
     }
+
+    #[tokio::test]
+    async fn test_max_peers_per_subnet() {
+        let diversity = PeerDiversityConfig { max_peers_per_subnet: Some(2), ..Default::default() };
+        let mut peers = PeersManager::new(PeersConfig::test().with_diversity(diversity));
+
+        for i in 0..4 {
+            let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, i));
+            peers.add_peer(PeerId::random(), PeerAddr::from_tcp(SocketAddr::new(ip, 8008)), None);
+        }
+        let other_subnet = IpAddr::V4(Ipv4Addr::new(127, 0, 2, 1));
+        peers.add_peer(
+            PeerId::random(),
+            PeerAddr::from_tcp(SocketAddr::new(other_subnet, 8008)),
+            None,
+        );
+
+        peers.fill_outbound_slots();
+        let dials = peers
+            .queued_actions
+            .iter()
+            .filter_map(|action| match action {
+                PeerAction::Connect { remote_addr, .. } => Some(remote_addr.ip()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(dials.len(), 3);
+        assert!(dials.contains(&other_subnet));
+    }
+
+    #[tokio::test]
+    async fn test_max_peers_per_client() {
+        let diversity = PeerDiversityConfig { max_peers_per_client: Some(1), ..Default::default() };
+        let mut peers = PeersManager::new(PeersConfig::test().with_diversity(diversity));
+
+        let mut peer_ids = Vec::new();
+        for i in 0..2 {
+            let peer_id = PeerId::random();
+            let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, i, 1)), 8008);
+            peers.on_incoming_pending_session(addr.ip()).unwrap();
+            peers.on_incoming_session_established(peer_id, addr);
+            peer_ids.push(peer_id);
+        }
+        peers.queued_actions.clear();
+
+        peers.on_session_client_version(peer_ids[0], "Geth/v1.14.8-stable/linux-amd64/go1.22.6");
+        assert!(peers.queued_actions.is_empty());
+
+        peers.on_session_client_version(peer_ids[1], "Geth/v1.14.7-stable/linux-amd64/go1.22.5");
+        match peers.queued_actions.pop_front() {
+            Some(PeerAction::Disconnect { peer_id, reason }) => {
+                assert_eq!(peer_id, peer_ids[1]);
+                assert_eq!(reason, Some(DisconnectReason::TooManyPeers));
+            }
+            _ => unreachable!(),
+        }
+    }
 }
@@ -0,0 +1,120 @@
+//! Liveness of peers that are persisted across restarts.
+
+use std::{collections::HashMap, time::Duration};
+
+use enr::Enr;
+use reth_network_peers::{
+    persisted::{live_peers, unix_timestamp, DEFAULT_PERSISTED_PEER_MAX_AGE},
+    NodeRecord, PeerId, PersistedPeer,
+};
+use secp256k1::SecretKey;
+
+/// Keeps track of when peers were last seen alive and of their signed node records, so that the
+/// peers can be persisted across restarts, see [`PersistedPeer`].
+#[derive(Debug)]
+pub(crate) struct PeerLiveness {
+    /// Unix timestamps at which peers were last seen alive.
+    last_seen: HashMap<PeerId, u64>,
+    /// Known signed node records of peers.
+    enrs: HashMap<PeerId, Enr<SecretKey>>,
+    /// How long a peer is kept if it is not seen alive again.
+    max_age: Duration,
+}
+
+impl PeerLiveness {
+    /// Creates a new instance that continues tracking the peers persisted by a previous run.
+    pub(crate) fn new(persisted_peers: Vec<PersistedPeer>, max_age: Duration) -> Self {
+        let mut last_seen = HashMap::with_capacity(persisted_peers.len());
+        let mut enrs = HashMap::new();
+        for PersistedPeer { record, enr, last_seen: timestamp } in persisted_peers {
+            last_seen.insert(record.id, timestamp);
+            if let Some(enr) = enr {
+                enrs.insert(record.id, enr);
+            }
+        }
+        Self { last_seen, enrs, max_age }
+    }
+
+    /// Marks the peer as seen alive.
+    pub(crate) fn on_peer_seen(&mut self, peer_id: PeerId) {
+        self.last_seen.insert(peer_id, unix_timestamp());
+    }
+
+    /// Records the signed node record of the peer.
+    pub(crate) fn on_enr(&mut self, peer_id: PeerId, enr: Enr<SecretKey>) {
+        self.enrs.insert(peer_id, enr);
+    }
+
+    /// Returns the given peers with their liveness, most recently seen first.
+    ///
+    /// Connected peers and peers that were never seen alive, because they were learned of in this
+    /// run, are treated as if they were just seen. Peers that expired are dropped.
+    pub(crate) fn persisted_peers(
+        &self,
+        records: impl IntoIterator<Item = NodeRecord>,
+        is_connected: impl Fn(&PeerId) -> bool,
+    ) -> Vec<PersistedPeer> {
+        let now = unix_timestamp();
+        let peers = records.into_iter().map(|record| {
+            let last_seen = match self.last_seen.get(&record.id) {
+                Some(last_seen) if !is_connected(&record.id) => *last_seen,
+                _ => now,
+            };
+            let peer = PersistedPeer::new(record, last_seen);
+            match self.enrs.get(&record.id) {
+                Some(enr) => peer.with_enr(enr.clone()),
+                None => peer,
+            }
+        });
+        live_peers(peers, now, self.max_age)
+    }
+}
+
+impl Default for PeerLiveness {
+    fn default() -> Self {
+        Self::new(Vec::new(), DEFAULT_PERSISTED_PEER_MAX_AGE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn record() -> NodeRecord {
+        NodeRecord {
+            address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            tcp_port: 30303,
+            udp_port: 30303,
+            id: PeerId::random(),
+        }
+    }
+
+    #[test]
+    fn decays_persisted_peers() {
+        let now = unix_timestamp();
+        let max_age = Duration::from_secs(100);
+        let (seen, connected, unseen, expired, new) =
+            (record(), record(), record(), record(), record());
+
+        let mut liveness = PeerLiveness::new(
+            vec![
+                PersistedPeer::new(seen, now - 90),
+                PersistedPeer::new(connected, now - 101),
+                PersistedPeer::new(unseen, now - 50),
+                PersistedPeer::new(expired, now - 101),
+            ],
+            max_age,
+        );
+        liveness.on_peer_seen(seen.id);
+
+        let peers = liveness
+            .persisted_peers([seen, connected, unseen, expired, new], |id| *id == connected.id);
+        assert_eq!(peers.len(), 4);
+        assert!(peers[..3].iter().all(|peer| peer.last_seen >= now));
+        for record in [seen, connected, new] {
+            assert!(peers[..3].iter().any(|peer| peer.record == record));
+        }
+        assert_eq!(peers[3], PersistedPeer::new(unseen, now - 50));
+    }
+}
@@ -15,7 +15,7 @@ use std::{
 use rand::seq::SliceRandom;
 use reth_eth_wire::{BlockHashNumber, Capabilities, DisconnectReason, NewBlockHashes, Status};
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent, PeerRequest, PeerRequestSender};
-use reth_network_peers::PeerId;
+use reth_network_peers::{PeerId, PersistedPeer};
 use reth_network_types::{PeerAddr, PeerKind};
 use reth_primitives::{ForkId, B256};
 use tokio::sync::oneshot;
@@ -124,6 +124,13 @@ impl NetworkState {
         &self.peers_manager
     }
 
+    /// Returns all peers in the peer set with their liveness, see [`PersistedPeer`].
+    pub(crate) fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        self.discovery.persisted_peers(self.peers_manager.iter_peers(), |peer_id| {
+            self.active_peers.contains_key(peer_id)
+        })
+    }
+
     /// Returns a new [`FetchClient`]
     pub(crate) fn fetch_client(&self) -> FetchClient {
         self.state_fetcher.client()
@@ -148,6 +155,8 @@ impl NetworkState {
     ) {
         debug_assert!(!self.active_peers.contains_key(&peer), "Already connected; not possible");
 
+        self.discovery.on_peer_seen(peer);
+
         // find the corresponding block number
         let block_number =
             self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
@@ -169,7 +178,9 @@ impl NetworkState {
     ///
     /// This will remove the peer from the available set of peers and close all inflight requests.
     pub(crate) fn on_session_closed(&mut self, peer: PeerId) {
-        self.active_peers.remove(&peer);
+        if self.active_peers.remove(&peer).is_some() {
+            self.discovery.on_peer_seen(peer);
+        }
         self.state_fetcher.on_session_closed(&peer);
     }
 
@@ -318,6 +329,8 @@ impl NetworkState {
                 self.queued_messages
                     .push_back(StateAction::DiscoveredEnrForkId { peer_id, fork_id });
             }
+            // only of interest to discovery listeners
+            DiscoveryEvent::EnrEntries(..) => {}
         }
     }
 
@@ -548,7 +561,7 @@ mod tests {
     use reth_eth_wire::{BlockBodies, Capabilities, Capability, EthVersion};
     use reth_network_api::PeerRequestSender;
     use reth_network_p2p::{bodies::client::BodiesClient, error::RequestError};
-    use reth_network_peers::PeerId;
+    use reth_network_peers::{PeerId, PersistedPeer};
     use reth_primitives::{BlockBody, Header, B256};
     use reth_provider::test_utils::NoopProvider;
     use tokio::sync::mpsc;
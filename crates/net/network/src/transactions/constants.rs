@@ -148,6 +148,20 @@ pub mod tx_fetcher {
     pub const DEFAULT_MAX_COUNT_PENDING_FETCH: usize =
         DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH as usize / 2;
 
+    /* ================= FETCH PRIORITY ================= */
+
+    /// Default weight of a single pool import in the moving average of the share of imported
+    /// transactions of a type that were rejected as underpriced.
+    ///
+    /// Default is 0.05, so the average roughly reflects the last 20 imports of a type.
+    pub const DEFAULT_UNDERPRICED_RATE_SMOOTHING: f64 = 0.05;
+
+    /// Default share of imported transactions of a type that must have been rejected as
+    /// underpriced for announced transactions of the type to be fetched last.
+    ///
+    /// Default is 0.5, so when most transactions of a type don't pay enough to enter the pool.
+    pub const DEFAULT_UNDERPRICED_RATE_DEPRIORITIZE: f64 = 0.5;
+
     /* ====== LIMITED CAPACITY ON FETCH PENDING HASHES ====== */
 
     /// Default budget for finding an idle fallback peer for any hash pending fetch, when said
@@ -90,6 +90,9 @@ pub struct TransactionFetcher {
     pub(super) filter_valid_message: MessageFilter,
     /// Info on capacity of the transaction fetcher.
     pub info: TransactionFetcherInfo,
+    /// Order in which the hashes of [`Eth68`](reth_eth_wire::EthVersion::Eth68) announcements
+    /// are fetched.
+    pub fetch_priority: FetchPriority,
     #[doc(hidden)]
     metrics: TransactionFetcherMetrics,
 }
@@ -255,9 +258,9 @@ impl TransactionFetcher {
     ///
     /// Returns left over hashes.
     ///
-    /// Loops through hashes passed as parameter and checks if a hash fits in the expected
-    /// response. If no, it's added to surplus hashes. If yes, it's added to hashes to the request
-    /// and expected response size is accumulated.
+    /// Orders the hashes by [`FetchPriority`], then loops through them and checks if a hash fits
+    /// in the expected response. If no, it's added to surplus hashes. If yes, it's added to hashes
+    /// to the request and expected response size is accumulated.
     pub fn pack_request_eth68(
         &self,
         hashes_to_request: &mut RequestTxHashes,
@@ -267,6 +270,10 @@ impl TransactionFetcher {
         let mut acc_size_response = 0;
         let hashes_from_announcement_len = hashes_from_announcement.len();
 
+        let mut hashes_from_announcement = hashes_from_announcement.into_iter().collect::<Vec<_>>();
+        let deprioritized = self.fetch_priority.sort(&mut hashes_from_announcement);
+        self.metrics.deprioritized_announced_hashes.increment(deprioritized as u64);
+
         let mut hashes_from_announcement_iter = hashes_from_announcement.into_iter();
 
         if let Some((hash, Some((_ty, size)))) = hashes_from_announcement_iter.next() {
@@ -1062,11 +1069,62 @@ impl Default for TransactionFetcher {
             ),
             filter_valid_message: Default::default(),
             info: TransactionFetcherInfo::default(),
+            fetch_priority: FetchPriority::default(),
             metrics: Default::default(),
         }
     }
 }
 
+/// Estimates how relevant announced transactions are to the pool, to decide in which order their
+/// hashes are fetched.
+///
+/// [`Eth68`](reth_eth_wire::EthVersion::Eth68) announcements carry the type and size of a
+/// transaction, but not its fees. During fee spikes, most transactions of a type can fall below
+/// what the pool accepts, e.g. blob transactions when the blob fee surges. The share of imported
+/// transactions of each type that were rejected as underpriced is tracked as a moving average, and
+/// hashes of types that are mostly rejected are fetched after all other hashes, smallest first,
+/// so that they only take bandwidth that isn't needed for transactions that are likely included.
+#[derive(Debug, Default)]
+pub struct FetchPriority {
+    /// Moving average of the share of imported transactions rejected as underpriced, by type.
+    underpriced_rates: HashMap<u8, f64>,
+}
+
+impl FetchPriority {
+    /// Records the outcome of importing a transaction of the given type into the pool.
+    pub fn on_import(&mut self, tx_type: u8, underpriced: bool) {
+        let rate = self.underpriced_rates.entry(tx_type).or_default();
+        *rate += DEFAULT_UNDERPRICED_RATE_SMOOTHING * (f64::from(u8::from(underpriced)) - *rate);
+    }
+
+    /// Returns the moving average of the share of imported transactions of the given type that
+    /// were rejected as underpriced.
+    pub fn underpriced_rate(&self, tx_type: u8) -> f64 {
+        self.underpriced_rates.get(&tx_type).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` if transactions of the given type are fetched last.
+    pub fn is_deprioritized(&self, tx_type: u8) -> bool {
+        self.underpriced_rate(tx_type) >= DEFAULT_UNDERPRICED_RATE_DEPRIORITIZE
+    }
+
+    /// Orders announced hashes in the order they should be fetched. The relative order of hashes
+    /// that aren't deprioritized is kept.
+    ///
+    /// Returns the number of deprioritized hashes.
+    pub fn sort(&self, hashes: &mut [(TxHash, Option<(u8, usize)>)]) -> usize {
+        let mut deprioritized = 0;
+        hashes.sort_by_cached_key(|(_hash, metadata)| match metadata {
+            Some((ty, size)) if self.is_deprioritized(*ty) => {
+                deprioritized += 1;
+                (true, *size)
+            }
+            _ => (false, 0),
+        });
+        deprioritized
+    }
+}
+
 /// Metadata of a transaction hash that is yet to be fetched.
 #[derive(Debug, Constructor)]
 pub struct TxFetchMetadata {
@@ -1416,6 +1474,42 @@ mod test {
         assert_eq!(expected_surplus_hashes, surplus_eth68_hashes);
     }
 
+    #[test]
+    fn pack_eth68_request_deprioritized_type() {
+        let tx_fetcher = &mut TransactionFetcher::default();
+
+        // most recently imported blob transactions were underpriced
+        tx_fetcher.fetch_priority.on_import(3, false);
+        for _ in 0..20 {
+            tx_fetcher.fetch_priority.on_import(3, true);
+        }
+        tx_fetcher.fetch_priority.on_import(2, true);
+        assert!(tx_fetcher.fetch_priority.is_deprioritized(3));
+        assert!(!tx_fetcher.fetch_priority.is_deprioritized(2));
+
+        let blob_large = (B256::from_slice(&[1; 32]), Some((3u8, 100_000)));
+        let blob_small = (B256::from_slice(&[2; 32]), Some((3u8, 20_000)));
+        let eip1559 = (B256::from_slice(&[3; 32]), Some((2u8, 50_000)));
+        let legacy = (B256::from_slice(&[4; 32]), Some((0u8, 50_000)));
+
+        let mut announcement = vec![blob_large, blob_small, eip1559, legacy];
+        assert_eq!(tx_fetcher.fetch_priority.sort(&mut announcement), 2);
+        assert_eq!(announcement, vec![eip1559, legacy, blob_small, blob_large]);
+
+        // the blob transactions only get the space that is left
+        let mut hashes_to_request = RequestTxHashes::default();
+        let surplus_hashes = tx_fetcher.pack_request_eth68(
+            &mut hashes_to_request,
+            TestValidAnnouncementData(vec![blob_large, blob_small, eip1559, legacy]),
+        );
+
+        assert_eq!(
+            hashes_to_request.into_iter().collect::<HashSet<_>>(),
+            HashSet::from([eip1559.0, legacy.0, blob_small.0])
+        );
+        assert_eq!(surplus_hashes.into_iter().collect::<Vec<_>>(), vec![blob_large.0]);
+    }
+
     #[tokio::test]
     async fn test_on_fetch_pending_hashes() {
         reth_tracing::init_test_tracing();
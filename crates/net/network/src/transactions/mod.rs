@@ -72,8 +72,9 @@ use crate::{
 
 /// The future for importing transactions into the pool.
 ///
-/// Resolves with the result of each transaction import.
-pub type PoolImportFuture = Pin<Box<dyn Future<Output = Vec<PoolResult<TxHash>>> + Send + 'static>>;
+/// Resolves with the type and the result of each transaction import.
+pub type PoolImportFuture =
+    Pin<Box<dyn Future<Output = Vec<(u8, PoolResult<TxHash>)>> + Send + 'static>>;
 
 /// Api to interact with [`TransactionsManager`] task.
 ///
@@ -1060,6 +1061,7 @@ where
                 trace!(target: "net::tx::propagation", new_txs_len=?new_txs.len(), "Importing new transactions");
                 let import = Box::pin(async move {
                     let added = new_txs.len();
+                    let tx_types = new_txs.iter().map(|tx| tx.tx_type()).collect::<Vec<_>>();
                     let res = pool.add_external_transactions(new_txs).await;
 
                     // update metrics
@@ -1067,7 +1069,7 @@ where
                     // update self-monitoring info
                     tx_manager_info_pending_pool_imports.fetch_sub(added, Ordering::Relaxed);
 
-                    res
+                    tx_types.into_iter().zip(res).collect()
                 });
 
                 self.pool_imports.push(import);
@@ -1093,8 +1095,13 @@ where
     }
 
     /// Processes a batch import results.
-    fn on_batch_import_result(&mut self, batch_results: Vec<PoolResult<TxHash>>) {
-        for res in batch_results {
+    fn on_batch_import_result(&mut self, batch_results: Vec<(u8, PoolResult<TxHash>)>) {
+        for (tx_type, res) in batch_results {
+            // track which types of transactions don't pay enough to enter the pool, to fetch
+            // announced transactions of these types last
+            let underpriced = res.as_ref().is_err_and(|err| err.is_underpriced());
+            self.transaction_fetcher.fetch_priority.on_import(tx_type, underpriced);
+
             match res {
                 Ok(hash) => {
                     self.on_good_import(hash);
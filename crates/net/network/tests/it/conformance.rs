@@ -0,0 +1,26 @@
+//! Conformance harness tests
+
+use reth_chainspec::MAINNET;
+use reth_network::{
+    conformance::{MaliciousPeer, Misbehavior},
+    test_utils::Testnet,
+};
+use reth_network_peers::NodeRecord;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_malicious_peer_default_suite() {
+    reth_tracing::init_test_tracing();
+
+    let net = Testnet::create(1).await;
+    let peer = net.peers().first().unwrap();
+    let node = NodeRecord::new(peer.local_addr(), peer.peer_id());
+    let handle = net.spawn();
+
+    let malicious =
+        MaliciousPeer::with_chain_spec(&MAINNET).with_response_timeout(Duration::from_millis(500));
+    let report = malicious.run(node, Misbehavior::default_suite()).await;
+    assert!(report.passed(), "{:?}", report.failures().collect::<Vec<_>>());
+
+    handle.terminate().await;
+}
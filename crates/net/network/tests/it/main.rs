@@ -1,4 +1,5 @@
 mod big_pooled_txs_req;
+mod conformance;
 mod connect;
 mod multiplex;
 mod requests;
@@ -39,11 +39,14 @@
 //! - [`TrustedPeer`]: A [`NodeRecord`] with an optional domain name, which can be resolved to a
 //!   [`NodeRecord`]. Useful for adding trusted peers at startup, whose IP address may not be
 //!   static.
+//! - `PersistedPeer`: A [`NodeRecord`] with an optional [`Enr`] and the time the peer was last seen
+//!   alive. Used to persist peers across restarts.
 //!
 //!
 //! ## Feature Flags
 //!
 //! - `net`: Support for address lookups.
+//! - `secp256k1`: Support for secp256k1 keys, [`Enr`]s and persisted peers.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -68,6 +71,11 @@ pub use node_record::{NodeRecord, NodeRecordParseError};
 pub mod trusted_peer;
 pub use trusted_peer::TrustedPeer;
 
+#[cfg(feature = "secp256k1")]
+pub mod persisted;
+#[cfg(feature = "secp256k1")]
+pub use persisted::PersistedPeer;
+
 mod bootnodes;
 pub use bootnodes::*;
 
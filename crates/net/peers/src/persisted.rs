@@ -0,0 +1,171 @@
+//! Peers that are persisted across restarts.
+
+use crate::{NodeRecord, PeerId};
+use enr::Enr;
+use secp256k1::SecretKey;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Default duration after which a persisted peer that was not seen alive again is dropped.
+///
+/// Default is 5 days.
+pub const DEFAULT_PERSISTED_PEER_MAX_AGE: Duration = Duration::from_secs(5 * 24 * 60 * 60);
+
+/// A peer that is persisted across restarts.
+///
+/// The peer is dropped once it was not seen alive for longer than the configured max age, see
+/// [`PersistedPeer::is_expired`].
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedPeer {
+    /// The node record of the peer.
+    pub record: NodeRecord,
+    /// The signed node record of the peer, if it is known from discovery v5 or DNS discovery.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enr: Option<Enr<SecretKey>>,
+    /// Unix timestamp in seconds at which the peer was last seen alive.
+    pub last_seen: u64,
+}
+
+impl PersistedPeer {
+    /// Creates a new peer that was last seen alive at the given unix timestamp.
+    pub const fn new(record: NodeRecord, last_seen: u64) -> Self {
+        Self { record, enr: None, last_seen }
+    }
+
+    /// Sets the signed node record of the peer.
+    pub fn with_enr(mut self, enr: Enr<SecretKey>) -> Self {
+        self.enr = Some(enr);
+        self
+    }
+
+    /// Returns the id of the peer.
+    pub const fn id(&self) -> PeerId {
+        self.record.id
+    }
+
+    /// Returns `true` if the peer was not seen alive for longer than `max_age` at the unix
+    /// timestamp `now`.
+    pub const fn is_expired(&self, now: u64, max_age: Duration) -> bool {
+        now.saturating_sub(self.last_seen) > max_age.as_secs()
+    }
+}
+
+impl<'de> Deserialize<'de> for PersistedPeer {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[serde_as]
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            /// Peers files of older versions only contain the node records.
+            Record(NodeRecord),
+            #[serde(rename_all = "camelCase")]
+            Peer {
+                record: NodeRecord,
+                #[serde_as(as = "Option<DisplayFromStr>")]
+                #[serde(default)]
+                enr: Option<Enr<SecretKey>>,
+                last_seen: u64,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            // treat peers without liveness information as if they were just seen
+            Repr::Record(record) => Self::new(record, unix_timestamp()),
+            Repr::Peer { record, enr, last_seen } => Self { record, enr, last_seen },
+        })
+    }
+}
+
+/// Drops the peers that expired at the unix timestamp `now` and orders the remaining peers by
+/// liveness, most recently seen first.
+///
+/// If a peer is included more than once, only its most recently seen entry is kept.
+pub fn live_peers(
+    peers: impl IntoIterator<Item = PersistedPeer>,
+    now: u64,
+    max_age: Duration,
+) -> Vec<PersistedPeer> {
+    let mut live = HashMap::<PeerId, PersistedPeer>::new();
+    for peer in peers.into_iter().filter(|peer| !peer.is_expired(now, max_age)) {
+        match live.get(&peer.id()) {
+            Some(existing) if existing.last_seen >= peer.last_seen => {}
+            _ => {
+                live.insert(peer.id(), peer);
+            }
+        }
+    }
+    let mut live = live.into_values().collect::<Vec<_>>();
+    live.sort_unstable_by(|a, b| b.last_seen.cmp(&a.last_seen).then_with(|| a.id().cmp(&b.id())));
+    live
+}
+
+/// Returns the current unix timestamp in seconds.
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn record(port: u16) -> NodeRecord {
+        NodeRecord {
+            address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            tcp_port: port,
+            udp_port: port,
+            id: PeerId::random(),
+        }
+    }
+
+    #[test]
+    fn deserialize_legacy_records() {
+        let record = record(30303);
+        let peers: Vec<PersistedPeer> =
+            serde_json::from_str(&serde_json::to_string(&[record]).unwrap()).unwrap();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].record, record);
+        assert_eq!(peers[0].enr, None);
+        assert!(!peers[0].is_expired(unix_timestamp(), DEFAULT_PERSISTED_PEER_MAX_AGE));
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        let enr = Enr::builder().ip4(Ipv4Addr::LOCALHOST).udp4(30303).build(&secret_key).unwrap();
+        let peers = vec![PersistedPeer::new(record(30303), 100).with_enr(enr)];
+
+        let json = serde_json::to_string(&peers).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<PersistedPeer>>(&json).unwrap(), peers);
+    }
+
+    #[test]
+    fn drops_expired_peers() {
+        let max_age = Duration::from_secs(100);
+        let fresh = record(1);
+        let stale = record(2);
+        let duplicate = record(3);
+
+        let peers = live_peers(
+            [
+                PersistedPeer::new(duplicate, 450),
+                PersistedPeer::new(stale, 399),
+                PersistedPeer::new(fresh, 500),
+                PersistedPeer::new(duplicate, 480),
+            ],
+            500,
+            max_age,
+        );
+        assert_eq!(peers, vec![PersistedPeer::new(fresh, 500), PersistedPeer::new(duplicate, 480)]);
+    }
+}
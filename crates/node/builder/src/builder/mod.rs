@@ -11,7 +11,8 @@ use std::sync::Arc;
 
 use futures::Future;
 use reth_chainspec::ChainSpec;
-use reth_cli_util::get_secret_key;
+use reth_cli_util::get_secret_key_with_passphrase;
+use reth_consensus::ValidationPolicy;
 use reth_db_api::{
     database::Database,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
@@ -549,6 +550,12 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().dev.dev
     }
 
+    /// Returns the policy of the header checks of the consensus and payload validators, from the
+    /// `[validation]` section of the toml config.
+    pub fn validation_policy(&self) -> ValidationPolicy {
+        crate::launch::common::validation_policy(&self.reth_config().validation)
+    }
+
     /// Returns the transaction pool config of the node.
     pub fn pool_config(&self) -> PoolConfig {
         self.config().txpool.pool_config()
@@ -641,7 +648,8 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     fn network_secret(&self, data_dir: &ChainPath<DataDirPath>) -> eyre::Result<SecretKey> {
         let network_secret_path =
             self.config().network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret());
-        let secret_key = get_secret_key(&network_secret_path)?;
+        let passphrase = self.config().secrets.passphrase()?;
+        let secret_key = get_secret_key_with_passphrase(&network_secret_path, passphrase.as_ref())?;
         Ok(secret_key)
     }
 
@@ -0,0 +1,80 @@
+//! Support for warming the execution caches with pending transactions between blocks.
+
+use futures::StreamExt;
+use reth_engine_tree::warming::{CacheWarmer, SenderCache};
+use reth_evm::ConfigureEvm;
+use reth_node_core::args::CacheWarmingArgs;
+use reth_primitives::IntoRecoveredTransaction;
+use reth_provider::{CanonStateSubscriptions, EvmEnvProvider, StateProviderFactory};
+use reth_tasks::{
+    pool::{BlockingTaskPool, BlockingTaskPriority},
+    TaskExecutor,
+};
+use reth_tracing::tracing::{info, warn};
+use reth_transaction_pool::TransactionPool;
+use std::collections::HashSet;
+
+/// Spawns a task that warms the caches with the best pending transactions of the pool after every
+/// new canonical head, if cache warming is enabled.
+///
+/// The senders of the transactions are kept in the given [`SenderCache`], which should be shared
+/// with the engine. The transactions are executed on a single thread with
+/// [`BlockingTaskPriority::Low`], so that warming never delays the execution of blocks.
+pub(crate) fn spawn_cache_warmer<P, Pool, EvmConfig>(
+    args: &CacheWarmingArgs,
+    provider: P,
+    pool: Pool,
+    evm_config: EvmConfig,
+    sender_cache: SenderCache,
+    executor: &TaskExecutor,
+) where
+    P: StateProviderFactory + EvmEnvProvider + CanonStateSubscriptions + Clone + 'static,
+    Pool: TransactionPool + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    if !args.enabled {
+        return
+    }
+
+    let warming_pool = match BlockingTaskPool::build_with_priority(1, BlockingTaskPriority::Low) {
+        Ok(pool) => pool,
+        Err(err) => {
+            warn!(target: "reth::cli", %err, "Failed to build the cache warming thread pool");
+            return
+        }
+    };
+
+    info!(target: "reth::cli", max_transactions = args.max_transactions, "Warming caches with pending transactions");
+    let max_transactions = args.max_transactions;
+    let mut canon_state = provider.canonical_state_stream();
+    let warmer = CacheWarmer::new(provider, evm_config, sender_cache);
+    executor.spawn(Box::pin(async move {
+        while let Some(notification) = canon_state.next().await {
+            let head = notification.tip().header.clone();
+            // the pool maintenance task may not have removed the transactions of the new blocks
+            // from the pool yet
+            let mined = notification
+                .committed()
+                .blocks_iter()
+                .flat_map(|block| block.body.iter().map(|tx| tx.hash()))
+                .collect::<HashSet<_>>();
+            let transactions = pool
+                .best_transactions()
+                .filter(|tx| !mined.contains(tx.hash()))
+                .take(max_transactions)
+                .map(|tx| tx.to_recovered_transaction())
+                .collect::<Vec<_>>();
+            if transactions.is_empty() {
+                continue
+            }
+
+            // executing the transactions is blocking, the next head is handled once it's done
+            let warmer = warmer.clone();
+            match warming_pool.spawn(move || warmer.warm(&head, transactions)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => warn!(target: "reth::cli", %err, "Failed to warm caches"),
+                Err(_) => warn!(target: "reth::cli", "Cache warming task panicked"),
+            }
+        }
+    }));
+}
@@ -1,6 +1,6 @@
 //! Helper types that can be used by launchers.
 
-use std::{marker::PhantomData, sync::Arc, thread::available_parallelism};
+use std::{marker::PhantomData, sync::Arc};
 
 use eyre::Context;
 use rayon::ThreadPoolBuilder;
@@ -10,17 +10,26 @@ use reth_blockchain_tree::{
     BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
 };
 use reth_chainspec::{Chain, ChainSpec};
-use reth_config::{config::EtlConfig, PruneConfig};
-use reth_consensus::Consensus;
+use reth_config::{
+    config::EtlConfig, MemoryLimits, MetricRelabelAction, MetricsConfig, PruneConfig,
+    ValidationConfig,
+};
+use reth_consensus::{Consensus, ValidationPolicy};
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
-use reth_db_common::init::{init_genesis, InitDatabaseError};
+use reth_db_common::{
+    init::{init_genesis, InitDatabaseError},
+    receipts_bloom::ReceiptsBloomVerifier,
+};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_evm::noop::NoopBlockExecutorProvider;
+use reth_metrics::{memory::MemoryConsumerMetrics, metrics::Gauge, Metrics};
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::FullNodeTypes;
 use reth_node_core::{
+    args::ThreadPoolArgs,
     dirs::{ChainPath, DataDirPath},
     node_config::NodeConfig,
+    utils::cold_static_files,
     version::{
         BUILD_PROFILE_NAME, CARGO_PKG_VERSION, VERGEN_BUILD_TIMESTAMP, VERGEN_CARGO_FEATURES,
         VERGEN_CARGO_TARGET_TRIPLE, VERGEN_GIT_SHA,
@@ -28,19 +37,24 @@ use reth_node_core::{
 };
 use reth_node_metrics::{
     hooks::Hooks,
-    server::{MetricServer, MetricServerConfig},
+    relabel::{MetricRelabeling, RelabelAction, RelabelRule},
+    server::{MetricServer, MetricServerConfig, MetricsAuth},
     version::VersionInfo,
 };
 use reth_primitives::{BlockNumber, Head, B256};
 use reth_provider::{
     providers::{BlockchainProvider, BlockchainProvider2, StaticFileProvider},
-    BlockHashReader, CanonStateNotificationSender, FullProvider, ProviderFactory, ProviderResult,
-    StageCheckpointReader, StaticFileProviderFactory, TreeViewer,
+    verify::{ConsistencyChecker, DEFAULT_TIP_CHECK_DEPTH},
+    BlockHashReader, BlockNumReader, CanonStateNotificationSender, FullProvider, InMemoryRetention,
+    ProviderFactory, ProviderResult, StageCheckpointReader, StaticFileProviderFactory, TreeViewer,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
-use reth_rpc_builder::config::RethRpcServerConfig;
+use reth_rpc_builder::{config::RethRpcServerConfig, RpcAccessControl};
 use reth_rpc_layer::JwtSecret;
-use reth_stages::{sets::DefaultStages, MetricEvent, Pipeline, PipelineTarget, StageId};
+use reth_stages::{
+    sets::DefaultStages, DiskSpaceCheck, DiskUsageEstimator, MetricEvent, Pipeline, PipelineTarget,
+    StageId,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
@@ -52,6 +66,8 @@ use tokio::sync::{
 use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::OnComponentInitializedHook,
+    launch::take_clean_shutdown_marker,
+    rpc::{reload_rpc_access_keys, rpc_access_keys},
     BuilderContext, NodeAdapter,
 };
 
@@ -126,6 +142,7 @@ impl LaunchContext {
 
         // Update the config with the command line arguments
         toml_config.peers.trusted_nodes_only = config.network.trusted_only;
+        toml_config.stages.index_logs.enabled |= config.index.logs;
 
         Ok(toml_config)
     }
@@ -154,11 +171,23 @@ impl LaunchContext {
         self
     }
 
+    /// Convenience function to [`Self::configure_globals_with_thread_pools`]
+    pub fn with_configured_globals_and_thread_pools(self, thread_pools: &ThreadPoolArgs) -> Self {
+        self.configure_globals_with_thread_pools(thread_pools);
+        self
+    }
+
     /// Configure global settings this includes:
     ///
     /// - Raising the file descriptor limit
     /// - Configuring the global rayon thread pool
     pub fn configure_globals(&self) {
+        self.configure_globals_with_thread_pools(&ThreadPoolArgs::default())
+    }
+
+    /// Same as [`Self::configure_globals`], but sizes the global rayon thread pool, which computes
+    /// state roots and recovers transaction senders, according to the given [`ThreadPoolArgs`].
+    pub fn configure_globals_with_thread_pools(&self, thread_pools: &ThreadPoolArgs) {
         // Raise the fd limit of the process.
         // Does not do anything on windows.
         match fdlimit::raise_fd_limit() {
@@ -169,12 +198,10 @@ impl LaunchContext {
             Err(err) => warn!(%err, "Failed to raise file descriptor limit"),
         }
 
-        // Limit the global rayon thread pool, reserving 2 cores for the rest of the system.
-        // If the system has less than 2 cores, it will use 1 core.
-        let num_threads =
-            available_parallelism().map_or(0, |num| num.get().saturating_sub(2).max(1));
+        // Limit the global rayon thread pool, by default reserving 2 cores for the rest of the
+        // system. If the system has less than 2 cores, it will use 1 core.
         if let Err(err) = ThreadPoolBuilder::new()
-            .num_threads(num_threads)
+            .num_threads(thread_pools.engine_threads())
             .thread_name(|i| format!("reth-rayon-{i}"))
             .build_global()
         {
@@ -322,6 +349,96 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
         &mut self.left_mut().toml_config
     }
 
+    /// Returns the byte size limits of the memory consumers, allocated from the `[limits]` section
+    /// of the toml config.
+    pub fn memory_limits(&self) -> eyre::Result<MemoryLimits> {
+        Ok(self.toml_config().limits.allocate()?)
+    }
+
+    /// Returns the policy that decides how many canonical blocks are kept in memory before they
+    /// are persisted, from the `[in_memory]` section and the `memory_overlay` limit of the toml
+    /// config.
+    pub fn in_memory_retention(&self) -> eyre::Result<InMemoryRetention> {
+        let config = self.toml_config().in_memory;
+        Ok(InMemoryRetention::default()
+            .with_max_blocks(config.max_blocks)
+            .with_target_blocks(config.target_blocks)
+            .with_max_bytes(self.memory_limits()?.memory_overlay)
+            .with_max_age(config.max_age))
+    }
+
+    /// Returns the policy of the header checks of the consensus and payload validators, from the
+    /// `[validation]` section of the toml config.
+    pub fn validation_policy(&self) -> ValidationPolicy {
+        validation_policy(&self.toml_config().validation)
+    }
+
+    /// Applies the byte size limits of the `[limits]` section of the toml config to the settings
+    /// of the consumers, and records them as metrics.
+    ///
+    /// This overrides:
+    /// - the maximum size of the transaction subpools
+    /// - the maximum size of the buffered blocks of the bodies downloader
+    /// - the maximum size of RPC responses
+    ///
+    /// Arguments that were set explicitly on the command line keep their value, see
+    /// [`NodeConfig::is_explicit`], and so does a buffer size of the bodies downloader that is set
+    /// in the toml config.
+    ///
+    /// The limit of the in-memory blocks of the engine is read with [`Self::memory_limits`].
+    pub fn with_memory_limits(mut self) -> eyre::Result<Self> {
+        let limits = self.memory_limits()?;
+
+        let metrics = MemoryBudgetMetrics::default();
+        metrics.budget_bytes.set(self.toml_config().limits.memory_budget_bytes.unwrap_or(0) as f64);
+        for (consumer, limit) in limits.iter() {
+            if let Some(limit) = limit {
+                info!(target: "reth::cli", consumer, limit, "Applying memory limit");
+            }
+            MemoryConsumerMetrics::consumer(consumer).limit_bytes.set(limit.unwrap_or(0) as f64);
+        }
+
+        if let Some(limit) = limits.transaction_pool {
+            // subpools with an explicit size keep it, the rest of the limit is shared equally by
+            // the other subpools
+            let node_config = self.node_config_mut();
+            let explicit = ["pending_max_size", "basefee_max_size", "queued_max_size"]
+                .map(|id| node_config.is_explicit(id));
+            let txpool = &mut node_config.txpool;
+            let subpools = [
+                &mut txpool.pending_max_size,
+                &mut txpool.basefee_max_size,
+                &mut txpool.queued_max_size,
+            ];
+            let explicit_size: usize = subpools
+                .iter()
+                .zip(explicit)
+                .filter_map(|(max_size, explicit)| explicit.then_some(**max_size * MIB))
+                .sum();
+            let derived = explicit.iter().filter(|explicit| !**explicit).count();
+            if derived > 0 {
+                let subpool_max_size = (limit.saturating_sub(explicit_size) / derived / MIB).max(1);
+                for (max_size, explicit) in subpools.into_iter().zip(explicit) {
+                    if !explicit {
+                        *max_size = subpool_max_size;
+                    }
+                }
+            }
+        }
+        if let Some(limit) = limits.bodies_downloader {
+            let bodies = &mut self.toml_config_mut().stages.bodies;
+            bodies.downloader_max_buffered_blocks_size_bytes.get_or_insert(limit);
+        }
+        if let Some(limit) = limits.rpc_responses {
+            if !self.node_config().is_explicit("rpc_max_response_size") {
+                let max_response_size = u32::try_from(limit / MIB).unwrap_or(u32::MAX).max(1);
+                self.node_config_mut().rpc.rpc_max_response_size = max_response_size.into();
+            }
+        }
+
+        Ok(self)
+    }
+
     /// Returns the configured chain spec.
     pub fn chain_spec(&self) -> Arc<ChainSpec> {
         self.node_config().chain.clone()
@@ -359,13 +476,47 @@ impl<R> LaunchContextWith<Attached<WithConfigs, R>> {
             .timeout(PrunerBuilder::DEFAULT_TIMEOUT)
     }
 
+    /// Returns the [`DiskSpaceCheck`] of the pipeline, based on the configured [`PruneModes`].
+    pub fn disk_space_check(&self) -> DiskSpaceCheck {
+        DiskSpaceCheck::new(
+            DiskUsageEstimator::new(self.prune_modes()),
+            self.data_dir().db(),
+            self.data_dir().static_files(),
+            self.toml_config().stages.disk_space_check,
+        )
+    }
+
     /// Loads the JWT secret for the engine API
     pub fn auth_jwt_secret(&self) -> eyre::Result<JwtSecret> {
         let default_jwt_path = self.data_dir().jwt();
-        let secret = self.node_config().rpc.auth_jwt_secret(default_jwt_path)?;
+        let passphrase = self.node_config().secrets.passphrase()?;
+        let secret = self
+            .node_config()
+            .rpc
+            .auth_jwt_secret_with_passphrase(default_jwt_path, passphrase.as_ref())?;
         Ok(secret)
     }
 
+    /// Returns the access control of the http and ws RPC servers, if keys are configured in the
+    /// `rpc_auth` section of the config file.
+    ///
+    /// This spawns a task that reloads the keys whenever the config file is modified.
+    pub fn rpc_access_control(&self) -> eyre::Result<Option<RpcAccessControl>> {
+        let Some(rpc_auth) = &self.toml_config().rpc_auth else { return Ok(None) };
+        if self.node_config().rpc.rpc_jwtsecret.is_some() {
+            eyre::bail!(
+                "--rpc.jwtsecret can't be combined with the rpc_auth keys of the config file"
+            )
+        }
+
+        let access_control = RpcAccessControl::new(rpc_access_keys(rpc_auth)?);
+        let config_path =
+            self.node_config().config.clone().unwrap_or_else(|| self.data_dir().config());
+        self.task_executor()
+            .spawn(Box::pin(reload_rpc_access_keys(config_path, access_control.clone())));
+        Ok(Some(access_control))
+    }
+
     /// Returns the [`MiningMode`] intended for --dev mode.
     pub fn dev_mining_mode(&self, pending_transactions_listener: Receiver<B256>) -> MiningMode {
         if let Some(interval) = self.node_config().dev.block_time {
@@ -386,16 +537,60 @@ where
     /// between the database and static files. **It may execute a pipeline unwind if it fails this
     /// check.**
     pub async fn create_provider_factory(&self) -> eyre::Result<ProviderFactory<DB>> {
-        let factory = ProviderFactory::new(
-            self.right().clone(),
-            self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
-        )
-        .with_prune_modes(self.prune_modes())
-        .with_static_files_metrics();
+        let mut static_file_provider =
+            StaticFileProvider::read_write(self.data_dir().static_files())?;
+        if let Some(cold_storage) = &self.toml_config().cold_storage {
+            static_file_provider = static_file_provider.with_cold_storage(cold_static_files(
+                cold_storage,
+                self.data_dir(),
+                self.task_executor().clone(),
+            )?)?;
+        }
+
+        let factory =
+            ProviderFactory::new(self.right().clone(), self.chain_spec(), static_file_provider)
+                .with_prune_modes(self.prune_modes())
+                .with_static_files_metrics();
+
+        // the marker is only written once all components finished their shutdown, otherwise the
+        // last persisted blocks are checked for torn writes
+        let last_block_number = factory.last_block_number()?;
+        match take_clean_shutdown_marker(self.data_dir()) {
+            Some(block_number) if block_number == last_block_number => {
+                debug!(target: "reth::cli", block_number, "Previous shutdown was clean")
+            }
+            marker if last_block_number > 0 => {
+                // a marker of another block means the storage was modified after the shutdown
+                warn!(target: "reth::cli", clean_shutdown_block = ?marker, last_block_number, "Previous shutdown was not clean, checking storage consistency");
+                let report =
+                    ConsistencyChecker::check_persisted_tip(&factory, DEFAULT_TIP_CHECK_DEPTH)?;
+                for inconsistency in &report.inconsistencies {
+                    warn!(target: "reth::cli", %inconsistency, "Inconsistent storage");
+                }
+
+                // the blocks from the first inconsistent block on are torn writes, unless the
+                // inconsistency extends below the checked blocks
+                if let Some(block) = report.first_inconsistent_block() {
+                    let checked_from = report.persisted.as_ref().map_or(0, |range| *range.start());
+                    if block <= checked_from {
+                        eyre::bail!(
+                            "Storage is inconsistent from block {block}, which is not above the \
+                             checked blocks {checked_from}..={last_block_number}. Unwind below the \
+                             inconsistent blocks with `reth stage unwind`"
+                        )
+                    }
+                    let unwind_target = PipelineTarget::Unwind(block - 1);
+                    info!(target: "reth::cli", %unwind_target, "Unwinding the inconsistent blocks of the unclean shutdown");
+                    self.unwind_storage(&factory, unwind_target).await?;
+                }
+            }
+            _ => {}
+        }
 
+        // receipts that are pruned, e.g. by `--full` or the full node profile, are kept in the
+        // database instead of static files
         let has_receipt_pruning =
-            self.toml_config().prune.as_ref().map_or(false, |a| a.has_receipts_pruning());
+            self.prune_config().as_ref().map_or(false, |a| a.has_receipts_pruning());
 
         // Check for consistency between database and static files. If it fails, it unwinds to
         // the first block that's consistent between database and static files.
@@ -408,43 +603,49 @@ where
             assert_ne!(unwind_target, PipelineTarget::Unwind(0), "A static file <> database inconsistency was found that would trigger an unwind to block 0");
 
             info!(target: "reth::cli", unwind_target = %unwind_target, "Executing an unwind after a failed storage consistency check.");
-
-            let (_tip_tx, tip_rx) = watch::channel(B256::ZERO);
-
-            // Builds an unwind-only pipeline
-            let pipeline = Pipeline::builder()
-                .add_stages(DefaultStages::new(
-                    factory.clone(),
-                    tip_rx,
-                    Arc::new(EthBeaconConsensus::new(self.chain_spec())),
-                    NoopHeaderDownloader::default(),
-                    NoopBodiesDownloader::default(),
-                    NoopBlockExecutorProvider::default(),
-                    self.toml_config().stages.clone(),
-                    self.prune_modes(),
-                ))
-                .build(
-                    factory.clone(),
-                    StaticFileProducer::new(factory.clone(), self.prune_modes()),
-                );
-
-            // Unwinds to block
-            let (tx, rx) = oneshot::channel();
-
-            // Pipeline should be run as blocking and panic if it fails.
-            self.task_executor().spawn_critical_blocking(
-                "pipeline task",
-                Box::pin(async move {
-                    let (_, result) = pipeline.run_as_fut(Some(unwind_target)).await;
-                    let _ = tx.send(result);
-                }),
-            );
-            rx.await??;
+            self.unwind_storage(&factory, unwind_target).await?;
         }
 
         Ok(factory)
     }
 
+    /// Runs an unwind-only pipeline to the target.
+    async fn unwind_storage(
+        &self,
+        factory: &ProviderFactory<DB>,
+        unwind_target: PipelineTarget,
+    ) -> eyre::Result<()> {
+        let (_tip_tx, tip_rx) = watch::channel(B256::ZERO);
+
+        // Builds an unwind-only pipeline
+        let pipeline = Pipeline::builder()
+            .add_stages(DefaultStages::new(
+                factory.clone(),
+                tip_rx,
+                Arc::new(EthBeaconConsensus::new(self.chain_spec())),
+                NoopHeaderDownloader::default(),
+                NoopBodiesDownloader::default(),
+                NoopBlockExecutorProvider::default(),
+                self.toml_config().stages.clone(),
+                self.prune_modes(),
+            ))
+            .build(factory.clone(), StaticFileProducer::new(factory.clone(), self.prune_modes()));
+
+        // Unwinds to block
+        let (tx, rx) = oneshot::channel();
+
+        // Pipeline should be run as blocking and panic if it fails.
+        self.task_executor().spawn_critical_blocking(
+            "pipeline task",
+            Box::pin(async move {
+                let (_, result) = pipeline.run_as_fut(Some(unwind_target)).await;
+                let _ = tx.send(result);
+            }),
+        );
+        rx.await??;
+        Ok(())
+    }
+
     /// Creates a new [`ProviderFactory`] and attaches it to the launch context.
     pub async fn with_provider_factory(
         self,
@@ -504,6 +705,7 @@ where
                 self.task_executor().clone(),
                 Hooks::new(self.database().clone(), self.static_file_provider()),
             );
+            let config = configure_metric_server(config, &self.toml_config().metrics)?;
 
             MetricServer::new(config).serve().await?;
         }
@@ -522,6 +724,15 @@ where
         init_genesis(self.provider_factory().clone())
     }
 
+    /// Spawns the [`ReceiptsBloomVerifier`] if it's enabled.
+    pub fn with_receipts_bloom_verifier(self) -> Self {
+        if let Some(samples) = self.node_config().debug.receipts_bloom_samples {
+            let verifier = ReceiptsBloomVerifier::new(self.provider_factory().clone(), samples);
+            self.task_executor().spawn(Box::pin(verifier.run()));
+        }
+        self
+    }
+
     /// Creates a new `WithMeteredProvider` container and attaches it to the
     /// launch context.
     ///
@@ -942,11 +1153,77 @@ where
     consensus: Arc<dyn Consensus>,
 }
 
+/// Applies the authentication and relabeling rules of the `metrics` section of the config file to
+/// the configuration of the metrics endpoint.
+pub fn configure_metric_server(
+    config: MetricServerConfig,
+    metrics: &MetricsConfig,
+) -> eyre::Result<MetricServerConfig> {
+    let auth = match (&metrics.bearer_token, &metrics.jwt_secret) {
+        (None, None) => None,
+        (Some(token), None) if !token.is_empty() => Some(MetricsAuth::BearerToken(token.clone())),
+        (None, Some(secret)) => Some(MetricsAuth::Jwt(
+            JwtSecret::from_hex(secret).wrap_err("Invalid JWT secret of the metrics endpoint")?,
+        )),
+        _ => eyre::bail!(
+            "The metrics endpoint must have either a non-empty bearer_token or a jwt_secret"
+        ),
+    };
+
+    let rules = metrics
+        .relabel
+        .iter()
+        .map(|rule| {
+            let action = match (rule.action, &rule.label) {
+                (MetricRelabelAction::Drop, _) => RelabelAction::Drop,
+                (MetricRelabelAction::DropLabel, Some(label)) => {
+                    RelabelAction::DropLabel(label.clone())
+                }
+                (MetricRelabelAction::DropLabel, None) => {
+                    eyre::bail!("Metric relabel rule for {} must have a label to drop", rule.name)
+                }
+            };
+            RelabelRule::new(&rule.name, action)
+                .wrap_err_with(|| format!("Invalid metric name pattern {}", rule.name))
+        })
+        .collect::<eyre::Result<_>>()?;
+
+    Ok(config.with_auth(auth).with_relabeling(MetricRelabeling::new(rules)))
+}
+
+/// Number of bytes in a mebibyte, the unit of the size limits of the CLI arguments.
+const MIB: usize = 1024 * 1024;
+
+/// Metrics of the memory budget of the node.
+#[derive(Metrics)]
+#[metrics(scope = "memory_budget")]
+struct MemoryBudgetMetrics {
+    /// The configured memory budget in bytes, zero if no budget is configured
+    budget_bytes: Gauge,
+}
+
+/// Converts the `[validation]` section of the toml config into a [`ValidationPolicy`].
+pub(crate) fn validation_policy(config: &ValidationConfig) -> ValidationPolicy {
+    let mut policy =
+        if config.permissive { ValidationPolicy::permissive() } else { ValidationPolicy::strict() };
+    if let Some(max_extra_data_size) = config.max_extra_data_size {
+        policy = policy.with_max_extra_data_size(Some(max_extra_data_size));
+    }
+    if let Some(gas_limit_bounds) = config.gas_limit_bounds {
+        policy = policy.with_gas_limit_bounds(gas_limit_bounds);
+    }
+    if let Some(max_future_timestamp_drift) = config.max_future_timestamp_drift {
+        policy = policy.with_max_future_timestamp_drift(Some(max_future_timestamp_drift));
+    }
+    policy
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{LaunchContext, NodeConfig};
-    use reth_config::Config;
+    use super::{LaunchContext, NodeConfig, WithConfigs};
+    use reth_config::{BodiesConfig, Config};
     use reth_node_core::args::PruningArgs;
+    use reth_tasks::TaskManager;
 
     const EXTENSION: &str = "toml";
 
@@ -979,4 +1256,36 @@ mod tests {
             assert_eq!(reth_config, loaded_config);
         })
     }
+
+    #[tokio::test]
+    async fn test_memory_limits_keep_explicit_bodies_buffer_size() {
+        let memory_limits = |downloader_max_buffered_blocks_size_bytes| {
+            let mut toml_config = Config::default();
+            toml_config.limits.bodies_downloader.max_bytes = Some(1024);
+            toml_config.stages.bodies.downloader_max_buffered_blocks_size_bytes =
+                downloader_max_buffered_blocks_size_bytes;
+
+            let node_config = NodeConfig::test();
+            let data_dir = node_config.datadir();
+            LaunchContext::new(TaskManager::current().executor(), data_dir)
+                .with(WithConfigs { config: node_config, toml_config })
+                .attach(())
+                .with_memory_limits()
+                .unwrap()
+                .toml_config()
+                .stages
+                .bodies
+                .max_buffered_blocks_size_bytes()
+        };
+
+        // an unset buffer size is replaced by the limit
+        assert_eq!(memory_limits(None), 1024);
+
+        // a buffer size set in the toml config is kept, even if it's equal to the default
+        assert_eq!(memory_limits(Some(4096)), 4096);
+        assert_eq!(
+            memory_limits(Some(BodiesConfig::DEFAULT_MAX_BUFFERED_BLOCKS_SIZE_BYTES)),
+            BodiesConfig::DEFAULT_MAX_BUFFERED_BLOCKS_SIZE_BYTES
+        );
+    }
 }
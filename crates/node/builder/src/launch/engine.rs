@@ -10,8 +10,9 @@ use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
     tree::TreeConfig,
+    warming::SenderCache,
 };
-use reth_engine_util::EngineMessageStreamExt;
+use reth_engine_util::{external_blocks::ExternalBlockHandle, EngineMessageStreamExt};
 use reth_exex::ExExManagerHandle;
 use reth_network::{NetworkSyncUpdater, SyncState};
 use reth_network_api::{BlockDownloaderProvider, NetworkEventListenerProvider};
@@ -27,16 +28,23 @@ use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
 use reth_provider::providers::BlockchainProvider2;
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_rpc_types::engine::ClientVersionV1;
-use reth_tasks::TaskExecutor;
+use reth_tasks::{shutdown::ShutdownPhase, TaskExecutor};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::{
     components::NodeComponents,
     hooks::NodeHooks,
-    launch::{LaunchContext, LaunchNode},
+    launch::{
+        spawn_cache_warmer, spawn_reorg_journal, spawn_rpc_follower, spawn_shutdown_sequence,
+        spawn_webhook_notifier, LaunchContext, LaunchNode,
+    },
     rpc::{launch_rpc_servers, EthApiBuilderProvider},
     setup::build_networked_pipeline,
     AddOns, ExExLauncher, FullNode, NodeAdapter, NodeBuilderWithComponents, NodeComponentsBuilder,
@@ -94,7 +102,7 @@ where
 
         // setup the launch context
         let ctx = ctx
-            .with_configured_globals()
+            .with_configured_globals_and_thread_pools(&config.thread_pools)
             // load the toml config
             .with_loaded_toml_config(config)?
             // add resolved peers
@@ -109,6 +117,8 @@ where
                 info!(target: "reth::cli", "Database opened");
             })
             .with_prometheus_server().await?
+            // apply the byte size limits of the memory consumers
+            .with_memory_limits()?
             .inspect(|this| {
                 debug!(target: "reth::cli", chain=%this.chain_id(), genesis=?this.genesis_hash(), "Initializing genesis");
             })
@@ -116,13 +126,22 @@ where
             .inspect(|this| {
                 info!(target: "reth::cli", "\n{}", this.chain_spec().display_hardforks());
             })
-            .with_metrics_task()
+            .with_receipts_bloom_verifier()
+            .with_metrics_task();
+
+        let in_memory_retention = ctx.in_memory_retention()?;
+        let ctx = ctx
             // passing FullNodeTypes as type parameter here so that we can build
             // later the components.
-            .with_blockchain_db::<T, _>(move |provider_factory| {
-                Ok(BlockchainProvider2::new(provider_factory)?)
-            }, tree_config, canon_state_notification_sender)?
-            .with_components(components_builder, on_component_initialized).await?;
+            .with_blockchain_db::<T, _>(
+                move |provider_factory| {
+                    BlockchainProvider2::with_retention(provider_factory, in_memory_retention)
+                },
+                tree_config,
+                canon_state_notification_sender,
+            )?
+            .with_components(components_builder, on_component_initialized)
+            .await?;
 
         // spawn exexs
         let exex_manager_handle = ExExLauncher::new(
@@ -138,14 +157,20 @@ where
         let network_client = ctx.components().network().fetch_client().await?;
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
+        // blocks from external consensus drivers are processed like the messages of the
+        // consensus layer
+        let (external_blocks, external_block_requests) = ExternalBlockHandle::new();
+
         let node_config = ctx.node_config();
         let consensus_engine_stream = UnboundedReceiverStream::from(consensus_engine_rx)
+            .external_blocks(external_block_requests)
             .maybe_skip_fcu(node_config.debug.skip_fcu)
             .maybe_skip_new_payload(node_config.debug.skip_new_payload)
             .maybe_reorg(
                 ctx.blockchain_db().clone(),
                 ctx.components().evm_config().clone(),
-                reth_payload_validator::ExecutionPayloadValidator::new(ctx.chain_spec()),
+                reth_payload_validator::ExecutionPayloadValidator::new(ctx.chain_spec())
+                    .with_validation_policy(ctx.validation_policy()),
                 node_config.debug.reorg_frequency,
             )
             // Store messages _after_ skipping so that `replay-engine` command
@@ -179,7 +204,9 @@ where
             static_file_producer,
             ctx.components().block_executor().clone(),
             pipeline_exex_handle,
-        )?;
+            ctx.data_dir().spilled_bodies(),
+        )?
+        .with_disk_space_check(ctx.disk_space_check());
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
         pipeline.move_to_static_files()?;
@@ -196,6 +223,17 @@ where
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
+        // the senders recovered by the cache warmer are reused by the engine
+        let sender_cache = SenderCache::default();
+        spawn_cache_warmer(
+            &ctx.node_config().cache_warming,
+            ctx.blockchain_db().clone(),
+            ctx.components().pool().clone(),
+            ctx.components().evm_config().clone(),
+            sender_cache.clone(),
+            ctx.task_executor(),
+        );
+
         // Configure the consensus engine
         let mut eth_service = EngineService::new(
             ctx.consensus(),
@@ -208,8 +246,15 @@ where
             ctx.provider_factory().clone(),
             ctx.blockchain_db().clone(),
             pruner,
+            ctx.sync_metrics_tx(),
             ctx.components().payload_builder().clone(),
-            TreeConfig::default(),
+            TreeConfig::default()
+                .with_in_memory_retention(
+                    ctx.blockchain_db().canonical_in_memory_state().retention(),
+                )
+                .with_max_reorg_depth(ctx.toml_config().in_memory.max_reorg_depth)
+                .with_validation_policy(ctx.validation_policy()),
+            sender_cache,
         );
 
         let event_sender = EventSender::default();
@@ -244,6 +289,19 @@ where
             ),
         );
 
+        spawn_webhook_notifier(
+            &ctx.node_config().webhook,
+            ctx.blockchain_db().clone(),
+            beacon_engine_handle.event_listener(),
+            ctx.task_executor(),
+        )?;
+
+        spawn_reorg_journal(
+            ctx.provider_factory().clone(),
+            ctx.blockchain_db().clone(),
+            ctx.task_executor(),
+        );
+
         let client = ClientVersionV1 {
             code: CLIENT_CODE,
             name: NAME_CLIENT.to_string(),
@@ -263,6 +321,7 @@ where
 
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
+        let access_control = ctx.rpc_access_control()?;
 
         // Start RPC servers
         let (rpc_server_handles, rpc_registry) = launch_rpc_servers(
@@ -270,10 +329,29 @@ where
             engine_api,
             ctx.node_config(),
             jwt_secret,
+            access_control,
+            ctx.provider_factory().db_ref().clone(),
             rpc,
         )
         .await?;
 
+        spawn_rpc_follower::<T::Engine>(
+            &ctx.node_config().follower,
+            &ctx.node_config().debug,
+            &rpc_server_handles.auth,
+            ctx.task_executor(),
+        )?;
+
+        let sync_stopped = Arc::new(AtomicBool::new(false));
+        spawn_shutdown_sequence(
+            ctx.provider_factory().clone(),
+            rpc_server_handles.clone(),
+            sync_stopped.clone(),
+            Some(eth_service.persistence_handle().clone()),
+            ctx.data_dir().clone(),
+            ctx.task_executor(),
+        );
+
         // Run consensus engine to completion
         let initial_target = ctx.initial_backfill_target()?;
         let network_handle = ctx.components().network().clone();
@@ -288,60 +366,85 @@ where
         let chainspec = ctx.chain_spec();
         let (exit, rx) = oneshot::channel();
         info!(target: "reth::cli", "Starting consensus engine");
-        ctx.task_executor().spawn_critical("consensus engine", async move {
-            if let Some(initial_target) = initial_target {
-                debug!(target: "reth::cli", %initial_target,  "start backfill sync");
-                eth_service.orchestrator_mut().start_backfill_sync(initial_target);
-            }
-
-            let mut res = Ok(());
-
-            // advance the chain and await payloads built locally to add into the engine api tree handler to prevent re-execution if that block is received as payload from the CL
-            loop {
-                tokio::select! {
-                    payload = built_payloads.select_next_some() => {
-                        if let Some(executed_block) = payload.executed_block() {
-                            debug!(target: "reth::cli", hash=%executed_block.block().hash(),  "inserting built payload");
-                            eth_service.orchestrator_mut().handler_mut().handler_mut().on_event(EngineApiRequest::InsertExecutedBlock(executed_block).into());
-                        }
-                    }
-                    event =  eth_service.next() => {
-                        let Some(event) = event else { break };
-                        debug!(target: "reth::cli", "Event: {event:?}");
-                        match event {
-                            ChainEvent::BackfillSyncFinished => {
-                                network_handle.update_sync_state(SyncState::Idle);
-                            }
-                            ChainEvent::BackfillSyncStarted => {
-                                network_handle.update_sync_state(SyncState::Syncing);
-                            }
-                            ChainEvent::FatalError => {
-                                error!(target: "reth::cli", "Fatal error in consensus engine");
-                                res = Err(eyre::eyre!("Fatal error in consensus engine"));
+        ctx.task_executor().spawn_critical_with_shutdown_phase(
+            "consensus engine",
+            ShutdownPhase::Sync,
+            |shutdown| async move {
+                if let Some(initial_target) = initial_target {
+                    debug!(target: "reth::cli", %initial_target,  "start backfill sync");
+                    eth_service.orchestrator_mut().start_backfill_sync(initial_target);
+                }
+
+                let mut res = Ok(());
+                let mut shutdown = std::pin::pin!(shutdown);
+                // the guard of the shutdown is held until a running backfill sync finished, since
+                // the pipeline keeps writing to the storage
+                let mut shutdown_guard = None;
+                let mut backfill_active = false;
+
+                // advance the chain and await payloads built locally to add into the engine api tree handler to prevent re-execution if that block is received as payload from the CL
+                loop {
+                    tokio::select! {
+                        guard = &mut shutdown, if shutdown_guard.is_none() => {
+                            if !backfill_active {
+                                sync_stopped.store(true, Ordering::Relaxed);
+                                debug!(target: "reth::cli", "Stopped consensus engine");
                                 break
                             }
-                            ChainEvent::Handler(ev) => {
-                                if let Some(head) = ev.canonical_header() {
-                                    let head_block = Head {
-                                        number: head.number,
-                                        hash: head.hash(),
-                                        difficulty: head.difficulty,
-                                        timestamp: head.timestamp,
-                                        total_difficulty: chainspec
-                                            .final_paris_total_difficulty(head.number)
-                                            .unwrap_or_default(),
-                                    };
-                                    network_handle.update_status(head_block);
+                            debug!(target: "reth::cli", "Waiting for backfill sync to finish");
+                            shutdown_guard = Some(guard);
+                        }
+                        payload = built_payloads.select_next_some(), if shutdown_guard.is_none() => {
+                            if let Some(executed_block) = payload.executed_block() {
+                                debug!(target: "reth::cli", hash=%executed_block.block().hash(),  "inserting built payload");
+                                eth_service.orchestrator_mut().handler_mut().handler_mut().on_event(EngineApiRequest::InsertExecutedBlock(executed_block).into());
+                            }
+                        }
+                        event =  eth_service.next() => {
+                            let Some(event) = event else { break };
+                            debug!(target: "reth::cli", "Event: {event:?}");
+                            match event {
+                                ChainEvent::BackfillSyncFinished => {
+                                    network_handle.update_sync_state(SyncState::Idle);
+                                    backfill_active = false;
+                                    if shutdown_guard.is_some() {
+                                        sync_stopped.store(true, Ordering::Relaxed);
+                                        debug!(target: "reth::cli", "Stopped consensus engine after backfill sync");
+                                        break
+                                    }
+                                }
+                                ChainEvent::BackfillSyncStarted => {
+                                    network_handle.update_sync_state(SyncState::Syncing);
+                                    backfill_active = true;
+                                }
+                                ChainEvent::FatalError => {
+                                    error!(target: "reth::cli", "Fatal error in consensus engine");
+                                    res = Err(eyre::eyre!("Fatal error in consensus engine"));
+                                    break
+                                }
+                                ChainEvent::Handler(ev) => {
+                                    if let Some(head) = ev.canonical_header() {
+                                        let head_block = Head {
+                                            number: head.number,
+                                            hash: head.hash(),
+                                            difficulty: head.difficulty,
+                                            timestamp: head.timestamp,
+                                            total_difficulty: chainspec
+                                                .final_paris_total_difficulty(head.number)
+                                                .unwrap_or_default(),
+                                        };
+                                        network_handle.update_status(head_block);
+                                    }
+                                    event_sender.notify(ev);
                                 }
-                                event_sender.notify(ev);
                             }
                         }
                     }
                 }
-            }
 
-            let _ = exit.send(res);
-        });
+                let _ = exit.send(res);
+            },
+        );
 
         let full_node = FullNode {
             evm_config: ctx.components().evm_config().clone(),
@@ -355,6 +458,7 @@ where
             rpc_registry,
             config: ctx.node_config().clone(),
             data_dir: ctx.data_dir().clone(),
+            external_blocks,
         };
         // Notify on node started
         on_node_started.on_event(full_node.clone())?;
@@ -7,6 +7,7 @@ use reth_exex::{ExExContext, ExExHandle, ExExManager, ExExManagerHandle};
 use reth_node_api::FullNodeComponents;
 use reth_primitives::Head;
 use reth_provider::CanonStateSubscriptions;
+use reth_tasks::shutdown::ShutdownPhase;
 use reth_tracing::tracing::{debug, info};
 
 use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
@@ -69,14 +70,23 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 // init the exex
                 let exex = exex.launch(context).await.unwrap();
 
-                // spawn it as a crit task
-                executor.spawn_critical("exex", async move {
-                    info!(target: "reth::cli", "ExEx started");
-                    match exex.await {
-                        Ok(_) => panic!("ExEx {id} finished. ExExes should run indefinitely"),
-                        Err(err) => panic!("ExEx {id} crashed: {err}"),
-                    }
-                });
+                // spawn it as a crit task, which is stopped before the storage is shut down
+                executor.spawn_critical_with_shutdown_phase(
+                    "exex",
+                    ShutdownPhase::Sync,
+                    |shutdown| async move {
+                        info!(target: "reth::cli", "ExEx started");
+                        tokio::select! {
+                            res = exex => match res {
+                                Ok(_) => panic!("ExEx {id} finished. ExExes should run indefinitely"),
+                                Err(err) => panic!("ExEx {id} crashed: {err}"),
+                            },
+                            _guard = shutdown => {
+                                debug!(target: "reth::cli", id, "Stopped ExEx");
+                            }
+                        }
+                    },
+                );
             });
         }
 
@@ -0,0 +1,32 @@
+//! Support for following the chain of another execution client.
+
+use reth_consensus_debug_client::RpcFollower;
+use reth_node_api::EngineTypes;
+use reth_node_core::args::{DebugArgs, FollowerArgs};
+use reth_rpc_builder::auth::AuthServerHandle;
+use reth_tasks::TaskExecutor;
+use reth_tracing::tracing::info;
+
+/// Spawns the [`RpcFollower`] if a node to follow is configured.
+///
+/// The follower drives the engine through the auth server, so it replaces the consensus client
+/// and can't be combined with the debug consensus clients.
+pub(crate) fn spawn_rpc_follower<Engine: EngineTypes>(
+    args: &FollowerArgs,
+    debug: &DebugArgs,
+    auth_server: &AuthServerHandle,
+    executor: &TaskExecutor,
+) -> eyre::Result<()> {
+    let Some(ws_url) = args.ws_url.clone() else { return Ok(()) };
+    if debug.tip.is_some() || debug.etherscan.is_some() || debug.rpc_consensus_ws.is_some() {
+        eyre::bail!(
+            "--follower.ws-url can't be combined with --debug.tip, --debug.etherscan or --debug.rpc-consensus-ws"
+        )
+    }
+
+    info!(target: "reth::cli", url = %ws_url, "Following the chain of another execution client");
+    let follower = RpcFollower::new(ws_url, auth_server.clone(), args.reconnect_interval);
+    executor.spawn_critical("rpc follower", follower.run::<Engine>());
+
+    Ok(())
+}
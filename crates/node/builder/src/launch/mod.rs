@@ -1,14 +1,27 @@
 //! Abstraction for launching a node.
 
+mod cache_warming;
 pub mod common;
 mod exex;
+mod follower;
+mod reorg_journal;
+mod shutdown;
+mod webhook;
 
 pub(crate) mod engine;
 
+pub(crate) use cache_warming::spawn_cache_warmer;
 pub use common::LaunchContext;
 pub use exex::ExExLauncher;
-
-use std::{future::Future, sync::Arc};
+pub(crate) use follower::spawn_rpc_follower;
+pub(crate) use reorg_journal::spawn_reorg_journal;
+pub(crate) use shutdown::{spawn_shutdown_sequence, take_clean_shutdown_marker};
+pub(crate) use webhook::spawn_webhook_notifier;
+
+use std::{
+    future::Future,
+    sync::{atomic::AtomicBool, Arc},
+};
 
 use futures::{future::Either, stream, stream_select, StreamExt};
 use reth_beacon_consensus::{
@@ -17,7 +30,7 @@ use reth_beacon_consensus::{
 };
 use reth_blockchain_tree::{noop::NoopBlockchainTree, BlockchainTreeConfig};
 use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider};
-use reth_engine_util::EngineMessageStreamExt;
+use reth_engine_util::{external_blocks::ExternalBlockHandle, EngineMessageStreamExt};
 use reth_exex::ExExManagerHandle;
 use reth_network::{BlockDownloaderProvider, NetworkEventListenerProvider};
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns};
@@ -140,7 +153,7 @@ where
 
         // setup the launch context
         let ctx = ctx
-            .with_configured_globals()
+            .with_configured_globals_and_thread_pools(&config.thread_pools)
             // load the toml config
             .with_loaded_toml_config(config)?
             // add resolved peers
@@ -155,6 +168,8 @@ where
                 info!(target: "reth::cli", "Database opened");
             })
             .with_prometheus_server().await?
+            // apply the byte size limits of the memory consumers
+            .with_memory_limits()?
             .inspect(|this| {
                 debug!(target: "reth::cli", chain=%this.chain_id(), genesis=?this.genesis_hash(), "Initializing genesis");
             })
@@ -162,6 +177,7 @@ where
             .inspect(|this| {
                 info!(target: "reth::cli", "\n{}", this.chain_spec().display_hardforks());
             })
+            .with_receipts_bloom_verifier()
             .with_metrics_task()
             // passing FullNodeTypes as type parameter here so that we can build
             // later the components.
@@ -184,14 +200,20 @@ where
         let network_client = ctx.components().network().fetch_client().await?;
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
+        // blocks from external consensus drivers are processed like the messages of the
+        // consensus layer
+        let (external_blocks, external_block_requests) = ExternalBlockHandle::new();
+
         let node_config = ctx.node_config();
         let consensus_engine_stream = UnboundedReceiverStream::from(consensus_engine_rx)
+            .external_blocks(external_block_requests)
             .maybe_skip_fcu(node_config.debug.skip_fcu)
             .maybe_skip_new_payload(node_config.debug.skip_new_payload)
             .maybe_reorg(
                 ctx.blockchain_db().clone(),
                 ctx.components().evm_config().clone(),
-                reth_payload_validator::ExecutionPayloadValidator::new(ctx.chain_spec()),
+                reth_payload_validator::ExecutionPayloadValidator::new(ctx.chain_spec())
+                    .with_validation_policy(ctx.validation_policy()),
                 node_config.debug.reorg_frequency,
             )
             // Store messages _after_ skipping so that `replay-engine` command
@@ -225,15 +247,18 @@ where
                 ctx.dev_mining_mode(ctx.components().pool().pending_transactions_listener());
             info!(target: "reth::cli", mode=%mining_mode, "configuring dev mining mode");
 
-            let (_, client, mut task) = reth_auto_seal_consensus::AutoSealBuilder::new(
+            let mut auto_seal = reth_auto_seal_consensus::AutoSealBuilder::new(
                 ctx.chain_spec(),
                 ctx.blockchain_db().clone(),
                 ctx.components().pool().clone(),
                 consensus_engine_tx.clone(),
                 mining_mode,
                 ctx.components().block_executor().clone(),
-            )
-            .build();
+            );
+            if let Some(interval) = ctx.node_config().dev.timestamp_interval() {
+                auto_seal = auto_seal.deterministic_timestamps(interval);
+            }
+            let (_, client, mut task) = auto_seal.build();
 
             let pipeline = crate::setup::build_networked_pipeline(
                 &ctx.toml_config().stages,
@@ -247,7 +272,9 @@ where
                 static_file_producer,
                 ctx.components().block_executor().clone(),
                 pipeline_exex_handle,
-            )?;
+                ctx.data_dir().spilled_bodies(),
+            )?
+            .with_disk_space_check(ctx.disk_space_check());
 
             let pipeline_events = pipeline.events();
             task.set_pipeline_events(pipeline_events);
@@ -268,7 +295,9 @@ where
                 static_file_producer,
                 ctx.components().block_executor().clone(),
                 pipeline_exex_handle,
-            )?;
+                ctx.data_dir().spilled_bodies(),
+            )?
+            .with_disk_space_check(ctx.disk_space_check());
 
             (pipeline, Either::Right(network_client.clone()))
         };
@@ -303,6 +332,8 @@ where
             Box::pin(consensus_engine_stream),
             hooks,
         )?;
+        let beacon_consensus_engine =
+            beacon_consensus_engine.with_validation_policy(ctx.validation_policy());
         info!(target: "reth::cli", "Consensus engine initialized");
 
         let events = stream_select!(
@@ -330,6 +361,19 @@ where
             ),
         );
 
+        spawn_webhook_notifier(
+            &ctx.node_config().webhook,
+            ctx.blockchain_db().clone(),
+            beacon_engine_handle.event_listener(),
+            ctx.task_executor(),
+        )?;
+
+        spawn_reorg_journal(
+            ctx.provider_factory().clone(),
+            ctx.blockchain_db().clone(),
+            ctx.task_executor(),
+        );
+
         let client = ClientVersionV1 {
             code: CLIENT_CODE,
             name: NAME_CLIENT.to_string(),
@@ -349,6 +393,7 @@ where
 
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
+        let access_control = ctx.rpc_access_control()?;
 
         // Start RPC servers
         let (rpc_server_handles, rpc_registry) = crate::rpc::launch_rpc_servers(
@@ -356,6 +401,8 @@ where
             engine_api,
             ctx.node_config(),
             jwt_secret,
+            access_control,
+            ctx.provider_factory().db_ref().clone(),
             rpc,
         )
         .await?;
@@ -365,6 +412,25 @@ where
             rpc_registry.eth_api().with_dev_accounts();
         }
 
+        spawn_rpc_follower::<T::Engine>(
+            &ctx.node_config().follower,
+            &ctx.node_config().debug,
+            &rpc_server_handles.auth,
+            ctx.task_executor(),
+        )?;
+
+        // the legacy engine runs until the node exits, so the persisted tip is always checked after
+        // a restart
+        let sync_stopped = Arc::new(AtomicBool::new(false));
+        spawn_shutdown_sequence(
+            ctx.provider_factory().clone(),
+            rpc_server_handles.clone(),
+            sync_stopped,
+            None,
+            ctx.data_dir().clone(),
+            ctx.task_executor(),
+        );
+
         // Run consensus engine to completion
         let (tx, rx) = oneshot::channel();
         info!(target: "reth::cli", "Starting consensus engine");
@@ -427,6 +493,7 @@ where
             rpc_registry,
             config: ctx.node_config().clone(),
             data_dir: ctx.data_dir().clone(),
+            external_blocks,
         };
         // Notify on node started
         on_node_started.on_event(full_node.clone())?;
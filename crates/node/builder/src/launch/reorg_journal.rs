@@ -0,0 +1,172 @@
+//! Support for recording the reorgs of the canonical chain.
+
+use futures::StreamExt;
+use reth_db_api::database::Database;
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_primitives::{BlockNumber, ReorgRecord, TxHash};
+use reth_provider::{CanonStateSubscriptions, ProviderFactory, ProviderResult, ReorgJournalWriter};
+use reth_tasks::TaskExecutor;
+use reth_tracing::tracing::{debug, error};
+use std::{
+    collections::HashSet,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The number of blocks after a reorg in which the transactions dropped by the reorg are still
+/// looked for, to count them as reincluded.
+const REINCLUSION_WINDOW: u64 = 64;
+
+/// The maximum number of reorgs that are kept in the reorg journal, older reorgs are removed.
+const MAX_REORG_JOURNAL_LEN: u64 = 10_000;
+
+/// A recorded reorg with transactions that weren't included again yet.
+#[derive(Debug)]
+struct PendingReorg {
+    /// The sequence number of the reorg in the journal.
+    sequence: u64,
+    /// The reorg as it is stored in the journal.
+    reorg: ReorgRecord,
+    /// The dropped transactions of the reorg that weren't included again yet.
+    dropped: HashSet<TxHash>,
+    /// The last block in which the dropped transactions are looked for.
+    last_block: BlockNumber,
+}
+
+/// Spawns a task that records every reorg of the canonical chain in the reorg journal of the
+/// database, and in the reorg metrics.
+///
+/// Reorgs are taken from the canonical state notifications of the provider. The transactions
+/// dropped by a reorg that are included by one of the next [`REINCLUSION_WINDOW`] blocks are
+/// counted as reincluded, and at most [`MAX_REORG_JOURNAL_LEN`] reorgs are kept in the journal.
+pub(crate) fn spawn_reorg_journal<DB, P>(
+    provider_factory: ProviderFactory<DB>,
+    provider: P,
+    executor: &TaskExecutor,
+) where
+    DB: Database + 'static,
+    P: CanonStateSubscriptions + 'static,
+{
+    let mut notifications = provider.canonical_state_stream();
+    executor.spawn(Box::pin(async move {
+        let metrics = ReorgMetrics::default();
+        let mut pending = Vec::<PendingReorg>::new();
+        while let Some(notification) = notifications.next().await {
+            let observed_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_millis() as u64);
+
+            // look for the dropped transactions of earlier reorgs in the new blocks
+            let committed = notification.committed();
+            let mut updated = Vec::new();
+            if !committed.is_empty() {
+                let tip = committed.tip().number;
+                pending.retain_mut(|entry| {
+                    let reincluded = committed
+                        .blocks_iter()
+                        .flat_map(|block| block.body.iter())
+                        .filter(|tx| entry.dropped.remove(&tx.hash()))
+                        .count() as u64;
+                    if reincluded > 0 {
+                        entry.reorg.reincluded_transactions += reincluded;
+                        metrics.reincluded_transactions.increment(reincluded);
+                        updated.push((entry.sequence, entry.reorg));
+                    }
+                    !entry.dropped.is_empty() && tip < entry.last_block
+                });
+            }
+
+            let reorg = notification.reorg_record(observed_at);
+            if updated.is_empty() && reorg.is_none() {
+                continue
+            }
+            if let Some(reorg) = &reorg {
+                metrics.record(reorg);
+            }
+
+            // the write transaction may have to wait for the persistence of blocks
+            let provider_factory = provider_factory.clone();
+            match tokio::task::spawn_blocking(move || {
+                write_reorgs(&provider_factory, updated, reorg)
+            })
+            .await
+            {
+                Ok(Ok(Some(sequence))) => {
+                    debug!(target: "reth::cli", sequence, ?reorg, "Recorded reorg");
+                    // the reorgs removed from the journal can't be updated anymore
+                    pending.retain(|entry| entry.sequence + MAX_REORG_JOURNAL_LEN > sequence);
+
+                    let dropped = notification.dropped_transactions();
+                    if let Some(reorg) = reorg.filter(|_| !dropped.is_empty()) {
+                        pending.push(PendingReorg {
+                            sequence,
+                            reorg,
+                            dropped,
+                            last_block: notification.tip().number + REINCLUSION_WINDOW,
+                        });
+                    }
+                }
+                Ok(Ok(None)) => {}
+                Ok(Err(err)) => error!(target: "reth::cli", %err, "Failed to record reorg"),
+                Err(err) => error!(target: "reth::cli", %err, "Reorg journal task failed"),
+            }
+        }
+    }));
+}
+
+/// Replaces the updated reorgs in the reorg journal, appends the new reorg, if any, and removes
+/// the oldest reorgs beyond [`MAX_REORG_JOURNAL_LEN`].
+///
+/// Returns the sequence number of the new reorg.
+fn write_reorgs<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    updated: Vec<(u64, ReorgRecord)>,
+    reorg: Option<ReorgRecord>,
+) -> ProviderResult<Option<u64>> {
+    let provider_rw = provider_factory.provider_rw()?;
+    for (sequence, reorg) in updated {
+        provider_rw.update_reorg(sequence, reorg)?;
+    }
+    let sequence = reorg.map(|reorg| provider_rw.append_reorg(reorg)).transpose()?;
+    if sequence.is_some() {
+        provider_rw.prune_reorgs(MAX_REORG_JOURNAL_LEN)?;
+    }
+    provider_rw.commit()?;
+    Ok(sequence)
+}
+
+/// Metrics of the reorgs of the canonical chain.
+#[derive(Metrics)]
+#[metrics(scope = "blockchain.reorgs")]
+struct ReorgMetrics {
+    /// The number of observed reorgs
+    reorgs: Counter,
+    /// The number of discarded blocks of a reorg, which is the depth of the reorg
+    discarded_blocks: Histogram,
+    /// The number of blocks that a reorg made canonical
+    added_blocks: Histogram,
+    /// The number of transactions of the discarded blocks of a reorg
+    discarded_transactions: Histogram,
+    /// The number of transactions of the discarded blocks of a reorg that are not included in the
+    /// new chain
+    dropped_transactions: Histogram,
+    /// The number of dropped transactions that were included by a block after a reorg
+    reincluded_transactions: Counter,
+    /// The time between the timestamp of the earliest block above the fork block and the reorg,
+    /// in seconds
+    resolution_duration: Histogram,
+}
+
+impl ReorgMetrics {
+    /// Records the reorg.
+    fn record(&self, reorg: &ReorgRecord) {
+        self.reorgs.increment(1);
+        self.discarded_blocks.record(reorg.discarded_blocks as f64);
+        self.added_blocks.record(reorg.added_blocks as f64);
+        self.discarded_transactions.record(reorg.discarded_transactions as f64);
+        self.dropped_transactions.record(reorg.dropped_transactions() as f64);
+        self.resolution_duration.record(reorg.resolution_duration as f64 / 1000.0);
+    }
+}
@@ -0,0 +1,126 @@
+//! Coordinated shutdown of the node.
+//!
+//! The components are shut down in the order of the [`ShutdownPhase`]s: RPC servers stop accepting
+//! requests, the transaction pool journal is written, the consensus engine and the backfill sync
+//! stop, the blocks that are being persisted are written and pruned, static files are committed,
+//! and finally the data directory is marked as consistent.
+
+use crate::rpc::RethRpcServerHandles;
+use reth_db_api::database::Database;
+use reth_engine_tree::persistence::PersistenceHandle;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_primitives::BlockNumber;
+use reth_provider::{
+    providers::StaticFileWriter, BlockNumReader, ProviderFactory, StaticFileProviderFactory,
+};
+use reth_tasks::{shutdown::ShutdownPhase, TaskExecutor};
+use reth_tracing::tracing::{debug, error, warn};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use tokio::sync::oneshot;
+
+/// Spawns the tasks that shut down the components of the node in order, see [`ShutdownPhase`].
+///
+/// The persistence handle is only available for the engine, which persists blocks in the
+/// background. The consensus engine sets `sync_stopped` once it stopped in
+/// [`ShutdownPhase::Sync`] without a running backfill sync. The data directory is only marked as
+/// consistent if the sync stopped, the blocks that were being persisted are written and the static
+/// files are committed, before the deadlines of their phases.
+pub(crate) fn spawn_shutdown_sequence<DB>(
+    provider_factory: ProviderFactory<DB>,
+    rpc_server_handles: RethRpcServerHandles,
+    sync_stopped: Arc<AtomicBool>,
+    persistence: Option<PersistenceHandle>,
+    data_dir: ChainPath<DataDirPath>,
+    executor: &TaskExecutor,
+) where
+    DB: Database + 'static,
+{
+    let persisted = Arc::new(AtomicBool::new(persistence.is_none()));
+
+    executor.spawn_critical_with_shutdown_phase(
+        "rpc shutdown",
+        ShutdownPhase::Rpc,
+        |shutdown| async move {
+            let _guard = shutdown.await;
+            let RethRpcServerHandles { rpc, auth } = rpc_server_handles;
+            let _ = rpc.stop();
+            let _ = auth.stop();
+            debug!(target: "reth::cli", "Stopped RPC servers");
+        },
+    );
+
+    if let Some(persistence) = persistence {
+        let persisted = persisted.clone();
+        executor.spawn_critical_with_shutdown_phase(
+            "persistence shutdown",
+            ShutdownPhase::Persistence,
+            |shutdown| async move {
+                let _guard = shutdown.await;
+                let (tx, rx) = oneshot::channel();
+                if persistence.shutdown(tx).is_ok() && rx.await.is_ok() {
+                    debug!(target: "reth::cli", "Persisted in-flight blocks");
+                    persisted.store(true, Ordering::Relaxed);
+                }
+            },
+        );
+    }
+
+    let static_file_provider = provider_factory.static_file_provider();
+    let static_files_committed = Arc::new(AtomicBool::new(false));
+    let committed = static_files_committed.clone();
+    executor.spawn_critical_with_shutdown_phase(
+        "static files shutdown",
+        ShutdownPhase::StaticFiles,
+        |shutdown| async move {
+            let _guard = shutdown.await;
+            match tokio::task::spawn_blocking(move || static_file_provider.commit()).await {
+                Ok(Ok(())) => {
+                    debug!(target: "reth::cli", "Committed static files");
+                    committed.store(true, Ordering::Relaxed);
+                }
+                Ok(Err(err)) => error!(target: "reth::cli", %err, "Failed to commit static files"),
+                Err(err) => error!(target: "reth::cli", %err, "Static files shutdown task failed"),
+            }
+        },
+    );
+
+    executor.spawn_critical_with_shutdown_phase(
+        "database shutdown",
+        ShutdownPhase::Database,
+        |shutdown| async move {
+            let _guard = shutdown.await;
+            if !sync_stopped.load(Ordering::Relaxed) ||
+                !persisted.load(Ordering::Relaxed) ||
+                !static_files_committed.load(Ordering::Relaxed)
+            {
+                warn!(target: "reth::cli", "Storage was not shut down cleanly");
+                return
+            }
+
+            let marker = data_dir.clean_shutdown_marker();
+            let res = provider_factory.last_block_number().map_err(eyre::Report::from).and_then(
+                |block_number| Ok(reth_fs_util::write(&marker, block_number.to_string())?),
+            );
+            match res {
+                Ok(()) => debug!(target: "reth::cli", ?marker, "Marked clean shutdown"),
+                Err(err) => error!(target: "reth::cli", %err, "Failed to mark clean shutdown"),
+            }
+        },
+    );
+}
+
+/// Removes the marker of a clean shutdown from the data directory, and returns the last block
+/// number of the database at the shutdown.
+///
+/// Returns `None` if the previous run of the node did not finish its shutdown.
+pub(crate) fn take_clean_shutdown_marker(data_dir: &ChainPath<DataDirPath>) -> Option<BlockNumber> {
+    let marker = data_dir.clean_shutdown_marker();
+    let block_number = reth_fs_util::read_to_string(&marker).ok()?;
+    if let Err(err) = reth_fs_util::remove_file(&marker) {
+        warn!(target: "reth::cli", %err, "Failed to remove clean shutdown marker");
+    }
+    block_number.trim().parse().ok()
+}
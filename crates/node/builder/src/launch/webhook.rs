@@ -0,0 +1,68 @@
+//! Support for launching the canonical chain event webhooks.
+
+use futures::{future, stream, Stream, StreamExt};
+use reth_beacon_consensus::{BeaconConsensusEngineEvent, ForkchoiceStatus};
+use reth_node_core::args::WebhookArgs;
+use reth_node_webhooks::{WebhookConfig, WebhookEvent, WebhookNotifier};
+use reth_primitives::B256;
+use reth_provider::{CanonStateSubscriptions, HeaderProvider};
+use reth_tasks::TaskExecutor;
+use reth_tracing::tracing::warn;
+
+/// Spawns the [`WebhookNotifier`] if any webhooks are configured.
+///
+/// New canonical heads and reorgs are taken from the canonical state notifications of the
+/// provider, finalized blocks from the valid forkchoice updates of the consensus engine.
+pub(crate) fn spawn_webhook_notifier<P, E>(
+    args: &WebhookArgs,
+    provider: P,
+    engine_events: E,
+    executor: &TaskExecutor,
+) -> eyre::Result<()>
+where
+    P: CanonStateSubscriptions + HeaderProvider + 'static,
+    E: Stream<Item = BeaconConsensusEngineEvent> + Send + Unpin + 'static,
+{
+    if args.urls.is_empty() {
+        return Ok(())
+    }
+
+    let mut config = WebhookConfig::new(args.urls.clone()).with_max_retries(args.max_retries);
+    if let Some(path) = &args.secret {
+        config = config.with_secret(reth_fs_util::read_to_string(path)?.trim());
+    }
+
+    let canon_events = provider
+        .canonical_state_stream()
+        .flat_map(|notification| stream::iter(WebhookEvent::from_canon_state(&notification)));
+
+    let mut last_finalized = B256::ZERO;
+    let finalized_events = engine_events.filter_map(move |event| {
+        let BeaconConsensusEngineEvent::ForkchoiceUpdated(state, ForkchoiceStatus::Valid) = event
+        else {
+            return future::ready(None)
+        };
+        let hash = state.finalized_block_hash;
+        if hash.is_zero() || hash == last_finalized {
+            return future::ready(None)
+        }
+
+        let event = match provider.header(&hash) {
+            Ok(Some(header)) => {
+                last_finalized = hash;
+                Some(WebhookEvent::Finalized { number: header.number, hash })
+            }
+            Ok(None) => None,
+            Err(err) => {
+                warn!(target: "reth::cli", %err, %hash, "Failed to read finalized header");
+                None
+            }
+        };
+        future::ready(event)
+    });
+
+    let notifier = WebhookNotifier::new(config);
+    executor.spawn(notifier.run(stream::select(canon_events, finalized_events)));
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ pub use reth_node_api::{FullNodeTypes, NodeTypes};
 use std::{marker::PhantomData, sync::Arc};
 
 use reth_chainspec::ChainSpec;
+use reth_engine_util::external_blocks::ExternalBlockHandle;
 use reth_node_api::FullNodeComponents;
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -105,6 +106,8 @@ pub struct FullNode<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     pub config: NodeConfig,
     /// The data dir of the node.
     pub data_dir: ChainPath<DataDirPath>,
+    /// Handle to feed blocks from external sources into the engine.
+    pub external_blocks: ExternalBlockHandle,
 }
 
 impl<Node, AddOns> FullNode<Node, AddOns>
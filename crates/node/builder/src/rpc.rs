@@ -1,25 +1,37 @@
 //! Builder support for rpc components.
 
 use std::{
+    collections::HashSet,
     fmt,
     ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
+use eyre::Context;
 use futures::TryFutureExt;
+use reth_config::RpcAuthConfig;
+use reth_db_api::database::Database;
 use reth_node_api::{BuilderProvider, FullNodeComponents};
 use reth_node_core::{
     node_config::NodeConfig,
-    rpc::{api::EngineApiServer, eth::FullEthApiServer},
+    rpc::{
+        api::{EngineApiServer, RethDbApiServer, RethExecutionTraceApiServer},
+        eth::FullEthApiServer,
+    },
 };
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_rpc::DbApi;
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    RpcAccessControl, RpcAccessKey, RpcCredential, RpcModuleBuilder, RpcRegistryInner,
+    RpcServerHandle, TransportRpcModules,
 };
+use reth_rpc_engine_api::{PayloadPreviewApi, RethPayloadApiServer};
 use reth_rpc_layer::JwtSecret;
 use reth_tasks::TaskExecutor;
-use reth_tracing::tracing::{debug, info};
+use reth_tracing::tracing::{debug, info, warn};
 
 use crate::{EthApiBuilderCtx, RpcAddOns};
 
@@ -251,17 +263,20 @@ impl<'a, Node: FullNodeComponents, EthApi> RpcContext<'a, Node, EthApi> {
 }
 
 /// Launch the rpc servers.
-pub async fn launch_rpc_servers<Node, Engine, EthApi>(
+pub async fn launch_rpc_servers<Node, Engine, EthApi, DB>(
     node: Node,
     engine_api: Engine,
     config: &NodeConfig,
     jwt_secret: JwtSecret,
+    access_control: Option<RpcAccessControl>,
+    database: DB,
     add_ons: RpcAddOns<Node, EthApi>,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node, EthApi>)>
 where
     EthApi: EthApiBuilderProvider<Node> + FullEthApiServer,
     Node: FullNodeComponents + Clone,
     Engine: EngineApiServer<Node::Engine>,
+    DB: Database + Clone + 'static,
 {
     let auth_config = config.rpc.auth_server_config(jwt_secret)?;
     let module_config = config.rpc.transport_rpc_module_config();
@@ -276,6 +291,37 @@ where
         .with_evm_config(node.evm_config().clone())
         .build_with_auth_server(module_config, engine_api, EthApi::eth_api_builder());
 
+    // payload previews are only served to proposers on the authenticated server
+    let payload_preview =
+        PayloadPreviewApi::new(node.provider().clone(), node.payload_builder().clone());
+    auth_module.merge_auth_methods(payload_preview.into_rpc())?;
+
+    // sql queries can scan large parts of the chain, so they are only served to authenticated
+    // clients
+    if config.rpc.auth_sql {
+        #[cfg(feature = "sql")]
+        {
+            use reth_node_core::rpc::api::RethSqlApiServer;
+            let sql_config = reth_sql::SqlConfig::default()
+                .with_max_blocks(config.rpc.auth_sql_max_blocks)
+                .with_max_rows(config.rpc.auth_sql_max_rows);
+            let engine = reth_sql::SqlEngine::new(node.provider().clone(), sql_config)?;
+            auth_module.merge_auth_methods(reth_sql::SqlApi::new(engine).into_rpc())?;
+        }
+        #[cfg(not(feature = "sql"))]
+        reth_tracing::tracing::warn!(target: "reth::cli", "Ignoring --authrpc.sql, reth was built without the `sql` feature");
+    }
+
+    // raw database access is only served to authenticated clients
+    if config.rpc.auth_db {
+        let db_api = DbApi::new(database, Box::new(node.task_executor().clone()));
+        auth_module.merge_auth_methods(db_api.into_rpc())?;
+    }
+
+    // re-executing blocks with recording is expensive, so execution traces are only served to
+    // authenticated clients
+    auth_module.merge_auth_methods(registry.execution_trace_api().into_rpc())?;
+
     let mut registry = RpcRegistry { registry };
     let ctx = RpcContext {
         node: node.clone(),
@@ -290,7 +336,11 @@ where
 
     extend_rpc_modules.extend_rpc_modules(ctx)?;
 
-    let server_config = config.rpc.rpc_server_config();
+    let server_config = config
+        .rpc
+        .rpc_server_config()
+        .with_access_control(access_control)
+        .with_strict_params(config.rpc.rpc_strict);
     let cloned_modules = modules.clone();
     let launch_rpc = server_config.start(&cloned_modules).map_ok(|handle| {
         if let Some(path) = handle.ipc_endpoint() {
@@ -332,6 +382,71 @@ where
     Ok((handles, registry))
 }
 
+/// Interval at which the config file is checked for changes of the RPC access keys.
+const RPC_ACCESS_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Returns the keys of the access control of the http and ws RPC servers that are configured in
+/// the `rpc_auth` section of the config file.
+pub fn rpc_access_keys(config: &RpcAuthConfig) -> eyre::Result<Vec<RpcAccessKey>> {
+    let mut names = HashSet::new();
+    config
+        .keys
+        .iter()
+        .map(|key| {
+            if !names.insert(key.name.as_str()) {
+                eyre::bail!("RPC key {} is configured more than once", key.name)
+            }
+            let credential = match (&key.api_key, &key.jwt_secret) {
+                (Some(api_key), None) if !api_key.is_empty() => {
+                    RpcCredential::ApiKey(api_key.clone())
+                }
+                (None, Some(secret)) => RpcCredential::Jwt(
+                    JwtSecret::from_hex(secret)
+                        .wrap_err_with(|| format!("Invalid JWT secret of RPC key {}", key.name))?,
+                ),
+                _ => eyre::bail!(
+                    "RPC key {} must have either a non-empty api_key or a jwt_secret",
+                    key.name
+                ),
+            };
+            Ok(RpcAccessKey::new(&key.name, credential)
+                .with_namespaces(&key.namespaces)
+                .with_methods(&key.methods))
+        })
+        .collect()
+}
+
+/// Reloads the keys of the access control whenever the config file at the given path is modified,
+/// so that keys can be rotated without restarting the node.
+///
+/// If the keys of the modified file are invalid, the previous keys are kept.
+pub async fn reload_rpc_access_keys(path: PathBuf, access_control: RpcAccessControl) {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    let mut last_modified = modified(&path);
+    let mut interval = tokio::time::interval(RPC_ACCESS_RELOAD_INTERVAL);
+    loop {
+        interval.tick().await;
+        let current = modified(&path);
+        if current.is_none() || current == last_modified {
+            continue
+        }
+        last_modified = current;
+
+        let keys = confy::load_path::<reth_config::Config>(&path)
+            .wrap_err_with(|| format!("Could not load config file {path:?}"))
+            .and_then(|config| rpc_access_keys(&config.rpc_auth.unwrap_or_default()));
+        match keys {
+            Ok(keys) => {
+                info!(target: "reth::cli", keys = keys.len(), "Reloaded RPC access keys");
+                access_control.set_keys(keys);
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", %err, "Failed to reload RPC access keys, keeping the previous keys")
+            }
+        }
+    }
+}
+
 /// Provides builder for the core `eth` API type.
 pub trait EthApiBuilderProvider<N: FullNodeComponents>: BuilderProvider<N> {
     /// Returns the eth api builder.
@@ -1,6 +1,6 @@
 //! Helpers for setting up parts of the node.
 
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use reth_config::{config::StageConfig, PruneConfig};
 use reth_consensus::Consensus;
@@ -23,6 +23,8 @@ use reth_tracing::tracing::debug;
 use tokio::sync::watch;
 
 /// Constructs a [Pipeline] that's wired to the network
+///
+/// Downloaded block bodies that don't fit into memory are spilled to `bodies_spill_dir`.
 #[allow(clippy::too_many_arguments)]
 pub fn build_networked_pipeline<DB, Client, Executor>(
     config: &StageConfig,
@@ -36,6 +38,7 @@ pub fn build_networked_pipeline<DB, Client, Executor>(
     static_file_producer: StaticFileProducer<DB>,
     executor: Executor,
     exex_manager_handle: ExExManagerHandle,
+    bodies_spill_dir: PathBuf,
 ) -> eyre::Result<Pipeline<DB>>
 where
     DB: Database + Unpin + Clone + 'static,
@@ -48,6 +51,7 @@ where
         .into_task_with(task_executor);
 
     let body_downloader = BodiesDownloaderBuilder::new(config.bodies)
+        .with_spill_dir(bodies_spill_dir)
         .build(client, Arc::clone(&consensus), provider_factory.clone())
         .into_task_with(task_executor);
 
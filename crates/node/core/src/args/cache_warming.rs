@@ -0,0 +1,65 @@
+//! clap [Args](clap::Args) for warming the execution caches between blocks
+
+use clap::Args;
+
+/// The default number of pending transactions executed to warm the caches.
+const DEFAULT_MAX_TRANSACTIONS: usize = 256;
+
+/// Parameters for warming the execution caches with pending transactions between blocks
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Cache Warming")]
+pub struct CacheWarmingArgs {
+    /// Speculatively execute the best pending transactions of the pool on top of every new head,
+    /// to warm the caches and recover the senders of the transactions of the next block.
+    ///
+    /// The results of the execution are discarded.
+    #[arg(long = "engine.cache-warming", default_value_t = false)]
+    pub enabled: bool,
+
+    /// The maximum number of pending transactions that are executed after every new head.
+    #[arg(
+        long = "engine.cache-warming.max-transactions",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_MAX_TRANSACTIONS,
+        requires = "enabled"
+    )]
+    pub max_transactions: usize,
+}
+
+impl Default for CacheWarmingArgs {
+    fn default() -> Self {
+        Self { enabled: false, max_transactions: DEFAULT_MAX_TRANSACTIONS }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_cache_warming_args_default_sanity_check() {
+        let default_args = CacheWarmingArgs::default();
+        let args = CommandParser::<CacheWarmingArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+    }
+
+    #[test]
+    fn test_parse_cache_warming_args() {
+        let args = CommandParser::<CacheWarmingArgs>::parse_from([
+            "reth",
+            "--engine.cache-warming",
+            "--engine.cache-warming.max-transactions",
+            "64",
+        ])
+        .args;
+        assert_eq!(args, CacheWarmingArgs { enabled: true, max_transactions: 64 });
+    }
+}
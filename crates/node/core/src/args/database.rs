@@ -6,6 +6,8 @@ use clap::{
     error::ErrorKind,
     Arg, Args, Command, Error,
 };
+use reth_config::{DatabaseConfig, DatabaseSyncMode};
+use reth_db::mdbx::SyncMode;
 use reth_storage_errors::db::LogLevel;
 
 /// Parameters for database configuration
@@ -28,6 +30,24 @@ impl DatabaseArgs {
             .with_log_level(self.log_level)
             .with_exclusive(self.exclusive)
     }
+
+    /// Returns the database arguments with the environment parameters of the [`DatabaseConfig`].
+    pub fn database_args_with_config(
+        &self,
+        config: &DatabaseConfig,
+    ) -> reth_db::mdbx::DatabaseArguments {
+        let sync_mode = match config.sync_mode {
+            DatabaseSyncMode::Durable => SyncMode::Durable,
+            DatabaseSyncMode::NoMetaSync => SyncMode::NoMetaSync,
+            DatabaseSyncMode::SafeNoSync => SyncMode::SafeNoSync,
+            DatabaseSyncMode::UtterlyNoSync => SyncMode::UtterlyNoSync,
+        };
+        self.database_args()
+            .with_max_size(Some(config.max_size))
+            .with_growth_step(Some(config.growth_step))
+            .with_sync_mode(Some(sync_mode))
+            .with_read_ahead(Some(config.read_ahead))
+    }
 }
 
 /// clap value parser for [`LogLevel`].
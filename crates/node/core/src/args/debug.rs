@@ -59,6 +59,11 @@ pub struct DebugArgs {
     /// will be written to specified location.
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
+
+    /// Periodically verifies the logs blooms of the given number of randomly sampled blocks
+    /// against the logs of their stored receipts, to detect corrupted receipts or headers.
+    #[arg(long = "debug.receipts-bloom-samples", help_heading = "Debug", value_name = "COUNT")]
+    pub receipts_bloom_samples: Option<usize>,
 }
 
 #[cfg(test)]
@@ -39,6 +39,25 @@ pub struct DevArgs {
         verbatim_doc_comment
     )]
     pub block_time: Option<Duration>,
+
+    /// Derive the timestamp of each mined block from its parent instead of the current time.
+    ///
+    /// Blocks are spaced by the configured `--dev.block-time`, or one second otherwise, which
+    /// makes the produced chain reproducible across runs.
+    #[arg(
+        long = "dev.deterministic-timestamps",
+        help_heading = "Dev testnet",
+        default_value_if("test", "true", "true")
+    )]
+    pub deterministic_timestamps: bool,
+}
+
+impl DevArgs {
+    /// Returns the interval between the timestamps of mined blocks, if they are derived from the
+    /// parent block.
+    pub fn timestamp_interval(&self) -> Option<Duration> {
+        self.deterministic_timestamps.then(|| self.block_time.unwrap_or(Duration::from_secs(1)))
+    }
 }
 
 #[cfg(test)]
@@ -56,13 +75,37 @@ mod tests {
     #[test]
     fn test_parse_dev_args() {
         let args = CommandParser::<DevArgs>::parse_from(["reth"]).args;
-        assert_eq!(args, DevArgs { dev: false, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: false,
+                block_max_transactions: None,
+                block_time: None,
+                deterministic_timestamps: false
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: None,
+                block_time: None,
+                deterministic_timestamps: false
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--auto-mine"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: None,
+                block_time: None,
+                deterministic_timestamps: false
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from([
             "reth",
@@ -71,7 +114,15 @@ mod tests {
             "2",
         ])
         .args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: Some(2), block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: Some(2),
+                block_time: None,
+                deterministic_timestamps: false
+            }
+        );
 
         let args =
             CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.block-time", "1s"]).args;
@@ -80,11 +131,36 @@ mod tests {
             DevArgs {
                 dev: true,
                 block_max_transactions: None,
-                block_time: Some(std::time::Duration::from_secs(1))
+                block_time: Some(std::time::Duration::from_secs(1)),
+                deterministic_timestamps: false,
             }
         );
     }
 
+    #[test]
+    fn test_parse_deterministic_timestamps() {
+        let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev"]).args;
+        assert_eq!(args.timestamp_interval(), None);
+
+        let args = CommandParser::<DevArgs>::parse_from([
+            "reth",
+            "--dev",
+            "--dev.deterministic-timestamps",
+        ])
+        .args;
+        assert_eq!(args.timestamp_interval(), Some(Duration::from_secs(1)));
+
+        let args = CommandParser::<DevArgs>::parse_from([
+            "reth",
+            "--dev",
+            "--dev.deterministic-timestamps",
+            "--dev.block-time",
+            "12s",
+        ])
+        .args;
+        assert_eq!(args.timestamp_interval(), Some(Duration::from_secs(12)));
+    }
+
     #[test]
     fn test_parse_dev_args_conflicts() {
         let args = CommandParser::<DevArgs>::try_parse_from([
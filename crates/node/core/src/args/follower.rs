@@ -0,0 +1,71 @@
+//! clap [Args](clap::Args) for following the chain of another execution client
+
+use clap::Args;
+use humantime::parse_duration;
+use std::time::Duration;
+
+/// The default time to wait before reconnecting to the followed node.
+const DEFAULT_RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Parameters for following the canonical chain of another execution client
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Follower")]
+pub struct FollowerArgs {
+    /// `WebSocket` URL of another execution client, like reth or geth, whose canonical chain is
+    /// followed instead of running a consensus client.
+    ///
+    /// The new heads of the followed node are validated and executed locally, its safe and
+    /// finalized blocks are followed as well.
+    #[arg(long = "follower.ws-url", value_name = "WS_URL")]
+    pub ws_url: Option<String>,
+
+    /// Time to wait before reconnecting after the connection to the followed node failed.
+    #[arg(
+        long = "follower.reconnect-interval",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "5s",
+        requires = "ws_url"
+    )]
+    pub reconnect_interval: Duration,
+}
+
+impl Default for FollowerArgs {
+    fn default() -> Self {
+        Self { ws_url: None, reconnect_interval: DEFAULT_RECONNECT_INTERVAL }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_follower_args_default_sanity_check() {
+        let default_args = FollowerArgs::default();
+        let args = CommandParser::<FollowerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+    }
+
+    #[test]
+    fn test_parse_follower_args() {
+        let args = CommandParser::<FollowerArgs>::parse_from([
+            "reth",
+            "--follower.ws-url",
+            "ws://localhost:8546",
+            "--follower.reconnect-interval",
+            "30s",
+        ])
+        .args;
+        assert_eq!(args.ws_url.as_deref(), Some("ws://localhost:8546"));
+        assert_eq!(args.reconnect_interval, Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,56 @@
+//! clap [Args](clap::Args) for optional indexes
+
+use clap::Args;
+
+/// Parameters for optional indexes that are recorded during execution
+#[derive(Debug, Clone, Copy, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Index")]
+pub struct IndexArgs {
+    /// Index the internal ETH transfers of transactions, made by calls, creates and
+    /// selfdestructs, during execution.
+    ///
+    /// The transfers are served by `reth_getInternalTransfers` and can be pruned with the
+    /// `internal_transfers` prune segment. Only blocks executed while this is enabled are
+    /// indexed, requests for other blocks return an error.
+    #[arg(long = "index.internal-transfers")]
+    pub internal_transfers: bool,
+
+    /// Index the blocks that contain logs of each address and topic, to speed up `eth_getLogs`
+    /// queries over wide block ranges.
+    ///
+    /// The index is built by the `IndexLogs` stage and is kept up to date when new blocks are
+    /// persisted. Same as setting `enabled` in the `[stages.index_logs]` section of the config.
+    #[arg(long = "index.logs")]
+    pub logs: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_index_args_default_sanity_check() {
+        let default_args = IndexArgs::default();
+        let args = CommandParser::<IndexArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+    }
+
+    #[test]
+    fn test_parse_index_args() {
+        let args =
+            CommandParser::<IndexArgs>::parse_from(["reth", "--index.internal-transfers"]).args;
+        assert!(args.internal_transfers);
+        assert!(!args.logs);
+
+        let args = CommandParser::<IndexArgs>::parse_from(["reth", "--index.logs"]).args;
+        assert!(args.logs);
+    }
+}
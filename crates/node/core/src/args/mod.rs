@@ -56,6 +56,34 @@ pub use datadir_args::DatadirArgs;
 mod benchmark_args;
 pub use benchmark_args::BenchmarkArgs;
 
+/// WebhookArgs for configuring the canonical chain event webhooks
+mod webhook;
+pub use webhook::WebhookArgs;
+
+/// IndexArgs for optional indexes recorded during execution
+mod index;
+pub use index::IndexArgs;
+
+/// FollowerArgs for following the chain of another execution client
+mod follower;
+pub use follower::FollowerArgs;
+
+/// CacheWarmingArgs for warming the execution caches with pending transactions
+mod cache_warming;
+pub use cache_warming::CacheWarmingArgs;
+
+/// SecretsArgs for configuring the encryption of secrets at rest
+mod secrets;
+pub use secrets::SecretsArgs;
+
+/// ThreadPoolArgs for configuring the thread pools of the node
+mod thread_pool;
+pub use thread_pool::ThreadPoolArgs;
+
+/// NodeProfile for selecting preset defaults for common deployment types
+mod profile;
+pub use profile::NodeProfile;
+
 pub mod utils;
 
 pub mod types;
@@ -97,6 +97,12 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub max_inbound_peers: Option<usize>,
 
+    /// Allow at most one peer connection at a time, regardless of its direction.
+    ///
+    /// Intended for conformance test suites that drive the node through a single peer.
+    #[arg(long, default_value_if("test", "true", "true"))]
+    pub single_peer: bool,
+
     /// Max number of seen transactions to remember per peer.
     ///
     /// Default is 320 transaction hashes.
@@ -160,7 +166,8 @@ impl NetworkArgs {
             .peers
             .clone()
             .with_max_inbound_opt(self.max_inbound_peers)
-            .with_max_outbound_opt(self.max_outbound_peers);
+            .with_max_outbound_opt(self.max_outbound_peers)
+            .with_max_peers_opt(self.single_peer.then_some(1));
 
         // Configure transactions manager
         let transactions_manager_config = TransactionsManagerConfig {
@@ -266,6 +273,7 @@ impl Default for NetworkArgs {
             port: DEFAULT_DISCOVERY_PORT,
             max_outbound_peers: None,
             max_inbound_peers: None,
+            single_peer: false,
             soft_limit_byte_size_pooled_transactions_response:
                 SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
             soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
@@ -278,7 +286,7 @@ impl Default for NetworkArgs {
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 pub struct DiscoveryArgs {
     /// Disable the discovery service.
-    #[arg(short, long, default_value_if("dev", "true", "true"))]
+    #[arg(short, long, default_value_ifs([("dev", "true", "true"), ("test", "true", "true")]))]
     pub disable_discovery: bool,
 
     /// Disable the DNS discovery.
@@ -483,6 +491,10 @@ mod tests {
         .args;
         assert_eq!(args.max_outbound_peers, Some(75));
         assert_eq!(args.max_inbound_peers, Some(15));
+        assert!(!args.single_peer);
+
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth", "--single-peer"]).args;
+        assert!(args.single_peer);
     }
 
     #[test]
@@ -7,7 +7,8 @@ use reth_cli_util::{parse_duration_from_secs, parse_duration_from_secs_or_ms};
 use reth_primitives::constants::{
     ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION,
 };
-use std::{borrow::Cow, ffi::OsStr, time::Duration};
+use std::{borrow::Cow, ffi::OsStr, net::SocketAddr, path::PathBuf, time::Duration};
+use url::Url;
 
 /// Parameters for configuring the Payload Builder
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -36,6 +37,37 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Maximum number of build attempts per payload job.
+    ///
+    /// Limits the resources each job can use when multiple jobs are built concurrently, e.g. for
+    /// validators with distinct fee recipients. Unlimited by default.
+    #[arg(long = "builder.max-builds-per-job", value_name = "COUNT", value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    pub max_builds_per_job: Option<u64>,
+
+    /// Comma separated URLs of MEV-boost relays that locally built payloads are submitted to.
+    ///
+    /// Payloads are only submitted for slots whose proposer registered with the relays and
+    /// requested the fee recipient the payload is built for.
+    #[arg(
+        long = "builder.relays",
+        value_delimiter = ',',
+        value_name = "URL",
+        requires = "relay_secret_key"
+    )]
+    pub relays: Vec<Url>,
+
+    /// Path to a file containing the hex encoded BLS secret key that relay submissions are
+    /// signed with.
+    #[arg(long = "builder.relay-secret-key", value_name = "PATH", requires = "relays")]
+    pub relay_secret_key: Option<PathBuf>,
+
+    /// Address to serve the validator registration endpoints of the builder API on.
+    ///
+    /// The consensus layer client can use this address as its builder endpoint, so that the
+    /// registrations of its validators are forwarded to the relays.
+    #[arg(long = "builder.relay-registration-addr", value_name = "SOCKET", requires = "relays")]
+    pub relay_registration_addr: Option<SocketAddr>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -46,6 +78,10 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            max_builds_per_job: None,
+            relays: Vec::new(),
+            relay_secret_key: None,
+            relay_registration_addr: None,
         }
     }
 }
@@ -70,6 +106,10 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn max_builds_per_job(&self) -> Option<u64> {
+        self.max_builds_per_job
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -129,6 +169,46 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_args_with_max_builds_per_job() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.max-builds-per-job",
+            "4",
+        ])
+        .args;
+        assert_eq!(args.max_builds_per_job, Some(4));
+
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.max-builds-per-job",
+            "0"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_args_with_relays() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.relays",
+            "https://relay-a.example,https://relay-b.example",
+            "--builder.relay-secret-key",
+            "builder.key",
+        ])
+        .args;
+        assert_eq!(args.relays.len(), 2);
+        assert_eq!(args.relay_secret_key, Some(PathBuf::from("builder.key")));
+        assert_eq!(args.relay_registration_addr, None);
+
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.relays",
+            "https://relay-a.example"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_default_extradata() {
         let extradata = default_extradata();
@@ -0,0 +1,30 @@
+//! Preset node profiles for common deployment types
+
+use clap::ValueEnum;
+
+/// A named set of defaults for the node, selected with `--profile`.
+///
+/// A profile only changes the default values of the arguments it covers, so every argument can
+/// still be overridden individually on the command line.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum NodeProfile {
+    /// Keeps the entire history of the chain.
+    ///
+    /// Nothing is pruned, logs are indexed and historical proofs can be generated up to the
+    /// maximum proof window.
+    Archive,
+    /// Keeps only the recent state of the chain, same as `--full`.
+    ///
+    /// Senders and receipts are pruned, so they are not moved to static files.
+    Full,
+    /// Serves a large volume of RPC requests from an archive node.
+    ///
+    /// In addition to the archive defaults, the RPC state cache is four times larger and the
+    /// server accepts more connections and returns more logs per response.
+    RpcHeavy,
+    /// Runs a full node alongside a consensus client that proposes blocks.
+    ///
+    /// In addition to the full node defaults, the transaction subpools are twice as large and the
+    /// RPC server accepts fewer connections.
+    Validator,
+}
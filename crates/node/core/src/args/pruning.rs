@@ -27,6 +27,7 @@ impl PruningArgs {
             segments: PruneModes {
                 sender_recovery: Some(PruneMode::Full),
                 transaction_lookup: None,
+                transaction_lookup_allowlist: Default::default(),
                 // prune all receipts if chain doesn't have deposit contract specified in chain spec
                 receipts: chain_spec
                     .deposit_contract
@@ -43,6 +44,8 @@ impl PruningArgs {
                         .into_iter()
                         .collect(),
                 ),
+                internal_transfers: None,
+                log_index: None,
             },
         })
     }
@@ -2,7 +2,7 @@
 
 use std::{
     ffi::OsStr,
-    net::{IpAddr, Ipv4Addr},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
 };
 
@@ -12,7 +12,7 @@ use clap::{
     Arg, Args, Command,
 };
 use rand::Rng;
-use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_rpc_server_types::{constants, RethRpcModule, RpcListener, RpcModuleSelection};
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
@@ -57,6 +57,15 @@ pub struct RpcServerArgs {
     #[arg(long = "http.corsdomain")]
     pub http_corsdomain: Option<String>,
 
+    /// Additional address for the HTTP server to listen on, with its own CORS domains and API
+    /// modules: `<ADDR:PORT>[;api=<MODULES>][;cors=<DOMAINS>]`.
+    ///
+    /// The listener serves all modules of `--http.api` unless `api` is set, and then only the
+    /// selected modules that are also enabled by `--http.api`. Can be repeated, for example to
+    /// listen on both IPv4 and IPv6.
+    #[arg(long = "http.listen", value_name = "LISTENER", requires = "http")]
+    pub http_listeners: Vec<RpcListener>,
+
     /// Enable the WS-RPC server
     #[arg(long)]
     pub ws: bool,
@@ -77,6 +86,13 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Additional address for the WS server to listen on, with its own allowed origins and API
+    /// modules: `<ADDR:PORT>[;api=<MODULES>][;cors=<ORIGINS>]`.
+    ///
+    /// See `--http.listen`.
+    #[arg(long = "ws.listen", value_name = "LISTENER", requires = "ws")]
+    pub ws_listeners: Vec<RpcListener>,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
@@ -85,6 +101,14 @@ pub struct RpcServerArgs {
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Additional IPC socket/pipe to listen on, with its own API modules:
+    /// `<PATH>[;api=<MODULES>]`.
+    ///
+    /// The listener serves all modules unless `api` is set. Can be repeated, for example to
+    /// expose only a few modules to a sidecar.
+    #[arg(long = "ipc.listen", value_name = "LISTENER", conflicts_with = "ipcdisable")]
+    pub ipc_listeners: Vec<RpcListener<String>>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
     pub auth_addr: IpAddr,
@@ -93,6 +117,11 @@ pub struct RpcServerArgs {
     #[arg(long = "authrpc.port", default_value_t = constants::DEFAULT_AUTH_PORT)]
     pub auth_port: u16,
 
+    /// Additional address for the auth server to listen on, for example to listen on both IPv4
+    /// and IPv6. Can be repeated.
+    #[arg(long = "authrpc.listen", value_name = "ADDR:PORT")]
+    pub auth_listeners: Vec<SocketAddr>,
+
     /// Path to a JWT secret to use for the authenticated engine-API RPC server.
     ///
     /// This will enforce JWT authentication for all requests coming from the consensus layer.
@@ -110,6 +139,25 @@ pub struct RpcServerArgs {
     #[arg(long = "auth-ipc.path", default_value_t = constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string())]
     pub auth_ipc_path: String,
 
+    /// Serve read-only SQL queries over chain data via `reth_sql` on the auth server.
+    ///
+    /// Requires reth to be built with the `sql` feature.
+    #[arg(long = "authrpc.sql")]
+    pub auth_sql: bool,
+
+    /// Maximum number of blocks a `reth_sql` query may scan per table.
+    #[arg(long = "authrpc.sql.max-blocks", value_name = "COUNT", default_value_t = constants::DEFAULT_SQL_MAX_BLOCKS)]
+    pub auth_sql_max_blocks: u64,
+
+    /// Maximum number of rows a `reth_sql` query may return.
+    #[arg(long = "authrpc.sql.max-rows", value_name = "COUNT", default_value_t = constants::DEFAULT_SQL_MAX_ROWS)]
+    pub auth_sql_max_rows: usize,
+
+    /// Serve raw key/value access to the database tables via `reth_dbGet` and `reth_dbRange` on
+    /// the auth server.
+    #[arg(long = "authrpc.db")]
+    pub auth_db: bool,
+
     /// Hex encoded JWT secret to authenticate the regular RPC server(s), see `--http.api` and
     /// `--ws.api`.
     ///
@@ -138,6 +186,11 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-tracing-requests", alias = "rpc-max-tracing-requests", value_name = "COUNT", default_value_t = constants::default_max_tracing_requests())]
     pub rpc_max_tracing_requests: usize,
 
+    /// Number of threads of the pool executing CPU heavy RPC tasks like tracing. (0 = number of
+    /// cores)
+    #[arg(long = "rpc.tracing-threads", value_name = "COUNT", default_value_t = 0)]
+    pub rpc_tracing_threads: usize,
+
     /// Maximum number of blocks that could be scanned per filter request. (0 = entire chain)
     #[arg(long = "rpc.max-blocks-per-filter", alias = "rpc-max-blocks-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_BLOCKS_PER_FILTER))]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
@@ -170,6 +223,16 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Reject requests with named parameters or with unknown fields in transaction request and
+    /// filter objects, instead of silently ignoring them.
+    #[arg(long = "rpc.strict", default_value_if("test", "true", "true"))]
+    pub rpc_strict: bool,
+
+    /// Include the logs of the pending block in the results of log filters whose `toBlock` is
+    /// `pending`, with unset block hash and block number.
+    #[arg(long = "rpc.pending-logs")]
+    pub rpc_pending_logs: bool,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -279,24 +342,33 @@ impl Default for RpcServerArgs {
             http_port: constants::DEFAULT_HTTP_RPC_PORT,
             http_api: None,
             http_corsdomain: None,
+            http_listeners: Vec::new(),
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            ws_listeners: Vec::new(),
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
+            ipc_listeners: Vec::new(),
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
+            auth_listeners: Vec::new(),
             auth_jwtsecret: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
+            auth_sql: false,
+            auth_sql_max_blocks: constants::DEFAULT_SQL_MAX_BLOCKS,
+            auth_sql_max_rows: constants::DEFAULT_SQL_MAX_ROWS,
+            auth_db: false,
             rpc_jwtsecret: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
+            rpc_tracing_threads: 0,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
@@ -304,6 +376,8 @@ impl Default for RpcServerArgs {
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_strict: false,
+            rpc_pending_logs: false,
         }
     }
 }
@@ -389,6 +463,53 @@ mod tests {
         assert_eq!(apis, expected);
     }
 
+    #[test]
+    fn test_rpc_server_listener_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http",
+            "--http.listen",
+            "[::1]:8545;cors=*",
+            "--http.listen",
+            "0.0.0.0:8645;api=eth,net",
+            "--ipc.listen",
+            "/tmp/sidecar.ipc;api=txpool",
+            "--authrpc.listen",
+            "[::1]:8551",
+        ])
+        .args;
+
+        assert_eq!(
+            args.http_listeners,
+            vec![
+                RpcListener::new("[::1]:8545".parse().unwrap()).with_cors(Some("*".into())),
+                RpcListener::new("0.0.0.0:8645".parse().unwrap())
+                    .with_modules(Some([RethRpcModule::Eth, RethRpcModule::Net].into())),
+            ]
+        );
+        assert_eq!(
+            args.ipc_listeners,
+            vec![RpcListener::new("/tmp/sidecar.ipc".to_string())
+                .with_modules(Some([RethRpcModule::Txpool].into()))]
+        );
+        assert_eq!(args.auth_listeners, vec!["[::1]:8551".parse::<SocketAddr>().unwrap()]);
+
+        // listeners require their server
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--ws.listen",
+            "[::1]:8546"
+        ])
+        .is_err());
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--http",
+            "--http.listen",
+            "[::1]:8545;api=foo"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn rpc_server_args_default_sanity_test() {
         let default_args = RpcServerArgs::default();
@@ -1,7 +1,8 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_PROVIDER_BLOCK_CACHE_MAX_LEN, DEFAULT_PROVIDER_HEADER_CACHE_MAX_LEN,
+    DEFAULT_PROVIDER_RECEIPT_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN,
 };
 
 /// Parameters to configure RPC state cache.
@@ -35,6 +36,32 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Serve the database requests of the cache with a provider that caches recent headers,
+    /// blocks and receipts, and is invalidated on reorgs.
+    #[arg(long = "rpc-cache.provider")]
+    pub provider_cache: bool,
+
+    /// Max number of headers in the provider cache.
+    #[arg(
+        long = "rpc-cache.provider-max-headers",
+        default_value_t = DEFAULT_PROVIDER_HEADER_CACHE_MAX_LEN,
+    )]
+    pub provider_max_headers: u32,
+
+    /// Max number of blocks in the provider cache.
+    #[arg(
+        long = "rpc-cache.provider-max-blocks",
+        default_value_t = DEFAULT_PROVIDER_BLOCK_CACHE_MAX_LEN,
+    )]
+    pub provider_max_blocks: u32,
+
+    /// Max number of block receipts in the provider cache.
+    #[arg(
+        long = "rpc-cache.provider-max-receipts",
+        default_value_t = DEFAULT_PROVIDER_RECEIPT_CACHE_MAX_LEN,
+    )]
+    pub provider_max_receipts: u32,
 }
 
 impl Default for RpcStateCacheArgs {
@@ -44,6 +71,10 @@ impl Default for RpcStateCacheArgs {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            provider_cache: false,
+            provider_max_headers: DEFAULT_PROVIDER_HEADER_CACHE_MAX_LEN,
+            provider_max_blocks: DEFAULT_PROVIDER_BLOCK_CACHE_MAX_LEN,
+            provider_max_receipts: DEFAULT_PROVIDER_RECEIPT_CACHE_MAX_LEN,
         }
     }
 }
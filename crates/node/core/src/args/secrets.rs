@@ -0,0 +1,57 @@
+//! clap [Args](clap::Args) for encrypted secret storage
+
+use clap::Args;
+use reth_cli_util::secrets::{SecretFileError, SecretsPassphrase, SECRETS_PASSPHRASE_ENV};
+use std::path::PathBuf;
+
+/// Parameters for the encryption of the P2P secret key and the JWT secret at rest
+#[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
+#[command(next_help_heading = "Secrets")]
+pub struct SecretsArgs {
+    /// Path to a file containing the passphrase that the P2P secret key and the JWT secret are
+    /// encrypted with.
+    ///
+    /// Encrypted secret files are decrypted with the passphrase, and newly generated secrets are
+    /// encrypted with it. If not set, the passphrase is read from the `RETH_SECRETS_PASSPHRASE`
+    /// environment variable.
+    #[arg(long = "secrets.passphrase-file", value_name = "PATH")]
+    pub passphrase_file: Option<PathBuf>,
+}
+
+impl SecretsArgs {
+    /// Returns the passphrase of the secret files, read from the passphrase file or the
+    /// [`SECRETS_PASSPHRASE_ENV`] environment variable, if configured.
+    pub fn passphrase(&self) -> Result<Option<SecretsPassphrase>, SecretFileError> {
+        match &self.passphrase_file {
+            Some(path) => SecretsPassphrase::from_file(path).map(Some),
+            None => Ok(SecretsPassphrase::from_env()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_secrets_args() {
+        let args = CommandParser::<SecretsArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, SecretsArgs::default());
+
+        let args = CommandParser::<SecretsArgs>::parse_from([
+            "reth",
+            "--secrets.passphrase-file",
+            "passphrase.txt",
+        ])
+        .args;
+        assert_eq!(args.passphrase_file, Some(PathBuf::from("passphrase.txt")));
+    }
+}
@@ -0,0 +1,56 @@
+//! clap [Args](clap::Args) for the thread pools of the node
+
+use clap::Args;
+
+/// Parameters for the thread pools that execute blocks and compute state roots
+#[derive(Debug, Clone, Copy, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Thread Pools")]
+pub struct ThreadPoolArgs {
+    /// Number of threads of the pool that computes state roots in parallel and recovers the
+    /// senders of the transactions of executed blocks. (0 = number of cores minus 2)
+    ///
+    /// The threads of this pool are scheduled before the threads executing RPC tracing calls,
+    /// see `--rpc.tracing-threads`.
+    #[arg(long = "engine.threads", value_name = "COUNT", default_value_t = 0)]
+    pub engine_threads: usize,
+}
+
+impl ThreadPoolArgs {
+    /// Returns the number of threads of the engine thread pool, reserving 2 cores for the rest of
+    /// the system if the number of threads is not configured.
+    pub fn engine_threads(&self) -> usize {
+        if self.engine_threads > 0 {
+            return self.engine_threads
+        }
+        std::thread::available_parallelism().map_or(0, |num| num.get().saturating_sub(2).max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_thread_pool_args_default_sanity_check() {
+        let default_args = ThreadPoolArgs::default();
+        let args = CommandParser::<ThreadPoolArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+        assert!(args.engine_threads() > 0);
+    }
+
+    #[test]
+    fn test_parse_thread_pool_args() {
+        let args =
+            CommandParser::<ThreadPoolArgs>::parse_from(["reth", "--engine.threads", "4"]).args;
+        assert_eq!(args, ThreadPoolArgs { engine_threads: 4 });
+        assert_eq!(args.engine_threads(), 4);
+    }
+}
@@ -7,11 +7,12 @@ use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
+    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_FEE_HYSTERESIS,
+    DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
     TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
     TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::path::PathBuf;
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -49,6 +50,11 @@ pub struct TxPoolArgs {
     #[arg(long = "blobpool.pricebump", default_value_t = REPLACE_BLOB_PRICE_BUMP)]
     pub blob_transaction_price_bump: u128,
 
+    /// Fee hysteresis (in %) for promoting parked transactions to the pending sub-pool on a new
+    /// head, once the projected fees of the next block dropped below their fee caps.
+    #[arg(long = "txpool.fee-hysteresis", default_value_t = DEFAULT_FEE_HYSTERESIS)]
+    pub fee_hysteresis: u64,
+
     /// Max size in bytes of a single transaction allowed to enter the pool
     #[arg(long = "txpool.max-tx-input-bytes", alias = "txpool.max_tx_input_bytes", default_value_t = DEFAULT_MAX_TX_INPUT_BYTES)]
     pub max_tx_input_bytes: usize,
@@ -77,6 +83,20 @@ pub struct TxPoolArgs {
     /// Maximum number of new transactions to buffer
     #[arg(long = "txpool.max-new-txns", alias = "txpool.max_new_txns", default_value_t = NEW_TX_LISTENER_BUFFER_SIZE)]
     pub new_tx_listener_buffer_size: usize,
+
+    /// Records all transaction announcements and submissions to the given file, with their
+    /// timing, so they can be replayed with `--txpool.replay`.
+    #[arg(long = "txpool.record", value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replays a recording of `--txpool.record` into the transaction pool at the original pacing.
+    #[arg(long = "txpool.replay", value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    /// Persists all transactions of the pool on shutdown, not only the local ones, and restores
+    /// them on startup after validating them against the new head.
+    #[arg(long = "txpool.persist-all")]
+    pub persist_all: bool,
 }
 
 impl Default for TxPoolArgs {
@@ -91,6 +111,7 @@ impl Default for TxPoolArgs {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bump: DEFAULT_PRICE_BUMP,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
+            fee_hysteresis: DEFAULT_FEE_HYSTERESIS,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
             no_locals: false,
@@ -99,6 +120,9 @@ impl Default for TxPoolArgs {
             additional_validation_tasks: DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
+            record: None,
+            replay: None,
+            persist_all: false,
         }
     }
 }
@@ -133,6 +157,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
             },
+            fee_hysteresis: self.fee_hysteresis,
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
         }
@@ -157,4 +182,21 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_parse_record_replay() {
+        let args =
+            CommandParser::<TxPoolArgs>::parse_from(["reth", "--txpool.record", "ingress.rlp"])
+                .args;
+        assert_eq!(args.record, Some(PathBuf::from("ingress.rlp")));
+
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.record",
+            "a.rlp",
+            "--txpool.replay",
+            "b.rlp"
+        ])
+        .is_err());
+    }
 }
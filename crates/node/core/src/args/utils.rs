@@ -63,7 +63,7 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
             // both serialized Genesis and ChainSpec structs supported
             let genesis: Genesis = serde_json::from_str(&raw)?;
 
-            Arc::new(genesis.into())
+            Arc::new(ChainSpec::try_from_genesis(genesis)?)
         }
     })
 }
@@ -0,0 +1,72 @@
+//! clap [Args](clap::Args) for webhook configuration
+
+use clap::Args;
+use std::path::PathBuf;
+use url::Url;
+
+/// Parameters for the canonical chain event webhooks
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Webhooks")]
+pub struct WebhookArgs {
+    /// Comma separated URLs that canonical head, finalized block and reorg events are posted to.
+    #[arg(long = "webhook.urls", value_name = "URL", value_delimiter = ',')]
+    pub urls: Vec<Url>,
+
+    /// Path to a file containing the secret that the webhook payloads are signed with.
+    ///
+    /// If set, the HMAC-SHA256 signature of every payload is sent in the `X-Reth-Signature`
+    /// header.
+    #[arg(long = "webhook.secret", value_name = "PATH", requires = "urls")]
+    pub secret: Option<PathBuf>,
+
+    /// The number of times a failed delivery is retried, with exponential backoff.
+    #[arg(long = "webhook.max-retries", value_name = "COUNT", default_value_t = 5)]
+    pub max_retries: usize,
+}
+
+impl Default for WebhookArgs {
+    fn default() -> Self {
+        Self { urls: Vec::new(), secret: None, max_retries: 5 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_webhook_args_default_sanity_check() {
+        let default_args = WebhookArgs::default();
+        let args = CommandParser::<WebhookArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+    }
+
+    #[test]
+    fn test_parse_webhook_args() {
+        let args = CommandParser::<WebhookArgs>::parse_from([
+            "reth",
+            "--webhook.urls",
+            "http://localhost:8080/a,http://localhost:8080/b",
+            "--webhook.secret",
+            "secret.txt",
+        ])
+        .args;
+        assert_eq!(args.urls.len(), 2);
+        assert_eq!(args.secret, Some(PathBuf::from("secret.txt")));
+
+        assert!(CommandParser::<WebhookArgs>::try_parse_from([
+            "reth",
+            "--webhook.secret",
+            "secret.txt"
+        ])
+        .is_err());
+    }
+}
@@ -29,6 +29,11 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// Maximum number of build attempts per payload job, unlimited if `None`.
+    fn max_builds_per_job(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// A trait that represents the configured network and can be used to apply additional configuration
@@ -289,6 +289,14 @@ impl<D> ChainPath<D> {
         self.data_dir().join("db")
     }
 
+    /// Returns the path to the directory of the block bodies that the bodies downloader spills
+    /// to disk.
+    ///
+    /// `<DIR>/<CHAIN_ID>/bodies_spill`
+    pub fn spilled_bodies(&self) -> PathBuf {
+        self.data_dir().join("bodies_spill")
+    }
+
     /// Returns the path to the static files directory for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/static_files`
@@ -330,6 +338,20 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the snapshot of the entire transaction pool
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-snapshot.rlp`
+    pub fn txpool_snapshot(&self) -> PathBuf {
+        self.data_dir().join("txpool-snapshot.rlp")
+    }
+
+    /// Returns the path to the marker file that is written when the node shut down cleanly.
+    ///
+    /// `<DIR>/<CHAIN_ID>/clean-shutdown`
+    pub fn clean_shutdown_marker(&self) -> PathBuf {
+        self.data_dir().join("clean-shutdown")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -13,6 +13,7 @@ pub mod cli;
 pub mod dirs;
 pub mod exit;
 pub mod node_config;
+pub mod object_store;
 pub mod utils;
 pub mod version;
 /// Re-exported from `reth_primitives`.
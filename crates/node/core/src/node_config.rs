@@ -2,8 +2,9 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        CacheWarmingArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, FollowerArgs, IndexArgs,
+        NetworkArgs, NodeProfile, PayloadBuilderArgs, PruningArgs, RpcServerArgs, SecretsArgs,
+        ThreadPoolArgs, TxPoolArgs, WebhookArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -16,10 +17,20 @@ use reth_network_p2p::headers::client::HeadersClient;
 use reth_primitives::{
     revm_primitives::EnvKzgSettings, BlockHashOrNumber, BlockNumber, Head, SealedHeader, B256,
 };
-use reth_provider::{BlockHashReader, HeaderProvider, ProviderFactory, StageCheckpointReader};
+use reth_provider::{
+    BlockHashReader, HeaderProvider, ProviderFactory, StageCheckpointReader,
+    StaticFileProviderFactory,
+};
+use reth_rpc_server_types::constants::{
+    cache::{
+        DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_ENV_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    },
+    MAX_ETH_PROOF_WINDOW,
+};
 use reth_stages_types::StageId;
 use reth_storage_errors::provider::ProviderResult;
-use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+use reth_transaction_pool::{TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT};
+use std::{collections::HashSet, net::SocketAddr, path::PathBuf, sync::Arc};
 use tracing::*;
 
 /// This includes all necessary configuration to launch the node.
@@ -124,6 +135,31 @@ pub struct NodeConfig {
 
     /// All pruning related arguments
     pub pruning: PruningArgs,
+
+    /// All webhook related arguments with --webhook prefix
+    pub webhook: WebhookArgs,
+
+    /// All index related arguments with --index prefix
+    pub index: IndexArgs,
+
+    /// All follower related arguments with --follower prefix
+    pub follower: FollowerArgs,
+
+    /// All cache warming related arguments with --engine.cache-warming prefix
+    pub cache_warming: CacheWarmingArgs,
+
+    /// All secrets related arguments with --secrets prefix
+    pub secrets: SecretsArgs,
+
+    /// All thread pool related arguments
+    pub thread_pools: ThreadPoolArgs,
+
+    /// The ids of the arguments that were set explicitly, on the command line or in the
+    /// environment.
+    ///
+    /// Values the node derives itself, e.g. from a profile or the memory budget, don't override
+    /// these arguments, see [`Self::is_explicit`].
+    pub explicit_args: HashSet<String>,
 }
 
 impl NodeConfig {
@@ -134,6 +170,18 @@ impl NodeConfig {
             .with_unused_ports()
     }
 
+    /// Sets the ids of the arguments that were set explicitly, see [`Self::is_explicit`].
+    pub fn with_explicit_args(mut self, ids: impl IntoIterator<Item = String>) -> Self {
+        self.explicit_args = ids.into_iter().collect();
+        self
+    }
+
+    /// Returns true if the argument with the given id, the name of its field, was set explicitly
+    /// on the command line or in the environment.
+    pub fn is_explicit(&self, id: &str) -> bool {
+        self.explicit_args.contains(id)
+    }
+
     /// Sets --dev mode for the node.
     ///
     /// In addition to setting the `--dev` flag, this also:
@@ -144,6 +192,111 @@ impl NodeConfig {
         self
     }
 
+    /// Applies the --test profile for conformance test suites like hive.
+    ///
+    /// This:
+    ///   - derives the timestamps of blocks mined in dev mode from their parent.
+    ///   - disables discovery in [`NetworkArgs`].
+    ///   - limits the node to a single peer connection.
+    ///   - enables strict validation of RPC request parameters.
+    pub const fn test_profile(mut self) -> Self {
+        self.dev.deterministic_timestamps = true;
+        self.network.discovery.disable_discovery = true;
+        self.network.single_peer = true;
+        self.rpc.rpc_strict = true;
+        self
+    }
+
+    /// Applies the defaults of the given [`NodeProfile`], same as `--profile`.
+    ///
+    /// Arguments that were set explicitly keep their value, so they take precedence over the
+    /// profile, see [`Self::is_explicit`].
+    pub fn with_profile(mut self, profile: NodeProfile) -> Self {
+        let explicit = &self.explicit_args;
+        match profile {
+            NodeProfile::Archive => {
+                set_unless_explicit(explicit, "logs", &mut self.index.logs, true);
+                set_unless_explicit(
+                    explicit,
+                    "rpc_eth_proof_window",
+                    &mut self.rpc.rpc_eth_proof_window,
+                    MAX_ETH_PROOF_WINDOW,
+                );
+            }
+            NodeProfile::Full => {
+                // receipts and senders are pruned, so they are kept in the database instead of
+                // being moved to static files, see `PruneConfig::has_receipts_pruning`
+                set_unless_explicit(explicit, "full", &mut self.pruning.full, true);
+            }
+            NodeProfile::RpcHeavy => {
+                self = self.with_profile(NodeProfile::Archive);
+                let explicit = &self.explicit_args;
+                let cache = &mut self.rpc.rpc_state_cache;
+                set_unless_explicit(
+                    explicit,
+                    "max_blocks",
+                    &mut cache.max_blocks,
+                    4 * DEFAULT_BLOCK_CACHE_MAX_LEN,
+                );
+                set_unless_explicit(
+                    explicit,
+                    "max_receipts",
+                    &mut cache.max_receipts,
+                    4 * DEFAULT_RECEIPT_CACHE_MAX_LEN,
+                );
+                set_unless_explicit(
+                    explicit,
+                    "max_envs",
+                    &mut cache.max_envs,
+                    4 * DEFAULT_ENV_CACHE_MAX_LEN,
+                );
+                set_unless_explicit(
+                    explicit,
+                    "rpc_max_connections",
+                    &mut self.rpc.rpc_max_connections,
+                    5000.into(),
+                );
+                set_unless_explicit(
+                    explicit,
+                    "rpc_max_logs_per_response",
+                    &mut self.rpc.rpc_max_logs_per_response,
+                    100_000.into(),
+                );
+            }
+            NodeProfile::Validator => {
+                self = self.with_profile(NodeProfile::Full);
+                let explicit = &self.explicit_args;
+                let txpool = &mut self.txpool;
+                for (id, value) in [
+                    ("pending_max_count", &mut txpool.pending_max_count),
+                    ("basefee_max_count", &mut txpool.basefee_max_count),
+                    ("queued_max_count", &mut txpool.queued_max_count),
+                ] {
+                    set_unless_explicit(explicit, id, value, 2 * TXPOOL_SUBPOOL_MAX_TXS_DEFAULT);
+                }
+                for (id, value) in [
+                    ("pending_max_size", &mut txpool.pending_max_size),
+                    ("basefee_max_size", &mut txpool.basefee_max_size),
+                    ("queued_max_size", &mut txpool.queued_max_size),
+                ] {
+                    set_unless_explicit(
+                        explicit,
+                        id,
+                        value,
+                        2 * TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+                    );
+                }
+                set_unless_explicit(
+                    explicit,
+                    "rpc_max_connections",
+                    &mut self.rpc.rpc_max_connections,
+                    100.into(),
+                );
+            }
+        }
+        self
+    }
+
     /// Sets --dev mode for the node [`NodeConfig::dev`], if `dev` is true.
     pub const fn set_dev(self, dev: bool) -> Self {
         if dev {
@@ -231,6 +384,42 @@ impl NodeConfig {
         self
     }
 
+    /// Set the webhook args for the node
+    pub fn with_webhook(mut self, webhook: WebhookArgs) -> Self {
+        self.webhook = webhook;
+        self
+    }
+
+    /// Set the index args for the node
+    pub const fn with_index(mut self, index: IndexArgs) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Set the follower args for the node
+    pub fn with_follower(mut self, follower: FollowerArgs) -> Self {
+        self.follower = follower;
+        self
+    }
+
+    /// Set the cache warming args for the node
+    pub const fn with_cache_warming(mut self, cache_warming: CacheWarmingArgs) -> Self {
+        self.cache_warming = cache_warming;
+        self
+    }
+
+    /// Set the secrets args for the node
+    pub fn with_secrets(mut self, secrets: SecretsArgs) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    /// Set the thread pool args for the node
+    pub const fn with_thread_pools(mut self, thread_pools: ThreadPoolArgs) -> Self {
+        self.thread_pools = thread_pools;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig> {
         self.pruning.prune_config(&self.chain)
@@ -275,13 +464,21 @@ impl NodeConfig {
             .header_by_number(head)?
             .expect("the header for the latest block is missing, database is corrupt");
 
-        let total_difficulty = provider
-            .header_td_by_number(head)?
-            .expect("the total difficulty for the latest block is missing, database is corrupt");
-
-        let hash = provider
-            .block_hash(head)?
-            .expect("the hash for the latest block is missing, database is corrupt");
+        // on a modern datadir the head is in the static files, where its hash and total
+        // difficulty are read together
+        let hash_and_td =
+            factory.static_file_provider().header_hashes_and_tds_iter(head..=head).next();
+        let (hash, total_difficulty) = match hash_and_td.transpose()? {
+            Some(hash_and_td) => hash_and_td,
+            None => (
+                provider
+                    .block_hash(head)?
+                    .expect("the hash for the latest block is missing, database is corrupt"),
+                provider.header_td_by_number(head)?.expect(
+                    "the total difficulty for the latest block is missing, database is corrupt",
+                ),
+            ),
+        };
 
         Ok(Head {
             number: head,
@@ -367,6 +564,14 @@ impl NodeConfig {
     }
 }
 
+/// Sets `value` to the value of a profile, unless the argument with the given id was set
+/// explicitly.
+fn set_unless_explicit<T>(explicit: &HashSet<String>, id: &str, value: &mut T, profile_value: T) {
+    if !explicit.contains(id) {
+        *value = profile_value;
+    }
+}
+
 impl Default for NodeConfig {
     fn default() -> Self {
         Self {
@@ -382,7 +587,70 @@ impl Default for NodeConfig {
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
             pruning: PruningArgs::default(),
+            webhook: WebhookArgs::default(),
+            index: IndexArgs::default(),
+            follower: FollowerArgs::default(),
+            cache_warming: CacheWarmingArgs::default(),
+            secrets: SecretsArgs::default(),
+            thread_pools: ThreadPoolArgs::default(),
             datadir: DatadirArgs::default(),
+            explicit_args: HashSet::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::tables;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::{StaticFileSegment, U256};
+    use reth_provider::{test_utils::create_test_provider_factory, StageCheckpointWriter};
+    use reth_stages_types::StageCheckpoint;
+    use reth_testing_utils::generators::{self, random_header_range};
+
+    #[test]
+    fn lookup_head_from_static_files() {
+        let factory = create_test_provider_factory();
+        let headers = random_header_range(&mut generators::rng(), 0..5, B256::ZERO);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let mut writer =
+            provider_rw.static_file_provider().latest_writer(StaticFileSegment::Headers).unwrap();
+        let mut td = U256::ZERO;
+        for header in &headers {
+            td += header.difficulty;
+            writer.append_header(header.header(), td, &header.hash()).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(4)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let head = NodeConfig::default().lookup_head(factory).unwrap();
+        assert_eq!(head.number, 4);
+        assert_eq!(head.hash, headers[4].hash());
+        assert_eq!(head.total_difficulty, td);
+        assert_eq!(head.timestamp, headers[4].timestamp);
+    }
+
+    #[test]
+    fn lookup_head_from_database() {
+        let factory = create_test_provider_factory();
+        let header = random_header_range(&mut generators::rng(), 0..1, B256::ZERO).remove(0);
+        let td = U256::from(7);
+
+        // a legacy datadir, whose headers are only in the database
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::CanonicalHeaders>(0, header.hash()).unwrap();
+        tx.put::<tables::Headers>(0, header.header().clone()).unwrap();
+        tx.put::<tables::HeaderTerminalDifficulties>(0, td.into()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let head = NodeConfig::default().lookup_head(factory).unwrap();
+        assert_eq!(head.number, 0);
+        assert_eq!(head.hash, header.hash());
+        assert_eq!(head.total_difficulty, td);
+    }
+}
@@ -1,16 +1,26 @@
 //! Utility functions for node startup and shutdown, for example path parsing and retrieving single
 //! blocks from the network.
 
+use crate::{
+    dirs::{ChainPath, DataDirPath},
+    object_store::{ObjectStoreAuth, RemoteObjectStore},
+};
 use eyre::Result;
 use reth_chainspec::ChainSpec;
+use reth_cli_util::secrets::{
+    read_secret_file, write_secret_file, SecretFileError, SecretsPassphrase,
+};
+use reth_config::ColdStorageConfig;
 use reth_consensus_common::validation::validate_block_pre_execution;
 use reth_network_p2p::{
     bodies::client::BodiesClient,
     headers::client::{HeadersClient, HeadersDirection, HeadersRequest},
     priority::Priority,
 };
-use reth_primitives::{BlockHashOrNumber, SealedBlock, SealedHeader};
+use reth_primitives::{hex, BlockHashOrNumber, SealedBlock, SealedHeader};
+use reth_provider::providers::{ColdStaticFiles, DEFAULT_MAX_COLD_CACHE_SIZE};
 use reth_rpc_types::engine::{JwtError, JwtSecret};
+use reth_tasks::TaskExecutor;
 use std::{
     env::VarError,
     path::{Path, PathBuf},
@@ -35,6 +45,87 @@ pub fn get_or_create_jwt_secret_from_path(path: &Path) -> Result<JwtSecret, JwtE
     }
 }
 
+/// Errors of loading the JWT secret.
+#[derive(Debug, thiserror::Error)]
+pub enum JwtSecretError {
+    /// The JWT secret is invalid.
+    #[error(transparent)]
+    Jwt(#[from] JwtError),
+    /// Error reading or writing the, possibly encrypted, JWT secret file.
+    #[error(transparent)]
+    SecretFile(#[from] SecretFileError),
+}
+
+/// Reads the JWT secret from the file, decrypting it with the passphrase if it's encrypted.
+pub fn read_jwt_secret(
+    path: &Path,
+    passphrase: Option<&SecretsPassphrase>,
+) -> Result<JwtSecret, JwtSecretError> {
+    let hex = read_secret_file(path, passphrase)?;
+    Ok(JwtSecret::from_hex(hex)?)
+}
+
+/// Creates a random JWT secret and stores it at the path, encrypted with the passphrase if
+/// provided.
+pub fn create_jwt_secret(
+    path: &Path,
+    passphrase: Option<&SecretsPassphrase>,
+) -> Result<JwtSecret, JwtSecretError> {
+    let hex = hex::encode(rand::random::<[u8; 32]>());
+    let secret = JwtSecret::from_hex(&hex)?;
+    write_secret_file(path, &hex, passphrase)?;
+    Ok(secret)
+}
+
+/// Like [`get_or_create_jwt_secret_from_path`], but an encrypted secret file is decrypted with the
+/// passphrase, and a newly created secret is encrypted with it.
+///
+/// Note that the consensus client needs the plaintext secret.
+pub fn get_or_create_jwt_secret_from_path_with_passphrase(
+    path: &Path,
+    passphrase: Option<&SecretsPassphrase>,
+) -> Result<JwtSecret, JwtSecretError> {
+    if path.exists() {
+        debug!(target: "reth::cli", ?path, "Reading JWT auth secret file");
+        read_jwt_secret(path, passphrase)
+    } else {
+        info!(target: "reth::cli", ?path, encrypted = passphrase.is_some(), "Creating JWT auth secret file");
+        create_jwt_secret(path, passphrase)
+    }
+}
+
+/// Creates the [`ColdStaticFiles`] for the cold storage configuration.
+///
+/// The cache directory defaults to `<DIR>/<CHAIN_ID>/static_files_cache`. The requests to the
+/// object store are spawned on the given executor.
+pub fn cold_static_files(
+    config: &ColdStorageConfig,
+    data_dir: &ChainPath<DataDirPath>,
+    executor: TaskExecutor,
+) -> Result<ColdStaticFiles> {
+    let cache_dir = config
+        .cache_dir
+        .clone()
+        .unwrap_or_else(|| ColdStorageConfig::cache_dir_from_datadir(data_dir.data_dir()));
+    let auth = if let Some(s3) = &config.s3 {
+        let (access_key_id, secret_access_key) = s3.credentials()?;
+        ObjectStoreAuth::S3 {
+            region: s3.region.clone(),
+            access_key_id,
+            secret_access_key,
+            session_token: s3.session_token(),
+        }
+    } else if let Some(token) = config.auth_token()? {
+        ObjectStoreAuth::Bearer(token)
+    } else {
+        ObjectStoreAuth::None
+    };
+    let store = RemoteObjectStore::new(config.url.parse()?, auth, executor)?;
+    let max_cache_size = config.max_cache_size.unwrap_or(DEFAULT_MAX_COLD_CACHE_SIZE);
+    info!(target: "reth::cli", url = %config.url, max_block = config.max_block, ?cache_dir, max_cache_size, "Using cold storage for static files");
+    Ok(ColdStaticFiles::new(store, cache_dir, config.max_block).with_max_cache_size(max_cache_size))
+}
+
 /// Get a single header from network
 pub async fn get_single_header<Client>(
     client: Client,
@@ -10,6 +10,7 @@
 /// The metrics hooks for prometheus.
 pub mod hooks;
 pub mod recorder;
+pub mod relabel;
 /// The metric server serving the metrics.
 pub mod server;
 pub mod version;
@@ -0,0 +1,293 @@
+//! Relabeling of the metrics served by the [`MetricServer`](crate::server::MetricServer).
+//!
+//! The rules are applied to the rendered metrics, so that they don't depend on when the global
+//! recorder is installed, and reduce the cardinality of what's scraped, e.g. of the metrics that
+//! are labeled per peer.
+
+use regex::Regex;
+use std::collections::HashMap;
+
+/// What a [`RelabelRule`] does with the metrics it matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelabelAction {
+    /// The metrics are not served.
+    Drop,
+    /// The label is removed from the metrics.
+    ///
+    /// Samples that only differed in the label are aggregated: quantiles keep the maximum value,
+    /// all other samples are summed.
+    DropLabel(String),
+}
+
+/// A rule that rewrites the metrics whose name matches a regular expression.
+#[derive(Debug, Clone)]
+pub struct RelabelRule {
+    /// Matches the whole name of a metric.
+    name: Regex,
+    /// What the rule does with the matching metrics.
+    action: RelabelAction,
+}
+
+impl RelabelRule {
+    /// Creates a rule that applies the action to the metrics whose whole name matches the regular
+    /// expression.
+    pub fn new(name: &str, action: RelabelAction) -> Result<Self, regex::Error> {
+        Ok(Self { name: Regex::new(&format!("^(?:{name})$"))?, action })
+    }
+
+    /// Creates a rule that drops the matching metrics.
+    pub fn drop(name: &str) -> Result<Self, regex::Error> {
+        Self::new(name, RelabelAction::Drop)
+    }
+
+    /// Creates a rule that removes the label from the matching metrics.
+    pub fn drop_label(name: &str, label: impl Into<String>) -> Result<Self, regex::Error> {
+        Self::new(name, RelabelAction::DropLabel(label.into()))
+    }
+
+    /// Returns the action of the rule.
+    pub const fn action(&self) -> &RelabelAction {
+        &self.action
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.name.is_match(name)
+    }
+}
+
+/// Rules that are applied in order to the metrics in the Prometheus text format.
+#[derive(Debug, Clone, Default)]
+pub struct MetricRelabeling {
+    rules: Vec<RelabelRule>,
+}
+
+impl MetricRelabeling {
+    /// Creates a relabeling with the given rules.
+    pub const fn new(rules: Vec<RelabelRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Returns `true` if there are no rules.
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Applies the rules to the metrics in the Prometheus text format.
+    ///
+    /// The rules match the name of the metric family, so the `_sum` and `_count` samples of a
+    /// summary are matched by the name of the summary.
+    pub fn apply(&self, metrics: &str) -> String {
+        if self.is_empty() {
+            return metrics.to_string()
+        }
+
+        let mut out = String::with_capacity(metrics.len());
+        let mut family = "";
+        let mut samples = Samples::default();
+        for line in metrics.lines() {
+            if let Some(comment) = line.strip_prefix('#') {
+                // `# HELP <name> ...` and `# TYPE <name> ...` start a new family
+                let name = comment.split_whitespace().nth(1).unwrap_or_default();
+                if name != family {
+                    samples.flush(&mut out);
+                    family = name;
+                }
+                if !self.is_dropped(family) {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+                continue
+            }
+
+            let Some((series, value)) = line.rsplit_once(' ') else {
+                samples.flush(&mut out);
+                out.push_str(line);
+                out.push('\n');
+                continue
+            };
+            let name_end = series.find('{').unwrap_or(series.len());
+            let name = &series[..name_end];
+            if family.is_empty() || !name.starts_with(family) {
+                samples.flush(&mut out);
+                family = name;
+            }
+            let Some(mut labels) = parse_labels(&series[name_end..]) else {
+                samples.push(series.to_string(), value, false);
+                continue
+            };
+
+            let mut dropped = false;
+            let mut relabeled = false;
+            for rule in self.rules.iter().filter(|rule| rule.matches(family)) {
+                match &rule.action {
+                    RelabelAction::Drop => dropped = true,
+                    RelabelAction::DropLabel(label) => {
+                        let len = labels.len();
+                        labels.retain(|(key, _)| key != label);
+                        relabeled |= labels.len() != len;
+                    }
+                }
+            }
+            if dropped {
+                continue
+            }
+
+            let is_quantile = labels.iter().any(|(key, _)| *key == "quantile");
+            let series = if relabeled { render_series(name, &labels) } else { series.to_string() };
+            samples.push(series, value, is_quantile);
+        }
+        samples.flush(&mut out);
+
+        out
+    }
+
+    /// Returns `true` if a rule drops the metric family with the given name.
+    fn is_dropped(&self, family: &str) -> bool {
+        self.rules.iter().any(|rule| rule.action == RelabelAction::Drop && rule.matches(family))
+    }
+}
+
+/// The samples of a metric family, which are aggregated by series.
+#[derive(Debug, Default)]
+struct Samples {
+    /// The series and values in the order they were first seen.
+    samples: Vec<(String, String)>,
+    /// Index of each series in `samples`.
+    index: HashMap<String, usize>,
+}
+
+impl Samples {
+    /// Adds the sample, aggregating it with a previous sample of the same series.
+    fn push(&mut self, series: String, value: &str, is_quantile: bool) {
+        let Some(&idx) = self.index.get(&series) else {
+            self.index.insert(series.clone(), self.samples.len());
+            self.samples.push((series, value.to_string()));
+            return
+        };
+
+        let current = &mut self.samples[idx].1;
+        if let (Ok(a), Ok(b)) = (current.parse::<f64>(), value.parse::<f64>()) {
+            let aggregated = if is_quantile { a.max(b) } else { a + b };
+            *current = aggregated.to_string();
+        }
+    }
+
+    /// Writes the samples to the output and clears them.
+    fn flush(&mut self, out: &mut String) {
+        for (series, value) in self.samples.drain(..) {
+            out.push_str(&series);
+            out.push(' ');
+            out.push_str(&value);
+            out.push('\n');
+        }
+        self.index.clear();
+    }
+}
+
+/// Parses the labels of a series, e.g. `{a="1",b="2"}`, keeping the escaped values.
+fn parse_labels(mut s: &str) -> Option<Vec<(&str, &str)>> {
+    let mut labels = Vec::new();
+    if s.is_empty() {
+        return Some(labels)
+    }
+    s = s.strip_prefix('{')?.strip_suffix('}')?;
+    while !s.is_empty() {
+        let (key, rest) = s.split_once("=\"")?;
+        let mut escaped = false;
+        let end = rest.char_indices().find_map(|(i, c)| match c {
+            '\\' if !escaped => {
+                escaped = true;
+                None
+            }
+            '"' if !escaped => Some(i),
+            _ => {
+                escaped = false;
+                None
+            }
+        })?;
+        labels.push((key.trim(), &rest[..end]));
+        s = rest[end + 1..].trim_start_matches(',');
+    }
+    Some(labels)
+}
+
+/// Renders the name and labels of a series.
+fn render_series(name: &str, labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return name.to_string()
+    }
+    let labels = labels.iter().map(|(key, value)| format!("{key}=\"{value}\"")).collect::<Vec<_>>();
+    format!("{name}{{{}}}", labels.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METRICS: &str = "\
+# TYPE reth_network_peer_messages counter
+reth_network_peer_messages{peer_id=\"a\",kind=\"tx\"} 2
+reth_network_peer_messages{peer_id=\"b\",kind=\"tx\"} 3
+reth_network_peer_messages{peer_id=\"b\",kind=\"block\"} 1
+
+# TYPE reth_network_peer_latency summary
+reth_network_peer_latency{peer_id=\"a\",quantile=\"0.5\"} 0.25
+reth_network_peer_latency{peer_id=\"b\",quantile=\"0.5\"} 0.5
+reth_network_peer_latency_sum{peer_id=\"a\"} 1
+reth_network_peer_latency_sum{peer_id=\"b\"} 2
+reth_network_peer_latency_count{peer_id=\"a\"} 4
+reth_network_peer_latency_count{peer_id=\"b\"} 4
+
+# TYPE reth_sync_checkpoint gauge
+reth_sync_checkpoint{stage=\"Headers\"} 100
+";
+
+    #[test]
+    fn no_rules() {
+        assert_eq!(MetricRelabeling::default().apply(METRICS), METRICS);
+    }
+
+    #[test]
+    fn drop_metrics() {
+        let relabeling =
+            MetricRelabeling::new(vec![RelabelRule::drop("reth_network_peer_.*").unwrap()]);
+        assert_eq!(
+            relabeling.apply(METRICS),
+            "\n\n# TYPE reth_sync_checkpoint gauge\nreth_sync_checkpoint{stage=\"Headers\"} 100\n"
+        );
+    }
+
+    #[test]
+    fn drop_label_aggregates_samples() {
+        let relabeling =
+            MetricRelabeling::new(vec![
+                RelabelRule::drop_label("reth_network_.*", "peer_id").unwrap()
+            ]);
+        assert_eq!(
+            relabeling.apply(METRICS),
+            "\
+# TYPE reth_network_peer_messages counter
+reth_network_peer_messages{kind=\"tx\"} 5
+reth_network_peer_messages{kind=\"block\"} 1
+
+# TYPE reth_network_peer_latency summary
+reth_network_peer_latency{quantile=\"0.5\"} 0.5
+reth_network_peer_latency_sum 3
+reth_network_peer_latency_count 8
+
+# TYPE reth_sync_checkpoint gauge
+reth_sync_checkpoint{stage=\"Headers\"} 100
+"
+        );
+    }
+
+    #[test]
+    fn parse_escaped_labels() {
+        assert_eq!(
+            parse_labels(r#"{a="x\"y,z",b="2"}"#).unwrap(),
+            vec![("a", r#"x\"y,z"#), ("b", "2")]
+        );
+        assert_eq!(parse_labels("").unwrap(), vec![]);
+        assert!(parse_labels("{a=1}").is_none());
+    }
+}
@@ -1,15 +1,21 @@
 use crate::{
     hooks::{Hook, Hooks},
     recorder::install_prometheus_recorder,
+    relabel::MetricRelabeling,
     version::VersionInfo,
 };
+use alloy_rpc_types_engine::JwtSecret;
 use eyre::WrapErr;
-use http::{header::CONTENT_TYPE, HeaderValue, Response};
+use http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    HeaderMap, HeaderValue, Request, Response, StatusCode,
+};
 use metrics::describe_gauge;
 use metrics_process::Collector;
 use reth_metrics::metrics::Unit;
 use reth_tasks::TaskExecutor;
 use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+use subtle::ConstantTimeEq;
 use tracing::info;
 
 /// Configuration for the [`MetricServer`]
@@ -19,6 +25,8 @@ pub struct MetricServerConfig {
     version_info: VersionInfo,
     task_executor: TaskExecutor,
     hooks: Hooks,
+    auth: Option<MetricsAuth>,
+    relabeling: MetricRelabeling,
 }
 
 impl MetricServerConfig {
@@ -29,7 +37,54 @@ impl MetricServerConfig {
         task_executor: TaskExecutor,
         hooks: Hooks,
     ) -> Self {
-        Self { listen_addr, hooks, task_executor, version_info }
+        Self {
+            listen_addr,
+            hooks,
+            task_executor,
+            version_info,
+            auth: None,
+            relabeling: MetricRelabeling::new(Vec::new()),
+        }
+    }
+
+    /// Requires scrapers to authenticate with the given credential.
+    pub fn with_auth(mut self, auth: Option<MetricsAuth>) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Sets the rules that are applied to the served metrics.
+    pub fn with_relabeling(mut self, relabeling: MetricRelabeling) -> Self {
+        self.relabeling = relabeling;
+        self
+    }
+}
+
+/// The credential that scrapers must pass as bearer token in the `Authorization` header of their
+/// requests to the [`MetricServer`].
+#[derive(Debug, Clone)]
+pub enum MetricsAuth {
+    /// The token is passed as is.
+    BearerToken(String),
+    /// A JWT signed with the secret is passed.
+    Jwt(JwtSecret),
+}
+
+impl MetricsAuth {
+    /// Returns `true` if the bearer token of the headers matches the credential.
+    pub fn authenticates(&self, headers: &HeaderMap) -> bool {
+        let Some(token) = headers
+            .get(AUTHORIZATION)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| header.strip_prefix("Bearer "))
+        else {
+            return false
+        };
+        match self {
+            // compared in constant time, to not leak how many leading bytes were guessed correctly
+            Self::BearerToken(expected) => expected.as_bytes().ct_eq(token.as_bytes()).into(),
+            Self::Jwt(secret) => secret.validate(token).is_ok(),
+        }
     }
 }
 
@@ -47,7 +102,14 @@ impl MetricServer {
 
     /// Spawns the metrics server
     pub async fn serve(&self) -> eyre::Result<()> {
-        let MetricServerConfig { listen_addr, hooks, task_executor, version_info } = &self.config;
+        let MetricServerConfig {
+            listen_addr,
+            hooks,
+            task_executor,
+            version_info,
+            auth,
+            relabeling,
+        } = &self.config;
 
         info!(target: "reth::cli", addr = %listen_addr, "Starting metrics endpoint");
 
@@ -56,6 +118,8 @@ impl MetricServer {
             *listen_addr,
             Arc::new(move || hooks.iter().for_each(|hook| hook())),
             task_executor.clone(),
+            auth.clone(),
+            Arc::new(relabeling.clone()),
         )
         .await
         .wrap_err("Could not start Prometheus endpoint")?;
@@ -77,6 +141,8 @@ impl MetricServer {
         listen_addr: SocketAddr,
         hook: Arc<F>,
         task_executor: TaskExecutor,
+        auth: Option<MetricsAuth>,
+        relabeling: Arc<MetricRelabeling>,
     ) -> eyre::Result<()> {
         let listener = tokio::net::TcpListener::bind(listen_addr)
             .await
@@ -99,13 +165,24 @@ impl MetricServer {
 
                 let handle = install_prometheus_recorder();
                 let hook = hook.clone();
-                let service = tower::service_fn(move |_| {
-                    (hook)();
-                    let metrics = handle.render();
-                    let mut response = Response::new(metrics);
-                    response
-                        .headers_mut()
-                        .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+                let auth = auth.clone();
+                let relabeling = relabeling.clone();
+                let service = tower::service_fn(move |req: Request<_>| {
+                    let response =
+                        if auth.as_ref().is_some_and(|auth| !auth.authenticates(req.headers())) {
+                            let mut response =
+                                Response::new("missing or invalid bearer token".to_string());
+                            *response.status_mut() = StatusCode::UNAUTHORIZED;
+                            response
+                        } else {
+                            (hook)();
+                            let metrics = relabeling.apply(&handle.render());
+                            let mut response = Response::new(metrics);
+                            response
+                                .headers_mut()
+                                .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+                            response
+                        };
                     async move { Ok::<_, Infallible>(response) }
                 });
 
@@ -203,6 +280,7 @@ const fn describe_io_stats() {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::relabel::RelabelRule;
     use reqwest::Client;
     use reth_chainspec::MAINNET;
     use reth_db::{
@@ -267,4 +345,43 @@ mod tests {
         assert!(body.contains("reth_db_table_size"));
         assert!(body.contains("reth_jemalloc_metadata"));
     }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_auth() {
+        let version_info = VersionInfo {
+            version: "test",
+            build_timestamp: "test",
+            cargo_features: "test",
+            git_sha: "test",
+            target_triple: "test",
+            build_profile: "test",
+        };
+
+        let tasks = TaskManager::current();
+        let executor = tasks.executor();
+
+        let factory = create_test_db();
+        let hooks = Hooks::new(factory.db_ref().clone(), factory.static_file_provider());
+
+        let listen_addr = get_random_available_addr();
+        let config = MetricServerConfig::new(listen_addr, version_info, executor, hooks)
+            .with_auth(Some(MetricsAuth::BearerToken("secret".to_string())))
+            .with_relabeling(MetricRelabeling::new(vec![RelabelRule::drop("reth_db_.*").unwrap()]));
+
+        MetricServer::new(config).serve().await.unwrap();
+
+        let url = format!("http://{}", listen_addr);
+        let response = Client::new().get(&url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = Client::new().get(&url).bearer_auth("wrong").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = Client::new().get(&url).bearer_auth("secret").send().await.unwrap();
+        assert!(response.status().is_success());
+
+        let body = response.text().await.unwrap();
+        assert!(!body.contains("reth_db_table_size"));
+        assert!(body.contains("process_cpu_seconds_total"));
+    }
 }
@@ -0,0 +1,51 @@
+use std::time::Duration;
+use url::Url;
+
+/// The default number of times a failed delivery is retried.
+pub const DEFAULT_MAX_RETRIES: usize = 5;
+
+/// The default delay before the first retry of a failed delivery, which doubles with every retry.
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Settings of the [`WebhookNotifier`](crate::WebhookNotifier).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    /// The URLs the events are delivered to.
+    pub urls: Vec<Url>,
+    /// The secret the payloads are signed with, if any.
+    pub secret: Option<Vec<u8>>,
+    /// The number of times a failed delivery is retried.
+    pub max_retries: usize,
+    /// The delay before the first retry of a failed delivery.
+    pub retry_delay: Duration,
+}
+
+impl WebhookConfig {
+    /// Creates a new config that delivers the events to the given URLs.
+    pub const fn new(urls: Vec<Url>) -> Self {
+        Self {
+            urls,
+            secret: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_delay: DEFAULT_RETRY_DELAY,
+        }
+    }
+
+    /// Signs the payloads with the given secret.
+    pub fn with_secret(mut self, secret: impl Into<Vec<u8>>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+
+    /// Sets the number of times a failed delivery is retried.
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry of a failed delivery.
+    pub const fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+}
@@ -0,0 +1,123 @@
+use reth_chain_state::CanonStateNotification;
+use reth_primitives::{SealedHeader, B256};
+use serde::{Deserialize, Serialize};
+
+/// A canonical chain event that is delivered to the webhooks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum WebhookEvent {
+    /// A new block became the canonical head.
+    NewHead {
+        /// Number of the block.
+        number: u64,
+        /// Hash of the block.
+        hash: B256,
+        /// Hash of the parent block.
+        parent_hash: B256,
+        /// Timestamp of the block.
+        timestamp: u64,
+    },
+    /// A new block was finalized.
+    Finalized {
+        /// Number of the block.
+        number: u64,
+        /// Hash of the block.
+        hash: B256,
+    },
+    /// The canonical chain was reorged.
+    ///
+    /// This is followed by a [`WebhookEvent::NewHead`] event for the new head.
+    Reorg {
+        /// Number of the new head.
+        number: u64,
+        /// Hash of the new head.
+        hash: B256,
+        /// Number of the head before the reorg.
+        old_number: u64,
+        /// Hash of the head before the reorg.
+        old_hash: B256,
+        /// Number of blocks that were removed from the canonical chain.
+        depth: u64,
+    },
+}
+
+impl WebhookEvent {
+    /// Creates a [`WebhookEvent::NewHead`] event for the header.
+    pub fn new_head(header: &SealedHeader) -> Self {
+        Self::NewHead {
+            number: header.number,
+            hash: header.hash(),
+            parent_hash: header.parent_hash,
+            timestamp: header.timestamp,
+        }
+    }
+
+    /// Returns the events for a canonical state notification.
+    pub fn from_canon_state(notification: &CanonStateNotification) -> Vec<Self> {
+        let new_head = Self::new_head(&notification.tip().header);
+        match notification {
+            CanonStateNotification::Commit { .. } => vec![new_head],
+            CanonStateNotification::Reorg { old, new } => {
+                let old_tip = old.tip();
+                let reorg = Self::Reorg {
+                    number: new.tip().number,
+                    hash: new.tip().hash(),
+                    old_number: old_tip.number,
+                    old_hash: old_tip.hash(),
+                    depth: old.len() as u64,
+                };
+                vec![reorg, new_head]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_execution_types::{Chain, ExecutionOutcome};
+    use reth_primitives::{Header, SealedBlock};
+    use std::sync::Arc;
+
+    fn chain(blocks: impl IntoIterator<Item = (u64, u8)>) -> Arc<Chain> {
+        let blocks = blocks.into_iter().map(|(number, extra)| {
+            let header = Header { number, gas_limit: extra.into(), ..Default::default() };
+            SealedBlock { header: header.seal_slow(), ..Default::default() }
+                .seal_with_senders()
+                .unwrap()
+        });
+        Arc::new(Chain::new(blocks, ExecutionOutcome::default(), None))
+    }
+
+    #[test]
+    fn new_head_json() {
+        let event = WebhookEvent::NewHead {
+            number: 1,
+            hash: B256::with_last_byte(1),
+            parent_hash: B256::ZERO,
+            timestamp: 12,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "newHead");
+        assert_eq!(json["number"], 1);
+        assert_eq!(json["parentHash"], B256::ZERO.to_string());
+        assert_eq!(serde_json::from_value::<WebhookEvent>(json).unwrap(), event);
+    }
+
+    #[test]
+    fn reorg_events() {
+        let old = chain([(1, 0), (2, 0)]);
+        let new = chain([(1, 1)]);
+        let events = WebhookEvent::from_canon_state(&CanonStateNotification::Reorg {
+            old,
+            new: new.clone(),
+        });
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[0],
+            WebhookEvent::Reorg { number: 1, old_number: 2, depth: 2, .. }
+        ));
+        assert_eq!(events[1], WebhookEvent::new_head(&new.tip().header));
+    }
+}
@@ -0,0 +1,27 @@
+//! Webhook notifications of canonical chain events.
+//!
+//! The [`WebhookNotifier`] POSTs a compact JSON [`WebhookEvent`] to the configured URLs whenever
+//! the canonical head changes, a new block is finalized or the chain is reorged, so lightweight
+//! consumers can follow the chain without holding a websocket subscription open.
+//!
+//! If a secret is configured, the payloads are signed with HMAC-SHA256 and the signature is sent
+//! in the [`SIGNATURE_HEADER`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod config;
+pub use config::{WebhookConfig, DEFAULT_MAX_RETRIES, DEFAULT_RETRY_DELAY};
+
+mod event;
+pub use event::WebhookEvent;
+
+mod metrics;
+
+mod notifier;
+pub use notifier::{sign, WebhookNotifier, SIGNATURE_HEADER};
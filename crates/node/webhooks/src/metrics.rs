@@ -0,0 +1,15 @@
+//! Webhook delivery metrics.
+
+use reth_metrics::{metrics::Counter, Metrics};
+
+/// Webhook delivery metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "node.webhooks")]
+pub(crate) struct WebhookMetrics {
+    /// Total number of events delivered to a webhook
+    pub(crate) delivered_events: Counter,
+    /// Total number of events that could not be delivered to a webhook after all retries
+    pub(crate) failed_events: Counter,
+    /// Total number of events dropped because the queue of a webhook was full
+    pub(crate) dropped_events: Counter,
+}
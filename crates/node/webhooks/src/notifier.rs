@@ -0,0 +1,157 @@
+use crate::{metrics::WebhookMetrics, WebhookConfig, WebhookEvent};
+use backon::{ExponentialBuilder, Retryable};
+use bytes::Bytes;
+use futures_util::{future::join_all, Stream, StreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::{header::CONTENT_TYPE, Client, StatusCode};
+use reth_primitives::hex;
+use sha2::Sha256;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use url::Url;
+
+/// The header that contains the HMAC-SHA256 signature of the payload, if a secret is configured.
+///
+/// The signature is hex encoded and prefixed with `sha256=`.
+pub const SIGNATURE_HEADER: &str = "X-Reth-Signature";
+
+/// The maximum number of events that are queued for a webhook before new events are dropped.
+const QUEUE_CAPACITY: usize = 1024;
+
+/// The timeout of a single delivery attempt.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Possible errors when delivering an event to a webhook.
+#[derive(Debug, thiserror::Error)]
+enum DeliveryError {
+    /// The request to the webhook failed.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The webhook responded with a non-success status.
+    #[error("webhook responded with {0}")]
+    Status(StatusCode),
+}
+
+/// Delivers canonical chain events to the configured webhooks.
+///
+/// Every event is sent as a JSON POST request to each webhook. Events are delivered to a webhook in
+/// order, and failed deliveries are retried with exponential backoff. A slow webhook doesn't hold
+/// back the others, its events are queued until the queue is full.
+#[derive(Debug)]
+pub struct WebhookNotifier {
+    /// The delivery settings.
+    config: WebhookConfig,
+    /// The HTTP client shared by all webhooks.
+    client: Client,
+    /// Delivery metrics.
+    metrics: WebhookMetrics,
+}
+
+impl WebhookNotifier {
+    /// Creates a new notifier with the given settings.
+    pub fn new(config: WebhookConfig) -> Self {
+        Self { config, client: Client::new(), metrics: WebhookMetrics::default() }
+    }
+
+    /// Delivers the events to the webhooks until the stream ends.
+    pub async fn run<S>(self, mut events: S)
+    where
+        S: Stream<Item = WebhookEvent> + Unpin,
+    {
+        info!(target: "node::webhooks", webhooks = self.config.urls.len(), "Delivering chain events to webhooks");
+
+        let (senders, receivers): (Vec<_>, Vec<_>) =
+            self.config.urls.iter().map(|_| mpsc::channel(QUEUE_CAPACITY)).unzip();
+        let deliveries = join_all(
+            self.config
+                .urls
+                .iter()
+                .zip(receivers)
+                .map(|(url, events)| self.deliver_all(url, events)),
+        );
+
+        let this = &self;
+        let dispatch = async move {
+            while let Some(event) = events.next().await {
+                let body = match serde_json::to_vec(&event) {
+                    Ok(body) => Bytes::from(body),
+                    Err(err) => {
+                        warn!(target: "node::webhooks", %err, ?event, "Failed to serialize event");
+                        continue
+                    }
+                };
+                for (url, sender) in this.config.urls.iter().zip(&senders) {
+                    if sender.try_send(body.clone()).is_err() {
+                        warn!(target: "node::webhooks", %url, ?event, "Webhook queue is full, dropping event");
+                        this.metrics.dropped_events.increment(1);
+                    }
+                }
+            }
+        };
+
+        tokio::join!(dispatch, deliveries);
+    }
+
+    /// Delivers the queued events to the webhook until the queue is closed.
+    async fn deliver_all(&self, url: &Url, mut events: mpsc::Receiver<Bytes>) {
+        let backoff = ExponentialBuilder::default()
+            .with_min_delay(self.config.retry_delay)
+            .with_max_times(self.config.max_retries);
+
+        while let Some(body) = events.recv().await {
+            let result = (|| self.deliver(url, body.clone()))
+                .retry(&backoff)
+                .notify(|err, delay| {
+                    debug!(target: "node::webhooks", %url, %err, ?delay, "Retrying webhook delivery")
+                })
+                .await;
+            match result {
+                Ok(()) => self.metrics.delivered_events.increment(1),
+                Err(err) => {
+                    warn!(target: "node::webhooks", %url, %err, "Failed to deliver event to webhook");
+                    self.metrics.failed_events.increment(1);
+                }
+            }
+        }
+    }
+
+    /// Makes a single attempt to deliver the payload to the webhook.
+    async fn deliver(&self, url: &Url, body: Bytes) -> Result<(), DeliveryError> {
+        let mut request = self
+            .client
+            .post(url.clone())
+            .timeout(REQUEST_TIMEOUT)
+            .header(CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.config.secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, &body));
+        }
+
+        let status = request.body(body).send().await?.status();
+        if !status.is_success() {
+            return Err(DeliveryError::Status(status))
+        }
+        Ok(())
+    }
+}
+
+/// Returns the value of the [`SIGNATURE_HEADER`] for the payload.
+pub fn sign(secret: &[u8], payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_payload() {
+        // test case 2 of RFC 4231
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -2,9 +2,9 @@ use clap::Subcommand;
 use import::ImportOpCommand;
 use import_receipts::ImportReceiptsOpCommand;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, init_cmd, init_state,
+    config_cmd, db, dump_genesis, export, init_cmd, init_state, keys,
     node::{self, NoArgs},
-    p2p, prune, recover, stage,
+    p2p, prune, recover, rollback, stage,
 };
 use std::fmt;
 
@@ -36,12 +36,18 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Database debugging utilities
     #[command(name = "db")]
     Db(db::Command),
+    /// Export chain data for analytics.
+    #[command(name = "export")]
+    Export(export::Command),
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command),
     /// P2P Debugging utilities
     #[command(name = "p2p")]
     P2P(p2p::Command),
+    /// Manage the P2P secret key and the JWT secret
+    #[command(name = "keys")]
+    Keys(keys::Command),
     /// Write config to stdout
     #[command(name = "config")]
     Config(config_cmd::Command),
@@ -51,4 +57,7 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand),
+    /// Roll the node back to a block, removing all blocks after it
+    #[command(name = "rollback")]
+    Rollback(rollback::RollbackCommand),
 }
@@ -32,7 +32,7 @@ pub use commands::{import::ImportOpCommand, import_receipts::ImportReceiptsOpCom
 use std::{ffi::OsString, fmt, sync::Arc};
 
 use chainspec::OpChainSpecParser;
-use clap::{command, value_parser, Parser};
+use clap::{command, value_parser, CommandFactory, FromArgMatches, Parser};
 use commands::Commands;
 use reth_chainspec::ChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
@@ -88,7 +88,7 @@ pub struct Cli<Ext: clap::Args + fmt::Debug = NoArgs> {
 impl Cli {
     /// Parsers only the default CLI arguments
     pub fn parse_args() -> Self {
-        Self::parse()
+        Self::try_parse_args_from(std::env::args_os()).unwrap_or_else(|err| err.exit())
     }
 
     /// Parsers only the default CLI arguments from the given iterator
@@ -97,6 +97,16 @@ impl Cli {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        Self::try_parse_from(itr)
+        let matches = Self::command().try_get_matches_from(itr)?;
+        let mut cli =
+            Self::from_arg_matches(&matches).map_err(|err| err.format(&mut Self::command()))?;
+        // record the explicitly set arguments of the node command, which take precedence over
+        // the values the node derives itself
+        if let (Commands::Node(command), Some(matches)) =
+            (&mut cli.command, matches.subcommand_matches("node"))
+        {
+            command.set_explicit_args(matches);
+        }
+        Ok(cli)
     }
 }
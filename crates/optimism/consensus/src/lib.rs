@@ -67,7 +67,7 @@ impl Consensus for OptimismBeaconConsensus {
 
         // ensure that the blob gas fields for this block
         if self.chain_spec.is_cancun_active_at_timestamp(header.timestamp) {
-            validate_against_parent_4844(header, parent)?;
+            validate_against_parent_4844(header, parent, &self.chain_spec)?;
         }
 
         Ok(())
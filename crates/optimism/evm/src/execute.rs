@@ -102,11 +102,12 @@ where
 {
     /// Executes the transactions in the block and returns the receipts.
     ///
-    /// This applies the pre-execution changes, and executes the transactions.
+    /// This applies the pre-execution changes, executes the transactions and applies the chain
+    /// specific post-execution changes of [`ConfigureEvm::apply_post_execution_changes`].
     ///
     /// # Note
     ///
-    /// It does __not__ apply post-execution changes.
+    /// It does __not__ apply the other post-execution changes.
     fn execute_pre_and_transactions<Ext, DB>(
         &self,
         block: &BlockWithSenders,
@@ -218,6 +219,9 @@ where
                 .then_some(1),
             });
         }
+
+        // apply the chain specific post-execution changes
+        self.evm_config.apply_post_execution_changes(&self.chain_spec, &mut evm)?;
         drop(evm);
 
         Ok((receipts, cumulative_gas_used))
@@ -356,6 +360,7 @@ where
             state: self.state.take_bundle(),
             receipts,
             requests: vec![],
+            internal_transfers: vec![],
             gas_used,
         })
     }
@@ -24,11 +24,23 @@ const CREATE_2_DEPLOYER_BYTECODE: [u8; 1584] = hex!("608060405260043610610043576
 /// The function selector of the "setL1BlockValuesEcotone" function in the `L1Block` contract.
 const L1_BLOCK_ECOTONE_SELECTOR: [u8; 4] = hex!("440a5e20");
 
-/// Extracts the [`L1BlockInfo`] from the L2 block. The L1 info transaction is always the first
-/// transaction in the L2 block.
-///
-/// Returns an error if the L1 info transaction is not found, if the block is empty.
-pub fn extract_l1_info(block: &Block) -> Result<L1BlockInfo, OptimismBlockExecutionError> {
+/// The L1 block that an L2 block was derived from, as recorded by the L1 info transaction of the
+/// L2 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L1Origin {
+    /// The number of the L1 block.
+    pub number: u64,
+    /// The hash of the L1 block.
+    pub hash: B256,
+    /// The timestamp of the L1 block.
+    pub timestamp: u64,
+    /// The number of L2 blocks since the first L2 block derived from the L1 block.
+    pub sequence_number: u64,
+}
+
+/// Returns the calldata of the L1 info transaction, which is always the first transaction in the
+/// L2 block.
+fn l1_info_tx_data(block: &Block) -> Result<&[u8], OptimismBlockExecutionError> {
     let l1_info_tx_data = block
         .body
         .first()
@@ -43,6 +55,78 @@ pub fn extract_l1_info(block: &Block) -> Result<L1BlockInfo, OptimismBlockExecut
         })
     }
 
+    Ok(l1_info_tx_data)
+}
+
+/// Extracts the [`L1Origin`] from the L2 block. The L1 info transaction is always the first
+/// transaction in the L2 block.
+///
+/// Returns an error if the L1 info transaction is not found, if the block is empty.
+pub fn extract_l1_origin(block: &Block) -> Result<L1Origin, OptimismBlockExecutionError> {
+    let l1_info_tx_data = l1_info_tx_data(block)?;
+
+    if l1_info_tx_data[0..4] == L1_BLOCK_ECOTONE_SELECTOR {
+        parse_l1_origin_ecotone(l1_info_tx_data[4..].as_ref())
+    } else {
+        parse_l1_origin_bedrock(l1_info_tx_data[4..].as_ref())
+    }
+}
+
+/// Parses the [`L1Origin`] from the calldata of the L1 info transaction pre-Ecotone hardfork.
+///
+/// See [`parse_l1_info_tx_bedrock`] for the layout of the calldata.
+pub fn parse_l1_origin_bedrock(data: &[u8]) -> Result<L1Origin, OptimismBlockExecutionError> {
+    if data.len() != 256 {
+        return Err(OptimismBlockExecutionError::L1BlockInfoError {
+            message: "unexpected l1 block info tx calldata length found".to_string(),
+        })
+    }
+
+    let parse_u64 = |word: &[u8], name: &str| {
+        U256::try_from_be_slice(word).and_then(|value| u64::try_from(value).ok()).ok_or_else(|| {
+            OptimismBlockExecutionError::L1BlockInfoError {
+                message: format!("could not convert l1 block {name}"),
+            }
+        })
+    };
+
+    Ok(L1Origin {
+        number: parse_u64(&data[0..32], "number")?,
+        timestamp: parse_u64(&data[32..64], "timestamp")?,
+        hash: B256::from_slice(&data[96..128]),
+        sequence_number: parse_u64(&data[128..160], "sequence number")?,
+    })
+}
+
+/// Parses the [`L1Origin`] from the calldata of the L1 info transaction post-Ecotone hardfork.
+///
+/// The values are packed: the sequence number, the timestamp and the number of the block are
+/// the 8 byte integers at offset 8, 16 and 24, the block hash is at offset 96.
+pub fn parse_l1_origin_ecotone(data: &[u8]) -> Result<L1Origin, OptimismBlockExecutionError> {
+    if data.len() != 160 {
+        return Err(OptimismBlockExecutionError::L1BlockInfoError {
+            message: "unexpected l1 block info tx calldata length found".to_string(),
+        })
+    }
+
+    let parse_u64 =
+        |word: &[u8]| u64::from_be_bytes(word.try_into().expect("slice is 8 bytes long"));
+
+    Ok(L1Origin {
+        sequence_number: parse_u64(&data[8..16]),
+        timestamp: parse_u64(&data[16..24]),
+        number: parse_u64(&data[24..32]),
+        hash: B256::from_slice(&data[96..128]),
+    })
+}
+
+/// Extracts the [`L1BlockInfo`] from the L2 block. The L1 info transaction is always the first
+/// transaction in the L2 block.
+///
+/// Returns an error if the L1 info transaction is not found, if the block is empty.
+pub fn extract_l1_info(block: &Block) -> Result<L1BlockInfo, OptimismBlockExecutionError> {
+    let l1_info_tx_data = l1_info_tx_data(block)?;
+
     // If the first 4 bytes of the calldata are the L1BlockInfoEcotone selector, then we parse the
     // calldata as an Ecotone hardfork L1BlockInfo transaction. Otherwise, we parse it as a
     // Bedrock hardfork L1BlockInfo transaction.
@@ -296,6 +380,17 @@ mod tests {
         assert_eq!(l1_info.l1_base_fee_scalar, U256::from(1_000_000));
         assert_eq!(l1_info.l1_blob_base_fee, None);
         assert_eq!(l1_info.l1_blob_base_fee_scalar, None);
+
+        let l1_origin = extract_l1_origin(&mock_block).unwrap();
+        assert_eq!(
+            l1_origin,
+            L1Origin {
+                number: 8_410_981,
+                hash: b256!("73d89754a1e0387b89520d989d3be9c37c1f32495a88faf1ea05c61121ab0d19"),
+                timestamp: 1_675_193_616,
+                sequence_number: 1,
+            }
+        );
     }
 
     #[test]
@@ -318,5 +413,16 @@ mod tests {
         assert_eq!(l1_info.l1_blob_base_fee, Some(U256::from(22_380_075_395u64)));
         assert_eq!(l1_info.l1_blob_base_fee_scalar, Some(U256::from(0)));
         assert_eq!(l1_info.l1_fee_overhead, None);
+
+        let l1_origin = extract_l1_origin(&mock_block).unwrap();
+        assert_eq!(
+            l1_origin,
+            L1Origin {
+                number: 10_501_995,
+                hash: b256!("dea59eac60478a64ecfdcde8571e611404295350de7ed4ccb404296c1a84ab7a"),
+                timestamp: 1_707_351_912,
+                sequence_number: 4,
+            }
+        );
     }
 }
@@ -19,10 +19,12 @@ use reth_optimism_consensus::OptimismBeaconConsensus;
 use reth_optimism_rpc::OpEthApi;
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::CanonStateSubscriptions;
+use reth_tasks::shutdown::ShutdownPhase;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore,
+    ingress::{read_ingress_records, replay_ingress, IngressRecorder},
+    CoinbaseTipOrdering, TransactionPool, TransactionValidationTaskExecutor,
 };
 
 use crate::{
@@ -168,12 +170,15 @@ where
                     .require_l1_data_gas_fee(!ctx.config().dev.dev)
             });
 
-        let transaction_pool = reth_transaction_pool::Pool::new(
+        let mut transaction_pool = reth_transaction_pool::Pool::new(
             validator,
             CoinbaseTipOrdering::default(),
             blob_store,
             ctx.pool_config(),
         );
+        if let Some(path) = &ctx.config().txpool.record {
+            transaction_pool = transaction_pool.with_ingress_recorder(IngressRecorder::new(path)?);
+        }
         info!(target: "reth::cli", "Transaction pool initialized");
         let transactions_path = data_dir.txpool_transactions();
 
@@ -182,11 +187,16 @@ where
             let pool = transaction_pool.clone();
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
+            let mut transactions_backup_config =
                 reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+            if ctx.config().txpool.persist_all {
+                transactions_backup_config =
+                    transactions_backup_config.with_pool_snapshot(data_dir.txpool_snapshot());
+            }
 
-            ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+            ctx.task_executor().spawn_critical_with_shutdown_phase(
                 "local transactions backup task",
+                ShutdownPhase::TransactionPool,
                 |shutdown| {
                     reth_transaction_pool::maintain::backup_local_transactions_task(
                         shutdown,
@@ -210,6 +220,15 @@ where
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
         }
 
+        if let Some(path) = &ctx.config().txpool.replay {
+            let records = read_ingress_records(path)?;
+            let pool = transaction_pool.clone();
+            ctx.task_executor().spawn(Box::pin(async move {
+                let outcome = replay_ingress(pool, records).await;
+                info!(target: "reth::cli", ?outcome, "Replayed transaction pool ingress");
+            }));
+        }
+
         Ok(transaction_pool)
     }
 }
@@ -257,6 +276,7 @@ where
             .interval(conf.interval())
             .deadline(conf.deadline())
             .max_payload_tasks(conf.max_payload_tasks())
+            .max_builds_per_job(conf.max_builds_per_job())
             // no extradata for OP
             .extradata(Default::default());
 
@@ -8,6 +8,8 @@ use reth_rpc_eth_api::RawTransactionForwarder;
 use reth_rpc_eth_types::error::{EthApiError, EthResult};
 use reth_rpc_types::ToRpcError;
 
+pub use reth_optimism_rpc::{OpProvenance, OpProvenanceApiServer};
+
 /// Error type when interacting with the Sequencer
 #[derive(Debug, thiserror::Error)]
 pub enum SequencerRpcError {
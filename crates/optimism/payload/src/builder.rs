@@ -7,12 +7,14 @@ use crate::{
 use reth_basic_payload_builder::*;
 use reth_chain_state::ExecutedBlock;
 use reth_chainspec::{EthereumHardforks, OptimismHardfork};
-use reth_evm::{system_calls::pre_block_beacon_root_contract_call, ConfigureEvm};
+use reth_evm::{
+    system_calls::{post_block_execution_changes, pre_block_beacon_root_contract_call},
+    ConfigureEvm,
+};
 use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::error::PayloadBuilderError;
 use reth_primitives::{
     constants::{BEACON_NONCE, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS},
-    eip4844::calculate_excess_blob_gas,
     proofs, Block, Header, IntoRecoveredTransaction, Receipt, TxType, EMPTY_OMMER_ROOT_HASH, U256,
 };
 use reth_provider::StateProviderFactory;
@@ -137,6 +139,23 @@ where
             PayloadBuilderError::Internal(err.into())
         })?;
 
+        // apply the chain specific post-execution changes
+        post_block_execution_changes(
+            &self.evm_config,
+            &chain_spec,
+            &mut db,
+            &initialized_cfg,
+            &initialized_block_env,
+        )
+        .map_err(|err| {
+            warn!(target: "payload_builder",
+                parent_hash=%parent_block.hash(),
+                %err,
+                "failed to apply post-execution changes for empty payload"
+            );
+            PayloadBuilderError::Internal(err.into())
+        })?;
+
         let WithdrawalsOutcome { withdrawals_root, withdrawals } = commit_withdrawals(
             &mut db,
             &chain_spec,
@@ -171,14 +190,19 @@ where
         let mut blob_gas_used = None;
 
         if chain_spec.is_cancun_active_at_timestamp(attributes.payload_attributes.timestamp) {
+            let blob_params =
+                chain_spec.blob_params_at_timestamp(attributes.payload_attributes.timestamp);
             excess_blob_gas = if chain_spec.is_cancun_active_at_timestamp(parent_block.timestamp) {
                 let parent_excess_blob_gas = parent_block.excess_blob_gas.unwrap_or_default();
                 let parent_blob_gas_used = parent_block.blob_gas_used.unwrap_or_default();
-                Some(calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used))
+                Some(
+                    blob_params
+                        .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used),
+                )
             } else {
                 // for the first post-fork block, both parent.blob_gas_used and
                 // parent.excess_blob_gas are evaluated as 0
-                Some(calculate_excess_blob_gas(0, 0))
+                Some(blob_params.next_block_excess_blob_gas(0, 0))
             };
 
             blob_gas_used = Some(0);
@@ -508,6 +532,16 @@ where
         return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
     }
 
+    // apply the chain specific post-execution changes
+    post_block_execution_changes(
+        &evm_config,
+        &chain_spec,
+        &mut db,
+        &initialized_cfg,
+        &initialized_block_env,
+    )
+    .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
+
     let WithdrawalsOutcome { withdrawals_root, withdrawals } = commit_withdrawals(
         &mut db,
         &chain_spec,
@@ -555,14 +589,19 @@ where
 
     // only determine cancun fields when active
     if chain_spec.is_cancun_active_at_timestamp(attributes.payload_attributes.timestamp) {
+        let blob_params =
+            chain_spec.blob_params_at_timestamp(attributes.payload_attributes.timestamp);
         excess_blob_gas = if chain_spec.is_cancun_active_at_timestamp(parent_block.timestamp) {
             let parent_excess_blob_gas = parent_block.excess_blob_gas.unwrap_or_default();
             let parent_blob_gas_used = parent_block.blob_gas_used.unwrap_or_default();
-            Some(calculate_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used))
+            Some(
+                blob_params
+                    .next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used),
+            )
         } else {
             // for the first post-fork block, both parent.blob_gas_used and
             // parent.excess_blob_gas are evaluated as 0
-            Some(calculate_excess_blob_gas(0, 0))
+            Some(blob_params.next_block_excess_blob_gas(0, 0))
         };
 
         blob_gas_used = Some(0);
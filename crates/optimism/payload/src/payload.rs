@@ -118,10 +118,11 @@ impl PayloadBuilderAttributes for OptimismPayloadBuilderAttributes {
         // ensure we're not missing any timestamp based hardforks
         let spec_id = revm_spec_by_timestamp_after_bedrock(chain_spec, self.timestamp());
 
+        let blob_params = chain_spec.blob_params_at_timestamp(self.timestamp());
         // if the parent block did not have excess blob gas (i.e. it was pre-cancun), but it is
         // cancun now, we need to set the excess blob gas to the default value
         let blob_excess_gas_and_price = parent
-            .next_block_excess_blob_gas()
+            .next_block_excess_blob_gas_with_params(blob_params)
             .or_else(|| {
                 if spec_id.is_enabled_in(SpecId::CANCUN) {
                     // default excess blob gas is zero
@@ -130,7 +131,10 @@ impl PayloadBuilderAttributes for OptimismPayloadBuilderAttributes {
                     None
                 }
             })
-            .map(BlobExcessGasAndPrice::new);
+            .map(|excess_blob_gas| BlobExcessGasAndPrice {
+                excess_blob_gas,
+                blob_gasprice: blob_params.calc_blob_fee(excess_blob_gas),
+            });
 
         let block_env = BlockEnv {
             number: U256::from(parent.number + 1),
@@ -1,7 +1,7 @@
 #![allow(missing_docs)]
 #![allow(unreachable_pub)]
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockNumber, ChainId, B256};
+use reth_primitives::{Address, BlockNumber, ChainId, B256, U256};
 use reth_rpc_types::{BlockId, BlockNumberOrTag};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, net::IpAddr};
@@ -210,6 +210,64 @@ pub struct PeerStats {
     pub known: u32,
 }
 
+/// The L1 block that an L2 block was derived from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockL1Origin {
+    /// The hash of the L2 block.
+    pub block_hash: B256,
+    /// The number of the L2 block.
+    pub block_number: BlockNumber,
+    /// The hash of the L1 origin.
+    pub l1_block_hash: B256,
+    /// The number of the L1 origin.
+    pub l1_block_number: BlockNumber,
+    /// The timestamp of the L1 origin.
+    pub l1_timestamp: u64,
+    /// The number of L2 blocks since the first L2 block derived from the L1 origin.
+    pub sequence_number: u64,
+}
+
+/// A deposit transaction of an L2 block, with the source hash that identifies its origin on L1.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositTransaction {
+    /// The hash of the transaction.
+    pub hash: B256,
+    /// The index of the transaction in the L2 block.
+    pub transaction_index: u64,
+    /// The source hash, which uniquely identifies the source of the deposit on L1.
+    pub source_hash: B256,
+    /// The sender of the deposit.
+    pub from: Address,
+    /// The recipient of the deposit, `None` for contract creations.
+    pub to: Option<Address>,
+    /// The ETH minted on L2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint: Option<U256>,
+    /// The ETH value sent to the recipient.
+    pub value: U256,
+    /// Whether the deposit is a system transaction, like the L1 info transaction.
+    pub is_system_transaction: bool,
+}
+
+/// The L1 provenance of L2 blocks and their deposit transactions, taken from the blocks.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "optimism"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "optimism"))]
+pub trait OpProvenanceApi {
+    /// Returns the L1 origin of the L2 block, as recorded by its L1 info transaction.
+    #[method(name = "l1Origin")]
+    async fn optimism_l1_origin(&self, block_id: BlockId) -> RpcResult<Option<BlockL1Origin>>;
+
+    /// Returns the deposit transactions of the L2 block with their L1 source hashes, including
+    /// the L1 info transaction.
+    #[method(name = "depositTransactions")]
+    async fn optimism_deposit_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<DepositTransaction>>>;
+}
+
 /// Optimism specified rpc interface.
 /// https://docs.optimism.io/builders/node-operators/json-rpc
 /// https://github.com/ethereum-optimism/optimism/blob/8dd17a7b114a7c25505cd2e15ce4e3d0f7e3f7c1/op-node/node/api.go#L114
@@ -354,6 +412,12 @@ mod tests {
         test_helper::<PeerDump>(peer_dump_json);
     }
 
+    #[test]
+    fn test_deposit_transaction() {
+        let deposit_json = r#"{"hash":"0x1c0c8f4b7e8ef1a3ee8b0a4b7a5f09eb2e5c0bfc14c4f3ea9c52d01f0ef6fa4b","transactionIndex":1,"sourceHash":"0x3d2f9c3f35a3a7fc4c45e7b56e63f0f5a04bd0d4c73f2b9f0b5f1f09e5f0e8a2","from":"0x977f82a600a1414e583f7f13623f1ac5d58b1c0b","to":"0x4200000000000000000000000000000000000007","mint":"0xde0b6b3a7640000","value":"0xde0b6b3a7640000","isSystemTransaction":false}"#;
+        test_helper::<DepositTransaction>(deposit_json);
+    }
+
     #[test]
     fn test_peer_stats() {
         let peer_stats_json = r#"{"connected":20,"table":94,"blocksTopic":20,"blocksTopicV2":18,"blocksTopicV3":20,"banned":0,"known":71}"#;
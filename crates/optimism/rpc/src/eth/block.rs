@@ -2,7 +2,7 @@
 
 use reth_node_api::FullNodeComponents;
 use reth_primitives::TransactionMeta;
-use reth_provider::{BlockReaderIdExt, HeaderProvider};
+use reth_provider::{BlockReaderIdExt, ChainSpecProvider, HeaderProvider};
 use reth_rpc_eth_api::{
     helpers::{
         EthApiSpec, EthBlocks, LoadBlock, LoadPendingBlock, LoadReceipt, LoadTransaction,
@@ -39,6 +39,8 @@ where
             let block_hash = block.hash();
             let excess_blob_gas = block.excess_blob_gas;
             let timestamp = block.timestamp;
+            let blob_params =
+                LoadReceipt::provider(self).chain_spec().blob_params_at_timestamp(timestamp);
             let block = block.unseal();
 
             let l1_block_info = reth_evm_optimism::extract_l1_info(&block).ok();
@@ -62,7 +64,7 @@ where
                     let optimism_tx_meta =
                         self.build_op_tx_meta(tx, l1_block_info.clone(), timestamp)?;
 
-                    ReceiptBuilder::new(tx, meta, receipt, &receipts)
+                    ReceiptBuilder::new(tx, meta, receipt, &receipts, blob_params)
                         .map(|builder| {
                             op_receipt_fields(builder, tx, receipt, optimism_tx_meta).build()
                         })
@@ -72,8 +72,7 @@ impl<N: FullNodeComponents> OpEthApi<N> {
     /// Creates a new instance for given context.
     #[allow(clippy::type_complexity)]
     pub fn with_spawner(ctx: &EthApiBuilderCtx<N>) -> Self {
-        let blocking_task_pool =
-            BlockingTaskPool::build().expect("failed to build blocking task pool");
+        let blocking_task_pool = ctx.new_blocking_task_pool();
 
         let inner = EthApiInner::new(
             ctx.provider.clone(),
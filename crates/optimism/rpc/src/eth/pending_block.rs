@@ -4,11 +4,12 @@ use crate::OpEthApi;
 use reth_evm::ConfigureEvm;
 use reth_node_api::FullNodeComponents;
 use reth_primitives::{
-    revm_primitives::BlockEnv, BlockHashOrNumber, BlockNumber, SealedBlockWithSenders, B256,
+    revm_primitives::BlockEnv, BlockHashOrNumber, BlockNumber, Receipt, SealedBlockWithSenders,
+    B256,
 };
 use reth_provider::{
     BlockReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, ExecutionOutcome,
-    StateProviderFactory,
+    ReceiptProvider, StateProviderFactory,
 };
 use reth_rpc_eth_api::{
     helpers::{LoadPendingBlock, SpawnBlocking},
@@ -58,6 +59,18 @@ where
             .map_err(Self::Error::from_eth_err)
     }
 
+    /// Returns the locally built pending block, which is the latest block, and its receipts
+    async fn local_pending_block_and_receipts(
+        &self,
+    ) -> Result<Option<(SealedBlockWithSenders, Vec<Receipt>)>, Self::Error> {
+        let Some(block) = self.local_pending_block().await? else { return Ok(None) };
+        let receipts = self
+            .provider()
+            .receipts_by_block(block.hash().into())
+            .map_err(Self::Error::from_eth_err)?;
+        Ok(receipts.map(|receipts| (block, receipts)))
+    }
+
     fn receipts_root(
         &self,
         _block_env: &BlockEnv,
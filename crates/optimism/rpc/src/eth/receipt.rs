@@ -2,6 +2,7 @@
 
 use reth_node_api::FullNodeComponents;
 use reth_primitives::{Receipt, TransactionMeta, TransactionSigned};
+use reth_provider::ChainSpecProvider;
 use reth_rpc_eth_api::{
     helpers::{EthApiSpec, LoadReceipt, LoadTransaction},
     FromEthApiError,
@@ -17,6 +18,11 @@ where
     Self::Error: From<OpEthApiError>,
     N: FullNodeComponents,
 {
+    #[inline]
+    fn provider(&self) -> impl ChainSpecProvider {
+        self.inner.provider()
+    }
+
     #[inline]
     fn cache(&self) -> &EthStateCache {
         self.inner.cache()
@@ -38,7 +44,9 @@ where
         let l1_block_info = reth_evm_optimism::extract_l1_info(&block).ok();
         let optimism_tx_meta = self.build_op_tx_meta(&tx, l1_block_info, block.timestamp)?;
 
-        let resp_builder = ReceiptBuilder::new(&tx, meta, &receipt, &receipts)
+        let blob_params =
+            LoadReceipt::provider(self).chain_spec().blob_params_at_timestamp(meta.timestamp);
+        let resp_builder = ReceiptBuilder::new(&tx, meta, &receipt, &receipts, blob_params)
             .map_err(Self::Error::from_eth_err)?;
         let resp_builder = op_receipt_fields(resp_builder, &tx, &receipt, optimism_tx_meta);
 
@@ -13,7 +13,9 @@
 pub mod api;
 pub mod error;
 pub mod eth;
+pub mod provenance;
 
-pub use api::OpEthApiServer;
+pub use api::{OpEthApiServer, OpProvenanceApiServer};
 pub use error::OpEthApiError;
 pub use eth::{receipt::op_receipt_fields, transaction::OptimismTxMeta, OpEthApi};
+pub use provenance::OpProvenance;
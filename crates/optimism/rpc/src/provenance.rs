@@ -0,0 +1,84 @@
+//! `optimism_` endpoints for the L1 provenance of L2 blocks.
+
+use jsonrpsee::core::{async_trait, RpcResult};
+use reth_evm_optimism::extract_l1_origin;
+use reth_primitives::{Transaction, U256};
+use reth_provider::BlockReaderIdExt;
+use reth_rpc_eth_types::EthApiError;
+use reth_rpc_server_types::result::internal_rpc_err;
+use reth_rpc_types::BlockId;
+
+use crate::api::{BlockL1Origin, DepositTransaction, OpProvenanceApiServer};
+
+/// `optimism_` API implementation that serves the L1 origin and the deposit transactions of L2
+/// blocks.
+///
+/// Both are taken from the blocks themselves: the L1 origin is recorded by the L1 info
+/// transaction, which is the first transaction of every L2 block, and every deposit transaction
+/// carries the source hash of its L1 deposit.
+#[derive(Debug, Clone)]
+pub struct OpProvenance<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> OpProvenance<Provider> {
+    /// Creates a new instance with the given provider.
+    pub const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl<Provider> OpProvenanceApiServer for OpProvenance<Provider>
+where
+    Provider: BlockReaderIdExt + 'static,
+{
+    /// Handler for `optimism_l1Origin`
+    async fn optimism_l1_origin(&self, block_id: BlockId) -> RpcResult<Option<BlockL1Origin>> {
+        let Some(block) = self.provider.block_by_id(block_id).map_err(EthApiError::from)? else {
+            return Ok(None)
+        };
+        let l1_origin =
+            extract_l1_origin(&block).map_err(|err| internal_rpc_err(err.to_string()))?;
+
+        Ok(Some(BlockL1Origin {
+            block_hash: block.header.hash_slow(),
+            block_number: block.header.number,
+            l1_block_hash: l1_origin.hash,
+            l1_block_number: l1_origin.number,
+            l1_timestamp: l1_origin.timestamp,
+            sequence_number: l1_origin.sequence_number,
+        }))
+    }
+
+    /// Handler for `optimism_depositTransactions`
+    async fn optimism_deposit_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<DepositTransaction>>> {
+        let Some(block) = self.provider.block_by_id(block_id).map_err(EthApiError::from)? else {
+            return Ok(None)
+        };
+
+        let deposits = block
+            .body
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tx)| {
+                let Transaction::Deposit(deposit) = &tx.transaction else { return None };
+                Some(DepositTransaction {
+                    hash: tx.hash(),
+                    transaction_index: index as u64,
+                    source_hash: deposit.source_hash,
+                    from: deposit.from,
+                    to: deposit.to.to().copied(),
+                    mint: deposit.mint.map(U256::from),
+                    value: deposit.value,
+                    is_system_transaction: deposit.is_system_transaction,
+                })
+            })
+            .collect();
+
+        Ok(Some(deposits))
+    }
+}
@@ -13,8 +13,8 @@ use futures_core::ready;
 use futures_util::FutureExt;
 use reth_chainspec::{ChainSpec, EthereumHardforks};
 use reth_payload_builder::{
-    database::CachedReads, error::PayloadBuilderError, KeepPayloadJobAlive, PayloadId, PayloadJob,
-    PayloadJobGenerator,
+    database::CachedReads, error::PayloadBuilderError, KeepPayloadJobAlive, PayloadFuture,
+    PayloadId, PayloadJob, PayloadJobGenerator,
 };
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
 use reth_primitives::{
@@ -132,24 +132,18 @@ impl<Client, Pool, Tasks, Builder> BasicPayloadJobGenerator<Client, Pool, Tasks,
     }
 }
 
-// === impl BasicPayloadJobGenerator ===
-
-impl<Client, Pool, Tasks, Builder> PayloadJobGenerator
-    for BasicPayloadJobGenerator<Client, Pool, Tasks, Builder>
+impl<Client, Pool, Tasks, Builder> BasicPayloadJobGenerator<Client, Pool, Tasks, Builder>
 where
-    Client: StateProviderFactory + BlockReaderIdExt + Clone + Unpin + 'static,
-    Pool: TransactionPool + Unpin + 'static,
-    Tasks: TaskSpawner + Clone + Unpin + 'static,
-    Builder: PayloadBuilder<Pool, Client> + Unpin + 'static,
-    <Builder as PayloadBuilder<Pool, Client>>::Attributes: Unpin + Clone,
-    <Builder as PayloadBuilder<Pool, Client>>::BuiltPayload: Unpin + Clone,
+    Client: BlockReaderIdExt,
 {
-    type Job = BasicPayloadJob<Client, Pool, Tasks, Builder>;
-
-    fn new_payload_job(
+    /// Returns the [`PayloadConfig`] for building on top of the parent block of the attributes.
+    fn payload_config<Attributes>(
         &self,
-        attributes: <Self::Job as PayloadJob>::PayloadAttributes,
-    ) -> Result<Self::Job, PayloadBuilderError> {
+        attributes: Attributes,
+    ) -> Result<PayloadConfig<Attributes>, PayloadBuilderError>
+    where
+        Attributes: PayloadBuilderAttributes,
+    {
         let parent_block = if attributes.parent().is_zero() {
             // use latest block if parent is zero: genesis block
             self.client
@@ -166,12 +160,34 @@ where
             block.seal(attributes.parent())
         };
 
-        let config = PayloadConfig::new(
+        Ok(PayloadConfig::new(
             Arc::new(parent_block),
             self.config.extradata.clone(),
             attributes,
             Arc::clone(&self.chain_spec),
-        );
+        ))
+    }
+}
+
+// === impl BasicPayloadJobGenerator ===
+
+impl<Client, Pool, Tasks, Builder> PayloadJobGenerator
+    for BasicPayloadJobGenerator<Client, Pool, Tasks, Builder>
+where
+    Client: StateProviderFactory + BlockReaderIdExt + Clone + Unpin + 'static,
+    Pool: TransactionPool + Unpin + 'static,
+    Tasks: TaskSpawner + Clone + Unpin + 'static,
+    Builder: PayloadBuilder<Pool, Client> + Unpin + 'static,
+    <Builder as PayloadBuilder<Pool, Client>>::Attributes: Unpin + Clone,
+    <Builder as PayloadBuilder<Pool, Client>>::BuiltPayload: Unpin + Clone,
+{
+    type Job = BasicPayloadJob<Client, Pool, Tasks, Builder>;
+
+    fn new_payload_job(
+        &self,
+        attributes: <Self::Job as PayloadJob>::PayloadAttributes,
+    ) -> Result<Self::Job, PayloadBuilderError> {
+        let config = self.payload_config(attributes)?;
 
         let until = self.job_deadline(config.attributes.timestamp());
         let deadline = Box::pin(tokio::time::sleep_until(until));
@@ -190,6 +206,7 @@ where
             pending_block: None,
             cached_reads,
             payload_task_guard: self.payload_task_guard.clone(),
+            remaining_builds: self.config.max_builds_per_job,
             metrics: Default::default(),
             builder: self.builder.clone(),
         };
@@ -200,6 +217,37 @@ where
         Ok(job)
     }
 
+    fn preview_payload(
+        &self,
+        attributes: <Self::Job as PayloadJob>::PayloadAttributes,
+    ) -> Result<PayloadFuture<<Self::Job as PayloadJob>::BuiltPayload>, PayloadBuilderError> {
+        let config = self.payload_config(attributes)?;
+        let cached_reads = self.maybe_pre_cached(config.parent_block.hash()).unwrap_or_default();
+
+        let (tx, rx) = oneshot::channel();
+        let client = self.client.clone();
+        let pool = self.pool.clone();
+        let guard = self.payload_task_guard.clone();
+        let builder = self.builder.clone();
+        self.executor.spawn_blocking(Box::pin(async move {
+            // previews share the permits with the payload jobs
+            let _permit = guard.acquire().await;
+            let args =
+                BuildArguments::new(client, pool, cached_reads, config, Cancelled::default(), None);
+            let _ = tx.send(builder.try_build(args));
+        }));
+
+        Ok(Box::pin(async move {
+            match rx.await?? {
+                BuildOutcome::Better { payload, .. } => Ok(payload),
+                // there's no best payload to compare against, so this is not expected
+                BuildOutcome::Aborted { .. } | BuildOutcome::Cancelled => {
+                    Err(PayloadBuilderError::MissingPayload)
+                }
+            }
+        }))
+    }
+
     fn on_new_state(&mut self, new_state: CanonStateNotification) {
         let mut cached = CachedReads::default();
 
@@ -265,6 +313,10 @@ pub struct BasicPayloadJobGeneratorConfig {
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
+    /// Maximum number of build attempts a single payload job is allowed to make.
+    ///
+    /// By default this is unlimited.
+    max_builds_per_job: Option<u64>,
 }
 
 // === impl BasicPayloadJobGeneratorConfig ===
@@ -293,6 +345,16 @@ impl BasicPayloadJobGeneratorConfig {
         self
     }
 
+    /// Sets the maximum number of build attempts a single payload job is allowed to make.
+    ///
+    /// Once a job used up its budget it keeps serving its best payload until it is resolved, so
+    /// that concurrent jobs for the same parent, e.g. for different fee recipients, can't starve
+    /// each other of payload tasks.
+    pub const fn max_builds_per_job(mut self, max_builds_per_job: Option<u64>) -> Self {
+        self.max_builds_per_job = max_builds_per_job;
+        self
+    }
+
     /// Sets the data to include in the block's extra data field.
     ///
     /// Defaults to the current client version: `rlp(RETH_CLIENT_VERSION)`.
@@ -310,6 +372,7 @@ impl Default for BasicPayloadJobGeneratorConfig {
             // 12s slot time
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            max_builds_per_job: None,
         }
     }
 }
@@ -338,6 +401,8 @@ where
     pending_block: Option<PendingPayload<Builder::BuiltPayload>>,
     /// Restricts how many generator tasks can be executed at once.
     payload_task_guard: PayloadTaskGuard,
+    /// Remaining build attempts of this job, if its budget is limited.
+    remaining_builds: Option<u64>,
     /// Caches all disk reads for the state the new payloads builds on
     ///
     /// This is used to avoid reading the same state over and over again when new attempts are
@@ -372,6 +437,9 @@ where
         let payload_config = self.config.clone();
         let best_payload = self.best_payload.clone();
         self.metrics.inc_initiated_payload_builds();
+        if let Some(remaining) = self.remaining_builds.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
         let cached_reads = self.cached_reads.take().unwrap_or_default();
         let builder = self.builder.clone();
         self.executor.spawn_blocking(Box::pin(async move {
@@ -415,8 +483,9 @@ where
 
         // check if the interval is reached
         while this.interval.poll_tick(cx).is_ready() {
-            // start a new job if there is no pending block and we haven't reached the deadline
-            if this.pending_block.is_none() {
+            // start a new job if there is no pending block, we haven't reached the deadline and
+            // the job has build attempts left
+            if this.pending_block.is_none() && this.remaining_builds != Some(0) {
                 this.spawn_build_job();
             }
         }
@@ -1,6 +1,6 @@
 //! A payload builder service task that does nothing.
 
-use crate::{service::PayloadServiceCommand, PayloadBuilderHandle};
+use crate::{error::PayloadBuilderError, service::PayloadServiceCommand, PayloadBuilderHandle};
 use futures_util::{ready, StreamExt};
 use reth_payload_primitives::{PayloadBuilderAttributes, PayloadTypes};
 use std::{
@@ -52,6 +52,9 @@ where
                 PayloadServiceCommand::BestPayload(_, tx) => tx.send(None).ok(),
                 PayloadServiceCommand::PayloadAttributes(_, tx) => tx.send(None).ok(),
                 PayloadServiceCommand::Resolve(_, tx) => tx.send(None).ok(),
+                PayloadServiceCommand::Preview(_, tx) => {
+                    tx.send(Err(PayloadBuilderError::PreviewUnsupported)).ok()
+                }
                 PayloadServiceCommand::Subscribe(_) => None,
             };
         }
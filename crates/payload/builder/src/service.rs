@@ -7,7 +7,7 @@ use crate::{
     error::PayloadBuilderError,
     events::{Events, PayloadEvents},
     metrics::PayloadBuilderServiceMetrics,
-    traits::PayloadJobGenerator,
+    traits::{PayloadFuture, PayloadJobGenerator},
     KeepPayloadJobAlive, PayloadJob,
 };
 use futures_util::{future::FutureExt, Stream, StreamExt};
@@ -27,8 +27,6 @@ use tokio::sync::{
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, info, trace, warn};
 
-type PayloadFuture<P> = Pin<Box<dyn Future<Output = Result<P, PayloadBuilderError>> + Send + Sync>>;
-
 /// A communication channel to the [`PayloadBuilderService`] that can retrieve payloads.
 #[derive(Debug)]
 pub struct PayloadStore<Engine: PayloadTypes> {
@@ -179,6 +177,21 @@ where
         self.send_new_payload(attr).await?
     }
 
+    /// Builds a payload for the given attributes right away and returns it, without starting a
+    /// payload job.
+    ///
+    /// The returned payload is not retained by the service and can't be resolved.
+    pub async fn preview_payload(
+        &self,
+        attr: Engine::PayloadBuilderAttributes,
+    ) -> Result<Engine::BuiltPayload, PayloadBuilderError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_service
+            .send(PayloadServiceCommand::Preview(attr, tx))
+            .map_err(|_| PayloadBuilderError::ChannelClosed)?;
+        rx.await??.await
+    }
+
     /// Sends a message to the service to subscribe to payload events.
     /// Returns a receiver that will receive them.
     pub async fn subscribe(&self) -> Result<PayloadEvents<Engine>, RecvError> {
@@ -322,6 +335,19 @@ where
 
         Some(Box::pin(fut))
     }
+
+    /// Returns a future that builds a payload for the given attributes without starting a job.
+    fn preview(
+        &self,
+        attr: Engine::PayloadBuilderAttributes,
+    ) -> Result<PayloadFuture<Engine::BuiltPayload>, PayloadBuilderError>
+    where
+        Gen::Job: 'static,
+    {
+        trace!(id = %attr.payload_id(), parent = %attr.parent(), "building payload preview");
+        let fut = self.generator.preview_payload(attr)?;
+        Ok(Box::pin(async move { fut.await.map(Into::into) }))
+    }
 }
 
 impl<Gen, St, Engine> PayloadBuilderService<Gen, St, Engine>
@@ -437,6 +463,9 @@ where
                     PayloadServiceCommand::Resolve(id, tx) => {
                         let _ = tx.send(this.resolve(id));
                     }
+                    PayloadServiceCommand::Preview(attr, tx) => {
+                        let _ = tx.send(this.preview(attr));
+                    }
                     PayloadServiceCommand::Subscribe(tx) => {
                         let new_rx = this.payload_events.subscribe();
                         let _ = tx.send(new_rx);
@@ -470,6 +499,11 @@ pub enum PayloadServiceCommand<Engine: PayloadTypes> {
     ),
     /// Resolve the payload and return the payload
     Resolve(PayloadId, oneshot::Sender<Option<PayloadFuture<Engine::BuiltPayload>>>),
+    /// Build a payload for the attributes without starting a payload job
+    Preview(
+        Engine::PayloadBuilderAttributes,
+        oneshot::Sender<Result<PayloadFuture<Engine::BuiltPayload>, PayloadBuilderError>>,
+    ),
     /// Payload service events
     Subscribe(oneshot::Sender<broadcast::Receiver<Events<Engine>>>),
 }
@@ -490,6 +524,7 @@ where
                 f.debug_tuple("PayloadAttributes").field(&f0).field(&f1).finish()
             }
             Self::Resolve(f0, _f1) => f.debug_tuple("Resolve").field(&f0).finish(),
+            Self::Preview(f0, _f1) => f.debug_tuple("Preview").field(&f0).finish(),
             Self::Subscribe(f0) => f.debug_tuple("Subscribe").field(&f0).finish(),
         }
     }
@@ -2,11 +2,11 @@
 
 use crate::{
     error::PayloadBuilderError, traits::KeepPayloadJobAlive, EthBuiltPayload,
-    EthPayloadBuilderAttributes, PayloadBuilderHandle, PayloadBuilderService, PayloadJob,
-    PayloadJobGenerator,
+    EthPayloadBuilderAttributes, PayloadBuilderHandle, PayloadBuilderService, PayloadFuture,
+    PayloadJob, PayloadJobGenerator,
 };
 use reth_payload_primitives::PayloadTypes;
-use reth_primitives::{Block, U256};
+use reth_primitives::{Block, Header, U256};
 use reth_provider::CanonStateNotification;
 use std::{
     future::Future,
@@ -59,6 +59,23 @@ impl PayloadJobGenerator for TestPayloadJobGenerator {
     ) -> Result<Self::Job, PayloadBuilderError> {
         Ok(TestPayloadJob { attr })
     }
+
+    fn preview_payload(
+        &self,
+        attr: EthPayloadBuilderAttributes,
+    ) -> Result<PayloadFuture<EthBuiltPayload>, PayloadBuilderError> {
+        let block = Block {
+            header: Header {
+                parent_hash: attr.parent,
+                beneficiary: attr.suggested_fee_recipient,
+                timestamp: attr.timestamp,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let payload = EthBuiltPayload::new(attr.payload_id(), block.seal_slow(), U256::ZERO);
+        Ok(Box::pin(futures_util::future::ok(payload)))
+    }
 }
 
 /// A [`PayloadJobGenerator`] for testing purposes
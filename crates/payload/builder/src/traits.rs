@@ -3,7 +3,11 @@
 use crate::error::PayloadBuilderError;
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
 use reth_provider::CanonStateNotification;
-use std::future::Future;
+use std::{future::Future, pin::Pin};
+
+/// A future that resolves to a built payload.
+pub type PayloadFuture<P> =
+    Pin<Box<dyn Future<Output = Result<P, PayloadBuilderError>> + Send + Sync>>;
 
 /// A type that can build a payload.
 ///
@@ -87,6 +91,19 @@ pub trait PayloadJobGenerator: Send + Sync {
         attr: <Self::Job as PayloadJob>::PayloadAttributes,
     ) -> Result<Self::Job, PayloadBuilderError>;
 
+    /// Builds a single payload with transactions for the given attributes right away, without
+    /// creating a [`PayloadJob`].
+    ///
+    /// This is used to preview the payload that would be built for the attributes, e.g. to monitor
+    /// the expected value of a proposal. By default previews are not supported.
+    fn preview_payload(
+        &self,
+        attr: <Self::Job as PayloadJob>::PayloadAttributes,
+    ) -> Result<PayloadFuture<<Self::Job as PayloadJob>::BuiltPayload>, PayloadBuilderError> {
+        let _ = attr;
+        Err(PayloadBuilderError::PreviewUnsupported)
+    }
+
     /// Handles new chain state events
     ///
     /// This is intended for any logic that needs to be run when the chain state changes or used to
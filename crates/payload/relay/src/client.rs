@@ -0,0 +1,215 @@
+use crate::RelayError;
+use futures_util::future::join_all;
+use reqwest::{Client, RequestBuilder};
+use reth_rpc_types::beacon::relay::{
+    BidTrace, SignedBidSubmissionV1, SignedBidSubmissionV2, SignedBidSubmissionV3, Validator,
+    ValidatorRegistration,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::warn;
+use url::Url;
+
+/// The endpoint for registering validators.
+const REGISTER_VALIDATORS_PATH: &str = "/eth/v1/builder/validators";
+
+/// The endpoint for the proposers of the current and next epoch.
+const PROPOSER_DUTIES_PATH: &str = "/relay/v1/builder/validators";
+
+/// The endpoint for submitting blocks.
+const SUBMIT_BLOCK_PATH: &str = "/relay/v1/builder/blocks";
+
+/// The timeout of requests to a relay.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A signed block submission for the `/relay/v1/builder/blocks` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum SignedBidSubmission {
+    /// A Bellatrix block submission.
+    V1(SignedBidSubmissionV1),
+    /// A Capella block submission.
+    V2(SignedBidSubmissionV2),
+    /// A Deneb block submission.
+    V3(SignedBidSubmissionV3),
+}
+
+impl SignedBidSubmission {
+    /// Returns the bid of the submission.
+    pub const fn message(&self) -> &BidTrace {
+        match self {
+            Self::V1(submission) => &submission.message,
+            Self::V2(submission) => &submission.message,
+            Self::V3(submission) => &submission.message,
+        }
+    }
+}
+
+/// The error response of a relay.
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+/// A client for the builder API of a single relay.
+#[derive(Debug, Clone)]
+pub struct RelayClient {
+    url: Url,
+    client: Client,
+}
+
+impl RelayClient {
+    /// Creates a new client for the relay at the given URL.
+    pub fn new(url: Url) -> Self {
+        Self::with_client(url, Client::new())
+    }
+
+    /// Creates a new client for the relay at the given URL that uses the given HTTP client.
+    pub const fn with_client(url: Url, client: Client) -> Self {
+        Self { url, client }
+    }
+
+    /// Returns the URL of the relay.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Registers the validators with the relay.
+    pub async fn register_validators(
+        &self,
+        registrations: &[ValidatorRegistration],
+    ) -> Result<(), RelayError> {
+        self.send(self.client.post(self.url.join(REGISTER_VALIDATORS_PATH)?).json(registrations))
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the registered proposers of the current and next epoch.
+    pub async fn proposer_duties(&self) -> Result<Vec<Validator>, RelayError> {
+        let response = self.send(self.client.get(self.url.join(PROPOSER_DUTIES_PATH)?)).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Submits the signed block to the relay.
+    pub async fn submit_bid(&self, submission: &SignedBidSubmission) -> Result<(), RelayError> {
+        self.send(self.client.post(self.url.join(SUBMIT_BLOCK_PATH)?).json(submission)).await?;
+        Ok(())
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, RelayError> {
+        let response = request.timeout(REQUEST_TIMEOUT).send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response)
+        }
+
+        let message = match response.json::<ErrorResponse>().await {
+            Ok(error) => error.message,
+            Err(_) => status.canonical_reason().unwrap_or_default().to_string(),
+        };
+        Err(RelayError::Rejected { relay: self.url.clone(), status, message })
+    }
+}
+
+/// The set of relays that payloads are submitted to.
+#[derive(Debug, Clone, Default)]
+pub struct Relays {
+    clients: Vec<RelayClient>,
+}
+
+impl Relays {
+    /// Creates clients for the relays at the given URLs.
+    pub fn new(urls: impl IntoIterator<Item = Url>) -> Self {
+        let client = Client::new();
+        Self {
+            clients: urls
+                .into_iter()
+                .map(|url| RelayClient::with_client(url, client.clone()))
+                .collect(),
+        }
+    }
+
+    /// Returns the clients of all relays.
+    pub fn clients(&self) -> &[RelayClient] {
+        &self.clients
+    }
+
+    /// Forwards the validator registrations to all relays.
+    ///
+    /// Succeeds if at least one relay accepted the registrations.
+    pub async fn register_validators(
+        &self,
+        registrations: &[ValidatorRegistration],
+    ) -> Result<(), RelayError> {
+        let results =
+            join_all(self.clients.iter().map(|relay| relay.register_validators(registrations)))
+                .await;
+
+        let mut last_error = None;
+        for (relay, result) in self.clients.iter().zip(results) {
+            if let Err(error) = result {
+                warn!(target: "payload::relay", relay = %relay.url(), %error, "Failed to register validators");
+                last_error = Some(error);
+            } else {
+                return Ok(())
+            }
+        }
+        last_error.map_or(Ok(()), Err)
+    }
+
+    /// Returns the relays that know the proposer of the given slot, together with the
+    /// registration of the proposer.
+    pub async fn proposer_duty(&self, slot: u64) -> Vec<(&RelayClient, Validator)> {
+        let duties = join_all(self.clients.iter().map(|relay| relay.proposer_duties())).await;
+
+        let mut relays = Vec::new();
+        for (relay, duties) in self.clients.iter().zip(duties) {
+            match duties {
+                Ok(duties) => {
+                    if let Some(duty) = duties.into_iter().find(|duty| duty.slot == slot) {
+                        relays.push((relay, duty));
+                    }
+                }
+                Err(error) => {
+                    warn!(target: "payload::relay", relay = %relay.url(), %error, "Failed to fetch proposer duties")
+                }
+            }
+        }
+        relays
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::engine::ExecutionPayloadV1;
+
+    #[test]
+    fn serialize_bid_submission() {
+        let submission = SignedBidSubmission::V1(SignedBidSubmissionV1 {
+            message: BidTrace { slot: 1, ..Default::default() },
+            execution_payload: ExecutionPayloadV1 {
+                parent_hash: Default::default(),
+                fee_recipient: Default::default(),
+                state_root: Default::default(),
+                receipts_root: Default::default(),
+                logs_bloom: Default::default(),
+                prev_randao: Default::default(),
+                block_number: 1,
+                gas_limit: 30_000_000,
+                gas_used: 0,
+                timestamp: 12,
+                extra_data: Default::default(),
+                base_fee_per_gas: Default::default(),
+                block_hash: Default::default(),
+                transactions: Vec::new(),
+            },
+            signature: Default::default(),
+        });
+
+        // The builder API encodes numbers as decimal strings.
+        let json = serde_json::to_value(&submission).unwrap();
+        assert_eq!(json["message"]["slot"], "1");
+        assert_eq!(json["execution_payload"]["gas_limit"], "30000000");
+    }
+}
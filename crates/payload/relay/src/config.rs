@@ -0,0 +1,97 @@
+use reth_chainspec::{Chain, NamedChain};
+use std::time::Duration;
+use url::Url;
+
+/// The default interval at which the best payload of a job is submitted to the relays.
+pub const DEFAULT_SUBMISSION_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Settings for submitting locally built payloads to relays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayConfig {
+    /// The URLs of the relays.
+    pub relays: Vec<Url>,
+    /// The beacon chain the relays serve.
+    pub beacon: BeaconChainConfig,
+    /// The interval at which the best payload of a job is submitted, if it improved.
+    pub submission_interval: Duration,
+}
+
+impl RelayConfig {
+    /// Creates a new config for the given relays.
+    pub const fn new(relays: Vec<Url>, beacon: BeaconChainConfig) -> Self {
+        Self { relays, beacon, submission_interval: DEFAULT_SUBMISSION_INTERVAL }
+    }
+
+    /// Sets the interval at which the best payload of a job is submitted.
+    pub const fn with_submission_interval(mut self, submission_interval: Duration) -> Self {
+        self.submission_interval = submission_interval;
+        self
+    }
+}
+
+/// The parameters of the beacon chain that are required to map payloads to slots and to sign
+/// bids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeaconChainConfig {
+    /// The unix timestamp of the beacon chain genesis.
+    pub genesis_time: u64,
+    /// The duration of a slot in seconds.
+    pub seconds_per_slot: u64,
+    /// The fork version at genesis, used to compute the builder signing domain.
+    pub genesis_fork_version: [u8; 4],
+}
+
+impl BeaconChainConfig {
+    /// The beacon chain of Ethereum mainnet.
+    pub const MAINNET: Self =
+        Self { genesis_time: 1606824023, seconds_per_slot: 12, genesis_fork_version: [0; 4] };
+
+    /// The beacon chain of the Sepolia testnet.
+    pub const SEPOLIA: Self = Self {
+        genesis_time: 1655733600,
+        seconds_per_slot: 12,
+        genesis_fork_version: [0x90, 0x00, 0x00, 0x69],
+    };
+
+    /// The beacon chain of the Holesky testnet.
+    pub const HOLESKY: Self = Self {
+        genesis_time: 1695902400,
+        seconds_per_slot: 12,
+        genesis_fork_version: [0x01, 0x01, 0x70, 0x00],
+    };
+
+    /// Returns the beacon chain of the given execution chain, if it is known.
+    pub fn from_chain(chain: Chain) -> Option<Self> {
+        match chain.named()? {
+            NamedChain::Mainnet => Some(Self::MAINNET),
+            NamedChain::Sepolia => Some(Self::SEPOLIA),
+            NamedChain::Holesky => Some(Self::HOLESKY),
+            _ => None,
+        }
+    }
+
+    /// Returns the slot that starts at the given timestamp.
+    ///
+    /// Returns `None` if the timestamp is before genesis or not at the start of a slot.
+    pub const fn slot_at(&self, timestamp: u64) -> Option<u64> {
+        let Some(elapsed) = timestamp.checked_sub(self.genesis_time) else { return None };
+        if elapsed % self.seconds_per_slot != 0 {
+            return None
+        }
+        Some(elapsed / self.seconds_per_slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_at_timestamp() {
+        let config = BeaconChainConfig::MAINNET;
+        assert_eq!(config.slot_at(config.genesis_time), Some(0));
+        assert_eq!(config.slot_at(1718900135), Some(9_339_676));
+        assert_eq!(config.slot_at(config.genesis_time + 13), None);
+        assert_eq!(config.slot_at(config.genesis_time - 12), None);
+    }
+}
@@ -0,0 +1,34 @@
+//! Error types emitted by types or implementations of this crate.
+
+use reqwest::StatusCode;
+use url::Url;
+
+/// Possible errors when interacting with a relay.
+#[derive(Debug, thiserror::Error)]
+pub enum RelayError {
+    /// The request to the relay failed.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The relay rejected the request.
+    #[error("relay {relay} responded with {status}: {message}")]
+    Rejected {
+        /// The URL of the relay.
+        relay: Url,
+        /// The status code of the response.
+        status: StatusCode,
+        /// The error message returned by the relay.
+        message: String,
+    },
+    /// The registration server failed to listen on its address.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The relay URL can not be joined with the endpoint path.
+    #[error(transparent)]
+    InvalidUrl(#[from] url::ParseError),
+    /// The builder secret key is not a valid BLS secret key.
+    #[error("invalid BLS secret key")]
+    InvalidSecretKey,
+    /// The payload can not be submitted with the builder API.
+    #[error("unsupported payload for block {0}, the builder API supports Bellatrix to Deneb")]
+    UnsupportedPayload(u64),
+}
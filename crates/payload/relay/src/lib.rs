@@ -0,0 +1,41 @@
+//! Submission of locally built payloads to MEV-boost relays.
+//!
+//! This allows a node to act as a minimal self-builder for its own validators: whenever the
+//! consensus layer starts a payload job for a slot whose proposer registered with the configured
+//! relays, the best payload built so far is signed and submitted to those relays via the
+//! [builder API](https://flashbots.github.io/relay-specs/) until the slot starts.
+//!
+//! The bids accepted by the relays are published to the subscribers of
+//! [`RelaySubmitter::subscribe_bids`].
+//!
+//! Validator registrations can be forwarded to all configured relays with
+//! [`Relays::register_validators`], or by pointing the builder endpoint of the consensus layer
+//! client at a [`RegistrationServer`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod client;
+pub use client::{RelayClient, Relays, SignedBidSubmission};
+
+mod config;
+pub use config::{BeaconChainConfig, RelayConfig, DEFAULT_SUBMISSION_INTERVAL};
+
+mod error;
+pub use error::RelayError;
+
+mod metrics;
+
+mod server;
+pub use server::RegistrationServer;
+
+mod service;
+pub use service::{PublishedBid, RelaySubmitter};
+
+mod signer;
+pub use signer::BuilderSigner;
@@ -0,0 +1,20 @@
+//! Relay submission metrics.
+
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+
+/// Relay submission metrics
+#[derive(Metrics, Clone)]
+#[metrics(scope = "payloads.relay")]
+pub(crate) struct RelaySubmitterMetrics {
+    /// Total number of payload jobs whose payloads were submitted to relays
+    pub(crate) submission_jobs: Counter,
+    /// Total number of bids accepted by relays
+    pub(crate) accepted_bids: Counter,
+    /// Total number of bids rejected by relays or failed to be delivered
+    pub(crate) failed_bids: Counter,
+    /// Value of the last bid accepted by a relay
+    pub(crate) last_bid_value: Gauge,
+}
@@ -0,0 +1,164 @@
+use crate::{RelayError, Relays};
+use http::{header::CONTENT_TYPE, HeaderValue, Method, Request, Response, StatusCode};
+use jsonrpsee::core::http_helpers::read_body;
+use reth_rpc_types::beacon::relay::ValidatorRegistration;
+use serde::Serialize;
+use std::{convert::Infallible, net::SocketAddr};
+use tracing::{debug, info, warn};
+
+/// The endpoint for registering validators.
+const REGISTER_VALIDATORS_PATH: &str = "/eth/v1/builder/validators";
+
+/// The endpoint for the status of the builder.
+const STATUS_PATH: &str = "/eth/v1/builder/status";
+
+/// The prefix of the endpoint for the bid of a slot.
+const HEADER_PATH_PREFIX: &str = "/eth/v1/builder/header/";
+
+/// The maximum size of a registration request, which contains the registrations of all validators
+/// of the consensus layer client.
+const MAX_REQUEST_BODY_SIZE: u32 = 10 * 1024 * 1024;
+
+/// The error response of the builder API.
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+/// Serves the validator registration endpoints of the builder API and forwards the registrations
+/// to the relays.
+///
+/// The consensus layer client uses this server as its builder endpoint, like it would use
+/// MEV-boost, so that its proposers are registered with the relays that the payloads are
+/// submitted to. Requests for bids are answered with `204 No Content`, which makes the proposer
+/// use the payload of the local execution client.
+#[derive(Debug, Clone)]
+pub struct RegistrationServer {
+    relays: Relays,
+}
+
+impl RegistrationServer {
+    /// Creates a new server that forwards the registrations to the given relays.
+    pub const fn new(relays: Relays) -> Self {
+        Self { relays }
+    }
+
+    /// Serves the builder API on the given address until the task is dropped.
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), RelayError> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        info!(target: "payload::relay", %addr, "Serving validator registrations");
+
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _remote_addr)) => stream,
+                Err(error) => {
+                    warn!(target: "payload::relay", %error, "Failed to accept connection");
+                    continue
+                }
+            };
+
+            let server = self.clone();
+            let service = tower::service_fn(move |request| {
+                let server = server.clone();
+                async move { Ok::<_, Infallible>(server.handle(request).await) }
+            });
+            tokio::spawn(async move {
+                if let Err(error) = jsonrpsee::server::serve(stream, service).await {
+                    debug!(target: "payload::relay", %error, "Failed to serve request")
+                }
+            });
+        }
+    }
+
+    async fn handle<B>(&self, request: Request<B>) -> Response<String>
+    where
+        B: http_body::Body<Data = bytes::Bytes> + Send + 'static,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        match (request.method(), request.uri().path()) {
+            (&Method::GET, STATUS_PATH) => Response::new(String::new()),
+            (&Method::GET, path) if path.starts_with(HEADER_PATH_PREFIX) => {
+                response(StatusCode::NO_CONTENT, String::new())
+            }
+            (&Method::POST, REGISTER_VALIDATORS_PATH) => {
+                let (parts, body) = request.into_parts();
+                let registrations = match read_body(&parts.headers, body, MAX_REQUEST_BODY_SIZE)
+                    .await
+                    .map_err(|error| error.to_string())
+                    .and_then(|(body, _)| {
+                        serde_json::from_slice::<Vec<ValidatorRegistration>>(&body)
+                            .map_err(|error| error.to_string())
+                    }) {
+                    Ok(registrations) => registrations,
+                    Err(message) => return error_response(StatusCode::BAD_REQUEST, message),
+                };
+
+                match self.relays.register_validators(&registrations).await {
+                    Ok(()) => {
+                        debug!(target: "payload::relay", validators = registrations.len(), "Forwarded validator registrations");
+                        Response::new(String::new())
+                    }
+                    Err(error) => error_response(StatusCode::BAD_GATEWAY, error.to_string()),
+                }
+            }
+            (_, path) => error_response(StatusCode::NOT_FOUND, format!("unknown endpoint {path}")),
+        }
+    }
+}
+
+fn response(status: StatusCode, body: String) -> Response<String> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response
+}
+
+fn error_response(status: StatusCode, message: String) -> Response<String> {
+    let body = serde_json::to_string(&ErrorResponse { code: status.as_u16(), message })
+        .unwrap_or_default();
+    let mut response = response(status, body);
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, path: &str, body: &str) -> Request<String> {
+        Request::builder().method(method).uri(path).body(body.to_string()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn builder_api_endpoints() {
+        let server = RegistrationServer::new(Relays::default());
+
+        let response = server.handle(request(Method::GET, STATUS_PATH, "")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = server
+            .handle(request(
+                Method::GET,
+                "/eth/v1/builder/header/1/0x0000000000000000000000000000000000000000000000000000000000000000/0x00",
+                "",
+            ))
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = server.handle(request(Method::GET, "/eth/v1/builder/unknown", "")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn forward_registrations() {
+        let server = RegistrationServer::new(Relays::default());
+
+        let response = server.handle(request(Method::POST, REGISTER_VALIDATORS_PATH, "[]")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = server
+            .handle(request(Method::POST, REGISTER_VALIDATORS_PATH, "[{\"message\":{}}]"))
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
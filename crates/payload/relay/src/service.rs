@@ -0,0 +1,321 @@
+use crate::{
+    metrics::RelaySubmitterMetrics, BuilderSigner, RelayConfig, RelayError, Relays,
+    SignedBidSubmission,
+};
+use futures_util::{future::join_all, stream::FuturesUnordered, StreamExt};
+use reth_payload_builder::{PayloadBuilderHandle, PayloadId};
+use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadTypes};
+use reth_primitives::U256;
+use reth_rpc_types::{
+    beacon::relay::{
+        BidTrace, SignedBidSubmissionV1, SignedBidSubmissionV2, SignedBidSubmissionV3, Validator,
+    },
+    engine::{
+        ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3, ExecutionPayloadFieldV2,
+        ExecutionPayloadV1,
+    },
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::{sync::broadcast, time::Instant};
+use tracing::{debug, info, warn};
+use url::Url;
+
+/// The number of published bids that are buffered for slow subscribers.
+const BID_CHANNEL_SIZE: usize = 64;
+
+/// A bid that was accepted by at least one relay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedBid {
+    /// The id of the payload job that built the payload of the bid.
+    pub id: PayloadId,
+    /// The signed bid.
+    pub bid: BidTrace,
+    /// The relays that accepted the bid.
+    pub relays: Vec<Url>,
+}
+
+/// Submits the payloads built by the local payload builder to relays.
+///
+/// For every payload job, the proposer of the slot is looked up in the proposer duties of the
+/// relays. If the proposer registered with a relay and the job builds for the fee recipient of the
+/// registration, the best payload of the job is submitted to that relay whenever its value
+/// improves, until the slot starts. The bids accepted by the relays are published to the
+/// subscribers of [`RelaySubmitter::subscribe_bids`].
+#[derive(Debug)]
+pub struct RelaySubmitter<Engine: PayloadTypes> {
+    /// Handle to the payload builder service.
+    payload_builder: PayloadBuilderHandle<Engine>,
+    /// The relays that payloads are submitted to.
+    relays: Relays,
+    /// Signs the submitted bids.
+    signer: BuilderSigner,
+    /// The submission settings.
+    config: RelayConfig,
+    /// Publishes the bids accepted by the relays.
+    bids: broadcast::Sender<PublishedBid>,
+    /// Submission metrics.
+    metrics: RelaySubmitterMetrics,
+}
+
+impl<Engine> RelaySubmitter<Engine>
+where
+    Engine: PayloadTypes + 'static,
+    Engine::BuiltPayload: Into<ExecutionPayloadV1>
+        + Into<ExecutionPayloadEnvelopeV2>
+        + Into<ExecutionPayloadEnvelopeV3>,
+{
+    /// Creates a new submitter for the payloads of the given payload builder.
+    pub fn new(
+        payload_builder: PayloadBuilderHandle<Engine>,
+        config: RelayConfig,
+        signer: BuilderSigner,
+    ) -> Self {
+        Self {
+            payload_builder,
+            relays: Relays::new(config.relays.clone()),
+            signer,
+            config,
+            bids: broadcast::channel(BID_CHANNEL_SIZE).0,
+            metrics: RelaySubmitterMetrics::default(),
+        }
+    }
+
+    /// Returns the relays that payloads are submitted to.
+    ///
+    /// This can be used to forward validator registrations to the relays.
+    pub const fn relays(&self) -> &Relays {
+        &self.relays
+    }
+
+    /// Returns a receiver of the bids that are accepted by the relays.
+    pub fn subscribe_bids(&self) -> broadcast::Receiver<PublishedBid> {
+        self.bids.subscribe()
+    }
+
+    /// Submits the payloads of all payload jobs until the payload builder shuts down.
+    pub async fn run(self) {
+        let Ok(events) = self.payload_builder.subscribe().await else {
+            warn!(target: "payload::relay", "Payload builder service is not running");
+            return
+        };
+        info!(target: "payload::relay", relays = self.relays.clients().len(), builder = %self.signer.public_key(), "Submitting payloads to relays");
+
+        let mut attributes = events.into_attributes_stream();
+        let mut jobs = FuturesUnordered::new();
+        loop {
+            tokio::select! {
+                attributes = attributes.next() => {
+                    let Some(attributes) = attributes else { break };
+                    jobs.push(self.submit_payloads(attributes));
+                }
+                Some(()) = jobs.next() => {}
+            }
+        }
+    }
+
+    /// Submits the best payload of the job started with the given attributes, whenever it
+    /// improves, until the slot starts.
+    async fn submit_payloads(&self, attributes: Engine::PayloadBuilderAttributes) {
+        let id = attributes.payload_id();
+        let Some(slot) = self.config.beacon.slot_at(attributes.timestamp()) else {
+            debug!(target: "payload::relay", %id, timestamp = attributes.timestamp(), "Payload timestamp is not at the start of a slot");
+            return
+        };
+        let Some(until_slot) = Duration::from_secs(attributes.timestamp())
+            .checked_sub(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default())
+        else {
+            return
+        };
+        let deadline = Instant::now() + until_slot;
+
+        let mut duties = self.relays.proposer_duty(slot).await;
+        let Some((_, duty)) = duties.first() else {
+            debug!(target: "payload::relay", %id, slot, "Proposer is not registered with any relay");
+            return
+        };
+        let duty = duty.clone();
+        if duty.entry.message.fee_recipient != attributes.suggested_fee_recipient() {
+            debug!(
+                target: "payload::relay",
+                %id,
+                slot,
+                registered = %duty.entry.message.fee_recipient,
+                suggested = %attributes.suggested_fee_recipient(),
+                "Payload is not built for the fee recipient of the registered proposer"
+            );
+            return
+        }
+        duties.retain(|(_, relay_duty)| relay_duty.entry.message == duty.entry.message);
+
+        self.metrics.submission_jobs.increment(1);
+        let mut interval = tokio::time::interval(self.config.submission_interval);
+        let mut best_value: Option<U256> = None;
+        loop {
+            interval.tick().await;
+            if Instant::now() >= deadline {
+                break
+            }
+
+            let payload = match self.payload_builder.best_payload(id).await {
+                Some(Ok(payload)) => payload,
+                Some(Err(error)) => {
+                    debug!(target: "payload::relay", %id, %error, "Failed to get best payload");
+                    continue
+                }
+                // the job was resolved or terminated
+                None => break,
+            };
+            let value = payload.fees();
+            if best_value.is_some_and(|best_value| value <= best_value) {
+                continue
+            }
+            best_value = Some(value);
+
+            let submission = match bid_submission(payload, slot, &duty, &self.signer) {
+                Ok(submission) => submission,
+                Err(error) => {
+                    warn!(target: "payload::relay", %id, %error, "Failed to create bid submission");
+                    break
+                }
+            };
+
+            let results =
+                join_all(duties.iter().map(|(relay, _)| relay.submit_bid(&submission))).await;
+            let mut accepted_by = Vec::new();
+            for ((relay, _), result) in duties.iter().zip(results) {
+                match result {
+                    Ok(()) => {
+                        accepted_by.push(relay.url().clone());
+                        self.metrics.accepted_bids.increment(1);
+                        self.metrics.last_bid_value.set(f64::from(value));
+                        debug!(target: "payload::relay", relay = %relay.url(), slot, block_hash = %submission.message().block_hash, %value, "Submitted bid");
+                    }
+                    Err(error) => {
+                        self.metrics.failed_bids.increment(1);
+                        warn!(target: "payload::relay", relay = %relay.url(), slot, %error, "Failed to submit bid");
+                    }
+                }
+            }
+
+            if !accepted_by.is_empty() {
+                // there may be no subscribers
+                let _ = self.bids.send(PublishedBid {
+                    id,
+                    bid: submission.message().clone(),
+                    relays: accepted_by,
+                });
+            }
+        }
+    }
+}
+
+/// Creates the signed bid submission of the payload for the proposer of the given slot.
+fn bid_submission<P>(
+    payload: P,
+    slot: u64,
+    duty: &Validator,
+    signer: &BuilderSigner,
+) -> Result<SignedBidSubmission, RelayError>
+where
+    P: BuiltPayload
+        + Into<ExecutionPayloadV1>
+        + Into<ExecutionPayloadEnvelopeV2>
+        + Into<ExecutionPayloadEnvelopeV3>,
+{
+    let block = payload.block();
+    let message = BidTrace {
+        slot,
+        parent_hash: block.parent_hash,
+        block_hash: block.hash(),
+        builder_pubkey: signer.public_key(),
+        proposer_pubkey: duty.entry.message.pubkey,
+        proposer_fee_recipient: duty.entry.message.fee_recipient,
+        gas_limit: block.gas_limit,
+        gas_used: block.gas_used,
+        value: payload.fees(),
+    };
+    let signature = signer.sign(&message);
+
+    if block.requests_root.is_some() {
+        return Err(RelayError::UnsupportedPayload(block.number))
+    }
+    let submission = if block.parent_beacon_block_root.is_some() {
+        let envelope: ExecutionPayloadEnvelopeV3 = payload.into();
+        SignedBidSubmission::V3(SignedBidSubmissionV3 {
+            message,
+            execution_payload: envelope.execution_payload,
+            blobs_bundle: envelope.blobs_bundle,
+            signature,
+        })
+    } else if block.withdrawals_root.is_some() {
+        let number = block.number;
+        let envelope: ExecutionPayloadEnvelopeV2 = payload.into();
+        let ExecutionPayloadFieldV2::V2(execution_payload) = envelope.execution_payload else {
+            return Err(RelayError::UnsupportedPayload(number))
+        };
+        SignedBidSubmission::V2(SignedBidSubmissionV2 { message, execution_payload, signature })
+    } else {
+        SignedBidSubmission::V1(SignedBidSubmissionV1 {
+            message,
+            execution_payload: payload.into(),
+            signature,
+        })
+    };
+
+    Ok(submission)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BeaconChainConfig;
+    use reth_payload_builder::{EthBuiltPayload, PayloadId};
+    use reth_primitives::{Address, Block, Header, B256};
+    use reth_rpc_types::beacon::relay::{ValidatorRegistration, ValidatorRegistrationMessage};
+
+    #[test]
+    fn cancun_bid_submission() {
+        let signer = BuilderSigner::from_hex(
+            "0x2e0834786285daccd064ca17f1654f67b4aef298acbb82cef9ec422fb4975622",
+            &BeaconChainConfig::MAINNET,
+        )
+        .unwrap();
+        let fee_recipient = Address::with_last_byte(1);
+        let duty = Validator {
+            slot: 10,
+            validator_index: 1,
+            entry: ValidatorRegistration {
+                message: ValidatorRegistrationMessage {
+                    fee_recipient,
+                    gas_limit: 30_000_000,
+                    timestamp: 0,
+                    pubkey: Default::default(),
+                },
+                signature: Default::default(),
+            },
+        };
+        let block = Block {
+            header: Header {
+                beneficiary: fee_recipient,
+                gas_limit: 30_000_000,
+                withdrawals_root: Some(B256::ZERO),
+                parent_beacon_block_root: Some(B256::ZERO),
+                blob_gas_used: Some(0),
+                excess_blob_gas: Some(0),
+                base_fee_per_gas: Some(7),
+                ..Default::default()
+            },
+            withdrawals: Some(Default::default()),
+            ..Default::default()
+        }
+        .seal_slow();
+        let payload = EthBuiltPayload::new(PayloadId::new([0; 8]), block.clone(), U256::from(100));
+
+        let submission = bid_submission(payload, 10, &duty, &signer).unwrap();
+        let SignedBidSubmission::V3(submission) = submission else { panic!("expected deneb bid") };
+        assert_eq!(submission.message.block_hash, block.hash());
+        assert_eq!(submission.message.proposer_fee_recipient, fee_recipient);
+        assert_eq!(submission.message.value, U256::from(100));
+        assert_eq!(submission.signature, signer.sign(&submission.message));
+    }
+}
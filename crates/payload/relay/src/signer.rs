@@ -0,0 +1,141 @@
+use crate::{BeaconChainConfig, RelayError};
+use blst::min_pk::SecretKey;
+use reth_primitives::{hex, B256};
+use reth_rpc_types::beacon::{relay::BidTrace, BlsPublicKey, BlsSignature};
+use sha2::{Digest, Sha256};
+
+/// The domain type of builder API messages, see
+/// <https://github.com/ethereum/builder-specs/blob/main/specs/bellatrix/builder.md#domain-types>
+const DOMAIN_APPLICATION_BUILDER: [u8; 4] = [0, 0, 0, 1];
+
+/// The domain separation tag of BLS signatures on the beacon chain.
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Signs bids with the BLS secret key of the builder.
+#[derive(Clone)]
+pub struct BuilderSigner {
+    secret_key: SecretKey,
+    public_key: BlsPublicKey,
+    domain: B256,
+}
+
+impl BuilderSigner {
+    /// Creates a new signer from the 32 byte BLS secret key.
+    pub fn new(secret_key: &[u8], beacon: &BeaconChainConfig) -> Result<Self, RelayError> {
+        let secret_key =
+            SecretKey::from_bytes(secret_key).map_err(|_| RelayError::InvalidSecretKey)?;
+        let public_key = BlsPublicKey::from(secret_key.sk_to_pk().to_bytes());
+        Ok(Self { secret_key, public_key, domain: builder_domain(beacon.genesis_fork_version) })
+    }
+
+    /// Creates a new signer from the hex encoded BLS secret key.
+    pub fn from_hex(secret_key: &str, beacon: &BeaconChainConfig) -> Result<Self, RelayError> {
+        let secret_key =
+            hex::decode(secret_key.trim()).map_err(|_| RelayError::InvalidSecretKey)?;
+        Self::new(&secret_key, beacon)
+    }
+
+    /// Returns the public key of the builder.
+    pub const fn public_key(&self) -> BlsPublicKey {
+        self.public_key
+    }
+
+    /// Signs the bid with the builder domain.
+    pub fn sign(&self, bid: &BidTrace) -> BlsSignature {
+        let signing_root = hash_pair(bid_trace_root(bid), self.domain);
+        BlsSignature::from(self.secret_key.sign(signing_root.as_slice(), BLS_DST, &[]).to_bytes())
+    }
+}
+
+impl std::fmt::Debug for BuilderSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuilderSigner")
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Computes the builder signing domain, which uses the genesis fork version and an empty genesis
+/// validators root on all networks.
+fn builder_domain(genesis_fork_version: [u8; 4]) -> B256 {
+    let mut version = B256::ZERO;
+    version[..4].copy_from_slice(&genesis_fork_version);
+    let fork_data_root = hash_pair(version, B256::ZERO);
+
+    let mut domain = B256::ZERO;
+    domain[..4].copy_from_slice(&DOMAIN_APPLICATION_BUILDER);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    domain
+}
+
+/// Computes the SSZ hash tree root of the bid.
+fn bid_trace_root(bid: &BidTrace) -> B256 {
+    let uint64 = |value: u64| {
+        let mut chunk = B256::ZERO;
+        chunk[..8].copy_from_slice(&value.to_le_bytes());
+        chunk
+    };
+    let pubkey = |key: &BlsPublicKey| {
+        let mut first = B256::ZERO;
+        let mut second = B256::ZERO;
+        first.copy_from_slice(&key[..32]);
+        second[..16].copy_from_slice(&key[32..]);
+        hash_pair(first, second)
+    };
+    let mut fee_recipient = B256::ZERO;
+    fee_recipient[..20].copy_from_slice(bid.proposer_fee_recipient.as_slice());
+
+    let mut chunks = vec![
+        uint64(bid.slot),
+        bid.parent_hash,
+        bid.block_hash,
+        pubkey(&bid.builder_pubkey),
+        pubkey(&bid.proposer_pubkey),
+        fee_recipient,
+        uint64(bid.gas_limit),
+        uint64(bid.gas_used),
+        B256::from(bid.value.to_le_bytes::<32>()),
+    ];
+    chunks.resize(chunks.len().next_power_of_two(), B256::ZERO);
+    while chunks.len() > 1 {
+        chunks = chunks.chunks(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+    }
+    chunks[0]
+}
+
+fn hash_pair(left: B256, right: B256) -> B256 {
+    B256::from_slice(&Sha256::new().chain_update(left).chain_update(right).finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::b256;
+
+    #[test]
+    fn mainnet_builder_domain() {
+        assert_eq!(
+            builder_domain(BeaconChainConfig::MAINNET.genesis_fork_version),
+            b256!("00000001f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a9")
+        );
+    }
+
+    #[test]
+    fn sign_bid() {
+        let signer = BuilderSigner::from_hex(
+            "0x2e0834786285daccd064ca17f1654f67b4aef298acbb82cef9ec422fb4975622",
+            &BeaconChainConfig::MAINNET,
+        )
+        .unwrap();
+        let bid = BidTrace { slot: 1, builder_pubkey: signer.public_key(), ..Default::default() };
+
+        let signature = blst::min_pk::Signature::from_bytes(signer.sign(&bid).as_slice()).unwrap();
+        let public_key =
+            blst::min_pk::PublicKey::from_bytes(signer.public_key().as_slice()).unwrap();
+        let signing_root = hash_pair(bid_trace_root(&bid), signer.domain);
+        assert_eq!(
+            signature.verify(true, signing_root.as_slice(), BLS_DST, &[], &public_key, true),
+            blst::BLST_ERROR::BLST_SUCCESS
+        );
+    }
+}
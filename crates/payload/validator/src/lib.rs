@@ -9,6 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_consensus::ValidationPolicy;
 use reth_primitives::SealedBlock;
 use reth_rpc_types::{engine::MaybeCancunPayloadFields, ExecutionPayload, PayloadError};
 use reth_rpc_types_compat::engine::payload::try_into_block;
@@ -19,12 +20,20 @@ use std::sync::Arc;
 pub struct ExecutionPayloadValidator {
     /// Chain spec to validate against.
     chain_spec: Arc<ChainSpec>,
+    /// The header checks to enforce.
+    policy: ValidationPolicy,
 }
 
 impl ExecutionPayloadValidator {
     /// Create a new validator.
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self { chain_spec, policy: ValidationPolicy::strict() }
+    }
+
+    /// Sets the [`ValidationPolicy`], which is [`ValidationPolicy::strict`] by default.
+    pub const fn with_validation_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.policy = policy;
+        self
     }
 
     /// Returns the chain spec used by the validator.
@@ -110,14 +119,22 @@ impl ExecutionPayloadValidator {
     /// <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#specification>
     pub fn ensure_well_formed_payload(
         &self,
-        payload: ExecutionPayload,
+        mut payload: ExecutionPayload,
         cancun_fields: MaybeCancunPayloadFields,
     ) -> Result<SealedBlock, PayloadError> {
         let expected_hash = payload.block_hash();
 
+        // The extra data is checked against the policy, since the conversion enforces the limit of
+        // Ethereum
+        let extra_data = std::mem::take(&mut payload.as_v1_mut().extra_data);
+        if self.policy.max_extra_data_size.is_some_and(|max| extra_data.len() > max) {
+            return Err(PayloadError::ExtraData(extra_data))
+        }
+
         // First parse the block
-        let sealed_block =
-            try_into_block(payload, cancun_fields.parent_beacon_block_root())?.seal_slow();
+        let mut block = try_into_block(payload, cancun_fields.parent_beacon_block_root())?;
+        block.header.extra_data = extra_data;
+        let sealed_block = block.seal_slow();
 
         // Ensure the hash included in the payload matches the block hash
         if expected_hash != sealed_block.hash() {
@@ -0,0 +1,104 @@
+//! [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) blob parameters of a fork.
+
+use alloy_eips::eip4844::{
+    BLOB_GASPRICE_UPDATE_FRACTION, BLOB_TX_MIN_BLOB_GASPRICE, DATA_GAS_PER_BLOB,
+    MAX_DATA_GAS_PER_BLOCK, TARGET_DATA_GAS_PER_BLOCK,
+};
+use revm_primitives::fake_exponential;
+use serde::{Deserialize, Serialize};
+
+/// The blob parameters of a fork, which determine how many blobs fit into a block and how the
+/// blob base fee is computed.
+///
+/// The defaults of [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) are [`BlobParams::cancun`],
+/// later forks can change them through the chain spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlobParams {
+    /// The target number of blobs per block.
+    #[serde(rename = "target")]
+    pub target_blob_count: u64,
+    /// The maximum number of blobs per block.
+    #[serde(rename = "max")]
+    pub max_blob_count: u64,
+    /// The update fraction of the blob base fee, which determines its maximum rate of change.
+    #[serde(rename = "baseFeeUpdateFraction")]
+    pub update_fraction: u64,
+}
+
+impl BlobParams {
+    /// Creates new blob parameters.
+    pub const fn new(target_blob_count: u64, max_blob_count: u64, update_fraction: u64) -> Self {
+        Self { target_blob_count, max_blob_count, update_fraction }
+    }
+
+    /// The blob parameters of the Cancun hardfork, as specified by
+    /// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844#parameters).
+    pub const fn cancun() -> Self {
+        Self::new(
+            TARGET_DATA_GAS_PER_BLOCK / DATA_GAS_PER_BLOB,
+            MAX_DATA_GAS_PER_BLOCK / DATA_GAS_PER_BLOB,
+            BLOB_GASPRICE_UPDATE_FRACTION as u64,
+        )
+    }
+
+    /// Returns the maximum blob gas a block can use.
+    pub const fn max_blob_gas_per_block(&self) -> u64 {
+        self.max_blob_count * DATA_GAS_PER_BLOB
+    }
+
+    /// Returns the target blob gas per block.
+    pub const fn target_blob_gas_per_block(&self) -> u64 {
+        self.target_blob_count * DATA_GAS_PER_BLOB
+    }
+
+    /// Calculates the excess blob gas of the next block from the excess blob gas and the blob gas
+    /// used of its parent.
+    pub const fn next_block_excess_blob_gas(
+        &self,
+        parent_excess_blob_gas: u64,
+        parent_blob_gas_used: u64,
+    ) -> u64 {
+        (parent_excess_blob_gas + parent_blob_gas_used)
+            .saturating_sub(self.target_blob_gas_per_block())
+    }
+
+    /// Calculates the blob base fee of a block with the given excess blob gas.
+    pub fn calc_blob_fee(&self, excess_blob_gas: u64) -> u128 {
+        fake_exponential(BLOB_TX_MIN_BLOB_GASPRICE as u64, excess_blob_gas, self.update_fraction)
+    }
+}
+
+impl Default for BlobParams {
+    fn default() -> Self {
+        Self::cancun()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::{calc_blob_gasprice, calc_excess_blob_gas};
+
+    #[test]
+    fn cancun_matches_eip4844() {
+        let params = BlobParams::cancun();
+        assert_eq!(params.max_blob_gas_per_block(), MAX_DATA_GAS_PER_BLOCK);
+        assert_eq!(params.target_blob_gas_per_block(), TARGET_DATA_GAS_PER_BLOCK);
+
+        for (excess, used) in [(0, 0), (0, MAX_DATA_GAS_PER_BLOCK), (10_000_000, 131_072)] {
+            assert_eq!(
+                params.next_block_excess_blob_gas(excess, used),
+                calc_excess_blob_gas(excess, used)
+            );
+            assert_eq!(params.calc_blob_fee(excess), calc_blob_gasprice(excess));
+        }
+    }
+
+    #[test]
+    fn deserialize() {
+        let params: BlobParams =
+            serde_json::from_str(r#"{"target":6,"max":9,"baseFeeUpdateFraction":5007716}"#)
+                .unwrap();
+        assert_eq!(params, BlobParams::new(6, 9, 5007716));
+    }
+}
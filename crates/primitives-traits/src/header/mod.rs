@@ -7,6 +7,7 @@ pub use error::HeaderError;
 #[cfg(any(test, feature = "test-utils", feature = "arbitrary"))]
 pub mod test_utils;
 
+use crate::BlobParams;
 use alloy_consensus::constants::{EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
 use alloy_eips::{
     calc_next_block_base_fee, eip1559::BaseFeeParams, merge::ALLOWED_FUTURE_BLOCK_TIME_SECONDS,
@@ -231,22 +232,43 @@ impl Header {
         self.transactions_root == EMPTY_ROOT_HASH
     }
 
-    /// Returns the blob fee for _this_ block according to the EIP-4844 spec.
+    /// Returns the blob fee for _this_ block according to the EIP-4844 spec, with the blob
+    /// parameters of Cancun.
     ///
     /// Returns `None` if `excess_blob_gas` is None
+    ///
+    /// See also [`Self::blob_fee_with_params`] for chains that change the blob parameters.
     pub fn blob_fee(&self) -> Option<u128> {
         self.excess_blob_gas.map(calc_blob_gasprice)
     }
 
-    /// Returns the blob fee for the next block according to the EIP-4844 spec.
+    /// Returns the blob fee for _this_ block with the given blob parameters.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None
+    pub fn blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        self.excess_blob_gas.map(|excess_blob_gas| blob_params.calc_blob_fee(excess_blob_gas))
+    }
+
+    /// Returns the blob fee for the next block according to the EIP-4844 spec, with the blob
+    /// parameters of Cancun.
     ///
     /// Returns `None` if `excess_blob_gas` is None.
     ///
-    /// See also [`Self::next_block_excess_blob_gas`]
+    /// See also [`Self::next_block_excess_blob_gas`] and [`Self::next_block_blob_fee_with_params`]
     pub fn next_block_blob_fee(&self) -> Option<u128> {
         self.next_block_excess_blob_gas().map(calc_blob_gasprice)
     }
 
+    /// Returns the blob fee for the next block with the given blob parameters of the next block.
+    ///
+    /// Returns `None` if `excess_blob_gas` is None.
+    ///
+    /// See also [`Self::next_block_excess_blob_gas_with_params`]
+    pub fn next_block_blob_fee_with_params(&self, blob_params: BlobParams) -> Option<u128> {
+        self.next_block_excess_blob_gas_with_params(blob_params)
+            .map(|excess_blob_gas| blob_params.calc_blob_fee(excess_blob_gas))
+    }
+
     /// Calculate base fee for next block according to the EIP-1559 spec.
     ///
     /// Returns a `None` if no base fee is set, no EIP-1559 support
@@ -259,13 +281,22 @@ impl Header {
         ) as u64)
     }
 
-    /// Calculate excess blob gas for the next block according to the EIP-4844 spec.
+    /// Calculate excess blob gas for the next block according to the EIP-4844 spec, with the blob
+    /// parameters of Cancun.
     ///
     /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
     pub fn next_block_excess_blob_gas(&self) -> Option<u64> {
         Some(calc_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
 
+    /// Calculate excess blob gas for the next block with the given blob parameters of the next
+    /// block.
+    ///
+    /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
+    pub fn next_block_excess_blob_gas_with_params(&self, blob_params: BlobParams) -> Option<u64> {
+        Some(blob_params.next_block_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
+    }
+
     /// Seal the header with a known hash.
     ///
     /// WARNING: This method does not perform validation whether the hash is correct.
@@ -0,0 +1,19 @@
+use alloy_primitives::{Address, U256};
+use reth_codecs::{add_arbitrary_tests, Compact};
+use serde::{Deserialize, Serialize};
+
+/// An ETH transfer between two accounts made by a message call, a contract creation or a
+/// selfdestruct within a transaction.
+///
+/// The value transfer of the transaction itself is not an internal transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct InternalTransfer {
+    /// Sender of the transfer.
+    pub from: Address,
+    /// Receiver of the transfer.
+    pub to: Address,
+    /// Transferred value in wei.
+    pub value: U256,
+}
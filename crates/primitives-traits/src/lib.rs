@@ -42,6 +42,15 @@ pub use log::{logs_bloom, Log, LogData};
 mod storage;
 pub use storage::StorageEntry;
 
+mod blob_params;
+pub use blob_params::BlobParams;
+
+mod internal_transfer;
+pub use internal_transfer::InternalTransfer;
+
+mod reorg;
+pub use reorg::ReorgRecord;
+
 /// Common header types
 pub mod header;
 #[cfg(any(test, feature = "arbitrary", feature = "test-utils"))]
@@ -0,0 +1,43 @@
+use alloy_primitives::{BlockNumber, B256};
+use reth_codecs::{add_arbitrary_tests, Compact};
+use serde::{Deserialize, Serialize};
+
+/// A reorg of the canonical chain observed by the node.
+///
+/// The blocks of the old chain above the fork block were discarded and replaced by the blocks of
+/// the new chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct ReorgRecord {
+    /// Unix timestamp in milliseconds at which the reorg was observed.
+    pub observed_at: u64,
+    /// Number of the last block shared by the old and the new chain.
+    pub fork_block_number: BlockNumber,
+    /// Hash of the last block shared by the old and the new chain.
+    pub fork_block_hash: B256,
+    /// Hash of the tip of the old chain.
+    pub old_tip_hash: B256,
+    /// Hash of the tip of the new chain.
+    pub new_tip_hash: B256,
+    /// Number of blocks of the old chain that were discarded, which is the depth of the reorg.
+    pub discarded_blocks: u64,
+    /// Number of blocks of the new chain that became canonical.
+    pub added_blocks: u64,
+    /// Number of transactions of the discarded blocks.
+    pub discarded_transactions: u64,
+    /// Number of transactions of the discarded blocks that are included in the new chain, or in
+    /// one of the blocks that followed it.
+    pub reincluded_transactions: u64,
+    /// Milliseconds between the timestamp of the earliest block of either chain above the fork
+    /// block and the time the reorg was observed, which is how long the fork lasted.
+    pub resolution_duration: u64,
+}
+
+impl ReorgRecord {
+    /// Returns the number of transactions of the discarded blocks that are not included in the
+    /// new chain.
+    pub const fn dropped_transactions(&self) -> u64 {
+        self.discarded_transactions.saturating_sub(self.reincluded_transactions)
+    }
+}
@@ -1,11 +1,14 @@
 //! [EIP-4895](https://eips.ethereum.org/EIPS/eip-4895) Withdrawal types.
 
+use alloy_primitives::{Address, U256};
 use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
 use derive_more::{AsRef, Deref, DerefMut, From, IntoIterator};
 use reth_codecs::{add_arbitrary_tests, Compact};
 
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
 
 /// Re-export from `alloy_eips`.
 #[doc(inline)]
@@ -68,6 +71,17 @@ impl Withdrawals {
     pub fn into_inner(self) -> Vec<Withdrawal> {
         self.0
     }
+
+    /// Returns the balance increments in wei that the withdrawals credit to each address.
+    ///
+    /// Zero-valued withdrawals are filtered out.
+    pub fn balance_increments(&self) -> BTreeMap<Address, U256> {
+        let mut balance_increments = BTreeMap::new();
+        for withdrawal in self.iter().filter(|withdrawal| withdrawal.amount > 0) {
+            *balance_increments.entry(withdrawal.address).or_default() += withdrawal.amount_wei();
+        }
+        balance_increments
+    }
 }
 
 impl<'a> IntoIterator for &'a Withdrawals {
@@ -132,6 +146,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_withdrawal_balance_increments() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let withdrawal =
+            |index, address, amount| Withdrawal { index, validator_index: index, address, amount };
+        let withdrawals =
+            Withdrawals::new(vec![withdrawal(0, a, 1), withdrawal(1, b, 0), withdrawal(2, a, 2)]);
+
+        assert_eq!(
+            withdrawals.balance_increments(),
+            BTreeMap::from([(a, U256::from(3_000_000_000u64))])
+        );
+    }
+
     // <https://github.com/paradigmxyz/reth/issues/1614>
     #[test]
     fn test_withdrawal_serde_roundtrip() {
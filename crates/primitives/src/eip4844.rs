@@ -1,6 +1,7 @@
 //! Helpers for working with EIP-4844 blob fee.
 
-// re-exports from revm for calculating blob fee
+// re-exports from revm for calculating blob fee with the blob parameters of Cancun, see
+// [`BlobParams`](crate::BlobParams) for the blob fee of other forks
 pub use crate::revm_primitives::{
     calc_blob_gasprice, calc_excess_blob_gas as calculate_excess_blob_gas,
 };
@@ -53,8 +53,9 @@ pub use receipt::{
     gas_spent_by_transactions, Receipt, ReceiptWithBloom, ReceiptWithBloomRef, Receipts,
 };
 pub use reth_primitives_traits::{
-    logs_bloom, Account, Bytecode, GotExpected, GotExpectedBoxed, Header, HeaderError, Log,
-    LogData, Request, Requests, SealedHeader, StorageEntry, Withdrawal, Withdrawals,
+    logs_bloom, Account, BlobParams, Bytecode, GotExpected, GotExpectedBoxed, Header, HeaderError,
+    InternalTransfer, Log, LogData, ReorgRecord, Request, Requests, SealedHeader, StorageEntry,
+    Withdrawal, Withdrawals,
 };
 pub use static_file::StaticFileSegment;
 
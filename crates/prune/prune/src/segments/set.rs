@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, InternalTransfers, LogIndex, ReceiptsByLogs, Segment, SenderRecovery,
+    StorageHistory, TransactionLookup, UserReceipts,
 };
 use reth_db_api::database::Database;
 use reth_provider::providers::StaticFileProvider;
@@ -48,10 +48,13 @@ impl<DB: Database> SegmentSet<DB> {
         let PruneModes {
             sender_recovery,
             transaction_lookup,
+            transaction_lookup_allowlist,
             receipts,
             account_history,
             storage_history,
             receipts_log_filter,
+            internal_transfers,
+            log_index,
         } = prune_modes;
 
         Self::default()
@@ -65,6 +68,8 @@ impl<DB: Database> SegmentSet<DB> {
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Log index, which is read from the receipts, so it's pruned before them
+            .segment_opt(log_index.map(LogIndex::new))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
@@ -73,9 +78,13 @@ impl<DB: Database> SegmentSet<DB> {
                     .then(|| ReceiptsByLogs::new(receipts_log_filter.clone())),
             )
             // Transaction lookup
-            .segment_opt(transaction_lookup.map(TransactionLookup::new))
+            .segment_opt(transaction_lookup.map(|mode| {
+                TransactionLookup::new(mode).with_allowlist(transaction_lookup_allowlist)
+            }))
             // Sender recovery
             .segment_opt(sender_recovery.map(SenderRecovery::new))
+            // Internal transfers
+            .segment_opt(internal_transfers.map(InternalTransfers::new))
     }
 }
 
@@ -9,6 +9,7 @@ use reth_db_api::{
     DatabaseError,
 };
 use reth_provider::DatabaseProviderRW;
+use reth_prune_types::PruneLimiter;
 
 enum PruneShardOutcome {
     Deleted,
@@ -72,6 +73,47 @@ where
     Ok(outcomes)
 }
 
+/// Prune the block numbers up to and including `to_block` from all shards of a history table.
+///
+/// Every deleted shard is counted by the limiter. Returns total number of deleted, updated and
+/// unchanged entities, and `true` if the whole table was walked before the limit was reached.
+pub(crate) fn prune_all_history_indices<DB, T, SK>(
+    provider: &DatabaseProviderRW<DB>,
+    to_block: BlockNumber,
+    limiter: &mut PruneLimiter,
+    key_matches: impl Fn(&T::Key, &T::Key) -> bool,
+) -> Result<(PrunedIndices, bool), DatabaseError>
+where
+    DB: Database,
+    T: Table<Value = BlockNumberList>,
+    T::Key: AsRef<ShardedKey<SK>>,
+{
+    let mut outcomes = PrunedIndices::default();
+    let mut cursor = provider.tx_ref().cursor_write::<RawTable<T>>()?;
+
+    let mut shard = cursor.first()?;
+    while let Some((key, block_nums)) =
+        shard.map(|(k, v)| Result::<_, DatabaseError>::Ok((k.key()?, v))).transpose()?
+    {
+        if limiter.is_limit_reached() {
+            return Ok((outcomes, false))
+        }
+
+        match prune_shard(&mut cursor, key, block_nums, to_block, &key_matches)? {
+            PruneShardOutcome::Deleted => {
+                outcomes.deleted += 1;
+                limiter.increment_deleted_entries_count();
+            }
+            PruneShardOutcome::Updated => outcomes.updated += 1,
+            PruneShardOutcome::Unchanged => outcomes.unchanged += 1,
+        }
+
+        shard = cursor.next()?;
+    }
+
+    Ok((outcomes, true))
+}
+
 /// Prunes one shard of a history table.
 ///
 /// 1. If the shard has `highest_block_number` less than or equal to the target block number for
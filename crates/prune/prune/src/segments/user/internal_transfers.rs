@@ -0,0 +1,163 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use reth_db::tables;
+use reth_db_api::database::Database;
+use reth_provider::{DatabaseProviderRW, TransactionsProvider};
+use reth_prune_types::{
+    PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+#[derive(Debug)]
+pub struct InternalTransfers {
+    mode: PruneMode,
+}
+
+impl InternalTransfers {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for InternalTransfers {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::InternalTransfers
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        let tx_range = match input.get_next_tx_num_range(provider)? {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No internal transfers to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let tx_range_end = *tx_range.end();
+
+        let mut limiter = input.limiter;
+
+        let mut last_pruned_entry = None;
+        let (pruned, done) = provider.prune_table_with_range::<tables::InternalTransfers>(
+            tx_range,
+            &mut limiter,
+            |_| false,
+            |row| last_pruned_entry = Some(row.0),
+        )?;
+        trace!(target: "pruner", %pruned, %done, "Pruned internal transfers");
+
+        // Not every transaction has internal transfers, so the whole range is pruned once there
+        // are no entries left in it.
+        let last_pruned_transaction = last_pruned_entry.filter(|_| !done).unwrap_or(tx_range_end);
+
+        let last_pruned_block = provider
+            .transaction_block(last_pruned_transaction)?
+            .ok_or(PrunerError::InconsistentData("Block for transaction is not found"))?
+            // If there's more internal transfers to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its internal transfers on the next run.
+            .checked_sub(if done { 0 } else { 1 });
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: Some(last_pruned_transaction),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{InternalTransfers, PruneInput, Segment};
+    use alloy_primitives::{Address, B256, U256};
+    use reth_db::tables;
+    use reth_db_api::{models::StoredInternalTransfers, transaction::DbTxMut};
+    use reth_primitives::InternalTransfer;
+    use reth_provider::PruneCheckpointReader;
+    use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneMode, PruneSegment};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::{generators, generators::random_block_range};
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=10, B256::ZERO, 2..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // only every other transaction makes internal transfers
+        let tx_count = blocks.iter().map(|block| block.body.len() as u64).sum::<u64>();
+        db.commit(|tx| {
+            for tx_number in (0..tx_count).step_by(2) {
+                let transfer = InternalTransfer {
+                    from: Address::random(),
+                    to: Address::random(),
+                    value: U256::from(tx_number + 1),
+                };
+                tx.put::<tables::InternalTransfers>(
+                    tx_number,
+                    StoredInternalTransfers { transfers: vec![transfer] },
+                )?;
+            }
+            Ok(())
+        })
+        .expect("insert internal transfers");
+
+        let to_block = 6;
+        let prune_mode = PruneMode::Before(to_block);
+        let segment = InternalTransfers::new(prune_mode);
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+
+        let checkpoint = result.checkpoint.unwrap().as_prune_checkpoint(prune_mode);
+        segment.save_checkpoint(&provider, checkpoint).unwrap();
+        provider.commit().expect("commit");
+
+        let last_pruned_tx_number = blocks
+            .iter()
+            .take(to_block as usize)
+            .map(|block| block.body.len() as u64)
+            .sum::<u64>() -
+            1;
+        let remaining = db.table::<tables::InternalTransfers>().unwrap();
+        assert_eq!(result.pruned, (last_pruned_tx_number as usize + 2) / 2);
+        assert!(remaining.iter().all(|(tx_number, _)| *tx_number > last_pruned_tx_number));
+        assert_eq!(remaining.len() as u64, (tx_count + 1) / 2 - result.pruned as u64);
+
+        assert_eq!(
+            db.factory
+                .provider()
+                .unwrap()
+                .get_prune_checkpoint(PruneSegment::InternalTransfers)
+                .unwrap(),
+            Some(PruneCheckpoint {
+                block_number: Some(to_block),
+                tx_number: Some(last_pruned_tx_number),
+                prune_mode
+            })
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use crate::{
+    segments::{
+        user::history::{prune_all_history_indices, prune_history_indices},
+        PruneInput, Segment,
+    },
+    PrunerError,
+};
+use reth_db::tables;
+use reth_db_api::{database::Database, models::ShardedKey};
+use reth_provider::{DatabaseProviderRW, PruneCheckpointReader};
+use reth_prune_types::{
+    PruneInterruptReason, PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput,
+    SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+/// Prunes the [`tables::LogAddressHistory`] and [`tables::LogTopicHistory`] indices.
+///
+/// The addresses and topics to prune are read from the receipts of the pruned blocks, so the
+/// segment runs before the receipts are pruned. The indices of the blocks whose receipts were
+/// already pruned, e.g. by a previous run that was interrupted by the limiter, are pruned by
+/// walking the whole index tables.
+#[derive(Debug)]
+pub struct LogIndex {
+    mode: PruneMode,
+}
+
+impl LogIndex {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for LogIndex {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::LogIndex
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No log index to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                PruneInterruptReason::new(&limiter),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let mut pruned = 0;
+        let mut last_pruned_block = None;
+
+        // The logs of the blocks up to the receipts checkpoint can't be read anymore, so their
+        // indices are pruned from all shards.
+        let receipts_checkpoint = provider
+            .get_prune_checkpoint(PruneSegment::Receipts)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .filter(|block_number| block_number >= range.start());
+        if let Some(receipts_checkpoint) = receipts_checkpoint {
+            let to_block = receipts_checkpoint.min(range_end);
+
+            let (addresses, addresses_done) =
+                prune_all_history_indices::<DB, tables::LogAddressHistory, _>(
+                    provider,
+                    to_block,
+                    &mut limiter,
+                    |a, b| a.key == b.key,
+                )?;
+            trace!(target: "pruner", ?addresses, done = %addresses_done, "Pruned log index of pruned receipts (addresses)");
+            pruned += addresses.deleted;
+
+            let mut done = addresses_done;
+
+            if done {
+                let (topics, topics_done) =
+                    prune_all_history_indices::<DB, tables::LogTopicHistory, _>(
+                        provider,
+                        to_block,
+                        &mut limiter,
+                        |a, b| a.key == b.key,
+                    )?;
+                trace!(target: "pruner", ?topics, done = %topics_done, "Pruned log index of pruned receipts (topics)");
+                pruned += topics.deleted;
+                done = topics_done;
+            }
+
+            if !done {
+                return Ok(SegmentOutput {
+                    progress: PruneProgress::new(false, &limiter),
+                    pruned,
+                    checkpoint: input
+                        .previous_checkpoint
+                        .map(SegmentOutputCheckpoint::from_prune_checkpoint),
+                })
+            }
+            last_pruned_block = Some(to_block);
+        }
+
+        // The remaining blocks are pruned one by one, so the checkpoint can be saved at any block
+        // once the limit is reached.
+        let start = last_pruned_block.map_or(*range.start(), |block_number| block_number + 1);
+        let mut done = true;
+        for block_number in start..=range_end {
+            if limiter.is_limit_reached() {
+                done = false;
+                break
+            }
+
+            let keys = provider.log_index_keys_with_range(block_number..=block_number)?;
+
+            let addresses = prune_history_indices::<DB, tables::LogAddressHistory, _>(
+                provider,
+                keys.addresses.into_keys().map(|address| ShardedKey::new(address, block_number)),
+                |a, b| a.key == b.key,
+            )?;
+            let topics = prune_history_indices::<DB, tables::LogTopicHistory, _>(
+                provider,
+                keys.topics.into_keys().map(|topic| ShardedKey::new(topic, block_number)),
+                |a, b| a.key == b.key,
+            )?;
+            trace!(target: "pruner", block_number, ?addresses, ?topics, "Pruned log index");
+
+            limiter.increment_deleted_entries_count_by(addresses.deleted + topics.deleted);
+            pruned += addresses.deleted + topics.deleted;
+            last_pruned_block = Some(block_number);
+        }
+
+        Ok(SegmentOutput {
+            progress: PruneProgress::new(done, &limiter),
+            pruned,
+            checkpoint: last_pruned_block
+                .map(|block_number| SegmentOutputCheckpoint {
+                    block_number: Some(block_number),
+                    tx_number: None,
+                })
+                .or_else(|| {
+                    input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint)
+                }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{LogIndex, PruneInput, Segment};
+    use alloy_primitives::{Address, B256};
+    use reth_db::{tables, BlockNumberList};
+    use reth_db_api::{
+        models::{ShardedKey, StoredBlockBodyIndices},
+        transaction::DbTxMut,
+    };
+    use reth_primitives::{Log, Receipt};
+    use reth_provider::{PruneCheckpointReader, PruneCheckpointWriter};
+    use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneMode, PruneSegment};
+    use reth_stages::test_utils::TestStageDB;
+
+    /// Inserts blocks `0..=10` with a single transaction with a single log each, and their log
+    /// index.
+    fn insert_indexed_logs(db: &TestStageDB) {
+        let (address, topic) = (Address::random(), B256::random());
+        db.commit(|tx| {
+            for block in 0..=10 {
+                tx.put::<tables::BlockBodyIndices>(
+                    block,
+                    StoredBlockBodyIndices { first_tx_num: block, tx_count: 1 },
+                )?;
+                tx.put::<tables::Receipts>(
+                    block,
+                    Receipt {
+                        logs: vec![Log::new_unchecked(address, vec![topic], Default::default())],
+                        ..Default::default()
+                    },
+                )?;
+            }
+            tx.put::<tables::LogAddressHistory>(
+                ShardedKey::new(address, 5),
+                BlockNumberList::new_pre_sorted([0, 1, 2, 3, 4, 5]),
+            )?;
+            tx.put::<tables::LogAddressHistory>(
+                ShardedKey::new(address, u64::MAX),
+                BlockNumberList::new_pre_sorted([6, 7, 8, 9, 10]),
+            )?;
+            tx.put::<tables::LogTopicHistory>(
+                ShardedKey::new(topic, u64::MAX),
+                BlockNumberList::new_pre_sorted((0..=10).collect::<Vec<_>>()),
+            )?;
+            Ok(())
+        })
+        .expect("insert receipts and log index");
+    }
+
+    fn blocks<K>(table: Vec<(K, BlockNumberList)>) -> Vec<u64> {
+        table.into_iter().flat_map(|(_, list)| list.iter().collect::<Vec<_>>()).collect()
+    }
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        insert_indexed_logs(&db);
+
+        let to_block = 6;
+        let prune_mode = PruneMode::Before(to_block + 1);
+        let segment = LogIndex::new(prune_mode);
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+        assert_eq!(result.pruned, 1);
+
+        let checkpoint = result.checkpoint.unwrap().as_prune_checkpoint(prune_mode);
+        segment.save_checkpoint(&provider, checkpoint).unwrap();
+        provider.commit().expect("commit");
+
+        assert_eq!(
+            blocks(db.table::<tables::LogAddressHistory>().unwrap()),
+            Vec::from_iter(7..=10)
+        );
+        assert_eq!(blocks(db.table::<tables::LogTopicHistory>().unwrap()), Vec::from_iter(7..=10));
+
+        assert_eq!(
+            db.factory.provider().unwrap().get_prune_checkpoint(PruneSegment::LogIndex).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(to_block), tx_number: None, prune_mode })
+        );
+    }
+
+    #[test]
+    fn prune_with_limiter() {
+        let db = TestStageDB::default();
+        insert_indexed_logs(&db);
+
+        let to_block = 6;
+        let prune_mode = PruneMode::Before(to_block + 1);
+        let segment = LogIndex::new(prune_mode);
+
+        // the first shard of the address is deleted at block 5, which reaches the limit
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(1),
+        };
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(!result.progress.is_finished());
+        assert_eq!(result.pruned, 1);
+
+        let checkpoint = result.checkpoint.unwrap().as_prune_checkpoint(prune_mode);
+        assert_eq!(checkpoint.block_number, Some(5));
+        segment.save_checkpoint(&provider, checkpoint).unwrap();
+        provider.commit().expect("commit");
+
+        assert_eq!(
+            blocks(db.table::<tables::LogAddressHistory>().unwrap()),
+            Vec::from_iter(6..=10)
+        );
+        assert_eq!(blocks(db.table::<tables::LogTopicHistory>().unwrap()), Vec::from_iter(6..=10));
+
+        // the next run continues after the checkpoint
+        let input = PruneInput {
+            previous_checkpoint: Some(checkpoint),
+            to_block,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(1),
+        };
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+        assert_eq!(result.pruned, 0);
+        assert_eq!(result.checkpoint.unwrap().block_number, Some(to_block));
+        provider.commit().expect("commit");
+
+        assert_eq!(
+            blocks(db.table::<tables::LogAddressHistory>().unwrap()),
+            Vec::from_iter(7..=10)
+        );
+        assert_eq!(blocks(db.table::<tables::LogTopicHistory>().unwrap()), Vec::from_iter(7..=10));
+    }
+
+    #[test]
+    fn prune_with_pruned_receipts() {
+        let db = TestStageDB::default();
+        insert_indexed_logs(&db);
+
+        // the receipts of the blocks up to 3 were pruned before their log index
+        let receipts_mode = PruneMode::Before(4);
+        db.commit(|tx| {
+            for tx_number in 0..=3 {
+                tx.delete::<tables::Receipts>(tx_number, None)?;
+            }
+            Ok(())
+        })
+        .expect("delete receipts");
+        let provider = db.factory.provider_rw().unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::Receipts,
+                PruneCheckpoint {
+                    block_number: Some(3),
+                    tx_number: Some(3),
+                    prune_mode: receipts_mode,
+                },
+            )
+            .unwrap();
+        provider.commit().expect("commit");
+
+        let to_block = 6;
+        let prune_mode = PruneMode::Before(to_block + 1);
+        let segment = LogIndex::new(prune_mode);
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+        assert_eq!(result.pruned, 1);
+        assert_eq!(result.checkpoint.unwrap().block_number, Some(to_block));
+        provider.commit().expect("commit");
+
+        assert_eq!(
+            blocks(db.table::<tables::LogAddressHistory>().unwrap()),
+            Vec::from_iter(7..=10)
+        );
+        assert_eq!(blocks(db.table::<tables::LogTopicHistory>().unwrap()), Vec::from_iter(7..=10));
+    }
+}
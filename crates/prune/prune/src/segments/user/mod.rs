@@ -1,5 +1,7 @@
 mod account_history;
 mod history;
+mod internal_transfers;
+mod log_index;
 mod receipts;
 mod receipts_by_logs;
 mod sender_recovery;
@@ -7,6 +9,8 @@ mod storage_history;
 mod transaction_lookup;
 
 pub use account_history::AccountHistory;
+pub use internal_transfers::InternalTransfers;
+pub use log_index::LogIndex;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
 pub use sender_recovery::SenderRecovery;
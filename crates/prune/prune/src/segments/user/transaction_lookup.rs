@@ -2,6 +2,7 @@ use crate::{
     segments::{PruneInput, Segment, SegmentOutput},
     PrunerError,
 };
+use alloy_primitives::Address;
 use rayon::prelude::*;
 use reth_db::tables;
 use reth_db_api::database::Database;
@@ -9,16 +10,25 @@ use reth_provider::{DatabaseProviderRW, TransactionsProvider};
 use reth_prune_types::{
     PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutputCheckpoint,
 };
+use std::collections::BTreeSet;
 use tracing::{instrument, trace};
 
 #[derive(Debug)]
 pub struct TransactionLookup {
     mode: PruneMode,
+    /// Addresses whose transactions retain their lookup entries.
+    allowlist: BTreeSet<Address>,
 }
 
 impl TransactionLookup {
     pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+        Self { mode, allowlist: BTreeSet::new() }
+    }
+
+    /// Retains the lookup entries of transactions sent to the given addresses.
+    pub fn with_allowlist(mut self, allowlist: BTreeSet<Address>) -> Self {
+        self.allowlist = allowlist;
+        self
     }
 }
 
@@ -56,35 +66,42 @@ impl<DB: Database> Segment<DB> for TransactionLookup {
         let tx_range_end = *tx_range.end();
 
         // Retrieve transactions in the range and calculate their hashes in parallel
-        let hashes = provider
-            .transactions_by_tx_range(tx_range.clone())?
-            .into_par_iter()
-            .map(|transaction| transaction.hash())
-            .collect::<Vec<_>>();
+        let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
 
         // Number of transactions retrieved from the database should match the tx range count
         let tx_count = tx_range.count();
-        if hashes.len() != tx_count {
+        if transactions.len() != tx_count {
             return Err(PrunerError::InconsistentData(
                 "Unexpected number of transaction hashes retrieved by transaction number range",
             ))
         }
 
+        let hashes = transactions
+            .into_par_iter()
+            .filter(|transaction| !transaction.to().is_some_and(|to| self.allowlist.contains(&to)))
+            .map(|transaction| transaction.hash())
+            .collect::<Vec<_>>();
+
         let mut limiter = input.limiter;
 
         let mut last_pruned_transaction = None;
-        let (pruned, done) = provider.prune_table_with_iterator::<tables::TransactionHashNumbers>(
-            hashes,
-            &mut limiter,
-            |row| {
-                last_pruned_transaction = Some(last_pruned_transaction.unwrap_or(row.1).max(row.1))
-            },
-        )?;
-
-        let done = done && tx_range_end == end;
+        let (pruned, range_done) = provider
+            .prune_table_with_iterator::<tables::TransactionHashNumbers>(
+                hashes,
+                &mut limiter,
+                |row| {
+                    last_pruned_transaction =
+                        Some(last_pruned_transaction.unwrap_or(row.1).max(row.1))
+                },
+            )?;
+
+        let done = range_done && tx_range_end == end;
         trace!(target: "pruner", %pruned, %done, "Pruned transaction lookup");
 
-        let last_pruned_transaction = last_pruned_transaction.unwrap_or(tx_range_end);
+        // The entries of allowlisted transactions are retained, so the last deleted entry can be
+        // below the end of the range even though the whole range was processed.
+        let last_pruned_transaction =
+            if range_done { tx_range_end } else { last_pruned_transaction.unwrap_or(tx_range_end) };
 
         let last_pruned_block = provider
             .transaction_block(last_pruned_transaction)?
@@ -123,7 +140,7 @@ mod tests {
     };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_testing_utils::{generators, generators::random_block_range};
-    use std::ops::Sub;
+    use std::{collections::BTreeSet, ops::Sub};
 
     #[test]
     fn prune() {
@@ -246,4 +263,70 @@ mod tests {
         test_prune(6, (PruneProgress::Finished, 2));
         test_prune(10, (PruneProgress::Finished, 8));
     }
+
+    #[test]
+    fn prune_with_allowlist() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 1..=10, B256::ZERO, 2..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let mut tx_hash_numbers = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body {
+                tx_hash_numbers.push((transaction.hash, tx_hash_numbers.len() as u64));
+            }
+        }
+        db.insert_tx_hash_numbers(tx_hash_numbers.clone()).expect("insert tx hash numbers");
+
+        // the last transaction of the pruned range is retained as well
+        let to_block = 10;
+        let last_transaction = blocks[to_block as usize - 1].body.last().unwrap();
+        let allowlist = blocks
+            .iter()
+            .flat_map(|block| &block.body)
+            .filter_map(|transaction| transaction.to())
+            .take(3)
+            .chain(last_transaction.to())
+            .collect::<BTreeSet<_>>();
+        let retained = blocks
+            .iter()
+            .flat_map(|block| &block.body)
+            .filter(|transaction| transaction.to().is_some_and(|to| allowlist.contains(&to)))
+            .map(|transaction| transaction.hash)
+            .collect::<Vec<_>>();
+        assert!(!retained.is_empty());
+
+        let segment = TransactionLookup::new(PruneMode::Before(to_block)).with_allowlist(allowlist);
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(1000),
+        };
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().expect("commit");
+
+        assert!(result.progress.is_finished());
+        let tx_count_to_block =
+            blocks.iter().take(to_block as usize).map(|block| block.body.len()).sum::<usize>();
+        let retained_to_block = blocks
+            .iter()
+            .take(to_block as usize)
+            .flat_map(|block| &block.body)
+            .filter(|transaction| retained.contains(&transaction.hash))
+            .count();
+        assert_eq!(result.pruned, tx_count_to_block - retained_to_block);
+        assert_eq!(
+            result.checkpoint.and_then(|checkpoint| checkpoint.tx_number),
+            Some(tx_count_to_block as TxNumber - 1)
+        );
+
+        let remaining = db.table::<tables::TransactionHashNumbers>().unwrap();
+        for hash in retained {
+            assert!(remaining.iter().any(|(remaining_hash, _)| *remaining_hash == hash));
+        }
+    }
 }
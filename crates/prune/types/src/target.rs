@@ -1,5 +1,7 @@
 use crate::{PruneMode, ReceiptsLogPruneConfig};
+use alloy_primitives::Address;
 use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeSet;
 
 /// Minimum distance from the tip necessary for the node to work correctly:
 /// 1. Minimum 2 epochs (32 blocks per epoch) required to handle any reorg according to the
@@ -18,6 +20,12 @@ pub struct PruneModes {
     /// Transaction Lookup pruning configuration.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_lookup: Option<PruneMode>,
+    /// Addresses whose transactions retain their transaction lookup entries, even if they are
+    /// older than the `transaction_lookup` prune target.
+    ///
+    /// A transaction is retained if it is sent to one of the addresses.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub transaction_lookup_allowlist: BTreeSet<Address>,
     /// Receipts pruning configuration. This setting overrides `receipts_log_filter`
     /// and offers improved performance.
     #[serde(
@@ -43,6 +51,15 @@ pub struct PruneModes {
     /// The [`BlockNumber`](`crate::BlockNumber`) represents the starting block from which point
     /// onwards the receipts are preserved.
     pub receipts_log_filter: ReceiptsLogPruneConfig,
+    /// Internal transfers pruning configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal_transfers: Option<PruneMode>,
+    /// Log index pruning configuration.
+    ///
+    /// The receipts of the pruned blocks are read to find the indexed addresses and topics, so
+    /// they must not be pruned before the log index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<PruneMode>,
 }
 
 impl PruneModes {
@@ -56,10 +73,13 @@ impl PruneModes {
         Self {
             sender_recovery: Some(PruneMode::Full),
             transaction_lookup: Some(PruneMode::Full),
+            transaction_lookup_allowlist: Default::default(),
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
+            internal_transfers: Some(PruneMode::Full),
+            log_index: Some(PruneMode::Full),
         }
     }
 
@@ -68,6 +88,15 @@ impl PruneModes {
         self.receipts.is_some() || !self.receipts_log_filter.is_empty()
     }
 
+    /// Returns the transaction lookup prune mode if the lookup entries of transactions below its
+    /// target don't need to be written at all, i.e. if no addresses are allowlisted.
+    ///
+    /// With an allowlist, all lookup entries are written and the pruner removes the ones that are
+    /// not retained.
+    pub fn transaction_lookup_skip_mode(&self) -> Option<PruneMode> {
+        self.transaction_lookup.filter(|_| self.transaction_lookup_allowlist.is_empty())
+    }
+
     /// Returns true if all prune modes are set to [`None`].
     pub fn is_empty(&self) -> bool {
         self == &Self::none()
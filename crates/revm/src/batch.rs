@@ -5,7 +5,7 @@ use crate::{
     primitives::alloy_primitives::BlockNumber,
 };
 use reth_execution_errors::{BlockExecutionError, InternalBlockExecutionError};
-use reth_primitives::{Receipt, Receipts, Request, Requests};
+use reth_primitives::{InternalTransfer, Receipt, Receipts, Request, Requests};
 use reth_prune_types::{PruneMode, PruneModes, PruneSegmentError, MINIMUM_PRUNING_DISTANCE};
 use revm::db::states::bundle_state::BundleRetention;
 
@@ -33,6 +33,12 @@ pub struct BlockBatchRecord {
     /// A transaction may have zero or more requests, so the length of the inner vector is not
     /// guaranteed to be the same as the number of transactions.
     requests: Vec<Requests>,
+    /// The collection of internal ETH transfers.
+    /// Outer vector stores the transfers of each block sequentially.
+    /// The middle vector stores the transfers of each transaction ordered by transaction number.
+    ///
+    /// The transfers of a block are empty if they are pruned.
+    internal_transfers: Vec<Vec<Vec<InternalTransfer>>>,
     /// Memoized address pruning filter.
     ///
     /// Empty implies that there is going to be addresses to include in the filter in a future
@@ -96,6 +102,11 @@ impl BlockBatchRecord {
         core::mem::take(&mut self.requests)
     }
 
+    /// Returns all recorded internal transfers.
+    pub fn take_internal_transfers(&mut self) -> Vec<Vec<Vec<InternalTransfer>>> {
+        core::mem::take(&mut self.internal_transfers)
+    }
+
     /// Returns the [`BundleRetention`] for the given block based on the configured prune modes.
     pub fn bundle_retention(&self, block_number: BlockNumber) -> BundleRetention {
         if self.tip.map_or(true, |tip| {
@@ -178,6 +189,24 @@ impl BlockBatchRecord {
     pub fn save_requests(&mut self, requests: Vec<Request>) {
         self.requests.push(requests.into());
     }
+
+    /// Save the internal transfers of the transactions of a block to the executor.
+    ///
+    /// The transfers are discarded if they are pruned according to the pruning configuration.
+    pub fn save_internal_transfers(&mut self, mut internal_transfers: Vec<Vec<InternalTransfer>>) {
+        if let Some(mode) = self.prune_modes.internal_transfers {
+            let prunable = match (self.first_block, self.tip) {
+                (Some(first_block), Some(tip)) => {
+                    mode.should_prune(first_block + self.internal_transfers.len() as u64, tip)
+                }
+                _ => mode.is_full(),
+            };
+            if prunable {
+                internal_transfers.clear();
+            }
+        }
+        self.internal_transfers.push(internal_transfers);
+    }
 }
 
 #[cfg(test)]
@@ -369,4 +398,20 @@ mod tests {
         assert_eq!(recorder.receipts().len(), 4);
         assert_eq!(recorder.receipts()[3][0], Some(receipt3));
     }
+
+    #[test]
+    fn test_save_internal_transfers_with_pruning() {
+        let prune_modes =
+            PruneModes { internal_transfers: Some(PruneMode::Before(2)), ..Default::default() };
+        let mut recorder = BlockBatchRecord::new(prune_modes);
+        recorder.set_first_block(1);
+        recorder.set_tip(10);
+
+        let transfers = vec![vec![InternalTransfer::default()], vec![]];
+
+        // The transfers of block 1 are pruned, the ones of block 2 are kept
+        recorder.save_internal_transfers(transfers.clone());
+        recorder.save_internal_transfers(transfers.clone());
+        assert_eq!(recorder.take_internal_transfers(), vec![vec![], transfers]);
+    }
 }
@@ -0,0 +1,212 @@
+//! Recording of internal ETH transfers during execution.
+
+use reth_primitives::{Address, InternalTransfer, U256};
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    Database, EvmContext, Inspector,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// An [Inspector] that records the [internal transfers](InternalTransfer) of the transaction that
+/// is executed.
+///
+/// The value transfer of the transaction itself is not recorded. Transfers of call frames that
+/// revert are discarded along with the transfers of their subcalls, so only transfers that are
+/// applied to the state are recorded.
+///
+/// The recorded transfers are cleared when the next transaction starts, so they should be taken
+/// with [`InternalTransfersInspector::take_transfers`] after every transaction.
+#[derive(Debug, Default)]
+pub struct InternalTransfersInspector {
+    /// The transfers of the current transaction, in the order they were made.
+    transfers: Vec<InternalTransfer>,
+    /// The number of transfers before each call frame that is currently executed.
+    checkpoints: Vec<usize>,
+}
+
+impl InternalTransfersInspector {
+    /// Returns the transfers of the last executed transaction.
+    pub fn take_transfers(&mut self) -> Vec<InternalTransfer> {
+        core::mem::take(&mut self.transfers)
+    }
+
+    /// Starts a new call frame.
+    fn enter_frame(&mut self) {
+        if self.checkpoints.is_empty() {
+            // this is the first frame of a new transaction
+            self.transfers.clear();
+        }
+        self.checkpoints.push(self.transfers.len());
+    }
+
+    /// Ends the current call frame and returns the number of transfers before the frame.
+    ///
+    /// If the frame reverted, its transfers are discarded.
+    fn exit_frame(&mut self, success: bool) -> usize {
+        let checkpoint = self.checkpoints.pop().unwrap_or_default();
+        if !success {
+            self.transfers.truncate(checkpoint);
+        }
+        checkpoint
+    }
+
+    /// Returns the transfer from `from` to `to` if it changes balances.
+    fn transfer(from: Address, to: Address, value: U256) -> Option<InternalTransfer> {
+        (from != to && !value.is_zero()).then_some(InternalTransfer { from, to, value })
+    }
+}
+
+impl<DB: Database> Inspector<DB> for InternalTransfersInspector {
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.enter_frame();
+        // the top level call is the transaction itself
+        if context.journaled_state.depth() > 0 && inputs.transfers_value() {
+            self.transfers.extend(Self::transfer(
+                inputs.caller,
+                inputs.target_address,
+                inputs.call_value(),
+            ));
+        }
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit_frame(outcome.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.enter_frame();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let checkpoint = self.exit_frame(outcome.result.is_ok());
+        // the address of the created contract is only known at the end, but the value is
+        // transferred before the transfers of the init code
+        if let Some(address) = outcome.address.filter(|_| outcome.result.is_ok()) {
+            if context.journaled_state.depth() > 0 {
+                if let Some(transfer) = Self::transfer(inputs.caller, address, inputs.value) {
+                    self.transfers.insert(checkpoint, transfer);
+                }
+            }
+        }
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.transfers.extend(Self::transfer(contract, target, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Bytes, TxKind};
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        inspector_handle_register,
+        primitives::{AccountInfo, Bytecode, ExecutionResult},
+        Evm,
+    };
+
+    const CALLER: Address = Address::repeat_byte(0x01);
+    const CONTRACT: Address = Address::repeat_byte(0x02);
+    const RECEIVER: Address = Address::repeat_byte(0x03);
+
+    /// Returns code that sends `value` to `to` with a `CALL` and then reverts if `revert` is set.
+    fn send_code(to: Address, value: u8, revert: bool) -> Bytes {
+        // PUSH1 0 (retSize), PUSH1 0 (retOffset), PUSH1 0 (argsSize), PUSH1 0 (argsOffset)
+        let mut code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00];
+        // PUSH1 value
+        code.extend([0x60, value]);
+        // PUSH20 to
+        code.push(0x73);
+        code.extend_from_slice(to.as_slice());
+        // GAS, CALL, POP
+        code.extend([0x5a, 0xf1, 0x50]);
+        if revert {
+            // PUSH1 0, PUSH1 0, REVERT
+            code.extend([0x60, 0x00, 0x60, 0x00, 0xfd]);
+        } else {
+            // STOP
+            code.push(0x00);
+        }
+        code.into()
+    }
+
+    fn execute(code: Bytes) -> (ExecutionResult, Vec<InternalTransfer>) {
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            CALLER,
+            AccountInfo { balance: U256::from(1_000_000_000_000u64), ..Default::default() },
+        );
+        db.insert_account_info(
+            CONTRACT,
+            AccountInfo {
+                balance: U256::from(100),
+                code: Some(Bytecode::new_raw(code)),
+                ..Default::default()
+            },
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(InternalTransfersInspector::default())
+            .append_handler_register(inspector_handle_register)
+            .modify_tx_env(|tx| {
+                tx.caller = CALLER;
+                tx.transact_to = TxKind::Call(CONTRACT);
+                tx.value = U256::from(1);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+        let result = evm.transact().unwrap().result;
+        (result, evm.context.external.take_transfers())
+    }
+
+    #[test]
+    fn records_internal_transfers() {
+        let (result, transfers) = execute(send_code(RECEIVER, 7, false));
+        assert!(result.is_success());
+        // the value of the transaction is not an internal transfer
+        assert_eq!(
+            transfers,
+            vec![InternalTransfer { from: CONTRACT, to: RECEIVER, value: U256::from(7) }]
+        );
+    }
+
+    #[test]
+    fn discards_reverted_transfers() {
+        let (result, transfers) = execute(send_code(RECEIVER, 7, true));
+        assert!(!result.is_success());
+        assert!(transfers.is_empty());
+    }
+
+    #[test]
+    fn skips_transfers_to_self() {
+        let (result, transfers) = execute(send_code(CONTRACT, 7, false));
+        assert!(result.is_success());
+        assert!(transfers.is_empty());
+    }
+}
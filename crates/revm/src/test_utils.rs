@@ -4,11 +4,12 @@ use reth_primitives::{
 };
 use reth_storage_api::{
     AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateRootProvider,
+    StorageRangeProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
-    HashedStorage,
+    HashedStorage, StorageRange,
 };
 
 #[cfg(not(feature = "std"))]
@@ -128,6 +129,18 @@ impl StateProofProvider for StateProviderTest {
     }
 }
 
+impl StorageRangeProvider for StateProviderTest {
+    fn hashed_storage_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _address: Address,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        unimplemented!("storage range iteration is not supported")
+    }
+}
+
 impl StateProvider for StateProviderTest {
     fn storage(
         &self,
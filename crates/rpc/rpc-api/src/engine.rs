@@ -12,7 +12,7 @@ use reth_rpc_types::{
         ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3, ExecutionPayloadV4,
         ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus, TransitionConfiguration,
     },
-    state::StateOverride,
+    overrides::ExtendedStateOverride,
     BlockOverrides, EIP1186AccountProofResponse, Filter, JsonStorageKey, Log, RichBlock,
     SyncStatus, TransactionRequest,
 };
@@ -238,7 +238,7 @@ pub trait EngineEthApi {
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-        state_overrides: Option<StateOverride>,
+        state_overrides: Option<ExtendedStateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes>;
 
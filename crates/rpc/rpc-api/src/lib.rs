@@ -42,7 +42,10 @@ pub mod servers {
         mev::MevApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{
+            RethApiServer, RethDbApiServer, RethExecutionTraceApiServer, RethPayloadApiServer,
+            RethPubSubApiServer, RethSqlApiServer,
+        },
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
@@ -72,6 +75,9 @@ pub mod clients {
         mev::MevApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
+        reth::{
+            RethDbApiClient, RethExecutionTraceApiClient, RethPayloadApiClient, RethSqlApiClient,
+        },
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
@@ -1,5 +1,14 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_engine_primitives::EngineTypes;
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, B256, U256};
+use reth_rpc_types::{
+    debug::ChromeTrace,
+    reth::{
+        AccountChanges, BlockFeeStats, BlockProposerRevenue, DataAvailability, DbEntry,
+        InternalTransfersTarget, PayloadPreview, Reorg, SqlQueryResult, StageCheckpointChange,
+        StorageChanges, TransactionBlobSidecar, TransactionInternalTransfers,
+    },
+};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +21,186 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns fee statistics for every block in the inclusive range `[from_block, to_block]`.
+    ///
+    /// The tip percentiles are weighted by gas used, similar to the rewards of
+    /// `eth_feeHistory`. If no percentiles are requested, the 10th, 25th, 50th, 75th and 90th
+    /// percentiles are returned.
+    #[method(name = "feeStats")]
+    async fn reth_fee_stats(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        tip_percentiles: Option<Vec<f64>>,
+    ) -> RpcResult<Vec<BlockFeeStats>>;
+
+    /// Returns the blob sidecars of all blob transactions in the given block.
+    ///
+    /// Sidecars are only retained until the block is finalized, the sidecar of a transaction is
+    /// `null` if it is no longer available. Returns `null` if the block is not found.
+    #[method(name = "getBlobSidecars")]
+    async fn reth_get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<TransactionBlobSidecar>>>;
+
+    /// Returns the internal ETH transfers of all transactions in the given block, or of a single
+    /// transaction given as `{"transactionHash": "0x..."}`.
+    ///
+    /// Internal transfers are only indexed if enabled with `--index.internal-transfers`. Returns
+    /// an error if the transfers of the block were not indexed or have been pruned, and `null` if
+    /// the block or transaction is not found.
+    #[method(name = "getInternalTransfers")]
+    async fn reth_get_internal_transfers(
+        &self,
+        block_or_tx: InternalTransfersTarget,
+    ) -> RpcResult<Option<Vec<TransactionInternalTransfers>>>;
+
+    /// Returns the value delivered to the proposer of every block in the inclusive range
+    /// `[from_block, to_block]`.
+    ///
+    /// The proposer is either paid by the fee recipient with the last transaction of the block,
+    /// or is the fee recipient itself, in which case the value is the increase of the balance of
+    /// the fee recipient.
+    #[method(name = "getProposerRevenue")]
+    async fn reth_get_proposer_revenue(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<BlockProposerRevenue>>;
+
+    /// Returns the earliest block for which the receipts, state history, transaction lookup and
+    /// block bodies are available, given the prune checkpoints of the node.
+    #[method(name = "dataAvailability")]
+    async fn reth_data_availability(&self) -> RpcResult<DataAvailability>;
+
+    /// Returns up to `count` of the most recent reorgs of the canonical chain observed by the
+    /// node, starting with the most recent one.
+    ///
+    /// If no count is given, the maximum of 1024 reorgs is returned.
+    #[method(name = "getReorgs")]
+    async fn reth_get_reorgs(&self, count: Option<usize>) -> RpcResult<Vec<Reorg>>;
+}
+
+/// Reth pub-sub rpc interface.
+#[rpc(server, namespace = "reth")]
+pub trait RethPubSubApi {
+    /// Creates a subscription that emits the values of the given storage slots of the account
+    /// whenever a canonical block changes any of them.
+    ///
+    /// If blocks that changed the slots are removed from the canonical chain by a reorg, the
+    /// values of the slots at the last block that remained canonical are emitted with `removed`
+    /// set.
+    #[subscription(
+        name = "subscribeStorage" => "subscription",
+        unsubscribe = "unsubscribeStorage",
+        item = StorageChanges
+    )]
+    async fn subscribe_storage(
+        &self,
+        address: Address,
+        slots: Vec<B256>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Creates a subscription that emits the balance, nonce and code hash of the given accounts
+    /// whenever a canonical block changes any of them.
+    ///
+    /// If blocks that changed the accounts are removed from the canonical chain by a reorg, the
+    /// states of the accounts at the last block that remained canonical are emitted with `removed`
+    /// set.
+    #[subscription(
+        name = "subscribeAccounts" => "subscription",
+        unsubscribe = "unsubscribeAccounts",
+        item = AccountChanges
+    )]
+    async fn subscribe_accounts(
+        &self,
+        addresses: Vec<Address>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Creates a subscription that emits the checkpoint of a sync stage whenever it is committed,
+    /// after the stage was executed or unwound.
+    #[subscription(
+        name = "subscribeStageCheckpoints" => "subscription",
+        unsubscribe = "unsubscribeStageCheckpoints",
+        item = StageCheckpointChange
+    )]
+    async fn subscribe_stage_checkpoints(&self) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// Reth API namespace for reth-specific payload building methods.
+///
+/// These methods are only served by the authenticated server, see also the note on the generics
+/// of [`EngineApi`](crate::EngineApiServer).
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth", server_bounds(Engine::PayloadAttributes: jsonrpsee::core::DeserializeOwned)))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth", client_bounds(Engine::PayloadAttributes: jsonrpsee::core::Serialize + Clone), server_bounds(Engine::PayloadAttributes: jsonrpsee::core::DeserializeOwned)))]
+pub trait RethPayloadApi<Engine: EngineTypes> {
+    /// Builds a payload with the given attributes on top of the current head right away and
+    /// returns the block that would be proposed.
+    ///
+    /// Unlike `engine_forkchoiceUpdated`, this does not start a payload job, so the payload can't
+    /// be retrieved with `engine_getPayload`.
+    #[method(name = "buildPayloadPreview")]
+    async fn build_payload_preview(
+        &self,
+        payload_attributes: Engine::PayloadAttributes,
+    ) -> RpcResult<PayloadPreview>;
+}
+
+/// Reth API namespace for read-only SQL queries over chain data.
+///
+/// These methods are only served by the authenticated server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethSqlApi {
+    /// Executes a read-only SQL query over the `blocks`, `transactions`, `receipts` and `logs`
+    /// tables.
+    ///
+    /// Queries should filter the block number column of the tables, the number of blocks a query
+    /// may scan per table is limited.
+    #[method(name = "sql")]
+    async fn sql(&self, query: String) -> RpcResult<SqlQueryResult>;
+}
+
+/// Reth API namespace for raw access to the tables of the database.
+///
+/// These methods are only served by the authenticated server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethDbApi {
+    /// Returns the entry of the table with the given key, or `null` if there is none.
+    ///
+    /// The key is the JSON representation of the key of the table, e.g. a block number for the
+    /// `Headers` table. Of the entries of a key in a table with duplicate keys, the first one is
+    /// returned.
+    #[method(name = "dbGet")]
+    async fn db_get(&self, table: String, key: serde_json::Value) -> RpcResult<Option<DbEntry>>;
+
+    /// Returns up to `limit` consecutive entries of the table, starting at the first entry with a
+    /// key greater than or equal to `start`, or at the first entry of the table if `start` is
+    /// `null`.
+    #[method(name = "dbRange")]
+    async fn db_range(
+        &self,
+        table: String,
+        start: Option<serde_json::Value>,
+        limit: usize,
+    ) -> RpcResult<Vec<DbEntry>>;
+}
+
+/// Reth API namespace for exporting the execution of blocks as traces.
+///
+/// These methods are only served by the authenticated server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethExecutionTraceApi {
+    /// Re-executes the given block and returns a trace of its execution in the Chrome trace event
+    /// format, which can be opened in Perfetto.
+    ///
+    /// The trace has a span for every transaction, with nested spans for its call frames and the
+    /// reads of the database that were not served by the cache. Returns `null` if the block is not
+    /// found.
+    #[method(name = "exportExecutionTrace")]
+    async fn export_execution_trace(&self, block_id: BlockId) -> RpcResult<Option<ChromeTrace>>;
 }
@@ -0,0 +1,309 @@
+use futures::future::{ready, Either, Ready};
+use http::{header::AUTHORIZATION, HeaderMap, Response, StatusCode};
+use jsonrpsee::{
+    server::{middleware::rpc::RpcServiceT, HttpBody, HttpRequest, HttpResponse},
+    types::Request,
+    MethodResponse,
+};
+use parking_lot::RwLock;
+use reth_rpc_layer::JwtSecret;
+use reth_rpc_server_types::result::rpc_error_with_code;
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use subtle::ConstantTimeEq;
+use tower::{Layer, Service};
+use tracing::trace;
+
+/// Error code of calls to methods the key of the request doesn't allow.
+const ACCESS_DENIED_CODE: i32 = -32001;
+
+/// The credential that authenticates a [`RpcAccessKey`].
+#[derive(Debug, Clone)]
+pub enum RpcCredential {
+    /// The key is passed as bearer token.
+    ApiKey(String),
+    /// A JWT signed with the secret is passed as bearer token.
+    Jwt(JwtSecret),
+}
+
+/// A key that is accepted by the RPC server, with the methods it can call.
+#[derive(Debug, Clone)]
+pub struct RpcAccessKey {
+    /// Name of the key.
+    name: String,
+    /// The credential that authenticates the key.
+    credential: RpcCredential,
+    /// The namespaces whose methods the key can call.
+    namespaces: HashSet<String>,
+    /// Additional methods the key can call.
+    methods: HashSet<String>,
+}
+
+impl RpcAccessKey {
+    /// Creates a new key that can't call any method.
+    pub fn new(name: impl Into<String>, credential: RpcCredential) -> Self {
+        Self { name: name.into(), credential, namespaces: HashSet::new(), methods: HashSet::new() }
+    }
+
+    /// Allows the key to call all methods of the given namespaces, e.g. `eth`.
+    pub fn with_namespaces(
+        mut self,
+        namespaces: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.namespaces.extend(namespaces.into_iter().map(Into::into));
+        self
+    }
+
+    /// Allows the key to call the given methods, e.g. `debug_traceTransaction`.
+    pub fn with_methods(mut self, methods: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.methods.extend(methods.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns the name of the key.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if the key can call the given method.
+    pub fn allows(&self, method: &str) -> bool {
+        self.methods.contains(method) ||
+            method
+                .split_once('_')
+                .is_some_and(|(namespace, _)| self.namespaces.contains(namespace))
+    }
+
+    /// Returns `true` if the bearer token authenticates the key.
+    fn authenticates(&self, token: &str) -> bool {
+        match &self.credential {
+            // compared in constant time, so that timing doesn't reveal a guessed prefix of the key
+            RpcCredential::ApiKey(key) => key.as_bytes().ct_eq(token.as_bytes()).into(),
+            RpcCredential::Jwt(secret) => secret.validate(token).is_ok(),
+        }
+    }
+}
+
+/// The key a request was authenticated with, which is inserted into the extensions of the request
+/// by the [`RpcAccessHttpLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedKey(String);
+
+impl AuthenticatedKey {
+    /// Returns the name of the key.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Access control of the http and ws RPC servers.
+///
+/// Requests must carry the credential of one of the keys as bearer token in their `Authorization`
+/// header, and can only call the methods that key allows. The keys are shared by all clones of
+/// this type, so they can be rotated while the servers are running with [`Self::set_keys`].
+///
+/// Requests are authenticated by the [`RpcAccessHttpLayer`] and their calls are checked against
+/// the allowlists of the key by the [`RpcAccessLayer`].
+#[derive(Debug, Clone, Default)]
+pub struct RpcAccessControl {
+    keys: Arc<RwLock<Vec<RpcAccessKey>>>,
+}
+
+impl RpcAccessControl {
+    /// Creates a new access control that accepts the given keys.
+    pub fn new(keys: Vec<RpcAccessKey>) -> Self {
+        Self { keys: Arc::new(RwLock::new(keys)) }
+    }
+
+    /// Replaces the accepted keys.
+    ///
+    /// Connections that were authenticated with a key that is removed can't call any method
+    /// afterwards.
+    pub fn set_keys(&self, keys: Vec<RpcAccessKey>) {
+        *self.keys.write() = keys;
+    }
+
+    /// Returns the key that the bearer token of the headers authenticates, if any.
+    pub fn authenticate(&self, headers: &HeaderMap) -> Option<AuthenticatedKey> {
+        let token = headers.get(AUTHORIZATION)?.to_str().ok()?.strip_prefix("Bearer ")?;
+        self.keys
+            .read()
+            .iter()
+            .find(|key| key.authenticates(token))
+            .map(|key| AuthenticatedKey(key.name.clone()))
+    }
+
+    /// Returns `true` if the key with the given name exists and can call the method.
+    pub fn is_allowed(&self, key: &str, method: &str) -> bool {
+        self.keys.read().iter().any(|k| k.name == key && k.allows(method))
+    }
+
+    /// Returns the http middleware that authenticates requests.
+    pub fn http_layer(&self) -> RpcAccessHttpLayer {
+        RpcAccessHttpLayer { access_control: self.clone() }
+    }
+
+    /// Returns the rpc middleware that checks the calls of authenticated requests.
+    pub fn rpc_layer(&self) -> RpcAccessLayer {
+        RpcAccessLayer { access_control: self.clone() }
+    }
+}
+
+/// A http middleware that rejects requests that aren't authenticated by any key of the
+/// [`RpcAccessControl`], and marks the other requests with their [`AuthenticatedKey`].
+#[derive(Debug, Clone)]
+pub struct RpcAccessHttpLayer {
+    access_control: RpcAccessControl,
+}
+
+impl<S> Layer<S> for RpcAccessHttpLayer {
+    type Service = RpcAccessHttpService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcAccessHttpService { access_control: self.access_control.clone(), inner }
+    }
+}
+
+/// The [`Service`] of the [`RpcAccessHttpLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcAccessHttpService<S> {
+    access_control: RpcAccessControl,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for RpcAccessHttpService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<HttpResponse, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest) -> Self::Future {
+        let Some(key) = self.access_control.authenticate(req.headers()) else {
+            return Either::Left(ready(Ok(unauthorized_response())))
+        };
+        req.extensions_mut().insert(key);
+        Either::Right(self.inner.call(req))
+    }
+}
+
+/// A [`RpcServiceT`] middleware layer that rejects calls to methods the [`AuthenticatedKey`] of
+/// the request doesn't allow.
+///
+/// Requests must have been authenticated by the [`RpcAccessHttpLayer`], other requests can't call
+/// any method.
+#[derive(Debug, Clone)]
+pub struct RpcAccessLayer {
+    access_control: RpcAccessControl,
+}
+
+impl<S> Layer<S> for RpcAccessLayer {
+    type Service = RpcAccessService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcAccessService { access_control: self.access_control.clone(), inner }
+    }
+}
+
+/// The [`RpcServiceT`] of the [`RpcAccessLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcAccessService<S> {
+    access_control: RpcAccessControl,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcAccessService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = Either<Ready<MethodResponse>, S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let allowed = req
+            .extensions()
+            .get::<AuthenticatedKey>()
+            .is_some_and(|key| self.access_control.is_allowed(key.name(), &req.method));
+        if !allowed {
+            trace!(target: "rpc::access", method = %req.method, "Denied call");
+            return Either::Left(ready(MethodResponse::error(
+                req.id,
+                rpc_error_with_code(
+                    ACCESS_DENIED_CODE,
+                    format!("method {} is not allowed", req.method),
+                ),
+            )))
+        }
+        Either::Right(self.inner.call(req))
+    }
+}
+
+/// Returns the response to requests that aren't authenticated.
+fn unauthorized_response() -> HttpResponse {
+    Response::builder()
+        .status(StatusCode::UNAUTHORIZED)
+        .body(HttpBody::from("missing or invalid bearer token"))
+        .expect("This should never happen")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+    use reth_rpc_layer::Claims;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn headers(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {token}")).unwrap());
+        headers
+    }
+
+    #[test]
+    fn allowlists() {
+        let key = RpcAccessKey::new("key", RpcCredential::ApiKey("secret".to_string()))
+            .with_namespaces(["eth"])
+            .with_methods(["debug_traceTransaction"]);
+        assert!(key.allows("eth_blockNumber"));
+        assert!(key.allows("eth_subscribe"));
+        assert!(key.allows("debug_traceTransaction"));
+        assert!(!key.allows("debug_traceCall"));
+        assert!(!key.allows("ethereum_blockNumber"));
+        assert!(!key.allows("eth"));
+    }
+
+    #[test]
+    fn authenticates_keys() {
+        let secret = JwtSecret::random();
+        let access_control = RpcAccessControl::new(vec![
+            RpcAccessKey::new("api", RpcCredential::ApiKey("secret".to_string()))
+                .with_namespaces(["eth"]),
+            RpcAccessKey::new("jwt", RpcCredential::Jwt(secret)).with_namespaces(["debug"]),
+        ]);
+
+        let key = access_control.authenticate(&headers("secret")).unwrap();
+        assert_eq!(key.name(), "api");
+        assert!(access_control.is_allowed(key.name(), "eth_call"));
+        assert!(!access_control.is_allowed(key.name(), "debug_traceCall"));
+
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let jwt = secret.encode(&Claims { iat, exp: None }).unwrap();
+        assert_eq!(access_control.authenticate(&headers(&jwt)).unwrap().name(), "jwt");
+
+        assert_eq!(access_control.authenticate(&headers("secre")), None);
+        assert_eq!(access_control.authenticate(&HeaderMap::new()), None);
+
+        // rotated keys take effect immediately
+        access_control
+            .set_keys(vec![RpcAccessKey::new("api", RpcCredential::ApiKey("rotated".to_string()))]);
+        assert_eq!(access_control.authenticate(&headers("secret")), None);
+        assert!(access_control.authenticate(&headers("rotated")).is_some());
+        assert!(!access_control.is_allowed("api", "eth_call"));
+    }
+}
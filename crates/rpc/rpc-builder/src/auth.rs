@@ -25,6 +25,9 @@ pub use reth_ipc::server::Builder as IpcServerBuilder;
 pub struct AuthServerConfig {
     /// Where the server should listen.
     pub(crate) socket_addr: SocketAddr,
+    /// Additional addresses where the server should listen, with their own configs for JSON-RPC
+    /// Http.
+    pub(crate) additional_socket_addrs: Vec<(SocketAddr, ServerBuilder<Identity, Identity>)>,
     /// The secret for the auth layer of the server.
     pub(crate) secret: JwtSecret,
     /// Configs for JSON-RPC Http.
@@ -50,15 +53,22 @@ impl AuthServerConfig {
 
     /// Convenience function to start a server in one step.
     pub async fn start(self, module: AuthRpcModule) -> Result<AuthServerHandle, RpcError> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint } = self;
+        let Self {
+            socket_addr,
+            additional_socket_addrs,
+            secret,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+        } = self;
 
         // Create auth middleware.
         let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+            || tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
 
         // By default, both http and ws are enabled.
         let server = server_config
-            .set_http_middleware(middleware)
+            .set_http_middleware(middleware())
             .build(socket_addr)
             .await
             .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
@@ -68,6 +78,20 @@ impl AuthServerConfig {
             .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
 
         let handle = server.start(module.inner.clone());
+
+        let mut additional_handles = Vec::with_capacity(additional_socket_addrs.len());
+        for (socket_addr, server_config) in additional_socket_addrs {
+            let server =
+                server_config
+                    .set_http_middleware(middleware())
+                    .build(socket_addr)
+                    .await
+                    .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            let local_addr = server
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+            additional_handles.push((local_addr, server.start(module.inner.clone())));
+        }
         let mut ipc_handle: Option<jsonrpsee::server::ServerHandle> = None;
 
         if let Some(ipc_server_config) = ipc_server_config {
@@ -82,7 +106,14 @@ impl AuthServerConfig {
             ipc_handle = Some(res);
         }
 
-        Ok(AuthServerHandle { handle, local_addr, secret, ipc_endpoint, ipc_handle })
+        Ok(AuthServerHandle {
+            handle,
+            local_addr,
+            additional_handles,
+            secret,
+            ipc_endpoint,
+            ipc_handle,
+        })
     }
 }
 
@@ -90,6 +121,7 @@ impl AuthServerConfig {
 #[derive(Debug)]
 pub struct AuthServerConfigBuilder {
     socket_addr: Option<SocketAddr>,
+    additional_socket_addrs: Vec<SocketAddr>,
     secret: JwtSecret,
     server_config: Option<ServerBuilder<Identity, Identity>>,
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
@@ -103,6 +135,7 @@ impl AuthServerConfigBuilder {
     pub const fn new(secret: JwtSecret) -> Self {
         Self {
             socket_addr: None,
+            additional_socket_addrs: Vec::new(),
             secret,
             server_config: None,
             ipc_server_config: None,
@@ -122,6 +155,18 @@ impl AuthServerConfigBuilder {
         self
     }
 
+    /// Set additional socket addresses the server listens on, for example to listen on both an
+    /// IPv4 and an IPv6 address.
+    ///
+    /// The servers of the additional addresses always use the default JSON-RPC server config.
+    pub fn additional_socket_addrs(
+        mut self,
+        socket_addrs: impl IntoIterator<Item = SocketAddr>,
+    ) -> Self {
+        self.additional_socket_addrs = socket_addrs.into_iter().collect();
+        self
+    }
+
     /// Set the secret for the server.
     pub const fn secret(mut self, secret: JwtSecret) -> Self {
         self.secret = secret;
@@ -151,29 +196,37 @@ impl AuthServerConfigBuilder {
         self
     }
 
+    /// Returns the default JSON-RPC server config of the auth server.
+    fn default_server_config() -> ServerBuilder<Identity, Identity> {
+        ServerBuilder::new()
+            // This needs to large enough to handle large eth_getLogs responses and maximum
+            // payload bodies limit for `engine_getPayloadBodiesByRangeV`
+            // ~750MB per response should be enough
+            .max_response_body_size(750 * 1024 * 1024)
+            // Connections to this server are always authenticated, hence this only affects
+            // connections from the CL or any other client that uses JWT, this should be
+            // more than enough so that the CL (or multiple CL nodes) will never get rate
+            // limited
+            .max_connections(500)
+            // bump the default request size slightly, there aren't any methods exposed with
+            // dynamic request params that can exceed this
+            .max_request_body_size(128 * 1024 * 1024)
+            .set_id_provider(EthSubscriptionIdProvider::default())
+    }
+
     /// Build the `AuthServerConfig`.
     pub fn build(self) -> AuthServerConfig {
         AuthServerConfig {
             socket_addr: self.socket_addr.unwrap_or_else(|| {
                 SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), constants::DEFAULT_AUTH_PORT)
             }),
+            additional_socket_addrs: self
+                .additional_socket_addrs
+                .into_iter()
+                .map(|socket_addr| (socket_addr, Self::default_server_config()))
+                .collect(),
             secret: self.secret,
-            server_config: self.server_config.unwrap_or_else(|| {
-                ServerBuilder::new()
-                    // This needs to large enough to handle large eth_getLogs responses and maximum
-                    // payload bodies limit for `engine_getPayloadBodiesByRangeV`
-                    // ~750MB per response should be enough
-                    .max_response_body_size(750 * 1024 * 1024)
-                    // Connections to this server are always authenticated, hence this only affects
-                    // connections from the CL or any other client that uses JWT, this should be
-                    // more than enough so that the CL (or multiple CL nodes) will never get rate
-                    // limited
-                    .max_connections(500)
-                    // bump the default request size slightly, there aren't any methods exposed with
-                    // dynamic request params that can exceed this
-                    .max_request_body_size(128 * 1024 * 1024)
-                    .set_id_provider(EthSubscriptionIdProvider::default())
-            }),
+            server_config: self.server_config.unwrap_or_else(Self::default_server_config),
             ipc_server_config: self.ipc_server_config.map(|ipc_server_config| {
                 ipc_server_config
                     .max_response_body_size(750 * 1024 * 1024)
@@ -239,6 +292,7 @@ impl AuthRpcModule {
 pub struct AuthServerHandle {
     local_addr: SocketAddr,
     handle: jsonrpsee::server::ServerHandle,
+    additional_handles: Vec<(SocketAddr, jsonrpsee::server::ServerHandle)>,
     secret: JwtSecret,
     ipc_endpoint: Option<String>,
     ipc_handle: Option<jsonrpsee::server::ServerHandle>,
@@ -252,8 +306,16 @@ impl AuthServerHandle {
         self.local_addr
     }
 
+    /// Returns the [`SocketAddr`]s of the servers on the additional addresses.
+    pub fn additional_local_addrs(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        self.additional_handles.iter().map(|(addr, _)| *addr)
+    }
+
     /// Tell the server to stop without waiting for the server to stop.
     pub fn stop(self) -> Result<(), AlreadyStoppedError> {
+        for (_, handle) in self.additional_handles {
+            handle.stop()?
+        }
         self.handle.stop()
     }
 
@@ -1,7 +1,14 @@
 use std::{net::SocketAddr, path::PathBuf};
 
 use jsonrpsee::server::ServerBuilder;
-use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
+use reth_cli_util::secrets::SecretsPassphrase;
+use reth_node_core::{
+    args::RpcServerArgs,
+    utils::{
+        get_or_create_jwt_secret_from_path, get_or_create_jwt_secret_from_path_with_passphrase,
+        read_jwt_secret, JwtSecretError,
+    },
+};
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
@@ -72,6 +79,14 @@ pub trait RethRpcServerConfig {
     /// jwt secret in case the `auth_jwtsecret` argument is not provided.
     fn auth_jwt_secret(&self, default_jwt_path: PathBuf) -> Result<JwtSecret, JwtError>;
 
+    /// Like [`Self::auth_jwt_secret`], but an encrypted secret file is decrypted with the
+    /// `passphrase`, and a generated secret is encrypted with it.
+    fn auth_jwt_secret_with_passphrase(
+        &self,
+        default_jwt_path: PathBuf,
+        passphrase: Option<&SecretsPassphrase>,
+    ) -> Result<JwtSecret, JwtSecretError>;
+
     /// Returns the configured jwt secret key for the regular rpc servers, if any.
     ///
     /// Note: this is not used for the auth server (engine API).
@@ -91,6 +106,7 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn eth_config(&self) -> EthConfig {
         EthConfig::default()
             .max_tracing_requests(self.rpc_max_tracing_requests)
+            .blocking_pool_threads(self.rpc_tracing_threads)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
             .eth_proof_window(self.rpc_eth_proof_window)
@@ -98,6 +114,7 @@ impl RethRpcServerConfig for RpcServerArgs {
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
             .proof_permits(self.rpc_proof_permits)
+            .pending_logs(self.rpc_pending_logs)
     }
 
     fn state_cache_config(&self) -> EthStateCacheConfig {
@@ -106,6 +123,10 @@ impl RethRpcServerConfig for RpcServerArgs {
             max_receipts: self.rpc_state_cache.max_receipts,
             max_envs: self.rpc_state_cache.max_envs,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
+            provider_cache: self.rpc_state_cache.provider_cache,
+            provider_max_headers: self.rpc_state_cache.provider_max_headers,
+            provider_max_blocks: self.rpc_state_cache.provider_max_blocks,
+            provider_max_receipts: self.rpc_state_cache.provider_max_receipts,
         }
     }
 
@@ -174,16 +195,25 @@ impl RethRpcServerConfig for RpcServerArgs {
                 .with_http(self.http_ws_server_builder())
                 .with_http_cors(self.http_corsdomain.clone())
                 .with_ws_cors(self.ws_allowed_origins.clone());
+            for listener in &self.http_listeners {
+                config = config.with_http_listener(self.http_ws_server_builder(), listener.clone());
+            }
         }
 
         if self.ws {
             let socket_address = SocketAddr::new(self.ws_addr, self.ws_port);
             config = config.with_ws_address(socket_address).with_ws(self.http_ws_server_builder());
+            for listener in &self.ws_listeners {
+                config = config.with_ws_listener(self.http_ws_server_builder(), listener.clone());
+            }
         }
 
         if self.is_ipc_enabled() {
             config =
                 config.with_ipc(self.ipc_server_builder()).with_ipc_endpoint(self.ipcpath.clone());
+            for listener in &self.ipc_listeners {
+                config = config.with_ipc_listener(self.ipc_server_builder(), listener.clone());
+            }
         }
 
         config
@@ -192,7 +222,9 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
-        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        let mut builder = AuthServerConfig::builder(jwt_secret)
+            .socket_addr(address)
+            .additional_socket_addrs(self.auth_listeners.iter().copied());
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
@@ -211,6 +243,22 @@ impl RethRpcServerConfig for RpcServerArgs {
         }
     }
 
+    fn auth_jwt_secret_with_passphrase(
+        &self,
+        default_jwt_path: PathBuf,
+        passphrase: Option<&SecretsPassphrase>,
+    ) -> Result<JwtSecret, JwtSecretError> {
+        match self.auth_jwtsecret.as_ref() {
+            Some(fpath) => {
+                debug!(target: "reth::cli", user_path=?fpath, "Reading JWT auth secret file");
+                read_jwt_secret(fpath, passphrase)
+            }
+            None => {
+                get_or_create_jwt_secret_from_path_with_passphrase(&default_jwt_path, passphrase)
+            }
+        }
+    }
+
     fn rpc_secret_key(&self) -> Option<JwtSecret> {
         self.rpc_jwtsecret
     }
@@ -334,6 +382,29 @@ mod tests {
         assert_eq!(config.ipc_endpoint().unwrap(), constants::DEFAULT_IPC_ENDPOINT);
     }
 
+    #[test]
+    fn test_rpc_server_config_listeners() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http",
+            "--http.listen",
+            "[::1]:8545;api=eth",
+            "--ipc.listen",
+            "/tmp/sidecar.ipc",
+        ])
+        .args;
+        let config = args.rpc_server_config();
+        assert_eq!(
+            config.http_listeners().map(|listener| listener.addr).collect::<Vec<_>>(),
+            vec!["[::1]:8545".parse::<SocketAddr>().unwrap()]
+        );
+        assert_eq!(config.ws_listeners().count(), 0);
+        assert_eq!(
+            config.ipc_listeners().map(|listener| listener.addr.as_str()).collect::<Vec<_>>(),
+            vec!["/tmp/sidecar.ipc"]
+        );
+    }
+
     #[test]
     fn test_zero_filter_limits() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
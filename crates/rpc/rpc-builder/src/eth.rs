@@ -1,5 +1,8 @@
 use reth_evm::ConfigureEvm;
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider, StateProviderFactory};
+use reth_provider::{
+    providers::{CachedBlockchainProvider, ProviderCacheConfig},
+    BlockReader, CanonStateSubscriptions, EvmEnvProvider, StateProviderFactory,
+};
 use reth_rpc::{EthFilter, EthPubSub};
 use reth_rpc_eth_types::{
     cache::cache_new_blocks_task, EthApiBuilderCtx, EthConfig, EthStateCache,
@@ -86,12 +89,35 @@ where
         let Self { provider, pool, network, evm_config, config, executor, events, eth_api_builder } =
             self;
 
-        let cache = EthStateCache::spawn_with(
-            provider.clone(),
-            config.cache,
-            executor.clone(),
-            evm_config.clone(),
-        );
+        let cache = if config.cache.provider_cache {
+            let cached_provider = CachedBlockchainProvider::new(
+                provider.clone(),
+                ProviderCacheConfig {
+                    max_headers: config.cache.provider_max_headers,
+                    max_blocks: config.cache.provider_max_blocks,
+                    max_receipts: config.cache.provider_max_receipts,
+                },
+            );
+            executor.spawn_critical(
+                "provider cache invalidation task",
+                Box::pin(
+                    cached_provider.cache_invalidation_task(events.subscribe_to_canonical_state()),
+                ),
+            );
+            EthStateCache::spawn_with(
+                cached_provider,
+                config.cache,
+                executor.clone(),
+                evm_config.clone(),
+            )
+        } else {
+            EthStateCache::spawn_with(
+                provider.clone(),
+                config.cache,
+                executor.clone(),
+                evm_config.clone(),
+            )
+        };
 
         let new_canonical_blocks = events.canonical_state_stream();
         let c = cache.clone();
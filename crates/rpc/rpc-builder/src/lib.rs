@@ -146,7 +146,7 @@ use http::{header::AUTHORIZATION, HeaderMap};
 use jsonrpsee::{
     core::RegisterMethodError,
     server::{
-        middleware::rpc::{RpcService, RpcServiceT},
+        middleware::rpc::{either::Either, RpcService, RpcServiceT},
         AlreadyStoppedError, IdProvider, RpcServiceBuilder, ServerHandle,
     },
     Methods, RpcModule,
@@ -159,8 +159,8 @@ use reth_provider::{
     EvmEnvProvider, FullRpcProvider, StateProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthBundle, ExecutionTraceApi, NetApi, OtterscanApi, RPCApi,
+    RethApi, RethPubSub, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -186,9 +186,18 @@ pub use jsonrpsee::server::ServerBuilder;
 pub use reth_ipc::server::{
     Builder as IpcServerBuilder, RpcServiceBuilder as IpcRpcServiceBuilder,
 };
-pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+pub use reth_rpc_server_types::{
+    constants, RethRpcModule, RpcListener, RpcListenerParseError, RpcModuleSelection,
+};
 pub use tower::layer::util::{Identity, Stack};
 
+/// Access control of the http and ws servers.
+mod access;
+pub use access::{
+    AuthenticatedKey, RpcAccessControl, RpcAccessHttpLayer, RpcAccessHttpService, RpcAccessKey,
+    RpcAccessLayer, RpcAccessService, RpcCredential,
+};
+
 /// Auth server utilities.
 pub mod auth;
 
@@ -209,6 +218,10 @@ pub use eth::EthHandlers;
 mod metrics;
 pub use metrics::{MeteredRequestFuture, RpcRequestMetricsService};
 
+/// Strict request validation.
+mod strict;
+pub use strict::{StrictParamsLayer, StrictParamsService};
+
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
 pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi>(
@@ -877,7 +890,10 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime.
-    pub fn register_reth(&mut self) -> &mut Self {
+    pub fn register_reth(&mut self) -> &mut Self
+    where
+        Pool: TransactionPool + Clone + 'static,
+    {
         let rethapi = self.reth_api();
         self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
         self
@@ -938,6 +954,19 @@ where
         DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates `ExecutionTraceApi`
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn execution_trace_api(&self) -> ExecutionTraceApi<EthApi>
+    where
+        EthApi: TraceExt,
+    {
+        let eth_api = self.eth_api().clone();
+        ExecutionTraceApi::new(eth_api, self.blocking_pool_guard.clone())
+    }
+
     /// Instantiates `NetApi`
     ///
     /// # Panics
@@ -952,8 +981,15 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    pub fn reth_api(&self) -> RethApi<Provider, Pool>
+    where
+        Pool: TransactionPool + Clone + 'static,
+    {
+        RethApi::with_pool(
+            self.provider.clone(),
+            self.pool.clone(),
+            Box::new(self.executor.clone()),
+        )
     }
 }
 
@@ -983,7 +1019,9 @@ where
 
         // also merge a subset of `eth_` handlers
         let eth_handlers = self.eth_handlers();
-        let engine_eth = EngineEthApi::new(eth_handlers.api.clone(), eth_handlers.filter.clone());
+        let eth_filter =
+            eth_handlers.filter.clone().with_local_pending_block(eth_handlers.api.clone());
+        let engine_eth = EngineEthApi::new(eth_handlers.api.clone(), eth_filter);
 
         module.merge(engine_eth.into_rpc()).expect("No conflicting methods");
 
@@ -1039,6 +1077,8 @@ where
     ) -> Vec<Methods> {
         let EthHandlers { api: eth_api, filter: eth_filter, pubsub: eth_pubsub, .. } =
             self.eth_handlers().clone();
+        // pending logs fall back to the pending block built by the `eth_` API
+        let eth_filter = eth_filter.with_local_pending_block(eth_api.clone());
 
         // Create a copy, so we can list out all the methods for rpc_ api
         let namespaces: Vec<_> = namespaces.collect();
@@ -1093,9 +1133,20 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
+                            let mut module = RethApi::with_pool(
+                                self.provider.clone(),
+                                self.pool.clone(),
+                                Box::new(self.executor.clone()),
+                            )
+                            .into_rpc();
+                            let pubsub = RethPubSub::with_spawner(
+                                self.events.clone(),
+                                Box::new(self.executor.clone()),
+                            )
+                            .with_stage_checkpoints(self.provider.clone());
+                            module.merge(pubsub.into_rpc()).expect("No conflicts");
+
+                            module.into()
                         }
                         RethRpcModule::EthCallBundle => {
                             EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
@@ -1138,8 +1189,16 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     /// The Endpoint where to launch the ipc server
     ipc_endpoint: Option<String>,
+    /// Additional http listeners and their server configs
+    http_listeners: Vec<(ServerBuilder<Identity, Identity>, RpcListener)>,
+    /// Additional ws listeners and their server configs
+    ws_listeners: Vec<(ServerBuilder<Identity, Identity>, RpcListener)>,
+    /// Additional ipc listeners and their server configs
+    ipc_listeners: Vec<(IpcServerBuilder<Identity, Identity>, RpcListener<String>)>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Access control of the http and ws servers
+    access_control: Option<RpcAccessControl>,
     /// Configurable RPC middleware
     rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
 }
@@ -1158,7 +1217,11 @@ impl Default for RpcServerConfig<Identity> {
             ws_addr: None,
             ipc_server_config: None,
             ipc_endpoint: None,
+            http_listeners: Vec::new(),
+            ws_listeners: Vec::new(),
+            ipc_listeners: Vec::new(),
             jwt_secret: None,
+            access_control: None,
             rpc_middleware: RpcServiceBuilder::new(),
         }
     }
@@ -1207,6 +1270,48 @@ impl RpcServerConfig {
         self.ipc_server_config = Some(config.set_id_provider(EthSubscriptionIdProvider::default()));
         self
     }
+
+    /// Adds an http listener that is served next to the http server.
+    ///
+    /// The listener serves the methods of the http modules, restricted to the
+    /// [modules](RpcListener::modules) of the listener, with its own CORS domains. It requires the
+    /// http server to be configured.
+    pub fn with_http_listener(
+        mut self,
+        config: ServerBuilder<Identity, Identity>,
+        listener: RpcListener,
+    ) -> Self {
+        self.http_listeners
+            .push((config.set_id_provider(EthSubscriptionIdProvider::default()), listener));
+        self
+    }
+
+    /// Adds a ws listener that is served next to the ws server.
+    ///
+    /// See also [`Self::with_http_listener`].
+    pub fn with_ws_listener(
+        mut self,
+        config: ServerBuilder<Identity, Identity>,
+        listener: RpcListener,
+    ) -> Self {
+        self.ws_listeners
+            .push((config.set_id_provider(EthSubscriptionIdProvider::default()), listener));
+        self
+    }
+
+    /// Adds an ipc listener on the endpoint of the listener that is served next to the ipc
+    /// server.
+    ///
+    /// The CORS domains of the listener are ignored. See also [`Self::with_http_listener`].
+    pub fn with_ipc_listener(
+        mut self,
+        config: IpcServerBuilder<Identity, Identity>,
+        listener: RpcListener<String>,
+    ) -> Self {
+        self.ipc_listeners
+            .push((config.set_id_provider(EthSubscriptionIdProvider::default()), listener));
+        self
+    }
 }
 
 impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
@@ -1221,11 +1326,51 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ws_addr: self.ws_addr,
             ipc_server_config: self.ipc_server_config,
             ipc_endpoint: self.ipc_endpoint,
+            http_listeners: self.http_listeners,
+            ws_listeners: self.ws_listeners,
+            ipc_listeners: self.ipc_listeners,
             jwt_secret: self.jwt_secret,
+            access_control: self.access_control,
             rpc_middleware,
         }
     }
 
+    /// Rejects requests with parameters that would otherwise be silently ignored, if `strict` is
+    /// true.
+    ///
+    /// See [`StrictParamsLayer`].
+    pub fn with_strict_params(
+        self,
+        strict: bool,
+    ) -> RpcServerConfig<Stack<Either<StrictParamsLayer, Identity>, RpcMiddleware>>
+    where
+        RpcMiddleware: Clone,
+    {
+        let rpc_middleware =
+            self.rpc_middleware.clone().option_layer(strict.then_some(StrictParamsLayer));
+        self.set_rpc_middleware(rpc_middleware)
+    }
+
+    /// Restricts the http and ws servers to requests that are authenticated by a key of the access
+    /// control, and the methods each key can call to its allowlists.
+    ///
+    /// See [`RpcAccessControl`].
+    pub fn with_access_control(
+        self,
+        access_control: Option<RpcAccessControl>,
+    ) -> RpcServerConfig<Stack<Either<RpcAccessLayer, Identity>, RpcMiddleware>>
+    where
+        RpcMiddleware: Clone,
+    {
+        let rpc_middleware = self
+            .rpc_middleware
+            .clone()
+            .option_layer(access_control.as_ref().map(RpcAccessControl::rpc_layer));
+        let mut config = self.set_rpc_middleware(rpc_middleware);
+        config.access_control = access_control;
+        config
+    }
+
     /// Configure the cors domains for http _and_ ws
     pub fn with_cors(self, cors_domain: Option<String>) -> Self {
         self.with_http_cors(cors_domain.clone()).with_ws_cors(cors_domain)
@@ -1275,8 +1420,23 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             self.ws_server_config = Some(ws.set_id_provider(id_provider.clone()));
         }
         if let Some(ipc) = self.ipc_server_config {
-            self.ipc_server_config = Some(ipc.set_id_provider(id_provider));
+            self.ipc_server_config = Some(ipc.set_id_provider(id_provider.clone()));
         }
+        self.http_listeners = self
+            .http_listeners
+            .into_iter()
+            .map(|(http, listener)| (http.set_id_provider(id_provider.clone()), listener))
+            .collect();
+        self.ws_listeners = self
+            .ws_listeners
+            .into_iter()
+            .map(|(ws, listener)| (ws.set_id_provider(id_provider.clone()), listener))
+            .collect();
+        self.ipc_listeners = self
+            .ipc_listeners
+            .into_iter()
+            .map(|(ipc, listener)| (ipc.set_id_provider(id_provider.clone()), listener))
+            .collect();
 
         self
     }
@@ -1319,6 +1479,21 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self.ipc_endpoint.clone()
     }
 
+    /// Returns the additional http listeners.
+    pub fn http_listeners(&self) -> impl Iterator<Item = &RpcListener> + '_ {
+        self.http_listeners.iter().map(|(_, listener)| listener)
+    }
+
+    /// Returns the additional ws listeners.
+    pub fn ws_listeners(&self) -> impl Iterator<Item = &RpcListener> + '_ {
+        self.ws_listeners.iter().map(|(_, listener)| listener)
+    }
+
+    /// Returns the additional ipc listeners.
+    pub fn ipc_listeners(&self) -> impl Iterator<Item = &RpcListener<String>> + '_ {
+        self.ipc_listeners.iter().map(|(_, listener)| listener)
+    }
+
     /// Creates the [`CorsLayer`] if any
     fn maybe_cors_layer(cors: Option<String>) -> Result<Option<CorsLayer>, CorsDomainError> {
         cors.as_deref().map(cors::create_cors_layer).transpose()
@@ -1329,17 +1504,122 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
-    /// Builds and starts the configured server(s): http, ws, ipc.
+    /// Creates the [`RpcAccessHttpLayer`] if any
+    fn maybe_access_layer(access_control: Option<&RpcAccessControl>) -> Option<RpcAccessHttpLayer> {
+        access_control.map(RpcAccessControl::http_layer)
+    }
+
+    /// Returns the methods of the module that belong to the selected modules of a listener.
+    fn listener_module(
+        module: &RpcModule<()>,
+        selection: Option<&RpcModuleSelection>,
+    ) -> RpcModule<()> {
+        let mut module = module.clone();
+        if let Some(selection) = selection {
+            let excluded = module
+                .method_names()
+                .filter(|method| {
+                    !RethRpcModule::from_method_name(method)
+                        .is_some_and(|rpc_module| selection.contains(&rpc_module))
+                })
+                .collect::<Vec<_>>();
+            for method in excluded {
+                module.remove_method(method);
+            }
+        }
+        module
+    }
+
+    /// Starts the additional listeners of all transports.
+    async fn start_listeners(
+        &mut self,
+        modules: &TransportRpcModules,
+    ) -> Result<RpcListenerHandles, RpcError>
+    where
+        RpcMiddleware: Layer<RpcRequestMetricsService<RpcService>> + Clone + Send + 'static,
+        for<'a> <RpcMiddleware as Layer<RpcRequestMetricsService<RpcService>>>::Service:
+            Send + Sync + 'static + RpcServiceT<'a>,
+    {
+        let mut handles = RpcListenerHandles::default();
+
+        for (builder, listener) in std::mem::take(&mut self.http_listeners) {
+            let module = modules.http.as_ref().ok_or_else(|| {
+                RpcError::Custom(format!("http listener {} requires http server", listener.addr))
+            })?;
+            let module = Self::listener_module(module, listener.modules.as_ref());
+            let server = builder
+                .http_only()
+                .set_http_middleware(
+                    tower::ServiceBuilder::new()
+                        .option_layer(Self::maybe_cors_layer(listener.cors_domains)?)
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_access_layer(self.access_control.as_ref())),
+                )
+                .set_rpc_middleware(
+                    self.rpc_middleware.clone().layer(RpcRequestMetrics::http(&module)),
+                )
+                .build(listener.addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::Http(listener.addr)))?;
+            let addr = server
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Http(listener.addr)))?;
+            handles.http.push((addr, server.start(module)));
+        }
+
+        for (builder, listener) in std::mem::take(&mut self.ws_listeners) {
+            let module = modules.ws.as_ref().ok_or_else(|| {
+                RpcError::Custom(format!("ws listener {} requires ws server", listener.addr))
+            })?;
+            let module = Self::listener_module(module, listener.modules.as_ref());
+            let server = builder
+                .ws_only()
+                .set_http_middleware(
+                    tower::ServiceBuilder::new()
+                        .option_layer(Self::maybe_cors_layer(listener.cors_domains)?)
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_access_layer(self.access_control.as_ref())),
+                )
+                .set_rpc_middleware(
+                    self.rpc_middleware.clone().layer(RpcRequestMetrics::ws(&module)),
+                )
+                .build(listener.addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::WS(listener.addr)))?;
+            let addr = server
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::WS(listener.addr)))?;
+            handles.ws.push((addr, server.start(module)));
+        }
+
+        for (builder, listener) in std::mem::take(&mut self.ipc_listeners) {
+            let module = modules.ipc.as_ref().ok_or_else(|| {
+                RpcError::Custom(format!("ipc listener {} requires ipc server", listener.addr))
+            })?;
+            let module = Self::listener_module(module, listener.modules.as_ref());
+            let ipc = builder
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new().layer(RpcRequestMetrics::ipc(&module)),
+                )
+                .build(listener.addr.clone());
+            handles.ipc.push((listener.addr, ipc.start(module).await?));
+        }
+
+        Ok(handles)
+    }
+
+    /// Builds and starts the configured server(s): http, ws, ipc, and their additional listeners.
     ///
     /// If both http and ws are on the same port, they are combined into one server.
     ///
     /// Returns the [`RpcServerHandle`] with the handle to the started servers.
-    pub async fn start(self, modules: &TransportRpcModules) -> Result<RpcServerHandle, RpcError>
+    pub async fn start(mut self, modules: &TransportRpcModules) -> Result<RpcServerHandle, RpcError>
     where
         RpcMiddleware: Layer<RpcRequestMetricsService<RpcService>> + Clone + Send + 'static,
         for<'a> <RpcMiddleware as Layer<RpcRequestMetricsService<RpcService>>>::Service:
             Send + Sync + 'static + RpcServiceT<'a>,
     {
+        let listeners = self.start_listeners(modules).await?;
         let mut http_handle = None;
         let mut ws_handle = None;
         let mut ipc_handle = None;
@@ -1393,7 +1673,8 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
                             .option_layer(Self::maybe_cors_layer(cors)?)
-                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(Self::maybe_access_layer(self.access_control.as_ref())),
                     )
                     .set_rpc_middleware(
                         self.rpc_middleware.clone().layer(
@@ -1425,6 +1706,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     ws: ws_handle,
                     ipc_endpoint: self.ipc_endpoint.clone(),
                     ipc: ipc_handle,
+                    listeners,
                     jwt_secret: self.jwt_secret,
                 });
             }
@@ -1441,7 +1723,8 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_access_layer(self.access_control.as_ref())),
                 )
                 .set_rpc_middleware(
                     self.rpc_middleware
@@ -1466,7 +1749,8 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(Self::maybe_access_layer(self.access_control.as_ref())),
                 )
                 .set_rpc_middleware(
                     self.rpc_middleware.clone().layer(
@@ -1494,6 +1778,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ws: ws_handle,
             ipc_endpoint: self.ipc_endpoint.clone(),
             ipc: ipc_handle,
+            listeners,
             jwt_secret: self.jwt_secret,
         })
     }
@@ -1719,9 +2004,18 @@ pub struct RpcServerHandle {
     ws: Option<ServerHandle>,
     ipc_endpoint: Option<String>,
     ipc: Option<jsonrpsee::server::ServerHandle>,
+    listeners: RpcListenerHandles,
     jwt_secret: Option<JwtSecret>,
 }
 
+/// Handles to the additional listeners of the servers.
+#[derive(Clone, Debug, Default)]
+struct RpcListenerHandles {
+    http: Vec<(SocketAddr, ServerHandle)>,
+    ws: Vec<(SocketAddr, ServerHandle)>,
+    ipc: Vec<(String, jsonrpsee::server::ServerHandle)>,
+}
+
 // === impl RpcServerHandle ===
 
 impl RpcServerHandle {
@@ -1765,6 +2059,14 @@ impl RpcServerHandle {
             handle.stop()?
         }
 
+        let RpcListenerHandles { http, ws, ipc } = self.listeners;
+        for (_, handle) in http.into_iter().chain(ws) {
+            handle.stop()?
+        }
+        for (_, handle) in ipc {
+            handle.stop()?
+        }
+
         Ok(())
     }
 
@@ -1773,6 +2075,21 @@ impl RpcServerHandle {
         self.ipc_endpoint.clone()
     }
 
+    /// Returns the [`SocketAddr`]s of the additional http listeners.
+    pub fn http_listener_addrs(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        self.listeners.http.iter().map(|(addr, _)| *addr)
+    }
+
+    /// Returns the [`SocketAddr`]s of the additional ws listeners.
+    pub fn ws_listener_addrs(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        self.listeners.ws.iter().map(|(addr, _)| *addr)
+    }
+
+    /// Returns the endpoints of the additional ipc listeners.
+    pub fn ipc_listener_endpoints(&self) -> impl Iterator<Item = &str> + '_ {
+        self.listeners.ipc.iter().map(|(endpoint, _)| endpoint.as_str())
+    }
+
     /// Returns the url to the http server
     pub fn http_url(&self) -> Option<String> {
         self.http_local_addr.map(|addr| format!("http://{addr}"))
@@ -0,0 +1,133 @@
+use futures::future::{ready, Either, Ready};
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use reth_rpc_server_types::result::invalid_params_rpc_err;
+use serde_json::{value::RawValue, Value};
+use tower::Layer;
+
+/// The fields of a transaction request object.
+const TRANSACTION_REQUEST_FIELDS: &[&str] = &[
+    "from",
+    "to",
+    "gasPrice",
+    "maxFeePerGas",
+    "maxPriorityFeePerGas",
+    "maxFeePerBlobGas",
+    "gas",
+    "value",
+    "input",
+    "data",
+    "nonce",
+    "chainId",
+    "accessList",
+    "type",
+    "blobVersionedHashes",
+    "blobs",
+    "commitments",
+    "proofs",
+];
+
+/// The fields of a log filter object.
+const FILTER_FIELDS: &[&str] = &["fromBlock", "toBlock", "blockHash", "address", "topics"];
+
+/// A layer that rejects requests with parameters that would otherwise be silently ignored.
+///
+/// This rejects requests that pass their parameters by name, and requests with unknown fields in
+/// the transaction request or filter object of methods like `eth_call` and `eth_getLogs`.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct StrictParamsLayer;
+
+impl<S> Layer<S> for StrictParamsLayer {
+    type Service = StrictParamsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        StrictParamsService { inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that validates the parameters of each request, see
+/// [`StrictParamsLayer`].
+#[derive(Debug, Clone)]
+pub struct StrictParamsService<S> {
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for StrictParamsService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = Either<Ready<MethodResponse>, S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        if let Err(message) = validate_params(&req.method, req.params.as_deref()) {
+            return Either::Left(ready(MethodResponse::error(
+                req.id,
+                invalid_params_rpc_err(message),
+            )))
+        }
+        Either::Right(self.inner.call(req))
+    }
+}
+
+/// Returns the fields of the object that the given method expects as its first parameter, if
+/// any.
+fn object_param_fields(method: &str) -> Option<&'static [&'static str]> {
+    match method {
+        "eth_call" |
+        "eth_estimateGas" |
+        "eth_createAccessList" |
+        "eth_sendTransaction" |
+        "eth_signTransaction" |
+        "debug_traceCall" |
+        "trace_call" => Some(TRANSACTION_REQUEST_FIELDS),
+        "eth_getLogs" | "eth_newFilter" => Some(FILTER_FIELDS),
+        _ => None,
+    }
+}
+
+/// Validates the raw parameters of a call to the given method.
+fn validate_params(method: &str, params: Option<&RawValue>) -> Result<(), String> {
+    let Some(params) = params else { return Ok(()) };
+    if params.get().trim_start().starts_with('{') {
+        return Err("parameters must be passed by position".to_string())
+    }
+
+    let Some(fields) = object_param_fields(method) else { return Ok(()) };
+    let params: Value = serde_json::from_str(params.get()).map_err(|err| err.to_string())?;
+    if let Some(Value::Object(object)) = params.get(0) {
+        if let Some(field) = object.keys().find(|field| !fields.contains(&field.as_str())) {
+            return Err(format!("unknown field `{field}`"))
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(method: &str, params: &str) -> Result<(), String> {
+        validate_params(method, Some(&RawValue::from_string(params.to_string()).unwrap()))
+    }
+
+    #[test]
+    fn rejects_named_params() {
+        assert!(
+            validate("eth_getBalance", r#"["0x0000000000000000000000000000000000000000"]"#).is_ok()
+        );
+        assert!(validate("eth_getBalance", r#"{"address":"0x00"}"#).is_err());
+        assert!(validate_params("eth_blockNumber", None).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        assert!(validate("eth_call", r#"[{"to":"0x00","gasPrice":"0x1"},"latest"]"#).is_ok());
+        assert_eq!(
+            validate("eth_call", r#"[{"to":"0x00","gasprice":"0x1"},"latest"]"#),
+            Err("unknown field `gasprice`".to_string())
+        );
+        assert!(validate("eth_getLogs", r#"[{"fromBlock":"0x1","topics":[]}]"#).is_ok());
+        assert!(validate("eth_getLogs", r#"[{"fromBlock":"0x1","topic":[]}]"#).is_err());
+    }
+}
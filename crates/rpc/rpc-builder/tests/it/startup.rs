@@ -2,10 +2,11 @@
 
 use std::io;
 
+use jsonrpsee::{core::client::ClientT, http_client::HttpClientBuilder, rpc_params};
 use reth_rpc::EthApi;
 use reth_rpc_builder::{
     error::{RpcError, ServerKind, WsHttpSamePortError},
-    RpcServerConfig, TransportRpcModuleConfig,
+    RpcListener, RpcServerConfig, TransportRpcModuleConfig,
 };
 use reth_rpc_server_types::RethRpcModule;
 
@@ -124,3 +125,48 @@ async fn test_launch_same_port_different_cors() {
         RpcError::WsHttpSamePortError(WsHttpSamePortError::ConflictingCorsDomains { .. })
     ));
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_launch_http_listeners() {
+    let builder = test_rpc_builder();
+    let server = builder.build(
+        TransportRpcModuleConfig::set_http(vec![RethRpcModule::Web3, RethRpcModule::Rpc]),
+        Box::new(EthApi::with_spawner),
+    );
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_http_listener(Default::default(), RpcListener::new(test_address()))
+        .with_http_listener(
+            Default::default(),
+            RpcListener::new(test_address()).with_modules(Some([RethRpcModule::Web3].into())),
+        )
+        .start(&server)
+        .await
+        .unwrap();
+
+    let addrs = handle.http_listener_addrs().collect::<Vec<_>>();
+    assert_eq!(addrs.len(), 2);
+    assert_ne!(addrs[0], handle.http_local_addr().unwrap());
+
+    for (addr, serves_rpc) in addrs.into_iter().zip([true, false]) {
+        let client = HttpClientBuilder::default().build(format!("http://{addr}")).unwrap();
+        client.request::<String, _>("web3_clientVersion", rpc_params![]).await.unwrap();
+        let modules = client.request::<serde_json::Value, _>("rpc_modules", rpc_params![]).await;
+        assert_eq!(modules.is_ok(), serves_rpc);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_launch_listener_without_server() {
+    let builder = test_rpc_builder();
+    let server = builder.build(
+        TransportRpcModuleConfig::set_http(vec![RethRpcModule::Web3]),
+        Box::new(EthApi::with_spawner),
+    );
+    let res = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .with_ws_listener(Default::default(), RpcListener::new(test_address()))
+        .start(&server)
+        .await;
+    assert!(matches!(res.unwrap_err(), RpcError::Custom(_)));
+}
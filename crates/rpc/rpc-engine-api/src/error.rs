@@ -72,6 +72,17 @@ pub enum EngineApiError {
         /// Consensus terminal block hash.
         consensus: B256,
     },
+    /// Thrown if the timestamp of the payload attributes is not greater than the timestamp of the
+    /// parent block.
+    #[error(
+        "invalid payload attributes timestamp: {timestamp}, parent timestamp: {parent_timestamp}"
+    )]
+    InvalidPayloadAttributesTimestamp {
+        /// Timestamp of the payload attributes.
+        timestamp: u64,
+        /// Timestamp of the parent block.
+        parent_timestamp: u64,
+    },
     /// An error occurred while processing the fork choice update in the beacon consensus engine.
     #[error(transparent)]
     ForkChoiceUpdate(#[from] BeaconForkChoiceUpdateError),
@@ -133,7 +144,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             }
             EngineApiError::EngineObjectValidationError(
                 EngineObjectValidationError::PayloadAttributes(_),
-            ) => {
+            ) |
+            EngineApiError::InvalidPayloadAttributesTimestamp { .. } => {
                 // Note: the data field is not required by the spec, but is also included by other
                 // clients
                 jsonrpsee_types::error::ErrorObject::owned(
@@ -24,12 +24,16 @@ mod error;
 /// Engine API metrics.
 mod metrics;
 
+/// The `reth_buildPayloadPreview` implementation.
+mod payload_preview;
+
 pub use engine_api::{EngineApi, EngineApiSender};
 pub use error::*;
 pub use message::EngineApiMessageVersion;
+pub use payload_preview::PayloadPreviewApi;
 
 // re-export server trait for convenience
-pub use reth_rpc_api::EngineApiServer;
+pub use reth_rpc_api::{EngineApiServer, RethPayloadApiServer};
 
 #[cfg(test)]
 #[allow(unused_imports)]
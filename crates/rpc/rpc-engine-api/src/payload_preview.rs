@@ -0,0 +1,187 @@
+use crate::{EngineApiError, EngineApiResult};
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use reth_engine_primitives::EngineTypes;
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_payload_primitives::{BuiltPayload, PayloadAttributes, PayloadBuilderAttributes};
+use reth_primitives::Withdrawals;
+use reth_rpc_api::RethPayloadApiServer;
+use reth_rpc_types::reth::PayloadPreview;
+use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt};
+use tracing::trace;
+
+/// Builds payload previews for proposers, see [`RethPayloadApiServer`].
+///
+/// Previews are built by the payload builder service, but unlike payloads requested via
+/// `engine_forkchoiceUpdated` they are not tracked by a payload job.
+pub struct PayloadPreviewApi<Provider, EngineT: EngineTypes> {
+    /// The provider to look up the current head.
+    provider: Provider,
+    /// The handle to the payload builder service.
+    payload_builder: PayloadBuilderHandle<EngineT>,
+}
+
+impl<Provider, EngineT> PayloadPreviewApi<Provider, EngineT>
+where
+    Provider: BlockReaderIdExt + 'static,
+    EngineT: EngineTypes,
+{
+    /// Create new instance of [`PayloadPreviewApi`].
+    pub const fn new(provider: Provider, payload_builder: PayloadBuilderHandle<EngineT>) -> Self {
+        Self { provider, payload_builder }
+    }
+
+    /// Builds a payload with the given attributes on top of the current head.
+    pub async fn build_payload_preview(
+        &self,
+        attributes: EngineT::PayloadAttributes,
+    ) -> EngineApiResult<PayloadPreview> {
+        let head = self
+            .provider
+            .latest_header()
+            .and_then(|header| header.ok_or(ProviderError::BestBlockNotFound))
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+
+        if attributes.timestamp() <= head.timestamp {
+            return Err(EngineApiError::InvalidPayloadAttributesTimestamp {
+                timestamp: attributes.timestamp(),
+                parent_timestamp: head.timestamp,
+            })
+        }
+
+        let attributes = EngineT::PayloadBuilderAttributes::try_new(head.hash(), attributes)
+            .map_err(|err| EngineApiError::Internal(err.to_string().into()))?;
+        trace!(target: "rpc::engine", parent = %head.hash(), id = %attributes.payload_id(), "Building payload preview");
+
+        let payload = self.payload_builder.preview_payload(attributes).await?;
+        let block = payload.block();
+        Ok(PayloadPreview {
+            number: block.number,
+            parent_hash: block.parent_hash,
+            value: payload.fees(),
+            gas_used: block.gas_used,
+            gas_limit: block.gas_limit,
+            blob_gas_used: block.blob_gas_used,
+            transactions: block.body.iter().map(|tx| tx.hash()).collect(),
+            withdrawal_credits: block
+                .withdrawals
+                .as_ref()
+                .map(Withdrawals::balance_increments)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[async_trait]
+impl<Provider, EngineT> RethPayloadApiServer<EngineT> for PayloadPreviewApi<Provider, EngineT>
+where
+    Provider: BlockReaderIdExt + 'static,
+    EngineT: EngineTypes,
+{
+    /// Handler for `reth_buildPayloadPreview`
+    async fn build_payload_preview(
+        &self,
+        payload_attributes: EngineT::PayloadAttributes,
+    ) -> RpcResult<PayloadPreview> {
+        trace!(target: "rpc::engine", "Serving reth_buildPayloadPreview");
+        Ok(Self::build_payload_preview(self, payload_attributes).await?)
+    }
+}
+
+impl<Provider, EngineT> std::fmt::Debug for PayloadPreviewApi<Provider, EngineT>
+where
+    EngineT: EngineTypes,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadPreviewApi").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_payload_builder::{
+        error::PayloadBuilderError, noop::NoopPayloadBuilderService,
+        test_utils::spawn_test_payload_service,
+    };
+    use reth_primitives::{Address, Block, Header, B256};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_rpc_types::engine::PayloadAttributes;
+
+    fn provider_with_head() -> (MockEthProvider, B256) {
+        let provider = MockEthProvider::default();
+        let head = Block {
+            header: Header { number: 1, timestamp: 1_000, ..Default::default() },
+            ..Default::default()
+        }
+        .seal_slow();
+        let hash = head.hash();
+        provider.add_block(hash, head.unseal());
+        (provider, hash)
+    }
+
+    fn attributes(timestamp: u64) -> PayloadAttributes {
+        PayloadAttributes {
+            timestamp,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: Address::with_last_byte(1),
+            withdrawals: Some(Vec::new()),
+            parent_beacon_block_root: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn builds_preview_on_head() {
+        let (provider, head) = provider_with_head();
+        let api =
+            PayloadPreviewApi::<_, EthEngineTypes>::new(provider, spawn_test_payload_service());
+
+        let preview = api.build_payload_preview(attributes(1_012)).await.unwrap();
+        assert_eq!(preview.parent_hash, head);
+        assert!(preview.transactions.is_empty());
+        assert!(preview.withdrawal_credits.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_stale_timestamp() {
+        let (provider, _) = provider_with_head();
+        let api =
+            PayloadPreviewApi::<_, EthEngineTypes>::new(provider, spawn_test_payload_service());
+
+        assert_matches!(
+            api.build_payload_preview(attributes(1_000)).await,
+            Err(EngineApiError::InvalidPayloadAttributesTimestamp {
+                timestamp: 1_000,
+                parent_timestamp: 1_000
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_without_head() {
+        let api = PayloadPreviewApi::<_, EthEngineTypes>::new(
+            MockEthProvider::default(),
+            spawn_test_payload_service(),
+        );
+
+        assert_matches!(
+            api.build_payload_preview(attributes(1_012)).await,
+            Err(EngineApiError::Internal(_))
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_if_previews_are_unsupported() {
+        let (provider, _) = provider_with_head();
+        let (service, payload_builder) = NoopPayloadBuilderService::<EthEngineTypes>::new();
+        tokio::spawn(service);
+        let api = PayloadPreviewApi::new(provider, payload_builder);
+
+        assert_matches!(
+            api.build_payload_preview(attributes(1_012)).await,
+            Err(EngineApiError::GetPayloadError(PayloadBuilderError::PreviewUnsupported))
+        );
+    }
+}
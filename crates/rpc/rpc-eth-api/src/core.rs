@@ -12,9 +12,9 @@ use reth_primitives::{
 };
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_rpc_types::{
+    overrides::{ExtendedEvmOverrides, ExtendedStateOverride},
     serde_helpers::JsonStorageKey,
     simulate::{SimBlock, SimulatedBlock},
-    state::{EvmOverrides, StateOverride},
     AnyTransactionReceipt, BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse,
     FeeHistory, Header, Index, RichBlock, StateContext, SyncStatus, Transaction,
     TransactionRequest, Work,
@@ -207,7 +207,7 @@ pub trait EthApi {
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-        state_overrides: Option<StateOverride>,
+        state_overrides: Option<ExtendedStateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes>;
 
@@ -218,7 +218,7 @@ pub trait EthApi {
         &self,
         bundle: Bundle,
         state_context: Option<StateContext>,
-        state_override: Option<StateOverride>,
+        state_override: Option<ExtendedStateOverride>,
     ) -> RpcResult<Vec<EthCallResponse>>;
 
     /// Generates an access list for a transaction.
@@ -249,7 +249,7 @@ pub trait EthApi {
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-        state_override: Option<StateOverride>,
+        state_override: Option<ExtendedStateOverride>,
     ) -> RpcResult<U256>;
 
     /// Returns the current price per gas in wei.
@@ -590,7 +590,7 @@ where
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-        state_overrides: Option<StateOverride>,
+        state_overrides: Option<ExtendedStateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes> {
         trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, "Serving eth_call");
@@ -598,7 +598,7 @@ where
             self,
             request,
             block_number,
-            EvmOverrides::new(state_overrides, block_overrides),
+            ExtendedEvmOverrides::new(state_overrides, block_overrides),
         )
         .await?)
     }
@@ -608,7 +608,7 @@ where
         &self,
         bundle: Bundle,
         state_context: Option<StateContext>,
-        state_override: Option<StateOverride>,
+        state_override: Option<ExtendedStateOverride>,
     ) -> RpcResult<Vec<EthCallResponse>> {
         trace!(target: "rpc::eth", ?bundle, ?state_context, ?state_override, "Serving eth_callMany");
         Ok(EthCall::call_many(self, bundle, state_context, state_override).await?)
@@ -629,7 +629,7 @@ where
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-        state_override: Option<StateOverride>,
+        state_override: Option<ExtendedStateOverride>,
     ) -> RpcResult<U256> {
         trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_estimateGas");
         Ok(EthCall::estimate_gas_at(
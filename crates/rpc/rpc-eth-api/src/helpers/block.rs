@@ -4,10 +4,12 @@ use std::sync::Arc;
 
 use futures::Future;
 use reth_primitives::{BlockId, Receipt, SealedBlock, SealedBlockWithSenders, TransactionMeta};
-use reth_provider::{BlockIdReader, BlockReader, BlockReaderIdExt, HeaderProvider};
+use reth_provider::{
+    BlockIdReader, BlockReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider,
+};
 use reth_rpc_eth_types::{EthApiError, EthStateCache, ReceiptBuilder};
 use reth_rpc_types::{AnyTransactionReceipt, Header, Index, RichBlock};
-use reth_rpc_types_compat::block::{from_block, uncle_block_from_header};
+use reth_rpc_types_compat::block::{from_block, from_primitive_with_hash, uncle_block_from_header};
 
 use crate::FromEthApiError;
 
@@ -22,6 +24,8 @@ pub trait EthBlocks: LoadBlock {
     fn provider(&self) -> impl HeaderProvider;
 
     /// Returns the block header for the given block id.
+    ///
+    /// Unless the pending block is requested, this only loads the header and not the block body.
     fn rpc_block_header(
         &self,
         block_id: BlockId,
@@ -29,7 +33,28 @@ pub trait EthBlocks: LoadBlock {
     where
         Self: LoadPendingBlock + SpawnBlocking,
     {
-        async move { Ok(self.rpc_block(block_id, false).await?.map(|block| block.inner.header)) }
+        async move {
+            if block_id.is_pending() {
+                // the pending block is built locally and isn't served by the provider
+                return Ok(self.rpc_block(block_id, false).await?.map(|block| block.inner.header))
+            }
+
+            // only the header is loaded, the provider serves it from memory if possible
+            let Some(header) = LoadBlock::provider(self)
+                .sealed_header_by_id(block_id)
+                .map_err(Self::Error::from_eth_err)?
+            else {
+                return Ok(None)
+            };
+            let total_difficulty = EthBlocks::provider(self)
+                .header_td_by_number(header.number)
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+
+            let mut header = from_primitive_with_hash(header);
+            header.total_difficulty = Some(total_difficulty);
+            Ok(Some(header))
+        }
     }
 
     /// Returns the populated rpc block object for the given block id.
@@ -110,6 +135,8 @@ pub trait EthBlocks: LoadBlock {
                 let block_hash = block.hash();
                 let excess_blob_gas = block.excess_blob_gas;
                 let timestamp = block.timestamp;
+                let blob_params =
+                    LoadReceipt::provider(self).chain_spec().blob_params_at_timestamp(timestamp);
                 let block = block.unseal();
 
                 let receipts = block
@@ -128,7 +155,7 @@ pub trait EthBlocks: LoadBlock {
                             timestamp,
                         };
 
-                        ReceiptBuilder::new(&tx, meta, receipt, &receipts)
+                        ReceiptBuilder::new(&tx, meta, receipt, &receipts, blob_params)
                             .map(|builder| builder.build())
                             .map_err(Self::Error::from_eth_err)
                     })
@@ -19,7 +19,7 @@ use reth_rpc_eth_types::{
     error::ensure_success,
     revm_utils::{
         apply_block_overrides, apply_state_overrides, caller_gas_allowance,
-        cap_tx_gas_limit_with_caller_allowance, get_precompiles, CallFees,
+        cap_tx_gas_limit_with_caller_allowance, get_precompiles, CallFees, PrecompileMoves,
     },
     EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
 };
@@ -27,8 +27,8 @@ use reth_rpc_server_types::constants::gas_oracle::{
     CALL_STIPEND_GAS, ESTIMATE_GAS_ERROR_RATIO, MIN_TRANSACTION_GAS,
 };
 use reth_rpc_types::{
+    overrides::{ExtendedEvmOverrides, ExtendedStateOverride},
     simulate::{SimBlock, SimulatedBlock},
-    state::{EvmOverrides, StateOverride},
     BlockId, Bundle, EthCallResponse, StateContext, TransactionInfo, TransactionRequest,
 };
 use revm::{Database, DatabaseCommit};
@@ -45,7 +45,7 @@ pub trait EthCall: Call + LoadPendingBlock {
         &self,
         request: TransactionRequest,
         at: BlockId,
-        state_override: Option<StateOverride>,
+        state_override: Option<ExtendedStateOverride>,
     ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
         Call::estimate_gas_at(self, request, at, state_override)
     }
@@ -67,7 +67,7 @@ pub trait EthCall: Call + LoadPendingBlock {
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-        overrides: EvmOverrides,
+        overrides: ExtendedEvmOverrides,
     ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
         async move {
             let (res, _env) =
@@ -83,7 +83,7 @@ pub trait EthCall: Call + LoadPendingBlock {
         &self,
         bundle: Bundle,
         state_context: Option<StateContext>,
-        mut state_override: Option<StateOverride>,
+        mut state_override: Option<ExtendedStateOverride>,
     ) -> impl Future<Output = Result<Vec<EthCallResponse>, Self::Error>> + Send
     where
         Self: LoadBlock,
@@ -111,6 +111,14 @@ pub trait EthCall: Call + LoadPendingBlock {
             let Some(block) = block else { return Err(EthApiError::UnknownBlockNumber.into()) };
             let gas_limit = self.call_gas_limit();
 
+            // precompiles stay moved for all transactions of the bundle
+            let precompile_moves = state_override
+                .as_ref()
+                .map(PrecompileMoves::new)
+                .transpose()
+                .map_err(Self::Error::from_eth_err)?
+                .unwrap_or_default();
+
             // we're essentially replaying the transactions in the block here, hence we need the
             // state that points to the beginning of the block, which is the state at
             // the parent block
@@ -141,7 +149,7 @@ pub trait EthCall: Call + LoadPendingBlock {
                             block_env.clone(),
                             Call::evm_config(&this).tx_env(&tx),
                         );
-                        let (res, _) = this.transact(&mut db, env)?;
+                        let (res, _) = this.transact(&mut db, env, &PrecompileMoves::default())?;
                         db.commit(res.state);
                     }
                 }
@@ -152,7 +160,8 @@ pub trait EthCall: Call + LoadPendingBlock {
                 while let Some(tx) = transactions.next() {
                     // apply state overrides only once, before the first transaction
                     let state_overrides = state_override.take();
-                    let overrides = EvmOverrides::new(state_overrides, block_overrides.clone());
+                    let overrides =
+                        ExtendedEvmOverrides::new(state_overrides, block_overrides.clone());
 
                     let env = this
                         .prepare_call_env(
@@ -164,7 +173,7 @@ pub trait EthCall: Call + LoadPendingBlock {
                             overrides,
                         )
                         .map(Into::into)?;
-                    let (res, _) = this.transact(&mut db, env)?;
+                    let (res, _) = this.transact(&mut db, env, &precompile_moves)?;
 
                     match ensure_success(res.result) {
                         Ok(output) => {
@@ -309,17 +318,19 @@ pub trait Call: LoadState + SpawnBlocking {
     }
 
     /// Executes the [`EnvWithHandlerCfg`] against the given [Database] without committing state
-    /// changes.
+    /// changes, with the precompiles that are moved by the state overrides at their new addresses.
     fn transact<DB>(
         &self,
         db: DB,
         env: EnvWithHandlerCfg,
+        precompile_moves: &PrecompileMoves,
     ) -> Result<(ResultAndState, EnvWithHandlerCfg), Self::Error>
     where
         DB: Database,
         EthApiError: From<DB::Error>,
     {
         let mut evm = self.evm_config().evm_with_env(db, env);
+        precompile_moves.apply(&mut evm).map_err(Self::Error::from_eth_err)?;
         let res = evm.transact().map_err(Self::Error::from_evm_err)?;
         let (_, env) = evm.into_db_and_env_with_handler_cfg();
         Ok((res, env))
@@ -330,13 +341,26 @@ pub trait Call: LoadState + SpawnBlocking {
         &self,
         request: TransactionRequest,
         at: BlockId,
-        overrides: EvmOverrides,
+        overrides: ExtendedEvmOverrides,
     ) -> impl Future<Output = Result<(ResultAndState, EnvWithHandlerCfg), Self::Error>> + Send
     where
         Self: LoadPendingBlock,
     {
-        let this = self.clone();
-        self.spawn_with_call_at(request, at, overrides, move |db, env| this.transact(db, env))
+        async move {
+            let precompile_moves = overrides
+                .state
+                .as_ref()
+                .map(PrecompileMoves::new)
+                .transpose()
+                .map_err(Self::Error::from_eth_err)?
+                .unwrap_or_default();
+
+            let this = self.clone();
+            self.spawn_with_call_at(request, at, overrides, move |db, env| {
+                this.transact(db, env, &precompile_moves)
+            })
+            .await
+        }
     }
 
     /// Executes the closure with the state that corresponds to the given [`BlockId`] on a new task
@@ -364,7 +388,7 @@ pub trait Call: LoadState + SpawnBlocking {
         &self,
         request: TransactionRequest,
         at: BlockId,
-        overrides: EvmOverrides,
+        overrides: ExtendedEvmOverrides,
         f: F,
     ) -> impl Future<Output = Result<R, Self::Error>> + Send
     where
@@ -451,7 +475,7 @@ pub trait Call: LoadState + SpawnBlocking {
                     Call::evm_config(&this).tx_env(&tx),
                 );
 
-                let (res, _) = this.transact(&mut db, env)?;
+                let (res, _) = this.transact(&mut db, env, &PrecompileMoves::default())?;
                 f(tx_info, res, db)
             })
             .await
@@ -501,7 +525,7 @@ pub trait Call: LoadState + SpawnBlocking {
         &self,
         request: TransactionRequest,
         at: BlockId,
-        state_override: Option<StateOverride>,
+        state_override: Option<ExtendedStateOverride>,
     ) -> impl Future<Output = Result<U256, Self::Error>> + Send
     where
         Self: LoadPendingBlock,
@@ -534,7 +558,7 @@ pub trait Call: LoadState + SpawnBlocking {
         block: BlockEnv,
         mut request: TransactionRequest,
         state: S,
-        state_override: Option<StateOverride>,
+        state_override: Option<ExtendedStateOverride>,
     ) -> Result<U256, Self::Error>
     where
         S: StateProvider,
@@ -567,7 +591,10 @@ pub trait Call: LoadState + SpawnBlocking {
         let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
         // Apply any state overrides if specified.
+        let mut precompile_moves = PrecompileMoves::default();
         if let Some(state_override) = state_override {
+            precompile_moves =
+                PrecompileMoves::new(&state_override).map_err(Self::Error::from_eth_err)?;
             apply_state_overrides(state_override, &mut db).map_err(Self::Error::from_eth_err)?;
         }
 
@@ -584,7 +611,7 @@ pub trait Call: LoadState + SpawnBlocking {
                         // with the minimum gas limit to make sure.
                         let mut env = env.clone();
                         env.tx.gas_limit = MIN_TRANSACTION_GAS;
-                        if let Ok((res, _)) = self.transact(&mut db, env) {
+                        if let Ok((res, _)) = self.transact(&mut db, env, &precompile_moves) {
                             if res.result.is_success() {
                                 return Ok(U256::from(MIN_TRANSACTION_GAS))
                             }
@@ -613,7 +640,7 @@ pub trait Call: LoadState + SpawnBlocking {
         trace!(target: "rpc::eth::estimate", ?env, "Starting gas estimation");
 
         // Execute the transaction with the highest possible gas limit.
-        let (mut res, mut env) = match self.transact(&mut db, env.clone()) {
+        let (mut res, mut env) = match self.transact(&mut db, env.clone(), &precompile_moves) {
             // Handle the exceptional case where the transaction initialization uses too much gas.
             // If the gas price or gas limit was specified in the request, retry the transaction
             // with the block's gas limit to determine if the failure was due to
@@ -622,7 +649,12 @@ pub trait Call: LoadState + SpawnBlocking {
                 if err.is_gas_too_high() &&
                     (tx_request_gas_limit.is_some() || tx_request_gas_price.is_some()) =>
             {
-                return Err(self.map_out_of_gas_err(block_env_gas_limit, env, &mut db))
+                return Err(self.map_out_of_gas_err(
+                    block_env_gas_limit,
+                    env,
+                    &mut db,
+                    &precompile_moves,
+                ))
             }
             // Propagate other results (successful or other errors).
             ethres => ethres?,
@@ -639,7 +671,12 @@ pub trait Call: LoadState + SpawnBlocking {
                 // if price or limit was included in the request then we can execute the request
                 // again with the block's gas limit to check if revert is gas related or not
                 return if tx_request_gas_limit.is_some() || tx_request_gas_price.is_some() {
-                    Err(self.map_out_of_gas_err(block_env_gas_limit, env, &mut db))
+                    Err(self.map_out_of_gas_err(
+                        block_env_gas_limit,
+                        env,
+                        &mut db,
+                        &precompile_moves,
+                    ))
                 } else {
                     // the transaction did revert
                     Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into_eth_err())
@@ -672,7 +709,7 @@ pub trait Call: LoadState + SpawnBlocking {
             env.tx.gas_limit = optimistic_gas_limit;
             // Re-execute the transaction with the new gas limit and update the result and
             // environment.
-            (res, env) = self.transact(&mut db, env)?;
+            (res, env) = self.transact(&mut db, env, &precompile_moves)?;
             // Update the gas used based on the new result.
             gas_used = res.result.gas_used();
             // Update the gas limit estimates (highest and lowest) based on the execution result.
@@ -707,7 +744,7 @@ pub trait Call: LoadState + SpawnBlocking {
             env.tx.gas_limit = mid_gas_limit;
 
             // Execute transaction and handle potential gas errors, adjusting limits accordingly.
-            match self.transact(&mut db, env.clone()) {
+            match self.transact(&mut db, env.clone(), &precompile_moves) {
                 Err(err) if err.is_gas_too_high() => {
                     // Increase the lowest gas limit if gas is too high
                     lowest_gas_limit = mid_gas_limit;
@@ -790,13 +827,14 @@ pub trait Call: LoadState + SpawnBlocking {
         env_gas_limit: U256,
         mut env: EnvWithHandlerCfg,
         db: &mut CacheDB<StateProviderDatabase<S>>,
+        precompile_moves: &PrecompileMoves,
     ) -> Self::Error
     where
         S: StateProvider,
     {
         let req_gas_limit = env.tx.gas_limit;
         env.tx.gas_limit = env_gas_limit.try_into().unwrap_or(u64::MAX);
-        let (res, _) = match self.transact(db, env) {
+        let (res, _) = match self.transact(db, env, precompile_moves) {
             Ok(res) => res,
             Err(err) => return err,
         };
@@ -925,7 +963,7 @@ pub trait Call: LoadState + SpawnBlocking {
         mut request: TransactionRequest,
         gas_limit: u64,
         db: &mut CacheDB<DB>,
-        overrides: EvmOverrides,
+        overrides: ExtendedEvmOverrides,
     ) -> Result<EnvWithHandlerCfg, Self::Error>
     where
         DB: DatabaseRef,
@@ -151,7 +151,11 @@ pub trait EthFees: LoadFee {
                     .push(last_entry.next_block_base_fee(&LoadFee::provider(self).chain_spec())
                         as u128);
 
-                base_fee_per_blob_gas.push(last_entry.next_block_blob_fee().unwrap_or_default());
+                base_fee_per_blob_gas.push(
+                    last_entry
+                        .next_block_blob_fee(&LoadFee::provider(self).chain_spec())
+                        .unwrap_or_default(),
+                );
             } else {
             // read the requested header range
             let headers = LoadFee::provider(self).sealed_headers_range(start_block..=end_block).map_err(Self::Error::from_eth_err)?;
@@ -159,13 +163,15 @@ pub trait EthFees: LoadFee {
                 return Err(EthApiError::InvalidBlockRange.into())
             }
 
+            let chain_spec = LoadFee::provider(self).chain_spec();
             for header in &headers {
+                let blob_params = chain_spec.blob_params_at_timestamp(header.timestamp);
                 base_fee_per_gas.push(header.base_fee_per_gas.unwrap_or_default() as u128);
                 gas_used_ratio.push(header.gas_used as f64 / header.gas_limit as f64);
-                base_fee_per_blob_gas.push(header.blob_fee().unwrap_or_default());
+                base_fee_per_blob_gas.push(header.blob_fee_with_params(blob_params).unwrap_or_default());
                 blob_gas_used_ratio.push(
                     header.blob_gas_used.unwrap_or_default() as f64 /
-                        reth_primitives::constants::eip4844::MAX_DATA_GAS_PER_BLOCK as f64,
+                        blob_params.max_blob_gas_per_block() as f64,
                 );
 
                 // Percentiles were specified, so we need to collect reward percentile ino
@@ -194,7 +200,7 @@ pub trait EthFees: LoadFee {
             // The unwrap is safe since we checked earlier that we got at least 1 header.
             let last_header = headers.last().expect("is present");
             base_fee_per_gas.push(
-                LoadFee::provider(self).chain_spec().base_fee_params_at_timestamp(last_header.timestamp).next_block_base_fee(
+                chain_spec.base_fee_params_at_timestamp(last_header.timestamp).next_block_base_fee(
                     last_header.gas_used as u128,
                     last_header.gas_limit as u128,
                     last_header.base_fee_per_gas.unwrap_or_default() as u128,
@@ -202,8 +208,13 @@ pub trait EthFees: LoadFee {
 
             // Same goes for the `base_fee_per_blob_gas`:
             // > "[..] includes the next block after the newest of the returned range, because this value can be derived from the newest block.
-            base_fee_per_blob_gas
-                .push(last_header.next_block_blob_fee().unwrap_or_default());
+            base_fee_per_blob_gas.push(
+                last_header
+                    .next_block_blob_fee_with_params(
+                        chain_spec.blob_params_at_timestamp(last_header.timestamp),
+                    )
+                    .unwrap_or_default(),
+            );
         };
 
             Ok(FeeHistory {
@@ -340,7 +351,12 @@ pub trait LoadFee: LoadBlock {
         async move {
             self.block(BlockNumberOrTag::Latest.into())
                 .await?
-                .and_then(|h: reth_primitives::SealedBlock| h.next_block_blob_fee())
+                .and_then(|h: reth_primitives::SealedBlock| {
+                    let chain_spec = LoadFee::provider(self).chain_spec();
+                    h.next_block_blob_fee_with_params(
+                        chain_spec.blob_params_at_timestamp(h.timestamp),
+                    )
+                })
                 .ok_or(EthApiError::ExcessBlobGasNotSet.into())
                 .map(U256::from)
         }
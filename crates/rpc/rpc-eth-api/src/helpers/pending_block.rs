@@ -5,10 +5,13 @@ use std::time::{Duration, Instant};
 
 use futures::Future;
 use reth_chainspec::EthereumHardforks;
-use reth_evm::{system_calls::pre_block_beacon_root_contract_call, ConfigureEvm, ConfigureEvmEnv};
+use reth_evm::{
+    system_calls::{post_block_execution_changes, pre_block_beacon_root_contract_call},
+    ConfigureEvm, ConfigureEvmEnv,
+};
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    constants::{eip4844::MAX_DATA_GAS_PER_BLOCK, BEACON_NONCE, EMPTY_ROOT_HASH},
+    constants::{BEACON_NONCE, EMPTY_ROOT_HASH},
     proofs::calculate_transaction_root,
     revm_primitives::{
         BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, EVMError, Env, ExecutionResult, InvalidTransaction,
@@ -94,7 +97,9 @@ pub trait LoadPendingBlock: EthApiTypes {
             );
 
             // update excess blob gas consumed above target
-            latest_header.excess_blob_gas = latest_header.next_block_excess_blob_gas();
+            latest_header.excess_blob_gas = latest_header.next_block_excess_blob_gas_with_params(
+                chain_spec.blob_params_at_timestamp(latest_header.timestamp),
+            );
 
             // we're reusing the same block hash because we need this to lookup the block's state
             let latest = SealedHeader::new(latest_header, block_hash);
@@ -132,6 +137,43 @@ pub trait LoadPendingBlock: EthApiTypes {
                 return Ok(pending.origin.into_actual_pending())
             }
 
+            Ok(self.built_pending_block(pending).await?.map(|(block, _)| block))
+        }
+    }
+
+    /// Returns the locally built pending block and its receipts.
+    fn local_pending_block_and_receipts(
+        &self,
+    ) -> impl Future<Output = Result<Option<(SealedBlockWithSenders, Vec<Receipt>)>, Self::Error>> + Send
+    where
+        Self: SpawnBlocking,
+    {
+        async move {
+            let pending = self.pending_block_env_and_cfg()?;
+            if pending.origin.is_actual_pending() {
+                // the receipts of the pending block from the CL are served by the provider
+                return Ok(self
+                    .provider()
+                    .pending_block_and_receipts()
+                    .map_err(Self::Error::from_eth_err)?
+                    .and_then(|(block, receipts)| Some((block.seal_with_senders()?, receipts))))
+            }
+
+            self.built_pending_block(pending).await
+        }
+    }
+
+    /// Returns the pending block built from the pool on top of the latest block, and its receipts.
+    ///
+    /// The built block is cached for a second, after which it is built again.
+    fn built_pending_block(
+        &self,
+        pending: PendingBlockEnv,
+    ) -> impl Future<Output = Result<Option<(SealedBlockWithSenders, Vec<Receipt>)>, Self::Error>> + Send
+    where
+        Self: SpawnBlocking,
+    {
+        async move {
             let mut lock = self.pending_block().lock().await;
 
             let now = Instant::now();
@@ -143,12 +185,12 @@ pub trait LoadPendingBlock: EthApiTypes {
                     pending.origin.header().hash() == pending_block.block.parent_hash &&
                     now <= pending_block.expires_at
                 {
-                    return Ok(Some(pending_block.block.clone()))
+                    return Ok(Some((pending_block.block.clone(), pending_block.receipts.clone())))
                 }
             }
 
             // no pending block from the CL yet, so we need to build it ourselves via txpool
-            let pending_block = match self
+            let (pending_block, receipts) = match self
                 .spawn_blocking_io(move |this| {
                     // we rebuild the block
                     this.build_block(pending)
@@ -163,9 +205,13 @@ pub trait LoadPendingBlock: EthApiTypes {
             };
 
             let now = Instant::now();
-            *lock = Some(PendingBlock::new(pending_block.clone(), now + Duration::from_secs(1)));
+            *lock = Some(PendingBlock::new(
+                pending_block.clone(),
+                receipts.clone(),
+                now + Duration::from_secs(1),
+            ));
 
-            Ok(Some(pending_block))
+            Ok(Some((pending_block, receipts)))
         }
     }
 
@@ -198,13 +244,17 @@ pub trait LoadPendingBlock: EthApiTypes {
         execution_outcome.receipts_root_slow(block_number).expect("Block is present")
     }
 
-    /// Builds a pending block using the configured provider and pool.
+    /// Builds a pending block using the configured provider and pool, and returns it with its
+    /// receipts.
     ///
     /// If the origin is the actual pending block, the block is built with withdrawals.
     ///
     /// After Cancun, if the origin is the actual pending block, the block includes the EIP-4788 pre
     /// block contract call using the parent beacon block root received from the CL.
-    fn build_block(&self, env: PendingBlockEnv) -> Result<SealedBlockWithSenders, Self::Error>
+    fn build_block(
+        &self,
+        env: PendingBlockEnv,
+    ) -> Result<(SealedBlockWithSenders, Vec<Receipt>), Self::Error>
     where
         EthApiError: From<ProviderError>,
     {
@@ -240,6 +290,9 @@ pub trait LoadPendingBlock: EthApiTypes {
         };
 
         let chain_spec = self.provider().chain_spec();
+        let max_blob_gas_per_block = chain_spec
+            .blob_params_at_timestamp(block_env.timestamp.to::<u64>())
+            .max_blob_gas_per_block();
 
         let parent_beacon_block_root = if origin.is_actual_pending() {
             // apply eip-4788 pre block contract call if we got the block from the CL with the real
@@ -294,7 +347,7 @@ pub trait LoadPendingBlock: EthApiTypes {
             // the EIP-4844 can still fit in the block
             if let Some(blob_tx) = tx.transaction.as_eip4844() {
                 let tx_blob_gas = blob_tx.blob_gas();
-                if sum_blob_gas_used + tx_blob_gas > MAX_DATA_GAS_PER_BLOCK {
+                if sum_blob_gas_used + tx_blob_gas > max_blob_gas_per_block {
                     // we can't fit this _blob_ transaction into the block, so we mark it as
                     // invalid, which removes its dependent transactions from
                     // the iterator. This is similar to the gas limit condition
@@ -345,7 +398,7 @@ pub trait LoadPendingBlock: EthApiTypes {
                 sum_blob_gas_used += tx_blob_gas;
 
                 // if we've reached the max data gas per block, we can skip blob txs entirely
-                if sum_blob_gas_used == MAX_DATA_GAS_PER_BLOCK {
+                if sum_blob_gas_used == max_blob_gas_per_block {
                     best_txs.skip_blobs();
                 }
             }
@@ -356,7 +409,7 @@ pub trait LoadPendingBlock: EthApiTypes {
             cumulative_gas_used += gas_used;
 
             // Push transaction changeset and calculate header bloom filter for receipt.
-            receipts.push(Some(self.assemble_receipt(&tx, result, cumulative_gas_used)));
+            receipts.push(self.assemble_receipt(&tx, result, cumulative_gas_used));
 
             // append transaction to the list of executed transactions
             let (tx, sender) = tx.to_components();
@@ -364,6 +417,17 @@ pub trait LoadPendingBlock: EthApiTypes {
             senders.push(sender);
         }
 
+        // apply the chain specific post-execution changes before the withdrawals, like the block
+        // executor and the payload builder
+        post_block_execution_changes(
+            self.evm_config(),
+            chain_spec.as_ref(),
+            &mut db,
+            &cfg,
+            &block_env,
+        )
+        .map_err(|err| EthApiError::Internal(err.into()))?;
+
         // executes the withdrawals and commits them to the Database and BundleState.
         let balance_increments = post_block_withdrawals_balance_increments(
             &chain_spec,
@@ -379,7 +443,7 @@ pub trait LoadPendingBlock: EthApiTypes {
 
         let execution_outcome = ExecutionOutcome::new(
             db.take_bundle(),
-            vec![receipts].into(),
+            vec![receipts.iter().cloned().map(Some).collect::<Vec<_>>()].into(),
             block_number,
             Vec::new(),
         );
@@ -437,6 +501,6 @@ pub trait LoadPendingBlock: EthApiTypes {
 
         // seal the block
         let block = Block { header, body: executed_txs, ommers: vec![], withdrawals, requests };
-        Ok(SealedBlockWithSenders { block: block.seal_slow(), senders })
+        Ok((SealedBlockWithSenders { block: block.seal_slow(), senders }, receipts))
     }
 }
@@ -3,6 +3,7 @@
 
 use futures::Future;
 use reth_primitives::{Receipt, TransactionMeta, TransactionSigned};
+use reth_provider::ChainSpecProvider;
 use reth_rpc_eth_types::{EthApiError, EthStateCache, ReceiptBuilder};
 use reth_rpc_types::AnyTransactionReceipt;
 
@@ -12,6 +13,11 @@ use crate::{EthApiTypes, FromEthApiError};
 ///
 /// Behaviour shared by several `eth_` RPC methods, not exclusive to `eth_` receipts RPC methods.
 pub trait LoadReceipt: EthApiTypes + Send + Sync {
+    /// Returns a handle for reading the chain spec.
+    ///
+    /// Data access in default (L1) trait method implementations.
+    fn provider(&self) -> impl ChainSpecProvider;
+
     /// Returns a handle for reading data from memory.
     ///
     /// Data access in default (L1) trait method implementations.
@@ -33,7 +39,10 @@ pub trait LoadReceipt: EthApiTypes + Send + Sync {
                 .map_err(Self::Error::from_eth_err)?
                 .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
 
-            Ok(ReceiptBuilder::new(&tx, meta, &receipt, &all_receipts)?.build())
+            let blob_params =
+                LoadReceipt::provider(self).chain_spec().blob_params_at_timestamp(meta.timestamp);
+
+            Ok(ReceiptBuilder::new(&tx, meta, &receipt, &all_receipts, blob_params)?.build())
         }
     }
 }
@@ -257,7 +257,12 @@ pub trait LoadState: EthApiTypes {
             let (cfg, mut block_env, _) = self.evm_env_at(header.parent_hash.into()).await?;
 
             let after_merge = cfg.handler_cfg.spec_id >= SpecId::MERGE;
-            self.evm_config().fill_block_env(&mut block_env, header, after_merge);
+            self.evm_config().fill_block_env(
+                &mut block_env,
+                &LoadState::provider(self).chain_spec(),
+                header,
+                after_merge,
+            );
 
             Ok((cfg, block_env))
         }
@@ -1,9 +1,19 @@
 //! Loads a pending block from database. Helper trait for `eth_` call and trace RPC methods.
 
+use std::fmt::Display;
+
 use futures::Future;
-use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
-use reth_primitives::B256;
-use reth_revm::database::StateProviderDatabase;
+use reth_chainspec::EthereumHardforks;
+use reth_evm::{
+    system_calls::{
+        post_block_consolidation_requests_contract_call, post_block_execution_changes,
+        post_block_withdrawal_requests_contract_call, pre_block_beacon_root_contract_call,
+    },
+    ConfigureEvm, ConfigureEvmEnv,
+};
+use reth_primitives::{Header, SealedBlockWithSenders, TransactionSignedEcRecovered, B256};
+use reth_provider::{ChainSpecProvider, ProviderError};
+use reth_revm::{database::StateProviderDatabase, state_change::apply_blockhashes_update};
 use reth_rpc_eth_types::{
     cache::db::{StateCacheDb, StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
     EthApiError,
@@ -11,7 +21,9 @@ use reth_rpc_eth_types::{
 use reth_rpc_types::{BlockId, TransactionInfo};
 use revm::{db::CacheDB, Database, DatabaseCommit, GetInspector, Inspector};
 use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
-use revm_primitives::{EnvWithHandlerCfg, EvmState, ExecutionResult, ResultAndState};
+use revm_primitives::{
+    BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, EvmState, ExecutionResult, ResultAndState,
+};
 
 use crate::FromEvmError;
 
@@ -63,6 +75,116 @@ pub trait Trace: LoadState {
         Ok((res, env, db))
     }
 
+    /// Applies the system calls that precede the transactions of the block to the database: the
+    /// EIP-4788 beacon root contract call and the EIP-2935 block hashes update.
+    fn apply_pre_block_system_calls<DB>(
+        &self,
+        db: &mut DB,
+        cfg: &CfgEnvWithHandlerCfg,
+        block_env: &BlockEnv,
+        header: &Header,
+    ) -> Result<(), Self::Error>
+    where
+        DB: Database<Error: Into<ProviderError> + Display> + DatabaseCommit,
+    {
+        let chain_spec = LoadState::provider(self).chain_spec();
+        pre_block_beacon_root_contract_call(
+            db,
+            self.evm_config(),
+            &chain_spec,
+            cfg,
+            block_env,
+            header.number,
+            header.timestamp,
+            header.parent_beacon_block_root,
+        )
+        .map_err(|err| EthApiError::Internal(err.into()))?;
+        apply_blockhashes_update(
+            db,
+            &chain_spec,
+            header.timestamp,
+            header.number,
+            header.parent_hash,
+        )
+        .map_err(|err| EthApiError::Internal(err.into()))?;
+        Ok(())
+    }
+
+    /// Applies the system calls and changes that follow the transactions of the block to the
+    /// database: the EIP-7002 and EIP-7251 request contract calls and the chain specific
+    /// post-execution changes, in the order of the block executor.
+    fn apply_post_block_system_calls<DB>(
+        &self,
+        db: &mut DB,
+        cfg: &CfgEnvWithHandlerCfg,
+        block_env: &BlockEnv,
+    ) -> Result<(), Self::Error>
+    where
+        DB: Database<Error: Into<ProviderError> + Display> + DatabaseCommit,
+    {
+        let chain_spec = LoadState::provider(self).chain_spec();
+        if chain_spec.is_prague_active_at_timestamp(block_env.timestamp.saturating_to()) {
+            post_block_withdrawal_requests_contract_call(self.evm_config(), db, cfg, block_env)
+                .map_err(|err| EthApiError::Internal(err.into()))?;
+            post_block_consolidation_requests_contract_call(self.evm_config(), db, cfg, block_env)
+                .map_err(|err| EthApiError::Internal(err.into()))?;
+        }
+        post_block_execution_changes(self.evm_config(), &chain_spec, db, cfg, block_env)
+            .map_err(|err| EthApiError::Internal(err.into()))?;
+        Ok(())
+    }
+
+    /// Replays the first `max_transactions` transactions of the block on the database, which must
+    /// hold the state of the parent block with the pre-block system calls applied, see
+    /// [`Self::apply_pre_block_system_calls`].
+    ///
+    /// `execute` is invoked for each transaction with its info, the transaction, its environment
+    /// and the database. It executes the transaction and returns the changed state, which is
+    /// committed to the database before the next transaction.
+    fn replay_block_transactions<DB, F>(
+        &self,
+        db: &mut DB,
+        cfg: &CfgEnvWithHandlerCfg,
+        block_env: &BlockEnv,
+        block: SealedBlockWithSenders,
+        max_transactions: usize,
+        mut execute: F,
+    ) -> Result<(), Self::Error>
+    where
+        DB: DatabaseCommit,
+        F: FnMut(
+            TransactionInfo,
+            &TransactionSignedEcRecovered,
+            EnvWithHandlerCfg,
+            &mut DB,
+        ) -> Result<EvmState, Self::Error>,
+    {
+        let block_hash = block.hash();
+        let block_number = block_env.number.saturating_to::<u64>();
+        let base_fee = block_env.basefee.saturating_to::<u128>();
+
+        for (idx, tx) in block.into_transactions_ecrecovered().take(max_transactions).enumerate() {
+            let tx_info = TransactionInfo {
+                hash: Some(tx.hash()),
+                index: Some(idx as u64),
+                block_hash: Some(block_hash),
+                block_number: Some(block_number),
+                base_fee: Some(base_fee),
+            };
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block_env.clone(),
+                self.evm_config().tx_env(&tx),
+            );
+
+            let state = execute(tx_info, &tx, env, db)?;
+            // the changes of the transaction apply to the next one
+            db.commit(state);
+        }
+
+        Ok(())
+    }
+
     /// Executes the transaction on top of the given [`BlockId`] with a tracer configured by the
     /// config.
     ///
@@ -189,11 +311,13 @@ pub trait Trace: LoadState {
             // we need to get the state of the parent block because we're essentially replaying the
             // block the transaction is included in
             let parent_block = block.parent_hash;
+            let header = block.header.header().clone();
             let block_txs = block.into_transactions_ecrecovered();
 
             let this = self.clone();
             self.spawn_with_state_at_block(parent_block.into(), move |state| {
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                this.apply_pre_block_system_calls(&mut db, &cfg, &block_env, &header)?;
 
                 // replay all transactions prior to the targeted transaction
                 this.replay_transactions_until(
@@ -297,61 +421,34 @@ pub trait Trace: LoadState {
 
             // replay all transactions of the block
             self.spawn_tracing(move |this| {
-                // we need to get the state of the parent block because we're replaying this block
-                // on top of its parent block's state
-                let state_at = block.parent_hash;
-                let block_hash = block.hash();
-
-                let block_number = block_env.number.saturating_to::<u64>();
-                let base_fee = block_env.basefee.saturating_to::<u128>();
-
-                // prepare transactions, we do everything upfront to reduce time spent with open
-                // state
                 let max_transactions = highest_index.map_or(block.body.len(), |highest| {
                     // we need + 1 because the index is 0-based
                     highest as usize + 1
                 });
                 let mut results = Vec::with_capacity(max_transactions);
 
-                let mut transactions = block
-                    .into_transactions_ecrecovered()
-                    .take(max_transactions)
-                    .enumerate()
-                    .map(|(idx, tx)| {
-                        let tx_info = TransactionInfo {
-                            hash: Some(tx.hash()),
-                            index: Some(idx as u64),
-                            block_hash: Some(block_hash),
-                            block_number: Some(block_number),
-                            base_fee: Some(base_fee),
-                        };
-                        let tx_env = Trace::evm_config(&this).tx_env(&tx);
-                        (tx_info, tx_env)
-                    })
-                    .peekable();
-
-                // now get the state
-                let state = this.state_at_block_id(state_at.into())?;
+                // we need to get the state of the parent block because we're replaying this block
+                // on top of its parent block's state
+                let state = this.state_at_block_id(block.parent_hash.into())?;
                 let mut db =
                     CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)));
 
-                while let Some((tx_info, tx)) = transactions.next() {
-                    let env =
-                        EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), tx);
-
-                    let mut inspector = inspector_setup();
-                    let (res, _) =
-                        this.inspect(StateCacheDbRefMutWrapper(&mut db), env, &mut inspector)?;
-                    let ResultAndState { result, state } = res;
-                    results.push(f(tx_info, inspector, result, &state, &db)?);
-
-                    // need to apply the state changes of this transaction before executing the
-                    // next transaction, but only if there's a next transaction
-                    if transactions.peek().is_some() {
-                        // commit the state changes to the DB
-                        db.commit(state)
-                    }
-                }
+                this.apply_pre_block_system_calls(&mut db, &cfg, &block_env, &block.header)?;
+                this.replay_block_transactions(
+                    &mut db,
+                    &cfg,
+                    &block_env,
+                    block,
+                    max_transactions,
+                    |tx_info, _, env, db| {
+                        let mut inspector = inspector_setup();
+                        let (res, _) =
+                            this.inspect(StateCacheDbRefMutWrapper(&mut *db), env, &mut inspector)?;
+                        let ResultAndState { result, state } = res;
+                        results.push(f(tx_info, inspector, result, &state, db)?);
+                        Ok(state)
+                    },
+                )?;
 
                 Ok(Some(results))
             })
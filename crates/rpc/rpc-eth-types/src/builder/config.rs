@@ -40,6 +40,13 @@ pub struct EthConfig {
     pub fee_history_cache: FeeHistoryCacheConfig,
     /// The maximum number of getproof calls that can be executed concurrently.
     pub proof_permits: usize,
+    /// The number of threads of the blocking task pool used for CPU heavy tasks like tracing.
+    ///
+    /// If `0`, the number of threads is determined by the number of available cores.
+    pub blocking_pool_threads: usize,
+    /// Whether log filters whose `toBlock` is `pending` also return the logs of the pending
+    /// block.
+    pub pending_logs: bool,
 }
 
 impl EthConfig {
@@ -49,6 +56,7 @@ impl EthConfig {
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
             .stale_filter_ttl(self.stale_filter_ttl)
+            .pending_logs(self.pending_logs)
     }
 }
 
@@ -65,6 +73,8 @@ impl Default for EthConfig {
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
+            blocking_pool_threads: 0,
+            pending_logs: false,
         }
     }
 }
@@ -117,6 +127,18 @@ impl EthConfig {
         self.proof_permits = permits;
         self
     }
+
+    /// Configures the number of threads of the blocking task pool
+    pub const fn blocking_pool_threads(mut self, threads: usize) -> Self {
+        self.blocking_pool_threads = threads;
+        self
+    }
+
+    /// Configures whether log filters return the logs of the pending block
+    pub const fn pending_logs(mut self, pending_logs: bool) -> Self {
+        self.pending_logs = pending_logs;
+        self
+    }
 }
 
 /// Config for the filter
@@ -135,6 +157,11 @@ pub struct EthFilterConfig {
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// Whether log filters whose `toBlock` is `pending` also return the logs of the pending
+    /// block.
+    ///
+    /// Pending logs are tagged by unset block hash and block number.
+    pub pending_logs: bool,
 }
 
 impl EthFilterConfig {
@@ -156,6 +183,13 @@ impl EthFilterConfig {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets whether log filters whose `toBlock` is `pending` also return the logs of the pending
+    /// block.
+    pub const fn pending_logs(mut self, pending_logs: bool) -> Self {
+        self.pending_logs = pending_logs;
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -165,6 +199,7 @@ impl Default for EthFilterConfig {
             max_logs_per_response: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
+            pending_logs: false,
         }
     }
 }
@@ -3,7 +3,10 @@
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::ChainSpecProvider;
 use reth_storage_api::BlockReaderIdExt;
-use reth_tasks::TaskSpawner;
+use reth_tasks::{
+    pool::{BlockingTaskPool, BlockingTaskPriority},
+    TaskSpawner,
+};
 
 use crate::{
     fee_history::fee_history_cache_new_blocks_task, EthConfig, EthStateCache, FeeHistoryCache,
@@ -50,6 +53,19 @@ where
     pub fn new_gas_price_oracle(&self) -> GasPriceOracle<Provider> {
         GasPriceOracleBuilder::build(self)
     }
+
+    /// Returns a new [`BlockingTaskPool`] for the context, sized according to
+    /// [`EthConfig::blocking_pool_threads`].
+    ///
+    /// The threads of the pool run with [`BlockingTaskPriority::Low`], so that the tracing calls
+    /// executed on the pool don't slow down block execution and state root computation.
+    pub fn new_blocking_task_pool(&self) -> BlockingTaskPool {
+        BlockingTaskPool::build_with_priority(
+            self.config.blocking_pool_threads,
+            BlockingTaskPriority::Low,
+        )
+        .expect("failed to build blocking task pool")
+    }
 }
 
 /// Builds `eth_` core api component [`GasPriceOracle`], for given context.
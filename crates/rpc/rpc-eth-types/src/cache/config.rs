@@ -4,7 +4,8 @@ use serde::{Deserialize, Serialize};
 
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_PROVIDER_BLOCK_CACHE_MAX_LEN, DEFAULT_PROVIDER_HEADER_CACHE_MAX_LEN,
+    DEFAULT_PROVIDER_RECEIPT_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN,
 };
 
 /// Settings for the [`EthStateCache`](super::EthStateCache).
@@ -27,6 +28,23 @@ pub struct EthStateCacheConfig {
     ///
     /// Default is 512.
     pub max_concurrent_db_requests: usize,
+    /// Whether the database requests are served by a provider that caches headers, blocks and
+    /// receipts, and is invalidated on reorgs.
+    ///
+    /// Default is false.
+    pub provider_cache: bool,
+    /// Max number of headers in the provider cache.
+    ///
+    /// Default is 10000.
+    pub provider_max_headers: u32,
+    /// Max number of blocks in the provider cache.
+    ///
+    /// Default is 500.
+    pub provider_max_blocks: u32,
+    /// Max number of block receipts in the provider cache.
+    ///
+    /// Default is 500.
+    pub provider_max_receipts: u32,
 }
 
 impl Default for EthStateCacheConfig {
@@ -36,6 +54,10 @@ impl Default for EthStateCacheConfig {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            provider_cache: false,
+            provider_max_headers: DEFAULT_PROVIDER_HEADER_CACHE_MAX_LEN,
+            provider_max_blocks: DEFAULT_PROVIDER_BLOCK_CACHE_MAX_LEN,
+            provider_max_receipts: DEFAULT_PROVIDER_RECEIPT_CACHE_MAX_LEN,
         }
     }
 }
@@ -78,6 +78,18 @@ impl<'a> reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'
     }
 }
 
+impl<'a> reth_storage_api::StorageRangeProvider for StateProviderTraitObjWrapper<'a> {
+    fn hashed_storage_range(
+        &self,
+        hashed_state: reth_trie::HashedPostState,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<reth_trie::StorageRange> {
+        self.0.hashed_storage_range(hashed_state, address, start, limit)
+    }
+}
+
 impl<'a> reth_storage_api::AccountReader for StateProviderTraitObjWrapper<'a> {
     fn basic_account(
         &self,
@@ -147,6 +159,20 @@ impl<'a> StateProvider for StateProviderTraitObjWrapper<'a> {
     ) -> reth_errors::ProviderResult<Option<reth_primitives::StorageValue>> {
         self.0.storage(account, storage_key)
     }
+
+    fn storage_multi(
+        &self,
+        slots: &[(revm_primitives::Address, reth_primitives::StorageKey)],
+    ) -> reth_errors::ProviderResult<Vec<Option<reth_primitives::StorageValue>>> {
+        self.0.storage_multi(slots)
+    }
+
+    fn basic_accounts(
+        &self,
+        addresses: &[revm_primitives::Address],
+    ) -> reth_errors::ProviderResult<Vec<Option<reth_primitives::Account>>> {
+        self.0.basic_accounts(addresses)
+    }
 }
 
 /// Hack to get around 'higher-ranked lifetime error', see
@@ -79,6 +79,18 @@ pub enum EthApiError {
     /// Thrown when an `AccountOverride` contains conflicting `state` and `stateDiff` fields
     #[error("account {0:?} has both 'state' and 'stateDiff'")]
     BothStateAndStateDiffInOverride(Address),
+    /// Thrown when an `AccountOverride` moves the precompile of an account that is not a
+    /// precompile
+    #[error("account {0:?} is not a precompile")]
+    NotAPrecompileInOverride(Address),
+    /// Thrown when an `AccountOverride` moves a precompile to an account that is already
+    /// overridden
+    #[error("account {0:?} is already overridden")]
+    AccountAlreadyOverridden(Address),
+    /// Thrown when the immutable references of an `AccountOverride` are out of bounds of the
+    /// current or the overriding code of the account
+    #[error("account {0:?} has immutable references out of bounds of its code")]
+    InvalidImmutableReferencesInOverride(Address),
     /// Other internal error
     #[error(transparent)]
     Internal(RethError),
@@ -158,6 +170,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
+            EthApiError::NotAPrecompileInOverride(_) |
+            EthApiError::AccountAlreadyOverridden(_) |
+            EthApiError::InvalidImmutableReferencesInOverride(_) |
             EthApiError::InvalidTracerConfig |
             EthApiError::TransactionConversionError => invalid_params_rpc_err(error.to_string()),
             EthApiError::InvalidTransaction(err) => err.into(),
@@ -0,0 +1,312 @@
+//! Recording of the execution of a block as a [`ChromeTrace`].
+//!
+//! The trace has a span for every transaction, nested spans for the call frames of the
+//! transaction and spans for the reads of the database that were not served by the cache, which
+//! shows where the time of executing a block was spent.
+
+use reth_primitives::{Address, B256, U256};
+use reth_rpc_types::debug::{ChromeTrace, TraceEvent};
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome},
+    primitives::{AccountInfo, Bytecode},
+    Database, EvmContext, Inspector,
+};
+use std::time::Instant;
+
+/// The process id of all events of an execution trace.
+const PID: u64 = 1;
+
+/// The thread id of all events of an execution trace.
+///
+/// Execution is single threaded, so the call frames and database reads nest within the span of
+/// their transaction.
+const TID: u64 = 1;
+
+/// The clock of an execution trace, all timestamps are relative to the start of the trace.
+#[derive(Debug, Clone, Copy)]
+pub struct TraceClock {
+    start: Instant,
+}
+
+impl TraceClock {
+    /// Starts a new clock.
+    pub fn start() -> Self {
+        Self { start: Instant::now() }
+    }
+
+    /// Returns the microseconds since the start of the trace at the given instant.
+    pub fn micros(&self, at: Instant) -> f64 {
+        at.saturating_duration_since(self.start).as_secs_f64() * 1e6
+    }
+
+    /// Returns a span from the given instant until now.
+    pub fn span(&self, name: impl Into<String>, cat: &str, start: Instant) -> TraceEvent {
+        let (ts, end) = (self.micros(start), self.micros(Instant::now()));
+        TraceEvent::complete(name, cat, ts, end - ts, PID, TID)
+    }
+}
+
+/// The events of an execution trace.
+#[derive(Debug)]
+pub struct ExecutionTrace {
+    clock: TraceClock,
+    events: Vec<TraceEvent>,
+}
+
+impl ExecutionTrace {
+    /// Starts a new trace.
+    pub fn start() -> Self {
+        Self { clock: TraceClock::start(), events: Vec::new() }
+    }
+
+    /// Returns the clock of the trace.
+    pub const fn clock(&self) -> TraceClock {
+        self.clock
+    }
+
+    /// Adds an event to the trace.
+    pub fn push(&mut self, event: TraceEvent) {
+        self.events.push(event);
+    }
+
+    /// Adds the events to the trace.
+    pub fn extend(&mut self, events: impl IntoIterator<Item = TraceEvent>) {
+        self.events.extend(events);
+    }
+
+    /// Finishes the trace with a span of the given name over the whole trace.
+    pub fn finish(mut self, name: impl Into<String>) -> ChromeTrace {
+        let name = name.into();
+        let mut trace_events = vec![
+            TraceEvent::metadata("process_name", name.clone(), PID, TID),
+            TraceEvent::metadata("thread_name", "execution", PID, TID),
+            self.clock.span(name, "block", self.clock.start),
+        ];
+        trace_events.append(&mut self.events);
+        ChromeTrace { trace_events, display_time_unit: Some("ns".to_string()) }
+    }
+}
+
+/// A [`Database`] that records every read as a span.
+///
+/// This should wrap the database below the cache of the state, so that only the reads that
+/// actually hit the database are recorded.
+#[derive(Debug)]
+pub struct RecordingDatabase<DB> {
+    inner: DB,
+    clock: TraceClock,
+    events: Vec<TraceEvent>,
+}
+
+impl<DB> RecordingDatabase<DB> {
+    /// Creates a new database that records the reads of the given database.
+    pub const fn new(inner: DB, clock: TraceClock) -> Self {
+        Self { inner, clock, events: Vec::new() }
+    }
+
+    /// Takes the recorded events.
+    pub fn take_events(&mut self) -> Vec<TraceEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Returns the wrapped database.
+    pub fn into_inner(self) -> DB {
+        self.inner
+    }
+
+    /// Records the read of the given closure.
+    fn record<T>(
+        &mut self,
+        name: &str,
+        read: impl FnOnce(&mut DB) -> T,
+        event: impl FnOnce(TraceEvent) -> TraceEvent,
+    ) -> T {
+        let start = Instant::now();
+        let value = read(&mut self.inner);
+        self.events.push(event(self.clock.span(name, "db", start)));
+        value
+    }
+}
+
+impl<DB: Database> Database for RecordingDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.record(
+            "basic",
+            |db| db.basic(address),
+            |event| event.with_arg("address", address.to_string()),
+        )
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.record(
+            "code_by_hash",
+            |db| db.code_by_hash(code_hash),
+            |event| event.with_arg("codeHash", code_hash.to_string()),
+        )
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.record(
+            "storage",
+            |db| db.storage(address, index),
+            |event| {
+                event
+                    .with_arg("address", address.to_string())
+                    .with_arg("slot", B256::from(index).to_string())
+            },
+        )
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.record(
+            "block_hash",
+            |db| db.block_hash(number),
+            |event| event.with_arg("number", number),
+        )
+    }
+}
+
+/// An [`Inspector`] that records every call frame of a transaction as a span.
+#[derive(Debug)]
+pub struct CallFrameRecorder {
+    clock: TraceClock,
+    /// The start and event of the frames that haven't returned yet.
+    open: Vec<(Instant, TraceEvent)>,
+    events: Vec<TraceEvent>,
+}
+
+impl CallFrameRecorder {
+    /// Creates a new recorder.
+    pub const fn new(clock: TraceClock) -> Self {
+        Self { clock, open: Vec::new(), events: Vec::new() }
+    }
+
+    /// Returns the recorded events.
+    pub fn into_events(self) -> Vec<TraceEvent> {
+        self.events
+    }
+
+    /// Closes the innermost open frame.
+    fn close(&mut self, gas_used: u64, success: bool) {
+        let Some((start, event)) = self.open.pop() else { return };
+        let span = self.clock.span(event.name, "call", start);
+        self.events.push(
+            TraceEvent { args: event.args, ..span }
+                .with_arg("gasUsed", gas_used)
+                .with_arg("success", success),
+        );
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallFrameRecorder {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let name = match inputs.scheme {
+            CallScheme::Call => "CALL",
+            CallScheme::CallCode => "CALLCODE",
+            CallScheme::DelegateCall => "DELEGATECALL",
+            CallScheme::StaticCall => "STATICCALL",
+            CallScheme::ExtCall => "EXTCALL",
+            CallScheme::ExtStaticCall => "EXTSTATICCALL",
+            CallScheme::ExtDelegateCall => "EXTDELEGATECALL",
+        };
+        let event = TraceEvent::complete(name, "call", 0., 0., PID, TID)
+            .with_arg("from", inputs.caller.to_string())
+            .with_arg("to", inputs.target_address.to_string())
+            .with_arg("gasLimit", inputs.gas_limit);
+        self.open.push((Instant::now(), event));
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.close(outcome.result.gas.spent(), outcome.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let event = TraceEvent::complete("CREATE", "call", 0., 0., PID, TID)
+            .with_arg("from", inputs.caller.to_string())
+            .with_arg("gasLimit", inputs.gas_limit);
+        self.open.push((Instant::now(), event));
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.close(outcome.gas().spent(), outcome.result.is_ok());
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::debug::TraceEventPhase;
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        inspector_handle_register,
+        primitives::TransactTo,
+        Evm,
+    };
+
+    #[test]
+    fn records_call_frames_and_reads() {
+        let mut trace = ExecutionTrace::start();
+        let clock = trace.clock();
+        let caller = Address::with_last_byte(1);
+
+        let mut db = RecordingDatabase::new(CacheDB::new(EmptyDB::default()), clock);
+        let mut inspector = CallFrameRecorder::new(clock);
+        let start = Instant::now();
+        {
+            let mut evm = Evm::builder()
+                .with_db(&mut db)
+                .with_external_context(&mut inspector)
+                .modify_tx_env(|tx| {
+                    tx.caller = caller;
+                    tx.transact_to = TransactTo::Call(Address::with_last_byte(2));
+                    tx.gas_price = U256::ZERO;
+                })
+                .modify_cfg_env(|cfg| cfg.disable_base_fee = true)
+                .append_handler_register(inspector_handle_register)
+                .build();
+            evm.transact().unwrap();
+        }
+        trace.push(clock.span("transaction", "tx", start));
+        trace.extend(inspector.into_events());
+        trace.extend(db.take_events());
+
+        let trace = trace.finish("block 1");
+        let names = trace.trace_events.iter().map(|e| e.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names[..4], ["process_name", "thread_name", "block 1", "transaction"]);
+        assert!(names.contains(&"CALL"));
+        assert!(names.contains(&"basic"));
+
+        let call = trace.trace_events.iter().find(|e| e.name == "CALL").unwrap();
+        assert_eq!(call.ph, TraceEventPhase::Complete);
+        assert_eq!(call.args["from"], caller.to_string());
+        assert_eq!(call.args["success"], true);
+
+        let block = &trace.trace_events[2];
+        let block_end = block.ts + block.dur.unwrap();
+        assert!(trace.trace_events[3..].iter().all(|e| e.ts + e.dur.unwrap() <= block_end));
+    }
+}
@@ -14,9 +14,7 @@ use metrics::atomics::AtomicU64;
 use reth_chain_state::CanonStateNotification;
 use reth_chainspec::{ChainSpec, ChainSpecProvider};
 use reth_primitives::{
-    basefee::calc_next_block_base_fee,
-    eip4844::{calc_blob_gasprice, calculate_excess_blob_gas},
-    Receipt, SealedBlock, TransactionSigned, B256,
+    basefee::calc_next_block_base_fee, Receipt, SealedBlock, TransactionSigned, B256,
 };
 use reth_rpc_types::TxGasAndReward;
 use reth_storage_api::BlockReaderIdExt;
@@ -74,7 +72,7 @@ impl FeeHistoryCache {
     }
 
     /// Insert block data into the cache.
-    async fn insert_blocks<I>(&self, blocks: I)
+    async fn insert_blocks<I>(&self, blocks: I, chain_spec: &ChainSpec)
     where
         I: IntoIterator<Item = (SealedBlock, Arc<Vec<Receipt>>)>,
     {
@@ -83,7 +81,7 @@ impl FeeHistoryCache {
         let percentiles = self.predefined_percentiles();
         // Insert all new blocks and calculate approximated rewards
         for (block, receipts) in blocks {
-            let mut fee_history_entry = FeeHistoryEntry::new(&block);
+            let mut fee_history_entry = FeeHistoryEntry::new(&block, chain_spec);
             fee_history_entry.rewards = calculate_reward_percentiles_for_block(
                 &percentiles,
                 fee_history_entry.gas_used,
@@ -218,6 +216,7 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
     // If the node transitions to stage sync, we need to fetch the missing blocks
     let mut missing_blocks = VecDeque::new();
     let mut fetch_missing_block = Fuse::terminated();
+    let chain_spec = provider.chain_spec();
 
     loop {
         if fetch_missing_block.is_terminated() {
@@ -238,7 +237,7 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
         tokio::select! {
             res = &mut fetch_missing_block =>  {
                 if let Ok(res) = res {
-                    fee_history_cache.insert_blocks(res.into_iter()).await;
+                    fee_history_cache.insert_blocks(res.into_iter(), &chain_spec).await;
                 }
             }
             event = events.next() =>  {
@@ -253,7 +252,7 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
                         (block.block.clone(), Arc::new(receipts.iter().flatten().cloned().collect::<Vec<_>>()))
                     })
                     .unzip();
-                fee_history_cache.insert_blocks(blocks.into_iter().zip(receipts)).await;
+                fee_history_cache.insert_blocks(blocks.into_iter().zip(receipts), &chain_spec).await;
 
                 // keep track of missing blocks
                 missing_blocks = fee_history_cache.missing_consecutive_blocks().await;
@@ -355,16 +354,18 @@ pub struct FeeHistoryEntry {
 }
 
 impl FeeHistoryEntry {
-    /// Creates a new entry from a sealed block.
+    /// Creates a new entry from a sealed block, with the blob parameters of the chain at the
+    /// block.
     ///
     /// Note: This does not calculate the rewards for the block.
-    pub fn new(block: &SealedBlock) -> Self {
+    pub fn new(block: &SealedBlock, chain_spec: &ChainSpec) -> Self {
+        let blob_params = chain_spec.blob_params_at_timestamp(block.timestamp);
         Self {
             base_fee_per_gas: block.base_fee_per_gas.unwrap_or_default(),
             gas_used_ratio: block.gas_used as f64 / block.gas_limit as f64,
-            base_fee_per_blob_gas: block.blob_fee(),
+            base_fee_per_blob_gas: block.blob_fee_with_params(blob_params),
             blob_gas_used_ratio: block.blob_gas_used() as f64 /
-                reth_primitives::constants::eip4844::MAX_DATA_GAS_PER_BLOCK as f64,
+                blob_params.max_blob_gas_per_block() as f64,
             excess_blob_gas: block.excess_blob_gas,
             blob_gas_used: block.blob_gas_used,
             gas_used: block.gas_used,
@@ -385,19 +386,26 @@ impl FeeHistoryEntry {
         ) as u64
     }
 
-    /// Returns the blob fee for the next block according to the EIP-4844 spec.
+    /// Returns the blob fee for the next block according to the blob parameters of the chain.
     ///
     /// Returns `None` if `excess_blob_gas` is None.
     ///
     /// See also [`Self::next_block_excess_blob_gas`]
-    pub fn next_block_blob_fee(&self) -> Option<u128> {
-        self.next_block_excess_blob_gas().map(calc_blob_gasprice)
+    pub fn next_block_blob_fee(&self, chain_spec: &ChainSpec) -> Option<u128> {
+        let blob_params = chain_spec.blob_params_at_timestamp(self.timestamp);
+        self.next_block_excess_blob_gas(chain_spec)
+            .map(|excess_blob_gas| blob_params.calc_blob_fee(excess_blob_gas))
     }
 
-    /// Calculate excess blob gas for the next block according to the EIP-4844 spec.
+    /// Calculate excess blob gas for the next block according to the blob parameters of the
+    /// chain.
     ///
     /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
-    pub fn next_block_excess_blob_gas(&self) -> Option<u64> {
-        Some(calculate_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
+    pub fn next_block_excess_blob_gas(&self, chain_spec: &ChainSpec) -> Option<u64> {
+        Some(
+            chain_spec
+                .blob_params_at_timestamp(self.timestamp)
+                .next_block_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?),
+        )
     }
 }
@@ -11,6 +11,7 @@
 pub mod builder;
 pub mod cache;
 pub mod error;
+pub mod execution_trace;
 pub mod fee_history;
 pub mod gas_oracle;
 pub mod id_provider;
@@ -4,10 +4,10 @@
 
 use reth_chainspec::ChainInfo;
 use reth_errors::ProviderError;
-use reth_primitives::{BlockNumHash, Receipt, TxHash};
+use reth_primitives::{BlockNumHash, Receipt, SealedBlock, TxHash};
 use reth_rpc_server_types::result::rpc_error_with_code;
-use reth_rpc_types::{FilterId, FilteredParams, Log};
-use reth_storage_api::BlockReader;
+use reth_rpc_types::{Filter, FilterId, FilteredParams, Log};
+use reth_storage_api::{BlockLog, LogBlock, LogFilter, LogsCollector};
 
 use crate::EthApiError;
 
@@ -61,6 +61,8 @@ impl From<ProviderError> for EthFilterError {
 }
 
 /// Returns all matching of a block's receipts when the transaction hashes are known.
+///
+/// The timestamp of the block is unknown, so it is unset in the logs.
 pub fn matching_block_logs_with_tx_hashes<'a, I>(
     filter: &FilteredParams,
     block_num_hash: BlockNumHash,
@@ -70,115 +72,71 @@ pub fn matching_block_logs_with_tx_hashes<'a, I>(
 where
     I: IntoIterator<Item = (TxHash, &'a Receipt)>,
 {
-    let mut all_logs = Vec::new();
-    // Tracks the index of a log in the entire block.
-    let mut log_index: u64 = 0;
-    // Iterate over transaction hashes and receipts and append matching logs.
-    for (receipt_idx, (tx_hash, receipt)) in tx_hashes_and_receipts.into_iter().enumerate() {
-        for log in &receipt.logs {
-            if log_matches_filter(block_num_hash, log, filter) {
-                let log = Log {
-                    inner: log.clone(),
-                    block_hash: Some(block_num_hash.hash),
-                    block_number: Some(block_num_hash.number),
-                    transaction_hash: Some(tx_hash),
-                    // The transaction and receipt index is always the same.
-                    transaction_index: Some(receipt_idx as u64),
-                    log_index: Some(log_index),
-                    removed,
-                    block_timestamp: None,
-                };
-                all_logs.push(log);
-            }
-            log_index += 1;
-        }
-    }
-    all_logs
+    let block = LogBlock { number: block_num_hash.number, hash: block_num_hash.hash, timestamp: 0 };
+    matching_logs_with_tx_hashes(filter, block, tx_hashes_and_receipts)
+        .into_iter()
+        .map(|log| Log { block_timestamp: None, ..block_log_to_rpc(log, removed) })
+        .collect()
 }
 
-/// Appends all matching logs of a block's receipts.
-/// If the log matches, look up the corresponding transaction hash.
-pub fn append_matching_block_logs(
-    all_logs: &mut Vec<Log>,
-    provider: impl BlockReader,
+/// Returns all matching logs of the pending block's receipts.
+///
+/// The logs are tagged as pending, as specified by the `eth` JSON-RPC API: their block hash and
+/// block number are unset, since the pending block is not part of the canonical chain yet.
+pub fn matching_pending_block_logs(
     filter: &FilteredParams,
-    block_num_hash: BlockNumHash,
+    block: &SealedBlock,
     receipts: &[Receipt],
-    removed: bool,
-    block_timestamp: u64,
-) -> Result<(), EthFilterError> {
-    // Tracks the index of a log in the entire block.
-    let mut log_index: u64 = 0;
-
-    // Lazy loaded number of the first transaction in the block.
-    // This is useful for blocks with multiple matching logs because it prevents
-    // re-querying the block body indices.
-    let mut loaded_first_tx_num = None;
-
-    // Iterate over receipts and append matching logs.
-    for (receipt_idx, receipt) in receipts.iter().enumerate() {
-        // The transaction hash of the current receipt.
-        let mut transaction_hash = None;
-
-        for log in &receipt.logs {
-            if log_matches_filter(block_num_hash, log, filter) {
-                let first_tx_num = match loaded_first_tx_num {
-                    Some(num) => num,
-                    None => {
-                        let block_body_indices =
-                            provider.block_body_indices(block_num_hash.number)?.ok_or(
-                                ProviderError::BlockBodyIndicesNotFound(block_num_hash.number),
-                            )?;
-                        loaded_first_tx_num = Some(block_body_indices.first_tx_num);
-                        block_body_indices.first_tx_num
-                    }
-                };
-
-                // if this is the first match in the receipt's logs, look up the transaction hash
-                if transaction_hash.is_none() {
-                    // This is safe because Transactions and Receipts have the same keys.
-                    let transaction_id = first_tx_num + receipt_idx as u64;
-                    let transaction = provider
-                        .transaction_by_id(transaction_id)?
-                        .ok_or(ProviderError::TransactionNotFound(transaction_id.into()))?;
-
-                    transaction_hash = Some(transaction.hash());
-                }
-
-                let log = Log {
-                    inner: log.clone(),
-                    block_hash: Some(block_num_hash.hash),
-                    block_number: Some(block_num_hash.number),
-                    transaction_hash,
-                    // The transaction and receipt index is always the same.
-                    transaction_index: Some(receipt_idx as u64),
-                    log_index: Some(log_index),
-                    removed,
-                    block_timestamp: Some(block_timestamp),
-                };
-                all_logs.push(log);
-            }
-            log_index += 1;
-        }
+) -> Vec<Log> {
+    let tx_hashes_and_receipts = block.body.iter().map(|tx| tx.hash()).zip(receipts);
+    matching_logs_with_tx_hashes(
+        filter,
+        LogBlock::new(&block.header, block.hash()),
+        tx_hashes_and_receipts,
+    )
+    .into_iter()
+    .map(|log| Log { block_hash: None, block_number: None, ..block_log_to_rpc(log, false) })
+    .collect()
+}
+
+/// Returns all logs of the block's receipts that match the filter, including its block range or
+/// hash.
+fn matching_logs_with_tx_hashes<'a>(
+    filter: &FilteredParams,
+    block: LogBlock,
+    tx_hashes_and_receipts: impl IntoIterator<Item = (TxHash, &'a Receipt)>,
+) -> Vec<BlockLog> {
+    if !filter.filter_block_range(block.number) || !filter.filter_block_hash(block.hash) {
+        return Vec::new()
+    }
+
+    let log_filter = filter.filter.as_ref().map(log_filter).unwrap_or_default();
+    let mut collector = LogsCollector::new(&log_filter, None, usize::MAX);
+    collector.append_block_logs_with_tx_hashes(block, tx_hashes_and_receipts);
+    collector.into_page().logs
+}
+
+/// Returns the [`LogFilter`] of the addresses and topics of the filter, to find matching logs with
+/// a [`LogsCollector`].
+pub fn log_filter(filter: &Filter) -> LogFilter {
+    LogFilter {
+        addresses: filter.address.iter().copied().collect(),
+        topics: filter.topics.clone().map(|topics| topics.into_iter().collect()),
     }
-    Ok(())
 }
 
-/// Returns true if the log matches the filter and should be included
-pub fn log_matches_filter(
-    block: BlockNumHash,
-    log: &reth_primitives::Log,
-    params: &FilteredParams,
-) -> bool {
-    if params.filter.is_some() &&
-        (!params.filter_block_range(block.number) ||
-            !params.filter_block_hash(block.hash) ||
-            !params.filter_address(&log.address) ||
-            !params.filter_topics(log.topics()))
-    {
-        return false
+/// Converts a log with its block and transaction metadata into an RPC log.
+pub fn block_log_to_rpc(log: BlockLog, removed: bool) -> Log {
+    Log {
+        inner: log.log,
+        block_hash: Some(log.block_hash),
+        block_number: Some(log.block_number),
+        transaction_hash: Some(log.transaction_hash),
+        transaction_index: Some(log.transaction_index),
+        log_index: Some(log.log_index),
+        removed,
+        block_timestamp: Some(log.block_timestamp),
     }
-    true
 }
 
 /// Computes the block range based on the filter range and current block numbers
@@ -256,6 +214,18 @@ mod tests {
         assert_eq!(range, (info.best_number, info.best_number));
     }
 
+    #[test]
+    fn log_filter_of_addresses_and_topics() {
+        let s = r#"{"address":["0x7de93682b9b5d80d45cd371f7a14f74d49b0914c"],"topics":[null,["0x0559884fd3a460db3073b7fc896cc77986f16e378210ded43186175bf646fc5f"]]}"#;
+        let filter: Filter = serde_json::from_str(s).unwrap();
+
+        let log_filter = log_filter(&filter);
+        assert_eq!(log_filter.addresses.len(), 1);
+        assert!(log_filter.topics[0].is_empty());
+        assert_eq!(log_filter.topics[1].len(), 1);
+        assert!(log_filter.topics[2].is_empty() && log_filter.topics[3].is_empty());
+    }
+
     #[test]
     fn parse_log_from_only() {
         let s = r#"{"fromBlock":"0xf47a42","address":["0x7de93682b9b5d80d45cd371f7a14f74d49b0914c","0x0f00392fcb466c0e4e4310d81b941e07b4d5a079","0xebf67ab8cff336d3f609127e8bbf8bd6dd93cd81"],"topics":["0x0559884fd3a460db3073b7fc896cc77986f16e378210ded43186175bf646fc5f"]}"#;
@@ -280,4 +250,30 @@ mod tests {
         assert_eq!(from_block_number, 16022082);
         assert_eq!(to_block_number, best_number);
     }
+
+    #[test]
+    fn pending_block_logs_are_tagged() {
+        let address = reth_primitives::Address::with_last_byte(1);
+        let log =
+            |address| reth_primitives::Log::new_unchecked(address, vec![], Default::default());
+        let tx = reth_primitives::TransactionSigned {
+            hash: TxHash::with_last_byte(2),
+            ..Default::default()
+        };
+        let block = SealedBlock { body: vec![tx], ..Default::default() };
+        let receipts = vec![Receipt {
+            logs: vec![log(reth_primitives::Address::ZERO), log(address)],
+            ..Default::default()
+        }];
+        let filter = FilteredParams::new(Some(
+            Filter::new().address(address).to_block(reth_rpc_types::BlockNumberOrTag::Pending),
+        ));
+
+        let logs = matching_pending_block_logs(&filter, &block, &receipts);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address(), address);
+        assert_eq!(logs[0].transaction_hash, Some(TxHash::with_last_byte(2)));
+        assert_eq!(logs[0].log_index, Some(1));
+        assert!(logs[0].block_hash.is_none() && logs[0].block_number.is_none());
+    }
 }
@@ -1,13 +1,11 @@
 //! RPC receipt response builder, extends a layer one receipt with layer two data.
 
-use reth_primitives::{Address, Receipt, TransactionMeta, TransactionSigned, TxKind};
+use super::{EthApiError, EthResult};
+use reth_primitives::{Address, BlobParams, Receipt, TransactionMeta, TransactionSigned, TxKind};
 use reth_rpc_types::{
     AnyReceiptEnvelope, AnyTransactionReceipt, Log, OtherFields, ReceiptWithBloom,
     TransactionReceipt, WithOtherFields,
 };
-use revm_primitives::calc_blob_gasprice;
-
-use super::{EthApiError, EthResult};
 
 /// Receipt response builder.
 #[derive(Debug)]
@@ -22,12 +20,13 @@ impl ReceiptBuilder {
     /// Returns a new builder with the base response body (L1 fields) set.
     ///
     /// Note: This requires _all_ block receipts because we need to calculate the gas used by the
-    /// transaction.
+    /// transaction. The blob gas price is calculated with the blob parameters of the block.
     pub fn new(
         transaction: &TransactionSigned,
         meta: TransactionMeta,
         receipt: &Receipt,
         all_receipts: &[Receipt],
+        blob_params: BlobParams,
     ) -> EthResult<Self> {
         // Note: we assume this transaction is valid, because it's mined (or part of pending block)
         // and we don't need to check for pre EIP-2
@@ -48,8 +47,8 @@ impl ReceiptBuilder {
 
         let blob_gas_used = transaction.transaction.blob_gas_used();
         // Blob gas price should only be present if the transaction is a blob transaction
-        let blob_gas_price =
-            blob_gas_used.and_then(|_| meta.excess_blob_gas.map(calc_blob_gasprice));
+        let blob_gas_price = blob_gas_used
+            .and_then(|_| meta.excess_blob_gas.map(|gas| blob_params.calc_blob_fee(gas)));
         let logs_bloom = receipt.bloom_slow();
 
         // get number of logs in the block
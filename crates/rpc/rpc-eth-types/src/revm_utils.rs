@@ -1,18 +1,22 @@
 //! utilities for working with revm
 
-use reth_primitives::{Address, B256, U256};
+use reth_primitives::{Address, Bytes, B256, U256};
 use reth_rpc_types::{
-    state::{AccountOverride, StateOverride},
+    overrides::{ExtendedAccountOverride, ExtendedStateOverride, ImmutableReference},
     BlockOverrides,
 };
 use revm::{
     db::CacheDB,
     precompile::{PrecompileSpecId, Precompiles},
     primitives::{db::DatabaseRef, Bytecode, SpecId, TxEnv},
-    Database,
+    Database, Evm,
 };
 use revm_primitives::BlockEnv;
-use std::cmp::min;
+use std::{
+    cmp::min,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use super::{EthApiError, EthResult, RpcInvalidTransactionError};
 
@@ -238,8 +242,11 @@ pub fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
     }
 }
 
-/// Applies the given state overrides (a set of [`AccountOverride`]) to the [`CacheDB`].
-pub fn apply_state_overrides<DB>(overrides: StateOverride, db: &mut CacheDB<DB>) -> EthResult<()>
+/// Applies the given state overrides (a set of [`ExtendedAccountOverride`]) to the [`CacheDB`].
+pub fn apply_state_overrides<DB>(
+    overrides: ExtendedStateOverride,
+    db: &mut CacheDB<DB>,
+) -> EthResult<()>
 where
     DB: DatabaseRef,
     EthApiError: From<<DB as DatabaseRef>::Error>,
@@ -250,10 +257,10 @@ where
     Ok(())
 }
 
-/// Applies a single [`AccountOverride`] to the [`CacheDB`].
+/// Applies a single [`ExtendedAccountOverride`] to the [`CacheDB`].
 fn apply_account_override<DB>(
     account: Address,
-    account_override: AccountOverride,
+    account_override: ExtendedAccountOverride,
     db: &mut CacheDB<DB>,
 ) -> EthResult<()>
 where
@@ -267,7 +274,19 @@ where
     if let Some(nonce) = account_override.nonce {
         account_info.nonce = nonce;
     }
-    if let Some(code) = account_override.code {
+    if let Some(mut code) = account_override.code {
+        if !account_override.immutable_references.is_empty() {
+            let current_code = match account_info.code.take() {
+                Some(code) => code,
+                None => DatabaseRef::code_by_hash_ref(db, account_info.code_hash)?,
+            };
+            code = apply_immutable_references(
+                account,
+                code,
+                current_code.original_byte_slice(),
+                &account_override.immutable_references,
+            )?;
+        }
         account_info.code = Some(Bytecode::new_raw(code));
     }
     if let Some(balance) = account_override.balance {
@@ -309,6 +328,81 @@ where
     Ok(())
 }
 
+/// Copies the values of the immutable variables from the current code of the account into the
+/// overriding code, at the given [`ImmutableReference`]s.
+fn apply_immutable_references(
+    account: Address,
+    code: Bytes,
+    current_code: &[u8],
+    immutable_references: &[ImmutableReference],
+) -> EthResult<Bytes> {
+    let mut code = code.to_vec();
+    for ImmutableReference { start, length } in immutable_references.iter().copied() {
+        let range = start..start.saturating_add(length);
+        if range.end > code.len() || range.end > current_code.len() {
+            return Err(EthApiError::InvalidImmutableReferencesInOverride(account))
+        }
+        code[range.clone()].copy_from_slice(&current_code[range]);
+    }
+    Ok(code.into())
+}
+
+/// The precompiles that are moved to other addresses by the `movePrecompileToAddress` field of
+/// [`ExtendedAccountOverride`]s, by their original address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrecompileMoves(HashMap<Address, Address>);
+
+impl PrecompileMoves {
+    /// Collects the precompile moves of the given state overrides.
+    ///
+    /// Returns an error if a precompile is moved to an account that is overridden as well, or to
+    /// the same address as another precompile.
+    pub fn new(overrides: &ExtendedStateOverride) -> EthResult<Self> {
+        let mut moves = HashMap::new();
+        let mut destinations = HashSet::new();
+        for (account, account_override) in overrides {
+            let Some(destination) = account_override.move_precompile_to_address else { continue };
+            if overrides.contains_key(&destination) || !destinations.insert(destination) {
+                return Err(EthApiError::AccountAlreadyOverridden(destination))
+            }
+            moves.insert(*account, destination);
+        }
+        Ok(Self(moves))
+    }
+
+    /// Returns `true` if no precompile is moved.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Moves the precompiles that are loaded by the [`Evm`] to their new addresses.
+    ///
+    /// Returns an error if a moved account is not a precompile of the [`Evm`].
+    pub fn apply<EXT, DB: Database>(&self, evm: &mut Evm<'_, EXT, DB>) -> EthResult<()> {
+        if self.is_empty() {
+            return Ok(())
+        }
+
+        let precompiles = evm.handler.pre_execution.load_precompiles();
+        if let Some(from) = self.0.keys().find(|from| !precompiles.contains(from)) {
+            return Err(EthApiError::NotAPrecompileInOverride(*from))
+        }
+
+        let load_precompiles = evm.handler.pre_execution.load_precompiles.clone();
+        let moves = self.0.clone();
+        evm.handler.pre_execution.load_precompiles = Arc::new(move || {
+            let mut precompiles = load_precompiles();
+            let moved = moves
+                .iter()
+                .filter_map(|(from, to)| Some((*to, precompiles.to_mut().remove(from)?)))
+                .collect::<Vec<_>>();
+            precompiles.extend(moved);
+            precompiles
+        });
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -423,4 +517,63 @@ mod tests {
         );
         assert!(call_fees.is_err());
     }
+
+    #[test]
+    fn test_apply_immutable_references() {
+        let account = Address::with_last_byte(1);
+        let current_code = [0x60, 0xaa, 0xbb, 0x60, 0xcc];
+        let references = [
+            ImmutableReference { start: 1, length: 2 },
+            ImmutableReference { start: 4, length: 1 },
+        ];
+
+        let code = Bytes::from_static(&[0x61, 0x00, 0x00, 0x61, 0x00, 0x00]);
+        assert_eq!(
+            apply_immutable_references(account, code.clone(), &current_code, &references).unwrap(),
+            Bytes::from_static(&[0x61, 0xaa, 0xbb, 0x61, 0xcc, 0x00])
+        );
+
+        // the references must be within both the current and the overriding code
+        let out_of_bounds = [ImmutableReference { start: 4, length: 2 }];
+        assert!(matches!(
+            apply_immutable_references(account, code, &current_code, &out_of_bounds),
+            Err(EthApiError::InvalidImmutableReferencesInOverride(_))
+        ));
+    }
+
+    #[test]
+    fn test_precompile_moves() {
+        let ecrecover = Address::with_last_byte(1);
+        let destination = Address::with_last_byte(0x42);
+        let mut overrides = ExtendedStateOverride::default();
+        overrides.insert(
+            ecrecover,
+            ExtendedAccountOverride {
+                move_precompile_to_address: Some(destination),
+                ..Default::default()
+            },
+        );
+
+        let moves = PrecompileMoves::new(&overrides).unwrap();
+        let mut evm = Evm::builder().with_empty_db().with_spec_id(SpecId::CANCUN).build();
+        moves.apply(&mut evm).unwrap();
+        let precompiles = evm.handler.pre_execution.load_precompiles();
+        assert!(!precompiles.contains(&ecrecover));
+        assert!(precompiles.contains(&destination));
+
+        // only precompiles can be moved
+        let mut evm = Evm::builder().with_empty_db().with_spec_id(SpecId::CANCUN).build();
+        let not_a_precompile = PrecompileMoves(HashMap::from([(destination, ecrecover)]));
+        assert!(matches!(
+            not_a_precompile.apply(&mut evm),
+            Err(EthApiError::NotAPrecompileInOverride(_))
+        ));
+
+        // the destination can't be overridden itself
+        overrides.insert(destination, ExtendedAccountOverride::default());
+        assert!(matches!(
+            PrecompileMoves::new(&overrides),
+            Err(EthApiError::AccountAlreadyOverridden(_))
+        ));
+    }
 }
@@ -29,6 +29,12 @@ pub fn default_max_tracing_requests() -> usize {
 /// The default number of getproof calls we are allowing to run concurrently.
 pub const DEFAULT_PROOF_PERMITS: usize = 25;
 
+/// The default maximum number of blocks a `reth_sql` query may scan per table.
+pub const DEFAULT_SQL_MAX_BLOCKS: u64 = 10_000;
+
+/// The default maximum number of rows a `reth_sql` query may return.
+pub const DEFAULT_SQL_MAX_ROWS: usize = 10_000;
+
 /// The default IPC endpoint
 #[cfg(windows)]
 pub const DEFAULT_IPC_ENDPOINT: &str = r"\\.\pipe\reth.ipc";
@@ -117,4 +123,13 @@ pub mod cache {
 
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
+
+    /// Default size of the header cache of the provider cache: 10000 headers.
+    pub const DEFAULT_PROVIDER_HEADER_CACHE_MAX_LEN: u32 = 10_000;
+
+    /// Default size of the block cache of the provider cache: 500 blocks.
+    pub const DEFAULT_PROVIDER_BLOCK_CACHE_MAX_LEN: u32 = 500;
+
+    /// Default size of the receipts cache of the provider cache: 500 block receipts.
+    pub const DEFAULT_PROVIDER_RECEIPT_CACHE_MAX_LEN: u32 = 500;
 }
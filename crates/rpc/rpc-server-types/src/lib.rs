@@ -15,4 +15,7 @@ pub mod result;
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
 
+mod listener;
+pub use listener::{RpcListener, RpcListenerParseError};
+
 pub use result::ToRpcResult;
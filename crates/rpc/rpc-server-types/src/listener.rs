@@ -0,0 +1,141 @@
+use std::{fmt, net::SocketAddr, str::FromStr};
+
+use crate::RpcModuleSelection;
+
+/// An additional listener of an RPC server, which is served next to the main listener of the
+/// transport.
+///
+/// Every listener has its own CORS domains and can restrict the namespaces that it serves, for
+/// example to bind a server to both an IPv4 and an IPv6 address, or to expose only a few
+/// namespaces to a sidecar.
+///
+/// A listener is parsed from `<ADDR>[;api=<MODULES>][;cors=<DOMAINS>]`:
+///
+/// ```
+/// use reth_rpc_server_types::{RethRpcModule, RpcListener, RpcModuleSelection};
+/// let listener: RpcListener = "[::1]:8545;api=eth,net;cors=*".parse().unwrap();
+/// assert_eq!(listener.addr, "[::1]:8545".parse().unwrap());
+/// assert_eq!(listener.cors_domains.as_deref(), Some("*"));
+/// assert_eq!(
+///     listener.modules,
+///     Some(RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Net]))
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcListener<Addr = SocketAddr> {
+    /// The address to bind to, a socket address for http and ws, or an endpoint for ipc.
+    pub addr: Addr,
+    /// The allowed CORS domains of the listener.
+    pub cors_domains: Option<String>,
+    /// The namespaces served by the listener.
+    ///
+    /// If not set, all namespaces of the transport are served, otherwise only the selected
+    /// namespaces that are also enabled for the transport.
+    pub modules: Option<RpcModuleSelection>,
+}
+
+impl<Addr> RpcListener<Addr> {
+    /// Creates a new listener on the given address that serves all namespaces of the transport.
+    pub const fn new(addr: Addr) -> Self {
+        Self { addr, cors_domains: None, modules: None }
+    }
+
+    /// Configures the allowed CORS domains.
+    pub fn with_cors(mut self, cors_domains: Option<String>) -> Self {
+        self.cors_domains = cors_domains;
+        self
+    }
+
+    /// Restricts the namespaces that are served.
+    pub fn with_modules(mut self, modules: Option<RpcModuleSelection>) -> Self {
+        self.modules = modules;
+        self
+    }
+}
+
+impl<Addr> FromStr for RpcListener<Addr>
+where
+    Addr: FromStr,
+    Addr::Err: fmt::Display,
+{
+    type Err = RpcListenerParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(';').map(str::trim);
+        let addr = parts.next().unwrap_or_default();
+        let mut listener = Self::new(
+            addr.parse()
+                .map_err(|err| RpcListenerParseError::InvalidAddress(format!("{addr}: {err}")))?,
+        );
+
+        for option in parts.filter(|option| !option.is_empty()) {
+            match option.split_once('=') {
+                Some(("api", modules)) => {
+                    listener.modules = Some(
+                        modules
+                            .parse()
+                            .map_err(|_| RpcListenerParseError::InvalidModules(modules.into()))?,
+                    )
+                }
+                Some(("cors", domains)) => listener.cors_domains = Some(domains.into()),
+                _ => return Err(RpcListenerParseError::UnknownOption(option.into())),
+            }
+        }
+
+        Ok(listener)
+    }
+}
+
+/// Errors when parsing an [`RpcListener`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RpcListenerParseError {
+    /// The address of the listener is invalid.
+    #[error("invalid listener address {0}")]
+    InvalidAddress(String),
+    /// The namespaces of the listener are invalid.
+    #[error("invalid listener api modules: {0}")]
+    InvalidModules(String),
+    /// The option is not `api` or `cors`.
+    #[error("unknown listener option {0}, expected api=<MODULES> or cors=<DOMAINS>")]
+    UnknownOption(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RethRpcModule;
+
+    #[test]
+    fn parse_listener() {
+        let listener: RpcListener = "0.0.0.0:8545".parse().unwrap();
+        assert_eq!(listener, RpcListener::new("0.0.0.0:8545".parse().unwrap()));
+
+        let listener: RpcListener =
+            "[::]:8545; cors=http://localhost:3000,http://localhost:3001 ;".parse().unwrap();
+        assert_eq!(
+            listener.cors_domains.as_deref(),
+            Some("http://localhost:3000,http://localhost:3001")
+        );
+        assert_eq!(listener.modules, None);
+
+        let listener: RpcListener<String> = "/tmp/sidecar.ipc;api=txpool".parse().unwrap();
+        assert_eq!(listener.addr, "/tmp/sidecar.ipc");
+        assert_eq!(listener.modules, Some(RpcModuleSelection::from([RethRpcModule::Txpool])));
+    }
+
+    #[test]
+    fn parse_invalid_listener() {
+        assert!(matches!(
+            "localhost".parse::<RpcListener>(),
+            Err(RpcListenerParseError::InvalidAddress(_))
+        ));
+        assert_eq!(
+            "127.0.0.1:8545;api=eth,foo".parse::<RpcListener>(),
+            Err(RpcListenerParseError::InvalidModules("eth,foo".into()))
+        );
+        assert_eq!(
+            "127.0.0.1:8545;jwt=secret".parse::<RpcListener>(),
+            Err(RpcListenerParseError::UnknownOption("jwt=secret".into()))
+        );
+    }
+}
@@ -140,6 +140,15 @@ impl RpcModuleSelection {
             (None, None) => true,
         }
     }
+
+    /// Returns true if the module is part of the selection.
+    pub fn contains(&self, module: &RethRpcModule) -> bool {
+        match self {
+            Self::All => true,
+            Self::Standard => Self::STANDARD_MODULES.contains(module),
+            Self::Selection(s) => s.contains(module),
+        }
+    }
 }
 
 impl From<&HashSet<RethRpcModule>> for RpcModuleSelection {
@@ -291,6 +300,18 @@ impl RethRpcModule {
     pub fn as_str(&self) -> &'static str {
         self.into()
     }
+
+    /// Returns the module of the method with the given name, which is determined by the namespace
+    /// prefix of the name.
+    ///
+    /// Returns `None` for methods outside of the namespaces of reth.
+    pub fn from_method_name(method: &str) -> Option<Self> {
+        if method == "eth_callBundle" {
+            return Some(Self::EthCallBundle)
+        }
+        let (namespace, _) = method.split_once('_')?;
+        namespace.parse().ok()
+    }
 }
 
 impl FromStr for RethRpcModule {
@@ -335,3 +356,20 @@ impl Serialize for RethRpcModule {
         s.serialize_str(self.as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_of_method() {
+        assert_eq!(RethRpcModule::from_method_name("eth_call"), Some(RethRpcModule::Eth));
+        assert_eq!(RethRpcModule::from_method_name("eth_subscribe"), Some(RethRpcModule::Eth));
+        assert_eq!(
+            RethRpcModule::from_method_name("eth_callBundle"),
+            Some(RethRpcModule::EthCallBundle)
+        );
+        assert_eq!(RethRpcModule::from_method_name("rpc_modules"), Some(RethRpcModule::Rpc));
+        assert_eq!(RethRpcModule::from_method_name("custom_method"), None);
+    }
+}
@@ -0,0 +1,182 @@
+//! Types for the `debug` namespace.
+
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A page of the storage of an account, as returned by `debug_storageRangeAt`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// The storage entries of this page, keyed by the hash of the storage slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// The hash of the storage slot the next page starts at, `None` if this is the last page.
+    pub next_key: Option<B256>,
+}
+
+/// A single storage entry of a [`StorageRangeResult`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The storage slot, `None` if the preimage of the hashed slot is unknown.
+    pub key: Option<B256>,
+    /// The value of the storage slot.
+    pub value: B256,
+}
+
+/// A trace in the Chrome trace event format, as returned by `reth_exportExecutionTrace`.
+///
+/// The JSON representation can be opened in Perfetto (<https://ui.perfetto.dev>) or
+/// `chrome://tracing`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChromeTrace {
+    /// The events of the trace.
+    pub trace_events: Vec<TraceEvent>,
+    /// The unit the timestamps are displayed in, e.g. `ns`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_time_unit: Option<String>,
+}
+
+/// A single event of a [`ChromeTrace`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEvent {
+    /// The name of the event.
+    pub name: String,
+    /// The category of the event.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub cat: String,
+    /// The type of the event.
+    pub ph: TraceEventPhase,
+    /// The start of the event in microseconds.
+    pub ts: f64,
+    /// The duration of a [`TraceEventPhase::Complete`] event in microseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dur: Option<f64>,
+    /// The id of the process the event belongs to.
+    pub pid: u64,
+    /// The id of the thread the event belongs to.
+    pub tid: u64,
+    /// Additional values of the event that are shown with it.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, serde_json::Value>,
+}
+
+impl TraceEvent {
+    /// Creates an event that spans the given duration.
+    pub fn complete(
+        name: impl Into<String>,
+        cat: impl Into<String>,
+        ts: f64,
+        dur: f64,
+        pid: u64,
+        tid: u64,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            cat: cat.into(),
+            ph: TraceEventPhase::Complete,
+            ts,
+            dur: Some(dur),
+            pid,
+            tid,
+            args: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a metadata event, e.g. a `process_name` or `thread_name` event that names the
+    /// process or thread.
+    pub fn metadata(kind: impl Into<String>, name: impl Into<String>, pid: u64, tid: u64) -> Self {
+        Self {
+            name: kind.into(),
+            cat: String::new(),
+            ph: TraceEventPhase::Metadata,
+            ts: 0.,
+            dur: None,
+            pid,
+            tid,
+            args: BTreeMap::from([("name".to_string(), serde_json::Value::String(name.into()))]),
+        }
+    }
+
+    /// Adds a value to the arguments of the event.
+    pub fn with_arg(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.args.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// The type of a [`TraceEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TraceEventPhase {
+    /// An event with a start and a duration.
+    #[serde(rename = "X")]
+    Complete,
+    /// An event without a duration.
+    #[serde(rename = "i")]
+    Instant,
+    /// An event that names a process or thread.
+    #[serde(rename = "M")]
+    Metadata,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_range_result_serde_roundtrip() {
+        let result = StorageRangeResult {
+            storage: BTreeMap::from([(
+                B256::with_last_byte(1),
+                StorageRangeEntry { key: None, value: B256::with_last_byte(2) },
+            )]),
+            next_key: None,
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["nextKey"], serde_json::Value::Null);
+        assert_eq!(
+            json["storage"][B256::with_last_byte(1).to_string()]["key"],
+            serde_json::Value::Null
+        );
+        assert_eq!(serde_json::from_value::<StorageRangeResult>(json).unwrap(), result);
+    }
+
+    #[test]
+    fn chrome_trace_serde() {
+        let trace = ChromeTrace {
+            trace_events: vec![
+                TraceEvent::metadata("thread_name", "execution", 1, 1),
+                TraceEvent::complete("CALL", "call", 1.5, 2., 1, 1).with_arg("gasUsed", 21000),
+            ],
+            display_time_unit: Some("ns".to_string()),
+        };
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "traceEvents": [
+                    {
+                        "name": "thread_name",
+                        "ph": "M",
+                        "ts": 0.0,
+                        "pid": 1,
+                        "tid": 1,
+                        "args": { "name": "execution" }
+                    },
+                    {
+                        "name": "CALL",
+                        "cat": "call",
+                        "ph": "X",
+                        "ts": 1.5,
+                        "dur": 2.0,
+                        "pid": 1,
+                        "tid": 1,
+                        "args": { "gasUsed": 21000 }
+                    }
+                ],
+                "displayTimeUnit": "ns"
+            })
+        );
+        assert_eq!(serde_json::from_value::<ChromeTrace>(json).unwrap(), trace);
+    }
+}
@@ -1,6 +1,7 @@
 //! Ethereum related types
 
 pub(crate) mod error;
+pub mod overrides;
 pub mod transaction;
 
 // re-export
@@ -0,0 +1,188 @@
+//! State overrides of calls, with the extensions that are used by account abstraction tooling.
+//!
+//! In addition to the standard [`state`] overrides, an account override can move a precompile to
+//! another address, and keep the immutable values of the code of the account when its code is
+//! overridden. This allows ERC-4337 bundlers to simulate modified versions of the `EntryPoint`
+//! contract exactly like the deployed one.
+
+use crate::{state, BlockOverrides};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A set of extended account overrides
+pub type ExtendedStateOverride = HashMap<Address, ExtendedAccountOverride>;
+
+/// Account override used in call, with the account abstraction extensions
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExtendedAccountOverride {
+    /// Fake balance to set for the account before executing the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Fake nonce to set for the account before executing the call.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub nonce: Option<u64>,
+    /// Fake EVM bytecode to inject into the account before executing the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Byte ranges of the fake EVM bytecode that are replaced with the bytes of the current code
+    /// of the account, which hold the values of its immutable variables.
+    ///
+    /// These are the `immutableReferences` that the compiler outputs for the deployed bytecode.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub immutable_references: Vec<ImmutableReference>,
+    /// Fake key-value mapping to override all slots in the account storage before executing the
+    /// call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<B256, B256>>,
+    /// Fake key-value mapping to override individual slots in the account storage before executing
+    /// the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<B256, B256>>,
+    /// Moves the precompile of the account to the given address, so that the account can be
+    /// overridden with code that calls the precompile at its new address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub move_precompile_to_address: Option<Address>,
+}
+
+impl From<state::AccountOverride> for ExtendedAccountOverride {
+    fn from(account_override: state::AccountOverride) -> Self {
+        let state::AccountOverride { balance, nonce, code, state, state_diff } = account_override;
+        Self { balance, nonce, code, state, state_diff, ..Default::default() }
+    }
+}
+
+/// The location of an immutable variable in the deployed bytecode of a contract.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImmutableReference {
+    /// Offset of the value in the bytecode.
+    pub start: usize,
+    /// Length of the value in bytes.
+    pub length: usize,
+}
+
+/// Converts the standard [`state::StateOverride`] to an [`ExtendedStateOverride`].
+pub fn extend_state_override(state_override: state::StateOverride) -> ExtendedStateOverride {
+    state_override.into_iter().map(|(address, account)| (address, account.into())).collect()
+}
+
+/// Helper type that bundles various overrides for EVM Execution, like [`state::EvmOverrides`]
+/// with the extended state overrides.
+///
+/// By `Default`, no overrides are included.
+#[derive(Debug, Clone, Default)]
+pub struct ExtendedEvmOverrides {
+    /// Applies overrides to the state before execution.
+    pub state: Option<ExtendedStateOverride>,
+    /// Applies overrides to the block before execution.
+    ///
+    /// This is a `Box` because less common and only available in debug trace endpoints.
+    pub block: Option<Box<BlockOverrides>>,
+}
+
+impl ExtendedEvmOverrides {
+    /// Creates a new instance with the given overrides
+    pub const fn new(
+        state: Option<ExtendedStateOverride>,
+        block: Option<Box<BlockOverrides>>,
+    ) -> Self {
+        Self { state, block }
+    }
+
+    /// Creates a new instance with the given state overrides.
+    pub const fn state(state: Option<ExtendedStateOverride>) -> Self {
+        Self { state, block: None }
+    }
+
+    /// Creates a new instance with the given block overrides.
+    pub const fn block(block: Option<Box<BlockOverrides>>) -> Self {
+        Self { state: None, block }
+    }
+
+    /// Returns `true` if the overrides contain state overrides.
+    pub const fn has_state(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Returns `true` if the overrides contain block overrides.
+    pub const fn has_block(&self) -> bool {
+        self.block.is_some()
+    }
+
+    /// Adds state overrides to an existing instance.
+    pub fn with_state(mut self, state: ExtendedStateOverride) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Adds block overrides to an existing instance.
+    pub fn with_block(mut self, block: Box<BlockOverrides>) -> Self {
+        self.block = Some(block);
+        self
+    }
+}
+
+impl From<state::EvmOverrides> for ExtendedEvmOverrides {
+    fn from(overrides: state::EvmOverrides) -> Self {
+        Self::new(overrides.state.map(extend_state_override), overrides.block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn test_account_abstraction_overrides() {
+        let s = r#"{
+            "0x0000000071727De22E5E9d8BAf0edAc6f37da032": {
+                "code": "0x6080604052",
+                "immutableReferences": [{ "start": 1, "length": 2 }]
+            },
+            "0x0000000000000000000000000000000000000001": {
+                "movePrecompileToAddress": "0x0000000000000000000000000000000000000123"
+            }
+        }"#;
+        let state_override: ExtendedStateOverride = serde_json::from_str(s).unwrap();
+
+        let entry_point =
+            state_override.get(&address!("0000000071727De22E5E9d8BAf0edAc6f37da032")).unwrap();
+        assert_eq!(
+            entry_point.immutable_references,
+            vec![ImmutableReference { start: 1, length: 2 }]
+        );
+
+        let ecrecover =
+            state_override.get(&address!("0000000000000000000000000000000000000001")).unwrap();
+        assert_eq!(
+            ecrecover.move_precompile_to_address,
+            Some(address!("0000000000000000000000000000000000000123"))
+        );
+
+        let serialized = serde_json::to_value(&state_override).unwrap();
+        assert_eq!(
+            serde_json::from_value::<ExtendedStateOverride>(serialized).unwrap(),
+            state_override
+        );
+    }
+
+    #[test]
+    fn test_standard_state_override() {
+        let s = r#"{
+            "0x1b5212AF6b76113afD94cD2B5a78a73B7d7A8222": {
+                "balance": "0x39726378b58c400000",
+                "stateDiff": {}
+            }
+        }"#;
+        let standard: state::StateOverride = serde_json::from_str(s).unwrap();
+        let state_override: ExtendedStateOverride = serde_json::from_str(s).unwrap();
+        assert_eq!(extend_state_override(standard), state_override);
+
+        let acc =
+            state_override.get(&address!("1b5212AF6b76113afD94cD2B5a78a73B7d7A8222")).unwrap();
+        assert!(acc.immutable_references.is_empty());
+        assert!(acc.move_precompile_to_address.is_none());
+    }
+}
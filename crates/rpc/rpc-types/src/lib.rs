@@ -9,8 +9,10 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
+pub mod debug;
 #[allow(hidden_glob_reexports)]
 mod eth;
+pub mod reth;
 
 /// Alias for a peer identifier
 pub type PeerId = B512;
@@ -49,7 +51,6 @@ pub use alloy_rpc_types_txpool as txpool;
 // Ethereum specific rpc types related to typed transaction requests and the engine API.
 #[cfg(feature = "jsonrpsee-types")]
 pub use eth::error::ToRpcError;
-pub use eth::transaction::{self, TransactionRequest, TypedTransactionRequest};
 #[cfg(feature = "jsonrpsee-types")]
 pub use eth::{
     engine,
@@ -57,3 +58,7 @@ pub use eth::{
         ExecutionPayload, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3, PayloadError,
     },
 };
+pub use eth::{
+    overrides,
+    transaction::{self, TransactionRequest, TypedTransactionRequest},
+};
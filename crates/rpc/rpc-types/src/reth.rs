@@ -0,0 +1,406 @@
+//! Types for the `reth` namespace.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types::{BlobTransactionSidecar, BlockId};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Fee statistics of a single block, as returned by `reth_feeStats`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockFeeStats {
+    /// Number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// Hash of the block.
+    pub hash: B256,
+    /// Gas used by all transactions of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Gas limit of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// Number of transactions in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_count: u64,
+    /// Base fee per gas of the block, `None` for blocks before London.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub base_fee_per_gas: Option<u64>,
+    /// Total base fee burnt by the transactions of the block.
+    pub burnt_fees: U256,
+    /// Effective priority fees per gas at the requested percentiles, weighted by gas used.
+    #[serde(with = "alloy_serde::quantity::vec")]
+    pub tip_percentiles: Vec<u128>,
+    /// Total priority fees paid to the fee recipient of the block.
+    pub builder_revenue: U256,
+    /// Blob gas used by the transactions of the block, `None` for blocks before Cancun.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub blob_gas_used: Option<u64>,
+    /// Excess blob gas of the block, `None` for blocks before Cancun.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub excess_blob_gas: Option<u64>,
+    /// Blob base fee per blob gas of the block, `None` for blocks before Cancun.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub blob_base_fee: Option<u128>,
+    /// Total blob fees burnt by the transactions of the block, `None` for blocks before Cancun.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_fees: Option<U256>,
+}
+
+/// The blob sidecar of a transaction in a block, as returned by `reth_getBlobSidecars`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBlobSidecar {
+    /// Hash of the transaction.
+    pub transaction_hash: B256,
+    /// Index of the transaction in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+    /// Versioned hashes of the blobs of the transaction.
+    pub blob_versioned_hashes: Vec<B256>,
+    /// The blobs, commitments and proofs of the transaction, `None` if the sidecar is no longer
+    /// retained by the node.
+    pub sidecar: Option<BlobTransactionSidecar>,
+}
+
+/// The payload the node would build for a proposal, as returned by `reth_buildPayloadPreview`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadPreview {
+    /// Number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// Hash of the parent block the payload was built on.
+    pub parent_hash: B256,
+    /// Total fees paid to the fee recipient by the transactions of the payload.
+    pub value: U256,
+    /// Gas used by all transactions of the payload.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Gas limit of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_limit: u64,
+    /// Blob gas used by the transactions of the payload, `None` for blocks before Cancun.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub blob_gas_used: Option<u64>,
+    /// Hashes of the transactions of the payload, in block order.
+    pub transactions: Vec<B256>,
+    /// Balance credits in wei of the withdrawals of the payload, per address.
+    #[serde(default)]
+    pub withdrawal_credits: BTreeMap<Address, U256>,
+}
+
+/// Changes of watched storage slots of an account, as emitted by `reth_subscribeStorage`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageChanges {
+    /// Number of the block after which the slots hold the values.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Hash of the block after which the slots hold the values.
+    pub block_hash: B256,
+    /// Whether the changes revert blocks that were removed from the canonical chain by a reorg.
+    ///
+    /// If set, the block is the last block that remained canonical and the values are the values
+    /// of the slots in that block.
+    pub removed: bool,
+    /// Address of the account.
+    pub address: Address,
+    /// The values of the changed slots.
+    pub storage: BTreeMap<B256, B256>,
+}
+
+/// Changes of watched accounts, as emitted by `reth_subscribeAccounts`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountChanges {
+    /// Number of the block after which the accounts have the states.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Hash of the block after which the accounts have the states.
+    pub block_hash: B256,
+    /// Whether the changes revert blocks that were removed from the canonical chain by a reorg.
+    ///
+    /// If set, the block is the last block that remained canonical and the states are the states
+    /// of the accounts in that block.
+    pub removed: bool,
+    /// The states of the changed accounts, `None` if the account does not exist.
+    pub accounts: BTreeMap<Address, Option<AccountState>>,
+}
+
+/// The balance, nonce and code hash of an account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    /// Balance of the account.
+    pub balance: U256,
+    /// Nonce of the account.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Hash of the code of the account.
+    pub code_hash: B256,
+}
+
+/// A committed checkpoint of a sync stage, as emitted by `reth_subscribeStageCheckpoints`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageCheckpointChange {
+    /// Name of the stage, e.g. `Execution`.
+    pub stage: String,
+    /// Number of the block the stage has processed up to.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Number of entities the stage has processed, if the stage tracks them.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub entities_processed: Option<u64>,
+    /// Total number of entities the stage has to process, if the stage tracks them.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub entities_total: Option<u64>,
+}
+
+/// The result of a query, as returned by `reth_sql`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlQueryResult {
+    /// The names of the columns.
+    pub columns: Vec<String>,
+    /// The rows, with one value per column.
+    ///
+    /// Integers and booleans are JSON numbers and booleans, binary values are hex encoded strings
+    /// and all other values are formatted as strings.
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// An entry of a database table, as returned by `reth_dbGet` and `reth_dbRange`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbEntry {
+    /// The encoded key, as stored in the database.
+    pub key: Bytes,
+    /// The compressed value, as stored in the database.
+    pub value: Bytes,
+    /// The decoded key, `None` if it can't be decoded.
+    pub decoded_key: Option<serde_json::Value>,
+    /// The decoded value, `None` if it can't be decoded.
+    pub decoded_value: Option<serde_json::Value>,
+}
+
+/// The block or transaction to return the internal transfers of with `reth_getInternalTransfers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InternalTransfersTarget {
+    /// A single transaction, `{"transactionHash": "0x..."}`.
+    #[serde(rename_all = "camelCase")]
+    Transaction {
+        /// Hash of the transaction.
+        transaction_hash: B256,
+    },
+    /// All transactions of a block.
+    Block(BlockId),
+}
+
+/// An ETH transfer made by a call, create or selfdestruct within a transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InternalTransfer {
+    /// Address the value was transferred from.
+    pub from: Address,
+    /// Address the value was transferred to.
+    pub to: Address,
+    /// Transferred value in wei.
+    pub value: U256,
+}
+
+/// The internal transfers of a transaction, as returned by `reth_getInternalTransfers`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInternalTransfers {
+    /// Number of the block of the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Hash of the transaction.
+    pub transaction_hash: B256,
+    /// Index of the transaction in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+    /// The internal transfers of the transaction in execution order, excluding the value
+    /// transfer of the transaction itself.
+    pub transfers: Vec<InternalTransfer>,
+}
+
+/// The value a block delivered to its proposer, as returned by `reth_getProposerRevenue`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockProposerRevenue {
+    /// Number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    /// Hash of the block.
+    pub hash: B256,
+    /// Fee recipient of the block.
+    pub fee_recipient: Address,
+    /// Balance of the fee recipient before the block.
+    pub fee_recipient_balance_before: U256,
+    /// Balance of the fee recipient after the block.
+    pub fee_recipient_balance_after: U256,
+    /// The payment of the fee recipient to the proposer with the last transaction of the block,
+    /// `None` if the fee recipient didn't pay the proposer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment: Option<ProposerPayment>,
+    /// The value delivered to the proposer, the value of the payment if there is one, otherwise
+    /// the increase of the balance of the fee recipient.
+    pub proposer_value: U256,
+}
+
+/// A payment of the fee recipient of a block to the proposer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposerPayment {
+    /// Hash of the payment transaction.
+    pub transaction_hash: B256,
+    /// Index of the payment transaction in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+    /// Recipient of the payment.
+    pub recipient: Address,
+    /// Value of the payment in wei.
+    pub value: U256,
+}
+
+/// The earliest blocks for which the node still has the data of each class, as returned by
+/// `reth_dataAvailability`.
+///
+/// Data of blocks before the earliest block has been pruned, data of blocks moved to static files
+/// is still available.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataAvailability {
+    /// Number of the latest block of the node.
+    #[serde(with = "alloy_serde::quantity")]
+    pub latest_block: u64,
+    /// Earliest block with receipts and logs. If receipts are pruned by contract address, the
+    /// receipts of other contracts may be pruned for later blocks too.
+    #[serde(with = "alloy_serde::quantity")]
+    pub receipts: u64,
+    /// Earliest block with the account and storage history, for state queries at past blocks.
+    #[serde(with = "alloy_serde::quantity")]
+    pub history: u64,
+    /// Earliest block whose transactions can be looked up by hash.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_lookup: u64,
+    /// Earliest block with the transactions of its body.
+    #[serde(with = "alloy_serde::quantity")]
+    pub bodies: u64,
+}
+
+/// A reorg of the canonical chain observed by the node, as returned by `reth_getReorgs`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reorg {
+    /// Sequence number of the reorg, which increases with every observed reorg.
+    #[serde(with = "alloy_serde::quantity")]
+    pub id: u64,
+    /// Unix timestamp in milliseconds at which the reorg was observed.
+    #[serde(with = "alloy_serde::quantity")]
+    pub observed_at: u64,
+    /// Number of the last block shared by the old and the new chain.
+    #[serde(with = "alloy_serde::quantity")]
+    pub fork_block_number: u64,
+    /// Hash of the last block shared by the old and the new chain.
+    pub fork_block_hash: B256,
+    /// Hash of the tip of the old chain.
+    pub old_tip_hash: B256,
+    /// Hash of the tip of the new chain.
+    pub new_tip_hash: B256,
+    /// Number of discarded blocks of the old chain, which is the depth of the reorg.
+    #[serde(with = "alloy_serde::quantity")]
+    pub discarded_blocks: u64,
+    /// Number of blocks of the new chain that became canonical.
+    #[serde(with = "alloy_serde::quantity")]
+    pub added_blocks: u64,
+    /// Number of transactions of the discarded blocks.
+    #[serde(with = "alloy_serde::quantity")]
+    pub discarded_transactions: u64,
+    /// Number of transactions of the discarded blocks that are included in the new chain.
+    #[serde(with = "alloy_serde::quantity")]
+    pub reincluded_transactions: u64,
+    /// Milliseconds between the timestamp of the first block of the new chain and the time the
+    /// reorg was observed.
+    #[serde(with = "alloy_serde::quantity")]
+    pub resolution_duration: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_fee_stats_serde_roundtrip() {
+        let stats = BlockFeeStats {
+            number: 1,
+            gas_used: 21_000,
+            gas_limit: 30_000_000,
+            transaction_count: 1,
+            base_fee_per_gas: Some(7),
+            burnt_fees: U256::from(147_000),
+            tip_percentiles: vec![1, 2],
+            builder_revenue: U256::from(21_000),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["gasUsed"], "0x5208");
+        assert_eq!(json["tipPercentiles"], serde_json::json!(["0x1", "0x2"]));
+        assert_eq!(serde_json::from_value::<BlockFeeStats>(json).unwrap(), stats);
+    }
+
+    #[test]
+    fn payload_preview_serde_roundtrip() {
+        let preview = PayloadPreview {
+            number: 1,
+            gas_used: 21_000,
+            gas_limit: 30_000_000,
+            value: U256::from(21_000),
+            transactions: vec![B256::with_last_byte(1)],
+            withdrawal_credits: BTreeMap::from([(Address::with_last_byte(1), U256::from(1))]),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&preview).unwrap();
+        assert_eq!(json["gasUsed"], "0x5208");
+        assert!(json.get("blobGasUsed").is_none());
+        assert_eq!(serde_json::from_value::<PayloadPreview>(json).unwrap(), preview);
+    }
+
+    #[test]
+    fn deserialize_internal_transfers_target() {
+        let hash = B256::with_last_byte(1);
+        let target: InternalTransfersTarget =
+            serde_json::from_value(serde_json::json!({ "transactionHash": hash })).unwrap();
+        assert_eq!(target, InternalTransfersTarget::Transaction { transaction_hash: hash });
+
+        let target: InternalTransfersTarget =
+            serde_json::from_value(serde_json::json!(hash)).unwrap();
+        assert_eq!(target, InternalTransfersTarget::Block(hash.into()));
+
+        let target: InternalTransfersTarget =
+            serde_json::from_value(serde_json::json!("latest")).unwrap();
+        assert_eq!(target, InternalTransfersTarget::Block(BlockId::latest()));
+    }
+
+    #[test]
+    fn reorg_serde_roundtrip() {
+        let reorg = Reorg {
+            id: 3,
+            observed_at: 1_700_000_000_000,
+            fork_block_number: 100,
+            discarded_blocks: 2,
+            added_blocks: 3,
+            discarded_transactions: 10,
+            reincluded_transactions: 8,
+            resolution_duration: 4_000,
+            ..Default::default()
+        };
+        let json = serde_json::to_value(reorg).unwrap();
+        assert_eq!(json["forkBlockNumber"], "0x64");
+        assert_eq!(json["reincludedTransactions"], "0x8");
+        assert_eq!(serde_json::from_value::<Reorg>(json).unwrap(), reorg);
+    }
+}
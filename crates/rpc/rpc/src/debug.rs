@@ -4,11 +4,12 @@ use jsonrpsee::core::RpcResult;
 use reth_chainspec::EthereumHardforks;
 use reth_evm::{system_calls::pre_block_beacon_root_contract_call, ConfigureEvmEnv};
 use reth_primitives::{
-    Address, Block, BlockId, BlockNumberOrTag, Bytes, TransactionSignedEcRecovered, B256, U256,
+    Address, Block, BlockId, BlockNumberOrTag, Bytes, TransactionSignedEcRecovered, Withdrawals,
+    B256, U256,
 };
 use reth_provider::{
     BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, StateProofProvider,
-    StateProviderFactory, TransactionVariant,
+    StateProviderFactory, StorageRangeProvider, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::DebugApiServer;
@@ -16,9 +17,10 @@ use reth_rpc_eth_api::{
     helpers::{Call, EthApiSpec, EthTransactions, TraceExt},
     EthApiTypes, FromEthApiError,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
+use reth_rpc_eth_types::{revm_utils::PrecompileMoves, EthApiError, StateCacheDb};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_rpc_types::{
+    debug::{StorageRangeEntry, StorageRangeResult},
     state::EvmOverrides,
     trace::geth::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
@@ -29,7 +31,7 @@ use reth_rpc_types::{
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_trie::{HashedPostState, HashedStorage};
 use revm::{
-    db::{states::bundle_state::BundleRetention, CacheDB},
+    db::{states::bundle_state::BundleRetention, AccountState, CacheDB},
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
     StateBuilder,
 };
@@ -38,9 +40,12 @@ use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig,
 };
 use revm_primitives::{keccak256, HashMap};
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// The maximum number of storage entries returned by a single `debug_storageRangeAt` call.
+const MAX_STORAGE_RANGE_RESULTS: u64 = 1024;
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
@@ -290,7 +295,7 @@ where
         let at = block_id.unwrap_or_default();
         let GethDebugTracingCallOptions { tracing_options, state_overrides, block_overrides } =
             opts;
-        let overrides = EvmOverrides::new(state_overrides, block_overrides.map(Box::new));
+        let overrides = EvmOverrides::new(state_overrides, block_overrides.map(Box::new)).into();
         let GethDebugTracingOptions { config, tracer, tracer_config, .. } = tracing_options;
 
         let this = self.clone();
@@ -503,7 +508,11 @@ where
                             ),
                             handler_cfg: cfg.handler_cfg,
                         };
-                        let (res, _) = this.inner.eth_api.transact(&mut db, env)?;
+                        let (res, _) = this.inner.eth_api.transact(
+                            &mut db,
+                            env,
+                            &PrecompileMoves::default(),
+                        )?;
                         db.commit(res.state);
                     }
                 }
@@ -520,7 +529,8 @@ where
                     while let Some(tx) = transactions.next() {
                         // apply state overrides only once, before the first transaction
                         let state_overrides = state_overrides.take();
-                        let overrides = EvmOverrides::new(state_overrides, block_overrides.clone());
+                        let overrides =
+                            EvmOverrides::new(state_overrides, block_overrides.clone()).into();
 
                         let env = this.eth_api().prepare_call_env(
                             cfg.clone(),
@@ -603,7 +613,8 @@ where
                         handler_cfg: cfg.handler_cfg,
                     };
 
-                    let (res, _) = this.inner.eth_api.transact(&mut db, env)?;
+                    let (res, _) =
+                        this.inner.eth_api.transact(&mut db, env, &PrecompileMoves::default())?;
                     db.commit(res.state);
                 }
 
@@ -649,6 +660,114 @@ where
             .await
     }
 
+    /// The `debug_storageRangeAt` method returns a page of the storage of the given contract at
+    /// the state before the transaction at `tx_idx` of the block is executed.
+    ///
+    /// Storage entries are ordered by the hash of their slot, the page starts at the first hashed
+    /// slot that is greater or equal to `key_start` and contains at most `max_result` entries, up
+    /// to 1024.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let ((cfg, block_env, _), maybe_block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_hash.into()),
+            self.inner.eth_api.block_with_senders(block_hash.into()),
+        )?;
+        let block = maybe_block.ok_or(EthApiError::UnknownBlockOrTxIndex)?;
+        if tx_idx > block.body.len() {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {block_hash}"
+            ))
+            .into())
+        }
+        let limit = max_result.min(MAX_STORAGE_RANGE_RESULTS) as usize;
+
+        let this = self.clone();
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                // replay all transactions prior to the targeted transaction
+                if tx_idx > 0 {
+                    let evm_config = Call::evm_config(this.eth_api()).clone();
+                    pre_block_beacon_root_contract_call(
+                        &mut db,
+                        &evm_config,
+                        &this.inner.provider.chain_spec(),
+                        &cfg,
+                        &block_env,
+                        block.timestamp,
+                        block.number,
+                        block.parent_beacon_block_root,
+                    )
+                    .map_err(|err| EthApiError::Internal(err.into()))?;
+
+                    for tx in block.into_transactions_ecrecovered().take(tx_idx) {
+                        let env = EnvWithHandlerCfg {
+                            env: Env::boxed(
+                                cfg.cfg_env.clone(),
+                                block_env.clone(),
+                                evm_config.tx_env(&tx),
+                            ),
+                            handler_cfg: cfg.handler_cfg,
+                        };
+                        let (res, _) = this.inner.eth_api.transact(
+                            &mut db,
+                            env,
+                            &PrecompileMoves::default(),
+                        )?;
+                        db.commit(res.state);
+                    }
+                }
+
+                // Overlay the storage changes of the replayed transactions, the plain slots of
+                // these entries are known and returned as preimages.
+                let mut hashed_state = HashedPostState::default();
+                let mut preimages = HashMap::new();
+                if let Some(account) = db.accounts.get(&contract_address) {
+                    let mut storage = HashedStorage::new(matches!(
+                        account.account_state,
+                        AccountState::StorageCleared | AccountState::NotExisting
+                    ));
+                    for (slot, value) in &account.storage {
+                        let slot = B256::from(*slot);
+                        let hashed_slot = keccak256(slot);
+                        preimages.insert(hashed_slot, slot);
+                        storage.storage.insert(hashed_slot, *value);
+                    }
+                    hashed_state.storages.insert(keccak256(contract_address), storage);
+                }
+
+                let range = db
+                    .db
+                    .into_inner()
+                    .hashed_storage_range(hashed_state, contract_address, key_start, limit)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                Ok(StorageRangeResult {
+                    storage: range
+                        .entries
+                        .into_iter()
+                        .map(|(hashed_slot, value)| {
+                            let entry = StorageRangeEntry {
+                                key: preimages.get(&hashed_slot).copied(),
+                                value: value.into(),
+                            };
+                            (hashed_slot, entry)
+                        })
+                        .collect(),
+                    next_key: range.next_key,
+                })
+            })
+            .await
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// Returns the trace frame and the state that got updated after executing the transaction.
@@ -843,6 +962,17 @@ where
             .collect())
     }
 
+    /// Handler for `debug_getWithdrawalCredits`
+    async fn withdrawal_credits(&self, block_id: BlockId) -> RpcResult<BTreeMap<Address, U256>> {
+        let block = self
+            .inner
+            .provider
+            .block_by_id(block_id)
+            .to_rpc_result()?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        Ok(block.withdrawals.as_ref().map(Withdrawals::balance_increments).unwrap_or_default())
+    }
+
     /// Handler for `debug_getBadBlocks`
     async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>> {
         Err(internal_rpc_err("unimplemented"))
@@ -1109,15 +1239,26 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_bad_block(
@@ -4,8 +4,8 @@ use reth_rpc_api::{EngineEthApiServer, EthApiServer, EthFilterApiServer};
 /// Re-export for convenience
 pub use reth_rpc_engine_api::EngineApi;
 use reth_rpc_types::{
-    state::StateOverride, BlockOverrides, EIP1186AccountProofResponse, Filter, JsonStorageKey, Log,
-    RichBlock, SyncStatus, TransactionRequest,
+    overrides::ExtendedStateOverride, BlockOverrides, EIP1186AccountProofResponse, Filter,
+    JsonStorageKey, Log, RichBlock, SyncStatus, TransactionRequest,
 };
 use tracing_futures::Instrument;
 
@@ -62,7 +62,7 @@ where
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-        state_overrides: Option<StateOverride>,
+        state_overrides: Option<ExtendedStateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<Bytes> {
         self.eth
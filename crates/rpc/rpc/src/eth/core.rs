@@ -91,8 +91,7 @@ where
         Tasks: TaskSpawner + Clone + 'static,
         Events: CanonStateSubscriptions,
     {
-        let blocking_task_pool =
-            BlockingTaskPool::build().expect("failed to build blocking task pool");
+        let blocking_task_pool = ctx.new_blocking_task_pool();
 
         let inner = EthApiInner::new(
             ctx.provider.clone(),
@@ -371,7 +370,7 @@ mod tests {
     use reth_chainspec::BaseFeeParams;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
-    use reth_primitives::{Block, BlockNumberOrTag, Header, TransactionSigned, B256, U64};
+    use reth_primitives::{Block, BlockNumberOrTag, Header, TransactionSigned, B256, U256, U64};
     use reth_provider::{
         test_utils::{MockEthProvider, NoopProvider},
         BlockReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
@@ -382,7 +381,7 @@ mod tests {
     };
     use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
     use reth_rpc_types::FeeHistory;
-    use reth_tasks::pool::BlockingTaskPool;
+    use reth_tasks::pool::{BlockingTaskPool, BlockingTaskPriority};
     use reth_testing_utils::{generators, generators::Rng};
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
 
@@ -414,7 +413,8 @@ mod tests {
             GasPriceOracle::new(provider, Default::default(), cache),
             gas_cap,
             DEFAULT_ETH_PROOF_WINDOW,
-            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            BlockingTaskPool::build_with_priority(0, BlockingTaskPriority::Low)
+                .expect("failed to build tracing pool"),
             fee_history_cache,
             evm_config,
             None,
@@ -662,4 +662,35 @@ mod tests {
             "all: no percentiles were requested, so there should be no rewards result"
         );
     }
+
+    #[tokio::test]
+    async fn test_header_by_number_and_hash() {
+        let mock_provider = MockEthProvider::default();
+        let header = Header { number: 1, difficulty: U256::from(1), ..Default::default() };
+        let hash = header.hash_slow();
+        mock_provider.add_block(hash, Block { header: header.clone(), ..Default::default() });
+        mock_provider.add_header(hash, header);
+        let eth_api = build_test_eth_api(mock_provider);
+
+        let by_number = <EthApi<_, _, _, _> as EthApiServer>::header_by_number(
+            &eth_api,
+            BlockNumberOrTag::Number(1),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let by_hash = <EthApi<_, _, _, _> as EthApiServer>::header_by_hash(&eth_api, hash)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(by_number.hash, Some(hash));
+        assert_eq!(by_number.number, Some(1));
+        assert_eq!(by_number.total_difficulty, Some(U256::from(1)));
+        assert_eq!(by_hash, by_number);
+        assert!(<EthApi<_, _, _, _> as EthApiServer>::header_by_hash(&eth_api, B256::ZERO)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
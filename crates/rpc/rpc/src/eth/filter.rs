@@ -3,37 +3,42 @@
 use std::{
     collections::HashMap,
     fmt,
-    iter::StepBy,
-    ops::RangeInclusive,
-    sync::Arc,
+    sync::{Arc, OnceLock},
     time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
+use futures::future::BoxFuture;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
-use reth_chainspec::ChainInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
-use reth_rpc_eth_api::EthFilterApiServer;
+use reth_primitives::{IntoRecoveredTransaction, Receipt, SealedBlock, TxHash, B256};
+use reth_provider::{
+    BlockIdReader, BlockReader, EvmEnvProvider, LogBlock, LogBlocks, LogCursor, LogsCollector,
+    LogsProvider, ProviderError, ProviderResult,
+};
+use reth_rpc_eth_api::{
+    helpers::{LoadPendingBlock, SpawnBlocking},
+    EthFilterApiServer,
+};
 use reth_rpc_eth_types::{
-    logs_utils::{self, append_matching_block_logs},
-    EthApiError, EthFilterConfig, EthFilterError, EthStateCache, EthSubscriptionIdProvider,
+    logs_utils, EthApiError, EthFilterConfig, EthFilterError, EthStateCache,
+    EthSubscriptionIdProvider,
 };
 use reth_rpc_server_types::ToRpcResult;
 use reth_rpc_types::{
-    BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
-    PendingTransactionFilterKind,
+    BlockNumHash, BlockNumberOrTag, Filter, FilterBlockOption, FilterChanges, FilterId,
+    FilteredParams, Log, PendingTransactionFilterKind,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use tokio::{
-    sync::{mpsc::Receiver, Mutex},
+    sync::{mpsc::Receiver, oneshot, Mutex},
     time::MissedTickBehavior,
 };
-use tracing::trace;
+use tracing::{debug, trace};
 
-/// The maximum number of headers we read at once when handling a range filter.
-const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
+/// The maximum number of blocks that may have matching logs that are looked up at once, before
+/// their logs are collected.
+const MAX_LOG_BLOCKS_BATCH: usize = 100;
 
 /// `Eth` filter RPC implementation.
 pub struct EthFilter<Provider, Pool> {
@@ -61,20 +66,25 @@ where
         config: EthFilterConfig,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            stale_filter_ttl,
+            pending_logs,
+        } = config;
         let inner = EthFilterInner {
             provider,
             active_filters: Default::default(),
             pool,
             id_provider: Arc::new(EthSubscriptionIdProvider::default()),
             eth_cache,
-            max_headers_range: MAX_HEADERS_RANGE,
             task_spawner,
             stale_filter_ttl,
             // if not set, use the max value, which is effectively no limit
             max_blocks_per_filter: max_blocks_per_filter.unwrap_or(u64::MAX),
             max_logs_per_response: max_logs_per_response.unwrap_or(usize::MAX),
+            pending_logs,
+            local_pending_block: OnceLock::new(),
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -90,6 +100,15 @@ where
         eth_filter
     }
 
+    /// Sets the source of the pending block that the `eth_` API builds from the transaction pool,
+    /// which is used for pending logs if the provider has no pending block.
+    ///
+    /// The source is shared by all clones of the filter and can only be set once.
+    pub fn with_local_pending_block(self, source: impl LocalPendingBlock) -> Self {
+        let _ = self.inner.local_pending_block.set(Box::new(source));
+        self
+    }
+
     /// Returns all currently active filters
     pub fn active_filters(&self) -> &ActiveFilters {
         &self.inner.active_filters
@@ -124,7 +143,7 @@ where
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogsProvider + Clone + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
 {
@@ -133,13 +152,32 @@ where
         let info = self.inner.provider.chain_info()?;
         let best_number = info.best_number;
 
+        let log_filter = {
+            let filters = self.inner.active_filters.inner.lock().await;
+            match filters.get(&id).ok_or_else(|| EthFilterError::FilterNotFound(id.clone()))?.kind {
+                FilterKind::Log(ref log_filter) => Some(log_filter.clone()),
+                _ => None,
+            }
+        };
+        // the pending block may be built, so this is done without holding the lock of the filters
+        let pending = match log_filter {
+            Some(log_filter) => {
+                self.inner.pending_block_for_filter(&log_filter, best_number).await?
+            }
+            None => None,
+        };
+
         // start_block is the block from which we should start fetching changes, the next block from
         // the last time changes were polled, in other words the best block at last poll + 1
-        let (start_block, kind) = {
+        let (start_block, cursor, kind, pending) = {
             let mut filters = self.inner.active_filters.inner.lock().await;
-            let filter = filters.get_mut(&id).ok_or(EthFilterError::FilterNotFound(id))?;
+            let filter =
+                filters.get_mut(&id).ok_or_else(|| EthFilterError::FilterNotFound(id.clone()))?;
+
+            // the pending block, if its logs haven't been returned by a previous poll
+            let pending = pending.filter(|(block, _)| filter.pending_block != Some(block.hash()));
 
-            if filter.block > best_number {
+            if filter.block > best_number && pending.is_none() {
                 // no new blocks since the last poll
                 return Ok(FilterChanges::Empty)
             }
@@ -151,7 +189,7 @@ where
             std::mem::swap(&mut filter.block, &mut block);
             filter.last_poll_timestamp = Instant::now();
 
-            (block, filter.kind.clone())
+            (block, filter.cursor.take(), filter.kind.clone(), pending)
         };
 
         match kind {
@@ -168,29 +206,60 @@ where
                 Ok(FilterChanges::Hashes(block_hashes))
             }
             FilterKind::Log(filter) => {
-                let (from_block_number, to_block_number) = match filter.block_option {
-                    FilterBlockOption::Range { from_block, to_block } => {
-                        let from = from_block
-                            .map(|num| self.inner.provider.convert_block_number(num))
-                            .transpose()?
-                            .flatten();
-                        let to = to_block
-                            .map(|num| self.inner.provider.convert_block_number(num))
-                            .transpose()?
-                            .flatten();
-                        logs_utils::get_filter_block_range(from, to, start_block, info)
+                let mut logs = Vec::new();
+                let mut next = None;
+                if start_block <= best_number {
+                    let (from_block_number, to_block_number) = match filter.block_option {
+                        FilterBlockOption::Range { from_block, to_block } => {
+                            let from = from_block
+                                .map(|num| self.inner.provider.convert_block_number(num))
+                                .transpose()?
+                                .flatten();
+                            let to = to_block
+                                .map(|num| self.inner.provider.convert_block_number(num))
+                                .transpose()?
+                                .flatten();
+                            logs_utils::get_filter_block_range(from, to, start_block, info)
+                        }
+                        FilterBlockOption::AtBlockHash(_) => {
+                            // blockHash is equivalent to fromBlock = toBlock = the block number
+                            // with hash blockHash
+                            // get_logs_in_block_range is inclusive
+                            (start_block, best_number)
+                        }
+                    };
+                    // the changes are returned in pages of at most the max logs per response, the
+                    // next poll continues at the cursor
+                    (logs, next) = self
+                        .inner
+                        .get_logs_in_block_range(
+                            &filter,
+                            from_block_number,
+                            to_block_number,
+                            cursor,
+                            self.inner.max_logs_per_response,
+                        )
+                        .await?;
+                }
+
+                let mut filters = self.inner.active_filters.inner.lock().await;
+                let active = filters.get_mut(&id);
+                if let Some(next) = next {
+                    if let Some(active) = active {
+                        active.block = next.block_number;
+                        active.cursor = Some(next);
                     }
-                    FilterBlockOption::AtBlockHash(_) => {
-                        // blockHash is equivalent to fromBlock = toBlock = the block number with
-                        // hash blockHash
-                        // get_logs_in_block_range is inclusive
-                        (start_block, best_number)
+                } else if let Some((block, receipts)) = pending {
+                    if let Some(active) = active {
+                        active.pending_block = Some(block.hash());
                     }
-                };
-                let logs = self
-                    .inner
-                    .get_logs_in_block_range(&filter, from_block_number, to_block_number, info)
-                    .await?;
+                    let filter_params = FilteredParams::new(Some(*filter));
+                    logs.extend(logs_utils::matching_pending_block_logs(
+                        &filter_params,
+                        &block,
+                        &receipts,
+                    ));
+                }
                 Ok(FilterChanges::Logs(logs))
             }
         }
@@ -221,7 +290,7 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogsProvider + Clone + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -313,6 +382,37 @@ impl<Provider, Pool> Clone for EthFilter<Provider, Pool> {
     }
 }
 
+/// Provides the pending block that the `eth_` API builds from the transaction pool.
+///
+/// See [`EthFilter::with_local_pending_block`].
+pub trait LocalPendingBlock: Send + Sync + 'static {
+    /// Returns the locally built pending block and its receipts, if it could be built.
+    fn pending_block_with_receipts(&self) -> BoxFuture<'_, Option<(SealedBlock, Vec<Receipt>)>>;
+}
+
+impl<T> LocalPendingBlock for T
+where
+    T: LoadPendingBlock + SpawnBlocking + 'static,
+{
+    fn pending_block_with_receipts(&self) -> BoxFuture<'_, Option<(SealedBlock, Vec<Receipt>)>> {
+        Box::pin(async move {
+            match self.local_pending_block_and_receipts().await {
+                Ok(pending) => pending.map(|(block, receipts)| (block.block, receipts)),
+                Err(err) => {
+                    debug!(target: "rpc::eth::filter", %err, "Failed to load the pending block");
+                    None
+                }
+            }
+        })
+    }
+}
+
+impl fmt::Debug for dyn LocalPendingBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalPendingBlock").finish_non_exhaustive()
+    }
+}
+
 /// Container type `EthFilter`
 #[derive(Debug)]
 struct EthFilterInner<Provider, Pool> {
@@ -330,17 +430,19 @@ struct EthFilterInner<Provider, Pool> {
     max_logs_per_response: usize,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
-    /// maximum number of headers to read at once for range filter
-    max_headers_range: u64,
     /// The type that can spawn tasks.
     task_spawner: Box<dyn TaskSpawner>,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// Whether log filters whose `toBlock` is `pending` also return the logs of the pending block
+    pending_logs: bool,
+    /// The pending block built by the `eth_` API, if the provider has no pending block
+    local_pending_block: OnceLock<Box<dyn LocalPendingBlock>>,
 }
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogsProvider + Clone + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -349,10 +451,10 @@ where
             FilterBlockOption::AtBlockHash(block_hash) => {
                 // for all matching logs in the block
                 // get the block header with the hash
-                let block = self
+                let header = self
                     .provider
                     .header_by_hash_or_number(block_hash.into())?
-                    .ok_or(ProviderError::HeaderNotFound(block_hash.into()))?;
+                    .ok_or_else(|| ProviderError::HeaderNotFound(block_hash.into()))?;
 
                 // we also need to ensure that the receipts are available and return an error if
                 // not, in case the block hash been reorged
@@ -362,19 +464,20 @@ where
                     .await?
                     .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
 
-                let mut all_logs = Vec::new();
-                let filter = FilteredParams::new(Some(filter));
-                logs_utils::append_matching_block_logs(
-                    &mut all_logs,
+                let log_filter = logs_utils::log_filter(&filter);
+                let mut collector = LogsCollector::new(&log_filter, None, usize::MAX);
+                collector.append_block_logs(
                     &self.provider,
-                    &filter,
-                    (block_hash, block.number).into(),
+                    LogBlock::new(&header, block_hash),
                     &receipts,
-                    false,
-                    block.timestamp,
                 )?;
 
-                Ok(all_logs)
+                Ok(collector
+                    .into_page()
+                    .logs
+                    .into_iter()
+                    .map(|log| logs_utils::block_log_to_rpc(log, false))
+                    .collect())
             }
             FilterBlockOption::Range { from_block, to_block } => {
                 // compute the range
@@ -392,12 +495,67 @@ where
                     .flatten();
                 let (from_block_number, to_block_number) =
                     logs_utils::get_filter_block_range(from, to, start_block, info);
-                self.get_logs_in_block_range(&filter, from_block_number, to_block_number, info)
-                    .await
+                // all logs of a single block are always returned
+                let limit = if from_block_number == to_block_number {
+                    usize::MAX
+                } else {
+                    self.max_logs_per_response
+                };
+                let (mut logs, next) = self
+                    .get_logs_in_block_range(
+                        &filter,
+                        from_block_number,
+                        to_block_number,
+                        None,
+                        limit,
+                    )
+                    .await?;
+                if next.is_some() {
+                    return Err(EthFilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+                }
+
+                if let Some((block, receipts)) =
+                    self.pending_block_for_filter(&filter, info.best_number).await?
+                {
+                    let filter_params = FilteredParams::new(Some(filter));
+                    logs.extend(logs_utils::matching_pending_block_logs(
+                        &filter_params,
+                        &block,
+                        &receipts,
+                    ));
+                }
+
+                Ok(logs)
             }
         }
     }
 
+    /// Returns the pending block and its receipts, if pending logs are enabled, the filter's
+    /// `toBlock` is `pending` and there is a pending block on top of the best block.
+    ///
+    /// If the provider has no pending block, the pending block that the `eth_` API builds is used.
+    async fn pending_block_for_filter(
+        &self,
+        filter: &Filter,
+        best_number: u64,
+    ) -> Result<Option<(SealedBlock, Vec<Receipt>)>, EthFilterError> {
+        let includes_pending = matches!(
+            filter.block_option,
+            FilterBlockOption::Range { to_block: Some(BlockNumberOrTag::Pending), .. }
+        );
+        if !self.pending_logs || !includes_pending {
+            return Ok(None)
+        }
+
+        let mut pending = self.provider.pending_block_and_receipts()?;
+        if pending.is_none() {
+            if let Some(source) = self.local_pending_block.get() {
+                pending = source.pending_block_with_receipts().await;
+            }
+        }
+        Ok(pending.filter(|(block, _)| block.number > best_number))
+    }
+
     /// Installs a new filter and returns the new identifier.
     async fn install_filter(&self, kind: FilterKind) -> RpcResult<FilterId> {
         let last_poll_block_number = self.provider.best_block_number().to_rpc_result()?;
@@ -408,26 +566,32 @@ where
             ActiveFilter {
                 block: last_poll_block_number,
                 last_poll_timestamp: Instant::now(),
+                pending_block: None,
+                cursor: None,
                 kind,
             },
         );
         Ok(id)
     }
 
-    /// Returns all logs in the given _inclusive_ range that match the filter
+    /// Returns at most `limit` logs in the given _inclusive_ range that match the filter, starting
+    /// at the cursor, and the cursor of the next matching log if there are more.
+    ///
+    /// The blocks that may have matching logs are looked up in batches on a blocking task, until
+    /// the page is full. Their receipts are read from the cache.
     ///
     /// Returns an error if:
     ///  - underlying database error
-    ///  - amount of matches exceeds configured limit
+    ///  - the range exceeds the configured max blocks per filter
     async fn get_logs_in_block_range(
         &self,
         filter: &Filter,
         from_block: u64,
         to_block: u64,
-        chain_info: ChainInfo,
-    ) -> Result<Vec<Log>, EthFilterError> {
+        cursor: Option<LogCursor>,
+        limit: usize,
+    ) -> Result<(Vec<Log>, Option<LogCursor>), EthFilterError> {
         trace!(target: "rpc::eth::filter", from=from_block, to=to_block, ?filter, "finding logs in range");
-        let best_number = chain_info.best_number;
 
         if to_block < from_block {
             return Err(EthFilterError::InvalidBlockRangeParams)
@@ -437,81 +601,57 @@ where
             return Err(EthFilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
         }
 
-        let mut all_logs = Vec::new();
+        let log_filter = logs_utils::log_filter(filter);
         let filter_params = FilteredParams::new(Some(filter.clone()));
-
-        if (to_block == best_number) && (from_block == best_number) {
-            // only one block to check and it's the current best block which we can fetch directly
-            // Note: In case of a reorg, the best block's hash might have changed, hence we only
-            // return early of we were able to fetch the best block's receipts
-            // perf: we're fetching the best block here which is expected to be cached
-            if let Some((block, receipts)) =
-                self.eth_cache.get_block_and_receipts(chain_info.best_hash).await?
-            {
-                logs_utils::append_matching_block_logs(
-                    &mut all_logs,
-                    &self.provider,
-                    &filter_params,
-                    chain_info.into(),
-                    &receipts,
-                    false,
-                    block.header.timestamp,
-                )?;
+        let mut collector = LogsCollector::new(&log_filter, cursor, limit);
+
+        let mut blocks = None;
+        'blocks: loop {
+            let (tx, rx) = oneshot::channel();
+            let provider = self.provider.clone();
+            let blocks_filter = log_filter.clone();
+            let remaining = blocks.take();
+            self.task_spawner.spawn_blocking(Box::pin(async move {
+                let batch = (|| {
+                    let mut blocks = match remaining {
+                        Some(blocks) => blocks,
+                        None => {
+                            LogBlocks::new(provider, from_block..=to_block, blocks_filter, cursor)?
+                        }
+                    };
+                    let batch = blocks
+                        .by_ref()
+                        .take(MAX_LOG_BLOCKS_BATCH)
+                        .collect::<ProviderResult<Vec<_>>>()?;
+                    Ok::<_, ProviderError>((batch, blocks))
+                })();
+                let _ = tx.send(batch);
+            }));
+            let (batch, remaining) = rx.await.map_err(|_| EthFilterError::InternalError)??;
+            if batch.is_empty() {
+                break
             }
-            return Ok(all_logs)
-        }
 
-        // derive bloom filters from filter input, so we can check headers for matching logs
-        let address_filter = FilteredParams::address_filter(&filter.address);
-        let topics_filter = FilteredParams::topics_filter(&filter.topics);
-
-        // loop over the range of new blocks and check logs if the filter matches the log's bloom
-        // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
-            let headers = self.provider.headers_range(from..=to)?;
-
-            for (idx, header) in headers.iter().enumerate() {
-                // only if filter matches
-                if FilteredParams::matches_address(header.logs_bloom, &address_filter) &&
-                    FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
-                {
-                    // these are consecutive headers, so we can use the parent hash of the next
-                    // block to get the current header's hash
-                    let block_hash = match headers.get(idx + 1) {
-                        Some(parent) => parent.parent_hash,
-                        None => self
-                            .provider
-                            .block_hash(header.number)?
-                            .ok_or(ProviderError::HeaderNotFound(header.number.into()))?,
-                    };
+            for block in batch {
+                if !filter_params.filter_block_hash(block.hash) {
+                    continue
+                }
 
-                    if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            &self.provider,
-                            &filter_params,
-                            BlockNumHash::new(header.number, block_hash),
-                            &receipts,
-                            false,
-                            header.timestamp,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(EthFilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
-                    }
+                // blocks without receipts are skipped
+                let Some(receipts) = self.eth_cache.get_receipts(block.hash).await? else {
+                    continue
+                };
+                if collector.append_block_logs(&self.provider, block, &receipts)? {
+                    break 'blocks
                 }
             }
+            blocks = Some(remaining);
         }
 
-        Ok(all_logs)
+        let page = collector.into_page();
+        let logs =
+            page.logs.into_iter().map(|log| logs_utils::block_log_to_rpc(log, false)).collect();
+        Ok((logs, page.next))
     }
 }
 
@@ -528,6 +668,10 @@ struct ActiveFilter {
     block: u64,
     /// Last time this filter was polled.
     last_poll_timestamp: Instant,
+    /// Hash of the pending block whose logs were returned by the last poll.
+    pending_block: Option<B256>,
+    /// The position of the next log to return, if the last poll returned a full page of logs.
+    cursor: Option<LogCursor>,
     /// What kind of filter it is.
     kind: FilterKind,
 }
@@ -628,59 +772,3 @@ enum FilterKind {
     Block,
     PendingTransaction(PendingTransactionKind),
 }
-
-/// An iterator that yields _inclusive_ block ranges of a given step size
-#[derive(Debug)]
-struct BlockRangeInclusiveIter {
-    iter: StepBy<RangeInclusive<u64>>,
-    step: u64,
-    end: u64,
-}
-
-impl BlockRangeInclusiveIter {
-    fn new(range: RangeInclusive<u64>, step: u64) -> Self {
-        Self { end: *range.end(), iter: range.step_by(step as usize + 1), step }
-    }
-}
-
-impl Iterator for BlockRangeInclusiveIter {
-    type Item = (u64, u64);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let start = self.iter.next()?;
-        let end = (start + self.step).min(self.end);
-        if start > end {
-            return None
-        }
-        Some((start, end))
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rand::{thread_rng, Rng};
-
-    #[test]
-    fn test_block_range_iter() {
-        for _ in 0..100 {
-            let mut rng = thread_rng();
-            let start = rng.gen::<u32>() as u64;
-            let end = start.saturating_add(rng.gen::<u32>() as u64);
-            let step = rng.gen::<u16>() as u64;
-            let range = start..=end;
-            let mut iter = BlockRangeInclusiveIter::new(range.clone(), step);
-            let (from, mut end) = iter.next().unwrap();
-            assert_eq!(from, start);
-            assert_eq!(end, (from + step).min(*range.end()));
-
-            for (next_from, next_end) in iter {
-                // ensure range starts with previous end + 1
-                assert_eq!(next_from, end + 1);
-                end = next_end;
-            }
-
-            assert_eq!(end, *range.end());
-        }
-    }
-}
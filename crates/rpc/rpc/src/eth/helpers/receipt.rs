@@ -1,5 +1,6 @@
 //! Builds an RPC receipt response w.r.t. data layout of network.
 
+use reth_provider::ChainSpecProvider;
 use reth_rpc_eth_api::helpers::LoadReceipt;
 use reth_rpc_eth_types::EthStateCache;
 
@@ -8,7 +9,13 @@ use crate::EthApi;
 impl<Provider, Pool, Network, EvmConfig> LoadReceipt for EthApi<Provider, Pool, Network, EvmConfig>
 where
     Self: Send + Sync,
+    Provider: ChainSpecProvider,
 {
+    #[inline]
+    fn provider(&self) -> impl ChainSpecProvider {
+        self.inner.provider()
+    }
+
     #[inline]
     fn cache(&self) -> &EthStateCache {
         self.inner.cache()
@@ -52,7 +52,7 @@ mod tests {
         EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
     };
     use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
-    use reth_tasks::pool::BlockingTaskPool;
+    use reth_tasks::pool::{BlockingTaskPool, BlockingTaskPriority};
     use reth_transaction_pool::test_utils::testing_pool;
     use std::collections::HashMap;
 
@@ -71,7 +71,8 @@ mod tests {
             GasPriceOracle::new(NoopProvider::default(), Default::default(), cache.clone()),
             ETHEREUM_BLOCK_GAS_LIMIT,
             DEFAULT_ETH_PROOF_WINDOW,
-            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            BlockingTaskPool::build_with_priority(0, BlockingTaskPriority::Low)
+                .expect("failed to build tracing pool"),
             FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
             evm_config,
             None,
@@ -98,7 +99,8 @@ mod tests {
             GasPriceOracle::new(mock_provider, Default::default(), cache.clone()),
             ETHEREUM_BLOCK_GAS_LIMIT,
             DEFAULT_ETH_PROOF_WINDOW,
-            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            BlockingTaskPool::build_with_priority(0, BlockingTaskPriority::Low)
+                .expect("failed to build tracing pool"),
             FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
             evm_config,
             None,
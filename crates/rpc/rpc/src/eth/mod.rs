@@ -9,7 +9,7 @@ pub mod pubsub;
 /// Implementation of `eth` namespace API.
 pub use bundle::EthBundle;
 pub use core::EthApi;
-pub use filter::EthFilter;
+pub use filter::{EthFilter, LocalPendingBlock};
 pub use pubsub::EthPubSub;
 
 pub use helpers::signer::DevSigner;
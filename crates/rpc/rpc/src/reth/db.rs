@@ -0,0 +1,227 @@
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
+use reth_db::{RawKey, RawTable, RawValue, TableViewer, Tables};
+use reth_db_api::{
+    cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx, DatabaseError,
+};
+use reth_rpc_api::RethDbApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_rpc_types::reth::DbEntry;
+use reth_tasks::TaskSpawner;
+use tokio::sync::oneshot;
+use tracing::trace;
+
+/// The maximum number of entries that can be queried with `reth_dbRange`.
+pub const MAX_DB_RANGE_LIMIT: usize = 1000;
+
+/// `reth` API implementation for raw access to the database, see [`RethDbApiServer`].
+///
+/// Keys are parsed from and entries are decoded to the JSON representation of the types of the
+/// table, like `reth db get` does.
+#[derive(Debug, Clone)]
+pub struct DbApi<DB> {
+    db: DB,
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl<DB> DbApi<DB>
+where
+    DB: Database + Clone + 'static,
+{
+    /// Creates a new instance of [`DbApi`].
+    pub const fn new(db: DB, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self { db, task_spawner }
+    }
+
+    /// Reads the table with the given name on a new blocking task.
+    async fn view_table<V, R>(&self, table: &str, viewer: V) -> Result<R, DbApiError>
+    where
+        V: TableViewer<R, Error = DbApiError> + Send + 'static,
+        R: Send + 'static,
+    {
+        let table = table.parse::<Tables>().map_err(DbApiError::UnknownTable)?;
+        let (tx, rx) = oneshot::channel();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(table.view(&viewer));
+        }));
+        rx.await.map_err(|_| DbApiError::TaskDropped)?
+    }
+
+    /// Returns the entry of the table with the given key.
+    pub async fn get(
+        &self,
+        table: &str,
+        key: serde_json::Value,
+    ) -> Result<Option<DbEntry>, DbApiError> {
+        self.view_table(table, GetEntry { db: self.db.clone(), key }).await
+    }
+
+    /// Returns up to `limit` entries of the table, starting at the given key.
+    pub async fn range(
+        &self,
+        table: &str,
+        start: Option<serde_json::Value>,
+        limit: usize,
+    ) -> Result<Vec<DbEntry>, DbApiError> {
+        if limit > MAX_DB_RANGE_LIMIT {
+            return Err(DbApiError::LimitTooLarge(limit))
+        }
+        self.view_table(table, RangeEntries { db: self.db.clone(), start, limit }).await
+    }
+}
+
+#[async_trait]
+impl<DB> RethDbApiServer for DbApi<DB>
+where
+    DB: Database + Clone + 'static,
+{
+    /// Handler for `reth_dbGet`
+    async fn db_get(&self, table: String, key: serde_json::Value) -> RpcResult<Option<DbEntry>> {
+        trace!(target: "rpc::reth", %table, %key, "Serving reth_dbGet");
+        Ok(self.get(&table, key).await?)
+    }
+
+    /// Handler for `reth_dbRange`
+    async fn db_range(
+        &self,
+        table: String,
+        start: Option<serde_json::Value>,
+        limit: usize,
+    ) -> RpcResult<Vec<DbEntry>> {
+        trace!(target: "rpc::reth", %table, ?start, limit, "Serving reth_dbRange");
+        Ok(self.range(&table, start, limit).await?)
+    }
+}
+
+/// Errors of the [`DbApi`].
+#[derive(Debug, thiserror::Error)]
+pub enum DbApiError {
+    /// The table does not exist.
+    #[error("{0}")]
+    UnknownTable(String),
+    /// The key is not a valid key of the table.
+    #[error("invalid key: {0}")]
+    InvalidKey(#[from] serde_json::Error),
+    /// More entries than [`MAX_DB_RANGE_LIMIT`] were requested.
+    #[error("limit {0} exceeds the maximum of {MAX_DB_RANGE_LIMIT}")]
+    LimitTooLarge(usize),
+    /// Reading the database failed.
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    /// The blocking task was dropped before it completed.
+    #[error("database task dropped")]
+    TaskDropped,
+}
+
+impl From<DbApiError> for ErrorObjectOwned {
+    fn from(err: DbApiError) -> Self {
+        match err {
+            DbApiError::UnknownTable(_) |
+            DbApiError::InvalidKey(_) |
+            DbApiError::LimitTooLarge(_) => invalid_params_rpc_err(err.to_string()),
+            DbApiError::Database(_) | DbApiError::TaskDropped => internal_rpc_err(err.to_string()),
+        }
+    }
+}
+
+/// Reads the entry of a key, see [`DbApi::get`].
+struct GetEntry<DB> {
+    db: DB,
+    key: serde_json::Value,
+}
+
+impl<DB: Database> TableViewer<Option<DbEntry>> for GetEntry<DB> {
+    type Error = DbApiError;
+
+    fn view<T: Table>(&self) -> Result<Option<DbEntry>, Self::Error> {
+        let key = serde_json::from_value::<T::Key>(self.key.clone())?;
+        let tx = self.db.tx()?;
+        // the first entry of the key in tables with duplicate keys
+        let entry = tx.cursor_read::<RawTable<T>>()?.seek_exact(RawKey::new(key))?;
+        Ok(entry.map(db_entry::<T>))
+    }
+}
+
+/// Reads consecutive entries, see [`DbApi::range`].
+struct RangeEntries<DB> {
+    db: DB,
+    start: Option<serde_json::Value>,
+    limit: usize,
+}
+
+impl<DB: Database> TableViewer<Vec<DbEntry>> for RangeEntries<DB> {
+    type Error = DbApiError;
+
+    fn view<T: Table>(&self) -> Result<Vec<DbEntry>, Self::Error> {
+        let start = self.start.clone().map(serde_json::from_value::<T::Key>).transpose()?;
+        let tx = self.db.tx()?;
+        let mut cursor = tx.cursor_read::<RawTable<T>>()?;
+        let entries = cursor
+            .walk(start.map(RawKey::new))?
+            .take(self.limit)
+            .map(|entry| entry.map(db_entry::<T>))
+            .collect::<Result<_, _>>()?;
+        Ok(entries)
+    }
+}
+
+/// Returns the raw entry along with its decoded key and value.
+fn db_entry<T: Table>((key, value): (RawKey<T::Key>, RawValue<T::Value>)) -> DbEntry {
+    DbEntry {
+        decoded_key: key.key().ok().and_then(|key| serde_json::to_value(key).ok()),
+        decoded_value: value.value().ok().and_then(|value| serde_json::to_value(value).ok()),
+        key: key.into_key().into(),
+        value: value.into_value().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{tables, test_utils::create_test_rw_db};
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::{Header, B256};
+    use reth_tasks::TokioTaskExecutor;
+
+    #[tokio::test]
+    async fn get_and_range() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        for number in 0..5 {
+            tx.put::<tables::Headers>(number, Header { number, ..Default::default() }).unwrap();
+            tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(number as u8)).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let api = DbApi::new(db, Box::<TokioTaskExecutor>::default());
+
+        let entry = api.get("CanonicalHeaders", serde_json::json!(3)).await.unwrap().unwrap();
+        assert_eq!(entry.key[..], 3u64.to_be_bytes());
+        assert_eq!(entry.value[..], B256::with_last_byte(3)[..]);
+        assert_eq!(entry.decoded_key, Some(serde_json::json!(3)));
+        assert_eq!(entry.decoded_value, Some(serde_json::json!(B256::with_last_byte(3))));
+        assert_eq!(api.get("CanonicalHeaders", serde_json::json!(5)).await.unwrap(), None);
+
+        let entries = api.range("Headers", Some(serde_json::json!(1)), 2).await.unwrap();
+        let numbers = entries
+            .iter()
+            .map(|entry| serde_json::from_value::<Header>(entry.decoded_value.clone().unwrap()))
+            .map(|header| header.unwrap().number)
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![1, 2]);
+        assert_eq!(api.range("Headers", None, 10).await.unwrap().len(), 5);
+
+        assert!(matches!(
+            api.get("Unknown", serde_json::json!(1)).await,
+            Err(DbApiError::UnknownTable(_))
+        ));
+        assert!(matches!(
+            api.get("Headers", serde_json::json!("0x01")).await,
+            Err(DbApiError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            api.range("Headers", None, MAX_DB_RANGE_LIMIT + 1).await,
+            Err(DbApiError::LimitTooLarge(_))
+        ));
+    }
+}
@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_primitives::BlockId;
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::RethExecutionTraceApiServer;
+use reth_rpc_eth_api::{
+    helpers::{Trace, TraceExt},
+    EthApiTypes,
+};
+use reth_rpc_eth_types::execution_trace::{CallFrameRecorder, ExecutionTrace, RecordingDatabase};
+use reth_rpc_types::debug::ChromeTrace;
+use reth_tasks::pool::BlockingTaskGuard;
+use revm::StateBuilder;
+use std::{sync::Arc, time::Instant};
+use tracing::trace;
+
+/// `reth` API implementation for exporting the execution of a block as a trace, see
+/// [`RethExecutionTraceApiServer`].
+pub struct ExecutionTraceApi<Eth> {
+    inner: Arc<ExecutionTraceApiInner<Eth>>,
+}
+
+impl<Eth> ExecutionTraceApi<Eth> {
+    /// Creates a new instance of [`ExecutionTraceApi`].
+    pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
+        Self { inner: Arc::new(ExecutionTraceApiInner { eth_api, blocking_task_guard }) }
+    }
+}
+
+impl<Eth> ExecutionTraceApi<Eth>
+where
+    Eth: EthApiTypes + TraceExt + 'static,
+{
+    /// Re-executes the block like the block executor and records the execution of its system
+    /// calls and transactions, the call frames of the transactions and the reads of the database.
+    ///
+    /// Returns `None` if the block is not found.
+    pub async fn export_execution_trace(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ChromeTrace>, Eth::Error> {
+        let Some(block) = self.inner.eth_api.block_with_senders(block_id).await? else {
+            return Ok(None)
+        };
+        let (cfg, block_env, _) = self.inner.eth_api.evm_env_at(block.hash().into()).await?;
+
+        let this = self.clone();
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+                let eth_api = &this.inner.eth_api;
+                let mut trace = ExecutionTrace::start();
+                let clock = trace.clock();
+                let mut db = StateBuilder::new()
+                    .with_database(RecordingDatabase::new(StateProviderDatabase::new(state), clock))
+                    .with_bundle_update()
+                    .build();
+
+                let start = Instant::now();
+                eth_api.apply_pre_block_system_calls(&mut db, &cfg, &block_env, &block.header)?;
+                trace.push(clock.span("pre-block system calls", "system", start));
+
+                let name = format!("block {}", block.number);
+                eth_api.replay_block_transactions(
+                    &mut db,
+                    &cfg,
+                    &block_env,
+                    block,
+                    usize::MAX,
+                    |tx_info, tx, env, db| {
+                        let start = Instant::now();
+                        let mut recorder = CallFrameRecorder::new(clock);
+                        let (res, _) = Trace::inspect(eth_api, &mut *db, env, &mut recorder)?;
+
+                        trace.push(
+                            clock
+                                .span(
+                                    format!("transaction {}", tx_info.index.unwrap_or_default()),
+                                    "tx",
+                                    start,
+                                )
+                                .with_arg("hash", tx.hash.to_string())
+                                .with_arg("from", tx.signer().to_string())
+                                .with_arg("gasUsed", res.result.gas_used())
+                                .with_arg("success", res.result.is_success()),
+                        );
+                        trace.extend(recorder.into_events());
+                        Ok(res.state)
+                    },
+                )?;
+
+                let start = Instant::now();
+                eth_api.apply_post_block_system_calls(&mut db, &cfg, &block_env)?;
+                trace.push(clock.span("post-block system calls", "system", start));
+
+                trace.extend(db.database.take_events());
+
+                Ok(Some(trace.finish(name)))
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl<Eth> RethExecutionTraceApiServer for ExecutionTraceApi<Eth>
+where
+    Eth: EthApiTypes + TraceExt + 'static,
+{
+    /// Handler for `reth_exportExecutionTrace`
+    async fn export_execution_trace(&self, block_id: BlockId) -> RpcResult<Option<ChromeTrace>> {
+        trace!(target: "rpc::reth", ?block_id, "Serving reth_exportExecutionTrace");
+        let _permit = self.inner.blocking_task_guard.clone().acquire_owned().await;
+        Self::export_execution_trace(self, block_id).await.map_err(Into::into)
+    }
+}
+
+impl<Eth> Clone for ExecutionTraceApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<Eth> std::fmt::Debug for ExecutionTraceApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecutionTraceApi").finish_non_exhaustive()
+    }
+}
+
+struct ExecutionTraceApiInner<Eth> {
+    /// The implementation of `eth` API
+    eth_api: Eth,
+    /// Restricts the number of concurrent re-executions.
+    blocking_task_guard: BlockingTaskGuard,
+}
@@ -0,0 +1,737 @@
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_errors::RethError;
+use reth_primitives::{
+    Address, BlobParams, BlockId, BlockNumber, BlockNumberOrTag, Receipt, ReorgRecord,
+    SealedHeader, StaticFileSegment, TransactionSigned, U256,
+};
+use reth_provider::{
+    BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, InternalTransfersProvider,
+    ProposerRevenue, ProviderError, PruneCheckpointReader, ReorgJournalReader,
+    StateProviderFactory, StaticFileProviderFactory, TransactionVariant,
+};
+use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_rpc_api::RethApiServer;
+use reth_rpc_eth_types::{
+    fee_history::calculate_reward_percentiles_for_block, EthApiError, EthResult,
+};
+use reth_rpc_types::reth::{
+    BlockFeeStats, BlockProposerRevenue, DataAvailability, InternalTransfer,
+    InternalTransfersTarget, ProposerPayment, Reorg, TransactionBlobSidecar,
+    TransactionInternalTransfers,
+};
+use reth_tasks::TaskSpawner;
+use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
+use tokio::sync::oneshot;
+
+mod db;
+mod execution_trace;
+mod pubsub;
+
+pub use db::{DbApi, DbApiError, MAX_DB_RANGE_LIMIT};
+pub use execution_trace::ExecutionTraceApi;
+pub use pubsub::RethPubSub;
+
+/// The maximum number of blocks that can be queried with `reth_feeStats`.
+const MAX_FEE_STATS_BLOCK_RANGE: u64 = 1024;
+
+/// The tip percentiles returned by `reth_feeStats` if none are requested.
+const DEFAULT_TIP_PERCENTILES: [f64; 5] = [10., 25., 50., 75., 90.];
+
+/// The maximum number of blocks that can be queried with `reth_getProposerRevenue`.
+const MAX_PROPOSER_REVENUE_BLOCK_RANGE: u64 = 1024;
+
+/// The maximum number of reorgs that can be queried with `reth_getReorgs`.
+const MAX_REORGS: usize = 1024;
+
+/// `reth` API implementation.
+///
+/// This type provides the functionality for handling `reth` prototype RPC requests.
+///
+/// The blob sidecars returned by `reth_getBlobSidecars` are read from the transaction pool, which
+/// is only set by [`RethApi::with_pool`].
+pub struct RethApi<Provider, Pool = NoopTransactionPool> {
+    inner: Arc<RethApiInner<Provider, Pool>>,
+}
+
+// === impl RethApi ===
+
+impl<Provider> RethApi<Provider> {
+    /// Create a new instance of the [`RethApi`]
+    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self::with_pool(provider, NoopTransactionPool::default(), task_spawner)
+    }
+}
+
+impl<Provider, Pool> RethApi<Provider, Pool> {
+    /// The provider that can interact with the chain.
+    pub fn provider(&self) -> &Provider {
+        &self.inner.provider
+    }
+
+    /// The transaction pool that retains the blob sidecars.
+    pub fn pool(&self) -> &Pool {
+        &self.inner.pool
+    }
+
+    /// Create a new instance of the [`RethApi`] that returns the blob sidecars retained by the
+    /// given transaction pool.
+    pub fn with_pool(provider: Provider, pool: Pool, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        let inner = Arc::new(RethApiInner { provider, pool, task_spawner });
+        Self { inner }
+    }
+}
+
+impl<Provider, Pool> RethApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + ChangeSetReader
+        + StateProviderFactory
+        + InternalTransfersProvider
+        + PruneCheckpointReader
+        + ReorgJournalReader
+        + StaticFileProviderFactory
+        + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = EthResult<R>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| EthApiError::InternalEthError)?
+    }
+
+    /// Returns a map of addresses to changed account balanced for a particular block.
+    pub async fn balance_changes_in_block(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<HashMap<Address, U256>> {
+        self.on_blocking_task(|this| async move { this.try_balance_changes_in_block(block_id) })
+            .await
+    }
+
+    fn try_balance_changes_in_block(&self, block_id: BlockId) -> EthResult<HashMap<Address, U256>> {
+        let Some(block_number) = self.provider().block_number_for_id(block_id)? else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+
+        let state = self.provider().state_by_block_id(block_id)?;
+        let accounts_before = self.provider().account_block_changeset(block_number)?;
+        let addresses = accounts_before.iter().map(|account| account.address).collect::<Vec<_>>();
+        let accounts = state.basic_accounts(&addresses)?;
+        let hash_map = accounts_before
+            .iter()
+            .zip(accounts)
+            .filter_map(|(account_before, account)| {
+                let current_balance = account.map(|account| account.balance);
+                let prev_balance = account_before.info.map(|info| info.balance);
+                (current_balance != prev_balance)
+                    .then(|| (account_before.address, current_balance.unwrap_or_default()))
+            })
+            .collect();
+        Ok(hash_map)
+    }
+
+    /// Returns the fee statistics of all blocks in the given range.
+    pub async fn fee_stats(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        tip_percentiles: Option<Vec<f64>>,
+    ) -> EthResult<Vec<BlockFeeStats>> {
+        self.on_blocking_task(|this| async move {
+            this.try_fee_stats(from_block, to_block, tip_percentiles)
+        })
+        .await
+    }
+
+    fn try_fee_stats(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        tip_percentiles: Option<Vec<f64>>,
+    ) -> EthResult<Vec<BlockFeeStats>> {
+        let percentiles = tip_percentiles.unwrap_or_else(|| DEFAULT_TIP_PERCENTILES.to_vec());
+        if percentiles.windows(2).any(|w| w[0] > w[1]) ||
+            percentiles.iter().any(|p| !(0. ..=100.).contains(p))
+        {
+            return Err(EthApiError::InvalidRewardPercentiles)
+        }
+
+        let (from, to) = self.block_range(from_block, to_block, MAX_FEE_STATS_BLOCK_RANGE)?;
+        self.provider()
+            .sealed_headers_range(from..=to)?
+            .into_iter()
+            .map(|header| {
+                let transactions = self
+                    .provider()
+                    .transactions_by_block(header.number.into())?
+                    .ok_or(EthApiError::UnknownBlockNumber)?;
+                let receipts = self
+                    .provider()
+                    .receipts_by_block(header.number.into())?
+                    .ok_or(EthApiError::UnknownBlockNumber)?;
+                let blob_params =
+                    self.provider().chain_spec().blob_params_at_timestamp(header.timestamp);
+                block_fee_stats(&header, &transactions, &receipts, &percentiles, blob_params)
+            })
+            .collect()
+    }
+
+    /// Returns the value delivered to the proposers of all blocks in the given range.
+    pub async fn proposer_revenue(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<BlockProposerRevenue>> {
+        self.on_blocking_task(|this| async move { this.try_proposer_revenue(from_block, to_block) })
+            .await
+    }
+
+    fn try_proposer_revenue(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<BlockProposerRevenue>> {
+        let (from, to) =
+            self.block_range(from_block, to_block, MAX_PROPOSER_REVENUE_BLOCK_RANGE)?;
+        (from..=to)
+            .map(|number| {
+                let block = self
+                    .provider()
+                    .sealed_block_with_senders(number.into(), TransactionVariant::WithHash)?
+                    .ok_or(EthApiError::UnknownBlockNumber)?;
+                let fee_recipient = block.beneficiary;
+
+                let balance_after = self
+                    .provider()
+                    .history_by_block_number(number)?
+                    .account_balance(fee_recipient)?
+                    .unwrap_or_default();
+                // the balance is unchanged if the fee recipient is not in the changeset
+                let balance_before = self
+                    .provider()
+                    .account_block_changeset(number)?
+                    .into_iter()
+                    .find(|account| account.address == fee_recipient)
+                    .map_or(balance_after, |account| {
+                        account.info.map(|info| info.balance).unwrap_or_default()
+                    });
+
+                let hash = block.hash();
+                let revenue = ProposerRevenue::new(&block.unseal(), balance_before, balance_after);
+                Ok(BlockProposerRevenue {
+                    number,
+                    hash,
+                    fee_recipient,
+                    fee_recipient_balance_before: balance_before,
+                    fee_recipient_balance_after: balance_after,
+                    payment: revenue.payment.map(|payment| ProposerPayment {
+                        transaction_hash: payment.transaction_hash,
+                        transaction_index: payment.transaction_index,
+                        recipient: payment.recipient,
+                        value: payment.value,
+                    }),
+                    proposer_value: revenue.proposer_value(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the earliest blocks for which the data of each class is available.
+    pub async fn data_availability(&self) -> EthResult<DataAvailability> {
+        self.on_blocking_task(|this| async move { this.try_data_availability() }).await
+    }
+
+    fn try_data_availability(&self) -> EthResult<DataAvailability> {
+        let checkpoint = |segment| self.provider().get_prune_checkpoint(segment);
+        let static_files = self.provider().static_file_provider();
+        let static_file_block = |segment| static_files.get_highest_static_file_block(segment);
+
+        Ok(DataAvailability {
+            latest_block: self.provider().best_block_number()?,
+            receipts: earliest_available_block(
+                checkpoint(PruneSegment::Receipts)?,
+                static_file_block(StaticFileSegment::Receipts),
+            ),
+            history: earliest_available_block(checkpoint(PruneSegment::AccountHistory)?, None)
+                .max(earliest_available_block(checkpoint(PruneSegment::StorageHistory)?, None)),
+            transaction_lookup: earliest_available_block(
+                checkpoint(PruneSegment::TransactionLookup)?,
+                None,
+            ),
+            bodies: earliest_available_block(
+                checkpoint(PruneSegment::Transactions)?,
+                static_file_block(StaticFileSegment::Transactions),
+            ),
+        })
+    }
+
+    /// Returns up to `count` of the most recent reorgs observed by the node.
+    pub async fn reorgs(&self, count: Option<usize>) -> EthResult<Vec<Reorg>> {
+        self.on_blocking_task(|this| async move { this.try_reorgs(count) }).await
+    }
+
+    fn try_reorgs(&self, count: Option<usize>) -> EthResult<Vec<Reorg>> {
+        let count = count.unwrap_or(MAX_REORGS);
+        if count > MAX_REORGS {
+            return Err(EthApiError::InvalidParams(format!(
+                "count exceeds the maximum of {MAX_REORGS} reorgs"
+            )))
+        }
+        Ok(self
+            .provider()
+            .latest_reorgs(count)?
+            .into_iter()
+            .map(|(id, record)| reorg(id, record))
+            .collect())
+    }
+
+    /// Resolves the inclusive range `[from_block, to_block]` of at most `max_range` blocks.
+    fn block_range(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        max_range: u64,
+    ) -> EthResult<(u64, u64)> {
+        let resolve = |block: BlockNumberOrTag| {
+            self.provider().convert_block_number(block)?.ok_or(EthApiError::UnknownBlockNumber)
+        };
+        let (from, to) = (resolve(from_block)?, resolve(to_block)?);
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to - from >= max_range {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the maximum of {max_range} blocks"
+            )))
+        }
+        Ok((from, to))
+    }
+
+    /// Returns the blob sidecars of all blob transactions in the given block.
+    pub async fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<TransactionBlobSidecar>>> {
+        self.on_blocking_task(|this| async move { this.try_blob_sidecars(block_id) }).await
+    }
+
+    fn try_blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<TransactionBlobSidecar>>> {
+        let Some(block) = self.provider().block_by_id(block_id)? else { return Ok(None) };
+
+        let blob_transactions = block
+            .body
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tx)| {
+                tx.blob_versioned_hashes().map(|hashes| (index as u64, tx.hash(), hashes))
+            })
+            .collect::<Vec<_>>();
+        if blob_transactions.is_empty() {
+            return Ok(Some(Vec::new()))
+        }
+
+        let mut sidecars: HashMap<_, _> = self
+            .pool()
+            .get_all_blobs(blob_transactions.iter().map(|(_, hash, _)| *hash).collect())
+            .map_err(RethError::other)?
+            .into_iter()
+            .collect();
+
+        Ok(Some(
+            blob_transactions
+                .into_iter()
+                .map(|(transaction_index, transaction_hash, blob_versioned_hashes)| {
+                    TransactionBlobSidecar {
+                        transaction_hash,
+                        transaction_index,
+                        blob_versioned_hashes,
+                        sidecar: sidecars.remove(&transaction_hash),
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    /// Returns the internal transfers of the transactions of a block or of a single transaction.
+    pub async fn internal_transfers(
+        &self,
+        target: InternalTransfersTarget,
+    ) -> EthResult<Option<Vec<TransactionInternalTransfers>>> {
+        self.on_blocking_task(|this| async move { this.try_internal_transfers(target) }).await
+    }
+
+    fn try_internal_transfers(
+        &self,
+        target: InternalTransfersTarget,
+    ) -> EthResult<Option<Vec<TransactionInternalTransfers>>> {
+        let (block_number, transaction_hash) = match target {
+            InternalTransfersTarget::Block(block_id) => {
+                let Some(number) = self.provider().block_number_for_id(block_id)? else {
+                    return Ok(None)
+                };
+                (number, None)
+            }
+            InternalTransfersTarget::Transaction { transaction_hash } => {
+                let Some((_, meta)) =
+                    self.provider().transaction_by_hash_with_meta(transaction_hash)?
+                else {
+                    return Ok(None)
+                };
+                (meta.block_number, Some(transaction_hash))
+            }
+        };
+
+        let Some(transactions) = self.provider().transactions_by_block(block_number.into())? else {
+            return Ok(None)
+        };
+        let internal_transfers =
+            match self.provider().internal_transfers_by_block(block_number.into()) {
+                Ok(Some(internal_transfers)) => internal_transfers,
+                Ok(None) => return Ok(None),
+                Err(
+                    err @ (ProviderError::InternalTransfersNotIndexed(_) |
+                    ProviderError::StateAtBlockPruned(_)),
+                ) => return Err(EthApiError::InvalidParams(err.to_string())),
+                Err(err) => return Err(err.into()),
+            };
+
+        Ok(Some(
+            transactions
+                .iter()
+                .zip(internal_transfers)
+                .enumerate()
+                .map(|(index, (tx, transfers))| TransactionInternalTransfers {
+                    block_number,
+                    transaction_hash: tx.hash(),
+                    transaction_index: index as u64,
+                    transfers: transfers
+                        .into_iter()
+                        .map(|transfer| InternalTransfer {
+                            from: transfer.from,
+                            to: transfer.to,
+                            value: transfer.value,
+                        })
+                        .collect(),
+                })
+                .filter(|transfers| {
+                    transaction_hash.map_or(true, |hash| transfers.transaction_hash == hash)
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// Converts the recorded reorg with the given sequence number into its RPC representation.
+const fn reorg(id: u64, record: ReorgRecord) -> Reorg {
+    Reorg {
+        id,
+        observed_at: record.observed_at,
+        fork_block_number: record.fork_block_number,
+        fork_block_hash: record.fork_block_hash,
+        old_tip_hash: record.old_tip_hash,
+        new_tip_hash: record.new_tip_hash,
+        discarded_blocks: record.discarded_blocks,
+        added_blocks: record.added_blocks,
+        discarded_transactions: record.discarded_transactions,
+        reincluded_transactions: record.reincluded_transactions,
+        resolution_duration: record.resolution_duration,
+    }
+}
+
+/// Returns the earliest block whose data is available, given the prune checkpoint of the segment
+/// and the highest block of the segment in static files.
+///
+/// Data that was moved to static files is pruned from the database, but is still available.
+fn earliest_available_block(
+    checkpoint: Option<PruneCheckpoint>,
+    highest_static_file_block: Option<BlockNumber>,
+) -> BlockNumber {
+    match checkpoint.and_then(|checkpoint| checkpoint.block_number) {
+        Some(pruned) if highest_static_file_block.map_or(true, |highest| pruned > highest) => {
+            pruned + 1
+        }
+        _ => 0,
+    }
+}
+
+/// Computes the fee statistics of a block from its transactions and receipts.
+fn block_fee_stats(
+    header: &SealedHeader,
+    transactions: &[TransactionSigned],
+    receipts: &[Receipt],
+    percentiles: &[f64],
+    blob_params: BlobParams,
+) -> EthResult<BlockFeeStats> {
+    let tip_percentiles = calculate_reward_percentiles_for_block(
+        percentiles,
+        header.gas_used,
+        header.base_fee_per_gas.unwrap_or_default(),
+        transactions,
+        receipts,
+    )?;
+
+    let mut previous_gas = 0;
+    let builder_revenue =
+        transactions.iter().zip(receipts).fold(U256::ZERO, |revenue, (tx, receipt)| {
+            let gas_used = receipt.cumulative_gas_used - previous_gas;
+            previous_gas = receipt.cumulative_gas_used;
+            let tip = tx.effective_tip_per_gas(header.base_fee_per_gas).unwrap_or_default();
+            revenue + U256::from(tip) * U256::from(gas_used)
+        });
+
+    let blob_base_fee = header.blob_fee_with_params(blob_params);
+    Ok(BlockFeeStats {
+        number: header.number,
+        hash: header.hash(),
+        gas_used: header.gas_used,
+        gas_limit: header.gas_limit,
+        transaction_count: transactions.len() as u64,
+        base_fee_per_gas: header.base_fee_per_gas,
+        burnt_fees: U256::from(header.base_fee_per_gas.unwrap_or_default()) *
+            U256::from(header.gas_used),
+        tip_percentiles,
+        builder_revenue,
+        blob_gas_used: header.blob_gas_used,
+        excess_blob_gas: header.excess_blob_gas,
+        blob_base_fee,
+        blob_fees: header
+            .blob_gas_used
+            .zip(blob_base_fee)
+            .map(|(gas, fee)| U256::from(gas) * U256::from(fee)),
+    })
+}
+
+#[async_trait]
+impl<Provider, Pool> RethApiServer for RethApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + ChangeSetReader
+        + StateProviderFactory
+        + InternalTransfersProvider
+        + PruneCheckpointReader
+        + ReorgJournalReader
+        + StaticFileProviderFactory
+        + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `reth_getBalanceChangesInBlock`
+    async fn reth_get_balance_changes_in_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<HashMap<Address, U256>> {
+        Ok(Self::balance_changes_in_block(self, block_id).await?)
+    }
+
+    /// Handler for `reth_feeStats`
+    async fn reth_fee_stats(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        tip_percentiles: Option<Vec<f64>>,
+    ) -> RpcResult<Vec<BlockFeeStats>> {
+        Ok(Self::fee_stats(self, from_block, to_block, tip_percentiles).await?)
+    }
+
+    /// Handler for `reth_getBlobSidecars`
+    async fn reth_get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<TransactionBlobSidecar>>> {
+        Ok(Self::blob_sidecars(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getInternalTransfers`
+    async fn reth_get_internal_transfers(
+        &self,
+        block_or_tx: InternalTransfersTarget,
+    ) -> RpcResult<Option<Vec<TransactionInternalTransfers>>> {
+        Ok(Self::internal_transfers(self, block_or_tx).await?)
+    }
+
+    /// Handler for `reth_getProposerRevenue`
+    async fn reth_get_proposer_revenue(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<BlockProposerRevenue>> {
+        Ok(Self::proposer_revenue(self, from_block, to_block).await?)
+    }
+
+    /// Handler for `reth_dataAvailability`
+    async fn reth_data_availability(&self) -> RpcResult<DataAvailability> {
+        Ok(Self::data_availability(self).await?)
+    }
+
+    /// Handler for `reth_getReorgs`
+    async fn reth_get_reorgs(&self, count: Option<usize>) -> RpcResult<Vec<Reorg>> {
+        Ok(Self::reorgs(self, count).await?)
+    }
+}
+
+impl<Provider, Pool> std::fmt::Debug for RethApi<Provider, Pool> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider, Pool> Clone for RethApi<Provider, Pool> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct RethApiInner<Provider, Pool> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The transaction pool that retains the blob sidecars.
+    pool: Pool,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        BlobTransactionSidecar, Block, Header, Signature, Transaction, TxEip1559, TxEip4844, B256,
+    };
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_prune_types::PruneMode;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::{
+        blobstore::InMemoryBlobStore,
+        test_utils::{TestPool, TestPoolBuilder},
+        BlobStore,
+    };
+
+    fn transaction(max_priority_fee_per_gas: u128) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas: 100,
+                max_priority_fee_per_gas,
+                ..Default::default()
+            }),
+            Signature::default(),
+        )
+    }
+
+    #[test]
+    fn computes_block_fee_stats() {
+        let header = Header {
+            number: 1,
+            gas_used: 100_000,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(10),
+            ..Default::default()
+        }
+        .seal_slow();
+        let transactions = vec![transaction(1), transaction(5)];
+        let receipts = vec![
+            Receipt { cumulative_gas_used: 30_000, ..Default::default() },
+            Receipt { cumulative_gas_used: 100_000, ..Default::default() },
+        ];
+
+        let stats = block_fee_stats(
+            &header,
+            &transactions,
+            &receipts,
+            &[0., 50., 100.],
+            BlobParams::cancun(),
+        )
+        .unwrap();
+        assert_eq!(stats.transaction_count, 2);
+        assert_eq!(stats.tip_percentiles, vec![1, 5, 5]);
+        assert_eq!(stats.burnt_fees, U256::from(1_000_000));
+        assert_eq!(stats.builder_revenue, U256::from(30_000 + 5 * 70_000));
+        assert_eq!(stats.blob_fees, None);
+    }
+
+    #[test]
+    fn computes_blob_fee_stats_with_blob_params() {
+        let header = Header {
+            excess_blob_gas: Some(10_000_000),
+            blob_gas_used: Some(262_144),
+            ..Default::default()
+        }
+        .seal_slow();
+
+        // a chain with a slower blob base fee update than mainnet
+        let blob_params = BlobParams::new(6, 9, 5_007_716);
+        let stats = block_fee_stats(&header, &[], &[], &[], blob_params).unwrap();
+        let blob_base_fee = blob_params.calc_blob_fee(10_000_000);
+        assert_ne!(Some(blob_base_fee), header.blob_fee());
+        assert_eq!(stats.blob_base_fee, Some(blob_base_fee));
+        assert_eq!(stats.blob_fees, Some(U256::from(262_144) * U256::from(blob_base_fee)));
+    }
+
+    #[test]
+    fn earliest_available_block_of_segment() {
+        let checkpoint = |block_number| PruneCheckpoint {
+            block_number,
+            tx_number: None,
+            prune_mode: PruneMode::Full,
+        };
+
+        assert_eq!(earliest_available_block(None, None), 0);
+        assert_eq!(earliest_available_block(Some(checkpoint(None)), None), 0);
+        assert_eq!(earliest_available_block(Some(checkpoint(Some(100))), None), 101);
+        // the pruned data was moved to static files
+        assert_eq!(earliest_available_block(Some(checkpoint(Some(100))), Some(100)), 0);
+        assert_eq!(earliest_available_block(Some(checkpoint(Some(100))), Some(50)), 101);
+    }
+
+    #[tokio::test]
+    async fn returns_blob_sidecars() {
+        let blob_transaction = |hash: u8| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::Eip4844(TxEip4844 {
+                    blob_versioned_hashes: vec![B256::with_last_byte(hash)],
+                    ..Default::default()
+                }),
+                Signature::default(),
+            )
+        };
+        let (retained, pruned) = (blob_transaction(1), blob_transaction(2));
+        let block = Block {
+            body: vec![transaction(1), retained.clone(), pruned.clone()],
+            ..Default::default()
+        };
+        let block_hash = B256::with_last_byte(1);
+
+        let provider = MockEthProvider::default();
+        provider.add_block(block_hash, block);
+        let blob_store = InMemoryBlobStore::default();
+        blob_store.insert(retained.hash(), BlobTransactionSidecar::default()).unwrap();
+        let pool: TestPool = TestPoolBuilder::default().with_blob_store(blob_store).into();
+        let api = RethApi::with_pool(provider, pool, Box::<TokioTaskExecutor>::default());
+
+        let sidecars = api.blob_sidecars(block_hash.into()).await.unwrap().unwrap();
+        assert_eq!(sidecars.len(), 2);
+        assert_eq!(sidecars[0].transaction_hash, retained.hash());
+        assert_eq!(sidecars[0].transaction_index, 1);
+        assert_eq!(sidecars[0].sidecar, Some(BlobTransactionSidecar::default()));
+        assert_eq!(sidecars[1].blob_versioned_hashes, vec![B256::with_last_byte(2)]);
+        assert_eq!(sidecars[1].sidecar, None);
+
+        assert_eq!(api.blob_sidecars(B256::ZERO.into()).await.unwrap(), None);
+    }
+}
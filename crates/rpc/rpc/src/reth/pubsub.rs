@@ -0,0 +1,465 @@
+//! `reth_` `PubSub` RPC handler implementation
+
+use crate::eth::pubsub::pipe_from_stream;
+use futures::StreamExt;
+use jsonrpsee::PendingSubscriptionSink;
+use reth_primitives::{Address, BlockNumber, B256, U256};
+use reth_provider::{
+    CanonStateNotification, CanonStateSubscriptions, Chain, StageCheckpointSubscriptions,
+    StageCheckpointUpdate,
+};
+use reth_revm::{db::states::reverts::AccountInfoRevert, primitives::AccountInfo};
+use reth_rpc_api::RethPubSubApiServer;
+use reth_rpc_server_types::result::invalid_params_rpc_err;
+use reth_rpc_types::reth::{AccountChanges, AccountState, StageCheckpointChange, StorageChanges};
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+use tokio_stream::wrappers::BroadcastStream;
+
+/// The maximum number of storage slots a single `reth_subscribeStorage` subscription can watch.
+const MAX_WATCHED_STORAGE_SLOTS: usize = 1024;
+
+/// The maximum number of accounts a single `reth_subscribeAccounts` subscription can watch.
+const MAX_WATCHED_ACCOUNTS: usize = 1024;
+
+/// `reth` pubsub RPC implementation.
+///
+/// This handles the `reth_subscribe*` RPC calls.
+#[derive(Clone)]
+pub struct RethPubSub<Events> {
+    /// A type that allows to create new event subscriptions.
+    chain_events: Events,
+    /// A type that allows to subscribe to the stage checkpoints, if they are available.
+    stage_checkpoints: Option<Arc<dyn StageCheckpointSubscriptions>>,
+    /// The type that's used to spawn subscription tasks.
+    subscription_task_spawner: Box<dyn TaskSpawner>,
+}
+
+// === impl RethPubSub ===
+
+impl<Events> RethPubSub<Events> {
+    /// Creates a new, shareable instance.
+    ///
+    /// Subscription tasks are spawned via [`tokio::task::spawn`]
+    pub fn new(chain_events: Events) -> Self {
+        Self::with_spawner(chain_events, Box::<TokioTaskExecutor>::default())
+    }
+
+    /// Creates a new, shareable instance.
+    pub fn with_spawner(
+        chain_events: Events,
+        subscription_task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        Self { chain_events, stage_checkpoints: None, subscription_task_spawner }
+    }
+
+    /// Enables `reth_subscribeStageCheckpoints`, which subscribes to the stage checkpoints of the
+    /// given provider.
+    pub fn with_stage_checkpoints(
+        mut self,
+        stage_checkpoints: impl StageCheckpointSubscriptions + 'static,
+    ) -> Self {
+        self.stage_checkpoints = Some(Arc::new(stage_checkpoints));
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl<Events> RethPubSubApiServer for RethPubSub<Events>
+where
+    Events: CanonStateSubscriptions + Clone + 'static,
+{
+    /// Handler for `reth_subscribeStorage`
+    async fn subscribe_storage(
+        &self,
+        pending: PendingSubscriptionSink,
+        address: Address,
+        slots: Vec<B256>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        if slots.is_empty() || slots.len() > MAX_WATCHED_STORAGE_SLOTS {
+            pending
+                .reject(invalid_params_rpc_err(format!(
+                    "expected between 1 and {MAX_WATCHED_STORAGE_SLOTS} storage slots"
+                )))
+                .await;
+            return Ok(())
+        }
+
+        let sink = pending.accept().await?;
+        let slots = slots.into_iter().map(U256::from).collect::<Vec<_>>();
+        let stream = self.chain_events.canonical_state_stream().flat_map(move |notification| {
+            futures::stream::iter(storage_changes(&notification, address, &slots))
+        });
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        }));
+
+        Ok(())
+    }
+
+    /// Handler for `reth_subscribeAccounts`
+    async fn subscribe_accounts(
+        &self,
+        pending: PendingSubscriptionSink,
+        addresses: Vec<Address>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        if addresses.is_empty() || addresses.len() > MAX_WATCHED_ACCOUNTS {
+            pending
+                .reject(invalid_params_rpc_err(format!(
+                    "expected between 1 and {MAX_WATCHED_ACCOUNTS} addresses"
+                )))
+                .await;
+            return Ok(())
+        }
+
+        let sink = pending.accept().await?;
+        let addresses = addresses.into_iter().collect::<HashSet<_>>();
+        let stream = self.chain_events.canonical_state_stream().flat_map(move |notification| {
+            futures::stream::iter(account_changes(&notification, &addresses))
+        });
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        }));
+
+        Ok(())
+    }
+
+    /// Handler for `reth_subscribeStageCheckpoints`
+    async fn subscribe_stage_checkpoints(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let Some(stage_checkpoints) = &self.stage_checkpoints else {
+            pending.reject(invalid_params_rpc_err("stage checkpoints are not available")).await;
+            return Ok(())
+        };
+
+        let sink = pending.accept().await?;
+        // updates are skipped if the subscriber lags behind, the next update of a stage supersedes
+        // its previous one
+        let stream = BroadcastStream::new(stage_checkpoints.subscribe_to_stage_checkpoints())
+            .filter_map(|update| futures::future::ready(update.ok().map(stage_checkpoint_change)));
+        self.subscription_task_spawner.spawn(Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        }));
+
+        Ok(())
+    }
+}
+
+impl<Events> std::fmt::Debug for RethPubSub<Events> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RethPubSub").finish_non_exhaustive()
+    }
+}
+
+/// Converts a committed stage checkpoint to its RPC representation.
+fn stage_checkpoint_change(update: StageCheckpointUpdate) -> StageCheckpointChange {
+    let entities = update.checkpoint.entities();
+    StageCheckpointChange {
+        stage: update.stage_id.to_string(),
+        block_number: update.checkpoint.block_number,
+        entities_processed: entities.map(|entities| entities.processed),
+        entities_total: entities.map(|entities| entities.total),
+    }
+}
+
+/// Returns the changes of the watched slots of the account for a canonical state notification.
+///
+/// If the notification is a reorg, this starts with the values the slots had before the reverted
+/// blocks changed them.
+fn storage_changes(
+    notification: &CanonStateNotification,
+    address: Address,
+    slots: &[U256],
+) -> Vec<StorageChanges> {
+    let mut changes = Vec::new();
+
+    if let Some(reverted) = notification.reverted() {
+        let (restored, _) = chain_storage_changes(&reverted, address, slots);
+        if !restored.is_empty() {
+            let fork = reverted.fork_block();
+            changes.push(StorageChanges {
+                block_number: fork.number,
+                block_hash: fork.hash,
+                removed: true,
+                address,
+                storage: restored,
+            });
+        }
+    }
+
+    let committed = notification.committed();
+    let (_, blocks) = chain_storage_changes(&committed, address, slots);
+    changes.extend(blocks.into_iter().filter_map(|(number, storage)| {
+        let block = committed.blocks().get(&number)?;
+        Some(StorageChanges {
+            block_number: number,
+            block_hash: block.hash(),
+            removed: false,
+            address,
+            storage,
+        })
+    }));
+
+    changes
+}
+
+/// Computes the values of the watched slots of the account from the storage reverts of the chain.
+///
+/// Returns the values the changed slots had before the first block of the chain, and the values
+/// after every block that changed any of the slots, in ascending block order.
+fn chain_storage_changes(
+    chain: &Chain,
+    address: Address,
+    slots: &[U256],
+) -> (BTreeMap<B256, B256>, Vec<(BlockNumber, BTreeMap<B256, B256>)>) {
+    let outcome = chain.execution_outcome();
+    let first_block = outcome.first_block();
+
+    // walk the blocks backwards, starting with the values after the last block, so the previous
+    // values recorded in the reverts of a block are the values after the block before it
+    let mut values = HashMap::new();
+    let mut blocks = Vec::new();
+    for (idx, block_reverts) in outcome.state().reverts.iter().enumerate().rev() {
+        let Some(revert) = block_reverts
+            .iter()
+            .find_map(|(revert_address, revert)| (*revert_address == address).then_some(revert))
+        else {
+            continue
+        };
+
+        let mut storage = BTreeMap::new();
+        for slot in slots {
+            let previous = match revert.storage.get(slot) {
+                Some(previous) => previous.to_previous_value(),
+                // the storage of a destroyed account is only recorded if it was loaded, the
+                // storage of accounts that are created and destroyed in the same transaction is
+                // empty
+                None if revert.wipe_storage => U256::ZERO,
+                None => continue,
+            };
+            let value = values
+                .insert(*slot, previous)
+                .or_else(|| outcome.storage(&address, *slot))
+                .unwrap_or_default();
+            storage.insert(B256::from(*slot), B256::from(value));
+        }
+
+        if !storage.is_empty() {
+            blocks.push((first_block + idx as u64, storage));
+        }
+    }
+    blocks.reverse();
+
+    let restored =
+        values.into_iter().map(|(slot, value)| (B256::from(slot), B256::from(value))).collect();
+    (restored, blocks)
+}
+
+/// Returns the changes of the watched accounts for a canonical state notification.
+///
+/// If the notification is a reorg, this starts with the states the accounts had before the
+/// reverted blocks changed them.
+fn account_changes(
+    notification: &CanonStateNotification,
+    addresses: &HashSet<Address>,
+) -> Vec<AccountChanges> {
+    let mut changes = Vec::new();
+
+    if let Some(reverted) = notification.reverted() {
+        let (restored, _) = chain_account_changes(&reverted, addresses);
+        if !restored.is_empty() {
+            let fork = reverted.fork_block();
+            changes.push(AccountChanges {
+                block_number: fork.number,
+                block_hash: fork.hash,
+                removed: true,
+                accounts: restored,
+            });
+        }
+    }
+
+    let committed = notification.committed();
+    let (_, blocks) = chain_account_changes(&committed, addresses);
+    changes.extend(blocks.into_iter().filter_map(|(number, accounts)| {
+        let block = committed.blocks().get(&number)?;
+        Some(AccountChanges {
+            block_number: number,
+            block_hash: block.hash(),
+            removed: false,
+            accounts,
+        })
+    }));
+
+    changes
+}
+
+/// Computes the states of the watched accounts from the account reverts of the chain.
+///
+/// Returns the states the changed accounts had before the first block of the chain, and the states
+/// after every block that changed any of the accounts, in ascending block order.
+#[allow(clippy::type_complexity)]
+fn chain_account_changes(
+    chain: &Chain,
+    addresses: &HashSet<Address>,
+) -> (
+    BTreeMap<Address, Option<AccountState>>,
+    Vec<(BlockNumber, BTreeMap<Address, Option<AccountState>>)>,
+) {
+    let outcome = chain.execution_outcome();
+    let first_block = outcome.first_block();
+
+    // walk the blocks backwards, see `chain_storage_changes`
+    let mut states = HashMap::new();
+    let mut blocks = Vec::new();
+    for (idx, block_reverts) in outcome.state().reverts.iter().enumerate().rev() {
+        let mut accounts = BTreeMap::new();
+        for (address, revert) in block_reverts {
+            if !addresses.contains(address) {
+                continue
+            }
+            let previous = match &revert.account {
+                // only the storage of the account changed
+                AccountInfoRevert::DoNothing => continue,
+                AccountInfoRevert::DeleteIt => None,
+                AccountInfoRevert::RevertTo(info) => Some(account_state(info)),
+            };
+            let state = states.insert(*address, previous).unwrap_or_else(|| {
+                outcome
+                    .state()
+                    .account(address)
+                    .and_then(|account| account.info.as_ref())
+                    .map(account_state)
+            });
+            // accounts can be touched without changing their state
+            if state != previous {
+                accounts.insert(*address, state);
+            }
+        }
+
+        if !accounts.is_empty() {
+            blocks.push((first_block + idx as u64, accounts));
+        }
+    }
+    blocks.reverse();
+
+    (states.into_iter().collect(), blocks)
+}
+
+/// Returns the balance, nonce and code hash of the account.
+const fn account_state(info: &AccountInfo) -> AccountState {
+    AccountState { balance: info.balance, nonce: info.nonce, code_hash: info.code_hash }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Block, Header, Receipts, SealedBlockWithSenders};
+    use reth_provider::ExecutionOutcome;
+    use reth_revm::db::{
+        states::{reverts::Reverts, AccountRevert, RevertToSlot},
+        BundleState,
+    };
+
+    fn block(number: BlockNumber) -> SealedBlockWithSenders {
+        let header = Header { number, ..Default::default() };
+        SealedBlockWithSenders::new(Block { header, ..Default::default() }.seal_slow(), vec![])
+            .unwrap()
+    }
+
+    #[test]
+    fn computes_storage_changes_per_block() {
+        let address = Address::with_last_byte(1);
+        let (slot, other_slot) = (U256::from(1), U256::from(2));
+        let revert = |storage: Vec<(U256, RevertToSlot)>| {
+            vec![(
+                address,
+                AccountRevert {
+                    account: AccountInfoRevert::DoNothing,
+                    storage: storage.into_iter().collect(),
+                    previous_status: Default::default(),
+                    wipe_storage: false,
+                },
+            )]
+        };
+
+        // block 1 sets the slot from 0 to 1, block 2 doesn't touch it, block 3 sets it to 3
+        let mut bundle = BundleState::builder(1..=3)
+            .state_storage(address, [(slot, (U256::ZERO, U256::from(3)))].into_iter().collect())
+            .build();
+        bundle.reverts = Reverts::new(vec![
+            revert(vec![(slot, RevertToSlot::Some(U256::ZERO))]),
+            revert(vec![(other_slot, RevertToSlot::Some(U256::ZERO))]),
+            revert(vec![(slot, RevertToSlot::Some(U256::from(1)))]),
+        ]);
+        let outcome = ExecutionOutcome::new(bundle, Receipts::default(), 1, vec![]);
+        let chain = Chain::new([block(1), block(2), block(3)], outcome, None);
+
+        let (restored, blocks) = chain_storage_changes(&chain, address, &[slot]);
+        assert_eq!(restored, BTreeMap::from([(B256::from(slot), B256::ZERO)]));
+        assert_eq!(
+            blocks,
+            vec![
+                (1, BTreeMap::from([(B256::from(slot), B256::from(U256::from(1)))])),
+                (3, BTreeMap::from([(B256::from(slot), B256::from(U256::from(3)))])),
+            ]
+        );
+
+        // a reorg restores the values from before the reverted blocks
+        let notification =
+            CanonStateNotification::Reorg { old: chain.clone().into(), new: chain.into() };
+        let changes = storage_changes(&notification, address, &[slot]);
+        assert_eq!(changes.len(), 3);
+        assert!(changes[0].removed);
+        assert_eq!(changes[0].block_number, 0);
+        assert_eq!(changes[2].block_number, 3);
+    }
+
+    #[test]
+    fn computes_account_changes_per_block() {
+        let address = Address::with_last_byte(1);
+        let info =
+            |balance: u64| AccountInfo { balance: U256::from(balance), ..Default::default() };
+        let revert = |account: AccountInfoRevert| {
+            vec![(
+                address,
+                AccountRevert {
+                    account,
+                    storage: Default::default(),
+                    previous_status: Default::default(),
+                    wipe_storage: false,
+                },
+            )]
+        };
+
+        // block 1 creates the account, block 2 only changes its storage, block 3 credits it
+        let mut bundle =
+            BundleState::builder(1..=3).state_present_account_info(address, info(2)).build();
+        bundle.reverts = Reverts::new(vec![
+            revert(AccountInfoRevert::DeleteIt),
+            revert(AccountInfoRevert::DoNothing),
+            revert(AccountInfoRevert::RevertTo(info(1))),
+        ]);
+        let outcome = ExecutionOutcome::new(bundle, Receipts::default(), 1, vec![]);
+        let chain = Chain::new([block(1), block(2), block(3)], outcome, None);
+
+        let addresses = HashSet::from([address]);
+        let (restored, blocks) = chain_account_changes(&chain, &addresses);
+        assert_eq!(restored, BTreeMap::from([(address, None)]));
+        assert_eq!(
+            blocks,
+            vec![
+                (1, BTreeMap::from([(address, Some(account_state(&info(1))))])),
+                (3, BTreeMap::from([(address, Some(account_state(&info(2))))])),
+            ]
+        );
+
+        let (restored, blocks) = chain_account_changes(&chain, &HashSet::new());
+        assert!(restored.is_empty());
+        assert!(blocks.is_empty());
+    }
+}
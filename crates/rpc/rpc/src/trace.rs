@@ -86,7 +86,7 @@ where
         let at = trace_request.block_id.unwrap_or_default();
         let config = TracingInspectorConfig::from_parity_config(&trace_request.trace_types);
         let overrides =
-            EvmOverrides::new(trace_request.state_overrides, trace_request.block_overrides);
+            EvmOverrides::new(trace_request.state_overrides, trace_request.block_overrides).into();
         let mut inspector = TracingInspector::new(config);
         let this = self.clone();
         self.eth_api()
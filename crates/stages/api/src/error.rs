@@ -7,6 +7,7 @@ use reth_primitives_traits::SealedHeader;
 use reth_provider::ProviderError;
 use reth_prune::{PruneSegment, PruneSegmentError, PrunerError};
 use reth_static_file_types::StaticFileSegment;
+use std::path::PathBuf;
 use thiserror::Error;
 use tokio::sync::broadcast::error::SendError;
 
@@ -185,4 +186,19 @@ pub enum PipelineError {
     /// Internal error
     #[error(transparent)]
     Internal(#[from] RethError),
+    /// The disk doesn't have enough free space for the pipeline to run.
+    #[error(
+        "insufficient disk space on {mount_point:?} to {operation}: \
+         {required} bytes estimated, {available} bytes available"
+    )]
+    InsufficientDiskSpace {
+        /// What the pipeline was about to do.
+        operation: &'static str,
+        /// The mount point of the disk.
+        mount_point: PathBuf,
+        /// The estimated number of bytes required.
+        required: u64,
+        /// The number of bytes available.
+        available: u64,
+    },
 }
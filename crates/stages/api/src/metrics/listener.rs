@@ -1,6 +1,9 @@
 use crate::{metrics::SyncMetrics, StageCheckpoint, StageId};
-use alloy_primitives::BlockNumber;
+use alloy_primitives::{BlockNumber, U256};
+use reth_db::tables;
+use reth_db_api::{cursor::DbDupCursorRO, transaction::DbTx, DatabaseError};
 use reth_primitives_traits::constants::MEGAGAS;
+use reth_provider::ExecutionOutcome;
 use std::{
     future::Future,
     pin::Pin,
@@ -35,6 +38,94 @@ pub enum MetricEvent {
         /// Gas processed.
         gas: u64,
     },
+    /// Execution stage or the persistence service of the engine read the size of the state from
+    /// the database.
+    ///
+    /// The state size gauges are set to these values and are then maintained from the
+    /// [`StateSizeDelta`]s of the executed block ranges.
+    ExecutionStageStateSizeTotal(StateSize),
+    /// Execution stage or the persistence service of the engine committed changes to the state.
+    ExecutionStageStateSize(StateSizeDelta),
+}
+
+/// Size of the state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateSize {
+    /// Number of accounts.
+    pub accounts: u64,
+    /// Number of non-zero storage slots.
+    pub storage_slots: u64,
+}
+
+/// Change of the state size caused by a range of executed blocks.
+///
+/// The values are derived from the state changes that are written to the changesets, so they
+/// only account for entries that were touched by the executed blocks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateSizeDelta {
+    /// Number of accounts that did not exist before the range.
+    pub accounts_created: u64,
+    /// Number of accounts that existed before the range and were destroyed.
+    pub accounts_destroyed: u64,
+    /// Number of storage slots that changed from zero to a non-zero value.
+    pub storage_slots_created: u64,
+    /// Number of storage slots that changed from a non-zero value to zero.
+    pub storage_slots_cleared: u64,
+    /// Number of non-zero storage slots that were wiped by the selfdestruct of their account.
+    pub storage_slots_wiped: u64,
+    /// Total size of the bytecode of contracts deployed in the range, in bytes.
+    pub bytecode_bytes_created: u64,
+}
+
+impl StateSize {
+    /// Reads the size of the state from the database.
+    pub fn from_tx<TX: DbTx>(tx: &TX) -> Result<Self, DatabaseError> {
+        Ok(Self {
+            accounts: tx.entries::<tables::PlainAccountState>()? as u64,
+            storage_slots: tx.entries::<tables::PlainStorageState>()? as u64,
+        })
+    }
+}
+
+impl StateSizeDelta {
+    /// Calculates the [`StateSizeDelta`] of the execution outcome, before it is written to the
+    /// database.
+    ///
+    /// The storage of destroyed accounts is wiped without its slots being part of the bundle
+    /// state, so the wiped slots are counted from the storage in the database.
+    pub fn from_execution_outcome<TX: DbTx>(
+        tx: &TX,
+        outcome: &ExecutionOutcome,
+    ) -> Result<Self, DatabaseError> {
+        let bundle = outcome.state();
+        let mut delta = Self::default();
+        let mut storage_cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+        for (address, account) in &bundle.state {
+            match (account.original_info.is_some(), account.info.is_some()) {
+                (false, true) => delta.accounts_created += 1,
+                (true, false) => delta.accounts_destroyed += 1,
+                _ => {}
+            }
+            if account.was_destroyed() {
+                for entry in storage_cursor.walk_dup(Some(*address), None)? {
+                    let (_, entry) = entry?;
+                    if !account.storage.contains_key(&U256::from_be_bytes(entry.key.0)) {
+                        delta.storage_slots_wiped += 1;
+                    }
+                }
+            }
+            for slot in account.storage.values() {
+                match (slot.previous_or_original_value.is_zero(), slot.present_value.is_zero()) {
+                    (true, false) => delta.storage_slots_created += 1,
+                    (false, true) => delta.storage_slots_cleared += 1,
+                    _ => {}
+                }
+            }
+        }
+        delta.bytecode_bytes_created =
+            bundle.contracts.values().map(|code| code.len() as u64).sum();
+        Ok(delta)
+    }
 }
 
 /// Metrics routine that listens to new metric events on the `events_rx` receiver.
@@ -85,6 +176,27 @@ impl MetricsListener {
             MetricEvent::ExecutionStageGas { gas } => {
                 self.sync_metrics.execution_stage.mgas_processed_total.increment(gas / MEGAGAS)
             }
+            MetricEvent::ExecutionStageStateSizeTotal(size) => {
+                let metrics = &self.sync_metrics.execution_stage;
+                metrics.accounts.set(size.accounts as f64);
+                metrics.storage_slots.set(size.storage_slots as f64);
+            }
+            MetricEvent::ExecutionStageStateSize(delta) => {
+                let metrics = &self.sync_metrics.execution_stage;
+                metrics.accounts_created_total.increment(delta.accounts_created);
+                metrics.accounts_destroyed_total.increment(delta.accounts_destroyed);
+                metrics.storage_slots_created_total.increment(delta.storage_slots_created);
+                metrics.storage_slots_cleared_total.increment(delta.storage_slots_cleared);
+                metrics.storage_slots_wiped_total.increment(delta.storage_slots_wiped);
+                metrics.bytecode_bytes_created_total.increment(delta.bytecode_bytes_created);
+
+                metrics.accounts.increment(delta.accounts_created as f64);
+                metrics.accounts.decrement(delta.accounts_destroyed as f64);
+                metrics.storage_slots.increment(delta.storage_slots_created as f64);
+                metrics
+                    .storage_slots
+                    .decrement((delta.storage_slots_cleared + delta.storage_slots_wiped) as f64);
+            }
         }
     }
 }
@@ -1,5 +1,5 @@
 mod listener;
 mod sync_metrics;
 
-pub use listener::{MetricEvent, MetricEventsSender, MetricsListener};
+pub use listener::{MetricEvent, MetricEventsSender, MetricsListener, StateSize, StateSizeDelta};
 use sync_metrics::*;
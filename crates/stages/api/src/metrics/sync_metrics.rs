@@ -38,4 +38,20 @@ pub(crate) struct StageMetrics {
 pub(crate) struct ExecutionStageMetrics {
     /// The total amount of gas processed (in millions)
     pub(crate) mgas_processed_total: Counter,
+    /// The number of accounts in the state
+    pub(crate) accounts: Gauge,
+    /// The number of non-zero storage slots in the state
+    pub(crate) storage_slots: Gauge,
+    /// The total number of accounts created
+    pub(crate) accounts_created_total: Counter,
+    /// The total number of accounts destroyed
+    pub(crate) accounts_destroyed_total: Counter,
+    /// The total number of storage slots that changed from zero to a non-zero value
+    pub(crate) storage_slots_created_total: Counter,
+    /// The total number of storage slots that changed from a non-zero value to zero
+    pub(crate) storage_slots_cleared_total: Counter,
+    /// The total number of non-zero storage slots wiped by selfdestructs
+    pub(crate) storage_slots_wiped_total: Counter,
+    /// The total size of deployed contract bytecode (in bytes)
+    pub(crate) bytecode_bytes_created_total: Counter,
 }
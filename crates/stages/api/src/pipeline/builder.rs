@@ -1,4 +1,6 @@
-use crate::{pipeline::BoxedStage, MetricEventsSender, Pipeline, Stage, StageId, StageSet};
+use crate::{
+    pipeline::BoxedStage, DiskSpaceCheck, MetricEventsSender, Pipeline, Stage, StageId, StageSet,
+};
 use alloy_primitives::{BlockNumber, B256};
 use reth_db_api::database::Database;
 use reth_provider::ProviderFactory;
@@ -18,6 +20,8 @@ where
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// Checks the free disk space before syncing or unwinding.
+    disk_space_check: Option<DiskSpaceCheck>,
 }
 
 impl<DB> PipelineBuilder<DB>
@@ -67,13 +71,19 @@ where
         self
     }
 
+    /// Set the check of the free disk space before syncing or unwinding.
+    pub fn with_disk_space_check(mut self, disk_space_check: DiskSpaceCheck) -> Self {
+        self.disk_space_check = Some(disk_space_check);
+        self
+    }
+
     /// Builds the final [`Pipeline`] using the given database.
     pub fn build(
         self,
         provider_factory: ProviderFactory<DB>,
         static_file_producer: StaticFileProducer<DB>,
     ) -> Pipeline<DB> {
-        let Self { stages, max_block, tip_tx, metrics_tx } = self;
+        let Self { stages, max_block, tip_tx, metrics_tx, disk_space_check } = self;
         Pipeline {
             provider_factory,
             stages,
@@ -83,13 +93,20 @@ where
             event_sender: Default::default(),
             progress: Default::default(),
             metrics_tx,
+            disk_space_check,
         }
     }
 }
 
 impl<DB: Database> Default for PipelineBuilder<DB> {
     fn default() -> Self {
-        Self { stages: Vec::new(), max_block: None, tip_tx: None, metrics_tx: None }
+        Self {
+            stages: Vec::new(),
+            max_block: None,
+            tip_tx: None,
+            metrics_tx: None,
+            disk_space_check: None,
+        }
     }
 }
 
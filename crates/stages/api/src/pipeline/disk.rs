@@ -0,0 +1,113 @@
+use crate::PipelineError;
+use reth_fs_util::DiskSpace;
+use reth_stages_types::{DiskSpacePolicy, DiskUsage, DiskUsageEstimator};
+use std::path::PathBuf;
+use tracing::{debug, warn};
+
+/// Checks that the disks of the database and the static files have enough free space for the
+/// data that the pipeline is estimated to write, before it starts.
+///
+/// Running out of space makes the database fail with a map full error, which can happen hours
+/// into a sync. The estimate is rough, so the check can be relaxed with the
+/// [`DiskSpacePolicy`].
+#[derive(Debug, Clone)]
+pub struct DiskSpaceCheck {
+    estimator: DiskUsageEstimator,
+    database_dir: PathBuf,
+    static_files_dir: PathBuf,
+    policy: DiskSpacePolicy,
+}
+
+impl DiskSpaceCheck {
+    /// Creates a new check for the database and static files in the given directories.
+    pub const fn new(
+        estimator: DiskUsageEstimator,
+        database_dir: PathBuf,
+        static_files_dir: PathBuf,
+        policy: DiskSpacePolicy,
+    ) -> Self {
+        Self { estimator, database_dir, static_files_dir, policy }
+    }
+
+    /// Returns the estimator of the disk usage.
+    pub const fn estimator(&self) -> &DiskUsageEstimator {
+        &self.estimator
+    }
+
+    /// Checks that there is enough free space for the required disk usage.
+    ///
+    /// Returns [`PipelineError::InsufficientDiskSpace`] if there isn't and the policy is
+    /// [`DiskSpacePolicy::Refuse`], logs a warning if the policy is [`DiskSpacePolicy::Warn`].
+    pub fn check(&self, operation: &'static str, required: DiskUsage) -> Result<(), PipelineError> {
+        if self.policy == DiskSpacePolicy::Off || required.total() == 0 {
+            return Ok(())
+        }
+
+        // the database and the static files are usually on the same disk, in which case it needs
+        // space for both
+        let mut disks: Vec<(DiskSpace, u64)> = Vec::with_capacity(2);
+        for (dir, bytes) in [
+            (&self.database_dir, required.database),
+            (&self.static_files_dir, required.static_files),
+        ] {
+            let Some(disk) = reth_fs_util::disk_space(dir) else {
+                debug!(target: "sync::pipeline", ?dir, "Unable to determine free disk space");
+                continue
+            };
+            match disks.iter_mut().find(|(other, _)| other.mount_point == disk.mount_point) {
+                Some((_, required)) => *required = required.saturating_add(bytes),
+                None => disks.push((disk, bytes)),
+            }
+        }
+
+        for (disk, required) in disks {
+            if required <= disk.available {
+                continue
+            }
+
+            let err = PipelineError::InsufficientDiskSpace {
+                operation,
+                mount_point: disk.mount_point,
+                required,
+                available: disk.available,
+            };
+            if self.policy == DiskSpacePolicy::Refuse {
+                return Err(err)
+            }
+            warn!(target: "sync::pipeline", %err, "Continuing despite insufficient disk space");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_disk_space() {
+        let dir = std::env::temp_dir();
+        if reth_fs_util::disk_space(&dir).is_none() {
+            // the disks of the system can't be listed
+            return
+        }
+        let check = |policy| {
+            DiskSpaceCheck::new(
+                Default::default(),
+                dir.join("db"),
+                dir.join("static_files"),
+                policy,
+            )
+        };
+        let too_much = DiskUsage { database: u64::MAX / 2, static_files: u64::MAX / 2 };
+
+        assert!(check(DiskSpacePolicy::Refuse).check("sync", DiskUsage::default()).is_ok());
+        assert!(matches!(
+            check(DiskSpacePolicy::Refuse).check("sync", too_much),
+            Err(PipelineError::InsufficientDiskSpace { operation: "sync", .. })
+        ));
+        assert!(check(DiskSpacePolicy::Warn).check("sync", too_much).is_ok());
+        assert!(check(DiskSpacePolicy::Off).check("sync", too_much).is_ok());
+    }
+}
@@ -1,7 +1,7 @@
 mod ctrl;
 mod event;
 pub use crate::pipeline::ctrl::ControlFlow;
-use crate::{PipelineTarget, StageCheckpoint, StageId};
+use crate::{DiskUsage, PipelineTarget, StageCheckpoint, StageId};
 use alloy_primitives::{BlockNumber, B256};
 pub use event::*;
 use futures_util::Future;
@@ -9,7 +9,8 @@ use reth_db_api::database::Database;
 use reth_primitives_traits::constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH;
 use reth_provider::{
     writer::UnifiedStorageWriter, FinalizedBlockReader, FinalizedBlockWriter, ProviderFactory,
-    StageCheckpointReader, StageCheckpointWriter, StaticFileProviderFactory,
+    StageCheckpointReader, StageCheckpointWriter, StaticFileProviderFactory, UnwindJournalReader,
+    UnwindJournalWriter,
 };
 use reth_prune::PrunerBuilder;
 use reth_static_file::StaticFileProducer;
@@ -19,6 +20,7 @@ use tokio::sync::watch;
 use tracing::*;
 
 mod builder;
+mod disk;
 mod progress;
 mod set;
 
@@ -27,6 +29,7 @@ use crate::{
     StageError, StageExt, UnwindInput,
 };
 pub use builder::*;
+pub use disk::DiskSpaceCheck;
 use progress::*;
 use reth_errors::RethResult;
 pub use set::*;
@@ -78,6 +81,8 @@ pub struct Pipeline<DB: Database> {
     /// A receiver for the current chain tip to sync to.
     tip_tx: Option<watch::Sender<B256>>,
     metrics_tx: Option<MetricEventsSender>,
+    /// Checks the free disk space before syncing or unwinding.
+    disk_space_check: Option<DiskSpaceCheck>,
 }
 
 impl<DB> Pipeline<DB>
@@ -103,6 +108,12 @@ where
         });
     }
 
+    /// Sets the check for free disk space that's run before syncing.
+    pub fn with_disk_space_check(mut self, disk_space_check: DiskSpaceCheck) -> Self {
+        self.disk_space_check = Some(disk_space_check);
+        self
+    }
+
     /// Listen for events on the pipeline.
     pub fn events(&self) -> EventStream<PipelineEvent> {
         self.event_sender.new_listener()
@@ -198,8 +209,24 @@ where
     pub async fn run_loop(&mut self) -> Result<ControlFlow, PipelineError> {
         self.move_to_static_files()?;
 
+        // Finish an unwind that was interrupted before the sync continues
+        if let Some(target) = self.provider_factory.provider()?.unwind_journal_target()? {
+            warn!(target: "sync::pipeline", %target, "Resuming interrupted unwind");
+            self.unwind(target, None)?;
+        }
+
         let mut previous_stage = None;
+        let mut checked_disk_space = false;
         for stage_index in 0..self.stages.len() {
+            // The target of the remaining stages is known once the first stage has run, unless
+            // the pipeline has a maximum block.
+            if let Some(target) = self.max_block.or(previous_stage) {
+                if !checked_disk_space {
+                    self.check_disk_space_for_sync(stage_index, target)?;
+                    checked_disk_space = true;
+                }
+            }
+
             let stage = &self.stages[stage_index];
             let stage_id = stage.id();
 
@@ -233,6 +260,27 @@ where
         Ok(self.progress.next_ctrl())
     }
 
+    /// Checks that there is enough free disk space for the stages from the given index onwards to
+    /// sync from their checkpoints to the target.
+    fn check_disk_space_for_sync(
+        &self,
+        from_stage: usize,
+        target: BlockNumber,
+    ) -> Result<(), PipelineError> {
+        let Some(check) = &self.disk_space_check else { return Ok(()) };
+
+        let provider = self.provider_factory.provider()?;
+        let mut required = DiskUsage::default();
+        for stage in &self.stages[from_stage..] {
+            let checkpoint = provider.get_stage_checkpoint(stage.id())?.unwrap_or_default();
+            required +=
+                check.estimator().estimate(stage.id(), checkpoint.block_number + 1..=target);
+        }
+
+        debug!(target: "sync::pipeline", %target, ?required, "Checking free disk space for sync");
+        check.check("sync", required)
+    }
+
     /// Run [static file producer](StaticFileProducer) and [pruner](reth_prune::Pruner) to **move**
     /// all data from the database to static files for corresponding
     /// [segments](reth_static_file_types::StaticFileSegment), according to their [stage
@@ -268,16 +316,33 @@ where
     /// Unwind the stages to the target block.
     ///
     /// If the unwind is due to a bad block the number of that block should be specified.
+    ///
+    /// The stages are unwound one by one, each in its own transaction. The target of the unwind is
+    /// recorded in the unwind journal, so that an interrupted unwind is resumed on the next run of
+    /// the pipeline.
     pub fn unwind(
         &mut self,
         to: BlockNumber,
         bad_block: Option<BlockNumber>,
     ) -> Result<(), PipelineError> {
+        let mut provider_rw = self.provider_factory.provider_rw()?;
+
+        let mut tip = to;
+        for stage in &self.stages {
+            let checkpoint = provider_rw.get_stage_checkpoint(stage.id())?.unwrap_or_default();
+            tip = tip.max(checkpoint.block_number);
+        }
+        // The preview of the removed data is only shown by the CLI, walking the tables would
+        // delay every unwind of the node.
+        let journaled = tip > to;
+        if journaled {
+            info!(target: "sync::pipeline", from = tip, to, ?bad_block, "Unwinding");
+            provider_rw.save_unwind_journal_target(to)?;
+        }
+
         // Unwind stages in reverse order of execution
         let unwind_pipeline = self.stages.iter_mut().rev();
 
-        let mut provider_rw = self.provider_factory.provider_rw()?;
-
         for stage in unwind_pipeline {
             let stage_id = stage.id();
             let span = info_span!("Unwinding", stage = %stage_id);
@@ -365,6 +430,11 @@ where
             }
         }
 
+        if journaled {
+            provider_rw.clear_unwind_journal()?;
+            provider_rw.commit()?;
+        }
+
         Ok(())
     }
 
@@ -591,7 +661,10 @@ mod tests {
     use assert_matches::assert_matches;
     use reth_consensus::ConsensusError;
     use reth_errors::ProviderError;
-    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, StageCheckpointSubscriptions,
+        StageCheckpointUpdate,
+    };
     use reth_prune::PruneModes;
     use reth_testing_utils::{generators, generators::random_header};
     use tokio_stream::StreamExt;
@@ -643,6 +716,7 @@ mod tests {
                 StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
             );
         let events = pipeline.events();
+        let mut checkpoints = provider_factory.subscribe_to_stage_checkpoints();
 
         // Run pipeline
         tokio::spawn(async move {
@@ -689,6 +763,22 @@ mod tests {
                 },
             ]
         );
+
+        // Check that the committed checkpoints were notified
+        assert_eq!(
+            checkpoints.recv().await.unwrap(),
+            StageCheckpointUpdate {
+                stage_id: StageId::Other("A"),
+                checkpoint: StageCheckpoint::new(20)
+            }
+        );
+        assert_eq!(
+            checkpoints.recv().await.unwrap(),
+            StageCheckpointUpdate {
+                stage_id: StageId::Other("B"),
+                checkpoint: StageCheckpoint::new(10)
+            }
+        );
     }
 
     /// Unwinds a simple pipeline.
@@ -825,6 +915,55 @@ mod tests {
         );
     }
 
+    /// Resumes an interrupted unwind before running the pipeline.
+    #[tokio::test]
+    async fn resume_interrupted_unwind() {
+        let provider_factory = create_test_provider_factory();
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("A"), StageCheckpoint::new(100)).unwrap();
+        provider_rw.save_unwind_journal_target(1).unwrap();
+        provider_rw.commit().unwrap();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(1) }))
+                    .add_exec(Ok(ExecOutput { checkpoint: StageCheckpoint::new(10), done: true })),
+            )
+            .with_max_block(10)
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        let events = pipeline.events();
+
+        tokio::spawn(async move {
+            pipeline.run_loop().await.expect("Could not run pipeline");
+        });
+
+        let events = events.collect::<Vec<PipelineEvent>>().await;
+        assert_eq!(
+            events.first(),
+            Some(&PipelineEvent::Unwind {
+                stage_id: StageId::Other("A"),
+                input: UnwindInput {
+                    checkpoint: StageCheckpoint::new(100),
+                    unwind_to: 1,
+                    bad_block: None
+                }
+            })
+        );
+        assert_eq!(
+            events.last(),
+            Some(&PipelineEvent::Ran {
+                pipeline_stages_progress: PipelineStagesProgress { current: 1, total: 1 },
+                stage_id: StageId::Other("A"),
+                result: ExecOutput { checkpoint: StageCheckpoint::new(10), done: true },
+            })
+        );
+        assert_eq!(provider_factory.provider().unwrap().unwind_journal_target().unwrap(), None);
+    }
+
     /// Unwinds a pipeline with intermediate progress.
     #[tokio::test]
     async fn unwind_pipeline_with_intermediate_progress() {
@@ -36,8 +36,9 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage,
-        PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexLogsStage, IndexStorageHistoryStage, MerkleStage,
+        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
+        TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -385,7 +386,7 @@ impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
             .add_stage(TransactionLookupStage::new(
                 self.stages_config.transaction_lookup,
                 self.stages_config.etl.clone(),
-                self.prune_modes.transaction_lookup,
+                self.prune_modes.transaction_lookup_skip_mode(),
             ))
             .add_stage(IndexStorageHistoryStage::new(
                 self.stages_config.index_storage_history,
@@ -397,5 +398,12 @@ impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
                 self.stages_config.etl.clone(),
                 self.prune_modes.storage_history,
             ))
+            .add_stage_opt(self.stages_config.index_logs.enabled.then(|| {
+                IndexLogsStage::new(
+                    self.stages_config.index_logs,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.log_index,
+                )
+            }))
     }
 }
@@ -2,29 +2,32 @@ use crate::stages::MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD;
 use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_db::{static_file::HeaderMask, tables};
-use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
-use reth_evm::execute::{BatchExecutor, BlockExecutorProvider};
-use reth_execution_types::{Chain, ExecutionOutcome};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_evm::execute::{BatchExecutor, BlockExecutionCheckpoint, BlockExecutorProvider};
+use reth_execution_types::Chain;
 use reth_exex::{ExExManagerHandle, ExExNotification};
 use reth_primitives::{BlockNumber, Header, StaticFileSegment};
 use reth_primitives_traits::format_gas_throughput;
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileProviderRWRefMut, StaticFileWriter},
     writer::UnifiedStorageWriter,
-    BlockReader, DatabaseProviderRW, HeaderProvider, LatestStateProviderRef, OriginalValuesKnown,
-    ProviderError, StateWriter, StatsReader, TransactionVariant,
+    BlockHashReader, BlockReader, DatabaseProviderRW, HeaderProvider, LatestStateProviderRef,
+    OriginalValuesKnown, ProviderError, StateWriter, StatsReader, TransactionVariant,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::{
     BlockErrorKind, CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput,
     ExecutionCheckpoint, ExecutionStageThresholds, MetricEvent, MetricEventsSender, Stage,
-    StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+    StageCheckpoint, StageError, StageId, StateSize, StateSizeDelta, UnwindInput, UnwindOutput,
 };
 use std::{
     cmp::Ordering,
     ops::RangeInclusive,
-    sync::Arc,
     task::{ready, Context, Poll},
     time::{Duration, Instant},
 };
@@ -53,6 +56,10 @@ use tracing::*;
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
 ///
+/// Blocks that use more gas than [`ExecutionStageThresholds::block_checkpoint_gas`] are executed
+/// in parts, and the [`BlockExecutionCheckpoint`] of every executed part is committed to
+/// [`tables::BlockExecutionCheckpoints`], so the execution resumes from them after a restart.
+///
 /// For unwinds we are accessing:
 /// - [`tables::BlockBodyIndices`] get tx index to know what needs to be unwinded
 /// - [`tables::AccountsHistory`] to remove change set and apply old values to
@@ -62,6 +69,9 @@ use tracing::*;
 #[allow(missing_debug_implementations)]
 pub struct ExecutionStage<E> {
     metrics_tx: Option<MetricEventsSender>,
+    /// Whether the size of the state was reported to the metrics since the last unwind, after
+    /// which only its changes are reported.
+    state_size_reported: bool,
     /// The stage's internal block executor
     executor_provider: E,
     /// The commit thresholds of the execution stage.
@@ -96,6 +106,7 @@ impl<E> ExecutionStage<E> {
     ) -> Self {
         Self {
             metrics_tx: None,
+            state_size_reported: false,
             external_clean_threshold,
             executor_provider,
             thresholds,
@@ -244,6 +255,19 @@ where
 
         debug!(target: "sync::stages::execution", start = start_block, end = max_block, "Executing range");
 
+        // The progress of a block whose execution was interrupted by the previous run
+        let mut block_checkpoint = block_execution_checkpoint(provider)?;
+        if let Some(checkpoint) = &block_checkpoint {
+            if checkpoint.block_number != start_block ||
+                provider.block_hash(start_block)? != Some(checkpoint.block_hash)
+            {
+                debug!(target: "sync::stages::execution", number = checkpoint.block_number, "Discarding block execution checkpoint of a different block");
+                clear_block_execution_checkpoints(provider)?;
+                block_checkpoint = None;
+            }
+        }
+        let has_block_checkpoint = block_checkpoint.is_some();
+
         // Execute block range
         let mut cumulative_gas = 0;
         let batch_start = Instant::now();
@@ -264,6 +288,14 @@ where
 
             fetch_block_duration += fetch_block_start.elapsed();
 
+            // Blocks above the block checkpoint threshold are executed in parts on top of the
+            // committed state of their parent, so they start a new batch.
+            let block_checkpoint_gas =
+                self.thresholds.block_checkpoint_gas.filter(|gas| block.gas_used > *gas);
+            if block_checkpoint_gas.is_some() && block_number > start_block {
+                break
+            }
+
             cumulative_gas += block.gas_used;
 
             // Configure the executor to use the current state.
@@ -272,14 +304,34 @@ where
             // Execute the block
             let execute_start = Instant::now();
 
-            executor.execute_and_verify_one((&block, td).into()).map_err(|error| {
-                StageError::Block {
-                    block: Box::new(block.header.clone().seal_slow()),
-                    error: BlockErrorKind::Execution(error),
-                }
+            let interrupted = if let Some(gas_threshold) = block_checkpoint_gas {
+                executor.execute_and_verify_one_with_checkpoint(
+                    (&block, td).into(),
+                    block_checkpoint.take(),
+                    gas_threshold,
+                )
+            } else {
+                executor.execute_and_verify_one((&block, td).into()).map(|_| None)
+            }
+            .map_err(|error| StageError::Block {
+                block: Box::new(block.header.clone().seal_slow()),
+                error: BlockErrorKind::Execution(error),
             })?;
             execution_duration += execute_start.elapsed();
 
+            // Commit the progress within the block, the batch only contains the interrupted block
+            if let Some(interrupted) = interrupted {
+                debug!(
+                    target: "sync::stages::execution",
+                    number = block_number,
+                    txs = interrupted.transactions(),
+                    gas_used = interrupted.gas_used(),
+                    "Interrupted block execution"
+                );
+                save_block_execution_checkpoint(provider, &interrupted)?;
+                return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+            }
+
             // Log execution throughput
             if last_log_instant.elapsed() >= log_duration {
                 info!(
@@ -322,12 +374,29 @@ where
             }
         }
 
+        // The interrupted block was either executed or is not part of the chain anymore
+        if has_block_checkpoint {
+            clear_block_execution_checkpoints(provider)?;
+        }
+
         // prepare execution output for writing
         let time = Instant::now();
-        let ExecutionOutcome { bundle, receipts, requests, first_block } = executor.finalize();
-        let state = ExecutionOutcome::new(bundle, receipts, first_block, requests);
+        let state = executor.finalize();
         let write_preparation_duration = time.elapsed();
 
+        // State size metrics, read before the state of the range is written
+        if let Some(metrics_tx) = &mut self.metrics_tx {
+            if !self.state_size_reported {
+                let _ = metrics_tx.send(MetricEvent::ExecutionStageStateSizeTotal(
+                    StateSize::from_tx(provider.tx_ref())?,
+                ));
+                self.state_size_reported = true;
+            }
+            let _ = metrics_tx.send(MetricEvent::ExecutionStageStateSize(
+                StateSizeDelta::from_execution_outcome(provider.tx_ref(), &state)?,
+            ));
+        }
+
         // log the gas per second for the range we just executed
         debug!(
             target: "sync::stages::execution",
@@ -387,9 +456,8 @@ where
 
         // NOTE: We can ignore the error here, since an error means that the channel is closed,
         // which means the manager has died, which then in turn means the node is shutting down.
-        let _ = self
-            .exex_manager_handle
-            .send(ExExNotification::ChainCommitted { new: Arc::new(chain) });
+        let _ =
+            self.exex_manager_handle.send(ExExNotification::ChainCommitted { new: chain.into() });
 
         Ok(())
     }
@@ -400,6 +468,12 @@ where
         provider: &DatabaseProviderRW<DB>,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
+        // The progress of an interrupted block execution is not valid on top of the unwound state
+        clear_block_execution_checkpoints(provider)?;
+
+        // The size of the unwound state is read again on the next execution
+        self.state_size_reported = false;
+
         let (range, unwind_to, _) =
             input.unwind_block_range_with_threshold(self.thresholds.max_blocks.unwrap_or(u64::MAX));
         if range.is_empty() {
@@ -473,12 +547,69 @@ where
         // NOTE: We can ignore the error here, since an error means that the channel is closed,
         // which means the manager has died, which then in turn means the node is shutting down.
         let _ =
-            self.exex_manager_handle.send(ExExNotification::ChainReverted { old: Arc::new(chain) });
+            self.exex_manager_handle.send(ExExNotification::ChainReverted { old: chain.into() });
 
         Ok(())
     }
 }
 
+/// Returns the [`BlockExecutionCheckpoint`] of the block whose execution was interrupted, if any,
+/// combined from the checkpoints of its executed parts.
+///
+/// Checkpoints that can't be decoded or belong to different blocks are discarded, so the block is
+/// executed from its start.
+fn block_execution_checkpoint<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+) -> Result<Option<BlockExecutionCheckpoint>, StageError> {
+    let mut checkpoint: Option<BlockExecutionCheckpoint> = None;
+    for entry in provider.tx_ref().cursor_read::<tables::BlockExecutionCheckpoints>()?.walk(None)? {
+        let (_, buf) = entry?;
+        let part = match bincode::deserialize::<BlockExecutionCheckpoint>(&buf) {
+            Ok(part) => part,
+            Err(err) => {
+                warn!(target: "sync::stages::execution", %err, "Discarding invalid block execution checkpoint");
+                return Ok(None)
+            }
+        };
+
+        match &mut checkpoint {
+            Some(checkpoint) if checkpoint.block_hash == part.block_hash => checkpoint.extend(part),
+            Some(_) => {
+                warn!(target: "sync::stages::execution", "Discarding block execution checkpoints of different blocks");
+                return Ok(None)
+            }
+            None => checkpoint = Some(part),
+        }
+    }
+
+    Ok(checkpoint)
+}
+
+/// Appends the [`BlockExecutionCheckpoint`] of the executed part of the interrupted block.
+///
+/// Only the changes of the part are written, so the cost of a checkpoint doesn't grow with the
+/// number of executed parts.
+fn save_block_execution_checkpoint<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    part: &BlockExecutionCheckpoint,
+) -> Result<(), StageError> {
+    let mut cursor = provider.tx_ref().cursor_write::<tables::BlockExecutionCheckpoints>()?;
+    let index = cursor.last()?.map_or(0, |(index, _)| index + 1);
+    let buf = bincode::serialize(part).map_err(|err| StageError::Fatal(Box::new(err)))?;
+    Ok(cursor.append(index, buf)?)
+}
+
+/// Removes the checkpoints of the executed parts of the interrupted block, if any.
+fn clear_block_execution_checkpoints<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+) -> Result<(), StageError> {
+    let tx = provider.tx_ref();
+    if tx.cursor_read::<tables::BlockExecutionCheckpoints>()?.first()?.is_some() {
+        tx.clear::<tables::BlockExecutionCheckpoints>()?;
+    }
+    Ok(())
+}
+
 fn execution_checkpoint(
     provider: &StaticFileProvider,
     start_block: BlockNumber,
@@ -660,16 +791,19 @@ mod tests {
     use reth_evm_ethereum::execute::EthExecutorProvider;
     use reth_execution_errors::BlockValidationError;
     use reth_primitives::{
-        address, hex_literal::hex, keccak256, Account, Address, Bytecode, SealedBlock,
-        StorageEntry, B256, U256,
+        address, constants::ETH_TO_WEI, hex_literal::hex, keccak256,
+        proofs::calculate_receipt_root_no_memo, public_key_to_address, Account, Address, Bytecode,
+        Bytes, Receipt, SealedBlock, StorageEntry, Transaction, TxKind, TxLegacy, TxType, B256,
+        U256,
     };
     use reth_provider::{
-        test_utils::create_test_provider_factory, AccountReader, ReceiptProvider,
+        test_utils::create_test_provider_factory, AccountReader, ChangeSetReader, ReceiptProvider,
         StaticFileProviderFactory,
     };
     use reth_prune_types::{PruneMode, ReceiptsLogPruneConfig};
     use reth_stages_api::StageUnitCheckpoint;
-    use std::collections::BTreeMap;
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+    use std::{collections::BTreeMap, sync::Arc};
 
     fn stage() -> ExecutionStage<EthExecutorProvider> {
         let executor_provider = EthExecutorProvider::ethereum(Arc::new(
@@ -682,6 +816,7 @@ mod tests {
                 max_changes: None,
                 max_cumulative_gas: None,
                 max_duration: None,
+                block_checkpoint_gas: None,
             },
             MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD,
             PruneModes::none(),
@@ -1240,4 +1375,126 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn resumes_interrupted_block_execution() {
+        let factory = create_test_provider_factory();
+        let chain_spec = ChainSpecBuilder::mainnet().berlin_activated().build();
+
+        let key_pair = generators::generate_keys(&mut generators::rng(), 1)[0];
+        let sender = public_key_to_address(key_pair.public_key());
+        let sender_info =
+            Account { nonce: 0, balance: U256::from(ETH_TO_WEI), bytecode_hash: None };
+        let receiver = Address::repeat_byte(0x03);
+
+        // a block with four plain transfers
+        let transactions = (0..4)
+            .map(|nonce| {
+                sign_tx_with_key_pair(
+                    key_pair,
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(chain_spec.chain.id()),
+                        nonce,
+                        gas_price: 10,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(receiver),
+                        value: U256::from(nonce + 1),
+                        input: Bytes::new(),
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        let receipts = (1..=4)
+            .map(|index| Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: 21_000 * index,
+                logs: vec![],
+            })
+            .collect::<Vec<_>>();
+        let genesis = SealedBlock { header: Header::default().seal_slow(), ..Default::default() };
+        let block = SealedBlock {
+            header: Header {
+                parent_hash: genesis.hash(),
+                number: 1,
+                gas_limit: 1_000_000,
+                gas_used: 84_000,
+                receipts_root: calculate_receipt_root_no_memo(&receipts.iter().collect::<Vec<_>>()),
+                ..Default::default()
+            }
+            .seal_slow(),
+            body: transactions,
+            ..Default::default()
+        };
+
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_historical_block(genesis.try_seal_with_senders().unwrap()).unwrap();
+        provider.insert_historical_block(block.clone().try_seal_with_senders().unwrap()).unwrap();
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        {
+            let mut receipts_writer =
+                provider.static_file_provider().latest_writer(StaticFileSegment::Receipts).unwrap();
+            receipts_writer.increment_block(0).unwrap();
+            receipts_writer.commit().unwrap();
+        }
+        provider.tx_ref().put::<tables::PlainAccountState>(sender, sender_info).unwrap();
+        provider.commit().unwrap();
+
+        // interrupt the execution after every transaction, every run resumes from the
+        // checkpoints committed by the previous runs
+        let mut stage = stage();
+        stage.thresholds.block_checkpoint_gas = Some(21_000);
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let mut parts = 0;
+        let output = loop {
+            let provider = factory.provider_rw().unwrap();
+            let output = stage.execute(&provider, input).unwrap();
+            provider.commit().unwrap();
+            if output.done {
+                break output
+            }
+
+            parts += 1;
+            let provider = factory.provider().unwrap();
+            assert_eq!(output.checkpoint, input.checkpoint());
+            assert_eq!(
+                provider.tx_ref().entries::<tables::BlockExecutionCheckpoints>().unwrap(),
+                parts
+            );
+            assert_eq!(provider.basic_account(receiver).unwrap(), None);
+        };
+        assert_eq!(parts, 3);
+        assert_eq!(output.checkpoint.block_number, 1);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::BlockExecutionCheckpoints>().unwrap(), 0);
+        assert_eq!(provider.receipts_by_block(1.into()).unwrap(), Some(receipts));
+        assert_eq!(
+            provider.basic_account(sender).unwrap(),
+            Some(Account {
+                nonce: 4,
+                balance: U256::from(ETH_TO_WEI - 10 - 84_000 * 10),
+                bytecode_hash: None
+            })
+        );
+        assert_eq!(
+            provider.basic_account(receiver).unwrap(),
+            Some(Account { nonce: 0, balance: U256::from(10), bytecode_hash: None })
+        );
+
+        // the changes of all parts are reverted to the state before the block
+        let account_changesets = provider.account_block_changeset(1).unwrap();
+        assert!(account_changesets
+            .contains(&AccountBeforeTx { address: sender, info: Some(sender_info) }));
+        assert!(account_changesets.contains(&AccountBeforeTx { address: receiver, info: None }));
+        assert_eq!(
+            account_changesets.iter().filter(|changeset| changeset.address == sender).count(),
+            1
+        );
+    }
 }
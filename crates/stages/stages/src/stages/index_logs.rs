@@ -0,0 +1,263 @@
+use super::load_history_indices;
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{
+    database::Database,
+    models::ShardedKey,
+    table::{Decode, Table},
+    transaction::DbTxMut,
+};
+use reth_etl::Collector;
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_provider::{
+    DatabaseProviderRW, LogIndexWriter, PruneCheckpointReader, PruneCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::BTreeMap, fmt::Debug};
+use tracing::info;
+
+/// Number of blocks of which the logs are read into memory at once, before being pushed to the
+/// [`Collector`]s.
+const BLOCKS_PER_CHUNK: u64 = 10_000;
+
+/// Stage is indexing the addresses and topics of the logs in the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage]. For more information on index sharding
+/// take a look at [`tables::LogAddressHistory`] and [`tables::LogTopicHistory`].
+///
+/// The stage is optional, and once it's caught up with the chain, the index is maintained by the
+/// provider when blocks are persisted.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(
+        config: IndexLogsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexLogsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(input.target(), PruneSegment::LogIndex, PrunePurpose::User)
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::LogIndex)?.is_none() {
+                    provider.save_prune_checkpoint(
+                        PruneSegment::LogIndex,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: None,
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (mut range, done) = input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we might have logs coming from genesis. We clear the tables since it's
+        // faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressHistory>()?;
+            provider.tx_ref().clear::<tables::LogTopicHistory>()?;
+            range = 0..=*range.end();
+        }
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, ?range, "Collecting indices");
+        let mut address_collector =
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        let mut topic_collector =
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+
+        for chunk in range.clone().step_by(BLOCKS_PER_CHUNK as usize) {
+            let chunk = chunk..=(chunk + BLOCKS_PER_CHUNK - 1).min(*range.end());
+            let keys = provider.log_index_keys_with_range(chunk)?;
+            collect_indices::<tables::LogAddressHistory, _>(
+                &mut address_collector,
+                keys.addresses,
+            )?;
+            collect_indices::<tables::LogTopicHistory, _>(&mut topic_collector, keys.topics)?;
+        }
+
+        info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressHistory, _>(
+            provider,
+            address_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicHistory, _>(
+            provider,
+            topic_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_indices(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Inserts the blocks of each key into the collector of the history table `H`, keyed by the
+/// highest block number of the list.
+fn collect_indices<H, K>(
+    collector: &mut Collector<H::Key, H::Value>,
+    indices: BTreeMap<K, Vec<BlockNumber>>,
+) -> Result<(), StageError>
+where
+    H: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+{
+    for (key, blocks) in indices {
+        let last = *blocks.last().expect("qed");
+        collector.insert(ShardedKey::new(key, last), BlockNumberList::new_pre_sorted(blocks))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use reth_db_api::models::StoredBlockBodyIndices;
+    use reth_primitives::{address, b256, Log, Receipt};
+
+    const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
+    const TOPIC: B256 = b256!("0000000000000000000000000000000000000000000000000000000000000002");
+
+    const MAX_BLOCK: BlockNumber = 10;
+
+    /// Every block has a single transaction. Even blocks have a log with a topic, odd blocks have
+    /// a log without topics.
+    fn setup(db: &TestStageDB) {
+        db.commit(|tx| {
+            for block in 0..=MAX_BLOCK {
+                tx.put::<tables::BlockBodyIndices>(
+                    block,
+                    StoredBlockBodyIndices { first_tx_num: block, tx_count: 1 },
+                )?;
+                let topics = if block % 2 == 0 { vec![TOPIC] } else { Vec::new() };
+                tx.put::<tables::Receipts>(
+                    block,
+                    Receipt {
+                        logs: vec![Log::new_unchecked(ADDRESS, topics, Default::default())],
+                        ..Default::default()
+                    },
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    fn table<T>(db: &TestStageDB) -> Vec<(T::Key, Vec<u64>)>
+    where
+        T: Table<Value = BlockNumberList>,
+    {
+        db.table::<T>().unwrap().into_iter().map(|(k, v)| (k, v.iter().collect())).collect()
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        setup(&db);
+
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(MAX_BLOCK), checkpoint: None };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(MAX_BLOCK), done: true });
+        provider.commit().unwrap();
+
+        assert_eq!(
+            table::<tables::LogAddressHistory>(&db),
+            vec![(ShardedKey::new(ADDRESS, u64::MAX), (0..=MAX_BLOCK).collect())]
+        );
+        assert_eq!(
+            table::<tables::LogTopicHistory>(&db),
+            vec![(ShardedKey::new(TOPIC, u64::MAX), (0..=MAX_BLOCK).step_by(2).collect())]
+        );
+
+        let provider = db.factory.provider_rw().unwrap();
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(MAX_BLOCK),
+            unwind_to: 5,
+            ..Default::default()
+        };
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(5) });
+        provider.commit().unwrap();
+
+        assert_eq!(
+            table::<tables::LogAddressHistory>(&db),
+            vec![(ShardedKey::new(ADDRESS, u64::MAX), (0..=5).collect())]
+        );
+        assert_eq!(
+            table::<tables::LogTopicHistory>(&db),
+            vec![(ShardedKey::new(TOPIC, u64::MAX), vec![0, 2, 4])]
+        );
+    }
+}
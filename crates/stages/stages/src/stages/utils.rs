@@ -102,10 +102,15 @@ where
 /// `Address.StorageKey`). It flushes indices to disk when reaching a shard's max length
 /// (`NUM_OF_INDICES_IN_SHARD`) or when the partial key changes, ensuring the last previous partial
 /// key shard is stored.
+///
+/// If it's not the first sync, the last shard of every partial key is loaded from the database
+/// and merged with the collected indices. Partial keys that come after the last key of the table
+/// don't have any shards yet, so from the first of them on, the shards are bulk appended without
+/// looking up the database.
 pub(crate) fn load_history_indices<DB, H, P>(
     provider: &DatabaseProviderRW<DB>,
     mut collector: Collector<H::Key, H::Value>,
-    append_only: bool,
+    mut append_only: bool,
     sharded_key_factory: impl Clone + Fn(P, u64) -> <H as Table>::Key,
     decode_key: impl Fn(Vec<u8>) -> Result<<H as Table>::Key, DatabaseError>,
     get_partial: impl Fn(<H as Table>::Key) -> P,
@@ -116,6 +121,9 @@ where
     P: Copy + Default + Eq,
 {
     let mut write_cursor = provider.tx_ref().cursor_write::<H>()?;
+    let last_key = if append_only { None } else { write_cursor.last()?.map(|(key, _)| key) };
+    append_only = append_only || last_key.is_none();
+
     let mut current_partial = P::default();
     let mut current_list = Vec::<u64>::new();
 
@@ -151,6 +159,12 @@ where
             current_partial = partial_key;
             current_list.clear();
 
+            // Once the partial key is past the last key of the table, so are all following ones
+            append_only = append_only ||
+                last_key
+                    .as_ref()
+                    .is_some_and(|last| sharded_key_factory(current_partial, 0) > *last);
+
             // If it's not the first sync, there might an existing shard already, so we need to
             // merge it with the one coming from the collector
             if !append_only {
@@ -0,0 +1,221 @@
+use crate::StageId;
+use alloy_primitives::BlockNumber;
+use reth_prune_types::{PruneMode, PruneModes};
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, AddAssign, RangeInclusive};
+
+/// What to do if the estimated disk usage of the pipeline exceeds the available disk space.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiskSpacePolicy {
+    /// Refuse to run the pipeline.
+    Refuse,
+    /// Log a warning and run the pipeline anyway.
+    #[default]
+    Warn,
+    /// Don't check the available disk space.
+    Off,
+}
+
+/// Disk usage in bytes, by where the data is stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Bytes stored in the database.
+    pub database: u64,
+    /// Bytes stored in static files.
+    pub static_files: u64,
+}
+
+impl DiskUsage {
+    /// Returns the total bytes.
+    pub const fn total(&self) -> u64 {
+        self.database.saturating_add(self.static_files)
+    }
+}
+
+impl Add for DiskUsage {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            database: self.database.saturating_add(rhs.database),
+            static_files: self.static_files.saturating_add(rhs.static_files),
+        }
+    }
+}
+
+impl AddAssign for DiskUsage {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+/// Where the data of a stage is stored.
+#[derive(Debug, Clone, Copy)]
+enum Storage {
+    Database,
+    StaticFiles,
+}
+
+/// A part of the data that a stage writes for every block.
+#[derive(Debug, Clone, Copy)]
+struct Component {
+    /// Average size of the data of a block in bytes, including the overhead of the storage.
+    bytes_per_block: u64,
+    storage: Storage,
+    /// Returns the prune mode of the data, if it can be pruned.
+    prune_mode: fn(&PruneModes) -> Option<PruneMode>,
+}
+
+impl Component {
+    const fn new(bytes_per_block: u64, storage: Storage) -> Self {
+        Self { bytes_per_block, storage, prune_mode: |_| None }
+    }
+
+    const fn pruned(
+        bytes_per_block: u64,
+        storage: Storage,
+        prune_mode: fn(&PruneModes) -> Option<PruneMode>,
+    ) -> Self {
+        Self { bytes_per_block, storage, prune_mode }
+    }
+}
+
+/// The data that the stages write for every block.
+///
+/// The sizes are rough averages over the blocks of Ethereum mainnet, so that estimates err on the
+/// side of caution for other chains.
+mod components {
+    use super::{
+        Component,
+        Storage::{Database, StaticFiles},
+    };
+
+    /// Headers, total difficulties and canonical hashes.
+    pub(super) const HEADERS: &[Component] = &[Component::new(700, StaticFiles)];
+    /// Transactions and body indices.
+    pub(super) const BODIES: &[Component] =
+        &[Component::new(14_000, StaticFiles), Component::new(100, Database)];
+    pub(super) const SENDER_RECOVERY: &[Component] =
+        &[Component::pruned(3_500, Database, |modes| modes.sender_recovery)];
+    /// Receipts, changesets and the growth of the plain state.
+    pub(super) const EXECUTION: &[Component] = &[
+        Component::pruned(12_000, StaticFiles, |modes| modes.receipts),
+        Component::pruned(6_000, Database, |modes| modes.account_history),
+        Component::pruned(20_000, Database, |modes| modes.storage_history),
+        Component::new(4_000, Database),
+    ];
+    pub(super) const ACCOUNT_HASHING: &[Component] = &[Component::new(1_000, Database)];
+    pub(super) const STORAGE_HASHING: &[Component] = &[Component::new(4_000, Database)];
+    pub(super) const MERKLE: &[Component] = &[Component::new(8_000, Database)];
+    pub(super) const TRANSACTION_LOOKUP: &[Component] =
+        &[Component::pruned(8_000, Database, |modes| modes.transaction_lookup)];
+    pub(super) const INDEX_STORAGE_HISTORY: &[Component] =
+        &[Component::pruned(12_000, Database, |modes| modes.storage_history)];
+    pub(super) const INDEX_ACCOUNT_HISTORY: &[Component] =
+        &[Component::pruned(5_000, Database, |modes| modes.account_history)];
+}
+
+/// Returns the data that the stage writes for every block.
+const fn components(stage: StageId) -> &'static [Component] {
+    match stage {
+        StageId::Headers => components::HEADERS,
+        StageId::Bodies => components::BODIES,
+        StageId::SenderRecovery => components::SENDER_RECOVERY,
+        StageId::Execution => components::EXECUTION,
+        StageId::AccountHashing => components::ACCOUNT_HASHING,
+        StageId::StorageHashing => components::STORAGE_HASHING,
+        StageId::MerkleExecute => components::MERKLE,
+        StageId::TransactionLookup => components::TRANSACTION_LOOKUP,
+        StageId::IndexStorageHistory => components::INDEX_STORAGE_HISTORY,
+        StageId::IndexAccountHistory => components::INDEX_ACCOUNT_HISTORY,
+        _ => &[],
+    }
+}
+
+/// Returns the number of blocks of the range that are kept with the prune mode, if the chain is
+/// synced to the end of the range.
+fn retained_blocks(prune_mode: Option<PruneMode>, blocks: &RangeInclusive<BlockNumber>) -> u64 {
+    let (start, end) = (*blocks.start(), *blocks.end());
+    let first_retained = match prune_mode {
+        None => start,
+        Some(PruneMode::Full) => return 0,
+        Some(PruneMode::Distance(distance)) => end.saturating_sub(distance).max(start),
+        Some(PruneMode::Before(block)) => block.max(start),
+    };
+    (end + 1).saturating_sub(first_retained)
+}
+
+/// Estimates the disk space that the stages of the pipeline use, given the prune configuration.
+#[derive(Debug, Clone, Default)]
+pub struct DiskUsageEstimator {
+    prune_modes: PruneModes,
+}
+
+impl DiskUsageEstimator {
+    /// Creates a new estimator for the given prune configuration.
+    pub const fn new(prune_modes: PruneModes) -> Self {
+        Self { prune_modes }
+    }
+
+    /// Returns the estimated disk usage of the data that the stage writes for the given blocks,
+    /// without the data that is pruned once the chain is synced to the end of the range.
+    pub fn estimate(&self, stage: StageId, blocks: RangeInclusive<BlockNumber>) -> DiskUsage {
+        let mut usage = DiskUsage::default();
+        if blocks.is_empty() {
+            return usage
+        }
+
+        for component in components(stage) {
+            let retained = retained_blocks((component.prune_mode)(&self.prune_modes), &blocks);
+            let bytes = component.bytes_per_block.saturating_mul(retained);
+            match component.storage {
+                Storage::Database => usage.database = usage.database.saturating_add(bytes),
+                Storage::StaticFiles => {
+                    usage.static_files = usage.static_files.saturating_add(bytes)
+                }
+            }
+        }
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_with_pruning() {
+        let blocks = 1..=1_000_000;
+        let archive = DiskUsageEstimator::default();
+        assert_eq!(
+            archive.estimate(StageId::Headers, blocks.clone()),
+            DiskUsage { database: 0, static_files: 700_000_000 }
+        );
+        assert_eq!(archive.estimate(StageId::Finish, blocks.clone()), DiskUsage::default());
+        assert_eq!(archive.estimate(StageId::Headers, 10..=9), DiskUsage::default());
+
+        let pruned = DiskUsageEstimator::new(PruneModes {
+            sender_recovery: Some(PruneMode::Full),
+            transaction_lookup: Some(PruneMode::Distance(99)),
+            receipts: Some(PruneMode::Before(900_001)),
+            ..PruneModes::none()
+        });
+        assert_eq!(pruned.estimate(StageId::SenderRecovery, blocks.clone()), DiskUsage::default());
+        assert_eq!(
+            pruned.estimate(StageId::TransactionLookup, blocks.clone()).database,
+            100 * 8_000
+        );
+        assert_eq!(
+            pruned.estimate(StageId::Execution, blocks.clone()).static_files,
+            100_000 * 12_000
+        );
+        assert_eq!(
+            pruned.estimate(StageId::Execution, blocks.clone()).database,
+            archive.estimate(StageId::Execution, blocks).database
+        );
+
+        // ranges that end before the first retained block are pruned entirely
+        assert_eq!(pruned.estimate(StageId::Execution, 1..=900_000).static_files, 0);
+    }
+}
@@ -17,6 +17,12 @@ pub struct ExecutionStageThresholds {
     pub max_cumulative_gas: Option<u64>,
     /// The maximum spent on blocks processing before the execution stage commits.
     pub max_duration: Option<Duration>,
+    /// The amount of gas of a single block to execute before the execution stage commits the
+    /// progress within the block.
+    ///
+    /// Blocks that use more gas are executed in parts, so an interrupted execution resumes from
+    /// the last part instead of the start of the block.
+    pub block_checkpoint_gas: Option<u64>,
 }
 
 impl Default for ExecutionStageThresholds {
@@ -28,6 +34,7 @@ impl Default for ExecutionStageThresholds {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            block_checkpoint_gas: None,
         }
     }
 }
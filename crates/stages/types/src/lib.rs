@@ -22,6 +22,9 @@ pub use checkpoints::{
 mod execution;
 pub use execution::*;
 
+mod disk;
+pub use disk::{DiskSpacePolicy, DiskUsage, DiskUsageEstimator};
+
 /// Direction and target block for pipeline operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PipelineTarget {
@@ -0,0 +1,119 @@
+use crate::{ChainArrowError, ChainTable};
+use reth_primitives::{
+    Address, BlockNumber, InternalTransfer, Receipt, SealedHeader, TransactionSigned,
+    TransactionSignedNoHash,
+};
+use reth_storage_api::{BlockReader, InternalTransfersProvider};
+use reth_storage_errors::provider::ProviderError;
+use std::ops::RangeInclusive;
+
+/// Chain data of a contiguous range of blocks.
+///
+/// Transactions, senders, receipts and internal transfers are only read if a table requires them.
+#[derive(Debug, Default)]
+pub struct ChainBatch {
+    /// The headers of the blocks.
+    pub(crate) headers: Vec<SealedHeader>,
+    /// The number of transactions of each block.
+    pub(crate) transaction_counts: Vec<u64>,
+    /// The transactions of all blocks.
+    pub(crate) transactions: Vec<TransactionSigned>,
+    /// The senders of all transactions.
+    pub(crate) senders: Vec<Address>,
+    /// The receipts of all transactions.
+    pub(crate) receipts: Vec<Receipt>,
+    /// The internal transfers of all transactions.
+    pub(crate) internal_transfers: Vec<Vec<InternalTransfer>>,
+}
+
+impl ChainBatch {
+    /// Reads the data of the given blocks that is required by the tables.
+    ///
+    /// Returns [`ChainArrowError::MissingData`] if receipts are required but have been pruned, or
+    /// if internal transfers are required but the blocks are not indexed.
+    pub fn read<P: BlockReader + InternalTransfersProvider>(
+        provider: &P,
+        range: RangeInclusive<BlockNumber>,
+        tables: &[ChainTable],
+    ) -> Result<Self, ChainArrowError> {
+        let missing = |data| ChainArrowError::MissingData { data, range: range.clone() };
+
+        let headers = provider.sealed_headers_range(range.clone())?;
+        if headers.len() as u64 != range.end() - range.start() + 1 {
+            return Err(missing("headers"))
+        }
+
+        let indices = range
+            .clone()
+            .map(|number| {
+                provider
+                    .block_body_indices(number)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(number))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let transaction_counts = indices.iter().map(|indices| indices.tx_count()).collect();
+
+        let mut batch = Self { headers, transaction_counts, ..Default::default() };
+        if tables.iter().all(|table| *table == ChainTable::Blocks) {
+            return Ok(batch)
+        }
+
+        let tx_range = indices.first().map_or(0, |indices| indices.first_tx_num())..
+            indices.last().map_or(0, |indices| indices.next_tx_num());
+        batch.transactions = provider
+            .transactions_by_tx_range(tx_range.clone())?
+            .into_iter()
+            .map(TransactionSignedNoHash::with_hash)
+            .collect();
+        if batch.transactions.len() as u64 != tx_range.end - tx_range.start {
+            return Err(missing("transactions"))
+        }
+
+        if tables.contains(&ChainTable::Transactions) {
+            batch.senders = TransactionSigned::recover_signers_unchecked(
+                &batch.transactions,
+                batch.transactions.len(),
+            )
+            .ok_or_else(|| ChainArrowError::SenderRecovery(range.clone()))?;
+        }
+
+        if tables.contains(&ChainTable::Receipts) || tables.contains(&ChainTable::Logs) {
+            batch.receipts = provider.receipts_by_tx_range(tx_range)?;
+            if batch.receipts.len() != batch.transactions.len() {
+                return Err(missing("receipts"))
+            }
+        }
+
+        if tables.contains(&ChainTable::Traces) {
+            for number in range.clone() {
+                let transfers = provider
+                    .internal_transfers_by_block(number.into())?
+                    .ok_or_else(|| missing("internal transfers"))?;
+                batch.internal_transfers.extend(transfers);
+            }
+            if batch.internal_transfers.len() != batch.transactions.len() {
+                return Err(missing("internal transfers"))
+            }
+        }
+
+        Ok(batch)
+    }
+
+    /// Returns the number of blocks in the batch.
+    pub fn len(&self) -> usize {
+        self.headers.len()
+    }
+
+    /// Returns `true` if the batch contains no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+
+    /// Returns the block number and the index in the block of every transaction.
+    pub(crate) fn transaction_positions(&self) -> impl Iterator<Item = (BlockNumber, u64)> + '_ {
+        self.headers
+            .iter()
+            .zip(&self.transaction_counts)
+            .flat_map(|(header, count)| (0..*count).map(move |index| (header.number, index)))
+    }
+}
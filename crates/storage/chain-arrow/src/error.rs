@@ -0,0 +1,26 @@
+use arrow_schema::ArrowError;
+use reth_primitives::BlockNumber;
+use reth_storage_errors::provider::ProviderError;
+use std::ops::RangeInclusive;
+
+/// Errors that can occur when converting chain data into arrow record batches.
+#[derive(Debug, thiserror::Error)]
+pub enum ChainArrowError {
+    /// Error reading chain data from the provider.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// Error building a record batch.
+    #[error(transparent)]
+    Arrow(#[from] ArrowError),
+    /// Some data of the blocks is not available, e.g. because it was pruned.
+    #[error("{data} of blocks {range:?} are missing, they may have been pruned")]
+    MissingData {
+        /// The kind of data that is missing.
+        data: &'static str,
+        /// The range of blocks.
+        range: RangeInclusive<BlockNumber>,
+    },
+    /// The sender of a transaction could not be recovered.
+    #[error("failed to recover senders of blocks {0:?}")]
+    SenderRecovery(RangeInclusive<BlockNumber>),
+}
@@ -0,0 +1,21 @@
+//! Arrow schemas and record batches of chain data.
+//!
+//! Chain data is read from the provider in [`ChainBatch`]es of contiguous blocks, which are
+//! converted into arrow record batches of the [`ChainTable`]s. The schemas of the tables are
+//! stable, so that datasets produced by different versions can be queried together.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod batch;
+mod error;
+mod table;
+
+pub use batch::ChainBatch;
+pub use error::ChainArrowError;
+pub use table::ChainTable;
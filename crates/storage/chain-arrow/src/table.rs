@@ -0,0 +1,389 @@
+use crate::{ChainArrowError, ChainBatch};
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, FixedSizeBinaryArray, RecordBatch, StringArray,
+    UInt64Array, UInt8Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use std::sync::Arc;
+
+/// Length of a hash column.
+const HASH_LEN: i32 = 32;
+
+/// Length of an address column.
+const ADDRESS_LEN: i32 = 20;
+
+/// Tables of chain data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ChainTable {
+    /// One row per block header.
+    Blocks,
+    /// One row per transaction.
+    Transactions,
+    /// One row per transaction receipt.
+    Receipts,
+    /// One row per log emitted by a transaction.
+    Logs,
+    /// One row per internal ETH transfer of a transaction, only available for blocks that were
+    /// executed with internal transfers indexed.
+    Traces,
+}
+
+impl ChainTable {
+    /// All tables.
+    pub const ALL: [Self; 5] =
+        [Self::Blocks, Self::Transactions, Self::Receipts, Self::Logs, Self::Traces];
+
+    /// The name of the table.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Blocks => "blocks",
+            Self::Transactions => "transactions",
+            Self::Receipts => "receipts",
+            Self::Logs => "logs",
+            Self::Traces => "traces",
+        }
+    }
+
+    /// The name of the column that contains the block number of a row.
+    pub const fn block_number_column(&self) -> &'static str {
+        match self {
+            Self::Blocks => "number",
+            Self::Transactions | Self::Receipts | Self::Logs | Self::Traces => "block_number",
+        }
+    }
+
+    /// The schema of the table.
+    pub fn schema(&self) -> SchemaRef {
+        let hash = || DataType::FixedSizeBinary(HASH_LEN);
+        let address = || DataType::FixedSizeBinary(ADDRESS_LEN);
+        let fields = match self {
+            Self::Blocks => vec![
+                Field::new("number", DataType::UInt64, false),
+                Field::new("hash", hash(), false),
+                Field::new("parent_hash", hash(), false),
+                Field::new("timestamp", DataType::UInt64, false),
+                Field::new("miner", address(), false),
+                Field::new("gas_used", DataType::UInt64, false),
+                Field::new("gas_limit", DataType::UInt64, false),
+                Field::new("base_fee_per_gas", DataType::UInt64, true),
+                Field::new("blob_gas_used", DataType::UInt64, true),
+                Field::new("excess_blob_gas", DataType::UInt64, true),
+                Field::new("state_root", hash(), false),
+                Field::new("transactions_root", hash(), false),
+                Field::new("receipts_root", hash(), false),
+                Field::new("extra_data", DataType::Binary, false),
+                Field::new("transaction_count", DataType::UInt64, false),
+            ],
+            Self::Transactions => vec![
+                Field::new("block_number", DataType::UInt64, false),
+                Field::new("transaction_index", DataType::UInt64, false),
+                Field::new("hash", hash(), false),
+                Field::new("type", DataType::UInt8, false),
+                Field::new("from", address(), false),
+                Field::new("to", address(), true),
+                Field::new("nonce", DataType::UInt64, false),
+                Field::new("value", DataType::Utf8, false),
+                Field::new("gas_limit", DataType::UInt64, false),
+                Field::new("gas_price", DataType::Utf8, false),
+                Field::new("max_fee_per_gas", DataType::Utf8, true),
+                Field::new("max_priority_fee_per_gas", DataType::Utf8, true),
+                Field::new("max_fee_per_blob_gas", DataType::Utf8, true),
+                Field::new("chain_id", DataType::UInt64, true),
+                Field::new("input", DataType::Binary, false),
+            ],
+            Self::Receipts => vec![
+                Field::new("block_number", DataType::UInt64, false),
+                Field::new("transaction_index", DataType::UInt64, false),
+                Field::new("transaction_hash", hash(), false),
+                Field::new("type", DataType::UInt8, false),
+                Field::new("success", DataType::Boolean, false),
+                Field::new("gas_used", DataType::UInt64, false),
+                Field::new("cumulative_gas_used", DataType::UInt64, false),
+            ],
+            Self::Logs => vec![
+                Field::new("block_number", DataType::UInt64, false),
+                Field::new("transaction_index", DataType::UInt64, false),
+                Field::new("transaction_hash", hash(), false),
+                Field::new("log_index", DataType::UInt64, false),
+                Field::new("address", address(), false),
+                Field::new("topic0", hash(), true),
+                Field::new("topic1", hash(), true),
+                Field::new("topic2", hash(), true),
+                Field::new("topic3", hash(), true),
+                Field::new("data", DataType::Binary, false),
+            ],
+            Self::Traces => vec![
+                Field::new("block_number", DataType::UInt64, false),
+                Field::new("transaction_index", DataType::UInt64, false),
+                Field::new("transaction_hash", hash(), false),
+                Field::new("trace_index", DataType::UInt64, false),
+                Field::new("from", address(), false),
+                Field::new("to", address(), false),
+                Field::new("value", DataType::Utf8, false),
+            ],
+        };
+        Arc::new(Schema::new(fields))
+    }
+
+    /// Converts the batch of chain data into a record batch of the table.
+    pub fn record_batch(&self, batch: &ChainBatch) -> Result<RecordBatch, ChainArrowError> {
+        let columns = match self {
+            Self::Blocks => blocks_columns(batch)?,
+            Self::Transactions => transactions_columns(batch)?,
+            Self::Receipts => receipts_columns(batch)?,
+            Self::Logs => logs_columns(batch)?,
+            Self::Traces => traces_columns(batch)?,
+        };
+        Ok(RecordBatch::try_new(self.schema(), columns)?)
+    }
+}
+
+fn blocks_columns(batch: &ChainBatch) -> Result<Vec<ArrayRef>, ChainArrowError> {
+    let headers = &batch.headers;
+    Ok(vec![
+        Arc::new(UInt64Array::from_iter_values(headers.iter().map(|header| header.number))),
+        fixed_bytes(HASH_LEN, headers.iter().map(|header| Some(header.hash())))?,
+        fixed_bytes(HASH_LEN, headers.iter().map(|header| Some(header.parent_hash)))?,
+        Arc::new(UInt64Array::from_iter_values(headers.iter().map(|header| header.timestamp))),
+        fixed_bytes(ADDRESS_LEN, headers.iter().map(|header| Some(header.beneficiary)))?,
+        Arc::new(UInt64Array::from_iter_values(headers.iter().map(|header| header.gas_used))),
+        Arc::new(UInt64Array::from_iter_values(headers.iter().map(|header| header.gas_limit))),
+        Arc::new(UInt64Array::from_iter(headers.iter().map(|header| header.base_fee_per_gas))),
+        Arc::new(UInt64Array::from_iter(headers.iter().map(|header| header.blob_gas_used))),
+        Arc::new(UInt64Array::from_iter(headers.iter().map(|header| header.excess_blob_gas))),
+        fixed_bytes(HASH_LEN, headers.iter().map(|header| Some(header.state_root)))?,
+        fixed_bytes(HASH_LEN, headers.iter().map(|header| Some(header.transactions_root)))?,
+        fixed_bytes(HASH_LEN, headers.iter().map(|header| Some(header.receipts_root)))?,
+        Arc::new(BinaryArray::from_iter_values(headers.iter().map(|header| &header.extra_data))),
+        Arc::new(UInt64Array::from_iter_values(batch.transaction_counts.iter().copied())),
+    ])
+}
+
+fn transactions_columns(batch: &ChainBatch) -> Result<Vec<ArrayRef>, ChainArrowError> {
+    let txs = &batch.transactions;
+    let base_fees = batch
+        .headers
+        .iter()
+        .zip(&batch.transaction_counts)
+        .flat_map(|(header, count)| (0..*count).map(|_| header.base_fee_per_gas));
+    let (block_numbers, indices): (Vec<_>, Vec<_>) = batch.transaction_positions().unzip();
+    let fee = |fee: Option<u128>| fee.map(|fee| fee.to_string());
+
+    Ok(vec![
+        Arc::new(UInt64Array::from(block_numbers)),
+        Arc::new(UInt64Array::from(indices)),
+        fixed_bytes(HASH_LEN, txs.iter().map(|tx| Some(tx.hash())))?,
+        Arc::new(UInt8Array::from_iter_values(txs.iter().map(|tx| u8::from(tx.tx_type())))),
+        fixed_bytes(ADDRESS_LEN, batch.senders.iter().map(Some))?,
+        fixed_bytes(ADDRESS_LEN, txs.iter().map(|tx| tx.to()))?,
+        Arc::new(UInt64Array::from_iter_values(txs.iter().map(|tx| tx.nonce()))),
+        Arc::new(StringArray::from_iter_values(txs.iter().map(|tx| tx.value().to_string()))),
+        Arc::new(UInt64Array::from_iter_values(txs.iter().map(|tx| tx.gas_limit()))),
+        Arc::new(StringArray::from_iter_values(
+            txs.iter()
+                .zip(base_fees)
+                .map(|(tx, base_fee)| tx.effective_gas_price(base_fee).to_string()),
+        )),
+        Arc::new(StringArray::from_iter(
+            txs.iter().map(|tx| fee(tx.is_dynamic_fee().then(|| tx.max_fee_per_gas()))),
+        )),
+        Arc::new(StringArray::from_iter(txs.iter().map(|tx| fee(tx.max_priority_fee_per_gas())))),
+        Arc::new(StringArray::from_iter(txs.iter().map(|tx| fee(tx.max_fee_per_blob_gas())))),
+        Arc::new(UInt64Array::from_iter(txs.iter().map(|tx| tx.chain_id()))),
+        Arc::new(BinaryArray::from_iter_values(txs.iter().map(|tx| tx.input()))),
+    ])
+}
+
+fn receipts_columns(batch: &ChainBatch) -> Result<Vec<ArrayRef>, ChainArrowError> {
+    let receipts = &batch.receipts;
+    let (block_numbers, indices): (Vec<_>, Vec<_>) = batch.transaction_positions().unzip();
+    // the cumulative gas used resets with every block
+    let gas_used = indices
+        .iter()
+        .zip(receipts.iter().enumerate())
+        .map(|(index, (position, receipt))| {
+            let previous = if *index == 0 { 0 } else { receipts[position - 1].cumulative_gas_used };
+            receipt.cumulative_gas_used - previous
+        })
+        .collect::<Vec<_>>();
+
+    Ok(vec![
+        Arc::new(UInt64Array::from(block_numbers)),
+        Arc::new(UInt64Array::from(indices)),
+        fixed_bytes(HASH_LEN, batch.transactions.iter().map(|tx| Some(tx.hash())))?,
+        Arc::new(UInt8Array::from_iter_values(
+            receipts.iter().map(|receipt| u8::from(receipt.tx_type)),
+        )),
+        Arc::new(BooleanArray::from_iter(receipts.iter().map(|receipt| Some(receipt.success)))),
+        Arc::new(UInt64Array::from(gas_used)),
+        Arc::new(UInt64Array::from_iter_values(
+            receipts.iter().map(|receipt| receipt.cumulative_gas_used),
+        )),
+    ])
+}
+
+fn logs_columns(batch: &ChainBatch) -> Result<Vec<ArrayRef>, ChainArrowError> {
+    let mut log_index = 0;
+    let mut previous_block = None;
+    let logs = batch
+        .transaction_positions()
+        .zip(batch.transactions.iter().zip(&batch.receipts))
+        .flat_map(|((block_number, index), (tx, receipt))| {
+            receipt.logs.iter().map(move |log| (block_number, index, tx.hash(), log))
+        })
+        .map(|(block_number, index, hash, log)| {
+            // log indices are counted per block
+            if previous_block != Some(block_number) {
+                previous_block = Some(block_number);
+                log_index = 0;
+            }
+            log_index += 1;
+            (block_number, index, hash, log_index - 1, log)
+        })
+        .collect::<Vec<_>>();
+    let topic =
+        |n: usize| fixed_bytes(HASH_LEN, logs.iter().map(|(.., log)| log.topics().get(n).copied()));
+
+    Ok(vec![
+        Arc::new(UInt64Array::from_iter_values(logs.iter().map(|(number, ..)| *number))),
+        Arc::new(UInt64Array::from_iter_values(logs.iter().map(|(_, index, ..)| *index))),
+        fixed_bytes(HASH_LEN, logs.iter().map(|(_, _, hash, ..)| Some(*hash)))?,
+        Arc::new(UInt64Array::from_iter_values(logs.iter().map(|(.., log_index, _)| *log_index))),
+        fixed_bytes(ADDRESS_LEN, logs.iter().map(|(.., log)| Some(log.address)))?,
+        topic(0)?,
+        topic(1)?,
+        topic(2)?,
+        topic(3)?,
+        Arc::new(BinaryArray::from_iter_values(logs.iter().map(|(.., log)| &log.data.data))),
+    ])
+}
+
+fn traces_columns(batch: &ChainBatch) -> Result<Vec<ArrayRef>, ChainArrowError> {
+    // trace indices are counted per transaction
+    let traces = batch
+        .transaction_positions()
+        .zip(batch.transactions.iter().zip(&batch.internal_transfers))
+        .flat_map(|((block_number, index), (tx, transfers))| {
+            transfers.iter().enumerate().map(move |(trace_index, transfer)| {
+                (block_number, index, tx.hash(), trace_index as u64, transfer)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(vec![
+        Arc::new(UInt64Array::from_iter_values(traces.iter().map(|(number, ..)| *number))),
+        Arc::new(UInt64Array::from_iter_values(traces.iter().map(|(_, index, ..)| *index))),
+        fixed_bytes(HASH_LEN, traces.iter().map(|(_, _, hash, ..)| Some(*hash)))?,
+        Arc::new(UInt64Array::from_iter_values(
+            traces.iter().map(|(.., trace_index, _)| *trace_index),
+        )),
+        fixed_bytes(ADDRESS_LEN, traces.iter().map(|(.., transfer)| Some(transfer.from)))?,
+        fixed_bytes(ADDRESS_LEN, traces.iter().map(|(.., transfer)| Some(transfer.to)))?,
+        Arc::new(StringArray::from_iter_values(
+            traces.iter().map(|(.., transfer)| transfer.value.to_string()),
+        )),
+    ])
+}
+
+/// Builds a fixed size binary column, `None` values are stored as nulls.
+fn fixed_bytes<T: AsRef<[u8]>>(
+    size: i32,
+    values: impl IntoIterator<Item = Option<T>>,
+) -> Result<ArrayRef, ChainArrowError> {
+    Ok(Arc::new(FixedSizeBinaryArray::try_from_sparse_iter_with_size(values.into_iter(), size)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        Address, Header, InternalTransfer, Log, LogData, Receipt, Signature, Transaction,
+        TransactionSigned, TxEip1559, TxKind, TxType, B256, U256,
+    };
+
+    #[test]
+    #[allow(clippy::needless_update)]
+    fn record_batches_match_schema() {
+        let header = |number: u64| {
+            Header { number, base_fee_per_gas: Some(7), ..Default::default() }.seal_slow()
+        };
+        let tx = |nonce: u64| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::Eip1559(TxEip1559 {
+                    chain_id: 1,
+                    nonce,
+                    gas_limit: 21_000,
+                    max_fee_per_gas: 10,
+                    max_priority_fee_per_gas: 2,
+                    to: TxKind::Call(Address::with_last_byte(1)),
+                    value: U256::from(1),
+                    ..Default::default()
+                }),
+                Signature::default(),
+            )
+        };
+        let receipt = |cumulative_gas_used: u64, logs: usize| Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used,
+            logs: vec![
+                Log {
+                    address: Address::with_last_byte(2),
+                    data: LogData::new_unchecked(vec![B256::with_last_byte(3)], Default::default()),
+                };
+                logs
+            ],
+            ..Default::default()
+        };
+
+        let batch = ChainBatch {
+            headers: vec![header(1), header(2), header(3)],
+            transaction_counts: vec![2, 0, 1],
+            transactions: vec![tx(0), tx(1), tx(2)],
+            senders: vec![Address::with_last_byte(4); 3],
+            receipts: vec![receipt(21_000, 1), receipt(42_000, 2), receipt(21_000, 1)],
+            internal_transfers: vec![
+                vec![InternalTransfer::default(); 2],
+                vec![],
+                vec![InternalTransfer::default()],
+            ],
+        };
+
+        let blocks = ChainTable::Blocks.record_batch(&batch).unwrap();
+        assert_eq!(blocks.num_rows(), 3);
+
+        let transactions = ChainTable::Transactions.record_batch(&batch).unwrap();
+        assert_eq!(transactions.num_rows(), 3);
+        let gas_price = transactions
+            .column_by_name("gas_price")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>();
+        assert_eq!(gas_price.unwrap().value(0), "9");
+
+        let receipts = ChainTable::Receipts.record_batch(&batch).unwrap();
+        let gas_used =
+            receipts.column_by_name("gas_used").unwrap().as_any().downcast_ref::<UInt64Array>();
+        assert_eq!(gas_used.unwrap().values(), &[21_000, 21_000, 21_000]);
+
+        let logs = ChainTable::Logs.record_batch(&batch).unwrap();
+        let log_index =
+            logs.column_by_name("log_index").unwrap().as_any().downcast_ref::<UInt64Array>();
+        assert_eq!(log_index.unwrap().values(), &[0, 1, 2, 0]);
+        assert_eq!(logs.column_by_name("topic1").unwrap().null_count(), 4);
+
+        let traces = ChainTable::Traces.record_batch(&batch).unwrap();
+        let trace_index =
+            traces.column_by_name("trace_index").unwrap().as_any().downcast_ref::<UInt64Array>();
+        assert_eq!(trace_index.unwrap().values(), &[0, 1, 0]);
+    }
+
+    #[test]
+    fn empty_batches_match_schema() {
+        for table in ChainTable::ALL {
+            let batch = table.record_batch(&ChainBatch::default()).unwrap();
+            assert_eq!(batch.num_rows(), 0);
+        }
+    }
+}
@@ -1,7 +1,10 @@
 //! Block related models and types.
 
 use reth_codecs::{add_arbitrary_tests, Compact};
-use reth_primitives::{Header, Withdrawals, B256};
+use reth_primitives::{
+    Address, Header, Requests, SealedBlock, SealedBlockWithSenders, TransactionSignedNoHash,
+    Withdrawals, B256,
+};
 use serde::{Deserialize, Serialize};
 
 /// The storage representation of a block's ommers.
@@ -24,6 +27,58 @@ pub struct StoredBlockWithdrawals {
     pub withdrawals: Withdrawals,
 }
 
+/// The storage representation of a validated block that is not part of the canonical chain.
+///
+/// The hash of the block is not stored, since fork blocks are keyed by their hash.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredForkBlock {
+    /// The transactions of the block.
+    pub transactions: Vec<TransactionSignedNoHash>,
+    /// The block headers of this block's uncles.
+    pub ommers: Vec<Header>,
+    /// The block withdrawals.
+    pub withdrawals: Option<Withdrawals>,
+    /// The block requests.
+    pub requests: Option<Requests>,
+    /// The senders of the transactions of the block.
+    pub senders: Vec<Address>,
+    /// The block header.
+    pub header: Header,
+}
+
+impl StoredForkBlock {
+    /// Converts the stored block into a [`SealedBlockWithSenders`] with the given hash.
+    ///
+    /// Returns `None` if the number of senders doesn't match the number of transactions.
+    pub fn into_sealed_block_with_senders(self, hash: B256) -> Option<SealedBlockWithSenders> {
+        let block = SealedBlock {
+            header: self.header.seal(hash),
+            body: self.transactions.into_iter().map(TransactionSignedNoHash::with_hash).collect(),
+            ommers: self.ommers,
+            withdrawals: self.withdrawals,
+            requests: self.requests,
+        };
+        SealedBlockWithSenders::new(block, self.senders)
+    }
+}
+
+impl From<SealedBlockWithSenders> for StoredForkBlock {
+    fn from(block: SealedBlockWithSenders) -> Self {
+        let SealedBlockWithSenders { block, senders } = block;
+        let SealedBlock { header, body, ommers, withdrawals, requests } = block;
+        Self {
+            header: header.unseal(),
+            transactions: body.into_iter().map(Into::into).collect(),
+            ommers,
+            withdrawals,
+            requests,
+            senders,
+        }
+    }
+}
+
 /// Hash of the block header.
 pub type HeaderHash = B256;
 
@@ -42,4 +97,29 @@ mod tests {
             StoredBlockOmmers::decompress::<Vec<_>>(ommer.compress()).unwrap()
         );
     }
+
+    #[test]
+    fn test_fork_block() {
+        let header = Header { number: 1, ..Default::default() }.seal_slow();
+        let transaction = TransactionSignedNoHash::default().with_hash();
+        let block = SealedBlockWithSenders {
+            block: SealedBlock {
+                header,
+                body: vec![transaction],
+                withdrawals: Some(Withdrawals::default()),
+                ..Default::default()
+            },
+            senders: vec![Address::random()],
+        };
+
+        let stored = StoredForkBlock::from(block.clone());
+        let decompressed =
+            StoredForkBlock::decompress::<Vec<_>>(stored.clone().compress()).unwrap();
+        assert_eq!(stored, decompressed);
+        assert_eq!(decompressed.clone().into_sealed_block_with_senders(block.hash()), Some(block));
+
+        // the senders must match the transactions
+        let stored = StoredForkBlock { senders: vec![], ..decompressed };
+        assert_eq!(stored.into_sealed_block_with_senders(B256::ZERO), None);
+    }
 }
@@ -0,0 +1,14 @@
+//! Internal transfer related models and types.
+
+use reth_codecs::{add_arbitrary_tests, Compact};
+use reth_primitives::InternalTransfer;
+use serde::{Deserialize, Serialize};
+
+/// The storage representation of the internal ETH transfers of a transaction.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredInternalTransfers {
+    /// The internal transfers of the transaction, in the order they were made.
+    pub transfers: Vec<InternalTransfer>,
+}
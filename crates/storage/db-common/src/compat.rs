@@ -0,0 +1,224 @@
+//! Compatibility checks of an existing datadir with the running binary.
+
+use reth_chainspec::ChainSpec;
+use reth_db::{
+    is_database_empty,
+    mdbx::DatabaseArguments,
+    open_db_read_only, tables,
+    version::{get_db_version, DatabaseVersionError, DB_VERSION},
+};
+use reth_db_api::{database::Database, transaction::DbTx};
+use reth_nippy_jar::{NippyJar, NIPPY_JAR_VERSION};
+use reth_primitives::{static_file::SegmentHeader, StaticFileSegment, B256};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// An incompatibility of the datadir with the running binary.
+#[derive(Debug, thiserror::Error)]
+pub enum DatadirIncompatibility {
+    /// The database was written with a different database version.
+    #[error(
+        "database version is v{found}, but this version of reth requires v{expected}: \
+         the database must be migrated or resynced from scratch"
+    )]
+    DatabaseVersion {
+        /// The version of the database.
+        found: u64,
+        /// The version of the database required by the binary.
+        expected: u64,
+    },
+    /// The version of the database couldn't be determined.
+    #[error("{0}: restore the database version file or resync from scratch")]
+    UnknownDatabaseVersion(DatabaseVersionError),
+    /// A static file was written with a different static file format version.
+    #[error(
+        "static file {path} has format version v{found}, but this version of reth requires \
+         v{expected}: the static files must be regenerated"
+    )]
+    StaticFileVersion {
+        /// The path of the static file.
+        path: PathBuf,
+        /// The format version of the static file.
+        found: usize,
+        /// The format version required by the binary.
+        expected: usize,
+    },
+    /// The configuration of a static file couldn't be read.
+    #[error("static file {path} can't be read: {err}: the static files must be regenerated")]
+    UnreadableStaticFile {
+        /// The path of the static file.
+        path: PathBuf,
+        /// The error of reading the configuration.
+        err: String,
+    },
+    /// The genesis block of the database doesn't belong to the chain of the chainspec.
+    #[error(
+        "genesis hash in the database is {database}, but the chainspec has {chain_spec}: \
+         the datadir belongs to a different chain, use a different datadir or chainspec"
+    )]
+    GenesisHash {
+        /// The genesis hash in the database.
+        database: B256,
+        /// The genesis hash of the chainspec.
+        chain_spec: B256,
+    },
+}
+
+/// The result of checking the compatibility of a datadir with [`check_datadir_compatibility`].
+#[derive(Debug, Default)]
+pub struct DatadirCompatibilityReport {
+    /// All incompatibilities that were found.
+    pub incompatibilities: Vec<DatadirIncompatibility>,
+}
+
+impl DatadirCompatibilityReport {
+    /// Returns true if no incompatibilities were found.
+    pub fn is_compatible(&self) -> bool {
+        self.incompatibilities.is_empty()
+    }
+}
+
+impl fmt::Display for DatadirCompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_compatible() {
+            return write!(f, "datadir is compatible")
+        }
+
+        write!(f, "datadir is incompatible with this version of reth:")?;
+        for incompatibility in &self.incompatibilities {
+            write!(f, "\n  - {incompatibility}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks whether the existing datadir can be used by this version of reth, before it's opened
+/// for writing.
+///
+/// Checks the version of the database, the format versions of the static files and whether the
+/// genesis block of the database matches the chainspec. All incompatibilities are collected in
+/// the report, instead of failing on the first one. A database that doesn't exist yet is
+/// compatible.
+pub fn check_datadir_compatibility(
+    db_path: &Path,
+    static_files_path: &Path,
+    chain_spec: &ChainSpec,
+    db_args: DatabaseArguments,
+) -> eyre::Result<DatadirCompatibilityReport> {
+    let mut report = DatadirCompatibilityReport::default();
+
+    if !is_database_empty(db_path) {
+        match get_db_version(db_path) {
+            Ok(found) if found != DB_VERSION => report
+                .incompatibilities
+                .push(DatadirIncompatibility::DatabaseVersion { found, expected: DB_VERSION }),
+            Ok(_) => {
+                // the genesis hash can only be read if the database has a known layout
+                let db = open_db_read_only(db_path, db_args)?;
+                let genesis_hash = db.view(|tx| tx.get::<tables::CanonicalHeaders>(0))??;
+                if let Some(database) = genesis_hash {
+                    let chain_spec = chain_spec.genesis_hash();
+                    if database != chain_spec {
+                        report
+                            .incompatibilities
+                            .push(DatadirIncompatibility::GenesisHash { database, chain_spec });
+                    }
+                }
+            }
+            Err(err) => {
+                report.incompatibilities.push(DatadirIncompatibility::UnknownDatabaseVersion(err))
+            }
+        }
+    }
+
+    report.incompatibilities.extend(check_static_file_versions(static_files_path)?);
+
+    Ok(report)
+}
+
+/// Checks the format versions of all static files in the directory.
+fn check_static_file_versions(path: &Path) -> eyre::Result<Vec<DatadirIncompatibility>> {
+    let mut incompatibilities = Vec::new();
+    if !path.exists() {
+        return Ok(incompatibilities)
+    }
+
+    let mut paths = reth_fs_util::read_dir(path)?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.metadata().map_or(false, |metadata| metadata.is_file()))
+        .filter(|entry| {
+            StaticFileSegment::parse_filename(&entry.file_name().to_string_lossy()).is_some()
+        })
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    paths.sort_unstable();
+
+    for path in paths {
+        match NippyJar::<SegmentHeader>::load(&path) {
+            Ok(jar) if jar.version() != NIPPY_JAR_VERSION => {
+                incompatibilities.push(DatadirIncompatibility::StaticFileVersion {
+                    path,
+                    found: jar.version(),
+                    expected: NIPPY_JAR_VERSION,
+                })
+            }
+            Ok(_) => {}
+            Err(err) => incompatibilities
+                .push(DatadirIncompatibility::UnreadableStaticFile { path, err: err.to_string() }),
+        }
+    }
+
+    Ok(incompatibilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_db::version::db_version_file_path;
+    use reth_db_api::models::ClientVersion;
+    use std::fs;
+
+    #[test]
+    fn empty_datadir_is_compatible() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = check_datadir_compatibility(
+            &dir.path().join("db"),
+            &dir.path().join("static_files"),
+            &MAINNET,
+            DatabaseArguments::new(ClientVersion::default()),
+        )
+        .unwrap();
+        assert!(report.is_compatible());
+    }
+
+    #[test]
+    fn report_database_version_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("db");
+        fs::create_dir_all(&db_path).unwrap();
+        fs::write(db_version_file_path(&db_path), "1").unwrap();
+
+        let static_files_path = dir.path().join("static_files");
+        fs::create_dir_all(&static_files_path).unwrap();
+        fs::write(static_files_path.join("static_file_headers_0_499999"), []).unwrap();
+
+        let report = check_datadir_compatibility(
+            &db_path,
+            &static_files_path,
+            &MAINNET,
+            DatabaseArguments::new(ClientVersion::default()),
+        )
+        .unwrap();
+        assert!(!report.is_compatible());
+        assert!(matches!(
+            report.incompatibilities.as_slice(),
+            [
+                DatadirIncompatibility::DatabaseVersion { found: 1, expected: DB_VERSION },
+                DatadirIncompatibility::UnreadableStaticFile { .. },
+            ]
+        ));
+    }
+}
@@ -1,23 +1,28 @@
 //! Reth genesis initialization utility functions.
 
 use alloy_genesis::GenesisAccount;
+use rayon::prelude::*;
 use reth_chainspec::ChainSpec;
 use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_db::tables;
-use reth_db_api::{database::Database, transaction::DbTxMut, DatabaseError};
+use reth_db_api::{
+    cursor::{DbCursorRW, DbDupCursorRO},
+    database::Database,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
 use reth_etl::Collector;
 use reth_primitives::{
-    Account, Address, Bytecode, Receipts, StaticFileSegment, StorageEntry, B256, U256,
+    keccak256, Account, Address, Bytecode, Receipts, StaticFileSegment, StorageEntry, B256, U256,
 };
 use reth_provider::{
     errors::provider::ProviderResult,
     providers::{StaticFileProvider, StaticFileWriter},
     writer::UnifiedStorageWriter,
     BlockHashReader, BlockNumReader, BundleStateInit, ChainSpecProvider, DatabaseProviderRW,
-    ExecutionOutcome, HashingWriter, HistoryWriter, OriginalValuesKnown, ProviderError,
-    ProviderFactory, RevertsInit, StageCheckpointWriter, StateWriter, StaticFileProviderFactory,
-    TrieWriter,
+    ExecutionOutcome, HistoryWriter, OriginalValuesKnown, ProviderError, ProviderFactory,
+    RevertsInit, StageCheckpointWriter, StateWriter, StaticFileProviderFactory, TrieWriter,
 };
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_trie::{IntermediateStateRootState, StateRoot as StateRootComputer, StateRootProgress};
@@ -28,7 +33,7 @@ use std::{
     io::BufRead,
     sync::Arc,
 };
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 /// Default soft limit for number of bytes to read from state dump file, before inserting into
 /// database.
@@ -44,6 +49,10 @@ pub const DEFAULT_SOFT_LIMIT_BYTE_LEN_ACCOUNTS_CHUNK: usize = 1_000_000_000;
 // account)
 pub const AVERAGE_COUNT_ACCOUNTS_PER_GB_STATE_DUMP: usize = 285_228;
 
+/// Maximum number of genesis alloc entries, i.e. accounts and storage slots, that are inserted in
+/// a single database transaction.
+const GENESIS_ALLOC_BATCH_SIZE: usize = 1_000_000;
+
 /// Soft limit for the number of flushed updates after which to log progress summary.
 const SOFT_LIMIT_COUNT_FLUSHED_UPDATES: usize = 1_000_000;
 
@@ -106,19 +115,14 @@ pub fn init_genesis<DB: Database>(factory: ProviderFactory<DB>) -> Result<B256,
 
     debug!("Writing genesis block.");
 
-    let alloc = &genesis.alloc;
-
-    // use transaction to insert genesis header
-    let provider_rw = factory.provider_rw()?;
-    insert_genesis_hashes(&provider_rw, alloc.iter())?;
-    insert_genesis_history(&provider_rw, alloc.iter())?;
+    // Large allocs are committed in batches, the returned transaction contains the last batch.
+    let alloc = genesis.alloc.iter().map(|(address, account)| (*address, account.clone()));
+    let provider_rw = insert_genesis_alloc(&factory, alloc, GENESIS_ALLOC_BATCH_SIZE)?;
 
     // Insert header
     let static_file_provider = factory.static_file_provider();
     insert_genesis_header(&provider_rw, &static_file_provider, chain.clone())?;
 
-    insert_genesis_state(&provider_rw, alloc.len(), alloc.iter())?;
-
     // insert sync stage
     for stage in StageId::ALL {
         provider_rw.save_stage_checkpoint(stage, Default::default())?;
@@ -138,6 +142,100 @@ pub fn init_genesis<DB: Database>(factory: ProviderFactory<DB>) -> Result<B256,
     Ok(hash)
 }
 
+/// Inserts the genesis alloc into the database in batches of at most `batch_size` accounts and
+/// storage slots.
+///
+/// The accounts are consumed from the iterator one batch at a time, so only the current batch is
+/// kept in memory. Every batch except the last one is committed in its own transaction, which keeps
+/// the size of the transactions bounded for huge allocs. The transaction containing the last batch
+/// is returned, so that the genesis header is committed together with it. An interrupted import is
+/// therefore detected by the missing genesis header, and its leftovers are cleared before the
+/// alloc is inserted again.
+fn insert_genesis_alloc<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    alloc: impl IntoIterator<Item = (Address, GenesisAccount)>,
+    batch_size: usize,
+) -> ProviderResult<DatabaseProviderRW<DB>> {
+    let mut provider_rw = factory.provider_rw()?;
+    clear_interrupted_genesis_alloc(&provider_rw)?;
+
+    let mut accounts = alloc.into_iter().peekable();
+    let total_accounts = accounts.size_hint().1;
+
+    let mut inserted_accounts = 0;
+    let mut committed_batches = 0;
+    let mut batch = Vec::new();
+    let mut batch_entries = 0;
+    while let Some((address, account)) = accounts.next() {
+        batch_entries += alloc_entries(&account);
+        batch.push((address, account));
+        if batch_entries < batch_size && accounts.peek().is_some() {
+            continue
+        }
+
+        let batch_iter = || batch.iter().map(|(address, account)| (address, account));
+        insert_genesis_hashes(&provider_rw, batch_iter())?;
+        insert_genesis_history(&provider_rw, batch_iter())?;
+        insert_genesis_state(&provider_rw, batch.len(), batch_iter())?;
+
+        inserted_accounts += batch.len();
+        let last_batch = accounts.peek().is_none();
+        if !last_batch || committed_batches > 0 {
+            match total_accounts {
+                Some(total_accounts) => info!(target: "reth::cli",
+                    inserted_accounts,
+                    total_accounts,
+                    progress = %format!("{:.2}%", inserted_accounts as f64 / total_accounts as f64 * 100.0),
+                    "Inserted genesis alloc batch"
+                ),
+                None => {
+                    info!(target: "reth::cli", inserted_accounts, "Inserted genesis alloc batch")
+                }
+            }
+        }
+
+        batch.clear();
+        batch_entries = 0;
+        if !last_batch {
+            provider_rw.commit()?;
+            provider_rw = factory.provider_rw()?;
+            committed_batches += 1;
+        }
+    }
+
+    Ok(provider_rw)
+}
+
+/// Returns the number of database entries of a genesis account, i.e. the account itself and its
+/// storage slots.
+fn alloc_entries(account: &GenesisAccount) -> usize {
+    1 + account.storage.as_ref().map_or(0, |storage| storage.len())
+}
+
+/// Clears the state written by a genesis import that was interrupted before the genesis header
+/// was committed.
+fn clear_interrupted_genesis_alloc<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+) -> ProviderResult<()> {
+    let tx = provider.tx_ref();
+    if tx.entries::<tables::PlainAccountState>()? == 0 {
+        return Ok(())
+    }
+
+    warn!(target: "reth::cli", "Found state of an interrupted genesis import, clearing it");
+    tx.clear::<tables::PlainAccountState>()?;
+    tx.clear::<tables::PlainStorageState>()?;
+    tx.clear::<tables::Bytecodes>()?;
+    tx.clear::<tables::HashedAccounts>()?;
+    tx.clear::<tables::HashedStorages>()?;
+    tx.clear::<tables::AccountChangeSets>()?;
+    tx.clear::<tables::StorageChangeSets>()?;
+    tx.clear::<tables::AccountsHistory>()?;
+    tx.clear::<tables::StoragesHistory>()?;
+
+    Ok(())
+}
+
 /// Inserts the genesis state into the database.
 pub fn insert_genesis_state<'a, 'b, DB: Database>(
     provider: &DatabaseProviderRW<DB>,
@@ -220,29 +318,55 @@ pub fn insert_state<'a, 'b, DB: Database>(
 }
 
 /// Inserts hashes for the genesis state.
+///
+/// Addresses and storage keys are hashed in parallel.
 pub fn insert_genesis_hashes<'a, 'b, DB: Database>(
     provider: &DatabaseProviderRW<DB>,
     alloc: impl Iterator<Item = (&'a Address, &'b GenesisAccount)> + Clone,
 ) -> ProviderResult<()> {
+    let alloc = alloc.collect::<Vec<_>>();
+
     // insert and hash accounts to hashing table
-    let alloc_accounts = alloc.clone().map(|(addr, account)| (*addr, Some(Account::from(account))));
-    provider.insert_account_for_hashing(alloc_accounts)?;
+    let mut hashed_accounts = alloc
+        .par_iter()
+        .map(|(address, account)| (keccak256(address), Account::from(*account)))
+        .collect::<Vec<_>>();
+    hashed_accounts.par_sort_unstable_by_key(|(hashed_address, _)| *hashed_address);
+
+    let mut hashed_accounts_cursor = provider.tx_ref().cursor_write::<tables::HashedAccounts>()?;
+    for (hashed_address, account) in hashed_accounts {
+        hashed_accounts_cursor.upsert(hashed_address, account)?;
+    }
 
     trace!(target: "reth::cli", "Inserted account hashes");
 
-    let alloc_storage = alloc.filter_map(|(addr, account)| {
-        // only return Some if there is storage
-        account.storage.as_ref().map(|storage| {
-            (
-                *addr,
-                storage
-                    .clone()
-                    .into_iter()
-                    .map(|(key, value)| StorageEntry { key, value: value.into() }),
-            )
+    let mut hashed_storages = alloc
+        .par_iter()
+        .filter_map(|(address, account)| account.storage.as_ref().map(|storage| (address, storage)))
+        .flat_map(|(address, storage)| {
+            let hashed_address = keccak256(address);
+            storage.par_iter().map(move |(key, value)| {
+                (hashed_address, keccak256(key), U256::from_be_bytes(value.0))
+            })
         })
-    });
-    provider.insert_storage_for_hashing(alloc_storage)?;
+        .collect::<Vec<_>>();
+    hashed_storages.par_sort_unstable_by_key(|(hashed_address, key, _)| (*hashed_address, *key));
+
+    let mut hashed_storage_cursor =
+        provider.tx_ref().cursor_dup_write::<tables::HashedStorages>()?;
+    for (hashed_address, key, value) in hashed_storages {
+        if hashed_storage_cursor
+            .seek_by_key_subkey(hashed_address, key)?
+            .filter(|entry| entry.key == key)
+            .is_some()
+        {
+            hashed_storage_cursor.delete_current()?;
+        }
+
+        if !value.is_zero() {
+            hashed_storage_cursor.upsert(hashed_address, StorageEntry { key, value })?;
+        }
+    }
 
     trace!(target: "reth::cli", "Inserted storage hashes");
 
@@ -654,4 +778,44 @@ mod tests {
             )],
         );
     }
+
+    #[test]
+    fn init_genesis_alloc_in_batches() {
+        let alloc = BTreeMap::from([
+            (
+                Address::with_last_byte(1),
+                GenesisAccount { balance: U256::from(1), ..Default::default() },
+            ),
+            (
+                Address::with_last_byte(2),
+                GenesisAccount {
+                    storage: Some(BTreeMap::from([
+                        (B256::with_last_byte(1), B256::with_last_byte(1)),
+                        (B256::with_last_byte(2), B256::with_last_byte(2)),
+                    ])),
+                    ..Default::default()
+                },
+            ),
+            (Address::with_last_byte(3), GenesisAccount { nonce: Some(1), ..Default::default() }),
+        ]);
+        let factory = create_test_provider_factory_with_chain_spec(MAINNET.clone());
+
+        // Interrupt the import before the last batch is committed
+        drop(insert_genesis_alloc(&factory, alloc.clone(), 1).unwrap());
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::PlainAccountState>().unwrap(), 2);
+        drop(provider);
+
+        // The leftovers of the interrupted import are cleared
+        insert_genesis_alloc(&factory, alloc, 1).unwrap().commit().unwrap();
+        let provider = factory.provider().unwrap();
+        let tx = provider.tx_ref();
+        assert_eq!(tx.entries::<tables::PlainAccountState>().unwrap(), 3);
+        assert_eq!(tx.entries::<tables::PlainStorageState>().unwrap(), 2);
+        assert_eq!(tx.entries::<tables::HashedAccounts>().unwrap(), 3);
+        assert_eq!(tx.entries::<tables::HashedStorages>().unwrap(), 2);
+        assert_eq!(tx.entries::<tables::AccountsHistory>().unwrap(), 3);
+        assert_eq!(tx.entries::<tables::StoragesHistory>().unwrap(), 2);
+        assert_eq!(tx.entries::<tables::AccountChangeSets>().unwrap(), 3);
+    }
 }
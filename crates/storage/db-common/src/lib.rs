@@ -8,7 +8,9 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod compat;
 pub mod init;
+pub mod receipts_bloom;
 
 mod db_tool;
 pub use db_tool::*;
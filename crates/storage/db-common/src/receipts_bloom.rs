@@ -0,0 +1,355 @@
+//! Verification of the logs blooms of stored receipts against the header blooms.
+//!
+//! The logs bloom of a header commits to the logs of all receipts of the block. Recomputing it
+//! from the stored receipts detects corruption of either the receipts or the header in long-lived
+//! datadirs. Since the header is committed to by the stored canonical hash, a mismatch can be
+//! attributed:
+//! - if the stored header hashes to the canonical hash, the receipts are corrupted and the block
+//!   has to be re-executed.
+//! - if the header with the bloom recomputed from the logs hashes to the canonical hash, only the
+//!   header bloom is corrupted and can be repaired with [`repair_header_bloom`].
+
+use rand::Rng;
+use reth_db::tables;
+use reth_db_api::{database::Database, transaction::DbTxMut};
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{logs_bloom, BlockNumber, Bloom, StaticFileSegment};
+use reth_provider::{
+    DatabaseProviderRW, HeaderProvider, ProviderError, ProviderFactory, ProviderResult,
+    PruneCheckpointReader, ReceiptProvider, StageCheckpointReader,
+};
+use reth_prune_types::PruneSegment;
+use reth_stages_types::StageId;
+use std::{ops::RangeInclusive, time::Duration};
+use tracing::{debug, error, info};
+
+/// The default interval between the rounds of the [`ReceiptsBloomVerifier`].
+pub const DEFAULT_RECEIPTS_BLOOM_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The part of the stored data that a [`BloomMismatch`] is attributed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BloomMismatchKind {
+    /// The header is intact, so the stored receipts are corrupted.
+    ///
+    /// This can only be repaired by re-executing the block.
+    Receipts,
+    /// The logs bloom of the header is corrupted, the header with the bloom recomputed from the
+    /// logs hashes to the canonical hash.
+    HeaderBloom,
+    /// Neither the stored header nor the header with the recomputed bloom hash to the canonical
+    /// hash.
+    Unknown,
+}
+
+/// A block whose header bloom doesn't match the logs of its stored receipts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BloomMismatch {
+    /// The block number.
+    pub block: BlockNumber,
+    /// The logs bloom of the stored header.
+    pub header_bloom: Bloom,
+    /// The logs bloom recomputed from the stored receipts.
+    pub receipts_bloom: Bloom,
+    /// The part of the stored data the mismatch is attributed to.
+    pub kind: BloomMismatchKind,
+}
+
+/// Recomputes the logs bloom of the block from its stored receipts and compares it to the header
+/// bloom.
+///
+/// Returns `None` if the header or receipts of the block are not available.
+pub fn check_receipts_bloom<P>(
+    provider: &P,
+    block: BlockNumber,
+) -> ProviderResult<Option<Result<(), BloomMismatch>>>
+where
+    P: HeaderProvider + ReceiptProvider,
+{
+    let Some(header) = provider.sealed_header(block)? else { return Ok(None) };
+    let Some(receipts) = provider.receipts_by_block(block.into())? else { return Ok(None) };
+
+    let receipts_bloom = logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs));
+    if receipts_bloom == header.logs_bloom {
+        return Ok(Some(Ok(())))
+    }
+
+    let header_bloom = header.logs_bloom;
+    let (header, hash) = header.split();
+    let kind = if header.hash_slow() == hash {
+        BloomMismatchKind::Receipts
+    } else {
+        let mut repaired = header;
+        repaired.logs_bloom = receipts_bloom;
+        if repaired.hash_slow() == hash {
+            BloomMismatchKind::HeaderBloom
+        } else {
+            BloomMismatchKind::Unknown
+        }
+    };
+
+    Ok(Some(Err(BloomMismatch { block, header_bloom, receipts_bloom, kind })))
+}
+
+/// Returns the range of blocks whose receipts can be verified.
+///
+/// This excludes all blocks whose receipts may have been pruned, fully or by the contract logs
+/// filter, and returns `None` if there are no such blocks.
+pub fn verifiable_receipts_range<P>(provider: &P) -> ProviderResult<Option<RangeInclusive<u64>>>
+where
+    P: StageCheckpointReader + PruneCheckpointReader,
+{
+    let tip = provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+    let mut start = 1;
+    for segment in [PruneSegment::Receipts, PruneSegment::ContractLogs] {
+        if let Some(pruned) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        {
+            start = start.max(pruned + 1);
+        }
+    }
+
+    Ok((start <= tip).then_some(start..=tip))
+}
+
+/// Replaces the logs bloom of the header with the bloom recomputed from the stored receipts, if
+/// the repaired header hashes to the canonical hash.
+///
+/// Returns `false` if the mismatch is not attributed to the header bloom. Headers that were
+/// already moved to static files are rewritten in the static files, see
+/// [`StaticFileProvider::replace_header`](reth_provider::providers::StaticFileProvider::replace_header).
+pub fn repair_header_bloom<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    mismatch: &BloomMismatch,
+) -> ProviderResult<bool> {
+    if mismatch.kind != BloomMismatchKind::HeaderBloom {
+        return Ok(false)
+    }
+
+    let block = mismatch.block;
+    let mut header = provider
+        .header_by_number(block)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?;
+    header.logs_bloom = mismatch.receipts_bloom;
+
+    let static_file_provider = provider.static_file_provider();
+    if static_file_provider
+        .get_highest_static_file_block(StaticFileSegment::Headers)
+        .is_some_and(|highest| block <= highest)
+    {
+        static_file_provider.replace_header(&header)?;
+    } else {
+        provider.tx_ref().put::<tables::Headers>(block, header)?;
+    }
+    Ok(true)
+}
+
+/// Metrics for the [`ReceiptsBloomVerifier`].
+#[derive(Metrics)]
+#[metrics(scope = "receipts_bloom_verifier")]
+struct ReceiptsBloomVerifierMetrics {
+    /// The number of blocks whose receipts bloom was verified.
+    verified_blocks: Counter,
+    /// The number of blocks whose header bloom doesn't match the logs of the stored receipts.
+    mismatches: Counter,
+}
+
+/// Periodically verifies the logs blooms of randomly sampled blocks with stored receipts.
+///
+/// Mismatches are only reported, repairing requires write access to the database, see
+/// [`repair_header_bloom`].
+#[derive(Debug)]
+pub struct ReceiptsBloomVerifier<DB> {
+    provider_factory: ProviderFactory<DB>,
+    /// The number of blocks that are verified per round.
+    samples: usize,
+    /// The interval between the rounds.
+    interval: Duration,
+}
+
+impl<DB> ReceiptsBloomVerifier<DB> {
+    /// Creates a new verifier that checks the given number of blocks per round.
+    pub const fn new(provider_factory: ProviderFactory<DB>, samples: usize) -> Self {
+        Self { provider_factory, samples, interval: DEFAULT_RECEIPTS_BLOOM_INTERVAL }
+    }
+
+    /// Sets the interval between the rounds.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+impl<DB: Database + 'static> ReceiptsBloomVerifier<DB> {
+    /// Verifies the blooms of randomly sampled blocks, returning the mismatches.
+    pub fn verify_samples(&self) -> ProviderResult<Vec<BloomMismatch>> {
+        let provider = self.provider_factory.provider()?;
+        let Some(range) = verifiable_receipts_range(&provider)? else { return Ok(Vec::new()) };
+
+        let mut rng = rand::thread_rng();
+        let mut mismatches = Vec::new();
+        for _ in 0..self.samples {
+            let block = rng.gen_range(range.clone());
+            if let Some(Err(mismatch)) = check_receipts_bloom(&provider, block)? {
+                mismatches.push(mismatch);
+            }
+        }
+
+        Ok(mismatches)
+    }
+
+    /// Runs the verifier until the future is dropped.
+    pub async fn run(self) {
+        let metrics = ReceiptsBloomVerifierMetrics::default();
+        let mut interval = tokio::time::interval(self.interval);
+        let verifier = std::sync::Arc::new(self);
+
+        info!(target: "db::receipts_bloom", samples = verifier.samples, interval = ?verifier.interval, "Verifying receipts blooms");
+        loop {
+            interval.tick().await;
+
+            let this = verifier.clone();
+            let result = tokio::task::spawn_blocking(move || this.verify_samples()).await;
+            let mismatches = match result {
+                Ok(Ok(mismatches)) => mismatches,
+                Ok(Err(err)) => {
+                    error!(target: "db::receipts_bloom", %err, "Failed to verify receipts blooms");
+                    continue
+                }
+                Err(_) => return,
+            };
+
+            metrics.verified_blocks.increment(verifier.samples as u64);
+            metrics.mismatches.increment(mismatches.len() as u64);
+            for mismatch in &mismatches {
+                error!(
+                    target: "db::receipts_bloom",
+                    block = mismatch.block,
+                    kind = ?mismatch.kind,
+                    "Header logs bloom doesn't match the stored receipts, run `reth db check-receipts-bloom` to inspect and repair"
+                );
+            }
+            debug!(target: "db::receipts_bloom", mismatches = mismatches.len(), "Verified receipts blooms");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db_api::models::StoredBlockBodyIndices;
+    use reth_primitives::{Header, Log, Receipt, B256, U256};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, StaticFileProviderFactory, StaticFileWriter,
+    };
+
+    /// Returns a receipt with a log and the logs bloom of the receipt.
+    fn receipt_with_log() -> (Receipt, Bloom) {
+        let receipt = Receipt {
+            logs: vec![Log::new_unchecked(
+                Default::default(),
+                vec![B256::with_last_byte(1)],
+                Default::default(),
+            )],
+            success: true,
+            ..Default::default()
+        };
+        let bloom = logs_bloom(&receipt.logs);
+        (receipt, bloom)
+    }
+
+    #[test]
+    fn check_and_repair() {
+        let (receipt, bloom) = receipt_with_log();
+        let header = Header { number: 1, logs_bloom: bloom, ..Default::default() };
+        let hash = header.hash_slow();
+
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        tx.put::<tables::Headers>(1, header.clone()).unwrap();
+        tx.put::<tables::CanonicalHeaders>(1, hash).unwrap();
+        tx.put::<tables::BlockBodyIndices>(
+            1,
+            StoredBlockBodyIndices { first_tx_num: 0, tx_count: 1 },
+        )
+        .unwrap();
+        tx.put::<tables::Receipts>(0, receipt).unwrap();
+
+        assert_eq!(check_receipts_bloom(&*provider, 1).unwrap(), Some(Ok(())));
+        assert_eq!(check_receipts_bloom(&*provider, 2).unwrap(), None);
+
+        // corrupted header bloom
+        tx.put::<tables::Headers>(1, Header { logs_bloom: Bloom::ZERO, ..header }).unwrap();
+        let mismatch = check_receipts_bloom(&*provider, 1).unwrap().unwrap().unwrap_err();
+        assert_eq!(mismatch.kind, BloomMismatchKind::HeaderBloom);
+        assert_eq!(mismatch.receipts_bloom, bloom);
+        assert!(repair_header_bloom(&provider, &mismatch).unwrap());
+        assert_eq!(check_receipts_bloom(&*provider, 1).unwrap(), Some(Ok(())));
+
+        // corrupted receipts
+        tx.put::<tables::Receipts>(0, Receipt::default()).unwrap();
+        let mismatch = check_receipts_bloom(&*provider, 1).unwrap().unwrap().unwrap_err();
+        assert_eq!(mismatch.kind, BloomMismatchKind::Receipts);
+        assert!(!repair_header_bloom(&provider, &mismatch).unwrap());
+    }
+
+    #[test]
+    fn repair_static_file_header() {
+        let (receipt, bloom) = receipt_with_log();
+        let headers = (0..3)
+            .map(|number| Header {
+                number,
+                logs_bloom: if number == 1 { bloom } else { Bloom::ZERO },
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+            for header in &headers {
+                // the header of block 1 is stored with a corrupted bloom
+                let stored = Header { logs_bloom: Bloom::ZERO, ..header.clone() };
+                writer
+                    .append_header(&stored, U256::from(header.number), &header.hash_slow())
+                    .unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        tx.put::<tables::BlockBodyIndices>(
+            1,
+            StoredBlockBodyIndices { first_tx_num: 0, tx_count: 1 },
+        )
+        .unwrap();
+        tx.put::<tables::Receipts>(0, receipt).unwrap();
+
+        let mismatch = check_receipts_bloom(&*provider, 1).unwrap().unwrap().unwrap_err();
+        assert_eq!(mismatch.kind, BloomMismatchKind::HeaderBloom);
+        assert!(repair_header_bloom(&provider, &mismatch).unwrap());
+        assert_eq!(check_receipts_bloom(&*provider, 1).unwrap(), Some(Ok(())));
+
+        // the headers after the repaired one are appended again unchanged
+        assert_eq!(
+            static_file_provider.get_highest_static_file_block(StaticFileSegment::Headers),
+            Some(2)
+        );
+        for header in headers {
+            let number = header.number;
+            assert_eq!(
+                static_file_provider.sealed_header(number).unwrap(),
+                Some(header.seal_slow()),
+                "{number}"
+            );
+            assert_eq!(
+                static_file_provider.header_td_by_number(number).unwrap(),
+                Some(U256::from(number))
+            );
+        }
+    }
+}
@@ -32,6 +32,7 @@ use tx::Tx;
 
 pub mod cursor;
 pub mod tx;
+pub mod utilization;
 
 const GIGABYTE: usize = 1024 * 1024 * 1024;
 const TERABYTE: usize = GIGABYTE * 1024;
@@ -89,6 +90,14 @@ pub struct DatabaseArguments {
     ///
     /// This flag affects only at environment opening but can't be changed after.
     exclusive: Option<bool>,
+    /// Maximum size of the database in bytes. If [None], the default value is used.
+    max_size: Option<usize>,
+    /// Size in bytes by which the database grows. If [None], the default value is used.
+    growth_step: Option<usize>,
+    /// Sync mode of read-write environments. If [None], [`SyncMode::Durable`] is used.
+    sync_mode: Option<SyncMode>,
+    /// Whether the OS reads ahead when pages are read. If [None], read-ahead is disabled.
+    read_ahead: Option<bool>,
 }
 
 impl DatabaseArguments {
@@ -99,6 +108,10 @@ impl DatabaseArguments {
             log_level: None,
             max_read_transaction_duration: None,
             exclusive: None,
+            max_size: None,
+            growth_step: None,
+            sync_mode: None,
+            read_ahead: None,
         }
     }
 
@@ -123,6 +136,30 @@ impl DatabaseArguments {
         self
     }
 
+    /// Set the maximum size of the database in bytes.
+    pub const fn with_max_size(mut self, max_size: Option<usize>) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Set the size in bytes by which the database grows.
+    pub const fn with_growth_step(mut self, growth_step: Option<usize>) -> Self {
+        self.growth_step = growth_step;
+        self
+    }
+
+    /// Set the sync mode of read-write environments.
+    pub const fn with_sync_mode(mut self, sync_mode: Option<SyncMode>) -> Self {
+        self.sync_mode = sync_mode;
+        self
+    }
+
+    /// Set whether the OS reads ahead when pages are read.
+    pub const fn with_read_ahead(mut self, read_ahead: Option<bool>) -> Self {
+        self.read_ahead = read_ahead;
+        self
+    }
+
     /// Returns the client version if any.
     pub const fn client_version(&self) -> &ClientVersion {
         &self.client_version
@@ -214,22 +251,52 @@ impl DatabaseMetrics for DatabaseEnv {
                         entries as f64,
                         vec![Label::new("table", table)],
                     ));
+                    // A decreasing number of entries per leaf page for a table with a stable
+                    // entry size indicates that the leaf pages are becoming fragmented.
+                    if leaf_pages > 0 {
+                        metrics.push((
+                            "db.table_entries_per_leaf_page",
+                            entries as f64 / leaf_pages as f64,
+                            vec![Label::new("table", table)],
+                        ));
+                    }
                 }
 
                 Ok::<(), eyre::Report>(())
             })
             .map_err(|error| error!(%error, "Failed to read db table stats"));
 
-        if let Ok(freelist) =
-            self.freelist().map_err(|error| error!(%error, "Failed to read db.freelist"))
-        {
+        let freelist =
+            self.freelist().map_err(|error| error!(%error, "Failed to read db.freelist")).ok();
+        if let Some(freelist) = freelist {
             metrics.push(("db.freelist", freelist as f64, vec![]));
         }
 
-        if let Ok(stat) = self.stat().map_err(|error| error!(%error, "Failed to read db.stat")) {
+        let stat = self.stat().map_err(|error| error!(%error, "Failed to read db.stat")).ok();
+        if let Some(stat) = &stat {
             metrics.push(("db.page_size", stat.page_size() as f64, vec![]));
         }
 
+        if let Ok(info) = self.info().map_err(|error| error!(%error, "Failed to read db.info")) {
+            let max_size = info.geometry().max() as usize;
+            metrics.push(("db.map_size", info.map_size() as f64, vec![]));
+            metrics.push(("db.map_max_size", max_size as f64, vec![]));
+
+            if let (Some(stat), Some(freelist)) = (stat, freelist) {
+                // pages are 0 based
+                let used_pages = (info.last_pgno() + 1).saturating_sub(freelist);
+                let used_size = used_pages * stat.page_size() as usize;
+                metrics.push(("db.map_used_size", used_size as f64, vec![]));
+                if max_size > 0 {
+                    metrics.push((
+                        "db.map_utilization",
+                        used_size as f64 / max_size as f64,
+                        vec![],
+                    ));
+                }
+            }
+        }
+
         metrics.push((
             "db.timed_out_not_aborted_transactions",
             self.timed_out_not_aborted_transactions() as f64,
@@ -271,7 +338,7 @@ impl DatabaseEnv {
             DatabaseEnvKind::RW => {
                 // enable writemap mode in RW mode
                 inner_env.write_map();
-                Mode::ReadWrite { sync_mode: SyncMode::Durable }
+                Mode::ReadWrite { sync_mode: args.sync_mode.unwrap_or(SyncMode::Durable) }
             }
         };
 
@@ -280,10 +347,10 @@ impl DatabaseEnv {
         debug_assert!(Tables::ALL.len() <= 256, "number of tables exceed max dbs");
         inner_env.set_max_dbs(256);
         inner_env.set_geometry(Geometry {
-            // Maximum database size of 4 terabytes
-            size: Some(0..(4 * TERABYTE)),
-            // We grow the database in increments of 4 gigabytes
-            growth_step: Some(4 * GIGABYTE as isize),
+            // Maximum database size of 4 terabytes by default
+            size: Some(0..args.max_size.unwrap_or(4 * TERABYTE)),
+            // We grow the database in increments of 4 gigabytes by default
+            growth_step: Some(args.growth_step.unwrap_or(4 * GIGABYTE) as isize),
             // The database never shrinks
             shrink_threshold: Some(0),
             page_size: Some(PageSize::Set(default_page_size())),
@@ -336,9 +403,9 @@ impl DatabaseEnv {
 
         inner_env.set_flags(EnvironmentFlags {
             mode,
-            // We disable readahead because it improves performance for linear scans, but
-            // worsens it for random access (which is our access pattern outside of sync)
-            no_rdahead: true,
+            // We disable readahead by default because it improves performance for linear scans,
+            // but worsens it for random access (which is our access pattern outside of sync)
+            no_rdahead: !args.read_ahead.unwrap_or_default(),
             coalesce: true,
             exclusive: args.exclusive.unwrap_or_default(),
             ..Default::default()
@@ -0,0 +1,200 @@
+//! Page utilization of MDBX tables.
+
+use super::DatabaseEnv;
+use crate::{tables::Tables, DatabaseError};
+use reth_db_api::database::Database;
+use reth_libmdbx::{DatabaseFlags, Stat};
+
+/// Size of the header of an MDBX page.
+const PAGE_HEADER_SIZE: usize = 20;
+
+/// Size of the header of a node on a leaf page.
+const NODE_HEADER_SIZE: usize = 8;
+
+/// Size of the entry of a node in the index of a page.
+const NODE_INDEX_SIZE: usize = 2;
+
+/// Size of the overflow page number that replaces the value of a node stored on overflow pages.
+const OVERFLOW_PAGE_NUMBER_SIZE: usize = 4;
+
+/// Tables smaller than this are never recommended for compaction, as the reclaimed space would be
+/// negligible.
+const MIN_COMPACTION_TABLE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Leaf fill factor below which a table is recommended for compaction.
+///
+/// B-trees filled in random key order settle at a fill factor of about 69%, so lower fill factors
+/// indicate that a significant part of the entries has been deleted.
+const COMPACTION_LEAF_FILL_THRESHOLD: f64 = 0.5;
+
+/// Page utilization of a single MDBX table.
+///
+/// The page counts are read from the table statistics, while the number of bytes stored on the
+/// pages is estimated by walking all entries of the table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TableUtilization {
+    /// Size of a database page in bytes.
+    pub page_size: usize,
+    /// Number of entries in the table.
+    pub entries: usize,
+    /// Number of branch pages.
+    pub branch_pages: usize,
+    /// Number of leaf pages.
+    pub leaf_pages: usize,
+    /// Number of overflow pages.
+    pub overflow_pages: usize,
+    /// Estimated number of bytes used on leaf pages, including node headers.
+    pub leaf_bytes: usize,
+    /// Number of value bytes stored on overflow pages.
+    pub overflow_bytes: usize,
+}
+
+impl TableUtilization {
+    /// Creates a new [`TableUtilization`] from the table statistics, without any used bytes.
+    pub fn from_stat(stat: &Stat) -> Self {
+        Self {
+            page_size: stat.page_size() as usize,
+            entries: stat.entries(),
+            branch_pages: stat.branch_pages(),
+            leaf_pages: stat.leaf_pages(),
+            overflow_pages: stat.overflow_pages(),
+            ..Default::default()
+        }
+    }
+
+    /// Accounts for a single entry of the table.
+    ///
+    /// For tables with duplicate values, `new_key` must be `false` for all but the first value of
+    /// a key, because the key is only stored once.
+    pub fn record_entry(&mut self, key_len: usize, value_len: usize, new_key: bool) {
+        let key_len = if new_key { key_len } else { 0 };
+        if NODE_HEADER_SIZE + key_len + value_len > self.leaf_node_max() {
+            self.leaf_bytes +=
+                NODE_HEADER_SIZE + NODE_INDEX_SIZE + key_len + OVERFLOW_PAGE_NUMBER_SIZE;
+            self.overflow_bytes += value_len;
+        } else {
+            self.leaf_bytes += NODE_HEADER_SIZE + NODE_INDEX_SIZE + key_len + value_len;
+        }
+    }
+
+    /// Returns the total size of the table in bytes.
+    pub const fn size(&self) -> usize {
+        (self.branch_pages + self.leaf_pages + self.overflow_pages) * self.page_size
+    }
+
+    /// Returns the share of the usable leaf page space that is occupied by entries.
+    pub fn leaf_fill(&self) -> f64 {
+        fill(self.leaf_bytes, self.leaf_pages * self.usable_page_size())
+    }
+
+    /// Returns the share of the overflow page space that is occupied by values.
+    pub fn overflow_fill(&self) -> f64 {
+        fill(self.overflow_bytes, self.overflow_pages * self.page_size)
+    }
+
+    /// Returns the estimated number of bytes that would be reclaimed by compacting the table.
+    ///
+    /// Compaction rewrites the leaf pages densely. Values on overflow pages are copied as is, so
+    /// their space is not reclaimed.
+    pub const fn reclaimable_bytes(&self) -> usize {
+        let usable = self.usable_page_size();
+        if usable == 0 {
+            return 0
+        }
+        let min_leaf_pages = self.leaf_bytes.div_ceil(usable);
+        self.leaf_pages.saturating_sub(min_leaf_pages) * self.page_size
+    }
+
+    /// Returns `true` if the table is large and its leaf pages are sparse enough that compacting
+    /// the database would reclaim a significant amount of space.
+    pub fn should_compact(&self) -> bool {
+        self.size() >= MIN_COMPACTION_TABLE_SIZE &&
+            self.leaf_fill() < COMPACTION_LEAF_FILL_THRESHOLD
+    }
+
+    /// Returns the number of bytes available for nodes on a page.
+    const fn usable_page_size(&self) -> usize {
+        self.page_size.saturating_sub(PAGE_HEADER_SIZE)
+    }
+
+    /// Returns the maximum size of a node on a leaf page, larger values are moved to overflow
+    /// pages.
+    const fn leaf_node_max(&self) -> usize {
+        ((self.usable_page_size() / 2) & !1).saturating_sub(NODE_INDEX_SIZE)
+    }
+}
+
+/// Returns the ratio of `used` to `available` bytes, or `1.0` if nothing is available.
+fn fill(used: usize, available: usize) -> f64 {
+    if available == 0 {
+        return 1.0
+    }
+    used as f64 / available as f64
+}
+
+impl DatabaseEnv {
+    /// Calculates the page utilization of the given table.
+    ///
+    /// This walks all entries of the table and can take a long time for large tables.
+    pub fn table_utilization(&self, table: Tables) -> Result<TableUtilization, DatabaseError> {
+        self.view(|tx| {
+            let db =
+                tx.inner.open_db(Some(table.name())).map_err(|e| DatabaseError::Open(e.into()))?;
+            let stat = tx.inner.db_stat(&db).map_err(|e| DatabaseError::Stats(e.into()))?;
+            let dup_sort = tx
+                .inner
+                .db_flags(&db)
+                .map_err(|e| DatabaseError::Stats(e.into()))?
+                .contains(DatabaseFlags::DUP_SORT);
+
+            let mut utilization = TableUtilization::from_stat(&stat);
+            let cursor = tx.inner.cursor(&db).map_err(|e| DatabaseError::InitCursor(e.into()))?;
+            let mut last_key = Vec::new();
+            for entry in cursor.iter_slices() {
+                let (key, value) = entry.map_err(|e| DatabaseError::Read(e.into()))?;
+                let new_key = !dup_sort || last_key.is_empty() || *last_key != *key;
+                utilization.record_entry(key.len(), value.len(), new_key);
+                if dup_sort && new_key {
+                    last_key = key.into_owned();
+                }
+            }
+
+            Ok(utilization)
+        })?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_utilization() {
+        let mut utilization =
+            TableUtilization { page_size: 4096, leaf_pages: 4, ..Default::default() };
+
+        // Small entries are stored on the leaf pages.
+        for _ in 0..100 {
+            utilization.record_entry(32, 28, true);
+        }
+        assert_eq!(utilization.leaf_bytes, 100 * 70);
+        assert_eq!(utilization.overflow_bytes, 0);
+        assert_eq!(utilization.reclaimable_bytes(), 2 * 4096);
+
+        // Duplicate values do not store the key again.
+        utilization.record_entry(32, 28, false);
+        assert_eq!(utilization.leaf_bytes, 100 * 70 + 38);
+
+        // Large values are moved to overflow pages.
+        utilization.overflow_pages = 2;
+        utilization.record_entry(32, 5000, true);
+        assert_eq!(utilization.leaf_bytes, 100 * 70 + 38 + 46);
+        assert_eq!(utilization.overflow_bytes, 5000);
+        assert!(utilization.overflow_fill() > 0.6);
+
+        assert!(utilization.leaf_fill() < 0.5);
+        assert!(!utilization.should_compact());
+        utilization.leaf_pages = 1 << 20;
+        assert!(utilization.should_compact());
+    }
+}
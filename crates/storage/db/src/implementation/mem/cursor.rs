@@ -0,0 +1,416 @@
+//! Cursors of the in-memory database.
+
+use super::{
+    compress_value, encode_key, put_row, write_error, SharedSnapshot, TableRows, TransactionKind,
+    KEY_EXISTS, KEY_MISMATCH, NOT_FOUND, RW,
+};
+use crate::{
+    tables::utils::{decode_one, decode_value, decoder},
+    DatabaseError,
+};
+use reth_db_api::{
+    common::{IterPairResult, PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{DupSort, Encode, Table},
+};
+use reth_storage_errors::db::{DatabaseErrorInfo, DatabaseWriteOperation};
+use std::{borrow::Cow, collections::Bound, marker::PhantomData, ops::RangeBounds, sync::Arc};
+
+/// Position of a [`Cursor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Position {
+    /// The cursor was not positioned yet.
+    Unset,
+    /// The cursor is at the encoded key and compressed value. The entry may have been deleted.
+    At(Vec<u8>, Vec<u8>),
+    /// The cursor moved past the entries of the table.
+    Eof,
+}
+
+/// A cursor over a table of a [`Tx`](super::Tx) of the in-memory database.
+///
+/// The cursor sees the changes of its transaction, including the ones made after it was created.
+#[derive(Debug)]
+pub struct Cursor<K: TransactionKind, T: Table> {
+    /// The tables of the transaction.
+    tables: SharedSnapshot,
+    /// The position of the cursor.
+    position: Position,
+    _dbi: PhantomData<(K, T)>,
+}
+
+impl<K: TransactionKind, T: Table> Cursor<K, T> {
+    /// Creates a cursor over the tables of a transaction.
+    pub(crate) const fn new(tables: SharedSnapshot) -> Self {
+        Self { tables, position: Position::Unset, _dbi: PhantomData }
+    }
+
+    /// Returns the rows of the table.
+    fn rows(&self) -> Arc<TableRows> {
+        self.tables.read().get(T::NAME).cloned().unwrap_or_default()
+    }
+
+    /// Applies the function to the rows of the table, which are copied if they're shared with
+    /// another snapshot.
+    fn update<R>(&self, f: impl FnOnce(&mut TableRows) -> R) -> R {
+        let mut tables = self.tables.write();
+        f(Arc::make_mut(tables.entry(T::NAME).or_default()))
+    }
+
+    /// Moves the cursor to the entry, or past the entries of the table if there's none, and
+    /// decodes it.
+    fn move_to(&mut self, entry: Option<(&Vec<u8>, &Vec<u8>)>) -> PairResult<T> {
+        match entry {
+            Some((key, value)) => {
+                self.position = Position::At(key.clone(), value.clone());
+                decoder::<T>((Cow::Borrowed(key), Cow::Borrowed(value))).map(Some)
+            }
+            None => {
+                self.position = Position::Eof;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns the first entry of the table.
+    fn first_entry(rows: &TableRows) -> Option<(&Vec<u8>, &Vec<u8>)> {
+        rows.iter().find_map(|(key, values)| Some((key, values.first()?)))
+    }
+
+    /// Returns the last entry of the table.
+    fn last_entry(rows: &TableRows) -> Option<(&Vec<u8>, &Vec<u8>)> {
+        rows.iter().rev().find_map(|(key, values)| Some((key, values.last()?)))
+    }
+
+    /// Returns the first entry of the first key that is greater than or equal to the given key.
+    fn seek_entry<'a>(rows: &'a TableRows, key: &[u8]) -> Option<(&'a Vec<u8>, &'a Vec<u8>)> {
+        rows.range::<[u8], _>((Bound::Included(key), Bound::Unbounded))
+            .find_map(|(key, values)| Some((key, values.first()?)))
+    }
+
+    /// Returns the first entry of the first key that is greater than the given key.
+    fn next_key_entry<'a>(rows: &'a TableRows, key: &[u8]) -> Option<(&'a Vec<u8>, &'a Vec<u8>)> {
+        rows.range::<[u8], _>((Bound::Excluded(key), Bound::Unbounded))
+            .find_map(|(key, values)| Some((key, values.first()?)))
+    }
+
+    /// Returns the next value of the key after the given value.
+    fn next_dup_entry<'a>(
+        rows: &'a TableRows,
+        key: &[u8],
+        value: &[u8],
+    ) -> Option<(&'a Vec<u8>, &'a Vec<u8>)> {
+        let (key, values) = rows.get_key_value(key)?;
+        let value = values.range::<[u8], _>((Bound::Excluded(value), Bound::Unbounded)).next()?;
+        Some((key, value))
+    }
+
+    /// Returns the entry at the position of the cursor, or the next one if it was deleted.
+    fn current_entry<'a>(&self, rows: &'a TableRows) -> Option<(&'a Vec<u8>, &'a Vec<u8>)> {
+        let Position::At(key, value) = &self.position else { return None };
+        let (key, values) = rows.get_key_value(key.as_slice())?;
+        let value =
+            values.range::<[u8], _>((Bound::Included(value.as_slice()), Bound::Unbounded)).next();
+        match value {
+            Some(value) => Some((key, value)),
+            None => Self::next_key_entry(rows, key),
+        }
+    }
+}
+
+impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
+    fn first(&mut self) -> PairResult<T> {
+        let rows = self.rows();
+        self.move_to(Self::first_entry(&rows))
+    }
+
+    fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        let rows = self.rows();
+        let entry = rows
+            .get_key_value(encode_key::<T>(key).as_slice())
+            .and_then(|(key, values)| Some((key, values.first()?)));
+        self.move_to(entry)
+    }
+
+    fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        let rows = self.rows();
+        self.move_to(Self::seek_entry(&rows, &encode_key::<T>(key)))
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        let rows = self.rows();
+        let entry = match &self.position {
+            Position::Unset => Self::first_entry(&rows),
+            Position::At(key, value) => {
+                Self::next_dup_entry(&rows, key, value).or_else(|| Self::next_key_entry(&rows, key))
+            }
+            Position::Eof => return Ok(None),
+        };
+        self.move_to(entry)
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        let rows = self.rows();
+        let entry = match &self.position {
+            Position::Unset | Position::Eof => Self::last_entry(&rows),
+            Position::At(key, value) => {
+                let prev_dup = rows.get_key_value(key.as_slice()).and_then(|(key, values)| {
+                    let value = values
+                        .range::<[u8], _>((Bound::Unbounded, Bound::Excluded(value.as_slice())))
+                        .next_back()?;
+                    Some((key, value))
+                });
+                prev_dup.or_else(|| {
+                    rows.range::<[u8], _>((Bound::Unbounded, Bound::Excluded(key.as_slice())))
+                        .rev()
+                        .find_map(|(key, values)| Some((key, values.last()?)))
+                })
+            }
+        };
+        self.move_to(entry)
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        let rows = self.rows();
+        self.move_to(Self::last_entry(&rows))
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        let rows = self.rows();
+        self.current_entry(&rows)
+            .map(|(key, value)| decoder::<T>((Cow::Borrowed(key), Cow::Borrowed(value))))
+            .transpose()
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = if let Some(start_key) = start_key {
+            self.seek(start_key).transpose()
+        } else {
+            self.first().transpose()
+        };
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.last() }
+                .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
+    /// Returns the next `(key, value)` pair of a DUPSORT table.
+    fn next_dup(&mut self) -> PairResult<T> {
+        let rows = self.rows();
+        let Position::At(key, value) = &self.position else { return Ok(None) };
+        // like MDBX, the cursor stays at the last value of the key
+        match Self::next_dup_entry(&rows, key, value) {
+            Some(entry) => self.move_to(Some(entry)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the next `(key, value)` pair skipping the duplicates.
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        let rows = self.rows();
+        let entry = match &self.position {
+            Position::Unset => Self::first_entry(&rows),
+            Position::At(key, _) => Self::next_key_entry(&rows, key),
+            Position::Eof => return Ok(None),
+        };
+        self.move_to(entry)
+    }
+
+    /// Returns the next `value` of a duplicate `key`.
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        let rows = self.rows();
+        let Position::At(key, value) = &self.position else { return Ok(None) };
+        let Some((key, value)) = Self::next_dup_entry(&rows, key, value) else { return Ok(None) };
+        self.position = Position::At(key.clone(), value.clone());
+        decode_value::<T>((Cow::Borrowed(key), Cow::Borrowed(value))).map(Some)
+    }
+
+    fn seek_by_key_subkey(
+        &mut self,
+        key: <T as Table>::Key,
+        subkey: <T as DupSort>::SubKey,
+    ) -> ValueOnlyResult<T> {
+        let rows = self.rows();
+        let subkey = subkey.encode();
+        let entry =
+            rows.get_key_value(encode_key::<T>(key).as_slice()).and_then(|(key, values)| {
+                let value = values
+                    .range::<[u8], _>((Bound::Included(subkey.as_ref()), Bound::Unbounded))
+                    .next()?;
+                Some((key, value))
+            });
+        match entry {
+            Some((key, value)) => {
+                self.position = Position::At(key.clone(), value.clone());
+                decode_one::<T>(Cow::Borrowed(value)).map(Some)
+            }
+            None => {
+                self.position = Position::Eof;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Depending on its arguments, returns an iterator starting at:
+    /// - Some(key), Some(subkey): a `key` item whose data is >= than `subkey`
+    /// - Some(key), None: first item of a specified `key`
+    /// - None, Some(subkey): like first case, but in the first key
+    /// - None, None: first item in the table of a DUPSORT table.
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start: IterPairResult<T> = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                let value = self.seek_by_key_subkey(key.clone(), subkey).transpose();
+                value.map(|value| value.map(|value| (key, value)))
+            }
+            (Some(key), None) => self.seek_exact(key).transpose(),
+            (None, Some(subkey)) => match self.first()? {
+                Some((key, _)) => {
+                    let value = self.seek_by_key_subkey(key.clone(), subkey).transpose();
+                    value.map(|value| value.map(|value| (key, value)))
+                }
+                None => None,
+            },
+            (None, None) => self.first().transpose(),
+        };
+
+        Ok(DupWalker::<'_, T, Self> { cursor: self, start })
+    }
+}
+
+impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
+    /// Database operation that will update an existing row if a specified value already
+    /// exists in a table, and insert a new row if the specified value doesn't already exist
+    ///
+    /// For a DUPSORT table, the value is added to the values of the key, like with MDBX.
+    fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let (key, value) = (encode_key::<T>(key), compress_value::<T>(value));
+        self.update(|rows| put_row::<T>(rows, key.clone(), value.clone()));
+        self.position = Position::At(key, value);
+        Ok(())
+    }
+
+    fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let (key, value) = (encode_key::<T>(key), compress_value::<T>(value));
+        if self.rows().contains_key(&key) {
+            return Err(write_error::<T>(
+                DatabaseWriteOperation::CursorInsert,
+                key,
+                KEY_EXISTS,
+                "key already exists",
+            ))
+        }
+        self.update(|rows| put_row::<T>(rows, key.clone(), value.clone()));
+        self.position = Position::At(key, value);
+        Ok(())
+    }
+
+    /// Appends the data to the end of the table. Consequently, the append operation
+    /// will fail if the inserted key is less than the last table key
+    fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let (key, value) = (encode_key::<T>(key), compress_value::<T>(value));
+        let out_of_order =
+            self.rows().last_key_value().is_some_and(|(last, values)| match key.cmp(last) {
+                std::cmp::Ordering::Less => true,
+                std::cmp::Ordering::Equal => {
+                    !T::DUPSORT || values.last().is_some_and(|last| value < *last)
+                }
+                std::cmp::Ordering::Greater => false,
+            });
+        if out_of_order {
+            return Err(write_error::<T>(
+                DatabaseWriteOperation::CursorAppend,
+                key,
+                KEY_MISMATCH,
+                "key is not greater than the last key",
+            ))
+        }
+        self.update(|rows| put_row::<T>(rows, key.clone(), value.clone()));
+        self.position = Position::At(key, value);
+        Ok(())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        let Position::At(key, value) = &self.position else {
+            return Err(DatabaseError::Delete(DatabaseErrorInfo {
+                message: "cursor is not positioned".to_string(),
+                code: NOT_FOUND,
+            }))
+        };
+        // the cursor keeps its position, so that the next entry is the one after the deleted one
+        self.update(|rows| {
+            if let Some(values) = rows.get_mut(key) {
+                values.remove(value);
+                if values.is_empty() {
+                    rows.remove(key);
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        let Position::At(key, _) = &self.position else {
+            return Err(DatabaseError::Delete(DatabaseErrorInfo {
+                message: "cursor is not positioned".to_string(),
+                code: NOT_FOUND,
+            }))
+        };
+        self.update(|rows| rows.remove(key));
+        Ok(())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let (key, value) = (encode_key::<T>(key), compress_value::<T>(value));
+        let out_of_order = self
+            .rows()
+            .get(&key)
+            .and_then(|values| values.last())
+            .is_some_and(|last| value < *last);
+        if out_of_order {
+            return Err(write_error::<T>(
+                DatabaseWriteOperation::CursorAppendDup,
+                key,
+                KEY_MISMATCH,
+                "value is not greater than the last value of the key",
+            ))
+        }
+        self.update(|rows| put_row::<T>(rows, key.clone(), value.clone()));
+        self.position = Position::At(key, value);
+        Ok(())
+    }
+}
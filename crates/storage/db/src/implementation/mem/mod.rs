@@ -0,0 +1,300 @@
+//! In-memory implementation of the database abstraction layer.
+
+use crate::DatabaseError;
+use parking_lot::{Condvar, Mutex, RwLock};
+use reth_db_api::{
+    database::Database,
+    database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
+    table::{Compress, Encode, Table},
+};
+use reth_storage_errors::db::{DatabaseErrorInfo, DatabaseWriteError, DatabaseWriteOperation};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Debug,
+    sync::Arc,
+};
+
+pub mod cursor;
+pub mod tx;
+
+pub use cursor::Cursor;
+pub use tx::Tx;
+
+/// Error code of writing a key that already exists, which is the same as the one of MDBX.
+const KEY_EXISTS: i32 = -30799;
+
+/// Error code of appending a key or value out of order, which is the same as the one of MDBX.
+const KEY_MISMATCH: i32 = -30418;
+
+/// Error code of an entry that is not found, which is the same as the one of MDBX.
+const NOT_FOUND: i32 = -30798;
+
+/// Marker of a read-only transaction or cursor.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RO;
+
+/// Marker of a read-write transaction or cursor.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RW;
+
+/// Kind of a transaction or cursor, either [`RO`] or [`RW`].
+pub trait TransactionKind: private::Sealed + Debug + Send + Sync + 'static {
+    /// Whether the transaction is read-only.
+    const IS_READ_ONLY: bool;
+}
+
+impl TransactionKind for RO {
+    const IS_READ_ONLY: bool = true;
+}
+
+impl TransactionKind for RW {
+    const IS_READ_ONLY: bool = false;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::RO {}
+    impl Sealed for super::RW {}
+}
+
+/// The rows of a table, by encoded key, with the compressed values of the key in ascending order.
+///
+/// Tables that are not `DUPSORT` tables have a single value per key.
+pub(crate) type TableRows = BTreeMap<Vec<u8>, BTreeSet<Vec<u8>>>;
+
+/// The rows of the tables by table name. The rows of a table are shared between snapshots until
+/// they're written.
+pub(crate) type Snapshot = HashMap<&'static str, Arc<TableRows>>;
+
+/// The tables of a transaction, which are shared with its cursors.
+pub(crate) type SharedSnapshot = Arc<RwLock<Snapshot>>;
+
+/// A database that keeps all tables in memory, without any files on disk.
+///
+/// It has the same semantics as the MDBX database: transactions are isolated from each other, only
+/// one read-write transaction can be open at a time, and the values of `DUPSORT` tables are sorted
+/// by their compressed bytes. This makes it a fast replacement of the MDBX database in tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryDatabase {
+    inner: Arc<MemoryDatabaseInner>,
+}
+
+impl MemoryDatabase {
+    /// Creates a new, empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Database for MemoryDatabase {
+    type TX = Tx<RO>;
+    type TXMut = Tx<RW>;
+
+    fn tx(&self) -> Result<Self::TX, DatabaseError> {
+        Ok(Tx::new_ro(self.inner.tables.read().clone()))
+    }
+
+    fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
+        let writer = WriterGuard::acquire(self.inner.clone());
+        let tables = self.inner.tables.read().clone();
+        Ok(Tx::new_rw(tables, writer))
+    }
+}
+
+impl DatabaseMetrics for MemoryDatabase {}
+
+impl DatabaseMetadata for MemoryDatabase {
+    fn metadata(&self) -> DatabaseMetadataValue {
+        DatabaseMetadataValue::new(None)
+    }
+}
+
+/// Shared state of a [`MemoryDatabase`].
+#[derive(Debug, Default)]
+pub(crate) struct MemoryDatabaseInner {
+    /// The committed tables.
+    tables: RwLock<Snapshot>,
+    /// Whether a read-write transaction is open.
+    writer: Mutex<bool>,
+    /// Notified when the open read-write transaction is closed.
+    writer_closed: Condvar,
+}
+
+/// Guard of the open read-write transaction of a [`MemoryDatabase`], which allows the next one to
+/// be opened when it's dropped.
+#[derive(Debug)]
+pub(crate) struct WriterGuard {
+    db: Arc<MemoryDatabaseInner>,
+}
+
+impl WriterGuard {
+    /// Waits until no other read-write transaction is open.
+    fn acquire(db: Arc<MemoryDatabaseInner>) -> Self {
+        let mut writer = db.writer.lock();
+        while *writer {
+            db.writer_closed.wait(&mut writer);
+        }
+        *writer = true;
+        drop(writer);
+        Self { db }
+    }
+
+    /// Replaces the committed tables of the database.
+    pub(crate) fn commit(&self, tables: Snapshot) {
+        *self.db.tables.write() = tables;
+    }
+}
+
+impl Drop for WriterGuard {
+    fn drop(&mut self) {
+        *self.db.writer.lock() = false;
+        self.db.writer_closed.notify_one();
+    }
+}
+
+/// Returns the encoded key.
+fn encode_key<T: Table>(key: T::Key) -> Vec<u8> {
+    key.encode().into()
+}
+
+/// Returns the compressed value.
+fn compress_value<T: Table>(value: T::Value) -> Vec<u8> {
+    match value.uncompressable_ref() {
+        Some(value) => value.to_vec(),
+        None => value.compress().into(),
+    }
+}
+
+/// Inserts the value of the key, replacing the value of a key that is not in a `DUPSORT` table.
+fn put_row<T: Table>(rows: &mut TableRows, key: Vec<u8>, value: Vec<u8>) {
+    if T::DUPSORT {
+        rows.entry(key).or_default().insert(value);
+    } else {
+        rows.insert(key, BTreeSet::from([value]));
+    }
+}
+
+/// Returns the error of a failed write operation.
+fn write_error<T: Table>(
+    operation: DatabaseWriteOperation,
+    key: Vec<u8>,
+    code: i32,
+    message: &str,
+) -> DatabaseError {
+    DatabaseWriteError {
+        info: DatabaseErrorInfo { message: message.to_string(), code },
+        operation,
+        table_name: T::NAME,
+        key,
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::{CanonicalHeaders, PlainStorageState};
+    use reth_db_api::{
+        cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::{Address, StorageEntry, B256, U256};
+
+    #[test]
+    fn transactions_are_isolated() {
+        let db = MemoryDatabase::new();
+
+        let tx = db.tx_mut().unwrap();
+        tx.put::<CanonicalHeaders>(1, B256::with_last_byte(1)).unwrap();
+        let before_commit = db.tx().unwrap();
+        tx.commit().unwrap();
+
+        let after_commit = db.tx().unwrap();
+        assert_eq!(before_commit.get::<CanonicalHeaders>(1).unwrap(), None);
+        assert_eq!(after_commit.get::<CanonicalHeaders>(1).unwrap(), Some(B256::with_last_byte(1)));
+
+        // changes of aborted transactions are discarded
+        let tx = db.tx_mut().unwrap();
+        tx.put::<CanonicalHeaders>(2, B256::with_last_byte(2)).unwrap();
+        tx.abort();
+        assert_eq!(db.tx().unwrap().get::<CanonicalHeaders>(2).unwrap(), None);
+    }
+
+    #[test]
+    fn cursor_walks_in_order() {
+        let db = MemoryDatabase::new();
+        let tx = db.tx_mut().unwrap();
+        let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+        for number in [3, 1, 2] {
+            cursor.upsert(number, B256::with_last_byte(number as u8)).unwrap();
+        }
+        assert!(cursor.insert(2, B256::ZERO).is_err());
+        assert!(cursor.append(1, B256::ZERO).is_err());
+        cursor.append(4, B256::with_last_byte(4)).unwrap();
+
+        let numbers = |cursor: &mut Cursor<RW, CanonicalHeaders>| {
+            cursor.walk(None).unwrap().map(|row| row.unwrap().0).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(&mut cursor), vec![1, 2, 3, 4]);
+        assert_eq!(
+            cursor.walk_range(2..4).unwrap().map(|row| row.unwrap().0).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(
+            cursor.walk_back(Some(2)).unwrap().map(|row| row.unwrap().0).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(cursor.walk(Some(5)).unwrap().count(), 0);
+
+        // deleting while walking continues with the next row
+        let mut walker = cursor.walk(Some(2)).unwrap();
+        walker.next().unwrap().unwrap();
+        walker.delete_current().unwrap();
+        assert_eq!(walker.next().unwrap().unwrap().0, 3);
+        assert_eq!(numbers(&mut cursor), vec![1, 3, 4]);
+        assert_eq!(tx.entries::<CanonicalHeaders>().unwrap(), 3);
+    }
+
+    #[test]
+    fn dupsort_values_are_sorted_by_subkey() {
+        let db = MemoryDatabase::new();
+        let tx = db.tx_mut().unwrap();
+        let address = Address::with_last_byte(1);
+        let entry = |key: u8, value: u64| StorageEntry {
+            key: B256::with_last_byte(key),
+            value: U256::from(value),
+        };
+
+        let mut cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+        cursor.upsert(address, entry(3, 3)).unwrap();
+        cursor.upsert(address, entry(1, 1)).unwrap();
+        cursor.append_dup(address, entry(4, 4)).unwrap();
+        assert!(cursor.append_dup(address, entry(2, 2)).is_err());
+        tx.put::<PlainStorageState>(Address::with_last_byte(2), entry(1, 5)).unwrap();
+
+        assert_eq!(
+            cursor.seek_by_key_subkey(address, B256::with_last_byte(2)).unwrap(),
+            Some(entry(3, 3))
+        );
+        assert_eq!(cursor.next_dup_val().unwrap(), Some(entry(4, 4)));
+        assert_eq!(cursor.next_dup().unwrap(), None);
+        assert_eq!(cursor.next_no_dup().unwrap(), Some((Address::with_last_byte(2), entry(1, 5))));
+
+        let entries = cursor
+            .walk_dup(Some(address), None)
+            .unwrap()
+            .map(|row| row.unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![entry(1, 1), entry(3, 3), entry(4, 4)]);
+
+        assert!(tx.delete::<PlainStorageState>(address, Some(entry(3, 3))).unwrap());
+        cursor.seek_exact(address).unwrap();
+        assert_eq!(cursor.next_dup_val().unwrap(), Some(entry(4, 4)));
+        cursor.delete_current_duplicates().unwrap();
+        assert_eq!(cursor.first().unwrap(), Some((Address::with_last_byte(2), entry(1, 5))));
+        assert_eq!(tx.entries::<PlainStorageState>().unwrap(), 1);
+    }
+}
@@ -0,0 +1,143 @@
+//! Transactions of the in-memory database.
+
+use super::{
+    compress_value, cursor::Cursor, encode_key, put_row, SharedSnapshot, Snapshot, TableRows,
+    TransactionKind, WriterGuard, RO, RW,
+};
+use crate::{tables::utils::decode_one, DatabaseError};
+use parking_lot::RwLock;
+use reth_db_api::{
+    table::{DupSort, Table, TableImporter},
+    transaction::{DbTx, DbTxMut},
+};
+use std::{borrow::Cow, marker::PhantomData, sync::Arc};
+
+/// A transaction of the [`MemoryDatabase`](super::MemoryDatabase).
+///
+/// The transaction works on a snapshot of the tables at the time it was opened. The changes of a
+/// read-write transaction are visible to its cursors, and to other transactions once it's
+/// committed.
+#[derive(Debug)]
+pub struct Tx<K: TransactionKind> {
+    /// The tables of the transaction.
+    tables: SharedSnapshot,
+    /// Guard of the read-write transaction, to commit its changes.
+    writer: Option<WriterGuard>,
+    _kind: PhantomData<K>,
+}
+
+impl Tx<RO> {
+    /// Creates a read-only transaction on the given tables.
+    pub(crate) fn new_ro(tables: Snapshot) -> Self {
+        Self { tables: Arc::new(RwLock::new(tables)), writer: None, _kind: PhantomData }
+    }
+}
+
+impl Tx<RW> {
+    /// Creates a read-write transaction on the given tables.
+    pub(crate) fn new_rw(tables: Snapshot, writer: WriterGuard) -> Self {
+        Self { tables: Arc::new(RwLock::new(tables)), writer: Some(writer), _kind: PhantomData }
+    }
+
+    /// Applies the function to the rows of the table, which are copied if they're shared with
+    /// another snapshot.
+    fn update<T: Table, R>(&self, f: impl FnOnce(&mut TableRows) -> R) -> R {
+        let mut tables = self.tables.write();
+        f(Arc::make_mut(tables.entry(T::NAME).or_default()))
+    }
+}
+
+impl<K: TransactionKind> Tx<K> {
+    /// Returns the rows of the table.
+    fn rows<T: Table>(&self) -> Option<Arc<TableRows>> {
+        self.tables.read().get(T::NAME).cloned()
+    }
+
+    /// Creates a cursor over the table.
+    fn new_cursor<T: Table>(&self) -> Cursor<K, T> {
+        Cursor::new(self.tables.clone())
+    }
+}
+
+impl<K: TransactionKind> DbTx for Tx<K> {
+    type Cursor<T: Table> = Cursor<K, T>;
+    type DupCursor<T: DupSort> = Cursor<K, T>;
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<T::Value>, DatabaseError> {
+        let Some(rows) = self.rows::<T>() else { return Ok(None) };
+        rows.get(&encode_key::<T>(key))
+            .and_then(|values| values.first())
+            .map(|value| decode_one::<T>(Cow::Borrowed(value)))
+            .transpose()
+    }
+
+    fn commit(self) -> Result<bool, DatabaseError> {
+        if let Some(writer) = &self.writer {
+            writer.commit(self.tables.read().clone());
+        }
+        Ok(false)
+    }
+
+    fn abort(self) {}
+
+    fn cursor_read<T: Table>(&self) -> Result<Self::Cursor<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        Ok(self.rows::<T>().map_or(0, |rows| rows.values().map(|values| values.len()).sum()))
+    }
+
+    fn disable_long_read_transaction_safety(&mut self) {}
+}
+
+impl DbTxMut for Tx<RW> {
+    type CursorMut<T: Table> = Cursor<RW, T>;
+    type DupCursorMut<T: DupSort> = Cursor<RW, T>;
+
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let (key, value) = (encode_key::<T>(key), compress_value::<T>(value));
+        self.update::<T, _>(|rows| put_row::<T>(rows, key, value));
+        Ok(())
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let key = encode_key::<T>(key);
+        // like MDBX, the value is ignored for tables that are not `DUPSORT` tables
+        let value = value.filter(|_| T::DUPSORT).map(compress_value::<T>);
+        Ok(self.update::<T, _>(|rows| match value {
+            Some(value) => {
+                let Some(values) = rows.get_mut(&key) else { return false };
+                let deleted = values.remove(&value);
+                if values.is_empty() {
+                    rows.remove(&key);
+                }
+                deleted
+            }
+            None => rows.remove(&key).is_some(),
+        }))
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        self.update::<T, _>(|rows| rows.clear());
+        Ok(())
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+
+    fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
+        Ok(self.new_cursor())
+    }
+}
+
+impl TableImporter for Tx<RW> {}
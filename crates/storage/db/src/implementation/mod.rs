@@ -1,2 +1,4 @@
 #[cfg(feature = "mdbx")]
 pub(crate) mod mdbx;
+
+pub(crate) mod mem;
@@ -17,6 +17,7 @@
 
 mod implementation;
 pub mod lockfile;
+pub mod mem;
 #[cfg(feature = "mdbx")]
 mod metrics;
 pub mod static_file;
@@ -33,6 +34,8 @@ pub use tables::*;
 #[cfg(feature = "mdbx")]
 pub use utils::is_database_empty;
 
+pub use mem::MemoryDatabase;
+
 #[cfg(feature = "mdbx")]
 pub use mdbx::{create_db, init_db, open_db, open_db_read_only, DatabaseEnv, DatabaseEnvKind};
 
@@ -164,6 +167,13 @@ pub mod test_utils {
         Arc::new(TempDatabase { db: Some(db), path })
     }
 
+    /// Create in-memory database for testing, with a temporary directory for the files that are
+    /// used with it, e.g. the static files, which is deleted when the database is dropped.
+    pub fn create_test_memory_db() -> Arc<TempDatabase<MemoryDatabase>> {
+        let path = tempdir_path();
+        Arc::new(TempDatabase { db: Some(MemoryDatabase::new()), path })
+    }
+
     /// Create read only database for testing
     pub fn create_test_ro_db() -> Arc<TempDatabase<DatabaseEnv>> {
         let args = DatabaseArguments::new(ClientVersion::default())
@@ -0,0 +1,6 @@
+//! In-memory database, which doesn't persist anything to disk.
+//!
+//! It implements the same traits as the MDBX database, so it can replace it in tests and in
+//! environments without a file system.
+
+pub use crate::implementation::mem::*;
@@ -1,7 +1,7 @@
 use super::{ReceiptMask, TransactionMask};
 use crate::{
     add_static_file_mask,
-    static_file::mask::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask},
+    static_file::mask::{ColumnSelectorOne, ColumnSelectorThree, ColumnSelectorTwo, HeaderMask},
     HeaderTerminalDifficulties, RawValue, Receipts, Transactions,
 };
 use reth_db_api::table::Table;
@@ -13,6 +13,13 @@ add_static_file_mask!(HeaderMask, <HeaderTerminalDifficulties as Table>::Value,
 add_static_file_mask!(HeaderMask, BlockHash, 0b100);
 add_static_file_mask!(HeaderMask, Header, BlockHash, 0b101);
 add_static_file_mask!(HeaderMask, <HeaderTerminalDifficulties as Table>::Value, BlockHash, 0b110);
+add_static_file_mask!(
+    HeaderMask,
+    Header,
+    <HeaderTerminalDifficulties as Table>::Value,
+    BlockHash,
+    0b111
+);
 
 // RECEIPT MASKS
 add_static_file_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
@@ -16,7 +16,6 @@ pub mod codecs;
 mod raw;
 pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 
-#[cfg(feature = "mdbx")]
 pub(crate) mod utils;
 
 use reth_db_api::{
@@ -26,12 +25,13 @@ use reth_db_api::{
         client_version::ClientVersion,
         storage_sharded_key::StorageShardedKey,
         AccountBeforeTx, CompactU256, ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
+        StoredForkBlock, StoredInternalTransfers,
     },
     table::{Decode, DupSort, Encode, Table},
 };
 use reth_primitives::{
-    Account, Address, BlockHash, BlockNumber, Bytecode, Header, Receipt, Requests, StorageEntry,
-    TransactionSignedNoHash, TxHash, TxNumber, B256,
+    Account, Address, BlockHash, BlockNumber, Bytecode, Header, Receipt, ReorgRecord, Requests,
+    StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
 };
 use reth_primitives_traits::IntegerList;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
@@ -131,6 +131,7 @@ macro_rules! tables {
 
             impl reth_db_api::table::Table for $name {
                 const NAME: &'static str = table_names::$name;
+                const DUPSORT: bool = tables!(@bool $($subkey)?);
 
                 type Key = $key;
                 type Value = $value;
@@ -407,6 +408,43 @@ tables! {
     /// Stores EIP-7685 EL -> CL requests, indexed by block number.
     table BlockRequests<Key = BlockNumber, Value = Requests>;
 
+    /// Stores the internal ETH transfers of canonical transactions, if they are recorded during
+    /// execution.
+    ///
+    /// Transactions without internal transfers have no entry, except the last transaction of a
+    /// block, which always has an entry to mark the transfers of the block as recorded.
+    table InternalTransfers<Key = TxNumber, Value = StoredInternalTransfers>;
+
+    /// Stores validated blocks that are not part of the canonical chain by block hash, after they
+    /// were evicted from memory, so that reorgs to them don't require downloading them again.
+    table ForkBlocks<Key = BlockHash, Value = StoredForkBlock>;
+
+    /// Stores the encoded execution outcome and trie updates of the blocks in [`ForkBlocks`], so
+    /// that the blocks don't have to be executed again when a reorg makes them canonical.
+    table ForkBlockExecutions<Key = BlockHash, Value = Vec<u8>>;
+
+    /// Stores the numbers of the blocks with logs emitted by each address, if the log index is
+    /// enabled.
+    ///
+    /// The shards are keyed like the shards of [`AccountsHistory`], the last shard of an address
+    /// has the `u64::MAX` block number.
+    table LogAddressHistory<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores the numbers of the blocks with logs that have each topic, at any position, if the
+    /// log index is enabled.
+    ///
+    /// The shards are keyed like the shards of [`AccountsHistory`], the last shard of a topic has
+    /// the `u64::MAX` block number.
+    table LogTopicHistory<Key = ShardedKey<B256>, Value = BlockNumberList>;
+
+    /// Stores the reorgs of the canonical chain observed by the node, keyed by a sequence number
+    /// that increases with every reorg.
+    table ReorgJournal<Key = u64, Value = ReorgRecord>;
+
+    /// Stores the encoded checkpoints of the executed parts of a block whose execution was
+    /// interrupted by the execution stage, keyed by the index of the part.
+    table BlockExecutionCheckpoints<Key = u64, Value = Vec<u8>>;
+
     /// Stores generic chain state info, like the last finalized block.
     table ChainState<Key = ChainStateKey, Value = BlockNumber>;
 }
@@ -416,6 +454,8 @@ tables! {
 pub enum ChainStateKey {
     /// Last finalized block key
     LastFinalizedBlock,
+    /// Target block of an unwind that was started but not finished
+    UnwindTarget,
 }
 
 impl Encode for ChainStateKey {
@@ -424,16 +464,17 @@ impl Encode for ChainStateKey {
     fn encode(self) -> Self::Encoded {
         match self {
             Self::LastFinalizedBlock => [0],
+            Self::UnwindTarget => [1],
         }
     }
 }
 
 impl Decode for ChainStateKey {
     fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, reth_db_api::DatabaseError> {
-        if value.as_ref() == [0] {
-            Ok(Self::LastFinalizedBlock)
-        } else {
-            Err(reth_db_api::DatabaseError::Decode)
+        match value.as_ref() {
+            [0] => Ok(Self::LastFinalizedBlock),
+            [1] => Ok(Self::UnwindTarget),
+            _ => Err(reth_db_api::DatabaseError::Decode),
         }
     }
 }
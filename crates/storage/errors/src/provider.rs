@@ -118,6 +118,9 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[display(fmt = "state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// The internal transfers of the block were not recorded when it was executed.
+    #[display(fmt = "internal transfers of block #{_0} are not indexed")]
+    InternalTransfersNotIndexed(BlockNumber),
     /// Provider does not support this particular request.
     #[display(fmt = "this provider does not support this request")]
     UnsupportedProvider,
@@ -137,6 +140,12 @@ pub enum ProviderError {
     /// Trying to insert data from an unexpected block number.
     #[display(fmt = "trying to append data to {_0} as block #{_1} but expected block #{_2}")]
     UnexpectedStaticFileBlockNumber(StaticFileSegment, BlockNumber, BlockNumber),
+    /// Error when accessing the object store of cold static files.
+    #[display(fmt = "object store error: {_0}")]
+    ObjectStore(String),
+    /// Error when requesting data from a remote node over JSON-RPC.
+    #[display(fmt = "remote rpc error: {_0}")]
+    Rpc(String),
     /// Static File Provider was initialized as read-only.
     #[display(fmt = "cannot get a writer on a read-only environment.")]
     ReadOnlyStaticFileAccess,
@@ -216,6 +225,14 @@ pub enum ConsistentViewError {
         /// The tip diff.
         tip: GotExpected<Option<B256>>,
     },
+    /// Error thrown when a block that was read from an earlier view is no longer canonical.
+    #[display(fmt = "block #{number} is no longer canonical: {hash:?}")]
+    Reorged {
+        /// The number of the block.
+        number: BlockNumber,
+        /// The canonical hash diff.
+        hash: GotExpected<Option<B256>>,
+    },
 }
 
 impl From<ConsistentViewError> for ProviderError {
@@ -361,6 +361,10 @@ impl GeometryInfo {
     pub const fn min(&self) -> u64 {
         self.0.lower
     }
+
+    pub const fn max(&self) -> u64 {
+        self.0.upper
+    }
 }
 
 /// Environment information.
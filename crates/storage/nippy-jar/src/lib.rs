@@ -45,7 +45,8 @@ pub use cursor::NippyJarCursor;
 mod writer;
 pub use writer::{ConsistencyFailStrategy, NippyJarWriter};
 
-const NIPPY_JAR_VERSION: usize = 1;
+/// The version of the `NippyJar` format written by this version of reth.
+pub const NIPPY_JAR_VERSION: usize = 1;
 
 const INDEX_FILE_EXTENSION: &str = "idx";
 const OFFSETS_FILE_EXTENSION: &str = "off";
@@ -208,6 +209,11 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Gets the version of the `NippyJar` format the jar was written with.
+    pub const fn version(&self) -> usize {
+        self.version
+    }
+
     /// Gets a reference to the user header.
     pub const fn user_header(&self) -> &H {
         &self.user_header
@@ -41,9 +41,12 @@ pub use revm::db::states::OriginalValuesKnown;
 /// Writer standalone type.
 pub mod writer;
 
+/// Consistency checks of the stored canonical chain.
+pub mod verify;
+
 pub use reth_chain_state::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions,
+    CanonStateNotifications, CanonStateSubscriptions, InMemoryRetention,
 };
 
 pub(crate) fn to_range<R: std::ops::RangeBounds<u64>>(bounds: R) -> std::ops::Range<u64> {
@@ -1,37 +1,62 @@
 use crate::{
-    providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    providers::{ConsistentProvider, RangeIter, StaticFileProvider},
+    to_range,
+    verify::{ConsistencyChecker, ConsistencyReport},
+    AccountHistoryReader, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
     BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker, CanonStateNotifications,
     CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    DatabaseProviderRO, EvmEnvProvider, FinalizedBlockReader, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    RequestsProvider, StageCheckpointReader, StateProviderBox, StateProviderFactory,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    DatabaseProviderRO, EvmEnvProvider, FinalizedBlockReader, ForkBlockReader, HeaderProvider,
+    InternalTransfersProvider, LogIndexProvider, LogsProvider, ProviderError, ProviderFactory,
+    PruneCheckpointReader, RangeRevIter, ReceiptProvider, ReceiptProviderIdExt, ReorgJournalReader,
+    RequestsProvider, StageCheckpointNotifications, StageCheckpointReader,
+    StageCheckpointSubscriptions, StateDiffProvider, StateProviderBox, StateProviderFactory,
+    StateReader, StaticFileProviderFactory, StorageChangeSetReader, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use alloy_rpc_types_engine::ForkchoiceState;
-use reth_chain_state::{BlockState, CanonicalInMemoryState, MemoryOverlayStateProvider};
+use parking_lot::Mutex;
+use reth_chain_state::{
+    BlockState, CanonicalInMemoryState, ExecutedBlock, InMemoryRetention,
+    MemoryOverlayStateProvider,
+};
 use reth_chainspec::{ChainInfo, ChainSpec};
+use reth_db::tables;
 use reth_db_api::{
+    cursor::DbDupCursorRO,
     database::Database,
     models::{AccountBeforeTx, StoredBlockBodyIndices},
+    transaction::DbTx,
 };
 use reth_evm::ConfigureEvmEnv;
+use reth_execution_types::ExecutionOutcome;
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
 use reth_primitives::{
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, EthereumHardforks, Header, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    BlockNumberOrTag, BlockWithSenders, EthereumHardforks, GotExpected, Header, Log, Receipt,
+    SealedBlock, SealedBlockWithSenders, SealedHeader, StorageEntry, TransactionMeta,
+    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
+    U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
-use reth_storage_errors::provider::ProviderResult;
+use reth_storage_errors::provider::{ConsistentViewError, ProviderResult};
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
+use schnellru::{ByLength, LruMap};
 use std::{
-    ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
+    collections::{BTreeMap, HashMap},
+    ops::{Add, Bound, Range, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
     time::Instant,
 };
 use tracing::trace;
 
+/// The maximum number of senders that were recovered from the signatures of persisted
+/// transactions, that are kept in the cache of the [`BlockchainProvider2`].
+const MAX_CACHED_RECOVERED_SENDERS: u32 = 1_000;
+
 /// The main type for interacting with the blockchain.
 ///
 /// This type serves as the main entry point for interacting with the blockchain and provides data
@@ -44,6 +69,15 @@ pub struct BlockchainProvider2<DB> {
     /// Tracks the chain info wrt forkchoice updates and in memory canonical
     /// state.
     pub(super) canonical_in_memory_state: CanonicalInMemoryState,
+    /// The senders recovered from the signatures of persisted transactions, by transaction hash,
+    /// for transactions of which the senders were pruned from the database.
+    recovered_senders: Arc<Mutex<LruMap<TxHash, Address, ByLength>>>,
+    /// The number of the first transaction after the anchor of the in memory blocks, by the hash
+    /// of the anchor, so that the body indices of in memory blocks don't read the database on
+    /// every lookup.
+    anchor_next_tx_num: Arc<Mutex<Option<(BlockHash, TxNumber)>>>,
+    /// Metrics of the lookups that are served from memory or from the database.
+    metrics: Arc<BlockchainProviderMetrics>,
 }
 
 impl<DB> Clone for BlockchainProvider2<DB> {
@@ -51,6 +85,9 @@ impl<DB> Clone for BlockchainProvider2<DB> {
         Self {
             database: self.database.clone(),
             canonical_in_memory_state: self.canonical_in_memory_state.clone(),
+            recovered_senders: self.recovered_senders.clone(),
+            anchor_next_tx_num: self.anchor_next_tx_num.clone(),
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -62,25 +99,37 @@ where
     /// Create a new provider using only the database, fetching the latest header from
     /// the database to initialize the provider.
     pub fn new(database: ProviderFactory<DB>) -> ProviderResult<Self> {
+        Self::with_retention(database, InMemoryRetention::default())
+    }
+
+    /// Create a new provider using only the database, fetching the latest header from the
+    /// database to initialize the provider, that keeps canonical blocks in memory according to the
+    /// given retention policy.
+    pub fn with_retention(
+        database: ProviderFactory<DB>,
+        retention: InMemoryRetention,
+    ) -> ProviderResult<Self> {
         let provider = database.provider()?;
         let best: ChainInfo = provider.chain_info()?;
         match provider.header_by_number(best.best_number)? {
             Some(header) => {
                 drop(provider);
-                Ok(Self::with_latest(database, header.seal(best.best_hash))?)
+                Ok(Self::with_latest(database, header.seal(best.best_hash), retention)?)
             }
             None => Err(ProviderError::HeaderNotFound(best.best_number.into())),
         }
     }
 
     /// Create new provider instance that wraps the database and the blockchain tree, using the
-    /// provided latest header to initialize the chain info tracker.
+    /// provided latest header to initialize the chain info tracker, and the retention policy to
+    /// decide how many canonical blocks are kept in memory before they are persisted.
     ///
     /// This returns a `ProviderResult` since it tries the retrieve the last finalized header from
     /// `database`.
     pub fn with_latest(
         database: ProviderFactory<DB>,
         latest: SealedHeader,
+        retention: InMemoryRetention,
     ) -> ProviderResult<Self> {
         let provider = database.provider()?;
         let finalized_header = provider
@@ -90,15 +139,66 @@ where
             .flatten();
         Ok(Self {
             database,
-            canonical_in_memory_state: CanonicalInMemoryState::with_head(latest, finalized_header),
+            canonical_in_memory_state: CanonicalInMemoryState::with_head(
+                latest,
+                finalized_header,
+                retention,
+            ),
+            recovered_senders: Arc::new(Mutex::new(LruMap::new(ByLength::new(
+                MAX_CACHED_RECOVERED_SENDERS,
+            )))),
+            anchor_next_tx_num: Default::default(),
+            metrics: Default::default(),
         })
     }
 
+    /// Returns the number of the first transaction after the given anchor of the in memory blocks,
+    /// reading the body indices of the anchor from the database only when the anchor changed.
+    fn anchor_next_tx_num(&self, anchor: BlockNumHash) -> ProviderResult<TxNumber> {
+        let mut cached = self.anchor_next_tx_num.lock();
+        if let Some((hash, next_tx_num)) = *cached {
+            if hash == anchor.hash {
+                return Ok(next_tx_num)
+            }
+        }
+
+        let next_tx_num = self
+            .database
+            .block_body_indices(anchor.number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(anchor.number))?
+            .next_tx_num();
+        *cached = Some((anchor.hash, next_tx_num));
+        Ok(next_tx_num)
+    }
+
     /// Gets a clone of `canonical_in_memory_state`.
     pub fn canonical_in_memory_state(&self) -> CanonicalInMemoryState {
         self.canonical_in_memory_state.clone()
     }
 
+    /// Waits until the given block is persisted to the database and static files, which resolves
+    /// right away if it already is.
+    ///
+    /// This is useful to make sure the data on disk includes the block, e.g. before taking a
+    /// snapshot of the data directory.
+    ///
+    /// Returns [`ProviderError::BlockHashNotFound`] if a different block was persisted at the
+    /// height of the block, because the block was reorged out.
+    pub async fn wait_for_persisted(&self, block: BlockNumHash) -> ProviderResult<()> {
+        // subscribe before reading the database, to not miss blocks that are persisted in between
+        let mut persisted_block = self.canonical_in_memory_state.subscribe_persisted_block();
+        if self.database.last_block_number()? < block.number {
+            // the sender is owned by the in-memory state of this provider, so it can't be dropped
+            // while waiting
+            let _ = persisted_block.wait_for(|persisted| persisted.number >= block.number).await;
+        }
+
+        if self.database.block_hash(block.number)? != Some(block.hash) {
+            return Err(ProviderError::BlockHashNotFound(block.hash))
+        }
+        Ok(())
+    }
+
     // Helper function to convert range bounds
     fn convert_range_bounds<T>(
         &self,
@@ -123,6 +223,172 @@ where
         (start, end)
     }
 
+    /// Returns a consistent view of the canonical chain, across the in-memory state and the
+    /// database, for reads that span both, like range queries.
+    ///
+    /// The view captures the in-memory state and pins a database transaction, so it should be
+    /// dropped as soon as the reads are done.
+    pub fn consistent_provider(&self) -> ProviderResult<ConsistentProvider<DB>> {
+        ConsistentProvider::new(&self.database, &self.canonical_in_memory_state)
+    }
+
+    /// Checks the consistency of the last `depth` persisted blocks and the blocks in memory.
+    ///
+    /// This is fast enough to run at startup, to detect torn writes after a crash. See
+    /// [`ConsistencyChecker`] for the checks that are done.
+    pub fn check_consistency(&self, depth: u64) -> ProviderResult<ConsistencyReport> {
+        let view = self.consistent_provider()?;
+        ConsistencyChecker::new(&view).check_tip(depth)
+    }
+
+    /// Returns an iterator over the headers of the range, which reads them lazily in chunks.
+    ///
+    /// See [`Self::chunked_range_iter`] for how the chunks are read.
+    pub fn headers_iter(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<Header>>> {
+        Ok(self.chunked_range_iter(to_range(range), |view, chunk| {
+            let headers = view.headers_range(chunk.start..=chunk.end - 1)?;
+            let end_block = headers.last().map(|header| header.number);
+            Ok((headers, end_block))
+        }))
+    }
+
+    /// Returns an iterator over the receipts of each block of the range, which reads them lazily
+    /// in chunks.
+    ///
+    /// See [`Self::chunked_range_iter`] for how the chunks are read.
+    pub fn receipts_by_block_iter(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<Vec<Receipt>>>> {
+        Ok(self.chunked_range_iter(to_range(range), |view, chunk| {
+            let receipts = view.receipts_by_block_range(chunk.start..=chunk.end - 1)?;
+            let end_block = (receipts.len() as u64).checked_sub(1).map(|last| chunk.start + last);
+            Ok((receipts, end_block))
+        }))
+    }
+
+    /// Returns an iterator over the transactions of the transaction range, which reads them
+    /// lazily in chunks.
+    ///
+    /// See [`Self::chunked_range_iter`] for how the chunks are read.
+    pub fn transactions_by_tx_range_iter(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<TransactionSignedNoHash>>> {
+        Ok(self.chunked_range_iter(to_range(range), |view, chunk| {
+            let transactions = view.transactions_by_tx_range(chunk.clone())?;
+            let end_block = match (transactions.len() as u64).checked_sub(1) {
+                Some(last) => view.transaction_block(chunk.start + last)?,
+                None => None,
+            };
+            Ok((transactions, end_block))
+        }))
+    }
+
+    /// Returns an iterator over the range, which reads each chunk from a new
+    /// [`ConsistentProvider`], so that no database transaction is held while the iterator is not
+    /// advanced.
+    ///
+    /// `read_chunk` returns the items of the chunk and the number of the block that its last item
+    /// belongs to. See [`Self::read_range_chunk`] for how the chunks are joined.
+    fn chunked_range_iter<T>(
+        &self,
+        range: Range<u64>,
+        mut read_chunk: impl FnMut(
+            &ConsistentProvider<DB>,
+            Range<u64>,
+        ) -> ProviderResult<(Vec<T>, Option<BlockNumber>)>,
+    ) -> RangeIter<Self, T, impl FnMut(&Self, Range<u64>) -> ProviderResult<Vec<T>>> {
+        let mut last_block = None;
+        RangeIter::new(self.clone(), range, move |provider: &Self, chunk| {
+            provider.read_range_chunk(&mut last_block, |view| read_chunk(view, chunk))
+        })
+    }
+
+    /// Returns an iterator over the block range in descending order, which reads each chunk from
+    /// a new [`ConsistentProvider`], like [`Self::chunked_range_iter`]. The range ends at the
+    /// canonical tip.
+    ///
+    /// `read_chunk` returns the items of the chunk in descending order and the number of the
+    /// block that its last item belongs to.
+    fn chunked_range_rev_iter<T>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        mut read_chunk: impl FnMut(
+            &ConsistentProvider<DB>,
+            RangeInclusive<BlockNumber>,
+        ) -> ProviderResult<(Vec<T>, Option<BlockNumber>)>,
+    ) -> RangeRevIter<T, impl FnMut(RangeInclusive<BlockNumber>) -> ProviderResult<Vec<T>>> {
+        let (start, end) = range.into_inner();
+        let end = end.min(self.canonical_in_memory_state.get_canonical_block_number());
+        let provider = self.clone();
+        let mut last_block = None;
+        RangeRevIter::new(start..=end, move |chunk| {
+            provider.read_range_chunk(&mut last_block, |view| read_chunk(view, chunk))
+        })
+    }
+
+    /// Reads the next chunk of a range iterator from a new [`ConsistentProvider`].
+    ///
+    /// Before the chunk is read, the block that the last item of the previous chunk belongs to is
+    /// checked to still be canonical, so that the items of two chains are never joined if the
+    /// chain was reorged in between. In that case, a [`ConsistentViewError::Reorged`] error is
+    /// returned and the iteration ends.
+    fn read_range_chunk<T>(
+        &self,
+        last_block: &mut Option<BlockNumHash>,
+        read_chunk: impl FnOnce(
+            &ConsistentProvider<DB>,
+        ) -> ProviderResult<(Vec<T>, Option<BlockNumber>)>,
+    ) -> ProviderResult<Vec<T>> {
+        let view = self.consistent_provider()?;
+        if let Some(last_block) = *last_block {
+            let hash = view.block_hash(last_block.number)?;
+            if hash != Some(last_block.hash) {
+                return Err(ConsistentViewError::Reorged {
+                    number: last_block.number,
+                    hash: GotExpected::new(hash, Some(last_block.hash)),
+                }
+                .into())
+            }
+        }
+
+        let (items, end_block) = read_chunk(&view)?;
+        if let Some(number) = end_block {
+            *last_block = view.block_hash(number)?.map(|hash| BlockNumHash::new(number, hash));
+        }
+        Ok(items)
+    }
+
+    /// Returns the block that contains the transaction with the given number, with the senders of
+    /// its transactions.
+    ///
+    /// This resolves the block like the other lookups by transaction number, instead of a
+    /// [`TransactionsProvider::transaction_block`] lookup followed by
+    /// [`BlockReader::block_with_senders`].
+    pub fn block_with_senders_by_tx_id(
+        &self,
+        id: TxNumber,
+    ) -> ProviderResult<Option<BlockWithSenders>> {
+        let mut lookup = self.metrics.lookup(Lookup::BlockWithSendersByTxId);
+        let provider = self.database.provider()?;
+        let Some((block_state, number, _)) = self.block_state_by_tx_id(&provider, id)? else {
+            return Ok(None)
+        };
+
+        if let Some(block_state) = block_state {
+            lookup.in_memory();
+            let block = block_state.block().block().clone();
+            let senders = block_state.block().senders().clone();
+            return Ok(Some(BlockWithSenders { block: block.unseal(), senders }))
+        }
+
+        provider.block_with_senders(number.into(), TransactionVariant::WithHash)
+    }
+
     /// This uses a given [`BlockState`] to initialize a state provider for that block.
     fn block_state_provider(
         &self,
@@ -131,18 +397,38 @@ where
         let state = state.as_ref();
         let anchor_hash = state.anchor().hash;
         let latest_historical = self.database.history_by_block_hash(anchor_hash)?;
-        Ok(self.canonical_in_memory_state.state_provider(state.hash(), latest_historical))
+        Ok(state.state_provider(latest_historical))
+    }
+
+    /// Returns the total difficulty at the given header, which can also be the header of a
+    /// non-canonical block in memory, like a block of a fork.
+    fn total_difficulty_at(&self, header: &Header) -> ProviderResult<Option<U256>> {
+        if let Some(td) = self.header_td_by_number(header.number)? {
+            return Ok(Some(td))
+        }
+
+        // The block can still be a non-canonical block in memory that is higher than the
+        // canonical tip. Blocks in memory are post-merge blocks with zero difficulty, so the total
+        // difficulty is the one of the last persisted block.
+        if self.canonical_in_memory_state.non_canonical_state_by_hash(header.hash_slow()).is_some()
+        {
+            let last_persisted_block_number = self.database.last_block_number()?;
+            return self.database.header_td_by_number(last_persisted_block_number)
+        }
+
+        Ok(None)
     }
 
     /// Returns:
     /// 1. The block state as [`Some`] if the block is in memory, and [`None`] if the block is in
     ///    database.
-    /// 2. The in-block transaction index.
+    /// 2. The number of the block.
+    /// 3. The in-block transaction index.
     fn block_state_by_tx_id(
         &self,
         provider: &DatabaseProviderRO<DB>,
         id: TxNumber,
-    ) -> ProviderResult<Option<(Option<Arc<BlockState>>, usize)>> {
+    ) -> ProviderResult<Option<(Option<Arc<BlockState>>, BlockNumber, usize)>> {
         // Get the last block number stored in the database
         let last_database_block_number = provider.last_block_number()?;
 
@@ -153,7 +439,7 @@ where
         else {
             return Ok(None);
         };
-        let mut in_memory_tx_num = last_block_body_index.next_tx_num();
+        let in_memory_tx_num = last_block_body_index.next_tx_num();
 
         if id < in_memory_tx_num {
             // If the transaction number is less than the first in-memory transaction number, make a
@@ -162,36 +448,29 @@ where
             let Some(body_index) = provider.block_body_indices(block_number)? else {
                 return Ok(None)
             };
-            let tx_index = id - body_index.last_tx_num();
-            Ok(Some((None, tx_index as usize)))
+            let tx_index = id - body_index.first_tx_num();
+            Ok(Some((None, block_number, tx_index as usize)))
         } else {
-            // Otherwise, iterate through in-memory blocks and find the transaction with the
-            // matching number
-
+            // Otherwise, find the in-memory block that contains the transaction by its offset from
+            // the first in-memory transaction number
             let first_in_memory_block_number = last_database_block_number.saturating_add(1);
-            let last_in_memory_block_number =
-                self.canonical_in_memory_state.get_canonical_block_number();
-
-            for block_number in first_in_memory_block_number..=last_in_memory_block_number {
-                let Some(block_state) =
-                    self.canonical_in_memory_state.state_by_number(block_number)
-                else {
-                    return Ok(None);
-                };
-
-                let executed_block = block_state.block();
-                let block = executed_block.block();
-
-                for tx_index in 0..block.body.len() {
-                    if id == in_memory_tx_num {
-                        return Ok(Some((Some(block_state), tx_index)))
-                    }
-
-                    in_memory_tx_num += 1;
-                }
+            let in_memory = self
+                .canonical_in_memory_state
+                .canonical_segment()
+                .slice(first_in_memory_block_number..);
+            if in_memory.first().map(|block| block.block().number) !=
+                Some(first_in_memory_block_number)
+            {
+                return Ok(None)
             }
 
-            Ok(None)
+            let Some((block, tx_index)) = in_memory.block_by_tx_index(id - in_memory_tx_num) else {
+                return Ok(None)
+            };
+            Ok(self
+                .canonical_in_memory_state
+                .state_by_hash(block.block().hash())
+                .map(|block_state| (Some(block_state), block.block().number, tx_index)))
         }
     }
 }
@@ -239,7 +518,15 @@ where
     DB: Database,
 {
     fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
-        if let Some(block_state) = self.canonical_in_memory_state.state_by_hash(*block_hash) {
+        let mut lookup = self.metrics.lookup(Lookup::Header);
+        // the block can also be a non-canonical block in memory, e.g. of a fork, which can be
+        // the target of calls by block hash
+        if let Some(block_state) = self
+            .canonical_in_memory_state
+            .state_by_hash(*block_hash)
+            .or_else(|| self.canonical_in_memory_state.non_canonical_state_by_hash(*block_hash))
+        {
+            lookup.in_memory();
             return Ok(Some(block_state.block().block().header.header().clone()));
         }
 
@@ -247,7 +534,9 @@ where
     }
 
     fn header_by_number(&self, num: BlockNumber) -> ProviderResult<Option<Header>> {
+        let mut lookup = self.metrics.lookup(Lookup::HeaderByNumber);
         if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
+            lookup.in_memory();
             return Ok(Some(block_state.block().block().header.header().clone()));
         }
 
@@ -285,33 +574,24 @@ where
         let (start, end) = self.convert_range_bounds(range, || {
             self.canonical_in_memory_state.get_canonical_block_number()
         });
-        let mut range = start..=end;
-        let mut headers = Vec::with_capacity((end - start + 1) as usize);
-
-        // First, fetch the headers from the database
-        let mut db_headers = self.database.headers_range(range.clone())?;
-
-        // Advance the range iterator by the number of headers fetched from the database
-        range.nth(db_headers.len() - 1);
-
-        headers.append(&mut db_headers);
-
-        // Fetch the remaining headers from the in-memory state
-        for num in range {
-            if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
-                // TODO: there might be an update between loop iterations, we
-                // need to handle that situation.
-                headers.push(block_state.block().block().header.header().clone());
-            } else {
-                break
-            }
-        }
+        self.consistent_provider()?.headers_range(start..=end)
+    }
 
-        Ok(headers)
+    fn headers_range_rev(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<Header>> {
+        self.chunked_range_rev_iter(range, |view, chunk| {
+            let headers = view.headers_range_rev(chunk)?;
+            let end_block = headers.last().map(|header| header.number);
+            Ok((headers, end_block))
+        })
     }
 
     fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>> {
+        let mut lookup = self.metrics.lookup(Lookup::SealedHeader);
         if let Some(block_state) = self.canonical_in_memory_state.state_by_number(number) {
+            lookup.in_memory();
             return Ok(Some(block_state.block().block().header.clone()));
         }
 
@@ -325,66 +605,18 @@ where
         let (start, end) = self.convert_range_bounds(range, || {
             self.canonical_in_memory_state.get_canonical_block_number()
         });
-        let mut range = start..=end;
-        let mut sealed_headers = Vec::with_capacity((end - start + 1) as usize);
-
-        // First, fetch the headers from the database
-        let mut db_headers = self.database.sealed_headers_range(range.clone())?;
-
-        // Advance the range iterator by the number of headers fetched from the database
-        range.nth(db_headers.len() - 1);
-
-        sealed_headers.append(&mut db_headers);
-
-        // Fetch the remaining headers from the in-memory state
-        for num in range {
-            if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
-                // TODO: there might be an update between loop iterations, we
-                // need to handle that situation.
-                sealed_headers.push(block_state.block().block().header.clone());
-            } else {
-                break
-            }
-        }
-
-        Ok(sealed_headers)
+        self.consistent_provider()?.sealed_headers_range(start..=end)
     }
 
     fn sealed_headers_while(
         &self,
         range: impl RangeBounds<BlockNumber>,
-        mut predicate: impl FnMut(&SealedHeader) -> bool,
+        predicate: impl FnMut(&SealedHeader) -> bool,
     ) -> ProviderResult<Vec<SealedHeader>> {
         let (start, end) = self.convert_range_bounds(range, || {
             self.canonical_in_memory_state.get_canonical_block_number()
         });
-        let mut range = start..=end;
-        let mut sealed_headers = Vec::with_capacity((end - start + 1) as usize);
-
-        // First, fetch the headers from the database
-        let mut db_headers = self.database.sealed_headers_while(range.clone(), &mut predicate)?;
-
-        // Advance the range iterator by the number of headers fetched from the database
-        range.nth(db_headers.len() - 1);
-
-        sealed_headers.append(&mut db_headers);
-
-        // Fetch the remaining headers from the in-memory state
-        for num in range {
-            if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
-                let header = block_state.block().block().header.clone();
-                if !predicate(&header) {
-                    break
-                }
-                // TODO: there might be an update between loop iterations, we
-                // need to handle that situation.
-                sealed_headers.push(header);
-            } else {
-                break
-            }
-        }
-
-        Ok(sealed_headers)
+        self.consistent_provider()?.sealed_headers_while(start..=end, predicate)
     }
 }
 
@@ -393,42 +625,23 @@ where
     DB: Database,
 {
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
+        let mut lookup = self.metrics.lookup(Lookup::BlockHash);
         if let Some(block_state) = self.canonical_in_memory_state.state_by_number(number) {
+            lookup.in_memory();
             return Ok(Some(block_state.hash()));
         }
 
         self.database.block_hash(number)
     }
 
+    /// Returns the canonical hashes of the range `start..=end`, see
+    /// [`ConsistentProvider::canonical_hashes_range`].
     fn canonical_hashes_range(
         &self,
         start: BlockNumber,
         end: BlockNumber,
     ) -> ProviderResult<Vec<B256>> {
-        let mut range = start..=end;
-
-        let mut hashes = Vec::with_capacity((end - start + 1) as usize);
-
-        // First, fetch the hashes from the database
-        let mut db_hashes = self.database.canonical_hashes_range(start, end)?;
-
-        // Advance the range iterator by the number of blocks fetched from the database
-        range.nth(db_hashes.len() - 1);
-
-        hashes.append(&mut db_hashes);
-
-        // Fetch the remaining blocks from the in-memory state
-        for num in range {
-            if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
-                // TODO: there might be an update between loop iterations, we
-                // need to handle that situation.
-                hashes.push(block_state.hash());
-            } else {
-                break
-            }
-        }
-
-        Ok(hashes)
+        self.consistent_provider()?.canonical_hashes_range(start, end)
     }
 }
 
@@ -449,7 +662,9 @@ where
     }
 
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
+        let mut lookup = self.metrics.lookup(Lookup::BlockNumber);
         if let Some(block_state) = self.canonical_in_memory_state.state_by_hash(hash) {
+            lookup.in_memory();
             return Ok(Some(block_state.number()));
         }
 
@@ -484,7 +699,9 @@ where
                 // check in memory first
                 // Note: it's fine to return the unsealed block because the caller already has
                 // the hash
+                let mut lookup = self.metrics.lookup(Lookup::FindBlockByHash);
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_hash(hash) {
+                    lookup.in_memory();
                     return Ok(Some(block_state.block().block().clone().unseal()));
                 }
                 self.database.find_block_by_hash(hash, source)
@@ -499,7 +716,9 @@ where
         match id {
             BlockHashOrNumber::Hash(hash) => self.find_block_by_hash(hash, BlockSource::Any),
             BlockHashOrNumber::Number(num) => {
+                let mut lookup = self.metrics.lookup(Lookup::BlockByNumber);
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
+                    lookup.in_memory();
                     return Ok(Some(block_state.block().block().clone().unseal()));
                 }
 
@@ -543,34 +762,18 @@ where
         &self,
         number: BlockNumber,
     ) -> ProviderResult<Option<StoredBlockBodyIndices>> {
-        if let Some(indices) = self.database.block_body_indices(number)? {
-            Ok(Some(indices))
-        } else if let Some(state) = self.canonical_in_memory_state.state_by_number(number) {
-            // we have to construct the stored indices for the in memory blocks
-            //
-            // To calculate this we will fetch the anchor block and walk forward from all parents
-            let mut parent_chain = state.parent_state_chain();
-            parent_chain.reverse();
-            let anchor_num = state.anchor().number;
-            let mut stored_indices = self
-                .database
-                .block_body_indices(anchor_num)?
-                .ok_or_else(|| ProviderError::BlockBodyIndicesNotFound(anchor_num))?;
-            stored_indices.first_tx_num = stored_indices.next_tx_num();
-
-            for state in parent_chain {
-                let txs = state.block().block.body.len() as u64;
-                if state.block().block().number == number {
-                    stored_indices.tx_count = txs;
-                } else {
-                    stored_indices.first_tx_num += txs;
-                }
-            }
+        let Some(state) = self.canonical_in_memory_state.state_by_number(number) else {
+            return self.database.block_body_indices(number)
+        };
 
-            Ok(Some(stored_indices))
-        } else {
-            Ok(None)
-        }
+        // we have to construct the stored indices for the in memory blocks
+        //
+        // The transactions of the block follow the transactions of the anchor block and of the
+        // in memory parents, whose number is cached in the block state
+        Ok(Some(StoredBlockBodyIndices {
+            first_tx_num: self.anchor_next_tx_num(state.anchor())? + state.parent_tx_count(),
+            tx_count: state.block().block().body.len() as u64,
+        }))
     }
 
     /// Returns the block with senders with matching number or hash from database.
@@ -584,9 +787,11 @@ where
         id: BlockHashOrNumber,
         transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<BlockWithSenders>> {
+        let mut lookup = self.metrics.lookup(Lookup::BlockWithSenders);
         match id {
             BlockHashOrNumber::Hash(hash) => {
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_hash(hash) {
+                    lookup.in_memory();
                     let block = block_state.block().block().clone();
                     let senders = block_state.block().senders().clone();
                     return Ok(Some(BlockWithSenders { block: block.unseal(), senders }));
@@ -594,6 +799,7 @@ where
             }
             BlockHashOrNumber::Number(num) => {
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
+                    lookup.in_memory();
                     let block = block_state.block().block().clone();
                     let senders = block_state.block().senders().clone();
                     return Ok(Some(BlockWithSenders { block: block.unseal(), senders }));
@@ -608,9 +814,11 @@ where
         id: BlockHashOrNumber,
         transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        let mut lookup = self.metrics.lookup(Lookup::SealedBlockWithSenders);
         match id {
             BlockHashOrNumber::Hash(hash) => {
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_hash(hash) {
+                    lookup.in_memory();
                     let block = block_state.block().block().clone();
                     let senders = block_state.block().senders().clone();
                     return Ok(Some(SealedBlockWithSenders { block, senders }));
@@ -618,6 +826,7 @@ where
             }
             BlockHashOrNumber::Number(num) => {
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
+                    lookup.in_memory();
                     let block = block_state.block().block().clone();
                     let senders = block_state.block().senders().clone();
                     return Ok(Some(SealedBlockWithSenders { block, senders }));
@@ -627,89 +836,33 @@ where
         self.database.sealed_block_with_senders(id, transaction_kind)
     }
 
-    fn block_range(&self, mut range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
-        let capacity = (range.end() - range.start() + 1) as usize;
-        let mut blocks = Vec::with_capacity(capacity);
-
-        // First, fetch the blocks from the database
-        let mut db_blocks = self.database.block_range(range.clone())?;
-        blocks.append(&mut db_blocks);
-
-        // Advance the range iterator by the number of blocks fetched from the database
-        range.nth(db_blocks.len() - 1);
-
-        // Fetch the remaining blocks from the in-memory state
-        for num in range {
-            if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
-                // TODO: there might be an update between loop iterations, we
-                // need to handle that situation.
-                blocks.push(block_state.block().block().clone().unseal());
-            } else {
-                break
-            }
-        }
+    fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
+        self.consistent_provider()?.block_range(range)
+    }
 
-        Ok(blocks)
+    fn blocks_range_rev(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<Block>> {
+        self.chunked_range_rev_iter(range, |view, chunk| {
+            let blocks = view.blocks_range_rev(chunk)?;
+            let end_block = blocks.last().map(|block| block.header.number);
+            Ok((blocks, end_block))
+        })
     }
 
     fn block_with_senders_range(
         &self,
-        mut range: RangeInclusive<BlockNumber>,
+        range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<BlockWithSenders>> {
-        let capacity = (range.end() - range.start() + 1) as usize;
-        let mut blocks = Vec::with_capacity(capacity);
-
-        // First, fetch the blocks from the database
-        let mut db_blocks = self.database.block_with_senders_range(range.clone())?;
-        blocks.append(&mut db_blocks);
-
-        // Advance the range iterator by the number of blocks fetched from the database
-        range.nth(db_blocks.len() - 1);
-
-        // Fetch the remaining blocks from the in-memory state
-        for num in range {
-            if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
-                let block = block_state.block().block().clone();
-                let senders = block_state.block().senders().clone();
-                // TODO: there might be an update between loop iterations, we
-                // need to handle that situation.
-                blocks.push(BlockWithSenders { block: block.unseal(), senders });
-            } else {
-                break
-            }
-        }
-
-        Ok(blocks)
+        self.consistent_provider()?.block_with_senders_range(range)
     }
 
     fn sealed_block_with_senders_range(
         &self,
-        mut range: RangeInclusive<BlockNumber>,
+        range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<Vec<SealedBlockWithSenders>> {
-        let capacity = (range.end() - range.start() + 1) as usize;
-        let mut blocks = Vec::with_capacity(capacity);
-
-        // First, fetch the blocks from the database
-        let mut db_blocks = self.database.sealed_block_with_senders_range(range.clone())?;
-        blocks.append(&mut db_blocks);
-
-        // Advance the range iterator by the number of blocks fetched from the database
-        range.nth(db_blocks.len() - 1);
-
-        // Fetch the remaining blocks from the in-memory state
-        for num in range {
-            if let Some(block_state) = self.canonical_in_memory_state.state_by_number(num) {
-                let block = block_state.block().block().clone();
-                let senders = block_state.block().senders().clone();
-                // TODO: there might be an update between loop iterations, we
-                // need to handle that situation.
-                blocks.push(SealedBlockWithSenders { block, senders });
-            } else {
-                break
-            }
-        }
-
-        Ok(blocks)
+        self.consistent_provider()?.sealed_block_with_senders_range(range)
     }
 }
 
@@ -735,22 +888,21 @@ where
 
         // Find the transaction in the in-memory state with the matching hash, and return its
         // number
-        let mut in_memory_tx_id = last_database_tx_id + 1;
-        for block_number in last_database_block_number.saturating_add(1)..=
-            self.canonical_in_memory_state.get_canonical_block_number()
+        let first_in_memory_block_number = last_database_block_number.saturating_add(1);
+        let in_memory = self
+            .canonical_in_memory_state
+            .canonical_segment()
+            .slice(first_in_memory_block_number..);
+        if in_memory.first().map(|block| block.block().number) != Some(first_in_memory_block_number)
         {
-            // TODO: there might be an update between loop iterations, we
-            // need to handle that situation.
-            let block_state = self
-                .canonical_in_memory_state
-                .state_by_number(block_number)
-                .ok_or(ProviderError::StateForNumberNotFound(block_number))?;
-            for tx in &block_state.block().block().body {
-                if tx.hash() == tx_hash {
-                    return Ok(Some(in_memory_tx_id))
-                }
+            return Ok(None)
+        }
 
-                in_memory_tx_id += 1;
+        for block in &in_memory {
+            let block = block.block();
+            if let Some(tx_index) = block.body.iter().position(|tx| tx.hash() == tx_hash) {
+                let tx_offset = in_memory.tx_offset(block.number).unwrap_or_default();
+                return Ok(Some(last_database_tx_id + 1 + tx_offset + tx_index as u64))
             }
         }
 
@@ -759,7 +911,7 @@ where
 
     fn transaction_by_id(&self, id: TxNumber) -> ProviderResult<Option<TransactionSigned>> {
         let provider = self.database.provider()?;
-        let Some((block_state, tx_index)) = self.block_state_by_tx_id(&provider, id)? else {
+        let Some((block_state, _, tx_index)) = self.block_state_by_tx_id(&provider, id)? else {
             return Ok(None)
         };
 
@@ -776,7 +928,7 @@ where
         id: TxNumber,
     ) -> ProviderResult<Option<TransactionSignedNoHash>> {
         let provider = self.database.provider()?;
-        let Some((block_state, tx_index)) = self.block_state_by_tx_id(&provider, id)? else {
+        let Some((block_state, _, tx_index)) = self.block_state_by_tx_id(&provider, id)? else {
             return Ok(None)
         };
 
@@ -810,26 +962,73 @@ where
         self.database.transaction_by_hash_with_meta(tx_hash)
     }
 
+    fn transactions_by_hashes(
+        &self,
+        hashes: &[TxHash],
+    ) -> ProviderResult<Vec<Option<(TransactionSigned, TransactionMeta)>>> {
+        let mut transactions = vec![None; hashes.len()];
+
+        // positions of the requested hashes, so the in-memory chain is only walked once
+        let mut positions = HashMap::<TxHash, Vec<usize>>::with_capacity(hashes.len());
+        for (index, hash) in hashes.iter().enumerate() {
+            positions.entry(*hash).or_default().push(index);
+        }
+
+        for block_state in self.canonical_in_memory_state.canonical_chain() {
+            if positions.is_empty() {
+                break
+            }
+
+            let block = &block_state.block().block;
+            for (index, tx) in block.body.iter().enumerate() {
+                let Some(indices) = positions.remove(&tx.hash()) else { continue };
+                let meta = TransactionMeta {
+                    tx_hash: tx.hash(),
+                    index: index as u64,
+                    block_hash: block.hash(),
+                    block_number: block.number,
+                    base_fee: block.base_fee_per_gas,
+                    timestamp: block.timestamp,
+                    excess_blob_gas: block.excess_blob_gas,
+                };
+                for position in indices {
+                    transactions[position] = Some((tx.clone(), meta));
+                }
+            }
+        }
+
+        // the remaining hashes are looked up in the database at once
+        let (database_hashes, database_positions): (Vec<_>, Vec<_>) = positions.into_iter().unzip();
+        let database_transactions = self.database.transactions_by_hashes(&database_hashes)?;
+        for (indices, transaction) in database_positions.into_iter().zip(database_transactions) {
+            for position in indices {
+                transactions[position].clone_from(&transaction);
+            }
+        }
+
+        Ok(transactions)
+    }
+
     fn transaction_block(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>> {
         let provider = self.database.provider()?;
-        Ok(self
-            .block_state_by_tx_id(&provider, id)?
-            .and_then(|(block_state, _)| block_state)
-            .map(|block_state| block_state.block().block().number))
+        Ok(self.block_state_by_tx_id(&provider, id)?.map(|(_, number, _)| number))
     }
 
     fn transactions_by_block(
         &self,
         id: BlockHashOrNumber,
     ) -> ProviderResult<Option<Vec<TransactionSigned>>> {
+        let mut lookup = self.metrics.lookup(Lookup::TransactionsByBlock);
         match id {
             BlockHashOrNumber::Hash(hash) => {
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_hash(hash) {
+                    lookup.in_memory();
                     return Ok(Some(block_state.block().block().body.clone()));
                 }
             }
             BlockHashOrNumber::Number(number) => {
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_number(number) {
+                    lookup.in_memory();
                     return Ok(Some(block_state.block().block().body.clone()));
                 }
             }
@@ -844,65 +1043,48 @@ where
         let (start, end) = self.convert_range_bounds(range, || {
             self.canonical_in_memory_state.get_canonical_block_number()
         });
-
-        let mut transactions = Vec::new();
-        let mut last_in_memory_block = None;
-
-        for number in start..=end {
-            if let Some(block_state) = self.canonical_in_memory_state.state_by_number(number) {
-                // TODO: there might be an update between loop iterations, we
-                // need to handle that situation.
-                transactions.push(block_state.block().block().body.clone());
-                last_in_memory_block = Some(number);
-            } else {
-                break;
-            }
-        }
-
-        if let Some(last_block) = last_in_memory_block {
-            if last_block < end {
-                let mut db_transactions =
-                    self.database.transactions_by_block_range((last_block + 1)..=end)?;
-                transactions.append(&mut db_transactions);
-            }
-        } else {
-            transactions = self.database.transactions_by_block_range(start..=end)?;
-        }
-
-        Ok(transactions)
+        self.consistent_provider()?.transactions_by_block_range(start..=end)
     }
 
     fn transactions_by_tx_range(
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<TransactionSignedNoHash>> {
-        self.database.transactions_by_tx_range(range)
+        self.consistent_provider()?.transactions_by_tx_range(range)
     }
 
     fn senders_by_tx_range(
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        self.database.senders_by_tx_range(range)
+        self.consistent_provider()?.senders_by_tx_range(range)
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
         let provider = self.database.provider()?;
-        let Some((block_state, tx_index)) = self.block_state_by_tx_id(&provider, id)? else {
+        let Some((block_state, _, tx_index)) = self.block_state_by_tx_id(&provider, id)? else {
             return Ok(None)
         };
 
         if let Some(block_state) = block_state {
-            let sender = block_state
-                .block()
-                .block()
-                .body
-                .get(tx_index)
-                .and_then(|transaction| transaction.recover_signer());
-            Ok(sender)
-        } else {
-            provider.transaction_sender(id)
+            // the senders of in-memory blocks were recovered before they were executed
+            return Ok(block_state.block().senders().get(tx_index).copied())
         }
+
+        if let Some(sender) = provider.transaction_sender(id)? {
+            return Ok(Some(sender))
+        }
+
+        // the sender was pruned, so it's recovered from the signature of the transaction
+        let Some(transaction) = provider.transaction_by_id(id)? else { return Ok(None) };
+        if let Some(sender) = self.recovered_senders.lock().get(&transaction.hash()) {
+            return Ok(Some(*sender))
+        }
+        let sender = transaction.recover_signer();
+        if let Some(sender) = sender {
+            self.recovered_senders.lock().insert(transaction.hash(), sender);
+        }
+        Ok(sender)
     }
 }
 
@@ -912,7 +1094,7 @@ where
 {
     fn receipt(&self, id: TxNumber) -> ProviderResult<Option<Receipt>> {
         let provider = self.database.provider()?;
-        let Some((block_state, tx_index)) = self.block_state_by_tx_id(&provider, id)? else {
+        let Some((block_state, _, tx_index)) = self.block_state_by_tx_id(&provider, id)? else {
             return Ok(None)
         };
 
@@ -947,14 +1129,17 @@ where
     }
 
     fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        let mut lookup = self.metrics.lookup(Lookup::ReceiptsByBlock);
         match block {
             BlockHashOrNumber::Hash(hash) => {
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_hash(hash) {
+                    lookup.in_memory();
                     return Ok(Some(block_state.executed_block_receipts()));
                 }
             }
             BlockHashOrNumber::Number(number) => {
                 if let Some(block_state) = self.canonical_in_memory_state.state_by_number(number) {
+                    lookup.in_memory();
                     return Ok(Some(block_state.executed_block_receipts()));
                 }
             }
@@ -967,7 +1152,7 @@ where
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Receipt>> {
-        self.database.receipts_by_tx_range(range)
+        self.consistent_provider()?.receipts_by_tx_range(range)
     }
 }
 
@@ -1020,7 +1205,9 @@ where
 
         let Some(number) = self.convert_hash_or_number(id)? else { return Ok(None) };
 
+        let mut lookup = self.metrics.lookup(Lookup::WithdrawalsByBlock);
         if let Some(block) = self.canonical_in_memory_state.state_by_number(number) {
+            lookup.in_memory();
             Ok(block.block().block().withdrawals.clone())
         } else {
             self.database.withdrawals_by_block(id, timestamp)
@@ -1061,44 +1248,237 @@ where
     }
 }
 
-impl<DB> StageCheckpointReader for BlockchainProvider2<DB>
+impl<DB> ForkBlockReader for BlockchainProvider2<DB>
 where
     DB: Database,
 {
-    fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
-        self.database.provider()?.get_stage_checkpoint(id)
+    fn fork_block(&self, hash: BlockHash) -> ProviderResult<Option<ExecutedBlock>> {
+        self.database.fork_block(hash)
     }
+}
 
-    fn get_stage_checkpoint_progress(&self, id: StageId) -> ProviderResult<Option<Vec<u8>>> {
-        self.database.provider()?.get_stage_checkpoint_progress(id)
+impl<DB> InternalTransfersProvider for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn internal_transfers_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<Vec<reth_primitives::InternalTransfer>>>> {
+        let Some(number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+        if let Some(block_state) = self.canonical_in_memory_state.state_by_number(number) {
+            let block = block_state.block();
+            let internal_transfers =
+                block.execution_outcome().internal_transfers_by_block(number).to_vec();
+            // the transfers of every transaction are recorded if the index is enabled
+            if internal_transfers.len() != block.block().body.len() {
+                return Err(ProviderError::InternalTransfersNotIndexed(number))
+            }
+            Ok(Some(internal_transfers))
+        } else {
+            self.database.internal_transfers_by_block(id)
+        }
     }
+}
 
-    fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>> {
-        self.database.provider()?.get_all_checkpoints()
+impl<DB> StateDiffProvider for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn state_diff_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<reth_execution_types::StateDiff>> {
+        let Some(number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+
+        // the in-memory blocks of the view follow the blocks of its database, so that the state
+        // before an in-memory block can be read from both
+        let view = self.consistent_provider()?;
+        let last_database_block = view.database().last_block_number()?;
+        if number <= last_database_block {
+            return view.database().state_diff_by_block(number.into())
+        }
+        let in_memory = view.in_memory_range(last_database_block + 1, number)?;
+        let Some(block) = in_memory.last().filter(|block| block.block().number == number) else {
+            return Ok(None)
+        };
+
+        // the storage of an account before the block, which is only needed for the accounts that
+        // were destroyed in the block, from the database and the in-memory blocks before it
+        let storage_before = |address| -> ProviderResult<BTreeMap<B256, U256>> {
+            let mut storage = view
+                .database()
+                .tx_ref()
+                .cursor_dup_read::<tables::PlainStorageState>()?
+                .walk_dup(Some(address), None)?
+                .map(|entry| entry.map(|(_, entry)| (entry.key, entry.value)))
+                .collect::<Result<BTreeMap<_, _>, _>>()?;
+            for parent in in_memory.iter().filter(|parent| parent.block().number < number) {
+                let Some(account) = parent.execution_outcome().state().account(&address) else {
+                    continue
+                };
+                if account.was_destroyed() {
+                    storage.clear();
+                }
+                storage.extend(
+                    account
+                        .storage
+                        .iter()
+                        .map(|(slot, value)| (B256::from(*slot), value.present_value)),
+                );
+            }
+            storage.retain(|_, value| !value.is_zero());
+            Ok(storage)
+        };
+
+        // the execution outcome of an in-memory block only contains the changes of the block, and
+        // the code that existed before the block is part of the latest state
+        let state = self.latest()?;
+        let diff = reth_execution_types::StateDiff::from_bundle(
+            block.execution_outcome().state(),
+            |hash| state.bytecode_by_hash(hash),
+            storage_before,
+        )?;
+        Ok(Some(diff))
     }
 }
 
-impl<DB> EvmEnvProvider for BlockchainProvider2<DB>
+impl<DB> ReorgJournalReader for BlockchainProvider2<DB>
 where
     DB: Database,
 {
-    fn fill_env_at<EvmConfig>(
+    fn latest_reorgs(
         &self,
-        cfg: &mut CfgEnvWithHandlerCfg,
-        block_env: &mut BlockEnv,
-        at: BlockHashOrNumber,
-        evm_config: EvmConfig,
-    ) -> ProviderResult<()>
-    where
-        EvmConfig: ConfigureEvmEnv,
-    {
-        let hash = self.convert_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
-        let header = self.header(&hash)?.ok_or(ProviderError::HeaderNotFound(at))?;
-        self.fill_env_with_header(cfg, block_env, &header, evm_config)
+        limit: usize,
+    ) -> ProviderResult<Vec<(u64, reth_primitives::ReorgRecord)>> {
+        self.database.latest_reorgs(limit)
     }
+}
 
-    fn fill_env_with_header<EvmConfig>(
-        &self,
+impl<DB> BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    /// Returns the blocks of the range from the log index of the database, and the in-memory blocks
+    /// of the range with a log that matches the predicate.
+    ///
+    /// The in-memory blocks aren't indexed yet. They are only covered if the log index is caught up
+    /// with the database, and are few enough to check their logs.
+    fn blocks_with_logs(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        from_database: impl FnOnce(
+            &DatabaseProviderRO<DB>,
+            RangeInclusive<BlockNumber>,
+        ) -> ProviderResult<Vec<BlockNumber>>,
+        mut predicate: impl FnMut(&Log) -> bool,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let consistent = self.consistent_provider()?;
+        let database = consistent.database();
+        let mut blocks = from_database(database, range.clone())?;
+
+        let last_database_block = database.last_block_number()?;
+        if database.log_index_range()?.is_some_and(|indexed| *indexed.end() == last_database_block)
+        {
+            let (start, end) = range.into_inner();
+            let in_memory = consistent.in_memory_range(start.max(last_database_block + 1), end)?;
+            for block in in_memory.iter() {
+                let number = block.block().number;
+                let receipts = block.execution_outcome().receipts_by_block(number);
+                if receipts.iter().flatten().flat_map(|receipt| &receipt.logs).any(&mut predicate) {
+                    blocks.push(number);
+                }
+            }
+        }
+
+        Ok(blocks)
+    }
+}
+
+impl<DB> LogIndexProvider for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    /// Returns the range of blocks covered by the log index of the database, extended by the
+    /// in-memory blocks if the log index is caught up with the database.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let consistent = self.consistent_provider()?;
+        let database = consistent.database();
+        let Some(indexed) = database.log_index_range()? else { return Ok(None) };
+
+        match consistent.in_memory().last() {
+            Some(tip) if *indexed.end() == database.last_block_number()? => {
+                Ok(Some(*indexed.start()..=tip.block().number.max(*indexed.end())))
+            }
+            _ => Ok(Some(indexed)),
+        }
+    }
+
+    fn blocks_with_log_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.blocks_with_logs(
+            range,
+            |database, range| database.blocks_with_log_address(address, range),
+            |log| log.address == address,
+        )
+    }
+
+    fn blocks_with_log_topic(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.blocks_with_logs(
+            range,
+            |database, range| database.blocks_with_log_topic(topic, range),
+            |log| log.topics().contains(&topic),
+        )
+    }
+}
+
+impl<DB> LogsProvider for BlockchainProvider2<DB> where DB: Database {}
+
+impl<DB> StageCheckpointReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
+        self.database.provider()?.get_stage_checkpoint(id)
+    }
+
+    fn get_stage_checkpoint_progress(&self, id: StageId) -> ProviderResult<Option<Vec<u8>>> {
+        self.database.provider()?.get_stage_checkpoint_progress(id)
+    }
+
+    fn get_all_checkpoints(&self) -> ProviderResult<Vec<(String, StageCheckpoint)>> {
+        self.database.provider()?.get_all_checkpoints()
+    }
+}
+
+impl<DB> EvmEnvProvider for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn fill_env_at<EvmConfig>(
+        &self,
+        cfg: &mut CfgEnvWithHandlerCfg,
+        block_env: &mut BlockEnv,
+        at: BlockHashOrNumber,
+        evm_config: EvmConfig,
+    ) -> ProviderResult<()>
+    where
+        EvmConfig: ConfigureEvmEnv,
+    {
+        let hash = self.convert_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        let header = self.header(&hash)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.fill_env_with_header(cfg, block_env, &header, evm_config)
+    }
+
+    fn fill_env_with_header<EvmConfig>(
+        &self,
         cfg: &mut CfgEnvWithHandlerCfg,
         block_env: &mut BlockEnv,
         header: &Header,
@@ -1108,7 +1488,7 @@ where
         EvmConfig: ConfigureEvmEnv,
     {
         let total_difficulty = self
-            .header_td_by_number(header.number)?
+            .total_difficulty_at(header)?
             .ok_or_else(|| ProviderError::HeaderNotFound(header.number.into()))?;
         evm_config.fill_cfg_and_block_env(
             cfg,
@@ -1144,7 +1524,7 @@ where
         EvmConfig: ConfigureEvmEnv,
     {
         let total_difficulty = self
-            .header_td_by_number(header.number)?
+            .total_difficulty_at(header)?
             .ok_or_else(|| ProviderError::HeaderNotFound(header.number.into()))?;
         evm_config.fill_cfg_env(cfg, &self.database.chain_spec(), header, total_difficulty);
         Ok(())
@@ -1184,8 +1564,10 @@ where
     fn latest(&self) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::blockchain", "Getting latest block state provider");
         // use latest state provider if the head state exists
+        let mut lookup = self.metrics.lookup(Lookup::LatestState);
         if let Some(state) = self.canonical_in_memory_state.head_state() {
             trace!(target: "providers::blockchain", "Using head state for latest state provider");
+            lookup.in_memory();
             Ok(self.block_state_provider(state)?.boxed())
         } else {
             trace!(target: "providers::blockchain", "Using database state for latest state provider");
@@ -1207,11 +1589,13 @@ where
 
     fn history_by_block_hash(&self, block_hash: BlockHash) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::blockchain", ?block_hash, "Getting history by block hash");
+        let mut lookup = self.metrics.lookup(Lookup::HistoryByBlockHash);
         if let Ok(state) = self.database.history_by_block_hash(block_hash) {
             // This could be tracked by a block in the database block
             Ok(state)
         } else if let Some(state) = self.canonical_in_memory_state.state_by_hash(block_hash) {
             // ... or this could be tracked by the in memory state
+            lookup.in_memory();
             let state_provider = self.block_state_provider(state)?;
             Ok(Box::new(state_provider))
         } else {
@@ -1254,14 +1638,11 @@ where
     }
 
     fn pending_state_by_hash(&self, block_hash: B256) -> ProviderResult<Option<StateProviderBox>> {
-        let historical = self.database.history_by_block_hash(block_hash)?;
-        if let Some(block) = self.canonical_in_memory_state.pending_block_num_hash() {
-            if block.hash == block_hash {
-                let pending_provider =
-                    self.canonical_in_memory_state.state_provider(block_hash, historical);
-
-                return Ok(Some(Box::new(pending_provider)))
-            }
+        // this could be the pending block or any other executed block that is not canonical, like
+        // a block of a fork
+        if let Some(state) = self.canonical_in_memory_state.non_canonical_state_by_hash(block_hash)
+        {
+            return Ok(Some(Box::new(self.block_state_provider(state)?)))
         }
         Ok(None)
     }
@@ -1310,6 +1691,14 @@ where
         self.canonical_in_memory_state.last_received_update_timestamp()
     }
 
+    fn on_forkchoice_update_acknowledged(&self) {
+        self.canonical_in_memory_state.on_forkchoice_update_acknowledged();
+    }
+
+    fn on_new_payload_received(&self, timestamp: u64) {
+        self.canonical_in_memory_state.on_new_payload_received(timestamp);
+    }
+
     fn on_transition_configuration_exchanged(&self) {
         self.canonical_in_memory_state.on_transition_configuration_exchanged();
     }
@@ -1333,6 +1722,7 @@ where
 
 impl<DB> BlockReaderIdExt for BlockchainProvider2<DB>
 where
+    DB: Database,
     Self: BlockReader + BlockIdReader + ReceiptProviderIdExt,
 {
     fn block_by_id(&self, id: BlockId) -> ProviderResult<Option<Block>> {
@@ -1353,6 +1743,25 @@ where
         }
     }
 
+    fn block_by_transaction_hash(
+        &self,
+        hash: TxHash,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        let mut lookup = self.metrics.lookup(Lookup::BlockByTransactionHash);
+        if let Some(block_state) = self.canonical_in_memory_state.state_by_transaction_hash(hash) {
+            lookup.in_memory();
+            let block = block_state.block().block().clone();
+            let senders = block_state.block().senders().clone();
+            return Ok(Some(SealedBlockWithSenders { block, senders }))
+        }
+
+        let Some((_, meta)) = self.database.transaction_by_hash_with_meta(hash)? else {
+            return Ok(None)
+        };
+        self.database
+            .sealed_block_with_senders(meta.block_hash.into(), TransactionVariant::WithHash)
+    }
+
     fn header_by_number_or_tag(&self, id: BlockNumberOrTag) -> ProviderResult<Option<Header>> {
         Ok(match id {
             BlockNumberOrTag::Latest => {
@@ -1428,6 +1837,15 @@ where
     }
 }
 
+impl<DB> StageCheckpointSubscriptions for BlockchainProvider2<DB>
+where
+    DB: Send + Sync,
+{
+    fn subscribe_to_stage_checkpoints(&self) -> StageCheckpointNotifications {
+        self.database.subscribe_to_stage_checkpoints()
+    }
+}
+
 impl<DB> ChangeSetReader for BlockchainProvider2<DB>
 where
     DB: Database,
@@ -1456,6 +1874,100 @@ where
     }
 }
 
+impl<DB> AccountHistoryReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    /// Returns the history of the persisted blocks of the range from the database, followed by
+    /// the history of the in-memory blocks, which is taken from the outcome of their execution.
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        let provider = self.consistent_provider()?;
+        let database = provider.database();
+        let last_database_block_number = database.last_block_number()?;
+
+        let mut history = database.account_history(
+            address,
+            *range.start()..=(*range.end()).min(last_database_block_number),
+        )?;
+
+        let first_in_memory_block_number =
+            (*range.start()).max(last_database_block_number.saturating_add(1));
+        for block in provider.in_memory_range(first_in_memory_block_number, *range.end())?.iter() {
+            // like the account changesets, only changes of the account info are recorded
+            if let Some(account) = block
+                .execution_output
+                .bundle
+                .account(&address)
+                .filter(|account| account.is_info_changed() || account.was_destroyed())
+            {
+                history.push((block.block().number, account.info.clone().map(Into::into)));
+            }
+        }
+
+        Ok(history)
+    }
+}
+
+impl<DB> StorageChangeSetReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    /// Returns the storage changeset of the block.
+    ///
+    /// The changesets of in-memory blocks are taken from the reverts of their execution, which
+    /// don't contain the storage of a wiped account that wasn't changed by the block itself.
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        if let Some(state) = self.canonical_in_memory_state.state_by_number(block_number) {
+            let mut changesets = state
+                .block()
+                .execution_output
+                .bundle
+                .reverts
+                .clone()
+                .into_plain_state_reverts()
+                .storage
+                .into_iter()
+                .flatten()
+                .flat_map(|revert| {
+                    revert.storage_revert.into_iter().map(move |(key, value)| {
+                        let key = B256::new(key.to_be_bytes());
+                        (revert.address, StorageEntry { key, value: value.to_previous_value() })
+                    })
+                })
+                .collect::<Vec<_>>();
+            // sort the changesets like the database does
+            changesets.sort_unstable_by_key(|(address, entry)| (*address, entry.key));
+            Ok(changesets)
+        } else {
+            self.database.provider()?.storage_block_changeset(block_number)
+        }
+    }
+}
+
+impl<DB> StateReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    /// Returns the [`ExecutionOutcome`] of the block.
+    ///
+    /// The outcome of an in-memory block is the outcome of its execution, the outcome of a
+    /// persisted block is reconstructed from the database.
+    fn execution_outcome(&self, block: BlockNumber) -> ProviderResult<Option<ExecutionOutcome>> {
+        if let Some(state) = self.canonical_in_memory_state.state_by_number(block) {
+            Ok(Some(state.block().execution_output.as_ref().clone()))
+        } else {
+            self.database.provider()?.execution_outcome(block)
+        }
+    }
+}
+
 impl<DB> AccountReader for BlockchainProvider2<DB>
 where
     DB: Database + Sync + Send,
@@ -1468,16 +1980,286 @@ where
     }
 }
 
+/// A lookup of the [`BlockchainProvider2`] that is served either from the in-memory canonical
+/// state or from the database, whose source and duration are recorded in metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lookup {
+    Header,
+    HeaderByNumber,
+    SealedHeader,
+    BlockHash,
+    BlockNumber,
+    FindBlockByHash,
+    BlockByNumber,
+    BlockWithSenders,
+    SealedBlockWithSenders,
+    TransactionsByBlock,
+    ReceiptsByBlock,
+    WithdrawalsByBlock,
+    LatestState,
+    HistoryByBlockHash,
+    BlockByTransactionHash,
+    BlockWithSendersByTxId,
+}
+
+impl Lookup {
+    /// All lookups, in the order of their discriminants.
+    const ALL: [Self; 16] = [
+        Self::Header,
+        Self::HeaderByNumber,
+        Self::SealedHeader,
+        Self::BlockHash,
+        Self::BlockNumber,
+        Self::FindBlockByHash,
+        Self::BlockByNumber,
+        Self::BlockWithSenders,
+        Self::SealedBlockWithSenders,
+        Self::TransactionsByBlock,
+        Self::ReceiptsByBlock,
+        Self::WithdrawalsByBlock,
+        Self::LatestState,
+        Self::HistoryByBlockHash,
+        Self::BlockByTransactionHash,
+        Self::BlockWithSendersByTxId,
+    ];
+
+    /// Returns the name of the provider method, which is used as label of the metrics.
+    const fn method(self) -> &'static str {
+        match self {
+            Self::Header => "header",
+            Self::HeaderByNumber => "header_by_number",
+            Self::SealedHeader => "sealed_header",
+            Self::BlockHash => "block_hash",
+            Self::BlockNumber => "block_number",
+            Self::FindBlockByHash => "find_block_by_hash",
+            Self::BlockByNumber => "block_by_number",
+            Self::BlockWithSenders => "block_with_senders",
+            Self::SealedBlockWithSenders => "sealed_block_with_senders",
+            Self::TransactionsByBlock => "transactions_by_block",
+            Self::ReceiptsByBlock => "receipts_by_block",
+            Self::WithdrawalsByBlock => "withdrawals_by_block",
+            Self::LatestState => "latest",
+            Self::HistoryByBlockHash => "history_by_block_hash",
+            Self::BlockByTransactionHash => "block_by_transaction_hash",
+            Self::BlockWithSendersByTxId => "block_with_senders_by_tx_id",
+        }
+    }
+}
+
+/// Metrics of the lookups of the [`BlockchainProvider2`], which are shared by all its clones.
+#[derive(Debug)]
+struct BlockchainProviderMetrics {
+    /// The metrics of every [`Lookup`], indexed by its discriminant.
+    lookups: Vec<LookupMetrics>,
+}
+
+impl Default for BlockchainProviderMetrics {
+    fn default() -> Self {
+        Self {
+            lookups: Lookup::ALL
+                .iter()
+                .map(|lookup| LookupMetrics::new_with_labels(&[("method", lookup.method())]))
+                .collect(),
+        }
+    }
+}
+
+impl BlockchainProviderMetrics {
+    /// Starts the lookup, which is recorded once the returned guard is dropped.
+    fn lookup(&self, lookup: Lookup) -> LookupGuard<'_> {
+        LookupGuard {
+            metrics: &self.lookups[lookup as usize],
+            start: Instant::now(),
+            in_memory: false,
+        }
+    }
+}
+
+/// Records a lookup as served by the database, unless it's marked as served from memory, when
+/// it's dropped.
+#[derive(Debug)]
+struct LookupGuard<'a> {
+    metrics: &'a LookupMetrics,
+    start: Instant,
+    in_memory: bool,
+}
+
+impl LookupGuard<'_> {
+    /// Marks the lookup as served from the in-memory canonical state.
+    fn in_memory(&mut self) {
+        self.in_memory = true;
+    }
+}
+
+impl Drop for LookupGuard<'_> {
+    fn drop(&mut self) {
+        if self.in_memory {
+            self.metrics.in_memory_lookups.increment(1);
+        } else {
+            self.metrics.database_lookups.increment(1);
+        }
+        self.metrics.lookup_duration.record(self.start.elapsed());
+    }
+}
+
+/// Metrics of a lookup method of the [`BlockchainProvider2`], labeled by the method.
+#[derive(Metrics)]
+#[metrics(scope = "storage.providers.blockchain")]
+struct LookupMetrics {
+    /// The number of lookups that were served from the in-memory canonical state.
+    in_memory_lookups: Counter,
+    /// The number of lookups that were served from the database, including lookups of blocks
+    /// that don't exist.
+    database_lookups: Counter,
+    /// The duration of a lookup.
+    lookup_duration: Histogram,
+}
+
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{
+        ops::{Range, RangeInclusive},
+        sync::Arc,
+    };
 
+    use assert_matches::assert_matches;
+    use rand::Rng;
     use reth_chain_state::{ExecutedBlock, NewCanonicalChain};
-    use reth_primitives::B256;
-    use reth_storage_api::{BlockHashReader, BlockNumReader, HeaderProvider};
-    use reth_testing_utils::generators::{self, random_block_range};
+    use reth_db::{tables, test_utils::TempDatabase, DatabaseEnv};
+    use reth_db_api::{database::Database, transaction::DbTxMut};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives::{
+        Address, BlockHashOrNumber, BlockNumHash, Bytes, GotExpected, Log, Receipt, SealedBlock,
+        TransactionSignedNoHash, B256, U256,
+    };
+    use reth_storage_api::{
+        BlockHashReader, BlockNumReader, BlockReader, BlockReaderIdExt, HeaderProvider, LogCursor,
+        LogFilter, LogsCollector, LogsPage, LogsProvider, ReceiptProvider, StateProviderFactory,
+        StateReader, TransactionsProvider,
+    };
+    use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
+    use reth_testing_utils::generators::{self, random_block, random_block_range};
+    use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
+
+    use super::Lookup;
+    use crate::{
+        providers::BlockchainProvider2,
+        test_utils::{create_test_provider_factory, create_test_provider_factory_in_memory},
+        EvmEnvProvider, ProviderFactory,
+    };
+
+    type TestProviderFactory<DB = DatabaseEnv> = ProviderFactory<Arc<TempDatabase<DB>>>;
+
+    type TestBlockchainProvider<DB = DatabaseEnv> = BlockchainProvider2<Arc<TempDatabase<DB>>>;
+
+    /// Returns an executed block with the recovered senders of the block and an empty outcome.
+    fn executed_block(block: SealedBlock) -> ExecutedBlock {
+        let senders = block.senders().expect("failed to recover senders");
+        ExecutedBlock::new(
+            Arc::new(block),
+            Arc::new(senders),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        )
+    }
+
+    /// Creates a provider with random blocks of the given range, of which the first
+    /// `database_blocks` are inserted into the database and the rest into the in-memory state.
+    ///
+    /// Returns the provider, its factory, and the database and the in-memory blocks.
+    #[allow(clippy::type_complexity)]
+    fn provider_with_random_blocks(
+        rng: &mut impl Rng,
+        block_range: RangeInclusive<u64>,
+        database_blocks: usize,
+        tx_count: Range<u8>,
+    ) -> eyre::Result<(
+        TestBlockchainProvider,
+        TestProviderFactory,
+        Vec<SealedBlock>,
+        Vec<SealedBlock>,
+    )> {
+        provider_with_random_blocks_and_outcomes(
+            rng,
+            block_range,
+            database_blocks,
+            tx_count,
+            |_| ExecutionOutcome::default(),
+        )
+    }
+
+    /// Same as [`provider_with_random_blocks`], with the execution outcomes of the in-memory
+    /// blocks returned by the given closure.
+    #[allow(clippy::type_complexity)]
+    fn provider_with_random_blocks_and_outcomes(
+        rng: &mut impl Rng,
+        block_range: RangeInclusive<u64>,
+        database_blocks: usize,
+        tx_count: Range<u8>,
+        outcome: impl FnMut(&SealedBlock) -> ExecutionOutcome,
+    ) -> eyre::Result<(
+        TestBlockchainProvider,
+        TestProviderFactory,
+        Vec<SealedBlock>,
+        Vec<SealedBlock>,
+    )> {
+        provider_with_random_blocks_in(
+            create_test_provider_factory(),
+            rng,
+            block_range,
+            database_blocks,
+            tx_count,
+            outcome,
+        )
+    }
+
+    /// Same as [`provider_with_random_blocks_and_outcomes`], with the database of the given
+    /// factory.
+    #[allow(clippy::type_complexity)]
+    fn provider_with_random_blocks_in<DB: Database>(
+        factory: TestProviderFactory<DB>,
+        rng: &mut impl Rng,
+        block_range: RangeInclusive<u64>,
+        database_blocks: usize,
+        tx_count: Range<u8>,
+        mut outcome: impl FnMut(&SealedBlock) -> ExecutionOutcome,
+    ) -> eyre::Result<(
+        TestBlockchainProvider<DB>,
+        TestProviderFactory<DB>,
+        Vec<SealedBlock>,
+        Vec<SealedBlock>,
+    )> {
+        let mut database_blocks_vec = random_block_range(rng, block_range, B256::ZERO, tx_count);
+        let in_memory_blocks = database_blocks_vec.split_off(database_blocks);
+        let database_blocks = database_blocks_vec;
+
+        let provider_rw = factory.provider_rw()?;
+        for block in &database_blocks {
+            provider_rw.insert_historical_block(
+                block.clone().seal_with_senders().expect("failed to seal block with senders"),
+            )?;
+        }
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider2::new(factory.clone())?;
+        provider.canonical_in_memory_state.update_chain(NewCanonicalChain::Commit {
+            new: in_memory_blocks
+                .iter()
+                .map(|block| {
+                    let mut executed = executed_block(block.clone());
+                    executed.execution_output = Arc::new(outcome(block));
+                    executed
+                })
+                .collect(),
+        });
+        if let Some(head) = in_memory_blocks.last() {
+            provider.canonical_in_memory_state.set_canonical_head(head.header.clone());
+        }
 
-    use crate::{providers::BlockchainProvider2, test_utils::create_test_provider_factory};
+        Ok((provider, factory, database_blocks, in_memory_blocks))
+    }
 
     #[test]
     fn test_block_hash_reader() -> eyre::Result<()> {
@@ -1532,6 +2314,73 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_canonical_hashes_range() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, factory, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 0..1)?;
+        let blocks = [database_blocks, in_memory_blocks].concat();
+        let hashes = blocks.iter().map(|block| block.hash()).collect::<Vec<_>>();
+
+        // the range includes its end, crosses the database and the in-memory blocks, and ends at
+        // the tip
+        assert_eq!(provider.canonical_hashes_range(3, 20)?, hashes[3..]);
+        assert_eq!(provider.canonical_hashes_range(2, 7)?, hashes[2..=7]);
+        assert_eq!(provider.canonical_hashes_range(5, 5)?, hashes[5..=5]);
+        assert_eq!(provider.canonical_hashes_range(6, 5)?, Vec::<B256>::new());
+
+        // a persisted block that is missing from the database leaves a gap
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.tx_ref().delete::<tables::CanonicalHeaders>(4, None)?;
+        provider_rw.commit()?;
+        assert!(matches!(
+            provider.canonical_hashes_range(0, 10),
+            Err(ProviderError::HeaderNotFound(BlockHashOrNumber::Number(4)))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonical_hashes_range_after_reorg() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, database_blocks, _) =
+            provider_with_random_blocks(&mut rng, 0..=7, 8, 0..1)?;
+
+        // the chain is reorged in memory below the last blocks of the database, which are not
+        // unwound yet
+        let fork = random_block_range(&mut rng, 5..=9, database_blocks[4].hash(), 0..1);
+        provider.canonical_in_memory_state.update_chain(NewCanonicalChain::Commit {
+            new: fork.iter().cloned().map(executed_block).collect(),
+        });
+        provider.canonical_in_memory_state.set_canonical_head(fork.last().unwrap().header.clone());
+
+        let expected =
+            database_blocks[..5].iter().chain(&fork).map(|block| block.hash()).collect::<Vec<_>>();
+        assert_eq!(provider.canonical_hashes_range(0, 9)?, expected);
+        assert_eq!(provider.canonical_hashes_range(6, 7)?, expected[6..=7]);
+        assert_eq!(provider.block_hash(6)?, Some(fork[1].hash()));
+
+        // the forward range agrees with the descending range
+        let mut headers_rev =
+            provider.headers_range_rev(0..=9).collect::<ProviderResult<Vec<_>>>()?;
+        headers_rev.reverse();
+        assert_eq!(
+            headers_rev.iter().map(|header| header.hash_slow()).collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(
+            provider
+                .sealed_headers_range(0..=9)?
+                .iter()
+                .map(|header| header.hash())
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_header_provider() -> eyre::Result<()> {
         let mut rng = generators::rng();
@@ -1684,4 +2533,542 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_tx_range_provider() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 1..3)?;
+        let blocks = [database_blocks, in_memory_blocks].concat();
+
+        let transactions = blocks.iter().flat_map(|b| b.body.clone()).collect::<Vec<_>>();
+        let senders = blocks
+            .iter()
+            .flat_map(|b| b.senders().expect("failed to recover senders"))
+            .collect::<Vec<_>>();
+
+        // a range that spans the database and the in-memory blocks
+        let range = 3..transactions.len() as u64 - 2;
+        assert_eq!(
+            provider.transactions_by_tx_range(range.clone())?,
+            transactions[3..transactions.len() - 2]
+                .iter()
+                .cloned()
+                .map(TransactionSignedNoHash::from)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(provider.senders_by_tx_range(range)?, senders[3..senders.len() - 2].to_vec());
+
+        // a range of in-memory blocks only
+        assert_eq!(
+            provider.senders_by_tx_range(senders.len() as u64 - 1..)?,
+            vec![*senders.last().unwrap()]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_transaction_sender() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, factory, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 1..3)?;
+        let blocks = [database_blocks, in_memory_blocks].concat();
+
+        // Prune the senders of the database blocks
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.tx_ref().clear::<tables::TransactionSenders>()?;
+        provider_rw.commit()?;
+
+        let senders = blocks
+            .iter()
+            .flat_map(|b| b.senders().expect("failed to recover senders"))
+            .collect::<Vec<_>>();
+        for (id, sender) in senders.iter().enumerate() {
+            assert_eq!(provider.transaction_sender(id as u64)?, Some(*sender));
+        }
+        assert_eq!(provider.transaction_sender(senders.len() as u64)?, None);
+
+        // the senders of the persisted transactions were recovered and cached
+        let persisted = blocks[..5].iter().map(|b| b.body.len()).sum::<usize>();
+        assert_eq!(provider.recovered_senders.lock().len(), persisted);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_transactions_by_hashes() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 1..3)?;
+        let blocks = [database_blocks, in_memory_blocks].concat();
+
+        // hashes from the database and the in-memory blocks, in reverse order, with a duplicate
+        // and an unknown hash
+        let mut hashes =
+            blocks.iter().flat_map(|b| b.body.iter().map(|tx| tx.hash())).collect::<Vec<_>>();
+        hashes.reverse();
+        hashes.push(hashes[0]);
+        hashes.push(B256::random());
+
+        let expected = hashes
+            .iter()
+            .map(|hash| provider.transaction_by_hash_with_meta(*hash))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert!(expected[..expected.len() - 1].iter().all(Option::is_some));
+        assert_eq!(provider.transactions_by_hashes(&hashes)?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_by_transaction_hash() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 1..3)?;
+        let blocks = [database_blocks, in_memory_blocks].concat();
+
+        // blocks from the database and from the in-memory state
+        for block in [&blocks[2], &blocks[8]] {
+            let expected = block.clone().seal_with_senders().expect("failed to seal block");
+            assert_eq!(provider.block_by_transaction_hash(block.body[0].hash())?, Some(expected));
+        }
+        assert_eq!(provider.block_by_transaction_hash(B256::random())?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_database_transactions_by_id() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let factory = create_test_provider_factory();
+
+        // Generate blocks with at least two transactions, so that most transactions are not the
+        // last of their block
+        let blocks = random_block_range(&mut rng, 0..=5, B256::ZERO, 2..4);
+
+        let provider_rw = factory.provider_rw()?;
+        for block in &blocks {
+            provider_rw.insert_historical_block(
+                block.clone().seal_with_senders().expect("failed to seal block with senders"),
+            )?;
+        }
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider2::new(factory)?;
+
+        // every transaction is served from the database, at any index of its block
+        let transactions = blocks.iter().flat_map(|block| block.body.clone()).collect::<Vec<_>>();
+        let senders = blocks
+            .iter()
+            .flat_map(|b| b.senders().expect("failed to recover senders"))
+            .collect::<Vec<_>>();
+        let block_numbers = blocks
+            .iter()
+            .flat_map(|block| std::iter::repeat(block.number).take(block.body.len()))
+            .collect::<Vec<_>>();
+        for (id, ((transaction, sender), block_number)) in
+            transactions.iter().zip(&senders).zip(&block_numbers).enumerate()
+        {
+            let id = id as u64;
+            assert_eq!(provider.transaction_by_id(id)?.as_ref(), Some(transaction));
+            assert_eq!(provider.transaction_by_id_no_hash(id)?, Some(transaction.clone().into()));
+            assert_eq!(provider.transaction_sender(id)?, Some(*sender));
+            assert_eq!(provider.transaction_block(id)?, Some(*block_number));
+        }
+        assert_eq!(provider.transaction_by_id(transactions.len() as u64)?, None);
+        assert_eq!(provider.transaction_block(transactions.len() as u64)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_memory_database() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, database_blocks, in_memory_blocks) = provider_with_random_blocks_in(
+            create_test_provider_factory_in_memory(),
+            &mut rng,
+            0..=10,
+            5,
+            1..3,
+            |_| ExecutionOutcome::default(),
+        )?;
+        let blocks = [database_blocks, in_memory_blocks].concat();
+
+        // blocks from the in-memory database and from the in-memory state
+        for block in [&blocks[2], &blocks[8]] {
+            assert_eq!(provider.block_hash(block.number)?, Some(block.hash()));
+            assert_eq!(provider.block_number(block.hash())?, Some(block.number));
+            assert_eq!(provider.sealed_header(block.number)?, Some(block.header.clone()));
+
+            let expected = block.clone().seal_with_senders().expect("failed to seal block");
+            assert_eq!(provider.block_by_transaction_hash(block.body[0].hash())?, Some(expected));
+        }
+
+        assert_eq!(
+            provider.canonical_hashes_range(0, 10)?,
+            blocks.iter().map(|block| block.hash()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            provider.sealed_headers_range(0..=10)?,
+            blocks.iter().map(|block| block.header.clone()).collect::<Vec<_>>()
+        );
+
+        let transactions = blocks.iter().flat_map(|block| block.body.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            provider.transactions_by_tx_range(0..transactions.len() as u64)?,
+            transactions.into_iter().map(Into::into).collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_with_senders_by_tx_id() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 1..3)?;
+        let blocks = [database_blocks, in_memory_blocks].concat();
+
+        // every transaction of the database and the in-memory blocks resolves to its block
+        let mut id = 0;
+        for block in &blocks {
+            let expected = block.clone().unseal().with_recovered_senders();
+            for _ in &block.body {
+                assert_eq!(provider.block_with_senders_by_tx_id(id)?, expected);
+                id += 1;
+            }
+        }
+        assert_eq!(provider.block_with_senders_by_tx_id(id)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pending_state_by_hash() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, _, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=7, 5, 0..1)?;
+
+        // a fork block on top of the first in-memory block
+        let fork = random_block(&mut rng, 6, Some(in_memory_blocks[0].hash()), Some(0), None);
+        assert!(provider.pending_state_by_hash(fork.hash())?.is_none());
+
+        provider.canonical_in_memory_state.insert_sidechain_block(executed_block(fork.clone()));
+        assert!(provider.pending_state_by_hash(fork.hash())?.is_some());
+        assert!(provider.state_by_block_hash(fork.hash()).is_ok());
+
+        // canonical blocks are not pending
+        assert!(provider.pending_state_by_hash(in_memory_blocks[1].hash())?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_call_at_fork_block() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, _, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=7, 5, 0..1)?;
+
+        // a fork of the first in-memory block that is longer than the canonical chain
+        let mut parent = in_memory_blocks[0].hash();
+        let fork = (6..=8)
+            .map(|number| {
+                let block = random_block(&mut rng, number, Some(parent), Some(0), None);
+                parent = block.hash();
+                provider
+                    .canonical_in_memory_state
+                    .insert_sidechain_block(executed_block(block.clone()));
+                block
+            })
+            .collect::<Vec<_>>();
+
+        // an `eth_call` by block hash reads the env and the state of the block
+        for block in &fork {
+            assert_eq!(provider.header(&block.hash())?, Some(block.header.header().clone()));
+
+            let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+            let mut block_env = BlockEnv::default();
+            provider.fill_env_at(
+                &mut cfg,
+                &mut block_env,
+                block.hash().into(),
+                EthEvmConfig::default(),
+            )?;
+            assert_eq!(block_env.number, U256::from(block.number));
+            assert_eq!(block_env.coinbase, block.beneficiary);
+
+            assert!(provider.state_by_block_hash(block.hash()).is_ok());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_iters() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, factory, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 1..3)?;
+        let database_transactions =
+            database_blocks.iter().map(|block| block.body.len() as u64).sum::<u64>();
+        let blocks = [database_blocks, in_memory_blocks].concat();
+
+        // the iterators over unbounded ranges end at the last in-memory block
+        assert_eq!(
+            provider.headers_iter(2..)?.collect::<ProviderResult<Vec<_>>>()?,
+            blocks[2..].iter().map(|block| block.header.header().clone()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            provider.receipts_by_block_iter(..)?.collect::<ProviderResult<Vec<_>>>()?,
+            (0..=10u64)
+                .map(|number| provider.receipts_by_block(number.into()).map(Option::unwrap))
+                .collect::<ProviderResult<Vec<_>>>()?
+        );
+        assert_eq!(
+            provider.transactions_by_tx_range_iter(1..)?.collect::<ProviderResult<Vec<_>>>()?,
+            provider.transactions_by_tx_range(1..)?
+        );
+
+        // the database provider only has the persisted blocks
+        let database = factory.provider()?;
+        assert_eq!(
+            database.headers_iter(..=7).collect::<ProviderResult<Vec<_>>>()?,
+            database.headers_range(0..5)?
+        );
+        assert_eq!(database.receipts_by_block_iter(3..).count(), 2);
+        assert_eq!(
+            database.transactions_by_tx_range_iter(..).collect::<ProviderResult<Vec<_>>>()?,
+            database.transactions_by_tx_range(0..database_transactions)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_iters_reorg() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 1..3)?;
+
+        let mut headers = provider
+            .chunked_range_iter(0..11, |view, chunk| {
+                let headers = view.headers_range(chunk.start..=chunk.end - 1)?;
+                let end_block = headers.last().map(|header| header.number);
+                Ok((headers, end_block))
+            })
+            .with_chunk_size(6);
+        for block in database_blocks.iter().chain(&in_memory_blocks[..1]) {
+            assert_eq!(headers.next().transpose()?.as_ref(), Some(block.header.header()));
+        }
+
+        // the last block of the first chunk is reorged before the second chunk is read
+        let fork = random_block_range(&mut rng, 5..=7, database_blocks[4].hash(), 1..3);
+        provider.canonical_in_memory_state.update_chain(NewCanonicalChain::Reorg {
+            new: fork.iter().cloned().map(executed_block).collect(),
+            old: in_memory_blocks.iter().cloned().map(executed_block).collect(),
+        });
+
+        assert!(matches!(
+            headers.next(),
+            Some(Err(ProviderError::ConsistentView(err)))
+                if *err == ConsistentViewError::Reorged {
+                    number: 5,
+                    hash: GotExpected::new(Some(fork[0].hash()), Some(in_memory_blocks[0].hash())),
+                }
+        ));
+        assert!(headers.next().is_none());
+
+        // a new iterator reads the new chain
+        assert_eq!(
+            provider.headers_iter(..)?.collect::<ProviderResult<Vec<_>>>()?,
+            database_blocks
+                .iter()
+                .chain(&fork)
+                .map(|block| block.header.header().clone())
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reverse_ranges() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 1..3)?;
+        let blocks = [database_blocks, in_memory_blocks].concat();
+
+        // ranges start at the last in-memory block and cross into the database
+        assert_eq!(
+            provider.headers_range_rev(2..=u64::MAX).collect::<ProviderResult<Vec<_>>>()?,
+            blocks[2..].iter().rev().map(|block| block.header.header().clone()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            provider.blocks_range_rev(3..=7).collect::<ProviderResult<Vec<_>>>()?,
+            blocks[3..=7].iter().rev().map(|block| block.clone().unseal()).collect::<Vec<_>>()
+        );
+
+        // ranges that are only in memory or only in the database
+        assert_eq!(provider.headers_range_rev(6..=8).collect::<ProviderResult<Vec<_>>>()?, {
+            let mut headers = provider.headers_range(6..=8)?;
+            headers.reverse();
+            headers
+        });
+        assert_eq!(provider.blocks_range_rev(0..=3).collect::<ProviderResult<Vec<_>>>()?, {
+            let mut blocks = provider.block_range(0..=3)?;
+            blocks.reverse();
+            blocks
+        });
+
+        // ranges beyond the tip only return the available blocks
+        assert_eq!(provider.blocks_range_rev(9..=20).count(), 2);
+        assert_eq!(provider.headers_range_rev(11..=u64::MAX).count(), 0);
+
+        // the blocks are read lazily, a walk back from the tip can stop early
+        assert_eq!(
+            provider.headers_range_rev(0..=u64::MAX).take(3).collect::<ProviderResult<Vec<_>>>()?,
+            blocks[8..].iter().rev().map(|block| block.header.header().clone()).collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_persisted() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, _, database_blocks, in_memory_blocks) =
+            provider_with_random_blocks(&mut rng, 0..=10, 5, 0..1)?;
+        let block4 = database_blocks[4].num_hash();
+        let block7 = in_memory_blocks[2].num_hash();
+
+        // blocks in the database are already persisted
+        provider.wait_for_persisted(block4).await?;
+
+        // a block that isn't part of the persisted chain is reported as not found
+        let reorged = BlockNumHash::new(block4.number, B256::random());
+        assert_matches!(
+            provider.wait_for_persisted(reorged).await,
+            Err(ProviderError::BlockHashNotFound(hash)) if hash == reorged.hash
+        );
+
+        // blocks in memory are persisted once they are removed from memory
+        let waiter = tokio::spawn({
+            let provider = provider.clone();
+            async move { provider.wait_for_persisted(block7).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        // the blocks are written to the database before they are removed from memory
+        let provider_rw = provider.database.provider_rw()?;
+        for block in &in_memory_blocks[..=2] {
+            provider_rw.insert_historical_block(
+                block.clone().seal_with_senders().expect("failed to seal block with senders"),
+            )?;
+        }
+        provider_rw.commit()?;
+        provider.canonical_in_memory_state.remove_persisted_blocks(block7);
+        waiter.await??;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lookups_indexed_by_discriminant() {
+        for (idx, lookup) in Lookup::ALL.into_iter().enumerate() {
+            assert_eq!(lookup as usize, idx, "{}", lookup.method());
+        }
+    }
+
+    #[test]
+    fn test_execution_outcome_reader() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        // the in-memory blocks have their own outcomes
+        let outcome = |number| {
+            ExecutionOutcome::new(
+                Default::default(),
+                vec![vec![Some(Receipt { cumulative_gas_used: number, ..Default::default() })]]
+                    .into(),
+                number,
+                Vec::new(),
+            )
+        };
+        let (provider, _, _, _) =
+            provider_with_random_blocks_and_outcomes(&mut rng, 0..=3, 2, 0..1, |block| {
+                outcome(block.number)
+            })?;
+
+        // the outcome of a persisted block is reconstructed from the database
+        let persisted = provider.execution_outcome(1)?.expect("block is persisted");
+        assert_eq!(persisted.first_block(), 1);
+        assert!(persisted.bundle.state.is_empty());
+
+        // the outcome of an in-memory block is taken from its execution
+        assert_eq!(provider.execution_outcome(3)?, Some(outcome(3)));
+
+        assert_eq!(provider.execution_outcome(4)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_logs_pagination() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        // the database blocks have no receipts, and the in-memory blocks have one transaction
+        // with two logs each
+        let log =
+            |address| Log::new_unchecked(Address::with_last_byte(address), vec![], Bytes::new());
+        let (provider, _, _, in_memory_blocks) =
+            provider_with_random_blocks_and_outcomes(&mut rng, 0..=5, 2, 1..2, |block| {
+                let receipt = Receipt { logs: vec![log(0), log(1)], ..Default::default() };
+                ExecutionOutcome::new(
+                    Default::default(),
+                    vec![vec![Some(receipt)]].into(),
+                    block.number,
+                    Vec::new(),
+                )
+            })?;
+
+        // collects a page of at most 3 logs, stopping at the first block that doesn't fit
+        let filter = LogFilter::default();
+        let page = |cursor| -> ProviderResult<LogsPage> {
+            let mut collector = LogsCollector::new(&filter, cursor, 3);
+            for block in provider.log_blocks(0..=5, &filter, cursor)? {
+                let block = block?;
+                let Some(receipts) = provider.receipts_by_block(block.hash.into())? else {
+                    continue
+                };
+                if collector.append_block_logs(&provider, block, &receipts)? {
+                    break
+                }
+            }
+            Ok(collector.into_page())
+        };
+        let positions = |page: &LogsPage| {
+            page.logs.iter().map(|log| (log.block_number, log.log_index)).collect::<Vec<_>>()
+        };
+
+        let first = page(None)?;
+        assert_eq!(positions(&first), vec![(2, 0), (2, 1), (3, 0)]);
+        assert_eq!(first.next, Some(LogCursor { block_number: 3, log_index: 1 }));
+
+        let second = page(first.next)?;
+        assert_eq!(positions(&second), vec![(3, 1), (4, 0), (4, 1)]);
+        assert_eq!(second.next, Some(LogCursor { block_number: 5, log_index: 0 }));
+
+        let last = page(second.next)?;
+        assert_eq!(positions(&last), vec![(5, 0), (5, 1)]);
+        assert_eq!(last.next, None);
+        assert_eq!(last.logs[0].transaction_hash, in_memory_blocks[3].body[0].hash());
+
+        // the blocks before the block of the cursor are skipped
+        let mut log_blocks = provider.log_blocks(0..=5, &filter, second.next)?;
+        assert_eq!(log_blocks.next().transpose()?.map(|block| block.number), Some(5));
+        assert!(log_blocks.next().is_none());
+
+        Ok(())
+    }
 }
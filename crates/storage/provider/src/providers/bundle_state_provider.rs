@@ -2,11 +2,11 @@ use crate::{
     AccountReader, BlockHashReader, ExecutionDataProvider, StateProvider, StateRootProvider,
 };
 use reth_primitives::{Account, Address, BlockNumber, Bytecode, Bytes, B256};
-use reth_storage_api::StateProofProvider;
+use reth_storage_api::{StateProofProvider, StorageRangeProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
-    HashedStorage,
+    HashedStorage, StorageRange,
 };
 use revm::db::BundleState;
 use std::collections::HashMap;
@@ -170,6 +170,23 @@ impl<SP: StateProvider, EDP: ExecutionDataProvider> StateProofProvider
     }
 }
 
+impl<SP: StateProvider, EDP: ExecutionDataProvider> StorageRangeProvider
+    for BundleStateProvider<SP, EDP>
+{
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let bundle_state = self.block_execution_data_provider.execution_outcome().state();
+        let mut state = HashedPostState::from_bundle_state(&bundle_state.state);
+        state.extend(hashed_state);
+        self.state_provider.hashed_storage_range(state, address, start, limit)
+    }
+}
+
 impl<SP: StateProvider, EDP: ExecutionDataProvider> StateProvider for BundleStateProvider<SP, EDP> {
     fn storage(
         &self,
@@ -188,6 +205,49 @@ impl<SP: StateProvider, EDP: ExecutionDataProvider> StateProvider for BundleStat
         self.state_provider.storage(account, storage_key)
     }
 
+    fn storage_multi(
+        &self,
+        slots: &[(Address, reth_primitives::StorageKey)],
+    ) -> ProviderResult<Vec<Option<reth_primitives::StorageValue>>> {
+        let execution_outcome = self.block_execution_data_provider.execution_outcome();
+        let values = slots
+            .iter()
+            .map(|(account, storage_key)| execution_outcome.storage(account, (*storage_key).into()))
+            .collect::<Vec<_>>();
+
+        // read the slots that are not in the execution outcome in a single batch
+        let missing = slots
+            .iter()
+            .zip(&values)
+            .filter(|(_, value)| value.is_none())
+            .map(|(slot, _)| *slot)
+            .collect::<Vec<_>>();
+        let mut fetched = self.state_provider.storage_multi(&missing)?.into_iter();
+        Ok(values
+            .into_iter()
+            .map(|value| value.map(Some).unwrap_or_else(|| fetched.next().flatten()))
+            .collect())
+    }
+
+    fn basic_accounts(&self, addresses: &[Address]) -> ProviderResult<Vec<Option<Account>>> {
+        let execution_outcome = self.block_execution_data_provider.execution_outcome();
+        let accounts =
+            addresses.iter().map(|address| execution_outcome.account(address)).collect::<Vec<_>>();
+
+        // read the accounts that are not in the execution outcome in a single batch
+        let missing = addresses
+            .iter()
+            .zip(&accounts)
+            .filter(|(_, account)| account.is_none())
+            .map(|(address, _)| *address)
+            .collect::<Vec<_>>();
+        let mut fetched = self.state_provider.basic_accounts(&missing)?.into_iter();
+        Ok(accounts
+            .into_iter()
+            .map(|account| account.unwrap_or_else(|| fetched.next().flatten()))
+            .collect())
+    }
+
     fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
         if let Some(bytecode) =
             self.block_execution_data_provider.execution_outcome().bytecode(&code_hash)
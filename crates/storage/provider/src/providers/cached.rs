@@ -0,0 +1,773 @@
+use crate::{
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    CanonStateNotification, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    EvmEnvProvider, HeaderProvider, ProviderError, ReceiptProvider, ReceiptProviderIdExt,
+    RequestsProvider, StateProviderBox, StateProviderFactory, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
+};
+use parking_lot::Mutex;
+use reth_chainspec::{ChainInfo, ChainSpec};
+use reth_db_api::models::StoredBlockBodyIndices;
+use reth_evm::ConfigureEvmEnv;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{
+    Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
+    BlockNumberOrTag, BlockWithSenders, Header, Receipt, Requests, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+};
+use reth_storage_errors::provider::ProviderResult;
+use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
+use schnellru::{ByLength, LruMap};
+use std::{
+    future::Future,
+    hash::Hash,
+    ops::{RangeBounds, RangeInclusive},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::debug;
+
+/// The default number of headers kept in the cache of the [`CachedBlockchainProvider`].
+pub const DEFAULT_MAX_CACHED_HEADERS: u32 = 10_000;
+
+/// The default number of blocks kept in the cache of the [`CachedBlockchainProvider`].
+pub const DEFAULT_MAX_CACHED_BLOCKS: u32 = 500;
+
+/// The default number of block receipts kept in the cache of the [`CachedBlockchainProvider`].
+pub const DEFAULT_MAX_CACHED_RECEIPTS: u32 = 500;
+
+/// The capacities of the caches of the [`CachedBlockchainProvider`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderCacheConfig {
+    /// The maximum number of cached headers, and canonical hashes by block number.
+    pub max_headers: u32,
+    /// The maximum number of cached blocks.
+    pub max_blocks: u32,
+    /// The maximum number of cached block receipts.
+    pub max_receipts: u32,
+}
+
+impl Default for ProviderCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_headers: DEFAULT_MAX_CACHED_HEADERS,
+            max_blocks: DEFAULT_MAX_CACHED_BLOCKS,
+            max_receipts: DEFAULT_MAX_CACHED_RECEIPTS,
+        }
+    }
+}
+
+/// A provider that caches the headers, blocks and receipts of the provider it wraps.
+///
+/// Recent blocks are requested over and over again by RPC consumers, and every request otherwise
+/// pays for the database lookups and the decompression of static files. The entries are cached by
+/// block hash, and lookups by block number are resolved to a hash with a cache of the canonical
+/// hashes.
+///
+/// Canonical hashes and the entries of blocks that are reorged out become stale, so the caches
+/// must be kept up to date with the canonical chain, by spawning the task of
+/// [`CachedBlockchainProvider::cache_invalidation_task`] with a subscription to the canonical
+/// state of the wrapped provider, or by passing every
+/// [`CanonStateNotification`] to [`CachedBlockchainProvider::on_canon_state_notification`].
+/// Only canonical blocks are cached, the pending block is always read from the inner provider.
+#[derive(Debug, Clone)]
+pub struct CachedBlockchainProvider<P> {
+    provider: P,
+    caches: Arc<ProviderCaches>,
+}
+
+impl<P> CachedBlockchainProvider<P> {
+    /// Creates a new caching provider around the given provider.
+    pub fn new(provider: P, config: ProviderCacheConfig) -> Self {
+        Self { provider, caches: Arc::new(ProviderCaches::new(config)) }
+    }
+
+    /// Returns the wrapped provider.
+    pub const fn inner(&self) -> &P {
+        &self.provider
+    }
+
+    /// Updates the caches with a change of the canonical chain.
+    ///
+    /// The entries of reverted blocks are removed, and the new canonical blocks are cached.
+    pub fn on_canon_state_notification(&self, notification: &CanonStateNotification) {
+        self.caches.on_canon_state_notification(notification)
+    }
+
+    /// Returns a task that keeps the caches up to date with the given canonical state
+    /// notifications, until the notification channel is closed.
+    pub fn cache_invalidation_task(
+        &self,
+        mut notifications: CanonStateNotifications,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        let caches = self.caches.clone();
+        async move {
+            loop {
+                match notifications.recv().await {
+                    Ok(notification) => caches.on_canon_state_notification(&notification),
+                    Err(RecvError::Lagged(skipped)) => {
+                        // the reverted blocks of the skipped notifications are unknown
+                        debug!(target: "providers::cached", skipped, "Missed canonical state notifications, clearing caches");
+                        caches.clear();
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+}
+
+impl<P: BlockHashReader> CachedBlockchainProvider<P> {
+    /// Resolves the block hash or number to a block hash, with the cached canonical hashes.
+    fn resolve_hash(&self, id: BlockHashOrNumber) -> ProviderResult<Option<BlockHash>> {
+        match id {
+            BlockHashOrNumber::Hash(hash) => Ok(Some(hash)),
+            BlockHashOrNumber::Number(number) => self.block_hash(number),
+        }
+    }
+}
+
+/// The caches of the [`CachedBlockchainProvider`].
+#[derive(Debug)]
+struct ProviderCaches {
+    canonical_hashes: Cache<BlockNumber, BlockHash>,
+    headers: Cache<BlockHash, Header>,
+    blocks: Cache<BlockHash, Block>,
+    receipts: Cache<BlockHash, Vec<Receipt>>,
+}
+
+impl ProviderCaches {
+    fn new(config: ProviderCacheConfig) -> Self {
+        Self {
+            canonical_hashes: Cache::new("canonical_hashes", config.max_headers),
+            headers: Cache::new("headers", config.max_headers),
+            blocks: Cache::new("blocks", config.max_blocks),
+            receipts: Cache::new("receipts", config.max_receipts),
+        }
+    }
+
+    fn on_canon_state_notification(&self, notification: &CanonStateNotification) {
+        if let Some(reverted) = notification.reverted() {
+            for (number, block) in reverted.blocks() {
+                self.canonical_hashes.remove(number);
+                self.headers.remove(&block.hash());
+                self.blocks.remove(&block.hash());
+                self.receipts.remove(&block.hash());
+            }
+        }
+
+        let committed = notification.committed();
+        for (number, block) in committed.blocks() {
+            let hash = block.hash();
+            self.canonical_hashes.insert(*number, hash);
+            self.headers.insert(hash, block.header.header().clone());
+            self.blocks.insert(hash, block.block.clone().unseal());
+            let receipts = committed.execution_outcome().receipts_by_block(*number);
+            self.receipts.insert(hash, receipts.iter().flatten().cloned().collect());
+        }
+    }
+
+    fn clear(&self) {
+        self.canonical_hashes.clear();
+        self.headers.clear();
+        self.blocks.clear();
+        self.receipts.clear();
+    }
+}
+
+/// A least recently used cache with metrics.
+///
+/// Every invalidation bumps the epoch of the cache, so that a fetch that raced an invalidation
+/// doesn't fill the cache with the data it read before the invalidation.
+#[derive(Debug)]
+struct Cache<K: Hash + PartialEq, V> {
+    entries: Mutex<LruMap<K, V, ByLength>>,
+    /// The number of invalidations, only modified while the entries are locked.
+    epoch: AtomicU64,
+    metrics: CacheMetrics,
+}
+
+impl<K: Hash + PartialEq, V: Clone> Cache<K, V> {
+    fn new(name: &'static str, max_len: u32) -> Self {
+        Self {
+            entries: Mutex::new(LruMap::new(ByLength::new(max_len))),
+            epoch: AtomicU64::new(0),
+            metrics: CacheMetrics::new_with_labels(&[("cache", name)]),
+        }
+    }
+
+    /// Returns the cached value, or fetches it and caches it if it exists.
+    ///
+    /// The fetched value isn't cached if the cache was invalidated during the fetch.
+    fn get_or_fetch(
+        &self,
+        key: K,
+        fetch: impl FnOnce(&K) -> ProviderResult<Option<V>>,
+    ) -> ProviderResult<Option<V>> {
+        let epoch = {
+            let mut entries = self.entries.lock();
+            if let Some(value) = entries.get(&key) {
+                self.metrics.hits.increment(1);
+                return Ok(Some(value.clone()))
+            }
+            self.epoch.load(Ordering::Relaxed)
+        };
+
+        self.metrics.misses.increment(1);
+        let value = fetch(&key)?;
+        if let Some(value) = &value {
+            let mut entries = self.entries.lock();
+            if self.epoch.load(Ordering::Relaxed) == epoch {
+                entries.insert(key, value.clone());
+            }
+        }
+        Ok(value)
+    }
+
+    fn insert(&self, key: K, value: V) {
+        self.entries.lock().insert(key, value);
+    }
+
+    fn remove(&self, key: &K) {
+        let mut entries = self.entries.lock();
+        entries.remove(key);
+        self.epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn clear(&self) {
+        let mut entries = self.entries.lock();
+        entries.clear();
+        self.epoch.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Metrics for a cache of the [`CachedBlockchainProvider`].
+#[derive(Metrics)]
+#[metrics(scope = "providers.cached")]
+struct CacheMetrics {
+    /// The number of lookups that were served from the cache.
+    hits: Counter,
+    /// The number of lookups that were forwarded to the wrapped provider.
+    misses: Counter,
+}
+
+impl<P: BlockHashReader> BlockHashReader for CachedBlockchainProvider<P> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.caches
+            .canonical_hashes
+            .get_or_fetch(number, |number| self.provider.block_hash(*number))
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.provider.canonical_hashes_range(start, end)
+    }
+}
+
+impl<P: BlockNumReader> BlockNumReader for CachedBlockchainProvider<P> {
+    fn chain_info(&self) -> ProviderResult<ChainInfo> {
+        self.provider.chain_info()
+    }
+
+    fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.provider.best_block_number()
+    }
+
+    fn last_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.provider.last_block_number()
+    }
+
+    fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
+        self.provider.block_number(hash)
+    }
+}
+
+impl<P: BlockIdReader> BlockIdReader for CachedBlockchainProvider<P> {
+    fn pending_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.provider.pending_block_num_hash()
+    }
+
+    fn safe_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.provider.safe_block_num_hash()
+    }
+
+    fn finalized_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.provider.finalized_block_num_hash()
+    }
+}
+
+impl<P: HeaderProvider + BlockHashReader> HeaderProvider for CachedBlockchainProvider<P> {
+    fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
+        self.caches.headers.get_or_fetch(*block_hash, |hash| self.provider.header(hash))
+    }
+
+    fn header_by_number(&self, num: u64) -> ProviderResult<Option<Header>> {
+        match self.block_hash(num)? {
+            Some(hash) => self.header(&hash),
+            None => Ok(None),
+        }
+    }
+
+    fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
+        self.provider.header_td(hash)
+    }
+
+    fn header_td_by_number(&self, number: BlockNumber) -> ProviderResult<Option<U256>> {
+        self.provider.header_td_by_number(number)
+    }
+
+    fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> ProviderResult<Vec<Header>> {
+        self.provider.headers_range(range)
+    }
+
+    fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>> {
+        let Some(hash) = self.block_hash(number)? else { return Ok(None) };
+        Ok(self.header(&hash)?.map(|header| SealedHeader::new(header, hash)))
+    }
+
+    fn sealed_headers_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        self.provider.sealed_headers_range(range)
+    }
+
+    fn sealed_headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        predicate: impl FnMut(&SealedHeader) -> bool,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        self.provider.sealed_headers_while(range, predicate)
+    }
+}
+
+impl<P: TransactionsProvider> TransactionsProvider for CachedBlockchainProvider<P> {
+    fn transaction_id(&self, tx_hash: TxHash) -> ProviderResult<Option<TxNumber>> {
+        self.provider.transaction_id(tx_hash)
+    }
+
+    fn transaction_by_id(&self, id: TxNumber) -> ProviderResult<Option<TransactionSigned>> {
+        self.provider.transaction_by_id(id)
+    }
+
+    fn transaction_by_id_no_hash(
+        &self,
+        id: TxNumber,
+    ) -> ProviderResult<Option<TransactionSignedNoHash>> {
+        self.provider.transaction_by_id_no_hash(id)
+    }
+
+    fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<TransactionSigned>> {
+        self.provider.transaction_by_hash(hash)
+    }
+
+    fn transaction_by_hash_with_meta(
+        &self,
+        hash: TxHash,
+    ) -> ProviderResult<Option<(TransactionSigned, TransactionMeta)>> {
+        self.provider.transaction_by_hash_with_meta(hash)
+    }
+
+    fn transactions_by_hashes(
+        &self,
+        hashes: &[TxHash],
+    ) -> ProviderResult<Vec<Option<(TransactionSigned, TransactionMeta)>>> {
+        self.provider.transactions_by_hashes(hashes)
+    }
+
+    fn transaction_block(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>> {
+        self.provider.transaction_block(id)
+    }
+
+    fn transactions_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<TransactionSigned>>> {
+        self.provider.transactions_by_block(block)
+    }
+
+    fn transactions_by_block_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<TransactionSigned>>> {
+        self.provider.transactions_by_block_range(range)
+    }
+
+    fn transactions_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<TransactionSignedNoHash>> {
+        self.provider.transactions_by_tx_range(range)
+    }
+
+    fn senders_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<Address>> {
+        self.provider.senders_by_tx_range(range)
+    }
+
+    fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
+        self.provider.transaction_sender(id)
+    }
+}
+
+impl<P: ReceiptProvider + BlockHashReader> ReceiptProvider for CachedBlockchainProvider<P> {
+    fn receipt(&self, id: TxNumber) -> ProviderResult<Option<Receipt>> {
+        self.provider.receipt(id)
+    }
+
+    fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Receipt>> {
+        self.provider.receipt_by_hash(hash)
+    }
+
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        let Some(hash) = self.resolve_hash(block)? else { return Ok(None) };
+        self.caches
+            .receipts
+            .get_or_fetch(hash, |hash| self.provider.receipts_by_block((*hash).into()))
+    }
+
+    fn receipts_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<Receipt>> {
+        self.provider.receipts_by_tx_range(range)
+    }
+}
+
+impl<P: ReceiptProvider + BlockIdReader> ReceiptProviderIdExt for CachedBlockchainProvider<P> {}
+
+impl<P: RequestsProvider> RequestsProvider for CachedBlockchainProvider<P> {
+    fn requests_by_block(
+        &self,
+        id: BlockHashOrNumber,
+        timestamp: u64,
+    ) -> ProviderResult<Option<Requests>> {
+        self.provider.requests_by_block(id, timestamp)
+    }
+}
+
+impl<P: WithdrawalsProvider> WithdrawalsProvider for CachedBlockchainProvider<P> {
+    fn withdrawals_by_block(
+        &self,
+        id: BlockHashOrNumber,
+        timestamp: u64,
+    ) -> ProviderResult<Option<Withdrawals>> {
+        self.provider.withdrawals_by_block(id, timestamp)
+    }
+
+    fn latest_withdrawal(&self) -> ProviderResult<Option<Withdrawal>> {
+        self.provider.latest_withdrawal()
+    }
+}
+
+impl<P: BlockReader> BlockReader for CachedBlockchainProvider<P> {
+    fn find_block_by_hash(&self, hash: B256, source: BlockSource) -> ProviderResult<Option<Block>> {
+        self.provider.find_block_by_hash(hash, source)
+    }
+
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Block>> {
+        let Some(hash) = self.resolve_hash(id)? else { return Ok(None) };
+        self.caches.blocks.get_or_fetch(hash, |hash| self.provider.block((*hash).into()))
+    }
+
+    fn pending_block(&self) -> ProviderResult<Option<SealedBlock>> {
+        self.provider.pending_block()
+    }
+
+    fn pending_block_with_senders(&self) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        self.provider.pending_block_with_senders()
+    }
+
+    fn pending_block_and_receipts(&self) -> ProviderResult<Option<(SealedBlock, Vec<Receipt>)>> {
+        self.provider.pending_block_and_receipts()
+    }
+
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Header>>> {
+        self.provider.ommers(id)
+    }
+
+    fn block_body_indices(&self, num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+        self.provider.block_body_indices(num)
+    }
+
+    fn block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<BlockWithSenders>> {
+        self.provider.block_with_senders(id, transaction_kind)
+    }
+
+    fn sealed_block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        self.provider.sealed_block_with_senders(id, transaction_kind)
+    }
+
+    fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
+        self.provider.block_range(range)
+    }
+
+    fn block_with_senders_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockWithSenders>> {
+        self.provider.block_with_senders_range(range)
+    }
+
+    fn sealed_block_with_senders_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedBlockWithSenders>> {
+        self.provider.sealed_block_with_senders_range(range)
+    }
+}
+
+impl<P: BlockReaderIdExt> BlockReaderIdExt for CachedBlockchainProvider<P> {
+    fn block_by_id(&self, id: BlockId) -> ProviderResult<Option<Block>> {
+        match id {
+            BlockId::Number(BlockNumberOrTag::Number(number)) => self.block_by_number(number),
+            BlockId::Hash(hash) if hash.require_canonical != Some(true) => {
+                self.block_by_hash(hash.block_hash)
+            }
+            id => self.provider.block_by_id(id),
+        }
+    }
+
+    fn sealed_header_by_id(&self, id: BlockId) -> ProviderResult<Option<SealedHeader>> {
+        match id {
+            BlockId::Number(BlockNumberOrTag::Number(number)) => self.sealed_header(number),
+            BlockId::Hash(hash) => Ok(self
+                .header(&hash.block_hash)?
+                .map(|header| SealedHeader::new(header, hash.block_hash))),
+            id => self.provider.sealed_header_by_id(id),
+        }
+    }
+
+    fn header_by_id(&self, id: BlockId) -> ProviderResult<Option<Header>> {
+        match id {
+            BlockId::Number(BlockNumberOrTag::Number(number)) => self.header_by_number(number),
+            BlockId::Hash(hash) => self.header(&hash.block_hash),
+            id => self.provider.header_by_id(id),
+        }
+    }
+
+    fn block_by_transaction_hash(
+        &self,
+        hash: TxHash,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        self.provider.block_by_transaction_hash(hash)
+    }
+
+    fn ommers_by_id(&self, id: BlockId) -> ProviderResult<Option<Vec<Header>>> {
+        self.provider.ommers_by_id(id)
+    }
+}
+
+impl<P: StateProviderFactory> StateProviderFactory for CachedBlockchainProvider<P> {
+    fn latest(&self) -> ProviderResult<StateProviderBox> {
+        self.provider.latest()
+    }
+
+    fn state_by_block_number_or_tag(
+        &self,
+        number_or_tag: BlockNumberOrTag,
+    ) -> ProviderResult<StateProviderBox> {
+        self.provider.state_by_block_number_or_tag(number_or_tag)
+    }
+
+    fn history_by_block_number(&self, block: BlockNumber) -> ProviderResult<StateProviderBox> {
+        self.provider.history_by_block_number(block)
+    }
+
+    fn history_by_block_hash(&self, block: BlockHash) -> ProviderResult<StateProviderBox> {
+        self.provider.history_by_block_hash(block)
+    }
+
+    fn state_by_block_hash(&self, block: BlockHash) -> ProviderResult<StateProviderBox> {
+        self.provider.state_by_block_hash(block)
+    }
+
+    fn pending(&self) -> ProviderResult<StateProviderBox> {
+        self.provider.pending()
+    }
+
+    fn pending_state_by_hash(&self, block_hash: B256) -> ProviderResult<Option<StateProviderBox>> {
+        self.provider.pending_state_by_hash(block_hash)
+    }
+}
+
+impl<P: EvmEnvProvider + HeaderProvider + BlockHashReader> EvmEnvProvider
+    for CachedBlockchainProvider<P>
+{
+    fn fill_env_at<EvmConfig>(
+        &self,
+        cfg: &mut CfgEnvWithHandlerCfg,
+        block_env: &mut BlockEnv,
+        at: BlockHashOrNumber,
+        evm_config: EvmConfig,
+    ) -> ProviderResult<()>
+    where
+        EvmConfig: ConfigureEvmEnv,
+    {
+        let hash = self.resolve_hash(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        let header = self.header(&hash)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.provider.fill_env_with_header(cfg, block_env, &header, evm_config)
+    }
+
+    fn fill_env_with_header<EvmConfig>(
+        &self,
+        cfg: &mut CfgEnvWithHandlerCfg,
+        block_env: &mut BlockEnv,
+        header: &Header,
+        evm_config: EvmConfig,
+    ) -> ProviderResult<()>
+    where
+        EvmConfig: ConfigureEvmEnv,
+    {
+        self.provider.fill_env_with_header(cfg, block_env, header, evm_config)
+    }
+
+    fn fill_cfg_env_at<EvmConfig>(
+        &self,
+        cfg: &mut CfgEnvWithHandlerCfg,
+        at: BlockHashOrNumber,
+        evm_config: EvmConfig,
+    ) -> ProviderResult<()>
+    where
+        EvmConfig: ConfigureEvmEnv,
+    {
+        let hash = self.resolve_hash(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        let header = self.header(&hash)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.provider.fill_cfg_env_with_header(cfg, &header, evm_config)
+    }
+
+    fn fill_cfg_env_with_header<EvmConfig>(
+        &self,
+        cfg: &mut CfgEnvWithHandlerCfg,
+        header: &Header,
+        evm_config: EvmConfig,
+    ) -> ProviderResult<()>
+    where
+        EvmConfig: ConfigureEvmEnv,
+    {
+        self.provider.fill_cfg_env_with_header(cfg, header, evm_config)
+    }
+}
+
+impl<P: ChainSpecProvider> ChainSpecProvider for CachedBlockchainProvider<P> {
+    fn chain_spec(&self) -> Arc<ChainSpec> {
+        self.provider.chain_spec()
+    }
+}
+
+impl<P: CanonStateSubscriptions> CanonStateSubscriptions for CachedBlockchainProvider<P> {
+    fn subscribe_to_canonical_state(&self) -> CanonStateNotifications {
+        self.provider.subscribe_to_canonical_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::MockEthProvider, Chain, ExecutionOutcome};
+    use reth_testing_utils::generators::{self, random_block};
+
+    #[test]
+    fn caches_blocks_until_reorged() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(1), None);
+        let hash = block.hash();
+
+        let mock = MockEthProvider::default();
+        mock.add_block(hash, block.clone().unseal());
+        let provider = CachedBlockchainProvider::new(mock.clone(), ProviderCacheConfig::default());
+
+        assert_eq!(provider.block_by_number(1).unwrap(), Some(block.clone().unseal()));
+        assert_eq!(provider.header_by_number(1).unwrap(), Some(block.header.header().clone()));
+
+        // cached blocks are served without the wrapped provider
+        mock.blocks.lock().clear();
+        mock.headers.lock().clear();
+        assert_eq!(provider.block_by_number(1).unwrap(), Some(block.clone().unseal()));
+        assert_eq!(provider.sealed_header(1).unwrap(), Some(block.header.clone()));
+
+        // the block is reorged out and replaced by a new block
+        let new_block = random_block(&mut rng, 1, None, Some(1), None);
+        let chain = |block: &SealedBlock| {
+            let block = SealedBlockWithSenders { block: block.clone(), senders: Vec::new() };
+            Arc::new(Chain::new([block], ExecutionOutcome::default(), None))
+        };
+        provider.on_canon_state_notification(&CanonStateNotification::Reorg {
+            old: chain(&block),
+            new: chain(&new_block),
+        });
+
+        assert_eq!(provider.block_hash(1).unwrap(), Some(new_block.hash()));
+        assert_eq!(provider.block_by_number(1).unwrap(), Some(new_block.clone().unseal()));
+        assert_eq!(provider.block_by_hash(hash).unwrap(), None);
+        assert_eq!(provider.receipts_by_block(1.into()).unwrap(), Some(Vec::new()));
+    }
+
+    #[tokio::test]
+    async fn invalidation_task_evicts_reorged_blocks() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(1), None);
+        let new_block = random_block(&mut rng, 1, None, Some(1), None);
+
+        let mock = MockEthProvider::default();
+        mock.add_block(block.hash(), block.clone().unseal());
+        let provider = CachedBlockchainProvider::new(mock.clone(), ProviderCacheConfig::default());
+        assert_eq!(provider.block_by_number(1).unwrap(), Some(block.clone().unseal()));
+
+        // the wrapped provider only knows the new block, so the stale block can only be served
+        // from the cache
+        mock.blocks.lock().clear();
+        mock.headers.lock().clear();
+        mock.add_block(new_block.hash(), new_block.clone().unseal());
+
+        let (tx, rx) = tokio::sync::broadcast::channel(1);
+        let task = provider.cache_invalidation_task(rx);
+        let chain = |block: &SealedBlock| {
+            let block = SealedBlockWithSenders { block: block.clone(), senders: Vec::new() };
+            Arc::new(Chain::new([block], ExecutionOutcome::default(), None))
+        };
+        tx.send(CanonStateNotification::Reorg { old: chain(&block), new: chain(&new_block) })
+            .unwrap();
+        drop(tx);
+        task.await;
+
+        assert_eq!(provider.block_hash(1).unwrap(), Some(new_block.hash()));
+        assert_eq!(provider.block_by_number(1).unwrap(), Some(new_block.clone().unseal()));
+        assert_eq!(provider.header(&block.hash()).unwrap(), None);
+    }
+
+    #[test]
+    fn does_not_fill_values_fetched_during_invalidation() {
+        let cache = Cache::<BlockNumber, BlockHash>::new("test", 10);
+
+        // the fetched hash is reorged out before the fetch completes
+        let stale = cache
+            .get_or_fetch(1, |number| {
+                cache.remove(number);
+                Ok(Some(B256::with_last_byte(1)))
+            })
+            .unwrap();
+        assert_eq!(stale, Some(B256::with_last_byte(1)));
+
+        let hash = cache.get_or_fetch(1, |_| Ok(Some(B256::with_last_byte(2)))).unwrap();
+        assert_eq!(hash, Some(B256::with_last_byte(2)));
+        assert_eq!(cache.get_or_fetch(1, |_| Ok(None)).unwrap(), Some(B256::with_last_byte(2)));
+    }
+}
@@ -0,0 +1,423 @@
+use crate::{
+    to_range, BlockHashReader, BlockNumReader, BlockReader, DatabaseProviderRO, HeaderProvider,
+    ProviderFactory, ReceiptProvider, TransactionsProvider,
+};
+use reth_chain_state::{CanonicalInMemoryState, ChainSegment, ExecutedBlock};
+use reth_db_api::database::Database;
+use reth_primitives::{
+    Address, Block, BlockNumber, BlockWithSenders, Header, Receipt, SealedBlockWithSenders,
+    SealedHeader, TransactionSigned, TransactionSignedNoHash, TxNumber, B256,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::ops::{Range, RangeBounds, RangeInclusive};
+
+/// A consistent view of the canonical chain, across the in-memory state and the database.
+///
+/// The in-memory canonical chain is captured before the read-only database transaction is opened.
+/// Blocks are only removed from memory after they were persisted, so the blocks of the snapshot
+/// either follow the blocks of the database transaction or overlap with them, and a range that
+/// is read from both never has gaps, even if blocks are persisted or the chain is advanced while
+/// it is read. The view doesn't observe any changes after it was created.
+#[derive(Debug)]
+pub struct ConsistentProvider<DB: Database> {
+    /// The canonical blocks in memory at the time the view was created.
+    in_memory: ChainSegment,
+    /// The read-only transaction of the database, opened after the in-memory snapshot was taken.
+    database: DatabaseProviderRO<DB>,
+}
+
+impl<DB: Database> ConsistentProvider<DB> {
+    /// Creates a new view by capturing the canonical chain of the in-memory state and opening a
+    /// database transaction afterwards.
+    pub fn new(
+        database: &ProviderFactory<DB>,
+        in_memory_state: &CanonicalInMemoryState,
+    ) -> ProviderResult<Self> {
+        let in_memory = in_memory_state.canonical_segment();
+        let database = database.provider()?;
+        Ok(Self { in_memory, database })
+    }
+
+    /// Returns the canonical blocks in memory of this view.
+    pub const fn in_memory(&self) -> &ChainSegment {
+        &self.in_memory
+    }
+
+    /// Returns the database provider of this view.
+    pub const fn database(&self) -> &DatabaseProviderRO<DB> {
+        &self.database
+    }
+
+    /// Returns the number of the highest canonical block of this view.
+    pub fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        match self.in_memory.last() {
+            Some(block) => Ok(block.block().number),
+            None => self.database.best_block_number(),
+        }
+    }
+
+    /// Returns the blocks of the in-memory snapshot from `next` up to and including `end`.
+    ///
+    /// No blocks are returned if the snapshot doesn't contain `next`, or if the first block isn't
+    /// a child of the block before it in the database. This only happens if the canonical chain
+    /// was reorged below the blocks of the database, and prevents returning a range with gaps or
+    /// with blocks of different forks.
+    pub(crate) fn in_memory_range(
+        &self,
+        next: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<ChainSegment> {
+        let segment = self.in_memory.slice(next..=end);
+        let Some(first) = segment.first().filter(|block| block.block().number == next) else {
+            return Ok(ChainSegment::default())
+        };
+
+        if let Some(parent) = next.checked_sub(1) {
+            let database_parent_hash = self.database.block_hash(parent)?;
+            if database_parent_hash.is_some_and(|hash| hash != first.block().parent_hash) {
+                return Ok(ChainSegment::default())
+            }
+        }
+
+        Ok(segment)
+    }
+
+    /// Fetches the items of the range from the database first, and the items of the remaining
+    /// blocks from the in-memory snapshot.
+    fn range<T>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        from_database: impl FnOnce(
+            &DatabaseProviderRO<DB>,
+            RangeInclusive<BlockNumber>,
+        ) -> ProviderResult<Vec<T>>,
+        from_memory: impl FnMut(&ExecutedBlock) -> T,
+    ) -> ProviderResult<Vec<T>> {
+        let (start, end) = range.into_inner();
+
+        // like in `range_rev`, the database is read up to the first block of the snapshot, which
+        // skips the blocks of the database that were reorged in memory but are not unwound yet
+        let database_end = match self.in_memory.first() {
+            Some(block) => block.block().number.checked_sub(1).map(|number| number.min(end)),
+            None => Some(end),
+        };
+        let mut items = match database_end.filter(|database_end| *database_end >= start) {
+            Some(database_end) => from_database(&self.database, start..=database_end)?,
+            None => Vec::new(),
+        };
+
+        let next = start + items.len() as u64;
+        let in_memory = if self.in_memory.first().is_some_and(|block| block.block().number < next) {
+            // the range starts above the first block of the snapshot, whose blocks are connected
+            self.in_memory.slice(next..=end)
+        } else {
+            self.in_memory_range(next, end)?
+        };
+        items.extend(in_memory.iter().map(from_memory));
+
+        Ok(items)
+    }
+
+    /// Fetches the items of the range in descending order, from the in-memory snapshot first and
+    /// the items of the remaining blocks below it from the database.
+    fn range_rev<T>(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        from_database: impl FnOnce(
+            &DatabaseProviderRO<DB>,
+            RangeInclusive<BlockNumber>,
+        ) -> ProviderResult<Vec<T>>,
+        from_memory: impl FnMut(&ExecutedBlock) -> T,
+    ) -> ProviderResult<Vec<T>> {
+        let (start, end) = range.clone().into_inner();
+        let in_memory = self.in_memory.slice(range);
+        let mut items = in_memory.iter().rev().map(from_memory).collect::<Vec<_>>();
+
+        // the database is read up to the first block of the snapshot, which also skips the
+        // blocks of the database that were reorged in memory but are not unwound yet
+        let database_end = match in_memory.first() {
+            Some(block) => block.block().number.checked_sub(1),
+            None => Some(end),
+        };
+        if let Some(database_end) = database_end.filter(|database_end| *database_end >= start) {
+            items.extend(from_database(&self.database, start..=database_end)?.into_iter().rev());
+        }
+
+        Ok(items)
+    }
+
+    /// Returns the number of the last block of the database, and the number of the first
+    /// transaction of the in-memory blocks that follow it.
+    fn first_in_memory_tx(&self) -> ProviderResult<(BlockNumber, TxNumber)> {
+        // the transactions of the in-memory blocks follow the transactions of the last block of
+        // the database
+        let last_database_block = self.database.last_block_number()?;
+        let in_memory_tx_num = self
+            .database
+            .block_body_indices(last_database_block)?
+            .map(|indices| indices.next_tx_num())
+            .unwrap_or_default();
+        Ok((last_database_block, in_memory_tx_num))
+    }
+
+    /// Fetches the items of the transaction range from the database first, and the items of the
+    /// remaining transactions from the blocks of the in-memory snapshot that follow the database.
+    ///
+    /// `from_memory` returns the items of all transactions of the block, in order.
+    fn tx_range<T>(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+        from_database: impl FnOnce(&DatabaseProviderRO<DB>, Range<TxNumber>) -> ProviderResult<Vec<T>>,
+        mut from_memory: impl FnMut(&ExecutedBlock) -> Vec<T>,
+    ) -> ProviderResult<Vec<T>> {
+        let range = to_range(range);
+        let (last_database_block, in_memory_tx_num) = self.first_in_memory_tx()?;
+
+        let mut items = if range.start < in_memory_tx_num {
+            from_database(&self.database, range.start..range.end.min(in_memory_tx_num))?
+        } else {
+            Vec::new()
+        };
+
+        let mut tx_num = in_memory_tx_num;
+        for block in self.in_memory_range(last_database_block + 1, BlockNumber::MAX)?.iter() {
+            if tx_num >= range.end {
+                break
+            }
+
+            let tx_count = block.block().body.len() as u64;
+            if tx_num + tx_count > range.start {
+                let skip = range.start.saturating_sub(tx_num) as usize;
+                let take = (range.end - tx_num).min(tx_count) as usize;
+                items.extend(from_memory(block).into_iter().take(take).skip(skip));
+            }
+            tx_num += tx_count;
+        }
+
+        Ok(items)
+    }
+
+    /// Returns the headers of the range.
+    pub fn headers_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Header>> {
+        self.range(
+            range,
+            |db, range| db.headers_range(range),
+            |block| block.block().header.header().clone(),
+        )
+    }
+
+    /// Returns the headers of the range in descending order.
+    pub fn headers_range_rev(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Header>> {
+        self.range_rev(
+            range,
+            |db, range| db.headers_range(range),
+            |block| block.block().header.header().clone(),
+        )
+    }
+
+    /// Returns the sealed headers of the range.
+    pub fn sealed_headers_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        self.range(
+            range,
+            |db, range| db.sealed_headers_range(range),
+            |block| block.block().header.clone(),
+        )
+    }
+
+    /// Returns the sealed headers of the range, up to the first header that doesn't match the
+    /// predicate.
+    pub fn sealed_headers_while(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        mut predicate: impl FnMut(&SealedHeader) -> bool,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        let (start, end) = range.clone().into_inner();
+        let mut headers = self.database.sealed_headers_while(range, &mut predicate)?;
+
+        let next = start + headers.len() as u64;
+        headers.extend(
+            self.in_memory_range(next, end)?
+                .iter()
+                .map(|block| block.block().header.clone())
+                .take_while(|header| predicate(header)),
+        );
+
+        Ok(headers)
+    }
+
+    /// Returns the canonical hashes of the range `start..=end`.
+    ///
+    /// Unlike [`BlockHashReader::canonical_hashes_range`] of the database, the range includes
+    /// `end`. The hashes end at the highest canonical block of the view, but they never have gaps:
+    /// if a block below it is missing, e.g. because the chain was reorged below the blocks of the
+    /// database, [`ProviderError::HeaderNotFound`] is returned for the first missing block.
+    pub fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        if start > end {
+            return Ok(Vec::new())
+        }
+
+        let hashes = self.range(
+            start..=end,
+            |db, range| db.canonical_hashes_range(*range.start(), range.end().saturating_add(1)),
+            |block| block.block().hash(),
+        )?;
+
+        let expected = end.min(self.best_block_number()?).saturating_add(1).saturating_sub(start);
+        if (hashes.len() as u64) < expected {
+            return Err(ProviderError::HeaderNotFound((start + hashes.len() as u64).into()))
+        }
+
+        Ok(hashes)
+    }
+
+    /// Returns the hash of the canonical block with the given number.
+    pub fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        Ok(self.canonical_hashes_range(number, number)?.pop())
+    }
+
+    /// Returns the number of the block that contains the transaction with the given number.
+    pub fn transaction_block(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>> {
+        let (last_database_block, in_memory_tx_num) = self.first_in_memory_tx()?;
+        if id < in_memory_tx_num {
+            return self.database.transaction_block(id)
+        }
+
+        Ok(self
+            .in_memory_range(last_database_block + 1, BlockNumber::MAX)?
+            .block_by_tx_index(id - in_memory_tx_num)
+            .map(|(block, _)| block.block().number))
+    }
+
+    /// Returns the blocks of the range.
+    pub fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
+        self.range(range, |db, range| db.block_range(range), |block| block.block().clone().unseal())
+    }
+
+    /// Returns the blocks of the range in descending order.
+    pub fn blocks_range_rev(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Block>> {
+        self.range_rev(
+            range,
+            |db, range| db.block_range(range),
+            |block| block.block().clone().unseal(),
+        )
+    }
+
+    /// Returns the blocks of the range with their senders.
+    pub fn block_with_senders_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockWithSenders>> {
+        self.range(
+            range,
+            |db, range| db.block_with_senders_range(range),
+            |block| BlockWithSenders {
+                block: block.block().clone().unseal(),
+                senders: block.senders().clone(),
+            },
+        )
+    }
+
+    /// Returns the sealed blocks of the range with their senders.
+    pub fn sealed_block_with_senders_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedBlockWithSenders>> {
+        self.range(
+            range,
+            |db, range| db.sealed_block_with_senders_range(range),
+            |block| SealedBlockWithSenders {
+                block: block.block().clone(),
+                senders: block.senders().clone(),
+            },
+        )
+    }
+
+    /// Returns the transactions of the blocks of the range.
+    pub fn transactions_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<TransactionSigned>>> {
+        self.range(
+            range,
+            |db, range| db.transactions_by_block_range(range),
+            |block| block.block().body.clone(),
+        )
+    }
+
+    /// Returns the transactions of the transaction range.
+    pub fn transactions_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<TransactionSignedNoHash>> {
+        self.tx_range(
+            range,
+            |db, range| db.transactions_by_tx_range(range),
+            |block| block.block().body.iter().cloned().map(Into::into).collect(),
+        )
+    }
+
+    /// Returns the senders of the transaction range.
+    pub fn senders_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<Address>> {
+        self.tx_range(
+            range,
+            |db, range| db.senders_by_tx_range(range),
+            |block| block.senders().clone(),
+        )
+    }
+
+    /// Returns the receipts of each block of the range.
+    pub fn receipts_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<Receipt>>> {
+        self.range(
+            range,
+            |db, range| db.receipts_by_block_range(range),
+            |block| {
+                block
+                    .execution_outcome()
+                    .receipts_by_block(block.block().number)
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .collect()
+            },
+        )
+    }
+
+    /// Returns the receipts of the transaction range.
+    pub fn receipts_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<Receipt>> {
+        self.tx_range(
+            range,
+            |db, range| db.receipts_by_tx_range(range),
+            |block| {
+                block
+                    .execution_outcome()
+                    .receipts_by_block(block.block().number)
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .collect()
+            },
+        )
+    }
+}
@@ -3,10 +3,14 @@ use crate::{
     to_range,
     traits::{BlockSource, ReceiptProvider},
     BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, RequestsProvider, StageCheckpointReader, StateProviderBox,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    EvmEnvProvider, ForkBlockReader, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    InternalTransfersProvider, LogIndexProvider, LogsProvider, ProviderError,
+    PruneCheckpointReader, ReorgJournalReader, RequestsProvider, StageCheckpointNotificationSender,
+    StageCheckpointNotifications, StageCheckpointReader, StageCheckpointSubscriptions,
+    StateDiffProvider, StateProviderBox, StaticFileProviderFactory, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
+use reth_chain_state::ExecutedBlock;
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
 use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
@@ -27,7 +31,7 @@ use std::{
     path::Path,
     sync::Arc,
 };
-use tokio::sync::watch;
+use tokio::sync::{broadcast, watch};
 use tracing::trace;
 
 mod provider;
@@ -35,6 +39,9 @@ pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
 
 mod metrics;
 
+/// The number of stage checkpoint updates that are buffered for slow subscribers.
+const STAGE_CHECKPOINT_CHANNEL_SIZE: usize = 256;
+
 /// A common provider that fetches data from a database or static file.
 ///
 /// This provider implements most provider or provider factory traits.
@@ -48,6 +55,8 @@ pub struct ProviderFactory<DB> {
     static_file_provider: StaticFileProvider,
     /// Optional pruning configuration
     prune_modes: PruneModes,
+    /// Notifies subscribers about committed stage checkpoints.
+    stage_checkpoint_tx: StageCheckpointNotificationSender,
 }
 
 impl<DB> ProviderFactory<DB> {
@@ -57,7 +66,13 @@ impl<DB> ProviderFactory<DB> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { db: Arc::new(db), chain_spec, static_file_provider, prune_modes: PruneModes::none() }
+        Self {
+            db: Arc::new(db),
+            chain_spec,
+            static_file_provider,
+            prune_modes: PruneModes::none(),
+            stage_checkpoint_tx: broadcast::channel(STAGE_CHECKPOINT_CHANNEL_SIZE).0,
+        }
     }
 
     /// Enables metrics on the static file provider.
@@ -98,6 +113,7 @@ impl ProviderFactory<DatabaseEnv> {
             chain_spec,
             static_file_provider,
             prune_modes: PruneModes::none(),
+            stage_checkpoint_tx: broadcast::channel(STAGE_CHECKPOINT_CHANNEL_SIZE).0,
         })
     }
 }
@@ -125,12 +141,15 @@ impl<DB: Database> ProviderFactory<DB> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<DB>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-        )))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+            )
+            .with_stage_checkpoint_notifications(self.stage_checkpoint_tx.clone()),
+        ))
     }
 
     /// State provider for latest block
@@ -408,6 +427,13 @@ impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
         self.provider()?.transaction_by_hash_with_meta(tx_hash)
     }
 
+    fn transactions_by_hashes(
+        &self,
+        hashes: &[TxHash],
+    ) -> ProviderResult<Vec<Option<(TransactionSigned, TransactionMeta)>>> {
+        self.provider()?.transactions_by_hashes(hashes)
+    }
+
     fn transaction_block(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>> {
         self.provider()?.transaction_block(id)
     }
@@ -504,6 +530,78 @@ where
     }
 }
 
+impl<DB> InternalTransfersProvider for ProviderFactory<DB>
+where
+    DB: Database,
+{
+    fn internal_transfers_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<Vec<reth_primitives::InternalTransfer>>>> {
+        self.provider()?.internal_transfers_by_block(id)
+    }
+}
+
+impl<DB> StateDiffProvider for ProviderFactory<DB>
+where
+    DB: Database,
+{
+    fn state_diff_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<reth_execution_types::StateDiff>> {
+        self.provider()?.state_diff_by_block(id)
+    }
+}
+
+impl<DB> ReorgJournalReader for ProviderFactory<DB>
+where
+    DB: Database,
+{
+    fn latest_reorgs(
+        &self,
+        limit: usize,
+    ) -> ProviderResult<Vec<(u64, reth_primitives::ReorgRecord)>> {
+        self.provider()?.latest_reorgs(limit)
+    }
+}
+
+impl<DB> ForkBlockReader for ProviderFactory<DB>
+where
+    DB: Database,
+{
+    fn fork_block(&self, hash: BlockHash) -> ProviderResult<Option<ExecutedBlock>> {
+        self.provider()?.fork_block(hash)
+    }
+}
+
+impl<DB> LogIndexProvider for ProviderFactory<DB>
+where
+    DB: Database,
+{
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.log_index_range()
+    }
+
+    fn blocks_with_log_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.blocks_with_log_address(address, range)
+    }
+
+    fn blocks_with_log_topic(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.blocks_with_log_topic(topic, range)
+    }
+}
+
+impl<DB> LogsProvider for ProviderFactory<DB> where DB: Database {}
+
 impl<DB: Database> StageCheckpointReader for ProviderFactory<DB> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.provider()?.get_stage_checkpoint(id)
@@ -517,6 +615,12 @@ impl<DB: Database> StageCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Send + Sync> StageCheckpointSubscriptions for ProviderFactory<DB> {
+    fn subscribe_to_stage_checkpoints(&self) -> StageCheckpointNotifications {
+        self.stage_checkpoint_tx.subscribe()
+    }
+}
+
 impl<DB: Database> EvmEnvProvider for ProviderFactory<DB> {
     fn fill_env_at<EvmConfig>(
         &self,
@@ -598,6 +702,7 @@ impl<DB> Clone for ProviderFactory<DB> {
             chain_spec: self.chain_spec.clone(),
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
+            stage_checkpoint_tx: self.stage_checkpoint_tx.clone(),
         }
     }
 }
@@ -606,8 +711,15 @@ mod tests {
     use super::*;
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
-        test_utils::{blocks::TEST_BLOCK, create_test_provider_factory},
-        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, TransactionsProvider,
+        test_utils::{
+            blocks::{BlockchainTestData, TEST_BLOCK},
+            create_test_provider_factory, create_test_provider_factory_in_memory,
+        },
+        AccountHistoryReader, BlockHashReader, BlockNumReader, BlockWriter, FinalizedBlockReader,
+        FinalizedBlockWriter, ForkBlockWriter, HeaderSyncGapProvider, HistoryWriter,
+        PruneCheckpointWriter, ReorgJournalWriter, StageCheckpointWriter, StateChangeWriter,
+        StateReader, TransactionsProvider, UnwindJournalReader, UnwindJournalWriter,
+        UnwindPreviewProvider,
     };
     use assert_matches::assert_matches;
     use rand::Rng;
@@ -616,15 +728,30 @@ mod tests {
         mdbx::DatabaseArguments,
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
+        BlockNumberList,
+    };
+    use reth_db_api::{
+        models::{AccountBeforeTx, BlockNumberAddress, ShardedKey, StoredBlockBodyIndices},
+        table::Table,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_execution_types::Delta;
+    use reth_primitives::{
+        revm_primitives::AccountInfo, Account, Address, ReorgRecord, Request, Requests,
+        StaticFileSegment, StorageEntry, TxNumber, B256, U256,
     };
-    use reth_primitives::{StaticFileSegment, TxNumber, B256, U256};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::{
         generators,
         generators::{random_block, random_header},
     };
-    use std::{ops::RangeInclusive, sync::Arc};
+    use reth_trie::{
+        updates::{StorageTrieUpdates, TrieUpdates},
+        BranchNodeCompact, HashedPostState, Nibbles,
+    };
+    use revm::db::states::{PlainStorageRevert, RevertToSlot};
+    use std::{collections::BTreeMap, ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
     #[test]
@@ -672,6 +799,30 @@ mod tests {
         provider.block_hash(0).unwrap();
     }
 
+    #[test]
+    fn stage_checkpoint_notifications() {
+        let factory = create_test_provider_factory();
+        let mut checkpoints = factory.subscribe_to_stage_checkpoints();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(1)).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(2)).unwrap();
+        provider_rw.update_pipeline_stages(3, false).unwrap();
+        assert!(checkpoints.try_recv().is_err());
+
+        // only the last checkpoint of each stage is notified, once it's committed
+        provider_rw.commit().unwrap();
+        let updates = std::iter::from_fn(|| checkpoints.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(updates.len(), StageId::ALL.len());
+        assert!(updates.iter().all(|update| update.checkpoint == StageCheckpoint::new(3)));
+
+        // dropped transactions are not notified
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(4)).unwrap();
+        drop(provider_rw);
+        assert!(checkpoints.try_recv().is_err());
+    }
+
     #[test]
     fn insert_block_with_prune_modes() {
         let factory = create_test_provider_factory();
@@ -706,6 +857,371 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_block_in_memory_database() {
+        let factory = create_test_provider_factory_in_memory();
+
+        let block = TEST_BLOCK.clone();
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_block(block.clone().try_seal_with_senders().unwrap()).unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.block_hash(block.number).unwrap(), Some(block.hash()));
+        assert_matches!(provider.transaction_id(block.body[0].hash), Ok(Some(0)));
+        assert_matches!(
+            provider.transaction_sender(0), Ok(Some(sender))
+            if sender == block.body[0].recover_signer().unwrap()
+        );
+    }
+
+    #[test]
+    fn state_diff_by_block() {
+        let factory = create_test_provider_factory();
+
+        let (address_a, address_b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (slot_1, slot_2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let account_a = |balance| Account { balance: U256::from(balance), ..Default::default() };
+        let account_b = Account { nonce: 1, ..Default::default() };
+        let storage = |key, value: u64| StorageEntry { key, value: U256::from(value) };
+
+        // block 0 creates account B, block 1 changes the balance and a storage slot of account A,
+        // and block 2 only changes a storage slot of account B, which is not part of the account
+        // changeset
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        tx.put::<tables::PlainAccountState>(address_a, account_a(2)).unwrap();
+        tx.put::<tables::PlainAccountState>(address_b, account_b).unwrap();
+        tx.put::<tables::PlainStorageState>(address_a, storage(slot_1, 5)).unwrap();
+        tx.put::<tables::PlainStorageState>(address_b, storage(slot_2, 8)).unwrap();
+        tx.put::<tables::AccountChangeSets>(0, AccountBeforeTx { address: address_b, info: None })
+            .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            1,
+            AccountBeforeTx { address: address_a, info: Some(account_a(1)) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>(BlockNumberAddress((1, address_a)), storage(slot_1, 0))
+            .unwrap();
+        tx.put::<tables::StorageChangeSets>(BlockNumberAddress((2, address_b)), storage(slot_2, 7))
+            .unwrap();
+        provider.update_history_indices(0..=2).unwrap();
+        provider.commit().unwrap();
+
+        let diff = factory.state_diff_by_block(1.into()).unwrap().unwrap();
+        assert_eq!(diff.accounts.keys().copied().collect::<Vec<_>>(), vec![address_a]);
+        let diff_a = &diff.accounts[&address_a];
+        assert_eq!((diff_a.before, diff_a.after), (Some(account_a(1)), Some(account_a(2))));
+        assert_eq!(
+            diff_a.storage,
+            BTreeMap::from([(slot_1, Delta { from: U256::ZERO, to: U256::from(5) })])
+        );
+
+        // the state after the tip block is the plain state
+        let diff = factory.state_diff_by_block(2.into()).unwrap().unwrap();
+        assert_eq!(diff.accounts.keys().copied().collect::<Vec<_>>(), vec![address_b]);
+        let diff_b = &diff.accounts[&address_b];
+        assert_eq!((diff_b.before, diff_b.after), (Some(account_b), Some(account_b)));
+        assert_eq!(
+            diff_b.storage,
+            BTreeMap::from([(slot_2, Delta { from: U256::from(7), to: U256::from(8) })])
+        );
+
+        // the changesets of pruned blocks are gone
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::StorageHistory,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        assert_matches!(
+            factory.state_diff_by_block(1.into()),
+            Err(ProviderError::StateAtBlockPruned(1))
+        );
+        assert!(factory.state_diff_by_block(2.into()).unwrap().is_some());
+    }
+
+    #[test]
+    fn execution_outcome_from_changesets() {
+        let factory = create_test_provider_factory();
+
+        let (address_a, address_b, address_c) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let (slot_1, slot_2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let account_a = |balance| Account { balance: U256::from(balance), ..Default::default() };
+        let account_b = Account { nonce: 1, ..Default::default() };
+        let account_c = Account { nonce: 2, ..Default::default() };
+        let storage = |key, value: u64| StorageEntry { key, value: U256::from(value) };
+
+        // block 0 creates account B, block 1 changes the balance and a storage slot of account A
+        // and creates account C, and block 2 only changes a storage slot of account B, which is not
+        // part of the account changeset
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        for block in 0..=2 {
+            tx.put::<tables::BlockBodyIndices>(block, StoredBlockBodyIndices::default()).unwrap();
+        }
+        tx.put::<tables::PlainAccountState>(address_a, account_a(2)).unwrap();
+        tx.put::<tables::PlainAccountState>(address_b, account_b).unwrap();
+        tx.put::<tables::PlainAccountState>(address_c, account_c).unwrap();
+        tx.put::<tables::PlainStorageState>(address_a, storage(slot_1, 5)).unwrap();
+        tx.put::<tables::PlainStorageState>(address_b, storage(slot_2, 8)).unwrap();
+        tx.put::<tables::AccountChangeSets>(0, AccountBeforeTx { address: address_b, info: None })
+            .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            1,
+            AccountBeforeTx { address: address_a, info: Some(account_a(1)) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(1, AccountBeforeTx { address: address_c, info: None })
+            .unwrap();
+        tx.put::<tables::StorageChangeSets>(BlockNumberAddress((1, address_a)), storage(slot_1, 0))
+            .unwrap();
+        tx.put::<tables::StorageChangeSets>(BlockNumberAddress((2, address_b)), storage(slot_2, 7))
+            .unwrap();
+        provider.update_history_indices(0..=2).unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert!(provider.execution_outcome(3).unwrap().is_none());
+
+        let outcome = provider.execution_outcome(1).unwrap().unwrap();
+        assert_eq!(outcome.first_block(), 1);
+        assert_eq!(outcome.account(&address_a), Some(Some(account_a(2))));
+        assert_eq!(outcome.account(&address_c), Some(Some(account_c)));
+        let state_a = &outcome.bundle.state[&address_a];
+        assert_eq!(state_a.original_info, Some(AccountInfo::from(account_a(1))));
+        let slot = &state_a.storage[&U256::from_be_bytes(slot_1.0)];
+        assert_eq!(
+            (slot.previous_or_original_value, slot.present_value),
+            (U256::ZERO, U256::from(5))
+        );
+        assert_eq!(outcome.bundle.state[&address_c].original_info, None);
+
+        // the reverts restore the balance of account A, its storage slot, and remove account C
+        let mut reverts = outcome.bundle.reverts.clone();
+        reverts.sort();
+        let reverts = reverts.into_plain_state_reverts();
+        assert_eq!(
+            reverts.accounts,
+            vec![vec![(address_a, Some(account_a(1).into())), (address_c, None)]]
+        );
+        assert_eq!(
+            reverts.storage,
+            vec![vec![PlainStorageRevert {
+                address: address_a,
+                wiped: false,
+                storage_revert: vec![(
+                    U256::from_be_bytes(slot_1.0),
+                    RevertToSlot::Some(U256::ZERO)
+                )],
+            }]]
+        );
+
+        // the state after the tip block is the plain state, and only the storage of account B is
+        // reverted
+        let outcome = provider.execution_outcome(2).unwrap().unwrap();
+        assert_eq!(outcome.account(&address_b), Some(Some(account_b)));
+        assert_eq!(outcome.storage(&address_b, U256::from_be_bytes(slot_2.0)), Some(U256::from(8)));
+        let mut reverts = outcome.bundle.reverts.clone();
+        reverts.sort();
+        let reverts = reverts.into_plain_state_reverts();
+        assert_eq!(reverts.accounts, vec![vec![]]);
+        assert_eq!(
+            reverts.storage,
+            vec![vec![PlainStorageRevert {
+                address: address_b,
+                wiped: false,
+                storage_revert: vec![(
+                    U256::from_be_bytes(slot_2.0),
+                    RevertToSlot::Some(U256::from(7))
+                )],
+            }]]
+        );
+        drop(provider);
+
+        // the changesets of pruned blocks are gone
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_matches!(provider.execution_outcome(1), Err(ProviderError::StateAtBlockPruned(1)));
+        assert!(provider.execution_outcome(2).unwrap().is_some());
+    }
+
+    #[test]
+    fn reorg_journal() {
+        let factory = create_test_provider_factory_in_memory();
+        let reorg = |observed_at| ReorgRecord { observed_at, ..Default::default() };
+
+        let provider = factory.provider_rw().unwrap();
+        for observed_at in 0..5 {
+            assert_eq!(provider.append_reorg(reorg(observed_at)).unwrap(), observed_at);
+        }
+        provider.update_reorg(4, ReorgRecord { reincluded_transactions: 1, ..reorg(4) }).unwrap();
+        assert_eq!(provider.prune_reorgs(3).unwrap(), 2);
+        assert_eq!(provider.prune_reorgs(3).unwrap(), 0);
+        // sequence numbers continue after the pruned reorgs
+        assert_eq!(provider.append_reorg(reorg(5)).unwrap(), 5);
+        provider.commit().unwrap();
+
+        assert_eq!(
+            factory.latest_reorgs(10).unwrap(),
+            vec![
+                (5, reorg(5)),
+                (4, ReorgRecord { reincluded_transactions: 1, ..reorg(4) }),
+                (3, reorg(3)),
+                (2, reorg(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn internal_transfers_of_executed_and_pruned_blocks() {
+        let factory = create_test_provider_factory();
+
+        let block = TEST_BLOCK.clone();
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_block(block.clone().try_seal_with_senders().unwrap()).unwrap();
+
+        // the block is not executed yet
+        assert_matches!(provider.internal_transfers_by_block(block.number.into()), Ok(None));
+
+        provider
+            .save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(block.number))
+            .unwrap();
+        // the transfers were not recorded during execution
+        assert_matches!(
+            provider.internal_transfers_by_block(block.number.into()),
+            Err(ProviderError::InternalTransfersNotIndexed(number)) if number == block.number
+        );
+
+        // a block without transfers is indexed too
+        provider
+            .write_internal_transfers(vec![vec![Vec::new(); block.body.len()]], block.number)
+            .unwrap();
+        assert_matches!(
+            provider.internal_transfers_by_block(block.number.into()),
+            Ok(Some(transfers)) if transfers.len() == block.body.len() &&
+                transfers.iter().all(Vec::is_empty)
+        );
+
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::InternalTransfers,
+                PruneCheckpoint {
+                    block_number: Some(block.number),
+                    tx_number: None,
+                    prune_mode: PruneMode::Full,
+                },
+            )
+            .unwrap();
+        assert_matches!(
+            provider.internal_transfers_by_block(block.number.into()),
+            Err(ProviderError::StateAtBlockPruned(number)) if number == block.number
+        );
+    }
+
+    #[test]
+    fn unwind_preview_and_journal() {
+        let factory = create_test_provider_factory();
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 0, None, Some(3), None);
+
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_block(block.try_seal_with_senders().unwrap()).unwrap();
+
+        let preview = provider.unwind_preview(0..=0).unwrap();
+        assert_eq!(preview.blocks(), 1);
+        assert_eq!(preview.transactions, 3);
+        let entries = |table: &str| {
+            preview.tables.iter().find(|preview| preview.table == table).map(|t| t.entries)
+        };
+        assert_eq!(entries(tables::Transactions::NAME), Some(3));
+        assert_eq!(entries(tables::BlockBodyIndices::NAME), Some(1));
+        assert!(preview.bytes() > 0);
+
+        // The unwind journal does not interfere with the last finalized block
+        assert_eq!(provider.unwind_journal_target(), Ok(None));
+        provider.save_unwind_journal_target(5).unwrap();
+        assert_eq!(provider.last_finalized_block_number(), Ok(None));
+        provider.save_finalized_block_number(10).unwrap();
+        assert_eq!(provider.unwind_journal_target(), Ok(Some(5)));
+        provider.clear_unwind_journal().unwrap();
+        assert_eq!(provider.unwind_journal_target(), Ok(None));
+        assert_eq!(provider.last_finalized_block_number(), Ok(Some(10)));
+    }
+
+    #[test]
+    fn account_history_across_shards() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let address = Address::with_last_byte(1);
+        let account = |nonce| Account { nonce, balance: U256::ZERO, bytecode_hash: None };
+
+        // the account was changed in blocks 1, 3, 7, 10 and 15
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(address, 7),
+            BlockNumberList::new([1, 3, 7]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(address, u64::MAX),
+            BlockNumberList::new([10, 15]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(Address::with_last_byte(2), u64::MAX),
+            BlockNumberList::new([4]).unwrap(),
+        )
+        .unwrap();
+        for (block_number, info) in
+            [(1, None), (3, Some(account(1))), (7, Some(account(3))), (10, Some(account(7)))]
+        {
+            tx.put::<tables::AccountChangeSets>(block_number, AccountBeforeTx { address, info })
+                .unwrap();
+        }
+        tx.put::<tables::AccountChangeSets>(15, AccountBeforeTx { address, info: None }).unwrap();
+        tx.put::<tables::PlainAccountState>(address, account(15)).unwrap();
+
+        assert_eq!(
+            provider.account_history(address, 0..=20).unwrap(),
+            vec![
+                (1, Some(account(1))),
+                (3, Some(account(3))),
+                (7, Some(account(7))),
+                (10, None),
+                (15, Some(account(15)))
+            ]
+        );
+        assert_eq!(
+            provider.account_history(address, 2..=8).unwrap(),
+            vec![(3, Some(account(3))), (7, Some(account(7)))]
+        );
+        assert_eq!(provider.account_history(address, 16..=20).unwrap(), vec![]);
+        assert_eq!(provider.account_history(Address::with_last_byte(3), 0..=20).unwrap(), vec![]);
+    }
+
     #[test]
     fn take_block_transaction_range_recover_senders() {
         let factory = create_test_provider_factory();
@@ -779,4 +1295,40 @@ mod tests {
         assert_eq!(gap.local_head, head);
         assert_eq!(gap.target.tip(), consensus_tip.into());
     }
+
+    #[test]
+    fn fork_block_execution_roundtrip() {
+        let factory = create_test_provider_factory();
+        let (block, mut execution_output) = BlockchainTestData::default().blocks[0].clone();
+        execution_output.requests =
+            vec![Requests(vec![Request::DepositRequest(Default::default())])];
+
+        let mut trie = TrieUpdates::default();
+        trie.insert_storage_updates(
+            B256::random(),
+            StorageTrieUpdates::new([(
+                Nibbles::from_nibbles([1, 2, 3]),
+                BranchNodeCompact::new(0b11, 0, 0, vec![], None),
+            )]),
+        );
+        let executed = ExecutedBlock::new(
+            Arc::new(block.block.clone()),
+            Arc::new(block.senders.clone()),
+            Arc::new(execution_output.clone()),
+            Arc::new(HashedPostState::from_bundle_state(&execution_output.bundle.state)),
+            Arc::new(trie),
+        );
+
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_fork_blocks([executed.clone()]).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(factory.fork_block(block.hash()).unwrap(), Some(executed));
+
+        let provider = factory.provider_rw().unwrap();
+        assert_eq!(provider.remove_fork_blocks_below(block.number + 1).unwrap(), 1);
+        assert_eq!(provider.tx_ref().entries::<tables::ForkBlockExecutions>().unwrap(), 0);
+        provider.commit().unwrap();
+        assert_eq!(factory.fork_block(block.hash()).unwrap(), None);
+    }
 }
@@ -1,25 +1,35 @@
 use crate::{
     bundle_state::StorageRevertsIter,
-    providers::{database::metrics, static_file::StaticFileWriter, StaticFileProvider},
+    providers::{database::metrics, static_file::StaticFileWriter, RangeIter, StaticFileProvider},
     to_range,
     traits::{
-        AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
+        AccountExtReader, AccountHistoryReader, BlockSource, ChangeSetReader, ReceiptProvider,
+        StageCheckpointWriter,
     },
     writer::UnifiedStorageWriter,
-    AccountReader, BlockExecutionReader, BlockExecutionWriter, BlockHashReader, BlockNumReader,
-    BlockReader, BlockWriter, BundleStateInit, EvmEnvProvider, FinalizedBlockReader,
-    FinalizedBlockWriter, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HistoricalStateProvider, HistoryWriter, LatestStateProvider, OriginalValuesKnown,
-    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, RequestsProvider, RevertsInit,
-    StageCheckpointReader, StateChangeWriter, StateProviderBox, StateWriter, StatsReader,
+    AccountReader, AccountRevertInit, BlockExecutionReader, BlockExecutionWriter, BlockHashReader,
+    BlockNumReader, BlockReader, BlockWriter, BundleStateInit, EvmEnvProvider,
+    FinalizedBlockReader, FinalizedBlockWriter, ForkBlockReader, ForkBlockWriter, HashingWriter,
+    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HistoricalStateProvider,
+    HistoricalStateProviderRef, HistoryWriter, InternalTransfersProvider, LatestStateProvider,
+    LogIndexKeys, LogIndexProvider, LogIndexWriter, LogsProvider, OriginalValuesKnown,
+    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, ReorgJournalReader,
+    ReorgJournalWriter, RequestsProvider, RevertsInit, StageCheckpointNotificationSender,
+    StageCheckpointReader, StageCheckpointUpdate, StateChangeWriter, StateDiffProvider,
+    StateProvider, StateProviderBox, StateReader, StateWriter, StatsReader, StorageChangeSetReader,
     StorageReader, StorageTrieWriter, TransactionVariant, TransactionsProvider,
-    TransactionsProviderExt, TrieWriter, WithdrawalsProvider,
+    TransactionsProviderExt, TrieWriter, UnwindJournalReader, UnwindJournalWriter, UnwindPreview,
+    UnwindPreviewProvider, UnwindTablePreview, WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
+use parking_lot::Mutex;
 use rayon::slice::ParallelSliceMut;
+use reth_chain_state::ExecutedBlock;
 use reth_chainspec::{ChainInfo, ChainSpec, EthereumHardforks};
+use reth_codecs::Compact;
 use reth_db::{
-    cursor::DbDupCursorRW, tables, BlockNumberList, PlainAccountState, PlainStorageState,
+    cursor::DbDupCursorRW, tables, BlockNumberList, PlainAccountState, PlainStorageState, RawKey,
+    RawTable,
 };
 use reth_db_api::{
     common::KeyValue,
@@ -28,20 +38,21 @@ use reth_db_api::{
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
         ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        StoredInternalTransfers,
     },
     table::{Table, TableRow},
     transaction::{DbTx, DbTxMut},
     DatabaseError,
 };
 use reth_evm::ConfigureEvmEnv;
-use reth_execution_types::{Chain, ExecutionOutcome};
+use reth_execution_types::{AccountDiff, Chain, Delta, ExecutionOutcome, StateDiff};
 use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
-    BlockWithSenders, Bytecode, GotExpected, Header, Receipt, Requests, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta,
-    TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    BlockWithSenders, Bytecode, GotExpected, Header, InternalTransfer, Receipt, Receipts,
+    ReorgRecord, Requests, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment,
+    StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -49,13 +60,17 @@ use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
     updates::{StorageTrieUpdates, TrieUpdates},
-    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
+    HashedPostState, HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
 };
 use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
 use revm::{
-    db::states::{PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset},
+    db::{
+        states::{PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset},
+        BundleState,
+    },
     primitives::{BlockEnv, CfgEnvWithHandlerCfg},
 };
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     collections::{hash_map, BTreeMap, BTreeSet, HashMap, HashSet},
@@ -103,6 +118,37 @@ impl<DB: Database> DatabaseProviderRW<DB> {
     }
 }
 
+/// The stage checkpoints saved in a transaction, which are notified once it's committed.
+#[derive(Debug)]
+struct PendingStageCheckpoints {
+    /// Notifies the subscribers about the committed checkpoints.
+    sender: StageCheckpointNotificationSender,
+    /// The last saved checkpoint of each stage.
+    updates: Mutex<Vec<StageCheckpointUpdate>>,
+}
+
+impl PendingStageCheckpoints {
+    const fn new(sender: StageCheckpointNotificationSender) -> Self {
+        Self { sender, updates: Mutex::new(Vec::new()) }
+    }
+
+    /// Records a saved checkpoint, replacing a previous checkpoint of the same stage.
+    fn record(&self, stage_id: StageId, checkpoint: StageCheckpoint) {
+        let mut updates = self.updates.lock();
+        match updates.iter_mut().find(|update| update.stage_id == stage_id) {
+            Some(update) => update.checkpoint = checkpoint,
+            None => updates.push(StageCheckpointUpdate { stage_id, checkpoint }),
+        }
+    }
+
+    /// Notifies the subscribers about the recorded checkpoints.
+    fn notify(self) {
+        for update in self.updates.into_inner() {
+            let _ = self.sender.send(update);
+        }
+    }
+}
+
 /// A provider struct that fetches data from the database.
 /// Wrapper around [`DbTx`] and [`DbTxMut`]. Example: [`HeaderProvider`] [`BlockHashReader`]
 #[derive(Debug)]
@@ -115,9 +161,28 @@ pub struct DatabaseProvider<TX> {
     static_file_provider: StaticFileProvider,
     /// Pruning configuration
     prune_modes: PruneModes,
+    /// The stage checkpoints saved in the transaction, notified once it's committed.
+    stage_checkpoints: Option<PendingStageCheckpoints>,
 }
 
 impl<TX> DatabaseProvider<TX> {
+    /// Notifies the subscribers of the sender about the stage checkpoints that are saved with this
+    /// provider, once its transaction is committed.
+    pub fn with_stage_checkpoint_notifications(
+        mut self,
+        sender: StageCheckpointNotificationSender,
+    ) -> Self {
+        self.stage_checkpoints = Some(PendingStageCheckpoints::new(sender));
+        self
+    }
+
+    /// Records a saved stage checkpoint, to notify it once the transaction is committed.
+    fn record_stage_checkpoint(&self, stage_id: StageId, checkpoint: StageCheckpoint) {
+        if let Some(stage_checkpoints) = &self.stage_checkpoints {
+            stage_checkpoints.record(stage_id, checkpoint);
+        }
+    }
+
     /// Returns a static file provider
     pub const fn static_file_provider(&self) -> &StaticFileProvider {
         &self.static_file_provider
@@ -137,7 +202,7 @@ impl<TX: DbTxMut> DatabaseProvider<TX> {
         static_file_provider: StaticFileProvider,
         prune_modes: PruneModes,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes }
+        Self { tx, chain_spec, static_file_provider, prune_modes, stage_checkpoints: None }
     }
 }
 
@@ -275,7 +340,7 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         static_file_provider: StaticFileProvider,
         prune_modes: PruneModes,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes }
+        Self { tx, chain_spec, static_file_provider, prune_modes, stage_checkpoints: None }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -925,8 +990,14 @@ impl<TX: DbTx> DatabaseProvider<TX> {
 
 impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
     /// Commit database transaction.
+    ///
+    /// The stage checkpoints saved in the transaction are notified once it's committed.
     pub fn commit(self) -> ProviderResult<bool> {
-        Ok(self.tx.commit()?)
+        let committed = self.tx.commit()?;
+        if let Some(stage_checkpoints) = self.stage_checkpoints {
+            stage_checkpoints.notify();
+        }
+        Ok(committed)
     }
 
     /// Remove the last N blocks of state.
@@ -1015,8 +1086,9 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
             }
         }
 
-        // iterate over block body and remove receipts
+        // iterate over block body and remove receipts and internal transfers
         self.remove::<tables::Receipts>(from_transaction_num..=to_transaction_num)?;
+        self.remove::<tables::InternalTransfers>(from_transaction_num..=to_transaction_num)?;
 
         Ok(())
     }
@@ -1116,8 +1188,13 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         // iterate over block body and create ExecutionResult
         let mut receipt_iter =
             self.take::<tables::Receipts>(from_transaction_num..=to_transaction_num)?.into_iter();
+        let internal_transfer_entries =
+            self.take::<tables::InternalTransfers>(from_transaction_num..=to_transaction_num)?;
+        let has_internal_transfers = !internal_transfer_entries.is_empty();
+        let mut internal_transfer_iter = internal_transfer_entries.into_iter().peekable();
 
         let mut receipts = Vec::new();
+        let mut internal_transfers = Vec::new();
         // loop break if we are at the end of the blocks.
         for (_, block_body) in block_bodies {
             let mut block_receipts = Vec::with_capacity(block_body.tx_count as usize);
@@ -1127,6 +1204,17 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
                 }
             }
             receipts.push(block_receipts);
+
+            if has_internal_transfers {
+                let mut block_internal_transfers = vec![Vec::new(); block_body.tx_count as usize];
+                while let Some((tx_number, entry)) = internal_transfer_iter
+                    .next_if(|(tx_number, _)| block_body.tx_num_range().contains(tx_number))
+                {
+                    block_internal_transfers[(tx_number - block_body.first_tx_num) as usize] =
+                        entry.transfers;
+                }
+                internal_transfers.push(block_internal_transfers);
+            }
         }
 
         Ok(ExecutionOutcome::new_init(
@@ -1136,7 +1224,8 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
             receipts.into(),
             start_block_number,
             Vec::new(),
-        ))
+        )
+        .with_internal_transfers(internal_transfers))
     }
 
     /// Remove list of entries from the table. Returns the number of entries removed.
@@ -1626,28 +1715,15 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         Ok(false)
     }
 
-    /// Load shard and remove it. If list is empty, last shard was full or
-    /// there are no shards at all.
-    fn take_shard<T>(&self, key: T::Key) -> ProviderResult<Vec<u64>>
-    where
-        T: Table<Value = BlockNumberList>,
-    {
-        let mut cursor = self.tx.cursor_read::<T>()?;
-        let shard = cursor.seek_exact(key)?;
-        if let Some((shard_key, list)) = shard {
-            // delete old shard so new one can be inserted.
-            self.tx.delete::<T>(shard_key, None)?;
-            let list = list.iter().collect::<Vec<_>>();
-            return Ok(list)
-        }
-        Ok(Vec::new())
-    }
-
     /// Insert history index to the database.
     ///
-    /// For each updated partial key, this function removes the last shard from
-    /// the database (if any), appends the new indices to it, chunks the resulting integer list and
-    /// inserts the new shards back into the database.
+    /// For each updated partial key, this function loads the last shard from the database (if
+    /// any), appends the new indices to it, chunks the resulting integer list and writes the new
+    /// shards back into the database, replacing the last shard.
+    ///
+    /// The updates are sorted, so they are written with a single cursor in key order. Partial keys
+    /// that come after the last key of the table don't have any shards yet, so their shards are
+    /// appended without looking up the last shard first.
     ///
     /// This function is used by history indexing stages.
     fn append_history_index<P, T>(
@@ -1659,8 +1735,26 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         P: Copy,
         T: Table<Value = BlockNumberList>,
     {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        let last_key = cursor.last()?.map(|(key, _)| key);
+        let mut append = last_key.is_none();
+
         for (partial_key, indices) in index_updates {
-            let last_shard = self.take_shard::<T>(sharded_key_factory(partial_key, u64::MAX))?;
+            // once a partial key is past the last key of the table, all following ones are too
+            append = append ||
+                last_key
+                    .as_ref()
+                    .is_some_and(|last| sharded_key_factory(partial_key, 0) > *last);
+
+            let last_shard = if append {
+                Vec::new()
+            } else {
+                cursor
+                    .seek_exact(sharded_key_factory(partial_key, u64::MAX))?
+                    .map(|(_, list)| list.iter().collect::<Vec<_>>())
+                    .unwrap_or_default()
+            };
+
             // chunk indices and insert them in shards of N size.
             let indices = last_shard.iter().chain(indices.iter());
             let chunks = indices
@@ -1677,10 +1771,14 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
                     // Insert last list with u64::MAX
                     u64::MAX
                 };
-                self.tx.put::<T>(
-                    sharded_key_factory(partial_key, highest_block_number),
-                    BlockNumberList::new_pre_sorted(list),
-                )?;
+                let key = sharded_key_factory(partial_key, highest_block_number);
+                let value = BlockNumberList::new_pre_sorted(list);
+                if append {
+                    cursor.append(key, value)?;
+                } else {
+                    // replaces the last shard, if any
+                    cursor.upsert(key, value)?;
+                }
             }
         }
         Ok(())
@@ -1737,6 +1835,76 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> AccountHistoryReader for DatabaseProvider<TX> {
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        if range.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        // the history of the pruned blocks is not available
+        if let Some(pruned) = self
+            .get_prune_checkpoint(PruneSegment::AccountHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+        {
+            if *range.start() <= pruned {
+                return Err(ProviderError::StateAtBlockPruned(*range.start()))
+            }
+        }
+
+        // Collect the blocks of the range that changed the account, and the first block after the
+        // range that changed it, from the shards that start with the first shard that includes a
+        // block of the range.
+        let mut changed_blocks = Vec::new();
+        let mut next_changed_block = None;
+        let mut history = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        'shards: for entry in history.walk(Some(ShardedKey::new(address, *range.start())))? {
+            let (key, blocks) = entry?;
+            if key.key != address {
+                break
+            }
+            for block_number in blocks.iter() {
+                if block_number > *range.end() {
+                    next_changed_block = Some(block_number);
+                    break 'shards
+                }
+                if block_number >= *range.start() {
+                    changed_blocks.push(block_number);
+                }
+            }
+        }
+
+        // The state after a block is the state before the next block that changed the account, or
+        // the plain state if no later block changed it.
+        let mut changesets = self.tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+        let next_changed_blocks =
+            changed_blocks.iter().skip(1).copied().map(Some).chain([next_changed_block]);
+        changed_blocks
+            .iter()
+            .zip(next_changed_blocks)
+            .map(|(&block_number, next_changed_block)| {
+                let account = match next_changed_block {
+                    Some(next_changed_block) => {
+                        changesets
+                            .seek_by_key_subkey(next_changed_block, address)?
+                            .filter(|acc| acc.address == address)
+                            .ok_or(ProviderError::AccountChangesetNotFound {
+                                block_number: next_changed_block,
+                                address,
+                            })?
+                            .info
+                    }
+                    None => self.basic_account(address)?,
+                };
+                Ok((block_number, account))
+            })
+            .collect()
+    }
+}
+
 impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
     fn account_block_changeset(
         &self,
@@ -1754,6 +1922,88 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> StorageChangeSetReader for DatabaseProvider<TX> {
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        let range = BlockNumberAddress::range(block_number..=block_number);
+        self.tx
+            .cursor_dup_read::<tables::StorageChangeSets>()?
+            .walk_range(range)?
+            .map(|result| -> ProviderResult<_> {
+                let (block_address, storage_entry) = result?;
+                Ok((block_address.address(), storage_entry))
+            })
+            .collect()
+    }
+}
+
+impl<TX: DbTx> StateReader for DatabaseProvider<TX> {
+    /// Reconstructs the [`ExecutionOutcome`] of the block from its changesets, receipts and
+    /// requests.
+    ///
+    /// The state before the block is taken from the changesets of the block, and the state after
+    /// the block from the history of the following blocks.
+    fn execution_outcome(&self, block: BlockNumber) -> ProviderResult<Option<ExecutionOutcome>> {
+        if self.block_body_indices(block)?.is_none() {
+            return Ok(None)
+        }
+
+        // the changesets of the block are required for the state before the block
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            if self
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .is_some_and(|pruned| block <= pruned)
+            {
+                return Err(ProviderError::StateAtBlockPruned(block))
+            }
+        }
+
+        // the state after the block is the state before the next block
+        let state_after =
+            HistoricalStateProviderRef::new(&self.tx, block + 1, self.static_file_provider.clone());
+
+        let mut state = BundleStateInit::new();
+        let mut reverts = HashMap::<Address, AccountRevertInit>::new();
+        for AccountBeforeTx { address, info } in self.account_block_changeset(block)? {
+            state.insert(address, (info, state_after.basic_account(address)?, HashMap::new()));
+            reverts.entry(address).or_default().0 = Some(info);
+        }
+        for (address, old_storage) in self.storage_block_changeset(block)? {
+            let account = match state.entry(address) {
+                hash_map::Entry::Vacant(entry) => {
+                    // the storage of the account changed, but not the account itself
+                    let info = state_after.basic_account(address)?;
+                    entry.insert((info, info, HashMap::new()))
+                }
+                hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            };
+            let new_value = state_after.storage(address, old_storage.key)?.unwrap_or_default();
+            account.2.insert(old_storage.key, (old_storage.value, new_value));
+            reverts.entry(address).or_default().1.push(old_storage);
+        }
+
+        let receipts = self
+            .receipts_by_block(block.into())?
+            .unwrap_or_default()
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let requests = self.tx.get::<tables::BlockRequests>(block)?.into_iter().collect();
+
+        Ok(Some(ExecutionOutcome::new_init(
+            state,
+            HashMap::from([(block, reverts)]),
+            Vec::new(),
+            vec![receipts].into(),
+            block,
+            requests,
+        )))
+    }
+}
+
 impl<TX: DbTx> HeaderSyncGapProvider for DatabaseProvider<TX> {
     fn sync_gap(
         &self,
@@ -2274,6 +2524,59 @@ impl<TX: DbTx> TransactionsProvider for DatabaseProvider<TX> {
         Ok(None)
     }
 
+    fn transactions_by_hashes(
+        &self,
+        hashes: &[TxHash],
+    ) -> ProviderResult<Vec<Option<(TransactionSigned, TransactionMeta)>>> {
+        // look up the hashes in the order of the table, so the cursor only moves forward
+        let mut sorted_hashes = hashes.iter().copied().enumerate().collect::<Vec<_>>();
+        sorted_hashes.sort_unstable_by_key(|(_, hash)| *hash);
+
+        let mut hash_cursor = self.tx.cursor_read::<tables::TransactionHashNumbers>()?;
+        let mut transaction_cursor = self.tx.cursor_read::<tables::TransactionBlocks>()?;
+
+        // the header, hash and first transaction number of every block that has been read, since
+        // batched transactions are often in the same blocks
+        let mut blocks = HashMap::new();
+
+        let mut transactions = vec![None; hashes.len()];
+        for (index, tx_hash) in sorted_hashes {
+            let Some((_, transaction_id)) = hash_cursor.seek_exact(tx_hash)? else { continue };
+            let Some(tx) = self.transaction_by_id_no_hash(transaction_id)? else { continue };
+            let Some((_, block_number)) = transaction_cursor.seek(transaction_id)? else {
+                continue
+            };
+
+            let (header, block_hash, first_tx_num) = match blocks.entry(block_number) {
+                hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                hash_map::Entry::Vacant(entry) => {
+                    let Some(sealed_header) = self.sealed_header(block_number)? else { continue };
+                    let Some(block_body) = self.block_body_indices(block_number)? else { continue };
+                    let (header, block_hash) = sealed_header.split();
+                    entry.insert((header, block_hash, block_body.first_tx_num()))
+                }
+            };
+
+            let meta = TransactionMeta {
+                tx_hash,
+                index: transaction_id - *first_tx_num,
+                block_hash: *block_hash,
+                block_number,
+                base_fee: header.base_fee_per_gas,
+                excess_blob_gas: header.excess_blob_gas,
+                timestamp: header.timestamp,
+            };
+            let transaction = TransactionSigned {
+                hash: tx_hash,
+                signature: tx.signature,
+                transaction: tx.transaction,
+            };
+            transactions[index] = Some((transaction, meta));
+        }
+
+        Ok(transactions)
+    }
+
     fn transaction_block(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>> {
         let mut cursor = self.tx.cursor_read::<tables::TransactionBlocks>()?;
         Ok(cursor.seek(id)?.map(|(_, bn)| bn))
@@ -2441,6 +2744,234 @@ impl<TX: DbTx> RequestsProvider for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> InternalTransfersProvider for DatabaseProvider<TX> {
+    fn internal_transfers_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<Vec<InternalTransfer>>>> {
+        let Some(number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+
+        // the transfers are written when the block is executed
+        if self
+            .get_stage_checkpoint(StageId::Execution)?
+            .map_or(true, |checkpoint| number > checkpoint.block_number)
+        {
+            return Ok(None)
+        }
+        if self
+            .get_prune_checkpoint(PruneSegment::InternalTransfers)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .is_some_and(|pruned| number <= pruned)
+        {
+            return Err(ProviderError::StateAtBlockPruned(number))
+        }
+
+        let Some(block_body) = self.block_body_indices(number)? else { return Ok(None) };
+
+        let mut internal_transfers = vec![Vec::new(); block_body.tx_count as usize];
+        let mut cursor = self.tx.cursor_read::<tables::InternalTransfers>()?;
+        let mut indexed = block_body.tx_count == 0;
+        for entry in cursor.walk_range(block_body.tx_num_range())? {
+            let (tx_number, entry) = entry?;
+            indexed |= tx_number == block_body.last_tx_num();
+            internal_transfers[(tx_number - block_body.first_tx_num) as usize] = entry.transfers;
+        }
+
+        // the last transaction of an indexed block always has an entry
+        if !indexed {
+            return Err(ProviderError::InternalTransfersNotIndexed(number))
+        }
+        Ok(Some(internal_transfers))
+    }
+}
+
+impl<TX: DbTx> StateDiffProvider for DatabaseProvider<TX> {
+    fn state_diff_by_block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<StateDiff>> {
+        let Some(number) = self.convert_hash_or_number(id)? else { return Ok(None) };
+
+        // the changesets are pruned together with the history
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            if self
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .is_some_and(|pruned| number <= pruned)
+            {
+                return Err(ProviderError::StateAtBlockPruned(number))
+            }
+        }
+
+        // the changesets of the block contain the values before the block, and the state at the
+        // start of the next block the values after it
+        let after = HistoricalStateProviderRef::new(
+            &self.tx,
+            number + 1,
+            self.static_file_provider.clone(),
+        );
+
+        let accounts_before = self
+            .account_block_changeset(number)?
+            .into_iter()
+            .map(|AccountBeforeTx { address, info }| (address, info))
+            .collect::<BTreeMap<_, _>>();
+        let mut storage = BTreeMap::<Address, BTreeMap<B256, Delta<U256>>>::new();
+        for (address, entry) in self.storage_block_changeset(number)? {
+            let value = after.storage(address, entry.key)?.unwrap_or_default();
+            if let Some(delta) = Delta::changed(entry.value, value) {
+                storage.entry(address).or_default().insert(entry.key, delta);
+            }
+        }
+
+        let addresses =
+            accounts_before.keys().chain(storage.keys()).copied().collect::<BTreeSet<_>>();
+        let mut diff = StateDiff::default();
+        for address in addresses {
+            let account_after = after.basic_account(address)?;
+            // accounts that only changed their storage are not part of the account changeset
+            let account_before = accounts_before.get(&address).copied().unwrap_or(account_after);
+            let storage = storage.remove(&address).unwrap_or_default();
+            if account_before == account_after && storage.is_empty() {
+                continue
+            }
+
+            let account = AccountDiff::new(account_before, account_after, storage, |hash| {
+                self.tx.get::<tables::Bytecodes>(hash)
+            })?;
+            diff.accounts.insert(address, account);
+        }
+        Ok(Some(diff))
+    }
+}
+
+impl<TX: DbTx> LogIndexProvider for DatabaseProvider<TX> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(None)
+        };
+
+        // blocks up to the prune checkpoint are no longer indexed
+        let start = self
+            .get_prune_checkpoint(PruneSegment::LogIndex)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+        Ok((start <= checkpoint.block_number).then_some(start..=checkpoint.block_number))
+    }
+
+    fn blocks_with_log_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.log_index_blocks::<tables::LogAddressHistory, _>(address, range)
+    }
+
+    fn blocks_with_log_topic(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.log_index_blocks::<tables::LogTopicHistory, _>(topic, range)
+    }
+}
+
+impl<TX: DbTx> LogsProvider for DatabaseProvider<TX> {}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns the numbers of the blocks in the range from the shards of the key in the log index
+    /// table.
+    fn log_index_blocks<T, K>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Clone + PartialEq,
+    {
+        let (start, end) = range.into_inner();
+        let mut blocks = Vec::new();
+
+        // the first shard with blocks in the range is the first one whose highest block number is
+        // not below the start of the range
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        for entry in cursor.walk(Some(ShardedKey::new(key.clone(), start)))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != key {
+                break
+            }
+
+            blocks.extend(
+                list.iter().skip_while(|block| *block < start).take_while(|block| *block <= end),
+            );
+            if sharded_key.highest_block_number >= end {
+                break
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Returns the addresses and topics of the logs of the blocks in the range, read from their
+    /// receipts.
+    pub fn log_index_keys_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<LogIndexKeys> {
+        let mut keys = LogIndexKeys::default();
+        for number in range {
+            let Some(body) = self.block_body_indices(number)? else { continue };
+            let receipts = self.receipts_by_tx_range(body.tx_num_range())?;
+            keys.insert_block(number, &receipts);
+        }
+        Ok(keys)
+    }
+
+    /// Returns the receipts of each block in the range, up to the first block without body
+    /// indices.
+    pub fn receipts_by_block_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<Receipt>>> {
+        let range = to_range(range);
+        let mut receipts = Vec::with_capacity(range_size_hint(&range).unwrap_or(0).min(1_000));
+        for number in range {
+            let Some(body) = self.block_body_indices(number)? else { break };
+            receipts.push(self.receipts_by_tx_range(body.tx_num_range())?);
+        }
+        Ok(receipts)
+    }
+
+    /// Returns an iterator over the headers of the range, which reads them lazily from the static
+    /// files and the database.
+    pub fn headers_iter(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<Header>> + '_ {
+        RangeIter::new(self, to_range(range), |provider, chunk| provider.headers_range(chunk))
+    }
+
+    /// Returns an iterator over the receipts of each block of the range, which reads them lazily
+    /// from the static files and the database.
+    pub fn receipts_by_block_iter(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<Vec<Receipt>>> + '_ {
+        RangeIter::new(self, to_range(range), |provider, chunk| {
+            provider.receipts_by_block_range(chunk)
+        })
+    }
+
+    /// Returns an iterator over the transactions of the transaction range, which reads them lazily
+    /// from the static files and the database.
+    pub fn transactions_by_tx_range_iter(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> impl Iterator<Item = ProviderResult<TransactionSignedNoHash>> + '_ {
+        RangeIter::new(self, to_range(range), |provider, chunk| {
+            provider.transactions_by_tx_range(chunk)
+        })
+    }
+}
+
 impl<TX: DbTx> EvmEnvProvider for DatabaseProvider<TX> {
     fn fill_env_at<EvmConfig>(
         &self,
@@ -2537,7 +3068,9 @@ impl<TX: DbTxMut> StageCheckpointWriter for DatabaseProvider<TX> {
         id: StageId,
         checkpoint: StageCheckpoint,
     ) -> ProviderResult<()> {
-        Ok(self.tx.put::<tables::StageCheckpoints>(id.to_string(), checkpoint)?)
+        self.tx.put::<tables::StageCheckpoints>(id.to_string(), checkpoint)?;
+        self.record_stage_checkpoint(id, checkpoint);
+        Ok(())
     }
 
     /// Save stage checkpoint progress.
@@ -2558,13 +3091,12 @@ impl<TX: DbTxMut> StageCheckpointWriter for DatabaseProvider<TX> {
         let mut cursor = self.tx.cursor_write::<tables::StageCheckpoints>()?;
         for stage_id in StageId::ALL {
             let (_, checkpoint) = cursor.seek_exact(stage_id.to_string())?.unwrap_or_default();
-            cursor.upsert(
-                stage_id.to_string(),
-                StageCheckpoint {
-                    block_number,
-                    ..if drop_stage_checkpoint { Default::default() } else { checkpoint }
-                },
-            )?;
+            let checkpoint = StageCheckpoint {
+                block_number,
+                ..if drop_stage_checkpoint { Default::default() } else { checkpoint }
+            };
+            cursor.upsert(stage_id.to_string(), checkpoint)?;
+            self.record_stage_checkpoint(stage_id, checkpoint);
         }
 
         Ok(())
@@ -2764,6 +3296,33 @@ impl<TX: DbTxMut + DbTx> StateChangeWriter for DatabaseProvider<TX> {
         Ok(())
     }
 
+    fn write_internal_transfers(
+        &self,
+        internal_transfers: Vec<Vec<Vec<InternalTransfer>>>,
+        first_block: BlockNumber,
+    ) -> ProviderResult<()> {
+        let mut cursor = self.tx_ref().cursor_write::<tables::InternalTransfers>()?;
+        for (block_index, block_internal_transfers) in internal_transfers.into_iter().enumerate() {
+            let block_number = first_block + block_index as BlockNumber;
+            let block_body = self
+                .block_body_indices(block_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+
+            // the entry of the last transaction is written even if it's empty, to mark the block
+            // as indexed
+            let last_tx_num = block_body.last_tx_num();
+            for (tx_number, transfers) in block_body
+                .tx_num_range()
+                .zip(block_internal_transfers)
+                .filter(|(tx_number, t)| !t.is_empty() || *tx_number == last_tx_num)
+            {
+                cursor.upsert(tx_number, StoredInternalTransfers { transfers })?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn write_hashed_state(&self, hashed_state: &HashedPostStateSorted) -> ProviderResult<()> {
         // Write hashed account updates.
         let mut hashed_accounts_cursor = self.tx_ref().cursor_write::<tables::HashedAccounts>()?;
@@ -3212,6 +3771,106 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTxMut + DbTx> LogIndexWriter for DatabaseProvider<TX> {
+    fn unwind_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let first_block = *range.start();
+        let LogIndexKeys { addresses, topics } = self.log_index_keys_with_range(range)?;
+
+        let unwound = self.unwind_log_index_shards::<tables::LogAddressHistory, _>(
+            addresses.into_keys(),
+            first_block,
+        )? + self.unwind_log_index_shards::<tables::LogTopicHistory, _>(
+            topics.into_keys(),
+            first_block,
+        )?;
+        Ok(unwound)
+    }
+
+    fn insert_log_indices(&self, keys: LogIndexKeys) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogAddressHistory>(keys.addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicHistory>(keys.topics, ShardedKey::new)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
+    /// Removes the blocks from `first_block` on from the shards of the keys in the log index
+    /// table.
+    fn unwind_log_index_shards<T, K>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        first_block: BlockNumber,
+    ) -> ProviderResult<usize>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Clone + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        let mut unwound = 0;
+        for key in keys {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key.clone()),
+                first_block,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // the remaining blocks of the last shard are reinserted
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+            unwound += 1;
+        }
+        Ok(unwound)
+    }
+
+    /// Inserts the log indices of blocks that are appended to the canonical chain, if the log
+    /// index is maintained and covers all blocks before them.
+    ///
+    /// The log index is maintained once it was built by the [`StageId::IndexLogs`] stage, whose
+    /// checkpoint is advanced to the last appended block. If the stage is behind, the blocks are
+    /// left to it.
+    pub(crate) fn append_maintained_log_indices(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        keys: impl FnOnce() -> LogIndexKeys,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number + 1 != *range.start() {
+            return Ok(())
+        }
+
+        self.insert_log_indices(keys())?;
+        self.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(*range.end()))
+    }
+
+    /// Unwinds the log indices of the blocks in the range, if the log index is maintained, and
+    /// moves the checkpoint of the [`StageId::IndexLogs`] stage below the range.
+    ///
+    /// Must be called before the receipts of the blocks are removed.
+    pub(crate) fn unwind_maintained_log_indices(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(())
+        };
+        if checkpoint.block_number < *range.start() {
+            return Ok(())
+        }
+
+        self.unwind_log_indices(*range.start()..=checkpoint.block_number.min(*range.end()))?;
+        self.save_stage_checkpoint(
+            StageId::IndexLogs,
+            StageCheckpoint::new(range.start().saturating_sub(1)),
+        )
+    }
+}
+
 impl<TX: DbTx> BlockExecutionReader for DatabaseProvider<TX> {
     fn get_block_and_execution_range(
         &self,
@@ -3264,6 +3923,9 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
         // Unwind storage history indices.
         self.unwind_storage_history_indices(storage_range)?;
 
+        // Unwind log indices, while the receipts of the blocks still exist.
+        self.unwind_maintained_log_indices(range.clone())?;
+
         // Calculate the reverted merkle root.
         // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
         // are pre-loaded.
@@ -3352,6 +4014,9 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
         // Unwind storage history indices.
         self.unwind_storage_history_indices(storage_range)?;
 
+        // Unwind log indices, while the receipts of the blocks still exist.
+        self.unwind_maintained_log_indices(range.clone())?;
+
         // Calculate the reverted merkle root.
         // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
         // are pre-loaded.
@@ -3485,6 +4150,9 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
 
         for (transaction, sender) in block.block.body.into_iter().zip(block.senders.iter()) {
             let hash = transaction.hash();
+            let is_lookup_retained = transaction
+                .to()
+                .is_some_and(|to| self.prune_modes.transaction_lookup_allowlist.contains(&to));
 
             if self
                 .prune_modes
@@ -3517,7 +4185,8 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
                 .prune_modes
                 .transaction_lookup
                 .filter(|prune_mode| prune_mode.is_full())
-                .is_none()
+                .is_none() ||
+                is_lookup_retained
             {
                 let start = Instant::now();
                 self.tx.put::<tables::TransactionHashNumbers>(hash, next_tx_num)?;
@@ -3596,6 +4265,18 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
             durations_recorder.record_relative(metrics::Action::InsertBlock);
         }
 
+        // Index the logs of the blocks, if the log index is maintained.
+        self.append_maintained_log_indices(first_number..=last_block_number, || {
+            let mut keys = LogIndexKeys::default();
+            for number in first_number..=last_block_number {
+                keys.insert_block(
+                    number,
+                    execution_outcome.receipts_by_block(number).iter().flatten(),
+                );
+            }
+            keys
+        })?;
+
         // Write state and changesets to the database.
         // Must be written after blocks because of the receipt lookup.
         // TODO: should _these_ be moved to storagewriter? seems like storagewriter should be
@@ -3664,15 +4345,7 @@ impl<TX: DbTx> StatsReader for DatabaseProvider<TX> {
 
 impl<TX: DbTx> FinalizedBlockReader for DatabaseProvider<TX> {
     fn last_finalized_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
-        let mut finalized_blocks = self
-            .tx
-            .cursor_read::<tables::ChainState>()?
-            .walk(Some(tables::ChainStateKey::LastFinalizedBlock))?
-            .take(1)
-            .collect::<Result<BTreeMap<tables::ChainStateKey, BlockNumber>, _>>()?;
-
-        let last_finalized_block_number = finalized_blocks.pop_first().map(|pair| pair.1);
-        Ok(last_finalized_block_number)
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock)?)
     }
 }
 
@@ -3684,6 +4357,220 @@ impl<TX: DbTxMut> FinalizedBlockWriter for DatabaseProvider<TX> {
     }
 }
 
+/// The execution outcome and trie updates of a fork block, as they are stored in
+/// [`tables::ForkBlockExecutions`].
+#[derive(Serialize, Deserialize)]
+struct StoredForkBlockExecution {
+    bundle: BundleState,
+    receipts: Receipts,
+    /// The compact-encoded EIP-7685 requests of the block, since their serde representation can't
+    /// be decoded with bincode.
+    requests: Vec<Vec<u8>>,
+    internal_transfers: Vec<Vec<Vec<InternalTransfer>>>,
+    trie: TrieUpdates,
+}
+
+impl<TX: DbTx> ForkBlockReader for DatabaseProvider<TX> {
+    fn fork_block(&self, hash: BlockHash) -> ProviderResult<Option<ExecutedBlock>> {
+        let Some(block) = self
+            .tx
+            .get::<tables::ForkBlocks>(hash)?
+            .and_then(|block| block.into_sealed_block_with_senders(hash))
+        else {
+            return Ok(None)
+        };
+        // fork blocks that were stored without their execution have to be executed again
+        let Some(execution) = self.tx.get::<tables::ForkBlockExecutions>(hash)? else {
+            return Ok(None)
+        };
+
+        let execution = bincode::deserialize::<StoredForkBlockExecution>(&execution)
+            .map_err(|_| DatabaseError::Decode)?;
+        let requests = execution
+            .requests
+            .iter()
+            .map(|requests| Requests::from_compact(requests, requests.len()).0)
+            .collect();
+        let execution_output =
+            ExecutionOutcome::new(execution.bundle, execution.receipts, block.number, requests)
+                .with_internal_transfers(execution.internal_transfers);
+        let hashed_state = HashedPostState::from_bundle_state(&execution_output.bundle.state);
+
+        let SealedBlockWithSenders { block, senders } = block;
+        Ok(Some(ExecutedBlock::new(
+            Arc::new(block),
+            Arc::new(senders),
+            Arc::new(execution_output),
+            Arc::new(hashed_state),
+            Arc::new(execution.trie),
+        )))
+    }
+}
+
+impl<TX: DbTxMut + DbTx> ForkBlockWriter for DatabaseProvider<TX> {
+    fn insert_fork_blocks(
+        &self,
+        blocks: impl IntoIterator<Item = ExecutedBlock>,
+    ) -> ProviderResult<()> {
+        for block in blocks {
+            let hash = block.block.hash();
+            let execution = StoredForkBlockExecution {
+                bundle: block.execution_output.bundle.clone(),
+                receipts: block.execution_output.receipts.clone(),
+                requests: block
+                    .execution_output
+                    .requests
+                    .iter()
+                    .map(|requests| {
+                        let mut buf = Vec::new();
+                        requests.to_compact(&mut buf);
+                        buf
+                    })
+                    .collect(),
+                internal_transfers: block.execution_output.internal_transfers.clone(),
+                trie: block.trie.as_ref().clone(),
+            };
+            let execution = bincode::serialize(&execution)
+                .map_err(|err| DatabaseError::Other(err.to_string()))?;
+
+            let block = SealedBlockWithSenders {
+                block: block.block.as_ref().clone(),
+                senders: block.senders.as_ref().clone(),
+            };
+            self.tx.put::<tables::ForkBlocks>(hash, block.into())?;
+            self.tx.put::<tables::ForkBlockExecutions>(hash, execution)?;
+        }
+        Ok(())
+    }
+
+    fn remove_fork_blocks_below(&self, block_number: BlockNumber) -> ProviderResult<usize> {
+        // fork blocks are keyed by hash, but only a few blocks near the tip are kept, so the whole
+        // table is walked
+        let mut removed = 0;
+        let mut cursor = self.tx.cursor_write::<tables::ForkBlocks>()?;
+        let mut walker = cursor.walk(None)?;
+        while let Some((hash, block)) = walker.next().transpose()? {
+            if block.header.number < block_number {
+                walker.delete_current()?;
+                self.tx.delete::<tables::ForkBlockExecutions>(hash, None)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+impl<TX: DbTx> ReorgJournalReader for DatabaseProvider<TX> {
+    fn latest_reorgs(&self, limit: usize) -> ProviderResult<Vec<(u64, ReorgRecord)>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::ReorgJournal>()?
+            .walk_back(None)?
+            .take(limit)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> ReorgJournalWriter for DatabaseProvider<TX> {
+    fn append_reorg(&self, reorg: ReorgRecord) -> ProviderResult<u64> {
+        let mut cursor = self.tx.cursor_write::<tables::ReorgJournal>()?;
+        let sequence = cursor.last()?.map_or(0, |(sequence, _)| sequence + 1);
+        cursor.append(sequence, reorg)?;
+        Ok(sequence)
+    }
+
+    fn update_reorg(&self, sequence: u64, reorg: ReorgRecord) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::ReorgJournal>(sequence, reorg)?)
+    }
+
+    fn prune_reorgs(&self, max_reorgs: u64) -> ProviderResult<usize> {
+        let mut cursor = self.tx.cursor_write::<tables::ReorgJournal>()?;
+        // sequence numbers are contiguous, so the reorgs below the cutoff are the oldest ones
+        let Some((last, _)) = cursor.last()? else { return Ok(0) };
+        let cutoff = (last + 1).saturating_sub(max_reorgs);
+
+        let mut removed = 0;
+        let mut walker = cursor.walk_range(..cutoff)?;
+        while walker.next().transpose()?.is_some() {
+            walker.delete_current()?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+impl<TX: DbTx> UnwindJournalReader for DatabaseProvider<TX> {
+    fn unwind_journal_target(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::UnwindTarget)?)
+    }
+}
+
+impl<TX: DbTxMut> UnwindJournalWriter for DatabaseProvider<TX> {
+    fn save_unwind_journal_target(&self, target: BlockNumber) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::ChainState>(tables::ChainStateKey::UnwindTarget, target)?)
+    }
+
+    fn clear_unwind_journal(&self) -> ProviderResult<()> {
+        self.tx.delete::<tables::ChainState>(tables::ChainStateKey::UnwindTarget, None)?;
+        Ok(())
+    }
+}
+
+impl<TX: DbTx> UnwindPreviewProvider for DatabaseProvider<TX> {
+    fn unwind_preview(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<UnwindPreview> {
+        let blocks = *range.start()..range.end() + 1;
+        let transactions = match (
+            self.block_body_indices(*range.start())?,
+            self.block_body_indices(*range.end())?,
+        ) {
+            (Some(first), Some(last)) => first.first_tx_num..last.next_tx_num(),
+            _ => 0..0,
+        };
+
+        let tables = vec![
+            self.table_range_preview::<tables::CanonicalHeaders>(blocks.clone())?,
+            self.table_range_preview::<tables::Headers>(blocks.clone())?,
+            self.table_range_preview::<tables::HeaderTerminalDifficulties>(blocks.clone())?,
+            self.table_range_preview::<tables::BlockBodyIndices>(blocks.clone())?,
+            self.table_range_preview::<tables::BlockOmmers>(blocks.clone())?,
+            self.table_range_preview::<tables::BlockWithdrawals>(blocks.clone())?,
+            self.table_range_preview::<tables::BlockRequests>(blocks.clone())?,
+            self.table_range_preview::<tables::Transactions>(transactions.clone())?,
+            self.table_range_preview::<tables::TransactionBlocks>(transactions.clone())?,
+            self.table_range_preview::<tables::TransactionSenders>(transactions.clone())?,
+            self.table_range_preview::<tables::Receipts>(transactions.clone())?,
+            self.table_range_preview::<tables::InternalTransfers>(transactions.clone())?,
+            self.table_range_preview::<tables::AccountChangeSets>(blocks)?,
+            self.table_range_preview::<tables::StorageChangeSets>(BlockNumberAddress::range(
+                range.clone(),
+            ))?,
+        ];
+
+        Ok(UnwindPreview {
+            range,
+            transactions: transactions.end - transactions.start,
+            tables: tables.into_iter().filter(|table| table.entries > 0).collect(),
+        })
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Counts the entries of the table in the given key range and their size in the database.
+    fn table_range_preview<T: Table>(
+        &self,
+        range: Range<T::Key>,
+    ) -> ProviderResult<UnwindTablePreview> {
+        let mut preview = UnwindTablePreview { table: T::NAME, entries: 0, bytes: 0 };
+        let mut cursor = self.tx.cursor_read::<RawTable<T>>()?;
+        for entry in cursor.walk_range(RawKey::new(range.start)..RawKey::new(range.end))? {
+            let (key, value) = entry?;
+            preview.entries += 1;
+            preview.bytes += (key.raw_key().len() + value.raw_value().len()) as u64;
+        }
+        Ok(preview)
+    }
+}
+
 /// Helper method to recover senders for any blocks in the db which do not have senders. This
 /// compares the length of the input senders [`Vec`], with the length of given transactions [`Vec`],
 /// and will add to the input senders vec if there are more transactions.
@@ -1,11 +1,14 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, FinalizedBlockReader, FullExecutionDataProvider, HeaderProvider, ProviderError,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    AccountHistoryReader, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockSource, BlockchainTreePendingStateProvider,
+    CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider, FinalizedBlockReader,
+    FullExecutionDataProvider, HeaderProvider, InternalTransfersProvider, LogIndexProvider,
+    LogsProvider, ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    ReorgJournalReader, RequestsProvider, StageCheckpointNotifications, StageCheckpointReader,
+    StageCheckpointSubscriptions, StateDiffProvider, StateProviderBox, StateProviderFactory,
+    StateReader, StaticFileProviderFactory, StorageChangeSetReader, TransactionVariant,
+    TransactionsProvider, TreeViewer, WithdrawalsProvider,
 };
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -19,11 +22,12 @@ use reth_db_api::{
     models::{AccountBeforeTx, StoredBlockBodyIndices},
 };
 use reth_evm::ConfigureEvmEnv;
+use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
     BlockNumberOrTag, BlockWithSenders, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    SealedHeader, StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
+    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -42,8 +46,9 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
-    StaticFileProviderRWRefMut, StaticFileWriter,
+    ColdStaticFiles, FsObjectStore, ObjectStore, StaticFileAccess, StaticFileJarProvider,
+    StaticFileProvider, StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWriter,
+    DEFAULT_MAX_COLD_CACHE_SIZE,
 };
 
 mod state;
@@ -62,6 +67,18 @@ pub use consistent_view::{ConsistentDbView, ConsistentViewError};
 mod blockchain_provider;
 pub use blockchain_provider::BlockchainProvider2;
 
+mod consistent;
+pub use consistent::ConsistentProvider;
+
+mod range_iter;
+use range_iter::RangeIter;
+
+mod cached;
+pub use cached::{
+    CachedBlockchainProvider, ProviderCacheConfig, DEFAULT_MAX_CACHED_BLOCKS,
+    DEFAULT_MAX_CACHED_HEADERS, DEFAULT_MAX_CACHED_RECEIPTS,
+};
+
 /// The main type for interacting with the blockchain.
 ///
 /// This type serves as the main entry point for interacting with the blockchain and provides data
@@ -407,6 +424,13 @@ where
         self.database.transaction_by_hash_with_meta(tx_hash)
     }
 
+    fn transactions_by_hashes(
+        &self,
+        hashes: &[TxHash],
+    ) -> ProviderResult<Vec<Option<(TransactionSigned, TransactionMeta)>>> {
+        self.database.transactions_by_hashes(hashes)
+    }
+
     fn transaction_block(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>> {
         self.database.transaction_block(id)
     }
@@ -525,6 +549,69 @@ where
     }
 }
 
+impl<DB> InternalTransfersProvider for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn internal_transfers_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<Vec<reth_primitives::InternalTransfer>>>> {
+        self.database.internal_transfers_by_block(id)
+    }
+}
+
+impl<DB> StateDiffProvider for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn state_diff_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<reth_execution_types::StateDiff>> {
+        self.database.state_diff_by_block(id)
+    }
+}
+
+impl<DB> ReorgJournalReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn latest_reorgs(
+        &self,
+        limit: usize,
+    ) -> ProviderResult<Vec<(u64, reth_primitives::ReorgRecord)>> {
+        self.database.latest_reorgs(limit)
+    }
+}
+
+impl<DB> LogIndexProvider for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.log_index_range()
+    }
+
+    fn blocks_with_log_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.blocks_with_log_address(address, range)
+    }
+
+    fn blocks_with_log_topic(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.blocks_with_log_topic(topic, range)
+    }
+}
+
+impl<DB> LogsProvider for BlockchainProvider<DB> where DB: Database {}
+
 impl<DB> StageCheckpointReader for BlockchainProvider<DB>
 where
     DB: Database,
@@ -819,6 +906,14 @@ where
         self.chain_info.last_forkchoice_update_received_at()
     }
 
+    fn on_forkchoice_update_acknowledged(&self) {
+        self.chain_info.on_forkchoice_update_acknowledged();
+    }
+
+    fn on_new_payload_received(&self, timestamp: u64) {
+        self.chain_info.on_new_payload_received(timestamp);
+    }
+
     fn on_transition_configuration_exchanged(&self) {
         self.chain_info.on_transition_configuration_exchanged();
     }
@@ -955,6 +1050,15 @@ where
     }
 }
 
+impl<DB> StageCheckpointSubscriptions for BlockchainProvider<DB>
+where
+    DB: Send + Sync,
+{
+    fn subscribe_to_stage_checkpoints(&self) -> StageCheckpointNotifications {
+        self.database.subscribe_to_stage_checkpoints()
+    }
+}
+
 impl<DB> ChangeSetReader for BlockchainProvider<DB>
 where
     DB: Database,
@@ -967,6 +1071,40 @@ where
     }
 }
 
+impl<DB> AccountHistoryReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        self.database.provider()?.account_history(address, range)
+    }
+}
+
+impl<DB> StorageChangeSetReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        self.database.provider()?.storage_block_changeset(block_number)
+    }
+}
+
+impl<DB> StateReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn execution_outcome(&self, block: BlockNumber) -> ProviderResult<Option<ExecutionOutcome>> {
+        self.database.provider()?.execution_outcome(block)
+    }
+}
+
 impl<DB> AccountReader for BlockchainProvider<DB>
 where
     DB: Database + Sync + Send,
@@ -0,0 +1,133 @@
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::Range;
+
+/// The default number of blocks or transactions that a [`RangeIter`] reads at once.
+const DEFAULT_RANGE_ITER_CHUNK_SIZE: u64 = 1_000;
+
+/// An iterator over the items of a block or transaction range, that reads them lazily from the
+/// provider in chunks, instead of collecting the whole range into memory.
+///
+/// The range is read in order, and the iteration ends at the first chunk that has fewer items
+/// than requested, because the range extends beyond the available data. An error is returned
+/// once, after which the iteration ends.
+pub(crate) struct RangeIter<P, T, F> {
+    /// The provider that the chunks are read from.
+    provider: P,
+    /// The part of the range that hasn't been read yet.
+    range: Range<u64>,
+    /// The number of blocks or transactions to read at once.
+    chunk_size: u64,
+    /// Reads the items of a chunk of the range.
+    read_chunk: F,
+    /// The items of the current chunk that haven't been yielded yet.
+    items: std::vec::IntoIter<T>,
+}
+
+impl<P, T, F> RangeIter<P, T, F>
+where
+    F: FnMut(&P, Range<u64>) -> ProviderResult<Vec<T>>,
+{
+    /// Creates a new iterator over the range that reads [`DEFAULT_RANGE_ITER_CHUNK_SIZE`] items at
+    /// once.
+    pub(crate) fn new(provider: P, range: Range<u64>, read_chunk: F) -> Self {
+        Self {
+            provider,
+            range,
+            chunk_size: DEFAULT_RANGE_ITER_CHUNK_SIZE,
+            read_chunk,
+            items: Vec::new().into_iter(),
+        }
+    }
+
+    /// Sets the number of blocks or transactions that are read at once.
+    pub(crate) const fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+}
+
+impl<P, T, F> Iterator for RangeIter<P, T, F>
+where
+    F: FnMut(&P, Range<u64>) -> ProviderResult<Vec<T>>,
+{
+    type Item = ProviderResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.next() {
+                return Some(Ok(item))
+            }
+            if self.range.is_empty() {
+                return None
+            }
+
+            let chunk_end = self.range.start.saturating_add(self.chunk_size).min(self.range.end);
+            let chunk = self.range.start..chunk_end;
+            let items = match (self.read_chunk)(&self.provider, chunk.clone()) {
+                Ok(items) => items,
+                Err(err) => {
+                    self.range.start = self.range.end;
+                    return Some(Err(err))
+                }
+            };
+
+            self.range.start = if (items.len() as u64) < chunk.end - chunk.start {
+                self.range.end
+            } else {
+                chunk.end
+            };
+            self.items = items.into_iter();
+        }
+    }
+}
+
+impl<P, T, F> std::fmt::Debug for RangeIter<P, T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RangeIter")
+            .field("range", &self.range)
+            .field("chunk_size", &self.chunk_size)
+            .field("items", &self.items.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_storage_errors::provider::ProviderError;
+
+    #[test]
+    fn reads_range_in_chunks() {
+        let mut chunks = Vec::new();
+        let iter = RangeIter {
+            chunk_size: 3,
+            ..RangeIter::new((), 2..10, |_: &(), chunk| {
+                chunks.push(chunk.clone());
+                Ok(chunk.collect::<Vec<_>>())
+            })
+        };
+        assert_eq!(iter.collect::<ProviderResult<Vec<_>>>().unwrap(), Vec::from_iter(2..10));
+        assert_eq!(chunks, vec![2..5, 5..8, 8..10]);
+    }
+
+    #[test]
+    fn ends_at_partial_chunk() {
+        // the data ends at 6, the unbounded range is not read beyond it
+        let iter = RangeIter {
+            chunk_size: 4,
+            ..RangeIter::new((), 0..u64::MAX, |_: &(), chunk| {
+                Ok(chunk.take_while(|number| *number < 6).collect::<Vec<_>>())
+            })
+        };
+        assert_eq!(iter.collect::<ProviderResult<Vec<_>>>().unwrap(), Vec::from_iter(0..6));
+    }
+
+    #[test]
+    fn ends_after_error() {
+        let mut iter = RangeIter::new((), 0..10, |_: &(), _| {
+            Err::<Vec<u64>, _>(ProviderError::UnsupportedProvider)
+        });
+        assert!(matches!(iter.next(), Some(Err(ProviderError::UnsupportedProvider))));
+        assert!(iter.next().is_none());
+    }
+}
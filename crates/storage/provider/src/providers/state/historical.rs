@@ -13,15 +13,15 @@ use reth_primitives::{
     constants::EPOCH_SLOTS, Account, Address, BlockNumber, Bytecode, Bytes, StaticFileSegment,
     StorageKey, StorageValue, B256,
 };
-use reth_storage_api::StateProofProvider;
+use reth_storage_api::{StateProofProvider, StorageRangeProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, proof::Proof, updates::TrieUpdates, witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, StateRoot, StorageRoot,
+    AccountProof, HashedPostState, HashedStorage, StateRoot, StorageRange, StorageRoot,
 };
 use reth_trie_db::{
-    DatabaseHashedPostState, DatabaseProof, DatabaseStateRoot, DatabaseStorageRoot,
-    DatabaseTrieWitness,
+    DatabaseHashedPostState, DatabaseProof, DatabaseStateRoot, DatabaseStorageRange,
+    DatabaseStorageRoot, DatabaseTrieWitness,
 };
 use std::{collections::HashMap, fmt::Debug};
 
@@ -85,7 +85,8 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
 
         // history key to search IntegerList of block number changesets.
         let history_key = ShardedKey::new(address, self.block_number);
-        self.history_info::<tables::AccountsHistory, _>(
+        self.history_info(
+            &mut self.tx.cursor_read::<tables::AccountsHistory>()?,
             history_key,
             |key| key.key == address,
             self.lowest_available_blocks.account_history_block_number,
@@ -104,7 +105,8 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
 
         // history key to search IntegerList of block number changesets.
         let history_key = StorageShardedKey::new(address, storage_key, self.block_number);
-        self.history_info::<tables::StoragesHistory, _>(
+        self.history_info(
+            &mut self.tx.cursor_read::<tables::StoragesHistory>()?,
             history_key,
             |key| key.address == address && key.sharded_key.key == storage_key,
             self.lowest_available_blocks.storage_history_block_number,
@@ -142,6 +144,7 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
 
     fn history_info<T, K>(
         &self,
+        cursor: &mut impl DbCursorRO<T>,
         key: K,
         key_filter: impl Fn(&K) -> bool,
         lowest_available_block_number: Option<BlockNumber>,
@@ -149,8 +152,6 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
     where
         T: Table<Key = K, Value = BlockNumberList>,
     {
-        let mut cursor = self.tx.cursor_read::<T>()?;
-
         // Lookup the history chunk in the history index. If they key does not appear in the
         // index, the first chunk for the next key will be returned so we filter out chunks that
         // have a different key.
@@ -207,22 +208,7 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
 impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
-        match self.account_history_lookup(address)? {
-            HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
-                .ok_or(ProviderError::AccountChangesetNotFound {
-                    block_number: changeset_block_number,
-                    address,
-                })?
-                .info),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
-                Ok(self.tx.get::<tables::PlainAccountState>(address)?)
-            }
-        }
+        Ok(self.basic_accounts(&[address])?.pop().flatten())
     }
 }
 
@@ -349,6 +335,20 @@ impl<'b, TX: DbTx> StateProofProvider for HistoricalStateProviderRef<'b, TX> {
     }
 }
 
+impl<'b, TX: DbTx> StorageRangeProvider for HistoricalStateProviderRef<'b, TX> {
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(hashed_state);
+        Ok(StorageRange::overlay_storage_range(self.tx, revert_state, address, start, limit)?)
+    }
+}
+
 impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     /// Get storage.
     fn storage(
@@ -356,28 +356,92 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
-        match self.storage_history_lookup(address, storage_key)? {
-            HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
-                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
-                        block_number: changeset_block_number,
-                        address,
-                        storage_key: Box::new(storage_key),
-                    })?
-                    .value,
-            )),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
-                .tx
-                .cursor_dup_read::<tables::PlainStorageState>()?
-                .seek_by_key_subkey(address, storage_key)?
-                .filter(|entry| entry.key == storage_key)
-                .map(|entry| entry.value)
-                .or(Some(StorageValue::ZERO))),
+        Ok(self.storage_multi(&[(address, storage_key)])?.pop().flatten())
+    }
+
+    /// Get storage of multiple slots, reusing the cursors of the history, changeset and plain
+    /// state tables.
+    fn storage_multi(
+        &self,
+        slots: &[(Address, StorageKey)],
+    ) -> ProviderResult<Vec<Option<StorageValue>>> {
+        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
+
+        let mut history = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut changesets = self.tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let mut plain_state = self.tx.cursor_dup_read::<tables::PlainStorageState>()?;
+        slots
+            .iter()
+            .map(|&(address, storage_key)| {
+                let history_info = self.history_info(
+                    &mut history,
+                    StorageShardedKey::new(address, storage_key, self.block_number),
+                    |key| key.address == address && key.sharded_key.key == storage_key,
+                    self.lowest_available_blocks.storage_history_block_number,
+                )?;
+                match history_info {
+                    HistoryInfo::NotYetWritten => Ok(None),
+                    HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
+                        changesets
+                            .seek_by_key_subkey(
+                                (changeset_block_number, address).into(),
+                                storage_key,
+                            )?
+                            .filter(|entry| entry.key == storage_key)
+                            .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                                block_number: changeset_block_number,
+                                address,
+                                storage_key: Box::new(storage_key),
+                            })?
+                            .value,
+                    )),
+                    HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(plain_state
+                        .seek_by_key_subkey(address, storage_key)?
+                        .filter(|entry| entry.key == storage_key)
+                        .map(|entry| entry.value)
+                        .or(Some(StorageValue::ZERO))),
+                }
+            })
+            .collect()
+    }
+
+    /// Get basic account information of multiple accounts, reusing the cursors of the history,
+    /// changeset and plain state tables.
+    fn basic_accounts(&self, addresses: &[Address]) -> ProviderResult<Vec<Option<Account>>> {
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        let mut history = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        let mut changesets = self.tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+        let mut plain_state = self.tx.cursor_read::<tables::PlainAccountState>()?;
+        addresses
+            .iter()
+            .map(|&address| {
+                let history_info = self.history_info(
+                    &mut history,
+                    ShardedKey::new(address, self.block_number),
+                    |key| key.key == address,
+                    self.lowest_available_blocks.account_history_block_number,
+                )?;
+                match history_info {
+                    HistoryInfo::NotYetWritten => Ok(None),
+                    HistoryInfo::InChangeset(changeset_block_number) => Ok(changesets
+                        .seek_by_key_subkey(changeset_block_number, address)?
+                        .filter(|acc| acc.address == address)
+                        .ok_or(ProviderError::AccountChangesetNotFound {
+                            block_number: changeset_block_number,
+                            address,
+                        })?
+                        .info),
+                    HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
+                        Ok(plain_state.seek_exact(address)?.map(|(_, account)| account))
+                    }
+                }
+            })
+            .collect()
     }
 
     /// Get account code by its hash
@@ -616,10 +680,20 @@ mod tests {
             Ok(None)
         );
         assert_eq!(
-            HistoricalStateProviderRef::new(&tx, 1000, static_file_provider)
+            HistoricalStateProviderRef::new(&tx, 1000, static_file_provider.clone())
                 .basic_account(HIGHER_ADDRESS),
             Ok(Some(higher_acc_plain))
         );
+
+        // batched reads return the accounts in the order of the addresses
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 9, static_file_provider).basic_accounts(&[
+                HIGHER_ADDRESS,
+                ADDRESS,
+                HIGHER_ADDRESS
+            ]),
+            Ok(vec![Some(higher_acc_plain), Some(acc_at10), Some(higher_acc_plain)])
+        );
     }
 
     #[test]
@@ -722,10 +796,17 @@ mod tests {
             Ok(None)
         );
         assert_eq!(
-            HistoricalStateProviderRef::new(&tx, 1000, static_file_provider)
+            HistoricalStateProviderRef::new(&tx, 1000, static_file_provider.clone())
                 .storage(HIGHER_ADDRESS, STORAGE),
             Ok(Some(higher_entry_plain.value))
         );
+
+        // batched reads return the values in the order of the slots
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 5, static_file_provider)
+                .storage_multi(&[(ADDRESS, STORAGE), (HIGHER_ADDRESS, STORAGE)]),
+            Ok(vec![Some(entry_at7.value), Some(higher_entry_plain.value)])
+        );
     }
 
     #[test]
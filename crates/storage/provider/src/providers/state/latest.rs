@@ -13,13 +13,16 @@ use reth_primitives::{
     Account, Address, BlockNumber, Bytecode, Bytes, StaticFileSegment, StorageKey, StorageValue,
     B256,
 };
-use reth_storage_api::StateProofProvider;
+use reth_storage_api::{StateProofProvider, StorageRangeProvider};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, proof::Proof, updates::TrieUpdates, witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, StateRoot, StorageRoot,
+    AccountProof, HashedPostState, HashedStorage, StateRoot, StorageRange, StorageRoot,
+};
+use reth_trie_db::{
+    DatabaseProof, DatabaseStateRoot, DatabaseStorageRange, DatabaseStorageRoot,
+    DatabaseTrieWitness,
 };
-use reth_trie_db::{DatabaseProof, DatabaseStateRoot, DatabaseStorageRoot, DatabaseTrieWitness};
 
 /// State provider over latest state that takes tx reference.
 #[derive(Debug)]
@@ -144,6 +147,18 @@ impl<'b, TX: DbTx> StateProofProvider for LatestStateProviderRef<'b, TX> {
     }
 }
 
+impl<'b, TX: DbTx> StorageRangeProvider for LatestStateProviderRef<'b, TX> {
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        Ok(StorageRange::overlay_storage_range(self.tx, hashed_state, address, start, limit)?)
+    }
+}
+
 impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
     /// Get storage.
     fn storage(
@@ -151,13 +166,33 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
         account: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
+        Ok(self.storage_multi(&[(account, storage_key)])?.pop().flatten())
+    }
+
+    /// Get storage of multiple slots, reusing a single cursor.
+    fn storage_multi(
+        &self,
+        slots: &[(Address, StorageKey)],
+    ) -> ProviderResult<Vec<Option<StorageValue>>> {
         let mut cursor = self.tx.cursor_dup_read::<tables::PlainStorageState>()?;
-        if let Some(entry) = cursor.seek_by_key_subkey(account, storage_key)? {
-            if entry.key == storage_key {
-                return Ok(Some(entry.value))
-            }
-        }
-        Ok(None)
+        slots
+            .iter()
+            .map(|(account, storage_key)| {
+                Ok(cursor
+                    .seek_by_key_subkey(*account, *storage_key)?
+                    .filter(|entry| entry.key == *storage_key)
+                    .map(|entry| entry.value))
+            })
+            .collect()
+    }
+
+    /// Get basic account information of multiple accounts, reusing a single cursor.
+    fn basic_accounts(&self, addresses: &[Address]) -> ProviderResult<Vec<Option<Account>>> {
+        let mut cursor = self.tx.cursor_read::<tables::PlainAccountState>()?;
+        addresses
+            .iter()
+            .map(|address| Ok(cursor.seek_exact(*address)?.map(|(_, account)| account)))
+            .collect()
     }
 
     /// Get account code by its hash
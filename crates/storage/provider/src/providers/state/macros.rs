@@ -39,6 +39,8 @@ macro_rules! delegate_provider_impls {
             }
             StateProvider $(where [$($generics)*])? {
                 fn storage(&self, account: reth_primitives::Address, storage_key: reth_primitives::StorageKey) -> reth_storage_errors::provider::ProviderResult<Option<reth_primitives::StorageValue>>;
+                fn storage_multi(&self, slots: &[(reth_primitives::Address, reth_primitives::StorageKey)]) -> reth_storage_errors::provider::ProviderResult<Vec<Option<reth_primitives::StorageValue>>>;
+                fn basic_accounts(&self, addresses: &[reth_primitives::Address]) -> reth_storage_errors::provider::ProviderResult<Vec<Option<reth_primitives::Account>>>;
                 fn bytecode_by_hash(&self, code_hash: reth_primitives::B256) -> reth_storage_errors::provider::ProviderResult<Option<reth_primitives::Bytecode>>;
             }
             StateRootProvider $(where [$($generics)*])? {
@@ -55,6 +57,9 @@ macro_rules! delegate_provider_impls {
                 fn hashed_proof(&self, state: reth_trie::HashedPostState, address: reth_primitives::Address, slots: &[reth_primitives::B256]) -> reth_storage_errors::provider::ProviderResult<reth_trie::AccountProof>;
                 fn witness(&self, state: reth_trie::HashedPostState, target: reth_trie::HashedPostState) -> reth_storage_errors::provider::ProviderResult<std::collections::HashMap<reth_primitives::B256, reth_primitives::Bytes>>;
             }
+            StorageRangeProvider $(where [$($generics)*])? {
+                fn hashed_storage_range(&self, state: reth_trie::HashedPostState, address: reth_primitives::Address, start: reth_primitives::B256, limit: usize) -> reth_storage_errors::provider::ProviderResult<reth_trie::StorageRange>;
+            }
         );
     }
 }
@@ -0,0 +1,436 @@
+//! Cold tier of static files that is served from an object store.
+
+use parking_lot::Mutex;
+use reth_nippy_jar::NippyJar;
+use reth_primitives::{
+    static_file::{find_fixed_range, SegmentHeader, SegmentRangeInclusive},
+    BlockNumber, StaticFileSegment,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use schnellru::{LruMap, Unlimited};
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum::IntoEnumIterator;
+use tracing::{debug, info};
+
+use super::BLOCKS_PER_STATIC_FILE;
+
+/// Block and transaction ranges of static files, organized by [`StaticFileSegment`].
+pub(crate) type ColdStaticFileRanges =
+    HashMap<StaticFileSegment, Vec<(SegmentRangeInclusive, Option<SegmentRangeInclusive>)>>;
+
+/// A remote store of static files, e.g. an S3 or GCS bucket.
+///
+/// Objects are keyed by the file names of the static files, so a bucket that was populated by
+/// syncing a `static_files` directory can be used as is.
+pub trait ObjectStore: fmt::Debug + Send + Sync {
+    /// Downloads the object to `dst`.
+    ///
+    /// Returns `false` if the object doesn't exist.
+    fn download(&self, key: &str, dst: &Path) -> ProviderResult<bool>;
+
+    /// Uploads the file at `src` as the object.
+    fn upload(&self, src: &Path, key: &str) -> ProviderResult<()>;
+}
+
+/// An [`ObjectStore`] backed by a local directory, e.g. a mounted bucket.
+#[derive(Debug, Clone)]
+pub struct FsObjectStore {
+    root: PathBuf,
+}
+
+impl FsObjectStore {
+    /// Creates a new store of the objects in the given directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl ObjectStore for FsObjectStore {
+    fn download(&self, key: &str, dst: &Path) -> ProviderResult<bool> {
+        let src = self.root.join(key);
+        if !src.exists() {
+            return Ok(false)
+        }
+        copy_file(&src, dst)?;
+        Ok(true)
+    }
+
+    fn upload(&self, src: &Path, key: &str) -> ProviderResult<()> {
+        reth_fs_util::create_dir_all(&self.root)
+            .map_err(|e| ProviderError::FsPathError(e.to_string()))?;
+        copy_file(src, &self.root.join(key))
+    }
+}
+
+/// Default maximum size of the data files in the cache directory, 64 GiB.
+pub const DEFAULT_MAX_COLD_CACHE_SIZE: u64 = 64 * 1024 * 1024 * 1024;
+
+/// Identifies a cold static file.
+pub(crate) type ColdStaticFileKey = (StaticFileSegment, SegmentRangeInclusive);
+
+/// Static files of old block ranges that are kept in an [`ObjectStore`] instead of the local
+/// `static_files` directory.
+///
+/// All static files whose block range ends at or below [`ColdStaticFiles::max_block`] are cold.
+/// The configuration files of cold static files are always cached locally, so the static file
+/// index can be built without touching the store. The data files are downloaded into the cache
+/// directory the first time they are accessed, and the least recently accessed ones are deleted
+/// again once the cache grows beyond [`ColdStaticFiles::max_cache_size`].
+#[derive(Debug)]
+pub struct ColdStaticFiles {
+    store: Box<dyn ObjectStore>,
+    cache_dir: PathBuf,
+    max_block: BlockNumber,
+    max_cache_size: u64,
+    /// Size of the cached data files of each static file, ordered by the time they were last
+    /// accessed.
+    cache: Mutex<ColdCache>,
+    /// Locks of the individual static files, so a static file is only downloaded or evicted by
+    /// one thread at a time, without blocking the access to other static files.
+    locks: Mutex<HashMap<ColdStaticFileKey, Arc<Mutex<()>>>>,
+}
+
+impl ColdStaticFiles {
+    /// Creates a new cold tier with the given store and cache directory.
+    pub fn new(
+        store: impl ObjectStore + 'static,
+        cache_dir: impl Into<PathBuf>,
+        max_block: BlockNumber,
+    ) -> Self {
+        Self {
+            store: Box::new(store),
+            cache_dir: cache_dir.into(),
+            max_block,
+            max_cache_size: DEFAULT_MAX_COLD_CACHE_SIZE,
+            cache: Mutex::new(ColdCache::default()),
+            locks: Mutex::default(),
+        }
+    }
+
+    /// Sets the maximum size of the data files in the cache directory.
+    ///
+    /// The most recently accessed static file is always kept, even if it alone exceeds the limit.
+    pub const fn with_max_cache_size(mut self, max_cache_size: u64) -> Self {
+        self.max_cache_size = max_cache_size;
+        self
+    }
+
+    /// Returns the highest block of the cold static files.
+    pub const fn max_block(&self) -> BlockNumber {
+        self.max_block
+    }
+
+    /// Returns the directory where the cold static files are cached.
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Returns the maximum size of the data files in the cache directory.
+    pub const fn max_cache_size(&self) -> u64 {
+        self.max_cache_size
+    }
+
+    /// Returns `true` if the static file of the given fixed block range is cold.
+    pub(crate) const fn is_cold(&self, fixed_block_range: &SegmentRangeInclusive) -> bool {
+        fixed_block_range.end() <= self.max_block
+    }
+
+    /// Returns the block and transaction ranges of all cold static files.
+    ///
+    /// Configuration files that aren't cached yet are downloaded from the store. Data files that
+    /// are already in the cache directory, e.g. from a previous run, are added to the cache.
+    ///
+    /// See [`ColdStaticFiles::fetch`] for `release`.
+    pub(crate) fn static_files(
+        &self,
+        release: impl Fn(ColdStaticFileKey) -> bool,
+    ) -> ProviderResult<ColdStaticFileRanges> {
+        reth_fs_util::create_dir_all(&self.cache_dir)
+            .map_err(|e| ProviderError::FsPathError(e.to_string()))?;
+
+        let mut static_files = ColdStaticFileRanges::default();
+        for segment in StaticFileSegment::iter() {
+            let mut block_start = 0;
+            while block_start <= self.max_block {
+                let fixed_block_range = find_fixed_range(block_start);
+                block_start += BLOCKS_PER_STATIC_FILE;
+                if !self.is_cold(&fixed_block_range) {
+                    break
+                }
+
+                let path = self.cache_dir.join(segment.filename(&fixed_block_range));
+                let config_path = with_suffix(&path, "conf");
+                if !config_path.exists() && !self.download(&config_path)? {
+                    continue
+                }
+
+                let jar = NippyJar::<SegmentHeader>::load(&path)
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                if jar.data_path().exists() {
+                    self.cache.lock().insert((segment, fixed_block_range), cached_size(&jar));
+                }
+                if let Some(block_range) = jar.user_header().block_range().copied() {
+                    static_files
+                        .entry(segment)
+                        .or_default()
+                        .push((block_range, jar.user_header().tx_range().copied()));
+                }
+            }
+        }
+        self.evict(release)?;
+
+        Ok(static_files)
+    }
+
+    /// Makes sure all files of the cold static file are cached locally and returns the path to
+    /// load it from.
+    ///
+    /// Fetching a static file may evict the data files of the least recently accessed static files
+    /// from the cache. Before the files of a static file are deleted, `release` is called to drop
+    /// the loaded static file, since its memory mapped files would otherwise keep occupying the
+    /// disk. If it returns `false`, because the static file is still in use, the static file is
+    /// kept until a later eviction.
+    pub(crate) fn fetch(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+        release: impl Fn(ColdStaticFileKey) -> bool,
+    ) -> ProviderResult<PathBuf> {
+        let path = {
+            let lock = self.lock((segment, *fixed_block_range));
+            let _guard = lock.lock();
+
+            let path = self.cache_dir.join(segment.filename(fixed_block_range));
+            let jar = NippyJar::<SegmentHeader>::load(&path)
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+            for path in [jar.data_path().to_path_buf(), jar.offsets_path()] {
+                if !path.exists() && !self.download(&path)? {
+                    return Err(ProviderError::ObjectStore(format!(
+                        "missing object {}",
+                        file_name(&path).to_string_lossy()
+                    )))
+                }
+            }
+
+            // The index file only exists if the static file was created with filters.
+            let index_path = jar.index_path();
+            if !index_path.exists() {
+                self.download(&index_path)?;
+            }
+
+            self.cache.lock().insert((segment, *fixed_block_range), cached_size(&jar));
+            path
+        };
+
+        // The lock of the fetched static file is released before evicting others, so two threads
+        // never wait on each other's static file.
+        self.evict(release)?;
+
+        Ok(path)
+    }
+
+    /// Marks the cached static file as the most recently accessed one.
+    pub(crate) fn touch(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) {
+        self.cache.lock().touch(&(segment, *fixed_block_range));
+    }
+
+    /// Deletes the data files of the least recently accessed static files until the cache fits
+    /// into [`ColdStaticFiles::max_cache_size`] again.
+    fn evict(&self, release: impl Fn(ColdStaticFileKey) -> bool) -> ProviderResult<()> {
+        loop {
+            let Some((key, size)) = self.cache.lock().pop_oldest(self.max_cache_size) else {
+                return Ok(())
+            };
+
+            let lock = self.lock(key);
+            let _guard = lock.lock();
+
+            // The static file may have been fetched again in the meantime.
+            if self.cache.lock().contains(&key) {
+                continue
+            }
+
+            if !release(key) {
+                // The static file is in use, so it's kept and evicted once it's accessed least
+                // recently again.
+                self.cache.lock().insert(key, size);
+                return Ok(())
+            }
+
+            let (segment, fixed_block_range) = key;
+            debug!(target: "provider::static_file", ?segment, ?fixed_block_range, "Evicting static file from cold storage cache");
+            let jar = NippyJar::<SegmentHeader>::load(
+                &self.cache_dir.join(segment.filename(&fixed_block_range)),
+            )
+            .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+            for path in [jar.data_path().to_path_buf(), jar.offsets_path(), jar.index_path()] {
+                if path.exists() {
+                    reth_fs_util::remove_file(&path)
+                        .map_err(|e| ProviderError::FsPathError(e.to_string()))?;
+                }
+            }
+        }
+    }
+
+    /// Returns the lock of the given static file.
+    fn lock(&self, key: ColdStaticFileKey) -> Arc<Mutex<()>> {
+        self.locks.lock().entry(key).or_default().clone()
+    }
+
+    /// Moves the static file to the store and deletes it from the local disk.
+    ///
+    /// Only the configuration file is kept, in the cache directory.
+    pub(crate) fn offload(&self, jar: NippyJar<SegmentHeader>) -> ProviderResult<()> {
+        info!(target: "provider::static_file", path = ?jar.data_path(), "Moving static file to cold storage");
+
+        // The configuration file is uploaded last, so a static file is only visible in the store
+        // once it's complete.
+        for path in
+            [jar.data_path().to_path_buf(), jar.offsets_path(), jar.index_path(), jar.config_path()]
+        {
+            if path.exists() {
+                self.store.upload(&path, &file_name(&path).to_string_lossy())?;
+            }
+        }
+
+        reth_fs_util::create_dir_all(&self.cache_dir)
+            .map_err(|e| ProviderError::FsPathError(e.to_string()))?;
+        copy_file(&jar.config_path(), &self.cache_dir.join(file_name(&jar.config_path())))?;
+
+        jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))
+    }
+
+    /// Downloads the file with the same name as `path` from the store.
+    ///
+    /// The file is downloaded to a temporary path first, so an interrupted download never leaves a
+    /// partial file behind.
+    fn download(&self, path: &Path) -> ProviderResult<bool> {
+        let key = file_name(path).to_string_lossy().into_owned();
+        debug!(target: "provider::static_file", %key, "Downloading static file from cold storage");
+
+        let tmp_path = with_suffix(path, "download");
+        if !self.store.download(&key, &tmp_path)? {
+            return Ok(false)
+        }
+        reth_fs_util::rename(&tmp_path, path)
+            .map_err(|e| ProviderError::FsPathError(e.to_string()))?;
+        Ok(true)
+    }
+}
+
+/// The cached static files with the size of their data files, ordered by the time they were last
+/// accessed.
+#[derive(Debug)]
+struct ColdCache {
+    entries: LruMap<ColdStaticFileKey, u64, Unlimited>,
+    size: u64,
+}
+
+impl Default for ColdCache {
+    fn default() -> Self {
+        Self { entries: LruMap::new(Unlimited), size: 0 }
+    }
+}
+
+impl ColdCache {
+    /// Inserts the static file as the most recently accessed one.
+    fn insert(&mut self, key: ColdStaticFileKey, size: u64) {
+        if let Some(previous) = self.entries.peek(&key) {
+            self.size -= previous;
+        }
+        self.entries.insert(key, size);
+        self.size += size;
+    }
+
+    /// Marks the static file as the most recently accessed one, if it's cached.
+    fn touch(&mut self, key: &ColdStaticFileKey) {
+        self.entries.get(key);
+    }
+
+    /// Returns `true` if the static file is cached.
+    fn contains(&self, key: &ColdStaticFileKey) -> bool {
+        self.entries.peek(key).is_some()
+    }
+
+    /// Removes and returns the least recently accessed static file and its size if the cache is
+    /// larger than `max_size`.
+    ///
+    /// The most recently accessed static file is never removed.
+    fn pop_oldest(&mut self, max_size: u64) -> Option<(ColdStaticFileKey, u64)> {
+        if self.size <= max_size || self.entries.len() <= 1 {
+            return None
+        }
+        let (key, size) = self.entries.pop_oldest()?;
+        self.size -= size;
+        Some((key, size))
+    }
+}
+
+/// Returns the size of the data, offsets and index files of the static file.
+fn cached_size(jar: &NippyJar<SegmentHeader>) -> u64 {
+    [jar.data_path().to_path_buf(), jar.offsets_path(), jar.index_path()]
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Returns the file name of the path.
+fn file_name(path: &Path) -> &std::ffi::OsStr {
+    path.file_name().unwrap_or_default()
+}
+
+/// Returns the path with `.<suffix>` appended to its file name.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(file_name(path));
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Copies the file at `src` to `dst`.
+fn copy_file(src: &Path, dst: &Path) -> ProviderResult<()> {
+    std::fs::copy(src, dst).map(|_| ()).map_err(|e| {
+        ProviderError::FsPathError(format!("{} -> {}: {e}", src.display(), dst.display()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cold_cache_evicts_least_recently_accessed() {
+        let key = |block| (StaticFileSegment::Headers, find_fixed_range(block));
+        let mut cache = ColdCache::default();
+        cache.insert(key(0), 10);
+        cache.insert(key(BLOCKS_PER_STATIC_FILE), 10);
+        cache.insert(key(2 * BLOCKS_PER_STATIC_FILE), 10);
+        cache.insert(key(3 * BLOCKS_PER_STATIC_FILE), 10);
+        assert_eq!(cache.pop_oldest(40), None);
+
+        // Fetching a static file again makes it the most recent one
+        cache.insert(key(0), 10);
+        // So does accessing it after it was fetched
+        cache.touch(&key(BLOCKS_PER_STATIC_FILE));
+        assert_eq!(cache.pop_oldest(25), Some((key(2 * BLOCKS_PER_STATIC_FILE), 10)));
+        assert_eq!(cache.pop_oldest(25), Some((key(3 * BLOCKS_PER_STATIC_FILE), 10)));
+        assert_eq!(cache.pop_oldest(15), Some((key(0), 10)));
+        assert!(cache.contains(&key(BLOCKS_PER_STATIC_FILE)));
+
+        // The most recently accessed static file is kept even if it's too large
+        assert_eq!(cache.pop_oldest(0), None);
+        assert_eq!(cache.size, 10);
+    }
+}
@@ -1,15 +1,19 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    cold::{ColdStaticFileKey, ColdStaticFiles},
+    metrics::StaticFileProviderMetrics,
+    writer::StaticFileWriters,
+    LoadedJar, StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
     BLOCKS_PER_STATIC_FILE,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, DatabaseProvider,
-    HeaderProvider, ReceiptProvider, RequestsProvider, StageCheckpointReader, StatsReader,
-    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    providers::RangeIter, to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource,
+    DatabaseProvider, HeaderProvider, ReceiptProvider, RequestsProvider, StageCheckpointReader,
+    StatsReader, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    WithdrawalsProvider,
 };
-use dashmap::DashMap;
+use dashmap::{mapref::entry::Entry as DashMapEntry, DashMap};
 use parking_lot::RwLock;
+use rayon::prelude::*;
 use reth_chainspec::ChainInfo;
 use reth_db::{
     lockfile::StorageLock,
@@ -37,11 +41,26 @@ use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
     ops::{Deref, Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
-    sync::{mpsc, Arc},
+    sync::{mpsc, Arc, LazyLock},
 };
 use strum::IntoEnumIterator;
 use tracing::{info, trace, warn};
 
+/// The number of threads that read the static files of a range query in parallel.
+const STATIC_FILE_READ_THREADS: usize = 4;
+
+/// The thread pool that reads the static files of range queries in parallel.
+///
+/// It's separate from the global rayon pool, so that large range queries can't starve the CPU
+/// heavy work that runs there, like the state root computation and sender recovery.
+static STATIC_FILE_READ_POOL: LazyLock<rayon::ThreadPool> = LazyLock::new(|| {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(STATIC_FILE_READ_THREADS)
+        .thread_name(|i| format!("static-file-reader-{i:02}"))
+        .build()
+        .expect("failed to build the static file read pool")
+});
+
 /// Alias type for a map that can be queried for block ranges from a transaction
 /// segment respectively. It uses `TxNumber` to represent the transaction end of a static file
 /// range.
@@ -118,6 +137,8 @@ pub struct StaticFileProviderInner {
     /// Maintains a writer set of [`StaticFileSegment`].
     writers: StaticFileWriters,
     metrics: Option<Arc<StaticFileProviderMetrics>>,
+    /// Static files of old block ranges that are served from an object store.
+    cold: Option<ColdStaticFiles>,
     /// Access rights of the provider.
     access: StaticFileAccess,
     /// Write lock for when access is [`StaticFileAccess::RW`].
@@ -141,6 +162,7 @@ impl StaticFileProviderInner {
             path: path.as_ref().to_path_buf(),
             load_filters: false,
             metrics: None,
+            cold: None,
             access,
             _lock_file,
         };
@@ -170,6 +192,40 @@ impl StaticFileProvider {
         Self(Arc::new(provider))
     }
 
+    /// Serves the static files of old block ranges from the given cold storage.
+    ///
+    /// With read-write access, local static files that became cold are moved to the object store.
+    /// The most recent static file of every segment always stays on the local disk.
+    pub fn with_cold_storage(self, cold: ColdStaticFiles) -> ProviderResult<Self> {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+
+        if provider.access.is_read_write() {
+            for (segment, ranges) in iter_static_files(&provider.path)
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?
+            {
+                // Skip the most recent static file, since it's still being written to.
+                for (block_range, _) in ranges.iter().rev().skip(1) {
+                    let fixed_block_range = find_fixed_range(block_range.start());
+                    if !cold.is_cold(&fixed_block_range) {
+                        continue
+                    }
+
+                    let jar = NippyJar::<SegmentHeader>::load(
+                        &provider.path.join(segment.filename(&fixed_block_range)),
+                    )
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                    cold.offload(jar)?;
+                }
+            }
+        }
+
+        provider.cold = Some(cold);
+        let provider = Self(Arc::new(provider));
+        provider.initialize_index()?;
+        Ok(provider)
+    }
+
     /// Reports metrics for the static files.
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
@@ -333,10 +389,13 @@ impl StaticFileProvider {
         trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Getting provider");
         let mut provider: StaticFileJarProvider<'_> = if let Some(jar) = self.map.get(&key) {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Jar found in cache");
+            if let Some(cold) = &self.cold {
+                cold.touch(segment, fixed_block_range);
+            }
             jar.into()
         } else {
             trace!(target: "provider::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
-            let path = self.path.join(segment.filename(fixed_block_range));
+            let path = self.jar_path(segment, fixed_block_range)?;
             let mut jar =
                 NippyJar::load(&path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
             if self.load_filters {
@@ -352,6 +411,39 @@ impl StaticFileProvider {
         Ok(provider)
     }
 
+    /// Returns the path of the static file of the given segment and fixed block range.
+    ///
+    /// If the static file is cold and not on the local disk, it's fetched into the cold storage
+    /// cache and the path in the cache is returned.
+    fn jar_path(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) -> ProviderResult<PathBuf> {
+        let path = self.path.join(segment.filename(fixed_block_range));
+        match &self.cold {
+            Some(cold) if cold.is_cold(fixed_block_range) && !path.exists() => {
+                cold.fetch(segment, fixed_block_range, |key| self.release_cold_jar(key))
+            }
+            _ => Ok(path),
+        }
+    }
+
+    /// Drops the loaded jar of the cold static file, so its files can be deleted from the cold
+    /// storage cache.
+    ///
+    /// Returns `false` if the jar is in use.
+    fn release_cold_jar(&self, (segment, fixed_block_range): ColdStaticFileKey) -> bool {
+        match self.map.try_entry((fixed_block_range.end(), segment)) {
+            Some(DashMapEntry::Occupied(entry)) => {
+                entry.remove();
+                true
+            }
+            Some(DashMapEntry::Vacant(_)) => true,
+            None => false,
+        }
+    }
+
     /// Gets a static file segment's block range from the provider inner block
     /// index.
     fn get_segment_ranges_from_block(
@@ -393,6 +485,85 @@ impl StaticFileProvider {
         None
     }
 
+    /// Splits the transaction range into the ranges of the static files of the segment that
+    /// contain them, in ascending order.
+    ///
+    /// The part of the range that is above the highest static file is returned as the last range.
+    pub(crate) fn split_tx_range_by_static_file(
+        &self,
+        segment: StaticFileSegment,
+        range: Range<TxNumber>,
+    ) -> Vec<Range<TxNumber>> {
+        let mut ranges = Vec::new();
+        let mut start = range.start;
+
+        if let Some(segment_static_files) = self.static_files_tx_index.read().get(&segment) {
+            for &tx_end in segment_static_files.range(range.start..).map(|(tx_end, _)| tx_end) {
+                if start >= range.end {
+                    break
+                }
+                let end = (tx_end + 1).min(range.end);
+                ranges.push(start..end);
+                start = end;
+            }
+        }
+
+        if start < range.end {
+            ranges.push(start..range.end);
+        }
+        ranges
+    }
+
+    /// Replaces the header of a block that is stored in static files, keeping its total difficulty
+    /// and hash.
+    ///
+    /// Static files are append-only, so the headers from the block to the end of its static file
+    /// are truncated and appended again. This must not be called while the static files are read
+    /// by another process.
+    pub fn replace_header(&self, header: &Header) -> ProviderResult<()> {
+        let segment = StaticFileSegment::Headers;
+        let number = header.number;
+        let end = self
+            .get_highest_static_file_block(segment)
+            .filter(|highest| number <= *highest)
+            .ok_or(ProviderError::MissingStaticFileBlock(segment, number))?
+            .min(find_fixed_range(number).end());
+
+        let rows = {
+            let provider = self.get_segment_provider_from_block(segment, number, None)?;
+            let mut cursor = provider.cursor()?;
+            (number..=end)
+                .map(|block| {
+                    cursor
+                        .get_three::<HeaderMask<Header, CompactU256, BlockHash>>(block.into())?
+                        .ok_or(ProviderError::MissingStaticFileBlock(segment, block))
+                })
+                .collect::<ProviderResult<Vec<_>>>()?
+        };
+
+        let mut writer = self.writers.replace(segment, || {
+            StaticFileProviderRW::new(
+                segment,
+                number,
+                Arc::downgrade(&self.0),
+                self.metrics.clone(),
+            )
+        })?;
+        writer.prune_headers(rows.len() as u64)?;
+        writer.commit()?;
+        for (stored, td, hash) in rows {
+            let header = if stored.number == number { header } else { &stored };
+            writer.append_header(header, td.into(), &hash)?;
+        }
+        writer.commit()?;
+
+        // the writer is not positioned at the latest static file anymore, so it's reopened on its
+        // next use, and the index is rebuilt as truncating the static file lowered it
+        writer.0.take();
+        drop(writer);
+        self.initialize_index()
+    }
+
     /// Updates the inner transaction and block indexes alongside the internal cached providers in
     /// `self.map`.
     ///
@@ -479,9 +650,23 @@ impl StaticFileProvider {
 
         tx_index.clear();
 
-        for (segment, ranges) in
-            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?
-        {
+        let mut static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+        // Add the cold static files that are not on the local disk.
+        if let Some(cold) = &self.cold {
+            for (segment, cold_ranges) in cold.static_files(|key| self.release_cold_jar(key))? {
+                let ranges = static_files.entry(segment).or_default();
+                for cold_range in cold_ranges {
+                    if !ranges.iter().any(|(block_range, _)| block_range == &cold_range.0) {
+                        ranges.push(cold_range);
+                    }
+                }
+                ranges.sort_by_key(|(block_range, _)| block_range.end());
+            }
+        }
+
+        for (segment, ranges) in static_files {
             // Update last block for each segment
             if let Some((block_range, _)) = ranges.last() {
                 max_block.insert(segment, block_range.end());
@@ -923,6 +1108,35 @@ impl StaticFileProvider {
         }))
     }
 
+    /// Returns an iterator over the hashes and total difficulties of the headers of the range,
+    /// which reads them lazily in chunks from the headers static files, without decoding the
+    /// headers or accessing the database.
+    ///
+    /// The items are yielded in block number order, starting at the start of the range. The
+    /// iteration ends at the highest block of the headers static files.
+    pub fn header_hashes_and_tds_iter(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<(BlockHash, U256)>> + '_ {
+        let mut range = to_range(range);
+        range.end = range.end.min(
+            self.get_highest_static_file_block(StaticFileSegment::Headers)
+                .map_or(0, |block| block + 1),
+        );
+        RangeIter::new(self, range, |provider, chunk| {
+            provider.fetch_range_with_predicate(
+                StaticFileSegment::Headers,
+                chunk,
+                |cursor, number| {
+                    Ok(cursor
+                        .get_two::<HeaderMask<CompactU256, BlockHash>>(number.into())?
+                        .map(|(td, hash)| (hash, td.0)))
+                },
+                |_| true,
+            )
+        })
+    }
+
     /// Returns directory where `static_files` are located.
     pub fn directory(&self) -> &Path {
         &self.path
@@ -1385,14 +1599,49 @@ impl TransactionsProvider for StaticFileProvider {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<TransactionSignedNoHash>> {
-        self.fetch_range_with_predicate(
-            StaticFileSegment::Transactions,
-            to_range(range),
-            |cursor, number| {
-                cursor.get_one::<TransactionMask<TransactionSignedNoHash>>(number.into())
-            },
-            |_| true,
-        )
+        let fetch_range = |range: Range<TxNumber>| {
+            self.fetch_range_with_predicate(
+                StaticFileSegment::Transactions,
+                range,
+                |cursor, number| {
+                    cursor.get_one::<TransactionMask<TransactionSignedNoHash>>(number.into())
+                },
+                |_| true,
+            )
+        };
+
+        let range = to_range(range);
+        let static_file_ranges =
+            self.split_tx_range_by_static_file(StaticFileSegment::Transactions, range.clone());
+        if static_file_ranges.len() <= 1 {
+            return fetch_range(range)
+        }
+
+        // The static files are read and decompressed in parallel on a dedicated pool, and the
+        // transactions are reassembled in order.
+        let expected_lens =
+            static_file_ranges.iter().map(|range| range.end - range.start).collect::<Vec<_>>();
+        // The range can be open, so the capacity is bounded by the transactions in static files.
+        let capacity = expected_lens.iter().sum::<u64>().min(
+            self.get_highest_static_file_tx(StaticFileSegment::Transactions)
+                .map_or(0, |highest| highest + 1)
+                .saturating_sub(range.start),
+        );
+        let chunks = STATIC_FILE_READ_POOL
+            .install(|| static_file_ranges.into_par_iter().map(fetch_range).collect::<Vec<_>>());
+
+        // Like a sequential read, the transactions end at the first static file that returns fewer
+        // transactions than requested.
+        let mut transactions = Vec::with_capacity(capacity as usize);
+        for (chunk, expected_len) in chunks.into_iter().zip(expected_lens) {
+            let chunk = chunk?;
+            let is_short = (chunk.len() as u64) < expected_len;
+            transactions.extend(chunk);
+            if is_short {
+                break
+            }
+        }
+        Ok(transactions)
     }
 
     fn senders_by_tx_range(
@@ -7,6 +7,9 @@ pub use jar::StaticFileJarProvider;
 mod writer;
 pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
+mod cold;
+pub use cold::{ColdStaticFiles, FsObjectStore, ObjectStore, DEFAULT_MAX_COLD_CACHE_SIZE};
+
 mod metrics;
 
 use reth_nippy_jar::NippyJar;
@@ -57,12 +60,15 @@ impl Deref for LoadedJar {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_utils::create_test_provider_factory, HeaderProvider};
+    use crate::{test_utils::create_test_provider_factory, HeaderProvider, TransactionsProvider};
     use rand::seq::SliceRandom;
     use reth_db::{CanonicalHeaders, HeaderNumbers, HeaderTerminalDifficulties, Headers};
     use reth_db_api::transaction::DbTxMut;
-    use reth_primitives::{static_file::find_fixed_range, B256, U256};
+    use reth_primitives::{
+        static_file::find_fixed_range, TransactionSignedNoHash, TxNumber, B256, U256,
+    };
     use reth_testing_utils::generators::{self, random_header_range};
+    use std::ops::Range;
 
     #[test]
     fn test_snap() {
@@ -122,6 +128,28 @@ mod tests {
 
             assert!(!headers.is_empty());
 
+            // Iterate over the hashes and total difficulties
+            let mut td = U256::ZERO;
+            let hashes_and_tds = headers
+                .iter()
+                .map(|header| {
+                    td += header.difficulty;
+                    (header.hash(), td)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                manager.header_hashes_and_tds_iter(..).collect::<ProviderResult<Vec<_>>>().unwrap(),
+                hashes_and_tds
+            );
+            // the iteration ends at the highest block of the static files
+            assert_eq!(
+                manager
+                    .header_hashes_and_tds_iter(90..200)
+                    .collect::<ProviderResult<Vec<_>>>()
+                    .unwrap(),
+                hashes_and_tds[90..].to_vec()
+            );
+
             // Shuffled for chaos.
             headers.shuffle(&mut generators::rng());
 
@@ -141,4 +169,145 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_transactions_across_static_files() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let mut rng = generators::rng();
+        let transactions = (0..3)
+            .map(|_| TransactionSignedNoHash::from(generators::random_signed_tx(&mut rng)))
+            .collect::<Vec<_>>();
+
+        // one transaction in the first block of each of three static files
+        {
+            let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+            let mut writer = manager.latest_writer(StaticFileSegment::Transactions).unwrap();
+            let mut transactions = transactions.iter().enumerate();
+            for block in 0..=2 * BLOCKS_PER_STATIC_FILE {
+                writer.increment_block(block).unwrap();
+                if block % BLOCKS_PER_STATIC_FILE == 0 {
+                    let (tx_num, tx) = transactions.next().unwrap();
+                    writer.append_transaction(tx_num as TxNumber, tx).unwrap();
+                }
+            }
+            writer.commit().unwrap();
+        }
+
+        let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+        let split =
+            |range| manager.split_tx_range_by_static_file(StaticFileSegment::Transactions, range);
+        assert_eq!(split(0..3), vec![0..1, 1..2, 2..3]);
+        assert_eq!(split(1..3), vec![1..2, 2..3]);
+        assert_eq!(split(1..2), vec![1..2]);
+        // the part above the highest static file is the last range
+        assert_eq!(split(2..10), vec![2..3, 3..10]);
+        assert_eq!(split(5..10), vec![5..10]);
+        assert_eq!(split(1..1), Vec::<Range<TxNumber>>::new());
+
+        // the static files are read in parallel and the transactions are returned in order
+        assert_eq!(manager.transactions_by_tx_range(0..3).unwrap(), transactions);
+        assert_eq!(manager.transactions_by_tx_range(1..=2).unwrap(), transactions[1..]);
+    }
+
+    #[test]
+    fn test_transactions_across_static_files_of_different_sizes() {
+        let static_files_path = tempfile::tempdir().unwrap();
+        let mut rng = generators::rng();
+        let transactions = (0..6)
+            .map(|_| TransactionSignedNoHash::from(generators::random_signed_tx(&mut rng)))
+            .collect::<Vec<_>>();
+
+        // three, one and two transactions in the first block of each of three static files
+        {
+            let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+            let mut writer = manager.latest_writer(StaticFileSegment::Transactions).unwrap();
+            let mut transactions = transactions.iter().enumerate();
+            for block in 0..=2 * BLOCKS_PER_STATIC_FILE {
+                writer.increment_block(block).unwrap();
+                let count = match block / BLOCKS_PER_STATIC_FILE {
+                    _ if block % BLOCKS_PER_STATIC_FILE != 0 => 0,
+                    0 => 3,
+                    1 => 1,
+                    _ => 2,
+                };
+                for (tx_num, tx) in transactions.by_ref().take(count) {
+                    writer.append_transaction(tx_num as TxNumber, tx).unwrap();
+                }
+            }
+            writer.commit().unwrap();
+        }
+
+        let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+        assert_eq!(
+            manager.split_tx_range_by_static_file(StaticFileSegment::Transactions, 0..6),
+            vec![0..3, 3..4, 4..6]
+        );
+
+        assert_eq!(manager.transactions_by_tx_range(0..6).unwrap(), transactions);
+        assert_eq!(manager.transactions_by_tx_range(2..5).unwrap(), transactions[2..5]);
+        assert_eq!(manager.transactions_by_tx_range(3..=4).unwrap(), transactions[3..5]);
+
+        // like a sequential read, a range above the static files fails, without the capacity of
+        // the open range being allocated
+        assert!(matches!(
+            manager.transactions_by_tx_range(1..),
+            Err(ProviderError::MissingStaticFileTx(StaticFileSegment::Transactions, 6))
+        ));
+    }
+
+    #[test]
+    fn test_cold_storage() {
+        let range = 0..=99;
+        let fixed_block_range = find_fixed_range(*range.end());
+        let headers = random_header_range(
+            &mut generators::rng(),
+            *range.start()..(*range.end() + 1),
+            B256::ZERO,
+        );
+
+        let static_files_path = tempfile::tempdir().unwrap();
+        let store_path = tempfile::tempdir().unwrap();
+        let cache_path = tempfile::tempdir().unwrap();
+
+        {
+            let manager = StaticFileProvider::read_write(static_files_path.path()).unwrap();
+            let mut writer = manager.latest_writer(StaticFileSegment::Headers).unwrap();
+            for header in headers.clone() {
+                let hash = header.hash();
+                writer.append_header(&header.unseal(), U256::ZERO, &hash).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        // Move the static file to the store
+        let static_file =
+            static_files_path.path().join(StaticFileSegment::Headers.filename(&fixed_block_range));
+        let cold = ColdStaticFiles::new(
+            FsObjectStore::new(store_path.path()),
+            cache_path.path(),
+            fixed_block_range.end(),
+        );
+        cold.offload(NippyJar::load(&static_file).unwrap()).unwrap();
+        assert!(!static_file.exists());
+
+        // Serve the static file from the store, with an empty local directory and cache
+        let empty_path = tempfile::tempdir().unwrap();
+        let empty_cache_path = tempfile::tempdir().unwrap();
+        let manager = StaticFileProvider::read_only(empty_path.path())
+            .unwrap()
+            .with_cold_storage(ColdStaticFiles::new(
+                FsObjectStore::new(store_path.path()),
+                empty_cache_path.path(),
+                fixed_block_range.end(),
+            ))
+            .unwrap();
+
+        assert_eq!(
+            manager.get_highest_static_file_block(StaticFileSegment::Headers),
+            Some(*range.end())
+        );
+        for header in headers {
+            assert_eq!(manager.header_by_number(header.number).unwrap(), Some(header.unseal()));
+        }
+    }
 }
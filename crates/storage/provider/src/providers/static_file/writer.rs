@@ -50,6 +50,27 @@ impl StaticFileWriters {
         Ok(StaticFileProviderRWRefMut(write_guard))
     }
 
+    /// Commits the writer of the segment, if any, and replaces it with the one returned by
+    /// `create_fn`.
+    pub(crate) fn replace(
+        &self,
+        segment: StaticFileSegment,
+        create_fn: impl FnOnce() -> ProviderResult<StaticFileProviderRW>,
+    ) -> ProviderResult<StaticFileProviderRWRefMut<'_>> {
+        let mut write_guard = match segment {
+            StaticFileSegment::Headers => self.headers.write(),
+            StaticFileSegment::Transactions => self.transactions.write(),
+            StaticFileSegment::Receipts => self.receipts.write(),
+        };
+
+        if let Some(writer) = write_guard.as_mut() {
+            writer.commit()?;
+        }
+        *write_guard = Some(create_fn()?);
+
+        Ok(StaticFileProviderRWRefMut(write_guard))
+    }
+
     pub(crate) fn commit(&self) -> ProviderResult<()> {
         for writer_lock in [&self.headers, &self.transactions, &self.receipts] {
             let mut writer = writer_lock.write();
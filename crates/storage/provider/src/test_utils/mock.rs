@@ -1,27 +1,37 @@
 use crate::{
+    providers::StaticFileProvider,
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, ReceiptProviderIdExt,
-    RequestsProvider, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, ForkBlockReader, HeaderProvider,
+    InternalTransfersProvider, LogIndexProvider, LogsProvider, PruneCheckpointReader,
+    ReceiptProviderIdExt, ReorgJournalReader, RequestsProvider, StageCheckpointNotifications,
+    StageCheckpointSubscriptions, StateDiffProvider, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, StaticFileProviderFactory, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
+use reth_chain_state::ExecutedBlock;
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_evm::ConfigureEvmEnv;
+use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
     BlockNumberOrTag, BlockWithSenders, Bytecode, Bytes, Header, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, StorageKey, StorageValue, TransactionMeta,
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
     U256,
 };
+use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
-use reth_storage_api::{StageCheckpointReader, StateProofProvider};
+use reth_storage_api::{
+    AccountHistoryReader, StageCheckpointReader, StateProofProvider, StateReader,
+    StorageChangeSetReader, StorageRangeProvider,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
-    HashedStorage,
+    HashedStorage, StorageRange,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
@@ -29,6 +39,7 @@ use std::{
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
 };
+use tokio::sync::broadcast;
 
 /// A mock implementation for Provider interfaces.
 #[derive(Debug, Clone)]
@@ -43,6 +54,8 @@ pub struct MockEthProvider {
     pub chain_spec: Arc<ChainSpec>,
     /// Local state roots
     pub state_roots: Arc<Mutex<Vec<B256>>>,
+    /// Local fork block store
+    pub fork_blocks: Arc<Mutex<HashMap<B256, ExecutedBlock>>>,
 }
 
 impl Default for MockEthProvider {
@@ -53,6 +66,7 @@ impl Default for MockEthProvider {
             accounts: Default::default(),
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
             state_roots: Default::default(),
+            fork_blocks: Default::default(),
         }
     }
 }
@@ -109,6 +123,11 @@ impl MockEthProvider {
         }
     }
 
+    /// Add fork block to local fork block store
+    pub fn add_fork_block(&self, block: ExecutedBlock) {
+        self.fork_blocks.lock().insert(block.block.hash(), block);
+    }
+
     /// Add header to local header store
     pub fn add_header(&self, hash: B256, header: Header) {
         self.headers.lock().insert(hash, header);
@@ -625,6 +644,18 @@ impl StateProofProvider for MockEthProvider {
     }
 }
 
+impl StorageRangeProvider for MockEthProvider {
+    fn hashed_storage_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _address: Address,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        Ok(StorageRange::default())
+    }
+}
+
 impl StateProvider for MockEthProvider {
     fn storage(
         &self,
@@ -775,6 +806,88 @@ impl RequestsProvider for MockEthProvider {
     }
 }
 
+impl InternalTransfersProvider for MockEthProvider {
+    fn internal_transfers_by_block(
+        &self,
+        _id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<Vec<reth_primitives::InternalTransfer>>>> {
+        Ok(None)
+    }
+}
+
+impl StateDiffProvider for MockEthProvider {
+    fn state_diff_by_block(
+        &self,
+        _id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<reth_execution_types::StateDiff>> {
+        Ok(None)
+    }
+}
+
+impl ReorgJournalReader for MockEthProvider {
+    fn latest_reorgs(
+        &self,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(u64, reth_primitives::ReorgRecord)>> {
+        Ok(Vec::new())
+    }
+}
+
+impl LogIndexProvider for MockEthProvider {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn blocks_with_log_address(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn blocks_with_log_topic(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+}
+
+impl LogsProvider for MockEthProvider {}
+
+impl ForkBlockReader for MockEthProvider {
+    fn fork_block(&self, hash: BlockHash) -> ProviderResult<Option<ExecutedBlock>> {
+        Ok(self.fork_blocks.lock().get(&hash).cloned())
+    }
+}
+
+impl PruneCheckpointReader for MockEthProvider {
+    fn get_prune_checkpoint(
+        &self,
+        _segment: PruneSegment,
+    ) -> ProviderResult<Option<PruneCheckpoint>> {
+        Ok(None)
+    }
+
+    fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        Ok(Vec::new())
+    }
+}
+
+impl StaticFileProviderFactory for MockEthProvider {
+    fn static_file_provider(&self) -> StaticFileProvider {
+        StaticFileProvider::default()
+    }
+}
+
+impl StageCheckpointSubscriptions for MockEthProvider {
+    fn subscribe_to_stage_checkpoints(&self) -> StageCheckpointNotifications {
+        broadcast::channel(1).1
+    }
+}
+
 impl ChangeSetReader for MockEthProvider {
     fn account_block_changeset(
         &self,
@@ -783,3 +896,28 @@ impl ChangeSetReader for MockEthProvider {
         Ok(Vec::default())
     }
 }
+
+impl AccountHistoryReader for MockEthProvider {
+    fn account_history(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl StorageChangeSetReader for MockEthProvider {
+    fn storage_block_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl StateReader for MockEthProvider {
+    fn execution_outcome(&self, _block: BlockNumber) -> ProviderResult<Option<ExecutionOutcome>> {
+        Ok(None)
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{providers::StaticFileProvider, HashingWriter, ProviderFactory, TrieWriter};
 use reth_chainspec::{ChainSpec, MAINNET};
 use reth_db::{
-    test_utils::{create_test_rw_db, create_test_static_files_dir, TempDatabase},
-    Database, DatabaseEnv,
+    test_utils::{create_test_memory_db, create_test_rw_db, TempDatabase},
+    Database, DatabaseEnv, MemoryDatabase,
 };
 use reth_errors::ProviderResult;
 use reth_primitives::{Account, StorageEntry, B256};
@@ -24,15 +24,40 @@ pub fn create_test_provider_factory() -> ProviderFactory<Arc<TempDatabase<Databa
 }
 
 /// Creates test provider factory with provided chain spec.
+///
+/// The static files are written to a directory within the database directory, which is deleted
+/// together with the database.
 pub fn create_test_provider_factory_with_chain_spec(
     chain_spec: Arc<ChainSpec>,
 ) -> ProviderFactory<Arc<TempDatabase<DatabaseEnv>>> {
-    let (static_dir, _) = create_test_static_files_dir();
     let db = create_test_rw_db();
+    let static_dir = db.path().join("static_files");
     ProviderFactory::new(
         db,
         chain_spec,
-        StaticFileProvider::read_write(static_dir.into_path()).expect("static file provider"),
+        StaticFileProvider::read_write(static_dir).expect("static file provider"),
+    )
+}
+
+/// Creates test provider factory with mainnet chain spec, backed by an in-memory database.
+pub fn create_test_provider_factory_in_memory() -> ProviderFactory<Arc<TempDatabase<MemoryDatabase>>>
+{
+    create_test_provider_factory_in_memory_with_chain_spec(MAINNET.clone())
+}
+
+/// Creates test provider factory with provided chain spec, backed by an in-memory database.
+///
+/// Unlike [`create_test_provider_factory_with_chain_spec`], no MDBX environment is created. Static
+/// files are still written to a temporary directory, which is deleted together with the database.
+pub fn create_test_provider_factory_in_memory_with_chain_spec(
+    chain_spec: Arc<ChainSpec>,
+) -> ProviderFactory<Arc<TempDatabase<MemoryDatabase>>> {
+    let db = create_test_memory_db();
+    let static_dir = db.path().join("static_files");
+    ProviderFactory::new(
+        db,
+        chain_spec,
+        StaticFileProvider::read_write(static_dir).expect("static file provider"),
     )
 }
 
@@ -5,25 +5,30 @@ use std::{
 };
 
 use reth_chain_state::{
-    CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications,
+    CanonStateNotifications, CanonStateSubscriptions, ExecutedBlock, ForkChoiceNotifications,
     ForkChoiceSubscriptions,
 };
 use reth_chainspec::{ChainInfo, ChainSpec, MAINNET};
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_errors::ProviderError;
 use reth_evm::ConfigureEvmEnv;
+use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, BlockNumberOrTag,
     BlockWithSenders, Bytecode, Bytes, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
+    SealedHeader, StorageEntry, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
     TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
-use reth_storage_api::StateProofProvider;
+use reth_storage_api::{
+    AccountHistoryReader, StateProofProvider, StateReader, StorageChangeSetReader,
+    StorageRangeProvider,
+};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
+    StorageRange,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use tokio::sync::{broadcast, watch};
@@ -32,10 +37,12 @@ use crate::{
     providers::StaticFileProvider,
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
-    ReceiptProviderIdExt, RequestsProvider, StageCheckpointReader, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, StaticFileProviderFactory, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, ForkBlockReader, HeaderProvider,
+    InternalTransfersProvider, LogIndexProvider, LogsProvider, PruneCheckpointReader,
+    ReceiptProviderIdExt, ReorgJournalReader, RequestsProvider, StageCheckpointNotifications,
+    StageCheckpointReader, StageCheckpointSubscriptions, StateDiffProvider, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRootProvider, StaticFileProviderFactory,
+    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 
 /// Supports various api interfaces for testing purposes.
@@ -318,6 +325,31 @@ impl ChangeSetReader for NoopProvider {
     }
 }
 
+impl AccountHistoryReader for NoopProvider {
+    fn account_history(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl StorageChangeSetReader for NoopProvider {
+    fn storage_block_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl StateReader for NoopProvider {
+    fn execution_outcome(&self, _block: BlockNumber) -> ProviderResult<Option<ExecutionOutcome>> {
+        Ok(None)
+    }
+}
+
 impl StateRootProvider for NoopProvider {
     fn hashed_state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(B256::default())
@@ -376,6 +408,18 @@ impl StateProofProvider for NoopProvider {
     }
 }
 
+impl StorageRangeProvider for NoopProvider {
+    fn hashed_storage_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _address: Address,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        Ok(StorageRange::default())
+    }
+}
+
 impl StateProvider for NoopProvider {
     fn storage(
         &self,
@@ -531,6 +575,63 @@ impl RequestsProvider for NoopProvider {
     }
 }
 
+impl InternalTransfersProvider for NoopProvider {
+    fn internal_transfers_by_block(
+        &self,
+        _id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<Vec<reth_primitives::InternalTransfer>>>> {
+        Ok(None)
+    }
+}
+
+impl StateDiffProvider for NoopProvider {
+    fn state_diff_by_block(
+        &self,
+        _id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<reth_execution_types::StateDiff>> {
+        Ok(None)
+    }
+}
+
+impl ReorgJournalReader for NoopProvider {
+    fn latest_reorgs(
+        &self,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(u64, reth_primitives::ReorgRecord)>> {
+        Ok(Vec::new())
+    }
+}
+
+impl LogIndexProvider for NoopProvider {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn blocks_with_log_address(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn blocks_with_log_topic(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+}
+
+impl LogsProvider for NoopProvider {}
+
+impl ForkBlockReader for NoopProvider {
+    fn fork_block(&self, _hash: BlockHash) -> ProviderResult<Option<ExecutedBlock>> {
+        Ok(None)
+    }
+}
+
 impl PruneCheckpointReader for NoopProvider {
     fn get_prune_checkpoint(
         &self,
@@ -556,6 +657,12 @@ impl CanonStateSubscriptions for NoopProvider {
     }
 }
 
+impl StageCheckpointSubscriptions for NoopProvider {
+    fn subscribe_to_stage_checkpoints(&self) -> StageCheckpointNotifications {
+        broadcast::channel(1).1
+    }
+}
+
 impl ForkChoiceSubscriptions for NoopProvider {
     fn subscribe_to_safe_block(&self) -> ForkChoiceNotifications {
         let (_, rx) = watch::channel(None);
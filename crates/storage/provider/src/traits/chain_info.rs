@@ -11,6 +11,13 @@ pub trait CanonChainTracker: Send + Sync {
     /// ([`CanonChainTracker::on_forkchoice_update_received`])
     fn last_received_update_timestamp(&self) -> Option<Instant>;
 
+    /// Notify the tracker that the forkchoice update that made the current canonical head the
+    /// head was acknowledged.
+    fn on_forkchoice_update_acknowledged(&self);
+
+    /// Notify the tracker about a received payload with the given timestamp.
+    fn on_new_payload_received(&self, timestamp: u64);
+
     /// Notify the tracker about a transition configuration exchange.
     fn on_transition_configuration_exchanged(&self);
 
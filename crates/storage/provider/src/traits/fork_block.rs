@@ -0,0 +1,29 @@
+use reth_chain_state::ExecutedBlock;
+use reth_errors::ProviderResult;
+use reth_primitives::{BlockHash, BlockNumber};
+
+/// Functionality to read validated blocks that are not part of the canonical chain from the
+/// database.
+pub trait ForkBlockReader: Send + Sync {
+    /// Returns the fork block with the given hash, together with its execution outcome and trie
+    /// updates.
+    ///
+    /// Returns `None` if the block is not stored, either because it was never validated, or
+    /// because it was too far below the canonical tip to be kept.
+    fn fork_block(&self, hash: BlockHash) -> ProviderResult<Option<ExecutedBlock>>;
+}
+
+/// Functionality to write validated blocks that are not part of the canonical chain to the
+/// database.
+pub trait ForkBlockWriter: Send + Sync {
+    /// Saves the given fork blocks, together with their execution outcome and trie updates.
+    fn insert_fork_blocks(
+        &self,
+        blocks: impl IntoIterator<Item = ExecutedBlock>,
+    ) -> ProviderResult<()>;
+
+    /// Removes all fork blocks below the given block number.
+    ///
+    /// Returns the number of removed blocks.
+    fn remove_fork_blocks_below(&self, block_number: BlockNumber) -> ProviderResult<usize>;
+}
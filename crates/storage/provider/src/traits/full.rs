@@ -2,8 +2,10 @@
 
 use crate::{
     AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, StageCheckpointReader, StateProviderFactory,
-    StaticFileProviderFactory, TransactionsProvider,
+    EvmEnvProvider, HeaderProvider, InternalTransfersProvider, LogIndexProvider, LogsProvider,
+    PruneCheckpointReader, ReorgJournalReader, StageCheckpointReader, StageCheckpointSubscriptions,
+    StateDiffProvider, StateProviderFactory, StateReader, StaticFileProviderFactory,
+    TransactionsProvider,
 };
 use reth_chain_state::CanonStateSubscriptions;
 use reth_db_api::database::Database;
@@ -20,6 +22,14 @@ pub trait FullProvider<DB: Database>:
     + ChangeSetReader
     + CanonStateSubscriptions
     + StageCheckpointReader
+    + StageCheckpointSubscriptions
+    + StateReader
+    + InternalTransfersProvider
+    + LogIndexProvider
+    + LogsProvider
+    + PruneCheckpointReader
+    + ReorgJournalReader
+    + StateDiffProvider
     + Clone
     + Unpin
     + 'static
@@ -37,6 +47,14 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + ChangeSetReader
         + CanonStateSubscriptions
         + StageCheckpointReader
+        + StageCheckpointSubscriptions
+        + StateReader
+        + InternalTransfersProvider
+        + LogIndexProvider
+        + LogsProvider
+        + PruneCheckpointReader
+        + ReorgJournalReader
+        + StateDiffProvider
         + Clone
         + Unpin
         + 'static
@@ -53,6 +71,14 @@ pub trait FullRpcProvider:
     + HeaderProvider
     + TransactionsProvider
     + StageCheckpointReader
+    + StageCheckpointSubscriptions
+    + StaticFileProviderFactory
+    + InternalTransfersProvider
+    + LogIndexProvider
+    + LogsProvider
+    + PruneCheckpointReader
+    + ReorgJournalReader
+    + StateDiffProvider
     + Clone
     + Unpin
     + 'static
@@ -67,6 +93,14 @@ impl<T> FullRpcProvider for T where
         + HeaderProvider
         + TransactionsProvider
         + StageCheckpointReader
+        + StageCheckpointSubscriptions
+        + StaticFileProviderFactory
+        + InternalTransfersProvider
+        + LogIndexProvider
+        + LogsProvider
+        + PruneCheckpointReader
+        + ReorgJournalReader
+        + StateDiffProvider
         + Clone
         + Unpin
         + 'static
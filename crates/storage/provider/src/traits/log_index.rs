@@ -0,0 +1,77 @@
+use auto_impl::auto_impl;
+use reth_primitives::{Address, BlockNumber, Receipt, B256};
+use reth_storage_errors::provider::ProviderResult;
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+/// The blocks with logs of each address and topic, to be inserted into the log index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogIndexKeys {
+    /// The numbers of the blocks with logs emitted by each address.
+    pub addresses: BTreeMap<Address, Vec<BlockNumber>>,
+    /// The numbers of the blocks with logs that have each topic, at any position.
+    pub topics: BTreeMap<B256, Vec<BlockNumber>>,
+}
+
+impl LogIndexKeys {
+    /// Adds the logs of the receipts of a block.
+    ///
+    /// Blocks must be added in ascending order.
+    pub fn insert_block<'a>(
+        &mut self,
+        number: BlockNumber,
+        receipts: impl IntoIterator<Item = &'a Receipt>,
+    ) {
+        fn push(blocks: &mut Vec<BlockNumber>, number: BlockNumber) {
+            if blocks.last() != Some(&number) {
+                blocks.push(number);
+            }
+        }
+
+        for log in receipts.into_iter().flat_map(|receipt| &receipt.logs) {
+            push(self.addresses.entry(log.address).or_default(), number);
+            for topic in log.topics() {
+                push(self.topics.entry(*topic).or_default(), number);
+            }
+        }
+    }
+
+    /// Returns `true` if there are no logs.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.topics.is_empty()
+    }
+}
+
+/// Log index writer
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexWriter: Send + Sync {
+    /// Unwind and clear the log indices of the blocks in the range.
+    ///
+    /// Returns the number of addresses and topics that were unwound.
+    fn unwind_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+
+    /// Insert log indices to database. Used inside the `IndexLogs` stage.
+    fn insert_log_indices(&self, keys: LogIndexKeys) -> ProviderResult<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Log;
+
+    #[test]
+    fn collects_blocks_of_addresses_and_topics() {
+        let (address, topic) = (Address::random(), B256::random());
+        let receipt = |topics: Vec<B256>| Receipt {
+            logs: vec![Log::new_unchecked(address, topics, Default::default())],
+            ..Default::default()
+        };
+
+        let mut keys = LogIndexKeys::default();
+        keys.insert_block(1, &[receipt(vec![topic]), receipt(vec![topic, topic])]);
+        keys.insert_block(2, &[]);
+        keys.insert_block(3, &[receipt(Vec::new())]);
+
+        assert_eq!(keys.addresses, BTreeMap::from([(address, vec![1, 3])]));
+        assert_eq!(keys.topics, BTreeMap::from([(topic, vec![1])]));
+    }
+}
@@ -29,6 +29,9 @@ pub use trie::{StorageTrieWriter, TrieWriter};
 mod history;
 pub use history::HistoryWriter;
 
+mod log_index;
+pub use log_index::{LogIndexKeys, LogIndexWriter};
+
 mod database_provider;
 pub use database_provider::DatabaseProviderFactory;
 
@@ -46,3 +49,21 @@ pub use tree_viewer::TreeViewer;
 
 mod finalized_block;
 pub use finalized_block::{FinalizedBlockReader, FinalizedBlockWriter};
+
+mod fork_block;
+pub use fork_block::{ForkBlockReader, ForkBlockWriter};
+
+mod reorg_journal;
+pub use reorg_journal::ReorgJournalWriter;
+
+mod stage_checkpoint;
+pub use stage_checkpoint::{
+    StageCheckpointNotificationSender, StageCheckpointNotifications, StageCheckpointSubscriptions,
+    StageCheckpointUpdate,
+};
+
+mod unwind;
+pub use unwind::{
+    UnwindJournalReader, UnwindJournalWriter, UnwindPreview, UnwindPreviewProvider,
+    UnwindTablePreview,
+};
@@ -0,0 +1,20 @@
+use reth_errors::ProviderResult;
+use reth_primitives::ReorgRecord;
+
+/// Functionality to write the reorgs of the canonical chain observed by the node to the database.
+pub trait ReorgJournalWriter: Send + Sync {
+    /// Appends the reorg to the journal.
+    ///
+    /// Returns the sequence number of the reorg, which is one more than the sequence number of the
+    /// previous reorg.
+    fn append_reorg(&self, reorg: ReorgRecord) -> ProviderResult<u64>;
+
+    /// Replaces the reorg with the given sequence number, e.g. once more of its discarded
+    /// transactions were included again.
+    fn update_reorg(&self, sequence: u64, reorg: ReorgRecord) -> ProviderResult<()>;
+
+    /// Removes the oldest reorgs from the journal, so that at most `max_reorgs` remain.
+    ///
+    /// Returns the number of removed reorgs.
+    fn prune_reorgs(&self, max_reorgs: u64) -> ProviderResult<usize>;
+}
@@ -0,0 +1,26 @@
+use auto_impl::auto_impl;
+use reth_stages_types::{StageCheckpoint, StageId};
+use tokio::sync::broadcast;
+
+/// Type alias for a receiver that receives [`StageCheckpointUpdate`]s.
+pub type StageCheckpointNotifications = broadcast::Receiver<StageCheckpointUpdate>;
+
+/// Type alias for a sender that sends [`StageCheckpointUpdate`]s.
+pub type StageCheckpointNotificationSender = broadcast::Sender<StageCheckpointUpdate>;
+
+/// A stage checkpoint that was committed to the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageCheckpointUpdate {
+    /// The stage whose checkpoint changed.
+    pub stage_id: StageId,
+    /// The new checkpoint of the stage.
+    pub checkpoint: StageCheckpoint,
+}
+
+/// A type that allows to subscribe to the stage checkpoint changes during sync.
+#[auto_impl(&, Arc)]
+pub trait StageCheckpointSubscriptions: Send + Sync {
+    /// Get notified when the checkpoint of a stage is committed, after the stage was executed or
+    /// unwound, or the engine persisted blocks.
+    fn subscribe_to_stage_checkpoints(&self) -> StageCheckpointNotifications;
+}
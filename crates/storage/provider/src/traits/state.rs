@@ -1,5 +1,5 @@
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives::BlockNumber;
+use reth_primitives::{BlockNumber, InternalTransfer};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostStateSorted;
 use revm::db::{
@@ -32,6 +32,17 @@ pub trait StateChangeWriter {
     /// Write state changes to the database.
     fn write_state_changes(&self, changes: StateChangeset) -> ProviderResult<()>;
 
+    /// Write the internal transfers of the transactions of consecutive blocks, starting at
+    /// `first_block`, to the database.
+    ///
+    /// Transactions without internal transfers are skipped, except the last transaction of each
+    /// block, which marks the block as indexed.
+    fn write_internal_transfers(
+        &self,
+        internal_transfers: Vec<Vec<Vec<InternalTransfer>>>,
+        first_block: BlockNumber,
+    ) -> ProviderResult<()>;
+
     /// Writes the hashed state changes to the database
     fn write_hashed_state(&self, hashed_state: &HashedPostStateSorted) -> ProviderResult<()>;
 }
@@ -0,0 +1,88 @@
+use reth_errors::ProviderResult;
+use reth_primitives::BlockNumber;
+use std::{fmt, ops::RangeInclusive};
+
+/// Functionality to preview the data that is removed by an unwind.
+pub trait UnwindPreviewProvider: Send + Sync {
+    /// Returns a preview of the data that would be removed from the database by unwinding the
+    /// given range of blocks.
+    fn unwind_preview(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<UnwindPreview>;
+}
+
+/// Functionality to read the unwind journal from the database.
+pub trait UnwindJournalReader: Send + Sync {
+    /// Returns the target of an unwind that was started but not finished.
+    ///
+    /// If no unwind is in progress, this returns `None`.
+    fn unwind_journal_target(&self) -> ProviderResult<Option<BlockNumber>>;
+}
+
+/// Functionality to write the unwind journal to the database.
+///
+/// The journal records the target of an unwind before any data is removed, so that an unwind that
+/// is performed in multiple transactions can be resumed after a crash.
+pub trait UnwindJournalWriter: Send + Sync {
+    /// Records that an unwind to the given block was started.
+    fn save_unwind_journal_target(&self, target: BlockNumber) -> ProviderResult<()>;
+
+    /// Removes the unwind journal entry, marking the unwind as finished.
+    fn clear_unwind_journal(&self) -> ProviderResult<()>;
+}
+
+/// A preview of the data that is removed by an unwind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnwindPreview {
+    /// The range of blocks that are unwound.
+    pub range: RangeInclusive<BlockNumber>,
+    /// The number of transactions in the unwound blocks.
+    pub transactions: u64,
+    /// The database tables that entries are removed from.
+    ///
+    /// The changeset tables are also used to revert the plain state, hashed state, history
+    /// indices and trie tables.
+    pub tables: Vec<UnwindTablePreview>,
+}
+
+impl UnwindPreview {
+    /// Returns the number of unwound blocks.
+    pub const fn blocks(&self) -> u64 {
+        self.range.end().saturating_sub(*self.range.start()) + 1
+    }
+
+    /// Returns the total number of removed table entries.
+    pub fn entries(&self) -> usize {
+        self.tables.iter().map(|table| table.entries).sum()
+    }
+
+    /// Returns the total size of the removed table entries in bytes.
+    pub fn bytes(&self) -> u64 {
+        self.tables.iter().map(|table| table.bytes).sum()
+    }
+}
+
+impl fmt::Display for UnwindPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "blocks {}..={} ({} blocks, {} transactions), {} entries ({} bytes) in {} tables",
+            self.range.start(),
+            self.range.end(),
+            self.blocks(),
+            self.transactions,
+            self.entries(),
+            self.bytes(),
+            self.tables.len()
+        )
+    }
+}
+
+/// The entries of a single database table that are removed by an unwind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnwindTablePreview {
+    /// The name of the table.
+    pub table: &'static str,
+    /// The number of removed entries.
+    pub entries: usize,
+    /// The size of the removed entries in bytes, as stored in the database.
+    pub bytes: u64,
+}
@@ -0,0 +1,630 @@
+//! Consistency checks of the canonical chain across the database, the static files and the
+//! in-memory state.
+
+use crate::{
+    providers::ConsistentProvider, BlockHashReader, BlockNumReader, BlockReader, HeaderProvider,
+    ProviderFactory, PruneCheckpointReader, ReceiptProvider, RequestsProvider,
+    StageCheckpointReader, TransactionsProvider, WithdrawalsProvider,
+};
+use reth_chain_state::CanonicalInMemoryState;
+use reth_db_api::database::Database;
+use reth_primitives::{
+    proofs::{calculate_requests_root, calculate_withdrawals_root},
+    BlockHashOrNumber, BlockNumber, TxNumber, B256,
+};
+use reth_prune_types::PruneSegment;
+use reth_stages_types::StageId;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{fmt, ops::RangeInclusive};
+
+/// The default number of persisted blocks below the in-memory chain that are checked by
+/// [`ConsistencyChecker::check_tip`], which is where torn writes after a crash are expected.
+pub const DEFAULT_TIP_CHECK_DEPTH: u64 = 64;
+
+/// An inconsistency between the data of the canonical chain in different tables, static file
+/// segments or the in-memory state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inconsistency {
+    /// The header of a block below the headers stage checkpoint is missing.
+    MissingHeader {
+        /// The number of the block.
+        block: BlockNumber,
+    },
+    /// The canonical hash of a block doesn't match the hash of its header.
+    CanonicalHashMismatch {
+        /// The number of the block.
+        block: BlockNumber,
+        /// The stored canonical hash.
+        canonical: B256,
+        /// The hash of the stored header.
+        header: B256,
+    },
+    /// The parent hash of a block doesn't match the hash of the previous canonical block.
+    ParentHashMismatch {
+        /// The number of the block.
+        block: BlockNumber,
+        /// The parent hash of the block.
+        parent_hash: B256,
+        /// The hash of the previous canonical block.
+        expected: B256,
+    },
+    /// The body indices of a block below the bodies stage checkpoint are missing.
+    MissingBodyIndices {
+        /// The number of the block.
+        block: BlockNumber,
+    },
+    /// The transactions of a block don't directly follow the transactions of the previous block.
+    TxNumberGap {
+        /// The number of the block.
+        block: BlockNumber,
+        /// The number of the first transaction of the block.
+        first_tx: TxNumber,
+        /// The number of the transaction after the last transaction of the previous block.
+        expected: TxNumber,
+    },
+    /// The number of stored transactions of a block doesn't match its body indices.
+    TransactionCountMismatch {
+        /// The number of the block.
+        block: BlockNumber,
+        /// The number of transactions of the body indices.
+        expected: u64,
+        /// The number of stored transactions.
+        found: u64,
+    },
+    /// The number of stored receipts of a block doesn't match its number of transactions.
+    ReceiptCountMismatch {
+        /// The number of the block.
+        block: BlockNumber,
+        /// The number of transactions of the block.
+        expected: u64,
+        /// The number of stored receipts.
+        found: u64,
+    },
+    /// The number of senders of an in-memory block doesn't match its number of transactions.
+    SenderCountMismatch {
+        /// The number of the block.
+        block: BlockNumber,
+        /// The number of transactions of the block.
+        expected: u64,
+        /// The number of senders.
+        found: u64,
+    },
+    /// The in-memory chain doesn't connect to the persisted chain.
+    InMemoryGap {
+        /// The number of the last persisted block.
+        last_persisted: BlockNumber,
+        /// The number of the first in-memory block.
+        first_in_memory: BlockNumber,
+    },
+    /// The withdrawals root of the header of a block doesn't match its stored withdrawals.
+    WithdrawalsRootMismatch {
+        /// The number of the block.
+        block: BlockNumber,
+        /// The withdrawals root of the header.
+        header: Option<B256>,
+        /// The root of the stored withdrawals.
+        computed: Option<B256>,
+    },
+    /// The requests root of the header of a block doesn't match its stored requests.
+    RequestsRootMismatch {
+        /// The number of the block.
+        block: BlockNumber,
+        /// The requests root of the header.
+        header: Option<B256>,
+        /// The root of the stored requests.
+        computed: Option<B256>,
+    },
+}
+
+impl Inconsistency {
+    /// Returns the number of the block that is inconsistent.
+    ///
+    /// If the in-memory chain doesn't connect to the persisted chain, this is the first in-memory
+    /// block.
+    pub const fn block(&self) -> BlockNumber {
+        match self {
+            Self::MissingHeader { block } |
+            Self::CanonicalHashMismatch { block, .. } |
+            Self::ParentHashMismatch { block, .. } |
+            Self::MissingBodyIndices { block } |
+            Self::TxNumberGap { block, .. } |
+            Self::TransactionCountMismatch { block, .. } |
+            Self::ReceiptCountMismatch { block, .. } |
+            Self::SenderCountMismatch { block, .. } |
+            Self::WithdrawalsRootMismatch { block, .. } |
+            Self::RequestsRootMismatch { block, .. } => *block,
+            Self::InMemoryGap { first_in_memory, .. } => *first_in_memory,
+        }
+    }
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader { block } => write!(f, "header of block {block} is missing"),
+            Self::CanonicalHashMismatch { block, canonical, header } => write!(
+                f,
+                "canonical hash {canonical} of block {block} doesn't match header hash {header}"
+            ),
+            Self::ParentHashMismatch { block, parent_hash, expected } => write!(
+                f,
+                "parent hash {parent_hash} of block {block} doesn't match the hash {expected} of \
+                 the previous block"
+            ),
+            Self::MissingBodyIndices { block } => {
+                write!(f, "body indices of block {block} are missing")
+            }
+            Self::TxNumberGap { block, first_tx, expected } => write!(
+                f,
+                "first transaction {first_tx} of block {block} doesn't follow the previous block, \
+                 expected {expected}"
+            ),
+            Self::TransactionCountMismatch { block, expected, found } => {
+                write!(f, "block {block} has {found} transactions, expected {expected}")
+            }
+            Self::ReceiptCountMismatch { block, expected, found } => {
+                write!(f, "block {block} has {found} receipts, expected {expected}")
+            }
+            Self::SenderCountMismatch { block, expected, found } => {
+                write!(f, "block {block} has {found} senders, expected {expected}")
+            }
+            Self::InMemoryGap { last_persisted, first_in_memory } => write!(
+                f,
+                "first in-memory block {first_in_memory} doesn't follow last persisted block \
+                 {last_persisted}"
+            ),
+            Self::WithdrawalsRootMismatch { block, header, computed } => write!(
+                f,
+                "withdrawals root {header:?} of block {block} doesn't match the root {computed:?} \
+                 of the stored withdrawals"
+            ),
+            Self::RequestsRootMismatch { block, header, computed } => write!(
+                f,
+                "requests root {header:?} of block {block} doesn't match the root {computed:?} of \
+                 the stored requests"
+            ),
+        }
+    }
+}
+
+/// The result of a consistency check.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// The persisted blocks that were checked, if any.
+    pub persisted: Option<RangeInclusive<BlockNumber>>,
+    /// The number of in-memory blocks that were checked.
+    pub in_memory: usize,
+    /// The inconsistencies that were found, in the order they were found.
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl ConsistencyReport {
+    /// Returns `true` if no inconsistencies were found.
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+
+    /// Returns the lowest number of an inconsistent block, if any.
+    pub fn first_inconsistent_block(&self) -> Option<BlockNumber> {
+        self.inconsistencies.iter().map(Inconsistency::block).min()
+    }
+}
+
+/// Cross-validates the data of the canonical chain of a [`ConsistentProvider`].
+///
+/// The persisted blocks are checked for:
+/// * header continuity: every header links to the previous one and matches its canonical hash.
+/// * transaction counts: the transactions of every block follow the previous block, and the stored
+///   transactions match the body indices.
+/// * receipt counts: every executed block has a receipt for each transaction, unless the receipts
+///   were pruned.
+///
+/// Each kind of data is only checked up to the checkpoint of the stage that writes it, so that a
+/// node that is still syncing is consistent. The in-memory blocks are checked to connect to the
+/// persisted chain and to have senders and receipts for each transaction.
+#[derive(Debug)]
+pub struct ConsistencyChecker<'a, DB: Database> {
+    view: &'a ConsistentProvider<DB>,
+}
+
+impl<'a, DB: Database> ConsistencyChecker<'a, DB> {
+    /// Creates a new checker of the given view.
+    pub const fn new(view: &'a ConsistentProvider<DB>) -> Self {
+        Self { view }
+    }
+
+    /// Checks the last `depth` persisted blocks of the database, before any blocks are in memory.
+    ///
+    /// This is fast enough to run at startup, to detect torn writes after a crash.
+    pub fn check_persisted_tip(
+        factory: &ProviderFactory<DB>,
+        depth: u64,
+    ) -> ProviderResult<ConsistencyReport> {
+        let view = ConsistentProvider::new(factory, &CanonicalInMemoryState::empty())?;
+        ConsistencyChecker::new(&view).check_tip(depth)
+    }
+
+    /// Checks the last `depth` persisted blocks and the in-memory blocks.
+    pub fn check_tip(&self, depth: u64) -> ProviderResult<ConsistencyReport> {
+        let last_persisted = self.view.database().last_block_number()?;
+        self.check(last_persisted.saturating_sub(depth.saturating_sub(1))..=last_persisted)
+    }
+
+    /// Checks the persisted blocks of the range and the in-memory blocks.
+    pub fn check(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<ConsistencyReport> {
+        let mut report = ConsistencyReport::default();
+        self.check_persisted(range, &mut report)?;
+        self.check_in_memory(&mut report)?;
+        Ok(report)
+    }
+
+    fn check_persisted(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        report: &mut ConsistencyReport,
+    ) -> ProviderResult<()> {
+        let db = self.view.database();
+        let (start, end) = range.into_inner();
+        let end = end.min(db.last_block_number()?);
+        if start > end {
+            return Ok(())
+        }
+        report.persisted = Some(start..=end);
+
+        let checkpoint = |stage| -> ProviderResult<BlockNumber> {
+            Ok(db.get_stage_checkpoint(stage)?.unwrap_or_default().block_number)
+        };
+
+        self.check_headers(start..=end.min(checkpoint(StageId::Headers)?), report)?;
+
+        // the genesis block is never executed, and the receipts below the prune checkpoints may be
+        // (partially) removed
+        let mut receipts_start = start.max(1);
+        for segment in [PruneSegment::Receipts, PruneSegment::ContractLogs] {
+            if let Some(pruned) = db.get_prune_checkpoint(segment)?.and_then(|cp| cp.block_number) {
+                receipts_start = receipts_start.max(pruned + 1);
+            }
+        }
+        let receipts = receipts_start..=end.min(checkpoint(StageId::Execution)?);
+        self.check_bodies(start..=end.min(checkpoint(StageId::Bodies)?), receipts, report)
+    }
+
+    /// Checks that the headers of the range match their canonical hashes and link to each other.
+    fn check_headers(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        report: &mut ConsistencyReport,
+    ) -> ProviderResult<()> {
+        let db = self.view.database();
+        let mut parent_hash = match range.start().checked_sub(1) {
+            Some(parent) => db.block_hash(parent)?,
+            None => None,
+        };
+
+        for block in range {
+            let canonical = db.block_hash(block)?;
+            let Some(header) = db.header_by_number(block)? else {
+                report.inconsistencies.push(Inconsistency::MissingHeader { block });
+                parent_hash = canonical;
+                continue
+            };
+
+            // the hash is recomputed, since static files store it next to the header
+            let hash = header.hash_slow();
+            if let Some(canonical) = canonical.filter(|canonical| *canonical != hash) {
+                report.inconsistencies.push(Inconsistency::CanonicalHashMismatch {
+                    block,
+                    canonical,
+                    header: hash,
+                });
+            }
+            if let Some(expected) = parent_hash.filter(|expected| *expected != header.parent_hash) {
+                report.inconsistencies.push(Inconsistency::ParentHashMismatch {
+                    block,
+                    parent_hash: header.parent_hash,
+                    expected,
+                });
+            }
+            parent_hash = Some(hash);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the transactions of the blocks of the range are contiguous and stored, and
+    /// that the blocks of `receipts` have a receipt for each transaction.
+    fn check_bodies(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        receipts: RangeInclusive<BlockNumber>,
+        report: &mut ConsistencyReport,
+    ) -> ProviderResult<()> {
+        let db = self.view.database();
+        let mut next_tx = match range.start().checked_sub(1) {
+            Some(parent) => db.block_body_indices(parent)?.map(|indices| indices.next_tx_num()),
+            None => Some(0),
+        };
+
+        for block in range {
+            let Some(indices) = db.block_body_indices(block)? else {
+                report.inconsistencies.push(Inconsistency::MissingBodyIndices { block });
+                next_tx = None;
+                continue
+            };
+
+            if let Some(expected) = next_tx.filter(|expected| *expected != indices.first_tx_num) {
+                report.inconsistencies.push(Inconsistency::TxNumberGap {
+                    block,
+                    first_tx: indices.first_tx_num,
+                    expected,
+                });
+            }
+            next_tx = Some(indices.next_tx_num());
+
+            if indices.tx_count == 0 {
+                continue
+            }
+
+            let found = db.transactions_by_tx_range(indices.tx_num_range())?.len() as u64;
+            if found != indices.tx_count {
+                report.inconsistencies.push(Inconsistency::TransactionCountMismatch {
+                    block,
+                    expected: indices.tx_count,
+                    found,
+                });
+            }
+
+            if receipts.contains(&block) {
+                let found = db.receipts_by_tx_range(indices.tx_num_range())?.len() as u64;
+                if found != indices.tx_count {
+                    report.inconsistencies.push(Inconsistency::ReceiptCountMismatch {
+                        block,
+                        expected: indices.tx_count,
+                        found,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the in-memory blocks connect to the persisted chain and to each other, and
+    /// that they have senders and receipts for each transaction.
+    fn check_in_memory(&self, report: &mut ConsistencyReport) -> ProviderResult<()> {
+        let in_memory = self.view.in_memory();
+        let Some(first) = in_memory.first() else { return Ok(()) };
+
+        let db = self.view.database();
+        let last_persisted = db.last_block_number()?;
+        let first_in_memory = first.block().number;
+        if first_in_memory > last_persisted + 1 {
+            report
+                .inconsistencies
+                .push(Inconsistency::InMemoryGap { last_persisted, first_in_memory });
+        }
+
+        // blocks that were persisted but not yet removed from memory overlap with the database,
+        // and the first in-memory block of a reorg follows a block below the persisted tip
+        let mut parent_hash = match first_in_memory.checked_sub(1) {
+            Some(parent) if parent <= last_persisted => db.block_hash(parent)?,
+            _ => None,
+        };
+
+        for executed in in_memory.iter() {
+            report.in_memory += 1;
+
+            let block = executed.block();
+            if let Some(expected) = parent_hash.filter(|expected| *expected != block.parent_hash) {
+                report.inconsistencies.push(Inconsistency::ParentHashMismatch {
+                    block: block.number,
+                    parent_hash: block.parent_hash,
+                    expected,
+                });
+            }
+            parent_hash = Some(block.hash());
+
+            let expected = block.body.len() as u64;
+            let senders = executed.senders().len() as u64;
+            if senders != expected {
+                report.inconsistencies.push(Inconsistency::SenderCountMismatch {
+                    block: block.number,
+                    expected,
+                    found: senders,
+                });
+            }
+            let receipts =
+                executed.execution_outcome().receipts_by_block(block.number).len() as u64;
+            if receipts != expected {
+                report.inconsistencies.push(Inconsistency::ReceiptCountMismatch {
+                    block: block.number,
+                    expected,
+                    found: receipts,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Recomputes the roots of the header of a block from the stored data of the block.
+///
+/// The data is read through the provider, so [`BlockchainProvider2`](crate::BlockchainProvider2)
+/// checks the in-memory blocks as well as the database, and a
+/// [`DatabaseProvider`](crate::DatabaseProvider) only the database.
+pub trait BlockRootsVerifier: HeaderProvider + WithdrawalsProvider + RequestsProvider {
+    /// Recomputes the withdrawals root of the block from its stored withdrawals and compares it
+    /// against the header.
+    ///
+    /// Returns the mismatch, if any, or [`ProviderError::HeaderNotFound`] if the block is not
+    /// stored.
+    fn verify_withdrawals_root(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Inconsistency>> {
+        let header =
+            self.header_by_hash_or_number(block)?.ok_or(ProviderError::HeaderNotFound(block))?;
+        let computed = self
+            .withdrawals_by_block(header.number.into(), header.timestamp)?
+            .map(|withdrawals| calculate_withdrawals_root(&withdrawals));
+        Ok((header.withdrawals_root != computed).then_some(
+            Inconsistency::WithdrawalsRootMismatch {
+                block: header.number,
+                header: header.withdrawals_root,
+                computed,
+            },
+        ))
+    }
+
+    /// Recomputes the requests root of the block from its stored requests and compares it against
+    /// the header.
+    ///
+    /// Returns the mismatch, if any, or [`ProviderError::HeaderNotFound`] if the block is not
+    /// stored.
+    fn verify_requests_root(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Inconsistency>> {
+        let header =
+            self.header_by_hash_or_number(block)?.ok_or(ProviderError::HeaderNotFound(block))?;
+        let computed = self
+            .requests_by_block(header.number.into(), header.timestamp)?
+            .map(|requests| calculate_requests_root(&requests.0));
+        Ok((header.requests_root != computed).then_some(Inconsistency::RequestsRootMismatch {
+            block: header.number,
+            header: header.requests_root,
+            computed,
+        }))
+    }
+}
+
+impl<T> BlockRootsVerifier for T where T: HeaderProvider + WithdrawalsProvider + RequestsProvider {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        providers::BlockchainProvider2,
+        test_utils::{create_test_provider_factory, create_test_provider_factory_with_chain_spec},
+        StageCheckpointWriter,
+    };
+    use reth_chain_state::{ExecutedBlock, NewCanonicalChain};
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_db::tables;
+    use reth_db_api::{models::StoredBlockWithdrawals, transaction::DbTxMut};
+    use reth_primitives::{Requests, Withdrawal, Withdrawals};
+    use reth_stages_types::StageCheckpoint;
+    use reth_testing_utils::generators::{self, random_block_range};
+    use std::sync::Arc;
+
+    #[test]
+    fn check_consistency() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+
+        let database_blocks = random_block_range(&mut rng, 0..=5, B256::ZERO, 1..3);
+        let provider_rw = factory.provider_rw()?;
+        for block in &database_blocks {
+            provider_rw.insert_historical_block(block.clone().seal_with_senders().unwrap())?;
+        }
+        for stage in [StageId::Headers, StageId::Bodies] {
+            provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(5))?;
+        }
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider2::new(factory.clone())?;
+        let in_memory_blocks =
+            random_block_range(&mut rng, 6..=8, database_blocks.last().unwrap().hash(), 0..1);
+        provider.canonical_in_memory_state().update_chain(NewCanonicalChain::Commit {
+            new: in_memory_blocks
+                .into_iter()
+                .map(|block| {
+                    ExecutedBlock::new(
+                        Arc::new(block),
+                        Default::default(),
+                        Default::default(),
+                        Default::default(),
+                        Default::default(),
+                    )
+                })
+                .collect(),
+        });
+
+        let report = provider.check_consistency(DEFAULT_TIP_CHECK_DEPTH)?;
+        assert!(report.is_consistent(), "{:?}", report.inconsistencies);
+        assert_eq!(report.persisted, Some(0..=5));
+        assert_eq!(report.in_memory, 3);
+
+        // remove a transaction of block 3 and the body indices of block 4
+        let provider_rw = factory.provider_rw()?;
+        let first_tx = provider_rw.block_body_indices(3)?.unwrap().first_tx_num;
+        provider_rw.tx_ref().delete::<tables::Transactions>(first_tx, None)?;
+        provider_rw.tx_ref().delete::<tables::BlockBodyIndices>(4, None)?;
+        provider_rw.commit()?;
+
+        let report = provider.check_consistency(3)?;
+        assert_eq!(report.persisted, Some(3..=5));
+        assert_eq!(
+            report.inconsistencies,
+            vec![
+                Inconsistency::TransactionCountMismatch {
+                    block: 3,
+                    expected: database_blocks[3].body.len() as u64,
+                    found: database_blocks[3].body.len() as u64 - 1,
+                },
+                Inconsistency::MissingBodyIndices { block: 4 },
+            ]
+        );
+        assert_eq!(report.first_inconsistent_block(), Some(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn verify_roots() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().prague_activated().build());
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+
+        let mut block = random_block_range(&mut rng, 0..=0, B256::ZERO, 0..1).remove(0).unseal();
+        let withdrawals =
+            Withdrawals::new(vec![Withdrawal { index: 1, amount: 10, ..Default::default() }]);
+        block.header.withdrawals_root = Some(calculate_withdrawals_root(&withdrawals));
+        block.withdrawals = Some(withdrawals);
+        block.header.requests_root = Some(calculate_requests_root(&[]));
+        block.requests = Some(Requests::default());
+
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.insert_historical_block(block.seal_slow().seal_with_senders().unwrap())?;
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider2::new(factory.clone())?;
+        assert_eq!(provider.verify_withdrawals_root(0.into())?, None);
+        assert_eq!(provider.verify_requests_root(0.into())?, None);
+        assert!(provider.verify_withdrawals_root(1.into()).is_err());
+
+        // overwrite the stored withdrawals of the block
+        let corrupted =
+            Withdrawals::new(vec![Withdrawal { index: 1, amount: 20, ..Default::default() }]);
+        let provider_rw = factory.provider_rw()?;
+        let header = provider_rw.header_by_number(0)?.unwrap();
+        provider_rw.tx_ref().put::<tables::BlockWithdrawals>(
+            0,
+            StoredBlockWithdrawals { withdrawals: corrupted.clone() },
+        )?;
+        provider_rw.commit()?;
+
+        assert_eq!(
+            provider.verify_withdrawals_root(0.into())?,
+            Some(Inconsistency::WithdrawalsRootMismatch {
+                block: 0,
+                header: header.withdrawals_root,
+                computed: Some(calculate_withdrawals_root(&corrupted)),
+            })
+        );
+        assert_eq!(provider.verify_requests_root(0.into())?, None);
+
+        Ok(())
+    }
+}
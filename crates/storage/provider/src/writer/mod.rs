@@ -2,7 +2,7 @@ use crate::{
     providers::{StaticFileProvider, StaticFileProviderRWRefMut, StaticFileWriter as SfWriter},
     writer::static_file::StaticFileWriter,
     BlockExecutionWriter, BlockWriter, DatabaseProvider, DatabaseProviderRW, HistoryWriter,
-    StateChangeWriter, StateWriter, TrieWriter,
+    LogIndexKeys, StateChangeWriter, StateWriter, TrieWriter,
 };
 use reth_chain_state::ExecutedBlock;
 use reth_db::{
@@ -167,6 +167,19 @@ where
             )
         };
 
+        // Index the logs of the blocks, if the log index is maintained.
+        self.database().append_maintained_log_indices(first_number..=last_block_number, || {
+            let mut keys = LogIndexKeys::default();
+            for block in blocks {
+                let number = block.block().number;
+                keys.insert_block(
+                    number,
+                    block.execution_outcome().receipts_by_block(number).iter().flatten(),
+                );
+            }
+            keys
+        })?;
+
         // TODO: remove all the clones and do performant / batched writes for each type of object
         // instead of a loop over all blocks,
         // meaning:
@@ -497,6 +510,11 @@ where
 
         self.database().write_state_reverts(reverts, execution_outcome.first_block)?;
 
+        self.database().write_internal_transfers(
+            execution_outcome.internal_transfers,
+            execution_outcome.first_block,
+        )?;
+
         self.append_receipts_from_blocks(
             execution_outcome.first_block,
             execution_outcome.receipts.into_iter(),
@@ -511,7 +529,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{test_utils::create_test_provider_factory, AccountReader, TrieWriter};
+    use crate::{
+        test_utils::{create_test_provider_factory, create_test_provider_factory_in_memory},
+        AccountReader, TrieWriter,
+    };
     use reth_db::tables;
     use reth_db_api::{
         cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
@@ -588,6 +609,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hashed_storages_are_written_to_memory_database() {
+        let provider_factory = create_test_provider_factory_in_memory();
+
+        let wiped_address = B256::random();
+        let updated_address = B256::random();
+        let slots = [B256::with_last_byte(1), B256::with_last_byte(2)];
+        {
+            let provider_rw = provider_factory.provider_rw().unwrap();
+            let mut storage_cursor =
+                provider_rw.tx_ref().cursor_write::<tables::HashedStorages>().unwrap();
+            for hashed_address in [wiped_address, updated_address] {
+                for key in slots {
+                    storage_cursor
+                        .upsert(hashed_address, StorageEntry { key, value: U256::from(1) })
+                        .unwrap();
+                }
+            }
+            drop(storage_cursor);
+            provider_rw.commit().unwrap();
+        }
+
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.storages.insert(wiped_address, HashedStorage::new(true));
+        hashed_state.storages.insert(
+            updated_address,
+            HashedStorage::from_iter(false, [(slots[0], U256::from(2)), (slots[1], U256::ZERO)]),
+        );
+
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        assert_eq!(provider_rw.write_hashed_state(&hashed_state.into_sorted()), Ok(()));
+        provider_rw.commit().unwrap();
+
+        // the duplicate entries of the storages are replaced and removed like on MDBX
+        let provider = provider_factory.provider().unwrap();
+        let mut storage_cursor =
+            provider.tx_ref().cursor_dup_read::<tables::HashedStorages>().unwrap();
+        assert_eq!(
+            storage_cursor
+                .walk_dup(Some(updated_address), None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            vec![(updated_address, StorageEntry { key: slots[0], value: U256::from(2) })]
+        );
+        assert_eq!(storage_cursor.seek_exact(wiped_address), Ok(None));
+    }
+
     #[test]
     fn write_to_db_account_info() {
         let factory = create_test_provider_factory();
@@ -1345,6 +1414,7 @@ mod tests {
             receipts: vec![vec![Some(Receipt::default()); 2]; 7].into(),
             first_block: 10,
             requests: Vec::new(),
+            internal_transfers: Vec::new(),
         };
 
         let mut this = base.clone();
@@ -1562,6 +1632,7 @@ mod tests {
             receipts: vec![vec![Some(Receipt::default()); 2]; 1].into(),
             first_block: 2,
             requests: Vec::new(),
+            internal_transfers: Vec::new(),
         };
 
         test.prepend_state(previous_state);
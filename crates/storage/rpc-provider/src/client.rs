@@ -0,0 +1,171 @@
+//! Blocking JSON-RPC client of a remote node.
+
+use alloy_provider::ReqwestProvider;
+use alloy_rpc_types::{BlockTransactions, TransactionReceipt};
+use alloy_transport::TransportResult;
+use parking_lot::Mutex;
+use reth_primitives::{
+    Address, Block, Bytecode, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    TxType, B256,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use schnellru::{ByLength, LruMap};
+use std::{
+    fmt,
+    future::Future,
+    sync::{mpsc, Arc},
+};
+use url::Url;
+
+/// The maximum number of bytecodes that are kept by a [`RpcClient`].
+const MAX_BYTECODES: u32 = 10_000;
+
+/// A job that is executed on the runtime of the [`RpcClient`].
+type Job = Box<dyn FnOnce(&tokio::runtime::Runtime) + Send>;
+
+/// A blocking JSON-RPC client of a remote node.
+///
+/// Requests are executed on a dedicated thread, so the blocking provider calls are safe to make
+/// from within an async context.
+#[derive(Clone)]
+pub(crate) struct RpcClient {
+    /// URL of the remote node.
+    url: Url,
+    provider: ReqwestProvider,
+    /// Sender of jobs to the thread that runs the requests.
+    jobs: mpsc::Sender<Job>,
+    /// Bytecodes of the accounts that were read, by code hash.
+    ///
+    /// The remote node can't look up bytecodes by their hash, so the bytecodes that are returned
+    /// with accounts are kept, to serve [`StateProvider::bytecode_by_hash`] calls that follow. At
+    /// most [`MAX_BYTECODES`] of the most recently used bytecodes are kept.
+    ///
+    /// [`StateProvider::bytecode_by_hash`]: reth_storage_api::StateProvider::bytecode_by_hash
+    bytecodes: Arc<Mutex<LruMap<B256, Bytecode, ByLength>>>,
+}
+
+impl fmt::Debug for RpcClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcClient").field("url", &self.url).finish_non_exhaustive()
+    }
+}
+
+impl RpcClient {
+    /// Creates a new client of the node at the given HTTP URL.
+    pub(crate) fn new(url: Url) -> ProviderResult<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| ProviderError::Rpc(e.to_string()))?;
+        let (jobs, rx) = mpsc::channel::<Job>();
+        std::thread::Builder::new()
+            .name("rpc-provider".to_string())
+            .spawn(move || {
+                for job in rx {
+                    job(&runtime)
+                }
+            })
+            .map_err(|e| ProviderError::Rpc(e.to_string()))?;
+
+        Ok(Self {
+            provider: ReqwestProvider::new_http(url.clone()),
+            url,
+            jobs,
+            bytecodes: Arc::new(Mutex::new(LruMap::new(ByLength::new(MAX_BYTECODES)))),
+        })
+    }
+
+    /// Returns the URL of the remote node.
+    pub(crate) const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Sends the request that is returned by `request` on the runtime of the client and waits for
+    /// its response.
+    pub(crate) fn request<T, F>(
+        &self,
+        request: impl FnOnce(ReqwestProvider) -> F,
+    ) -> ProviderResult<T>
+    where
+        F: Future<Output = TransportResult<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let fut = request(self.provider.clone());
+        let (tx, rx) = mpsc::sync_channel(1);
+        self.jobs
+            .send(Box::new(move |runtime| {
+                let _ = tx.send(runtime.block_on(fut));
+            }))
+            .map_err(|_| ProviderError::Rpc("rpc provider thread stopped".to_string()))?;
+        rx.recv()
+            .map_err(|_| ProviderError::Rpc("rpc provider thread stopped".to_string()))?
+            .map_err(|e| ProviderError::Rpc(e.to_string()))
+    }
+
+    /// Keeps the bytecode, so it can be looked up by its hash.
+    pub(crate) fn insert_bytecode(&self, code_hash: B256, bytecode: Bytecode) {
+        self.bytecodes.lock().insert(code_hash, bytecode);
+    }
+
+    /// Returns the bytecode with the given hash, if it was returned with an account before.
+    pub(crate) fn bytecode(&self, code_hash: &B256) -> Option<Bytecode> {
+        self.bytecodes.lock().get(code_hash).cloned()
+    }
+}
+
+/// Converts the header of a block returned by the remote node.
+///
+/// The hash that is returned by the remote node is trusted, only the header of a pending block
+/// without a hash is hashed.
+pub(crate) fn sealed_header(header: alloy_rpc_types::Header) -> ProviderResult<SealedHeader> {
+    let hash = header.hash;
+    let header = Header::try_from(header).map_err(|e| ProviderError::Rpc(e.to_string()))?;
+    Ok(match hash {
+        Some(hash) => header.seal(hash),
+        None => header.seal_slow(),
+    })
+}
+
+/// Converts a block with full transactions returned by the remote node.
+///
+/// The senders are taken from the transactions instead of being recovered. The ommers are not
+/// returned by the remote node with the block, so they are empty.
+pub(crate) fn sealed_block_with_senders(
+    block: alloy_rpc_types::Block,
+) -> ProviderResult<SealedBlockWithSenders> {
+    let senders = block_senders(&block);
+    let header = sealed_header(block.header.clone())?;
+    let Block { body, ommers, withdrawals, requests, .. } =
+        Block::try_from(block).map_err(|e| ProviderError::Rpc(e.to_string()))?;
+    let block = SealedBlock { header, body, ommers, withdrawals, requests };
+    SealedBlockWithSenders::new(block, senders)
+        .ok_or_else(|| ProviderError::Rpc("missing transaction senders".to_string()))
+}
+
+/// Converts a transaction receipt returned by the remote node.
+pub(crate) fn receipt(receipt: TransactionReceipt) -> ProviderResult<Receipt> {
+    let tx_type = TxType::try_from(receipt.transaction_type() as u8)
+        .map_err(|e| ProviderError::Rpc(e.to_string()))?;
+    let cumulative_gas_used = receipt
+        .inner
+        .cumulative_gas_used()
+        .try_into()
+        .map_err(|_| ProviderError::Rpc("cumulative gas used overflows u64".to_string()))?;
+    #[allow(clippy::needless_update)]
+    Ok(Receipt {
+        tx_type,
+        success: receipt.status(),
+        cumulative_gas_used,
+        logs: receipt.inner.logs().iter().map(|log| log.inner.clone()).collect(),
+        ..Default::default()
+    })
+}
+
+/// Returns the senders of the transactions of the block, which are the `from` fields of the
+/// transactions returned by the remote node.
+fn block_senders(block: &alloy_rpc_types::Block) -> Vec<Address> {
+    match &block.transactions {
+        BlockTransactions::Full(transactions) => transactions.iter().map(|tx| tx.from).collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,734 @@
+//! Block and state providers backed by a remote node over JSON-RPC.
+//!
+//! The [`RpcBlockchainProvider`] reads blocks, headers, transactions, receipts and state from the
+//! standard `eth_` namespace of a remote node, instead of a local database. This makes it possible
+//! to run components that only need read access to the chain, like the EVM or parts of the RPC,
+//! without a synced node.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod client;
+mod state;
+
+pub use state::RpcStateProvider;
+
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockTransactionsKind, TransactionReceipt};
+use client::RpcClient;
+use reth_chainspec::ChainInfo;
+use reth_db_models::StoredBlockBodyIndices;
+use reth_primitives::{
+    Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
+    BlockNumberOrTag, BlockWithSenders, Header, Receipt, Requests, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+};
+use reth_storage_api::{
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    HeaderProvider, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider, StateProviderBox,
+    StateProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+use url::Url;
+
+/// A provider of the blocks and state of a remote node, that reads them over JSON-RPC.
+///
+/// Blocks are looked up with `eth_getBlockByNumber` and `eth_getBlockByHash`, and state with
+/// `eth_getBalance`, `eth_getTransactionCount`, `eth_getCode` and `eth_getStorageAt`, see
+/// [`RpcStateProvider`]. The remote node doesn't expose the internal transaction numbers and block
+/// body indices of its database, so the methods that look up data by transaction number return
+/// [`ProviderError::UnsupportedProvider`].
+///
+/// Calls block until the remote node responds, but they are made from a dedicated thread, so the
+/// provider can be used from within an async context.
+#[derive(Debug, Clone)]
+pub struct RpcBlockchainProvider {
+    client: RpcClient,
+}
+
+impl RpcBlockchainProvider {
+    /// Creates a new provider of the node at the given HTTP URL.
+    pub fn new(url: Url) -> ProviderResult<Self> {
+        Ok(Self { client: RpcClient::new(url)? })
+    }
+
+    /// Returns the URL of the remote node.
+    pub const fn url(&self) -> &Url {
+        self.client.url()
+    }
+
+    /// Returns the client of the remote node.
+    pub(crate) const fn client(&self) -> &RpcClient {
+        &self.client
+    }
+
+    /// Returns the block with the given id, with full transactions or only their hashes.
+    fn rpc_block(
+        &self,
+        id: BlockId,
+        kind: BlockTransactionsKind,
+    ) -> ProviderResult<Option<alloy_rpc_types::Block>> {
+        self.client.request(move |provider| async move { provider.get_block(id, kind).await })
+    }
+
+    /// Returns the blocks in the given range, with full transactions or only their hashes.
+    ///
+    /// The blocks are requested concurrently, and only the blocks before the first block that
+    /// doesn't exist are returned.
+    fn rpc_blocks(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        kind: BlockTransactionsKind,
+    ) -> ProviderResult<Vec<alloy_rpc_types::Block>> {
+        let blocks = self.client.request(move |provider| async move {
+            futures::future::try_join_all(range.map(|number| {
+                let provider = provider.clone();
+                async move { provider.get_block(number.into(), kind).await }
+            }))
+            .await
+        })?;
+        Ok(blocks.into_iter().map_while(|block| block).collect())
+    }
+
+    /// Returns the sealed header of the block with the given id.
+    fn rpc_header(&self, id: BlockId) -> ProviderResult<Option<SealedHeader>> {
+        self.rpc_block(id, BlockTransactionsKind::Hashes)?
+            .map(|block| client::sealed_header(block.header))
+            .transpose()
+    }
+
+    /// Returns the block with the given id, with its ommers.
+    fn rpc_sealed_block_with_senders(
+        &self,
+        id: BlockId,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        let Some(block) = self.rpc_block(id, BlockTransactionsKind::Full)? else { return Ok(None) };
+        let ommers = self.rpc_ommers(&block)?;
+        let mut block = client::sealed_block_with_senders(block)?;
+        block.block.ommers = ommers;
+        Ok(Some(block))
+    }
+
+    /// Returns the ommers of the block, which are only returned by hash with the block.
+    fn rpc_ommers(&self, block: &alloy_rpc_types::Block) -> ProviderResult<Vec<Header>> {
+        if block.uncles.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let block_id = match block.header.hash {
+            Some(hash) => BlockId::hash(hash),
+            None => BlockId::pending(),
+        };
+        let count = block.uncles.len() as u64;
+        let ommers = self.client.request(move |provider| async move {
+            futures::future::try_join_all((0..count).map(|index| {
+                let provider = provider.clone();
+                async move { provider.get_uncle(block_id, index).await }
+            }))
+            .await
+        })?;
+        ommers
+            .into_iter()
+            .map(|ommer| {
+                let ommer = ommer.ok_or_else(|| ProviderError::Rpc("missing ommer".to_string()))?;
+                Header::try_from(ommer.header).map_err(|e| ProviderError::Rpc(e.to_string()))
+            })
+            .collect()
+    }
+
+    /// Returns the receipts of the block with the given id.
+    fn rpc_receipts(&self, id: BlockId) -> ProviderResult<Option<Vec<Receipt>>> {
+        let receipts = self.client.request(move |provider| async move {
+            provider
+                .raw_request::<_, Option<Vec<TransactionReceipt>>>(
+                    "eth_getBlockReceipts".into(),
+                    (id,),
+                )
+                .await
+        })?;
+        receipts.map(|receipts| receipts.into_iter().map(client::receipt).collect()).transpose()
+    }
+
+    /// Converts the range bounds to an inclusive range, that ends at the latest block if the range
+    /// is unbounded.
+    fn to_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<RangeInclusive<BlockNumber>> {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&0) => return Ok(RangeInclusive::new(1, 0)),
+            Bound::Excluded(&n) => n - 1,
+            Bound::Unbounded => self.best_block_number()?,
+        };
+        Ok(start..=end)
+    }
+}
+
+/// Converts the block hash or number to a [`BlockId`].
+const fn block_id(id: BlockHashOrNumber) -> BlockId {
+    match id {
+        BlockHashOrNumber::Hash(hash) => BlockId::hash(hash),
+        BlockHashOrNumber::Number(number) => BlockId::number(number),
+    }
+}
+
+impl BlockHashReader for RpcBlockchainProvider {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        Ok(self.rpc_header(number.into())?.map(|header| header.hash()))
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        if start >= end {
+            return Ok(Vec::new())
+        }
+        self.rpc_blocks(start..=end - 1, BlockTransactionsKind::Hashes)?
+            .into_iter()
+            .map(|block| Ok(client::sealed_header(block.header)?.hash()))
+            .collect()
+    }
+}
+
+impl BlockNumReader for RpcBlockchainProvider {
+    fn chain_info(&self) -> ProviderResult<ChainInfo> {
+        let header = self
+            .rpc_header(BlockNumberOrTag::Latest.into())?
+            .ok_or_else(|| ProviderError::Rpc("missing latest block".to_string()))?;
+        Ok(ChainInfo { best_hash: header.hash(), best_number: header.number })
+    }
+
+    fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.client.request(|provider| async move { provider.get_block_number().await })
+    }
+
+    fn last_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.best_block_number()
+    }
+
+    fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.rpc_header(hash.into())?.map(|header| header.number))
+    }
+}
+
+impl BlockIdReader for RpcBlockchainProvider {
+    fn pending_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        Ok(self.rpc_header(BlockId::pending())?.map(|header| header.num_hash()))
+    }
+
+    fn safe_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        Ok(self.rpc_header(BlockNumberOrTag::Safe.into())?.map(|header| header.num_hash()))
+    }
+
+    fn finalized_block_num_hash(&self) -> ProviderResult<Option<BlockNumHash>> {
+        Ok(self.rpc_header(BlockNumberOrTag::Finalized.into())?.map(|header| header.num_hash()))
+    }
+}
+
+impl HeaderProvider for RpcBlockchainProvider {
+    fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
+        Ok(self.rpc_header((*block_hash).into())?.map(SealedHeader::unseal))
+    }
+
+    fn header_by_number(&self, num: u64) -> ProviderResult<Option<Header>> {
+        Ok(self.rpc_header(num.into())?.map(SealedHeader::unseal))
+    }
+
+    fn header_td(&self, hash: &BlockHash) -> ProviderResult<Option<U256>> {
+        Ok(self
+            .rpc_block((*hash).into(), BlockTransactionsKind::Hashes)?
+            .and_then(|block| block.header.total_difficulty))
+    }
+
+    fn header_td_by_number(&self, number: BlockNumber) -> ProviderResult<Option<U256>> {
+        Ok(self
+            .rpc_block(number.into(), BlockTransactionsKind::Hashes)?
+            .and_then(|block| block.header.total_difficulty))
+    }
+
+    fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> ProviderResult<Vec<Header>> {
+        Ok(self.sealed_headers_range(range)?.into_iter().map(SealedHeader::unseal).collect())
+    }
+
+    fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>> {
+        self.rpc_header(number.into())
+    }
+
+    fn sealed_headers_while(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+        mut predicate: impl FnMut(&SealedHeader) -> bool,
+    ) -> ProviderResult<Vec<SealedHeader>> {
+        let range = self.to_range(range)?;
+        let mut headers = Vec::new();
+        for block in self.rpc_blocks(range, BlockTransactionsKind::Hashes)? {
+            let header = client::sealed_header(block.header)?;
+            if !predicate(&header) {
+                break
+            }
+            headers.push(header);
+        }
+        Ok(headers)
+    }
+}
+
+impl TransactionsProvider for RpcBlockchainProvider {
+    fn transaction_id(&self, _tx_hash: TxHash) -> ProviderResult<Option<TxNumber>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn transaction_by_id(&self, _id: TxNumber) -> ProviderResult<Option<TransactionSigned>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn transaction_by_id_no_hash(
+        &self,
+        _id: TxNumber,
+    ) -> ProviderResult<Option<TransactionSignedNoHash>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<TransactionSigned>> {
+        let transaction = self
+            .client
+            .request(move |provider| async move { provider.get_transaction_by_hash(hash).await })?;
+        transaction
+            .map(|tx| {
+                TransactionSigned::try_from(tx).map_err(|e| ProviderError::Rpc(e.to_string()))
+            })
+            .transpose()
+    }
+
+    fn transaction_by_hash_with_meta(
+        &self,
+        hash: TxHash,
+    ) -> ProviderResult<Option<(TransactionSigned, TransactionMeta)>> {
+        let Some(transaction) = self
+            .client
+            .request(move |provider| async move { provider.get_transaction_by_hash(hash).await })?
+        else {
+            return Ok(None)
+        };
+        // pending transactions are not mined in a block yet
+        let (Some(block_hash), Some(index)) =
+            (transaction.block_hash, transaction.transaction_index)
+        else {
+            return Ok(None)
+        };
+        let Some(header) = self.rpc_header(block_hash.into())? else { return Ok(None) };
+
+        let meta = TransactionMeta {
+            tx_hash: transaction.hash,
+            index,
+            block_hash,
+            block_number: header.number,
+            base_fee: header.base_fee_per_gas,
+            excess_blob_gas: header.excess_blob_gas,
+            timestamp: header.timestamp,
+        };
+        let transaction = TransactionSigned::try_from(transaction)
+            .map_err(|e| ProviderError::Rpc(e.to_string()))?;
+        Ok(Some((transaction, meta)))
+    }
+
+    fn transaction_block(&self, _id: TxNumber) -> ProviderResult<Option<BlockNumber>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn transactions_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<TransactionSigned>>> {
+        Ok(self.block(block)?.map(|block| block.body))
+    }
+
+    fn transactions_by_block_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<Vec<TransactionSigned>>> {
+        let range = self.to_range(range)?;
+        self.rpc_blocks(range, BlockTransactionsKind::Full)?
+            .into_iter()
+            .map(|block| Ok(client::sealed_block_with_senders(block)?.block.body))
+            .collect()
+    }
+
+    fn transactions_by_tx_range(
+        &self,
+        _range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<TransactionSignedNoHash>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn senders_by_tx_range(
+        &self,
+        _range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<Address>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn transaction_sender(&self, _id: TxNumber) -> ProviderResult<Option<Address>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl ReceiptProvider for RpcBlockchainProvider {
+    fn receipt(&self, _id: TxNumber) -> ProviderResult<Option<Receipt>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Receipt>> {
+        let receipt = self
+            .client
+            .request(move |provider| async move { provider.get_transaction_receipt(hash).await })?;
+        receipt.map(client::receipt).transpose()
+    }
+
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        self.rpc_receipts(block_id(block))
+    }
+
+    fn receipts_by_tx_range(
+        &self,
+        _range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<Receipt>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl ReceiptProviderIdExt for RpcBlockchainProvider {
+    fn receipts_by_block_id(&self, block: BlockId) -> ProviderResult<Option<Vec<Receipt>>> {
+        self.rpc_receipts(block)
+    }
+}
+
+impl WithdrawalsProvider for RpcBlockchainProvider {
+    fn withdrawals_by_block(
+        &self,
+        id: BlockHashOrNumber,
+        _timestamp: u64,
+    ) -> ProviderResult<Option<Withdrawals>> {
+        Ok(self
+            .rpc_block(block_id(id), BlockTransactionsKind::Hashes)?
+            .and_then(|block| block.withdrawals)
+            .map(Withdrawals::new))
+    }
+
+    fn latest_withdrawal(&self) -> ProviderResult<Option<Withdrawal>> {
+        Ok(self
+            .rpc_block(BlockNumberOrTag::Latest.into(), BlockTransactionsKind::Hashes)?
+            .and_then(|block| block.withdrawals)
+            .and_then(|mut withdrawals| withdrawals.pop()))
+    }
+}
+
+impl RequestsProvider for RpcBlockchainProvider {
+    /// The requests are not returned by the remote node, so this always returns `None`.
+    fn requests_by_block(
+        &self,
+        _id: BlockHashOrNumber,
+        _timestamp: u64,
+    ) -> ProviderResult<Option<Requests>> {
+        Ok(None)
+    }
+}
+
+impl BlockReader for RpcBlockchainProvider {
+    /// The remote node only serves its canonical chain, so the source is ignored.
+    fn find_block_by_hash(
+        &self,
+        hash: B256,
+        _source: BlockSource,
+    ) -> ProviderResult<Option<Block>> {
+        self.block(hash.into())
+    }
+
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Block>> {
+        self.block_by_id(block_id(id))
+    }
+
+    fn pending_block(&self) -> ProviderResult<Option<SealedBlock>> {
+        Ok(self.pending_block_with_senders()?.map(|block| block.block))
+    }
+
+    fn pending_block_with_senders(&self) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        self.rpc_sealed_block_with_senders(BlockId::pending())
+    }
+
+    fn pending_block_and_receipts(&self) -> ProviderResult<Option<(SealedBlock, Vec<Receipt>)>> {
+        let Some(block) = self.pending_block()? else { return Ok(None) };
+        let receipts = self.rpc_receipts(BlockId::pending())?.unwrap_or_default();
+        Ok(Some((block, receipts)))
+    }
+
+    fn ommers(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<Header>>> {
+        self.ommers_by_id(block_id(id))
+    }
+
+    fn block_body_indices(&self, _num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    /// The senders are returned by the remote node, so the transaction variant is ignored.
+    fn block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        _transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<BlockWithSenders>> {
+        Ok(self.rpc_sealed_block_with_senders(block_id(id))?.map(SealedBlockWithSenders::unseal))
+    }
+
+    /// The senders are returned by the remote node, so the transaction variant is ignored.
+    fn sealed_block_with_senders(
+        &self,
+        id: BlockHashOrNumber,
+        _transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        self.rpc_sealed_block_with_senders(block_id(id))
+    }
+
+    fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
+        Ok(self
+            .sealed_block_with_senders_range(range)?
+            .into_iter()
+            .map(|block| block.block.unseal())
+            .collect())
+    }
+
+    fn block_with_senders_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockWithSenders>> {
+        Ok(self
+            .sealed_block_with_senders_range(range)?
+            .into_iter()
+            .map(SealedBlockWithSenders::unseal)
+            .collect())
+    }
+
+    fn sealed_block_with_senders_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<SealedBlockWithSenders>> {
+        self.rpc_blocks(range, BlockTransactionsKind::Full)?
+            .into_iter()
+            .map(|block| {
+                let ommers = self.rpc_ommers(&block)?;
+                let mut block = client::sealed_block_with_senders(block)?;
+                block.block.ommers = ommers;
+                Ok(block)
+            })
+            .collect()
+    }
+}
+
+impl BlockReaderIdExt for RpcBlockchainProvider {
+    fn block_by_id(&self, id: BlockId) -> ProviderResult<Option<Block>> {
+        Ok(self.rpc_sealed_block_with_senders(id)?.map(|block| block.block.unseal()))
+    }
+
+    fn block_with_senders_by_id(
+        &self,
+        id: BlockId,
+        _transaction_kind: TransactionVariant,
+    ) -> ProviderResult<Option<BlockWithSenders>> {
+        Ok(self.rpc_sealed_block_with_senders(id)?.map(SealedBlockWithSenders::unseal))
+    }
+
+    fn sealed_header_by_id(&self, id: BlockId) -> ProviderResult<Option<SealedHeader>> {
+        self.rpc_header(id)
+    }
+
+    fn header_by_id(&self, id: BlockId) -> ProviderResult<Option<Header>> {
+        Ok(self.rpc_header(id)?.map(SealedHeader::unseal))
+    }
+
+    fn ommers_by_id(&self, id: BlockId) -> ProviderResult<Option<Vec<Header>>> {
+        self.rpc_block(id, BlockTransactionsKind::Hashes)?
+            .map(|block| self.rpc_ommers(&block))
+            .transpose()
+    }
+}
+
+impl StateProviderFactory for RpcBlockchainProvider {
+    /// Returns the state at the latest block, which is pinned to the hash of the latest block at
+    /// the time of the call, so that all reads see the same state, even if the block is reorged.
+    fn latest(&self) -> ProviderResult<StateProviderBox> {
+        let best_hash = self.chain_info()?.best_hash;
+        self.history_by_block_hash(best_hash)
+    }
+
+    fn state_by_block_number_or_tag(
+        &self,
+        number_or_tag: BlockNumberOrTag,
+    ) -> ProviderResult<StateProviderBox> {
+        match number_or_tag {
+            BlockNumberOrTag::Latest => self.latest(),
+            BlockNumberOrTag::Finalized => {
+                let hash =
+                    self.finalized_block_hash()?.ok_or(ProviderError::FinalizedBlockNotFound)?;
+                self.history_by_block_hash(hash)
+            }
+            BlockNumberOrTag::Safe => {
+                let hash = self.safe_block_hash()?.ok_or(ProviderError::SafeBlockNotFound)?;
+                self.history_by_block_hash(hash)
+            }
+            BlockNumberOrTag::Earliest => self.history_by_block_number(0),
+            BlockNumberOrTag::Pending => self.pending(),
+            BlockNumberOrTag::Number(num) => self.history_by_block_number(num),
+        }
+    }
+
+    fn history_by_block_number(&self, block: BlockNumber) -> ProviderResult<StateProviderBox> {
+        Ok(Box::new(RpcStateProvider::new(self.clone(), BlockId::number(block))))
+    }
+
+    fn history_by_block_hash(&self, block: BlockHash) -> ProviderResult<StateProviderBox> {
+        Ok(Box::new(RpcStateProvider::new(self.clone(), BlockId::hash(block))))
+    }
+
+    fn state_by_block_hash(&self, block: BlockHash) -> ProviderResult<StateProviderBox> {
+        self.history_by_block_hash(block)
+    }
+
+    fn pending(&self) -> ProviderResult<StateProviderBox> {
+        Ok(Box::new(RpcStateProvider::new(self.clone(), BlockId::pending())))
+    }
+
+    /// The remote node doesn't keep the state of blocks that are not canonical, so this always
+    /// returns `None`.
+    fn pending_state_by_hash(&self, _block_hash: B256) -> ProviderResult<Option<StateProviderBox>> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{server::ServerHandle, RpcModule};
+    use reth_primitives::{address, b256, bytes, keccak256, Bytes, B64};
+    use reth_storage_api::{AccountReader, StateProvider};
+
+    /// The hash of the only block of the remote node.
+    const BLOCK_HASH: B256 =
+        b256!("a4c0f4ddd34c3ab2f2d0b37c3a9ab1a03c1fcce6f7d21a4bed6d3b49b0c9f0e1");
+
+    /// Starts a remote node with a single block at height 2 and a single account.
+    async fn spawn_remote_node() -> (RpcBlockchainProvider, ServerHandle) {
+        let block: alloy_rpc_types::Block = alloy_rpc_types::Block {
+            header: alloy_rpc_types::Header {
+                hash: Some(BLOCK_HASH),
+                number: Some(2),
+                gas_limit: 30_000_000,
+                timestamp: 1_700_000_000,
+                mix_hash: Some(B256::ZERO),
+                nonce: Some(B64::ZERO),
+                base_fee_per_gas: Some(7),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let block = serde_json::to_value(block).unwrap();
+
+        let mut module = RpcModule::new(());
+        module.register_method("eth_blockNumber", |_, _, _| "0x2").unwrap();
+        let block_by_number = block.clone();
+        module
+            .register_method("eth_getBlockByNumber", move |params, _, _| {
+                let (number, _): (BlockNumberOrTag, bool) = params.parse().unwrap();
+                match number {
+                    BlockNumberOrTag::Number(2) | BlockNumberOrTag::Latest => {
+                        block_by_number.clone()
+                    }
+                    _ => serde_json::Value::Null,
+                }
+            })
+            .unwrap();
+        module
+            .register_method("eth_getBlockByHash", move |params, _, _| {
+                let (hash, _): (B256, bool) = params.parse().unwrap();
+                if hash == BLOCK_HASH {
+                    block.clone()
+                } else {
+                    serde_json::Value::Null
+                }
+            })
+            .unwrap();
+        // the balance is only returned for the state at the hash of the block
+        module
+            .register_method("eth_getBalance", |params, _, _| {
+                let (_, block): (Address, BlockId) = params.parse().unwrap();
+                match block {
+                    BlockId::Hash(hash) if hash.block_hash == BLOCK_HASH => "0x64",
+                    _ => "0x0",
+                }
+            })
+            .unwrap();
+        module.register_method("eth_getTransactionCount", |_, _, _| "0x1").unwrap();
+        module.register_method("eth_getCode", |_, _, _| "0x6000").unwrap();
+        module
+            .register_method("eth_getStorageAt", |_, _, _| {
+                "0x0000000000000000000000000000000000000000000000000000000000000005"
+            })
+            .unwrap();
+
+        let server = jsonrpsee::server::Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap()).parse().unwrap();
+        (RpcBlockchainProvider::new(url).unwrap(), server.start(module))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reads_headers() {
+        let (provider, _server) = spawn_remote_node().await;
+
+        assert_eq!(provider.best_block_number().unwrap(), 2);
+        let header = provider.sealed_header(2).unwrap().unwrap();
+        assert_eq!(header.hash(), BLOCK_HASH);
+        assert_eq!(header.number, 2);
+        assert_eq!(header.base_fee_per_gas, Some(7));
+        assert_eq!(provider.block_number(BLOCK_HASH).unwrap(), Some(2));
+        assert_eq!(provider.header_by_number(3).unwrap(), None);
+        assert_eq!(
+            provider.chain_info().unwrap(),
+            ChainInfo { best_hash: BLOCK_HASH, best_number: 2 }
+        );
+
+        // the range ends at the first missing block
+        let headers = provider.sealed_headers_range(2..5).unwrap();
+        assert_eq!(headers.into_iter().map(|h| h.hash()).collect::<Vec<_>>(), vec![BLOCK_HASH]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reads_state() {
+        let (provider, _server) = spawn_remote_node().await;
+        let state = provider.latest().unwrap();
+        let address = address!("00000000000000000000000000000000000000aa");
+
+        let account = state.basic_account(address).unwrap().unwrap();
+        let code: Bytes = bytes!("6000");
+        assert_eq!(account.nonce, 1);
+        assert_eq!(account.balance, U256::from(100));
+        assert_eq!(account.bytecode_hash, Some(keccak256(&code)));
+        assert_eq!(
+            state.bytecode_by_hash(keccak256(&code)).unwrap().map(|code| code.original_bytes()),
+            Some(code)
+        );
+        assert_eq!(state.storage(address, B256::ZERO).unwrap(), Some(U256::from(5)));
+        assert_eq!(state.block_hash(2).unwrap(), Some(BLOCK_HASH));
+    }
+}
@@ -0,0 +1,177 @@
+//! State provider backed by a remote node.
+
+use crate::{client::RpcClient, RpcBlockchainProvider};
+use alloy_provider::Provider;
+use reth_primitives::{
+    keccak256, Account, Address, BlockId, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue,
+    B256,
+};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateRootProvider,
+    StorageRangeProvider,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_trie::{
+    prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
+    HashedStorage, StorageRange,
+};
+use std::{collections::HashMap, future::IntoFuture};
+
+/// A [`StateProvider`] that reads the state at a block from a remote node.
+///
+/// Accounts and storage slots are requested from the remote node with `eth_getBalance`,
+/// `eth_getTransactionCount`, `eth_getCode` and `eth_getStorageAt`, so the remote node must keep
+/// the state of the block. State roots, proofs and storage ranges can't be computed and return
+/// [`ProviderError::UnsupportedProvider`].
+#[derive(Debug, Clone)]
+pub struct RpcStateProvider {
+    /// Provider of the blocks of the remote node, for the block hashes.
+    blocks: RpcBlockchainProvider,
+    /// The block whose state is read.
+    block_id: BlockId,
+}
+
+impl RpcStateProvider {
+    /// Creates a new state provider of the state at the given block.
+    pub(crate) const fn new(blocks: RpcBlockchainProvider, block_id: BlockId) -> Self {
+        Self { blocks, block_id }
+    }
+
+    const fn client(&self) -> &RpcClient {
+        self.blocks.client()
+    }
+
+    /// Returns the block whose state is read.
+    pub const fn block_id(&self) -> BlockId {
+        self.block_id
+    }
+}
+
+impl BlockHashReader for RpcStateProvider {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.blocks.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.blocks.canonical_hashes_range(start, end)
+    }
+}
+
+impl AccountReader for RpcStateProvider {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        let block_id = self.block_id;
+        let (balance, nonce, code) = self.client().request(move |provider| async move {
+            futures::try_join!(
+                provider.get_balance(address).block_id(block_id).into_future(),
+                provider.get_transaction_count(address).block_id(block_id).into_future(),
+                provider.get_code_at(address).block_id(block_id).into_future(),
+            )
+        })?;
+
+        // accounts without balance, nonce and code don't exist
+        if balance.is_zero() && nonce == 0 && code.is_empty() {
+            return Ok(None)
+        }
+
+        let bytecode_hash = (!code.is_empty()).then(|| {
+            let code_hash = keccak256(&code);
+            self.client().insert_bytecode(code_hash, Bytecode::new_raw(code));
+            code_hash
+        });
+        Ok(Some(Account { nonce, balance, bytecode_hash }))
+    }
+}
+
+impl StateProvider for RpcStateProvider {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        let block_id = self.block_id;
+        let value = self.client().request(move |provider| async move {
+            provider.get_storage_at(account, storage_key.into()).block_id(block_id).await
+        })?;
+        Ok(Some(value))
+    }
+
+    /// Returns the bytecode with the given hash, if it was returned with an account that was read
+    /// by a state provider of the same [`RpcBlockchainProvider`] before.
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        Ok(self.client().bytecode(&code_hash))
+    }
+}
+
+impl StateRootProvider for RpcStateProvider {
+    fn hashed_state_root(&self, _hashed_state: HashedPostState) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn hashed_state_root_from_nodes(
+        &self,
+        _nodes: TrieUpdates,
+        _hashed_state: HashedPostState,
+        _prefix_sets: TriePrefixSetsMut,
+    ) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn hashed_state_root_with_updates(
+        &self,
+        _hashed_state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn hashed_state_root_from_nodes_with_updates(
+        &self,
+        _nodes: TrieUpdates,
+        _hashed_state: HashedPostState,
+        _prefix_sets: TriePrefixSetsMut,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn hashed_storage_root(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl StateProofProvider for RpcStateProvider {
+    fn hashed_proof(
+        &self,
+        _hashed_state: HashedPostState,
+        _address: Address,
+        _slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn witness(
+        &self,
+        _overlay: HashedPostState,
+        _target: HashedPostState,
+    ) -> ProviderResult<HashMap<B256, Bytes>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl StorageRangeProvider for RpcStateProvider {
+    fn hashed_storage_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _address: Address,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
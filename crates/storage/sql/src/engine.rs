@@ -0,0 +1,189 @@
+use crate::{json::json_rows, table::ChainTableProvider, SqlError};
+use datafusion::{
+    arrow::{array::RecordBatch, datatypes::SchemaRef},
+    execution::context::{SQLOptions, SessionContext},
+};
+use reth_chain_arrow::ChainTable;
+use reth_rpc_server_types::constants;
+use reth_rpc_types::reth::SqlQueryResult;
+use reth_storage_api::{BlockReader, InternalTransfersProvider};
+use std::{fmt, sync::Arc};
+
+/// Limits of the [`SqlEngine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqlConfig {
+    /// The maximum number of blocks a scan of a table may read.
+    pub max_blocks: u64,
+    /// The maximum number of rows a query may return.
+    pub max_rows: usize,
+}
+
+impl SqlConfig {
+    /// Sets the maximum number of blocks a scan of a table may read.
+    pub const fn with_max_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_blocks = max_blocks;
+        self
+    }
+
+    /// Sets the maximum number of rows a query may return.
+    pub const fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+}
+
+impl Default for SqlConfig {
+    fn default() -> Self {
+        Self {
+            max_blocks: constants::DEFAULT_SQL_MAX_BLOCKS,
+            max_rows: constants::DEFAULT_SQL_MAX_ROWS,
+        }
+    }
+}
+
+/// Executes read-only SQL queries over the [`ChainTable`]s.
+#[derive(Clone)]
+pub struct SqlEngine {
+    ctx: SessionContext,
+    config: SqlConfig,
+}
+
+impl SqlEngine {
+    /// Creates a new engine that reads the tables from the provider.
+    pub fn new<P>(provider: P, config: SqlConfig) -> Result<Self, SqlError>
+    where
+        P: BlockReader + InternalTransfersProvider + Clone + 'static,
+    {
+        let ctx = SessionContext::new();
+        for table in ChainTable::ALL {
+            ctx.register_table(
+                table.name(),
+                Arc::new(ChainTableProvider::new(table, provider.clone(), config)),
+            )?;
+        }
+        Ok(Self { ctx, config })
+    }
+
+    /// Executes the query and returns the schema and the rows of the result.
+    ///
+    /// Returns [`SqlError::TooManyRows`] if the query returns more than
+    /// [`SqlConfig::max_rows`] rows.
+    pub async fn query(&self, sql: &str) -> Result<(SchemaRef, Vec<RecordBatch>), SqlError> {
+        let options = SQLOptions::new()
+            .with_allow_ddl(false)
+            .with_allow_dml(false)
+            .with_allow_statements(false);
+        let df = self.ctx.sql_with_options(sql, options).await?;
+        let schema = df.schema().inner().clone();
+
+        // fetch one more row to detect results that exceed the limit
+        let batches = df.limit(0, Some(self.config.max_rows + 1))?.collect().await?;
+        if batches.iter().map(RecordBatch::num_rows).sum::<usize>() > self.config.max_rows {
+            return Err(SqlError::TooManyRows(self.config.max_rows))
+        }
+
+        Ok((schema, batches))
+    }
+
+    /// Executes the query and returns the result as JSON, see [`SqlQueryResult`].
+    pub async fn query_json(&self, sql: &str) -> Result<SqlQueryResult, SqlError> {
+        let (schema, batches) = self.query(sql).await?;
+        Ok(SqlQueryResult {
+            columns: schema.fields().iter().map(|field| field.name().clone()).collect(),
+            rows: json_rows(&batches)?,
+        })
+    }
+}
+
+impl fmt::Debug for SqlEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqlEngine").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{StaticFileSegment, B256};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, StageCheckpointWriter, StaticFileProviderFactory,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_testing_utils::generators::{self, random_block_range};
+
+    /// Creates an engine over a chain of 10 blocks without transactions.
+    fn engine(config: SqlConfig) -> SqlEngine {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in random_block_range(&mut generators::rng(), 0..=9, B256::ZERO, 0..1) {
+            provider_rw.insert_historical_block(block.try_seal_with_senders().unwrap()).unwrap();
+        }
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(9)).unwrap();
+        provider_rw
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        SqlEngine::new(factory, config).unwrap()
+    }
+
+    fn rows(batches: &[RecordBatch]) -> usize {
+        batches.iter().map(RecordBatch::num_rows).sum()
+    }
+
+    #[tokio::test]
+    async fn rejects_writes() {
+        let engine = engine(SqlConfig::default());
+
+        for sql in [
+            "CREATE TABLE accounts (address BINARY)",
+            "INSERT INTO blocks (number) VALUES (10)",
+            "DROP TABLE blocks",
+            "SET datafusion.execution.batch_size = 1",
+        ] {
+            let err = engine.query(sql).await.unwrap_err();
+            assert!(err.is_query_error(), "{sql}: {err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_too_many_rows() {
+        let engine = engine(SqlConfig::default().with_max_rows(5));
+
+        let err = engine.query("SELECT number FROM blocks").await.unwrap_err();
+        assert!(matches!(err, SqlError::TooManyRows(5)), "{err}");
+
+        let (_, batches) =
+            engine.query("SELECT number FROM blocks WHERE number < 5").await.unwrap();
+        assert_eq!(rows(&batches), 5);
+    }
+
+    #[tokio::test]
+    async fn restricts_scanned_blocks() {
+        let engine = engine(SqlConfig::default().with_max_blocks(5));
+
+        let err = engine.query("SELECT number FROM blocks").await.unwrap_err();
+        assert!(err.is_query_error(), "{err}");
+        let err = engine.query("SELECT number FROM blocks WHERE number > 3").await.unwrap_err();
+        assert!(err.is_query_error(), "{err}");
+
+        let (_, batches) =
+            engine.query("SELECT number FROM blocks WHERE number BETWEEN 2 AND 6").await.unwrap();
+        assert_eq!(rows(&batches), 5);
+
+        // the limit is pushed down to the scan, which stops reading once it is reached
+        let (_, batches) = engine.query("SELECT number FROM blocks LIMIT 3").await.unwrap();
+        assert_eq!(rows(&batches), 3);
+    }
+
+    #[tokio::test]
+    async fn traces_require_index() {
+        let engine = engine(SqlConfig::default());
+
+        let err = engine.query("SELECT * FROM traces WHERE block_number = 1").await.unwrap_err();
+        assert!(!err.is_query_error(), "{err}");
+    }
+}
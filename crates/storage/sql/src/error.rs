@@ -0,0 +1,19 @@
+use datafusion::error::DataFusionError;
+
+/// Errors that can occur when executing a query.
+#[derive(Debug, thiserror::Error)]
+pub enum SqlError {
+    /// Error planning or executing the query.
+    #[error(transparent)]
+    DataFusion(#[from] DataFusionError),
+    /// The query returned more rows than allowed.
+    #[error("query returned more than {0} rows")]
+    TooManyRows(usize),
+}
+
+impl SqlError {
+    /// Returns `true` if the error is caused by the query rather than by reading chain data.
+    pub const fn is_query_error(&self) -> bool {
+        !matches!(self, Self::DataFusion(DataFusionError::External(_)))
+    }
+}
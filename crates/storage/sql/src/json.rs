@@ -0,0 +1,97 @@
+//! Conversion of query results into JSON.
+
+use crate::SqlError;
+use datafusion::{
+    arrow::{
+        array::{Array, AsArray, RecordBatch},
+        datatypes::DataType,
+        util::display::{ArrayFormatter, FormatOptions},
+    },
+    error::DataFusionError,
+};
+use reth_primitives::hex;
+use serde_json::{Number, Value};
+
+/// Converts the record batches into rows of JSON values, see
+/// [`SqlQueryResult`](reth_rpc_types::reth::SqlQueryResult).
+pub(crate) fn json_rows(batches: &[RecordBatch]) -> Result<Vec<Vec<Value>>, SqlError> {
+    let options = FormatOptions::default();
+    let mut rows = Vec::with_capacity(batches.iter().map(RecordBatch::num_rows).sum());
+    for batch in batches {
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), &options))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(DataFusionError::from)?;
+
+        for row in 0..batch.num_rows() {
+            rows.push(
+                batch
+                    .columns()
+                    .iter()
+                    .zip(&formatters)
+                    .map(|(column, formatter)| json_value(column.as_ref(), formatter, row))
+                    .collect(),
+            );
+        }
+    }
+    Ok(rows)
+}
+
+/// Converts a single value of the column into JSON.
+fn json_value(column: &dyn Array, formatter: &ArrayFormatter<'_>, row: usize) -> Value {
+    if column.is_null(row) {
+        return Value::Null
+    }
+    match column.data_type() {
+        DataType::Boolean => Value::Bool(column.as_boolean().value(row)),
+        DataType::Binary => hex::encode_prefixed(column.as_binary::<i32>().value(row)).into(),
+        DataType::LargeBinary => hex::encode_prefixed(column.as_binary::<i64>().value(row)).into(),
+        DataType::FixedSizeBinary(_) => {
+            hex::encode_prefixed(column.as_fixed_size_binary().value(row)).into()
+        }
+        data_type => {
+            let value = formatter.value(row).to_string();
+            if data_type.is_integer() {
+                if let Ok(number) = value.parse::<Number>() {
+                    return Value::Number(number)
+                }
+            }
+            Value::String(value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::arrow::array::{BooleanArray, FixedSizeBinaryArray, StringArray, UInt64Array};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn converts_rows() {
+        let batch = RecordBatch::try_from_iter([
+            ("number", Arc::new(UInt64Array::from(vec![Some(1), None])) as _),
+            (
+                "hash",
+                Arc::new(
+                    FixedSizeBinaryArray::try_from_iter([[0xab, 0xcd], [0x01, 0x02]].into_iter())
+                        .unwrap(),
+                ) as _,
+            ),
+            ("success", Arc::new(BooleanArray::from(vec![true, false])) as _),
+            ("value", Arc::new(StringArray::from(vec!["10", "20"])) as _),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            json_rows(&[batch]).unwrap(),
+            vec![
+                vec![json!(1), json!("0xabcd"), json!(true), json!("10")],
+                vec![Value::Null, json!("0x0102"), json!(false), json!("20")],
+            ]
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! Read-only SQL queries over chain data.
+//!
+//! The [`SqlEngine`] exposes the [`ChainTable`](reth_chain_arrow::ChainTable)s as virtual tables
+//! that are read from the provider when a query scans them, using an embedded
+//! [datafusion](https://docs.rs/datafusion) query engine. Statements that modify data or the
+//! catalog are rejected.
+//!
+//! Tables are read in batches of blocks while a query consumes them, and scans are restricted to
+//! [`SqlConfig::max_blocks`] blocks. Filters on the block number column of a table, e.g.
+//! `WHERE block_number BETWEEN 100 AND 200`, restrict the blocks that are scanned, and scans with
+//! a `LIMIT` stop once enough rows are read.
+//!
+//! The `traces` table contains the internal ETH transfers of transactions, and can only be scanned
+//! for blocks that were executed with internal transfers indexed.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod engine;
+mod error;
+mod json;
+mod range;
+mod rpc;
+mod table;
+
+pub use engine::{SqlConfig, SqlEngine};
+pub use error::SqlError;
+pub use rpc::SqlApi;
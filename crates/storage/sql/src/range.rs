@@ -0,0 +1,137 @@
+//! Block ranges of table scans.
+
+use datafusion::{
+    logical_expr::{expr::Between, BinaryExpr, Expr, Operator},
+    scalar::ScalarValue,
+};
+use reth_primitives::BlockNumber;
+use std::ops::RangeInclusive;
+
+/// Returns `true` if the filter restricts the block number column, see [`block_range`].
+pub(crate) fn is_block_filter(column: &str, filter: &Expr) -> bool {
+    match filter {
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+            comparison(column, left, *op, right).is_some()
+        }
+        Expr::Between(Between { expr, negated: false, low, high }) => {
+            is_column(column, expr) && literal(low).is_some() && literal(high).is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Narrows the range of block numbers that a scan has to read with its filters.
+///
+/// Only comparisons of the block number column with literals are considered, all other filters
+/// are applied by the query engine after the scan.
+pub(crate) fn block_range(column: &str, filters: &[Expr]) -> RangeInclusive<BlockNumber> {
+    let (mut start, mut end) = (0, BlockNumber::MAX);
+    for filter in filters {
+        match filter {
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                let Some((op, value)) = comparison(column, left, *op, right) else { continue };
+                match op {
+                    Operator::Eq => {
+                        start = start.max(value);
+                        end = end.min(value);
+                    }
+                    Operator::Gt => match value.checked_add(1) {
+                        Some(value) => start = start.max(value),
+                        None => return empty(),
+                    },
+                    Operator::GtEq => start = start.max(value),
+                    Operator::Lt => match value.checked_sub(1) {
+                        Some(value) => end = end.min(value),
+                        None => return empty(),
+                    },
+                    Operator::LtEq => end = end.min(value),
+                    _ => {}
+                }
+            }
+            Expr::Between(Between { expr, negated: false, low, high })
+                if is_column(column, expr) =>
+            {
+                if let (Some(low), Some(high)) = (literal(low), literal(high)) {
+                    start = start.max(low);
+                    end = end.min(high);
+                }
+            }
+            _ => {}
+        }
+    }
+    start..=end
+}
+
+/// An empty range of blocks.
+#[allow(clippy::reversed_empty_ranges)]
+const fn empty() -> RangeInclusive<BlockNumber> {
+    1..=0
+}
+
+/// Returns the operator and the literal of a comparison of the column with a literal.
+///
+/// The operator is swapped if the literal is on the left side.
+fn comparison(column: &str, left: &Expr, op: Operator, right: &Expr) -> Option<(Operator, u64)> {
+    if !matches!(op, Operator::Eq | Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq) {
+        return None
+    }
+    if is_column(column, left) {
+        literal(right).map(|value| (op, value))
+    } else if is_column(column, right) {
+        Some((op.swap()?, literal(left)?))
+    } else {
+        None
+    }
+}
+
+fn is_column(column: &str, expr: &Expr) -> bool {
+    matches!(expr, Expr::Column(col) if col.name == column)
+}
+
+/// Returns the value of an integer literal, if it is a valid block number.
+fn literal(expr: &Expr) -> Option<u64> {
+    let Expr::Literal(value) = expr else { return None };
+    match value {
+        ScalarValue::UInt64(value) => *value,
+        ScalarValue::UInt32(value) => value.map(Into::into),
+        ScalarValue::UInt16(value) => value.map(Into::into),
+        ScalarValue::UInt8(value) => value.map(Into::into),
+        ScalarValue::Int64(value) => value.and_then(|value| value.try_into().ok()),
+        ScalarValue::Int32(value) => value.and_then(|value| value.try_into().ok()),
+        ScalarValue::Int16(value) => value.and_then(|value| value.try_into().ok()),
+        ScalarValue::Int8(value) => value.and_then(|value| value.try_into().ok()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion::prelude::{col, lit};
+
+    #[test]
+    fn narrows_block_range() {
+        let column = "block_number";
+        assert_eq!(block_range(column, &[]), 0..=u64::MAX);
+
+        let filters = [col(column).gt_eq(lit(10u64)), col(column).lt(lit(20i64))];
+        assert_eq!(block_range(column, &filters), 10..=19);
+
+        // literal on the left side
+        let filters = [lit(10u64).lt(col(column)), col(column).eq(lit(15u64))];
+        assert_eq!(block_range(column, &filters), 15..=15);
+
+        let filters = [col(column).between(lit(5u64), lit(8u64))];
+        assert!(is_block_filter(column, &filters[0]));
+        assert_eq!(block_range(column, &filters), 5..=8);
+
+        let filters = [col(column).lt(lit(0u64))];
+        assert!(block_range(column, &filters).is_empty());
+
+        // filters on other columns and negative literals are ignored
+        let filters = [col("gas_used").gt(lit(10u64)), col(column).gt(lit(-1i64))];
+        assert!(!is_block_filter(column, &filters[0]));
+        assert!(!is_block_filter(column, &filters[1]));
+        assert_eq!(block_range(column, &filters), 0..=u64::MAX);
+    }
+}
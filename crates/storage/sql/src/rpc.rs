@@ -0,0 +1,35 @@
+use crate::{SqlEngine, SqlError};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_api::RethSqlApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_rpc_types::reth::SqlQueryResult;
+use tracing::trace;
+
+/// `reth` API implementation for SQL queries, see [`RethSqlApiServer`].
+#[derive(Debug, Clone)]
+pub struct SqlApi {
+    engine: SqlEngine,
+}
+
+impl SqlApi {
+    /// Creates a new instance of [`SqlApi`].
+    pub const fn new(engine: SqlEngine) -> Self {
+        Self { engine }
+    }
+}
+
+#[async_trait]
+impl RethSqlApiServer for SqlApi {
+    /// Handler for `reth_sql`
+    async fn sql(&self, query: String) -> RpcResult<SqlQueryResult> {
+        trace!(target: "rpc::reth", %query, "Serving reth_sql");
+        self.engine.query_json(&query).await.map_err(|err: SqlError| {
+            if err.is_query_error() {
+                invalid_params_rpc_err(err.to_string())
+            } else {
+                internal_rpc_err(err.to_string())
+            }
+        })
+    }
+}
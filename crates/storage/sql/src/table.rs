@@ -0,0 +1,228 @@
+//! Virtual tables over chain data.
+
+use crate::{
+    range::{block_range, is_block_filter},
+    SqlConfig,
+};
+use async_trait::async_trait;
+use datafusion::{
+    arrow::datatypes::SchemaRef,
+    catalog::Session,
+    datasource::{TableProvider, TableType},
+    error::{DataFusionError, Result},
+    execution::{SendableRecordBatchStream, TaskContext},
+    logical_expr::{Expr, TableProviderFilterPushDown},
+    physical_plan::{
+        stream::RecordBatchStreamAdapter,
+        streaming::{PartitionStream, StreamingTableExec},
+        ExecutionPlan,
+    },
+};
+use reth_chain_arrow::{ChainBatch, ChainTable};
+use reth_primitives::BlockNumber;
+use reth_storage_api::{BlockReader, InternalTransfersProvider};
+use std::{any::Any, fmt, ops::RangeInclusive, sync::Arc};
+use tracing::trace;
+
+/// Number of blocks that are read from the provider at once.
+const BATCH_BLOCKS: u64 = 1_000;
+
+/// A [`ChainTable`] that is read from the provider when it is scanned.
+pub(crate) struct ChainTableProvider<P> {
+    table: ChainTable,
+    provider: P,
+    config: SqlConfig,
+}
+
+impl<P> ChainTableProvider<P> {
+    /// Creates a new table provider for the table.
+    pub(crate) const fn new(table: ChainTable, provider: P, config: SqlConfig) -> Self {
+        Self { table, provider, config }
+    }
+}
+
+#[async_trait]
+impl<P> TableProvider for ChainTableProvider<P>
+where
+    P: BlockReader + InternalTransfersProvider + Clone + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        // block filters only restrict the blocks that are read, so they are applied again
+        let column = self.table.block_number_column();
+        Ok(filters
+            .iter()
+            .map(|filter| {
+                if is_block_filter(column, filter) {
+                    TableProviderFilterPushDown::Inexact
+                } else {
+                    TableProviderFilterPushDown::Unsupported
+                }
+            })
+            .collect())
+    }
+
+    async fn scan(
+        &self,
+        _state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let range = block_range(self.table.block_number_column(), filters);
+        let best_block = self
+            .provider
+            .best_block_number()
+            .map_err(|err| DataFusionError::External(Box::new(err)))?;
+        let range = *range.start()..=(*range.end()).min(best_block);
+
+        // scans with a limit stop reading once enough rows are read, so they are only rejected
+        // if they actually read too many blocks
+        if limit.is_none() && block_count(&range) > self.config.max_blocks {
+            return Err(self.too_many_blocks())
+        }
+        trace!(target: "sql", table = self.table.name(), ?range, ?limit, "Scanning table");
+
+        // blocks have one row each, so a limited scan of them doesn't have to read more blocks
+        let batch_blocks = match (self.table, limit) {
+            (ChainTable::Blocks, Some(limit)) => (limit as u64).clamp(1, BATCH_BLOCKS),
+            _ => BATCH_BLOCKS,
+        };
+        let stream = ChainTableStream {
+            table: self.table,
+            provider: self.provider.clone(),
+            range,
+            batch_blocks,
+            max_blocks: self.config.max_blocks,
+            too_many_blocks: self.too_many_blocks().to_string(),
+            schema: self.schema(),
+        };
+
+        Ok(Arc::new(StreamingTableExec::try_new(
+            self.schema(),
+            vec![Arc::new(stream)],
+            projection,
+            [],
+            false,
+            limit,
+        )?))
+    }
+}
+
+impl<P> ChainTableProvider<P> {
+    /// Returns the error of scans that read more than [`SqlConfig::max_blocks`] blocks.
+    fn too_many_blocks(&self) -> DataFusionError {
+        DataFusionError::Plan(format!(
+            "scan of table {} reads more than {} blocks, filter the {} column to restrict the \
+             scanned blocks",
+            self.table.name(),
+            self.config.max_blocks,
+            self.table.block_number_column(),
+        ))
+    }
+}
+
+impl<P> fmt::Debug for ChainTableProvider<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainTableProvider")
+            .field("table", &self.table)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reads the rows of a table for the blocks of a scan, [`BATCH_BLOCKS`] blocks at a time.
+///
+/// Blocks are only read when the next record batch is polled, so a scan whose consumer stops
+/// early, e.g. because of a `LIMIT`, doesn't read the remaining blocks.
+struct ChainTableStream<P> {
+    table: ChainTable,
+    provider: P,
+    range: RangeInclusive<BlockNumber>,
+    /// Number of blocks that are read at once.
+    batch_blocks: u64,
+    /// Maximum number of blocks that may be read.
+    max_blocks: u64,
+    /// Message of the error that is returned if more than `max_blocks` blocks would be read.
+    too_many_blocks: String,
+    schema: SchemaRef,
+}
+
+impl<P> PartitionStream for ChainTableStream<P>
+where
+    P: BlockReader + InternalTransfersProvider + Clone + 'static,
+{
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let (table, provider, range) = (self.table, self.provider.clone(), self.range.clone());
+        let (batch_blocks, max_blocks) = (self.batch_blocks, self.max_blocks);
+        let too_many_blocks = self.too_many_blocks.clone();
+
+        let batches = futures::stream::try_unfold(
+            (!range.is_empty()).then(|| *range.start()),
+            move |batch_start| {
+                let provider = provider.clone();
+                let (end, too_many_blocks) = (*range.end(), too_many_blocks.clone());
+                let read_blocks = batch_start.map_or(0, |start| start - range.start());
+                async move {
+                    let Some(batch_start) = batch_start else { return Ok(None) };
+                    if read_blocks >= max_blocks {
+                        return Err(DataFusionError::Plan(too_many_blocks))
+                    }
+
+                    let batch_end = batch_start
+                        .saturating_add(batch_blocks.min(max_blocks - read_blocks) - 1)
+                        .min(end);
+                    let batch = tokio::task::spawn_blocking(move || {
+                        ChainBatch::read(&provider, batch_start..=batch_end, &[table])
+                            .and_then(|batch| table.record_batch(&batch))
+                    })
+                    .await
+                    .map_err(|err| DataFusionError::External(Box::new(err)))?
+                    .map_err(|err| DataFusionError::External(Box::new(err)))?;
+
+                    let next = batch_end.checked_add(1).filter(|next| *next <= end);
+                    Ok(Some((batch, next)))
+                }
+            },
+        );
+
+        Box::pin(RecordBatchStreamAdapter::new(self.schema.clone(), batches))
+    }
+}
+
+impl<P> fmt::Debug for ChainTableStream<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainTableStream")
+            .field("table", &self.table)
+            .field("range", &self.range)
+            .field("batch_blocks", &self.batch_blocks)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the number of blocks in the range.
+const fn block_count(range: &RangeInclusive<BlockNumber>) -> u64 {
+    if *range.start() > *range.end() {
+        0
+    } else {
+        (*range.end() - *range.start()).saturating_add(1)
+    }
+}
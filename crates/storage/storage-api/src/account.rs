@@ -53,3 +53,19 @@ pub trait ChangeSetReader: Send + Sync {
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
 }
+
+/// Account history reader
+#[auto_impl(&, Arc, Box)]
+pub trait AccountHistoryReader: Send + Sync {
+    /// Returns the state of the account after each block of the range that changed it, in
+    /// ascending order of the block numbers.
+    ///
+    /// The state is `None` if the account didn't exist after the block.
+    ///
+    /// NOTE: Get inclusive range of blocks.
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>>;
+}
@@ -1,11 +1,12 @@
 use crate::{
-    BlockIdReader, BlockNumReader, HeaderProvider, ReceiptProvider, ReceiptProviderIdExt,
-    RequestsProvider, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    BlockIdReader, BlockNumReader, HeaderProvider, RangeRevIter, ReceiptProvider,
+    ReceiptProviderIdExt, RequestsProvider, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use reth_db_models::StoredBlockBodyIndices;
 use reth_primitives::{
     Block, BlockHashOrNumber, BlockId, BlockNumber, BlockNumberOrTag, BlockWithSenders, Header,
-    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, B256,
+    Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TxHash, B256,
 };
 use reth_storage_errors::provider::ProviderResult;
 use std::ops::RangeInclusive;
@@ -134,6 +135,23 @@ pub trait BlockReader:
     /// Note: returns only available blocks
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>>;
 
+    /// Returns all blocks in the given inclusive range in descending order, starting at the end of
+    /// the range.
+    ///
+    /// The blocks are read lazily in chunks, see [`RangeRevIter`].
+    ///
+    /// Note: returns only available blocks
+    fn blocks_range_rev(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<Block>> {
+        RangeRevIter::new(range, |chunk| {
+            let mut blocks = self.block_range(chunk)?;
+            blocks.reverse();
+            Ok(blocks)
+        })
+    }
+
     /// Returns a range of blocks from the database, along with the senders of each
     /// transaction in the blocks.
     fn block_with_senders_range(
@@ -226,6 +244,19 @@ pub trait BlockReaderIdExt: BlockReader + BlockIdReader + ReceiptProviderIdExt {
         }
     }
 
+    /// Returns the block with senders that includes the transaction with the given hash.
+    ///
+    /// The block is fetched once, after resolving the transaction to its block.
+    ///
+    /// Returns `None` if the transaction is not found.
+    fn block_by_transaction_hash(
+        &self,
+        hash: TxHash,
+    ) -> ProviderResult<Option<SealedBlockWithSenders>> {
+        let Some((_, meta)) = self.transaction_by_hash_with_meta(hash)? else { return Ok(None) };
+        self.sealed_block_with_senders(meta.block_hash.into(), TransactionVariant::WithHash)
+    }
+
     /// Returns the header with matching tag from the database
     ///
     /// Returns `None` if header is not found.
@@ -1,6 +1,7 @@
+use crate::RangeRevIter;
 use reth_primitives::{BlockHash, BlockHashOrNumber, BlockNumber, Header, SealedHeader, U256};
 use reth_storage_errors::provider::ProviderResult;
-use std::ops::RangeBounds;
+use std::ops::{RangeBounds, RangeInclusive};
 
 /// Client trait for fetching `Header` related data.
 #[auto_impl::auto_impl(&, Arc)]
@@ -36,6 +37,21 @@ pub trait HeaderProvider: Send + Sync {
     /// Get headers in range of block numbers
     fn headers_range(&self, range: impl RangeBounds<BlockNumber>) -> ProviderResult<Vec<Header>>;
 
+    /// Get headers in range of block numbers in descending order, starting at the end of the
+    /// range.
+    ///
+    /// The headers are read lazily in chunks, see [`RangeRevIter`].
+    fn headers_range_rev(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> impl Iterator<Item = ProviderResult<Header>> {
+        RangeRevIter::new(range, |chunk| {
+            let mut headers = self.headers_range(chunk)?;
+            headers.reverse();
+            Ok(headers)
+        })
+    }
+
     /// Get a single sealed header by block number.
     fn sealed_header(&self, number: BlockNumber) -> ProviderResult<Option<SealedHeader>>;
 
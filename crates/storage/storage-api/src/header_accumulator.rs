@@ -0,0 +1,240 @@
+use crate::{BlockHashReader, HeaderProvider};
+use reth_primitives::{BlockHash, BlockNumber, B256, U256};
+use reth_storage_errors::provider::ProviderResult;
+use sha2::{Digest, Sha256};
+use std::ops::RangeInclusive;
+
+/// The number of blocks of an era1 epoch, which is the minimum capacity of a [`HeaderAccumulator`].
+pub const EPOCH_SIZE: usize = 8192;
+
+/// The hash and total difficulty of a canonical header, which is a leaf of a
+/// [`HeaderAccumulator`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// The hash of the header.
+    pub block_hash: BlockHash,
+    /// The total difficulty of the chain up to and including the header.
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Returns the SSZ hash tree root of the record.
+    pub fn tree_hash_root(&self) -> B256 {
+        hash_pair(self.block_hash, B256::from(self.total_difficulty.to_le_bytes::<32>()))
+    }
+}
+
+/// A Merkle commitment over the header records of a range of canonical blocks.
+///
+/// The root is the SSZ hash tree root of the list of [`HeaderRecord`]s with a capacity of
+/// [`EPOCH_SIZE`], or the next power of two of the number of records if there are more. The root of
+/// the accumulator of an era1 epoch is therefore its epoch accumulator root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderAccumulator {
+    /// The number of the first block of the range.
+    start: BlockNumber,
+    /// The records of the blocks of the range, in ascending order.
+    records: Vec<HeaderRecord>,
+    /// The non-empty nodes of each level of the tree, starting with the leaves.
+    levels: Vec<Vec<B256>>,
+}
+
+impl HeaderAccumulator {
+    /// Creates the accumulator of the records of the blocks starting at `start`.
+    pub fn new(start: BlockNumber, records: Vec<HeaderRecord>) -> Self {
+        let depth = records.len().max(EPOCH_SIZE).next_power_of_two().trailing_zeros() as usize;
+        let zero_hashes = zero_hashes(depth);
+
+        let mut levels = Vec::with_capacity(depth + 1);
+        levels.push(records.iter().map(HeaderRecord::tree_hash_root).collect::<Vec<_>>());
+        for zero_hash in &zero_hashes[..depth] {
+            let level = levels.last().expect("not empty");
+            let parents = level
+                .chunks(2)
+                .map(|pair| hash_pair(pair[0], pair.get(1).copied().unwrap_or(*zero_hash)))
+                .collect();
+            levels.push(parents);
+        }
+
+        Self { start, records, levels }
+    }
+
+    /// Returns the range of blocks of the accumulator.
+    pub fn range(&self) -> RangeInclusive<BlockNumber> {
+        self.start..=(self.start + self.records.len() as u64).saturating_sub(1)
+    }
+
+    /// Returns the records of the blocks of the accumulator, in ascending order.
+    pub fn records(&self) -> &[HeaderRecord] {
+        &self.records
+    }
+
+    /// Returns the root of the accumulator.
+    pub fn root(&self) -> B256 {
+        let depth = self.levels.len() - 1;
+        let tree_root =
+            self.levels[depth].first().copied().unwrap_or_else(|| zero_hashes(depth)[depth]);
+        hash_pair(tree_root, length_chunk(self.records.len()))
+    }
+
+    /// Returns the proof that the header of the block is included in the accumulator, or `None` if
+    /// the block is not in its range.
+    pub fn proof(&self, number: BlockNumber) -> Option<HeaderInclusionProof> {
+        let index = number.checked_sub(self.start)? as usize;
+        let record = *self.records.get(index)?;
+
+        let depth = self.levels.len() - 1;
+        let zero_hashes = zero_hashes(depth);
+        let mut branch = Vec::with_capacity(depth + 1);
+        for (level, nodes) in self.levels[..depth].iter().enumerate() {
+            branch.push(nodes.get((index >> level) ^ 1).copied().unwrap_or(zero_hashes[level]));
+        }
+        branch.push(length_chunk(self.records.len()));
+
+        Some(HeaderInclusionProof { number, index: index as u64, record, branch })
+    }
+}
+
+/// A proof that the [`HeaderRecord`] of a block is included in a [`HeaderAccumulator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderInclusionProof {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The index of the block in the accumulator.
+    pub index: u64,
+    /// The record of the block.
+    pub record: HeaderRecord,
+    /// The sibling nodes from the leaf to the root of the tree, followed by the chunk of the
+    /// number of records that is mixed into the root.
+    pub branch: Vec<B256>,
+}
+
+impl HeaderInclusionProof {
+    /// Returns `true` if the proof is valid for the given accumulator root.
+    pub fn verify(&self, root: B256) -> bool {
+        let Some((length, siblings)) = self.branch.split_last() else { return false };
+        if siblings.len() >= u64::BITS as usize || self.index >> siblings.len() != 0 {
+            return false
+        }
+
+        let mut node = self.record.tree_hash_root();
+        for (level, sibling) in siblings.iter().enumerate() {
+            node = if (self.index >> level) & 1 == 0 {
+                hash_pair(node, *sibling)
+            } else {
+                hash_pair(*sibling, node)
+            };
+        }
+        hash_pair(node, *length) == root
+    }
+}
+
+/// Client trait for fetching a [`HeaderAccumulator`] over a range of canonical blocks.
+pub trait HeaderAccumulatorProvider: BlockHashReader + HeaderProvider {
+    /// Returns the accumulator of the canonical headers of the range.
+    ///
+    /// Returns `None` if the range is empty or any header of the range is not available.
+    fn header_accumulator(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<HeaderAccumulator>> {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return Ok(None)
+        }
+
+        // some providers include the end of the range in the hashes, so the hashes are truncated
+        // to the requested range
+        let mut hashes = self.canonical_hashes_range(start, end.saturating_add(1))?;
+        hashes.truncate((end - start + 1) as usize);
+        if hashes.len() as u64 != end - start + 1 {
+            return Ok(None)
+        }
+
+        let mut records = Vec::with_capacity(hashes.len());
+        for (number, block_hash) in (start..=end).zip(hashes) {
+            let Some(total_difficulty) = self.header_td_by_number(number)? else { return Ok(None) };
+            records.push(HeaderRecord { block_hash, total_difficulty });
+        }
+
+        Ok(Some(HeaderAccumulator::new(start, records)))
+    }
+}
+
+impl<T> HeaderAccumulatorProvider for T where T: BlockHashReader + HeaderProvider {}
+
+/// Returns the SHA-256 hash of the concatenation of the two nodes.
+fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// Returns the chunk of the length of a list that is mixed into its SSZ hash tree root.
+fn length_chunk(len: usize) -> B256 {
+    B256::from(U256::from(len).to_le_bytes::<32>())
+}
+
+/// Returns the roots of empty subtrees of each depth up to and including the given depth.
+fn zero_hashes(depth: usize) -> Vec<B256> {
+    let mut hashes = Vec::with_capacity(depth + 1);
+    hashes.push(B256::ZERO);
+    for _ in 0..depth {
+        let last = *hashes.last().expect("not empty");
+        hashes.push(hash_pair(last, last));
+    }
+    hashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(len: usize) -> Vec<HeaderRecord> {
+        (0..len)
+            .map(|i| HeaderRecord {
+                block_hash: B256::with_last_byte(i as u8),
+                total_difficulty: U256::from(i * 1_000),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn root_of_single_record() {
+        let record = records(1)[0];
+        let accumulator = HeaderAccumulator::new(10, vec![record]);
+        assert_eq!(accumulator.range(), 10..=10);
+
+        let mut node = record.tree_hash_root();
+        for zero_hash in &zero_hashes(13)[..13] {
+            node = hash_pair(node, *zero_hash);
+        }
+        assert_eq!(accumulator.root(), hash_pair(node, length_chunk(1)));
+    }
+
+    #[test]
+    fn verify_proofs() {
+        for len in [1, 2, 5, EPOCH_SIZE + 3] {
+            let accumulator = HeaderAccumulator::new(100, records(len));
+            let root = accumulator.root();
+
+            for number in [100, 100 + len as u64 / 2, 100 + len as u64 - 1] {
+                let proof = accumulator.proof(number).unwrap();
+                assert_eq!(proof.record, accumulator.records()[(number - 100) as usize]);
+                assert!(proof.verify(root));
+
+                let mut tampered = proof.clone();
+                tampered.record.total_difficulty += U256::from(1);
+                assert!(!tampered.verify(root));
+
+                let mut tampered = proof;
+                tampered.index ^= 1;
+                assert!(!tampered.verify(root));
+            }
+
+            assert!(accumulator.proof(99).is_none());
+            assert!(accumulator.proof(100 + len as u64).is_none());
+        }
+    }
+}
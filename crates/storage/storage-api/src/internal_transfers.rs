@@ -0,0 +1,15 @@
+use reth_primitives::{BlockHashOrNumber, InternalTransfer};
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for fetching the [internal transfers](InternalTransfer) of blocks.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait InternalTransfersProvider: Send + Sync {
+    /// Get the internal transfers of every transaction in the block by block id.
+    ///
+    /// Returns `None` if the block is not found or not executed yet, and an error if the transfers
+    /// of the block have been pruned or were not recorded when the block was executed.
+    fn internal_transfers_by_block(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<Vec<InternalTransfer>>>>;
+}
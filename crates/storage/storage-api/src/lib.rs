@@ -25,12 +25,30 @@ pub use block_hash::*;
 mod header;
 pub use header::*;
 
+mod header_accumulator;
+pub use header_accumulator::*;
+
+mod internal_transfers;
+pub use internal_transfers::*;
+
+mod log_index;
+pub use log_index::*;
+
+mod logs;
+pub use logs::*;
+
 mod prune_checkpoint;
 pub use prune_checkpoint::*;
 
+mod range_rev;
+pub use range_rev::*;
+
 mod receipts;
 pub use receipts::*;
 
+mod reorg_journal;
+pub use reorg_journal::*;
+
 mod requests;
 pub use requests::*;
 
@@ -40,6 +58,9 @@ pub use stage_checkpoint::*;
 mod state;
 pub use state::*;
 
+mod state_diff;
+pub use state_diff::*;
+
 mod storage;
 pub use storage::*;
 
@@ -0,0 +1,35 @@
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Client trait for looking up the blocks with logs of an address or topic in the log index.
+///
+/// The log index is optional. It allows finding the blocks with matching logs in wide block
+/// ranges, without checking the logs bloom of every header.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait LogIndexProvider: Send + Sync {
+    /// Returns the range of blocks that are covered by the log index.
+    ///
+    /// Returns `None` if there is no log index.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the numbers of the blocks in the range with logs emitted by the address, in
+    /// ascending order.
+    ///
+    /// Only the blocks covered by the log index are returned.
+    fn blocks_with_log_address(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the numbers of the blocks in the range with logs that have the topic at any
+    /// position, in ascending order.
+    ///
+    /// Only the blocks covered by the log index are returned.
+    fn blocks_with_log_topic(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
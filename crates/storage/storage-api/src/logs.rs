@@ -0,0 +1,563 @@
+use crate::{BlockReader, LogIndexProvider};
+use reth_primitives::{
+    Address, BlockHash, BlockNumber, Bloom, BloomInput, Header, Log, Receipt, TxHash, B256,
+};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    collections::{BTreeSet, HashSet, VecDeque},
+    convert::Infallible,
+    iter::StepBy,
+    ops::RangeInclusive,
+};
+
+/// The maximum number of headers that are read at once when checking their logs bloom.
+const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
+
+/// A filter for logs by the address that emitted them and their topics.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    /// The addresses of which any may have emitted the log. Empty matches any address.
+    pub addresses: HashSet<Address>,
+    /// The topics of which any must be at each position of the log topics. Empty matches any
+    /// topic, or no topic at all.
+    pub topics: [HashSet<B256>; 4],
+}
+
+impl LogFilter {
+    /// Returns `true` if the filter matches any log.
+    pub fn is_unrestricted(&self) -> bool {
+        self.addresses.is_empty() && self.topics.iter().all(HashSet::is_empty)
+    }
+
+    /// Returns `true` if the log matches the filter.
+    pub fn matches(&self, log: &Log) -> bool {
+        if !self.addresses.is_empty() && !self.addresses.contains(&log.address) {
+            return false
+        }
+
+        self.topics.iter().enumerate().all(|(position, topics)| {
+            topics.is_empty() ||
+                log.topics().get(position).map_or(false, |topic| topics.contains(topic))
+        })
+    }
+
+    /// Returns `true` if the logs bloom may contain a log that matches the filter.
+    pub fn matches_bloom(&self, bloom: Bloom) -> bool {
+        let contains = |input: &[u8]| bloom.contains_input(BloomInput::Raw(input));
+
+        (self.addresses.is_empty() ||
+            self.addresses.iter().any(|address| contains(address.as_slice()))) &&
+            self.topics.iter().all(|topics| {
+                topics.is_empty() || topics.iter().any(|topic| contains(topic.as_slice()))
+            })
+    }
+}
+
+/// The position of a log in the chain, from which a paginated logs query is continued.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LogCursor {
+    /// The number of the block of the log.
+    pub block_number: BlockNumber,
+    /// The index of the log in the block.
+    pub log_index: u64,
+}
+
+/// A log with the metadata of the block and transaction it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockLog {
+    /// The log.
+    pub log: Log,
+    /// The number of the block.
+    pub block_number: BlockNumber,
+    /// The hash of the block.
+    pub block_hash: BlockHash,
+    /// The timestamp of the block.
+    pub block_timestamp: u64,
+    /// The hash of the transaction that emitted the log.
+    pub transaction_hash: TxHash,
+    /// The index of the transaction in the block.
+    pub transaction_index: u64,
+    /// The index of the log in the block.
+    pub log_index: u64,
+}
+
+/// A page of logs collected by a [`LogsCollector`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogsPage {
+    /// The matched logs, in the order of the chain.
+    pub logs: Vec<BlockLog>,
+    /// The cursor to continue the query from, if the limit was reached before the end of the
+    /// range.
+    pub next: Option<LogCursor>,
+}
+
+/// A block that may have logs matching a filter, see [`LogsProvider::log_blocks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogBlock {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The hash of the block.
+    pub hash: BlockHash,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+}
+
+impl LogBlock {
+    /// Returns the block of the header with the given hash.
+    pub const fn new(header: &Header, hash: BlockHash) -> Self {
+        Self { number: header.number, hash, timestamp: header.timestamp }
+    }
+}
+
+/// Client trait for finding the logs that match a filter in a range of blocks.
+///
+/// The blocks covered by the log index are looked up in the index, the logs bloom of all other
+/// headers is checked before their receipts are read.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait LogsProvider: BlockReader + LogIndexProvider {
+    /// Returns an iterator over the blocks in the range, starting at the block of the cursor, that
+    /// may have logs matching the filter.
+    ///
+    /// The receipts of these blocks are matched with a [`LogsCollector`], which allows reading
+    /// them from a cache. The blocks are looked up lazily, so that a query that stops once its
+    /// page is full doesn't read the rest of the range.
+    #[auto_impl(keep_default_for(&, Arc))]
+    fn log_blocks(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        filter: &LogFilter,
+        cursor: Option<LogCursor>,
+    ) -> ProviderResult<LogBlocks<&Self>>
+    where
+        Self: Sized,
+    {
+        LogBlocks::new(self, range, filter.clone(), cursor)
+    }
+}
+
+/// An iterator over the blocks of a range that may have logs matching a filter, see
+/// [`LogsProvider::log_blocks`].
+///
+/// The blocks are looked up in chunks of consecutive blocks when the iterator is advanced.
+#[derive(Debug)]
+pub struct LogBlocks<P> {
+    provider: P,
+    filter: LogFilter,
+    /// The chunks of the remaining parts of the range, with whether the part is covered by the
+    /// log index.
+    parts: VecDeque<(BlockRangeInclusiveIter, bool)>,
+    /// The blocks of the last chunk that were not returned yet.
+    blocks: VecDeque<LogBlock>,
+}
+
+impl<P: BlockReader + LogIndexProvider> LogBlocks<P> {
+    /// Creates an iterator over the blocks in the range, starting at the block of the cursor, that
+    /// may have logs matching the filter.
+    pub fn new(
+        provider: P,
+        range: RangeInclusive<BlockNumber>,
+        filter: LogFilter,
+        cursor: Option<LogCursor>,
+    ) -> ProviderResult<Self> {
+        let start =
+            cursor.map_or_else(|| *range.start(), |cursor| cursor.block_number.max(*range.start()));
+        let range = start..=*range.end();
+
+        let indexed = if filter.is_unrestricted() || range.is_empty() {
+            None
+        } else {
+            provider.log_index_range()?.and_then(|index| {
+                let indexed = *range.start().max(index.start())..=*range.end().min(index.end());
+                (!indexed.is_empty()).then_some(indexed)
+            })
+        };
+
+        let mut parts = VecDeque::new();
+        let mut push = |range: RangeInclusive<BlockNumber>, indexed| {
+            if !range.is_empty() {
+                parts.push_back((BlockRangeInclusiveIter::new(range, MAX_HEADERS_RANGE), indexed));
+            }
+        };
+        match indexed {
+            Some(indexed) => {
+                if *range.start() < *indexed.start() {
+                    push(*range.start()..=indexed.start() - 1, false);
+                }
+                push(indexed.clone(), true);
+                if *indexed.end() < *range.end() {
+                    push(indexed.end() + 1..=*range.end(), false);
+                }
+            }
+            None => push(range, false),
+        }
+
+        Ok(Self { provider, filter, parts, blocks: VecDeque::new() })
+    }
+
+    /// Looks up the blocks of the next chunk of the range.
+    ///
+    /// Returns `false` if the end of the range is reached.
+    fn next_chunk(&mut self) -> ProviderResult<bool> {
+        let (range, indexed) = loop {
+            let Some((chunks, indexed)) = self.parts.front_mut() else { return Ok(false) };
+            match chunks.next() {
+                Some((from, to)) => break (from..=to, *indexed),
+                None => {
+                    self.parts.pop_front();
+                }
+            }
+        };
+
+        if indexed {
+            for number in indexed_blocks(&self.provider, &self.filter, range)? {
+                let header = self
+                    .provider
+                    .header_by_number(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                let hash = self
+                    .provider
+                    .block_hash(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                self.blocks.push_back(LogBlock::new(&header, hash));
+            }
+        } else {
+            blocks_with_bloom(&self.provider, &self.filter, range, &mut self.blocks)?;
+        }
+
+        Ok(true)
+    }
+}
+
+impl<P: BlockReader + LogIndexProvider> Iterator for LogBlocks<P> {
+    type Item = ProviderResult<LogBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(block) = self.blocks.pop_front() {
+                return Some(Ok(block))
+            }
+
+            match self.next_chunk() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(err) => {
+                    // the iterator ends after an error
+                    self.parts.clear();
+                    return Some(Err(err))
+                }
+            }
+        }
+    }
+}
+
+/// Appends the blocks in the range of which the logs bloom matches the filter.
+fn blocks_with_bloom<P: BlockReader + ?Sized>(
+    provider: &P,
+    filter: &LogFilter,
+    range: RangeInclusive<BlockNumber>,
+    blocks: &mut VecDeque<LogBlock>,
+) -> ProviderResult<()> {
+    let headers = provider.headers_range(range)?;
+
+    for (idx, header) in headers.iter().enumerate() {
+        if !filter.matches_bloom(header.logs_bloom) {
+            continue
+        }
+
+        // these are consecutive headers, so we can use the parent hash of the next
+        // block to get the current header's hash
+        let hash = match headers.get(idx + 1) {
+            Some(parent) => parent.parent_hash,
+            None => provider
+                .block_hash(header.number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(header.number.into()))?,
+        };
+        blocks.push_back(LogBlock::new(header, hash));
+    }
+
+    Ok(())
+}
+
+/// Returns the blocks in the range that may have logs matching the addresses and topics of the
+/// filter, according to the log index.
+///
+/// Topics are indexed regardless of their position, so the logs of the blocks still need to be
+/// matched against the filter.
+fn indexed_blocks<P: LogIndexProvider + ?Sized>(
+    provider: &P,
+    filter: &LogFilter,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<BTreeSet<BlockNumber>> {
+    let mut candidates: Option<BTreeSet<BlockNumber>> = None;
+    let mut intersect = |blocks: BTreeSet<BlockNumber>| {
+        candidates = Some(match candidates.take() {
+            Some(candidates) => candidates.intersection(&blocks).copied().collect(),
+            None => blocks,
+        });
+    };
+
+    if !filter.addresses.is_empty() {
+        let mut blocks = BTreeSet::new();
+        for address in &filter.addresses {
+            blocks.extend(provider.blocks_with_log_address(*address, range.clone())?);
+        }
+        intersect(blocks);
+    }
+
+    for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+        let mut blocks = BTreeSet::new();
+        for topic in topics {
+            blocks.extend(provider.blocks_with_log_topic(*topic, range.clone())?);
+        }
+        intersect(blocks);
+    }
+
+    Ok(candidates.unwrap_or_default())
+}
+
+/// Collects the logs of blocks that match a filter into a [`LogsPage`].
+///
+/// This is the one place where receipts are matched against a filter, regardless of whether they
+/// are read from the database, a cache or a block that is not persisted yet.
+#[derive(Debug)]
+pub struct LogsCollector<'a> {
+    filter: &'a LogFilter,
+    cursor: Option<LogCursor>,
+    limit: usize,
+    page: LogsPage,
+}
+
+impl<'a> LogsCollector<'a> {
+    /// Creates a collector for at most `limit` logs that match the filter and come at or after the
+    /// cursor.
+    pub fn new(filter: &'a LogFilter, cursor: Option<LogCursor>, limit: usize) -> Self {
+        Self { filter, cursor, limit, page: LogsPage::default() }
+    }
+
+    /// Appends the matching logs of the block's receipts, looking up the transaction hashes of the
+    /// matching logs with the provider.
+    ///
+    /// Returns `true` if the page is full, in which case the position of the next matching log is
+    /// set as [`LogsPage::next`].
+    pub fn append_block_logs<P: BlockReader + ?Sized>(
+        &mut self,
+        provider: &P,
+        block: LogBlock,
+        receipts: &[Receipt],
+    ) -> ProviderResult<bool> {
+        // Lazy loaded number of the first transaction in the block.
+        let mut first_tx_num = None;
+
+        self.append_logs(block, receipts, |receipt_idx| {
+            let first_tx_num = match first_tx_num {
+                Some(num) => num,
+                None => {
+                    let num = provider
+                        .block_body_indices(block.number)?
+                        .ok_or_else(|| ProviderError::BlockBodyIndicesNotFound(block.number))?
+                        .first_tx_num;
+                    *first_tx_num.insert(num)
+                }
+            };
+
+            // This is safe because Transactions and Receipts have the same keys.
+            let transaction_id = first_tx_num + receipt_idx as u64;
+            Ok(provider
+                .transaction_by_id(transaction_id)?
+                .ok_or_else(|| ProviderError::TransactionNotFound(transaction_id.into()))?
+                .hash())
+        })
+    }
+
+    /// Appends the matching logs of the receipts of a block of which the transaction hashes are
+    /// known.
+    ///
+    /// Returns `true` if the page is full, see [`Self::append_block_logs`].
+    pub fn append_block_logs_with_tx_hashes<'r>(
+        &mut self,
+        block: LogBlock,
+        tx_hashes_and_receipts: impl IntoIterator<Item = (TxHash, &'r Receipt)>,
+    ) -> bool {
+        let (tx_hashes, receipts): (Vec<_>, Vec<_>) = tx_hashes_and_receipts.into_iter().unzip();
+        self.append_logs(block, receipts, |receipt_idx| Ok::<_, Infallible>(tx_hashes[receipt_idx]))
+            .unwrap_or_else(|never| match never {})
+    }
+
+    /// Returns the collected logs.
+    pub fn into_page(self) -> LogsPage {
+        self.page
+    }
+
+    /// Appends the matching logs of the receipts, looking up the hash of the transaction of a
+    /// receipt once it has a matching log.
+    fn append_logs<'r, E>(
+        &mut self,
+        block: LogBlock,
+        receipts: impl IntoIterator<Item = &'r Receipt>,
+        mut transaction_hash_of: impl FnMut(usize) -> Result<TxHash, E>,
+    ) -> Result<bool, E> {
+        let first_log_index = self
+            .cursor
+            .filter(|cursor| cursor.block_number == block.number)
+            .map_or(0, |cursor| cursor.log_index);
+
+        // Tracks the index of a log in the entire block.
+        let mut log_index: u64 = 0;
+
+        for (receipt_idx, receipt) in receipts.into_iter().enumerate() {
+            // The transaction hash of the current receipt.
+            let mut transaction_hash = None;
+
+            for log in &receipt.logs {
+                if log_index >= first_log_index && self.filter.matches(log) {
+                    if self.page.logs.len() >= self.limit {
+                        self.page.next = Some(LogCursor { block_number: block.number, log_index });
+                        return Ok(true)
+                    }
+
+                    // if this is the first match in the receipt's logs, look up the transaction
+                    // hash
+                    let transaction_hash = match transaction_hash {
+                        Some(hash) => hash,
+                        None => *transaction_hash.insert(transaction_hash_of(receipt_idx)?),
+                    };
+
+                    self.page.logs.push(BlockLog {
+                        log: log.clone(),
+                        block_number: block.number,
+                        block_hash: block.hash,
+                        block_timestamp: block.timestamp,
+                        transaction_hash,
+                        // The transaction and receipt index is always the same.
+                        transaction_index: receipt_idx as u64,
+                        log_index,
+                    });
+                }
+                log_index += 1;
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+/// An iterator that yields _inclusive_ block ranges of a given step size
+#[derive(Debug)]
+struct BlockRangeInclusiveIter {
+    iter: StepBy<RangeInclusive<u64>>,
+    step: u64,
+    end: u64,
+}
+
+impl BlockRangeInclusiveIter {
+    fn new(range: RangeInclusive<u64>, step: u64) -> Self {
+        Self { end: *range.end(), iter: range.step_by(step as usize + 1), step }
+    }
+}
+
+impl Iterator for BlockRangeInclusiveIter {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.iter.next()?;
+        let end = (start + self.step).min(self.end);
+        if start > end {
+            return None
+        }
+        Some((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn test_block_range_iter() {
+        for _ in 0..100 {
+            let mut rng = thread_rng();
+            let start = rng.gen::<u32>() as u64;
+            let end = start.saturating_add(rng.gen::<u32>() as u64);
+            let step = rng.gen::<u16>() as u64;
+            let range = start..=end;
+            let mut iter = BlockRangeInclusiveIter::new(range.clone(), step);
+            let (from, mut end) = iter.next().unwrap();
+            assert_eq!(from, start);
+            assert_eq!(end, (from + step).min(*range.end()));
+
+            for (next_from, next_end) in iter {
+                // ensure range starts with previous end + 1
+                assert_eq!(next_from, end + 1);
+                end = next_end;
+            }
+
+            assert_eq!(end, *range.end());
+        }
+    }
+
+    #[test]
+    fn logs_collector_pages() {
+        let address = Address::random();
+        let log = Log::new_unchecked(address, vec![], Default::default());
+        let other = Log::new_unchecked(Address::random(), vec![], Default::default());
+        let receipts = [
+            Receipt { logs: vec![log.clone(), other], ..Default::default() },
+            Receipt { logs: vec![log.clone()], ..Default::default() },
+        ];
+        let tx_hashes = [B256::random(), B256::random()];
+        let block = LogBlock { number: 1, hash: B256::random(), timestamp: 2 };
+        let filter = LogFilter { addresses: HashSet::from([address]), ..Default::default() };
+
+        let mut collector = LogsCollector::new(&filter, None, 1);
+        assert!(
+            collector.append_block_logs_with_tx_hashes(block, tx_hashes.into_iter().zip(&receipts))
+        );
+        let page = collector.into_page();
+        assert_eq!(page.logs.len(), 1);
+        assert_eq!(page.logs[0].transaction_hash, tx_hashes[0]);
+        assert_eq!(page.next, Some(LogCursor { block_number: 1, log_index: 2 }));
+
+        // continuing at the cursor skips the logs that were returned
+        let mut collector = LogsCollector::new(&filter, page.next, usize::MAX);
+        assert!(!collector
+            .append_block_logs_with_tx_hashes(block, tx_hashes.into_iter().zip(&receipts)));
+        let page = collector.into_page();
+        assert_eq!(page.next, None);
+        assert_eq!(page.logs.len(), 1);
+        assert_eq!(page.logs[0].log, log);
+        assert_eq!(page.logs[0].transaction_hash, tx_hashes[1]);
+        assert_eq!((page.logs[0].transaction_index, page.logs[0].log_index), (1, 2));
+    }
+
+    #[test]
+    fn log_filter_matches() {
+        let (address, topic) = (Address::random(), B256::random());
+        let log = Log::new_unchecked(address, vec![B256::ZERO, topic], Default::default());
+        let bloom = reth_primitives::logs_bloom(std::iter::once(&log));
+
+        let mut filter = LogFilter::default();
+        assert!(filter.is_unrestricted());
+        assert!(filter.matches(&log) && filter.matches_bloom(bloom));
+
+        filter.addresses.insert(address);
+        filter.topics[1].insert(topic);
+        assert!(!filter.is_unrestricted());
+        assert!(filter.matches(&log) && filter.matches_bloom(bloom));
+
+        // the topic must be at the position
+        filter.topics[1].clear();
+        filter.topics[0].insert(topic);
+        assert!(!filter.matches(&log));
+
+        // logs without a topic at the position don't match
+        let filter = LogFilter {
+            topics: [HashSet::new(), HashSet::new(), HashSet::from([topic]), HashSet::new()],
+            ..Default::default()
+        };
+        assert!(!filter.matches(&log));
+
+        let filter = LogFilter { addresses: HashSet::from([Address::ZERO]), ..Default::default() };
+        assert!(!filter.matches(&log) && !filter.matches_bloom(bloom));
+    }
+}
@@ -0,0 +1,127 @@
+use reth_primitives::BlockNumber;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// The number of blocks that a [`RangeRevIter`] reads at once.
+pub const RANGE_REV_CHUNK_SIZE: u64 = 100;
+
+/// An iterator over the items of a block range in descending order, that reads them lazily in
+/// chunks, starting at the end of the range.
+///
+/// This allows a caller that walks back from the tip to stop early, without reading the whole
+/// range. The blocks of the range that are not available are skipped. An error is returned once,
+/// after which the iteration ends.
+pub struct RangeRevIter<T, F> {
+    /// The part of the range that hasn't been read yet, `None` once the range is read.
+    range: Option<RangeInclusive<BlockNumber>>,
+    /// The number of blocks to read at once.
+    chunk_size: u64,
+    /// Reads the items of a chunk of the range, in descending order.
+    read_chunk: F,
+    /// The items of the current chunk that haven't been yielded yet.
+    items: std::vec::IntoIter<T>,
+}
+
+impl<T, F> RangeRevIter<T, F>
+where
+    F: FnMut(RangeInclusive<BlockNumber>) -> ProviderResult<Vec<T>>,
+{
+    /// Creates a new iterator over the range that reads [`RANGE_REV_CHUNK_SIZE`] blocks at once.
+    ///
+    /// `read_chunk` returns the items of the blocks of a chunk in descending order.
+    pub fn new(range: RangeInclusive<BlockNumber>, read_chunk: F) -> Self {
+        Self {
+            range: (!range.is_empty()).then_some(range),
+            chunk_size: RANGE_REV_CHUNK_SIZE,
+            read_chunk,
+            items: Vec::new().into_iter(),
+        }
+    }
+
+    /// Sets the number of blocks that are read at once.
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+}
+
+impl<T, F> Iterator for RangeRevIter<T, F>
+where
+    F: FnMut(RangeInclusive<BlockNumber>) -> ProviderResult<Vec<T>>,
+{
+    type Item = ProviderResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.next() {
+                return Some(Ok(item))
+            }
+            let (start, end) = self.range.take()?.into_inner();
+
+            let chunk_start = end.saturating_sub(self.chunk_size - 1).max(start);
+            let items = match (self.read_chunk)(chunk_start..=end) {
+                Ok(items) => items,
+                Err(err) => return Some(Err(err)),
+            };
+
+            if chunk_start > start {
+                self.range = Some(start..=chunk_start - 1);
+            }
+            self.items = items.into_iter();
+        }
+    }
+}
+
+impl<T, F> std::fmt::Debug for RangeRevIter<T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RangeRevIter")
+            .field("range", &self.range)
+            .field("chunk_size", &self.chunk_size)
+            .field("items", &self.items.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_storage_errors::provider::ProviderError;
+
+    #[test]
+    fn reads_range_in_descending_chunks() {
+        let mut chunks = Vec::new();
+        let iter = RangeRevIter::new(2..=9, |chunk| {
+            chunks.push(chunk.clone());
+            Ok(chunk.rev().collect())
+        })
+        .with_chunk_size(3);
+        assert_eq!(
+            iter.collect::<ProviderResult<Vec<_>>>().unwrap(),
+            (2..=9).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(chunks, vec![7..=9, 4..=6, 2..=3]);
+    }
+
+    #[test]
+    fn stops_early() {
+        let mut chunks = 0;
+        let iter = RangeRevIter::new(0..=u64::MAX, |chunk| {
+            chunks += 1;
+            Ok(chunk.rev().collect())
+        })
+        .with_chunk_size(4);
+        assert_eq!(
+            iter.take(5).collect::<ProviderResult<Vec<_>>>().unwrap(),
+            ((u64::MAX - 4)..=u64::MAX).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(chunks, 2);
+    }
+
+    #[test]
+    fn ends_after_error() {
+        let mut iter =
+            RangeRevIter::new(0..=10, |_| Err::<Vec<u64>, _>(ProviderError::UnsupportedProvider));
+        assert!(matches!(iter.next(), Some(Err(ProviderError::UnsupportedProvider))));
+        assert!(iter.next().is_none());
+    }
+}
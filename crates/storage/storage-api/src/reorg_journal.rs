@@ -0,0 +1,10 @@
+use reth_primitives::ReorgRecord;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for reading the [reorgs](ReorgRecord) of the canonical chain observed by the node.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ReorgJournalReader: Send + Sync {
+    /// Returns up to `limit` of the most recently observed reorgs with their sequence numbers,
+    /// starting with the most recent one.
+    fn latest_reorgs(&self, limit: usize) -> ProviderResult<Vec<(u64, ReorgRecord)>>;
+}
@@ -1,9 +1,12 @@
-use super::{AccountReader, BlockHashReader, BlockIdReader, StateProofProvider, StateRootProvider};
+use super::{
+    AccountReader, BlockHashReader, BlockIdReader, StateProofProvider, StateRootProvider,
+    StorageRangeProvider,
+};
 use auto_impl::auto_impl;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode, StorageKey,
-    StorageValue, B256, KECCAK_EMPTY, U256,
+    Account, Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode,
+    StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
@@ -13,7 +16,13 @@ pub type StateProviderBox = Box<dyn StateProvider>;
 /// An abstraction for a type that provides state data.
 #[auto_impl(&, Arc, Box)]
 pub trait StateProvider:
-    BlockHashReader + AccountReader + StateRootProvider + StateProofProvider + Send + Sync
+    BlockHashReader
+    + AccountReader
+    + StateRootProvider
+    + StateProofProvider
+    + StorageRangeProvider
+    + Send
+    + Sync
 {
     /// Get storage of given account.
     fn storage(
@@ -22,6 +31,24 @@ pub trait StateProvider:
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>>;
 
+    /// Get storage of multiple slots, in the order of the given slots.
+    ///
+    /// A more efficient version than calling [`StateProvider::storage`] repeatedly.
+    fn storage_multi(
+        &self,
+        slots: &[(Address, StorageKey)],
+    ) -> ProviderResult<Vec<Option<StorageValue>>> {
+        slots.iter().map(|(account, storage_key)| self.storage(*account, *storage_key)).collect()
+    }
+
+    /// Get basic account information for multiple accounts, in the order of the given addresses.
+    ///
+    /// A more efficient version than calling [`AccountReader::basic_account`] repeatedly. The
+    /// account is `None` if it doesn't exist.
+    fn basic_accounts(&self, addresses: &[Address]) -> ProviderResult<Vec<Option<Account>>> {
+        addresses.iter().map(|address| self.basic_account(*address)).collect()
+    }
+
     /// Get account code by its hash
     fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>>;
 
@@ -144,7 +171,8 @@ pub trait StateProviderFactory: BlockIdReader + Send + Sync {
 
     /// Storage provider for pending state for the given block hash.
     ///
-    /// Represents the state at the block that extends the canonical chain.
+    /// Represents the state at a block that is not part of the canonical chain yet, like the block
+    /// that extends the canonical chain or a block of a fork.
     ///
     /// If the block couldn't be found, returns `None`.
     fn pending_state_by_hash(&self, block_hash: B256) -> ProviderResult<Option<StateProviderBox>>;
@@ -223,3 +251,14 @@ pub trait BlockExecutionForkProvider {
 pub trait FullExecutionDataProvider: ExecutionDataProvider + BlockExecutionForkProvider {}
 
 impl<T> FullExecutionDataProvider for T where T: ExecutionDataProvider + BlockExecutionForkProvider {}
+
+/// Reader of the post-execution state of canonical blocks.
+#[auto_impl(&, Arc, Box)]
+pub trait StateReader: Send + Sync {
+    /// Returns the [`ExecutionOutcome`] of the canonical block: the changes of the state by the
+    /// block, with the state before the block as reverts, and the receipts and requests of the
+    /// block.
+    ///
+    /// Returns `None` if the block is not found.
+    fn execution_outcome(&self, block: BlockNumber) -> ProviderResult<Option<ExecutionOutcome>>;
+}
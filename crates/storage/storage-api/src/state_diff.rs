@@ -0,0 +1,13 @@
+use reth_execution_types::StateDiff;
+use reth_primitives::BlockHashOrNumber;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for fetching the [state changes](StateDiff) of blocks.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait StateDiffProvider: Send + Sync {
+    /// Get the changes of the balance, nonce, code and storage of every account changed by the
+    /// block, by block id.
+    ///
+    /// Returns `None` if the block is not found.
+    fn state_diff_by_block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<StateDiff>>;
+}
@@ -29,3 +29,16 @@ pub trait StorageReader: Send + Sync {
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>>;
 }
+
+/// Storage changeset reader
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait StorageChangeSetReader: Send + Sync {
+    /// Iterate over storage changesets and return the storage state from before this block, by
+    /// address and storage key.
+    ///
+    /// A value of zero means that the storage slot didn't exist before the block.
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(Address, StorageEntry)>>;
+}
@@ -47,6 +47,18 @@ pub trait TransactionsProvider: BlockNumReader + Send + Sync {
         hash: TxHash,
     ) -> ProviderResult<Option<(TransactionSigned, TransactionMeta)>>;
 
+    /// Get transactions by transaction hashes and additional metadata of the blocks the
+    /// transactions were mined in.
+    ///
+    /// Returns the transactions in the order of the hashes, with `None` for every transaction that
+    /// is not found. Providers can look up all hashes at once, instead of one at a time.
+    fn transactions_by_hashes(
+        &self,
+        hashes: &[TxHash],
+    ) -> ProviderResult<Vec<Option<(TransactionSigned, TransactionMeta)>>> {
+        hashes.iter().map(|hash| self.transaction_by_hash_with_meta(*hash)).collect()
+    }
+
     /// Get transaction block number
     fn transaction_block(&self, id: TxNumber) -> ProviderResult<Option<BlockNumber>>;
 
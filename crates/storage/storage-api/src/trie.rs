@@ -2,7 +2,7 @@ use reth_primitives::{Address, Bytes, B256};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     prefix_set::TriePrefixSetsMut, updates::TrieUpdates, AccountProof, HashedPostState,
-    HashedStorage,
+    HashedStorage, StorageRange,
 };
 use revm::db::BundleState;
 use std::collections::HashMap;
@@ -99,3 +99,17 @@ pub trait StateProofProvider: Send + Sync {
         target: HashedPostState,
     ) -> ProviderResult<HashMap<B256, Bytes>>;
 }
+
+/// A type that can iterate over the hashed storage of an account on top of a given post state.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait StorageRangeProvider: Send + Sync {
+    /// Returns up to `limit` hashed storage entries of the account in the `HashedPostState` on
+    /// top of the current state, starting at the first hashed slot greater or equal to `start`.
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange>;
+}
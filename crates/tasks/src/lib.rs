@@ -14,7 +14,10 @@
 
 use crate::{
     metrics::{IncCounterOnDrop, TaskExecutorMetrics},
-    shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, Shutdown, Signal},
+    shutdown::{
+        signal, GracefulShutdown, GracefulShutdownGuard, PhaseShutdowns, Shutdown,
+        ShutdownDeadlines, ShutdownPhase, Signal,
+    },
 };
 use dyn_clone::DynClone;
 use futures_util::{
@@ -36,7 +39,7 @@ use tokio::{
     sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     task::JoinHandle,
 };
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 use tracing_futures::Instrument;
 
 pub mod metrics;
@@ -164,6 +167,12 @@ pub struct TaskManager {
     on_shutdown: Shutdown,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// The [Signal]s to fire when the tasks of a [`ShutdownPhase`] should be shutdown.
+    ///
+    /// These are fired when dropped.
+    phase_signals: [Option<Signal>; 6],
+    /// Receivers of the shutdown signals of the [`ShutdownPhase`]s.
+    phase_shutdowns: PhaseShutdowns,
 }
 
 // === impl TaskManager ===
@@ -183,6 +192,7 @@ impl TaskManager {
     pub fn new(handle: Handle) -> Self {
         let (panicked_tasks_tx, panicked_tasks_rx) = unbounded_channel();
         let (signal, on_shutdown) = signal();
+        let (phase_signals, phase_shutdowns) = PhaseShutdowns::new();
         Self {
             handle,
             panicked_tasks_tx,
@@ -190,6 +200,8 @@ impl TaskManager {
             signal: Some(signal),
             on_shutdown,
             graceful_tasks: Arc::new(AtomicUsize::new(0)),
+            phase_signals: phase_signals.map(Some),
+            phase_shutdowns,
         }
     }
 
@@ -202,37 +214,94 @@ impl TaskManager {
             panicked_tasks_tx: self.panicked_tasks_tx.clone(),
             metrics: Default::default(),
             graceful_tasks: Arc::clone(&self.graceful_tasks),
+            phase_shutdowns: self.phase_shutdowns.clone(),
         }
     }
 
     /// Fires the shutdown signal and awaits until all tasks are shutdown.
+    ///
+    /// The tasks of the [`ShutdownPhase`]s are shut down first, one phase after the other.
     pub fn graceful_shutdown(self) {
-        let _ = self.do_graceful_shutdown(None);
+        let _ = self.do_graceful_shutdown(None, None);
     }
 
     /// Fires the shutdown signal and awaits until all tasks are shutdown.
     ///
+    /// The tasks of the [`ShutdownPhase`]s are shut down first, one phase after the other, with
+    /// the default deadline of each phase. The timeout bounds the entire shutdown, so a phase is
+    /// given less time than its deadline if the timeout elapses first.
+    ///
     /// Returns true if all tasks were shutdown before the timeout elapsed.
     pub fn graceful_shutdown_with_timeout(self, timeout: std::time::Duration) -> bool {
-        self.do_graceful_shutdown(Some(timeout))
+        self.do_graceful_shutdown(
+            Some(ShutdownDeadlines::default().with_remaining(timeout)),
+            Some(std::time::Instant::now() + timeout),
+        )
     }
 
-    fn do_graceful_shutdown(self, timeout: Option<std::time::Duration>) -> bool {
-        drop(self.signal);
-        let when = timeout.map(|t| std::time::Instant::now() + t);
-        while self.graceful_tasks.load(Ordering::Relaxed) > 0 {
-            if when.map(|when| std::time::Instant::now() > when).unwrap_or(false) {
-                debug!("graceful shutdown timed out");
-                return false
+    /// Shuts down the tasks of the [`ShutdownPhase`]s one phase after the other, and then the tasks
+    /// without a phase.
+    ///
+    /// The next phase is shut down once all tasks of a phase finished their shutdown, or once the
+    /// deadline of the phase elapsed.
+    ///
+    /// Returns true if all tasks were shutdown before their deadlines elapsed.
+    pub fn graceful_shutdown_with_deadlines(self, deadlines: ShutdownDeadlines) -> bool {
+        self.do_graceful_shutdown(Some(deadlines), None)
+    }
+
+    /// Shuts down all tasks, waiting at most until `until` in total if set.
+    fn do_graceful_shutdown(
+        mut self,
+        deadlines: Option<ShutdownDeadlines>,
+        until: Option<std::time::Instant>,
+    ) -> bool {
+        // caps the deadline at the time left until the entire shutdown must be done
+        let bounded = |deadline: std::time::Duration| match until {
+            Some(until) => deadline.min(until.saturating_duration_since(std::time::Instant::now())),
+            None => deadline,
+        };
+
+        let mut all_shutdown = true;
+        for phase in ShutdownPhase::ALL {
+            drop(self.phase_signals[phase.index()].take());
+            let deadline = deadlines.map(|deadlines| bounded(deadlines.phase(phase)));
+            if !wait_for_graceful_tasks(self.phase_shutdowns.graceful_tasks(phase), deadline) {
+                warn!(?phase, ?deadline, "shutdown phase timed out");
+                all_shutdown = false;
             }
-            std::hint::spin_loop();
+        }
+
+        drop(self.signal.take());
+        let deadline = deadlines.map(|deadlines| bounded(deadlines.remaining()));
+        if !wait_for_graceful_tasks(&self.graceful_tasks, deadline) {
+            debug!("graceful shutdown timed out");
+            return false
         }
 
         debug!("gracefully shut down");
-        true
+        all_shutdown
     }
 }
 
+/// Waits until all [`GracefulShutdown`] tasks of the counter finished, or until the deadline
+/// elapsed.
+///
+/// Returns true if all tasks finished.
+fn wait_for_graceful_tasks(
+    graceful_tasks: &AtomicUsize,
+    deadline: Option<std::time::Duration>,
+) -> bool {
+    let when = deadline.map(|t| std::time::Instant::now() + t);
+    while graceful_tasks.load(Ordering::Relaxed) > 0 {
+        if when.map(|when| std::time::Instant::now() > when).unwrap_or(false) {
+            return false
+        }
+        std::hint::spin_loop();
+    }
+    true
+}
+
 /// An endless future that resolves if a critical task panicked.
 ///
 /// See [`TaskExecutor::spawn_critical`]
@@ -292,6 +361,8 @@ pub struct TaskExecutor {
     metrics: TaskExecutorMetrics,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Receivers of the shutdown signals of the [`ShutdownPhase`]s.
+    phase_shutdowns: PhaseShutdowns,
 }
 
 // === impl TaskExecutor ===
@@ -499,12 +570,54 @@ impl TaskExecutor {
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        let panicked_tasks_tx = self.panicked_tasks_tx.clone();
         let on_shutdown = GracefulShutdown::new(
             self.on_shutdown.clone(),
             GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks)),
         );
-        let fut = f(on_shutdown);
+        self.spawn_critical_graceful(name, f(on_shutdown))
+    }
+
+    /// This spawns a critical task onto the runtime that is shut down in the given
+    /// [`ShutdownPhase`].
+    ///
+    /// When the [`TaskManager`] is shut down, the tasks of a phase receive the shutdown signal
+    /// after the tasks of all previous phases finished their shutdown, or the deadlines of these
+    /// phases elapsed. See [`TaskManager::graceful_shutdown_with_deadlines`].
+    ///
+    /// ```no_run
+    /// # async fn t(executor: reth_tasks::TaskExecutor) {
+    /// use reth_tasks::shutdown::ShutdownPhase;
+    ///
+    /// executor.spawn_critical_with_shutdown_phase(
+    ///     "flush",
+    ///     ShutdownPhase::StaticFiles,
+    ///     |shutdown| async move {
+    ///         // await the shutdown signal of the phase
+    ///         let guard = shutdown.await;
+    ///         // flush to disk before the next phase is shut down
+    ///         drop(guard);
+    ///     },
+    /// );
+    /// # }
+    /// ```
+    pub fn spawn_critical_with_shutdown_phase<F>(
+        &self,
+        name: &'static str,
+        phase: ShutdownPhase,
+        f: impl FnOnce(GracefulShutdown) -> F,
+    ) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_critical_graceful(name, f(self.phase_shutdowns.graceful_shutdown(phase)))
+    }
+
+    /// Spawns the critical task with a graceful shutdown signal onto the runtime.
+    fn spawn_critical_graceful<F>(&self, name: &'static str, fut: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let panicked_tasks_tx = self.panicked_tasks_tx.clone();
 
         // wrap the task in catch unwind
         let task = std::panic::AssertUnwindSafe(fut)
@@ -731,6 +844,88 @@ mod tests {
         assert!(val.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_manager_graceful_shutdown_phases() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let o = order.clone();
+        executor.spawn_critical_with_graceful_shutdown_signal("no phase", |shutdown| async move {
+            let _guard = shutdown.await;
+            o.lock().unwrap().push(None);
+        });
+        // spawn in reverse order, to check that the phases are shut down in order
+        for phase in ShutdownPhase::ALL.into_iter().rev() {
+            let o = order.clone();
+            executor.spawn_critical_with_shutdown_phase("phase", phase, |shutdown| async move {
+                let _guard = shutdown.await;
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                o.lock().unwrap().push(Some(phase));
+            });
+        }
+
+        manager.graceful_shutdown();
+        let mut expected = ShutdownPhase::ALL.map(Some).to_vec();
+        expected.push(None);
+        assert_eq!(*order.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_manager_graceful_shutdown_phase_deadline() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        executor.spawn_critical_with_shutdown_phase(
+            "stuck",
+            ShutdownPhase::Persistence,
+            |shutdown| async move {
+                let _guard = shutdown.await;
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            },
+        );
+        let val = Arc::new(AtomicBool::new(false));
+        let c = val.clone();
+        executor.spawn_critical_with_shutdown_phase(
+            "database",
+            ShutdownPhase::Database,
+            |shutdown| async move {
+                let _guard = shutdown.await;
+                c.store(true, Ordering::Relaxed);
+            },
+        );
+
+        let deadlines = ShutdownDeadlines::default()
+            .with_phase(ShutdownPhase::Persistence, Duration::from_millis(100));
+        assert!(!manager.graceful_shutdown_with_deadlines(deadlines));
+        assert!(val.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_manager_graceful_shutdown_timeout_bounds_phases() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        executor.spawn_critical_with_shutdown_phase(
+            "stuck",
+            ShutdownPhase::Persistence,
+            |shutdown| async move {
+                let _guard = shutdown.await;
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            },
+        );
+
+        let start = std::time::Instant::now();
+        assert!(!manager.graceful_shutdown_with_timeout(Duration::from_millis(100)));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
     #[test]
     fn test_manager_graceful_shutdown_many() {
         let runtime = tokio::runtime::Runtime::new().unwrap();
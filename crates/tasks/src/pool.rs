@@ -75,6 +75,28 @@ impl BlockingTaskPool {
         Self::builder().build().map(Self::new)
     }
 
+    /// Convenience function to build a new threadpool with the given number of threads, whose
+    /// threads run with the given [`BlockingTaskPriority`].
+    ///
+    /// If `num_threads` is `0`, the number of threads is chosen by rayon, see
+    /// [`rayon::ThreadPoolBuilder::num_threads`].
+    pub fn build_with_priority(
+        num_threads: usize,
+        priority: BlockingTaskPriority,
+    ) -> Result<Self, rayon::ThreadPoolBuildError> {
+        Self::builder()
+            .num_threads(num_threads)
+            .thread_name(|i| format!("reth-blocking-{i}"))
+            .start_handler(move |_| priority.apply_to_current_thread())
+            .build()
+            .map(Self::new)
+    }
+
+    /// Returns the number of threads of the underlying threadpool.
+    pub fn num_threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
     /// Asynchronous wrapper around Rayon's
     /// [`ThreadPool::spawn`](rayon::ThreadPool::spawn).
     ///
@@ -118,6 +140,51 @@ impl BlockingTaskPool {
     }
 }
 
+/// Scheduling priority of the threads of a [`BlockingTaskPool`].
+///
+/// The priority is applied to the operating system threads of the pool, so that the threads of
+/// the node that run critical work, like state root computation and block execution, are
+/// scheduled before the threads of a [`BlockingTaskPriority::Low`] pool under load.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BlockingTaskPriority {
+    /// Regular work that should not delay critical work, for example RPC tracing calls.
+    Low,
+    /// The scheduling priority of the process.
+    #[default]
+    Normal,
+}
+
+impl BlockingTaskPriority {
+    /// The nice value added to the threads of a [`BlockingTaskPriority::Low`] pool.
+    #[cfg(target_os = "linux")]
+    const LOW_NICE_INCREMENT: libc::c_int = 10;
+
+    /// Applies the priority to the current thread.
+    ///
+    /// This only has an effect on Linux, where the nice value of a thread can be changed
+    /// independently of the other threads of the process.
+    fn apply_to_current_thread(self) {
+        #[cfg(target_os = "linux")]
+        if self == Self::Low {
+            // SAFETY: `getpriority` and `setpriority` don't access memory, on Linux the `0` target
+            // refers to the calling thread only
+            let lowered = unsafe {
+                *libc::__errno_location() = 0;
+                let nice = libc::getpriority(libc::PRIO_PROCESS, 0);
+                (nice != -1 || *libc::__errno_location() == 0) &&
+                    libc::setpriority(libc::PRIO_PROCESS, 0, nice + Self::LOW_NICE_INCREMENT) ==
+                        0
+            };
+            if !lowered {
+                tracing::warn!(
+                    err = %std::io::Error::last_os_error(),
+                    "Failed to lower the priority of a blocking task pool thread"
+                );
+            }
+        }
+    }
+}
+
 /// Async handle for a blocking task running in a Rayon thread pool.
 ///
 /// ## Panics
@@ -171,4 +238,17 @@ mod tests {
         let res = res.await;
         assert!(res.is_err());
     }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn blocking_pool_low_priority() {
+        let nice = || unsafe { libc::getpriority(libc::PRIO_PROCESS, 0) };
+
+        let pool = BlockingTaskPool::build_with_priority(1, BlockingTaskPriority::Normal).unwrap();
+        assert_eq!(pool.num_threads(), 1);
+        assert_eq!(pool.spawn(nice).await.unwrap(), nice());
+
+        let pool = BlockingTaskPool::build_with_priority(1, BlockingTaskPriority::Low).unwrap();
+        assert!(pool.spawn(nice).await.unwrap() > nice());
+    }
 }
@@ -9,6 +9,7 @@ use std::{
     pin::Pin,
     sync::{atomic::AtomicUsize, Arc},
     task::{ready, Context, Poll},
+    time::Duration,
 };
 use tokio::sync::oneshot;
 
@@ -103,6 +104,130 @@ pub fn signal() -> (Signal, Shutdown) {
     (Signal(sender), Shutdown(receiver.shared()))
 }
 
+/// A phase of the coordinated shutdown of the node.
+///
+/// Tasks that are spawned in a phase are shut down after all tasks of the previous phases finished
+/// their shutdown or the deadline of a phase elapsed, and before the tasks without a phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShutdownPhase {
+    /// Stops accepting RPC requests, including engine API requests.
+    Rpc,
+    /// Writes the transaction pool journal to disk.
+    TransactionPool,
+    /// Stops the consensus engine and the execution extensions, and waits for a running backfill
+    /// sync to finish.
+    Sync,
+    /// Finishes writing the blocks that are being persisted and pruning them, and stops persisting
+    /// new blocks.
+    Persistence,
+    /// Commits and closes the static files.
+    StaticFiles,
+    /// Closes the database and marks the data directory as consistent.
+    Database,
+}
+
+impl ShutdownPhase {
+    /// All phases, in the order they are shut down.
+    pub const ALL: [Self; 6] = [
+        Self::Rpc,
+        Self::TransactionPool,
+        Self::Sync,
+        Self::Persistence,
+        Self::StaticFiles,
+        Self::Database,
+    ];
+
+    /// Returns the index of the phase in [`Self::ALL`].
+    pub(crate) const fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns the default deadline of the phase.
+    pub const fn default_deadline(&self) -> Duration {
+        match self {
+            Self::Rpc | Self::TransactionPool => Duration::from_secs(5),
+            Self::Sync | Self::Persistence => Duration::from_secs(30),
+            Self::StaticFiles | Self::Database => Duration::from_secs(10),
+        }
+    }
+}
+
+/// Deadlines of the phases of a coordinated shutdown, see [`ShutdownPhase`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownDeadlines {
+    /// Deadlines of the phases, by [`ShutdownPhase::index`].
+    phases: [Duration; 6],
+    /// Deadline of the tasks that were not spawned in a phase.
+    remaining: Duration,
+}
+
+impl Default for ShutdownDeadlines {
+    fn default() -> Self {
+        Self {
+            phases: ShutdownPhase::ALL.map(|phase| phase.default_deadline()),
+            remaining: Duration::from_secs(5),
+        }
+    }
+}
+
+impl ShutdownDeadlines {
+    /// Returns the deadline of the phase.
+    pub const fn phase(&self, phase: ShutdownPhase) -> Duration {
+        self.phases[phase.index()]
+    }
+
+    /// Returns the deadline of the tasks that were not spawned in a phase.
+    pub const fn remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    /// Sets the deadline of the phase.
+    pub const fn with_phase(mut self, phase: ShutdownPhase, deadline: Duration) -> Self {
+        self.phases[phase.index()] = deadline;
+        self
+    }
+
+    /// Sets the deadline of the tasks that were not spawned in a phase.
+    pub const fn with_remaining(mut self, deadline: Duration) -> Self {
+        self.remaining = deadline;
+        self
+    }
+}
+
+/// Shutdown signals of the [`ShutdownPhase`]s, which are shared by the
+/// [`TaskExecutor`](crate::TaskExecutor)s.
+#[derive(Debug, Clone)]
+pub(crate) struct PhaseShutdowns {
+    /// Receivers of the shutdown signal of each phase.
+    shutdowns: [Shutdown; 6],
+    /// How many [`GracefulShutdown`] tasks of each phase are currently active.
+    graceful_tasks: [Arc<AtomicUsize>; 6],
+}
+
+impl PhaseShutdowns {
+    /// Creates the shutdown signals of all phases.
+    pub(crate) fn new() -> ([Signal; 6], Self) {
+        let [rpc, pool, sync, persistence, static_files, database] =
+            ShutdownPhase::ALL.map(|_| signal());
+        let signals = [rpc.0, pool.0, sync.0, persistence.0, static_files.0, database.0];
+        let shutdowns = [rpc.1, pool.1, sync.1, persistence.1, static_files.1, database.1];
+        (signals, Self { shutdowns, graceful_tasks: Default::default() })
+    }
+
+    /// Returns a new [`GracefulShutdown`] of a task in the phase.
+    pub(crate) fn graceful_shutdown(&self, phase: ShutdownPhase) -> GracefulShutdown {
+        GracefulShutdown::new(
+            self.shutdowns[phase.index()].clone(),
+            GracefulShutdownGuard::new(Arc::clone(&self.graceful_tasks[phase.index()])),
+        )
+    }
+
+    /// Returns the number of [`GracefulShutdown`] tasks of the phase that are currently active.
+    pub(crate) fn graceful_tasks(&self, phase: ShutdownPhase) -> &AtomicUsize {
+        &self.graceful_tasks[phase.index()]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +269,21 @@ mod tests {
         join_all(tasks).await;
     }
 
+    #[test]
+    fn shutdown_phases_in_order() {
+        let mut phases = ShutdownPhase::ALL;
+        phases.sort();
+        assert_eq!(phases, ShutdownPhase::ALL);
+
+        let deadlines =
+            ShutdownDeadlines::default().with_phase(ShutdownPhase::Rpc, Duration::from_secs(1));
+        assert_eq!(deadlines.phase(ShutdownPhase::Rpc), Duration::from_secs(1));
+        assert_eq!(
+            deadlines.phase(ShutdownPhase::Persistence),
+            ShutdownPhase::Persistence.default_deadline()
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_drop_signal_from_thread() {
         let (signal, shutdown) = signal();
@@ -24,6 +24,11 @@ pub const DEFAULT_PRICE_BUMP: u128 = 10;
 /// This enforces that a blob transaction requires a 100% price bump to be replaced
 pub const REPLACE_BLOB_PRICE_BUMP: u128 = 100;
 
+/// Default fee hysteresis (in %) for the promotion of parked transactions on a new head.
+///
+/// Parked transactions are promoted as soon as their fee caps cover the fees of the next block.
+pub const DEFAULT_FEE_HYSTERESIS: u64 = 0;
+
 /// Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -42,6 +47,15 @@ pub struct PoolConfig {
     /// How to handle locally received transactions:
     /// [`TransactionOrigin::Local`](crate::TransactionOrigin).
     pub local_transactions_config: LocalTransactionConfig,
+    /// Fee hysteresis (in %) for moving transactions between the pending and the parked fee
+    /// sub-pools on a new head.
+    ///
+    /// On every head update the fee caps of all transactions are checked against the projected
+    /// base fee and blob fee of the next block. A pending transaction is demoted as soon as its
+    /// fee caps no longer cover them, but a parked transaction is only promoted once its fee
+    /// caps exceed them by this percentage, so that fees that oscillate around a fee cap don't
+    /// move the transaction back and forth every block.
+    pub fee_hysteresis: u64,
     /// Bound on number of pending transactions from `reth_network::TransactionsManager` to buffer.
     pub pending_tx_listener_buffer_size: usize,
     /// Bound on number of new transactions from `reth_network::TransactionsManager` to buffer.
@@ -69,6 +83,7 @@ impl Default for PoolConfig {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
+            fee_hysteresis: DEFAULT_FEE_HYSTERESIS,
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
         }
@@ -135,6 +135,24 @@ impl PoolError {
             }
         }
     }
+
+    /// Returns `true` if the transaction was rejected because its fees are too low for the current
+    /// state of the pool, e.g. below the base fee or below the cheapest transactions of a full
+    /// pool.
+    #[inline]
+    pub const fn is_underpriced(&self) -> bool {
+        matches!(
+            self.kind,
+            PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(_) |
+                PoolErrorKind::DiscardedOnInsert |
+                PoolErrorKind::InvalidTransaction(
+                    InvalidPoolTransactionError::Underpriced |
+                        InvalidPoolTransactionError::Consensus(
+                            InvalidTransactionError::FeeCapTooLow
+                        )
+                )
+        )
+    }
 }
 
 /// Represents all errors that can happen when validating transactions for the pool for EIP-4844
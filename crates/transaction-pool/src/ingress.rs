@@ -0,0 +1,437 @@
+//! Recording and replaying of the transaction pool ingress.
+//!
+//! The [`IngressRecorder`] appends every transaction hash lookup of a network announcement and
+//! every transaction submission to a file, together with the time since the recording started.
+//! [`replay_ingress`] feeds the recorded ingress back into a pool at the original pacing, which is
+//! useful to reproduce pool performance issues that were observed in production.
+//!
+//! Records are buffered in memory and written in batches. If the node crashes, the buffered
+//! records are lost and the last record in the file may be incomplete, which
+//! [`read_ingress_records`] treats as the end of the recording.
+//!
+//! Note: blob sidecars are not recorded, so replayed blob transactions are expected to fail
+//! validation.
+
+use crate::{PoolTransaction, TransactionOrigin, TransactionPool};
+use alloy_rlp::{Decodable, Encodable, Header, RlpDecodable, RlpEncodable, EMPTY_LIST_CODE};
+use futures_util::{stream::FuturesUnordered, StreamExt};
+use parking_lot::Mutex;
+use reth_eth_wire_types::HandleMempoolData;
+use reth_fs_util::FsPathError;
+use reth_primitives::{Address, TransactionSigned, TransactionSignedEcRecovered, TxHash};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+/// An event of the pool ingress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IngressEvent {
+    /// Hashes of announced or broadcast transactions that were checked against the pool.
+    Announcement(Vec<TxHash>),
+    /// Transactions that were submitted to the pool.
+    Transactions {
+        /// Origin of the transactions.
+        origin: TransactionOrigin,
+        /// The submitted transactions.
+        transactions: Vec<TransactionSignedEcRecovered>,
+    },
+}
+
+/// A recorded [`IngressEvent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngressRecord {
+    /// Time since the recording started.
+    pub timestamp: Duration,
+    /// The recorded event.
+    pub event: IngressEvent,
+}
+
+/// The encoding of an [`IngressRecord`] in the recording file.
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct RawIngressRecord {
+    /// Time since the recording started in microseconds.
+    timestamp: u64,
+    /// `0` for announcements, `1` for transactions.
+    kind: u8,
+    origin: u8,
+    hashes: Vec<TxHash>,
+    transactions: Vec<TransactionSigned>,
+    senders: Vec<Address>,
+}
+
+const ANNOUNCEMENT: u8 = 0;
+const TRANSACTIONS: u8 = 1;
+
+impl From<&IngressRecord> for RawIngressRecord {
+    fn from(record: &IngressRecord) -> Self {
+        let timestamp = record.timestamp.as_micros() as u64;
+        match &record.event {
+            IngressEvent::Announcement(hashes) => Self {
+                timestamp,
+                kind: ANNOUNCEMENT,
+                origin: 0,
+                hashes: hashes.clone(),
+                transactions: Vec::new(),
+                senders: Vec::new(),
+            },
+            IngressEvent::Transactions { origin, transactions } => Self {
+                timestamp,
+                kind: TRANSACTIONS,
+                origin: match origin {
+                    TransactionOrigin::Local => 0,
+                    TransactionOrigin::External => 1,
+                    TransactionOrigin::Private => 2,
+                },
+                hashes: Vec::new(),
+                senders: transactions.iter().map(|tx| tx.signer()).collect(),
+                transactions: transactions.iter().map(|tx| tx.clone().into_signed()).collect(),
+            },
+        }
+    }
+}
+
+impl TryFrom<RawIngressRecord> for IngressRecord {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(raw: RawIngressRecord) -> Result<Self, Self::Error> {
+        let event = match raw.kind {
+            ANNOUNCEMENT => IngressEvent::Announcement(raw.hashes),
+            TRANSACTIONS => {
+                let origin = match raw.origin {
+                    0 => TransactionOrigin::Local,
+                    1 => TransactionOrigin::External,
+                    2 => TransactionOrigin::Private,
+                    _ => return Err(alloy_rlp::Error::Custom("unknown transaction origin")),
+                };
+                if raw.transactions.len() != raw.senders.len() {
+                    return Err(alloy_rlp::Error::Custom("transaction and sender count mismatch"))
+                }
+                let transactions = raw
+                    .transactions
+                    .into_iter()
+                    .zip(raw.senders)
+                    .map(|(tx, sender)| {
+                        TransactionSignedEcRecovered::from_signed_transaction(tx, sender)
+                    })
+                    .collect();
+                IngressEvent::Transactions { origin, transactions }
+            }
+            _ => return Err(alloy_rlp::Error::Custom("unknown ingress event")),
+        };
+        Ok(Self { timestamp: Duration::from_micros(raw.timestamp), event })
+    }
+}
+
+/// Records the pool ingress to a file.
+///
+/// The recorder is cheap to clone, all clones append to the same file. Records are buffered and
+/// written once the buffer is full, on [`IngressRecorder::flush`], or when the last clone is
+/// dropped.
+#[derive(Debug, Clone)]
+pub struct IngressRecorder {
+    /// When the recording started.
+    start: Instant,
+    /// The recording file.
+    file: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl IngressRecorder {
+    /// Creates a new recorder that writes to the given file, replacing any existing recording.
+    pub fn new(path: &Path) -> Result<Self, IngressError> {
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        let file = reth_fs_util::create_file(path)?;
+        info!(target: "txpool", ?path, "Recording transaction pool ingress");
+        Ok(Self { start: Instant::now(), file: Arc::new(Mutex::new(BufWriter::new(file))) })
+    }
+
+    /// Records the hashes of an announcement.
+    pub fn record_announcement(&self, hashes: Vec<TxHash>) {
+        self.record(IngressEvent::Announcement(hashes))
+    }
+
+    /// Records submitted transactions.
+    pub fn record_transactions<'a, T: PoolTransaction + 'a>(
+        &self,
+        origin: TransactionOrigin,
+        transactions: impl IntoIterator<Item = &'a T>,
+    ) {
+        let transactions = transactions.into_iter().map(|tx| tx.clone().into()).collect();
+        self.record(IngressEvent::Transactions { origin, transactions })
+    }
+
+    /// Records the event.
+    pub fn record(&self, event: IngressEvent) {
+        let record = IngressRecord { timestamp: self.start.elapsed(), event };
+        let mut buf = Vec::new();
+        RawIngressRecord::from(&record).encode(&mut buf);
+
+        // The record is encoded before taking the lock, which only guards the copy into the
+        // buffer and the occasional write of a full buffer.
+        if let Err(err) = self.file.lock().write_all(&buf) {
+            warn!(target: "txpool", %err, "Failed to record transaction pool ingress");
+        }
+    }
+
+    /// Writes all buffered records to the file.
+    pub fn flush(&self) -> io::Result<()> {
+        self.file.lock().flush()
+    }
+}
+
+/// Opens an ingress recording for reading.
+///
+/// The records are read lazily, one at a time.
+pub fn read_ingress_records(path: &Path) -> Result<IngressRecords, IngressError> {
+    let file = File::open(path).map_err(|err| FsPathError::open(err, path))?;
+    Ok(IngressRecords { path: path.to_path_buf(), reader: BufReader::new(file), done: false })
+}
+
+/// An iterator over the records of an ingress recording, see [`read_ingress_records`].
+///
+/// An incomplete last record, which is left behind if the recording node crashed, ends the
+/// iteration. Any other error is returned once, after which the iteration ends.
+#[derive(Debug)]
+pub struct IngressRecords {
+    /// The path of the recording file.
+    path: PathBuf,
+    /// The reader of the recording file.
+    reader: BufReader<File>,
+    /// Whether the end of the recording or an error was reached.
+    done: bool,
+}
+
+impl IngressRecords {
+    /// Reads the next record, `None` at the end of the recording.
+    fn read_record(&mut self) -> Result<Option<IngressRecord>, IngressError> {
+        let Some(&first) = self.reader.fill_buf()?.first() else { return Ok(None) };
+        if first < EMPTY_LIST_CODE {
+            return Err(alloy_rlp::Error::UnexpectedString.into())
+        }
+
+        // Each record is an RLP list, whose header has the length of its length in the first byte
+        // if the list is longer than 55 bytes.
+        let header_len = 1 + first.saturating_sub(EMPTY_LIST_CODE + 55) as usize;
+        let mut buf = vec![0; header_len];
+        if !self.read_exact(&mut buf)? {
+            return Ok(None)
+        }
+        let header = Header::decode(&mut buf.as_slice())?;
+
+        buf.resize(header_len + header.payload_length, 0);
+        if !self.read_exact(&mut buf[header_len..])? {
+            return Ok(None)
+        }
+        Ok(Some(RawIngressRecord::decode(&mut buf.as_slice())?.try_into()?))
+    }
+
+    /// Fills the buffer, returns `false` if the recording ends before the buffer is full.
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<bool> {
+        match self.reader.read_exact(buf) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                warn!(target: "txpool", path = ?self.path, "Ignoring incomplete last ingress record");
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl Iterator for IngressRecords {
+    type Item = Result<IngressRecord, IngressError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+        let record = self.read_record().transpose();
+        self.done = !matches!(record, Some(Ok(_)));
+        record
+    }
+}
+
+/// Outcome of [`replay_ingress`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayOutcome {
+    /// Number of replayed announcements.
+    pub announcements: usize,
+    /// Number of replayed transactions.
+    pub transactions: usize,
+    /// Number of replayed transactions that were added to the pool.
+    pub accepted: usize,
+    /// Number of replayed transactions that were rejected.
+    pub rejected: usize,
+}
+
+/// The hashes of a recorded announcement.
+#[derive(Debug)]
+struct RecordedAnnouncement(Vec<TxHash>);
+
+impl HandleMempoolData for RecordedAnnouncement {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn retain_by_hash(&mut self, f: impl FnMut(&TxHash) -> bool) {
+        self.0.retain(f)
+    }
+}
+
+/// Feeds the recorded ingress into the pool at the original pacing.
+///
+/// Submitted transactions are validated concurrently, so a slow validation doesn't delay the
+/// records that follow. The replay stops at the first record that can't be read.
+pub async fn replay_ingress<P>(
+    pool: P,
+    records: impl IntoIterator<Item = Result<IngressRecord, IngressError>>,
+) -> ReplayOutcome
+where
+    P: TransactionPool + Clone + 'static,
+{
+    info!(target: "txpool", "Replaying transaction pool ingress");
+
+    let mut outcome = ReplayOutcome::default();
+    let mut imports = FuturesUnordered::new();
+    let start = tokio::time::Instant::now();
+
+    for record in records {
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                warn!(target: "txpool", %err, "Failed to read ingress record, stopping replay");
+                break
+            }
+        };
+        let deadline = start + record.timestamp;
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep_until(deadline) => break,
+                Some(results) = imports.next() => tally(&mut outcome, results),
+            }
+        }
+
+        match record.event {
+            IngressEvent::Announcement(hashes) => {
+                outcome.announcements += 1;
+                pool.retain_unknown(&mut RecordedAnnouncement(hashes));
+            }
+            IngressEvent::Transactions { origin, transactions } => {
+                outcome.transactions += transactions.len();
+                let transactions = transactions
+                    .into_iter()
+                    .filter_map(|tx| match P::Transaction::try_from(tx) {
+                        Ok(tx) => Some(tx),
+                        Err(_) => {
+                            outcome.rejected += 1;
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let pool = pool.clone();
+                imports.push(async move { pool.add_transactions(origin, transactions).await });
+            }
+        }
+    }
+
+    while let Some(results) = imports.next().await {
+        tally(&mut outcome, results);
+    }
+
+    debug!(target: "txpool", ?outcome, "Replayed transaction pool ingress");
+    outcome
+}
+
+/// Counts the results of replayed transactions.
+fn tally<T, E>(outcome: &mut ReplayOutcome, results: Vec<Result<T, E>>) {
+    for result in results {
+        if result.is_ok() {
+            outcome.accepted += 1;
+        } else {
+            outcome.rejected += 1;
+        }
+    }
+}
+
+/// Errors of reading an ingress recording.
+#[derive(Debug, thiserror::Error)]
+pub enum IngressError {
+    /// Error decoding a record.
+    #[error("failed to decode ingress record: {0}")]
+    Decode(#[from] alloy_rlp::Error),
+    /// Error accessing the recording file.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+    /// Error reading the recording file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{testing_pool, MockTransaction};
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ingress.rlp");
+
+        let transactions = vec![MockTransaction::eip1559(), MockTransaction::eip1559()];
+        let recorder = IngressRecorder::new(&path).unwrap();
+        recorder.record_announcement(vec![TxHash::with_last_byte(1)]);
+        recorder.record_transactions(TransactionOrigin::External, &transactions);
+        recorder.flush().unwrap();
+
+        let records = read_ingress_records(&path).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].event, IngressEvent::Announcement(vec![TxHash::with_last_byte(1)]));
+        assert!(records[0].timestamp <= records[1].timestamp);
+        let IngressEvent::Transactions { origin, transactions: recorded } = &records[1].event
+        else {
+            panic!("expected transactions")
+        };
+        assert_eq!(*origin, TransactionOrigin::External);
+        assert_eq!(
+            recorded.iter().map(|tx| tx.signer()).collect::<Vec<_>>(),
+            transactions.iter().map(|tx| tx.sender()).collect::<Vec<_>>()
+        );
+
+        let pool = testing_pool();
+        let outcome = replay_ingress(pool.clone(), records.into_iter().map(Ok)).await;
+        assert_eq!(
+            outcome,
+            ReplayOutcome { announcements: 1, transactions: 2, accepted: 2, rejected: 0 }
+        );
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn ignore_incomplete_last_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ingress.rlp");
+
+        let recorder = IngressRecorder::new(&path).unwrap();
+        recorder.record_announcement(vec![TxHash::with_last_byte(1)]);
+        recorder.record_announcement(vec![TxHash::with_last_byte(2)]);
+        recorder.flush().unwrap();
+
+        let data = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &data[..data.len() - 1]).unwrap();
+
+        let records = read_ingress_records(&path).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].event, IngressEvent::Announcement(vec![TxHash::with_last_byte(1)]));
+    }
+}
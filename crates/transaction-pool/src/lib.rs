@@ -150,7 +150,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
-use crate::{identifier::TransactionId, pool::PoolInner};
+use crate::{identifier::TransactionId, ingress::IngressRecorder, pool::PoolInner};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, U256};
@@ -162,8 +162,8 @@ use tracing::{instrument, trace};
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
+        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_FEE_HYSTERESIS,
+        DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
         TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
         TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
@@ -181,6 +181,7 @@ pub use crate::{
 };
 
 pub mod error;
+pub mod ingress;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
@@ -209,6 +210,8 @@ pub type EthTransactionPool<Client, S> = Pool<
 pub struct Pool<V, T: TransactionOrdering, S> {
     /// Arc'ed instance of the pool internals
     pool: Arc<PoolInner<V, T, S>>,
+    /// Records the pool ingress, if enabled.
+    recorder: Option<IngressRecorder>,
 }
 
 // === impl Pool ===
@@ -221,7 +224,16 @@ where
 {
     /// Create a new transaction pool instance.
     pub fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
-        Self { pool: Arc::new(PoolInner::new(validator, ordering, blob_store, config)) }
+        Self {
+            pool: Arc::new(PoolInner::new(validator, ordering, blob_store, config)),
+            recorder: None,
+        }
+    }
+
+    /// Records all transaction announcements and submissions with the given [`IngressRecorder`].
+    pub fn with_ingress_recorder(mut self, recorder: IngressRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
     }
 
     /// Returns the wrapped pool.
@@ -339,6 +351,9 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TransactionEvents> {
+        if let Some(recorder) = &self.recorder {
+            recorder.record_transactions(origin, [&transaction]);
+        }
         let (_, tx) = self.validate(origin, transaction).await;
         self.pool.add_transaction_and_subscribe(origin, tx)
     }
@@ -348,6 +363,9 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash> {
+        if let Some(recorder) = &self.recorder {
+            recorder.record_transactions(origin, [&transaction]);
+        }
         let (_, tx) = self.validate(origin, transaction).await;
         let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
         results.pop().expect("result length is the same as the input")
@@ -361,6 +379,9 @@ where
         if transactions.is_empty() {
             return Vec::new()
         }
+        if let Some(recorder) = &self.recorder {
+            recorder.record_transactions(origin, &transactions);
+        }
         let validated = self.validate_all(origin, transactions).await;
 
         self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
@@ -463,6 +484,14 @@ where
     where
         A: HandleMempoolData,
     {
+        if let Some(recorder) = self.recorder.as_ref().filter(|_| !announcement.is_empty()) {
+            let mut hashes = Vec::with_capacity(announcement.len());
+            announcement.retain_by_hash(|hash| {
+                hashes.push(*hash);
+                true
+            });
+            recorder.record_announcement(hashes);
+        }
         self.pool.retain_unknown(announcement)
     }
 
@@ -560,6 +589,6 @@ where
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
     fn clone(&self) -> Self {
-        Self { pool: Arc::clone(&self.pool) }
+        Self { pool: Arc::clone(&self.pool), recorder: self.recorder.clone() }
     }
 }
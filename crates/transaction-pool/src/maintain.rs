@@ -5,8 +5,9 @@ use crate::{
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction,
+    BlockInfo, PoolTransaction, TransactionOrigin,
 };
+use alloy_rlp::{RlpDecodable, RlpEncodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -17,7 +18,7 @@ use reth_execution_types::ExecutionOutcome;
 use reth_fs_util::FsPathError;
 use reth_primitives::{
     Address, BlockHash, BlockNumber, BlockNumberOrTag, IntoRecoveredTransaction,
-    PooledTransactionsElementEcRecovered, TransactionSigned,
+    PooledTransactionsElement, PooledTransactionsElementEcRecovered, TransactionSigned,
 };
 use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -26,6 +27,7 @@ use std::{
     collections::HashSet,
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
+    time::Instant,
 };
 use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, warn};
@@ -55,12 +57,24 @@ impl Default for MaintainPoolConfig {
 pub struct LocalTransactionBackupConfig {
     /// Path to transactions backup file
     pub transactions_path: Option<PathBuf>,
+    /// Path to the snapshot of the entire pool.
+    ///
+    /// If set, all transactions of the pool are persisted on shutdown instead of only the local
+    /// transactions.
+    pub pool_snapshot_path: Option<PathBuf>,
 }
 
 impl LocalTransactionBackupConfig {
     /// Receive path to transactions backup and return initialized config
     pub const fn with_local_txs_backup(transactions_path: PathBuf) -> Self {
-        Self { transactions_path: Some(transactions_path) }
+        Self { transactions_path: Some(transactions_path), pool_snapshot_path: None }
+    }
+
+    /// Persists all transactions of the pool to the given path on shutdown, instead of only the
+    /// local transactions.
+    pub fn with_pool_snapshot(mut self, pool_snapshot_path: PathBuf) -> Self {
+        self.pool_snapshot_path = Some(pool_snapshot_path);
+        self
     }
 }
 
@@ -111,7 +125,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
             pending_basefee: latest
                 .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(latest.timestamp + 12))
                 .unwrap_or_default(),
-            pending_blob_fee: latest.next_block_blob_fee(),
+            pending_blob_fee: latest.next_block_blob_fee_with_params(
+                chain_spec.blob_params_at_timestamp(latest.timestamp + 12),
+            ),
         };
         pool.set_block_info(info);
     }
@@ -268,7 +284,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                         chain_spec.base_fee_params_at_timestamp(new_tip.timestamp + 12),
                     )
                     .unwrap_or_default();
-                let pending_block_blob_fee = new_tip.next_block_blob_fee();
+                let pending_block_blob_fee = new_tip.next_block_blob_fee_with_params(
+                    chain_spec.blob_params_at_timestamp(new_tip.timestamp + 12),
+                );
 
                 // we know all changed account in the new chain
                 let new_changed_accounts: HashSet<_> =
@@ -348,7 +366,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     // all transactions mined in the new chain need to be removed from the pool
                     mined_transactions: new_blocks.transaction_hashes().collect(),
                 };
+                let start = Instant::now();
                 pool.on_canonical_state_change(update);
+                metrics.record_canonical_state_update(start.elapsed());
 
                 // all transactions that were mined in the old chain but not in the new chain need
                 // to be re-injected
@@ -373,7 +393,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                         chain_spec.base_fee_params_at_timestamp(tip.timestamp + 12),
                     )
                     .unwrap_or_default();
-                let pending_block_blob_fee = tip.next_block_blob_fee();
+                let pending_block_blob_fee = tip.next_block_blob_fee_with_params(
+                    chain_spec.blob_params_at_timestamp(tip.timestamp + 12),
+                );
 
                 let first_block = blocks.first();
                 trace!(
@@ -429,7 +451,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     changed_accounts,
                     mined_transactions,
                 };
+                let start = Instant::now();
                 pool.on_canonical_state_change(update);
+                metrics.record_canonical_state_update(start.elapsed());
 
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
@@ -644,8 +668,122 @@ pub enum TransactionsBackupError {
     Pool(#[from] PoolError),
 }
 
+/// A transaction of the pool snapshot file.
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct PoolSnapshotEntry {
+    /// `0` for local, `1` for external and `2` for private transactions.
+    origin: u8,
+    /// The transaction, including the sidecar of blob transactions.
+    transaction: PooledTransactionsElement,
+}
+
+const fn encode_origin(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+const fn decode_origin(origin: u8) -> Option<TransactionOrigin> {
+    match origin {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// Loads the snapshot of the entire pool and reinserts the transactions with their original
+/// origin.
+///
+/// The transactions are validated against the current head, so transactions that were mined or
+/// became invalid while the node was offline are discarded. The file is removed afterwards.
+async fn load_pool_snapshot<P>(pool: P, file_path: &Path) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", snapshot_file =?file_path, "Loading transaction pool snapshot");
+    let data = reth_fs_util::read(file_path)?;
+
+    if data.is_empty() {
+        return Ok(())
+    }
+
+    let entries: Vec<PoolSnapshotEntry> = alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+    let num_txs = entries.len();
+
+    let mut transactions = [
+        (TransactionOrigin::Local, Vec::new()),
+        (TransactionOrigin::External, Vec::new()),
+        (TransactionOrigin::Private, Vec::new()),
+    ];
+    for entry in entries {
+        let Some(origin) = decode_origin(entry.origin) else { continue };
+        let Ok(tx) = entry.transaction.try_into_ecrecovered() else { continue };
+        if let Some((_, txs)) = transactions.iter_mut().find(|(o, _)| *o == origin) {
+            txs.push(<P::Transaction as PoolTransaction>::from_pooled(tx));
+        }
+    }
+
+    let mut num_reinserted = 0;
+    for (origin, transactions) in transactions {
+        if transactions.is_empty() {
+            continue
+        }
+        let outcome = pool.add_transactions(origin, transactions).await;
+        num_reinserted += outcome.iter().filter(|res| res.is_ok()).count();
+    }
+
+    info!(target: "txpool", snapshot_file =?file_path, num_txs, num_reinserted, "Restored transaction pool snapshot");
+    reth_fs_util::remove_file(file_path)?;
+    Ok(())
+}
+
+/// Writes all transactions of the pool to the snapshot file.
+fn save_pool_snapshot<P>(pool: P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let transactions = pool.pooled_transactions();
+    if transactions.is_empty() {
+        trace!(target: "txpool", "no transactions to save");
+        return
+    }
+
+    let entries = transactions
+        .iter()
+        .filter_map(|tx| {
+            // this also fetches the sidecars of blob transactions from the blob store
+            let transaction = pool.get_pooled_transaction_element(*tx.hash())?;
+            Some(PoolSnapshotEntry { origin: encode_origin(tx.origin), transaction })
+        })
+        .collect::<Vec<_>>();
+
+    let num_txs = entries.len();
+    let mut buf = Vec::new();
+    alloy_rlp::encode_list(&entries, &mut buf);
+    info!(target: "txpool", snapshot_file =?file_path, num_txs, "Saving transaction pool snapshot");
+    let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
+
+    match parent_dir.map(|_| reth_fs_util::write(file_path, buf)) {
+        Ok(_) => {
+            info!(target: "txpool", snapshot_file=?file_path, "Wrote transaction pool snapshot to file");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, snapshot_file=?file_path, "Failed to write transaction pool snapshot to file");
+        }
+    }
+}
+
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
 /// Reloads the transactions from the file on the boot up and inserts them into the pool.
+///
+/// If a pool snapshot path is configured, all transactions of the pool are saved instead.
 pub async fn backup_local_transactions_task<P>(
     shutdown: reth_tasks::shutdown::GracefulShutdown,
     pool: P,
@@ -653,19 +791,33 @@ pub async fn backup_local_transactions_task<P>(
 ) where
     P: TransactionPool + Clone,
 {
-    let Some(transactions_path) = config.transactions_path else {
+    let LocalTransactionBackupConfig { transactions_path, pool_snapshot_path } = config;
+    if transactions_path.is_none() && pool_snapshot_path.is_none() {
         // nothing to do
         return
-    };
+    }
 
-    if let Err(err) = load_and_reinsert_transactions(pool.clone(), &transactions_path).await {
-        error!(target: "txpool", "{}", err)
+    // a local transactions backup can exist even if the snapshot is enabled, e.g. from a previous
+    // run without the snapshot
+    if let Some(transactions_path) = &transactions_path {
+        if let Err(err) = load_and_reinsert_transactions(pool.clone(), transactions_path).await {
+            error!(target: "txpool", "{}", err)
+        }
+    }
+    if let Some(pool_snapshot_path) = &pool_snapshot_path {
+        if let Err(err) = load_pool_snapshot(pool.clone(), pool_snapshot_path).await {
+            error!(target: "txpool", "{}", err)
+        }
     }
 
     let graceful_guard = shutdown.await;
 
     // write transactions to disk
-    save_local_txs_backup(pool, &transactions_path);
+    if let Some(pool_snapshot_path) = &pool_snapshot_path {
+        save_pool_snapshot(pool, pool_snapshot_path);
+    } else if let Some(transactions_path) = &transactions_path {
+        save_local_txs_backup(pool, transactions_path);
+    }
 
     drop(graceful_guard)
 }
@@ -742,6 +894,42 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_save_and_load_pool_snapshot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snapshot_path = temp_dir.path().join("test_pool_snapshot").with_extension(EXTENSION);
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_enveloped(&mut &tx_bytes[..]).unwrap();
+        let provider = MockEthProvider::default();
+        let transaction: EthPooledTransaction = tx.try_into_ecrecovered().unwrap().into();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider, blob_store.clone());
+
+        let txpool = Pool::new(
+            validator.clone(),
+            CoinbaseTipOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+
+        save_pool_snapshot(txpool, &snapshot_path);
+        assert!(snapshot_path.exists());
+
+        let restored =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        load_pool_snapshot(restored.clone(), &snapshot_path).await.unwrap();
+
+        let txs = restored.pooled_transactions();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].hash(), transaction.hash());
+        assert_eq!(txs[0].origin, TransactionOrigin::External);
+        assert!(!snapshot_path.exists());
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
@@ -1,9 +1,10 @@
 //! Transaction pool metrics.
 
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
+use std::time::Duration;
 
 /// Transaction pool metrics
 #[derive(Metrics)]
@@ -72,6 +73,9 @@ pub struct MaintainPoolMetrics {
     pub(crate) reinserted_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
+    /// Time spent updating the pool with a new canonical state, after a commit or a reorg of the
+    /// canonical chain.
+    pub(crate) canonical_state_update_duration: Histogram,
 }
 
 impl MaintainPoolMetrics {
@@ -94,6 +98,11 @@ impl MaintainPoolMetrics {
     pub(crate) fn inc_drift(&self) {
         self.drift_count.increment(1);
     }
+
+    #[inline]
+    pub(crate) fn record_canonical_state_update(&self, duration: Duration) {
+        self.canonical_state_update_duration.record(duration);
+    }
 }
 
 /// All Transactions metrics
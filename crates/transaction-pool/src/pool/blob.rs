@@ -187,8 +187,9 @@ impl<T: PoolTransaction> BlobTransactions<T> {
     }
 
     /// Removes all transactions (and their descendants) which:
-    ///  * have a `max_fee_per_blob_gas` greater than or equal to the given `blob_fee`, _and_
-    ///  * have a `max_fee_per_gas` greater than or equal to the given `base_fee`
+    ///  * have a `max_fee_per_blob_gas` greater than or equal to the `blob_fee` of the promotion
+    ///    fees, _and_
+    ///  * have a `max_fee_per_gas` greater than or equal to the `base_fee` of the promotion fees
     ///
     /// This also sets the [`PendingFees`] for the pool, resorting transactions based on their
     /// updated priority.
@@ -197,8 +198,9 @@ impl<T: PoolTransaction> BlobTransactions<T> {
     pub(crate) fn enforce_pending_fees(
         &mut self,
         pending_fees: &PendingFees,
+        promotion_fees: &PendingFees,
     ) -> Vec<Arc<ValidPoolTransaction<T>>> {
-        let to_remove = self.satisfy_pending_fee_ids(pending_fees);
+        let to_remove = self.satisfy_pending_fee_ids(promotion_fees);
 
         let mut removed = Vec::with_capacity(to_remove.len());
         for id in to_remove {
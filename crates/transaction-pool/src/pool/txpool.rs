@@ -1,7 +1,7 @@
 //! The internal transaction pool implementation.
 
 use crate::{
-    config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
+    config::{LocalTransactionConfig, DEFAULT_FEE_HYSTERESIS, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind},
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
@@ -18,6 +18,7 @@ use crate::{
     PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
+use reth_metrics::memory::MemoryConsumerMetrics;
 use reth_primitives::{
     constants::{
         eip4844::BLOB_TX_MIN_BLOB_GASPRICE, ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE,
@@ -73,6 +74,8 @@ pub struct TxPool<T: TransactionOrdering> {
     all_transactions: AllTransactions<T::Transaction>,
     /// Transaction pool metrics
     metrics: TxPoolMetrics,
+    /// Memory usage metrics of the pending, basefee and queued subpools
+    memory_metrics: MemoryConsumerMetrics,
 }
 
 // === impl TxPool ===
@@ -89,6 +92,9 @@ impl<T: TransactionOrdering> TxPool<T> {
             all_transactions: AllTransactions::new(&config),
             config,
             metrics: Default::default(),
+            memory_metrics: MemoryConsumerMetrics::consumer(
+                MemoryConsumerMetrics::TRANSACTION_POOL,
+            ),
         }
     }
 
@@ -167,9 +173,15 @@ impl<T: TransactionOrdering> TxPool<T> {
                 }
             }
             (Ordering::Less, _) | (_, Ordering::Less) => {
-                // decreased blob/base fee: recheck blob pool and promote all that are now valid
-                let removed =
-                    self.blob_pool.enforce_pending_fees(&self.all_transactions.pending_fees);
+                // decreased blob/base fee: recheck blob pool and promote all that now cover the
+                // fees with the hysteresis
+                let promotion_fees = self
+                    .all_transactions
+                    .pending_fees
+                    .with_hysteresis(self.all_transactions.fee_hysteresis);
+                let removed = self
+                    .blob_pool
+                    .enforce_pending_fees(&self.all_transactions.pending_fees, &promotion_fees);
                 for tx in removed {
                     let to = {
                         let tx =
@@ -214,9 +226,13 @@ impl<T: TransactionOrdering> TxPool<T> {
                 Ordering::Greater
             }
             Ordering::Less => {
-                // decreased base fee: recheck basefee pool and promote all that are now valid
-                let removed =
-                    self.basefee_pool.enforce_basefee(self.all_transactions.pending_fees.base_fee);
+                // decreased base fee: recheck basefee pool and promote all that now cover the base
+                // fee with the hysteresis
+                let promotion_fees = self
+                    .all_transactions
+                    .pending_fees
+                    .with_hysteresis(self.all_transactions.fee_hysteresis);
+                let removed = self.basefee_pool.enforce_basefee(promotion_fees.base_fee);
                 for tx in removed {
                     let to = {
                         let tx =
@@ -399,8 +415,8 @@ impl<T: TransactionOrdering> TxPool<T> {
 
     /// Updates the entire pool after a new block was mined.
     ///
-    /// This removes all mined transactions, updates according to the new base fee and rechecks
-    /// sender allowance.
+    /// This removes all mined transactions, updates according to the new base fee and blob fee,
+    /// with the configured fee hysteresis, and rechecks sender allowance.
     pub(crate) fn on_canonical_state_change(
         &mut self,
         block_info: BlockInfo,
@@ -438,6 +454,9 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.metrics.blob_pool_transactions.set(stats.blob as f64);
         self.metrics.blob_pool_size_bytes.set(stats.blob_size as f64);
         self.metrics.total_transactions.set(stats.total as f64);
+        self.memory_metrics
+            .usage_bytes
+            .set((stats.pending_size + stats.basefee_size + stats.queued_size) as f64);
     }
 
     /// Adds the transaction into the pool.
@@ -926,6 +945,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     last_seen_block_hash: B256,
     /// Expected blob and base fee for the pending block.
     pending_fees: PendingFees,
+    /// Fee hysteresis (in %) for the promotion of parked transactions on updates.
+    fee_hysteresis: u64,
     /// Configured price bump settings for replacements
     price_bumps: PriceBumpConfig,
     /// How to handle [`TransactionOrigin::Local`](crate::TransactionOrigin) transactions.
@@ -939,6 +960,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     fn new(config: &PoolConfig) -> Self {
         Self {
             max_account_slots: config.max_account_slots,
+            fee_hysteresis: config.fee_hysteresis,
             price_bumps: config.price_bumps,
             local_transactions_config: config.local_transactions_config.clone(),
             ..Default::default()
@@ -1038,6 +1060,10 @@ impl<T: PoolTransaction> AllTransactions<T> {
         // pre-allocate a few updates
         let mut updates = Vec::with_capacity(64);
 
+        // the fees that parked transactions need to cover to be promoted
+        let pending_fees = self.pending_fees.clone();
+        let promotion_fees = pending_fees.with_hysteresis(self.fee_hysteresis);
+
         let mut iter = self.txs.iter_mut().peekable();
 
         // Loop over all individual senders and update all affected transactions.
@@ -1103,7 +1129,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
             tx.state.insert(TxState::NO_PARKED_ANCESTORS);
 
             // Update the first transaction of this sender.
-            Self::update_tx_base_fee(self.pending_fees.base_fee, tx);
+            Self::update_tx_fees(&pending_fees, &promotion_fees, tx);
             // Track if the transaction's sub-pool changed.
             Self::record_subpool_update(&mut updates, tx);
 
@@ -1158,7 +1184,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 has_parked_ancestor = !tx.state.is_pending();
 
                 // Update and record sub-pool changes.
-                Self::update_tx_base_fee(self.pending_fees.base_fee, tx);
+                Self::update_tx_fees(&pending_fees, &promotion_fees, tx);
                 Self::record_subpool_update(&mut updates, tx);
 
                 // Advance iterator
@@ -1186,10 +1212,22 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
-    /// Rechecks the transaction's dynamic fee condition.
-    fn update_tx_base_fee(pending_block_base_fee: u64, tx: &mut PoolInternalTransaction<T>) {
+    /// Rechecks the transaction's dynamic fee and blob fee conditions.
+    ///
+    /// A fee cap that satisfied the condition is checked against the pending fees, a fee cap that
+    /// didn't is checked against the promotion fees, which include the fee hysteresis.
+    fn update_tx_fees(
+        pending_fees: &PendingFees,
+        promotion_fees: &PendingFees,
+        tx: &mut PoolInternalTransaction<T>,
+    ) {
         // Recheck dynamic fee condition.
-        match tx.transaction.max_fee_per_gas().cmp(&(pending_block_base_fee as u128)) {
+        let base_fee = if tx.state.contains(TxState::ENOUGH_FEE_CAP_BLOCK) {
+            pending_fees.base_fee
+        } else {
+            promotion_fees.base_fee
+        };
+        match tx.transaction.max_fee_per_gas().cmp(&(base_fee as u128)) {
             Ordering::Greater | Ordering::Equal => {
                 tx.state.insert(TxState::ENOUGH_FEE_CAP_BLOCK);
             }
@@ -1197,6 +1235,20 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 tx.state.remove(TxState::ENOUGH_FEE_CAP_BLOCK);
             }
         }
+
+        // Recheck blob fee condition, non-EIP4844 transactions always satisfy it.
+        if let Some(blob_fee_cap) = tx.transaction.max_fee_per_blob_gas() {
+            let blob_fee = if tx.state.contains(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK) {
+                pending_fees.blob_fee
+            } else {
+                promotion_fees.blob_fee
+            };
+            if blob_fee_cap >= blob_fee {
+                tx.state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+            } else {
+                tx.state.remove(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+            }
+        }
     }
 
     /// Returns an iterator over all transactions for the given sender, starting with the lowest
@@ -1708,6 +1760,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             last_seen_block_number: Default::default(),
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
+            fee_hysteresis: DEFAULT_FEE_HYSTERESIS,
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
             metrics: Default::default(),
@@ -1724,6 +1777,18 @@ pub(crate) struct PendingFees {
     pub(crate) blob_fee: u128,
 }
 
+impl PendingFees {
+    /// Returns the fees increased by the given hysteresis (in %).
+    pub(crate) fn with_hysteresis(&self, hysteresis: u64) -> Self {
+        Self {
+            base_fee: self.base_fee.saturating_add(self.base_fee.saturating_mul(hysteresis) / 100),
+            blob_fee: self
+                .blob_fee
+                .saturating_add(self.blob_fee.saturating_mul(hysteresis as u128) / 100),
+        }
+    }
+}
+
 impl Default for PendingFees {
     fn default() -> Self {
         Self { base_fee: Default::default(), blob_fee: BLOB_TX_MIN_BLOB_GASPRICE }
@@ -2643,6 +2708,81 @@ mod tests {
         assert_eq!(pool.all_transactions.txs.get(&id).unwrap().subpool, SubPool::BaseFee)
     }
 
+    /// Applies a new head with the given pending fees and returns the sub-pool of the only
+    /// transaction in the pool.
+    fn on_new_head(
+        pool: &mut TxPool<MockOrdering>,
+        pending_basefee: u64,
+        pending_blob_fee: u128,
+    ) -> SubPool {
+        let block_info = BlockInfo {
+            pending_basefee,
+            pending_blob_fee: Some(pending_blob_fee),
+            ..pool.block_info()
+        };
+        pool.on_canonical_state_change(block_info, Vec::new(), Default::default());
+        pool.assert_invariants();
+        pool.all_transactions.txs.values().next().unwrap().subpool
+    }
+
+    #[test]
+    fn update_basefee_subpools_with_hysteresis() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(
+            MockOrdering::default(),
+            PoolConfig { fee_hysteresis: 10, ..Default::default() },
+        );
+
+        let tx = MockTransaction::eip1559().with_max_fee(1_000);
+        pool.add_transaction(f.validated(tx), U256::MAX, 0).unwrap();
+        assert_eq!(pool.pending_pool.len(), 1);
+
+        // demoted as soon as the fee cap doesn't cover the base fee
+        assert_eq!(on_new_head(&mut pool, 1_001, 1), SubPool::BaseFee);
+        // only promoted once the fee cap covers the base fee and the hysteresis
+        assert_eq!(on_new_head(&mut pool, 950, 1), SubPool::BaseFee);
+        assert_eq!(on_new_head(&mut pool, 900, 1), SubPool::Pending);
+        assert_eq!(on_new_head(&mut pool, 1_000, 1), SubPool::Pending);
+        assert_eq!(on_new_head(&mut pool, 1_001, 1), SubPool::BaseFee);
+    }
+
+    #[test]
+    fn update_basefee_subpools_setting_block_info_with_hysteresis() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(
+            MockOrdering::default(),
+            PoolConfig { fee_hysteresis: 10, ..Default::default() },
+        );
+
+        let tx = MockTransaction::eip1559().with_max_fee(1_000);
+        pool.add_transaction(f.validated(tx), U256::MAX, 0).unwrap();
+        assert_eq!(pool.pending_pool.len(), 1);
+
+        let mut set_basefee = |pending_basefee| {
+            pool.set_block_info(BlockInfo { pending_basefee, ..pool.block_info() });
+            pool.assert_invariants();
+            pool.all_transactions.txs.values().next().unwrap().subpool
+        };
+        assert_eq!(set_basefee(1_001), SubPool::BaseFee);
+        assert_eq!(set_basefee(950), SubPool::BaseFee);
+        assert_eq!(set_basefee(900), SubPool::Pending);
+    }
+
+    #[test]
+    fn update_blob_fee_subpools_on_canonical_state_change() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        pool.all_transactions.pending_fees.blob_fee = 10_000;
+
+        let tx = MockTransaction::eip4844().with_max_fee(1_000).with_blob_fee(100);
+        pool.add_transaction(f.validated(tx), U256::MAX, 0).unwrap();
+        assert_eq!(pool.blob_pool.len(), 1);
+
+        // the projected blob fee of the next block is applied on every head update
+        assert_eq!(on_new_head(&mut pool, 1, 100), SubPool::Pending);
+        assert_eq!(on_new_head(&mut pool, 1, 101), SubPool::Blob);
+    }
+
     #[test]
     fn get_highest_transaction_by_sender_and_nonce() {
         // Set up a mock transaction factory and a new transaction pool.
@@ -9,11 +9,10 @@ use crate::{
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig, PoolTransaction,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
-use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_chainspec::{BlobParams, ChainSpec, EthereumHardforks};
 use reth_primitives::{
-    constants::eip4844::MAX_BLOBS_PER_BLOCK, GotExpected, InvalidTransactionError, SealedBlock,
-    EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
-    LEGACY_TX_TYPE_ID,
+    GotExpected, InvalidTransactionError, SealedBlock, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID,
+    EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
 };
 use reth_storage_api::{AccountReader, BlockReaderIdExt, StateProviderFactory};
 use reth_tasks::TaskSpawner;
@@ -23,7 +22,10 @@ use revm::{
 };
 use std::{
     marker::PhantomData,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize},
+        Arc,
+    },
 };
 use tokio::sync::Mutex;
 
@@ -301,14 +303,15 @@ where
                 )
             }
 
-            if blob_count > MAX_BLOBS_PER_BLOCK {
+            let max_blob_count = self.fork_tracker.max_blob_count();
+            if blob_count > max_blob_count {
                 // too many blobs
                 return TransactionValidationOutcome::Invalid(
                     transaction,
                     InvalidPoolTransactionError::Eip4844(
                         Eip4844PoolTransactionError::TooManyEip4844Blobs {
                             have: blob_count,
-                            permitted: MAX_BLOBS_PER_BLOCK,
+                            permitted: max_blob_count,
                         },
                     ),
                 )
@@ -430,6 +433,11 @@ where
         if self.chain_spec.is_prague_active_at_timestamp(new_tip_block.timestamp) {
             self.fork_tracker.prague.store(true, std::sync::atomic::Ordering::Relaxed);
         }
+
+        let blob_params = self.chain_spec.blob_params_at_timestamp(new_tip_block.timestamp);
+        self.fork_tracker
+            .max_blob_count
+            .store(blob_params.max_blob_count as usize, std::sync::atomic::Ordering::Relaxed);
     }
 }
 
@@ -443,6 +451,8 @@ pub struct EthTransactionValidatorBuilder {
     cancun: bool,
     /// Fork indicator whether we are in the Cancun hardfork.
     prague: bool,
+    /// The maximum number of blobs per block of the current fork.
+    max_blob_count: usize,
     /// Whether using EIP-2718 type transactions is allowed
     eip2718: bool,
     /// Whether using EIP-1559 type transactions is allowed
@@ -501,6 +511,9 @@ impl EthTransactionValidatorBuilder {
 
             // prague not yet activated
             prague: false,
+
+            // the cancun blob parameters apply by default
+            max_blob_count: BlobParams::cancun().max_blob_count as usize,
         }
     }
 
@@ -603,6 +616,8 @@ impl EthTransactionValidatorBuilder {
     pub fn with_head_timestamp(mut self, timestamp: u64) -> Self {
         self.cancun = self.chain_spec.is_cancun_active_at_timestamp(timestamp);
         self.shanghai = self.chain_spec.is_shanghai_active_at_timestamp(timestamp);
+        self.max_blob_count =
+            self.chain_spec.blob_params_at_timestamp(timestamp).max_blob_count as usize;
         self
     }
 
@@ -634,6 +649,7 @@ impl EthTransactionValidatorBuilder {
             shanghai,
             cancun,
             prague,
+            max_blob_count,
             eip2718,
             eip1559,
             eip4844,
@@ -650,6 +666,7 @@ impl EthTransactionValidatorBuilder {
             shanghai: AtomicBool::new(shanghai),
             cancun: AtomicBool::new(cancun),
             prague: AtomicBool::new(prague),
+            max_blob_count: AtomicUsize::new(max_blob_count),
         };
 
         let inner = EthTransactionValidatorInner {
@@ -725,6 +742,8 @@ pub struct ForkTracker {
     pub cancun: AtomicBool,
     /// Tracks if prague is activated at the block's timestamp.
     pub prague: AtomicBool,
+    /// Tracks the maximum number of blobs per block at the block's timestamp.
+    pub max_blob_count: AtomicUsize,
 }
 
 impl ForkTracker {
@@ -742,6 +761,11 @@ impl ForkTracker {
     pub fn is_prague_activated(&self) -> bool {
         self.prague.load(std::sync::atomic::Ordering::Relaxed)
     }
+
+    /// Returns the maximum number of blobs per block.
+    pub fn max_blob_count(&self) -> usize {
+        self.max_blob_count.load(std::sync::atomic::Ordering::Relaxed)
+    }
 }
 
 /// Ensure that the code size is not greater than `max_init_code_size`.
@@ -814,8 +838,12 @@ mod tests {
     #[tokio::test]
     async fn validate_transaction() {
         let transaction = get_transaction();
-        let mut fork_tracker =
-            ForkTracker { shanghai: false.into(), cancun: false.into(), prague: false.into() };
+        let mut fork_tracker = ForkTracker {
+            shanghai: false.into(),
+            cancun: false.into(),
+            prague: false.into(),
+            max_blob_count: 0.into(),
+        };
 
         let res = ensure_intrinsic_gas(&transaction, &fork_tracker);
         assert!(res.is_ok());
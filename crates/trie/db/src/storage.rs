@@ -4,7 +4,8 @@ use reth_db_api::transaction::DbTx;
 use reth_execution_errors::StorageRootError;
 use reth_primitives::{keccak256, Address, BlockNumber, B256};
 use reth_trie::{
-    hashed_cursor::HashedPostStateCursorFactory, HashedPostState, HashedStorage, StorageRoot,
+    hashed_cursor::HashedPostStateCursorFactory, HashedPostState, HashedStorage, StorageRange,
+    StorageRoot,
 };
 
 #[cfg(feature = "metrics")]
@@ -33,6 +34,19 @@ pub trait DatabaseHashedStorage<TX>: Sized {
     fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError>;
 }
 
+/// Extends [`StorageRange`] with operations specific for working with a database transaction.
+pub trait DatabaseStorageRange<'a, TX>: Sized {
+    /// Collects up to `limit` hashed storage entries of the account from the database with the
+    /// [`HashedPostState`] applied on top, starting at the hashed slot `start`.
+    fn overlay_storage_range(
+        tx: &'a TX,
+        post_state: HashedPostState,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> Result<Self, DatabaseError>;
+}
+
 impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
     for StorageRoot<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>
 {
@@ -76,6 +90,24 @@ impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
     }
 }
 
+impl<'a, TX: DbTx> DatabaseStorageRange<'a, TX> for StorageRange {
+    fn overlay_storage_range(
+        tx: &'a TX,
+        post_state: HashedPostState,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> Result<Self, DatabaseError> {
+        let state_sorted = post_state.into_sorted();
+        Self::from_cursor_factory(
+            &HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state_sorted),
+            keccak256(address),
+            start,
+            limit,
+        )
+    }
+}
+
 impl<TX: DbTx> DatabaseHashedStorage<TX> for HashedStorage {
     fn from_reverts(tx: &TX, address: Address, from: BlockNumber) -> Result<Self, DatabaseError> {
         let mut storage = Self::new(false);
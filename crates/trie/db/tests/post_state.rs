@@ -2,14 +2,14 @@ use proptest::prelude::*;
 use proptest_arbitrary_interop::arb;
 use reth_db::{tables, test_utils::create_test_rw_db};
 use reth_db_api::{database::Database, transaction::DbTxMut};
-use reth_primitives::{Account, StorageEntry, B256, U256};
+use reth_primitives::{keccak256, Account, Address, StorageEntry, B256, U256};
 use reth_trie::{
     hashed_cursor::{
         HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory, HashedStorageCursor,
     },
-    HashedPostState, HashedStorage,
+    HashedPostState, HashedStorage, StorageRange,
 };
-use reth_trie_db::DatabaseHashedCursorFactory;
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseStorageRange};
 use std::collections::BTreeMap;
 
 fn assert_account_cursor_order(
@@ -432,6 +432,51 @@ fn post_state_storages_take_precedence() {
     assert_storage_cursor_order(&factory, expected);
 }
 
+#[test]
+fn storage_range_pagination() {
+    let address = Address::random();
+    let hashed_address = keccak256(address);
+
+    let db = create_test_rw_db();
+    db.update(|tx| {
+        for key in 1..6 {
+            let entry = StorageEntry { key: B256::with_last_byte(key), value: U256::from(key) };
+            tx.put::<tables::HashedStorages>(hashed_address, entry).unwrap();
+        }
+    })
+    .unwrap();
+
+    // remove the second slot and add a new one at the end
+    let mut hashed_storage = HashedStorage::new(false);
+    hashed_storage.storage.insert(B256::with_last_byte(2), U256::ZERO);
+    hashed_storage.storage.insert(B256::with_last_byte(6), U256::from(6));
+    let hashed_post_state = HashedPostState::from_hashed_storage(hashed_address, hashed_storage);
+
+    let tx = db.tx().unwrap();
+    let first =
+        StorageRange::overlay_storage_range(&tx, hashed_post_state.clone(), address, B256::ZERO, 3)
+            .unwrap();
+    assert_eq!(
+        first.entries,
+        [1, 3, 4].map(|key| (B256::with_last_byte(key), U256::from(key))).to_vec()
+    );
+    assert_eq!(first.next_key, Some(B256::with_last_byte(5)));
+
+    let second = StorageRange::overlay_storage_range(
+        &tx,
+        hashed_post_state,
+        address,
+        first.next_key.unwrap(),
+        3,
+    )
+    .unwrap();
+    assert_eq!(
+        second.entries,
+        [5, 6].map(|key| (B256::with_last_byte(key), U256::from(key))).to_vec()
+    );
+    assert_eq!(second.next_key, None);
+}
+
 #[test]
 fn fuzz_hashed_storage_cursor() {
     proptest!(ProptestConfig::with_cases(10),
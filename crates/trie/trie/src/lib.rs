@@ -42,6 +42,10 @@ pub mod proof;
 /// Trie witness generation.
 pub mod witness;
 
+/// Paginated iteration over hashed storage.
+mod storage_range;
+pub use storage_range::StorageRange;
+
 /// The implementation of the Merkle Patricia Trie.
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
@@ -0,0 +1,38 @@
+use crate::hashed_cursor::{HashedCursor, HashedCursorFactory};
+use reth_primitives::{B256, U256};
+use reth_storage_errors::db::DatabaseError;
+
+/// A page of hashed storage entries of a single account, ordered by hashed slot.
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct StorageRange {
+    /// The hashed slots and values of the storage entries in this page.
+    pub entries: Vec<(B256, U256)>,
+    /// The hashed slot of the first entry after this page, if any.
+    pub next_key: Option<B256>,
+}
+
+impl StorageRange {
+    /// Collects up to `limit` storage entries of the account, starting at the first hashed slot
+    /// that is greater or equal to `start`.
+    pub fn from_cursor_factory<H: HashedCursorFactory>(
+        hashed_cursor_factory: &H,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<Self, DatabaseError> {
+        let mut cursor = hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+
+        let mut range = Self::default();
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_slot, value)) = entry {
+            if range.entries.len() == limit {
+                range.next_key = Some(hashed_slot);
+                break
+            }
+            range.entries.push((hashed_slot, value));
+            entry = cursor.next()?;
+        }
+
+        Ok(range)
+    }
+}
@@ -22,6 +22,7 @@ pub(crate) fn bsc_chain_spec() -> Arc<ChainSpec> {
         )]),
         deposit_contract: None,
         base_fee_params: reth_chainspec::BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
+        blob_params: Default::default(),
         max_gas_limit: 140_000_000,
         prune_delete_limit: 0,
     }
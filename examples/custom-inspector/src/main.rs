@@ -25,7 +25,7 @@ use reth::{
     transaction_pool::TransactionPool,
 };
 use reth_node_ethereum::node::EthereumNode;
-use reth_rpc_types::state::EvmOverrides;
+use reth_rpc_types::overrides::ExtendedEvmOverrides;
 
 fn main() {
     Cli::<RethCliTxpoolExt>::parse()
@@ -59,7 +59,7 @@ fn main() {
                                 .spawn_with_call_at(
                                     call_request,
                                     BlockNumberOrTag::Latest.into(),
-                                    EvmOverrides::default(),
+                                    ExtendedEvmOverrides::default(),
                                     move |db, env| {
                                         let mut dummy_inspector = DummyInspector::default();
                                         {
@@ -0,0 +1,495 @@
+//! Deterministic generator of chains with executed state.
+//!
+//! Unlike the random blocks of [`crate::generators`], the chains produced by [`ChainGenerator`]
+//! are consistent: transactions are signed by funded accounts with the correct nonces and the
+//! generator executes them with revm to produce the gas used, receipts, changesets and state roots
+//! of the blocks.
+
+use crate::generators::{generate_keys, sign_tx_with_key_pair, ChangeSet};
+use alloy_eips::eip1559::BaseFeeParams;
+use alloy_genesis::GenesisAccount;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use reth_primitives::{
+    constants::{
+        EIP1559_INITIAL_BASE_FEE, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH, EMPTY_WITHDRAWALS,
+        ETHEREUM_BLOCK_GAS_LIMIT,
+    },
+    hex, keccak256, logs_bloom, proofs, public_key_to_address, Account, Address, Bytes, Header,
+    Receipt, SealedBlock, SealedHeader, StorageEntry, Transaction, TxEip1559, TxKind, TxType,
+    Withdrawals, B256, U256,
+};
+use reth_trie_common::root::{state_root_unhashed, storage_root_unhashed};
+use revm::{
+    db::InMemoryDB,
+    primitives::{BlockEnv, Bytecode, ResultAndState, SpecId, TxEnv},
+    DatabaseCommit, Evm,
+};
+use secp256k1::Keypair;
+use std::{collections::BTreeMap, ops::Range};
+
+/// The code of the contracts of the generated chains.
+///
+/// Stores the second word of the calldata at the slot given by the first word and emits a log
+/// with the slot as topic and the value as data.
+pub const STORE_CONTRACT_CODE: [u8; 22] = hex!("6020356000355560203560005260003560206000a100");
+
+/// Gas limit of value transfers.
+const TRANSFER_GAS_LIMIT: u64 = 21_000;
+
+/// Gas limit of calls to the store contracts, which use less gas than this.
+const STORE_GAS_LIMIT: u64 = 100_000;
+
+/// The priority fee per gas paid by all transactions.
+const PRIORITY_FEE: u64 = 1_000_000_000;
+
+/// Number of distinct storage slots that are written by transactions, so slots are overwritten.
+const STORAGE_SLOTS: u64 = 16;
+
+/// An account and its storage.
+pub type AccountWithStorage = (Account, BTreeMap<B256, U256>);
+
+/// Configuration of a [`ChainGenerator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainGeneratorConfig {
+    /// The seed of the generator, the same seed always produces the same chain.
+    pub seed: u64,
+    /// The chain id of the transactions.
+    pub chain_id: u64,
+    /// Number of funded accounts that send and receive transactions.
+    pub accounts: usize,
+    /// Number of store contracts, see [`STORE_CONTRACT_CODE`].
+    pub contracts: usize,
+    /// Range of the number of transactions per block.
+    pub txs_per_block: Range<usize>,
+}
+
+impl Default for ChainGeneratorConfig {
+    fn default() -> Self {
+        Self { seed: 0, chain_id: 1, accounts: 10, contracts: 2, txs_per_block: 0..10 }
+    }
+}
+
+/// A block generated by [`ChainGenerator`] along with the results of its execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedBlock {
+    /// The block.
+    pub block: SealedBlock,
+    /// The senders of the transactions of the block.
+    pub senders: Vec<Address>,
+    /// The receipts of the transactions of the block.
+    pub receipts: Vec<Receipt>,
+    /// The accounts and storage slots changed by the block, with their values before the block.
+    pub changeset: ChangeSet,
+}
+
+/// Generates a deterministic chain of post-merge blocks on top of a generated genesis.
+///
+/// Transactions are either value transfers between the funded accounts or calls to the store
+/// contracts, which write storage and emit logs. The fees are paid by the senders and the
+/// priority fees are credited to the beneficiary, which is one of the funded accounts.
+#[derive(Debug)]
+pub struct ChainGenerator {
+    rng: StdRng,
+    chain_id: u64,
+    txs_per_block: Range<usize>,
+    /// The funded accounts and their keys.
+    signers: Vec<(Address, Keypair)>,
+    contracts: Vec<Address>,
+    genesis: SealedHeader,
+    genesis_state: BTreeMap<Address, AccountWithStorage>,
+    head: SealedHeader,
+    state: BTreeMap<Address, AccountWithStorage>,
+}
+
+impl ChainGenerator {
+    /// Creates a new generator and its genesis block.
+    pub fn new(config: ChainGeneratorConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let signers = generate_keys(&mut rng, config.accounts)
+            .into_iter()
+            .map(|key| (public_key_to_address(key.public_key()), key))
+            .collect::<Vec<_>>();
+        let contracts = (0..config.contracts).map(|_| rng.gen()).collect::<Vec<Address>>();
+
+        let balance = U256::from(10).pow(U256::from(21));
+        let code_hash = keccak256(STORE_CONTRACT_CODE);
+        let state = signers
+            .iter()
+            .map(|(address, _)| {
+                (*address, (Account { nonce: 0, balance, bytecode_hash: None }, BTreeMap::new()))
+            })
+            .chain(contracts.iter().map(|address| {
+                let account =
+                    Account { nonce: 1, balance: U256::ZERO, bytecode_hash: Some(code_hash) };
+                (*address, (account, BTreeMap::new()))
+            }))
+            .collect::<BTreeMap<_, _>>();
+
+        let genesis = Header {
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            state_root: state_root(&state),
+            transactions_root: EMPTY_ROOT_HASH,
+            receipts_root: EMPTY_ROOT_HASH,
+            withdrawals_root: Some(EMPTY_WITHDRAWALS),
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+            base_fee_per_gas: Some(EIP1559_INITIAL_BASE_FEE),
+            ..Default::default()
+        }
+        .seal_slow();
+
+        Self {
+            rng,
+            chain_id: config.chain_id,
+            txs_per_block: config.txs_per_block,
+            signers,
+            contracts,
+            genesis: genesis.clone(),
+            genesis_state: state.clone(),
+            head: genesis,
+            state,
+        }
+    }
+
+    /// Returns the genesis header.
+    pub const fn genesis(&self) -> &SealedHeader {
+        &self.genesis
+    }
+
+    /// Returns the state of the genesis block.
+    pub const fn genesis_state(&self) -> &BTreeMap<Address, AccountWithStorage> {
+        &self.genesis_state
+    }
+
+    /// Returns the genesis state as genesis allocations, including the private keys of the funded
+    /// accounts.
+    pub fn genesis_alloc(&self) -> BTreeMap<Address, GenesisAccount> {
+        let keys = self
+            .signers
+            .iter()
+            .map(|(address, key)| (*address, B256::from(key.secret_bytes())))
+            .collect::<BTreeMap<_, _>>();
+        self.genesis_state
+            .iter()
+            .map(|(address, (account, _))| {
+                let code = account.bytecode_hash.map(|_| Bytes::from_static(&STORE_CONTRACT_CODE));
+                let alloc = GenesisAccount {
+                    nonce: Some(account.nonce),
+                    balance: account.balance,
+                    code,
+                    storage: None,
+                    private_key: keys.get(address).copied(),
+                };
+                (*address, alloc)
+            })
+            .collect()
+    }
+
+    /// Returns the header of the last generated block.
+    pub const fn head(&self) -> &SealedHeader {
+        &self.head
+    }
+
+    /// Returns the state after the last generated block.
+    pub const fn state(&self) -> &BTreeMap<Address, AccountWithStorage> {
+        &self.state
+    }
+
+    /// Generates the given number of blocks.
+    pub fn generate_blocks(&mut self, count: usize) -> Vec<GeneratedBlock> {
+        (0..count).map(|_| self.generate_block()).collect()
+    }
+
+    /// Generates and executes the next block.
+    pub fn generate_block(&mut self) -> GeneratedBlock {
+        let base_fee = self
+            .head
+            .next_block_base_fee(BaseFeeParams::ethereum())
+            .unwrap_or(EIP1559_INITIAL_BASE_FEE);
+        let beneficiary = self.signers.choose(&mut self.rng).map(|(address, _)| *address);
+        let tx_count = self.rng.gen_range(self.txs_per_block.clone());
+        let number = self.head.number + 1;
+        let timestamp = self.head.timestamp + 12;
+        let mix_hash: B256 = self.rng.gen();
+
+        let mut db = self.evm_db();
+        let block_env = BlockEnv {
+            number: U256::from(number),
+            coinbase: beneficiary.unwrap_or_default(),
+            timestamp: U256::from(timestamp),
+            gas_limit: U256::from(ETHEREUM_BLOCK_GAS_LIMIT),
+            basefee: U256::from(base_fee),
+            prevrandao: Some(mix_hash),
+            ..Default::default()
+        };
+
+        let mut prestate = BTreeMap::<Address, AccountWithStorage>::new();
+        let mut transactions = Vec::with_capacity(tx_count);
+        let mut senders = Vec::with_capacity(tx_count);
+        let mut receipts = Vec::<Receipt>::with_capacity(tx_count);
+        let mut gas_used = 0;
+
+        for _ in 0..tx_count {
+            let (sender, key) = *self.signers.choose(&mut self.rng).expect("no accounts");
+            let (to, value, input, gas_limit) = match self.contracts.choose(&mut self.rng).copied()
+            {
+                Some(contract) if self.rng.gen_bool(0.5) => {
+                    let slot = B256::from(U256::from(self.rng.gen_range(0..STORAGE_SLOTS)));
+                    // some writes clear the slot
+                    let value = if self.rng.gen_ratio(1, 8) {
+                        B256::ZERO
+                    } else {
+                        B256::from(U256::from(self.rng.gen::<u64>()))
+                    };
+                    let input = [slot.as_slice(), value.as_slice()].concat().into();
+                    (contract, U256::ZERO, input, STORE_GAS_LIMIT)
+                }
+                _ => {
+                    let to = self.signers.choose(&mut self.rng).expect("no accounts").0;
+                    let value = U256::from(self.rng.gen_range(1..1_000_000_000_000_000u64));
+                    (to, value, Bytes::new(), TRANSFER_GAS_LIMIT)
+                }
+            };
+
+            let nonce = self.state[&sender].0.nonce;
+            let tx = Transaction::Eip1559(TxEip1559 {
+                chain_id: self.chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas: (base_fee + PRIORITY_FEE) as u128,
+                max_priority_fee_per_gas: PRIORITY_FEE as u128,
+                to: TxKind::Call(to),
+                value,
+                input: input.clone(),
+                ..Default::default()
+            });
+
+            let tx_env = TxEnv {
+                caller: sender,
+                gas_limit,
+                gas_price: U256::from(base_fee + PRIORITY_FEE),
+                gas_priority_fee: Some(U256::from(PRIORITY_FEE)),
+                transact_to: TxKind::Call(to),
+                value,
+                data: input,
+                nonce: Some(nonce),
+                chain_id: Some(self.chain_id),
+                ..Default::default()
+            };
+            let ResultAndState { result, state } = Evm::builder()
+                .with_db(&mut db)
+                .with_spec_id(SpecId::SHANGHAI)
+                .modify_cfg_env(|cfg| cfg.chain_id = self.chain_id)
+                .with_block_env(block_env.clone())
+                .with_tx_env(tx_env)
+                .build()
+                .transact()
+                .expect("generated transaction is invalid");
+
+            for (address, account) in &state {
+                if !account.is_touched() {
+                    continue
+                }
+                let changed = Account::from(account.info.clone());
+                if changed !=
+                    self.state.get(address).map(|(account, _)| *account).unwrap_or_default()
+                {
+                    *self.account_mut(&mut prestate, *address) = changed;
+                }
+                for (slot, value) in account.changed_storage_slots() {
+                    self.write_storage(
+                        &mut prestate,
+                        *address,
+                        B256::from(*slot),
+                        value.present_value(),
+                    );
+                }
+            }
+            db.commit(state);
+
+            gas_used += result.gas_used();
+            #[allow(clippy::needless_update)] // side-effect of optimism fields
+            receipts.push(Receipt {
+                tx_type: TxType::Eip1559,
+                success: result.is_success(),
+                cumulative_gas_used: gas_used,
+                logs: result.into_logs(),
+                ..Default::default()
+            });
+            transactions.push(sign_tx_with_key_pair(key, tx));
+            senders.push(sender);
+        }
+
+        let receipts_root =
+            proofs::calculate_receipt_root_no_memo(&receipts.iter().collect::<Vec<_>>());
+        let header = Header {
+            parent_hash: self.head.hash(),
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            beneficiary: beneficiary.unwrap_or_default(),
+            state_root: state_root(&self.state),
+            transactions_root: proofs::calculate_transaction_root(&transactions),
+            receipts_root,
+            withdrawals_root: Some(EMPTY_WITHDRAWALS),
+            logs_bloom: logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs)),
+            number,
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+            gas_used,
+            timestamp,
+            mix_hash,
+            base_fee_per_gas: Some(base_fee),
+            ..Default::default()
+        }
+        .seal_slow();
+        self.head = header.clone();
+
+        let changeset = prestate
+            .into_iter()
+            .map(|(address, (account, storage))| {
+                let storage =
+                    storage.into_iter().map(|(key, value)| StorageEntry { key, value }).collect();
+                (address, account, storage)
+            })
+            .collect();
+
+        GeneratedBlock {
+            block: SealedBlock {
+                header,
+                body: transactions,
+                ommers: Vec::new(),
+                withdrawals: Some(Withdrawals::default()),
+                requests: None,
+            },
+            senders,
+            receipts,
+            changeset,
+        }
+    }
+
+    /// Returns a database with the current state for the EVM.
+    fn evm_db(&self) -> InMemoryDB {
+        let mut db = InMemoryDB::default();
+        for (address, (account, storage)) in &self.state {
+            let mut info = revm::primitives::AccountInfo::from(*account);
+            if account.bytecode_hash.is_some() {
+                info.code = Some(Bytecode::new_raw(Bytes::from_static(&STORE_CONTRACT_CODE)));
+            }
+            db.insert_account_info(*address, info);
+            for (slot, value) in storage {
+                db.insert_account_storage(*address, (*slot).into(), *value)
+                    .expect("account was inserted");
+            }
+        }
+        db
+    }
+
+    /// Returns the account to modify, recording its value before the block in the prestate.
+    fn account_mut(
+        &mut self,
+        prestate: &mut BTreeMap<Address, AccountWithStorage>,
+        address: Address,
+    ) -> &mut Account {
+        let (account, _) = self.state.get_mut(&address).expect("unknown account");
+        prestate.entry(address).or_insert_with(|| (*account, BTreeMap::new()));
+        account
+    }
+
+    /// Writes the storage slot, recording its value before the block in the prestate.
+    fn write_storage(
+        &mut self,
+        prestate: &mut BTreeMap<Address, AccountWithStorage>,
+        address: Address,
+        slot: B256,
+        value: U256,
+    ) {
+        self.account_mut(prestate, address);
+        let (_, storage) = self.state.get_mut(&address).expect("unknown account");
+        let previous =
+            if value.is_zero() { storage.remove(&slot) } else { storage.insert(slot, value) };
+        let (_, prestate_storage) = prestate.get_mut(&address).expect("recorded above");
+        prestate_storage.entry(slot).or_insert_with(|| previous.unwrap_or_default());
+    }
+}
+
+/// Calculates the state root of the state.
+fn state_root(state: &BTreeMap<Address, AccountWithStorage>) -> B256 {
+    state_root_unhashed(state.iter().map(|(address, (account, storage))| {
+        let storage_root = storage_root_unhashed(storage.iter().map(|(k, v)| (*k, *v)));
+        (*address, (*account, storage_root))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_deterministic_chains() {
+        let blocks = ChainGenerator::new(Default::default()).generate_blocks(10);
+        assert_eq!(blocks, ChainGenerator::new(Default::default()).generate_blocks(10));
+
+        let config = ChainGeneratorConfig { seed: 1, ..Default::default() };
+        assert_ne!(blocks, ChainGenerator::new(config).generate_blocks(10));
+    }
+
+    #[test]
+    fn executes_transactions() {
+        let config = ChainGeneratorConfig { txs_per_block: 5..10, ..Default::default() };
+        let mut store_calls = 0;
+        for block in ChainGenerator::new(config).generate_blocks(10) {
+            let mut cumulative_gas_used = 0;
+            for (tx, receipt) in block.block.body.iter().zip(&block.receipts) {
+                let gas_used = receipt.cumulative_gas_used - cumulative_gas_used;
+                cumulative_gas_used = receipt.cumulative_gas_used;
+                assert!(receipt.success);
+
+                if tx.input().is_empty() {
+                    assert_eq!(gas_used, TRANSFER_GAS_LIMIT);
+                    assert!(receipt.logs.is_empty());
+                } else {
+                    store_calls += 1;
+                    assert!(gas_used > TRANSFER_GAS_LIMIT && gas_used < STORE_GAS_LIMIT);
+                    let [log] = receipt.logs.as_slice() else { panic!("expected a single log") };
+                    assert_eq!(log.topics(), [B256::from_slice(&tx.input()[..32])]);
+                    assert_eq!(log.data.data[..], tx.input()[32..]);
+                }
+            }
+        }
+        assert!(store_calls > 0);
+    }
+
+    #[test]
+    fn changesets_revert_to_genesis_state() {
+        let config = ChainGeneratorConfig { txs_per_block: 5..10, ..Default::default() };
+        let mut generator = ChainGenerator::new(config);
+        let blocks = generator.generate_blocks(20);
+
+        let mut parent = generator.genesis().clone();
+        for block in &blocks {
+            assert_eq!(block.block.parent_hash, parent.hash());
+            assert_eq!(block.block.senders().unwrap(), block.senders);
+            assert_eq!(
+                block.receipts.last().map(|receipt| receipt.cumulative_gas_used),
+                Some(block.block.gas_used)
+            );
+            parent = block.block.header.clone();
+        }
+        assert!(blocks.iter().any(|block| block.changeset.iter().any(|(_, _, s)| !s.is_empty())));
+
+        let mut state = generator.state().clone();
+        for block in blocks.iter().rev() {
+            assert_eq!(state_root(&state), block.block.state_root);
+            for (address, account, storage) in &block.changeset {
+                let (current, current_storage) = state.get_mut(address).unwrap();
+                *current = *account;
+                for entry in storage {
+                    if entry.value.is_zero() {
+                        current_storage.remove(&entry.key);
+                    } else {
+                        current_storage.insert(entry.key, entry.value);
+                    }
+                }
+            }
+        }
+        assert_eq!(&state, generator.genesis_state());
+        assert_eq!(state_root(&state), generator.genesis().state_root);
+    }
+}
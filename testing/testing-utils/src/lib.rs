@@ -13,3 +13,6 @@ pub mod genesis_allocator;
 pub use genesis_allocator::GenesisAllocator;
 
 pub mod generators;
+
+pub mod chain_generator;
+pub use chain_generator::{ChainGenerator, ChainGeneratorConfig, GeneratedBlock};